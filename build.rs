@@ -0,0 +1,149 @@
+//! Embeds build provenance for `zeroclaw version --provenance` and the
+//! feature manifest that `zeroclaw preset` checks selected packs against.
+//!
+//! Every value can be pinned from the environment so release pipelines and
+//! source builds without a `.git` directory still report something accurate:
+//! `ZEROCLAW_GIT_COMMIT`, `ZEROCLAW_BUILDER`.
+//!
+//! The SBOM is resolved here from `Cargo.toml` and `Cargo.lock` and written
+//! to `$OUT_DIR/sbom.txt` as a compact `name@version` list.
+
+#[path = "src/provenance/sbom.rs"]
+mod sbom;
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/provenance/sbom.rs");
+    // Watching a path that does not exist makes Cargo rerun this script on
+    // every build, so only watch git files that are there (crates.io and
+    // tarball builds have none, and packed refs have no per-ref file).
+    rerun_if_exists(".git/HEAD");
+    if let Some(head_ref) = git(&["symbolic-ref", "-q", "HEAD"]) {
+        if !rerun_if_exists(&format!(".git/{head_ref}")) {
+            rerun_if_exists(".git/packed-refs");
+        }
+    }
+    for var in [
+        "ZEROCLAW_GIT_COMMIT",
+        "ZEROCLAW_BUILDER",
+        "GITHUB_ACTIONS",
+        "GITHUB_REPOSITORY",
+        "GITHUB_RUN_ID",
+        "USER",
+        "USERNAME",
+    ] {
+        println!("cargo:rerun-if-env-changed={var}");
+    }
+
+    let commit = env("ZEROCLAW_GIT_COMMIT")
+        .or_else(|| git(&["rev-parse", "HEAD"]))
+        .unwrap_or_else(|| "unknown".into());
+    println!("cargo:rustc-env=ZEROCLAW_BUILD_GIT_COMMIT={commit}");
+
+    println!("cargo:rustc-env=ZEROCLAW_BUILD_BUILDER={}", builder());
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".into());
+    println!("cargo:rustc-env=ZEROCLAW_BUILD_RUSTC={rustc_version}");
+
+    for (var, name) in [("TARGET", "TARGET"), ("PROFILE", "PROFILE")] {
+        let value = std::env::var(var).unwrap_or_else(|_| "unknown".into());
+        println!("cargo:rustc-env=ZEROCLAW_BUILD_{name}={value}");
+    }
+
+    let features = features();
+    println!("cargo:rustc-env=ZEROCLAW_BUILD_FEATURES={features}");
+
+    let features: Vec<&str> = features
+        .split(',')
+        .filter(|feature| !feature.is_empty())
+        .collect();
+    let out_dir = std::env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    std::fs::write(Path::new(&out_dir).join("sbom.txt"), components(&features))
+        .expect("failed to write sbom.txt");
+}
+
+/// The compact SBOM, or an empty list (with a warning) when the manifest or
+/// lock file cannot be read.
+fn components(features: &[&str]) -> String {
+    rerun_if_exists("Cargo.toml");
+    rerun_if_exists("Cargo.lock");
+    let manifest = std::fs::read_to_string("Cargo.toml");
+    let lock = std::fs::read_to_string("Cargo.lock");
+    let (Ok(manifest), Ok(lock)) = (manifest, lock) else {
+        println!("cargo:warning=Cargo.lock not found; the SBOM will be empty");
+        return String::new();
+    };
+    let root = std::env::var("CARGO_PKG_NAME").unwrap_or_else(|_| "zeroclaw".into());
+    match sbom::sbom(&manifest, &lock, &root, features) {
+        Ok(components) => sbom::render_list(&components),
+        Err(e) => {
+            println!("cargo:warning=SBOM unavailable: {e}");
+            String::new()
+        }
+    }
+}
+
+fn rerun_if_exists(path: &str) -> bool {
+    let exists = Path::new(path).exists();
+    if exists {
+        println!("cargo:rerun-if-changed={path}");
+    }
+    exists
+}
+
+/// Enabled cargo features, comma-separated. Cargo exposes them as
+/// `CARGO_FEATURE_<NAME>` with `-` mapped to `_`; every feature name in
+/// `Cargo.toml` uses `-`, so the mapping is reversible.
+fn features() -> String {
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(var, _)| {
+            var.strip_prefix("CARGO_FEATURE_")
+                .map(|name| name.to_ascii_lowercase().replace('_', "-"))
+        })
+        .filter(|name| name != "default")
+        .collect();
+    features.sort();
+    features.join(",")
+}
+
+/// Who built the binary: an explicit `ZEROCLAW_BUILDER`, the CI run, or the
+/// local user (never the host name).
+fn builder() -> String {
+    if let Some(builder) = env("ZEROCLAW_BUILDER") {
+        return builder;
+    }
+    if env("GITHUB_ACTIONS").as_deref() == Some("true") {
+        let repository = env("GITHUB_REPOSITORY").unwrap_or_else(|| "unknown".into());
+        let run = env("GITHUB_RUN_ID").unwrap_or_else(|| "unknown".into());
+        return format!("github-actions:{repository}/runs/{run}");
+    }
+    env("USER")
+        .or_else(|| env("USERNAME"))
+        .map_or_else(|| "unknown".into(), |user| format!("local:{user}"))
+}
+
+fn env(var: &str) -> Option<String> {
+    std::env::var(var)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
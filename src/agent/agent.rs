@@ -282,20 +282,41 @@ impl Agent {
             .unwrap_or("anthropic/claude-sonnet-4-20250514")
             .to_string();
 
-        let provider: Box<dyn Provider> = providers::create_routed_provider(
+        let provider_runtime_options = providers::ProviderRuntimeOptions {
+            custom_providers: config.custom_providers.clone(),
+            provider_plugins: config.provider_plugins.clone(),
+            ..providers::ProviderRuntimeOptions::default()
+        };
+
+        let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
             provider_name,
             config.api_key.as_deref(),
             config.api_url.as_deref(),
             &config.reliability,
             &config.model_routes,
             &model_name,
+            &provider_runtime_options,
         )?;
+        let provider = providers::shadow::wrap_with_shadow(
+            provider,
+            provider_name,
+            config,
+            &provider_runtime_options,
+        );
 
         let dispatcher_choice = config.agent.tool_dispatcher.as_str();
         let tool_dispatcher: Box<dyn ToolDispatcher> = match dispatcher_choice {
             "native" => Box::new(NativeToolDispatcher),
             "xml" => Box::new(XmlToolDispatcher),
-            _ if provider.supports_native_tools() => Box::new(NativeToolDispatcher),
+            _ if providers::capabilities::negotiate(
+                provider.as_ref(),
+                provider_name,
+                &model_name,
+            )
+            .native_tools =>
+            {
+                Box::new(NativeToolDispatcher)
+            }
             _ => Box::new(XmlToolDispatcher),
         };
 
@@ -466,6 +487,11 @@ impl Agent {
             .push(ConversationMessage::Chat(ChatMessage::user(enriched)));
 
         let effective_model = self.classify_model(user_message);
+        let tool_specs = super::tool_schemas::prepare(
+            &self.tool_specs,
+            &self.tool_dispatcher.to_provider_messages(&self.history),
+            self.provider.caches_tool_schemas(),
+        );
 
         for _ in 0..self.config.max_tool_iterations {
             let messages = self.tool_dispatcher.to_provider_messages(&self.history);
@@ -475,7 +501,7 @@ impl Agent {
                     ChatRequest {
                         messages: &messages,
                         tools: if self.tool_dispatcher.should_send_tool_specs() {
-                            Some(&self.tool_specs)
+                            Some(&tool_specs)
                         } else {
                             None
                         },
@@ -0,0 +1,269 @@
+//! Optional shadow-git checkpoints of the workspace around agent tasks.
+//!
+//! With `[agent] git_checkpoints = true`, the workspace is tracked by a
+//! separate git repository at `<workspace>/state/checkpoints.git` (the
+//! workspace's own `.git`, if any, is never touched). Before the first tool
+//! call of an agent task and after the task ends, the work tree is committed
+//! when it changed, with the task's trace `turn_id` in the commit message, so
+//! everything the agent does can be inspected with ordinary `git log` /
+//! `git diff`. Tasks that run no tools never touch git.
+
+use crate::config::Config;
+use crate::observability::runtime_trace;
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+use tokio::process::Command;
+
+const CHECKPOINT_GIT_DIR: &str = "state/checkpoints.git";
+
+/// Runtime-owned paths that change on every turn and would drown real edits.
+const EXCLUDED_PATHS: &[&str] = &["/state/", "/memory/", "/cron/", "/sessions/"];
+
+static CHECKPOINT_WORKSPACE: LazyLock<RwLock<Option<PathBuf>>> =
+    LazyLock::new(|| RwLock::new(None));
+
+/// When a checkpoint is taken relative to the task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointPhase {
+    Before,
+    After,
+}
+
+impl CheckpointPhase {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Before => "before",
+            Self::After => "after",
+        }
+    }
+}
+
+/// Checkpoints for one agent task, taken only once the task runs tools.
+pub struct TaskCheckpoints<'a> {
+    workspace: Option<PathBuf>,
+    turn_id: &'a str,
+    channel: &'a str,
+    started: bool,
+}
+
+impl<'a> TaskCheckpoints<'a> {
+    /// Checkpoints for the configured workspace, if enabled.
+    pub fn new(turn_id: &'a str, channel: &'a str) -> Self {
+        let workspace = CHECKPOINT_WORKSPACE
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        Self::for_workspace(workspace, turn_id, channel)
+    }
+
+    fn for_workspace(workspace: Option<PathBuf>, turn_id: &'a str, channel: &'a str) -> Self {
+        Self {
+            workspace,
+            turn_id,
+            channel,
+            started: false,
+        }
+    }
+
+    /// Take the "before" checkpoint ahead of the task's first tool call.
+    pub async fn before_tools(&mut self) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        if let Some(workspace) = &self.workspace {
+            record(
+                workspace,
+                CheckpointPhase::Before,
+                self.turn_id,
+                self.channel,
+            )
+            .await;
+        }
+    }
+
+    /// Take the "after" checkpoint if the task ran any tools.
+    pub async fn finish(self) {
+        if let (true, Some(workspace)) = (self.started, &self.workspace) {
+            record(
+                workspace,
+                CheckpointPhase::After,
+                self.turn_id,
+                self.channel,
+            )
+            .await;
+        }
+    }
+}
+
+/// Enable (or disable) checkpointing from config.
+pub fn init_from_config(config: &Config) {
+    let workspace = config
+        .agent
+        .git_checkpoints
+        .then(|| config.workspace_dir.clone());
+    let mut guard = CHECKPOINT_WORKSPACE
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+    *guard = workspace;
+}
+
+/// Path of the shadow repository for a workspace.
+pub fn checkpoint_git_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join(CHECKPOINT_GIT_DIR)
+}
+
+/// Checkpoint `workspace`. Failures are logged and never interrupt the agent
+/// task.
+async fn record(workspace: &Path, phase: CheckpointPhase, turn_id: &str, channel: &str) {
+    let message = format!(
+        "zeroclaw: checkpoint {} task {turn_id}\n\nChannel: {channel}\nTrace-Id: {turn_id}\n",
+        phase.as_str()
+    );
+    match commit_checkpoint(workspace, &message).await {
+        Ok(Some(commit)) => {
+            tracing::debug!(%commit, phase = phase.as_str(), "Workspace checkpoint created");
+            runtime_trace::record_event(
+                "workspace_checkpoint",
+                Some(channel),
+                None,
+                None,
+                Some(turn_id),
+                Some(true),
+                None,
+                serde_json::json!({ "phase": phase.as_str(), "commit": commit }),
+            );
+        }
+        Ok(None) => {}
+        Err(error) => tracing::warn!("Workspace checkpoint failed: {error:#}"),
+    }
+}
+
+/// Commit all workspace changes to the shadow repository.
+///
+/// Returns the new commit hash, or `None` when nothing changed.
+pub async fn commit_checkpoint(workspace_dir: &Path, message: &str) -> Result<Option<String>> {
+    let git_dir = checkpoint_git_dir(workspace_dir);
+    if !git_dir.join("HEAD").exists() {
+        git(workspace_dir, &["init", "--quiet"]).await?;
+        let exclude = git_dir.join("info").join("exclude");
+        tokio::fs::create_dir_all(exclude.parent().unwrap_or(&git_dir)).await?;
+        tokio::fs::write(&exclude, EXCLUDED_PATHS.join("\n") + "\n").await?;
+    }
+
+    git(workspace_dir, &["add", "--all"]).await?;
+    let staged = Command::new("git")
+        .args(shadow_args(workspace_dir))
+        .args(["diff", "--cached", "--quiet"])
+        .status()
+        .await
+        .context("failed to run git")?;
+    if staged.success() {
+        return Ok(None);
+    }
+
+    git(
+        workspace_dir,
+        &[
+            "-c",
+            "user.name=ZeroClaw",
+            "-c",
+            "user.email=zeroclaw@localhost",
+            "commit",
+            "--quiet",
+            "--no-verify",
+            "-m",
+            message,
+        ],
+    )
+    .await?;
+    let head = git(workspace_dir, &["rev-parse", "HEAD"]).await?;
+    Ok(Some(head.trim().to_string()))
+}
+
+fn shadow_args(workspace_dir: &Path) -> [String; 2] {
+    [
+        format!("--git-dir={}", checkpoint_git_dir(workspace_dir).display()),
+        format!("--work-tree={}", workspace_dir.display()),
+    ]
+}
+
+async fn git(workspace_dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(shadow_args(workspace_dir))
+        .args(args)
+        .output()
+        .await
+        .context("failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn commit_checkpoint_commits_only_when_workspace_changes() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path();
+        tokio::fs::write(workspace.join("notes.md"), "v1\n")
+            .await
+            .unwrap();
+        tokio::fs::create_dir_all(workspace.join("state"))
+            .await
+            .unwrap();
+        tokio::fs::write(workspace.join("state/costs.jsonl"), "{}\n")
+            .await
+            .unwrap();
+
+        let Ok(first) = commit_checkpoint(workspace, "first").await else {
+            // git is not installed in this environment.
+            return;
+        };
+        assert!(first.is_some());
+        assert_eq!(commit_checkpoint(workspace, "noop").await.unwrap(), None);
+
+        tokio::fs::write(workspace.join("notes.md"), "v2\n")
+            .await
+            .unwrap();
+        let second = commit_checkpoint(workspace, "second").await.unwrap();
+        assert!(second.is_some());
+        assert_ne!(first, second);
+
+        let tracked = git(workspace, &["ls-files"]).await.unwrap();
+        assert!(tracked.contains("notes.md"));
+        assert!(!tracked.contains("state/"));
+    }
+
+    #[tokio::test]
+    async fn task_without_tool_calls_takes_no_checkpoint() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path();
+        tokio::fs::write(workspace.join("notes.md"), "v1\n")
+            .await
+            .unwrap();
+
+        let chat_only = TaskCheckpoints::for_workspace(Some(workspace.into()), "t1", "cli");
+        chat_only.finish().await;
+        assert!(!checkpoint_git_dir(workspace).exists());
+
+        let mut with_tools = TaskCheckpoints::for_workspace(Some(workspace.into()), "t2", "cli");
+        with_tools.before_tools().await;
+        with_tools.before_tools().await;
+        with_tools.finish().await;
+        let Ok(log) = git(workspace, &["log", "--format=%s"]).await else {
+            // git is not installed in this environment.
+            return;
+        };
+        assert_eq!(log.trim(), "zeroclaw: checkpoint before task t2");
+    }
+}
@@ -1,12 +1,19 @@
+use super::checkpoint::TaskCheckpoints;
+use super::tool_schemas;
+use super::tool_stream;
+use super::tool_validation::{self, Checked};
 use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalResponse};
 use crate::config::Config;
+use crate::errors::{self, ErrorClass};
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::multimodal;
 use crate::observability::{self, runtime_trace, Observer, ObserverEvent};
 use crate::providers::{
     self, ChatMessage, ChatRequest, Provider, ProviderCapabilityError, ToolCall,
 };
+use crate::rag::collections::Identity;
 use crate::runtime;
+use crate::security::policy_engine::Verdict;
 use crate::security::SecurityPolicy;
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
@@ -15,6 +22,7 @@ use regex::{Regex, RegexSet};
 use std::collections::HashSet;
 use std::fmt::Write;
 use std::io::Write as _;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, LazyLock};
 use std::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
@@ -91,6 +99,19 @@ fn strip_untrusted_approval_argument(arguments: &mut serde_json::Value) {
     }
 }
 
+/// `approved` is granted by the approval flow; a tool whose schema does not
+/// declare it must never receive a value the model made up.
+fn strip_undeclared_approval_argument(tool: Option<&dyn Tool>, arguments: &mut serde_json::Value) {
+    let declared = tool.is_some_and(|tool| {
+        tool.parameters_schema()
+            .pointer("/properties/approved")
+            .is_some()
+    });
+    if !declared {
+        strip_untrusted_approval_argument(arguments);
+    }
+}
+
 fn apply_explicit_approval_argument(arguments: &mut serde_json::Value) {
     if let serde_json::Value::Object(map) = arguments {
         map.insert("approved".to_string(), serde_json::Value::Bool(true));
@@ -253,6 +274,10 @@ pub(crate) const PROGRESS_MIN_INTERVAL_MS: u64 = 500;
 /// Used before streaming the final answer so progress lines are replaced by the clean response.
 pub(crate) const DRAFT_CLEAR_SENTINEL: &str = "\x00CLEAR\x00";
 
+/// Prefix marking an on_delta message as the live status line shown under the draft
+/// (e.g. "Running tool: browser…"). A bare prefix clears the status line.
+pub(crate) const DRAFT_STATUS_PREFIX: &str = "\x00STATUS\x00";
+
 /// Extract a short hint from tool call arguments for progress display.
 fn truncate_tool_args_for_progress(name: &str, args: &serde_json::Value, max_len: usize) -> String {
     let hint = match name {
@@ -269,6 +294,26 @@ fn truncate_tool_args_for_progress(name: &str, args: &serde_json::Value, max_len
     }
 }
 
+/// Live status line for the tools about to run.
+fn running_tools_status(calls: &[ParsedToolCall]) -> String {
+    let label = match calls {
+        [call] => {
+            let hint = truncate_tool_args_for_progress(&call.name, &call.arguments, 60);
+            if hint.is_empty() {
+                call.name.clone()
+            } else {
+                format!("{}: {hint}", call.name)
+            }
+        }
+        _ => calls
+            .iter()
+            .map(|call| call.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+    crate::i18n::tr_args("channel.running_tool", &[("tool", &label)])
+}
+
 /// Convert a tool registry to OpenAI function-calling format for native tool support.
 fn tools_to_openai_format(tools_registry: &[Box<dyn Tool>]) -> Vec<serde_json::Value> {
     tools_registry
@@ -402,7 +447,7 @@ async fn build_context(mem: &dyn Memory, user_msg: &str, min_relevance_score: f6
         if !relevant.is_empty() {
             context.push_str("[Memory context]\n");
             for entry in &relevant {
-                if memory::is_assistant_autosave_key(&entry.key) {
+                if memory::is_assistant_autosave_key(&entry.key) || memory::is_kv_key(&entry.key) {
                     continue;
                 }
                 let _ = writeln!(context, "- {}: {}", entry.key, entry.content);
@@ -1975,6 +2020,7 @@ async fn execute_one_tool(
             output: reason.clone(),
             success: false,
             error_reason: Some(scrub_credentials(&reason)),
+            error_class: Some(ErrorClass::Validation),
             duration,
         });
     };
@@ -2002,6 +2048,7 @@ async fn execute_one_tool(
                     output: scrub_credentials(&r.output),
                     success: true,
                     error_reason: None,
+                    error_class: None,
                     duration,
                 })
             } else {
@@ -2009,6 +2056,7 @@ async fn execute_one_tool(
                 Ok(ToolExecutionOutcome {
                     output: format!("Error: {reason}"),
                     success: false,
+                    error_class: Some(errors::classify_tool_failure(&reason)),
                     error_reason: Some(scrub_credentials(&reason)),
                     duration,
                 })
@@ -2021,11 +2069,16 @@ async fn execute_one_tool(
                 duration,
                 success: false,
             });
+            let error_class = match errors::classify(&e) {
+                ErrorClass::Internal => ErrorClass::ToolFailure,
+                class => class,
+            };
             let reason = format!("Error executing {call_name}: {e}");
             Ok(ToolExecutionOutcome {
                 output: reason.clone(),
                 success: false,
                 error_reason: Some(scrub_credentials(&reason)),
+                error_class: Some(error_class),
                 duration,
             })
         }
@@ -2036,6 +2089,7 @@ struct ToolExecutionOutcome {
     output: String,
     success: bool,
     error_reason: Option<String>,
+    error_class: Option<ErrorClass>,
     duration: Duration,
 }
 
@@ -2137,6 +2191,54 @@ pub(crate) async fn run_tool_call_loop(
     on_delta: Option<tokio::sync::mpsc::Sender<String>>,
     hooks: Option<&crate::hooks::HookRunner>,
     excluded_tools: &[String],
+) -> Result<String> {
+    let turn_id = Uuid::new_v4().to_string();
+    let mut checkpoints = TaskCheckpoints::new(&turn_id, channel_name);
+    let result = run_tool_call_turn(
+        provider,
+        history,
+        tools_registry,
+        observer,
+        provider_name,
+        model,
+        temperature,
+        silent,
+        approval,
+        channel_name,
+        multimodal_config,
+        max_tool_iterations,
+        cancellation_token,
+        on_delta,
+        hooks,
+        excluded_tools,
+        &turn_id,
+        &mut checkpoints,
+    )
+    .await;
+    checkpoints.finish().await;
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_tool_call_turn(
+    provider: &dyn Provider,
+    history: &mut Vec<ChatMessage>,
+    tools_registry: &[Box<dyn Tool>],
+    observer: &dyn Observer,
+    provider_name: &str,
+    model: &str,
+    temperature: f64,
+    silent: bool,
+    approval: Option<&ApprovalManager>,
+    channel_name: &str,
+    multimodal_config: &crate::config::MultimodalConfig,
+    max_tool_iterations: usize,
+    cancellation_token: Option<CancellationToken>,
+    on_delta: Option<tokio::sync::mpsc::Sender<String>>,
+    hooks: Option<&crate::hooks::HookRunner>,
+    excluded_tools: &[String],
+    turn_id: &str,
+    checkpoints: &mut TaskCheckpoints<'_>,
 ) -> Result<String> {
     let max_iterations = if max_tool_iterations == 0 {
         DEFAULT_MAX_TOOL_ITERATIONS
@@ -2144,14 +2246,35 @@ pub(crate) async fn run_tool_call_loop(
         max_tool_iterations
     };
 
-    let tool_specs: Vec<crate::tools::ToolSpec> = tools_registry
+    let all_tool_specs: Vec<crate::tools::ToolSpec> = tools_registry
         .iter()
         .filter(|tool| !excluded_tools.iter().any(|ex| ex == tool.name()))
         .map(|tool| tool.spec())
         .collect();
-    let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
-    let turn_id = Uuid::new_v4().to_string();
+    let tool_specs =
+        tool_schemas::prepare(&all_tool_specs, history, provider.caches_tool_schemas());
+    let tool_schema_tokens = tool_schemas::estimate_tokens(&tool_specs);
+    let tool_schema_tokens_full = tool_schemas::estimate_tokens(&all_tool_specs);
+    let capabilities = providers::capabilities::negotiate(provider, provider_name, model);
+    let use_native_tools = capabilities.native_tools && !tool_specs.is_empty();
+    // The provider speaks native tools but this model doesn't: describe the
+    // tools in the system prompt unless the caller already did.
+    let emulated_tool_instructions =
+        (provider.supports_native_tools() && !use_native_tools && !tool_specs.is_empty())
+            .then(|| providers::traits::build_tool_instructions_text(&tool_specs));
     let mut seen_tool_signatures: HashSet<(String, String)> = HashSet::new();
+    let validation = tool_validation::settings();
+    let registry_specs: Vec<crate::tools::ToolSpec> =
+        tools_registry.iter().map(|tool| tool.spec()).collect();
+    let mut invalid_rounds = 0;
+    // The inbound request, recorded with the final response so the turn can
+    // be replayed or distilled (`zeroclaw skills distill`).
+    let request = history
+        .iter()
+        .rev()
+        .find(|m| m.role == "user")
+        .map(|m| scrub_credentials(&m.content))
+        .unwrap_or_default();
 
     for iteration in 0..max_iterations {
         if cancellation_token
@@ -2173,8 +2296,36 @@ pub(crate) async fn run_tool_call_loop(
             .into());
         }
 
-        let prepared_messages =
-            multimodal::prepare_messages_for_provider(history, multimodal_config).await?;
+        if let Some(max_tokens) = capabilities.max_context_tokens {
+            let estimated_tokens = estimate_history_tokens(history);
+            if estimated_tokens > max_tokens {
+                return Err(ProviderCapabilityError {
+                    provider: provider_name.to_string(),
+                    capability: "context".to_string(),
+                    message: format!(
+                        "conversation is ~{estimated_tokens} tokens but model '{model}' accepts {max_tokens}; start a new conversation or pick a model with a larger context window"
+                    ),
+                }
+                .into());
+            }
+        }
+
+        let stripped_history;
+        let history_for_provider = if image_marker_count > 0 && !capabilities.vision {
+            stripped_history = multimodal::strip_image_markers(
+                history,
+                &format!("model '{model}' does not accept images"),
+            );
+            &stripped_history
+        } else {
+            &*history
+        };
+        let mut prepared_messages =
+            multimodal::prepare_messages_for_provider(history_for_provider, multimodal_config)
+                .await?;
+        if let Some(instructions) = &emulated_tool_instructions {
+            inject_tool_instructions(&mut prepared_messages.messages, instructions);
+        }
 
         // ── Progress: LLM thinking ────────────────────────────
         if let Some(ref tx) = on_delta {
@@ -2196,12 +2347,15 @@ pub(crate) async fn run_tool_call_loop(
             Some(channel_name),
             Some(provider_name),
             Some(model),
-            Some(&turn_id),
+            Some(turn_id),
             None,
             None,
             serde_json::json!({
                 "iteration": iteration + 1,
                 "messages_count": history.len(),
+                "tools_count": tool_specs.len(),
+                "tool_schema_tokens": tool_schema_tokens,
+                "tool_schema_tokens_full": tool_schema_tokens_full,
             }),
         );
 
@@ -2220,14 +2374,34 @@ pub(crate) async fn run_tool_call_loop(
             None
         };
 
-        let chat_future = provider.chat(
-            ChatRequest {
-                messages: &prepared_messages.messages,
-                tools: request_tools,
-            },
-            model,
-            temperature,
-        );
+        let chat_request = ChatRequest {
+            messages: &prepared_messages.messages,
+            tools: request_tools,
+        };
+        let stream_draft = on_delta
+            .as_ref()
+            .filter(|_| tool_stream::should_stream(provider, use_native_tools));
+        let chat_future = async {
+            let Some(draft) = stream_draft else {
+                return provider.chat(chat_request, model, temperature).await;
+            };
+            let (response, issues) =
+                tool_stream::chat(provider, chat_request, model, temperature, draft).await?;
+            for issue in issues {
+                tracing::warn!("Streamed tool call issue: {issue}");
+                runtime_trace::record_event(
+                    "tool_call_stream_issue",
+                    Some(channel_name),
+                    Some(provider_name),
+                    Some(model),
+                    Some(turn_id),
+                    Some(false),
+                    Some(&issue),
+                    serde_json::json!({ "iteration": iteration + 1 }),
+                );
+            }
+            Ok(response)
+        };
 
         let chat_result = if let Some(token) = cancellation_token.as_ref() {
             tokio::select! {
@@ -2241,11 +2415,23 @@ pub(crate) async fn run_tool_call_loop(
         let (response_text, parsed_text, tool_calls, assistant_history_content, native_tool_calls) =
             match chat_result {
                 Ok(resp) => {
-                    let (resp_input_tokens, resp_output_tokens) = resp
+                    let (resp_input_tokens, resp_output_tokens, resp_cached_input_tokens) = resp
                         .usage
                         .as_ref()
-                        .map(|u| (u.input_tokens, u.output_tokens))
-                        .unwrap_or((None, None));
+                        .map(|u| (u.input_tokens, u.output_tokens, u.cached_input_tokens))
+                        .unwrap_or((None, None, None));
+                    let resp_cost_usd = runtime_trace::estimate_cost_usd(
+                        provider_name,
+                        model,
+                        resp_input_tokens,
+                        resp_output_tokens,
+                    );
+                    crate::cost::downgrade::record_response(
+                        provider_name,
+                        model,
+                        resp_input_tokens,
+                        resp_output_tokens,
+                    );
 
                     observer.record_event(&ObserverEvent::LlmResponse {
                         provider: provider_name.to_string(),
@@ -2280,7 +2466,7 @@ pub(crate) async fn run_tool_call_loop(
                             Some(channel_name),
                             Some(provider_name),
                             Some(model),
-                            Some(&turn_id),
+                            Some(turn_id),
                             Some(false),
                             Some(&parse_issue),
                             serde_json::json!({
@@ -2298,7 +2484,7 @@ pub(crate) async fn run_tool_call_loop(
                         Some(channel_name),
                         Some(provider_name),
                         Some(model),
-                        Some(&turn_id),
+                        Some(turn_id),
                         Some(true),
                         None,
                         serde_json::json!({
@@ -2306,6 +2492,8 @@ pub(crate) async fn run_tool_call_loop(
                             "duration_ms": llm_started_at.elapsed().as_millis(),
                             "input_tokens": resp_input_tokens,
                             "output_tokens": resp_output_tokens,
+                            "cached_input_tokens": resp_cached_input_tokens,
+                            "cost_usd": resp_cost_usd,
                             "raw_response": scrub_credentials(&response_text),
                             "native_tool_calls": resp.tool_calls.len(),
                             "parsed_tool_calls": calls.len(),
@@ -2350,12 +2538,13 @@ pub(crate) async fn run_tool_call_loop(
                         Some(channel_name),
                         Some(provider_name),
                         Some(model),
-                        Some(&turn_id),
+                        Some(turn_id),
                         Some(false),
                         Some(&safe_error),
                         serde_json::json!({
                             "iteration": iteration + 1,
                             "duration_ms": llm_started_at.elapsed().as_millis(),
+                            "error_code": errors::classify(&e).code(),
                         }),
                     );
                     return Err(e);
@@ -2387,11 +2576,12 @@ pub(crate) async fn run_tool_call_loop(
                 Some(channel_name),
                 Some(provider_name),
                 Some(model),
-                Some(&turn_id),
+                Some(turn_id),
                 Some(true),
                 None,
                 serde_json::json!({
                     "iteration": iteration + 1,
+                    "request": request,
                     "text": scrub_credentials(&display_text),
                 }),
             );
@@ -2432,6 +2622,77 @@ pub(crate) async fn run_tool_call_loop(
             let _ = std::io::stdout().flush();
         }
 
+        // ── Tool-call validation ────────────────────────────
+        // Calls that don't match their schema are coerced or re-prompted;
+        // those still invalid are answered with the problems found.
+        let mut tool_calls = tool_calls;
+        let mut invalid_calls: Vec<Option<Vec<String>>> = vec![None; tool_calls.len()];
+        if validation.enabled {
+            for (idx, call) in tool_calls.iter_mut().enumerate() {
+                match tool_validation::check(
+                    provider,
+                    model,
+                    temperature,
+                    &registry_specs,
+                    &call.name,
+                    &call.arguments,
+                    validation.repair_attempts,
+                )
+                .await
+                {
+                    Checked::Valid => {}
+                    Checked::Repaired {
+                        name,
+                        arguments,
+                        how,
+                    } => {
+                        runtime_trace::record_event(
+                            "tool_call_repaired",
+                            Some(channel_name),
+                            Some(provider_name),
+                            Some(model),
+                            Some(turn_id),
+                            Some(true),
+                            None,
+                            serde_json::json!({
+                                "iteration": iteration + 1,
+                                "tool": call.name,
+                                "repaired_tool": name,
+                                "how": how,
+                                "arguments": scrub_credentials(&call.arguments.to_string()),
+                                "repaired_arguments": scrub_credentials(&arguments.to_string()),
+                            }),
+                        );
+                        call.name = name;
+                        call.arguments = arguments;
+                    }
+                    Checked::Invalid(issues) => {
+                        invalid_calls[idx] = Some(issues);
+                    }
+                }
+            }
+
+            if invalid_calls.iter().all(Option::is_some) {
+                invalid_rounds += 1;
+                if validation.max_invalid_rounds > 0
+                    && invalid_rounds >= validation.max_invalid_rounds
+                {
+                    let last = invalid_calls
+                        .iter()
+                        .flatten()
+                        .flatten()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join("; ");
+                    anyhow::bail!(
+                        "Model '{model}' kept emitting invalid tool calls ({invalid_rounds} rounds in a row; last: {last}). Try rephrasing the request or switching to a stronger model."
+                    );
+                }
+            } else {
+                invalid_rounds = 0;
+            }
+        }
+
         // Execute tool calls and build results. `individual_results` tracks per-call output so
         // native-mode history can emit one role=tool message per tool call with the correct ID.
         //
@@ -2444,8 +2705,40 @@ pub(crate) async fn run_tool_call_loop(
         let allow_parallel_execution = should_execute_tools_in_parallel(&tool_calls, approval);
         let mut executable_indices: Vec<usize> = Vec::new();
         let mut executable_calls: Vec<ParsedToolCall> = Vec::new();
+        let mut change_previews: Vec<Option<String>> = Vec::new();
 
         for (idx, call) in tool_calls.iter().enumerate() {
+            if let Some(issues) = invalid_calls[idx].take() {
+                let invalid = tool_validation::invalid_message(&call.name, &issues);
+                runtime_trace::record_event(
+                    "tool_call_result",
+                    Some(channel_name),
+                    Some(provider_name),
+                    Some(model),
+                    Some(turn_id),
+                    Some(false),
+                    Some(&invalid),
+                    serde_json::json!({
+                        "iteration": iteration + 1,
+                        "tool": call.name,
+                        "arguments": scrub_credentials(&call.arguments.to_string()),
+                        "error_code": ErrorClass::Validation.code(),
+                    }),
+                );
+                ordered_results[idx] = Some((
+                    call.name.clone(),
+                    call.tool_call_id.clone(),
+                    ToolExecutionOutcome {
+                        output: invalid.clone(),
+                        success: false,
+                        error_reason: Some(scrub_credentials(&invalid)),
+                        error_class: Some(ErrorClass::Validation),
+                        duration: Duration::ZERO,
+                    },
+                ));
+                continue;
+            }
+
             // ── Hook: before_tool_call (modifying) ──────────
             let mut tool_name = call.name.clone();
             let mut tool_args = call.arguments.clone();
@@ -2462,13 +2755,14 @@ pub(crate) async fn run_tool_call_loop(
                             Some(channel_name),
                             Some(provider_name),
                             Some(model),
-                            Some(&turn_id),
+                            Some(turn_id),
                             Some(false),
                             Some(&cancelled),
                             serde_json::json!({
                                 "iteration": iteration + 1,
                                 "tool": call.name,
                                 "arguments": scrub_credentials(&tool_args.to_string()),
+                                "error_code": ErrorClass::PolicyDenied.code(),
                             }),
                         );
                         ordered_results[idx] = Some((
@@ -2478,6 +2772,7 @@ pub(crate) async fn run_tool_call_loop(
                                 output: cancelled,
                                 success: false,
                                 error_reason: Some(scrub_credentials(&reason)),
+                                error_class: Some(ErrorClass::PolicyDenied),
                                 duration: Duration::ZERO,
                             },
                         ));
@@ -2490,13 +2785,103 @@ pub(crate) async fn run_tool_call_loop(
                 }
             }
 
+            strip_undeclared_approval_argument(
+                find_tool(tools_registry, &tool_name),
+                &mut tool_args,
+            );
+            let change_preview =
+                find_tool(tools_registry, &tool_name).and_then(|t| t.change_preview(&tool_args));
+
+            // ── Policy engine (policy-as-code) ───────────────
+            let mut policy_approval_reason = None;
+            let mut policy_denial = None;
+            match crate::security::policy_engine::evaluate(&tool_name, &tool_args, channel_name)
+                .await
+            {
+                Some(Verdict::Deny(reason)) => {
+                    policy_denial = Some(format!("Denied by policy: {reason}"));
+                }
+                // Only the CLI can prompt; elsewhere a second person signs off.
+                Some(Verdict::Approve(_)) if approval.is_none() || channel_name != "cli" => {
+                    if let Err(reason) = crate::security::policy_engine::request_second_approval(
+                        &tool_name, &tool_args,
+                    )
+                    .await
+                    {
+                        policy_denial = Some(reason);
+                    }
+                }
+                Some(Verdict::Approve(reason)) => policy_approval_reason = Some(reason),
+                Some(Verdict::Allow) | None => {}
+            }
+            if let Some(denied) = policy_denial {
+                runtime_trace::record_event(
+                    "tool_call_result",
+                    Some(channel_name),
+                    Some(provider_name),
+                    Some(model),
+                    Some(turn_id),
+                    Some(false),
+                    Some(&denied),
+                    serde_json::json!({
+                        "iteration": iteration + 1,
+                        "tool": tool_name.clone(),
+                        "arguments": scrub_credentials(&tool_args.to_string()),
+                        "error_code": ErrorClass::PolicyDenied.code(),
+                    }),
+                );
+                ordered_results[idx] = Some((
+                    tool_name.clone(),
+                    call.tool_call_id.clone(),
+                    ToolExecutionOutcome {
+                        output: denied.clone(),
+                        success: false,
+                        error_reason: Some(denied),
+                        error_class: Some(ErrorClass::PolicyDenied),
+                        duration: Duration::ZERO,
+                    },
+                ));
+                continue;
+            }
+
+            // ── Autonomous mode (`/auto`) ────────────────────
+            // Skips the approval step only; policy-engine approvals and
+            // `always_ask` tools still go through it. Calls that would not
+            // have been gated do not spend the window's budget.
+            let approval_required = match approval {
+                Some(mgr) => !mgr.always_asks(&tool_name) && mgr.needs_approval(&tool_name),
+                None => {
+                    !crate::approval::is_builtin_always_ask(&tool_name)
+                        && find_tool(tools_registry, &tool_name).is_some_and(|tool| {
+                            tool.parameters_schema()
+                                .pointer("/properties/approved")
+                                .is_some()
+                        })
+                }
+            };
+            let auto_approved = policy_approval_reason.is_none()
+                && approval_required
+                && crate::approval::autonomous::take_action(
+                    &crate::security::four_eyes::current_requester(),
+                    &tool_name,
+                    &tool_args,
+                );
+
             // ── Approval hook ────────────────────────────────
-            let mut explicit_approval_granted = false;
-            if let Some(mgr) = approval {
-                if mgr.needs_approval(&tool_name) {
+            let mut explicit_approval_granted = auto_approved;
+            if let Some(mgr) = approval.filter(|_| !auto_approved) {
+                if mgr.needs_approval(&tool_name) || policy_approval_reason.is_some() {
+                    let preview = match policy_approval_reason.as_deref() {
+                        Some(reason) if !reason.is_empty() => Some(match &change_preview {
+                            Some(preview) => format!("Policy: {reason}\n{preview}"),
+                            None => format!("Policy: {reason}"),
+                        }),
+                        _ => change_preview.clone(),
+                    };
                     let request = ApprovalRequest {
                         tool_name: tool_name.clone(),
                         arguments: tool_args.clone(),
+                        preview,
                     };
 
                     // Only prompt interactively on CLI.
@@ -2509,7 +2894,7 @@ pub(crate) async fn run_tool_call_loop(
                         ApprovalResponse::No
                     };
 
-                    mgr.record_decision(&tool_name, &tool_args, decision, channel_name);
+                    mgr.record_request_decision(&request, decision, channel_name);
 
                     if decision == ApprovalResponse::No {
                         let denied = "Denied by user.".to_string();
@@ -2518,13 +2903,14 @@ pub(crate) async fn run_tool_call_loop(
                             Some(channel_name),
                             Some(provider_name),
                             Some(model),
-                            Some(&turn_id),
+                            Some(turn_id),
                             Some(false),
                             Some(&denied),
                             serde_json::json!({
                                 "iteration": iteration + 1,
                                 "tool": tool_name.clone(),
                                 "arguments": scrub_credentials(&tool_args.to_string()),
+                                "error_code": ErrorClass::PolicyDenied.code(),
                             }),
                         );
                         ordered_results[idx] = Some((
@@ -2534,6 +2920,7 @@ pub(crate) async fn run_tool_call_loop(
                                 output: denied.clone(),
                                 success: false,
                                 error_reason: Some(denied),
+                                error_class: Some(ErrorClass::PolicyDenied),
                                 duration: Duration::ZERO,
                             },
                         ));
@@ -2559,7 +2946,7 @@ pub(crate) async fn run_tool_call_loop(
                     Some(channel_name),
                     Some(provider_name),
                     Some(model),
-                    Some(&turn_id),
+                    Some(turn_id),
                     Some(false),
                     Some(&duplicate),
                     serde_json::json!({
@@ -2567,6 +2954,7 @@ pub(crate) async fn run_tool_call_loop(
                         "tool": tool_name.clone(),
                         "arguments": scrub_credentials(&tool_args.to_string()),
                         "deduplicated": true,
+                        "error_code": ErrorClass::Validation.code(),
                     }),
                 );
                 ordered_results[idx] = Some((
@@ -2576,6 +2964,7 @@ pub(crate) async fn run_tool_call_loop(
                         output: duplicate.clone(),
                         success: false,
                         error_reason: Some(duplicate),
+                        error_class: Some(ErrorClass::Validation),
                         duration: Duration::ZERO,
                     },
                 ));
@@ -2587,7 +2976,7 @@ pub(crate) async fn run_tool_call_loop(
                 Some(channel_name),
                 Some(provider_name),
                 Some(model),
-                Some(&turn_id),
+                Some(turn_id),
                 None,
                 None,
                 serde_json::json!({
@@ -2597,19 +2986,8 @@ pub(crate) async fn run_tool_call_loop(
                 }),
             );
 
-            // ── Progress: tool start ────────────────────────────
-            if let Some(ref tx) = on_delta {
-                let hint = truncate_tool_args_for_progress(&tool_name, &tool_args, 60);
-                let progress = if hint.is_empty() {
-                    format!("\u{23f3} {}\n", tool_name)
-                } else {
-                    format!("\u{23f3} {}: {hint}\n", tool_name)
-                };
-                tracing::debug!(tool = %tool_name, "Sending progress start to draft");
-                let _ = tx.send(progress).await;
-            }
-
             executable_indices.push(idx);
+            change_previews.push(change_preview);
             executable_calls.push(ParsedToolCall {
                 name: tool_name,
                 arguments: tool_args,
@@ -2617,6 +2995,21 @@ pub(crate) async fn run_tool_call_loop(
             });
         }
 
+        // ── Progress: live status line ──────────────────────
+        if let Some(ref tx) = on_delta {
+            if !executable_calls.is_empty() {
+                tracing::debug!(
+                    count = executable_calls.len(),
+                    "Sending tool status to draft"
+                );
+                let status = running_tools_status(&executable_calls);
+                let _ = tx.send(format!("{DRAFT_STATUS_PREFIX}{status}")).await;
+            }
+        }
+
+        if !executable_calls.is_empty() {
+            checkpoints.before_tools().await;
+        }
         let executed_outcomes = if allow_parallel_execution && executable_calls.len() > 1 {
             execute_tools_parallel(
                 &executable_calls,
@@ -2635,27 +3028,57 @@ pub(crate) async fn run_tool_call_loop(
             .await?
         };
 
-        for ((idx, call), outcome) in executable_indices
+        for (((idx, call), outcome), change_preview) in executable_indices
             .iter()
             .zip(executable_calls.iter())
             .zip(executed_outcomes.into_iter())
+            .zip(change_previews)
         {
+            // Large outputs go to the artifact store; the trace keeps a preview.
+            let scrubbed_output = scrub_credentials(&outcome.output);
+            let artifact = crate::artifacts::offload(&call.name, Some(turn_id), &scrubbed_output);
+            let mut result_payload = serde_json::json!({
+                "iteration": iteration + 1,
+                "tool": call.name.clone(),
+                "duration_ms": outcome.duration.as_millis(),
+                "output": scrubbed_output,
+                "error_code": outcome.error_class.map(ErrorClass::code),
+            });
+            if let Some((artifact, preview)) = artifact {
+                result_payload["output"] = serde_json::Value::String(preview);
+                result_payload["artifact_id"] = serde_json::Value::String(artifact.id);
+                result_payload["output_bytes"] = serde_json::json!(artifact.bytes);
+            }
             runtime_trace::record_event(
                 "tool_call_result",
                 Some(channel_name),
                 Some(provider_name),
                 Some(model),
-                Some(&turn_id),
+                Some(turn_id),
                 Some(outcome.success),
                 outcome.error_reason.as_deref(),
-                serde_json::json!({
-                    "iteration": iteration + 1,
-                    "tool": call.name.clone(),
-                    "duration_ms": outcome.duration.as_millis(),
-                    "output": scrub_credentials(&outcome.output),
-                }),
+                result_payload,
             );
 
+            // ── Audit: applied file change diff ──────────────
+            if let Some(diff) = change_preview.filter(|d| outcome.success && !d.is_empty()) {
+                runtime_trace::record_event(
+                    "file_change_applied",
+                    Some(channel_name),
+                    Some(provider_name),
+                    Some(model),
+                    Some(turn_id),
+                    Some(true),
+                    None,
+                    serde_json::json!({
+                        "iteration": iteration + 1,
+                        "tool": call.name.clone(),
+                        "path": call.arguments.get("path").cloned().unwrap_or_default(),
+                        "diff": scrub_credentials(&diff),
+                    }),
+                );
+            }
+
             // ── Hook: after_tool_call (void) ─────────────────
             if let Some(hooks) = hooks {
                 let tool_result_obj = crate::tools::ToolResult {
@@ -2683,6 +3106,12 @@ pub(crate) async fn run_tool_call_loop(
             ordered_results[*idx] = Some((call.name.clone(), call.tool_call_id.clone(), outcome));
         }
 
+        if let Some(ref tx) = on_delta {
+            if !executable_calls.is_empty() {
+                let _ = tx.send(DRAFT_STATUS_PREFIX.to_string()).await;
+            }
+        }
+
         for entry in ordered_results {
             if let Some((tool_name, tool_call_id, outcome)) = entry {
                 individual_results.push((tool_call_id, outcome.output.clone()));
@@ -2734,7 +3163,7 @@ pub(crate) async fn run_tool_call_loop(
         Some(channel_name),
         Some(provider_name),
         Some(model),
-        Some(&turn_id),
+        Some(turn_id),
         Some(false),
         Some("agent exceeded maximum tool iterations"),
         serde_json::json!({
@@ -2744,22 +3173,33 @@ pub(crate) async fn run_tool_call_loop(
     anyhow::bail!("Agent exceeded maximum tool iterations ({max_iterations})")
 }
 
+/// Rough token count of the conversation (about four characters per token).
+fn estimate_history_tokens(history: &[ChatMessage]) -> u64 {
+    let chars: usize = history.iter().map(|m| m.content.chars().count()).sum();
+    (chars / 4) as u64
+}
+
+/// Append prompt-guided tool instructions to the system message, unless the
+/// system prompt already carries a tool protocol section.
+fn inject_tool_instructions(messages: &mut Vec<ChatMessage>, instructions: &str) {
+    match messages.iter_mut().find(|m| m.role == "system") {
+        Some(system) if system.content.contains("## Tool Use Protocol") => {}
+        Some(system) => {
+            system.content.push_str("\n\n");
+            system.content.push_str(instructions);
+        }
+        None => messages.insert(0, ChatMessage::system(instructions)),
+    }
+}
+
 /// Build the tool instruction block for the system prompt so the LLM knows
 /// how to invoke tools.
-pub(crate) fn build_tool_instructions(tools_registry: &[Box<dyn Tool>]) -> String {
-    let mut instructions = String::new();
-    instructions.push_str("\n## Tool Use Protocol\n\n");
-    instructions.push_str("To use a tool, wrap a JSON object in <tool_call></tool_call> tags:\n\n");
-    instructions.push_str("```\n<tool_call>\n{\"name\": \"tool_name\", \"arguments\": {\"param\": \"value\"}}\n</tool_call>\n```\n\n");
-    instructions.push_str(
-        "CRITICAL: Output actual <tool_call> tags—never describe steps or give examples.\n\n",
-    );
-    instructions.push_str("Example: User says \"what's the date?\". You MUST respond with:\n<tool_call>\n{\"name\":\"shell\",\"arguments\":{\"command\":\"date\"}}\n</tool_call>\n\n");
-    instructions.push_str("You may use multiple tool calls in a single response. ");
-    instructions.push_str("After tool execution, results appear in <tool_result> tags. ");
-    instructions
-        .push_str("Continue reasoning with the results until you can give a final answer.\n\n");
-    instructions.push_str("If a tool result reports a security guardrail block, do NOT invent bypasses. Explain the block, provide least-privilege options first, and include risk warnings before suggesting policy relaxation.\n\n");
+pub(crate) fn build_tool_instructions(
+    tools_registry: &[Box<dyn Tool>],
+    workspace_dir: &std::path::Path,
+) -> String {
+    let mut instructions = String::from("\n");
+    crate::prompts::push_section(&mut instructions, workspace_dir, "tool_protocol");
     instructions.push_str("### Available Tools\n\n");
 
     for tool in tools_registry {
@@ -2790,7 +3230,12 @@ pub async fn run(
     temperature: f64,
     peripheral_overrides: Vec<String>,
     interactive: bool,
+    simulate: bool,
+    allowed_tools: Vec<String>,
+    handoff: crate::handoff::CliHandoff,
 ) -> Result<String> {
+    crate::config::ensure_runtime_globals(&config);
+
     // ── Wire up agnostic subsystems ──────────────────────────────
     let base_observer = observability::create_observer(&config.observability);
     let observer: Arc<dyn Observer> = Arc::from(base_observer);
@@ -2802,12 +3247,12 @@ pub async fn run(
     ));
 
     // ── Memory (the brain) ────────────────────────────────────────
-    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
+    let mem = memory::scoped::for_current_task(Arc::from(memory::create_memory_with_storage(
         &config.memory,
         Some(&config.storage.provider.config),
         &config.workspace_dir,
         config.api_key.as_deref(),
-    )?);
+    )?));
     tracing::info!(backend = mem.name(), "Memory initialized");
 
     // ── Peripherals (merge peripheral tools into registry) ─
@@ -2843,12 +3288,38 @@ pub async fn run(
     );
 
     let peripheral_tools: Vec<Box<dyn Tool>> =
-        crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
+        crate::peripherals::create_peripheral_tools(&config.peripherals, &config.workspace_dir)
+            .await?;
     if !peripheral_tools.is_empty() {
         tracing::info!(count = peripheral_tools.len(), "Peripheral tools added");
         tools_registry.extend(peripheral_tools);
     }
 
+    // ── Collection Q&A: the collection is the only source ────────
+    let collection = config
+        .agent
+        .collection
+        .as_deref()
+        .map(|name| crate::presets::knowledge::Collection::load(&config.workspace_dir, name))
+        .transpose()?;
+    if collection.is_some() {
+        tools_registry.clear();
+    }
+    let rag = crate::rag::collections::RagCollections::from_config(&config)?;
+
+    // ── Task toolset (empty = every tool) ────────────────────────
+    if !allowed_tools.is_empty() {
+        tools_registry.retain(|tool| allowed_tools.iter().any(|name| name == tool.name()));
+        tracing::info!(
+            count = tools_registry.len(),
+            "Tool registry limited to task toolset"
+        );
+    }
+
+    // ── Simulation mode (tools answer with mock results) ─────────
+    let simulation = Arc::new(AtomicBool::new(simulate));
+    let tools_registry = tools::simulation::wrap_tools(tools_registry, &simulation);
+
     // ── Resolve provider ─────────────────────────────────────────
     let provider_name = provider_override
         .as_deref()
@@ -2866,6 +3337,8 @@ pub async fn run(
         secrets_encrypt: config.secrets.encrypt,
         provider_api_url: config.api_url.clone(),
         reasoning_enabled: config.runtime.reasoning_enabled,
+        custom_providers: config.custom_providers.clone(),
+        provider_plugins: config.provider_plugins.clone(),
     };
 
     let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
@@ -2877,6 +3350,14 @@ pub async fn run(
         model_name,
         &provider_runtime_options,
     )?;
+    let provider = providers::shadow::wrap_with_shadow(
+        provider,
+        provider_name,
+        &config,
+        &provider_runtime_options,
+    );
+    let provider =
+        providers::budget::wrap_with_budget(provider, provider_name, config.agent.max_run_cost_usd);
 
     observer.record_event(&ObserverEvent::AgentStart {
         provider: provider_name.to_string(),
@@ -2930,6 +3411,10 @@ pub async fn run(
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
         ),
+        (
+            "pin",
+            "Pin a memory, workspace file or note so it stays in context. Use when: the user asks to keep something in view for the whole conversation. Don't use when: a one-off lookup suffices.",
+        ),
     ];
     tool_descs.push((
         "cron_add",
@@ -3013,12 +3498,17 @@ pub async fn run(
             "Query connected hardware for reported GPIO pins and LED pin. Use when: user asks what pins are available.",
         ));
     }
+    if collection.is_some() {
+        tool_descs.clear();
+    }
     let bootstrap_max_chars = if config.agent.compact_context {
         Some(6000)
     } else {
         None
     };
-    let native_tools = provider.supports_native_tools();
+    let native_tools =
+        providers::capabilities::negotiate(provider.as_ref(), provider_name, model_name)
+            .native_tools;
     let mut system_prompt = crate::channels::build_system_prompt_with_mode(
         &config.workspace_dir,
         model_name,
@@ -3032,8 +3522,36 @@ pub async fn run(
 
     // Append structured tool-use instructions with schemas (only for non-native providers)
     if !native_tools {
-        system_prompt.push_str(&build_tool_instructions(&tools_registry));
+        system_prompt.push_str(&build_tool_instructions(
+            &tools_registry,
+            &config.workspace_dir,
+        ));
+    }
+    if let Some(collection) = &collection {
+        system_prompt.push_str(&collection.instructions());
+    }
+    // The local user's reply preferences (`zeroclaw identity prefs`),
+    // standing instructions (`zeroclaw instructions`) and pins (`[pins]`).
+    let preferences = crate::preferences::PreferenceStore::for_workspace(&config.workspace_dir);
+    let standing = crate::instructions::InstructionStore::from_config(&config);
+    let skill_names: Vec<String> = skills.iter().map(|skill| skill.name.clone()).collect();
+    let base_system_prompt = system_prompt.clone();
+    let session_prompt = |pinned: &str| {
+        let mut prompt = base_system_prompt.clone();
+        if interactive {
+            prompt.push_str(&preferences.prompt_section("cli"));
+        }
+        prompt.push_str(
+            &standing.prompt_section(if interactive { "cli" } else { "daemon" }, &skill_names),
+        );
+        prompt.push_str(pinned);
+        prompt
+    };
+    let mut pinned = crate::pins::resolve(&config.workspace_dir, Some(mem.as_ref())).await;
+    for warning in &pinned.warnings {
+        eprintln!("⚠️  {warning}");
     }
+    system_prompt = session_prompt(&pinned.section);
 
     // ── Approval manager (supervised mode) ───────────────────────
     let approval_manager = if interactive {
@@ -3049,6 +3567,17 @@ pub async fn run(
     let mut final_output = String::new();
 
     if let Some(msg) = message {
+        let msg = match tools::simulation::strip_simulate_directive(&msg) {
+            Some(rest) => {
+                simulation.store(true, Ordering::Relaxed);
+                rest.to_string()
+            }
+            None => msg,
+        };
+        if simulation.load(Ordering::Relaxed) {
+            println!("🧪 Simulation mode: tools return mock results and nothing is executed.\n");
+        }
+
         // Auto-save user message to memory (skip short/trivial messages)
         if config.memory.auto_save && msg.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
             let user_key = autosave_memory_key("user_msg");
@@ -3057,15 +3586,24 @@ pub async fn run(
                 .await;
         }
 
-        // Inject memory + hardware RAG context into user message
-        let mem_context =
-            build_context(mem.as_ref(), &msg, config.memory.min_relevance_score).await;
+        // Inject memory + hardware RAG context (or collection excerpts) into user message
         let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-        let hw_context = hardware_rag
-            .as_ref()
-            .map(|r| build_hardware_context(r, &msg, &board_names, rag_limit))
-            .unwrap_or_default();
-        let context = format!("{mem_context}{hw_context}");
+        let context = match &collection {
+            Some(collection) => collection.context(mem.as_ref(), &msg, rag_limit).await,
+            None => {
+                let mem_context =
+                    build_context(mem.as_ref(), &msg, config.memory.min_relevance_score).await;
+                let doc_context = match &rag {
+                    Some(rag) => rag.context(Identity::local(channel_name), &msg).await,
+                    None => String::new(),
+                };
+                let hw_context = hardware_rag
+                    .as_ref()
+                    .map(|r| build_hardware_context(r, &msg, &board_names, rag_limit))
+                    .unwrap_or_default();
+                format!("{mem_context}{doc_context}{hw_context}")
+            }
+        };
         let enriched = if context.is_empty() {
             msg.clone()
         } else {
@@ -3086,7 +3624,7 @@ pub async fn run(
             model_name,
             temperature,
             false,
-            approval_manager.as_ref(),
+            approval_for_turn(approval_manager.as_ref(), &simulation),
             channel_name,
             &config.multimodal,
             config.agent.max_tool_iterations,
@@ -3106,6 +3644,18 @@ pub async fn run(
 
         // Persistent conversation history across turns
         let mut history = vec![ChatMessage::system(&system_prompt)];
+        // Cost ledger key for this conversation; `/clear` starts a new one.
+        let mut conversation_id = format!("cli:{}", Uuid::new_v4());
+        let mut session_simulate = simulate;
+        let mut park_for = handoff.park_for;
+        if let Some(resumed) = handoff.resume {
+            println!(
+                "📥 Continuing the conversation from {} ({} messages).\n",
+                resumed.source,
+                resumed.turns.len()
+            );
+            history.extend(resumed.turns);
+        }
 
         loop {
             print!("> ");
@@ -3125,15 +3675,52 @@ pub async fn run(
             if user_input.is_empty() {
                 continue;
             }
+            if let Some(crate::handoff::HandoffCommand::Park(target)) =
+                crate::handoff::parse_command(&user_input)
+            {
+                if target.is_empty() {
+                    println!("Usage: /handoff <channel>, e.g. /handoff telegram\n");
+                    continue;
+                }
+                park_for = Some(target);
+                break;
+            }
+            if let Some(command) = crate::approval::autonomous::parse_command(&user_input) {
+                println!(
+                    "{}\n",
+                    crate::approval::autonomous::handle_command("cli", command)
+                );
+                continue;
+            }
             match user_input.as_str() {
                 "/quit" | "/exit" => break,
                 "/help" => {
                     println!("Available commands:");
                     println!("  /help        Show this help message");
                     println!("  /clear /new  Clear conversation history");
+                    println!("  /simulate    Toggle simulation mode (tools return mock results)");
+                    println!("  /simulate <message>  Run one message in simulation mode");
+                    println!("  /pin [memory|file|note] <target>  Keep something in context");
+                    println!("  /pins        List pins");
+                    println!("  /unpin <id>  Remove a pin");
+                    println!("  /auto <20m|off>      Skip approval prompts for a while");
+                    println!(
+                        "  /handoff <channel>   Continue this conversation on a channel and exit"
+                    );
                     println!("  /quit /exit  Exit interactive mode\n");
                     continue;
                 }
+                "/simulate" => {
+                    let enabled = !simulation.load(Ordering::Relaxed);
+                    simulation.store(enabled, Ordering::Relaxed);
+                    session_simulate = enabled;
+                    if enabled {
+                        println!("🧪 Simulation mode on: tools return mock results.\n");
+                    } else {
+                        println!("Simulation mode off: tools execute normally.\n");
+                    }
+                    continue;
+                }
                 "/clear" | "/new" => {
                     println!(
                         "This will clear the current conversation and delete all session memory."
@@ -3153,6 +3740,7 @@ pub async fn run(
 
                     history.clear();
                     history.push(ChatMessage::system(&system_prompt));
+                    conversation_id = format!("cli:{}", Uuid::new_v4());
                     // Clear conversation and daily memory
                     let mut cleared = 0;
                     for category in [MemoryCategory::Conversation, MemoryCategory::Daily] {
@@ -3172,49 +3760,184 @@ pub async fn run(
                 }
                 _ => {}
             }
-
-            // Auto-save conversation turns (skip short/trivial messages)
-            if config.memory.auto_save && user_input.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
-                let user_key = autosave_memory_key("user_msg");
-                let _ = mem
-                    .store(&user_key, &user_input, MemoryCategory::Conversation, None)
-                    .await;
+            if let Some((field, value)) = crate::preferences::parse_instruction(&user_input) {
+                match preferences.set("cli", field, &value) {
+                    Ok(_) => {
+                        system_prompt = session_prompt(&pinned.section);
+                        if let Some(system) = history.first_mut().filter(|m| m.role == "system") {
+                            system.content.clone_from(&system_prompt);
+                        }
+                        println!("Saved preference: {} = {value}\n", field.as_str());
+                    }
+                    Err(err) => eprintln!("Could not save the preference: {err:#}\n"),
+                }
+                continue;
             }
-
-            // Inject memory + hardware RAG context into user message
-            let mem_context =
-                build_context(mem.as_ref(), &user_input, config.memory.min_relevance_score).await;
-            let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-            let hw_context = hardware_rag
-                .as_ref()
-                .map(|r| build_hardware_context(r, &user_input, &board_names, rag_limit))
-                .unwrap_or_default();
-            let context = format!("{mem_context}{hw_context}");
+            if let Some(command) = crate::pins::parse_command(&user_input) {
+                let store = crate::pins::PinStore::for_workspace(&config.workspace_dir);
+                let outcome = match command {
+                    crate::pins::Command::List => store.list().map(|pins| {
+                        if pins.is_empty() {
+                            return "No pins.".to_string();
+                        }
+                        let mut out = format!(
+                            "Pins (~{} of {} tokens in use):",
+                            pinned.tokens, config.pins.token_budget
+                        );
+                        for pin in pins {
+                            let _ = write!(out, "\n  #{} {} {}", pin.id, pin.kind, pin.target);
+                        }
+                        out
+                    }),
+                    crate::pins::Command::Add(kind, target) => store
+                        .add(kind, &target)
+                        .map(|pin| format!("Pinned #{}: {} {}", pin.id, pin.kind, pin.target)),
+                    crate::pins::Command::Remove(id) => {
+                        store.remove(id).map(|removed| match removed {
+                            Some(pin) => format!("Unpinned #{id}: {} {}", pin.kind, pin.target),
+                            None => format!("No pin #{id}"),
+                        })
+                    }
+                };
+                match outcome {
+                    Ok(message) => {
+                        pinned =
+                            crate::pins::resolve(&config.workspace_dir, Some(mem.as_ref())).await;
+                        system_prompt = session_prompt(&pinned.section);
+                        if let Some(system) = history.first_mut().filter(|m| m.role == "system") {
+                            system.content.clone_from(&system_prompt);
+                        }
+                        println!("{message}");
+                        for warning in &pinned.warnings {
+                            println!("⚠️  {warning}");
+                        }
+                        println!();
+                    }
+                    Err(err) => eprintln!("Could not update pins: {err:#}\n"),
+                }
+                continue;
+            }
+            if let Some(command) = crate::instructions::parse_command(&user_input) {
+                let outcome = match command {
+                    crate::instructions::Command::Add(text) => standing
+                        .add(crate::instructions::Scope::Global, &text)
+                        .map(|saved| {
+                            format!("Saved standing instruction #{}: {}", saved.id, saved.text)
+                        }),
+                    crate::instructions::Command::Remove(id) => {
+                        standing.remove(id).map(|removed| match removed {
+                            Some(removed) => {
+                                format!("Removed standing instruction #{id}: {}", removed.text)
+                            }
+                            None => format!("No standing instruction #{id}"),
+                        })
+                    }
+                };
+                match outcome {
+                    Ok(message) => {
+                        system_prompt = session_prompt(&pinned.section);
+                        if let Some(system) = history.first_mut().filter(|m| m.role == "system") {
+                            system.content.clone_from(&system_prompt);
+                        }
+                        println!("{message}\n");
+                    }
+                    Err(err) => eprintln!("Could not update standing instructions: {err:#}\n"),
+                }
+                continue;
+            }
+
+            let user_input = match tools::simulation::strip_simulate_directive(&user_input) {
+                Some(rest) => {
+                    simulation.store(true, Ordering::Relaxed);
+                    rest.to_string()
+                }
+                None => {
+                    simulation.store(session_simulate, Ordering::Relaxed);
+                    user_input
+                }
+            };
+
+            // Auto-save conversation turns (skip short/trivial messages)
+            if config.memory.auto_save && user_input.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
+                let user_key = autosave_memory_key("user_msg");
+                let _ = mem
+                    .store(&user_key, &user_input, MemoryCategory::Conversation, None)
+                    .await;
+            }
+
+            // Inject memory + hardware RAG context (or collection excerpts) into user message
+            let rag_limit = if config.agent.compact_context { 2 } else { 5 };
+            let context = match &collection {
+                Some(collection) => {
+                    collection
+                        .context(mem.as_ref(), &user_input, rag_limit)
+                        .await
+                }
+                None => {
+                    let mem_context =
+                        build_context(mem.as_ref(), &user_input, config.memory.min_relevance_score)
+                            .await;
+                    let doc_context = match &rag {
+                        Some(rag) => {
+                            rag.context(Identity::local(channel_name), &user_input)
+                                .await
+                        }
+                        None => String::new(),
+                    };
+                    let hw_context = hardware_rag
+                        .as_ref()
+                        .map(|r| build_hardware_context(r, &user_input, &board_names, rag_limit))
+                        .unwrap_or_default();
+                    format!("{mem_context}{doc_context}{hw_context}")
+                }
+            };
             let enriched = if context.is_empty() {
                 user_input.clone()
             } else {
                 format!("{context}{user_input}")
             };
 
+            // Pinned files and memories are re-read every turn so edits (and
+            // pins added by the `pin` tool) show up; warn only about new issues.
+            let refreshed = crate::pins::resolve(&config.workspace_dir, Some(mem.as_ref())).await;
+            if refreshed != pinned {
+                for warning in refreshed
+                    .warnings
+                    .iter()
+                    .filter(|w| !pinned.warnings.contains(w))
+                {
+                    eprintln!("⚠️  {warning}");
+                }
+                pinned = refreshed;
+                system_prompt = session_prompt(&pinned.section);
+                if let Some(system) = history.first_mut().filter(|m| m.role == "system") {
+                    system.content.clone_from(&system_prompt);
+                }
+            }
+
             history.push(ChatMessage::user(&enriched));
 
-            let response = match run_tool_call_loop(
-                provider.as_ref(),
-                &mut history,
-                &tools_registry,
-                observer.as_ref(),
-                provider_name,
-                model_name,
-                temperature,
-                false,
-                approval_manager.as_ref(),
-                channel_name,
-                &config.multimodal,
-                config.agent.max_tool_iterations,
-                None,
-                None,
-                None,
-                &[],
+            let cost_before = crate::cost::conversation::totals(&conversation_id);
+            let response = match crate::cost::conversation::scope(
+                conversation_id.clone(),
+                run_tool_call_loop(
+                    provider.as_ref(),
+                    &mut history,
+                    &tools_registry,
+                    observer.as_ref(),
+                    provider_name,
+                    model_name,
+                    temperature,
+                    false,
+                    approval_for_turn(approval_manager.as_ref(), &simulation),
+                    channel_name,
+                    &config.multimodal,
+                    config.agent.max_tool_iterations,
+                    None,
+                    None,
+                    None,
+                    &[],
+                ),
             )
             .await
             {
@@ -3233,6 +3956,11 @@ pub async fn run(
             {
                 eprintln!("\nError sending CLI response: {e}\n");
             }
+            if let Some(footer) =
+                crate::cost::conversation::cli_footer(&conversation_id, cost_before)
+            {
+                println!("{footer}\n");
+            }
             observer.record_event(&ObserverEvent::TurnComplete);
 
             // Auto-compaction before hard trimming to preserve long-context signal.
@@ -3252,6 +3980,10 @@ pub async fn run(
             // Hard cap as a safety net.
             trim_history(&mut history, config.agent.max_history_messages);
         }
+
+        if let Some(target) = park_for {
+            park_cli_session(&config, &target, &history, provider_name, model_name);
+        }
     }
 
     let duration = start.elapsed();
@@ -3266,23 +3998,94 @@ pub async fn run(
     Ok(final_output)
 }
 
+/// Park the interactive session for a channel and tell the user how to
+/// claim it there.
+fn park_cli_session(
+    config: &Config,
+    target: &str,
+    history: &[ChatMessage],
+    provider_name: &str,
+    model_name: &str,
+) {
+    let store = crate::handoff::HandoffStore::for_workspace(&config.workspace_dir);
+    match store.park(
+        crate::handoff::CLI_TARGET,
+        crate::handoff::CLI_TARGET,
+        target,
+        crate::handoff::transferable_turns(history),
+        Some(provider_name.to_string()),
+        Some(model_name.to_string()),
+    ) {
+        Ok(handoff) => println!(
+            "📤 Conversation saved for {}. Send `/continue-here {}` there within {} minutes to pick it up.",
+            handoff.target,
+            handoff.code,
+            crate::handoff::HANDOFF_TTL_MINUTES
+        ),
+        Err(e) => eprintln!("Handoff failed: {e}"),
+    }
+}
+
+/// Simulated turns never execute tools, so they skip interactive approval.
+fn approval_for_turn<'a>(
+    approval_manager: Option<&'a ApprovalManager>,
+    simulation: &AtomicBool,
+) -> Option<&'a ApprovalManager> {
+    if simulation.load(Ordering::Relaxed) {
+        None
+    } else {
+        approval_manager
+    }
+}
+
 /// Process a single message through the full agent (with tools, peripherals, memory).
 /// Used by channels (Telegram, Discord, etc.) to enable hardware and tool use.
 pub async fn process_message(config: Config, message: &str) -> Result<String> {
     let observer: Arc<dyn Observer> =
         Arc::from(observability::create_observer(&config.observability));
+    Box::pin(process_message_observed(config, message, observer, None)).await
+}
+
+/// Like [`process_message`], but reuses the board connections in
+/// `peripherals` instead of opening new ones.
+pub async fn process_message_with_peripherals(
+    config: Config,
+    message: &str,
+    peripherals: &crate::peripherals::PeripheralHandles,
+) -> Result<String> {
+    let observer: Arc<dyn Observer> =
+        Arc::from(observability::create_observer(&config.observability));
+    Box::pin(process_message_observed(
+        config,
+        message,
+        observer,
+        Some(peripherals),
+    ))
+    .await
+}
+
+/// Like [`process_message`], but reports agent events to `observer`.
+/// Boards are connected for this message unless shared `peripherals` are
+/// given.
+pub async fn process_message_observed(
+    config: Config,
+    message: &str,
+    observer: Arc<dyn Observer>,
+    peripherals: Option<&crate::peripherals::PeripheralHandles>,
+) -> Result<String> {
+    crate::config::ensure_runtime_globals(&config);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
         &config.autonomy,
         &config.workspace_dir,
     ));
-    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
+    let mem = memory::scoped::for_current_task(Arc::from(memory::create_memory_with_storage(
         &config.memory,
         Some(&config.storage.provider.config),
         &config.workspace_dir,
         config.api_key.as_deref(),
-    )?);
+    )?));
 
     let (composio_key, composio_entity_id) = if config.composio.enabled {
         (
@@ -3306,9 +4109,36 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         config.api_key.as_deref(),
         &config,
     );
-    let peripheral_tools: Vec<Box<dyn Tool>> =
-        crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
+    let peripheral_tools: Vec<Box<dyn Tool>> = match peripherals {
+        Some(handles) => handles
+            .tools(&config.peripherals, &config.workspace_dir)
+            .await?
+            .into_iter()
+            .map(tools::boxed_shared_tool)
+            .collect(),
+        None => {
+            crate::peripherals::create_peripheral_tools(&config.peripherals, &config.workspace_dir)
+                .await?
+        }
+    };
     tools_registry.extend(peripheral_tools);
+    let collection = config
+        .agent
+        .collection
+        .as_deref()
+        .map(|name| crate::presets::knowledge::Collection::load(&config.workspace_dir, name))
+        .transpose()?;
+    if collection.is_some() {
+        tools_registry.clear();
+    }
+    let rag = crate::rag::collections::RagCollections::from_config(&config)?;
+
+    let (message, simulate) = match tools::simulation::strip_simulate_directive(message) {
+        Some(rest) => (rest, true),
+        None => (message, false),
+    };
+    let tools_registry =
+        tools::simulation::wrap_tools(tools_registry, &Arc::new(AtomicBool::new(simulate)));
 
     let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
     let model_name = config
@@ -3321,6 +4151,8 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         secrets_encrypt: config.secrets.encrypt,
         provider_api_url: config.api_url.clone(),
         reasoning_enabled: config.runtime.reasoning_enabled,
+        custom_providers: config.custom_providers.clone(),
+        provider_plugins: config.provider_plugins.clone(),
     };
     let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
         provider_name,
@@ -3331,6 +4163,14 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         &model_name,
         &provider_runtime_options,
     )?;
+    let provider = providers::shadow::wrap_with_shadow(
+        provider,
+        provider_name,
+        &config,
+        &provider_runtime_options,
+    );
+    let provider =
+        providers::budget::wrap_with_budget(provider, provider_name, config.agent.max_run_cost_usd);
 
     let hardware_rag: Option<crate::rag::HardwareRag> = config
         .peripherals
@@ -3395,12 +4235,17 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
             "Query connected hardware for reported GPIO pins and LED pin. Use when user asks what pins are available.",
         ));
     }
+    if collection.is_some() {
+        tool_descs.clear();
+    }
     let bootstrap_max_chars = if config.agent.compact_context {
         Some(6000)
     } else {
         None
     };
-    let native_tools = provider.supports_native_tools();
+    let native_tools =
+        providers::capabilities::negotiate(provider.as_ref(), provider_name, &model_name)
+            .native_tools;
     let mut system_prompt = crate::channels::build_system_prompt_with_mode(
         &config.workspace_dir,
         &model_name,
@@ -3412,16 +4257,45 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         config.skills.prompt_injection_mode,
     );
     if !native_tools {
-        system_prompt.push_str(&build_tool_instructions(&tools_registry));
+        system_prompt.push_str(&build_tool_instructions(
+            &tools_registry,
+            &config.workspace_dir,
+        ));
     }
+    if let Some(collection) = &collection {
+        system_prompt.push_str(&collection.instructions());
+    }
+    let skill_names: Vec<String> = skills.iter().map(|skill| skill.name.clone()).collect();
+    system_prompt.push_str(
+        &crate::instructions::InstructionStore::from_config(&config)
+            .prompt_section("daemon", &skill_names),
+    );
+    system_prompt.push_str(
+        &Box::pin(crate::pins::resolve(
+            &config.workspace_dir,
+            Some(mem.as_ref()),
+        ))
+        .await
+        .section,
+    );
 
-    let mem_context = build_context(mem.as_ref(), message, config.memory.min_relevance_score).await;
     let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-    let hw_context = hardware_rag
-        .as_ref()
-        .map(|r| build_hardware_context(r, message, &board_names, rag_limit))
-        .unwrap_or_default();
-    let context = format!("{mem_context}{hw_context}");
+    let context = match &collection {
+        Some(collection) => collection.context(mem.as_ref(), message, rag_limit).await,
+        None => {
+            let mem_context =
+                build_context(mem.as_ref(), message, config.memory.min_relevance_score).await;
+            let doc_context = match &rag {
+                Some(rag) => rag.context(Identity::local("gateway"), message).await,
+                None => String::new(),
+            };
+            let hw_context = hardware_rag
+                .as_ref()
+                .map(|r| build_hardware_context(r, message, &board_names, rag_limit))
+                .unwrap_or_default();
+            format!("{mem_context}{doc_context}{hw_context}")
+        }
+    };
     let enriched = if context.is_empty() {
         message.to_string()
     } else {
@@ -3458,6 +4332,22 @@ mod tests {
     use std::sync::{Arc, Mutex};
     use std::time::Duration;
 
+    #[test]
+    fn running_tools_status_names_single_and_parallel_calls() {
+        let shell = ParsedToolCall {
+            name: "shell".into(),
+            arguments: serde_json::json!({"command": "cargo test"}),
+            tool_call_id: None,
+        };
+        let browser = ParsedToolCall {
+            name: "browser".into(),
+            arguments: serde_json::json!({}),
+            tool_call_id: None,
+        };
+        assert!(running_tools_status(std::slice::from_ref(&shell)).contains("shell: cargo test"));
+        assert!(running_tools_status(&[shell, browser]).contains("shell, browser"));
+    }
+
     #[test]
     fn test_scrub_credentials() {
         let input = "API_KEY=sk-1234567890abcdef; token: 1234567890; password=\"secret123456\"";
@@ -3487,6 +4377,13 @@ mod tests {
         assert_eq!(args.get("approved"), Some(&serde_json::Value::Bool(true)));
     }
 
+    #[test]
+    fn approval_argument_is_stripped_unless_declared() {
+        let mut args = serde_json::json!({"command": "ls", "approved": true});
+        strip_undeclared_approval_argument(None, &mut args);
+        assert!(args.get("approved").is_none());
+    }
+
     #[test]
     fn security_restriction_output_includes_remediation_plan() {
         let raw = "Command requires explicit approval (approved=true): medium-risk operation";
@@ -3585,6 +4482,11 @@ mod tests {
         }
     }
 
+    /// The policy hook denies every call until it is initialized.
+    fn init_policy_engine() {
+        crate::security::policy_engine::init_from_config(&crate::config::Config::default());
+    }
+
     struct ScriptedProvider {
         responses: Arc<Mutex<VecDeque<ChatResponse>>>,
         capabilities: ProviderCapabilities,
@@ -3801,6 +4703,131 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 0);
     }
 
+    /// Messages of each request and whether tool specs were attached.
+    type RecordedRequests = Arc<Mutex<Vec<(Vec<ChatMessage>, bool)>>>;
+
+    struct RecordingProvider {
+        requests: RecordedRequests,
+    }
+
+    #[async_trait]
+    impl Provider for RecordingProvider {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: true,
+                vision: true,
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("chat_with_system should not be used in recording provider tests");
+        }
+
+        async fn chat(
+            &self,
+            request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push((request.messages.to_vec(), request.tools.is_some()));
+            Ok(ChatResponse {
+                text: Some("done".to_string()),
+                tool_calls: Vec::new(),
+                usage: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_adapts_to_catalog_model_capabilities() {
+        providers::capabilities::register(
+            "catalog-provider",
+            std::collections::BTreeMap::from([(
+                "text-only".to_string(),
+                providers::capabilities::ModelCapabilities {
+                    vision: Some(false),
+                    tools: Some(false),
+                    max_context_tokens: Some(1_000),
+                    ..Default::default()
+                },
+            )]),
+        );
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let provider = RecordingProvider {
+            requests: Arc::clone(&requests),
+        };
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(CountingTool::new(
+            "count_tool",
+            Arc::new(AtomicUsize::new(0)),
+        ))];
+        let mut history = vec![
+            ChatMessage::system("You are helpful."),
+            ChatMessage::user("what is this [IMAGE:data:image/png;base64,iVBORw0KGgo=]"),
+        ];
+
+        let reply = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &NoopObserver,
+            "catalog-provider",
+            "text-only",
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            3,
+            None,
+            None,
+            None,
+            &[],
+        )
+        .await
+        .unwrap();
+        assert_eq!(reply, "done");
+
+        let (messages, sent_tools) = requests.lock().unwrap()[0].clone();
+        assert!(!sent_tools, "tool specs must not be sent natively");
+        assert!(messages[0].content.contains("## Tool Use Protocol"));
+        assert!(messages[0].content.contains("count_tool"));
+        assert!(!crate::multimodal::contains_image_markers(&messages));
+        assert!(messages[1].content.contains("image(s) omitted"));
+
+        history.push(ChatMessage::user("x".repeat(8_000)));
+        let err = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &NoopObserver,
+            "catalog-provider",
+            "text-only",
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            3,
+            None,
+            None,
+            None,
+            &[],
+        )
+        .await
+        .expect_err("history beyond the context window must fail early");
+        assert!(err.to_string().contains("capability=context"));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_rejects_oversized_image_payload() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -3948,6 +4975,7 @@ mod tests {
 
     #[tokio::test]
     async fn run_tool_call_loop_executes_multiple_tools_with_ordered_results() {
+        init_policy_engine();
         let provider = ScriptedProvider::from_text_responses(vec![
             r#"<tool_call>
 {"name":"delay_a","arguments":{"value":"A"}}
@@ -4034,6 +5062,7 @@ mod tests {
 
     #[tokio::test]
     async fn run_tool_call_loop_deduplicates_repeated_tool_calls() {
+        init_policy_engine();
         let provider = ScriptedProvider::from_text_responses(vec![
             r#"<tool_call>
 {"name":"count_tool","arguments":{"value":"A"}}
@@ -4092,8 +5121,267 @@ mod tests {
         assert!(tool_results.content.contains("Skipped duplicate tool call"));
     }
 
+    #[tokio::test]
+    async fn run_tool_call_loop_coerces_or_rejects_invalid_tool_calls() {
+        init_policy_engine();
+        let provider = ScriptedProvider::from_text_responses(vec![
+            r#"<tool_call>
+{"name":"count_tool","arguments":{"value":5}}
+</tool_call>
+<tool_call>
+{"name":"missing_tool","arguments":{}}
+</tool_call>"#,
+            "done",
+        ]);
+
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(CountingTool::new(
+            "count_tool",
+            Arc::clone(&invocations),
+        ))];
+
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("run tool calls"),
+        ];
+        let observer = NoopObserver;
+
+        let result = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &observer,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            None,
+            None,
+            &[],
+        )
+        .await
+        .expect("loop should finish after rejecting the invalid call");
+
+        assert_eq!(result, "done");
+        assert_eq!(invocations.load(Ordering::SeqCst), 1);
+        let tool_results = history
+            .iter()
+            .find(|msg| msg.role == "user" && msg.content.starts_with("[Tool results]"))
+            .expect("prompt-mode tool result payload should be present");
+        assert!(tool_results.content.contains("counted:5"));
+        assert!(tool_results
+            .content
+            .contains("Invalid call to `missing_tool`: unknown tool `missing_tool`"));
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_fails_after_repeated_invalid_rounds() {
+        let invalid = r#"<tool_call>
+{"name":"missing_tool","arguments":{}}
+</tool_call>"#;
+        let provider = ScriptedProvider::from_text_responses(vec![invalid; 4]);
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(CountingTool::new(
+            "count_tool",
+            Arc::new(AtomicUsize::new(0)),
+        ))];
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("run tool calls"),
+        ];
+
+        let err = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &NoopObserver,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            10,
+            None,
+            None,
+            None,
+            &[],
+        )
+        .await
+        .expect_err("loop should stop once the model keeps emitting invalid calls");
+
+        let message = err.to_string();
+        assert!(message.contains("kept emitting invalid tool calls (3 rounds in a row"));
+        assert!(message.contains("unknown tool `missing_tool`"));
+    }
+
+    /// Records the `approved` flag each call arrives with.
+    struct ApprovalProbeTool {
+        name: String,
+        approvals: Arc<Mutex<Vec<bool>>>,
+    }
+
+    #[async_trait]
+    impl Tool for ApprovalProbeTool {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn description(&self) -> &str {
+            "Records approvals for auto-mode tests"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "approved": { "type": "boolean" }
+                }
+            })
+        }
+
+        async fn execute(
+            &self,
+            args: serde_json::Value,
+        ) -> anyhow::Result<crate::tools::ToolResult> {
+            let approved = args
+                .get("approved")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false);
+            self.approvals.lock().unwrap().push(approved);
+            Ok(crate::tools::ToolResult {
+                success: true,
+                output: "ok".into(),
+                error: None,
+            })
+        }
+    }
+
+    /// Run one scripted turn for `session` with an open `/auto` window and
+    /// return the auto-mode announcements it produced.
+    async fn run_in_auto_window(
+        session: &str,
+        script: &str,
+        tools_registry: &[Box<dyn Tool>],
+        approval: Option<&ApprovalManager>,
+    ) -> Vec<String> {
+        init_policy_engine();
+        let mut config = crate::config::Config::default();
+        config.autonomy.auto_mode.enabled = true;
+        crate::approval::autonomous::init_from_config(&config);
+        crate::approval::autonomous::handle_command(
+            session,
+            crate::approval::autonomous::AutoCommand::Start(chrono::Duration::minutes(5)),
+        );
+
+        let provider = ScriptedProvider::from_text_responses(vec![script, "done"]);
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("run tool calls"),
+        ];
+        let multimodal = crate::config::MultimodalConfig::default();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let turn = run_tool_call_loop(
+            &provider,
+            &mut history,
+            tools_registry,
+            &NoopObserver,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            approval,
+            "telegram",
+            &multimodal,
+            4,
+            None,
+            None,
+            None,
+            &[],
+        );
+        let result = crate::security::four_eyes::with_requester(
+            session.to_string(),
+            crate::approval::autonomous::with_announcer(tx, turn),
+        )
+        .await;
+        assert_eq!(result.expect("turn should complete"), "done");
+        crate::approval::autonomous::handle_command(
+            session,
+            crate::approval::autonomous::AutoCommand::Stop,
+        );
+
+        let mut announcements = Vec::new();
+        while let Ok(text) = rx.try_recv() {
+            announcements.push(text);
+        }
+        announcements
+    }
+
+    #[tokio::test]
+    async fn auto_mode_never_approves_builtin_always_ask_tools() {
+        let approvals = Arc::new(Mutex::new(Vec::new()));
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(ApprovalProbeTool {
+            name: "email_send".into(),
+            approvals: Arc::clone(&approvals),
+        })];
+
+        let announcements = Box::pin(run_in_auto_window(
+            "test:auto-always-ask",
+            r#"<tool_call>
+{"name":"email_send","arguments":{}}
+</tool_call>"#,
+            &tools_registry,
+            None,
+        ))
+        .await;
+
+        assert_eq!(*approvals.lock().unwrap(), vec![false]);
+        assert!(announcements.is_empty(), "{announcements:?}");
+    }
+
+    #[tokio::test]
+    async fn auto_mode_spends_budget_only_on_gated_calls() {
+        let approvals = Arc::new(Mutex::new(Vec::new()));
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let tools_registry: Vec<Box<dyn Tool>> = vec![
+            Box::new(ApprovalProbeTool {
+                name: "probe_tool".into(),
+                approvals: Arc::clone(&approvals),
+            }),
+            Box::new(CountingTool::new("count_tool", Arc::clone(&invocations))),
+        ];
+        let approval_mgr = ApprovalManager::from_config(&crate::config::AutonomyConfig {
+            auto_approve: vec!["count_tool".into()],
+            ..crate::config::AutonomyConfig::default()
+        });
+
+        let announcements = Box::pin(run_in_auto_window(
+            "test:auto-budget",
+            r#"<tool_call>
+{"name":"probe_tool","arguments":{}}
+</tool_call>
+<tool_call>
+{"name":"count_tool","arguments":{"value":"A"}}
+</tool_call>"#,
+            &tools_registry,
+            Some(&approval_mgr),
+        ))
+        .await;
+
+        assert_eq!(*approvals.lock().unwrap(), vec![true]);
+        assert_eq!(invocations.load(Ordering::SeqCst), 1);
+        assert_eq!(announcements.len(), 1, "{announcements:?}");
+        assert!(announcements[0].contains("probe_tool"));
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_native_mode_preserves_fallback_tool_call_ids() {
+        init_policy_engine();
         let provider = ScriptedProvider::from_text_responses(vec![
             r#"{"content":"Need to call tool","tool_calls":[{"id":"call_abc","name":"count_tool","arguments":"{\"value\":\"X\"}"}]}"#,
             "done",
@@ -4567,7 +5855,7 @@ Tail"#;
             std::path::Path::new("/tmp"),
         ));
         let tools = tools::default_tools(security);
-        let instructions = build_tool_instructions(&tools);
+        let instructions = build_tool_instructions(&tools, std::path::Path::new("/tmp"));
 
         assert!(instructions.contains("## Tool Use Protocol"));
         assert!(instructions.contains("<tool_call>"));
@@ -45,7 +45,7 @@ impl MemoryLoader for DefaultMemoryLoader {
 
         let mut context = String::from("[Memory context]\n");
         for entry in entries {
-            if memory::is_assistant_autosave_key(&entry.key) {
+            if memory::is_assistant_autosave_key(&entry.key) || memory::is_kv_key(&entry.key) {
                 continue;
             }
             if let Some(score) = entry.score {
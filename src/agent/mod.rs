@@ -1,10 +1,14 @@
 #[allow(clippy::module_inception)]
 pub mod agent;
+pub mod checkpoint;
 pub mod classifier;
 pub mod dispatcher;
 pub mod loop_;
 pub mod memory_loader;
 pub mod prompt;
+pub mod tool_schemas;
+pub mod tool_stream;
+pub mod tool_validation;
 
 #[cfg(test)]
 mod tests;
@@ -12,4 +16,4 @@ mod tests;
 #[allow(unused_imports)]
 pub use agent::{Agent, AgentBuilder};
 #[allow(unused_imports)]
-pub use loop_::{process_message, run};
+pub use loop_::{process_message, process_message_observed, process_message_with_peripherals, run};
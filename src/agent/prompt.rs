@@ -3,7 +3,7 @@ use crate::identity;
 use crate::skills::Skill;
 use crate::tools::Tool;
 use anyhow::Result;
-use chrono::Local;
+use chrono::Utc;
 use std::fmt::Write;
 use std::path::Path;
 
@@ -107,6 +107,9 @@ impl PromptSection for IdentitySection {
             "BOOTSTRAP.md",
             "MEMORY.md",
         ] {
+            if file == "MEMORY.md" && !crate::memory::scoped::shared_memory_visible() {
+                continue;
+            }
             inject_workspace_file(&mut prompt, ctx.workspace_dir, file);
         }
 
@@ -143,8 +146,8 @@ impl PromptSection for SafetySection {
         "safety"
     }
 
-    fn build(&self, _ctx: &PromptContext<'_>) -> Result<String> {
-        Ok("## Safety\n\n- Do not exfiltrate private data.\n- Do not run destructive commands without asking.\n- Do not bypass oversight or approval mechanisms.\n- Prefer `trash` over `rm`.\n- When in doubt, ask before acting externally.".into())
+    fn build(&self, ctx: &PromptContext<'_>) -> Result<String> {
+        Ok(crate::prompts::load(ctx.workspace_dir, "safety").into_owned())
     }
 }
 
@@ -197,12 +200,7 @@ impl PromptSection for DateTimeSection {
     }
 
     fn build(&self, _ctx: &PromptContext<'_>) -> Result<String> {
-        let now = Local::now();
-        Ok(format!(
-            "## Current Date & Time\n\n{} ({})",
-            now.format("%Y-%m-%d %H:%M:%S"),
-            now.format("%Z")
-        ))
+        Ok(crate::datetime::user_clock().prompt_section(Utc::now()))
     }
 }
 
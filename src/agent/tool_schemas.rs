@@ -0,0 +1,295 @@
+//! Per-turn tool schema minification and selection (`[tool_schemas]`).
+//!
+//! Native tool calling sends every tool's JSON schema with each request, which
+//! dominates the prompt of daemons with dozens of tools enabled. Before a turn
+//! the schemas are minified (schema metadata stripped, descriptions shortened)
+//! and, above `select_above` tools, narrowed to the tools relevant to the
+//! recent conversation. Providers that cache tool definitions keep the full
+//! list: a request whose tool set changes every turn would miss the cache.
+
+use crate::config::{Config, ToolSchemasConfig};
+use crate::providers::ChatMessage;
+use crate::tools::ToolSpec;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::{LazyLock, RwLock};
+
+/// Schema keywords that only document and never constrain.
+const METADATA_KEYS: &[&str] = &["$schema", "$comment", "title", "examples", "example"];
+
+/// User turns considered when scoring relevance.
+const RECENT_USER_TURNS: usize = 3;
+
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "that", "this", "from", "into", "what", "when", "where", "which",
+    "can", "you", "your", "please", "about", "have", "has", "are", "was", "were", "will", "would",
+    "should", "could", "there", "then", "than", "them", "they", "its", "not", "but", "all", "any",
+    "use", "using", "get", "set", "let", "make",
+];
+
+static TOOL_SCHEMAS: LazyLock<RwLock<ToolSchemasConfig>> =
+    LazyLock::new(|| RwLock::new(ToolSchemasConfig::default()));
+
+/// Apply `[tool_schemas]` from config.
+pub fn init_from_config(config: &Config) {
+    let mut guard = TOOL_SCHEMAS.write().unwrap_or_else(|e| e.into_inner());
+    *guard = config.tool_schemas.clone();
+}
+
+fn settings() -> ToolSchemasConfig {
+    TOOL_SCHEMAS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Rough token estimate of the schemas as sent (chars / 4).
+pub fn estimate_tokens(specs: &[ToolSpec]) -> u64 {
+    let chars: usize = specs
+        .iter()
+        .map(|spec| {
+            spec.name.len()
+                + spec.description.chars().count()
+                + spec.parameters.to_string().chars().count()
+        })
+        .sum();
+    (chars / 4) as u64
+}
+
+/// Collapse whitespace, keep the first paragraph and cut at a sentence (or
+/// word) boundary so the result fits in `max` characters (`0` = no limit).
+pub fn shorten(text: &str, max: usize) -> String {
+    let paragraph = text.trim().split("\n\n").next().unwrap_or_default();
+    let text = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+    if max == 0 || text.chars().count() <= max {
+        return text;
+    }
+    let cut: String = text.chars().take(max).collect();
+    if let Some(end) = cut.rfind(". ").filter(|end| *end >= cut.len() / 2) {
+        return cut[..=end].to_string();
+    }
+    let cut = cut.trim_end_matches(|c: char| !c.is_whitespace());
+    let cut = if cut.trim().is_empty() {
+        text.chars().take(max.saturating_sub(1)).collect::<String>()
+    } else {
+        cut.trim_end().to_string()
+    };
+    format!("{cut}…")
+}
+
+fn minify_schema(value: &mut Value, max_description: usize) {
+    match value {
+        Value::Object(map) => {
+            for key in METADATA_KEYS {
+                map.remove(*key);
+            }
+            if let Some(Value::String(description)) = map.get_mut("description") {
+                *description = shorten(description, max_description);
+            }
+            for (key, child) in map.iter_mut() {
+                // `properties` maps names to schemas; a property called
+                // "description" or "title" is not metadata.
+                if key == "properties" {
+                    if let Value::Object(properties) = child {
+                        for schema in properties.values_mut() {
+                            minify_schema(schema, max_description);
+                        }
+                    }
+                } else if key != "description" {
+                    minify_schema(child, max_description);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                minify_schema(item, max_description);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Minified copy of `spec`.
+pub fn minify(spec: &ToolSpec, config: &ToolSchemasConfig) -> ToolSpec {
+    let mut parameters = spec.parameters.clone();
+    minify_schema(&mut parameters, config.max_parameter_description_chars);
+    ToolSpec {
+        name: spec.name.clone(),
+        description: shorten(&spec.description, config.max_description_chars),
+        parameters,
+    }
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() >= 3)
+        .map(str::to_lowercase)
+        .filter(|word| !STOPWORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Whether the conversation already called `name` (native JSON or XML
+/// tool-call history both carry the quoted tool name).
+fn used_in(history: &[ChatMessage], name: &str) -> bool {
+    let quoted = format!("\"{name}\"");
+    history
+        .iter()
+        .any(|m| m.role != "user" && m.role != "system" && m.content.contains(&quoted))
+}
+
+/// Pick the tools relevant to the recent conversation: `always_include`,
+/// tools already used, then up to `max_tools` by keyword overlap with the
+/// latest user turns. Registry order is preserved.
+pub fn select(
+    specs: &[ToolSpec],
+    history: &[ChatMessage],
+    config: &ToolSchemasConfig,
+) -> Vec<ToolSpec> {
+    if config.select_above == 0 || specs.len() <= config.select_above {
+        return specs.to_vec();
+    }
+    let query: HashSet<String> = history
+        .iter()
+        .rev()
+        .filter(|m| m.role == "user")
+        .take(RECENT_USER_TURNS)
+        .flat_map(|m| words(&m.content))
+        .collect();
+
+    let mut keep = vec![false; specs.len()];
+    let mut scored = Vec::new();
+    for (i, spec) in specs.iter().enumerate() {
+        if config.always_include.iter().any(|name| name == &spec.name)
+            || used_in(history, &spec.name)
+        {
+            keep[i] = true;
+            continue;
+        }
+        let name_words = words(&spec.name.replace('_', " "));
+        let description_words = words(&spec.description);
+        let score = query
+            .iter()
+            .map(|word| {
+                if name_words.contains(word) {
+                    3
+                } else {
+                    usize::from(description_words.contains(word))
+                }
+            })
+            .sum::<usize>();
+        if score > 0 {
+            scored.push((score, i));
+        }
+    }
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for (_, i) in scored.into_iter().take(config.max_tools) {
+        keep[i] = true;
+    }
+    specs
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(spec, _)| spec.clone())
+        .collect()
+}
+
+/// Tool specs to send this turn under the configured `[tool_schemas]`.
+/// `cached` is true when the provider caches tool definitions.
+pub fn prepare(specs: &[ToolSpec], history: &[ChatMessage], cached: bool) -> Vec<ToolSpec> {
+    let config = settings();
+    let selected = if cached {
+        specs.to_vec()
+    } else {
+        select(specs, history, &config)
+    };
+    if config.minify {
+        selected.iter().map(|spec| minify(spec, &config)).collect()
+    } else {
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(name: &str, description: &str) -> ToolSpec {
+        ToolSpec {
+            name: name.into(),
+            description: description.into(),
+            parameters: json!({"type": "object", "properties": {}}),
+        }
+    }
+
+    #[test]
+    fn minify_strips_metadata_and_shortens_descriptions() {
+        let config = ToolSchemasConfig {
+            max_description_chars: 40,
+            max_parameter_description_chars: 20,
+            ..ToolSchemasConfig::default()
+        };
+        let original = ToolSpec {
+            name: "calendar".into(),
+            description:
+                "Manage calendar events. Supports listing, creating and deleting.\n\nExamples: ..."
+                    .into(),
+            parameters: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "title": "CalendarArgs",
+                "type": "object",
+                "properties": {
+                    "title": {"type": "string", "description": "Event title shown in the calendar UI", "examples": ["Standup"]},
+                    "description": {"type": "string"}
+                },
+                "required": ["title"]
+            }),
+        };
+        let minified = minify(&original, &config);
+        assert_eq!(minified.description, "Manage calendar events.");
+        assert_eq!(
+            minified.parameters,
+            json!({
+                "type": "object",
+                "properties": {
+                    "title": {"type": "string", "description": "Event title shown…"},
+                    "description": {"type": "string"}
+                },
+                "required": ["title"]
+            })
+        );
+        assert!(estimate_tokens(&[minified]) < estimate_tokens(&[original]));
+        assert_eq!(shorten("short  text", 0), "short text");
+    }
+
+    #[test]
+    fn select_keeps_relevant_used_and_pinned_tools() {
+        let mut specs = vec![
+            spec("shell", "Run a shell command"),
+            spec("calendar", "Manage calendar events"),
+            spec("smart_home_switch", "Turn smart home devices on or off"),
+            spec("email_send", "Send an email"),
+        ];
+        specs.extend((0..30).map(|i| spec(&format!("extra_{i}"), "Unrelated capability")));
+        let config = ToolSchemasConfig {
+            always_include: vec!["shell".into()],
+            ..ToolSchemasConfig::default()
+        };
+        let history = vec![
+            ChatMessage::system("You are helpful."),
+            ChatMessage::assistant(r#"{"tool_calls":[{"name":"email_send","arguments":"{}"}]}"#),
+            ChatMessage::user("Turn off the living room lights, then check my calendar"),
+        ];
+        let names: Vec<String> = select(&specs, &history, &config)
+            .into_iter()
+            .map(|spec| spec.name)
+            .collect();
+        assert_eq!(
+            names,
+            ["shell", "calendar", "smart_home_switch", "email_send"]
+        );
+
+        // Below the threshold every tool is sent.
+        assert_eq!(select(&specs[..4], &history, &config).len(), 4);
+    }
+}
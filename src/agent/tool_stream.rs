@@ -0,0 +1,437 @@
+//! Streaming native tool calls (`[agent] stream_tool_calls`).
+//!
+//! Instead of waiting for the full completion, tool-call arguments are parsed
+//! as they stream in: the draft shows which tool is being prepared as soon as
+//! its name arrives, and top-level arguments are checked against the tool's
+//! schema while the rest of a long input (file contents, large JSON) is still
+//! being generated. The assembled response is identical to a non-streamed
+//! `Provider::chat` result, so the tool loop is otherwise unchanged.
+
+use super::loop_::DRAFT_STATUS_PREFIX;
+use crate::config::Config;
+use crate::providers::traits::{ChatRequest, ChatResponse, StreamChunk, TokenUsage, ToolCall};
+use crate::providers::Provider;
+use crate::tools::ToolSpec;
+use anyhow::Result;
+use futures_util::StreamExt;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static STREAM_TOOL_CALLS: AtomicBool = AtomicBool::new(false);
+
+/// Apply `[agent] stream_tool_calls` from config.
+pub fn init_from_config(config: &Config) {
+    STREAM_TOOL_CALLS.store(config.agent.stream_tool_calls, Ordering::Relaxed);
+}
+
+/// Whether a turn with a live draft should stream: enabled, native tools in
+/// use, and the provider can stream tool calls.
+pub(crate) fn should_stream(provider: &dyn Provider, native_tools: bool) -> bool {
+    STREAM_TOOL_CALLS.load(Ordering::Relaxed) && native_tools && provider.supports_tool_streaming()
+}
+
+/// What the scanner learned from one argument fragment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ScanEvent {
+    NotObject,
+    Key(String),
+    Closed,
+}
+
+/// Incremental scanner for a JSON object that reports each top-level key
+/// once its `:` arrives, without buffering or re-parsing the whole value.
+#[derive(Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
+struct ArgumentScanner {
+    started: bool,
+    done: bool,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    expecting_key: bool,
+    key: Option<String>,
+    pending_key: Option<String>,
+}
+
+impl ArgumentScanner {
+    fn feed(&mut self, fragment: &str) -> Vec<ScanEvent> {
+        let mut events = Vec::new();
+        for c in fragment.chars() {
+            if self.done {
+                break;
+            }
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if c == '\\' {
+                    self.escaped = true;
+                    continue;
+                } else if c == '"' {
+                    self.in_string = false;
+                    self.pending_key = self.key.take();
+                    continue;
+                }
+                if let Some(key) = &mut self.key {
+                    key.push(c);
+                }
+                continue;
+            }
+            if !self.started {
+                if c.is_whitespace() {
+                    continue;
+                }
+                if c == '{' {
+                    self.started = true;
+                    self.depth = 1;
+                    self.expecting_key = true;
+                } else {
+                    self.done = true;
+                    events.push(ScanEvent::NotObject);
+                }
+                continue;
+            }
+            match c {
+                '"' => {
+                    self.in_string = true;
+                    if self.depth == 1 && self.expecting_key {
+                        self.expecting_key = false;
+                        self.key = Some(String::new());
+                    }
+                }
+                ':' if self.depth == 1 => {
+                    if let Some(key) = self.pending_key.take() {
+                        events.push(ScanEvent::Key(key));
+                    }
+                }
+                ',' if self.depth == 1 => {
+                    self.pending_key = None;
+                    self.expecting_key = true;
+                }
+                '{' | '[' => self.depth += 1,
+                '}' | ']' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    if self.depth == 0 {
+                        self.done = true;
+                        events.push(ScanEvent::Closed);
+                    }
+                }
+                _ => {}
+            }
+        }
+        events
+    }
+}
+
+/// Progress worth surfacing while a response streams.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ToolStreamEvent {
+    /// The model started a call to this tool.
+    Preparing { tool: String },
+    /// The call will fail validation; reported before the response ends.
+    Issue { tool: String, reason: String },
+}
+
+#[derive(Debug, Default)]
+struct PartialCall {
+    id: String,
+    name: String,
+    arguments: String,
+    scanner: ArgumentScanner,
+    announced: bool,
+    keys: Vec<String>,
+}
+
+/// Assembles streamed chunks into a [`ChatResponse`], validating tool-call
+/// arguments against the offered schemas as they arrive.
+pub(crate) struct ToolCallAssembler<'a> {
+    specs: &'a [ToolSpec],
+    text: String,
+    calls: BTreeMap<usize, PartialCall>,
+    usage: Option<TokenUsage>,
+}
+
+impl<'a> ToolCallAssembler<'a> {
+    pub(crate) fn new(specs: &'a [ToolSpec]) -> Self {
+        Self {
+            specs,
+            text: String::new(),
+            calls: BTreeMap::new(),
+            usage: None,
+        }
+    }
+
+    pub(crate) fn push(&mut self, chunk: StreamChunk) -> Vec<ToolStreamEvent> {
+        self.text.push_str(&chunk.delta);
+        if chunk.usage.is_some() {
+            self.usage = chunk.usage;
+        }
+        let Some(delta) = chunk.tool_call else {
+            return Vec::new();
+        };
+
+        let call = self.calls.entry(delta.index).or_default();
+        if let Some(id) = delta.id.filter(|id| !id.is_empty()) {
+            call.id = id;
+        }
+        if let Some(name) = delta.name {
+            call.name.push_str(&name);
+        }
+        let spec = self.specs.iter().find(|spec| spec.name == call.name);
+
+        let mut events = Vec::new();
+        let tool = call_name(delta.index, &call.name);
+        let issue = |reason: String| ToolStreamEvent::Issue {
+            tool: tool.clone(),
+            reason,
+        };
+        if !call.announced && !call.name.is_empty() {
+            call.announced = true;
+            events.push(ToolStreamEvent::Preparing {
+                tool: call.name.clone(),
+            });
+            if spec.is_none() {
+                events.push(issue("is not an available tool".into()));
+            }
+        }
+
+        call.arguments.push_str(&delta.arguments);
+        for scanned in call.scanner.feed(&delta.arguments) {
+            match scanned {
+                ScanEvent::NotObject => {
+                    events.push(issue("arguments are not a JSON object".into()));
+                }
+                ScanEvent::Key(key) => {
+                    if spec.is_some_and(|spec| rejects_argument(&spec.parameters, &key)) {
+                        events.push(issue(format!("unknown argument `{key}`")));
+                    }
+                    call.keys.push(key);
+                }
+                ScanEvent::Closed => {
+                    let missing: Vec<&str> = spec
+                        .map(|spec| required_arguments(&spec.parameters))
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|required| !call.keys.iter().any(|key| key == required))
+                        .collect();
+                    if !missing.is_empty() {
+                        events.push(issue(format!(
+                            "missing required argument(s): {}",
+                            missing.join(", ")
+                        )));
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    pub(crate) fn finish(self) -> ChatResponse {
+        let tool_calls = self
+            .calls
+            .into_values()
+            .filter(|call| !call.name.is_empty())
+            .map(|call| {
+                let arguments = if serde_json::from_str::<Value>(&call.arguments).is_ok() {
+                    call.arguments
+                } else {
+                    if !call.arguments.trim().is_empty() {
+                        tracing::warn!(
+                            tool = call.name,
+                            "Invalid JSON in streamed tool-call arguments, using empty object"
+                        );
+                    }
+                    "{}".into()
+                };
+                ToolCall {
+                    id: if call.id.is_empty() {
+                        uuid::Uuid::new_v4().to_string()
+                    } else {
+                        call.id
+                    },
+                    name: call.name,
+                    arguments,
+                }
+            })
+            .collect();
+        ChatResponse {
+            text: (!self.text.is_empty()).then_some(self.text),
+            tool_calls,
+            usage: self.usage,
+        }
+    }
+}
+
+fn call_name(index: usize, name: &str) -> String {
+    if name.is_empty() {
+        format!("tool call #{}", index + 1)
+    } else {
+        name.to_string()
+    }
+}
+
+/// A schema that closes its properties rejects keys it does not list.
+pub(super) fn rejects_argument(schema: &Value, key: &str) -> bool {
+    schema.get("additionalProperties") == Some(&Value::Bool(false))
+        && schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .is_some_and(|properties| !properties.contains_key(key))
+}
+
+pub(super) fn required_arguments(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Stream one completion, relaying tool preparation and early validation
+/// issues to the draft. Falls back to a regular `chat` call when the stream
+/// fails before producing anything. Returns the response and the issues.
+pub(crate) async fn chat(
+    provider: &dyn Provider,
+    request: ChatRequest<'_>,
+    model: &str,
+    temperature: f64,
+    draft: &tokio::sync::mpsc::Sender<String>,
+) -> Result<(ChatResponse, Vec<String>)> {
+    let specs = request.tools.unwrap_or_default();
+    let mut stream = provider.stream_chat(request, model, temperature);
+    let mut assembler = ToolCallAssembler::new(specs);
+    let mut issues = Vec::new();
+    let mut received = false;
+
+    while let Some(item) = stream.next().await {
+        let chunk = match item {
+            Ok(chunk) => chunk,
+            Err(err) if !received => {
+                tracing::debug!("Tool-call streaming unavailable, falling back: {err}");
+                let response = provider.chat(request, model, temperature).await?;
+                return Ok((response, issues));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        received = true;
+        for event in assembler.push(chunk) {
+            let status = match event {
+                ToolStreamEvent::Preparing { tool } => {
+                    crate::i18n::tr_args("channel.preparing_tool", &[("tool", &tool)])
+                }
+                ToolStreamEvent::Issue { tool, reason } => {
+                    let issue = format!("{tool}: {reason}");
+                    issues.push(issue.clone());
+                    crate::i18n::tr_args("channel.tool_stream_issue", &[("issue", &issue)])
+                }
+            };
+            let _ = draft.send(format!("{DRAFT_STATUS_PREFIX}{status}")).await;
+        }
+    }
+    Ok((assembler.finish(), issues))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::traits::ToolCallDelta;
+    use serde_json::json;
+
+    fn spec() -> ToolSpec {
+        ToolSpec {
+            name: "file_write".into(),
+            description: "Write a file".into(),
+            parameters: json!({
+                "type": "object",
+                "properties": {"path": {"type": "string"}, "content": {"type": "string"}},
+                "required": ["path", "content"],
+                "additionalProperties": false
+            }),
+        }
+    }
+
+    fn args(index: usize, fragment: &str) -> StreamChunk {
+        StreamChunk::tool_call(ToolCallDelta {
+            index,
+            id: None,
+            name: None,
+            arguments: fragment.into(),
+        })
+    }
+
+    #[test]
+    fn scanner_reports_top_level_keys_as_they_complete() {
+        let mut scanner = ArgumentScanner::default();
+        assert!(scanner.feed(r#"{"pa"#).is_empty());
+        assert_eq!(
+            scanner.feed(r#"th": "a:b", "nested": {"x": [1, "}"]}, "#),
+            [
+                ScanEvent::Key("path".into()),
+                ScanEvent::Key("nested".into())
+            ]
+        );
+        assert_eq!(
+            scanner.feed(r#""content": "say \"hi\", ok"}"#),
+            [ScanEvent::Key("content".into()), ScanEvent::Closed]
+        );
+        assert_eq!(
+            ArgumentScanner::default().feed(r#"  ["x"]"#),
+            [ScanEvent::NotObject]
+        );
+    }
+
+    #[test]
+    fn assembler_announces_validates_and_assembles_calls() {
+        let specs = [spec()];
+        let mut assembler = ToolCallAssembler::new(&specs);
+        assert!(assembler.push(StreamChunk::delta("Writing it.")).is_empty());
+
+        let events = assembler.push(StreamChunk::tool_call(ToolCallDelta {
+            index: 0,
+            id: Some("call_1".into()),
+            name: Some("file_write".into()),
+            arguments: r#"{"path": "a.txt", "#.into(),
+        }));
+        assert_eq!(
+            events,
+            [ToolStreamEvent::Preparing {
+                tool: "file_write".into()
+            }]
+        );
+        let events = assembler.push(args(0, r#""mode": "w"}"#));
+        assert_eq!(
+            events,
+            [
+                ToolStreamEvent::Issue {
+                    tool: "file_write".into(),
+                    reason: "unknown argument `mode`".into()
+                },
+                ToolStreamEvent::Issue {
+                    tool: "file_write".into(),
+                    reason: "missing required argument(s): content".into()
+                },
+            ]
+        );
+
+        let events = assembler.push(StreamChunk::tool_call(ToolCallDelta {
+            index: 1,
+            id: None,
+            name: Some("teleport".into()),
+            arguments: String::new(),
+        }));
+        assert!(events.contains(&ToolStreamEvent::Issue {
+            tool: "teleport".into(),
+            reason: "is not an available tool".into()
+        }));
+
+        let response = assembler.finish();
+        assert_eq!(response.text.as_deref(), Some("Writing it."));
+        assert_eq!(response.tool_calls.len(), 2);
+        assert_eq!(response.tool_calls[0].id, "call_1");
+        assert_eq!(
+            response.tool_calls[0].arguments,
+            r#"{"path": "a.txt", "mode": "w"}"#
+        );
+        assert_eq!(response.tool_calls[1].arguments, "{}");
+    }
+}
@@ -0,0 +1,547 @@
+//! Validation and repair of model-emitted tool calls (`[tool_validation]`).
+//!
+//! Weaker models call tools that do not exist, leave out required arguments
+//! or pass the wrong types, and the tool then fails with an error the model
+//! rarely recovers from. Every call is checked against the registered schemas
+//! before it runs. Mistyped scalars (`"5"` for an integer) are coerced in
+//! place; anything else gets a constrained re-prompt that asks the model to
+//! rewrite only the broken call. A call that is still invalid is answered
+//! with a validation error listing each problem, and a turn whose rounds keep
+//! producing nothing but invalid calls fails with an error the user can act on.
+
+use super::tool_stream::{rejects_argument, required_arguments};
+use crate::config::{Config, ToolValidationConfig};
+use crate::providers::Provider;
+use crate::tools::ToolSpec;
+use serde_json::{Map, Value};
+use std::fmt::Write;
+use std::sync::{LazyLock, RwLock};
+
+const REPAIR_SYSTEM_PROMPT: &str = "You repair malformed tool calls. Reply with exactly one JSON object of the form {\"name\": \"<tool>\", \"arguments\": {...}} and nothing else. Keep the caller's intent; change only what the listed problems require.";
+
+static TOOL_VALIDATION: LazyLock<RwLock<ToolValidationConfig>> =
+    LazyLock::new(|| RwLock::new(ToolValidationConfig::default()));
+
+/// Apply `[tool_validation]` from config.
+pub fn init_from_config(config: &Config) {
+    let mut guard = TOOL_VALIDATION.write().unwrap_or_else(|e| e.into_inner());
+    *guard = config.tool_validation.clone();
+}
+
+pub(crate) fn settings() -> ToolValidationConfig {
+    TOOL_VALIDATION
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Result of checking one tool call.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Checked {
+    /// Valid as emitted.
+    Valid,
+    /// Valid after `how` (`"coerced"` or `"reprompted"`) changed it.
+    Repaired {
+        name: String,
+        arguments: Value,
+        how: &'static str,
+    },
+    /// Still invalid; the problems found.
+    Invalid(Vec<String>),
+}
+
+/// Problems with calling `name` with `arguments`; empty when the call is valid.
+pub(crate) fn validate(specs: &[ToolSpec], name: &str, arguments: &Value) -> Vec<String> {
+    let Some(spec) = specs.iter().find(|spec| spec.name == name) else {
+        return vec![format!("unknown tool `{name}`")];
+    };
+    let empty = Value::Object(Map::new());
+    let arguments = if arguments.is_null() {
+        &empty
+    } else {
+        arguments
+    };
+    let Some(object) = arguments.as_object() else {
+        return vec![format!(
+            "arguments must be a JSON object, got {}",
+            type_name(arguments)
+        )];
+    };
+
+    let schema = &spec.parameters;
+    let mut issues = Vec::new();
+    for required in required_arguments(schema) {
+        if !object.contains_key(required) {
+            issues.push(format!("missing required argument `{required}`"));
+        }
+    }
+    for (key, value) in object {
+        if rejects_argument(schema, key) {
+            issues.push(format!("unexpected argument `{key}`"));
+        } else if let Some(property) = schema.get("properties").and_then(|p| p.get(key)) {
+            check_value(key, value, property, &mut issues);
+        }
+    }
+    issues
+}
+
+fn check_value(path: &str, value: &Value, schema: &Value, issues: &mut Vec<String>) {
+    let types = schema_types(schema);
+    if !types.is_empty() && !types.iter().any(|ty| type_matches(value, ty)) {
+        issues.push(format!(
+            "argument `{path}` should be {}, got {}",
+            types.join(" or "),
+            type_name(value)
+        ));
+        return;
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            issues.push(format!(
+                "argument `{path}` must be one of {}, got {value}",
+                allowed.join(", ")
+            ));
+        }
+        return;
+    }
+    match value {
+        Value::Object(object) => {
+            for required in required_arguments(schema) {
+                if !object.contains_key(required) {
+                    issues.push(format!("missing required argument `{path}.{required}`"));
+                }
+            }
+            for (key, nested) in object {
+                let nested_path = format!("{path}.{key}");
+                if rejects_argument(schema, key) {
+                    issues.push(format!("unexpected argument `{nested_path}`"));
+                } else if let Some(property) = schema.get("properties").and_then(|p| p.get(key)) {
+                    check_value(&nested_path, nested, property, issues);
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    check_value(&format!("{path}[{index}]"), item, item_schema, issues);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn schema_types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn type_matches(value: &Value, ty: &str) -> bool {
+    match ty {
+        "string" => value.is_string(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Fix mistakes that need no model: arguments sent as a JSON-encoded string
+/// and scalars of the wrong type (`"5"`, `"true"`, `5` for a string).
+/// Returns the coerced arguments when anything changed.
+pub(crate) fn coerce(spec: &ToolSpec, arguments: &Value) -> Option<Value> {
+    let mut coerced = match arguments {
+        Value::String(raw) => match serde_json::from_str::<Value>(raw) {
+            Ok(parsed @ Value::Object(_)) => parsed,
+            _ => return None,
+        },
+        other => other.clone(),
+    };
+    let reencoded = !arguments.is_object() && coerced.is_object();
+    let changed = coerce_properties(&mut coerced, &spec.parameters);
+    (reencoded || changed).then_some(coerced)
+}
+
+fn coerce_properties(value: &mut Value, schema: &Value) -> bool {
+    let (Some(object), Some(properties)) = (
+        value.as_object_mut(),
+        schema.get("properties").and_then(Value::as_object),
+    ) else {
+        return false;
+    };
+    let mut changed = false;
+    for (key, nested) in object.iter_mut() {
+        let Some(property) = properties.get(key) else {
+            continue;
+        };
+        let types = schema_types(property);
+        if types.is_empty() || types.iter().any(|ty| type_matches(nested, ty)) {
+            changed |= coerce_properties(nested, property);
+            continue;
+        }
+        if let Some(replacement) = types.iter().find_map(|ty| coerce_scalar(nested, ty)) {
+            *nested = replacement;
+            changed = true;
+        }
+    }
+    changed
+}
+
+fn coerce_scalar(value: &Value, ty: &str) -> Option<Value> {
+    match (value, ty) {
+        (Value::String(raw), "integer") => raw.trim().parse::<i64>().ok().map(Value::from),
+        (Value::String(raw), "number") => raw
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .map(Value::from),
+        (Value::String(raw), "boolean") => match raw.trim().to_ascii_lowercase().as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        (Value::Number(n), "string") => Some(Value::String(n.to_string())),
+        (Value::Bool(b), "string") => Some(Value::String(b.to_string())),
+        _ => None,
+    }
+}
+
+/// Ask the model to rewrite one call. Returns the new name and arguments,
+/// or `None` when the reply is not a call.
+async fn reprompt(
+    provider: &dyn Provider,
+    model: &str,
+    temperature: f64,
+    specs: &[ToolSpec],
+    name: &str,
+    arguments: &Value,
+    issues: &[String],
+) -> Option<(String, Value)> {
+    let mut message = format!(
+        "This tool call was rejected:\n{}\n\nProblems:\n",
+        serde_json::json!({ "name": name, "arguments": arguments })
+    );
+    for issue in issues {
+        let _ = writeln!(message, "- {issue}");
+    }
+    match specs.iter().find(|spec| spec.name == name) {
+        Some(spec) => {
+            let _ = write!(
+                message,
+                "\nParameters schema of `{name}`:\n{}",
+                spec.parameters
+            );
+        }
+        None => {
+            let names: Vec<&str> = specs.iter().map(|spec| spec.name.as_str()).collect();
+            let _ = write!(message, "\nAvailable tools: {}", names.join(", "));
+        }
+    }
+
+    let reply = match provider
+        .chat_with_system(Some(REPAIR_SYSTEM_PROMPT), &message, model, temperature)
+        .await
+    {
+        Ok(reply) => reply,
+        Err(e) => {
+            tracing::debug!(tool = name, "Tool call repair prompt failed: {e}");
+            return None;
+        }
+    };
+    parse_repaired_call(&reply)
+}
+
+fn parse_repaired_call(reply: &str) -> Option<(String, Value)> {
+    let start = reply.find('{')?;
+    let end = reply.rfind('}')?;
+    let parsed: Value = serde_json::from_str(reply.get(start..=end)?).ok()?;
+    let name = parsed.get("name")?.as_str()?.trim().to_string();
+    let arguments = parsed
+        .get("arguments")
+        .cloned()
+        .unwrap_or_else(|| Value::Object(Map::new()));
+    (!name.is_empty()).then_some((name, arguments))
+}
+
+/// Validate one call, coercing and then re-prompting up to `repair_attempts`
+/// times when it is invalid.
+pub(crate) async fn check(
+    provider: &dyn Provider,
+    model: &str,
+    temperature: f64,
+    specs: &[ToolSpec],
+    name: &str,
+    arguments: &Value,
+    repair_attempts: u32,
+) -> Checked {
+    let mut issues = validate(specs, name, arguments);
+    if issues.is_empty() {
+        return Checked::Valid;
+    }
+
+    if let Some(coerced) = specs
+        .iter()
+        .find(|spec| spec.name == name)
+        .and_then(|spec| coerce(spec, arguments))
+    {
+        let remaining = validate(specs, name, &coerced);
+        if remaining.is_empty() {
+            return Checked::Repaired {
+                name: name.to_string(),
+                arguments: coerced,
+                how: "coerced",
+            };
+        }
+        issues = remaining;
+    }
+
+    let mut current = (name.to_string(), arguments.clone());
+    for _ in 0..repair_attempts {
+        let Some(candidate) = reprompt(
+            provider,
+            model,
+            temperature,
+            specs,
+            &current.0,
+            &current.1,
+            &issues,
+        )
+        .await
+        else {
+            break;
+        };
+        let remaining = validate(specs, &candidate.0, &candidate.1);
+        if remaining.is_empty() {
+            return Checked::Repaired {
+                name: candidate.0,
+                arguments: candidate.1,
+                how: "reprompted",
+            };
+        }
+        current = candidate;
+        issues = remaining;
+    }
+    Checked::Invalid(issues)
+}
+
+/// Tool result returned to the model for a call that stayed invalid.
+pub(crate) fn invalid_message(name: &str, issues: &[String]) -> String {
+    format!(
+        "Invalid call to `{name}`: {}. Fix the call to match the tool's schema and try again.",
+        issues.join("; ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn specs() -> Vec<ToolSpec> {
+        vec![ToolSpec {
+            name: "file_read".into(),
+            description: "Read a file".into(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string" },
+                    "limit": { "type": "integer" },
+                    "mode": { "type": "string", "enum": ["text", "bytes"] },
+                    "options": {
+                        "type": "object",
+                        "properties": { "follow": { "type": "boolean" } },
+                        "additionalProperties": false
+                    }
+                },
+                "required": ["path"],
+                "additionalProperties": false
+            }),
+        }]
+    }
+
+    struct RepairProvider {
+        replies: Mutex<Vec<String>>,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    impl RepairProvider {
+        fn new(replies: &[&str]) -> Self {
+            Self {
+                replies: Mutex::new(replies.iter().rev().map(|r| (*r).to_string()).collect()),
+                prompts: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for RepairProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.prompts.lock().unwrap().push(message.to_string());
+            self.replies
+                .lock()
+                .unwrap()
+                .pop()
+                .ok_or_else(|| anyhow::anyhow!("no scripted reply"))
+        }
+    }
+
+    #[test]
+    fn validate_reports_unknown_tools_missing_extra_and_mistyped_arguments() {
+        let specs = specs();
+        assert!(validate(&specs, "file_read", &json!({ "path": "a.txt" })).is_empty());
+        assert_eq!(
+            validate(&specs, "file_reed", &json!({})),
+            vec!["unknown tool `file_reed`"]
+        );
+        assert_eq!(
+            validate(&specs, "file_read", &json!("a.txt")),
+            vec!["arguments must be a JSON object, got string"]
+        );
+
+        let issues = validate(
+            &specs,
+            "file_read",
+            &json!({
+                "limit": "ten",
+                "mode": "lines",
+                "encoding": "utf8",
+                "options": { "follow": "yes", "depth": 2 }
+            }),
+        );
+        assert!(issues.contains(&"missing required argument `path`".to_string()));
+        assert!(issues.contains(&"argument `limit` should be integer, got string".to_string()));
+        assert!(issues.contains(
+            &"argument `mode` must be one of \"text\", \"bytes\", got \"lines\"".to_string()
+        ));
+        assert!(issues.contains(&"unexpected argument `encoding`".to_string()));
+        assert!(
+            issues.contains(&"argument `options.follow` should be boolean, got string".to_string())
+        );
+        assert!(issues.contains(&"unexpected argument `options.depth`".to_string()));
+    }
+
+    #[test]
+    fn open_schemas_accept_undeclared_arguments() {
+        let specs = vec![ToolSpec {
+            name: "shell".into(),
+            description: "Run a command".into(),
+            parameters: json!({
+                "type": "object",
+                "properties": { "command": { "type": "string" } },
+                "required": ["command"]
+            }),
+        }];
+        assert!(validate(
+            &specs,
+            "shell",
+            &json!({ "command": "ls", "approved": true })
+        )
+        .is_empty());
+        assert!(validate(&specs, "shell", &Value::Null)
+            .contains(&"missing required argument `command`".to_string()));
+    }
+
+    #[tokio::test]
+    async fn check_coerces_scalars_without_asking_the_model() {
+        let provider = RepairProvider::new(&[]);
+        let checked = check(
+            &provider,
+            "m",
+            0.0,
+            &specs(),
+            "file_read",
+            &json!("{\"path\": 7, \"limit\": \"20\", \"options\": {\"follow\": \"TRUE\"}}"),
+            1,
+        )
+        .await;
+        assert_eq!(
+            checked,
+            Checked::Repaired {
+                name: "file_read".into(),
+                arguments: json!({ "path": "7", "limit": 20, "options": { "follow": true } }),
+                how: "coerced",
+            }
+        );
+        assert!(provider.prompts.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn check_reprompts_for_unknown_tool_and_revalidates() {
+        let provider = RepairProvider::new(&[
+            "```json\n{\"name\": \"file_read\", \"arguments\": {}}\n```",
+            "{\"name\": \"file_read\", \"arguments\": {\"path\": \"notes.md\"}}",
+        ]);
+        let checked = check(
+            &provider,
+            "m",
+            0.0,
+            &specs(),
+            "read_file",
+            &json!({ "file": "notes.md" }),
+            2,
+        )
+        .await;
+        assert_eq!(
+            checked,
+            Checked::Repaired {
+                name: "file_read".into(),
+                arguments: json!({ "path": "notes.md" }),
+                how: "reprompted",
+            }
+        );
+        let prompts = provider.prompts.lock().unwrap();
+        assert!(prompts[0].contains("Available tools: file_read"));
+        assert!(prompts[1].contains("missing required argument `path`"));
+        assert!(prompts[1].contains("Parameters schema of `file_read`"));
+    }
+
+    #[tokio::test]
+    async fn check_reports_issues_when_repair_fails() {
+        let provider = RepairProvider::new(&["I cannot help with that."]);
+        let checked = check(&provider, "m", 0.0, &specs(), "file_read", &json!({}), 1).await;
+        let Checked::Invalid(issues) = checked else {
+            panic!("expected invalid, got {checked:?}");
+        };
+        assert_eq!(issues, vec!["missing required argument `path`"]);
+        assert_eq!(
+            invalid_message("file_read", &issues),
+            "Invalid call to `file_read`: missing required argument `path`. Fix the call to match the tool's schema and try again."
+        );
+
+        let disabled = RepairProvider::new(&[]);
+        assert!(matches!(
+            check(&disabled, "m", 0.0, &specs(), "nope", &json!({}), 0).await,
+            Checked::Invalid(_)
+        ));
+        assert!(disabled.prompts.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,289 @@
+//! Session-scoped autonomous mode (`/auto 20m`, `[autonomy.auto_mode]`).
+//!
+//! When enabled, a session (a channel owner, or the interactive CLI) can let
+//! the agent run guarded tool calls without approving each step, for a
+//! bounded time and number of calls. Only the approval step is skipped:
+//! security policy, policy-engine rules, four-eyes sign-off and `always_ask`
+//! tools behave as usual. Every call that skipped approval is announced in
+//! the session, and once the timer or the call budget runs out the session is
+//! back on the normal approval flow.
+
+use super::summarize_args;
+use crate::config::{AutoModeConfig, Config};
+use crate::i18n;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Largest number accepted in a `/auto` duration; longer requests are
+/// clamped to `max_minutes` anyway.
+const MAX_DURATION_COUNT: i64 = 100_000;
+
+tokio::task_local! {
+    static ANNOUNCER: UnboundedSender<String>;
+}
+
+static CONFIG: LazyLock<RwLock<AutoModeConfig>> =
+    LazyLock::new(|| RwLock::new(AutoModeConfig::default()));
+
+/// Open windows by session identity (`channel:sender`, or `cli`).
+static WINDOWS: LazyLock<Mutex<HashMap<String, Window>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Apply `[autonomy.auto_mode]` from config.
+pub fn init_from_config(config: &Config) {
+    *CONFIG.write() = config.autonomy.auto_mode.clone();
+}
+
+/// A `/auto` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutoCommand {
+    Start(Duration),
+    Stop,
+    Status,
+    Usage,
+}
+
+/// Parse `/auto`, `/auto off` and `/auto <duration>` (`20m`, `1h`, or plain
+/// minutes).
+pub fn parse_command(text: &str) -> Option<AutoCommand> {
+    let mut words = text.split_whitespace();
+    if !words.next()?.eq_ignore_ascii_case("/auto") {
+        return None;
+    }
+    let command = match (words.next(), words.next()) {
+        (None, _) => AutoCommand::Status,
+        (Some(arg), None) if matches!(arg.to_ascii_lowercase().as_str(), "off" | "stop") => {
+            AutoCommand::Stop
+        }
+        (Some(arg), None) => parse_duration(arg).map_or(AutoCommand::Usage, AutoCommand::Start),
+        _ => AutoCommand::Usage,
+    };
+    Some(command)
+}
+
+fn parse_duration(raw: &str) -> Option<Duration> {
+    let raw = raw.to_ascii_lowercase();
+    let (count, minutes_per_unit) = if let Some(count) = raw.strip_suffix('h') {
+        (count, 60)
+    } else {
+        (raw.strip_suffix('m').unwrap_or(&raw), 1)
+    };
+    let count: i64 = count
+        .parse()
+        .ok()
+        .filter(|count| (1..=MAX_DURATION_COUNT).contains(count))?;
+    Some(Duration::minutes(count * minutes_per_unit))
+}
+
+#[derive(Debug, Clone)]
+struct Window {
+    until: DateTime<Utc>,
+    actions_left: u32,
+    used: u32,
+}
+
+/// Run a `/auto` command for `session` and return the reply.
+pub fn handle_command(session: &str, command: AutoCommand) -> String {
+    let config = CONFIG.read().clone();
+    let now = Utc::now();
+    let mut windows = WINDOWS.lock();
+    if windows
+        .get(session)
+        .is_some_and(|window| now >= window.until)
+    {
+        windows.remove(session);
+    }
+    match command {
+        AutoCommand::Usage => i18n::tr("channel.auto_usage"),
+        AutoCommand::Start(_) if !config.enabled => i18n::tr("channel.auto_disabled"),
+        AutoCommand::Start(requested) => {
+            let duration = requested.min(Duration::minutes(i64::from(config.max_minutes)));
+            if duration <= Duration::zero() || config.max_actions == 0 {
+                return i18n::tr("channel.auto_disabled");
+            }
+            windows.insert(
+                session.to_string(),
+                Window {
+                    until: now + duration,
+                    actions_left: config.max_actions,
+                    used: 0,
+                },
+            );
+            tracing::info!(
+                session,
+                minutes = duration.num_minutes(),
+                "Autonomous mode started"
+            );
+            i18n::tr_args(
+                "channel.auto_started",
+                &[
+                    ("minutes", &duration.num_minutes().to_string()),
+                    ("actions", &config.max_actions.to_string()),
+                ],
+            )
+        }
+        AutoCommand::Stop => match windows.remove(session) {
+            Some(window) => {
+                tracing::info!(session, used = window.used, "Autonomous mode stopped");
+                i18n::tr_args(
+                    "channel.auto_stopped",
+                    &[("used", &window.used.to_string())],
+                )
+            }
+            None => i18n::tr("channel.auto_inactive"),
+        },
+        AutoCommand::Status => match windows.get(session) {
+            Some(window) => i18n::tr_args(
+                "channel.auto_status",
+                &[
+                    ("minutes", &minutes_left(window, now).to_string()),
+                    ("actions", &window.actions_left.to_string()),
+                ],
+            ),
+            None => i18n::tr("channel.auto_inactive"),
+        },
+    }
+}
+
+fn minutes_left(window: &Window, now: DateTime<Utc>) -> i64 {
+    // Round up so a window with seconds left does not read as 0 minutes.
+    (window.until - now + Duration::seconds(59)).num_minutes()
+}
+
+/// Whether `session` has an open window.
+pub fn is_active(session: &str) -> bool {
+    WINDOWS
+        .lock()
+        .get(session)
+        .is_some_and(|window| Utc::now() < window.until)
+}
+
+/// Spend one call of `session`'s window on `tool`.
+///
+/// Returns `true` when the call may skip approval. The call is announced, and
+/// so is the end of the window when the timer or the budget ran out.
+pub fn take_action(session: &str, tool: &str, args: &serde_json::Value) -> bool {
+    let mut windows = WINDOWS.lock();
+    let Some(window) = windows.get_mut(session) else {
+        return false;
+    };
+    if Utc::now() >= window.until {
+        windows.remove(session);
+        drop(windows);
+        tracing::info!(session, "Autonomous mode expired");
+        announce(i18n::tr("channel.auto_ended_timer"));
+        return false;
+    }
+    window.actions_left -= 1;
+    window.used += 1;
+    let (remaining, used) = (window.actions_left, window.used);
+    if remaining == 0 {
+        windows.remove(session);
+    }
+    drop(windows);
+
+    announce(i18n::tr_args(
+        "channel.auto_action",
+        &[
+            ("tool", tool),
+            ("summary", &summarize_args(args)),
+            ("remaining", &remaining.to_string()),
+        ],
+    ));
+    if remaining == 0 {
+        tracing::info!(session, "Autonomous mode used up its call budget");
+        announce(i18n::tr_args(
+            "channel.auto_ended_budget",
+            &[("actions", &used.to_string())],
+        ));
+    }
+    true
+}
+
+/// Run `future` with announcements sent to `sink` instead of stderr.
+pub async fn with_announcer<F: std::future::Future>(
+    sink: UnboundedSender<String>,
+    future: F,
+) -> F::Output {
+    ANNOUNCER.scope(sink, future).await
+}
+
+fn announce(text: String) {
+    let undelivered = ANNOUNCER
+        .try_with(|sink| sink.send(text.clone()).is_err())
+        .unwrap_or(true);
+    if undelivered {
+        eprintln!("{text}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enable() {
+        CONFIG.write().enabled = true;
+    }
+
+    #[test]
+    fn parse_command_reads_durations_and_subcommands() {
+        assert_eq!(
+            parse_command("/auto 20m"),
+            Some(AutoCommand::Start(Duration::minutes(20)))
+        );
+        assert_eq!(
+            parse_command("/auto 2H"),
+            Some(AutoCommand::Start(Duration::minutes(120)))
+        );
+        assert_eq!(
+            parse_command("/auto 15"),
+            Some(AutoCommand::Start(Duration::minutes(15)))
+        );
+        assert_eq!(parse_command("/auto"), Some(AutoCommand::Status));
+        assert_eq!(parse_command("/auto off"), Some(AutoCommand::Stop));
+        assert_eq!(parse_command("/auto soon"), Some(AutoCommand::Usage));
+        assert_eq!(parse_command("/auto 0m"), Some(AutoCommand::Usage));
+        assert_eq!(parse_command("/autopilot"), None);
+        assert_eq!(parse_command("auto 20m"), None);
+    }
+
+    #[test]
+    fn window_spends_budget_then_reverts() {
+        let session = "test:window-budget";
+        enable();
+        let max_actions = CONFIG.read().max_actions;
+        assert!(!take_action(session, "shell", &serde_json::json!({})));
+
+        handle_command(session, AutoCommand::Start(Duration::minutes(600)));
+        let until = WINDOWS.lock().get(session).unwrap().until;
+        assert!(until <= Utc::now() + Duration::minutes(i64::from(CONFIG.read().max_minutes)));
+
+        for _ in 0..max_actions {
+            assert!(take_action(
+                session,
+                "shell",
+                &serde_json::json!({"command": "ls"})
+            ));
+        }
+        assert!(!is_active(session));
+        assert!(!take_action(session, "shell", &serde_json::json!({})));
+    }
+
+    #[test]
+    fn expired_and_stopped_windows_do_not_grant() {
+        let session = "test:window-expired";
+        enable();
+        handle_command(session, AutoCommand::Start(Duration::minutes(5)));
+        assert!(is_active(session));
+        WINDOWS.lock().get_mut(session).unwrap().until = Utc::now() - Duration::seconds(1);
+        assert!(!take_action(session, "shell", &serde_json::json!({})));
+        assert!(!WINDOWS.lock().contains_key(session));
+
+        handle_command(session, AutoCommand::Start(Duration::minutes(5)));
+        handle_command(session, AutoCommand::Stop);
+        assert!(!take_action(session, "shell", &serde_json::json!({})));
+    }
+}
@@ -0,0 +1,228 @@
+//! Minimal line-based unified diff rendering for approval previews and audit
+//! records of file changes.
+
+use std::fmt::Write;
+
+/// Lines of unchanged context shown around each hunk.
+const CONTEXT_LINES: usize = 3;
+
+/// Upper bound on the LCS table size; larger changes degrade to a
+/// delete-all/insert-all diff of the changed region instead of a minimal one.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Render a unified diff between `old` and `new` for `path`.
+///
+/// An empty `old` is treated as file creation (`--- /dev/null`). Returns an
+/// empty string when the contents are identical.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let old_header = if old.is_empty() {
+        "/dev/null".to_string()
+    } else {
+        format!("a/{path}")
+    };
+    let mut out = format!("--- {old_header}\n+++ b/{path}\n");
+
+    // Line numbers (0-based) in old/new before each op.
+    let mut old_pos = Vec::with_capacity(ops.len() + 1);
+    let mut new_pos = Vec::with_capacity(ops.len() + 1);
+    let (mut o, mut n) = (0usize, 0usize);
+    for op in &ops {
+        old_pos.push(o);
+        new_pos.push(n);
+        match op {
+            Op::Equal(_) => {
+                o += 1;
+                n += 1;
+            }
+            Op::Delete(_) => o += 1,
+            Op::Insert(_) => n += 1,
+        }
+    }
+    old_pos.push(o);
+    new_pos.push(n);
+
+    let is_change = |idx: usize| !matches!(ops[idx], Op::Equal(_));
+    let mut cursor = 0;
+    while let Some(first_change) = (cursor..ops.len()).find(|&idx| is_change(idx)) {
+        let mut last_change = first_change;
+        while let Some(next) = (last_change + 1..ops.len()).find(|&idx| is_change(idx)) {
+            if next - last_change - 1 > CONTEXT_LINES * 2 {
+                break;
+            }
+            last_change = next;
+        }
+
+        let start = first_change.saturating_sub(CONTEXT_LINES);
+        let end = (last_change + CONTEXT_LINES + 1).min(ops.len());
+        let old_count = old_pos[end] - old_pos[start];
+        let new_count = new_pos[end] - new_pos[start];
+        let old_start = if old_count == 0 {
+            old_pos[start]
+        } else {
+            old_pos[start] + 1
+        };
+        let new_start = if new_count == 0 {
+            new_pos[start]
+        } else {
+            new_pos[start] + 1
+        };
+        let _ = writeln!(
+            out,
+            "@@ -{old_start},{old_count} +{new_start},{new_count} @@"
+        );
+        for op in &ops[start..end] {
+            let (prefix, line) = match op {
+                Op::Equal(line) => (' ', line),
+                Op::Delete(line) => ('-', line),
+                Op::Insert(line) => ('+', line),
+            };
+            out.push(prefix);
+            out.push_str(line);
+            out.push('\n');
+        }
+        cursor = end;
+    }
+
+    out
+}
+
+/// Truncate a rendered diff to at most `max_lines`, noting how many were cut.
+pub fn truncate_diff(diff: &str, max_lines: usize) -> String {
+    let total = diff.lines().count();
+    if total <= max_lines {
+        return diff.to_string();
+    }
+    let mut out = String::new();
+    for line in diff.lines().take(max_lines) {
+        out.push_str(line);
+        out.push('\n');
+    }
+    let _ = writeln!(out, "… ({} more diff lines)", total - max_lines);
+    out
+}
+
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<Op<'a>> = old[..prefix].iter().map(|line| Op::Equal(line)).collect();
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_LCS_CELLS {
+        ops.extend(old_mid.iter().map(|line| Op::Delete(line)));
+        ops.extend(new_mid.iter().map(|line| Op::Insert(line)));
+    } else {
+        ops.extend(lcs_ops(old_mid, new_mid));
+    }
+    ops.extend(old[old.len() - suffix..].iter().map(|line| Op::Equal(line)));
+    ops
+}
+
+fn lcs_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    let width = new.len() + 1;
+    // table[i * width + j] = LCS length of old[i..] and new[j..]
+    let mut table = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            table[i * width + j] = if old[i] == new[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            ops.push(Op::Equal(old[i]));
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            ops.push(Op::Delete(old[i]));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|line| Op::Delete(line)));
+    ops.extend(new[j..].iter().map(|line| Op::Insert(line)));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_contents_produce_empty_diff() {
+        assert!(unified_diff("a.txt", "same\n", "same\n").is_empty());
+    }
+
+    #[test]
+    fn new_file_diffs_against_dev_null() {
+        let diff = unified_diff("notes.md", "", "hello\nworld\n");
+        assert_eq!(
+            diff,
+            "--- /dev/null\n+++ b/notes.md\n@@ -0,0 +1,2 @@\n+hello\n+world\n"
+        );
+    }
+
+    #[test]
+    fn single_line_change_includes_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n";
+        let diff = unified_diff("n.txt", old, new);
+        assert_eq!(
+            diff,
+            "--- a/n.txt\n+++ b/n.txt\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n"
+        );
+    }
+
+    #[test]
+    fn distant_changes_split_into_separate_hunks() {
+        let old_lines: Vec<String> = (1..=20).map(|n| n.to_string()).collect();
+        let mut new_lines = old_lines.clone();
+        new_lines[1] = "two".into();
+        new_lines[18] = "nineteen".into();
+        let old = old_lines.join("\n") + "\n";
+        let new = new_lines.join("\n") + "\n";
+        let diff = unified_diff("n.txt", &old, &new);
+        assert_eq!(diff.matches("@@ -").count(), 2);
+        assert!(diff.contains("-19\n+nineteen\n"));
+    }
+
+    #[test]
+    fn truncate_diff_notes_omitted_lines() {
+        let diff = "a\nb\nc\nd\n";
+        assert_eq!(truncate_diff(diff, 10), diff);
+        assert_eq!(truncate_diff(diff, 2), "a\nb\n… (2 more diff lines)\n");
+    }
+}
@@ -4,6 +4,7 @@
 //! with session-scoped "Always" allowlists and audit logging.
 
 use crate::config::AutonomyConfig;
+use crate::i18n;
 use crate::security::AutonomyLevel;
 use chrono::Utc;
 use parking_lot::Mutex;
@@ -11,6 +12,14 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::io::{self, BufRead, Write};
 
+pub mod autonomous;
+mod diff;
+
+pub use diff::{truncate_diff, unified_diff};
+
+/// Maximum diff lines shown in an approval prompt.
+const MAX_PREVIEW_LINES: usize = 60;
+
 // ── Types ────────────────────────────────────────────────────────
 
 /// A request to approve a tool call before execution.
@@ -18,6 +27,9 @@ use std::io::{self, BufRead, Write};
 pub struct ApprovalRequest {
     pub tool_name: String,
     pub arguments: serde_json::Value,
+    /// Change preview (unified diff for file writes), when the tool provides one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview: Option<String>,
 }
 
 /// The user's response to an approval request.
@@ -40,10 +52,23 @@ pub struct ApprovalLogEntry {
     pub arguments_summary: String,
     pub decision: ApprovalResponse,
     pub channel: String,
+    /// Diff of the change that was approved or denied, for later review.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
 }
 
 // ── ApprovalManager ──────────────────────────────────────────────
 
+/// Tools that ask below full autonomy even when listed in `auto_approve`:
+/// their effects leave the machine and cannot be taken back.
+const BUILTIN_ALWAYS_ASK: &[&str] = &["email_send", "message_contact"];
+
+/// Whether `tool_name` always asks below full autonomy, with or without an
+/// [`ApprovalManager`] (channels run without one).
+pub fn is_builtin_always_ask(tool_name: &str) -> bool {
+    BUILTIN_ALWAYS_ASK.contains(&tool_name)
+}
+
 /// Manages the interactive approval workflow.
 ///
 /// - Checks config-level `auto_approve` / `always_ask` lists
@@ -69,7 +94,12 @@ impl ApprovalManager {
     pub fn from_config(config: &AutonomyConfig) -> Self {
         Self {
             auto_approve: config.auto_approve.iter().cloned().collect(),
-            always_ask: config.always_ask.iter().cloned().collect(),
+            always_ask: config
+                .always_ask
+                .iter()
+                .cloned()
+                .chain(BUILTIN_ALWAYS_ASK.iter().map(|tool| (*tool).to_string()))
+                .collect(),
             autonomy_level: config.level,
             allow_non_cli_auto_approval: config.allow_non_cli_auto_approval,
             session_allowlist: Mutex::new(HashSet::new()),
@@ -133,11 +163,28 @@ impl ApprovalManager {
             arguments_summary: summary,
             decision,
             channel: channel.to_string(),
+            diff: None,
         };
         let mut log = self.audit_log.lock();
         log.push(entry);
     }
 
+    /// Record a decision for a full request, keeping its change preview in
+    /// the audit log.
+    pub fn record_request_decision(
+        &self,
+        request: &ApprovalRequest,
+        decision: ApprovalResponse,
+        channel: &str,
+    ) {
+        self.record_decision(&request.tool_name, &request.arguments, decision, channel);
+        if let Some(preview) = request.preview.as_ref().filter(|p| !p.is_empty()) {
+            if let Some(entry) = self.audit_log.lock().last_mut() {
+                entry.diff = Some(preview.clone());
+            }
+        }
+    }
+
     /// Get a snapshot of the audit log.
     pub fn audit_log(&self) -> Vec<ApprovalLogEntry> {
         self.audit_log.lock().clone()
@@ -148,6 +195,11 @@ impl ApprovalManager {
         self.session_allowlist.lock().clone()
     }
 
+    /// Whether the tool is listed in `always_ask`.
+    pub fn always_asks(&self, tool_name: &str) -> bool {
+        self.always_ask.contains(tool_name)
+    }
+
     /// Whether non-CLI channels may bypass interactive approval prompts.
     pub fn allow_non_cli_auto_approval(&self) -> bool {
         self.allow_non_cli_auto_approval
@@ -165,9 +217,24 @@ impl ApprovalManager {
 fn prompt_cli_interactive(request: &ApprovalRequest) -> ApprovalResponse {
     let summary = summarize_args(&request.arguments);
     eprintln!();
-    eprintln!("🔧 Agent wants to execute: {}", request.tool_name);
+    eprintln!(
+        "{}",
+        i18n::tr_args("approval.wants_to_execute", &[("tool", &request.tool_name)])
+    );
     eprintln!("   {summary}");
-    eprint!("   [Y]es / [N]o / [A]lways for {}: ", request.tool_name);
+    if let Some(preview) = request.preview.as_deref() {
+        if preview.is_empty() {
+            eprintln!("   {}", i18n::tr("approval.no_content_changes"));
+        } else {
+            for line in truncate_diff(preview, MAX_PREVIEW_LINES).lines() {
+                eprintln!("   {line}");
+            }
+        }
+    }
+    eprint!(
+        "   {} ",
+        i18n::tr_args("approval.choices", &[("tool", &request.tool_name)])
+    );
     let _ = io::stderr().flush();
 
     let stdin = io::stdin();
@@ -257,6 +324,22 @@ mod tests {
         assert!(mgr.needs_approval("shell"));
     }
 
+    #[test]
+    fn email_send_always_prompts_below_full_autonomy() {
+        let mut config = supervised_config();
+        config.auto_approve.push("email_send".into());
+        let mgr = ApprovalManager::from_config(&config);
+        assert!(mgr.needs_approval("email_send"));
+        assert!(!ApprovalManager::from_config(&full_config()).needs_approval("email_send"));
+    }
+
+    #[test]
+    fn message_contact_always_prompts_below_full_autonomy() {
+        let mut config = supervised_config();
+        config.auto_approve.push("message_contact".into());
+        assert!(ApprovalManager::from_config(&config).needs_approval("message_contact"));
+    }
+
     #[test]
     fn unknown_tool_needs_approval_in_supervised() {
         let mgr = ApprovalManager::from_config(&supervised_config());
@@ -439,9 +522,28 @@ mod tests {
         let req = ApprovalRequest {
             tool_name: "shell".into(),
             arguments: serde_json::json!({"command": "echo hi"}),
+            preview: None,
         };
         let json = serde_json::to_string(&req).unwrap();
+        assert!(!json.contains("preview"));
         let parsed: ApprovalRequest = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.tool_name, "shell");
     }
+
+    #[test]
+    fn request_decision_keeps_diff_in_audit_log() {
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        let req = ApprovalRequest {
+            tool_name: "file_write".into(),
+            arguments: serde_json::json!({"path": "out.txt", "content": "new"}),
+            preview: Some(unified_diff("out.txt", "old\n", "new\n")),
+        };
+
+        mgr.record_request_decision(&req, ApprovalResponse::Yes, "cli");
+
+        let log = mgr.audit_log();
+        assert_eq!(log.len(), 1);
+        let diff = log[0].diff.as_deref().unwrap();
+        assert!(diff.contains("-old\n+new\n"));
+    }
 }
@@ -0,0 +1,271 @@
+//! Content-addressed tool output artifacts (`[artifacts]`).
+//!
+//! Command logs, fetched pages and query results can be far larger than the
+//! rest of a runtime trace. While traces are recorded, tool outputs of at
+//! least `min_bytes` are written once to `<workspace>/state/artifacts/<sha256>`
+//! (with a `<sha256>.json` metadata file) and the `tool_call_result` event
+//! keeps only a preview plus the `artifact_id`. Identical outputs share one
+//! artifact. Retrieve them with `zeroclaw artifacts get <id>` or
+//! `GET /api/artifacts/{id}`; `zeroclaw artifacts prune` removes old ones.
+
+use crate::config::{ArtifactsConfig, Config};
+use crate::observability::runtime_trace;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+
+const ARTIFACTS_DIR: &str = "state/artifacts";
+/// Shortest id prefix accepted by [`ArtifactStore::resolve`].
+const MIN_PREFIX_LEN: usize = 8;
+
+struct Settings {
+    config: ArtifactsConfig,
+    dir: Option<PathBuf>,
+}
+
+static SETTINGS: LazyLock<RwLock<Settings>> = LazyLock::new(|| {
+    RwLock::new(Settings {
+        config: ArtifactsConfig::default(),
+        dir: None,
+    })
+});
+
+/// Apply `[artifacts]` from config.
+pub fn init_from_config(config: &Config) {
+    let mut guard = SETTINGS.write().unwrap_or_else(|e| e.into_inner());
+    guard.config = config.artifacts.clone();
+    guard.dir = Some(config.workspace_dir.clone());
+}
+
+/// Metadata stored next to each artifact.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Artifact {
+    /// SHA-256 of the content, hex encoded.
+    pub id: String,
+    /// Tool that produced the output.
+    pub tool: String,
+    pub bytes: usize,
+    pub created_at: DateTime<Utc>,
+    /// Runtime-trace turn the output belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_id: Option<String>,
+}
+
+/// Artifacts of one workspace.
+pub struct ArtifactStore {
+    dir: PathBuf,
+}
+
+impl ArtifactStore {
+    pub fn for_workspace(workspace_dir: &Path) -> Self {
+        Self {
+            dir: workspace_dir.join(ARTIFACTS_DIR),
+        }
+    }
+
+    /// Store `content`, or return the existing artifact with the same hash.
+    pub fn put(&self, tool: &str, turn_id: Option<&str>, content: &str) -> Result<Artifact> {
+        let id = hex::encode(Sha256::digest(content.as_bytes()));
+        if let Some(existing) = self.metadata(&id)? {
+            return Ok(existing);
+        }
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let artifact = Artifact {
+            id: id.clone(),
+            tool: tool.to_string(),
+            bytes: content.len(),
+            created_at: Utc::now(),
+            turn_id: turn_id.map(str::to_string),
+        };
+        let content_path = self.dir.join(&id);
+        fs::write(&content_path, content)
+            .with_context(|| format!("Failed to write {}", content_path.display()))?;
+        // Metadata last: an artifact only counts as stored once it exists.
+        let meta_path = self.dir.join(format!("{id}.json"));
+        fs::write(&meta_path, serde_json::to_vec_pretty(&artifact)?)
+            .with_context(|| format!("Failed to write {}", meta_path.display()))?;
+        Ok(artifact)
+    }
+
+    /// Full id for `id`, which may be a unique prefix of at least 8 characters.
+    pub fn resolve(&self, id: &str) -> Result<Option<String>> {
+        let id = id.trim().to_ascii_lowercase();
+        if id.len() < MIN_PREFIX_LEN || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!("Invalid artifact id '{id}' (expected at least {MIN_PREFIX_LEN} hex characters)");
+        }
+        let mut matches: Vec<String> = self
+            .ids()?
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(&id))
+            .collect();
+        match matches.len() {
+            0 => Ok(None),
+            1 => Ok(matches.pop()),
+            n => bail!("Artifact id '{id}' is ambiguous ({n} matches); use more characters"),
+        }
+    }
+
+    /// Metadata and content for an id or unique id prefix.
+    pub fn get(&self, id: &str) -> Result<Option<(Artifact, String)>> {
+        let Some(id) = self.resolve(id)? else {
+            return Ok(None);
+        };
+        let Some(artifact) = self.metadata(&id)? else {
+            return Ok(None);
+        };
+        let path = self.dir.join(&id);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Some((artifact, content)))
+    }
+
+    /// All artifacts, newest first.
+    pub fn list(&self) -> Result<Vec<Artifact>> {
+        let mut artifacts = Vec::new();
+        for id in self.ids()? {
+            if let Some(artifact) = self.metadata(&id)? {
+                artifacts.push(artifact);
+            }
+        }
+        artifacts.sort_by_key(|artifact| std::cmp::Reverse(artifact.created_at));
+        Ok(artifacts)
+    }
+
+    /// Delete artifacts created more than `max_age` ago. Returns how many.
+    pub fn prune(&self, max_age: Duration) -> Result<usize> {
+        let cutoff = Utc::now() - max_age;
+        let mut removed = 0;
+        for artifact in self.list()? {
+            if artifact.created_at >= cutoff {
+                continue;
+            }
+            let _ = fs::remove_file(self.dir.join(&artifact.id));
+            fs::remove_file(self.dir.join(format!("{}.json", artifact.id)))
+                .with_context(|| format!("Failed to remove artifact {}", artifact.id))?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    fn ids(&self) -> Result<Vec<String>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut ids = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if let Some(id) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .filter(|_| path.extension().is_some_and(|ext| ext == "json"))
+            {
+                ids.push(id.to_string());
+            }
+        }
+        Ok(ids)
+    }
+
+    fn metadata(&self, id: &str) -> Result<Option<Artifact>> {
+        let path = self.dir.join(format!("{id}.json"));
+        if !path.exists() {
+            return Ok(None);
+        }
+        let artifact = serde_json::from_slice(&fs::read(&path)?)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(artifact))
+    }
+}
+
+/// Store a large, already-scrubbed tool output as an artifact when traces
+/// are being recorded. Returns the artifact and the preview to inline in its
+/// place, or `None` when the output should stay inline.
+pub fn offload(tool: &str, turn_id: Option<&str>, output: &str) -> Option<(Artifact, String)> {
+    let (config, workspace_dir) = {
+        let guard = SETTINGS.read().unwrap_or_else(|e| e.into_inner());
+        (guard.config.clone(), guard.dir.clone()?)
+    };
+    if !config.enabled || output.len() < config.min_bytes || !runtime_trace::records_payloads() {
+        return None;
+    }
+    match ArtifactStore::for_workspace(&workspace_dir).put(tool, turn_id, output) {
+        Ok(artifact) => Some((artifact, preview(output, config.preview_chars))),
+        Err(e) => {
+            tracing::warn!("Failed to store {tool} output as an artifact: {e}");
+            None
+        }
+    }
+}
+
+fn preview(output: &str, chars: usize) -> String {
+    match output.char_indices().nth(chars) {
+        Some((end, _)) => format!("{}…", &output[..end]),
+        None => output.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn identical_outputs_share_one_artifact() {
+        let tmp = TempDir::new().unwrap();
+        let store = ArtifactStore::for_workspace(tmp.path());
+        let first = store.put("shell", Some("turn-1"), "build log").unwrap();
+        let second = store.put("shell", Some("turn-2"), "build log").unwrap();
+        let other = store.put("web_fetch", None, "<html>").unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(first.id.len(), 64);
+        assert_eq!(store.list().unwrap().len(), 2);
+        let (artifact, content) = store.get(&first.id[..12]).unwrap().unwrap();
+        assert_eq!(
+            (artifact.tool.as_str(), content.as_str()),
+            ("shell", "build log")
+        );
+        assert_eq!(store.get(&other.id).unwrap().unwrap().1, "<html>");
+    }
+
+    #[test]
+    fn resolve_rejects_short_or_non_hex_ids() {
+        let tmp = TempDir::new().unwrap();
+        let store = ArtifactStore::for_workspace(tmp.path());
+        assert!(store.resolve("abc").is_err());
+        assert!(store.resolve("../../config.toml").is_err());
+        assert_eq!(store.resolve("0123456789abcdef").unwrap(), None);
+    }
+
+    #[test]
+    fn prune_removes_only_old_artifacts() {
+        let tmp = TempDir::new().unwrap();
+        let store = ArtifactStore::for_workspace(tmp.path());
+        let old = store.put("shell", None, "old output").unwrap();
+        let meta_path = tmp
+            .path()
+            .join(ARTIFACTS_DIR)
+            .join(format!("{}.json", old.id));
+        let aged = Artifact {
+            created_at: Utc::now() - Duration::days(40),
+            ..old.clone()
+        };
+        fs::write(&meta_path, serde_json::to_vec(&aged).unwrap()).unwrap();
+        let fresh = store.put("shell", None, "new output").unwrap();
+
+        assert_eq!(store.prune(Duration::days(30)).unwrap(), 1);
+        let ids: Vec<_> = store.list().unwrap().into_iter().map(|a| a.id).collect();
+        assert_eq!(ids, [fresh.id]);
+        assert!(!tmp.path().join(ARTIFACTS_DIR).join(&old.id).exists());
+    }
+
+    #[test]
+    fn preview_truncates_on_char_boundaries() {
+        assert_eq!(preview("short", 10), "short");
+        assert_eq!(preview("héllo wörld", 5), "héllo…");
+    }
+}
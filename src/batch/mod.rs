@@ -0,0 +1,439 @@
+//! Batch mode: fan one prompt template across many inputs.
+//!
+//! `zeroclaw batch run --input files.txt --template "Summarize {{file}}"`
+//! renders the template once per input line, runs each prompt through a
+//! fresh single-turn agent with bounded concurrency and per-item retries, and
+//! writes every output plus a `manifest.json` (status, attempts, tokens and
+//! estimated cost per item, with totals) into the output directory.
+
+use crate::config::Config;
+use crate::observability::traits::ObserverMetric;
+use crate::observability::{MultiObserver, Observer, ObserverEvent};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use futures_util::{stream, StreamExt};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+/// Inputs longer than this are not inlined through `{{content}}`.
+const MAX_CONTENT_BYTES: u64 = 512 * 1024;
+const MAX_SLUG_CHARS: usize = 40;
+
+/// Settings for a batch run.
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    pub template: String,
+    pub concurrency: usize,
+    /// Additional attempts after a failed item.
+    pub retries: u32,
+    /// Delay before the first retry; doubles on each further attempt.
+    pub retry_backoff: Duration,
+    pub out_dir: PathBuf,
+}
+
+/// Token usage and estimated cost of one agent run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ItemUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl ItemUsage {
+    fn add(&mut self, other: Self) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cost_usd += other.cost_usd;
+    }
+}
+
+/// Outcome of one input, as recorded in the manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchItem {
+    pub index: usize,
+    pub input: String,
+    /// `ok` or `failed`.
+    pub status: String,
+    pub attempts: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+    /// Usage summed over every attempt, including failed ones.
+    pub usage: ItemUsage,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchTotals {
+    pub items: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub usage: ItemUsage,
+}
+
+/// The `manifest.json` written at the end of a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchManifest {
+    pub template: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub concurrency: usize,
+    pub items: Vec<BatchItem>,
+    pub totals: BatchTotals,
+}
+
+/// Read inputs, one per line; blank lines and `#` comments are skipped.
+pub fn read_inputs(path: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read batch inputs from {}", path.display()))?;
+    let inputs: Vec<String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+    if inputs.is_empty() {
+        bail!("{} contains no inputs", path.display());
+    }
+    Ok(inputs)
+}
+
+/// Render the template for one input.
+///
+/// `{{file}}` and `{{input}}` expand to the input line, `{{index}}` to its
+/// 1-based position and `{{content}}` to the contents of the file it names.
+pub fn render_prompt(template: &str, index: usize, input: &str) -> Result<String> {
+    let mut prompt = template
+        .replace("{{file}}", input)
+        .replace("{{input}}", input)
+        .replace("{{index}}", &index.to_string());
+    if prompt.contains("{{content}}") {
+        let path = Path::new(input);
+        let size = fs::metadata(path)
+            .with_context(|| format!("{{{{content}}}} needs a readable file: {input}"))?
+            .len();
+        if size > MAX_CONTENT_BYTES {
+            bail!("{input} is too large to inline ({size} bytes)");
+        }
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Failed to read {input}"))?;
+        prompt = prompt.replace("{{content}}", &content);
+    }
+    Ok(prompt)
+}
+
+/// Run `runner` for every input and write outputs plus the manifest.
+pub async fn run_batch<F, Fut>(
+    inputs: Vec<String>,
+    options: &BatchOptions,
+    runner: F,
+) -> Result<BatchManifest>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = (Result<String>, ItemUsage)>,
+{
+    fs::create_dir_all(&options.out_dir)
+        .with_context(|| format!("Failed to create {}", options.out_dir.display()))?;
+    let started_at = Utc::now();
+    let total = inputs.len();
+    let done = AtomicUsize::new(0);
+    let runner = &runner;
+    let done = &done;
+
+    let mut items: Vec<BatchItem> = stream::iter(inputs.into_iter().enumerate())
+        .map(|(i, input)| async move {
+            let item = run_item(i + 1, input, options, runner).await;
+            let finished = done.fetch_add(1, Ordering::Relaxed) + 1;
+            print_progress(finished, total, &item);
+            item
+        })
+        .buffer_unordered(options.concurrency.max(1))
+        .collect()
+        .await;
+    items.sort_by_key(|item| item.index);
+
+    let mut usage = ItemUsage::default();
+    for item in &items {
+        usage.add(item.usage);
+    }
+    let succeeded = items.iter().filter(|item| item.status == "ok").count();
+    let manifest = BatchManifest {
+        template: options.template.clone(),
+        started_at,
+        finished_at: Utc::now(),
+        concurrency: options.concurrency.max(1),
+        totals: BatchTotals {
+            items: items.len(),
+            succeeded,
+            failed: items.len() - succeeded,
+            usage,
+        },
+        items,
+    };
+    let manifest_path = options.out_dir.join(MANIFEST_FILE);
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)
+        .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+    Ok(manifest)
+}
+
+async fn run_item<F, Fut>(
+    index: usize,
+    input: String,
+    options: &BatchOptions,
+    runner: &F,
+) -> BatchItem
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = (Result<String>, ItemUsage)>,
+{
+    let started = Instant::now();
+    let mut item = BatchItem {
+        index,
+        input: input.clone(),
+        status: "failed".to_string(),
+        attempts: 0,
+        output_file: None,
+        error: None,
+        duration_ms: 0,
+        usage: ItemUsage::default(),
+    };
+
+    let prompt = match render_prompt(&options.template, index, &input) {
+        Ok(prompt) => prompt,
+        Err(e) => {
+            item.error = Some(e.to_string());
+            return item;
+        }
+    };
+
+    let mut backoff = options.retry_backoff;
+    loop {
+        item.attempts += 1;
+        let (result, usage) = runner(prompt.clone()).await;
+        item.usage.add(usage);
+        match result {
+            Ok(text) => {
+                let file_name = format!("{index:04}-{}.md", slug(&input));
+                match fs::write(options.out_dir.join(&file_name), &text) {
+                    Ok(()) => {
+                        item.status = "ok".to_string();
+                        item.output_file = Some(file_name);
+                        item.error = None;
+                    }
+                    Err(e) => item.error = Some(format!("Failed to write {file_name}: {e}")),
+                }
+                break;
+            }
+            Err(e) => {
+                item.error = Some(crate::providers::sanitize_api_error(&e.to_string()));
+                if item.attempts > options.retries {
+                    break;
+                }
+                tokio::time::sleep(backoff).await;
+                backoff = backoff.saturating_mul(2);
+            }
+        }
+    }
+    item.duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    item
+}
+
+fn print_progress(finished: usize, total: usize, item: &BatchItem) {
+    let retries = if item.attempts > 1 {
+        format!(", {} attempts", item.attempts)
+    } else {
+        String::new()
+    };
+    let icon = if item.status == "ok" { "✅" } else { "❌" };
+    println!(
+        "[{finished}/{total}] {icon} {} ({:.1}s, ${:.4}{retries})",
+        item.input,
+        item.duration_ms as f64 / 1000.0,
+        item.usage.cost_usd
+    );
+    if let Some(error) = &item.error {
+        println!("        {error}");
+    }
+}
+
+/// File-name-safe stem derived from an input (its last path segment).
+fn slug(input: &str) -> String {
+    let base = input
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(input);
+    let slug: String = base
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .take(MAX_SLUG_CHARS)
+        .collect();
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        "item".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Sums token usage and estimated cost of the LLM calls in one agent run.
+#[derive(Clone, Default)]
+struct UsageCollector {
+    usage: Arc<Mutex<ItemUsage>>,
+}
+
+impl Observer for UsageCollector {
+    fn record_event(&self, event: &ObserverEvent) {
+        if let ObserverEvent::LlmResponse {
+            provider,
+            model,
+            input_tokens,
+            output_tokens,
+            ..
+        } = event
+        {
+            let mut usage = self.usage.lock();
+            usage.input_tokens += input_tokens.unwrap_or(0);
+            usage.output_tokens += output_tokens.unwrap_or(0);
+            usage.cost_usd += crate::observability::runtime_trace::estimate_cost_usd(
+                provider,
+                model,
+                *input_tokens,
+                *output_tokens,
+            )
+            .unwrap_or(0.0);
+        }
+    }
+
+    fn record_metric(&self, _metric: &ObserverMetric) {}
+
+    fn name(&self) -> &str {
+        "batch-usage"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Run one prompt through a fresh single-turn agent, capturing its usage.
+pub async fn run_agent_prompt(config: &Config, prompt: String) -> (Result<String>, ItemUsage) {
+    let collector = UsageCollector::default();
+    let observer: Arc<dyn Observer> = Arc::new(MultiObserver::new(vec![
+        crate::observability::create_observer(&config.observability),
+        Box::new(collector.clone()),
+    ]));
+    let result = Box::pin(crate::agent::process_message_observed(
+        config.clone(),
+        &prompt,
+        observer,
+        None,
+    ))
+    .await;
+    let usage = *collector.usage.lock();
+    (result, usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn options(out_dir: &Path) -> BatchOptions {
+        BatchOptions {
+            template: "Summarize {{file}} (#{{index}})".into(),
+            concurrency: 3,
+            retries: 1,
+            retry_backoff: Duration::from_millis(1),
+            out_dir: out_dir.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn read_inputs_skips_blank_lines_and_comments() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("inputs.txt");
+        fs::write(&path, "# docs\na.md\n\n  b.md  \n").unwrap();
+        assert_eq!(read_inputs(&path).unwrap(), vec!["a.md", "b.md"]);
+    }
+
+    #[test]
+    fn render_prompt_inlines_file_content() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("note.txt");
+        fs::write(&path, "hello").unwrap();
+        let input = path.to_string_lossy();
+        let prompt = render_prompt("[{{index}}] {{content}}", 2, &input).unwrap();
+        assert_eq!(prompt, "[2] hello");
+        assert!(render_prompt("{{content}}", 1, "/definitely/missing").is_err());
+    }
+
+    #[test]
+    fn slug_uses_last_path_segment() {
+        assert_eq!(slug("docs/reports/Q3 plan.md"), "Q3-plan-md");
+        assert_eq!(slug("///"), "item");
+    }
+
+    #[tokio::test]
+    async fn run_batch_retries_and_writes_manifest() {
+        let tmp = TempDir::new().unwrap();
+        let attempts: Mutex<HashMap<String, u32>> = Mutex::new(HashMap::new());
+        let inputs = vec!["a.md".to_string(), "flaky.md".into(), "broken.md".into()];
+
+        let manifest = run_batch(inputs, &options(tmp.path()), |prompt: String| {
+            let attempt = {
+                let mut map = attempts.lock();
+                let entry = map.entry(prompt.clone()).or_default();
+                *entry += 1;
+                *entry
+            };
+            async move {
+                let usage = ItemUsage {
+                    input_tokens: 10,
+                    output_tokens: 5,
+                    cost_usd: 0.01,
+                };
+                let result =
+                    if prompt.contains("broken") || (prompt.contains("flaky") && attempt == 1) {
+                        Err(anyhow::anyhow!("provider error"))
+                    } else {
+                        Ok(format!("summary of {prompt}"))
+                    };
+                (result, usage)
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(manifest.totals.succeeded, 2);
+        assert_eq!(manifest.totals.failed, 1);
+        let attempts: Vec<u32> = manifest.items.iter().map(|item| item.attempts).collect();
+        assert_eq!(attempts, vec![1, 2, 2]);
+        assert_eq!(manifest.totals.usage.input_tokens, 50);
+        assert!((manifest.totals.usage.cost_usd - 0.05).abs() < 1e-9);
+
+        let output = fs::read_to_string(tmp.path().join("0002-flaky-md.md")).unwrap();
+        assert_eq!(output, "summary of Summarize flaky.md (#2)");
+        let written: BatchManifest =
+            serde_json::from_str(&fs::read_to_string(tmp.path().join(MANIFEST_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(written.items[2].error.as_deref(), Some("provider error"));
+        assert!(written.items[2].output_file.is_none());
+    }
+}
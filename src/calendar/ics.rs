@@ -0,0 +1,591 @@
+//! Minimal iCalendar (RFC 5545) support: busy time from `VEVENT`s and
+//! serialization of new events.
+//!
+//! Recurring events are expanded for `FREQ=DAILY`, `WEEKLY` (with `BYDAY`),
+//! `MONTHLY` and `YEARLY` with `INTERVAL`, `COUNT` and `UNTIL`; `EXDATE`
+//! removes occurrences. Other recurrence parts are ignored.
+
+use chrono::{
+    DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use std::fmt::Write;
+
+/// Occurrences expanded per recurring event at most.
+const MAX_OCCURRENCES: usize = 20_000;
+/// Octets per line before folding (RFC 5545 §3.1).
+const MAX_LINE_OCTETS: usize = 75;
+
+/// A span during which someone is not available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Busy {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Busy {
+    pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.start < end && start < self.end
+    }
+}
+
+/// Where an event's local times live.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Zone {
+    Utc,
+    Named(Tz),
+}
+
+impl Zone {
+    fn localize(self, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Utc => Some(Utc.from_utc_datetime(&naive)),
+            // Times skipped by a DST jump resolve to the hour after.
+            Self::Named(tz) => tz
+                .from_local_datetime(&naive)
+                .earliest()
+                .or_else(|| {
+                    tz.from_local_datetime(&(naive + Duration::hours(1)))
+                        .earliest()
+                })
+                .map(|t| t.with_timezone(&Utc)),
+        }
+    }
+}
+
+/// A parsed `DTSTART`/`DTEND`/`EXDATE` value.
+#[derive(Debug, Clone, Copy)]
+struct Stamp {
+    local: NaiveDateTime,
+    zone: Zone,
+    all_day: bool,
+}
+
+#[derive(Debug, Default)]
+struct RawEvent {
+    start: Option<Stamp>,
+    end: Option<Stamp>,
+    duration: Option<Duration>,
+    rrule: Option<String>,
+    exdates: Vec<DateTime<Utc>>,
+    transparent: bool,
+    cancelled: bool,
+}
+
+/// Join folded lines (continuations start with a space or tab).
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Property parameters as upper-case key and unquoted value.
+type Params = Vec<(String, String)>;
+
+/// Split `NAME;PARAM=x:value` into upper-case name, params and value.
+fn split_property(line: &str) -> Option<(String, Params, &str)> {
+    let (head, value) = line.split_once(':')?;
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((
+                key.trim().to_ascii_uppercase(),
+                value.trim().trim_matches('"').to_string(),
+            ))
+        })
+        .collect();
+    Some((name, params, value.trim()))
+}
+
+fn parse_stamp(params: &[(String, String)], value: &str, default_tz: Tz) -> Option<Stamp> {
+    let param = |key: &str| {
+        params
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    };
+    if param("VALUE") == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some(Stamp {
+            local: date.and_time(NaiveTime::MIN),
+            zone: Zone::Named(default_tz),
+            all_day: true,
+        });
+    }
+    let (naive, utc) = match value.strip_suffix('Z') {
+        Some(rest) => (rest, true),
+        None => (value, false),
+    };
+    let local = NaiveDateTime::parse_from_str(naive, "%Y%m%dT%H%M%S").ok()?;
+    let zone = if utc {
+        Zone::Utc
+    } else {
+        let tz = param("TZID")
+            .and_then(|name| name.parse::<Tz>().ok())
+            .unwrap_or(default_tz);
+        Zone::Named(tz)
+    };
+    Some(Stamp {
+        local,
+        zone,
+        all_day: false,
+    })
+}
+
+/// `P1W`, `P1D`, `PT1H30M`, `P1DT2H`, ...
+fn parse_duration(value: &str) -> Option<Duration> {
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let mut rest = value.strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut in_time = false;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('T') {
+            in_time = true;
+            rest = after;
+            continue;
+        }
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let amount: i64 = rest[..digits].parse().ok()?;
+        let unit = rest[digits..].chars().next()?;
+        total += match (unit, in_time) {
+            ('W', false) => Duration::weeks(amount),
+            ('D', false) => Duration::days(amount),
+            ('H', true) => Duration::hours(amount),
+            ('M', true) => Duration::minutes(amount),
+            ('S', true) => Duration::seconds(amount),
+            _ => return None,
+        };
+        rest = &rest[digits + unit.len_utf8()..];
+    }
+    Some(if negative { -total } else { total })
+}
+
+fn parse_events(ics: &str, default_tz: Tz) -> Vec<RawEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<RawEvent> = None;
+    // Depth of components nested in the current event (VALARM, ...).
+    let mut nested = 0usize;
+    for line in unfold(ics) {
+        let Some((name, params, value)) = split_property(&line) else {
+            continue;
+        };
+        match (name.as_str(), value.to_ascii_uppercase().as_str()) {
+            ("BEGIN", "VEVENT") => {
+                current = Some(RawEvent::default());
+                nested = 0;
+                continue;
+            }
+            ("END", "VEVENT") => {
+                events.extend(current.take());
+                continue;
+            }
+            ("BEGIN", _) => nested += 1,
+            ("END", _) => nested = nested.saturating_sub(1),
+            _ => {}
+        }
+        let Some(event) = current.as_mut().filter(|_| nested == 0) else {
+            continue;
+        };
+        match name.as_str() {
+            "DTSTART" => event.start = parse_stamp(&params, value, default_tz),
+            "DTEND" => event.end = parse_stamp(&params, value, default_tz),
+            "DURATION" => event.duration = parse_duration(value),
+            "RRULE" => event.rrule = Some(value.to_ascii_uppercase()),
+            "EXDATE" => event.exdates.extend(value.split(',').filter_map(|item| {
+                let stamp = parse_stamp(&params, item.trim(), default_tz)?;
+                stamp.zone.localize(stamp.local)
+            })),
+            "TRANSP" => event.transparent = value.eq_ignore_ascii_case("TRANSPARENT"),
+            "STATUS" => event.cancelled = value.eq_ignore_ascii_case("CANCELLED"),
+            _ => {}
+        }
+    }
+    events
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Freq {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug)]
+struct Rule {
+    freq: Freq,
+    interval: u32,
+    count: Option<usize>,
+    until: Option<DateTime<Utc>>,
+    by_day: Vec<Weekday>,
+}
+
+fn parse_rule(rrule: &str, zone: Zone) -> Option<Rule> {
+    let mut rule = Rule {
+        freq: Freq::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+        by_day: Vec::new(),
+    };
+    let mut freq = None;
+    for part in rrule.split(';') {
+        let (key, value) = part.split_once('=')?;
+        match key {
+            "FREQ" => {
+                freq = match value {
+                    "DAILY" => Some(Freq::Daily),
+                    "WEEKLY" => Some(Freq::Weekly),
+                    "MONTHLY" => Some(Freq::Monthly),
+                    "YEARLY" => Some(Freq::Yearly),
+                    _ => None,
+                };
+            }
+            "INTERVAL" => rule.interval = value.parse().unwrap_or(1).max(1),
+            "COUNT" => rule.count = value.parse().ok(),
+            "UNTIL" => {
+                rule.until = parse_stamp(&[], value, chrono_tz::UTC).and_then(|stamp| {
+                    let zone = if value.ends_with('Z') {
+                        Zone::Utc
+                    } else {
+                        zone
+                    };
+                    let local = if stamp.all_day {
+                        stamp.local + Duration::days(1)
+                    } else {
+                        stamp.local
+                    };
+                    zone.localize(local)
+                });
+            }
+            "BYDAY" => {
+                rule.by_day = value
+                    .split(',')
+                    .filter_map(|day| {
+                        // Ordinal prefixes ("2TU") only apply to monthly rules.
+                        let code = day.trim_start_matches(|c: char| {
+                            c == '+' || c == '-' || c.is_ascii_digit()
+                        });
+                        match code {
+                            "MO" => Some(Weekday::Mon),
+                            "TU" => Some(Weekday::Tue),
+                            "WE" => Some(Weekday::Wed),
+                            "TH" => Some(Weekday::Thu),
+                            "FR" => Some(Weekday::Fri),
+                            "SA" => Some(Weekday::Sat),
+                            "SU" => Some(Weekday::Sun),
+                            _ => None,
+                        }
+                    })
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+    rule.freq = freq?;
+    Some(rule)
+}
+
+/// Local start times of a recurring event, in order, from `first`.
+fn occurrences(first: NaiveDateTime, rule: &Rule) -> impl Iterator<Item = NaiveDateTime> + '_ {
+    let interval = i64::from(rule.interval);
+    let week_start =
+        first.date() - Duration::days(i64::from(first.weekday().num_days_from_monday()));
+    let mut step: u32 = 0;
+    let mut day_offset: i64 = 0;
+    std::iter::from_fn(move || loop {
+        match rule.freq {
+            Freq::Weekly if !rule.by_day.is_empty() => {
+                let date = first.date() + Duration::days(day_offset);
+                day_offset += 1;
+                if day_offset > 366 * 50 {
+                    return None;
+                }
+                let week = (date - week_start).num_days() / 7;
+                if week % interval == 0 && rule.by_day.contains(&date.weekday()) {
+                    return Some(date.and_time(first.time()));
+                }
+            }
+            freq => {
+                let n = step;
+                step += 1;
+                return match freq {
+                    Freq::Daily => Some(first + Duration::days(interval * i64::from(n))),
+                    Freq::Weekly => Some(first + Duration::weeks(interval * i64::from(n))),
+                    Freq::Monthly => first.checked_add_months(Months::new(rule.interval * n)),
+                    Freq::Yearly => first.checked_add_months(Months::new(12 * rule.interval * n)),
+                };
+            }
+        }
+    })
+}
+
+/// Busy intervals overlapping `[from, to)`. Floating and all-day times are
+/// read in `default_tz`. Transparent and cancelled events are skipped.
+pub fn busy_intervals(
+    ics: &str,
+    default_tz: Tz,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<Busy> {
+    let mut busy = Vec::new();
+    for event in parse_events(ics, default_tz) {
+        let Some(start) = event.start else {
+            continue;
+        };
+        if event.transparent || event.cancelled {
+            continue;
+        }
+        let length = match (event.end, event.duration) {
+            (Some(end), _) => end.local - start.local,
+            (None, Some(duration)) => duration,
+            (None, None) if start.all_day => Duration::days(1),
+            (None, None) => Duration::zero(),
+        };
+        if length <= Duration::zero() {
+            continue;
+        }
+        let rule = event
+            .rrule
+            .as_deref()
+            .and_then(|rrule| parse_rule(rrule, start.zone));
+        let starts: Box<dyn Iterator<Item = NaiveDateTime>> = match &rule {
+            Some(rule) => Box::new(occurrences(start.local, rule)),
+            None => Box::new(std::iter::once(start.local)),
+        };
+        for (index, local) in starts.enumerate().take(MAX_OCCURRENCES) {
+            if rule
+                .as_ref()
+                .and_then(|rule| rule.count)
+                .is_some_and(|count| index >= count)
+            {
+                break;
+            }
+            let Some(begin) = start.zone.localize(local) else {
+                continue;
+            };
+            if begin >= to
+                || rule
+                    .as_ref()
+                    .and_then(|rule| rule.until)
+                    .is_some_and(|until| begin > until)
+            {
+                break;
+            }
+            let Some(end) = start.zone.localize(local + length) else {
+                continue;
+            };
+            if end > from && !event.exdates.contains(&begin) {
+                busy.push(Busy { start: begin, end });
+            }
+        }
+    }
+    busy.sort_by_key(|b| b.start);
+    busy
+}
+
+/// An event to create.
+#[derive(Debug, Clone)]
+pub struct NewEvent {
+    pub uid: String,
+    pub title: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub description: String,
+    pub location: String,
+    pub organizer: Option<String>,
+    pub attendees: Vec<String>,
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Append `line` folded at 75 octets, with CRLF endings.
+fn push_folded(out: &mut String, line: &str) {
+    let mut used = 0;
+    for ch in line.chars() {
+        if used + ch.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            used = 1;
+        }
+        out.push(ch);
+        used += ch.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn utc_stamp(instant: DateTime<Utc>) -> String {
+    instant.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Serialize `event` as a `VCALENDAR` with one `VEVENT`.
+pub fn event_ics(event: &NewEvent, now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//ZeroClaw//Calendar//EN".to_string(),
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}", event.uid),
+        format!("DTSTAMP:{}", utc_stamp(now)),
+        format!("DTSTART:{}", utc_stamp(event.start)),
+        format!("DTEND:{}", utc_stamp(event.end)),
+        format!("SUMMARY:{}", escape_text(&event.title)),
+    ];
+    if !event.description.trim().is_empty() {
+        lines.push(format!("DESCRIPTION:{}", escape_text(&event.description)));
+    }
+    if !event.location.trim().is_empty() {
+        lines.push(format!("LOCATION:{}", escape_text(&event.location)));
+    }
+    if let Some(organizer) = &event.organizer {
+        lines.push(format!("ORGANIZER:mailto:{organizer}"));
+    }
+    for attendee in &event.attendees {
+        lines.push(format!(
+            "ATTENDEE;ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=TRUE:mailto:{attendee}"
+        ));
+    }
+    lines.extend(["END:VEVENT".to_string(), "END:VCALENDAR".to_string()]);
+
+    let mut out = String::new();
+    for line in lines {
+        push_folded(&mut out, &line);
+    }
+    out
+}
+
+/// One-line description of a busy span, for tool output.
+pub fn describe(busy: &Busy, tz: Tz) -> String {
+    let start = busy.start.with_timezone(&tz);
+    let end = busy.end.with_timezone(&tz);
+    let mut out = start.format("%a %Y-%m-%d %H:%M").to_string();
+    if end.date_naive() == start.date_naive() {
+        let _ = write!(out, "–{}", end.format("%H:%M"));
+    } else {
+        let _ = write!(out, " – {}", end.format("%a %Y-%m-%d %H:%M"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    const CALENDAR: &str = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Standup\r\n\
+DTSTART;TZID=Europe/Berlin:20261019T093000\r\n\
+DTEND;TZID=Europe/Berlin:20261019T094500\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE,FR;COUNT=5\r\n\
+EXDATE;TZID=Europe/Berlin:20261021T093000\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Lunch\r\n\
+DTSTART:20261020T110000Z\r\n\
+DURATION:PT1H\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Holiday (free)\r\n\
+DTSTART;VALUE=DATE:20261022\r\n\
+TRANSP:TRANSPARENT\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Offsite\r\n\
+DTSTART;VALUE=DATE:20261023\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    #[test]
+    fn busy_intervals_expand_recurrences_and_skip_free_time() {
+        let busy = busy_intervals(
+            CALENDAR,
+            chrono_tz::Europe::Berlin,
+            utc(2026, 10, 19, 0, 0),
+            utc(2026, 10, 26, 0, 0),
+        );
+        let starts: Vec<_> = busy.iter().map(|b| b.start).collect();
+        assert_eq!(
+            starts,
+            vec![
+                // Standup on Monday (CEST, UTC+2); Wednesday is an EXDATE and
+                // the next Monday falls after the window.
+                utc(2026, 10, 19, 7, 30),
+                utc(2026, 10, 20, 11, 0),
+                // All-day offsite, midnight to midnight Berlin time.
+                utc(2026, 10, 22, 22, 0),
+                utc(2026, 10, 23, 7, 30),
+            ]
+        );
+        assert_eq!(busy[1].end - busy[1].start, Duration::hours(1));
+    }
+
+    #[test]
+    fn durations_and_folded_lines_parse() {
+        assert_eq!(parse_duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("P1DT2H"), Some(Duration::hours(26)));
+        assert_eq!(parse_duration("P2W"), Some(Duration::weeks(2)));
+        assert_eq!(parse_duration("1H"), None);
+        assert_eq!(
+            unfold("SUMMARY:Long\r\n  title\r\nEND:VEVENT"),
+            vec!["SUMMARY:Long title".to_string(), "END:VEVENT".to_string()]
+        );
+    }
+
+    #[test]
+    fn new_events_serialize_as_folded_utc_vevents() {
+        let event = NewEvent {
+            uid: "abc@zeroclaw".into(),
+            title: "Planning; Q4, budget".into(),
+            start: utc(2026, 10, 20, 14, 0),
+            end: utc(2026, 10, 20, 14, 30),
+            description: "Agenda:\n- numbers ".repeat(8),
+            location: String::new(),
+            organizer: Some("me@example.com".into()),
+            attendees: vec!["bob@example.com".into()],
+        };
+        let ics = event_ics(&event, utc(2026, 10, 17, 9, 0));
+        assert!(ics.contains("DTSTART:20261020T140000Z\r\n"));
+        assert!(ics.contains("SUMMARY:Planning\\; Q4\\, budget\r\n"));
+        assert!(unfold(&ics).contains(
+            &"ATTENDEE;ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=TRUE:mailto:bob@example.com"
+                .to_string()
+        ));
+        assert!(ics.contains("\r\n "));
+        assert!(ics.lines().all(|line| line.len() <= MAX_LINE_OCTETS + 1));
+
+        let roundtrip = busy_intervals(
+            &ics,
+            chrono_tz::UTC,
+            utc(2026, 10, 20, 0, 0),
+            utc(2026, 10, 21, 0, 0),
+        );
+        assert_eq!(
+            roundtrip,
+            vec![Busy {
+                start: event.start,
+                end: event.end
+            }]
+        );
+    }
+}
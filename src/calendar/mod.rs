@@ -0,0 +1,400 @@
+//! Calendar access and availability (`[calendar]`).
+//!
+//! Busy time is read from the owner's iCalendar feeds (`busy_sources`: ICS
+//! URLs, CalDAV collection exports or local `.ics` files) and new events are
+//! written to a CalDAV collection (`caldav_url`). All timezone arithmetic
+//! happens here: working hours are applied in each participant's own IANA
+//! zone, so slots stay correct across DST changes and the model never has to
+//! convert times itself.
+
+pub mod ics;
+
+use crate::config::{CalendarConfig, Config};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+
+pub use ics::{Busy, NewEvent};
+
+/// Non-overlapping slots considered per day before moving to the next round.
+const MAX_CANDIDATES_PER_DAY: usize = 16;
+
+/// Parse `mon`, `Monday`, `TUE`, ...
+pub fn parse_weekday(value: &str) -> Option<Weekday> {
+    let value = value.trim().to_ascii_lowercase();
+    let prefix = value.get(..3)?;
+    match prefix {
+        "mon" => Some(Weekday::Mon),
+        "tue" => Some(Weekday::Tue),
+        "wed" => Some(Weekday::Wed),
+        "thu" => Some(Weekday::Thu),
+        "fri" => Some(Weekday::Fri),
+        "sat" => Some(Weekday::Sat),
+        "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Daily availability window on given weekdays, in the participant's zone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkingHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    pub days: Vec<Weekday>,
+}
+
+impl WorkingHours {
+    pub fn parse(start: &str, end: &str, days: &[String]) -> Result<Self> {
+        let time = |value: &str| {
+            NaiveTime::parse_from_str(value.trim(), "%H:%M")
+                .with_context(|| format!("invalid time '{value}' (expected HH:MM)"))
+        };
+        let (start, end) = (time(start)?, time(end)?);
+        if start >= end {
+            bail!("working hours must start before they end");
+        }
+        let days = days
+            .iter()
+            .map(|day| parse_weekday(day).with_context(|| format!("invalid weekday '{day}'")))
+            .collect::<Result<Vec<_>>>()?;
+        if days.is_empty() {
+            bail!("at least one working day is required");
+        }
+        Ok(Self { start, end, days })
+    }
+
+    /// Monday to Friday, 09:00-17:00.
+    pub fn office() -> Self {
+        Self {
+            start: NaiveTime::from_hms_opt(9, 0, 0).unwrap_or(NaiveTime::MIN),
+            end: NaiveTime::from_hms_opt(17, 0, 0).unwrap_or(NaiveTime::MIN),
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+        }
+    }
+
+    /// Whether `[start, end)` lies inside one working day in `tz`.
+    fn admits(&self, tz: Tz, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        let (start, end) = (start.with_timezone(&tz), end.with_timezone(&tz));
+        start.date_naive() == end.date_naive()
+            && self.days.contains(&start.weekday())
+            && start.time() >= self.start
+            && end.time() <= self.end
+    }
+}
+
+/// Someone whose availability constrains the meeting.
+#[derive(Debug, Clone)]
+pub struct Participant {
+    pub name: String,
+    pub tz: Tz,
+    pub hours: WorkingHours,
+    pub busy: Vec<Busy>,
+}
+
+impl Participant {
+    fn is_free(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.hours.admits(self.tz, start, end) && !self.busy.iter().any(|b| b.overlaps(start, end))
+    }
+}
+
+/// Parameters of a slot search. Days are counted in the first participant's zone.
+#[derive(Debug, Clone)]
+pub struct SlotSearch {
+    pub first_day: NaiveDate,
+    pub days: u32,
+    pub duration: Duration,
+    pub step: Duration,
+    pub max_slots: usize,
+    /// Slots starting earlier are skipped (usually "now").
+    pub not_before: DateTime<Utc>,
+}
+
+/// UTC instant of a local wall-clock time; times skipped by DST move forward an hour.
+fn localize(tz: Tz, date: NaiveDate, time: NaiveTime) -> Option<DateTime<Utc>> {
+    let naive = date.and_time(time);
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(naive + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Start times free for every participant, spread across days: the first
+/// slot of each day is offered before a second slot on any day.
+pub fn find_slots(participants: &[Participant], search: &SlotSearch) -> Vec<DateTime<Utc>> {
+    let Some(owner) = participants.first() else {
+        return Vec::new();
+    };
+    if search.duration <= Duration::zero() || search.step <= Duration::zero() {
+        return Vec::new();
+    }
+    let mut per_day: BTreeMap<NaiveDate, Vec<DateTime<Utc>>> = BTreeMap::new();
+    for offset in 0..search.days {
+        let day = search.first_day + Duration::days(i64::from(offset));
+        if !owner.hours.days.contains(&day.weekday()) {
+            continue;
+        }
+        let (Some(open), Some(close)) = (
+            localize(owner.tz, day, owner.hours.start),
+            localize(owner.tz, day, owner.hours.end),
+        ) else {
+            continue;
+        };
+        let slots = per_day.entry(day).or_default();
+        let mut start = open;
+        while start + search.duration <= close && slots.len() < MAX_CANDIDATES_PER_DAY {
+            let end = start + search.duration;
+            if start >= search.not_before && participants.iter().all(|p| p.is_free(start, end)) {
+                slots.push(start);
+                start = end;
+            } else {
+                start += search.step;
+            }
+        }
+    }
+
+    let mut chosen = Vec::new();
+    for round in 0..MAX_CANDIDATES_PER_DAY {
+        for slots in per_day.values() {
+            if chosen.len() >= search.max_slots {
+                break;
+            }
+            if let Some(slot) = slots.get(round) {
+                chosen.push(*slot);
+            }
+        }
+    }
+    chosen.sort();
+    chosen
+}
+
+/// `Tue 2026-10-20 10:00–10:30 (Europe/Berlin)`.
+pub fn format_slot(start: DateTime<Utc>, duration: Duration, tz: Tz) -> String {
+    let busy = Busy {
+        start,
+        end: start + duration,
+    };
+    format!("{} ({})", ics::describe(&busy, tz), tz.name())
+}
+
+/// The owner's zone: `[calendar].timezone`, then `[ui].timezone`, then UTC.
+pub fn owner_timezone(config: &Config) -> Tz {
+    [&config.calendar.timezone, &config.ui.timezone]
+        .into_iter()
+        .flatten()
+        .find_map(|name| name.trim().parse::<Tz>().ok())
+        .unwrap_or(chrono_tz::UTC)
+}
+
+pub fn owner_hours(calendar: &CalendarConfig) -> Result<WorkingHours> {
+    WorkingHours::parse(
+        &calendar.work_start,
+        &calendar.work_end,
+        &calendar.work_days,
+    )
+}
+
+fn same_origin(a: &str, b: &str) -> bool {
+    match (reqwest::Url::parse(a), reqwest::Url::parse(b)) {
+        (Ok(a), Ok(b)) => {
+            a.scheme() == b.scheme()
+                && a.host_str() == b.host_str()
+                && a.port_or_known_default() == b.port_or_known_default()
+        }
+        _ => false,
+    }
+}
+
+/// Credentials go only to the CalDAV server, never to third-party feeds.
+fn authorize(
+    calendar: &CalendarConfig,
+    url: &str,
+    request: reqwest::RequestBuilder,
+) -> reqwest::RequestBuilder {
+    match (&calendar.username, &calendar.caldav_url) {
+        (Some(username), Some(caldav)) if same_origin(url, caldav) => {
+            request.basic_auth(username, calendar.password.as_deref())
+        }
+        _ => request,
+    }
+}
+
+async fn fetch_source(calendar: &CalendarConfig, source: &str) -> Result<String> {
+    let source = source.trim();
+    if source.starts_with("https://") || source.starts_with("http://") {
+        let response = authorize(
+            calendar,
+            source,
+            crate::config::build_runtime_proxy_client("tool.calendar").get(source),
+        )
+        .send()
+        .await?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("{status}");
+        }
+        Ok(response.text().await?)
+    } else {
+        let path = crate::util::expand_tilde(source);
+        Ok(tokio::fs::read_to_string(&path).await?)
+    }
+}
+
+/// The owner's busy time in `[from, to)` from every `busy_sources` feed.
+/// An unreachable feed is an error: treating it as free would double-book.
+pub async fn owner_busy(
+    config: &Config,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<Busy>> {
+    let tz = owner_timezone(config);
+    let mut busy = Vec::new();
+    for source in &config.calendar.busy_sources {
+        let text = fetch_source(&config.calendar, source)
+            .await
+            .with_context(|| format!("failed to read calendar source {source}"))?;
+        busy.extend(ics::busy_intervals(&text, tz, from, to));
+    }
+    busy.sort_by_key(|b| b.start);
+    Ok(busy)
+}
+
+/// Create `event` in the `caldav_url` collection and return its URL.
+pub async fn create_event(config: &Config, event: &NewEvent) -> Result<String> {
+    let calendar = &config.calendar;
+    let collection = calendar
+        .caldav_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .context("set [calendar].caldav_url to create events")?;
+    let file_name: String = event
+        .uid
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect();
+    let url = format!("{}/{file_name}.ics", collection.trim_end_matches('/'));
+    let response = authorize(
+        calendar,
+        &url,
+        crate::config::build_runtime_proxy_client("tool.calendar").put(&url),
+    )
+    .header("Content-Type", "text/calendar; charset=utf-8")
+    .header("If-None-Match", "*")
+    .body(ics::event_ics(event, Utc::now()))
+    .send()
+    .await?;
+    let status = response.status();
+    if !status.is_success() {
+        let detail = response.text().await.unwrap_or_default();
+        bail!(
+            "CalDAV server returned {status}: {}",
+            crate::util::truncate_with_ellipsis(detail.trim(), 300)
+        );
+    }
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn participant(name: &str, tz: Tz, busy: Vec<Busy>) -> Participant {
+        Participant {
+            name: name.into(),
+            tz,
+            hours: WorkingHours::office(),
+            busy,
+        }
+    }
+
+    #[test]
+    fn slots_respect_every_participants_local_working_hours() {
+        let owner = participant(
+            "Owner",
+            chrono_tz::Europe::Berlin,
+            vec![Busy {
+                start: utc(2026, 10, 20, 13, 0),
+                end: utc(2026, 10, 20, 14, 0),
+            }],
+        );
+        let bob = participant("Bob", chrono_tz::America::New_York, Vec::new());
+        let search = SlotSearch {
+            first_day: NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+            days: 2,
+            duration: Duration::minutes(60),
+            step: Duration::minutes(30),
+            max_slots: 3,
+            not_before: utc(2026, 10, 19, 0, 0),
+        };
+        // Overlap of 09-17 Berlin (UTC+2) and 09-17 New York (UTC-4) is 13:00-15:00 UTC.
+        let slots = find_slots(&[owner, bob], &search);
+        assert_eq!(
+            slots,
+            vec![
+                utc(2026, 10, 19, 13, 0),
+                utc(2026, 10, 19, 14, 0),
+                utc(2026, 10, 20, 14, 0),
+            ]
+        );
+        assert_eq!(
+            format_slot(
+                slots[2],
+                Duration::minutes(60),
+                chrono_tz::America::New_York
+            ),
+            "Tue 2026-10-20 10:00–11:00 (America/New_York)"
+        );
+    }
+
+    #[test]
+    fn slots_follow_dst_changes_and_skip_the_past() {
+        // Europe leaves DST on 2026-10-25, the US on 2026-11-01: for that week
+        // the Berlin/New York offset is 5 hours instead of 6.
+        let owner = participant("Owner", chrono_tz::Europe::Berlin, Vec::new());
+        let bob = participant("Bob", chrono_tz::America::New_York, Vec::new());
+        let search = SlotSearch {
+            first_day: NaiveDate::from_ymd_opt(2026, 10, 26).unwrap(),
+            days: 1,
+            duration: Duration::minutes(30),
+            step: Duration::minutes(30),
+            max_slots: 10,
+            not_before: utc(2026, 10, 26, 14, 0),
+        };
+        let slots = find_slots(&[owner, bob], &search);
+        // Bob starts at 13:00 UTC (EDT); the owner stops at 16:00 UTC (CET).
+        assert_eq!(slots.first(), Some(&utc(2026, 10, 26, 14, 0)));
+        assert_eq!(slots.last(), Some(&utc(2026, 10, 26, 15, 30)));
+        assert_eq!(slots.len(), 4);
+    }
+
+    #[test]
+    fn working_hours_and_weekdays_parse() {
+        let hours =
+            WorkingHours::parse("08:30", "12:00", &["Monday".into(), "thu".into()]).unwrap();
+        assert_eq!(hours.days, vec![Weekday::Mon, Weekday::Thu]);
+        assert!(WorkingHours::parse("17:00", "09:00", &["mon".into()]).is_err());
+        assert!(WorkingHours::parse("09:00", "17:00", &["funday".into()]).is_err());
+        assert!(same_origin(
+            "https://dav.example.com/cal/feed.ics",
+            "https://dav.example.com:443/cal/"
+        ));
+        assert!(!same_origin(
+            "https://calendar.google.com/x.ics",
+            "https://dav.example.com/cal/"
+        ));
+    }
+}
@@ -0,0 +1,63 @@
+//! Frame-difference motion detection.
+//!
+//! Frames are downscaled to a small grayscale thumbnail and compared with
+//! the previous one; the share of pixels that changed noticeably is the
+//! motion score. Privacy zones are already black, so they never move.
+
+use image::imageops::{self, FilterType};
+use image::{GrayImage, RgbImage};
+
+const SAMPLE_WIDTH: u32 = 64;
+const SAMPLE_HEIGHT: u32 = 48;
+/// Brightness change (0–255) for a thumbnail pixel to count as changed;
+/// filters sensor noise and compression artefacts.
+const PIXEL_DELTA: u8 = 24;
+
+#[derive(Debug, Default)]
+pub struct MotionDetector {
+    previous: Option<GrayImage>,
+}
+
+impl MotionDetector {
+    /// Percentage of the frame that changed since the previous call, or
+    /// `None` for the first frame.
+    pub fn changed_pct(&mut self, frame: &RgbImage) -> Option<f64> {
+        let sample = imageops::resize(
+            &imageops::grayscale(frame),
+            SAMPLE_WIDTH,
+            SAMPLE_HEIGHT,
+            FilterType::Triangle,
+        );
+        let previous = self.previous.replace(sample.clone())?;
+        let changed = sample
+            .pixels()
+            .zip(previous.pixels())
+            .filter(|(now, before)| now.0[0].abs_diff(before.0[0]) > PIXEL_DELTA)
+            .count();
+        #[allow(clippy::cast_precision_loss)]
+        Some(changed as f64 * 100.0 / f64::from(SAMPLE_WIDTH * SAMPLE_HEIGHT))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_changed_share_of_frame() {
+        let mut detector = MotionDetector::default();
+        let still = RgbImage::from_pixel(640, 480, image::Rgb([90, 90, 90]));
+        assert_eq!(detector.changed_pct(&still), None);
+        assert_eq!(detector.changed_pct(&still), Some(0.0));
+
+        // A bright object covering the left quarter of the frame.
+        let mut moved = still.clone();
+        for y in 0..480 {
+            for x in 0..160 {
+                moved.put_pixel(x, y, image::Rgb([250, 250, 250]));
+            }
+        }
+        let changed = detector.changed_pct(&moved).unwrap();
+        assert!((20.0..=30.0).contains(&changed), "changed = {changed}");
+    }
+}
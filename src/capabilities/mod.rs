@@ -0,0 +1,343 @@
+//! Runtime capability report (`zeroclaw capabilities`, `GET /api/capabilities`).
+//!
+//! Describes what this instance can do with its current config: the Cargo
+//! features and feature packs compiled in, the tools the agent would be given
+//! (with their schemas and the security gates in front of each), configured
+//! channels and active integrations, and what the default model supports.
+//! Orchestrators read the JSON form to decide what to send here.
+
+use crate::config::Config;
+use crate::integrations::{registry::all_integrations, IntegrationCategory, IntegrationStatus};
+use crate::providers::capabilities::{self as model_capabilities, ModelCapabilities};
+use crate::security::{AutonomyLevel, EstopManager, EstopState, SecurityPolicy};
+use crate::tools::{self, Tool};
+use crate::{memory, providers, runtime};
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Everything the report covers.
+#[derive(Debug, Clone, Serialize)]
+pub struct Capabilities {
+    pub version: &'static str,
+    pub features: Vec<&'static str>,
+    pub packs: Vec<&'static str>,
+    pub autonomy: AutonomyLevel,
+    pub memory_backend: String,
+    pub model: ModelReport,
+    pub tools: Vec<ToolReport>,
+    pub channels: Vec<&'static str>,
+    pub integrations: Vec<IntegrationReport>,
+}
+
+/// The default provider and model.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelReport {
+    pub provider: String,
+    pub model: String,
+    /// Provider API supports native tool calling (`None` when the provider
+    /// could not be created, e.g. it needs credentials).
+    pub native_tools: Option<bool>,
+    pub vision: Option<bool>,
+    /// What the provider's model catalog says about the model, if cached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub catalog: Option<ModelCapabilities>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolReport {
+    pub name: String,
+    pub description: String,
+    pub parameters: serde_json::Value,
+    pub gates: ToolGates,
+}
+
+/// Checks a call to one tool passes before it runs.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ToolGates {
+    /// `none`, `prompt` (supervised approval), `always` (`always_ask`) or
+    /// `read_only` (side effects refused).
+    pub approval: &'static str,
+    /// Needs a second approver (`[security.four_eyes]`).
+    pub four_eyes: bool,
+    /// Subject to financial limits (`[security.financial]`).
+    pub financial: bool,
+    /// Evaluated by `[security.policy_engine]`.
+    pub policy_engine: bool,
+    /// Needs an OTP code (`[security.otp]`).
+    pub otp: bool,
+    /// Hidden from non-CLI channels (`[autonomy].non_cli_excluded_tools`).
+    pub cli_only: bool,
+    /// Frozen by the emergency stop.
+    pub frozen: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrationReport {
+    pub name: &'static str,
+    pub category: IntegrationCategory,
+}
+
+/// Gates in front of `tool` under `config` and the estop `estop` state.
+pub fn gates(config: &Config, estop: &EstopState, tool: &str, financial: bool) -> ToolGates {
+    let autonomy = &config.autonomy;
+    let listed = |names: &[String]| names.iter().any(|name| name == tool);
+    let security = &config.security;
+    ToolGates {
+        approval: match autonomy.level {
+            AutonomyLevel::ReadOnly => "read_only",
+            AutonomyLevel::Full => "none",
+            AutonomyLevel::Supervised if listed(&autonomy.always_ask) => "always",
+            AutonomyLevel::Supervised if listed(&autonomy.auto_approve) => "none",
+            AutonomyLevel::Supervised => "prompt",
+        },
+        four_eyes: security.four_eyes.enabled && listed(&security.four_eyes.tools),
+        financial: financial || listed(&security.financial.tools),
+        policy_engine: security.policy_engine.enabled
+            && (security.policy_engine.tools.is_empty() || listed(&security.policy_engine.tools)),
+        otp: security.otp.enabled && listed(&security.otp.gated_actions),
+        cli_only: listed(&autonomy.non_cli_excluded_tools),
+        frozen: estop.kill_all || listed(&estop.frozen_tools),
+    }
+}
+
+/// The tools an agent run would be given, without peripheral boards.
+fn tool_registry(config: &Config) -> Result<Vec<Box<dyn Tool>>> {
+    let security = Arc::new(SecurityPolicy::from_config(
+        &config.autonomy,
+        &config.workspace_dir,
+    ));
+    let runtime: Arc<dyn runtime::RuntimeAdapter> =
+        Arc::from(runtime::create_runtime(&config.runtime)?);
+    let mem: Arc<dyn memory::Memory> = Arc::from(memory::create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?);
+    let (composio_key, composio_entity_id) = if config.composio.enabled {
+        (
+            config.composio.api_key.as_deref(),
+            Some(config.composio.entity_id.as_str()),
+        )
+    } else {
+        (None, None)
+    };
+    Ok(tools::all_tools_with_runtime(
+        Arc::new(config.clone()),
+        &security,
+        runtime,
+        mem,
+        composio_key,
+        composio_entity_id,
+        &config.browser,
+        &config.http_request,
+        &config.workspace_dir,
+        &config.agents,
+        config.api_key.as_deref(),
+        config,
+    ))
+}
+
+fn estop_state(config: &Config) -> EstopState {
+    if !config.security.estop.enabled {
+        return EstopState::default();
+    }
+    let config_dir = config.config_path.parent().unwrap_or(Path::new("."));
+    EstopManager::load(&config.security.estop, config_dir)
+        .map(|manager| manager.status())
+        .unwrap_or_else(|e| {
+            tracing::warn!("Cannot read estop state: {e:#}");
+            EstopState::fail_closed()
+        })
+}
+
+fn model_report(config: &Config) -> ModelReport {
+    let provider = config
+        .default_provider
+        .clone()
+        .unwrap_or_else(|| "openrouter".into());
+    let model = config
+        .default_model
+        .clone()
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4".into());
+    model_capabilities::init_from_workspace(&config.workspace_dir);
+    let declared = providers::create_provider(&provider, config.api_key.as_deref())
+        .ok()
+        .map(|p| p.capabilities());
+    ModelReport {
+        native_tools: declared.as_ref().map(|c| c.native_tool_calling),
+        vision: declared.as_ref().map(|c| c.vision),
+        catalog: model_capabilities::lookup(&provider, &model),
+        provider,
+        model,
+    }
+}
+
+/// Collect the report for `config`.
+pub fn collect(config: &Config) -> Result<Capabilities> {
+    let estop = estop_state(config);
+    let mut tools: Vec<ToolReport> = tool_registry(config)?
+        .iter()
+        .map(|tool| {
+            let spec = tool.spec();
+            ToolReport {
+                gates: gates(config, &estop, &spec.name, tool.financial()),
+                name: spec.name,
+                description: spec.description,
+                parameters: spec.parameters,
+            }
+        })
+        .collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let features = crate::provenance::enabled_features();
+    Ok(Capabilities {
+        version: env!("CARGO_PKG_VERSION"),
+        packs: crate::provenance::enabled_packs(&features),
+        features,
+        autonomy: config.autonomy.level,
+        memory_backend: memory::effective_memory_backend_name(
+            &config.memory.backend,
+            Some(&config.storage.provider.config),
+        ),
+        model: model_report(config),
+        tools,
+        channels: config
+            .channels_config
+            .channels()
+            .into_iter()
+            .filter(|(_, present)| *present)
+            .map(|(channel, _)| channel.name())
+            .collect(),
+        integrations: all_integrations()
+            .into_iter()
+            .filter(|entry| (entry.status_fn)(config) == IntegrationStatus::Active)
+            .map(|entry| IntegrationReport {
+                name: entry.name,
+                category: entry.category,
+            })
+            .collect(),
+    })
+}
+
+impl Capabilities {
+    /// Human-readable summary.
+    pub fn render(&self) -> String {
+        let list = |items: Vec<&str>| {
+            if items.is_empty() {
+                "(none)".to_string()
+            } else {
+                items.join(", ")
+            }
+        };
+        let flag = |value: Option<bool>| match value {
+            Some(true) => "yes",
+            Some(false) => "no",
+            None => "unknown",
+        };
+        let mut out = format!(
+            "zeroclaw {}\n\
+             Features:      {}\n\
+             Packs:         {}\n\
+             Autonomy:      {:?}\n\
+             Memory:        {}\n\
+             Model:         {}/{} (native tools: {}, vision: {})\n",
+            self.version,
+            list(self.features.clone()),
+            list(self.packs.clone()),
+            self.autonomy,
+            self.memory_backend,
+            self.model.provider,
+            self.model.model,
+            flag(self.model.native_tools),
+            flag(self.model.vision),
+        );
+        if let Some(catalog) = &self.model.catalog {
+            let _ = writeln!(out, "Catalog:       {}", catalog.summary());
+        }
+        let _ = writeln!(out, "Channels:      {}", list(self.channels.clone()));
+        let _ = writeln!(
+            out,
+            "Integrations:  {}",
+            list(self.integrations.iter().map(|i| i.name).collect())
+        );
+        let _ = writeln!(out, "\nTools ({}):", self.tools.len());
+        for tool in &self.tools {
+            let gates = &tool.gates;
+            let mut notes = vec![format!("approval: {}", gates.approval)];
+            for (on, name) in [
+                (gates.four_eyes, "four-eyes"),
+                (gates.financial, "financial"),
+                (gates.policy_engine, "policy"),
+                (gates.otp, "otp"),
+                (gates.cli_only, "cli-only"),
+                (gates.frozen, "frozen"),
+            ] {
+                if on {
+                    notes.push(name.to_string());
+                }
+            }
+            let _ = writeln!(out, "  {:<24} {}", tool.name, notes.join(", "));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gates_follow_autonomy_and_security_config() {
+        let mut config = Config::default();
+        config.autonomy.level = AutonomyLevel::Supervised;
+        config.autonomy.auto_approve = vec!["file_read".into()];
+        config.autonomy.always_ask = vec!["shell".into()];
+        config.autonomy.non_cli_excluded_tools = vec!["shell".into()];
+        config.security.four_eyes.enabled = true;
+        config.security.four_eyes.tools = vec!["shell".into()];
+        config.security.policy_engine.enabled = true;
+        let estop = EstopState {
+            frozen_tools: vec!["browser".into()],
+            ..EstopState::default()
+        };
+
+        let shell = gates(&config, &estop, "shell", false);
+        assert_eq!(shell.approval, "always");
+        assert!(shell.four_eyes && shell.cli_only && shell.policy_engine);
+        assert!(!shell.frozen);
+
+        assert_eq!(gates(&config, &estop, "file_read", false).approval, "none");
+        assert_eq!(
+            gates(&config, &estop, "file_write", false).approval,
+            "prompt"
+        );
+        assert!(gates(&config, &estop, "browser", false).frozen);
+        assert!(gates(&config, &estop, "composio", true).financial);
+
+        config.autonomy.level = AutonomyLevel::ReadOnly;
+        assert_eq!(gates(&config, &estop, "shell", false).approval, "read_only");
+    }
+
+    #[test]
+    fn report_lists_registry_tools_with_schemas() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+
+        let report = collect(&config).unwrap();
+        let shell = report.tools.iter().find(|t| t.name == "shell").unwrap();
+        assert_eq!(shell.parameters["type"], "object");
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json["tools"].as_array().unwrap().len() > 1);
+        assert_eq!(json["model"]["provider"], "openrouter");
+    }
+}
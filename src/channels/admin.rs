@@ -0,0 +1,397 @@
+//! Owner admin commands in chat (`[channels_config.admin]`).
+//!
+//! Owners listed in `owners` can run a small command set from any bound
+//! channel, so day-to-day operation does not need a shell on the host:
+//!
+//! - `!status`: running and paused channels, default model, estop state.
+//! - `!budget`: today's and this month's spend against `[cost]` limits.
+//! - `!pause [channel]` / `!resume [channel]`: stop or restart handling
+//!   messages on a channel (the current one by default).
+//! - `!model <name>`: switch the default model until the next config edit.
+//! - `!estop [network]`: engage the emergency stop (kill-all by default).
+//!
+//! With `require_otp`, `!pause`, `!model` and `!estop` must end with a current
+//! `[security.otp]` code, e.g. `!estop 123456`. Owners are matched through the
+//! contact book, so an approved `contact:<name>` covers every channel linked
+//! to them.
+
+use crate::config::{ChannelAdminConfig, Config, CostConfig, EstopConfig};
+use crate::cost::CostTracker;
+use crate::i18n;
+use crate::security::{EstopLevel, EstopManager, EstopState, OtpValidator, SecretStore};
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+
+/// One admin command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminCommand {
+    Help,
+    Status,
+    Budget,
+    Pause(Option<String>),
+    Resume(Option<String>),
+    Model(String),
+    Estop { network: bool },
+}
+
+impl AdminCommand {
+    /// Commands that change what the daemon does, and need OTP when required.
+    pub fn is_destructive(&self) -> bool {
+        matches!(self, Self::Pause(_) | Self::Model(_) | Self::Estop { .. })
+    }
+}
+
+/// A parsed `!command` with the OTP code it ended with, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminRequest {
+    pub command: AdminCommand,
+    pub otp: Option<String>,
+}
+
+/// Parse `!status`, `!budget`, `!pause [channel]`, `!resume [channel]`,
+/// `!model <name>`, `!estop [network]` and `!help`. A trailing all-digit word
+/// is taken as the OTP code. Anything else is left for the agent.
+pub fn parse_command(text: &str) -> Option<AdminRequest> {
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    let command = words.first()?.strip_prefix('!')?.to_ascii_lowercase();
+    let otp = match words.last() {
+        Some(last) if words.len() > 1 && last.chars().all(|c| c.is_ascii_digit()) => {
+            words.pop().map(str::to_string)
+        }
+        _ => None,
+    };
+    let args = &words[1..];
+    let command = match (command.as_str(), args) {
+        ("help", []) => AdminCommand::Help,
+        ("status", []) => AdminCommand::Status,
+        ("budget", []) => AdminCommand::Budget,
+        ("pause", []) => AdminCommand::Pause(None),
+        ("pause", [channel]) => AdminCommand::Pause(Some(channel.to_ascii_lowercase())),
+        ("resume", []) => AdminCommand::Resume(None),
+        ("resume", [channel]) => AdminCommand::Resume(Some(channel.to_ascii_lowercase())),
+        ("model", [model]) => AdminCommand::Model((*model).to_string()),
+        ("estop", []) => AdminCommand::Estop { network: false },
+        ("estop", [level]) if level.eq_ignore_ascii_case("network") => {
+            AdminCommand::Estop { network: true }
+        }
+        _ => return None,
+    };
+    Some(AdminRequest { command, otp })
+}
+
+/// Owner check, OTP and the commands that do not need the channel runtime.
+pub struct ChannelAdmin {
+    owners: Vec<String>,
+    require_otp: bool,
+    otp: Option<OtpValidator>,
+    estop: EstopConfig,
+    cost: CostConfig,
+    config_dir: PathBuf,
+    workspace_dir: PathBuf,
+}
+
+impl ChannelAdmin {
+    /// `None` unless `[channels_config.admin]` is enabled with owners.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let admin = &config.channels_config.admin;
+        if !admin.enabled || admin.owners.is_empty() {
+            return None;
+        }
+        let config_dir = config
+            .config_path
+            .parent()
+            .unwrap_or(&config.workspace_dir)
+            .to_path_buf();
+        let otp = if admin.require_otp && config.security.otp.enabled {
+            let store = SecretStore::new(&config_dir, config.secrets.encrypt);
+            OtpValidator::from_config(&config.security.otp, &config_dir, &store)
+                .map(|(validator, _)| validator)
+                .map_err(|e| {
+                    tracing::warn!(
+                        "OTP unavailable; admin commands that need it will be refused: {e}"
+                    );
+                })
+                .ok()
+        } else {
+            None
+        };
+        Some(Self::new(
+            admin,
+            otp,
+            config.security.estop.clone(),
+            config.cost.clone(),
+            &config_dir,
+            &config.workspace_dir,
+        ))
+    }
+
+    fn new(
+        config: &ChannelAdminConfig,
+        otp: Option<OtpValidator>,
+        estop: EstopConfig,
+        cost: CostConfig,
+        config_dir: &Path,
+        workspace_dir: &Path,
+    ) -> Self {
+        Self {
+            owners: config
+                .owners
+                .iter()
+                .map(|owner| normalize_owner(owner))
+                .collect(),
+            require_otp: config.require_otp,
+            otp,
+            estop,
+            cost,
+            config_dir: config_dir.to_path_buf(),
+            workspace_dir: workspace_dir.to_path_buf(),
+        }
+    }
+
+    /// Whether the sender is an owner, by `channel:sender` or through an
+    /// approved contact listing the address. Approvals are read from the
+    /// config directory and pin the approved addresses, so neither adding an
+    /// address nor editing `contacts.toml` in the workspace hands out owner
+    /// rights.
+    pub fn is_owner(&self, channel: &str, sender: &str) -> bool {
+        let direct = super::verification::identity(channel, sender);
+        if self.owners.contains(&direct) {
+            return true;
+        }
+        let book = crate::contacts::ContactBook::new(&self.workspace_dir, &self.config_dir);
+        let contacts = match book.list() {
+            Ok(contacts) => contacts,
+            Err(e) => {
+                tracing::warn!("Failed to read contacts for admin owners: {e:#}");
+                return false;
+            }
+        };
+        contacts.iter().any(|contact| {
+            contact.approved
+                && self
+                    .owners
+                    .contains(&format!("contact:{}", contact.name.to_ascii_lowercase()))
+                && contact
+                    .addresses
+                    .iter()
+                    .any(|(ch, address)| ch.eq_ignore_ascii_case(channel) && address == sender)
+        })
+    }
+
+    /// Check the OTP code of a destructive command. `Err` holds the reply.
+    pub fn authorize(&self, request: &AdminRequest) -> Result<(), String> {
+        if !self.require_otp || !request.command.is_destructive() {
+            return Ok(());
+        }
+        let Some(validator) = self.otp.as_ref() else {
+            return Err(i18n::tr("channel.admin_otp_unavailable"));
+        };
+        let Some(code) = request.otp.as_deref() else {
+            return Err(i18n::tr("channel.admin_otp_required"));
+        };
+        match validator.validate(code) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(i18n::tr("channel.admin_otp_invalid")),
+            Err(err) => Err(i18n::tr_args(
+                "channel.admin_failed",
+                &[("details", &err.to_string())],
+            )),
+        }
+    }
+
+    /// Current emergency-stop state, when estop is enabled.
+    pub fn estop_state(&self) -> Option<EstopState> {
+        if !self.estop.enabled {
+            return None;
+        }
+        EstopManager::load(&self.estop, &self.config_dir)
+            .map(|manager| manager.status())
+            .ok()
+    }
+
+    /// Engage the emergency stop. Resuming stays a CLI action.
+    pub fn engage_estop(&self, network: bool) -> Result<EstopState> {
+        if !self.estop.enabled {
+            bail!("Emergency stop is disabled ([security.estop] enabled = false)");
+        }
+        let mut manager = EstopManager::load(&self.estop, &self.config_dir)?;
+        manager.engage(if network {
+            EstopLevel::NetworkKill
+        } else {
+            EstopLevel::KillAll
+        })?;
+        Ok(manager.status())
+    }
+
+    /// Reply for `!budget`.
+    pub fn budget(&self) -> Result<String> {
+        if !self.cost.enabled {
+            return Ok(i18n::tr("channel.admin_budget_disabled"));
+        }
+        let summary = CostTracker::new(self.cost.clone(), &self.workspace_dir)?.get_summary()?;
+        Ok(i18n::tr_args(
+            "channel.admin_budget",
+            &[
+                ("daily", &format!("{:.2}", summary.daily_cost_usd)),
+                ("daily_limit", &format!("{:.2}", self.cost.daily_limit_usd)),
+                ("monthly", &format!("{:.2}", summary.monthly_cost_usd)),
+                (
+                    "monthly_limit",
+                    &format!("{:.2}", self.cost.monthly_limit_usd),
+                ),
+            ],
+        ))
+    }
+}
+
+/// Short description of an estop state for `!status` and `!estop` replies.
+pub fn describe_estop(state: Option<&EstopState>) -> String {
+    let Some(state) = state else {
+        return "disabled".to_string();
+    };
+    let mut engaged = Vec::new();
+    if state.kill_all {
+        engaged.push("kill-all".to_string());
+    }
+    if state.network_kill {
+        engaged.push("network".to_string());
+    }
+    if !state.blocked_domains.is_empty() {
+        engaged.push(format!("domains: {}", state.blocked_domains.join(", ")));
+    }
+    if !state.frozen_tools.is_empty() {
+        engaged.push(format!("tools: {}", state.frozen_tools.join(", ")));
+    }
+    if engaged.is_empty() {
+        "off".to_string()
+    } else {
+        format!("engaged ({})", engaged.join("; "))
+    }
+}
+
+fn normalize_owner(raw: &str) -> String {
+    let raw = raw.trim();
+    match raw.split_once(':') {
+        Some((kind, name)) if kind.eq_ignore_ascii_case("contact") => {
+            format!("contact:{}", name.trim()).to_ascii_lowercase()
+        }
+        Some((channel, sender)) => super::verification::identity(channel, sender),
+        None => raw.to_ascii_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn admin(tmp: &TempDir, owners: &[&str], require_otp: bool) -> ChannelAdmin {
+        let config = ChannelAdminConfig {
+            enabled: true,
+            owners: owners.iter().map(|owner| owner.to_string()).collect(),
+            require_otp,
+        };
+        ChannelAdmin::new(
+            &config,
+            None,
+            EstopConfig::default(),
+            CostConfig::default(),
+            tmp.path(),
+            &tmp.path().join("workspace"),
+        )
+    }
+
+    #[test]
+    fn parse_command_reads_arguments_and_trailing_otp() {
+        let parsed = parse_command("!model gpt-x 123456").unwrap();
+        assert_eq!(parsed.command, AdminCommand::Model("gpt-x".into()));
+        assert_eq!(parsed.otp.as_deref(), Some("123456"));
+
+        assert_eq!(
+            parse_command("!Pause Telegram").unwrap().command,
+            AdminCommand::Pause(Some("telegram".into()))
+        );
+        assert_eq!(
+            parse_command("!estop network").unwrap().command,
+            AdminCommand::Estop { network: true }
+        );
+        assert_eq!(parse_command("!status").unwrap().otp, None);
+        assert!(parse_command("!model").is_none());
+        assert!(parse_command("!status now please").is_none());
+        assert!(parse_command("status").is_none());
+        assert!(parse_command("!!! wow").is_none());
+    }
+
+    #[test]
+    fn owners_match_by_channel_sender_only() {
+        let tmp = TempDir::new().unwrap();
+        let admin = admin(&tmp, &["Telegram:42", "contact:Alice"], false);
+        assert!(admin.is_owner("telegram", "42"));
+        assert!(!admin.is_owner("telegram", "43"));
+        assert!(!admin.is_owner("discord", "42"));
+    }
+
+    #[test]
+    fn contact_owners_must_be_approved() {
+        let tmp = TempDir::new().unwrap();
+        let admin = admin(&tmp, &["contact:Alice"], false);
+        let book = crate::contacts::ContactBook::new(&admin.workspace_dir, &admin.config_dir);
+        let alice = |approved| crate::contacts::Contact {
+            name: "Alice".into(),
+            aliases: Vec::new(),
+            addresses: [("telegram".to_string(), "42".to_string())].into(),
+            preferred_channel: None,
+            notes: None,
+            approved,
+        };
+        book.upsert(alice(true), true).unwrap();
+        assert!(admin.is_owner("telegram", "42"));
+
+        // The agent re-pointing the contact clears its approval.
+        let mut hijacked = alice(true);
+        hijacked
+            .addresses
+            .insert("discord".to_string(), "666".to_string());
+        book.upsert(hijacked, false).unwrap();
+        assert!(!admin.is_owner("discord", "666"));
+        assert!(!admin.is_owner("telegram", "42"));
+
+        // Nor can it approve the contact by rewriting the workspace file.
+        let raw = std::fs::read_to_string(book.path()).unwrap();
+        std::fs::write(
+            book.path(),
+            raw.replace("[[contacts]]", "[[contacts]]\napproved = true"),
+        )
+        .unwrap();
+        assert!(!admin.is_owner("discord", "666"));
+    }
+
+    #[test]
+    fn destructive_commands_need_otp_when_required() {
+        let tmp = TempDir::new().unwrap();
+        let strict = admin(&tmp, &["telegram:42"], true);
+        let status = parse_command("!status").unwrap();
+        let estop = parse_command("!estop 123456").unwrap();
+        assert!(strict.authorize(&status).is_ok());
+        // No validator configured: fail closed.
+        assert!(strict.authorize(&estop).is_err());
+
+        let relaxed = admin(&tmp, &["telegram:42"], false);
+        assert!(relaxed.authorize(&parse_command("!estop").unwrap()).is_ok());
+    }
+
+    #[test]
+    fn describe_estop_lists_engaged_levels() {
+        assert_eq!(describe_estop(None), "disabled");
+        assert_eq!(describe_estop(Some(&EstopState::default())), "off");
+        let state = EstopState {
+            network_kill: true,
+            frozen_tools: vec!["shell".into()],
+            ..EstopState::default()
+        };
+        assert_eq!(
+            describe_estop(Some(&state)),
+            "engaged (network; tools: shell)"
+        );
+    }
+}
@@ -0,0 +1,318 @@
+//! Native command menus built from installed skills and task templates.
+//!
+//! Telegram (`setMyCommands`) and Discord (application commands) show a menu
+//! of `/commands` as the user types. The menu lists the runtime commands plus
+//! one command per task template (`/deploy_report env=prod` runs
+//! `/task deploy-report env=prod`) and per installed workspace skill
+//! (`/weather Berlin` asks the agent to use the `weather` skill). The channel
+//! runtime republishes the menu whenever skills or tasks change.
+
+use super::traits::Channel;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Telegram and Discord both cap a bot's command list at 100 entries.
+pub const MAX_COMMANDS: usize = 100;
+/// Command names: 1-32 chars of `[a-z0-9_]` (the Telegram/Discord intersection).
+const MAX_NAME_LEN: usize = 32;
+/// Discord caps descriptions at 100 chars (Telegram at 256).
+const MAX_DESCRIPTION_LEN: usize = 100;
+/// How often the runtime checks skills and tasks for menu changes.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What a menu command does when invoked.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CommandTarget {
+    /// Handled by the channel runtime itself (`/task`, `/models`, `/model`,
+    /// `/handoff`, `/continue_here`, `/incognito`).
+    Builtin,
+    /// Runs the named task template.
+    Task(String),
+    /// Asks the agent to use the named skill.
+    Skill(String),
+}
+
+/// One entry of a platform command menu.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BotCommand {
+    /// Name without the leading `/`.
+    pub name: String,
+    pub description: String,
+    pub target: CommandTarget,
+}
+
+/// The commands published to channels that support native menus.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandMenu {
+    pub commands: Vec<BotCommand>,
+}
+
+impl CommandMenu {
+    /// Build the menu from the workspace's task templates and skills.
+    /// Commands whose sanitized names collide with an earlier entry are
+    /// dropped, as is everything past [`MAX_COMMANDS`].
+    pub fn load(workspace_dir: &Path) -> Self {
+        let mut menu = Self::default();
+        let mut taken = HashSet::new();
+        for (name, description) in [
+            (
+                "task",
+                "Run a saved task: /task <name> key=value ...".to_string(),
+            ),
+            ("models", "Show or switch the provider".to_string()),
+            ("model", "Show or switch the model".to_string()),
+            (
+                "handoff",
+                "Continue this conversation elsewhere: /handoff <cli|channel>".to_string(),
+            ),
+            (
+                "continue_here",
+                "Pick up a handed-off conversation: /continue_here <code>".to_string(),
+            ),
+            (
+                "incognito",
+                "Nothing saved from this conversation: /incognito [on|off]".to_string(),
+            ),
+        ] {
+            menu.push(&mut taken, name, description, CommandTarget::Builtin);
+        }
+
+        match crate::tasks::TaskStore::for_workspace(workspace_dir).list() {
+            Ok(tasks) => {
+                for task in tasks {
+                    let description = task
+                        .description
+                        .clone()
+                        .unwrap_or_else(|| format!("Run the {} task", task.name));
+                    menu.push(
+                        &mut taken,
+                        &task.name,
+                        description,
+                        CommandTarget::Task(task.name.clone()),
+                    );
+                }
+            }
+            Err(error) => tracing::warn!("Command menu: failed to list tasks: {error}"),
+        }
+
+        let mut skills = crate::skills::load_workspace_skills(workspace_dir);
+        skills.sort_by(|a, b| a.name.cmp(&b.name));
+        for skill in skills {
+            menu.push(
+                &mut taken,
+                &skill.name,
+                skill.description.clone(),
+                CommandTarget::Skill(skill.name.clone()),
+            );
+        }
+        menu
+    }
+
+    fn push(
+        &mut self,
+        taken: &mut HashSet<String>,
+        raw_name: &str,
+        description: String,
+        target: CommandTarget,
+    ) {
+        let Some(name) = command_name(raw_name) else {
+            tracing::debug!("Command menu: '{raw_name}' has no usable command name");
+            return;
+        };
+        if self.commands.len() >= MAX_COMMANDS || !taken.insert(name.clone()) {
+            tracing::debug!("Command menu: skipping /{name} for '{raw_name}'");
+            return;
+        }
+        self.commands.push(BotCommand {
+            name,
+            description: command_description(&description, raw_name),
+            target,
+        });
+    }
+
+    /// Rewrite a menu command into the message the runtime understands:
+    /// `/deploy_report env=prod` → `/task deploy-report env=prod`, and
+    /// `/weather Berlin` → a request to use the `weather` skill. Returns
+    /// `None` for built-in commands and anything that is not a menu command.
+    pub fn expand(&self, content: &str) -> Option<String> {
+        let rest = content.trim().strip_prefix('/')?;
+        let (token, args) = rest
+            .split_once(char::is_whitespace)
+            .map_or((rest, ""), |(token, args)| (token, args.trim()));
+        // Telegram appends the bot name to commands in groups (`/cmd@my_bot`).
+        let name = token
+            .split('@')
+            .next()
+            .unwrap_or(token)
+            .to_ascii_lowercase();
+        let command = self.commands.iter().find(|c| c.name == name)?;
+        match &command.target {
+            CommandTarget::Builtin => None,
+            CommandTarget::Task(task) if args.is_empty() => {
+                Some(format!("{} {task}", crate::tasks::TASK_COMMAND))
+            }
+            CommandTarget::Task(task) => {
+                Some(format!("{} {task} {args}", crate::tasks::TASK_COMMAND))
+            }
+            CommandTarget::Skill(skill) if args.is_empty() => {
+                Some(format!("Use the \"{skill}\" skill."))
+            }
+            CommandTarget::Skill(skill) => Some(format!("Use the \"{skill}\" skill. {args}")),
+        }
+    }
+}
+
+/// Lowercase `raw` and map every other character outside `[a-z0-9_]` to `_`.
+fn command_name(raw: &str) -> Option<String> {
+    let name: String = raw
+        .trim()
+        .chars()
+        .map(|c| {
+            let c = c.to_ascii_lowercase();
+            if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_NAME_LEN)
+        .collect();
+    let name = name.trim_matches('_').to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+fn command_description(description: &str, fallback: &str) -> String {
+    let line = description.lines().next().unwrap_or("").trim();
+    let line = if line.is_empty() { fallback } else { line };
+    if line.chars().count() <= MAX_DESCRIPTION_LEN {
+        return line.to_string();
+    }
+    let mut truncated: String = line.chars().take(MAX_DESCRIPTION_LEN - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Rewrite `content` when it invokes a skill or task menu command.
+pub fn expand_invocation(workspace_dir: &Path, content: &str) -> Option<String> {
+    if !content.trim_start().starts_with('/') {
+        return None;
+    }
+    CommandMenu::load(workspace_dir).expand(content)
+}
+
+/// Publish the menu to every channel now, then again whenever skills or
+/// task templates change. Runs until the task is aborted.
+pub async fn publish_and_refresh(channels: Vec<Arc<dyn Channel>>, workspace_dir: &Path) {
+    let mut published: Option<CommandMenu> = None;
+    let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+    loop {
+        interval.tick().await;
+        let menu = CommandMenu::load(workspace_dir);
+        if published.as_ref() == Some(&menu) {
+            continue;
+        }
+        for channel in &channels {
+            match channel.register_commands(&menu.commands).await {
+                Ok(true) => tracing::info!(
+                    "{}: published {} menu commands",
+                    channel.name(),
+                    menu.commands.len()
+                ),
+                Ok(false) => {}
+                Err(error) => {
+                    tracing::warn!(
+                        "{}: failed to publish command menu: {error}",
+                        channel.name()
+                    );
+                }
+            }
+        }
+        published = Some(menu);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn workspace_with_task_and_skill() -> TempDir {
+        let tmp = TempDir::new().unwrap();
+        crate::tasks::TaskStore::for_workspace(tmp.path())
+            .save(&crate::tasks::TaskTemplate {
+                name: "deploy-report".into(),
+                description: Some("Summarize the last deploy".into()),
+                prompt: "Report on {{env}}".into(),
+                inputs: vec![crate::tasks::TaskInput {
+                    name: "env".into(),
+                    description: None,
+                    default: None,
+                }],
+                tools: Vec::new(),
+            })
+            .unwrap();
+        let skill_dir = tmp.path().join("skills/weather");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.toml"),
+            "[skill]\nname = \"weather\"\ndescription = \"Forecasts for any city\"\n",
+        )
+        .unwrap();
+        tmp
+    }
+
+    #[test]
+    fn menu_lists_builtins_tasks_and_skills() {
+        let tmp = workspace_with_task_and_skill();
+        let menu = CommandMenu::load(tmp.path());
+        let names: Vec<_> = menu.commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "task",
+                "models",
+                "model",
+                "handoff",
+                "continue_here",
+                "incognito",
+                "deploy_report",
+                "weather"
+            ]
+        );
+        assert_eq!(menu.commands[6].description, "Summarize the last deploy");
+        assert_eq!(
+            menu.commands[7].target,
+            CommandTarget::Skill("weather".into())
+        );
+    }
+
+    #[test]
+    fn expand_rewrites_task_and_skill_commands() {
+        let tmp = workspace_with_task_and_skill();
+        let menu = CommandMenu::load(tmp.path());
+        assert_eq!(
+            menu.expand("/deploy_report@zc_bot env=prod").as_deref(),
+            Some("/task deploy-report env=prod")
+        );
+        assert_eq!(
+            menu.expand("/weather Berlin").as_deref(),
+            Some("Use the \"weather\" skill. Berlin")
+        );
+        assert!(menu.expand("/model gpt-5").is_none());
+        assert!(menu.expand("/unknown").is_none());
+        assert!(menu.expand("weather").is_none());
+    }
+
+    #[test]
+    fn names_and_descriptions_fit_platform_limits() {
+        assert_eq!(command_name("My Skill-v2").as_deref(), Some("my_skill_v2"));
+        assert_eq!(command_name("---").as_deref(), None);
+        assert_eq!(command_name(&"a".repeat(40)).unwrap().len(), MAX_NAME_LEN);
+        assert_eq!(command_description("", "fallback"), "fallback");
+        let long = command_description(&"x".repeat(300), "f");
+        assert_eq!(long.chars().count(), MAX_DESCRIPTION_LEN);
+        assert!(long.ends_with('…'));
+    }
+}
@@ -1,4 +1,5 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelMessage, GroupContext, SendMessage};
+use crate::config::StreamMode;
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
@@ -16,6 +17,9 @@ pub struct DiscordChannel {
     listen_to_bots: bool,
     mention_only: bool,
     typing_handles: Mutex<HashMap<String, tokio::task::JoinHandle<()>>>,
+    stream_mode: StreamMode,
+    draft_update_interval_ms: u64,
+    last_draft_edit: Mutex<HashMap<String, std::time::Instant>>,
 }
 
 impl DiscordChannel {
@@ -33,9 +37,57 @@ impl DiscordChannel {
             listen_to_bots,
             mention_only,
             typing_handles: Mutex::new(HashMap::new()),
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            last_draft_edit: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Configure streaming mode for progressive draft updates.
+    pub fn with_streaming(
+        mut self,
+        stream_mode: StreamMode,
+        draft_update_interval_ms: u64,
+    ) -> Self {
+        self.stream_mode = stream_mode;
+        self.draft_update_interval_ms = draft_update_interval_ms;
+        self
+    }
+
+    fn message_url(channel_id: &str, message_id: &str) -> String {
+        format!("https://discord.com/api/v10/channels/{channel_id}/messages/{message_id}")
+    }
+
+    async fn edit_message(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        content: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        Ok(self
+            .http_client()
+            .patch(Self::message_url(channel_id, message_id))
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&json!({ "content": content }))
+            .send()
+            .await?)
+    }
+
+    async fn delete_message(&self, channel_id: &str, message_id: &str) -> anyhow::Result<()> {
+        let resp = self
+            .http_client()
+            .delete(Self::message_url(channel_id, message_id))
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            tracing::debug!("Discord delete message failed ({status}): {err}");
+        }
+        Ok(())
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.discord")
     }
@@ -53,6 +105,64 @@ impl DiscordChannel {
         base64_decode(part)
     }
 
+    /// Acknowledge a slash command and turn it into a channel message.
+    /// Unauthorized users get an ephemeral refusal instead.
+    async fn accept_slash_command(&self, invocation: SlashInvocation) -> Option<ChannelMessage> {
+        let allowed = self.is_user_allowed(&invocation.user_id);
+        let guild_ok = match (&self.guild_id, &invocation.guild_id) {
+            (Some(filter), Some(guild)) => filter == guild,
+            _ => true,
+        };
+        let response = if allowed && guild_ok {
+            json!({ "type": 4, "data": { "content": invocation.content } })
+        } else {
+            tracing::warn!(
+                "Discord: ignoring slash command from unauthorized user: {}",
+                invocation.user_id
+            );
+            // Flag 64 = ephemeral: only the invoking user sees the reply.
+            json!({ "type": 4, "data": { "content": "Not authorized.", "flags": 64 } })
+        };
+        let url = format!(
+            "https://discord.com/api/v10/interactions/{}/{}/callback",
+            invocation.interaction_id, invocation.interaction_token
+        );
+        match self.http_client().post(&url).json(&response).send().await {
+            Ok(resp) if !resp.status().is_success() => {
+                let status = resp.status();
+                let err = resp.text().await.unwrap_or_default();
+                tracing::debug!("Discord interaction response failed ({status}): {err}");
+            }
+            Ok(_) => {}
+            Err(err) => tracing::debug!("Discord interaction response request failed: {err}"),
+        }
+        if !(allowed && guild_ok) {
+            return None;
+        }
+
+        Some(ChannelMessage {
+            id: format!("discord_interaction_{}", invocation.interaction_id),
+            sender: invocation.user_id.clone(),
+            reply_target: if invocation.channel_id.is_empty() {
+                invocation.user_id
+            } else {
+                invocation.channel_id
+            },
+            content: invocation.content,
+            channel: "discord".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: None,
+            // Slash commands are addressed to the bot by definition.
+            group: invocation.guild_id.map(|_| GroupContext {
+                mentions_bot: true,
+                replies_to_bot: false,
+            }),
+        })
+    }
+
     fn spawn_inbound_ack_reaction(&self, channel_id: &str, message_id: &str) {
         if channel_id.is_empty() || message_id.is_empty() {
             return;
@@ -198,6 +308,13 @@ fn split_message_for_discord(message: &str) -> Vec<String> {
     chunks
 }
 
+/// Truncate mid-stream draft text to Discord's message limit (char-safe).
+fn truncate_for_discord_draft(text: &str) -> &str {
+    text.char_indices()
+        .nth(DISCORD_MAX_MESSAGE_LENGTH)
+        .map_or(text, |(idx, _)| &text[..idx])
+}
+
 /// URL-encode a Unicode emoji for use in Discord reaction API paths.
 ///
 /// Discord's reaction endpoints accept raw Unicode emoji in the URL path,
@@ -224,6 +341,99 @@ fn contains_bot_mention(content: &str, bot_user_id: &str) -> bool {
     content.contains(&tags[0]) || content.contains(&tags[1])
 }
 
+/// Describe how a guild message relates to the bot. Returns `None` for DMs.
+fn group_context(message: &serde_json::Value, bot_user_id: &str) -> Option<GroupContext> {
+    message.get("guild_id")?;
+    let content = message
+        .get("content")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or("");
+    let replies_to_bot = message
+        .get("referenced_message")
+        .and_then(|referenced| referenced.get("author"))
+        .and_then(|author| author.get("id"))
+        .and_then(serde_json::Value::as_str)
+        .is_some_and(|id| id == bot_user_id);
+    Some(GroupContext {
+        mentions_bot: contains_bot_mention(content, bot_user_id),
+        replies_to_bot,
+    })
+}
+
+/// A slash command invoked from the Discord command menu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SlashInvocation {
+    interaction_id: String,
+    interaction_token: String,
+    user_id: String,
+    channel_id: String,
+    guild_id: Option<String>,
+    /// The command as message text, e.g. `/deploy_report env=prod`.
+    content: String,
+}
+
+/// Parse an `INTERACTION_CREATE` payload for an application (slash) command.
+fn parse_slash_interaction(d: &serde_json::Value) -> Option<SlashInvocation> {
+    // Type 2 = APPLICATION_COMMAND; components and autocomplete are ignored.
+    if d.get("type").and_then(serde_json::Value::as_u64) != Some(2) {
+        return None;
+    }
+    let str_at = |value: &serde_json::Value, pointer: &str| {
+        value
+            .pointer(pointer)
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string)
+    };
+    let data = d.get("data")?;
+    let name = str_at(data, "/name")?;
+    let input = data
+        .get("options")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .find(|option| option.get("name").and_then(serde_json::Value::as_str) == Some("input"))
+        .and_then(|option| option.get("value"))
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .unwrap_or("");
+    Some(SlashInvocation {
+        interaction_id: str_at(d, "/id")?,
+        interaction_token: str_at(d, "/token")?,
+        // Guild interactions carry `member.user`, DMs carry `user`.
+        user_id: str_at(d, "/member/user/id").or_else(|| str_at(d, "/user/id"))?,
+        channel_id: str_at(d, "/channel_id").unwrap_or_default(),
+        guild_id: str_at(d, "/guild_id"),
+        content: if input.is_empty() {
+            format!("/{name}")
+        } else {
+            format!("/{name} {input}")
+        },
+    })
+}
+
+/// Application command definitions for a bulk overwrite. Every command takes
+/// one optional free-text `input` option carrying its arguments.
+fn application_commands_body(commands: &[super::command_menu::BotCommand]) -> serde_json::Value {
+    serde_json::Value::Array(
+        commands
+            .iter()
+            .map(|c| {
+                json!({
+                    "name": c.name,
+                    "description": c.description,
+                    "type": 1,
+                    "options": [{
+                        "type": 3,
+                        "name": "input",
+                        "description": "Arguments",
+                        "required": false,
+                    }],
+                })
+            })
+            .collect(),
+    )
+}
+
 fn normalize_incoming_content(
     content: &str,
     mention_only: bool,
@@ -458,8 +668,19 @@ impl Channel for DiscordChannel {
                         _ => {}
                     }
 
-                    // Only handle MESSAGE_CREATE (opcode 0, type "MESSAGE_CREATE")
+                    // Handle slash commands and MESSAGE_CREATE (opcode 0 dispatches)
                     let event_type = event.get("t").and_then(|t| t.as_str()).unwrap_or("");
+                    if event_type == "INTERACTION_CREATE" {
+                        let Some(invocation) = event.get("d").and_then(parse_slash_interaction) else {
+                            continue;
+                        };
+                        if let Some(channel_msg) = self.accept_slash_command(invocation).await {
+                            if tx.send(channel_msg).await.is_err() {
+                                break;
+                            }
+                        }
+                        continue;
+                    }
                     if event_type != "MESSAGE_CREATE" {
                         continue;
                     }
@@ -541,6 +762,7 @@ impl Channel for DiscordChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        group: group_context(d, &bot_user_id),
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -596,6 +818,180 @@ impl Channel for DiscordChannel {
         Ok(())
     }
 
+    fn max_message_length(&self) -> Option<usize> {
+        Some(DISCORD_MAX_MESSAGE_LENGTH)
+    }
+
+    async fn send_file(
+        &self,
+        message: &SendMessage,
+        file_name: &str,
+        contents: &[u8],
+    ) -> anyhow::Result<bool> {
+        let url = format!(
+            "https://discord.com/api/v10/channels/{}/messages",
+            message.recipient
+        );
+        let caption = truncate_for_discord_draft(&message.content);
+        let form = reqwest::multipart::Form::new()
+            .text("payload_json", json!({ "content": caption }).to_string())
+            .part(
+                "files[0]",
+                reqwest::multipart::Part::bytes(contents.to_vec()).file_name(file_name.to_string()),
+            );
+
+        let resp = self
+            .http_client()
+            .post(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Discord file upload failed ({status}): {err}");
+        }
+        Ok(true)
+    }
+
+    async fn register_commands(
+        &self,
+        commands: &[super::command_menu::BotCommand],
+    ) -> anyhow::Result<bool> {
+        let application_id = Self::bot_user_id_from_token(&self.bot_token)
+            .ok_or_else(|| anyhow::anyhow!("cannot derive application id from bot token"))?;
+        // Guild commands update instantly; global ones can take a while to
+        // propagate to clients.
+        let url = match &self.guild_id {
+            Some(guild_id) => format!(
+                "https://discord.com/api/v10/applications/{application_id}/guilds/{guild_id}/commands"
+            ),
+            None => format!("https://discord.com/api/v10/applications/{application_id}/commands"),
+        };
+        let resp = self
+            .http_client()
+            .put(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&application_commands_body(commands))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Discord command registration failed ({status}): {err}");
+        }
+        Ok(true)
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
+        }
+
+        let initial_text = if message.content.is_empty() {
+            "..."
+        } else {
+            truncate_for_discord_draft(&message.content)
+        };
+        let url = format!(
+            "https://discord.com/api/v10/channels/{}/messages",
+            message.recipient
+        );
+        let resp = self
+            .http_client()
+            .post(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&json!({ "content": initial_text }))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Discord send draft failed ({status}): {err}");
+        }
+
+        let resp_json: serde_json::Value = resp.json().await?;
+        let message_id = resp_json
+            .get("id")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string);
+
+        self.last_draft_edit
+            .lock()
+            .insert(message.recipient.clone(), std::time::Instant::now());
+
+        Ok(message_id)
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        // Rate-limit edits per channel
+        {
+            let last_edits = self.last_draft_edit.lock();
+            if let Some(last_time) = last_edits.get(recipient) {
+                let elapsed = u64::try_from(last_time.elapsed().as_millis()).unwrap_or(u64::MAX);
+                if elapsed < self.draft_update_interval_ms {
+                    return Ok(());
+                }
+            }
+        }
+
+        let resp = self
+            .edit_message(recipient, message_id, truncate_for_discord_draft(text))
+            .await?;
+        if resp.status().is_success() {
+            self.last_draft_edit
+                .lock()
+                .insert(recipient.to_string(), std::time::Instant::now());
+        } else {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            tracing::debug!("Discord draft edit failed ({status}): {err}");
+        }
+
+        Ok(())
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        let text = super::strip_tool_call_tags(text);
+        self.last_draft_edit.lock().remove(recipient);
+
+        // Too long for one message: replace the draft with chunked sends.
+        if text.chars().count() > DISCORD_MAX_MESSAGE_LENGTH {
+            self.delete_message(recipient, message_id).await?;
+            return self.send(&SendMessage::new(text, recipient)).await;
+        }
+
+        let resp = self.edit_message(recipient, message_id, &text).await?;
+        if resp.status().is_success() {
+            return Ok(());
+        }
+
+        tracing::warn!("Discord finalize_draft edit failed; falling back to a new message");
+        self.send(&SendMessage::new(text, recipient)).await
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+        self.delete_message(recipient, message_id).await
+    }
+
     async fn add_reaction(
         &self,
         channel_id: &str,
@@ -808,6 +1204,44 @@ mod tests {
         assert!(cleaned.is_none());
     }
 
+    #[test]
+    fn group_context_reports_mentions_and_replies_in_guilds() {
+        let dm = json!({"content": "<@12345> hi"});
+        assert!(group_context(&dm, "12345").is_none());
+
+        let mention = json!({"guild_id": "g1", "content": "<@12345> hi"});
+        let ctx = group_context(&mention, "12345").unwrap();
+        assert!(ctx.mentions_bot);
+        assert!(!ctx.replies_to_bot);
+
+        let reply = json!({
+            "guild_id": "g1",
+            "content": "thanks",
+            "referenced_message": {"author": {"id": "12345"}}
+        });
+        let ctx = group_context(&reply, "12345").unwrap();
+        assert!(!ctx.mentions_bot);
+        assert!(ctx.replies_to_bot);
+    }
+
+    #[test]
+    fn supports_draft_updates_respects_stream_mode() {
+        let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
+        assert!(!ch.supports_draft_updates());
+        let ch = ch.with_streaming(StreamMode::Partial, 750);
+        assert!(ch.supports_draft_updates());
+        assert_eq!(ch.draft_update_interval_ms, 750);
+    }
+
+    #[test]
+    fn truncate_for_discord_draft_is_char_safe() {
+        let short = "hello";
+        assert_eq!(truncate_for_discord_draft(short), short);
+        let long = "é".repeat(DISCORD_MAX_MESSAGE_LENGTH + 10);
+        let truncated = truncate_for_discord_draft(&long);
+        assert_eq!(truncated.chars().count(), DISCORD_MAX_MESSAGE_LENGTH);
+    }
+
     // Message splitting tests
 
     #[test]
@@ -1212,4 +1646,59 @@ mod tests {
         let result = process_attachments(&attachments, &client).await;
         assert!(result.is_empty());
     }
+
+    #[test]
+    fn parses_slash_command_interactions() {
+        let guild = json!({
+            "type": 2,
+            "id": "111",
+            "token": "tok",
+            "guild_id": "g1",
+            "channel_id": "c1",
+            "member": { "user": { "id": "u1" } },
+            "data": {
+                "name": "deploy_report",
+                "options": [{ "name": "input", "type": 3, "value": " env=prod " }],
+            },
+        });
+        assert_eq!(
+            parse_slash_interaction(&guild),
+            Some(SlashInvocation {
+                interaction_id: "111".into(),
+                interaction_token: "tok".into(),
+                user_id: "u1".into(),
+                channel_id: "c1".into(),
+                guild_id: Some("g1".into()),
+                content: "/deploy_report env=prod".into(),
+            })
+        );
+
+        let dm = json!({
+            "type": 2,
+            "id": "222",
+            "token": "tok",
+            "channel_id": "dm1",
+            "user": { "id": "u2" },
+            "data": { "name": "task" },
+        });
+        let invocation = parse_slash_interaction(&dm).unwrap();
+        assert_eq!(invocation.user_id, "u2");
+        assert_eq!(invocation.content, "/task");
+
+        let component = json!({ "type": 3, "id": "1", "token": "t", "data": {} });
+        assert!(parse_slash_interaction(&component).is_none());
+    }
+
+    #[test]
+    fn application_commands_take_optional_input() {
+        let body = application_commands_body(&[crate::channels::command_menu::BotCommand {
+            name: "weather".into(),
+            description: "Forecasts".into(),
+            target: crate::channels::command_menu::CommandTarget::Skill("weather".into()),
+        }]);
+        assert_eq!(body[0]["name"], "weather");
+        assert_eq!(body[0]["type"], 1);
+        assert_eq!(body[0]["options"][0]["name"], "input");
+        assert_eq!(body[0]["options"][0]["required"], false);
+    }
 }
@@ -460,6 +460,7 @@ impl EmailChannel {
                 channel: "email".to_string(),
                 timestamp: email.timestamp,
                 thread_ts: None,
+                group: None,
             };
 
             if tx.send(msg).await.is_err() {
@@ -472,22 +473,27 @@ impl EmailChannel {
     }
 
     fn create_smtp_transport(&self) -> Result<SmtpTransport> {
-        let creds = Credentials::new(self.config.username.clone(), self.config.password.clone());
-        let transport = if self.config.smtp_tls {
-            SmtpTransport::relay(&self.config.smtp_host)?
-                .port(self.config.smtp_port)
-                .credentials(creds)
-                .build()
-        } else {
-            SmtpTransport::builder_dangerous(&self.config.smtp_host)
-                .port(self.config.smtp_port)
-                .credentials(creds)
-                .build()
-        };
-        Ok(transport)
+        smtp_transport(&self.config)
     }
 }
 
+/// SMTP transport for the configured relay (shared with the `email_send` tool).
+pub(crate) fn smtp_transport(config: &EmailConfig) -> Result<SmtpTransport> {
+    let creds = Credentials::new(config.username.clone(), config.password.clone());
+    let transport = if config.smtp_tls {
+        SmtpTransport::relay(&config.smtp_host)?
+            .port(config.smtp_port)
+            .credentials(creds)
+            .build()
+    } else {
+        SmtpTransport::builder_dangerous(&config.smtp_host)
+            .port(config.smtp_port)
+            .credentials(creds)
+            .build()
+    };
+    Ok(transport)
+}
+
 /// Internal struct for parsed email data
 struct ParsedEmail {
     _uid: u32,
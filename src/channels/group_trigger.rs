@@ -0,0 +1,187 @@
+//! Group-chat engagement triggers.
+//!
+//! Decides whether an inbound group message reaches the agent, based on
+//! `[channels_config.group_triggers.<channel>]`. Messages without group
+//! metadata (direct chats, channels that do not report it) always engage.
+
+use super::traits::ChannelMessage;
+use crate::config::GroupTriggerConfig;
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+
+/// What to do with an inbound message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupDecision {
+    /// Run the agent and reply.
+    Engage,
+    /// Store the message in memory as context without replying.
+    Observe,
+    /// Drop the message.
+    Ignore,
+}
+
+/// Compiled trigger rules for one channel.
+#[derive(Debug, Clone)]
+pub struct GroupTrigger {
+    mention: bool,
+    replies_to_bot: bool,
+    keywords: Vec<Regex>,
+    observe: bool,
+}
+
+impl GroupTrigger {
+    pub fn from_config(config: &GroupTriggerConfig) -> Self {
+        let keywords = config
+            .keywords
+            .iter()
+            .filter_map(|pattern| {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|err| {
+                        tracing::warn!("Ignoring invalid group trigger keyword '{pattern}': {err}");
+                    })
+                    .ok()
+            })
+            .collect();
+        Self {
+            mention: config.mention,
+            replies_to_bot: config.replies_to_bot,
+            keywords,
+            observe: config.observe,
+        }
+    }
+
+    pub fn decide(&self, msg: &ChannelMessage) -> GroupDecision {
+        let Some(group) = &msg.group else {
+            return GroupDecision::Engage;
+        };
+        let triggered = (self.mention && group.mentions_bot)
+            || (self.replies_to_bot && group.replies_to_bot)
+            || self.keywords.iter().any(|re| re.is_match(&msg.content));
+        if triggered {
+            GroupDecision::Engage
+        } else if self.observe {
+            GroupDecision::Observe
+        } else {
+            GroupDecision::Ignore
+        }
+    }
+}
+
+/// Trigger rules for every configured channel.
+#[derive(Debug, Clone, Default)]
+pub struct GroupTriggers {
+    by_channel: HashMap<String, GroupTrigger>,
+}
+
+impl GroupTriggers {
+    pub fn from_config(config: &HashMap<String, GroupTriggerConfig>) -> Self {
+        Self {
+            by_channel: config
+                .iter()
+                .map(|(channel, trigger)| (channel.clone(), GroupTrigger::from_config(trigger)))
+                .collect(),
+        }
+    }
+
+    pub fn decide(&self, msg: &ChannelMessage) -> GroupDecision {
+        self.by_channel
+            .get(&msg.channel)
+            .map_or(GroupDecision::Engage, |trigger| trigger.decide(msg))
+    }
+}
+
+/// Memory key for an observed group message.
+pub fn observation_memory_key(msg: &ChannelMessage) -> String {
+    format!("group_{}_{}_{}", msg.channel, msg.reply_target, msg.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::GroupContext;
+
+    fn group_message(content: &str, group: Option<GroupContext>) -> ChannelMessage {
+        ChannelMessage {
+            id: "m1".into(),
+            sender: "alice".into(),
+            reply_target: "-100".into(),
+            content: content.into(),
+            channel: "telegram".into(),
+            timestamp: 0,
+            thread_ts: None,
+            group,
+        }
+    }
+
+    fn triggers(config: GroupTriggerConfig) -> GroupTriggers {
+        GroupTriggers::from_config(&HashMap::from([("telegram".to_string(), config)]))
+    }
+
+    #[test]
+    fn direct_messages_and_unconfigured_channels_always_engage() {
+        let set = triggers(GroupTriggerConfig::default());
+        assert_eq!(
+            set.decide(&group_message("hi", None)),
+            GroupDecision::Engage
+        );
+
+        let mut other = group_message("hi", Some(GroupContext::default()));
+        other.channel = "discord".into();
+        assert_eq!(set.decide(&other), GroupDecision::Engage);
+    }
+
+    #[test]
+    fn group_messages_engage_on_mention_reply_or_keyword() {
+        let set = triggers(GroupTriggerConfig {
+            keywords: vec![r"\bdeploy\b".into()],
+            ..GroupTriggerConfig::default()
+        });
+        let mention = GroupContext {
+            mentions_bot: true,
+            replies_to_bot: false,
+        };
+        let reply = GroupContext {
+            mentions_bot: false,
+            replies_to_bot: true,
+        };
+        assert_eq!(
+            set.decide(&group_message("hey", Some(mention))),
+            GroupDecision::Engage
+        );
+        assert_eq!(
+            set.decide(&group_message("ok", Some(reply))),
+            GroupDecision::Engage
+        );
+        assert_eq!(
+            set.decide(&group_message(
+                "please DEPLOY now",
+                Some(GroupContext::default())
+            )),
+            GroupDecision::Engage
+        );
+        assert_eq!(
+            set.decide(&group_message("lunch?", Some(GroupContext::default()))),
+            GroupDecision::Ignore
+        );
+    }
+
+    #[test]
+    fn disabled_triggers_fall_through_to_observe() {
+        let set = triggers(GroupTriggerConfig {
+            mention: false,
+            replies_to_bot: true,
+            keywords: Vec::new(),
+            observe: true,
+        });
+        let mention = GroupContext {
+            mentions_bot: true,
+            replies_to_bot: false,
+        };
+        assert_eq!(
+            set.decide(&group_message("hey", Some(mention))),
+            GroupDecision::Observe
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Incognito channel sessions (`/incognito`, `[channels_config.incognito]`).
+//!
+//! A sender turns incognito on with `/incognito` (or writes on a channel
+//! listed in `channels`). While active, nothing from the conversation is
+//! written to memory, memory-writing tools are withheld, and runtime traces
+//! keep metadata only. The conversation history lives in RAM and is dropped
+//! when the session ends or has been idle for `ttl_minutes`.
+
+use crate::config::IncognitoConfig;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Tools that persist what they are given.
+pub const PERSISTENT_TOOLS: &[&str] = &["memory_store", "kv"];
+
+/// `/incognito [on|off|status]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncognitoCommand {
+    On,
+    Off,
+    Status,
+}
+
+/// Parse `/incognito [on|off|status]` (with an optional `@bot` suffix).
+pub fn parse_command(text: &str) -> Option<IncognitoCommand> {
+    let mut words = text.split_whitespace();
+    let command = words.next()?.split('@').next()?.to_ascii_lowercase();
+    if command != "/incognito" {
+        return None;
+    }
+    match words.next().map(str::to_ascii_lowercase).as_deref() {
+        None | Some("on") => Some(IncognitoCommand::On),
+        Some("off" | "stop" | "end") => Some(IncognitoCommand::Off),
+        Some("status") => Some(IncognitoCommand::Status),
+        Some(_) => None,
+    }
+}
+
+/// Incognito state per conversation (`conversation_history_key`).
+pub struct IncognitoSessions {
+    channels: HashSet<String>,
+    ttl: Duration,
+    /// Conversation key → last activity.
+    active: Mutex<HashMap<String, Instant>>,
+}
+
+impl Default for IncognitoSessions {
+    fn default() -> Self {
+        Self::from_config(&IncognitoConfig::default())
+    }
+}
+
+impl IncognitoSessions {
+    pub fn from_config(config: &IncognitoConfig) -> Self {
+        Self {
+            channels: config
+                .channels
+                .iter()
+                .map(|channel| channel.trim().to_ascii_lowercase())
+                .collect(),
+            ttl: Duration::from_secs(config.ttl_minutes.saturating_mul(60)),
+            active: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether every conversation on `channel` is incognito.
+    pub fn is_always(&self, channel: &str) -> bool {
+        self.channels.contains(&channel.to_ascii_lowercase())
+    }
+
+    /// Idle time after which a session expires.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Whether the conversation is incognito. Always-incognito channels are
+    /// tracked on first use so their history expires too.
+    pub fn is_active(&self, channel: &str, key: &str) -> bool {
+        let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        if self.is_always(channel) {
+            active.entry(key.to_string()).or_insert_with(Instant::now);
+            return true;
+        }
+        active.contains_key(key)
+    }
+
+    /// Start (or restart) a session.
+    pub fn start(&self, key: &str) {
+        self.active
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.to_string(), Instant::now());
+    }
+
+    /// End a session; returns whether one was active.
+    pub fn stop(&self, key: &str) -> bool {
+        self.active
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key)
+            .is_some()
+    }
+
+    /// Record activity so the session does not expire mid-conversation.
+    pub fn touch(&self, key: &str) {
+        if let Some(last) = self
+            .active
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(key)
+        {
+            *last = Instant::now();
+        }
+    }
+
+    /// Remove and return sessions idle for longer than the TTL.
+    pub fn take_expired(&self, now: Instant) -> Vec<String> {
+        let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        let expired: Vec<String> = active
+            .iter()
+            .filter(|(_, last)| now.saturating_duration_since(**last) > self.ttl)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            active.remove(key);
+        }
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_incognito_commands() {
+        assert_eq!(parse_command("/incognito"), Some(IncognitoCommand::On));
+        assert_eq!(
+            parse_command("/incognito@zeroclaw_bot off"),
+            Some(IncognitoCommand::Off)
+        );
+        assert_eq!(
+            parse_command("/Incognito status"),
+            Some(IncognitoCommand::Status)
+        );
+        assert_eq!(parse_command("/incognito maybe"), None);
+        assert_eq!(parse_command("go incognito"), None);
+    }
+
+    #[test]
+    fn sessions_expire_after_idle_ttl() {
+        let sessions = IncognitoSessions::from_config(&IncognitoConfig {
+            channels: vec!["Signal".into()],
+            ttl_minutes: 1,
+        });
+        assert!(!sessions.is_active("telegram", "telegram_alice"));
+        sessions.start("telegram_alice");
+        assert!(sessions.is_active("telegram", "telegram_alice"));
+        assert!(sessions.is_active("signal", "signal_bob"));
+
+        let later = Instant::now() + Duration::from_secs(61);
+        let mut expired = sessions.take_expired(later);
+        expired.sort();
+        assert_eq!(expired, ["signal_bob", "telegram_alice"]);
+        assert!(!sessions.is_active("telegram", "telegram_alice"));
+        assert!(!sessions.stop("telegram_alice"));
+    }
+}
@@ -822,6 +822,7 @@ impl LarkChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        group: None,
                     };
 
                     self.spawn_inbound_ack_reaction(Some(lark_msg.message_id.clone()), reaction_locale);
@@ -1025,6 +1026,7 @@ impl LarkChannel {
             channel: "lark".to_string(),
             timestamp,
             thread_ts: None,
+            group: None,
         });
 
         messages
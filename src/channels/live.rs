@@ -0,0 +1,242 @@
+//! Runtime controls for the running channel dispatcher.
+//!
+//! `start_channels` registers itself here so the control socket can act on
+//! the live daemon: pausing a channel drops its inbound messages until it is
+//! resumed, flushing empties the inbound message bus, and the session list
+//! reports the per-sender conversation histories held in memory. Paused
+//! channels stay paused across listener restarts within the process.
+
+use super::ConversationHistoryMap;
+use crate::providers::ChatMessage;
+use anyhow::{bail, Result};
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, Weak};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+/// How long a flush waits for the dispatcher to answer.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Asks the dispatcher to drop queued messages; answered with the count.
+pub(super) type FlushRequest = oneshot::Sender<usize>;
+
+struct Runtime {
+    id: u64,
+    channels: Vec<String>,
+    histories: Weak<Mutex<HashMap<String, Vec<ChatMessage>>>>,
+    flush: mpsc::Sender<FlushRequest>,
+}
+
+static PAUSED: LazyLock<RwLock<BTreeSet<String>>> = LazyLock::new(|| RwLock::new(BTreeSet::new()));
+static RUNTIME: LazyLock<RwLock<Option<Runtime>>> = LazyLock::new(|| RwLock::new(None));
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Keeps the dispatcher reachable; unregisters it when dropped.
+pub(super) struct Registration(u64);
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let mut runtime = RUNTIME.write();
+        // A restarted dispatcher may already have registered itself.
+        if runtime.as_ref().is_some_and(|runtime| runtime.id == self.0) {
+            runtime.take();
+        }
+    }
+}
+
+/// Make the running dispatcher reachable from the control socket.
+pub(super) fn register(
+    mut channels: Vec<String>,
+    histories: &ConversationHistoryMap,
+) -> (Registration, mpsc::Receiver<FlushRequest>) {
+    channels.sort();
+    let (flush, requests) = mpsc::channel(4);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    *RUNTIME.write() = Some(Runtime {
+        id,
+        channels,
+        histories: std::sync::Arc::downgrade(histories),
+        flush,
+    });
+    (Registration(id), requests)
+}
+
+/// Names of the channels the dispatcher is serving.
+pub fn running_channels() -> Result<Vec<String>> {
+    match RUNTIME.read().as_ref() {
+        Some(runtime) => Ok(runtime.channels.clone()),
+        None => bail!("Channels are not running in this process"),
+    }
+}
+
+fn known_channel(channel: &str) -> Result<String> {
+    let channel = channel.trim().to_ascii_lowercase();
+    let running = running_channels()?;
+    if !running.contains(&channel) {
+        bail!(
+            "Channel '{channel}' is not running (running: {})",
+            running.join(", ")
+        );
+    }
+    Ok(channel)
+}
+
+/// Stop dispatching messages from `channel`. Returns false if already paused.
+pub fn pause(channel: &str) -> Result<bool> {
+    let channel = known_channel(channel)?;
+    let paused = PAUSED.write().insert(channel.clone());
+    if paused {
+        tracing::warn!("Channel {channel} paused; inbound messages will be dropped");
+    }
+    Ok(paused)
+}
+
+/// Resume a paused channel. Returns false if it was not paused.
+pub fn resume(channel: &str) -> Result<bool> {
+    let channel = known_channel(channel)?;
+    let resumed = PAUSED.write().remove(&channel);
+    if resumed {
+        tracing::info!("Channel {channel} resumed");
+    }
+    Ok(resumed)
+}
+
+pub fn is_paused(channel: &str) -> bool {
+    PAUSED.read().contains(channel)
+}
+
+pub fn paused_channels() -> Vec<String> {
+    PAUSED.read().iter().cloned().collect()
+}
+
+/// Drop every message waiting on the inbound bus and return how many.
+pub async fn flush() -> Result<usize> {
+    let sender = match RUNTIME.read().as_ref() {
+        Some(runtime) => runtime.flush.clone(),
+        None => bail!("Channels are not running in this process"),
+    };
+    let (reply, dropped) = oneshot::channel();
+    if sender.send(reply).await.is_err() {
+        bail!("The channel dispatcher has stopped");
+    }
+    match tokio::time::timeout(FLUSH_TIMEOUT, dropped).await {
+        Ok(Ok(count)) => Ok(count),
+        Ok(Err(_)) => bail!("The channel dispatcher has stopped"),
+        Err(_) => bail!("The channel dispatcher did not answer within {FLUSH_TIMEOUT:?}"),
+    }
+}
+
+/// Empty `rx` without blocking; used by the dispatcher to answer a flush.
+pub(super) fn drain<T>(rx: &mut mpsc::Receiver<T>) -> usize {
+    let mut dropped = 0;
+    while rx.try_recv().is_ok() {
+        dropped += 1;
+    }
+    if dropped > 0 {
+        tracing::warn!("Flushed {dropped} queued channel message(s)");
+    }
+    dropped
+}
+
+/// A per-sender conversation held in memory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionInfo {
+    pub key: String,
+    pub channel: String,
+    pub sender: String,
+    pub messages: usize,
+}
+
+/// Conversation histories currently held by the dispatcher, by key.
+pub fn sessions() -> Result<Vec<SessionInfo>> {
+    let (channels, histories) = match RUNTIME.read().as_ref() {
+        Some(runtime) => (runtime.channels.clone(), runtime.histories.upgrade()),
+        None => bail!("Channels are not running in this process"),
+    };
+    let Some(histories) = histories else {
+        bail!("The channel dispatcher has stopped");
+    };
+    let histories = histories.lock().unwrap_or_else(|e| e.into_inner());
+    let mut sessions: Vec<SessionInfo> = histories
+        .iter()
+        .map(|(key, turns)| {
+            // Keys are `{channel}_{sender}`; channel names may contain `_`,
+            // so match the longest running channel name.
+            let channel = channels
+                .iter()
+                .filter(|name| key.starts_with(&format!("{name}_")))
+                .max_by_key(|name| name.len())
+                .cloned()
+                .unwrap_or_default();
+            let sender = key
+                .get(channel.len() + usize::from(!channel.is_empty())..)
+                .unwrap_or(key)
+                .to_string();
+            SessionInfo {
+                key: key.clone(),
+                channel,
+                sender,
+                messages: turns.len(),
+            }
+        })
+        .collect();
+    sessions.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(sessions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn registered_runtime_serves_pause_flush_and_sessions() {
+        let histories: ConversationHistoryMap = Arc::new(Mutex::new(HashMap::from([
+            (
+                "whatsapp_web_+4912".to_string(),
+                vec![ChatMessage::user("hi"), ChatMessage::assistant("hello")],
+            ),
+            ("live-test_alice".to_string(), vec![ChatMessage::user("yo")]),
+        ])));
+        let (registration, mut requests) = register(
+            vec!["live-test".into(), "whatsapp".into(), "whatsapp_web".into()],
+            &histories,
+        );
+
+        // A made-up channel name keeps other dispatcher tests unaffected.
+        assert!(pause("Live-Test").unwrap());
+        assert!(!pause("live-test").unwrap());
+        assert!(is_paused("live-test"));
+        assert!(pause("irc")
+            .unwrap_err()
+            .to_string()
+            .contains("not running"));
+        assert!(resume("live-test").unwrap());
+        assert!(!is_paused("live-test"));
+
+        let listed = sessions().unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].channel, "live-test");
+        assert_eq!(listed[0].sender, "alice");
+        assert_eq!(listed[1].channel, "whatsapp_web");
+        assert_eq!(listed[1].sender, "+4912");
+        assert_eq!(listed[1].messages, 2);
+
+        let (bus, mut inbound) = mpsc::channel::<u8>(8);
+        bus.send(1).await.unwrap();
+        bus.send(2).await.unwrap();
+        let dispatcher = tokio::spawn(async move {
+            let reply = requests.recv().await.unwrap();
+            let _ = reply.send(drain(&mut inbound));
+            inbound
+        });
+        assert_eq!(flush().await.unwrap(), 2);
+        assert!(dispatcher.await.unwrap().try_recv().is_err());
+
+        drop(registration);
+        assert!(sessions().unwrap_err().to_string().contains("not running"));
+    }
+}
@@ -14,27 +14,35 @@
 //! To add a new channel, implement [`Channel`] in a new submodule and wire it into
 //! [`start_channels`]. See `AGENTS.md` §7.2 for the full change playbook.
 
+pub mod admin;
 pub mod clawdtalk;
 pub mod cli;
+pub mod command_menu;
 pub mod dingtalk;
 pub mod discord;
 pub mod email_channel;
+pub mod group_trigger;
 pub mod imessage;
+pub mod incognito;
 pub mod irc;
 #[cfg(feature = "channel-lark")]
 pub mod lark;
 pub mod linq;
+pub mod live;
 #[cfg(feature = "channel-matrix")]
 pub mod matrix;
 pub mod mattermost;
 pub mod nextcloud_talk;
 pub mod nostr;
+pub mod outbound;
 pub mod qq;
 pub mod signal;
 pub mod slack;
 pub mod telegram;
 pub mod traits;
 pub mod transcription;
+pub mod translation;
+pub mod verification;
 pub mod whatsapp;
 #[cfg(feature = "whatsapp-web")]
 pub mod whatsapp_storage;
@@ -67,6 +75,7 @@ pub use whatsapp_web::WhatsAppWebChannel;
 
 use crate::agent::loop_::{build_tool_instructions, run_tool_call_loop, scrub_credentials};
 use crate::config::Config;
+use crate::i18n;
 use crate::identity;
 use crate::memory::{self, Memory};
 use crate::observability::{self, runtime_trace, Observer};
@@ -85,6 +94,7 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant, SystemTime};
 use tokio_util::sync::CancellationToken;
+use tokio_util::task::AbortOnDropHandle;
 
 /// Per-sender conversation history for channel messages.
 type ConversationHistoryMap = Arc<Mutex<HashMap<String, Vec<ChatMessage>>>>;
@@ -221,6 +231,28 @@ struct ChannelRuntimeContext {
     multimodal: crate::config::MultimodalConfig,
     hooks: Option<Arc<crate::hooks::HookRunner>>,
     non_cli_excluded_tools: Arc<Vec<String>>,
+    group_triggers: Arc<group_trigger::GroupTriggers>,
+    long_messages: crate::config::LongMessageConfig,
+    queue_policy: crate::queue_store::QueuePolicy,
+    inbox: Option<Arc<crate::inbox::Inbox>>,
+    /// Knowledge collection each bound channel answers from exclusively.
+    collections: Arc<HashMap<String, crate::presets::knowledge::Collection>>,
+    /// Document collections, searched per message within the sender's access.
+    rag: Option<Arc<crate::rag::collections::RagCollections>>,
+    /// Language detection and translation (`[translation]`).
+    translator: Option<Arc<translation::Translator>>,
+    /// Conversations that must not leave a trace (`/incognito`).
+    incognito: Arc<incognito::IncognitoSessions>,
+    /// Loaded skills, for `skill:` scoped standing instructions.
+    skill_names: Arc<Vec<String>>,
+    /// Standing instructions kept next to `config.toml`.
+    standing_instructions: Option<Arc<crate::instructions::InstructionStore>>,
+    /// Identity challenges for unverified senders (`[channels_config.verification]`).
+    verification: Option<Arc<verification::SenderVerifier>>,
+    /// Owner `!status`, `!pause`, … commands (`[channels_config.admin]`).
+    admin: Option<Arc<admin::ChannelAdmin>>,
+    /// Rebuilds `system_prompt` when workspace prompt overrides change.
+    prompt_reloader: Option<Arc<crate::prompts::PromptReloader>>,
 }
 
 #[derive(Clone)]
@@ -679,6 +711,29 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
     Ok(())
 }
 
+/// Switch the default model for every sender without a `/model` override.
+/// Holds until the config file changes and is reloaded.
+fn set_runtime_default_model(
+    ctx: &ChannelRuntimeContext,
+    model: &str,
+) -> Result<ChannelRuntimeDefaults> {
+    let config_path =
+        runtime_config_path(ctx).context("Runtime model switching needs a config directory")?;
+    let mut defaults = runtime_defaults_snapshot(ctx);
+    defaults.model = model.to_string();
+    let mut store = runtime_config_store()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    store
+        .entry(config_path)
+        .and_modify(|state| state.defaults.model = model.to_string())
+        .or_insert_with(|| RuntimeConfigState {
+            defaults: defaults.clone(),
+            last_applied_stamp: None,
+        });
+    Ok(defaults)
+}
+
 fn default_route_selection(ctx: &ChannelRuntimeContext) -> ChannelRouteSelection {
     let defaults = runtime_defaults_snapshot(ctx);
     ChannelRouteSelection {
@@ -791,7 +846,7 @@ fn rollback_orphan_user_turn(
 }
 
 fn should_skip_memory_context_entry(key: &str, content: &str) -> bool {
-    if memory::is_assistant_autosave_key(key) {
+    if memory::is_assistant_autosave_key(key) || memory::is_kv_key(key) {
         return true;
     }
 
@@ -908,27 +963,36 @@ async fn create_resilient_provider_nonblocking(
     .context("failed to join provider initialization task")?
 }
 
+fn current_route_line(current: &ChannelRouteSelection) -> String {
+    i18n::tr_args(
+        "channel.current_route",
+        &[("provider", &current.provider), ("model", &current.model)],
+    )
+}
+
 fn build_models_help_response(current: &ChannelRouteSelection, workspace_dir: &Path) -> String {
     let mut response = String::new();
-    let _ = writeln!(
-        response,
-        "Current provider: `{}`\nCurrent model: `{}`",
-        current.provider, current.model
-    );
-    response.push_str("\nSwitch model with `/model <model-id>`.\n");
+    let _ = writeln!(response, "{}", current_route_line(current));
+    let _ = writeln!(response, "\n{}", i18n::tr("channel.switch_model_hint"));
 
     let cached_models = load_cached_model_preview(workspace_dir, &current.provider);
     if cached_models.is_empty() {
         let _ = writeln!(
             response,
-            "\nNo cached model list found for `{}`. Ask the operator to run `zeroclaw models refresh --provider {}`.",
-            current.provider, current.provider
+            "\n{}",
+            i18n::tr_args(
+                "channel.no_cached_models",
+                &[("provider", &current.provider)]
+            )
         );
     } else {
         let _ = writeln!(
             response,
-            "\nCached model IDs (top {}):",
-            cached_models.len()
+            "\n{}",
+            i18n::tr_args(
+                "channel.cached_models",
+                &[("count", &cached_models.len().to_string())]
+            )
         );
         for model in cached_models {
             let _ = writeln!(response, "- `{model}`");
@@ -940,23 +1004,22 @@ fn build_models_help_response(current: &ChannelRouteSelection, workspace_dir: &P
 
 fn build_providers_help_response(current: &ChannelRouteSelection) -> String {
     let mut response = String::new();
-    let _ = writeln!(
-        response,
-        "Current provider: `{}`\nCurrent model: `{}`",
-        current.provider, current.model
-    );
-    response.push_str("\nSwitch provider with `/models <provider>`.\n");
-    response.push_str("Switch model with `/model <model-id>`.\n\n");
-    response.push_str("Available providers:\n");
+    let _ = writeln!(response, "{}", current_route_line(current));
+    let _ = writeln!(response, "\n{}", i18n::tr("channel.switch_provider_hint"));
+    let _ = writeln!(response, "{}\n", i18n::tr("channel.switch_model_hint"));
+    let _ = writeln!(response, "{}", i18n::tr("channel.available_providers"));
     for provider in providers::list_providers() {
         if provider.aliases.is_empty() {
             let _ = writeln!(response, "- {}", provider.name);
         } else {
             let _ = writeln!(
                 response,
-                "- {} (aliases: {})",
+                "- {} ({})",
                 provider.name,
-                provider.aliases.join(", ")
+                i18n::tr_args(
+                    "channel.provider_aliases",
+                    &[("aliases", &provider.aliases.join(", "))]
+                )
             );
         }
     }
@@ -991,21 +1054,20 @@ async fn handle_runtime_command_if_needed(
                             clear_sender_history(ctx, &sender_key);
                         }
 
-                        format!(
-                            "Provider switched to `{provider_name}` for this sender session. Current model is `{}`.\nUse `/model <model-id>` to set a provider-compatible model.",
-                            current.model
+                        i18n::tr_args(
+                            "channel.provider_switched",
+                            &[("provider", &provider_name), ("model", &current.model)],
                         )
                     }
                     Err(err) => {
                         let safe_err = providers::sanitize_api_error(&err.to_string());
-                        format!(
-                            "Failed to initialize provider `{provider_name}`. Route unchanged.\nDetails: {safe_err}"
+                        i18n::tr_args(
+                            "channel.provider_switch_failed",
+                            &[("provider", &provider_name), ("details", &safe_err)],
                         )
                     }
                 },
-                None => format!(
-                    "Unknown provider `{raw_provider}`. Use `/models` to list valid providers."
-                ),
+                None => i18n::tr_args("channel.unknown_provider", &[("provider", &raw_provider)]),
             }
         }
         ChannelRuntimeCommand::ShowModel => {
@@ -1014,15 +1076,15 @@ async fn handle_runtime_command_if_needed(
         ChannelRuntimeCommand::SetModel(raw_model) => {
             let model = raw_model.trim().trim_matches('`').to_string();
             if model.is_empty() {
-                "Model ID cannot be empty. Use `/model <model-id>`.".to_string()
+                i18n::tr("channel.model_empty")
             } else {
                 current.model = model.clone();
                 set_route_selection(ctx, &sender_key, current.clone());
                 clear_sender_history(ctx, &sender_key);
 
-                format!(
-                    "Model switched to `{model}` for provider `{}` in this sender session.",
-                    current.provider
+                i18n::tr_args(
+                    "channel.model_switched",
+                    &[("model", &model), ("provider", &current.provider)],
                 )
             }
         }
@@ -1041,6 +1103,274 @@ async fn handle_runtime_command_if_needed(
     true
 }
 
+/// Whether a message on a paused channel is an owner admin command, which
+/// must still get through so the owner can `!resume`.
+fn is_admin_command_from_owner(ctx: &ChannelRuntimeContext, msg: &traits::ChannelMessage) -> bool {
+    ctx.admin.as_ref().is_some_and(|admin| {
+        admin::parse_command(&msg.content).is_some() && admin.is_owner(&msg.channel, &msg.sender)
+    })
+}
+
+/// Run an owner admin command. Returns the reply to send.
+fn handle_admin_command(
+    ctx: &ChannelRuntimeContext,
+    admin: &admin::ChannelAdmin,
+    msg: &traits::ChannelMessage,
+    request: admin::AdminRequest,
+) -> String {
+    use admin::AdminCommand;
+
+    if let Err(reply) = admin.authorize(&request) {
+        return reply;
+    }
+    let failed = |err: anyhow::Error| {
+        i18n::tr_args("channel.admin_failed", &[("details", &format!("{err:#}"))])
+    };
+    tracing::info!(channel = %msg.channel, sender = %msg.sender, command = ?request.command, "Running admin command");
+    match request.command {
+        AdminCommand::Help => i18n::tr("channel.admin_help"),
+        AdminCommand::Status => {
+            let mut channels: Vec<String> = ctx.channels_by_name.keys().cloned().collect();
+            channels.sort();
+            let mut paused = live::paused_channels();
+            paused.sort();
+            let defaults = runtime_defaults_snapshot(ctx);
+            let sessions = live::sessions()
+                .map(|sessions| sessions.len())
+                .unwrap_or_default();
+            i18n::tr_args(
+                "channel.admin_status",
+                &[
+                    ("channels", &channels.join(", ")),
+                    (
+                        "paused",
+                        &if paused.is_empty() {
+                            "-".to_string()
+                        } else {
+                            paused.join(", ")
+                        },
+                    ),
+                    ("provider", &defaults.default_provider),
+                    ("model", &defaults.model),
+                    (
+                        "estop",
+                        &admin::describe_estop(admin.estop_state().as_ref()),
+                    ),
+                    ("sessions", &sessions.to_string()),
+                ],
+            )
+        }
+        AdminCommand::Budget => admin.budget().unwrap_or_else(failed),
+        AdminCommand::Pause(channel) => {
+            let channel = channel.unwrap_or_else(|| msg.channel.clone());
+            match live::pause(&channel) {
+                Ok(true) => i18n::tr_args("channel.admin_paused", &[("channel", &channel)]),
+                Ok(false) => {
+                    i18n::tr_args("channel.admin_already_paused", &[("channel", &channel)])
+                }
+                Err(err) => failed(err),
+            }
+        }
+        AdminCommand::Resume(channel) => {
+            let channel = channel.unwrap_or_else(|| msg.channel.clone());
+            match live::resume(&channel) {
+                Ok(true) => i18n::tr_args("channel.admin_resumed", &[("channel", &channel)]),
+                Ok(false) => i18n::tr_args("channel.admin_not_paused", &[("channel", &channel)]),
+                Err(err) => failed(err),
+            }
+        }
+        AdminCommand::Model(model) => match set_runtime_default_model(ctx, &model) {
+            Ok(defaults) => i18n::tr_args(
+                "channel.admin_model",
+                &[
+                    ("model", &defaults.model),
+                    ("provider", &defaults.default_provider),
+                ],
+            ),
+            Err(err) => failed(err),
+        },
+        AdminCommand::Estop { network } => match admin.engage_estop(network) {
+            Ok(state) => {
+                tracing::warn!(channel = %msg.channel, sender = %msg.sender, "Emergency stop engaged from chat");
+                i18n::tr_args(
+                    "channel.admin_estop",
+                    &[("state", &admin::describe_estop(Some(&state)))],
+                )
+            }
+            Err(err) => failed(err),
+        },
+    }
+}
+
+/// Park this sender's conversation for another surface, or claim one parked
+/// for this channel. Returns the reply to send.
+fn handle_incognito_command(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    command: incognito::IncognitoCommand,
+) -> String {
+    use incognito::IncognitoCommand;
+
+    let key = conversation_history_key(msg);
+    let minutes = (ctx.incognito.ttl().as_secs() / 60).to_string();
+    let clear_history = || {
+        ctx.conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&key);
+    };
+    if ctx.incognito.is_always(&msg.channel) {
+        return i18n::tr_args("channel.incognito_always", &[("minutes", &minutes)]);
+    }
+    match command {
+        IncognitoCommand::On => {
+            // Start from an empty history so nothing earlier mixes in.
+            clear_history();
+            ctx.incognito.start(&key);
+            i18n::tr_args("channel.incognito_on", &[("minutes", &minutes)])
+        }
+        IncognitoCommand::Off => {
+            if ctx.incognito.stop(&key) {
+                clear_history();
+                i18n::tr("channel.incognito_off")
+            } else {
+                i18n::tr("channel.incognito_inactive")
+            }
+        }
+        IncognitoCommand::Status => {
+            if ctx.incognito.is_active(&msg.channel, &key) {
+                i18n::tr_args("channel.incognito_on", &[("minutes", &minutes)])
+            } else {
+                i18n::tr("channel.incognito_inactive")
+            }
+        }
+    }
+}
+
+fn handle_handoff_command(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    command: crate::handoff::HandoffCommand,
+) -> String {
+    use crate::handoff::{HandoffCommand, HandoffStore, CLI_TARGET, HANDOFF_TTL_MINUTES};
+
+    let store = HandoffStore::for_workspace(ctx.workspace_dir.as_path());
+    let sender_key = conversation_history_key(msg);
+    let minutes = HANDOFF_TTL_MINUTES.to_string();
+    let result = match command {
+        HandoffCommand::Park(target) => {
+            if target != CLI_TARGET && !ctx.channels_by_name.contains_key(&target) {
+                let mut surfaces: Vec<&str> =
+                    ctx.channels_by_name.keys().map(String::as_str).collect();
+                surfaces.push(CLI_TARGET);
+                surfaces.sort_unstable();
+                Err(anyhow::anyhow!(
+                    "unknown surface '{target}' (available: {})",
+                    surfaces.join(", ")
+                ))
+            } else {
+                let turns = ctx
+                    .conversation_histories
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .get(&sender_key)
+                    .cloned()
+                    .unwrap_or_default();
+                let route = get_route_selection(ctx, &sender_key);
+                store
+                    .park(
+                        &msg.channel,
+                        &sender_key,
+                        &target,
+                        normalize_cached_channel_turns(turns),
+                        Some(route.provider),
+                        Some(route.model),
+                    )
+                    .map(|handoff| {
+                        if handoff.target == CLI_TARGET {
+                            i18n::tr_args("channel.handoff_parked_cli", &[("minutes", &minutes)])
+                        } else {
+                            i18n::tr_args(
+                                "channel.handoff_parked",
+                                &[
+                                    ("target", &handoff.target),
+                                    ("code", &handoff.code),
+                                    ("minutes", &minutes),
+                                ],
+                            )
+                        }
+                    })
+            }
+        }
+        HandoffCommand::Continue(code) => {
+            store.claim(&msg.channel, code.as_deref()).map(|handoff| {
+                let mut turns = normalize_cached_channel_turns(handoff.turns);
+                let skip = turns.len().saturating_sub(MAX_CHANNEL_HISTORY);
+                turns.drain(..skip);
+                let count = turns.len().to_string();
+                ctx.conversation_histories
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(sender_key.clone(), turns);
+                if let (Some(provider), Some(model)) = (
+                    handoff.provider.as_deref().and_then(resolve_provider_alias),
+                    handoff.model,
+                ) {
+                    set_route_selection(
+                        ctx,
+                        &sender_key,
+                        ChannelRouteSelection { provider, model },
+                    );
+                }
+                let queue = crate::questions::QuestionQueue::new(
+                    ctx.workspace_dir.as_path(),
+                    ctx.queue_policy.clone(),
+                );
+                match queue.reroute_pending(&msg.channel, &msg.reply_target) {
+                    Ok(moved) if !moved.is_empty() => {
+                        tracing::info!(
+                            count = moved.len(),
+                            "Re-routed pending questions after handoff"
+                        );
+                    }
+                    Ok(_) => {}
+                    Err(err) => tracing::warn!("Failed to re-route pending questions: {err}"),
+                }
+                i18n::tr_args(
+                    "channel.handoff_resumed",
+                    &[("source", &handoff.source), ("turns", &count)],
+                )
+            })
+        }
+    };
+    result.unwrap_or_else(|err| {
+        i18n::tr_args("channel.handoff_failed", &[("details", &err.to_string())])
+    })
+}
+
+/// Collections bound to running channels: `[channels_config.collections]`,
+/// falling back to `[agent].collection` for every channel.
+fn channel_collections<'a>(
+    config: &Config,
+    channels: impl Iterator<Item = &'a String>,
+) -> Result<HashMap<String, crate::presets::knowledge::Collection>> {
+    let mut bound = HashMap::new();
+    for channel in channels {
+        let name = config
+            .channels_config
+            .collections
+            .get(channel)
+            .or(config.agent.collection.as_ref());
+        if let Some(name) = name {
+            let collection =
+                crate::presets::knowledge::Collection::load(&config.workspace_dir, name)
+                    .with_context(|| format!("channel '{channel}'"))?;
+            bound.insert(channel.clone(), collection);
+        }
+    }
+    Ok(bound)
+}
+
 async fn build_memory_context(
     mem: &dyn Memory,
     user_msg: &str,
@@ -1351,6 +1681,17 @@ fn strip_isolated_tool_json_artifacts(message: &str, known_tool_names: &HashSet<
     result.trim().to_string()
 }
 
+/// Compose the in-progress draft: streamed text followed by the live status
+/// line (e.g. "Running tool: browser…"), if any.
+fn render_draft_text(accumulated: &str, status: &str) -> String {
+    let body = accumulated.trim_end();
+    match (body.is_empty(), status.is_empty()) {
+        (_, true) => body.to_string(),
+        (true, false) => status.to_string(),
+        (false, false) => format!("{body}\n\n{status}"),
+    }
+}
+
 fn spawn_supervised_listener(
     ch: Arc<dyn Channel>,
     tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
@@ -1472,21 +1813,93 @@ fn spawn_scoped_typing_task(
     handle
 }
 
+/// Drop the history of incognito sessions idle past their TTL.
+fn expire_incognito_sessions(ctx: &ChannelRuntimeContext) {
+    let expired = ctx.incognito.take_expired(Instant::now());
+    if expired.is_empty() {
+        return;
+    }
+    let mut histories = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    for key in &expired {
+        histories.remove(key);
+    }
+    tracing::info!(count = expired.len(), "Expired idle incognito sessions");
+}
+
 async fn process_channel_message(
     ctx: Arc<ChannelRuntimeContext>,
     msg: traits::ChannelMessage,
     cancellation_token: CancellationToken,
+) {
+    expire_incognito_sessions(&ctx);
+    let history_key = conversation_history_key(&msg);
+    let incognito = ctx.incognito.is_active(&msg.channel, &history_key);
+    if incognito {
+        ctx.incognito.touch(&history_key);
+    }
+    let requester = format!("{}:{}", msg.channel, msg.sender);
+    crate::security::four_eyes::with_requester(
+        requester,
+        runtime_trace::with_redaction(
+            incognito,
+            Box::pin(handle_channel_message(
+                ctx,
+                msg,
+                cancellation_token,
+                incognito,
+            )),
+        ),
+    )
+    .await;
+}
+
+/// Tell the owner about a new sender on every reachable `notify` target.
+async fn notify_verification_owner(
+    ctx: &ChannelRuntimeContext,
+    verifier: &verification::SenderVerifier,
+    notice: &str,
+) {
+    println!("  🔐 {notice}");
+    for (channel, target) in verifier.notify_targets() {
+        let Some(channel_impl) = ctx.channels_by_name.get(channel) else {
+            tracing::warn!("Verification notify channel '{channel}' is not running");
+            continue;
+        };
+        if let Err(err) = channel_impl.send(&SendMessage::new(notice, target)).await {
+            tracing::warn!("Failed to notify owner on {channel}:{target}: {err}");
+        }
+    }
+}
+
+async fn handle_channel_message(
+    ctx: Arc<ChannelRuntimeContext>,
+    msg: traits::ChannelMessage,
+    cancellation_token: CancellationToken,
+    incognito: bool,
 ) {
     if cancellation_token.is_cancelled() {
         return;
     }
 
-    println!(
-        "  💬 [{}] from {}: {}",
-        msg.channel,
-        msg.sender,
-        truncate_with_ellipsis(&msg.content, 80)
-    );
+    // The activity mirror chat is a read-only feed, never a command surface.
+    if crate::observability::mirror::is_mirror_chat(&msg.channel, &msg.reply_target) {
+        tracing::debug!(channel = %msg.channel, "Ignoring message from activity mirror chat");
+        return;
+    }
+
+    if incognito {
+        println!("  💬 [{}] incognito message", msg.channel);
+    } else {
+        println!(
+            "  💬 [{}] from {}: {}",
+            msg.channel,
+            msg.sender,
+            truncate_with_ellipsis(&msg.content, 80)
+        );
+    }
     runtime_trace::record_event(
         "channel_message_inbound",
         Some(msg.channel.as_str()),
@@ -1516,7 +1929,81 @@ async fn process_channel_message(
         msg
     };
 
+    match ctx.group_triggers.decide(&msg) {
+        group_trigger::GroupDecision::Engage => {}
+        group_trigger::GroupDecision::Observe if incognito => return,
+        group_trigger::GroupDecision::Observe => {
+            let key = group_trigger::observation_memory_key(&msg);
+            let content = format!("{}: {}", msg.sender, msg.content);
+            if let Err(err) = ctx
+                .memory
+                .store(
+                    &key,
+                    &content,
+                    crate::memory::MemoryCategory::Conversation,
+                    None,
+                )
+                .await
+            {
+                tracing::warn!("Failed to store observed group message: {err}");
+            }
+            return;
+        }
+        group_trigger::GroupDecision::Ignore => return,
+    }
+
+    // ── Inbox triage: email is classified and drafted, never auto-answered ──
+    if msg.channel == "email" {
+        if let Some(inbox) = ctx.inbox.as_ref() {
+            match inbox
+                .handle_incoming(
+                    ctx.provider.as_ref(),
+                    &ctx.model,
+                    ctx.memory.as_ref(),
+                    &msg.sender,
+                    &msg.content,
+                )
+                .await
+            {
+                Ok(entry) => tracing::info!(
+                    email = %entry.id,
+                    category = entry.category.label(),
+                    "Triaged incoming email"
+                ),
+                Err(err) => tracing::warn!("Failed to triage email from {}: {err:#}", msg.sender),
+            }
+            return;
+        }
+    }
+
     let target_channel = ctx.channels_by_name.get(&msg.channel).cloned();
+
+    // ── Sender verification: strangers get a challenge, not the agent ──
+    if let Some(verifier) = ctx.verification.as_ref() {
+        match verifier.screen(&msg.channel, &msg.sender, &msg.content, chrono::Utc::now()) {
+            Ok(verification::Screening::Pass) => {}
+            Ok(verification::Screening::Held { reply, notice }) => {
+                if let Some(notice) = notice {
+                    notify_verification_owner(ctx.as_ref(), verifier, &notice).await;
+                }
+                if let Some(channel) = target_channel.as_ref() {
+                    let _ = channel
+                        .send(
+                            &SendMessage::new(reply, &msg.reply_target)
+                                .in_thread(msg.thread_ts.clone()),
+                        )
+                        .await;
+                }
+                return;
+            }
+            Err(err) => {
+                // Fail closed: an unreadable store must not let strangers through.
+                tracing::error!("Sender verification failed, dropping message: {err:#}");
+                return;
+            }
+        }
+    }
+
     if let Err(err) = maybe_apply_runtime_config_update(ctx.as_ref()).await {
         tracing::warn!("Failed to apply runtime config update: {err}");
     }
@@ -1524,86 +2011,475 @@ async fn process_channel_message(
         return;
     }
 
-    let history_key = conversation_history_key(&msg);
-    let route = get_route_selection(ctx.as_ref(), &history_key);
-    let runtime_defaults = runtime_defaults_snapshot(ctx.as_ref());
-    let active_provider = match get_or_create_provider(ctx.as_ref(), &route.provider).await {
-        Ok(provider) => provider,
-        Err(err) => {
-            let safe_err = providers::sanitize_api_error(&err.to_string());
-            let message = format!(
-                "⚠️ Failed to initialize provider `{}`. Please run `/models` to choose another provider.\nDetails: {safe_err}",
-                route.provider
-            );
-            if let Some(channel) = target_channel.as_ref() {
-                let _ = channel
-                    .send(
-                        &SendMessage::new(message, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await;
-            }
-            return;
+    // ── Owner admin commands: `!status`, `!pause`, `!estop` … ──────
+    if let (Some(admin), Some(request)) = (ctx.admin.as_ref(), admin::parse_command(&msg.content)) {
+        let reply = if admin.is_owner(&msg.channel, &msg.sender) {
+            handle_admin_command(ctx.as_ref(), admin, &msg, request)
+        } else {
+            tracing::warn!(channel = %msg.channel, sender = %msg.sender, "Refused admin command from non-owner");
+            i18n::tr("channel.admin_denied")
+        };
+        if let Some(channel) = target_channel.as_ref() {
+            let _ = channel
+                .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+                .await;
         }
-    };
-    if ctx.auto_save_memory && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
-        let autosave_key = conversation_memory_key(&msg);
-        let _ = ctx
-            .memory
-            .store(
-                &autosave_key,
-                &msg.content,
-                crate::memory::MemoryCategory::Conversation,
-                None,
-            )
-            .await;
+        return;
     }
 
-    println!("  ⏳ Processing message...");
-    let started_at = Instant::now();
+    // ── Owner answers: `/answer [id] <text>` ──────────────
+    if let Some(args) = crate::questions::parse_answer_command(&msg.content) {
+        let queue = crate::questions::QuestionQueue::new(
+            ctx.workspace_dir.as_path(),
+            ctx.queue_policy.clone(),
+        );
+        let reply = match queue.answer_command(args, Some(&msg.channel)) {
+            Ok(question) => {
+                tracing::info!(question = %question.id, "Recorded answer from channel");
+                i18n::tr_args("channel.question_answered", &[("id", &question.id)])
+            }
+            Err(err) => i18n::tr_args(
+                "channel.question_answer_failed",
+                &[("details", &err.to_string())],
+            ),
+        };
+        if let Some(channel) = target_channel.as_ref() {
+            let _ = channel
+                .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+                .await;
+        }
+        return;
+    }
 
-    let had_prior_history = ctx
-        .conversation_histories
-        .lock()
-        .unwrap_or_else(|e| e.into_inner())
-        .get(&history_key)
-        .is_some_and(|turns| !turns.is_empty());
+    // ── `/simulate` is CLI-only: the shared registry cannot be switched per turn ──
+    if crate::tools::simulation::strip_simulate_directive(&msg.content).is_some() {
+        if let Some(channel) = target_channel.as_ref() {
+            let _ = channel
+                .send(
+                    &SendMessage::new(i18n::tr("channel.simulate_unavailable"), &msg.reply_target)
+                        .in_thread(msg.thread_ts.clone()),
+                )
+                .await;
+        }
+        return;
+    }
 
-    // Preserve user turn before the LLM call so interrupted requests keep context.
-    append_sender_turn(ctx.as_ref(), &history_key, ChatMessage::user(&msg.content));
+    // ── Inbox drafts: `/inbox approve <id>`, `/inbox summary` ──────
+    if let (Some(inbox), Some(args)) = (
+        ctx.inbox.as_ref(),
+        crate::inbox::parse_inbox_command(&msg.content),
+    ) {
+        let reply = if inbox.is_owner_channel(&msg.channel) {
+            inbox
+                .command(args)
+                .await
+                .unwrap_or_else(|err| format!("⚠️ {err:#}"))
+        } else {
+            "Inbox commands are only accepted on the owner channel.".to_string()
+        };
+        if let Some(channel) = target_channel.as_ref() {
+            let _ = channel
+                .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+                .await;
+        }
+        return;
+    }
 
-    // Build history from per-sender conversation cache.
-    let prior_turns_raw = ctx
-        .conversation_histories
-        .lock()
-        .unwrap_or_else(|e| e.into_inner())
-        .get(&history_key)
-        .cloned()
-        .unwrap_or_default();
-    let mut prior_turns = normalize_cached_channel_turns(prior_turns_raw);
+    // ── Incognito: `/incognito [on|off|status]` ──────
+    if let Some(command) = incognito::parse_command(&msg.content) {
+        let reply = handle_incognito_command(ctx.as_ref(), &msg, command);
+        if let Some(channel) = target_channel.as_ref() {
+            let _ = channel
+                .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+                .await;
+        }
+        return;
+    }
 
-    // Only enrich with memory context when there is no prior conversation
-    // history. Follow-up turns already include context from previous messages.
-    if !had_prior_history {
-        let memory_context =
-            build_memory_context(ctx.memory.as_ref(), &msg.content, ctx.min_relevance_score).await;
-        if let Some(last_turn) = prior_turns.last_mut() {
-            if last_turn.role == "user" && !memory_context.is_empty() {
-                last_turn.content = format!("{memory_context}{}", msg.content);
-            }
+    // ── Autonomous mode (owners only): `/auto 20m`, `/auto off` ──
+    if let Some(command) = crate::approval::autonomous::parse_command(&msg.content) {
+        let is_owner = ctx
+            .admin
+            .as_ref()
+            .is_some_and(|admin| admin.is_owner(&msg.channel, &msg.sender));
+        let reply = if is_owner {
+            let session = format!("{}:{}", msg.channel, msg.sender);
+            crate::approval::autonomous::handle_command(&session, command)
+        } else {
+            tracing::warn!(channel = %msg.channel, sender = %msg.sender, "Refused /auto from non-owner");
+            i18n::tr("channel.auto_owner_only")
+        };
+        if let Some(channel) = target_channel.as_ref() {
+            let _ = channel
+                .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+                .await;
         }
+        return;
     }
 
-    let system_prompt = build_channel_system_prompt(ctx.system_prompt.as_str(), &msg.channel);
-    let mut history = vec![ChatMessage::system(system_prompt)];
-    history.extend(prior_turns);
-    let use_streaming = target_channel
-        .as_ref()
-        .is_some_and(|ch| ch.supports_draft_updates());
+    // ── Session handoff: `/handoff <surface>`, `/continue-here <code>` ──
+    if let Some(command) = crate::handoff::parse_command(&msg.content) {
+        let reply = if incognito {
+            i18n::tr("channel.incognito_no_handoff")
+        } else {
+            handle_handoff_command(ctx.as_ref(), &msg, command)
+        };
+        if let Some(channel) = target_channel.as_ref() {
+            let _ = channel
+                .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+                .await;
+        }
+        return;
+    }
 
-    tracing::debug!(
-        channel = %msg.channel,
-        has_target_channel = target_channel.is_some(),
+    // ── Preferences: "be more concise", "reply in German" ──────
+    if let Some((field, value)) = crate::preferences::parse_instruction(&msg.content) {
+        let store = crate::preferences::PreferenceStore::for_workspace(ctx.workspace_dir.as_path());
+        let identity = store.identity_for(&msg.channel, &msg.sender);
+        let reply = if incognito {
+            i18n::tr("channel.incognito_not_saved")
+        } else {
+            match store.set(&identity, field, &value) {
+                Ok(_) => {
+                    tracing::info!(identity = %identity, preference = field.as_str(), "Saved preference from channel");
+                    i18n::tr_args(
+                        "channel.preference_saved",
+                        &[("preference", &format!("{} = {value}", field.as_str()))],
+                    )
+                }
+                Err(err) => i18n::tr_args(
+                    "channel.preference_failed",
+                    &[("details", &err.to_string())],
+                ),
+            }
+        };
+        if let Some(channel) = target_channel.as_ref() {
+            let _ = channel
+                .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+                .await;
+        }
+        return;
+    }
+
+    // ── Second-person sign-off: "/approve <code>" ─────────────
+    if let (Some((approved, code)), Some(guard)) = (
+        crate::security::four_eyes::parse_command(&msg.content),
+        crate::security::four_eyes::current(),
+    ) {
+        let approver = format!("{}:{}", msg.channel, msg.sender);
+        let reply = match guard.decide(&code, &approver, approved) {
+            Ok(pending) => i18n::tr_args(
+                if approved {
+                    "channel.approval_granted"
+                } else {
+                    "channel.approval_denied"
+                },
+                &[
+                    ("code", &pending.code),
+                    ("tool", &pending.tool),
+                    ("requester", &pending.requester),
+                ],
+            ),
+            Err(err) => i18n::tr_args("channel.approval_failed", &[("details", &err.to_string())]),
+        };
+        if let Some(channel) = target_channel.as_ref() {
+            let _ = channel
+                .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+                .await;
+        }
+        return;
+    }
+
+    // ── Standing instructions: "from now on, always …" ────────
+    if let (Some(command), Some(store)) = (
+        crate::instructions::parse_command(&msg.content),
+        ctx.standing_instructions.as_ref(),
+    ) {
+        // Senders manage their own channel's rules; only owners touch others.
+        let scope = crate::instructions::Scope::Channel(msg.channel.to_ascii_lowercase());
+        let is_owner = ctx
+            .admin
+            .as_ref()
+            .is_some_and(|admin| admin.is_owner(&msg.channel, &msg.sender));
+        let outcome = match command {
+            _ if incognito => Ok(i18n::tr("channel.incognito_not_saved")),
+            crate::instructions::Command::Add(text) => store.add(scope, &text).map(|saved| {
+                i18n::tr_args(
+                    "channel.instruction_saved",
+                    &[("id", &saved.id.to_string()), ("text", &saved.text)],
+                )
+            }),
+            crate::instructions::Command::Remove(id) => if is_owner {
+                store.remove(id)
+            } else {
+                store.remove_in_scope(id, &scope)
+            }
+            .map(|removed| {
+                let key = if removed.is_some() {
+                    "channel.instruction_removed"
+                } else {
+                    "channel.instruction_missing"
+                };
+                i18n::tr_args(key, &[("id", &id.to_string())])
+            }),
+        };
+        let reply = outcome.unwrap_or_else(|err| {
+            tracing::warn!(channel = %msg.channel, sender = %msg.sender, "Standing instruction not changed: {err:#}");
+            i18n::tr_args(
+                "channel.instruction_failed",
+                &[("details", &err.to_string())],
+            )
+        });
+        if let Some(channel) = target_channel.as_ref() {
+            let _ = channel
+                .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+                .await;
+        }
+        return;
+    }
+
+    // Slash commands and task templates are written in the working language.
+    let typed_command = msg.content.trim_start().starts_with('/');
+
+    // ── Command menu: `/deploy_report ...`, `/weather ...` ──────
+    let msg = match command_menu::expand_invocation(ctx.workspace_dir.as_path(), &msg.content) {
+        Some(content) => traits::ChannelMessage { content, ..msg },
+        None => msg,
+    };
+
+    // ── Task templates: `/task <name> key=value ...` ──────
+    let mut task_tools = Vec::new();
+    let msg = match crate::tasks::expand_invocation(ctx.workspace_dir.as_path(), &msg.content) {
+        None => msg,
+        Some(Ok(task)) => {
+            tracing::info!(task = %task.name, "Expanded task template from channel message");
+            task_tools = task.tools;
+            traits::ChannelMessage {
+                content: task.prompt,
+                ..msg
+            }
+        }
+        Some(Err(err)) => {
+            if let Some(channel) = target_channel.as_ref() {
+                let message =
+                    i18n::tr_args("channel.task_failed", &[("details", &err.to_string())]);
+                let _ = channel
+                    .send(
+                        &SendMessage::new(message, &msg.reply_target)
+                            .in_thread(msg.thread_ts.clone()),
+                    )
+                    .await;
+            }
+            return;
+        }
+    };
+
+    let history_key = conversation_history_key(&msg);
+    let route = get_route_selection(ctx.as_ref(), &history_key);
+    let runtime_defaults = runtime_defaults_snapshot(ctx.as_ref());
+    let active_provider = match get_or_create_provider(ctx.as_ref(), &route.provider).await {
+        Ok(provider) => provider,
+        Err(err) => {
+            let safe_err = providers::sanitize_api_error(&err.to_string());
+            let message = i18n::tr_args(
+                "channel.provider_unavailable",
+                &[("provider", &route.provider), ("details", &safe_err)],
+            );
+            if let Some(channel) = target_channel.as_ref() {
+                let _ = channel
+                    .send(
+                        &SendMessage::new(message, &msg.reply_target)
+                            .in_thread(msg.thread_ts.clone()),
+                    )
+                    .await;
+            }
+            return;
+        }
+    };
+
+    // ── Translation: the agent sees and answers in the working language ──
+    let mut reply_language = None;
+    let mut memory_text = msg.content.clone();
+    let msg = match ctx.translator.as_ref() {
+        Some(translator) if !typed_command => {
+            match translator
+                .incoming(
+                    active_provider.as_ref(),
+                    &route.model,
+                    &msg.channel,
+                    &history_key,
+                    &msg.content,
+                )
+                .await
+            {
+                Ok(incoming) => {
+                    memory_text = translation::memory_content(&incoming);
+                    if incoming.language != translator.working_language() {
+                        reply_language = Some(incoming.language);
+                    }
+                    traits::ChannelMessage {
+                        content: incoming.text,
+                        ..msg
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!("Translation failed; passing the message through: {err}");
+                    msg
+                }
+            }
+        }
+        _ => msg,
+    };
+
+    if ctx.auto_save_memory
+        && !incognito
+        && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS
+    {
+        let autosave_key = conversation_memory_key(&msg);
+        let _ = ctx
+            .memory
+            .store(
+                &autosave_key,
+                &memory_text,
+                crate::memory::MemoryCategory::Conversation,
+                None,
+            )
+            .await;
+    }
+
+    let mut excluded_tools = if msg.channel == "cli" {
+        Vec::new()
+    } else {
+        ctx.non_cli_excluded_tools.as_ref().clone()
+    };
+    // The user is present in a conversation; queued questions are for
+    // unattended runs only.
+    excluded_tools.push("ask_user".to_string());
+    if incognito {
+        excluded_tools.extend(
+            incognito::PERSISTENT_TOOLS
+                .iter()
+                .map(|tool| (*tool).to_string()),
+        );
+    }
+    if !task_tools.is_empty() {
+        excluded_tools.extend(
+            ctx.tools_registry
+                .iter()
+                .map(|tool| tool.name().to_string())
+                .filter(|name| !task_tools.contains(name)),
+        );
+    }
+    let collection = ctx.collections.get(&msg.channel);
+    if collection.is_some() {
+        // The collection is the only source a bound channel answers from.
+        excluded_tools.extend(
+            ctx.tools_registry
+                .iter()
+                .map(|tool| tool.name().to_string()),
+        );
+    }
+
+    println!("  ⏳ Processing message...");
+    let started_at = Instant::now();
+
+    let had_prior_history = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&history_key)
+        .is_some_and(|turns| !turns.is_empty());
+
+    // Preserve user turn before the LLM call so interrupted requests keep context.
+    append_sender_turn(ctx.as_ref(), &history_key, ChatMessage::user(&msg.content));
+
+    // Build history from per-sender conversation cache.
+    let prior_turns_raw = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&history_key)
+        .cloned()
+        .unwrap_or_default();
+    let mut prior_turns = normalize_cached_channel_turns(prior_turns_raw);
+
+    // A bound channel gets fresh collection excerpts on every turn. Otherwise
+    // only enrich with memory context when there is no prior conversation
+    // history; follow-up turns already include context from previous messages.
+    if let Some(collection) = collection {
+        let excerpts = collection
+            .context(
+                ctx.memory.as_ref(),
+                &msg.content,
+                MEMORY_CONTEXT_MAX_ENTRIES,
+            )
+            .await;
+        if let Some(last_turn) = prior_turns.last_mut() {
+            if last_turn.role == "user" {
+                last_turn.content = format!("{excerpts}{}", msg.content);
+            }
+        }
+    } else {
+        let mut context = if had_prior_history {
+            String::new()
+        } else {
+            build_memory_context(ctx.memory.as_ref(), &msg.content, ctx.min_relevance_score).await
+        };
+        // Document collections are searched on every turn, limited to the
+        // collections this sender may read.
+        if let Some(rag) = &ctx.rag {
+            let identity = crate::rag::collections::Identity {
+                channel: &msg.channel,
+                sender: &msg.sender,
+            };
+            context.push_str(&rag.context(identity, &msg.content).await);
+        }
+        if let Some(last_turn) = prior_turns.last_mut() {
+            if last_turn.role == "user" && !context.is_empty() {
+                last_turn.content = format!("{context}{}", msg.content);
+            }
+        }
+    }
+
+    // The base prompt is built at startup and again when prompt overrides
+    // change; refresh the sender's clock per message so relative dates
+    // resolve against the current day.
+    let base_prompt = ctx.prompt_reloader.as_ref().map_or_else(
+        || Arc::clone(&ctx.system_prompt),
+        |reloader| reloader.prompt(),
+    );
+    let mut system_prompt = format!(
+        "{}\n\n{}",
+        build_channel_system_prompt(base_prompt.as_str(), &msg.channel),
+        crate::datetime::user_clock().sender_section(&msg.channel, &msg.sender, chrono::Utc::now())
+    );
+    if let Some(collection) = collection {
+        system_prompt.push_str(&collection.instructions());
+    }
+    let preferences =
+        crate::preferences::PreferenceStore::for_workspace(ctx.workspace_dir.as_path());
+    system_prompt.push_str(
+        &preferences.prompt_section(&preferences.identity_for(&msg.channel, &msg.sender)),
+    );
+    if let Some(standing) = ctx.standing_instructions.as_ref() {
+        system_prompt.push_str(&standing.prompt_section(&msg.channel, &ctx.skill_names));
+    }
+    let pinned = crate::pins::resolve(ctx.workspace_dir.as_path(), Some(ctx.memory.as_ref())).await;
+    for warning in &pinned.warnings {
+        tracing::warn!(channel = %msg.channel, "{warning}");
+    }
+    system_prompt.push_str(&pinned.section);
+    let mut history = vec![ChatMessage::system(system_prompt)];
+    history.extend(prior_turns);
+    // Translated replies are only known once complete, so they are not streamed.
+    let use_streaming = reply_language.is_none()
+        && target_channel
+            .as_ref()
+            .is_some_and(|ch| ch.supports_draft_updates());
+
+    tracing::debug!(
+        channel = %msg.channel,
+        has_target_channel = target_channel.is_some(),
         use_streaming,
         supports_draft = target_channel.as_ref().map_or(false, |ch| ch.supports_draft_updates()),
         "Draft streaming decision"
@@ -1647,16 +2523,23 @@ async fn process_channel_message(
         let draft_id = draft_id_ref.to_string();
         Some(tokio::spawn(async move {
             let mut accumulated = String::new();
+            let mut status = String::new();
             while let Some(delta) = rx.recv().await {
                 if delta == crate::agent::loop_::DRAFT_CLEAR_SENTINEL {
                     accumulated.clear();
+                    status.clear();
                     continue;
                 }
-                accumulated.push_str(&delta);
-                if let Err(e) = channel
-                    .update_draft(&reply_target, &draft_id, &accumulated)
-                    .await
-                {
+                if let Some(next) = delta.strip_prefix(crate::agent::loop_::DRAFT_STATUS_PREFIX) {
+                    next.clone_into(&mut status);
+                } else {
+                    accumulated.push_str(&delta);
+                }
+                let draft = render_draft_text(&accumulated, &status);
+                if draft.is_empty() {
+                    continue;
+                }
+                if let Err(e) = channel.update_draft(&reply_target, &draft_id, &draft).await {
                     tracing::debug!("Draft update failed: {e}");
                 }
             }
@@ -1685,6 +2568,22 @@ async fn process_channel_message(
         _ => None,
     };
 
+    // Tool calls made under `/auto` are announced in the conversation.
+    let (announce_tx, mut announce_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    let announcer = target_channel.as_ref().map(|channel| {
+        let channel = Arc::clone(channel);
+        let reply_target = msg.reply_target.clone();
+        let thread_ts = msg.thread_ts.clone();
+        tokio::spawn(async move {
+            while let Some(text) = announce_rx.recv().await {
+                let message = SendMessage::new(text, &reply_target).in_thread(thread_ts.clone());
+                if let Err(e) = channel.send(&message).await {
+                    tracing::debug!("Autonomous mode announcement failed: {e}");
+                }
+            }
+        })
+    });
+
     // Record history length before tool loop so we can extract tool context after.
     let history_len_before_tools = history.len();
 
@@ -1695,35 +2594,42 @@ async fn process_channel_message(
 
     let timeout_budget_secs =
         channel_message_timeout_budget_secs(ctx.message_timeout_secs, ctx.max_tool_iterations);
+    let cost_before = crate::cost::conversation::totals(&history_key);
     let llm_result = tokio::select! {
         () = cancellation_token.cancelled() => LlmExecutionResult::Cancelled,
         result = tokio::time::timeout(
             Duration::from_secs(timeout_budget_secs),
-            run_tool_call_loop(
-                active_provider.as_ref(),
-                &mut history,
-                ctx.tools_registry.as_ref(),
-                ctx.observer.as_ref(),
-                route.provider.as_str(),
-                route.model.as_str(),
-                runtime_defaults.temperature,
-                true,
-                None,
-                msg.channel.as_str(),
-                &ctx.multimodal,
-                ctx.max_tool_iterations,
-                Some(cancellation_token.clone()),
-                delta_tx,
-                ctx.hooks.as_deref(),
-                if msg.channel == "cli" {
-                    &[]
-                } else {
-                    ctx.non_cli_excluded_tools.as_ref()
-                },
+            crate::approval::autonomous::with_announcer(
+                announce_tx,
+                crate::cost::conversation::scope(
+                    history_key.clone(),
+                    run_tool_call_loop(
+                        active_provider.as_ref(),
+                        &mut history,
+                        ctx.tools_registry.as_ref(),
+                        ctx.observer.as_ref(),
+                        route.provider.as_str(),
+                        route.model.as_str(),
+                        runtime_defaults.temperature,
+                        true,
+                        None,
+                        msg.channel.as_str(),
+                        &ctx.multimodal,
+                        ctx.max_tool_iterations,
+                        Some(cancellation_token.clone()),
+                        delta_tx,
+                        ctx.hooks.as_deref(),
+                        &excluded_tools,
+                    ),
+                ),
             ),
         ) => LlmExecutionResult::Completed(result),
     };
 
+    if let Some(handle) = announcer {
+        log_worker_join_result(handle.await);
+    }
+
     if let Some(handle) = draft_updater {
         let _ = handle.await;
     }
@@ -1866,31 +2772,44 @@ async fn process_channel_message(
                 &history_key,
                 ChatMessage::assistant(&history_response),
             );
+            let delivered_response = match (ctx.translator.as_ref(), reply_language.as_deref()) {
+                (Some(translator), Some(language)) => translator
+                    .outgoing(
+                        active_provider.as_ref(),
+                        &route.model,
+                        language,
+                        &delivered_response,
+                    )
+                    .await
+                    .unwrap_or_else(|err| {
+                        tracing::warn!("Reply translation failed; sending the original: {err}");
+                        delivered_response
+                    }),
+                _ => delivered_response,
+            };
+            let delivered_response = match crate::cost::conversation::channel_suffix(
+                &msg.channel,
+                &history_key,
+                cost_before,
+            ) {
+                Some(suffix) => format!("{delivered_response}\n\n{suffix}"),
+                None => delivered_response,
+            };
             println!(
                 "  🤖 Reply ({}ms): {}",
                 started_at.elapsed().as_millis(),
                 truncate_with_ellipsis(&delivered_response, 80)
             );
             if let Some(channel) = target_channel.as_ref() {
-                if let Some(ref draft_id) = draft_message_id {
-                    if let Err(e) = channel
-                        .finalize_draft(&msg.reply_target, draft_id, &delivered_response)
-                        .await
-                    {
-                        tracing::warn!("Failed to finalize draft: {e}; sending as new message");
-                        let _ = channel
-                            .send(
-                                &SendMessage::new(&delivered_response, &msg.reply_target)
-                                    .in_thread(msg.thread_ts.clone()),
-                            )
-                            .await;
-                    }
-                } else if let Err(e) = channel
-                    .send(
-                        &SendMessage::new(delivered_response, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await
+                if let Err(e) = outbound::deliver_reply(
+                    channel.as_ref(),
+                    &msg.reply_target,
+                    msg.thread_ts.as_deref(),
+                    draft_message_id.as_deref(),
+                    &delivered_response,
+                    &ctx.long_messages,
+                )
+                .await
                 {
                     eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
                 }
@@ -1927,9 +2846,9 @@ async fn process_channel_message(
             } else if is_context_window_overflow_error(&e) {
                 let compacted = compact_sender_history(ctx.as_ref(), &history_key);
                 let error_text = if compacted {
-                    "⚠️ Context window exceeded for this conversation. I compacted recent history and kept the latest context. Please resend your last message."
+                    i18n::tr("channel.context_overflow_compacted")
                 } else {
-                    "⚠️ Context window exceeded for this conversation. Please resend your last message."
+                    i18n::tr("channel.context_overflow")
                 };
                 eprintln!(
                     "  ⚠️ Context window exceeded after {}ms; sender history compacted={}",
@@ -1948,17 +2867,18 @@ async fn process_channel_message(
                         "sender": msg.sender,
                         "elapsed_ms": started_at.elapsed().as_millis(),
                         "history_compacted": compacted,
+                        "error_code": crate::errors::ErrorClass::Validation.code(),
                     }),
                 );
                 if let Some(channel) = target_channel.as_ref() {
                     if let Some(ref draft_id) = draft_message_id {
                         let _ = channel
-                            .finalize_draft(&msg.reply_target, draft_id, error_text)
+                            .finalize_draft(&msg.reply_target, draft_id, &error_text)
                             .await;
                     } else {
                         let _ = channel
                             .send(
-                                &SendMessage::new(error_text, &msg.reply_target)
+                                &SendMessage::new(&error_text, &msg.reply_target)
                                     .in_thread(msg.thread_ts.clone()),
                             )
                             .await;
@@ -1981,6 +2901,7 @@ async fn process_channel_message(
                     serde_json::json!({
                         "sender": msg.sender,
                         "elapsed_ms": started_at.elapsed().as_millis(),
+                        "error_code": crate::errors::classify(&e).code(),
                     }),
                 );
                 let should_rollback_user_turn = e
@@ -1999,14 +2920,15 @@ async fn process_channel_message(
                     );
                 }
                 if let Some(channel) = target_channel.as_ref() {
+                    let error_text = i18n::tr_args("channel.error", &[("error", &e.to_string())]);
                     if let Some(ref draft_id) = draft_message_id {
                         let _ = channel
-                            .finalize_draft(&msg.reply_target, draft_id, &format!("⚠️ Error: {e}"))
+                            .finalize_draft(&msg.reply_target, draft_id, &error_text)
                             .await;
                     } else {
                         let _ = channel
                             .send(
-                                &SendMessage::new(format!("⚠️ Error: {e}"), &msg.reply_target)
+                                &SendMessage::new(&error_text, &msg.reply_target)
                                     .in_thread(msg.thread_ts.clone()),
                             )
                             .await;
@@ -2030,6 +2952,7 @@ async fn process_channel_message(
                 serde_json::json!({
                     "sender": msg.sender,
                     "elapsed_ms": started_at.elapsed().as_millis(),
+                    "error_code": crate::errors::ErrorClass::Network.code(),
                 }),
             );
             eprintln!(
@@ -2045,16 +2968,15 @@ async fn process_channel_message(
                 ChatMessage::assistant("[Task timed out — not continuing this request]"),
             );
             if let Some(channel) = target_channel.as_ref() {
-                let error_text =
-                    "⚠️ Request timed out while waiting for the model. Please try again.";
+                let error_text = i18n::tr("channel.request_timeout");
                 if let Some(ref draft_id) = draft_message_id {
                     let _ = channel
-                        .finalize_draft(&msg.reply_target, draft_id, error_text)
+                        .finalize_draft(&msg.reply_target, draft_id, &error_text)
                         .await;
                 } else {
                     let _ = channel
                         .send(
-                            &SendMessage::new(error_text, &msg.reply_target)
+                            &SendMessage::new(&error_text, &msg.reply_target)
                                 .in_thread(msg.thread_ts.clone()),
                         )
                         .await;
@@ -2074,10 +2996,21 @@ async fn process_channel_message(
     }
 }
 
+/// Next flush request from the control socket; pending forever without one.
+async fn next_flush_request(
+    requests: &mut Option<tokio::sync::mpsc::Receiver<live::FlushRequest>>,
+) -> Option<live::FlushRequest> {
+    match requests {
+        Some(requests) => requests.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
 async fn run_message_dispatch_loop(
     mut rx: tokio::sync::mpsc::Receiver<traits::ChannelMessage>,
     ctx: Arc<ChannelRuntimeContext>,
     max_in_flight_messages: usize,
+    mut flush_requests: Option<tokio::sync::mpsc::Receiver<live::FlushRequest>>,
 ) {
     let semaphore = Arc::new(tokio::sync::Semaphore::new(max_in_flight_messages));
     let mut workers = tokio::task::JoinSet::new();
@@ -2087,11 +3020,39 @@ async fn run_message_dispatch_loop(
     >::new()));
     let task_sequence = Arc::new(AtomicU64::new(1));
 
-    while let Some(msg) = rx.recv().await {
-        let permit = match Arc::clone(&semaphore).acquire_owned().await {
-            Ok(permit) => permit,
-            Err(_) => break,
+    loop {
+        // Flush requests are served while waiting for a worker slot or a
+        // message, so a saturated dispatcher can still be drained.
+        let permit = tokio::select! {
+            biased;
+            Some(reply) = next_flush_request(&mut flush_requests) => {
+                let _ = reply.send(live::drain(&mut rx));
+                continue;
+            }
+            permit = Arc::clone(&semaphore).acquire_owned() => match permit {
+                Ok(permit) => permit,
+                Err(_) => break,
+            },
+        };
+        let msg = tokio::select! {
+            biased;
+            Some(reply) = next_flush_request(&mut flush_requests) => {
+                let _ = reply.send(live::drain(&mut rx));
+                continue;
+            }
+            msg = rx.recv() => match msg {
+                Some(msg) => msg,
+                None => break,
+            },
         };
+        if live::is_paused(&msg.channel) && !is_admin_command_from_owner(&ctx, &msg) {
+            tracing::info!(
+                "Dropped message from {} on paused channel {}",
+                msg.sender,
+                msg.channel
+            );
+            continue;
+        }
 
         let worker_ctx = Arc::clone(&ctx);
         let in_flight = Arc::clone(&in_flight_by_sender);
@@ -2176,8 +3137,11 @@ fn load_openclaw_bootstrap_files(
         inject_workspace_file(prompt, workspace_dir, "BOOTSTRAP.md", max_chars_per_file);
     }
 
-    // MEMORY.md — curated long-term memory (main session only)
-    inject_workspace_file(prompt, workspace_dir, "MEMORY.md", max_chars_per_file);
+    // MEMORY.md — curated long-term memory (main session only; hidden from
+    // runs confined to their own memory scope)
+    if crate::memory::scoped::shared_memory_visible() {
+        inject_workspace_file(prompt, workspace_dir, "MEMORY.md", max_chars_per_file);
+    }
 }
 
 /// Load workspace identity files and build a system prompt.
@@ -2250,42 +3214,19 @@ pub fn build_system_prompt_with_mode(
             || *name == "hardware_capabilities"
     });
     if has_hardware {
-        prompt.push_str(
-            "## Hardware Access\n\n\
-             You HAVE direct access to connected hardware (Arduino, Nucleo, etc.). The user owns this system and has configured it.\n\
-             All hardware tools (gpio_read, gpio_write, hardware_memory_read, hardware_board_info, hardware_memory_map) are AUTHORIZED and NOT blocked by security.\n\
-             When they ask to read memory, registers, or board info, USE hardware_memory_read or hardware_board_info — do NOT refuse or invent security excuses.\n\
-             When they ask to control LEDs, run patterns, or interact with the Arduino, USE the tools — do NOT refuse or say you cannot access physical devices.\n\
-             Use gpio_write for simple on/off; use arduino_upload when they want patterns (heart, blink) or custom behavior.\n\n",
-        );
+        crate::prompts::push_section(&mut prompt, workspace_dir, "hardware");
     }
 
     // ── 1c. Action instruction (avoid meta-summary) ───────────────
-    if native_tools {
-        prompt.push_str(
-            "## Your Task\n\n\
-             When the user sends a message, respond naturally. Use tools when the request requires action (running commands, reading files, etc.).\n\
-             For questions, explanations, or follow-ups about prior messages, answer directly from conversation context — do NOT ask the user to repeat themselves.\n\
-             Do NOT: summarize this configuration, describe your capabilities, or output step-by-step meta-commentary.\n\n",
-        );
+    let task = if native_tools {
+        "task_native"
     } else {
-        prompt.push_str(
-            "## Your Task\n\n\
-             When the user sends a message, ACT on it. Use the tools to fulfill their request.\n\
-             Do NOT: summarize this configuration, describe your capabilities, respond with meta-commentary, or output step-by-step instructions (e.g. \"1. First... 2. Next...\").\n\
-             Instead: emit actual <tool_call> tags when you need to act. Just do what they ask.\n\n",
-        );
-    }
+        "task_xml"
+    };
+    crate::prompts::push_section(&mut prompt, workspace_dir, task);
 
     // ── 2. Safety ───────────────────────────────────────────────
-    prompt.push_str("## Safety\n\n");
-    prompt.push_str(
-        "- Do not exfiltrate private data.\n\
-         - Do not run destructive commands without asking.\n\
-         - Do not bypass oversight or approval mechanisms.\n\
-         - Prefer `trash` over `rm` (recoverable beats gone forever).\n\
-         - When in doubt, ask before acting externally.\n\n",
-    );
+    crate::prompts::push_section(&mut prompt, workspace_dir, "safety");
 
     // ── 3. Skills (full or compact, based on config) ─────────────
     if !skills.is_empty() {
@@ -2346,12 +3287,10 @@ pub fn build_system_prompt_with_mode(
     }
 
     // ── 6. Date & Time ──────────────────────────────────────────
-    let now = chrono::Local::now();
     let _ = writeln!(
         prompt,
-        "## Current Date & Time\n\n{} ({})\n",
-        now.format("%Y-%m-%d %H:%M:%S"),
-        now.format("%Z")
+        "{}\n",
+        crate::datetime::user_clock().prompt_section(chrono::Utc::now())
     );
 
     // ── 7. Runtime ──────────────────────────────────────────────
@@ -2364,11 +3303,7 @@ pub fn build_system_prompt_with_mode(
     );
 
     // ── 8. Channel Capabilities ─────────────────────────────────────
-    prompt.push_str("## Channel Capabilities\n\n");
-    prompt.push_str("- You are running as a messaging bot. Your response is automatically sent back to the user's channel.\n");
-    prompt.push_str("- You do NOT need to ask permission to respond — just respond directly.\n");
-    prompt.push_str("- NEVER repeat, describe, or echo credentials, tokens, API keys, or secrets in your responses.\n");
-    prompt.push_str("- If a tool output contains credentials, they have already been redacted — do not mention them.\n\n");
+    crate::prompts::push_section(&mut prompt, workspace_dir, "channel_capabilities");
 
     if prompt.is_empty() {
         "You are ZeroClaw, a fast and efficient AI assistant built in Rust. Be helpful, concise, and direct."
@@ -2619,7 +3554,60 @@ pub(crate) async fn handle_command(command: crate::ChannelCommands, config: &Con
             anyhow::bail!("Remove channel '{name}' — edit ~/.zeroclaw/config.toml directly");
         }
         crate::ChannelCommands::BindTelegram { identity } => {
-            bind_telegram_identity(config, &identity).await
+            Box::pin(bind_telegram_identity(config, &identity)).await
+        }
+        crate::ChannelCommands::Verifications => {
+            let verifier = verification::SenderVerifier::for_config(config);
+            let state = verifier.load()?;
+            if !config.channels_config.verification.enabled {
+                println!(
+                    "Sender verification is off ([channels_config.verification].enabled = false)."
+                );
+            }
+            println!("Pending challenges:");
+            if state.pending.is_empty() {
+                println!("  (none)");
+            }
+            let now = chrono::Utc::now();
+            for (identity, challenge) in &state.pending {
+                let status = if challenge.expires_at <= now {
+                    "expired".to_string()
+                } else if challenge.attempts >= config.channels_config.verification.max_attempts {
+                    "locked".to_string()
+                } else {
+                    "waiting for code".to_string()
+                };
+                println!(
+                    "  {identity}  {status}  (until {})",
+                    challenge.expires_at.format("%Y-%m-%d %H:%M UTC")
+                );
+            }
+            println!("Verified senders:");
+            if state.verified.is_empty() {
+                println!("  (none)");
+            }
+            for (identity, sender) in &state.verified {
+                println!(
+                    "  {identity}  via {} on {}",
+                    sender.method,
+                    sender.verified_at.format("%Y-%m-%d %H:%M UTC")
+                );
+            }
+            Ok(())
+        }
+        crate::ChannelCommands::ApproveSender { identity } => {
+            let identity = verification::SenderVerifier::for_config(config).approve(&identity)?;
+            println!("✅ {identity} is verified and may talk to the agent.");
+            Ok(())
+        }
+        crate::ChannelCommands::RevokeSender { identity } => {
+            let removed = verification::SenderVerifier::for_config(config).revoke(&identity)?;
+            if removed {
+                println!("🗑️ {identity} must verify again before the agent answers.");
+            } else {
+                println!("{identity} was not verified.");
+            }
+            Ok(())
         }
     }
 }
@@ -2671,24 +3659,30 @@ fn collect_configured_channels(
     if let Some(ref dc) = config.channels_config.discord {
         channels.push(ConfiguredChannel {
             display_name: "Discord",
-            channel: Arc::new(DiscordChannel::new(
-                dc.bot_token.clone(),
-                dc.guild_id.clone(),
-                dc.allowed_users.clone(),
-                dc.listen_to_bots,
-                dc.mention_only,
-            )),
+            channel: Arc::new(
+                DiscordChannel::new(
+                    dc.bot_token.clone(),
+                    dc.guild_id.clone(),
+                    dc.allowed_users.clone(),
+                    dc.listen_to_bots,
+                    dc.mention_only,
+                )
+                .with_streaming(dc.stream_mode, dc.draft_update_interval_ms),
+            ),
         });
     }
 
     if let Some(ref sl) = config.channels_config.slack {
         channels.push(ConfiguredChannel {
             display_name: "Slack",
-            channel: Arc::new(SlackChannel::new(
-                sl.bot_token.clone(),
-                sl.channel_id.clone(),
-                sl.allowed_users.clone(),
-            )),
+            channel: Arc::new(
+                SlackChannel::new(
+                    sl.bot_token.clone(),
+                    sl.channel_id.clone(),
+                    sl.allowed_users.clone(),
+                )
+                .with_streaming(sl.stream_mode, sl.draft_update_interval_ms),
+            ),
         });
     }
 
@@ -2955,8 +3949,18 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
 }
 
 /// Start all configured channels and route messages to the agent
-#[allow(clippy::too_many_lines)]
 pub async fn start_channels(config: Config) -> Result<()> {
+    Box::pin(start_channels_with(config, Vec::new())).await
+}
+
+/// Like [`start_channels`], with `extra_channels` served alongside the
+/// configured ones (used by `test_support` to plug in in-memory channels).
+#[allow(clippy::too_many_lines)]
+pub async fn start_channels_with(
+    config: Config,
+    extra_channels: Vec<Arc<dyn Channel>>,
+) -> Result<()> {
+    crate::config::ensure_runtime_globals(&config);
     let provider_name = resolved_default_provider(&config);
     let provider_runtime_options = providers::ProviderRuntimeOptions {
         auth_profile_override: None,
@@ -2964,8 +3968,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
         secrets_encrypt: config.secrets.encrypt,
         provider_api_url: config.api_url.clone(),
         reasoning_enabled: config.runtime.reasoning_enabled,
+        custom_providers: config.custom_providers.clone(),
+        provider_plugins: config.provider_plugins.clone(),
     };
-    let provider: Arc<dyn Provider> = Arc::from(
+    let provider: Arc<dyn Provider> = Arc::from(providers::shadow::wrap_with_shadow(
         create_resilient_provider_nonblocking(
             &provider_name,
             config.api_key.clone(),
@@ -2974,7 +3980,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
             provider_runtime_options.clone(),
         )
         .await?,
-    );
+        &provider_name,
+        &config,
+        &provider_runtime_options,
+    ));
 
     // Warm up the provider connection pool (TLS handshake, DNS, HTTP/2 setup)
     // so the first real message doesn't hit a cold-start timeout.
@@ -3065,6 +4074,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
         ),
+        (
+            "pin",
+            "Pin a memory, workspace file or note so it stays in context. Use when: the user asks to keep something in view for the whole conversation. Don't use when: a one-off lookup suffices.",
+        ),
     ];
 
     if config.browser.enabled {
@@ -3106,20 +4119,44 @@ pub async fn start_channels(config: Config) -> Result<()> {
     } else {
         None
     };
-    let native_tools = provider.supports_native_tools();
-    let mut system_prompt = build_system_prompt_with_mode(
-        &workspace,
-        &model,
-        &tool_descs,
-        &skills,
-        Some(&config.identity),
-        bootstrap_max_chars,
-        native_tools,
-        config.skills.prompt_injection_mode,
-    );
-    if !native_tools {
-        system_prompt.push_str(&build_tool_instructions(tools_registry.as_ref()));
-    }
+    let native_tools =
+        providers::capabilities::negotiate(provider.as_ref(), &provider_name, &model).native_tools;
+    // Rebuilt whenever `<workspace>/prompts/` overrides change.
+    let prompt_reloader = {
+        let workspace = workspace.clone();
+        let model = model.clone();
+        let tool_descs: Vec<(String, String)> = tool_descs
+            .iter()
+            .map(|(name, desc)| ((*name).to_string(), (*desc).to_string()))
+            .collect();
+        let skills = skills.clone();
+        let identity = config.identity.clone();
+        let prompt_injection_mode = config.skills.prompt_injection_mode;
+        let tools_registry = Arc::clone(&tools_registry);
+        crate::prompts::PromptReloader::new(&config.workspace_dir, move || {
+            let tool_descs: Vec<(&str, &str)> = tool_descs
+                .iter()
+                .map(|(name, desc)| (name.as_str(), desc.as_str()))
+                .collect();
+            let mut system_prompt = build_system_prompt_with_mode(
+                &workspace,
+                &model,
+                &tool_descs,
+                &skills,
+                Some(&identity),
+                bootstrap_max_chars,
+                native_tools,
+                prompt_injection_mode,
+            );
+            if !native_tools {
+                system_prompt.push_str(&build_tool_instructions(
+                    tools_registry.as_ref(),
+                    &workspace,
+                ));
+            }
+            system_prompt
+        })
+    };
 
     if !skills.is_empty() {
         println!(
@@ -3144,6 +4181,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
             NostrChannel::new(&ns.private_key, ns.relays.clone(), &ns.allowed_pubkeys).await?,
         ));
     }
+    channels.extend(extra_channels);
     if channels.is_empty() {
         println!("No channels configured. Run `zeroclaw onboard` to set up channels.");
         return Ok(());
@@ -3186,24 +4224,41 @@ pub async fn start_channels(config: Config) -> Result<()> {
     // Single message bus — all channels send messages here
     let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(100);
 
-    // Spawn a listener for each channel
+    // Spawn a listener for each channel. The handles abort their tasks when
+    // dropped, so listeners stop too if this future is cancelled (for example
+    // when an HA daemon steps down as leader).
     let mut handles = Vec::new();
     for ch in &channels {
-        handles.push(spawn_supervised_listener(
+        handles.push(AbortOnDropHandle::new(spawn_supervised_listener(
             ch.clone(),
             tx.clone(),
             initial_backoff_secs,
             max_backoff_secs,
-        ));
+        )));
     }
     drop(tx); // Drop our copy so rx closes when all channels stop
 
+    let command_menu_handle = config.channels_config.command_menu.then(|| {
+        let channels = channels.clone();
+        let workspace_dir = config.workspace_dir.clone();
+        AbortOnDropHandle::new(tokio::spawn(async move {
+            command_menu::publish_and_refresh(channels, &workspace_dir).await;
+        }))
+    });
+
     let channels_by_name = Arc::new(
         channels
             .iter()
             .map(|ch| (ch.name().to_string(), Arc::clone(ch)))
             .collect::<HashMap<_, _>>(),
     );
+    let collections = channel_collections(&config, channels_by_name.keys())?;
+    for (channel, collection) in &collections {
+        println!(
+            "  📚 {channel} answers only from collection '{}'",
+            collection.name
+        );
+    }
     let max_in_flight_messages = compute_max_in_flight_messages(channels.len());
 
     println!("  🚦 In-flight message limit: {max_in_flight_messages}");
@@ -3218,6 +4273,19 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .as_ref()
         .is_some_and(|tg| tg.interrupt_on_new_message);
 
+    let translator = translation::Translator::from_config(
+        &config.translation,
+        config.api_key.as_deref(),
+        &config.reliability,
+        &provider_runtime_options,
+    )?;
+    if translator.is_some() {
+        println!(
+            "  🌐 Translating channel messages to and from '{}'",
+            config.translation.working_language
+        );
+    }
+
     let runtime_ctx = Arc::new(ChannelRuntimeContext {
         channels_by_name,
         provider: Arc::clone(&provider),
@@ -3225,7 +4293,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         memory: Arc::clone(&mem),
         tools_registry: Arc::clone(&tools_registry),
         observer,
-        system_prompt: Arc::new(system_prompt),
+        system_prompt: prompt_reloader.prompt(),
         model: Arc::new(model.clone()),
         temperature,
         auto_save_memory: config.memory.auto_save,
@@ -3252,9 +4320,53 @@ pub async fn start_channels(config: Config) -> Result<()> {
             None
         },
         non_cli_excluded_tools: Arc::new(config.autonomy.non_cli_excluded_tools.clone()),
+        group_triggers: Arc::new(group_trigger::GroupTriggers::from_config(
+            &config.channels_config.group_triggers,
+        )),
+        long_messages: config.channels_config.long_messages.clone(),
+        queue_policy: crate::queue_store::QueuePolicy::from_config(&config),
+        inbox: crate::inbox::Inbox::from_config(&config).map(Arc::new),
+        collections: Arc::new(collections),
+        rag: crate::rag::collections::RagCollections::from_config(&config)?.map(Arc::new),
+        translator: translator.map(Arc::new),
+        incognito: Arc::new(incognito::IncognitoSessions::from_config(
+            &config.channels_config.incognito,
+        )),
+        skill_names: Arc::new(skills.iter().map(|skill| skill.name.clone()).collect()),
+        standing_instructions: Some(Arc::new(
+            crate::instructions::InstructionStore::from_config(&config),
+        )),
+        verification: verification::SenderVerifier::from_config(&config).map(Arc::new),
+        admin: admin::ChannelAdmin::from_config(&config).map(Arc::new),
+        prompt_reloader: Some(Arc::new(prompt_reloader)),
     });
+    let incognito_sweeper = {
+        let ctx = Arc::clone(&runtime_ctx);
+        AbortOnDropHandle::new(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                expire_incognito_sessions(&ctx);
+            }
+        }))
+    };
 
-    run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
+    let (live_registration, flush_requests) = live::register(
+        runtime_ctx.channels_by_name.keys().cloned().collect(),
+        &runtime_ctx.conversation_histories,
+    );
+    run_message_dispatch_loop(
+        rx,
+        runtime_ctx,
+        max_in_flight_messages,
+        Some(flush_requests),
+    )
+    .await;
+    drop(live_registration);
+    incognito_sweeper.abort();
+    if let Some(handle) = command_menu_handle {
+        handle.abort();
+    }
 
     // Wait for all channel tasks
     for h in handles {
@@ -3340,6 +4452,20 @@ mod tests {
         assert!(!is_context_window_overflow_error(&other_err));
     }
 
+    #[test]
+    fn render_draft_text_appends_live_status_line() {
+        assert_eq!(render_draft_text("", ""), "");
+        assert_eq!(render_draft_text("partial answer\n", ""), "partial answer");
+        assert_eq!(
+            render_draft_text("", "⏳ Running tool: shell…"),
+            "⏳ Running tool: shell…"
+        );
+        assert_eq!(
+            render_draft_text("✅ browser (2s)\n", "⏳ Running tool: shell…"),
+            "✅ browser (2s)\n\n⏳ Running tool: shell…"
+        );
+    }
+
     #[test]
     fn memory_context_skip_rules_exclude_history_blobs() {
         assert!(should_skip_memory_context_entry(
@@ -3465,6 +4591,19 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -3514,6 +4653,19 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         };
 
         append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
@@ -3566,6 +4718,19 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         };
 
         assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
@@ -4039,6 +5204,19 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4053,6 +5231,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -4098,6 +5277,19 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4112,6 +5304,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -4173,6 +5366,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -4185,6 +5391,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -4232,6 +5439,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -4244,6 +5464,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -4300,6 +5521,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -4312,6 +5546,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -4336,6 +5571,472 @@ BTC is currently around $65,000 based on latest tool output."#
         assert_eq!(fallback_provider_impl.call_count.load(Ordering::SeqCst), 0);
     }
 
+    #[tokio::test]
+    async fn process_channel_message_refuses_simulate_directive() {
+        let channel_impl = Arc::new(TelegramRecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(ModelCaptureProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-sim-1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-1".to_string(),
+                content: "/simulate check the BTC price".to_string(),
+                channel: "telegram".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+                group: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].contains("/simulate"));
+        assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_incognito_does_not_persist_preferences_or_instructions() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        let channel_impl = Arc::new(TelegramRecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(ModelCaptureProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+
+        let incognito = Arc::new(incognito::IncognitoSessions::default());
+        incognito.start("telegram_alice");
+        let standing = Arc::new(crate::instructions::InstructionStore::new(workspace.path()));
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(workspace.path().to_path_buf()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito,
+            skill_names: Arc::default(),
+            standing_instructions: Some(Arc::clone(&standing)),
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
+        });
+
+        for (id, content) in [
+            ("msg-incognito-1", "reply in German"),
+            (
+                "msg-incognito-2",
+                "from now on, always reply in metric units",
+            ),
+        ] {
+            process_channel_message(
+                Arc::clone(&runtime_ctx),
+                traits::ChannelMessage {
+                    id: id.to_string(),
+                    sender: "alice".to_string(),
+                    reply_target: "chat-1".to_string(),
+                    content: content.to_string(),
+                    channel: "telegram".to_string(),
+                    timestamp: 1,
+                    thread_ts: None,
+                    group: None,
+                },
+                CancellationToken::new(),
+            )
+            .await;
+        }
+
+        let preferences = crate::preferences::PreferenceStore::for_workspace(workspace.path());
+        assert!(preferences.list().unwrap().is_empty());
+        assert!(standing.list().unwrap().is_empty());
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent.len(), 2);
+        assert!(sent.iter().all(|reply| reply.contains("not saved")));
+        assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_observes_untriggered_group_messages() {
+        let channel_impl = Arc::new(TelegramRecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(ModelCaptureProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+
+        let tmp = TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+
+        let triggers = HashMap::from([(
+            "telegram".to_string(),
+            crate::config::GroupTriggerConfig {
+                observe: true,
+                ..Default::default()
+            },
+        )]);
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::clone(&mem),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::from_config(&triggers)),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
+        });
+
+        let group_msg = traits::ChannelMessage {
+            id: "msg-group-1".to_string(),
+            sender: "alice".to_string(),
+            reply_target: "-100".to_string(),
+            content: "the staging deploy is blocked on review".to_string(),
+            channel: "telegram".to_string(),
+            timestamp: 1,
+            thread_ts: None,
+            group: Some(traits::GroupContext::default()),
+        };
+        process_channel_message(
+            runtime_ctx.clone(),
+            group_msg.clone(),
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert!(channel_impl.sent_messages.lock().await.is_empty());
+        assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 0);
+        let stored = mem
+            .get(&group_trigger::observation_memory_key(&group_msg))
+            .await
+            .unwrap()
+            .expect("observed message should be stored");
+        assert_eq!(
+            stored.content,
+            "alice: the staging deploy is blocked on review"
+        );
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_expands_task_templates() {
+        let channel_impl = Arc::new(TelegramRecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+
+        let workspace = TempDir::new().unwrap();
+        crate::tasks::TaskStore::for_workspace(workspace.path())
+            .save(&crate::tasks::TaskTemplate {
+                name: "deploy-report".into(),
+                description: None,
+                prompt: "Report on the {{env}} deploy".into(),
+                inputs: vec![crate::tasks::TaskInput {
+                    name: "env".into(),
+                    description: None,
+                    default: None,
+                }],
+                tools: Vec::new(),
+            })
+            .unwrap();
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(workspace.path().to_path_buf()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
+        });
+
+        let message = |id: &str, content: &str| traits::ChannelMessage {
+            id: id.to_string(),
+            sender: "alice".to_string(),
+            reply_target: "chat-1".to_string(),
+            content: content.to_string(),
+            channel: "telegram".to_string(),
+            timestamp: 1,
+            thread_ts: None,
+            group: None,
+        };
+
+        process_channel_message(
+            runtime_ctx.clone(),
+            message("msg-1", "/task deploy-report env=prod"),
+            CancellationToken::new(),
+        )
+        .await;
+        {
+            let calls = provider_impl
+                .calls
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            assert_eq!(calls.len(), 1);
+            let (role, content) = calls[0].last().unwrap();
+            assert_eq!(role, "user");
+            assert!(content.ends_with("Report on the prod deploy"));
+        }
+
+        process_channel_message(
+            runtime_ctx,
+            message("msg-2", "/task deploy-report"),
+            CancellationToken::new(),
+        )
+        .await;
+        assert_eq!(
+            provider_impl
+                .calls
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .len(),
+            1
+        );
+        let sent = channel_impl.sent_messages.lock().await;
+        assert!(sent
+            .last()
+            .is_some_and(|message| message.contains("requires input 'env'")));
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_records_question_answers() {
+        let channel_impl = Arc::new(TelegramRecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+
+        let workspace = TempDir::new().unwrap();
+        let queue = crate::questions::QuestionQueue::for_workspace(workspace.path());
+        let asked = queue
+            .ask(
+                "Which region?",
+                vec!["eu".into(), "us".into()],
+                "Nightly deploy",
+                Some(("telegram".into(), "chat-1".into())),
+            )
+            .unwrap();
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(workspace.path().to_path_buf()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-1".to_string(),
+                content: "/answer 2".to_string(),
+                channel: "telegram".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+                group: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert!(provider_impl
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty());
+        let claimed = queue.take_answered().unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, asked.id);
+        assert_eq!(claimed[0].answer.as_deref(), Some("us"));
+        let sent = channel_impl.sent_messages.lock().await;
+        assert!(sent
+            .last()
+            .is_some_and(|message| message.contains(&asked.id)));
+    }
+
     #[tokio::test]
     async fn process_channel_message_uses_route_override_provider_and_model() {
         let channel_impl = Arc::new(TelegramRecordingChannel::default());
@@ -4389,6 +6090,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -4401,6 +6115,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -4460,6 +6175,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -4472,6 +6200,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -4546,6 +6275,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -4558,6 +6300,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 4,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -4617,6 +6360,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -4629,6 +6385,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -4677,6 +6434,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -4689,6 +6459,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -4848,6 +6619,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -4859,6 +6643,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "test-channel".to_string(),
             timestamp: 1,
             thread_ts: None,
+            group: None,
         })
         .await
         .unwrap();
@@ -4870,13 +6655,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "test-channel".to_string(),
             timestamp: 2,
             thread_ts: None,
+            group: None,
         })
         .await
         .unwrap();
         drop(tx);
 
         let started = Instant::now();
-        run_message_dispatch_loop(rx, runtime_ctx, 2).await;
+        run_message_dispatch_loop(rx, runtime_ctx, 2, None).await;
         let elapsed = started.elapsed();
 
         assert!(
@@ -4928,6 +6714,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -4940,6 +6739,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             })
             .await
             .unwrap();
@@ -4952,12 +6752,13 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
             })
             .await
             .unwrap();
         });
 
-        run_message_dispatch_loop(rx, runtime_ctx, 4).await;
+        run_message_dispatch_loop(rx, runtime_ctx, 4, None).await;
         send_task.await.unwrap();
 
         let sent_messages = channel_impl.sent_messages.lock().await;
@@ -5020,6 +6821,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5032,6 +6846,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             })
             .await
             .unwrap();
@@ -5044,12 +6859,13 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
             })
             .await
             .unwrap();
         });
 
-        run_message_dispatch_loop(rx, runtime_ctx, 4).await;
+        run_message_dispatch_loop(rx, runtime_ctx, 4, None).await;
         send_task.await.unwrap();
 
         let sent_messages = channel_impl.sent_messages.lock().await;
@@ -5094,6 +6910,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -5106,6 +6935,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -5153,6 +6983,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -5165,6 +7008,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -5234,7 +7078,7 @@ BTC is currently around $65,000 based on latest tool output."#
             "build_system_prompt should not emit protocol block directly"
         );
 
-        prompt.push_str(&build_tool_instructions(&[]));
+        prompt.push_str(&build_tool_instructions(&[], ws.path()));
 
         assert_eq!(
             prompt.matches("## Tool Use Protocol").count(),
@@ -5541,6 +7385,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            group: None,
         };
 
         assert_eq!(conversation_memory_key(&msg), "slack_U123_msg_abc123");
@@ -5556,6 +7401,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            group: None,
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -5565,6 +7411,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            group: None,
         };
 
         assert_ne!(
@@ -5586,6 +7433,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            group: None,
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -5595,6 +7443,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            group: None,
         };
 
         mem.store(
@@ -5669,6 +7518,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -5681,6 +7543,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -5696,6 +7559,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -5754,6 +7618,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -5766,6 +7643,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -5839,6 +7717,19 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -5851,6 +7742,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -6388,6 +8280,19 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         // Simulate a photo attachment message with [IMAGE:] marker.
@@ -6401,6 +8306,7 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -6454,6 +8360,19 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            group_triggers: Arc::new(group_trigger::GroupTriggers::default()),
+            long_messages: crate::config::LongMessageConfig::default(),
+            queue_policy: crate::queue_store::QueuePolicy::default(),
+            inbox: None,
+            collections: Arc::new(HashMap::new()),
+            rag: None,
+            translator: None,
+            incognito: Arc::default(),
+            skill_names: Arc::default(),
+            standing_instructions: None,
+            verification: None,
+            admin: None,
+            prompt_reloader: None,
         });
 
         process_channel_message(
@@ -6466,6 +8385,7 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
@@ -6481,6 +8401,7 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                group: None,
             },
             CancellationToken::new(),
         )
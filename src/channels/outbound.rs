@@ -0,0 +1,345 @@
+//! Channel-aware shaping of long outbound replies.
+//!
+//! Replies that exceed a platform's message limit are split into pages at
+//! paragraph or line boundaries. Fenced code blocks that must be split are
+//! closed at the end of one page and reopened (with the same info string) at
+//! the start of the next, and Markdown tables are kept on a single page when a
+//! nearby break allows it. Beyond `[channels_config.long_messages]
+//! file_threshold_chars`, the reply is uploaded as a file instead on channels
+//! that support attachments.
+
+use super::traits::{Channel, SendMessage};
+use crate::config::{LongMessageConfig, LongMessageFileFormat};
+use crate::i18n;
+use crate::util::truncate_with_ellipsis;
+use std::fmt::Write;
+
+/// Characters reserved at the end of each page for a `(n/m)` marker.
+const PAGE_MARKER_RESERVE: usize = 12;
+/// Characters of the reply quoted in the caption of a file attachment.
+const FILE_PREVIEW_CHARS: usize = 280;
+const FENCE_CLOSE: &str = "```\n";
+
+/// Deliver a final reply, paginating or attaching it as a file as needed.
+///
+/// When a streaming draft exists it is finalized in place if the reply fits a
+/// single message; otherwise the draft is removed and the reply is re-sent.
+pub async fn deliver_reply(
+    channel: &dyn Channel,
+    reply_target: &str,
+    thread_ts: Option<&str>,
+    draft_id: Option<&str>,
+    text: &str,
+    config: &LongMessageConfig,
+) -> anyhow::Result<()> {
+    let chars = text.chars().count();
+    let thread_ts = thread_ts.map(str::to_string);
+
+    if config.file_threshold_chars > 0 && chars > config.file_threshold_chars {
+        let file_name = format!("reply.{}", config.file_format.extension());
+        let caption = file_caption(text, chars, &file_name);
+        let message = SendMessage::new(caption, reply_target).in_thread(thread_ts.clone());
+        match channel
+            .send_file(&message, &file_name, text.as_bytes())
+            .await
+        {
+            Ok(true) => {
+                if let Some(draft_id) = draft_id {
+                    let _ = channel.cancel_draft(reply_target, draft_id).await;
+                }
+                return Ok(());
+            }
+            Ok(false) => {}
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to attach long reply on {}: {e}; paginating instead",
+                    channel.name()
+                );
+            }
+        }
+    }
+
+    let fits = channel
+        .max_message_length()
+        .map_or(true, |limit| chars <= limit);
+
+    if let Some(draft_id) = draft_id {
+        if fits {
+            if let Err(e) = channel.finalize_draft(reply_target, draft_id, text).await {
+                tracing::warn!("Failed to finalize draft: {e}; sending as new message");
+                return channel
+                    .send(&SendMessage::new(text, reply_target).in_thread(thread_ts))
+                    .await;
+            }
+            return Ok(());
+        }
+        let _ = channel.cancel_draft(reply_target, draft_id).await;
+    }
+
+    let pages = match channel.max_message_length() {
+        Some(limit) if !fits => paginate(text, limit, config.page_markers),
+        _ => vec![text.to_string()],
+    };
+    for page in pages {
+        channel
+            .send(&SendMessage::new(page, reply_target).in_thread(thread_ts.clone()))
+            .await?;
+    }
+    Ok(())
+}
+
+fn file_caption(text: &str, chars: usize, file_name: &str) -> String {
+    let preview = text
+        .split("\n\n")
+        .map(str::trim)
+        .find(|paragraph| !paragraph.is_empty() && !paragraph.starts_with("```"))
+        .map(|paragraph| truncate_with_ellipsis(paragraph, FILE_PREVIEW_CHARS))
+        .unwrap_or_default();
+    let note = i18n::tr_args(
+        "channel.long_reply_attached",
+        &[("chars", &chars.to_string()), ("file", file_name)],
+    );
+    if preview.is_empty() {
+        note
+    } else {
+        format!("{preview}\n\n{note}")
+    }
+}
+
+/// Split `text` into pages of at most `limit` characters, optionally
+/// suffixed with `(n/m)` markers.
+pub fn paginate(text: &str, limit: usize, page_markers: bool) -> Vec<String> {
+    if text.chars().count() <= limit {
+        return vec![text.to_string()];
+    }
+    let budget = if page_markers && limit > PAGE_MARKER_RESERVE * 4 {
+        limit - PAGE_MARKER_RESERVE
+    } else {
+        limit
+    };
+    let mut pages = split_markdown(text, budget);
+    if page_markers && pages.len() > 1 {
+        let total = pages.len();
+        for (i, page) in pages.iter_mut().enumerate() {
+            let trimmed_len = page.trim_end().len();
+            page.truncate(trimmed_len);
+            let _ = write!(page, "\n\n({}/{total})", i + 1);
+        }
+    }
+    pages
+}
+
+/// Split Markdown into chunks of at most `limit` characters without breaking
+/// code fences or (where possible) tables.
+pub fn split_markdown(text: &str, limit: usize) -> Vec<String> {
+    let limit = limit.max(32);
+    let lines = prepare_lines(text, limit / 2);
+    let mut pages = Vec::new();
+    let mut start = 0;
+    let mut reopen: Option<String> = None;
+    while start < lines.len() {
+        let (page, next, carry) = take_page(&lines, start, limit, reopen.as_deref());
+        if !page.trim().is_empty() {
+            pages.push(page);
+        }
+        start = next;
+        reopen = carry;
+    }
+    if pages.is_empty() {
+        pages.push(String::new());
+    }
+    pages
+}
+
+/// Split into newline-terminated lines, hard-splitting any line longer than
+/// `max_line` characters.
+fn prepare_lines(text: &str, max_line: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for line in text.split_inclusive('\n') {
+        if line.chars().count() <= max_line {
+            lines.push(line.to_string());
+            continue;
+        }
+        let chars: Vec<char> = line.chars().collect();
+        for piece in chars.chunks(max_line) {
+            lines.push(piece.iter().collect());
+        }
+    }
+    lines
+}
+
+fn fence_marker(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    (trimmed.starts_with("```") || trimmed.starts_with("~~~")).then(|| trimmed.trim_end())
+}
+
+fn is_table_row(line: &str) -> bool {
+    line.trim_start().starts_with('|')
+}
+
+/// A position where the page may end: next line index, byte length and char
+/// length of the page up to that point.
+#[derive(Clone, Copy)]
+struct Break {
+    next: usize,
+    bytes: usize,
+    chars: usize,
+}
+
+fn take_page(
+    lines: &[String],
+    start: usize,
+    limit: usize,
+    reopen: Option<&str>,
+) -> (String, usize, Option<String>) {
+    let mut page = String::new();
+    let mut page_chars = 0;
+    let mut fence: Option<String> = None;
+    if let Some(opener) = reopen {
+        page.push_str(opener);
+        page.push('\n');
+        page_chars = opener.chars().count() + 1;
+        fence = Some(opener.to_string());
+    }
+
+    let mut paragraph_break: Option<Break> = None;
+    let mut line_break: Option<Break> = None;
+
+    for (j, line) in lines.iter().enumerate().skip(start) {
+        let line_chars = line.chars().count();
+        let next_fence = match (fence.as_deref(), fence_marker(line)) {
+            (Some(_), Some(_)) => None,
+            (None, Some(opener)) => Some(opener.to_string()),
+            (current, None) => current.map(str::to_string),
+        };
+        let close_cost = if next_fence.is_some() {
+            FENCE_CLOSE.len() + 1
+        } else {
+            0
+        };
+
+        if j > start && page_chars + line_chars + close_cost > limit {
+            let half = limit / 2;
+            if let Some(brk) = paragraph_break
+                .filter(|b| b.chars >= half)
+                .or(line_break.filter(|b| b.chars >= half))
+            {
+                page.truncate(brk.bytes);
+                return (page, brk.next, None);
+            }
+            if let Some(opener) = fence {
+                if !page.ends_with('\n') {
+                    page.push('\n');
+                }
+                page.push_str(FENCE_CLOSE);
+                return (page, j, Some(opener));
+            }
+            return (page, j, None);
+        }
+
+        page.push_str(line);
+        page_chars += line_chars;
+        fence = next_fence;
+
+        if fence.is_none() {
+            let here = Break {
+                next: j + 1,
+                bytes: page.len(),
+                chars: page_chars,
+            };
+            if line.trim().is_empty() {
+                paragraph_break = Some(here);
+            }
+            let inside_table =
+                is_table_row(line) && lines.get(j + 1).is_some_and(|next| is_table_row(next));
+            if !inside_table {
+                line_break = Some(here);
+            }
+        }
+    }
+
+    (page, lines.len(), None)
+}
+
+impl LongMessageFileFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Text => "txt",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text_is_a_single_page() {
+        assert_eq!(paginate("hello", 100, true), vec!["hello".to_string()]);
+    }
+
+    #[test]
+    fn pages_respect_limit_and_carry_markers() {
+        let text = (0..200).fold(String::new(), |mut text, i| {
+            let _ = write!(text, "paragraph {i} with some words in it\n\n");
+            text
+        });
+        let pages = paginate(&text, 500, true);
+        assert!(pages.len() > 1);
+        let total = pages.len();
+        for (i, page) in pages.iter().enumerate() {
+            assert!(page.chars().count() <= 500, "page {i} too long");
+            assert!(page.ends_with(&format!("({}/{total})", i + 1)));
+        }
+    }
+
+    #[test]
+    fn split_code_fence_is_closed_and_reopened() {
+        let body = (0..80).fold(String::new(), |mut body, i| {
+            let _ = writeln!(body, "let value_{i} = {i};");
+            body
+        });
+        let text = format!("Intro\n\n```rust\n{body}```\n\nDone.\n");
+        let pages = split_markdown(&text, 400);
+        assert!(pages.len() > 1);
+        for page in &pages {
+            assert!(page.chars().count() <= 400);
+            let fences = page
+                .lines()
+                .filter(|line| line.trim_start().starts_with("```"))
+                .count();
+            assert_eq!(fences % 2, 0, "unbalanced fence in page:\n{page}");
+        }
+        assert!(pages[1].starts_with("```rust\n"));
+        let rejoined: String = pages.concat();
+        for i in 0..80 {
+            assert!(rejoined.contains(&format!("let value_{i} = {i};")));
+        }
+    }
+
+    #[test]
+    fn tables_stay_on_one_page_when_possible() {
+        let filler = "word ".repeat(50);
+        let table = "| a | b |\n|---|---|\n| 1 | 2 |\n| 3 | 4 |\n| 5 | 6 |\n";
+        let text = format!("{filler}\n{filler}\n{table}after\n");
+        let pages = split_markdown(&text, 540);
+        assert!(pages.len() > 1);
+        assert!(pages.iter().any(|page| page.contains(table)));
+    }
+
+    #[test]
+    fn overlong_lines_are_hard_split() {
+        let text = "x".repeat(1000);
+        let pages = split_markdown(&text, 300);
+        assert!(pages.iter().all(|page| page.chars().count() <= 300));
+        assert_eq!(pages.concat(), text);
+    }
+
+    #[test]
+    fn file_caption_quotes_first_prose_paragraph() {
+        let caption = file_caption("```\ncode\n```\n\nSummary line.\n\nMore.", 9000, "reply.md");
+        assert!(caption.starts_with("Summary line."));
+        assert!(caption.contains("reply.md"));
+    }
+}
@@ -1,13 +1,21 @@
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::StreamMode;
 use async_trait::async_trait;
+use parking_lot::Mutex;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+/// Slack truncates `chat.postMessage` text beyond this many characters.
+const SLACK_MAX_MESSAGE_LENGTH: usize = 40_000;
+
 /// Slack channel — polls conversations.history via Web API
 pub struct SlackChannel {
     bot_token: String,
     channel_id: Option<String>,
     allowed_users: Vec<String>,
+    stream_mode: StreamMode,
+    draft_update_interval_ms: u64,
+    last_draft_edit: Mutex<HashMap<String, Instant>>,
 }
 
 impl SlackChannel {
@@ -16,7 +24,55 @@ impl SlackChannel {
             bot_token,
             channel_id,
             allowed_users,
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            last_draft_edit: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Configure streaming mode for progressive draft updates.
+    pub fn with_streaming(
+        mut self,
+        stream_mode: StreamMode,
+        draft_update_interval_ms: u64,
+    ) -> Self {
+        self.stream_mode = stream_mode;
+        self.draft_update_interval_ms = draft_update_interval_ms;
+        self
+    }
+
+    /// Call a Slack Web API method, treating `"ok": false` as an error.
+    async fn api_call(
+        &self,
+        method: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let resp = self
+            .http_client()
+            .post(format!("https://slack.com/api/{method}"))
+            .bearer_auth(&self.bot_token)
+            .json(body)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        let text = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+        if !status.is_success() {
+            anyhow::bail!("Slack {method} failed ({status}): {text}");
+        }
+
+        let parsed: serde_json::Value = serde_json::from_str(&text).unwrap_or_default();
+        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
+            let err = parsed
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack {method} failed: {err}");
         }
+        Ok(parsed)
     }
 
     fn http_client(&self) -> reqwest::Client {
@@ -341,6 +397,7 @@ impl Channel for SlackChannel {
                                 .unwrap_or_default()
                                 .as_secs(),
                             thread_ts: Self::inbound_thread_ts(msg, ts),
+                            group: None,
                         };
 
                         if tx.send(channel_msg).await.is_err() {
@@ -352,6 +409,165 @@ impl Channel for SlackChannel {
         }
     }
 
+    fn max_message_length(&self) -> Option<usize> {
+        Some(SLACK_MAX_MESSAGE_LENGTH)
+    }
+
+    async fn send_file(
+        &self,
+        message: &SendMessage,
+        file_name: &str,
+        contents: &[u8],
+    ) -> anyhow::Result<bool> {
+        // External upload flow: reserve an upload URL, post the bytes to it,
+        // then share the file into the conversation.
+        let resp = self
+            .http_client()
+            .post("https://slack.com/api/files.getUploadURLExternal")
+            .bearer_auth(&self.bot_token)
+            .form(&[
+                ("filename", file_name.to_string()),
+                ("length", contents.len().to_string()),
+            ])
+            .send()
+            .await?;
+        let status = resp.status();
+        let reserved: serde_json::Value = resp.json().await.unwrap_or_default();
+        let (Some(upload_url), Some(file_id)) = (
+            reserved.get("upload_url").and_then(|v| v.as_str()),
+            reserved.get("file_id").and_then(|v| v.as_str()),
+        ) else {
+            let err = reserved
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack files.getUploadURLExternal failed ({status}): {err}");
+        };
+
+        let resp = self
+            .http_client()
+            .post(upload_url)
+            .body(contents.to_vec())
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Slack file upload failed ({})", resp.status());
+        }
+
+        let mut body = serde_json::json!({
+            "files": [{ "id": file_id, "title": file_name }],
+            "channel_id": message.recipient,
+        });
+        if !message.content.is_empty() {
+            body["initial_comment"] = serde_json::json!(message.content);
+        }
+        if let Some(ref ts) = message.thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
+        }
+        self.api_call("files.completeUploadExternal", &body).await?;
+        Ok(true)
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
+        }
+
+        let initial_text = if message.content.is_empty() {
+            "..."
+        } else {
+            message.content.as_str()
+        };
+        let mut body = serde_json::json!({
+            "channel": message.recipient,
+            "text": initial_text,
+        });
+        if let Some(ref ts) = message.thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
+        }
+
+        let resp = self.api_call("chat.postMessage", &body).await?;
+        self.last_draft_edit
+            .lock()
+            .insert(message.recipient.clone(), Instant::now());
+
+        Ok(resp
+            .get("ts")
+            .and_then(|ts| ts.as_str())
+            .map(str::to_string))
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        // Rate-limit edits per channel
+        {
+            let last_edits = self.last_draft_edit.lock();
+            if let Some(last_time) = last_edits.get(recipient) {
+                let elapsed = u64::try_from(last_time.elapsed().as_millis()).unwrap_or(u64::MAX);
+                if elapsed < self.draft_update_interval_ms {
+                    return Ok(());
+                }
+            }
+        }
+
+        let body = serde_json::json!({
+            "channel": recipient,
+            "ts": message_id,
+            "text": text,
+        });
+        match self.api_call("chat.update", &body).await {
+            Ok(_) => {
+                self.last_draft_edit
+                    .lock()
+                    .insert(recipient.to_string(), Instant::now());
+            }
+            Err(e) => tracing::debug!("Slack draft update failed: {e}"),
+        }
+
+        Ok(())
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+
+        let body = serde_json::json!({
+            "channel": recipient,
+            "ts": message_id,
+            "text": text,
+        });
+        if let Err(e) = self.api_call("chat.update", &body).await {
+            tracing::warn!("Slack finalize_draft edit failed: {e}; sending as new message");
+            return self.send(&SendMessage::new(text, recipient)).await;
+        }
+        Ok(())
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        self.last_draft_edit.lock().remove(recipient);
+
+        let body = serde_json::json!({
+            "channel": recipient,
+            "ts": message_id,
+        });
+        if let Err(e) = self.api_call("chat.delete", &body).await {
+            tracing::debug!("Slack draft delete failed: {e}");
+        }
+        Ok(())
+    }
+
     async fn health_check(&self) -> bool {
         self.http_client()
             .get("https://slack.com/api/auth.test")
@@ -373,6 +589,25 @@ mod tests {
         assert_eq!(ch.name(), "slack");
     }
 
+    #[test]
+    fn supports_draft_updates_respects_stream_mode() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec![]);
+        assert!(!ch.supports_draft_updates());
+        let ch = ch.with_streaming(StreamMode::Partial, 500);
+        assert!(ch.supports_draft_updates());
+        assert_eq!(ch.draft_update_interval_ms, 500);
+    }
+
+    #[tokio::test]
+    async fn send_draft_returns_none_when_stream_mode_off() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec![]);
+        let id = ch
+            .send_draft(&SendMessage::new("draft", "C12345"))
+            .await
+            .unwrap();
+        assert!(id.is_none());
+    }
+
     #[test]
     fn slack_channel_with_channel_id() {
         let ch = SlackChannel::new("xoxb-fake".into(), Some("C12345".into()), vec![]);
@@ -1,4 +1,4 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelMessage, GroupContext, SendMessage};
 use crate::config::{Config, StreamMode};
 use crate::security::pairing::PairingGuard;
 use anyhow::Context;
@@ -561,6 +561,30 @@ impl TelegramChannel {
             .unwrap_or(false)
     }
 
+    /// Describe how a group-chat message relates to the bot. Returns `None`
+    /// for private chats.
+    fn group_context(&self, message: &serde_json::Value, text: &str) -> Option<GroupContext> {
+        if !Self::is_group_message(message) {
+            return None;
+        }
+        let bot_username = self.bot_username.lock().clone();
+        let Some(bot_username) = bot_username else {
+            return Some(GroupContext::default());
+        };
+        let replies_to_bot = message
+            .get("reply_to_message")
+            .and_then(|reply| reply.get("from"))
+            .and_then(|from| from.get("username"))
+            .and_then(serde_json::Value::as_str)
+            .is_some_and(|username| {
+                username.eq_ignore_ascii_case(bot_username.trim_start_matches('@'))
+            });
+        Some(GroupContext {
+            mentions_bot: Self::contains_bot_mention(text, &bot_username),
+            replies_to_bot,
+        })
+    }
+
     fn is_user_allowed(&self, username: &str) -> bool {
         let identity = Self::normalize_identity(username);
         self.allowed_users
@@ -633,7 +657,7 @@ impl TelegramChannel {
 
                         if let Some(identity) = bind_identity {
                             self.add_allowed_identity_runtime(&identity);
-                            match self.persist_allowed_identity(&identity).await {
+                            match Box::pin(self.persist_allowed_identity(&identity)).await {
                                 Ok(()) => {
                                     let _ = self
                                         .send(&SendMessage::new(
@@ -950,6 +974,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         if let Some(quote) = self.extract_reply_context(message) {
             content = format!("{quote}\n\n{content}");
         }
+        let group = self.group_context(message, attachment.caption.as_deref().unwrap_or(""));
 
         Some(ChannelMessage {
             id: format!("telegram_{chat_id}_{message_id}"),
@@ -962,6 +987,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: None,
+            group,
         })
     }
 
@@ -1062,6 +1088,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             cache.insert(format!("{chat_id}:{message_id}"), text.clone());
         }
 
+        let group = self.group_context(message, &text);
         let content = if let Some(quote) = self.extract_reply_context(message) {
             format!("{quote}\n\n[Voice] {text}")
         } else {
@@ -1079,6 +1106,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: None,
+            group,
         })
     }
 
@@ -1223,6 +1251,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         } else {
             content
         };
+        let group = self.group_context(message, text);
 
         Some(ChannelMessage {
             id: format!("telegram_{chat_id}_{message_id}"),
@@ -1235,6 +1264,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: None,
+            group,
         })
     }
 
@@ -2052,12 +2082,63 @@ Allowlist Telegram username (without '@') or numeric user ID.",
     }
 }
 
+/// `setMyCommands` payload for the command menu.
+fn set_my_commands_body(commands: &[super::command_menu::BotCommand]) -> serde_json::Value {
+    serde_json::json!({
+        "commands": commands
+            .iter()
+            .map(|c| serde_json::json!({ "command": c.name, "description": c.description }))
+            .collect::<Vec<_>>(),
+    })
+}
+
 #[async_trait]
 impl Channel for TelegramChannel {
     fn name(&self) -> &str {
         "telegram"
     }
 
+    fn max_message_length(&self) -> Option<usize> {
+        Some(TELEGRAM_MAX_MESSAGE_LENGTH)
+    }
+
+    async fn send_file(
+        &self,
+        message: &SendMessage,
+        file_name: &str,
+        contents: &[u8],
+    ) -> anyhow::Result<bool> {
+        let (chat_id, thread_id) = Self::parse_reply_target(&message.recipient);
+        let caption = (!message.content.is_empty()).then_some(message.content.as_str());
+        self.send_document_bytes(
+            &chat_id,
+            thread_id.as_deref(),
+            contents.to_vec(),
+            file_name,
+            caption,
+        )
+        .await?;
+        Ok(true)
+    }
+
+    async fn register_commands(
+        &self,
+        commands: &[super::command_menu::BotCommand],
+    ) -> anyhow::Result<bool> {
+        let resp = self
+            .http_client()
+            .post(self.api_url("setMyCommands"))
+            .json(&set_my_commands_body(commands))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Telegram setMyCommands failed ({status}): {err}");
+        }
+        Ok(true)
+    }
+
     fn supports_draft_updates(&self) -> bool {
         self.stream_mode != StreamMode::Off
     }
@@ -2421,7 +2502,7 @@ Ensure only one `zeroclaw` process is using this bot token."
                     } else if let Some(m) = self.try_parse_attachment_message(update).await {
                         m
                     } else {
-                        self.handle_unauthorized_message(update).await;
+                        Box::pin(self.handle_unauthorized_message(update)).await;
                         continue;
                     };
 
@@ -3520,6 +3601,49 @@ mod tests {
         assert!(ch.parse_update_message(&update).is_none());
     }
 
+    #[test]
+    fn parse_update_message_reports_group_context() {
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false);
+        {
+            let mut cache = ch.bot_username.lock();
+            *cache = Some("mybot".to_string());
+        }
+
+        let update = serde_json::json!({
+            "update_id": 12,
+            "message": {
+                "message_id": 46,
+                "text": "sounds good",
+                "from": { "id": 555, "username": "alice" },
+                "chat": { "id": -100_200_300, "type": "supergroup" },
+                "reply_to_message": {
+                    "message_id": 40,
+                    "text": "Deploy finished.",
+                    "from": { "id": 1, "username": "MyBot", "is_bot": true }
+                }
+            }
+        });
+        let msg = ch.parse_update_message(&update).unwrap();
+        assert_eq!(
+            msg.group,
+            Some(GroupContext {
+                mentions_bot: false,
+                replies_to_bot: true,
+            })
+        );
+
+        let private = serde_json::json!({
+            "update_id": 13,
+            "message": {
+                "message_id": 47,
+                "text": "hello",
+                "from": { "id": 555, "username": "alice" },
+                "chat": { "id": 555, "type": "private" }
+            }
+        });
+        assert!(ch.parse_update_message(&private).unwrap().group.is_none());
+    }
+
     #[test]
     fn parse_update_message_mention_only_group_strips_mention_and_drops_empty() {
         let ch = TelegramChannel::new("token".into(), vec!["*".into()], true);
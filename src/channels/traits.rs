@@ -12,6 +12,19 @@ pub struct ChannelMessage {
     /// Platform thread identifier (e.g. Slack `ts`, Discord thread ID).
     /// When set, replies should be posted as threaded responses.
     pub thread_ts: Option<String>,
+    /// Group-chat metadata. `None` for direct conversations or channels that
+    /// do not distinguish group chats; such messages always engage the agent.
+    pub group: Option<GroupContext>,
+}
+
+/// How an inbound group-chat message relates to the bot, used to evaluate
+/// `[channels_config.group_triggers]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupContext {
+    /// The message @-mentions the bot.
+    pub mentions_bot: bool,
+    /// The message is a reply to one of the bot's own messages.
+    pub replies_to_bot: bool,
 }
 
 /// Message to send through a channel
@@ -84,6 +97,33 @@ pub trait Channel: Send + Sync {
         Ok(())
     }
 
+    /// Maximum characters per outbound message, if the platform enforces one.
+    /// Longer replies are split into pages before they reach [`Channel::send`].
+    fn max_message_length(&self) -> Option<usize> {
+        None
+    }
+
+    /// Upload `contents` as a file named `file_name`, using `message.content`
+    /// as the caption. Returns `Ok(false)` when the channel cannot send files.
+    async fn send_file(
+        &self,
+        _message: &SendMessage,
+        _file_name: &str,
+        _contents: &[u8],
+    ) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    /// Replace the platform's native command menu (Telegram bot commands,
+    /// Discord slash commands). Returns `Ok(false)` when the channel has no
+    /// command menu.
+    async fn register_commands(
+        &self,
+        _commands: &[super::command_menu::BotCommand],
+    ) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
     /// Whether this channel supports progressive message updates via draft edits.
     fn supports_draft_updates(&self) -> bool {
         false
@@ -172,6 +212,7 @@ mod tests {
                 channel: "dummy".into(),
                 timestamp: 123,
                 thread_ts: None,
+                group: None,
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))
@@ -188,6 +229,7 @@ mod tests {
             channel: "dummy".into(),
             timestamp: 999,
             thread_ts: None,
+            group: None,
         };
 
         let cloned = message.clone();
@@ -210,6 +252,7 @@ mod tests {
             .send(&SendMessage::new("hello", "bob"))
             .await
             .is_ok());
+        assert!(!channel.register_commands(&[]).await.unwrap());
     }
 
     #[tokio::test]
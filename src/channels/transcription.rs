@@ -31,16 +31,104 @@ fn normalize_audio_filename(file_name: &str) -> String {
     }
 }
 
+/// Whether `api_url` points at a self-hosted server on this machine or LAN.
+pub fn is_local_endpoint(api_url: &str) -> bool {
+    reqwest::Url::parse(api_url.trim())
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .is_some_and(|host| crate::tools::http_request::is_private_or_local_host(&host))
+}
+
 /// Transcribe audio bytes via a Whisper-compatible transcription API.
 ///
 /// Returns the transcribed text on success.  Requires `GROQ_API_KEY` in the
-/// environment.  The caller is responsible for enforcing duration limits
-/// *before* downloading the file; this function enforces the byte-size cap.
+/// environment unless `api_url` is a local endpoint.  The caller is
+/// responsible for enforcing duration limits *before* downloading the file;
+/// this function enforces the byte-size cap.
 pub async fn transcribe_audio(
     audio_data: Vec<u8>,
     file_name: &str,
     config: &TranscriptionConfig,
 ) -> Result<String> {
+    let body = request_transcription(audio_data, file_name, config, "json").await?;
+    let text = body["text"]
+        .as_str()
+        .context("Transcription response missing 'text' field")?
+        .to_string();
+
+    Ok(text)
+}
+
+/// A timed piece of a transcript. `speaker` is only set by servers that diarize.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptSegment {
+    /// Seconds from the start of the audio.
+    pub start: f64,
+    pub end: f64,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+/// Transcribe audio with timestamps (`verbose_json`).
+///
+/// Speaker labels are kept when the server returns them (WhisperX-style
+/// `speaker` fields on segments); plain Whisper servers yield unlabeled
+/// segments. Same key and size rules as [`transcribe_audio`].
+pub async fn transcribe_segments(
+    audio_data: Vec<u8>,
+    file_name: &str,
+    config: &TranscriptionConfig,
+) -> Result<Vec<TranscriptSegment>> {
+    let body = request_transcription(audio_data, file_name, config, "verbose_json").await?;
+    Ok(parse_segments(&body))
+}
+
+/// Segments from a `verbose_json` response, falling back to the whole `text`.
+pub fn parse_segments(body: &serde_json::Value) -> Vec<TranscriptSegment> {
+    let segments: Vec<TranscriptSegment> = body["segments"]
+        .as_array()
+        .map(|segments| {
+            segments
+                .iter()
+                .filter_map(|segment| {
+                    let text = segment["text"].as_str()?.trim();
+                    let speaker = match &segment["speaker"] {
+                        serde_json::Value::String(label) if !label.trim().is_empty() => {
+                            Some(label.trim().to_string())
+                        }
+                        serde_json::Value::Number(id) => Some(format!("SPEAKER_{id}")),
+                        _ => None,
+                    };
+                    (!text.is_empty()).then(|| TranscriptSegment {
+                        start: segment["start"].as_f64().unwrap_or(0.0),
+                        end: segment["end"].as_f64().unwrap_or(0.0),
+                        speaker,
+                        text: text.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if !segments.is_empty() {
+        return segments;
+    }
+    match body["text"].as_str().map(str::trim) {
+        Some(text) if !text.is_empty() => vec![TranscriptSegment {
+            start: 0.0,
+            end: body["duration"].as_f64().unwrap_or(0.0),
+            speaker: None,
+            text: text.to_string(),
+        }],
+        _ => Vec::new(),
+    }
+}
+
+async fn request_transcription(
+    audio_data: Vec<u8>,
+    file_name: &str,
+    config: &TranscriptionConfig,
+    response_format: &str,
+) -> Result<serde_json::Value> {
     if audio_data.len() > MAX_AUDIO_BYTES {
         bail!(
             "Audio file too large ({} bytes, max {MAX_AUDIO_BYTES})",
@@ -59,9 +147,14 @@ pub async fn transcribe_audio(
         )
     })?;
 
-    let api_key = std::env::var("GROQ_API_KEY").context(
-        "GROQ_API_KEY environment variable is not set — required for voice transcription",
-    )?;
+    let api_key = match std::env::var("GROQ_API_KEY") {
+        Ok(key) => Some(key),
+        // Self-hosted Whisper servers usually run without authentication.
+        Err(_) if is_local_endpoint(&config.api_url) => None,
+        Err(_) => {
+            bail!("GROQ_API_KEY environment variable is not set — required for voice transcription")
+        }
+    };
 
     let client = crate::config::build_runtime_proxy_client("transcription.groq");
 
@@ -72,15 +165,17 @@ pub async fn transcribe_audio(
     let mut form = Form::new()
         .part("file", file_part)
         .text("model", config.model.clone())
-        .text("response_format", "json");
+        .text("response_format", response_format.to_string());
 
     if let Some(ref lang) = config.language {
         form = form.text("language", lang.clone());
     }
 
-    let resp = client
-        .post(&config.api_url)
-        .bearer_auth(&api_key)
+    let mut request = client.post(&config.api_url);
+    if let Some(api_key) = &api_key {
+        request = request.bearer_auth(api_key);
+    }
+    let resp = request
         .multipart(form)
         .send()
         .await
@@ -97,12 +192,7 @@ pub async fn transcribe_audio(
         bail!("Transcription API error ({}): {}", status, error_msg);
     }
 
-    let text = body["text"]
-        .as_str()
-        .context("Transcription response missing 'text' field")?
-        .to_string();
-
-    Ok(text)
+    Ok(body)
 }
 
 #[cfg(test)]
@@ -140,6 +230,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verbose_segments_keep_speaker_labels() {
+        let body = serde_json::json!({
+            "text": "Hi. Hello.",
+            "segments": [
+                {"start": 0.0, "end": 1.5, "text": " Hi.", "speaker": "SPEAKER_00"},
+                {"start": 1.5, "end": 2.0, "text": "  "},
+                {"start": 2.0, "end": 3.0, "text": "Hello.", "speaker": 1}
+            ]
+        });
+        let segments = parse_segments(&body);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].speaker.as_deref(), Some("SPEAKER_00"));
+        assert_eq!(segments[1].speaker.as_deref(), Some("SPEAKER_1"));
+
+        let plain = parse_segments(&serde_json::json!({"text": " Just text ", "duration": 4.0}));
+        assert_eq!(plain[0].text, "Just text");
+        assert_eq!(plain[0].end, 4.0);
+    }
+
+    #[test]
+    fn local_endpoints_are_detected() {
+        assert!(is_local_endpoint(
+            "http://127.0.0.1:8000/v1/audio/transcriptions"
+        ));
+        assert!(is_local_endpoint("http://192.168.1.5:9000/inference"));
+        assert!(is_local_endpoint(
+            "http://whisper.local/v1/audio/transcriptions"
+        ));
+        assert!(!is_local_endpoint(&TranscriptionConfig::default().api_url));
+        assert!(!is_local_endpoint("not a url"));
+    }
+
     #[test]
     fn mime_for_audio_maps_accepted_formats() {
         let cases = [
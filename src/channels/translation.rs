@@ -0,0 +1,392 @@
+//! Language detection and translation for channel messages (`[translation]`).
+//!
+//! Incoming messages are detected and, when they are not in the agent's
+//! working language, translated before the agent sees them; the reply is
+//! translated back into the sender's language. A channel can be pinned to a
+//! language in `[translation.channels]`, which skips detection. Messages too
+//! short to detect reliably reuse the sender's last detected language.
+
+use crate::config::TranslationConfig;
+use crate::providers::{self, Provider};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Pin value that keeps detection on for a channel.
+pub const AUTO: &str = "auto";
+
+const DETECT_PROMPT: &str = "Detect the language of the user's message and translate it into \
+the target language given below. Answer with JSON only: {\"language\": \"<ISO 639-1 code>\", \
+\"translation\": \"<text>\"}. When the message is already in the target language, return an \
+empty translation. Translate faithfully; keep names, code, URLs and formatting unchanged.";
+
+const TRANSLATE_PROMPT: &str = "Translate the user's message into the language given below. \
+Reply with the translation only. Keep names, code, URLs, Markdown and line breaks unchanged.";
+
+/// An incoming message as the agent should see it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incoming {
+    /// Detected or pinned language of the sender.
+    pub language: String,
+    /// Text in the working language.
+    pub text: String,
+    /// The sender's original text, when it was translated.
+    pub original: Option<String>,
+}
+
+/// Normalize a language code (`"DE "`, `"de-AT"` -> `"de"`).
+pub fn normalize_language(code: &str) -> String {
+    let code = code.trim().to_ascii_lowercase();
+    code.split(['-', '_']).next().unwrap_or(&code).to_string()
+}
+
+/// Whether `code` looks like an ISO 639 code (two or three letters).
+pub fn is_language_code(code: &str) -> bool {
+    let code = normalize_language(code);
+    (2..=3).contains(&code.len()) && code.chars().all(|c| c.is_ascii_lowercase())
+}
+
+/// Parse the detector's `{"language", "translation"}` answer, tolerating
+/// surrounding prose or code fences.
+fn parse_detection(answer: &str) -> Option<(String, String)> {
+    let start = answer.find('{')?;
+    let end = answer.rfind('}')?;
+    let value: serde_json::Value = serde_json::from_str(answer.get(start..=end)?).ok()?;
+    let language = value.get("language")?.as_str()?;
+    if !is_language_code(language) {
+        return None;
+    }
+    let translation = value
+        .get("translation")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    Some((normalize_language(language), translation))
+}
+
+/// Per-channel translation state shared by channel workers.
+pub struct Translator {
+    working: String,
+    min_detect_chars: usize,
+    pins: HashMap<String, String>,
+    /// Dedicated provider from `[translation].provider`; `None` uses the
+    /// provider answering the message.
+    provider: Option<Arc<dyn Provider>>,
+    model: Option<String>,
+    last_language: Mutex<HashMap<String, String>>,
+}
+
+impl Translator {
+    /// `None` unless `[translation]` is enabled.
+    pub fn from_config(
+        config: &TranslationConfig,
+        api_key: Option<&str>,
+        reliability: &crate::config::ReliabilityConfig,
+        options: &providers::ProviderRuntimeOptions,
+    ) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let provider = match &config.provider {
+            Some(name) => Some(Arc::from(
+                providers::create_resilient_provider_with_options(
+                    name,
+                    api_key,
+                    None,
+                    reliability,
+                    options,
+                )
+                .with_context(|| format!("Failed to create translation provider '{name}'"))?,
+            )),
+            None => None,
+        };
+        Ok(Some(Self {
+            working: normalize_language(&config.working_language),
+            min_detect_chars: config.min_detect_chars,
+            pins: config
+                .channels
+                .iter()
+                .map(|(channel, language)| (channel.clone(), normalize_language(language)))
+                .collect(),
+            provider,
+            model: config.model.clone(),
+            last_language: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    /// The agent's working language.
+    pub fn working_language(&self) -> &str {
+        &self.working
+    }
+
+    fn pin(&self, channel: &str) -> Option<&str> {
+        self.pins
+            .get(channel)
+            .map(String::as_str)
+            .filter(|language| *language != AUTO)
+    }
+
+    fn remembered(&self, sender_key: &str) -> Option<String> {
+        self.last_language
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(sender_key)
+            .cloned()
+    }
+
+    fn remember(&self, sender_key: &str, language: &str) {
+        self.last_language
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(sender_key.to_string(), language.to_string());
+    }
+
+    fn route<'a>(
+        &'a self,
+        fallback: &'a dyn Provider,
+        fallback_model: &'a str,
+    ) -> (&'a dyn Provider, &'a str) {
+        (
+            self.provider.as_deref().unwrap_or(fallback),
+            self.model.as_deref().unwrap_or(fallback_model),
+        )
+    }
+
+    /// Detect the language of `text` and translate it into the working
+    /// language when needed. `sender_key` identifies the conversation.
+    pub async fn incoming(
+        &self,
+        provider: &dyn Provider,
+        model: &str,
+        channel: &str,
+        sender_key: &str,
+        text: &str,
+    ) -> Result<Incoming> {
+        let untranslated = |language: &str| Incoming {
+            language: language.to_string(),
+            text: text.to_string(),
+            original: None,
+        };
+        let (provider, model) = self.route(provider, model);
+
+        let known = self.pin(channel).map(str::to_string).or_else(|| {
+            (text.chars().count() < self.min_detect_chars)
+                .then(|| self.remembered(sender_key))
+                .flatten()
+        });
+        if let Some(language) = known {
+            if language == self.working {
+                return Ok(untranslated(&language));
+            }
+            let translated = self.translate(provider, model, &self.working, text).await?;
+            return Ok(Incoming {
+                language,
+                text: translated,
+                original: Some(text.to_string()),
+            });
+        }
+        if text.chars().count() < self.min_detect_chars {
+            return Ok(untranslated(&self.working));
+        }
+
+        let answer = provider
+            .chat_with_system(
+                Some(&format!(
+                    "{DETECT_PROMPT}\n\nTarget language: {}",
+                    self.working
+                )),
+                text,
+                model,
+                0.0,
+            )
+            .await?;
+        let Some((language, translation)) = parse_detection(&answer) else {
+            tracing::debug!("Unparseable language detection answer; passing message through");
+            return Ok(untranslated(&self.working));
+        };
+        self.remember(sender_key, &language);
+        if language == self.working || translation.is_empty() {
+            return Ok(untranslated(&language));
+        }
+        Ok(Incoming {
+            language,
+            text: translation,
+            original: Some(text.to_string()),
+        })
+    }
+
+    /// Translate a reply into `language` (no-op for the working language).
+    pub async fn outgoing(
+        &self,
+        provider: &dyn Provider,
+        model: &str,
+        language: &str,
+        text: &str,
+    ) -> Result<String> {
+        if language == self.working || text.trim().is_empty() {
+            return Ok(text.to_string());
+        }
+        let (provider, model) = self.route(provider, model);
+        self.translate(provider, model, language, text).await
+    }
+
+    async fn translate(
+        &self,
+        provider: &dyn Provider,
+        model: &str,
+        language: &str,
+        text: &str,
+    ) -> Result<String> {
+        let translated = provider
+            .chat_with_system(
+                Some(&format!(
+                    "{TRANSLATE_PROMPT}\n\nTarget language: {language}"
+                )),
+                text,
+                model,
+                0.0,
+            )
+            .await?;
+        let translated = translated.trim();
+        Ok(if translated.is_empty() {
+            text.to_string()
+        } else {
+            translated.to_string()
+        })
+    }
+}
+
+/// Memory content keeping the sender's original next to the translation.
+pub fn memory_content(incoming: &Incoming) -> String {
+    match &incoming.original {
+        Some(original) => format!(
+            "{}\n\n[original, {}]: {original}",
+            incoming.text, incoming.language
+        ),
+        None => incoming.text.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TranslationConfig;
+    use async_trait::async_trait;
+
+    /// Answers detection prompts with German and translations with a marker.
+    struct FakeTranslator;
+
+    #[async_trait]
+    impl Provider for FakeTranslator {
+        async fn chat_with_system(
+            &self,
+            system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<String> {
+            let system = system_prompt.unwrap_or_default();
+            let target = system
+                .rsplit("Target language: ")
+                .next()
+                .unwrap_or_default();
+            if system.starts_with("Detect") {
+                Ok(format!(
+                    "```json\n{{\"language\": \"de-DE\", \"translation\": \"({target}) {message}\"}}\n```"
+                ))
+            } else {
+                Ok(format!("({target}) {message}"))
+            }
+        }
+    }
+
+    fn translator(pins: &[(&str, &str)]) -> Translator {
+        let config = TranslationConfig {
+            enabled: true,
+            channels: pins
+                .iter()
+                .map(|(channel, language)| ((*channel).to_string(), (*language).to_string()))
+                .collect(),
+            ..TranslationConfig::default()
+        };
+        Translator::from_config(
+            &config,
+            None,
+            &crate::config::ReliabilityConfig::default(),
+            &providers::ProviderRuntimeOptions::default(),
+        )
+        .unwrap()
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn detected_language_is_translated_both_ways_and_remembered() {
+        let translator = translator(&[]);
+        let incoming = translator
+            .incoming(
+                &FakeTranslator,
+                "m",
+                "telegram",
+                "alice",
+                "Wie spät ist es heute?",
+            )
+            .await
+            .unwrap();
+        assert_eq!(incoming.language, "de");
+        assert_eq!(incoming.text, "(en) Wie spät ist es heute?");
+        assert_eq!(
+            memory_content(&incoming),
+            "(en) Wie spät ist es heute?\n\n[original, de]: Wie spät ist es heute?"
+        );
+
+        // Too short to detect: the sender's last language is reused.
+        let short = translator
+            .incoming(&FakeTranslator, "m", "telegram", "alice", "Danke")
+            .await
+            .unwrap();
+        assert_eq!(short.language, "de");
+        assert_eq!(short.original.as_deref(), Some("Danke"));
+
+        let reply = translator
+            .outgoing(&FakeTranslator, "m", &incoming.language, "It is noon.")
+            .await
+            .unwrap();
+        assert_eq!(reply, "(de) It is noon.");
+        assert_eq!(
+            translator
+                .outgoing(&FakeTranslator, "m", "en", "It is noon.")
+                .await
+                .unwrap(),
+            "It is noon."
+        );
+    }
+
+    #[tokio::test]
+    async fn pinned_channels_skip_detection() {
+        let translator = translator(&[("slack", "en"), ("matrix", "fr"), ("discord", "auto")]);
+        let slack = translator
+            .incoming(
+                &FakeTranslator,
+                "m",
+                "slack",
+                "bob",
+                "Wie spät ist es heute?",
+            )
+            .await
+            .unwrap();
+        assert_eq!(slack.original, None);
+        assert_eq!(slack.language, "en");
+
+        let matrix = translator
+            .incoming(&FakeTranslator, "m", "matrix", "bob", "Salut")
+            .await
+            .unwrap();
+        assert_eq!(matrix.language, "fr");
+        assert_eq!(matrix.text, "(en) Salut");
+
+        assert!(translator.pin("discord").is_none());
+        assert!(is_language_code("pt-BR"));
+        assert!(!is_language_code("english"));
+    }
+}
@@ -0,0 +1,462 @@
+//! Identity challenges for new channel senders (`[channels_config.verification]`).
+//!
+//! When enabled, the agent only answers senders that have been verified. The
+//! first message from anyone else starts a challenge instead of reaching the
+//! model:
+//!
+//! - `code` mode: the owner is sent a one-time code out of band (on the
+//!   `notify` targets, and in the daemon output). The sender proves the owner
+//!   knows them by replying `/verify <code>`.
+//! - `approve` mode: the owner lets the sender in with
+//!   `zeroclaw channel approve-sender <channel:sender>`.
+//!
+//! Verified senders and open challenges live in `verified_senders.json` next
+//! to `config.toml`, so CLI approvals reach a running daemon without a
+//! restart. The file is outside the workspace the agent's tools can write, and
+//! challenges only keep a hash of their code.
+
+use crate::config::{Config, SenderVerificationConfig, VerificationMode};
+use crate::i18n;
+use crate::security::pairing::constant_time_eq;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Parse `/verify <code>` (with an optional `@bot` suffix on the command).
+pub fn parse_command(text: &str) -> Option<String> {
+    let mut words = text.split_whitespace();
+    let command = words.next()?.split('@').next()?.to_ascii_lowercase();
+    if command != "/verify" {
+        return None;
+    }
+    let code = words.next()?;
+    if words.next().is_some() || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(code.to_string())
+}
+
+/// `channel:sender`, with the channel part lowercased.
+pub fn identity(channel: &str, sender: &str) -> String {
+    format!("{}:{}", channel.trim().to_ascii_lowercase(), sender.trim())
+}
+
+fn normalize_identity(raw: &str) -> Option<String> {
+    let (channel, sender) = raw.trim().split_once(':')?;
+    (!channel.trim().is_empty() && !sender.trim().is_empty()).then(|| identity(channel, sender))
+}
+
+fn hash_code(code: &str) -> String {
+    hex::encode(Sha256::digest(code.as_bytes()))
+}
+
+/// A sender allowed to talk to the agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifiedSender {
+    pub verified_at: DateTime<Utc>,
+    /// `code` or `cli`.
+    pub method: String,
+}
+
+/// An open challenge for an unverified sender.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Challenge {
+    /// SHA-256 of the code; the code itself is only sent to the owner.
+    pub code_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+/// Contents of `verified_senders.json`, keyed by `channel:sender`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct VerificationState {
+    #[serde(default)]
+    pub verified: BTreeMap<String, VerifiedSender>,
+    #[serde(default)]
+    pub pending: BTreeMap<String, Challenge>,
+}
+
+/// What to do with an incoming message.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Screening {
+    /// The sender is verified (or the channel does not challenge): handle it.
+    Pass,
+    /// The challenge flow consumed the message. Send `reply` to the sender
+    /// and, when a new challenge was issued, `notice` to the owner.
+    Held {
+        reply: String,
+        notice: Option<String>,
+    },
+}
+
+pub fn state_path(zeroclaw_dir: &Path) -> PathBuf {
+    zeroclaw_dir.join("verified_senders.json")
+}
+
+/// Sender verification for one config directory.
+pub struct SenderVerifier {
+    mode: VerificationMode,
+    channels: HashSet<String>,
+    notify: Vec<(String, String)>,
+    ttl: Duration,
+    max_attempts: u32,
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl SenderVerifier {
+    /// `None` unless verification is enabled.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        config
+            .channels_config
+            .verification
+            .enabled
+            .then(|| Self::for_config(config))
+    }
+
+    /// Verifier for CLI management, whether or not screening is enabled.
+    pub fn for_config(config: &Config) -> Self {
+        let zeroclaw_dir = config.config_path.parent().unwrap_or(&config.workspace_dir);
+        Self::new(&config.channels_config.verification, zeroclaw_dir)
+    }
+
+    fn new(config: &SenderVerificationConfig, zeroclaw_dir: &Path) -> Self {
+        Self {
+            mode: config.mode,
+            channels: config
+                .channels
+                .iter()
+                .map(|channel| channel.trim().to_ascii_lowercase())
+                .collect(),
+            notify: config
+                .notify
+                .iter()
+                .filter_map(|target| {
+                    let (channel, target) = target.trim().split_once(':')?;
+                    Some((
+                        channel.trim().to_ascii_lowercase(),
+                        target.trim().to_string(),
+                    ))
+                })
+                .collect(),
+            ttl: Duration::minutes(i64::try_from(config.code_ttl_minutes.max(1)).unwrap_or(60)),
+            max_attempts: config.max_attempts.max(1),
+            path: state_path(zeroclaw_dir),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// `(channel, target)` pairs the owner is notified on.
+    pub fn notify_targets(&self) -> &[(String, String)] {
+        &self.notify
+    }
+
+    /// Whether senders on `channel` must be verified.
+    pub fn covers(&self, channel: &str) -> bool {
+        self.channels.is_empty() || self.channels.contains(&channel.to_ascii_lowercase())
+    }
+
+    pub fn load(&self) -> Result<VerificationState> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(raw) => serde_json::from_str(&raw)
+                .with_context(|| format!("Failed to parse {}", self.path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(VerificationState::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", self.path.display())),
+        }
+    }
+
+    fn save(&self, state: &VerificationState) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(state)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    /// Decide whether a message from `sender` on `channel` reaches the agent,
+    /// advancing the sender's challenge if it does not.
+    pub fn screen(
+        &self,
+        channel: &str,
+        sender: &str,
+        text: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Screening> {
+        if !self.covers(channel) {
+            return Ok(Screening::Pass);
+        }
+        let identity = identity(channel, sender);
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut state = self.load()?;
+        if state.verified.contains_key(&identity) {
+            return Ok(Screening::Pass);
+        }
+
+        let open = state
+            .pending
+            .get(&identity)
+            .filter(|challenge| challenge.expires_at > now)
+            .cloned();
+        let Some(mut challenge) = open else {
+            let code = format!("{:06}", rand::random::<u32>() % 1_000_000);
+            let challenge = Challenge {
+                code_hash: hash_code(&code),
+                created_at: now,
+                expires_at: now + self.ttl,
+                attempts: 0,
+            };
+            let notice = self.owner_notice(&identity, &code);
+            state.pending.insert(identity.clone(), challenge);
+            self.save(&state)?;
+            tracing::info!(sender = %identity, "Challenged unverified channel sender");
+            return Ok(Screening::Held {
+                reply: self.challenge_reply(),
+                notice: Some(notice),
+            });
+        };
+
+        let minutes_left = (challenge.expires_at - now)
+            .num_minutes()
+            .max(1)
+            .to_string();
+        let reply = match (self.mode, parse_command(text)) {
+            (_, _) if challenge.attempts >= self.max_attempts => {
+                i18n::tr_args("channel.verify_locked", &[("minutes", &minutes_left)])
+            }
+            (VerificationMode::Code, Some(code))
+                if constant_time_eq(&hash_code(&code), &challenge.code_hash) =>
+            {
+                state.pending.remove(&identity);
+                state.verified.insert(
+                    identity.clone(),
+                    VerifiedSender {
+                        verified_at: now,
+                        method: "code".into(),
+                    },
+                );
+                self.save(&state)?;
+                tracing::info!(sender = %identity, "Channel sender verified by code");
+                return Ok(Screening::Held {
+                    reply: i18n::tr("channel.verify_success"),
+                    notice: None,
+                });
+            }
+            (VerificationMode::Code, Some(_)) => {
+                challenge.attempts += 1;
+                let remaining = self.max_attempts.saturating_sub(challenge.attempts);
+                state.pending.insert(identity.clone(), challenge);
+                self.save(&state)?;
+                tracing::warn!(sender = %identity, "Wrong verification code from channel sender");
+                if remaining == 0 {
+                    i18n::tr_args("channel.verify_locked", &[("minutes", &minutes_left)])
+                } else {
+                    i18n::tr_args(
+                        "channel.verify_wrong_code",
+                        &[("remaining", &remaining.to_string())],
+                    )
+                }
+            }
+            _ => self.challenge_reply(),
+        };
+        Ok(Screening::Held {
+            reply,
+            notice: None,
+        })
+    }
+
+    fn challenge_reply(&self) -> String {
+        match self.mode {
+            VerificationMode::Code => i18n::tr_args(
+                "channel.verify_code_challenge",
+                &[("minutes", &self.ttl.num_minutes().to_string())],
+            ),
+            VerificationMode::Approve => i18n::tr("channel.verify_approve_challenge"),
+        }
+    }
+
+    fn owner_notice(&self, identity: &str, code: &str) -> String {
+        match self.mode {
+            VerificationMode::Code => i18n::tr_args(
+                "channel.verify_owner_code",
+                &[
+                    ("identity", identity),
+                    ("code", code),
+                    ("minutes", &self.ttl.num_minutes().to_string()),
+                ],
+            ),
+            VerificationMode::Approve => {
+                i18n::tr_args("channel.verify_owner_approve", &[("identity", identity)])
+            }
+        }
+    }
+
+    /// Verify `identity` (`channel:sender`) directly, e.g. from the CLI.
+    pub fn approve(&self, identity: &str) -> Result<String> {
+        let identity = normalize_identity(identity)
+            .context("Sender must be given as <channel>:<sender>, e.g. telegram:alice")?;
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut state = self.load()?;
+        state.pending.remove(&identity);
+        state.verified.insert(
+            identity.clone(),
+            VerifiedSender {
+                verified_at: Utc::now(),
+                method: "cli".into(),
+            },
+        );
+        self.save(&state)?;
+        Ok(identity)
+    }
+
+    /// Forget a verified sender (and any open challenge). Returns whether
+    /// anything was removed.
+    pub fn revoke(&self, identity: &str) -> Result<bool> {
+        let identity = normalize_identity(identity)
+            .context("Sender must be given as <channel>:<sender>, e.g. telegram:alice")?;
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut state = self.load()?;
+        let removed =
+            state.verified.remove(&identity).is_some() | state.pending.remove(&identity).is_some();
+        if removed {
+            self.save(&state)?;
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verifier(tmp: &tempfile::TempDir, mode: VerificationMode) -> SenderVerifier {
+        let config = SenderVerificationConfig {
+            enabled: true,
+            mode,
+            max_attempts: 2,
+            ..SenderVerificationConfig::default()
+        };
+        SenderVerifier::new(&config, tmp.path())
+    }
+
+    /// The code from the owner notice; the state file only has its hash.
+    fn notice_code(notice: Option<String>) -> String {
+        let notice = notice.expect("a new challenge notifies the owner");
+        notice
+            .split(|c: char| !c.is_ascii_digit())
+            .find(|word| word.len() == 6)
+            .expect("notice carries a six-digit code")
+            .to_string()
+    }
+
+    fn held(screening: Screening) -> (String, Option<String>) {
+        match screening {
+            Screening::Held { reply, notice } => (reply, notice),
+            Screening::Pass => panic!("message should have been held"),
+        }
+    }
+
+    #[test]
+    fn parses_verify_command() {
+        assert_eq!(parse_command("/verify 042917"), Some("042917".into()));
+        assert_eq!(parse_command("/verify@zc_bot 1234"), Some("1234".into()));
+        assert_eq!(parse_command("/verify"), None);
+        assert_eq!(parse_command("/verify abc"), None);
+        assert_eq!(parse_command("verify 1234"), None);
+    }
+
+    #[test]
+    fn code_challenge_admits_sender_after_correct_code() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let verifier = verifier(&tmp, VerificationMode::Code);
+        let now = Utc::now();
+
+        let (_, notice) = held(verifier.screen("Telegram", "alice", "hi", now).unwrap());
+        let code = notice_code(notice);
+        let raw = std::fs::read_to_string(state_path(tmp.path())).unwrap();
+        assert!(!raw.contains(&code), "{raw}");
+        assert!(raw.contains(&hash_code(&code)));
+
+        // A repeat message does not notify the owner again.
+        let (_, notice) = held(verifier.screen("telegram", "alice", "hello?", now).unwrap());
+        assert!(notice.is_none());
+
+        let wrong = if code == "000000" { "111111" } else { "000000" };
+        held(
+            verifier
+                .screen("telegram", "alice", &format!("/verify {wrong}"), now)
+                .unwrap(),
+        );
+        held(
+            verifier
+                .screen("telegram", "alice", &format!("/verify {code}"), now)
+                .unwrap(),
+        );
+        assert!(verifier
+            .load()
+            .unwrap()
+            .verified
+            .contains_key("telegram:alice"));
+        assert_eq!(
+            verifier.screen("telegram", "alice", "hi", now).unwrap(),
+            Screening::Pass
+        );
+    }
+
+    #[test]
+    fn wrong_codes_lock_the_challenge_until_it_expires() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let verifier = verifier(&tmp, VerificationMode::Code);
+        let now = Utc::now();
+        let (_, notice) = held(verifier.screen("discord", "mallory", "hi", now).unwrap());
+        let code = notice_code(notice);
+        let wrong = if code == "000000" { "111111" } else { "000000" };
+        for _ in 0..2 {
+            held(
+                verifier
+                    .screen("discord", "mallory", &format!("/verify {wrong}"), now)
+                    .unwrap(),
+            );
+        }
+        held(
+            verifier
+                .screen("discord", "mallory", &format!("/verify {code}"), now)
+                .unwrap(),
+        );
+        assert!(verifier.load().unwrap().verified.is_empty());
+
+        // Once expired, a fresh challenge (and owner notice) is issued.
+        let later = now + Duration::hours(2);
+        let (_, notice) = held(verifier.screen("discord", "mallory", "hi", later).unwrap());
+        assert!(notice.is_some());
+    }
+
+    #[test]
+    fn cli_approval_and_revocation() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let verifier = verifier(&tmp, VerificationMode::Approve);
+        let now = Utc::now();
+        held(
+            verifier
+                .screen("slack", "U123", "/verify 123456", now)
+                .unwrap(),
+        );
+
+        assert_eq!(verifier.approve("Slack:U123").unwrap(), "slack:U123");
+        assert_eq!(
+            verifier.screen("slack", "U123", "hi", now).unwrap(),
+            Screening::Pass
+        );
+        assert!(verifier.revoke("slack:U123").unwrap());
+        assert!(!verifier.revoke("slack:U123").unwrap());
+        assert!(verifier.approve("nobody").is_err());
+    }
+}
@@ -0,0 +1,345 @@
+//! Guardrailed autonomous coding runs (`zeroclaw code`).
+//!
+//! A run checks out a fresh branch in a clean repository, lets the agent
+//! iterate with file, search, git and shell tools confined to the repository
+//! (workspace-only policy, `[code]` iteration and cost caps), commits any
+//! leftover changes and opens a pull request. It never pushes to the base
+//! branch or any `[code].protected_branches` entry.
+
+use crate::config::{CodeConfig, Config};
+use crate::security::AutonomyLevel;
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// Tools available to a coding run.
+const CODE_TOOLS: &[&str] = &[
+    "file_read",
+    "file_write",
+    "file_edit",
+    "glob_search",
+    "content_search",
+    "git_operations",
+    "shell",
+];
+
+/// Characters of the task kept in branch names.
+const MAX_SLUG_CHARS: usize = 40;
+
+/// One `zeroclaw code` invocation.
+#[derive(Debug, Clone)]
+pub struct CodeRun {
+    pub repo: PathBuf,
+    pub task: String,
+    /// Branch to start from and target with the PR; defaults to the current branch.
+    pub base: Option<String>,
+    pub max_iterations: Option<usize>,
+    pub max_cost_usd: Option<f64>,
+    pub open_pr: bool,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub temperature: f64,
+}
+
+pub async fn run(config: Config, request: CodeRun) -> Result<()> {
+    if config.autonomy.level == AutonomyLevel::ReadOnly {
+        bail!("zeroclaw code needs [autonomy] level = \"supervised\" or \"full\"");
+    }
+    let code = config.code.clone();
+    let repo = request
+        .repo
+        .canonicalize()
+        .with_context(|| format!("repository {} does not exist", request.repo.display()))?;
+    let repo = PathBuf::from(git(&repo, &["rev-parse", "--show-toplevel"]).await?);
+
+    if !git(&repo, &["status", "--porcelain"]).await?.is_empty() {
+        bail!(
+            "{} has uncommitted changes; commit or stash them first",
+            repo.display()
+        );
+    }
+    let base = match request.base.clone() {
+        Some(base) => base,
+        None => git(&repo, &["symbolic-ref", "--short", "HEAD"])
+            .await
+            .context("repository is in detached HEAD state; pass --base")?,
+    };
+    let branch = branch_name(&code, &request.task, chrono::Local::now());
+    if is_protected(&code, &branch) {
+        bail!("branch {branch} is protected by [code].protected_branches");
+    }
+    git(&repo, &["checkout", "-b", &branch, &base]).await?;
+    println!("🌿 Working on branch {branch} (base: {base})");
+
+    let max_iterations = request.max_iterations.unwrap_or(code.max_iterations);
+    let max_cost_usd = request.max_cost_usd.unwrap_or(code.max_cost_usd);
+    let outcome = Box::pin(crate::agent::run(
+        scoped_config(&config, &repo, max_iterations, max_cost_usd),
+        Some(build_prompt(&request.task, &branch, &base)),
+        request.provider.clone(),
+        request.model.clone(),
+        request.temperature,
+        Vec::new(),
+        true,
+        false,
+        CODE_TOOLS.iter().map(|tool| (*tool).to_string()).collect(),
+        crate::handoff::CliHandoff::default(),
+    ))
+    .await;
+
+    let current = git(&repo, &["symbolic-ref", "--short", "HEAD"])
+        .await
+        .unwrap_or_default();
+    if current != branch {
+        bail!("the run left branch {branch} (now on {current:?}); nothing was pushed");
+    }
+    if !git(&repo, &["status", "--porcelain"]).await?.is_empty() {
+        git(&repo, &["add", "-A"]).await?;
+        git(&repo, &["commit", "-m", &commit_title(&request.task)]).await?;
+    }
+    let summary = match outcome {
+        Ok(summary) => summary,
+        Err(err) => bail!("run stopped: {err:#}. Work so far is on branch {branch}"),
+    };
+
+    let commits: u32 = git(&repo, &["rev-list", "--count", &format!("{base}..HEAD")])
+        .await?
+        .parse()
+        .unwrap_or(0);
+    if commits == 0 {
+        println!("No changes were made; branch {branch} is identical to {base}.");
+        return Ok(());
+    }
+    if !request.open_pr {
+        println!("✅ {commits} commit(s) on {branch}; pull request skipped (--no-pr).");
+        return Ok(());
+    }
+
+    let remote = code.remote.trim();
+    git(
+        &repo,
+        &["push", remote, &format!("HEAD:refs/heads/{branch}")],
+    )
+    .await?;
+    let remote_url = git(&repo, &["remote", "get-url", remote]).await?;
+    let (owner, name) = github_repo(&remote_url)
+        .with_context(|| format!("cannot derive a GitHub repository from {remote_url}"))?;
+    let token = github_token(&code)
+        .context("no GitHub token: set [code].github_token, GITHUB_TOKEN or GH_TOKEN")?;
+    let body = format!(
+        "{}\n\n---\nTask: {}\n\nOpened by `zeroclaw code` ({commits} commit(s)).",
+        summary.trim(),
+        request.task.trim()
+    );
+    let url = open_pull_request(
+        &code,
+        &token,
+        (&owner, &name),
+        &branch,
+        &base,
+        &commit_title(&request.task),
+        &body,
+    )
+    .await?;
+    println!("✅ Pull request opened: {url}");
+    Ok(())
+}
+
+/// Config for the agent: the repository is the workspace and nothing else is reachable.
+fn scoped_config(config: &Config, repo: &Path, max_iterations: usize, max_cost_usd: f64) -> Config {
+    let mut scoped = config.clone();
+    scoped.workspace_dir = repo.to_path_buf();
+    scoped.autonomy.workspace_only = true;
+    scoped.autonomy.allowed_roots.clear();
+    scoped.agent.max_tool_iterations = max_iterations;
+    scoped.agent.max_run_cost_usd = max_cost_usd;
+    // Keep runtime state out of the repository.
+    scoped.agent.git_checkpoints = false;
+    scoped.memory.backend = "none".into();
+    scoped.memory.auto_save = false;
+    scoped.runtime.remote.enabled = false;
+    scoped
+}
+
+fn build_prompt(task: &str, branch: &str, base: &str) -> String {
+    format!(
+        "You are working in a git repository (the workspace root) on branch `{branch}`, \
+         created from `{base}`.\n\n\
+         Task: {task}\n\n\
+         Work in small steps: read the relevant code, edit files, then build and run the \
+         tests with the shell tool. Repeat until the build and tests pass. Commit finished \
+         work with git_operations. Do not push, switch branches or touch files outside the \
+         repository. When done, reply with a short summary of the change for the pull \
+         request description."
+    )
+}
+
+fn branch_name(code: &CodeConfig, task: &str, now: chrono::DateTime<chrono::Local>) -> String {
+    let mut slug = String::new();
+    for ch in task.chars().flat_map(char::to_lowercase) {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.len() >= MAX_SLUG_CHARS {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    let slug = if slug.is_empty() { "task" } else { slug };
+    format!(
+        "{}{slug}-{}",
+        code.branch_prefix.trim(),
+        now.format("%Y%m%d-%H%M%S")
+    )
+}
+
+fn is_protected(code: &CodeConfig, branch: &str) -> bool {
+    code.protected_branches
+        .iter()
+        .any(|protected| protected.trim() == branch)
+}
+
+fn commit_title(task: &str) -> String {
+    let first_line = task.lines().next().unwrap_or_default().trim();
+    crate::util::truncate_with_ellipsis(first_line, 72)
+}
+
+/// `(owner, repo)` from an https, ssh or scp-style remote URL.
+fn github_repo(remote_url: &str) -> Option<(String, String)> {
+    let url = remote_url.trim();
+    let path = if let Some((_, rest)) = url.split_once("://") {
+        rest.split_once('/')?.1
+    } else {
+        // scp-like: git@github.com:owner/repo.git
+        url.split_once(':')?.1
+    };
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let mut segments = path.rsplit('/');
+    let name = segments.next()?;
+    let owner = segments.next()?;
+    (!owner.is_empty() && !name.is_empty()).then(|| (owner.to_string(), name.to_string()))
+}
+
+fn github_token(code: &CodeConfig) -> Option<String> {
+    code.github_token
+        .clone()
+        .or_else(|| std::env::var("GITHUB_TOKEN").ok())
+        .or_else(|| std::env::var("GH_TOKEN").ok())
+        .filter(|token| !token.trim().is_empty())
+}
+
+async fn open_pull_request(
+    code: &CodeConfig,
+    token: &str,
+    (owner, name): (&str, &str),
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+) -> Result<String> {
+    let url = format!(
+        "{}/repos/{owner}/{name}/pulls",
+        code.github_api_url.trim().trim_end_matches('/')
+    );
+    let response = crate::config::build_runtime_proxy_client("code.github")
+        .post(url)
+        .bearer_auth(token.trim())
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "zeroclaw")
+        .json(&json!({
+            "title": title,
+            "head": head,
+            "base": base,
+            "body": body,
+            "draft": code.draft,
+        }))
+        .send()
+        .await?;
+    let status = response.status();
+    let payload: Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        bail!(
+            "GitHub rejected the pull request ({status}): {}",
+            payload["message"].as_str().unwrap_or("no details")
+        );
+    }
+    payload["html_url"]
+        .as_str()
+        .map(str::to_string)
+        .context("GitHub response has no html_url")
+}
+
+async fn git(repo: &Path, args: &[&str]) -> Result<String> {
+    let output = tokio::process::Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .await
+        .context("failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn branch_names_are_prefixed_slugs() {
+        let now = chrono::Local
+            .with_ymd_and_hms(2026, 3, 1, 9, 30, 0)
+            .unwrap();
+        let code = CodeConfig::default();
+        assert_eq!(
+            branch_name(&code, "Fix the flaky `parser` test!", now),
+            "zeroclaw/fix-the-flaky-parser-test-20260301-093000"
+        );
+        assert_eq!(
+            branch_name(&code, "日本語", now),
+            "zeroclaw/task-20260301-093000"
+        );
+        assert!(!is_protected(&code, "zeroclaw/task-20260301-093000"));
+        assert!(is_protected(&code, "main"));
+    }
+
+    #[test]
+    fn github_repo_parses_common_remote_forms() {
+        let expected = Some(("zeroclaw-labs".to_string(), "zeroclaw".to_string()));
+        for url in [
+            "https://github.com/zeroclaw-labs/zeroclaw.git",
+            "https://token@github.com/zeroclaw-labs/zeroclaw",
+            "git@github.com:zeroclaw-labs/zeroclaw.git",
+            "ssh://git@github.com/zeroclaw-labs/zeroclaw/",
+        ] {
+            assert_eq!(github_repo(url), expected, "{url}");
+        }
+        assert_eq!(github_repo("/srv/git/zeroclaw"), None);
+    }
+
+    #[test]
+    fn scoped_config_confines_the_agent_to_the_repo() {
+        let mut config = Config::default();
+        config.autonomy.workspace_only = false;
+        config.autonomy.allowed_roots = vec!["/srv/shared".into()];
+        config.runtime.remote.enabled = true;
+
+        let scoped = scoped_config(&config, Path::new("/work/repo"), 12, 1.5);
+        assert_eq!(scoped.workspace_dir, Path::new("/work/repo"));
+        assert!(scoped.autonomy.workspace_only);
+        assert!(scoped.autonomy.allowed_roots.is_empty());
+        assert_eq!(scoped.agent.max_tool_iterations, 12);
+        assert!((scoped.agent.max_run_cost_usd - 1.5).abs() < f64::EPSILON);
+        assert_eq!(scoped.memory.backend, "none");
+        assert!(!scoped.runtime.remote.enabled);
+    }
+}
@@ -0,0 +1,262 @@
+//! Versioned upgrades for `config.toml`.
+//!
+//! The file records the schema it was written for in a top-level
+//! `schema_version` key (files without one are version 0). Each
+//! [`Migration`] rewrites the raw TOML table from one version to the next,
+//! before it is deserialized into [`Config`](super::Config), so renamed or
+//! restructured keys upgrade instead of failing to parse. `Config::load_or_init`
+//! applies pending migrations and rewrites the file after copying the original
+//! to `config.toml.v<N>.bak`; `zeroclaw config migrate --dry-run` previews
+//! the same steps without writing.
+//!
+//! To change the schema incompatibly, bump [`CURRENT_SCHEMA_VERSION`] and
+//! append a migration whose `to` is the new version.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use toml::{Table, Value};
+
+/// Schema version written by this build.
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+const VERSION_KEY: &str = "schema_version";
+
+/// One upgrade step, from version `to - 1` to `to`.
+struct Migration {
+    to: u32,
+    summary: &'static str,
+    /// Rewrites the table and appends a line per change it made.
+    apply: fn(&mut Table, &mut Vec<String>),
+}
+
+/// Ordered by `to`; each step assumes every earlier step has run.
+const MIGRATIONS: &[Migration] = &[Migration {
+    to: 1,
+    summary: "use canonical names for keys previously accepted as aliases",
+    apply: canonical_key_names,
+}];
+
+/// What [`migrate`] did to a config table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub from: u32,
+    pub to: u32,
+    /// `v<N>: <summary>` headers followed by the individual changes.
+    pub changes: Vec<String>,
+}
+
+impl MigrationReport {
+    /// Whether the table was already at the current version.
+    pub fn is_noop(&self) -> bool {
+        self.from == self.to
+    }
+}
+
+/// The `schema_version` recorded in a raw config table (0 when absent).
+pub fn schema_version(table: &Table) -> Result<u32> {
+    match table.get(VERSION_KEY) {
+        None => Ok(0),
+        Some(Value::Integer(version)) => u32::try_from(*version)
+            .with_context(|| format!("Invalid {VERSION_KEY} {version} in config")),
+        Some(other) => bail!("Invalid {VERSION_KEY} in config: expected an integer, got {other}"),
+    }
+}
+
+/// Apply every pending migration to `table` and stamp the current version.
+/// Fails for files written by a newer release.
+pub fn migrate(table: &mut Table) -> Result<MigrationReport> {
+    let from = schema_version(table)?;
+    if from > CURRENT_SCHEMA_VERSION {
+        bail!(
+            "config.toml has {VERSION_KEY} = {from}, but this build only understands up to \
+             {CURRENT_SCHEMA_VERSION}. Upgrade zeroclaw or restore an older config backup."
+        );
+    }
+    let mut changes = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.to > from) {
+        changes.push(format!("v{}: {}", migration.to, migration.summary));
+        (migration.apply)(table, &mut changes);
+    }
+    table.insert(
+        VERSION_KEY.into(),
+        Value::Integer(i64::from(CURRENT_SCHEMA_VERSION)),
+    );
+    Ok(MigrationReport {
+        from,
+        to: CURRENT_SCHEMA_VERSION,
+        changes,
+    })
+}
+
+/// Outcome of [`migrate_file`].
+#[derive(Debug, Clone)]
+pub struct FileMigration {
+    pub report: MigrationReport,
+    /// Migrated file contents.
+    pub migrated: String,
+    /// Copy of the original file; `None` for dry runs and no-ops.
+    pub backup_path: Option<PathBuf>,
+}
+
+/// Migrate the config file at `path`. Unless `dry_run`, the original is
+/// copied to `<file>.v<from>.bak` and the file replaced atomically.
+pub async fn migrate_file(path: &Path, dry_run: bool) -> Result<FileMigration> {
+    let contents = fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut table: Table =
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))?;
+    let report = migrate(&mut table)?;
+    let migrated = toml::to_string_pretty(&table).context("Failed to serialize config")?;
+    let backup_path = if report.is_noop() || dry_run {
+        None
+    } else {
+        Some(write_migrated(path, &migrated, report.from).await?)
+    };
+    Ok(FileMigration {
+        report,
+        migrated,
+        backup_path,
+    })
+}
+
+/// Back up `path` and replace it with `migrated`. Returns the backup path.
+pub(crate) async fn write_migrated(path: &Path, migrated: &str, from: u32) -> Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("config.toml");
+    let backup_path = path.with_file_name(format!("{file_name}.v{from}.bak"));
+    fs::copy(path, &backup_path)
+        .await
+        .with_context(|| format!("Failed to back up config to {}", backup_path.display()))?;
+
+    let temp_path = path.with_file_name(format!(".{file_name}.migrate-{}", uuid::Uuid::new_v4()));
+    fs::write(&temp_path, migrated)
+        .await
+        .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+    if let Err(e) = fs::rename(&temp_path, path).await {
+        let _ = fs::remove_file(&temp_path).await;
+        bail!("Failed to replace {}: {e}", path.display());
+    }
+    Ok(backup_path)
+}
+
+/// Move `table[from]` to `table[to]` unless `to` is already set. A value left
+/// at `from` because `to` exists is dropped, matching how serde resolved the
+/// alias (the canonical key wins).
+fn rename_key(table: &mut Table, path: &str, from: &str, to: &str, changes: &mut Vec<String>) {
+    let Some(value) = table.remove(from) else {
+        return;
+    };
+    if table.contains_key(to) {
+        changes.push(format!(
+            "  removed {path}.{from} (superseded by {path}.{to})"
+        ));
+    } else {
+        table.insert(to.to_string(), value);
+        changes.push(format!("  renamed {path}.{from} -> {path}.{to}"));
+    }
+}
+
+fn subtable<'a>(table: &'a mut Table, path: &[&str]) -> Option<&'a mut Table> {
+    path.iter()
+        .try_fold(table, |table, key| table.get_mut(*key)?.as_table_mut())
+}
+
+/// v1: keys that older releases documented under other names.
+fn canonical_key_names(table: &mut Table, changes: &mut Vec<String>) {
+    if let Some(composio) = subtable(table, &["composio"]) {
+        rename_key(composio, "composio", "enable", "enabled", changes);
+    }
+    if let Some(provider) = subtable(table, &["storage", "provider", "config"]) {
+        for alias in ["dbURL", "database_url", "databaseUrl"] {
+            rename_key(
+                provider,
+                "storage.provider.config",
+                alias,
+                "db_url",
+                changes,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const LEGACY: &str = r#"
+default_temperature = 0.7
+
+[composio]
+enable = true
+
+[storage.provider.config]
+provider = "postgres"
+dbURL = "postgres://localhost/zeroclaw"
+"#;
+
+    #[test]
+    fn migrates_legacy_keys_and_stamps_version() {
+        let mut table: Table = toml::from_str(LEGACY).unwrap();
+        let report = migrate(&mut table).unwrap();
+        assert_eq!((report.from, report.to), (0, CURRENT_SCHEMA_VERSION));
+        assert!(report
+            .changes
+            .contains(&"  renamed composio.enable -> composio.enabled".to_string()));
+        assert_eq!(table["composio"]["enabled"].as_bool(), Some(true));
+        assert_eq!(
+            table["storage"]["provider"]["config"]["db_url"].as_str(),
+            Some("postgres://localhost/zeroclaw")
+        );
+        assert_eq!(schema_version(&table).unwrap(), CURRENT_SCHEMA_VERSION);
+
+        // Idempotent once stamped.
+        let again = migrate(&mut table).unwrap();
+        assert!(again.is_noop());
+        assert!(again.changes.is_empty());
+    }
+
+    #[test]
+    fn canonical_key_wins_over_alias() {
+        let mut table: Table =
+            toml::from_str("[composio]\nenable = false\nenabled = true\n").unwrap();
+        let report = migrate(&mut table).unwrap();
+        assert_eq!(table["composio"]["enabled"].as_bool(), Some(true));
+        assert!(table["composio"].get("enable").is_none());
+        assert!(report.changes[1].contains("removed composio.enable"));
+    }
+
+    #[test]
+    fn rejects_newer_and_malformed_versions() {
+        let mut table: Table = toml::from_str("schema_version = 999\n").unwrap();
+        assert!(migrate(&mut table).is_err());
+        let mut table: Table = toml::from_str("schema_version = \"1\"\n").unwrap();
+        assert!(migrate(&mut table).is_err());
+    }
+
+    #[tokio::test]
+    async fn migrate_file_backs_up_unless_dry_run() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(&path, LEGACY).unwrap();
+
+        let preview = migrate_file(&path, true).await.unwrap();
+        assert!(preview.backup_path.is_none());
+        assert!(preview.migrated.contains("schema_version = 1"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), LEGACY);
+
+        let applied = migrate_file(&path, false).await.unwrap();
+        let backup = applied.backup_path.unwrap();
+        assert_eq!(backup, tmp.path().join("config.toml.v0.bak"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), LEGACY);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), applied.migrated);
+
+        let noop = migrate_file(&path, false).await.unwrap();
+        assert!(noop.report.is_noop());
+        assert!(noop.backup_path.is_none());
+    }
+}
@@ -1,28 +1,82 @@
+pub mod migrations;
 pub mod schema;
 pub mod traits;
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
 #[allow(unused_imports)]
 pub use schema::{
     apply_runtime_proxy_to_builder, build_runtime_proxy_client,
-    build_runtime_proxy_client_with_timeouts, runtime_proxy_config, set_runtime_proxy_config,
-    AgentConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig, BrowserConfig,
-    BuiltinHooksConfig, ChannelsConfig, ClassificationRule, ComposioConfig, Config, CostConfig,
-    CronConfig, DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, EmbeddingRouteConfig,
-    EstopConfig, GatewayConfig, HardwareConfig, HardwareTransport, HeartbeatConfig, HooksConfig,
-    HttpRequestConfig, IMessageConfig, IdentityConfig, LarkConfig, MatrixConfig, MemoryConfig,
-    ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig, ObservabilityConfig, OtpConfig,
-    OtpMethod, PeripheralBoardConfig, PeripheralsConfig, ProxyConfig, ProxyScope,
-    QueryClassificationConfig, ReliabilityConfig, ResourceLimitsConfig, RuntimeConfig,
-    SandboxBackend, SandboxConfig, SchedulerConfig, SecretsConfig, SecurityConfig, SkillsConfig,
-    SkillsPromptInjectionMode, SlackConfig, StorageConfig, StorageProviderConfig,
-    StorageProviderSection, StreamMode, TelegramConfig, TranscriptionConfig, TunnelConfig,
-    WebSearchConfig, WebhookConfig,
+    build_runtime_proxy_client_with_timeouts, resolve_config_path, runtime_proxy_config,
+    set_runtime_proxy_config, AgentConfig, ArtifactsConfig, AuditConfig, AutoModeConfig,
+    AutonomyConfig, BleConfig, BleFormat, BleSensorConfig, BrowserComputerUseConfig, BrowserConfig,
+    BuiltinHooksConfig, CalendarConfig, CameraConfig, CameraMotionConfig, CameraSourceConfig,
+    ChannelAdminConfig, ChannelsConfig, ClassificationRule, CodeConfig, ComposioConfig, Config,
+    ControlConfig, CoordinationBackend, CoordinationConfig, CostConfig, CostDisplayConfig,
+    CostDowngradeConfig, CostDowngradeTier, CronConfig, CronMemoryScope, CustomProviderConfig,
+    DataRetentionConfig, DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, EmailSendConfig,
+    EmbeddingRouteConfig, EstopConfig, FederationConfig, FederationNodeConfig, FederationRole,
+    FetchUrlConfig, FinancialGuardrailConfig, FirmwareBuildConfig, FirmwareProjectConfig,
+    FirmwareToolchain, FourEyesConfig, GatewayConfig, GatewayLimitsConfig, GatewayRouteLimitConfig,
+    GraphqlConfig, GraphqlEndpointConfig, GroupTriggerConfig, HardwareConfig, HardwareLogsConfig,
+    HardwareTransport, HeartbeatConfig, HeartbeatProbeConfig, HooksConfig, HttpRequestConfig,
+    HttpSinkConfig, IMessageConfig, IdentityConfig, InboxConfig, IncognitoConfig, LarkConfig,
+    LokiSinkConfig, LongMessageConfig, LongMessageFileFormat, MatrixConfig, MemoryConfig,
+    ModelPricing, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig, ObservabilityConfig,
+    ObservabilityMirrorConfig, ObservabilitySinksConfig, OtpConfig, OtpMethod, PeerInstanceConfig,
+    PeersConfig, PeripheralBoardConfig, PeripheralsConfig, PinsConfig, PolicyEngineConfig,
+    PolicyEngineKind, PolicyFallback, PrivacyZone, ProbeCheck, ProviderPluginConfig, ProxyConfig,
+    ProxyScope, QueryClassificationConfig, QuestionsConfig, QueuesConfig, QuotasConfig,
+    RagCollectionConfig, RagConfig, RecordingsConfig, ReliabilityConfig, RemoteWorkerConfig,
+    RequestSigningConfig, ResearchConfig, ResearchTopicConfig, ResourceLimitsConfig,
+    RetentionConfig, RetentionPolicyConfig, RuntimeConfig, SandboxBackend, SandboxConfig,
+    SchedulerConfig, SecretsConfig, SecurityConfig, SenderVerificationConfig, ShadowConfig,
+    SkillsConfig, SkillsPromptInjectionMode, SlackConfig, SmartHomeConfig, StorageConfig,
+    StorageProviderConfig, StorageProviderSection, StreamMode, SyslogSinkConfig, TelegramConfig,
+    TelemetryConfig, ToolNetworkPolicyConfig, ToolSchemasConfig, ToolValidationConfig,
+    TranscriptionConfig, TranslationConfig, TriageConfig, TunnelConfig, UiConfig,
+    VectorIndexConfig, VectorIndexKind, VerificationMode, VoiceConfig, VoiceMode, WebSearchConfig,
+    WebhookConfig, WindowsShell,
 };
 
 pub fn name_and_presence<T: traits::ChannelConfig>(channel: &Option<T>) -> (&'static str, bool) {
     (T::name(), channel.is_some())
 }
 
+static RUNTIME_GLOBALS_READY: AtomicBool = AtomicBool::new(false);
+
+/// Point the process-wide handles (locale, tool hooks, approval and
+/// security hooks, sinks) at `config`. Run at startup and on reload.
+pub fn init_runtime_globals(config: &Config) {
+    crate::i18n::init_from_config(&config.ui);
+    crate::datetime::init_from_config(&config.ui);
+    crate::agent::checkpoint::init_from_config(config);
+    crate::agent::tool_schemas::init_from_config(config);
+    crate::agent::tool_validation::init_from_config(config);
+    crate::agent::tool_stream::init_from_config(config);
+    crate::pins::init_from_config(config);
+    crate::artifacts::init_from_config(config);
+    crate::retention::init_from_config(config);
+    crate::cost::downgrade::init_from_config(config);
+    crate::cost::conversation::init_from_config(config);
+    crate::approval::autonomous::init_from_config(config);
+    crate::security::four_eyes::init_from_config(config);
+    crate::security::policy_engine::init_from_config(config);
+    crate::security::shell_dialect::init_from_config(config);
+    crate::observability::sinks::init_from_config(&config.observability.sinks);
+    crate::observability::mirror::init_from_config(config);
+    crate::telemetry::init_from_config(config);
+    RUNTIME_GLOBALS_READY.store(true, Ordering::Release);
+}
+
+/// [`init_runtime_globals`] unless it already ran, for entry points that
+/// library callers reach without going through `main`.
+pub fn ensure_runtime_globals(config: &Config) {
+    if !RUNTIME_GLOBALS_READY.load(Ordering::Acquire) {
+        init_runtime_globals(config);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,6 +107,8 @@ mod tests {
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 1000,
         };
 
         let lark = LarkConfig {
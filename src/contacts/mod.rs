@@ -0,0 +1,479 @@
+//! Contact book: people the agent may message, and how to reach them.
+//!
+//! Each contact maps a name (plus aliases) to per-channel addresses, a
+//! preferred channel and free-form notes. Contacts live in
+//! `<workspace>/contacts.toml` so they can be edited by hand.
+//!
+//! A contact is `approved` once the owner has vouched for it: contacts added
+//! from the CLI are approved immediately, while contacts added or re-pointed
+//! by the agent stay unapproved until `zeroclaw contacts approve`. Approvals
+//! are kept in `contact_approvals.json` next to `config.toml`, outside the
+//! workspace the agent's tools can write, and each one pins the addresses
+//! that were approved, so editing `contacts.toml` can never approve a contact.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONTACTS_FILE: &str = "contacts.toml";
+const APPROVALS_FILE: &str = "contact_approvals.json";
+
+/// Approved addresses by lowercase contact name.
+type Approvals = BTreeMap<String, BTreeMap<String, String>>;
+
+/// A person (or group) reachable over one or more channels.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contact {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Channel name → recipient on that channel (chat ID, user ID, ...).
+    #[serde(default)]
+    pub addresses: BTreeMap<String, String>,
+    /// Channel used when a send does not name one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_channel: Option<String>,
+    /// Preferences the agent should respect (tone, language, quiet hours, ...).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Whether outbound messages may be sent without asking first. Never read
+    /// from or written to `contacts.toml`; see [`ContactBook::list`].
+    #[serde(skip)]
+    pub approved: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ContactsFile {
+    #[serde(default)]
+    contacts: Vec<Contact>,
+}
+
+impl Contact {
+    /// Whether `query` is this contact's name or one of its aliases,
+    /// case-insensitively.
+    fn matches(&self, query: &str) -> bool {
+        self.name.eq_ignore_ascii_case(query)
+            || self.aliases.iter().any(|a| a.eq_ignore_ascii_case(query))
+    }
+
+    fn first_name_matches(&self, query: &str) -> bool {
+        self.name
+            .split_whitespace()
+            .next()
+            .is_some_and(|first| first.eq_ignore_ascii_case(query))
+    }
+
+    /// Pick the channel and recipient for a message.
+    ///
+    /// An explicit `channel` must have an address. Otherwise the preferred
+    /// channel wins when it is deliverable, then the first deliverable
+    /// address in channel-name order.
+    pub fn route(&self, channel: Option<&str>, available: &[&str]) -> Result<(String, String)> {
+        let address = |name: &str| {
+            self.addresses
+                .iter()
+                .find(|(channel, _)| channel.eq_ignore_ascii_case(name))
+                .map(|(channel, to)| (channel.to_ascii_lowercase(), to.clone()))
+        };
+        let deliverable = |name: &str| available.iter().any(|a| a.eq_ignore_ascii_case(name));
+
+        if let Some(channel) = channel {
+            return address(channel)
+                .ok_or_else(|| anyhow::anyhow!("{} has no {channel} address", self.name));
+        }
+        if let Some(route) = self
+            .preferred_channel
+            .as_deref()
+            .filter(|preferred| deliverable(preferred))
+            .and_then(address)
+        {
+            return Ok(route);
+        }
+        self.addresses
+            .keys()
+            .find(|channel| deliverable(channel))
+            .and_then(|channel| address(channel))
+            .ok_or_else(|| {
+                let known: Vec<&str> = self.addresses.keys().map(String::as_str).collect();
+                anyhow::anyhow!(
+                    "{} has no address on a configured channel (known: {}; configured: {})",
+                    self.name,
+                    if known.is_empty() {
+                        "none".to_string()
+                    } else {
+                        known.join(", ")
+                    },
+                    if available.is_empty() {
+                        "none".to_string()
+                    } else {
+                        available.join(", ")
+                    }
+                )
+            })
+    }
+
+    /// One-line description used by listings and tool output.
+    pub fn summary(&self) -> String {
+        let addresses: Vec<String> = self
+            .addresses
+            .iter()
+            .map(|(channel, to)| format!("{channel}:{to}"))
+            .collect();
+        let mut line = format!("{} [{}]", self.name, addresses.join(", "));
+        if let Some(preferred) = &self.preferred_channel {
+            let _ = write!(line, " prefers {preferred}");
+        }
+        if !self.aliases.is_empty() {
+            let _ = write!(line, " aka {}", self.aliases.join(", "));
+        }
+        if !self.approved {
+            line.push_str(" (unapproved)");
+        }
+        if let Some(notes) = &self.notes {
+            let _ = write!(line, " — {notes}");
+        }
+        line
+    }
+}
+
+/// Workspace-backed contact store.
+pub struct ContactBook {
+    path: PathBuf,
+    /// Where approvals are recorded; without it every contact is unapproved.
+    approvals_path: Option<PathBuf>,
+}
+
+impl ContactBook {
+    /// Contacts only; approvals are not visible and cannot be granted.
+    pub fn for_workspace(workspace_dir: &Path) -> Self {
+        Self {
+            path: workspace_dir.join(CONTACTS_FILE),
+            approvals_path: None,
+        }
+    }
+
+    /// Contacts in `workspace_dir` with approvals kept in `zeroclaw_dir`.
+    pub fn new(workspace_dir: &Path, zeroclaw_dir: &Path) -> Self {
+        Self {
+            path: workspace_dir.join(CONTACTS_FILE),
+            approvals_path: Some(zeroclaw_dir.join(APPROVALS_FILE)),
+        }
+    }
+
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        let zeroclaw_dir = config.config_path.parent().unwrap_or(&config.workspace_dir);
+        Self::new(&config.workspace_dir, zeroclaw_dir)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All contacts. A contact is `approved` only while its addresses match
+    /// the ones recorded when it was approved.
+    pub fn list(&self) -> Result<Vec<Contact>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let raw = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        let file: ContactsFile = toml::from_str(&raw)
+            .with_context(|| format!("Failed to parse {}", self.path.display()))?;
+        let approvals = self.read_approvals()?;
+        Ok(file
+            .contacts
+            .into_iter()
+            .map(|mut contact| {
+                contact.approved = approvals
+                    .get(&contact.name.to_ascii_lowercase())
+                    .is_some_and(|addresses| *addresses == contact.addresses);
+                contact
+            })
+            .collect())
+    }
+
+    fn write(&self, contacts: Vec<Contact>) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let raw = toml::to_string_pretty(&ContactsFile { contacts })?;
+        fs::write(&self.path, raw)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    /// A missing file means nothing is approved; an unreadable one is an
+    /// error rather than a silent reset.
+    fn read_approvals(&self) -> Result<Approvals> {
+        let Some(path) = &self.approvals_path else {
+            return Ok(Approvals::new());
+        };
+        let raw = match fs::read_to_string(path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Approvals::new()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        serde_json::from_str(&raw).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn write_approvals(&self, approvals: &Approvals) -> Result<()> {
+        let Some(path) = &self.approvals_path else {
+            bail!("Contact approvals are not available here");
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(approvals)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn record_approval(&self, contact: &Contact) -> Result<()> {
+        let mut approvals = self.read_approvals()?;
+        approvals.insert(contact.name.to_ascii_lowercase(), contact.addresses.clone());
+        self.write_approvals(&approvals)
+    }
+
+    /// Resolve a name or alias; falls back to a unique first-name match.
+    pub fn find(&self, query: &str) -> Result<Option<Contact>> {
+        let query = query.trim();
+        let contacts = self.list()?;
+        if let Some(contact) = contacts.iter().find(|c| c.matches(query)) {
+            return Ok(Some(contact.clone()));
+        }
+        let candidates: Vec<&Contact> = contacts
+            .iter()
+            .filter(|c| c.first_name_matches(query))
+            .collect();
+        match candidates.as_slice() {
+            [] => Ok(None),
+            [contact] => Ok(Some((*contact).clone())),
+            many => bail!(
+                "'{query}' matches several contacts: {}",
+                many.iter()
+                    .map(|c| c.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// Insert or replace a contact by name.
+    ///
+    /// Only a `trusted` caller of a book with approvals can approve the
+    /// contact through `contact.approved`. Changing the addresses of an approved contact
+    /// revokes its approval until it is approved again, so messages cannot be
+    /// silently redirected.
+    pub fn upsert(&self, mut contact: Contact, trusted: bool) -> Result<()> {
+        contact.name = contact.name.trim().to_string();
+        if contact.name.is_empty() {
+            bail!("Contact name cannot be empty");
+        }
+        if contact.addresses.is_empty() {
+            bail!(
+                "Contact '{}' needs at least one channel address",
+                contact.name
+            );
+        }
+        contact.addresses = contact
+            .addresses
+            .into_iter()
+            .map(|(channel, to)| (channel.trim().to_ascii_lowercase(), to.trim().to_string()))
+            .filter(|(channel, to)| !channel.is_empty() && !to.is_empty())
+            .collect();
+        contact.preferred_channel = contact
+            .preferred_channel
+            .map(|channel| channel.trim().to_ascii_lowercase())
+            .filter(|channel| !channel.is_empty());
+
+        if trusted && contact.approved && self.approvals_path.is_some() {
+            self.record_approval(&contact)?;
+        }
+        let mut contacts = self.list()?;
+        match contacts
+            .iter_mut()
+            .find(|c| c.name.eq_ignore_ascii_case(&contact.name))
+        {
+            Some(existing) => *existing = contact,
+            None => contacts.push(contact),
+        }
+        contacts.sort_by_key(|c| c.name.to_ascii_lowercase());
+        self.write(contacts)
+    }
+
+    /// Approve a contact's current addresses for outbound messages.
+    pub fn approve(&self, name: &str) -> Result<()> {
+        let contacts = self.list()?;
+        let contact = contacts
+            .iter()
+            .find(|c| c.matches(name))
+            .ok_or_else(|| anyhow::anyhow!("Unknown contact '{name}'"))?;
+        self.record_approval(contact)
+    }
+
+    /// Delete a contact; returns whether it existed.
+    pub fn remove(&self, name: &str) -> Result<bool> {
+        let mut contacts = self.list()?;
+        let before = contacts.len();
+        contacts.retain(|c| !c.matches(name));
+        if contacts.len() == before {
+            return Ok(false);
+        }
+        if self.approvals_path.is_some() {
+            let mut approvals = self.read_approvals()?;
+            approvals.retain(|approved, _| {
+                contacts
+                    .iter()
+                    .any(|c| c.name.eq_ignore_ascii_case(approved))
+            });
+            self.write_approvals(&approvals)?;
+        }
+        self.write(contacts)?;
+        Ok(true)
+    }
+}
+
+/// Parse `channel=recipient` pairs (as accepted by `--address`).
+pub fn parse_addresses(raw: &[String]) -> Result<BTreeMap<String, String>> {
+    raw.iter()
+        .map(|pair| match pair.split_once('=') {
+            Some((channel, to)) if !channel.trim().is_empty() && !to.trim().is_empty() => {
+                Ok((channel.trim().to_ascii_lowercase(), to.trim().to_string()))
+            }
+            _ => bail!("Invalid address '{pair}' (expected channel=recipient)"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn alice() -> Contact {
+        Contact {
+            name: "Alice Smith".into(),
+            aliases: vec!["al".into()],
+            addresses: BTreeMap::from([
+                ("slack".to_string(), "U123".to_string()),
+                ("telegram".to_string(), "42".to_string()),
+            ]),
+            preferred_channel: Some("telegram".into()),
+            notes: Some("Prefers short messages".into()),
+            approved: false,
+        }
+    }
+
+    #[test]
+    fn find_matches_name_alias_and_unique_first_name() {
+        let tmp = TempDir::new().unwrap();
+        let book = ContactBook::for_workspace(tmp.path());
+        book.upsert(alice(), true).unwrap();
+
+        assert_eq!(
+            book.find("alice smith").unwrap().unwrap().name,
+            "Alice Smith"
+        );
+        assert_eq!(book.find("AL").unwrap().unwrap().name, "Alice Smith");
+        assert_eq!(book.find("Alice").unwrap().unwrap().name, "Alice Smith");
+        assert!(book.find("Bob").unwrap().is_none());
+
+        let mut other = alice();
+        other.name = "Alice Jones".into();
+        other.aliases.clear();
+        book.upsert(other, true).unwrap();
+        let err = book.find("alice").unwrap_err().to_string();
+        assert!(err.contains("several contacts"), "{err}");
+    }
+
+    #[test]
+    fn route_prefers_explicit_then_preferred_then_available() {
+        let contact = alice();
+        assert_eq!(
+            contact.route(Some("slack"), &["telegram"]).unwrap(),
+            ("slack".to_string(), "U123".to_string())
+        );
+        assert_eq!(
+            contact.route(None, &["slack", "telegram"]).unwrap(),
+            ("telegram".to_string(), "42".to_string())
+        );
+        assert_eq!(
+            contact.route(None, &["slack"]).unwrap(),
+            ("slack".to_string(), "U123".to_string())
+        );
+        assert!(contact.route(Some("discord"), &["discord"]).is_err());
+        assert!(contact.route(None, &["discord"]).is_err());
+    }
+
+    #[test]
+    fn untrusted_updates_cannot_keep_approval_after_readdressing() {
+        let tmp = TempDir::new().unwrap();
+        let book = ContactBook::new(&tmp.path().join("workspace"), tmp.path());
+        book.upsert(alice(), false).unwrap();
+        assert!(!book.find("al").unwrap().unwrap().approved);
+
+        book.approve("al").unwrap();
+        let mut notes_only = alice();
+        notes_only.notes = Some("Out until Monday".into());
+        book.upsert(notes_only, false).unwrap();
+        assert!(book.find("al").unwrap().unwrap().approved);
+
+        let mut redirected = alice();
+        redirected.addresses.insert("telegram".into(), "666".into());
+        book.upsert(redirected, false).unwrap();
+        assert!(!book.find("al").unwrap().unwrap().approved);
+
+        assert!(book.remove("Alice Smith").unwrap());
+        assert!(book.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn approval_cannot_be_granted_by_editing_the_workspace() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path().join("workspace");
+        let book = ContactBook::new(&workspace, tmp.path());
+        let mut approved = alice();
+        approved.approved = true;
+        book.upsert(approved.clone(), false).unwrap();
+        assert!(!book.find("al").unwrap().unwrap().approved);
+
+        let raw = fs::read_to_string(book.path()).unwrap();
+        assert!(!raw.contains("approved"), "{raw}");
+        fs::write(
+            book.path(),
+            raw.replace("[[contacts]]", "[[contacts]]\napproved = true"),
+        )
+        .unwrap();
+        assert!(!book.find("al").unwrap().unwrap().approved);
+        assert!(
+            !ContactBook::for_workspace(&workspace)
+                .find("al")
+                .unwrap()
+                .unwrap()
+                .approved
+        );
+
+        book.upsert(approved, true).unwrap();
+        assert!(book.find("al").unwrap().unwrap().approved);
+        assert!(tmp.path().join(APPROVALS_FILE).exists());
+        assert!(!workspace.join(APPROVALS_FILE).exists());
+        assert!(
+            !ContactBook::for_workspace(&workspace)
+                .find("al")
+                .unwrap()
+                .unwrap()
+                .approved
+        );
+    }
+
+    #[test]
+    fn parse_addresses_requires_channel_and_recipient() {
+        let parsed = parse_addresses(&["Telegram=42".into()]).unwrap();
+        assert_eq!(parsed.get("telegram").map(String::as_str), Some("42"));
+        assert!(parse_addresses(&["telegram".into()]).is_err());
+        assert!(parse_addresses(&["=42".into()]).is_err());
+    }
+}
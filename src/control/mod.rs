@@ -0,0 +1,673 @@
+//! Local control socket for a running daemon (`[control]`).
+//!
+//! The daemon listens on `$XDG_RUNTIME_DIR/zeroclaw.sock` (or
+//! `zeroclaw.sock` next to `config.toml`), created readable only by its
+//! user. Clients write one JSON [`Request`] per line and read one JSON
+//! [`Response`] per line back. Every request carries the token from
+//! `control.token` in the config directory, so only someone who can read
+//! the config can drive the daemon, and no TCP port is opened.
+
+use crate::config::Config;
+use crate::security::pairing::constant_time_eq;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Socket file name under the runtime or config directory.
+const SOCKET_NAME: &str = "zeroclaw.sock";
+/// Token file name in the config directory.
+const TOKEN_FILE: &str = "control.token";
+/// Longest request line accepted, in bytes.
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+
+/// One control request: the shared token plus a command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Request {
+    pub token: String,
+    #[serde(flatten)]
+    pub command: Command,
+}
+
+/// Commands understood by the daemon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Command {
+    /// Component health, estop state, pending approvals and job counts.
+    Status,
+    /// Re-read `config.toml` and apply the settings held process-wide.
+    Reload,
+    /// Engage the emergency stop. With no options, everything is stopped.
+    Estop {
+        #[serde(default)]
+        network: bool,
+        #[serde(default)]
+        domains: Vec<String>,
+        #[serde(default)]
+        tools: Vec<String>,
+    },
+    /// Second-approval requests waiting for a decision.
+    Approvals,
+    /// Approve or deny a pending request as `[control].operator`.
+    Decide { code: String, approved: bool },
+    /// Run an agent turn in the background and return its job id.
+    Submit { message: String },
+    /// State and output of a submitted job.
+    Job { id: String },
+    /// Drop inbound messages from a channel until it is resumed.
+    PauseChannel { channel: String },
+    /// Dispatch messages from a paused channel again.
+    ResumeChannel { channel: String },
+    /// Drop every inbound message still waiting to be dispatched.
+    FlushQueues,
+    /// Per-sender conversation histories held by the channels.
+    Sessions,
+}
+
+/// Answer to one request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Response {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub data: Value,
+}
+
+impl Response {
+    fn ok(data: Value) -> Self {
+        Self {
+            ok: true,
+            error: None,
+            data,
+        }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            error: Some(message.into()),
+            data: Value::Null,
+        }
+    }
+}
+
+fn config_dir(config: &Config) -> PathBuf {
+    config
+        .config_path
+        .parent()
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf)
+}
+
+/// Where the daemon listens and clients connect.
+pub fn socket_path(config: &Config) -> PathBuf {
+    if let Some(path) = config.control.socket_path.as_deref() {
+        return PathBuf::from(crate::util::expand_tilde(path));
+    }
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map_or_else(|| config_dir(config), PathBuf::from)
+        .join(SOCKET_NAME)
+}
+
+/// File holding the token clients must present.
+pub fn token_path(config: &Config) -> PathBuf {
+    config_dir(config).join(TOKEN_FILE)
+}
+
+/// Read the control token, creating it (mode 0600) on first use.
+fn ensure_token(path: &Path) -> Result<String> {
+    if let Ok(token) = std::fs::read_to_string(path) {
+        let token = token.trim();
+        if !token.is_empty() {
+            return Ok(token.to_string());
+        }
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let token = hex::encode(rand::random::<[u8; 32]>());
+    // Replace an empty leftover rather than reuse its permissions, and create
+    // the file private so the token is never readable by others.
+    let _ = std::fs::remove_file(path);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .and_then(|mut file| std::io::Write::write_all(&mut file, token.as_bytes()))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(token)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum JobState {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// A background agent turn started with [`Command::Submit`].
+#[derive(Debug, Clone, Serialize)]
+struct Job {
+    id: String,
+    state: JobState,
+    submitted_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    finished_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Request handling shared by all connections.
+pub struct ControlServer {
+    config: RwLock<Config>,
+    token: String,
+    started_at: DateTime<Utc>,
+    jobs: Mutex<VecDeque<Job>>,
+}
+
+impl ControlServer {
+    pub fn new(config: Config, token: String) -> Self {
+        Self {
+            config: RwLock::new(config),
+            token,
+            started_at: Utc::now(),
+            jobs: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Check the token and run one command.
+    pub async fn handle(self: &Arc<Self>, request: Request) -> Response {
+        if !constant_time_eq(request.token.trim(), &self.token) {
+            tracing::warn!("Control socket: rejected request with an invalid token");
+            return Response::error("Invalid control token");
+        }
+        tracing::debug!("Control socket: {:?}", request.command);
+        let result = match request.command {
+            Command::Status => Ok(self.status()),
+            Command::Reload => self.reload().await,
+            Command::Estop {
+                network,
+                domains,
+                tools,
+            } => self.estop(network, domains, tools),
+            Command::Approvals => self.approvals(),
+            Command::Decide { code, approved } => self.decide(&code, approved),
+            Command::Submit { message } => Ok(self.submit(message)),
+            Command::Job { id } => self.job(&id),
+            Command::PauseChannel { channel } => Self::pause_channel(&channel, true),
+            Command::ResumeChannel { channel } => Self::pause_channel(&channel, false),
+            Command::FlushQueues => Self::flush_queues().await,
+            Command::Sessions => Self::sessions(),
+        };
+        result.unwrap_or_else(|e| Response::error(format!("{e:#}")))
+    }
+
+    fn status(&self) -> Response {
+        let config = self.config.read().clone();
+        let estop = if config.security.estop.enabled {
+            crate::security::EstopManager::load(&config.security.estop, &config_dir(&config))
+                .ok()
+                .map(|manager| manager.status())
+        } else {
+            None
+        };
+        let pending_approvals =
+            crate::security::four_eyes::current().map(|guard| guard.pending().len());
+        let (running, finished) = {
+            let jobs = self.jobs.lock();
+            let running = jobs
+                .iter()
+                .filter(|job| job.state == JobState::Running)
+                .count();
+            (running, jobs.len() - running)
+        };
+        Response::ok(serde_json::json!({
+            "pid": std::process::id(),
+            "version": env!("CARGO_PKG_VERSION"),
+            "started_at": self.started_at,
+            "uptime_secs": (Utc::now() - self.started_at).num_seconds(),
+            "health": crate::health::snapshot_json(),
+            "estop": estop,
+            "pending_approvals": pending_approvals,
+            "jobs": {"running": running, "finished": finished},
+            "channels": crate::channels::live::running_channels().ok(),
+            "paused_channels": crate::channels::live::paused_channels(),
+        }))
+    }
+
+    /// Settings read through process-wide handles take effect at once;
+    /// components started from the old config keep it until restart.
+    async fn reload(&self) -> Result<Response> {
+        let mut config = Box::pin(Config::load_or_init()).await?;
+        config.apply_env_overrides();
+        config.validate()?;
+        crate::config::init_runtime_globals(&config);
+        let path = config.config_path.display().to_string();
+        *self.config.write() = config;
+        tracing::info!("Control socket: reloaded {path}");
+        Ok(Response::ok(serde_json::json!({ "reloaded": path })))
+    }
+
+    fn estop(&self, network: bool, domains: Vec<String>, tools: Vec<String>) -> Result<Response> {
+        use crate::security::EstopLevel;
+        let config = self.config.read().clone();
+        if !config.security.estop.enabled {
+            bail!("Emergency stop is disabled; set [security.estop].enabled = true");
+        }
+        let level = match (network, domains.is_empty(), tools.is_empty()) {
+            (false, true, true) => EstopLevel::KillAll,
+            (true, true, true) => EstopLevel::NetworkKill,
+            (false, false, true) => EstopLevel::DomainBlock(domains),
+            (false, true, false) => EstopLevel::ToolFreeze(tools),
+            _ => bail!("Choose one of network, domains or tools"),
+        };
+        let mut manager =
+            crate::security::EstopManager::load(&config.security.estop, &config_dir(&config))?;
+        manager.engage(level)?;
+        tracing::warn!("Control socket: emergency stop engaged");
+        Ok(Response::ok(serde_json::to_value(manager.status())?))
+    }
+
+    fn approvals(&self) -> Result<Response> {
+        let Some(guard) = crate::security::four_eyes::current() else {
+            bail!("Second approval is disabled; set [security.four_eyes].enabled = true");
+        };
+        Ok(Response::ok(serde_json::to_value(guard.pending())?))
+    }
+
+    /// The approver is the configured operator, never a client-supplied
+    /// identity: anyone holding the token could otherwise claim to be any
+    /// approver.
+    fn decide(&self, code: &str, approved: bool) -> Result<Response> {
+        let Some(guard) = crate::security::four_eyes::current() else {
+            bail!("Second approval is disabled; set [security.four_eyes].enabled = true");
+        };
+        let Some(operator) = self.config.read().control.operator.clone() else {
+            bail!("Set [control].operator to the approver identity that decides over the control socket");
+        };
+        let pending = guard.decide(code, &operator, approved)?;
+        Ok(Response::ok(serde_json::to_value(pending)?))
+    }
+
+    fn pause_channel(channel: &str, pause: bool) -> Result<Response> {
+        let changed = if pause {
+            crate::channels::live::pause(channel)?
+        } else {
+            crate::channels::live::resume(channel)?
+        };
+        Ok(Response::ok(serde_json::json!({
+            "channel": channel.trim().to_ascii_lowercase(),
+            "paused": pause,
+            "changed": changed,
+        })))
+    }
+
+    async fn flush_queues() -> Result<Response> {
+        let dropped = crate::channels::live::flush().await?;
+        Ok(Response::ok(serde_json::json!({ "dropped": dropped })))
+    }
+
+    fn sessions() -> Result<Response> {
+        let sessions = crate::channels::live::sessions()?;
+        Ok(Response::ok(serde_json::to_value(sessions)?))
+    }
+
+    fn submit(self: &Arc<Self>, message: String) -> Response {
+        let id = uuid::Uuid::new_v4().simple().to_string()[..12].to_string();
+        self.record(Job {
+            id: id.clone(),
+            state: JobState::Running,
+            submitted_at: Utc::now(),
+            finished_at: None,
+            output: None,
+            error: None,
+        });
+        let server = Arc::clone(self);
+        let config = self.config.read().clone();
+        let job_id = id.clone();
+        tokio::spawn(async move {
+            let result = Box::pin(crate::agent::process_message(config, &message)).await;
+            server.finish(&job_id, result);
+        });
+        Response::ok(serde_json::json!({ "id": id }))
+    }
+
+    fn job(&self, id: &str) -> Result<Response> {
+        let jobs = self.jobs.lock();
+        let Some(job) = jobs.iter().find(|job| job.id == id) else {
+            bail!("No job {id}");
+        };
+        Ok(Response::ok(serde_json::to_value(job)?))
+    }
+
+    /// Track a job, forgetting the oldest finished ones past `max_jobs`.
+    fn record(&self, job: Job) {
+        let max_jobs = self.config.read().control.max_jobs;
+        let mut jobs = self.jobs.lock();
+        jobs.push_back(job);
+        let mut finished = jobs
+            .iter()
+            .filter(|job| job.state != JobState::Running)
+            .count();
+        while finished > max_jobs {
+            let Some(oldest) = jobs.iter().position(|job| job.state != JobState::Running) else {
+                break;
+            };
+            jobs.remove(oldest);
+            finished -= 1;
+        }
+    }
+
+    fn finish(&self, id: &str, result: Result<String>) {
+        let mut jobs = self.jobs.lock();
+        if let Some(job) = jobs.iter_mut().find(|job| job.id == id) {
+            job.finished_at = Some(Utc::now());
+            match result {
+                Ok(output) => {
+                    job.state = JobState::Succeeded;
+                    job.output = Some(output);
+                }
+                Err(e) => {
+                    job.state = JobState::Failed;
+                    job.error = Some(format!("{e:#}"));
+                }
+            }
+        }
+    }
+}
+
+/// Removes the socket file when the listener stops.
+#[cfg(unix)]
+struct SocketFile(PathBuf);
+
+#[cfg(unix)]
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Listen on the control socket until the task is aborted.
+#[cfg(unix)]
+pub async fn serve(config: Config) -> Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use tokio::net::{UnixListener, UnixStream};
+
+    let path = socket_path(&config);
+    if path.exists() {
+        if UnixStream::connect(&path).await.is_ok() {
+            bail!("Another daemon is already listening on {}", path.display());
+        }
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+    }
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    std::fs::create_dir_all(parent)?;
+    let token = ensure_token(&token_path(&config))?;
+    // Bind inside a private (0700) directory and move the socket into place
+    // once it is 0600, so it is never reachable under the process umask.
+    let staging = parent.join(format!(
+        ".zeroclaw-control-{}",
+        hex::encode(rand::random::<[u8; 8]>())
+    ));
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&staging)
+        .with_context(|| {
+            format!(
+                "Failed to create a private directory in {}",
+                parent.display()
+            )
+        })?;
+    let staged = staging.join(SOCKET_NAME);
+    let bound = UnixListener::bind(&staged)
+        .with_context(|| format!("Failed to bind control socket {}", path.display()))
+        .and_then(|listener| {
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))?;
+            std::fs::rename(&staged, &path)
+                .with_context(|| format!("Failed to move control socket to {}", path.display()))?;
+            Ok(listener)
+        });
+    let _ = std::fs::remove_dir_all(&staging);
+    let listener = bound?;
+    let socket = SocketFile(path);
+    tracing::info!("Control socket listening on {}", socket.0.display());
+
+    let server = Arc::new(ControlServer::new(config, token));
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let server = Arc::clone(&server);
+        tokio::spawn(async move {
+            if let Err(e) = serve_connection(&server, stream).await {
+                tracing::debug!("Control connection closed: {e}");
+            }
+        });
+    }
+}
+
+#[cfg(not(unix))]
+pub async fn serve(_config: Config) -> Result<()> {
+    bail!("The control socket needs a UNIX platform")
+}
+
+#[cfg(unix)]
+async fn serve_connection(
+    server: &Arc<ControlServer>,
+    stream: tokio::net::UnixStream,
+) -> Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+    loop {
+        let mut line = String::new();
+        let read = (&mut reader)
+            .take(MAX_REQUEST_BYTES)
+            .read_line(&mut line)
+            .await?;
+        if read == 0 {
+            return Ok(());
+        }
+        let oversized = !line.ends_with('\n') && read as u64 >= MAX_REQUEST_BYTES;
+        let response = if oversized {
+            Response::error(format!("Request exceeds {MAX_REQUEST_BYTES} bytes"))
+        } else {
+            match serde_json::from_str::<Request>(line.trim()) {
+                Ok(request) => server.handle(request).await,
+                Err(e) => Response::error(format!("Invalid request: {e}")),
+            }
+        };
+        let mut payload = serde_json::to_vec(&response)?;
+        payload.push(b'\n');
+        write.write_all(&payload).await?;
+        if oversized {
+            return Ok(());
+        }
+    }
+}
+
+/// Send one command to the running daemon and wait for its answer.
+#[cfg(unix)]
+pub async fn request(config: &Config, command: Command) -> Result<Response> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::UnixStream;
+
+    let path = socket_path(config);
+    let token_file = token_path(config);
+    let token = std::fs::read_to_string(&token_file)
+        .with_context(|| format!("Failed to read {}", token_file.display()))?;
+    let stream = UnixStream::connect(&path).await.with_context(|| {
+        format!(
+            "No daemon is listening on {}; start it with `zeroclaw daemon`",
+            path.display()
+        )
+    })?;
+    let (read, mut write) = stream.into_split();
+    let mut payload = serde_json::to_vec(&Request {
+        token: token.trim().to_string(),
+        command,
+    })?;
+    payload.push(b'\n');
+    write.write_all(&payload).await?;
+
+    let mut line = String::new();
+    BufReader::new(read).read_line(&mut line).await?;
+    if line.is_empty() {
+        bail!("The daemon closed the control connection without answering");
+    }
+    serde_json::from_str(line.trim()).context("Invalid response from the daemon")
+}
+
+#[cfg(not(unix))]
+pub async fn request(_config: &Config, _command: Command) -> Result<Response> {
+    bail!("The control socket needs a UNIX platform")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config(tmp: &TempDir) -> Config {
+        let mut config = Config::default();
+        config.workspace_dir = tmp.path().join("workspace");
+        config.config_path = tmp.path().join("config.toml");
+        config.control.socket_path = Some(tmp.path().join("ctl.sock").display().to_string());
+        config.control.max_jobs = 1;
+        config
+    }
+
+    #[test]
+    fn requests_parse_from_flat_json() {
+        let request: Request =
+            serde_json::from_str(r#"{"token":"t","command":"estop","tools":["shell"]}"#).unwrap();
+        assert_eq!(
+            request.command,
+            Command::Estop {
+                network: false,
+                domains: Vec::new(),
+                tools: vec!["shell".into()],
+            }
+        );
+        let request: Request =
+            serde_json::from_str(r#"{"token":"t","command":"job","id":"abc"}"#).unwrap();
+        assert_eq!(request.command, Command::Job { id: "abc".into() });
+        let request: Request =
+            serde_json::from_str(r#"{"token":"t","command":"pause_channel","channel":"telegram"}"#)
+                .unwrap();
+        assert_eq!(
+            request.command,
+            Command::PauseChannel {
+                channel: "telegram".into()
+            }
+        );
+        let request: Request = serde_json::from_str(
+            r#"{"token":"t","command":"decide","code":"123456","approved":true}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request.command,
+            Command::Decide {
+                code: "123456".into(),
+                approved: true
+            }
+        );
+        assert!(serde_json::from_str::<Request>(r#"{"token":"t","command":"rm"}"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn wrong_token_is_rejected_and_finished_jobs_are_pruned() {
+        let tmp = TempDir::new().unwrap();
+        let server = Arc::new(ControlServer::new(config(&tmp), "secret".into()));
+        let denied = server
+            .handle(Request {
+                token: "guess".into(),
+                command: Command::Status,
+            })
+            .await;
+        assert!(!denied.ok);
+        assert_eq!(denied.error.as_deref(), Some("Invalid control token"));
+
+        for id in ["a", "b"] {
+            server.record(Job {
+                id: id.into(),
+                state: JobState::Running,
+                submitted_at: Utc::now(),
+                finished_at: None,
+                output: None,
+                error: None,
+            });
+            server.finish(id, Ok(format!("done {id}")));
+        }
+        server.record(Job {
+            id: "c".into(),
+            state: JobState::Running,
+            submitted_at: Utc::now(),
+            finished_at: None,
+            output: None,
+            error: None,
+        });
+        assert!(server.job("a").is_err());
+        assert_eq!(server.job("b").unwrap().data["output"], "done b");
+        assert_eq!(server.job("c").unwrap().data["state"], "running");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn client_talks_to_daemon_over_the_socket() {
+        let tmp = TempDir::new().unwrap();
+        let config = config(&tmp);
+        let listener = tokio::spawn(serve(config.clone()));
+        let path = socket_path(&config);
+        while !path.exists() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        use std::os::unix::fs::PermissionsExt;
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&path), 0o600);
+        assert_eq!(mode(&token_path(&config)), 0o600);
+        assert!(std::fs::read_dir(tmp.path()).unwrap().all(|entry| !entry
+            .unwrap()
+            .file_name()
+            .to_string_lossy()
+            .starts_with(".zeroclaw-control-")));
+
+        let status = request(&config, Command::Status).await.unwrap();
+        assert!(status.ok, "{:?}", status.error);
+        assert_eq!(status.data["pid"], std::process::id());
+
+        let estop = request(
+            &config,
+            Command::Estop {
+                network: false,
+                domains: Vec::new(),
+                tools: Vec::new(),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!estop.ok);
+        assert!(estop.error.unwrap().contains("disabled"));
+
+        listener.abort();
+        let _ = listener.await;
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,459 @@
+//! Shared limits and locks for multiple daemons (`[coordination]`).
+//!
+//! With the default `local` backend every process keeps its own gateway rate
+//! limits, cost totals and cron schedule. With `redis` or `postgres`, daemons
+//! that share channels or budgets keep these in the shared store instead, so
+//! limits hold across all of them:
+//!
+//! - gateway rate limits become fixed-window counters shared by key;
+//! - recorded costs also accumulate in shared daily and monthly totals, which
+//!   budget checks read;
+//! - the cron scheduler only runs on the node holding the `cron` lease;
+//! - with `ha = true`, channels, heartbeat and the scheduler only run on the
+//!   node holding the `leader` lease (see `daemon`).
+//!
+//! When the store cannot be reached, rate limits and budgets fall back to the
+//! local view and leases are treated as held by someone else, so scheduled
+//! jobs pause rather than run twice.
+
+#[cfg(feature = "coordination-postgres")]
+pub mod postgres;
+#[cfg(feature = "coordination-redis")]
+pub mod redis;
+
+use crate::config::{Config, CoordinationBackend, CoordinationConfig};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Duration;
+
+/// Lease held by the node that runs channels, heartbeat and the scheduler in HA mode.
+pub const LEADER_LEASE: &str = "leader";
+
+/// Keep shared daily and monthly cost totals a little past their period.
+const DAILY_COST_TTL: Duration = Duration::from_secs(2 * 24 * 60 * 60);
+const MONTHLY_COST_TTL: Duration = Duration::from_secs(32 * 24 * 60 * 60);
+
+/// Storage for shared counters and leases.
+pub trait Backend: Send + Sync {
+    /// Increment an integer counter, (re)setting its expiry; returns the new value.
+    fn incr(&self, key: &str, ttl: Duration) -> Result<u64>;
+    /// Add to a float counter, (re)setting its expiry; returns the new value.
+    fn incr_float(&self, key: &str, by: f64, ttl: Duration) -> Result<f64>;
+    /// Read a float counter; missing counters read as zero.
+    fn get_float(&self, key: &str) -> Result<f64>;
+    /// Take or renew a lease for `holder`; false while someone else holds it.
+    fn try_lease(&self, key: &str, holder: &str, ttl: Duration) -> Result<bool>;
+    /// Current holder of an unexpired lease.
+    fn lease_holder(&self, key: &str) -> Result<Option<String>>;
+    /// Give up a lease, if `holder` still holds it.
+    fn release_lease(&self, key: &str, holder: &str) -> Result<()>;
+}
+
+/// Shared coordination state for this process.
+pub struct Coordinator {
+    backend: Box<dyn Backend>,
+    prefix: String,
+    node_id: String,
+}
+
+static COORDINATOR: LazyLock<RwLock<Option<Arc<Coordinator>>>> =
+    LazyLock::new(|| RwLock::new(None));
+
+/// Initialize (or reset to per-process limits) from config.
+pub fn init_from_config(config: &Config) {
+    let coordinator = match Coordinator::from_config(&config.coordination) {
+        Ok(coordinator) => coordinator.map(Arc::new),
+        Err(e) => {
+            tracing::warn!("Shared coordination unavailable, using per-process limits: {e:#}");
+            None
+        }
+    };
+    *COORDINATOR.write().unwrap_or_else(|e| e.into_inner()) = coordinator;
+}
+
+fn current() -> Option<Arc<Coordinator>> {
+    COORDINATOR
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Identity of this process in leases: `[coordination].node_id` or `<hostname>-<pid>`.
+pub fn node_id(config: &CoordinationConfig) -> String {
+    config
+        .node_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map_or_else(
+            || {
+                let host = hostname::get()
+                    .map_or_else(|_| "unknown".into(), |h| h.to_string_lossy().to_string());
+                format!("{host}-{}", std::process::id())
+            },
+            str::to_string,
+        )
+}
+
+/// Count a hit against a shared limit of `limit` per `window`.
+///
+/// Returns `None` when limits are per-process (or Redis is unreachable) and
+/// the caller should apply its local limiter.
+pub fn rate_limit(scope: &str, key: &str, limit: u32, window: Duration) -> Option<bool> {
+    current()?.rate_limit(scope, key, limit, window, Utc::now())
+}
+
+/// Add a recorded cost to the shared daily and monthly totals.
+pub fn add_cost(cost_usd: f64, at: DateTime<Utc>) {
+    if let Some(coordinator) = current() {
+        coordinator.add_cost(cost_usd, at);
+    }
+}
+
+/// Shared `(daily, monthly)` cost totals, or `None` to use local totals.
+pub fn shared_costs(now: DateTime<Utc>) -> Option<(f64, f64)> {
+    current()?.costs(now)
+}
+
+/// Take or keep the named lease for this node. Always true with per-process
+/// coordination.
+pub fn hold_lease(name: &str, ttl: Duration) -> bool {
+    current().is_none_or(|coordinator| coordinator.hold_lease(name, ttl))
+}
+
+/// Give up the named lease so another node can take it without waiting for
+/// it to expire.
+pub fn release_lease(name: &str) {
+    if let Some(coordinator) = current() {
+        coordinator.release_lease(name);
+    }
+}
+
+/// Node currently holding the named lease.
+pub fn lease_holder(name: &str) -> Result<Option<String>> {
+    let coordinator = current().context("shared coordination is not available")?;
+    coordinator.lease_holder(name)
+}
+
+/// Whether limits and leases are shared with other daemons.
+pub fn is_shared() -> bool {
+    current().is_some()
+}
+
+/// One-line high-availability summary for `zeroclaw status`.
+pub fn describe_ha(config: &CoordinationConfig) -> String {
+    if !config.ha {
+        return "off (enable with [coordination].ha = true)".into();
+    }
+    let backend = match config.backend {
+        CoordinationBackend::Local => "local",
+        CoordinationBackend::Redis => "redis",
+        CoordinationBackend::Postgres => "postgres",
+    };
+    let leader = match lease_holder(LEADER_LEASE) {
+        Ok(Some(holder)) if config.node_id.as_deref().map(str::trim) == Some(holder.as_str()) => {
+            format!("leader {holder} (this node)")
+        }
+        Ok(Some(holder)) => format!("leader {holder}"),
+        Ok(None) => "no leader elected".into(),
+        Err(e) => format!("leader unknown ({e:#})"),
+    };
+    format!("{leader}; {backend}, lease {}s", config.lease_secs)
+}
+
+impl Coordinator {
+    /// `None` for the `local` backend.
+    pub fn from_config(config: &CoordinationConfig) -> Result<Option<Self>> {
+        match config.backend {
+            CoordinationBackend::Local => Ok(None),
+            CoordinationBackend::Redis => Self::redis(config).map(Some),
+            CoordinationBackend::Postgres => Self::postgres(config).map(Some),
+        }
+    }
+
+    #[cfg(feature = "coordination-redis")]
+    fn redis(config: &CoordinationConfig) -> Result<Self> {
+        let backend =
+            redis::RedisBackend::new(&config.redis_url, Duration::from_millis(config.timeout_ms))?;
+        Ok(Self::new(
+            Box::new(backend),
+            &config.key_prefix,
+            node_id(config),
+        ))
+    }
+
+    #[cfg(not(feature = "coordination-redis"))]
+    fn redis(_config: &CoordinationConfig) -> Result<Self> {
+        anyhow::bail!(
+            "coordination backend 'redis' requested but this build was compiled without \
+            `coordination-redis`; rebuild with `--features coordination-redis`"
+        )
+    }
+
+    #[cfg(feature = "coordination-postgres")]
+    fn postgres(config: &CoordinationConfig) -> Result<Self> {
+        let url = config
+            .postgres_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .context("coordination backend 'postgres' requires coordination.postgres_url")?;
+        let backend =
+            postgres::PostgresBackend::new(url, Duration::from_millis(config.timeout_ms))?;
+        Ok(Self::new(
+            Box::new(backend),
+            &config.key_prefix,
+            node_id(config),
+        ))
+    }
+
+    #[cfg(not(feature = "coordination-postgres"))]
+    fn postgres(_config: &CoordinationConfig) -> Result<Self> {
+        anyhow::bail!(
+            "coordination backend 'postgres' requested but this build was compiled without \
+            `coordination-postgres`; rebuild with `--features coordination-postgres`"
+        )
+    }
+
+    pub fn new(backend: Box<dyn Backend>, prefix: &str, node_id: String) -> Self {
+        Self {
+            backend,
+            prefix: prefix.trim().trim_end_matches(':').to_string(),
+            node_id,
+        }
+    }
+
+    fn key(&self, parts: &[&str]) -> String {
+        let mut key = self.prefix.clone();
+        for part in parts {
+            key.push(':');
+            key.push_str(part);
+        }
+        key
+    }
+
+    fn rate_limit(
+        &self,
+        scope: &str,
+        key: &str,
+        limit: u32,
+        window: Duration,
+        now: DateTime<Utc>,
+    ) -> Option<bool> {
+        if limit == 0 {
+            return Some(true);
+        }
+        let window_secs = window.as_secs().max(1);
+        let slot = u64::try_from(now.timestamp()).unwrap_or(0) / window_secs;
+        let counter = self.key(&["rate", scope, key, &slot.to_string()]);
+        match self
+            .backend
+            .incr(&counter, Duration::from_secs(window_secs * 2))
+        {
+            Ok(count) => Some(count <= u64::from(limit)),
+            Err(e) => {
+                tracing::warn!(scope, "Shared rate limit unavailable: {e:#}");
+                None
+            }
+        }
+    }
+
+    fn cost_keys(&self, at: DateTime<Utc>) -> (String, String) {
+        (
+            self.key(&["cost", "day", &at.format("%Y-%m-%d").to_string()]),
+            self.key(&["cost", "month", &at.format("%Y-%m").to_string()]),
+        )
+    }
+
+    fn add_cost(&self, cost_usd: f64, at: DateTime<Utc>) {
+        if !(cost_usd.is_finite() && cost_usd > 0.0) {
+            return;
+        }
+        let (day, month) = self.cost_keys(at);
+        let added = self
+            .backend
+            .incr_float(&day, cost_usd, DAILY_COST_TTL)
+            .and_then(|_| self.backend.incr_float(&month, cost_usd, MONTHLY_COST_TTL));
+        if let Err(e) = added {
+            tracing::warn!("Failed to add cost to shared totals: {e:#}");
+        }
+    }
+
+    fn costs(&self, now: DateTime<Utc>) -> Option<(f64, f64)> {
+        let (day, month) = self.cost_keys(now);
+        let totals = self
+            .backend
+            .get_float(&day)
+            .and_then(|daily| Ok((daily, self.backend.get_float(&month)?)));
+        match totals {
+            Ok(totals) => Some(totals),
+            Err(e) => {
+                tracing::warn!("Shared cost totals unavailable: {e:#}");
+                None
+            }
+        }
+    }
+
+    fn hold_lease(&self, name: &str, ttl: Duration) -> bool {
+        match self
+            .backend
+            .try_lease(&self.key(&["lease", name]), &self.node_id, ttl)
+        {
+            Ok(held) => held,
+            Err(e) => {
+                tracing::warn!(lease = name, "Shared lease unavailable: {e:#}");
+                false
+            }
+        }
+    }
+
+    fn release_lease(&self, name: &str) {
+        if let Err(e) = self
+            .backend
+            .release_lease(&self.key(&["lease", name]), &self.node_id)
+        {
+            tracing::warn!(lease = name, "Failed to release shared lease: {e:#}");
+        }
+    }
+
+    fn lease_holder(&self, name: &str) -> Result<Option<String>> {
+        self.backend.lease_holder(&self.key(&["lease", name]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::collections::HashMap;
+
+    /// Single-process stand-in for a shared store; expiry is not simulated.
+    #[derive(Default)]
+    struct MemoryBackend {
+        counters: Mutex<HashMap<String, f64>>,
+        leases: Mutex<HashMap<String, String>>,
+    }
+
+    impl Backend for MemoryBackend {
+        fn incr(&self, key: &str, _ttl: Duration) -> Result<u64> {
+            let mut counters = self.counters.lock();
+            let value = counters.entry(key.to_string()).or_default();
+            *value += 1.0;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Ok(*value as u64)
+        }
+
+        fn incr_float(&self, key: &str, by: f64, _ttl: Duration) -> Result<f64> {
+            let mut counters = self.counters.lock();
+            let value = counters.entry(key.to_string()).or_default();
+            *value += by;
+            Ok(*value)
+        }
+
+        fn get_float(&self, key: &str) -> Result<f64> {
+            Ok(self.counters.lock().get(key).copied().unwrap_or_default())
+        }
+
+        fn try_lease(&self, key: &str, holder: &str, _ttl: Duration) -> Result<bool> {
+            let mut leases = self.leases.lock();
+            let current = leases
+                .entry(key.to_string())
+                .or_insert_with(|| holder.to_string());
+            Ok(current == holder)
+        }
+
+        fn lease_holder(&self, key: &str) -> Result<Option<String>> {
+            Ok(self.leases.lock().get(key).cloned())
+        }
+
+        fn release_lease(&self, key: &str, holder: &str) -> Result<()> {
+            let mut leases = self.leases.lock();
+            if leases.get(key).is_some_and(|current| current == holder) {
+                leases.remove(key);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn shared_rate_limit_counts_per_window_and_key() {
+        let coordinator = Coordinator::new(Box::<MemoryBackend>::default(), "zc:", "a".into());
+        let now = Utc::now();
+        let window = Duration::from_secs(60);
+        assert_eq!(
+            coordinator.rate_limit("pair", "ip-1", 2, window, now),
+            Some(true)
+        );
+        assert_eq!(
+            coordinator.rate_limit("pair", "ip-1", 2, window, now),
+            Some(true)
+        );
+        assert_eq!(
+            coordinator.rate_limit("pair", "ip-1", 2, window, now),
+            Some(false)
+        );
+        assert_eq!(
+            coordinator.rate_limit("pair", "ip-2", 2, window, now),
+            Some(true)
+        );
+        let next_window = now + chrono::Duration::seconds(60);
+        assert_eq!(
+            coordinator.rate_limit("pair", "ip-1", 2, window, next_window),
+            Some(true)
+        );
+        assert_eq!(coordinator.key(&["rate", "pair"]), "zc:rate:pair");
+    }
+
+    #[test]
+    fn costs_accumulate_in_daily_and_monthly_totals() {
+        let coordinator = Coordinator::new(Box::<MemoryBackend>::default(), "zc", "a".into());
+        let now = Utc::now();
+        coordinator.add_cost(0.25, now);
+        coordinator.add_cost(0.5, now);
+        coordinator.add_cost(f64::NAN, now);
+        let (daily, monthly) = coordinator.costs(now).unwrap();
+        assert!((daily - 0.75).abs() < 1e-9);
+        assert!((monthly - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn only_one_node_holds_a_lease() {
+        let backend = Arc::new(MemoryBackend::default());
+        struct Shared(Arc<MemoryBackend>);
+        impl Backend for Shared {
+            fn incr(&self, key: &str, ttl: Duration) -> Result<u64> {
+                self.0.incr(key, ttl)
+            }
+            fn incr_float(&self, key: &str, by: f64, ttl: Duration) -> Result<f64> {
+                self.0.incr_float(key, by, ttl)
+            }
+            fn get_float(&self, key: &str) -> Result<f64> {
+                self.0.get_float(key)
+            }
+            fn try_lease(&self, key: &str, holder: &str, ttl: Duration) -> Result<bool> {
+                self.0.try_lease(key, holder, ttl)
+            }
+            fn lease_holder(&self, key: &str) -> Result<Option<String>> {
+                self.0.lease_holder(key)
+            }
+            fn release_lease(&self, key: &str, holder: &str) -> Result<()> {
+                self.0.release_lease(key, holder)
+            }
+        }
+        let a = Coordinator::new(Box::new(Shared(Arc::clone(&backend))), "zc", "a".into());
+        let b = Coordinator::new(Box::new(Shared(backend)), "zc", "b".into());
+        let ttl = Duration::from_secs(30);
+        assert!(a.hold_lease("cron", ttl));
+        assert!(!b.hold_lease("cron", ttl));
+        assert!(a.hold_lease("cron", ttl));
+        assert_eq!(b.lease_holder("cron").unwrap().as_deref(), Some("a"));
+
+        // A standby cannot release the leader's lease, but the leader can hand it over.
+        b.release_lease("cron");
+        assert!(!b.hold_lease("cron", ttl));
+        a.release_lease("cron");
+        assert!(b.hold_lease("cron", ttl));
+        assert_eq!(a.lease_holder("cron").unwrap().as_deref(), Some("b"));
+    }
+}
@@ -0,0 +1,169 @@
+//! PostgreSQL backend for shared coordination (`--features coordination-postgres`).
+//!
+//! Counters and leases are rows of one table keyed by the full (prefixed)
+//! key, so daemons can share a database with other data.
+
+use super::Backend;
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use postgres::{Client, NoTls};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS zeroclaw_coordination (
+    key TEXT PRIMARY KEY,
+    holder TEXT,
+    value DOUBLE PRECISION NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_zeroclaw_coordination_expires_at
+    ON zeroclaw_coordination(expires_at);
+";
+
+/// Add to a counter, starting over once it has expired.
+const INCR_SQL: &str = "
+INSERT INTO zeroclaw_coordination AS t (key, value, expires_at)
+VALUES ($1, $2, now() + make_interval(secs => $3))
+ON CONFLICT (key) DO UPDATE SET
+    value = CASE WHEN t.expires_at < now() THEN EXCLUDED.value ELSE t.value + EXCLUDED.value END,
+    expires_at = EXCLUDED.expires_at
+RETURNING value
+";
+
+/// Take the lease when free, expired or already ours; returns a row only then.
+const LEASE_SQL: &str = "
+INSERT INTO zeroclaw_coordination AS t (key, holder, expires_at)
+VALUES ($1, $2, now() + make_interval(secs => $3))
+ON CONFLICT (key) DO UPDATE SET
+    holder = EXCLUDED.holder,
+    expires_at = EXCLUDED.expires_at
+WHERE t.holder = EXCLUDED.holder OR t.expires_at < now()
+RETURNING holder
+";
+
+/// Expired rows are swept after this many counter updates.
+const SWEEP_EVERY: u64 = 1000;
+
+/// One lazily (re)connected PostgreSQL client with a statement timeout.
+///
+/// The `postgres` client drives its own runtime, so every call runs on a
+/// short-lived OS thread rather than on the caller's async worker.
+pub struct PostgresBackend {
+    config: postgres::Config,
+    client: Mutex<Option<Client>>,
+    timeout: Duration,
+    writes: AtomicU64,
+}
+
+impl PostgresBackend {
+    pub fn new(url: &str, timeout: Duration) -> Result<Self> {
+        let mut config: postgres::Config =
+            url.parse().context("Invalid coordination.postgres_url")?;
+        config.connect_timeout(timeout);
+        Ok(Self {
+            config,
+            client: Mutex::new(None),
+            timeout,
+            writes: AtomicU64::new(0),
+        })
+    }
+
+    fn connect(&self) -> Result<Client> {
+        let mut client = self
+            .config
+            .connect(NoTls)
+            .context("Failed to connect to PostgreSQL")?;
+        client.batch_execute(&format!(
+            "SET statement_timeout = {}",
+            self.timeout.as_millis()
+        ))?;
+        client.batch_execute(SCHEMA)?;
+        Ok(client)
+    }
+
+    /// Run `f` on the shared client; a failed call drops the client so the
+    /// next one reconnects.
+    fn with_client<T: Send>(
+        &self,
+        f: impl FnOnce(&mut Client) -> Result<T, postgres::Error> + Send,
+    ) -> Result<T> {
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let mut guard = self.client.lock();
+                    if guard.is_none() {
+                        *guard = Some(self.connect()?);
+                    }
+                    let Some(client) = guard.as_mut() else {
+                        unreachable!("client was just connected");
+                    };
+                    f(client).map_err(|e| {
+                        *guard = None;
+                        anyhow::Error::new(e).context("PostgreSQL coordination query failed")
+                    })
+                })
+                .join()
+                .map_err(|_| anyhow::anyhow!("PostgreSQL coordination thread panicked"))?
+        })
+    }
+}
+
+impl Backend for PostgresBackend {
+    fn incr(&self, key: &str, ttl: Duration) -> Result<u64> {
+        let total = self.incr_float(key, 1.0, ttl)?;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(total.max(0.0) as u64)
+    }
+
+    fn incr_float(&self, key: &str, by: f64, ttl: Duration) -> Result<f64> {
+        let sweep = self.writes.fetch_add(1, Ordering::Relaxed) % SWEEP_EVERY == 0;
+        self.with_client(|client| {
+            if sweep {
+                client.execute(
+                    "DELETE FROM zeroclaw_coordination WHERE expires_at < now()",
+                    &[],
+                )?;
+            }
+            let row = client.query_one(INCR_SQL, &[&key, &by, &ttl.as_secs_f64()])?;
+            Ok(row.get(0))
+        })
+    }
+
+    fn get_float(&self, key: &str) -> Result<f64> {
+        self.with_client(|client| {
+            let row = client.query_opt(
+                "SELECT value FROM zeroclaw_coordination WHERE key = $1 AND expires_at > now()",
+                &[&key],
+            )?;
+            Ok(row.map_or(0.0, |row| row.get(0)))
+        })
+    }
+
+    fn try_lease(&self, key: &str, holder: &str, ttl: Duration) -> Result<bool> {
+        self.with_client(|client| {
+            let row = client.query_opt(LEASE_SQL, &[&key, &holder, &ttl.as_secs_f64()])?;
+            Ok(row.is_some())
+        })
+    }
+
+    fn lease_holder(&self, key: &str) -> Result<Option<String>> {
+        self.with_client(|client| {
+            let row = client.query_opt(
+                "SELECT holder FROM zeroclaw_coordination WHERE key = $1 AND expires_at > now()",
+                &[&key],
+            )?;
+            Ok(row.and_then(|row| row.get(0)))
+        })
+    }
+
+    fn release_lease(&self, key: &str, holder: &str) -> Result<()> {
+        self.with_client(|client| {
+            client.execute(
+                "DELETE FROM zeroclaw_coordination WHERE key = $1 AND holder = $2",
+                &[&key, &holder],
+            )?;
+            Ok(())
+        })
+    }
+}
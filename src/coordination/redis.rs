@@ -0,0 +1,139 @@
+//! Redis backend for shared coordination (`--features coordination-redis`).
+
+use super::Backend;
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use std::time::Duration;
+
+/// Take the lease when free or already ours, and (re)set its expiry.
+const LEASE_SCRIPT: &str = r"
+local holder = redis.call('GET', KEYS[1])
+if holder == false or holder == ARGV[1] then
+    redis.call('SET', KEYS[1], ARGV[1], 'PX', ARGV[2])
+    return 1
+end
+return 0
+";
+
+/// Delete the lease only while `ARGV[1]` still holds it.
+const RELEASE_SCRIPT: &str = r"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    redis.call('DEL', KEYS[1])
+end
+return 0
+";
+
+/// One lazily (re)connected Redis connection with bounded I/O timeouts.
+pub struct RedisBackend {
+    client: redis::Client,
+    connection: Mutex<Option<redis::Connection>>,
+    timeout: Duration,
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+impl RedisBackend {
+    pub fn new(url: &str, timeout: Duration) -> Result<Self> {
+        let client = redis::Client::open(url).context("Invalid coordination.redis_url")?;
+        Ok(Self {
+            client,
+            connection: Mutex::new(None),
+            timeout,
+        })
+    }
+
+    /// Run `f` on the shared connection; a failed call drops the connection
+    /// so the next one reconnects.
+    fn with_connection<T>(
+        &self,
+        f: impl FnOnce(&mut redis::Connection) -> redis::RedisResult<T>,
+    ) -> Result<T> {
+        let mut guard = self.connection.lock();
+        if guard.is_none() {
+            let connection = self
+                .client
+                .get_connection_with_timeout(self.timeout)
+                .context("Failed to connect to Redis")?;
+            connection.set_read_timeout(Some(self.timeout))?;
+            connection.set_write_timeout(Some(self.timeout))?;
+            *guard = Some(connection);
+        }
+        let Some(connection) = guard.as_mut() else {
+            unreachable!("connection was just established");
+        };
+        f(connection).map_err(|e| {
+            *guard = None;
+            anyhow::Error::new(e).context("Redis command failed")
+        })
+    }
+}
+
+impl Backend for RedisBackend {
+    fn incr(&self, key: &str, ttl: Duration) -> Result<u64> {
+        self.with_connection(|connection| {
+            let (count,): (u64,) = redis::pipe()
+                .atomic()
+                .cmd("INCR")
+                .arg(key)
+                .cmd("PEXPIRE")
+                .arg(key)
+                .arg(millis(ttl))
+                .ignore()
+                .query(connection)?;
+            Ok(count)
+        })
+    }
+
+    fn incr_float(&self, key: &str, by: f64, ttl: Duration) -> Result<f64> {
+        self.with_connection(|connection| {
+            let (total,): (f64,) = redis::pipe()
+                .atomic()
+                .cmd("INCRBYFLOAT")
+                .arg(key)
+                .arg(by)
+                .cmd("PEXPIRE")
+                .arg(key)
+                .arg(millis(ttl))
+                .ignore()
+                .query(connection)?;
+            Ok(total)
+        })
+    }
+
+    fn get_float(&self, key: &str) -> Result<f64> {
+        self.with_connection(|connection| {
+            let value: Option<f64> = redis::cmd("GET").arg(key).query(connection)?;
+            Ok(value.unwrap_or_default())
+        })
+    }
+
+    fn try_lease(&self, key: &str, holder: &str, ttl: Duration) -> Result<bool> {
+        self.with_connection(|connection| {
+            let taken: i64 = redis::cmd("EVAL")
+                .arg(LEASE_SCRIPT)
+                .arg(1)
+                .arg(key)
+                .arg(holder)
+                .arg(millis(ttl))
+                .query(connection)?;
+            Ok(taken == 1)
+        })
+    }
+
+    fn lease_holder(&self, key: &str) -> Result<Option<String>> {
+        self.with_connection(|connection| redis::cmd("GET").arg(key).query(connection))
+    }
+
+    fn release_lease(&self, key: &str, holder: &str) -> Result<()> {
+        self.with_connection(|connection| {
+            redis::cmd("EVAL")
+                .arg(RELEASE_SCRIPT)
+                .arg(1)
+                .arg(key)
+                .arg(holder)
+                .exec(connection)
+        })
+    }
+}
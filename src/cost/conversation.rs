@@ -0,0 +1,173 @@
+//! Per-conversation cost display (`[cost.display]`).
+//!
+//! While a conversation's turn runs inside [`scope`], every priced response
+//! the agent loop writes to the cost ledger is tagged with the conversation
+//! (the interactive CLI session, or a channel sender). After the reply,
+//! interactive `zeroclaw agent` prints a footer and channels can append a
+//! suffix with that reply's usage and the conversation's running total.
+
+use super::ConversationCost;
+use crate::config::{Config, CostDisplayConfig};
+use crate::i18n;
+use parking_lot::RwLock;
+use std::future::Future;
+use std::sync::LazyLock;
+
+tokio::task_local! {
+    static CONVERSATION: String;
+}
+
+static DISPLAY: LazyLock<RwLock<CostDisplayConfig>> =
+    LazyLock::new(|| RwLock::new(CostDisplayConfig::default()));
+
+/// Apply `[cost.display]` from config.
+pub fn init_from_config(config: &Config) {
+    *DISPLAY.write() = config.cost.display.clone();
+}
+
+/// Run `future` with its provider usage recorded under `conversation`.
+pub async fn scope<F: Future>(conversation: String, future: F) -> F::Output {
+    CONVERSATION.scope(conversation, future).await
+}
+
+/// The conversation in scope for the current task, if any.
+pub fn current() -> Option<String> {
+    CONVERSATION.try_with(Clone::clone).ok()
+}
+
+/// Totals of `conversation` so far, or `None` when the cost ledger is off
+/// (`[cost].enabled = false`).
+pub fn totals(conversation: &str) -> Option<ConversationCost> {
+    let ledger = super::downgrade::ledger()?;
+    ledger
+        .tracker
+        .conversation_cost(conversation)
+        .map_err(|e| tracing::warn!("Cannot read conversation cost: {e:#}"))
+        .ok()
+}
+
+/// Footer for an interactive reply, given the totals from before it.
+pub fn cli_footer(conversation: &str, before: Option<ConversationCost>) -> Option<String> {
+    if !DISPLAY.read().cli_footer {
+        return None;
+    }
+    let after = totals(conversation)?;
+    format_cli_footer(&after.since(&before.unwrap_or_default()), &after)
+}
+
+/// Suffix for a reply on `channel`, given the totals from before it.
+pub fn channel_suffix(
+    channel: &str,
+    conversation: &str,
+    before: Option<ConversationCost>,
+) -> Option<String> {
+    {
+        let display = DISPLAY.read();
+        let listed = display.channels.is_empty()
+            || display
+                .channels
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(channel));
+        if !display.channel_suffix || !listed {
+            return None;
+        }
+    }
+    let after = totals(conversation)?;
+    format_channel_suffix(&after.since(&before.unwrap_or_default()), &after)
+}
+
+/// Nothing is shown until the conversation has priced usage, so unpriced
+/// models do not print a misleading `$0`.
+fn format_cli_footer(reply: &ConversationCost, total: &ConversationCost) -> Option<String> {
+    (total.requests > 0).then(|| {
+        i18n::tr_args(
+            "cli.cost_footer",
+            &[
+                ("reply_cost", &format_usd(reply.cost_usd)),
+                ("reply_tokens", &format_tokens(reply.total_tokens())),
+                ("total_cost", &format_usd(total.cost_usd)),
+                ("total_tokens", &format_tokens(total.total_tokens())),
+            ],
+        )
+    })
+}
+
+fn format_channel_suffix(reply: &ConversationCost, total: &ConversationCost) -> Option<String> {
+    (reply.requests > 0).then(|| {
+        i18n::tr_args(
+            "channel.cost_suffix",
+            &[
+                ("reply", &format_usd(reply.cost_usd)),
+                ("total", &format_usd(total.cost_usd)),
+            ],
+        )
+    })
+}
+
+/// Sub-dollar amounts keep four decimals so single replies are not `$0.00`.
+fn format_usd(amount: f64) -> String {
+    if amount < 1.0 {
+        format!("${amount:.4}")
+    } else {
+        format!("${amount:.2}")
+    }
+}
+
+fn format_tokens(count: u64) -> String {
+    let digits = count.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cost(requests: usize, tokens: u64, cost_usd: f64) -> ConversationCost {
+        ConversationCost {
+            requests,
+            input_tokens: tokens,
+            output_tokens: 0,
+            cost_usd,
+        }
+    }
+
+    #[test]
+    fn amounts_and_token_counts_are_readable() {
+        assert_eq!(format_usd(0.00412), "$0.0041");
+        assert_eq!(format_usd(12.345), "$12.35");
+        assert_eq!(format_tokens(0), "0");
+        assert_eq!(format_tokens(999), "999");
+        assert_eq!(format_tokens(1_234_567), "1,234,567");
+    }
+
+    #[test]
+    fn footer_and_suffix_need_priced_usage() {
+        let total = cost(3, 5_678, 0.019);
+        let reply = total.since(&cost(2, 4_444, 0.0149));
+
+        let footer = format_cli_footer(&reply, &total).unwrap();
+        assert!(footer.contains("$0.0041"), "{footer}");
+        assert!(footer.contains("1,234"), "{footer}");
+        assert!(footer.contains("$0.0190"), "{footer}");
+        let suffix = format_channel_suffix(&reply, &total).unwrap();
+        assert!(suffix.contains("$0.0041") && suffix.contains("$0.0190"));
+
+        let none = ConversationCost::default();
+        assert!(format_cli_footer(&none, &none).is_none());
+        assert!(format_channel_suffix(&none, &total).is_none());
+    }
+
+    #[tokio::test]
+    async fn scope_names_the_current_conversation() {
+        assert_eq!(current(), None);
+        let inner = scope("telegram_alice".into(), async { current() }).await;
+        assert_eq!(inner.as_deref(), Some("telegram_alice"));
+    }
+}
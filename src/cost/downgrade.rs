@@ -0,0 +1,251 @@
+//! Cheaper models for background work as the daily budget runs low
+//! (`[cost.downgrade]`).
+//!
+//! Agent responses are priced with `[cost].prices` and written to the cost
+//! ledger ([`record_response`]). Before a cron job, heartbeat task or camera
+//! watch starts, [`background_overrides`] compares today's spend with
+//! `[cost].daily_limit_usd`: past a tier's `at_percent` the run uses that
+//! tier's model, and the owner is told once per tier per day.
+
+use super::{CostTracker, TokenUsage};
+use crate::config::{Config, CostConfig, CostDowngradeTier};
+use crate::observability::runtime_trace;
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+
+pub(super) struct Ledger {
+    pub(super) config: CostConfig,
+    pub(super) tracker: CostTracker,
+}
+
+static LEDGER: LazyLock<RwLock<Option<Arc<Ledger>>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Highest tier announced today, so each switch is reported once.
+static ANNOUNCED: LazyLock<Mutex<Announced>> = LazyLock::new(|| Mutex::new(Announced::default()));
+
+/// Open (or close) the process-wide cost ledger used by the agent loop.
+pub fn init_from_config(config: &Config) {
+    let ledger = if config.cost.enabled {
+        CostTracker::new(config.cost.clone(), &config.workspace_dir)
+            .map_err(|e| tracing::warn!("Cost tracking disabled: {e:#}"))
+            .ok()
+            .map(|tracker| {
+                Arc::new(Ledger {
+                    config: config.cost.clone(),
+                    tracker,
+                })
+            })
+    } else {
+        None
+    };
+    *LEDGER.write().unwrap_or_else(|e| e.into_inner()) = ledger;
+}
+
+pub(super) fn ledger() -> Option<Arc<Ledger>> {
+    LEDGER.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Record the usage of one provider response, under the conversation in
+/// [`super::conversation::scope`] if any. Unpriced models are skipped.
+pub fn record_response(
+    provider: &str,
+    model: &str,
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+) {
+    let Some(ledger) = ledger() else {
+        return;
+    };
+    if input_tokens.is_none() && output_tokens.is_none() {
+        return;
+    }
+    let Some(pricing) = runtime_trace::lookup_pricing(&ledger.config.prices, provider, model)
+    else {
+        return;
+    };
+    let usage = TokenUsage::new(
+        model,
+        input_tokens.unwrap_or(0),
+        output_tokens.unwrap_or(0),
+        pricing.input,
+        pricing.output,
+    );
+    let recorded = match super::conversation::current() {
+        Some(conversation) => ledger
+            .tracker
+            .record_conversation_usage(&conversation, usage),
+        None => ledger.tracker.record_usage(usage),
+    };
+    if let Err(e) = recorded {
+        tracing::warn!("Failed to record cost usage: {e:#}");
+    }
+}
+
+/// The tier in force at `spent_usd` of today's limit, if any.
+pub fn active_tier(config: &CostConfig, spent_usd: f64) -> Option<&CostDowngradeTier> {
+    let downgrade = &config.downgrade;
+    if !downgrade.enabled || config.daily_limit_usd <= 0.0 {
+        return None;
+    }
+    let percent = spent_usd / config.daily_limit_usd * 100.0;
+    downgrade
+        .tiers
+        .iter()
+        .filter(|tier| f64::from(tier.at_percent) <= percent)
+        .max_by_key(|tier| tier.at_percent)
+}
+
+#[derive(Default)]
+struct Announced {
+    date: Option<NaiveDate>,
+    at_percent: u8,
+}
+
+impl Announced {
+    /// Whether `at_percent` is a higher tier than any announced on `date`.
+    fn first(&mut self, date: NaiveDate, at_percent: u8) -> bool {
+        if self.date != Some(date) {
+            self.date = Some(date);
+            self.at_percent = 0;
+        }
+        if at_percent <= self.at_percent {
+            return false;
+        }
+        self.at_percent = at_percent;
+        true
+    }
+}
+
+/// Provider and model overrides for a background run, given the model it
+/// would otherwise use. `workload` names the run in logs (e.g. `"heartbeat"`).
+pub async fn background_overrides(
+    config: &Config,
+    workload: &str,
+    model: Option<String>,
+) -> (Option<String>, Option<String>) {
+    let Some(ledger) = ledger().filter(|ledger| ledger.config.downgrade.enabled) else {
+        return (None, model);
+    };
+    let spent = match ledger.tracker.get_summary() {
+        Ok(summary) => summary.daily_cost_usd,
+        Err(e) => {
+            tracing::warn!("Cannot read today's spend for the model downgrade: {e:#}");
+            return (None, model);
+        }
+    };
+    let Some(tier) = active_tier(&ledger.config, spent) else {
+        return (None, model);
+    };
+    tracing::info!(
+        workload,
+        model = %tier.model,
+        spent_usd = spent,
+        "Budget downgrade: running background work on a cheaper model"
+    );
+
+    let announce = ledger.config.downgrade.notify
+        && ANNOUNCED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .first(Utc::now().date_naive(), tier.at_percent);
+    if announce {
+        let text = notice(&ledger.config, tier, spent);
+        if let Err(e) = notify(config, &text).await {
+            tracing::warn!("Failed to announce the model downgrade: {e:#}");
+        }
+    }
+    (tier.provider.clone(), Some(tier.model.clone()))
+}
+
+fn notice(config: &CostConfig, tier: &CostDowngradeTier, spent_usd: f64) -> String {
+    let model = match &tier.provider {
+        Some(provider) => format!("{provider}/{}", tier.model),
+        None => tier.model.clone(),
+    };
+    format!(
+        "Today's spend is ${spent_usd:.2} of the ${:.2} daily limit (past {}%). \
+         Cron jobs, heartbeat tasks and watches now use {model} until midnight UTC; \
+         conversations keep their model.",
+        config.daily_limit_usd, tier.at_percent
+    )
+}
+
+/// Channel and recipient that receive downgrade notices.
+pub fn route(config: &Config) -> Result<(String, String)> {
+    let downgrade = &config.cost.downgrade;
+    match (&downgrade.channel, &downgrade.to) {
+        (Some(channel), Some(to)) => Ok((channel.to_ascii_lowercase(), to.clone())),
+        _ => crate::questions::owner_route(config).context(
+            "Set [cost.downgrade].channel and .to (or configure the [questions] owner route)",
+        ),
+    }
+}
+
+async fn notify(config: &Config, text: &str) -> Result<()> {
+    let (channel, to) = route(config)?;
+    crate::cron::scheduler::send_announcement(config, &channel, &to, text).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CostDowngradeConfig;
+
+    fn config() -> CostConfig {
+        let tier = |at_percent, model: &str| CostDowngradeTier {
+            at_percent,
+            model: model.into(),
+            provider: None,
+        };
+        CostConfig {
+            enabled: true,
+            daily_limit_usd: 10.0,
+            downgrade: CostDowngradeConfig {
+                enabled: true,
+                tiers: vec![tier(90, "tiny"), tier(60, "small")],
+                ..CostDowngradeConfig::default()
+            },
+            ..CostConfig::default()
+        }
+    }
+
+    #[test]
+    fn highest_crossed_tier_applies() {
+        let config = config();
+        assert!(active_tier(&config, 5.99).is_none());
+        assert_eq!(active_tier(&config, 6.0).unwrap().model, "small");
+        assert_eq!(active_tier(&config, 9.5).unwrap().model, "tiny");
+        assert_eq!(active_tier(&config, 25.0).unwrap().model, "tiny");
+
+        let mut disabled = config;
+        disabled.downgrade.enabled = false;
+        assert!(active_tier(&disabled, 9.5).is_none());
+    }
+
+    #[test]
+    fn each_tier_is_announced_once_per_day() {
+        let mut announced = Announced::default();
+        let day = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        assert!(announced.first(day, 60));
+        assert!(!announced.first(day, 60));
+        assert!(announced.first(day, 90));
+        assert!(!announced.first(day, 60));
+
+        let next = day.succ_opt().unwrap();
+        assert!(announced.first(next, 60));
+    }
+
+    #[test]
+    fn notice_names_provider_and_limit() {
+        let config = config();
+        let tier = CostDowngradeTier {
+            at_percent: 90,
+            model: "llama3.2".into(),
+            provider: Some("ollama".into()),
+        };
+        let text = notice(&config, &tier, 9.25);
+        assert!(text.contains("$9.25 of the $10.00"));
+        assert!(text.contains("ollama/llama3.2"));
+    }
+}
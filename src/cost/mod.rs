@@ -1,3 +1,5 @@
+pub mod conversation;
+pub mod downgrade;
 pub mod tracker;
 pub mod types;
 
@@ -5,4 +7,6 @@ pub mod types;
 #[allow(unused_imports)]
 pub use tracker::CostTracker;
 #[allow(unused_imports)]
-pub use types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
+pub use types::{
+    BudgetCheck, ConversationCost, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod,
+};
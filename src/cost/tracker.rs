@@ -1,7 +1,9 @@
-use super::types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
+use super::types::{
+    BudgetCheck, ConversationCost, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod,
+};
 use crate::config::schema::CostConfig;
 use anyhow::{anyhow, Context, Result};
-use chrono::{Datelike, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use parking_lot::{Mutex, MutexGuard};
 use std::collections::HashMap;
 use std::fs::{self, File, OpenOptions};
@@ -15,6 +17,9 @@ pub struct CostTracker {
     storage: Arc<Mutex<CostStorage>>,
     session_id: String,
     session_costs: Arc<Mutex<Vec<CostRecord>>>,
+    /// Totals of the conversations asked about so far, kept current as
+    /// their usage is recorded.
+    conversations: Arc<Mutex<HashMap<String, ConversationCost>>>,
 }
 
 impl CostTracker {
@@ -31,9 +36,19 @@ impl CostTracker {
             storage: Arc::new(Mutex::new(storage)),
             session_id: uuid::Uuid::new_v4().to_string(),
             session_costs: Arc::new(Mutex::new(Vec::new())),
+            conversations: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 
+    /// Daily and monthly totals, shared across daemons when `[coordination]`
+    /// provides them.
+    fn aggregated_costs(&self) -> Result<(f64, f64)> {
+        if let Some(shared) = crate::coordination::shared_costs(Utc::now()) {
+            return Ok(shared);
+        }
+        self.lock_storage().get_aggregated_costs()
+    }
+
     /// Get the session ID.
     pub fn session_id(&self) -> &str {
         &self.session_id
@@ -59,8 +74,7 @@ impl CostTracker {
             ));
         }
 
-        let mut storage = self.lock_storage();
-        let (daily_cost, monthly_cost) = storage.get_aggregated_costs()?;
+        let (daily_cost, monthly_cost) = self.aggregated_costs()?;
 
         // Check daily limit
         let projected_daily = daily_cost + estimated_cost_usd;
@@ -108,6 +122,15 @@ impl CostTracker {
 
     /// Record a usage event.
     pub fn record_usage(&self, usage: TokenUsage) -> Result<()> {
+        self.record(usage, None)
+    }
+
+    /// Record a usage event made for `conversation`.
+    pub fn record_conversation_usage(&self, conversation: &str, usage: TokenUsage) -> Result<()> {
+        self.record(usage, Some(conversation))
+    }
+
+    fn record(&self, usage: TokenUsage, conversation: Option<&str>) -> Result<()> {
         if !self.config.enabled {
             return Ok(());
         }
@@ -118,13 +141,22 @@ impl CostTracker {
             ));
         }
 
-        let record = CostRecord::new(&self.session_id, usage);
+        let mut record = CostRecord::new(&self.session_id, usage);
+        record.conversation = conversation.map(str::to_string);
 
         // Persist first for durability guarantees.
         {
             let mut storage = self.lock_storage();
             storage.add_record(record.clone())?;
+            // Updated under the storage lock so a concurrent first read of
+            // the conversation counts this record exactly once.
+            if let Some(conversation) = conversation {
+                if let Some(totals) = self.conversations.lock().get_mut(conversation) {
+                    totals.add(&record.usage);
+                }
+            }
         }
+        crate::coordination::add_cost(record.usage.cost_usd, record.usage.timestamp);
 
         // Then update in-memory session snapshot.
         let mut session_costs = self.lock_session_costs();
@@ -135,10 +167,7 @@ impl CostTracker {
 
     /// Get the current cost summary.
     pub fn get_summary(&self) -> Result<CostSummary> {
-        let (daily_cost, monthly_cost) = {
-            let mut storage = self.lock_storage();
-            storage.get_aggregated_costs()?
-        };
+        let (daily_cost, monthly_cost) = self.aggregated_costs()?;
 
         let session_costs = self.lock_session_costs();
         let session_cost: f64 = session_costs
@@ -162,6 +191,26 @@ impl CostTracker {
         })
     }
 
+    /// Totals of `conversation`, read from the ledger the first time and
+    /// kept in memory after that.
+    pub fn conversation_cost(&self, conversation: &str) -> Result<ConversationCost> {
+        if let Some(totals) = self.conversations.lock().get(conversation) {
+            return Ok(*totals);
+        }
+        let storage = self.lock_storage();
+        let mut totals = ConversationCost::default();
+        storage.for_each_record(|record| {
+            if record.conversation.as_deref() == Some(conversation) {
+                totals.add(&record.usage);
+            }
+        })?;
+        Ok(*self
+            .conversations
+            .lock()
+            .entry(conversation.to_string())
+            .or_insert(totals))
+    }
+
     /// Get the daily cost for a specific date.
     pub fn get_daily_cost(&self, date: NaiveDate) -> Result<f64> {
         let storage = self.lock_storage();
@@ -173,6 +222,22 @@ impl CostTracker {
         let storage = self.lock_storage();
         storage.get_cost_for_month(year, month)
     }
+
+    /// Get persisted usage records with a timestamp in `[since, until)`.
+    pub fn get_records_between(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<CostRecord>> {
+        let storage = self.lock_storage();
+        let mut records = Vec::new();
+        storage.for_each_record(|record| {
+            if record.usage.timestamp >= since && record.usage.timestamp < until {
+                records.push(record);
+            }
+        })?;
+        Ok(records)
+    }
 }
 
 fn resolve_storage_path(workspace_dir: &Path) -> Result<PathBuf> {
@@ -447,6 +512,39 @@ mod tests {
         assert_eq!(summary.by_model.len(), 1);
     }
 
+    #[test]
+    fn conversation_totals_survive_a_restart() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+        tracker
+            .record_conversation_usage("telegram_alice", TokenUsage::new("m", 1000, 500, 1.0, 2.0))
+            .unwrap();
+        tracker
+            .record_usage(TokenUsage::new("m", 9000, 9000, 1.0, 2.0))
+            .unwrap();
+
+        let first = tracker.conversation_cost("telegram_alice").unwrap();
+        assert_eq!(first.requests, 1);
+        assert_eq!(first.total_tokens(), 1500);
+
+        tracker
+            .record_conversation_usage("telegram_alice", TokenUsage::new("m", 100, 50, 1.0, 2.0))
+            .unwrap();
+        let cached = tracker.conversation_cost("telegram_alice").unwrap();
+        assert_eq!(cached.requests, 2);
+        assert_eq!(cached.since(&first).total_tokens(), 150);
+
+        let reopened = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+        assert_eq!(
+            reopened.conversation_cost("telegram_alice").unwrap(),
+            cached
+        );
+        assert_eq!(
+            reopened.conversation_cost("cli:other").unwrap(),
+            ConversationCost::default()
+        );
+    }
+
     #[test]
     fn budget_exceeded_daily_limit() {
         let tmp = TempDir::new().unwrap();
@@ -77,6 +77,9 @@ pub struct CostRecord {
     pub usage: TokenUsage,
     /// Session identifier (for grouping)
     pub session_id: String,
+    /// Conversation the call was made for (CLI session or channel sender)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversation: Option<String>,
 }
 
 impl CostRecord {
@@ -86,6 +89,44 @@ impl CostRecord {
             id: uuid::Uuid::new_v4().to_string(),
             usage,
             session_id: session_id.into(),
+            conversation: None,
+        }
+    }
+}
+
+/// Running totals of one conversation.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ConversationCost {
+    /// Number of priced provider calls
+    pub requests: usize,
+    /// Input/prompt tokens
+    pub input_tokens: u64,
+    /// Output/completion tokens
+    pub output_tokens: u64,
+    /// Cost in USD
+    pub cost_usd: f64,
+}
+
+impl ConversationCost {
+    /// Add one provider call.
+    pub fn add(&mut self, usage: &TokenUsage) {
+        self.requests += 1;
+        self.input_tokens = self.input_tokens.saturating_add(usage.input_tokens);
+        self.output_tokens = self.output_tokens.saturating_add(usage.output_tokens);
+        self.cost_usd += usage.cost_usd;
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens.saturating_add(self.output_tokens)
+    }
+
+    /// Usage added since `earlier`, a snapshot of the same conversation.
+    pub fn since(&self, earlier: &Self) -> Self {
+        Self {
+            requests: self.requests.saturating_sub(earlier.requests),
+            input_tokens: self.input_tokens.saturating_sub(earlier.input_tokens),
+            output_tokens: self.output_tokens.saturating_sub(earlier.output_tokens),
+            cost_usd: (self.cost_usd - earlier.cost_usd).max(0.0),
         }
     }
 }
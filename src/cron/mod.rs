@@ -1,4 +1,5 @@
 use crate::config::Config;
+use crate::datetime::UserClock;
 use crate::security::SecurityPolicy;
 use anyhow::{bail, Result};
 
@@ -7,6 +8,7 @@ mod store;
 mod types;
 
 pub mod scheduler;
+pub mod timeline;
 
 #[allow(unused_imports)]
 pub use schedule::{
@@ -17,7 +19,9 @@ pub use store::{
     add_agent_job, add_job, add_shell_job, due_jobs, get_job, list_jobs, list_runs,
     record_last_run, record_run, remove_job, reschedule_after_run, update_job,
 };
-pub use types::{CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget};
+pub use types::{
+    CronJob, CronJobPatch, CronRun, DeliveryConfig, JobContext, JobType, Schedule, SessionTarget,
+};
 
 #[allow(clippy::needless_pass_by_value)]
 pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<()> {
@@ -36,7 +40,7 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 let last_run = job
                     .last_run
                     .map_or_else(|| "never".into(), |d| d.to_rfc3339());
-                let last_status = job.last_status.unwrap_or_else(|| "n/a".into());
+                let last_status = job.last_status.as_deref().unwrap_or("n/a");
                 println!(
                     "- {} | {:?} | next={} | last={} ({})",
                     job.id,
@@ -51,6 +55,21 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 if let Some(prompt) = &job.prompt {
                     println!("    prompt: {prompt}");
                 }
+                if job.job_type == JobType::Agent {
+                    let model = scheduler::job_model(config, &job)
+                        .or_else(|| config.default_model.clone())
+                        .unwrap_or_else(|| "default".into());
+                    let memory = job.context.memory.unwrap_or(config.cron.default_memory);
+                    let tools = if job.context.tools.is_empty() {
+                        "all".to_string()
+                    } else {
+                        job.context.tools.join(",")
+                    };
+                    println!(
+                        "    model: {model} | memory: {} | tools: {tools}",
+                        memory.as_str()
+                    );
+                }
             }
             Ok(())
         }
@@ -59,6 +78,7 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             tz,
             command,
         } => {
+            let tz = tz.or_else(|| UserClock::from_config(&config.ui).default.iana_name());
             let schedule = Schedule::Cron {
                 expr: expression,
                 tz,
@@ -71,9 +91,14 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             Ok(())
         }
         crate::CronCommands::AddAt { at, command } => {
-            let at = chrono::DateTime::parse_from_rfc3339(&at)
-                .map_err(|e| anyhow::anyhow!("Invalid RFC3339 timestamp for --at: {e}"))?
-                .with_timezone(&chrono::Utc);
+            let at = UserClock::from_config(&config.ui)
+                .default
+                .resolve(&at, chrono::Utc::now())
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid --at value '{at}': expected RFC3339 or a phrase like 'tomorrow at 9'"
+                    )
+                })?;
             let schedule = Schedule::At { at };
             let job = add_shell_job(config, None, schedule, &command)?;
             println!("✅ Added one-shot cron job {}", job.id);
@@ -103,9 +128,22 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             tz,
             command,
             name,
+            model,
+            memory,
+            tools,
+            all_tools,
         } => {
-            if expression.is_none() && tz.is_none() && command.is_none() && name.is_none() {
-                bail!("At least one of --expression, --tz, --command, or --name must be provided");
+            let context_changed = memory.is_some() || !tools.is_empty() || all_tools;
+            if expression.is_none()
+                && tz.is_none()
+                && command.is_none()
+                && name.is_none()
+                && model.is_none()
+                && !context_changed
+            {
+                bail!(
+                    "At least one of --expression, --tz, --command, --name, --model, --memory, --tool, or --all-tools must be provided"
+                );
             }
 
             // Merge expression/tz with the existing schedule so that
@@ -135,10 +173,28 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 }
             }
 
+            let context = if context_changed {
+                let mut context = get_job(config, &id)?.context;
+                if let Some(memory) = memory {
+                    context.memory =
+                        Some(serde_json::from_value(serde_json::Value::String(memory))?);
+                }
+                if all_tools {
+                    context.tools.clear();
+                } else if !tools.is_empty() {
+                    context.tools = tools;
+                }
+                Some(context)
+            } else {
+                None
+            };
+
             let patch = CronJobPatch {
                 schedule,
                 command,
                 name,
+                model,
+                context,
                 ..CronJobPatch::default()
             };
 
@@ -160,6 +216,17 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             println!("▶️  Resumed cron job {id}");
             Ok(())
         }
+        crate::CronCommands::Timeline { next, json } => {
+            let window = timeline::parse_window(&next)?;
+            let forecast = timeline::build(config, chrono::Utc::now(), window)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&forecast)?);
+            } else {
+                let tz = UserClock::from_config(&config.ui).default;
+                print!("{}", timeline::render(&forecast, tz));
+            }
+            Ok(())
+        }
     }
 }
 
@@ -264,11 +331,69 @@ mod tests {
                 tz: tz.map(Into::into),
                 command: command.map(Into::into),
                 name: name.map(Into::into),
+                model: None,
+                memory: None,
+                tools: vec![],
+                all_tools: false,
             },
             config,
         )
     }
 
+    #[test]
+    fn update_sets_agent_job_context_via_handler() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = add_agent_job(
+            &config,
+            None,
+            Schedule::Cron {
+                expr: "0 3 * * *".into(),
+                tz: None,
+            },
+            "Rotate logs",
+            SessionTarget::Isolated,
+            None,
+            JobContext::default(),
+            None,
+            false,
+        )
+        .unwrap();
+
+        let update = |memory: Option<&str>, tools: Vec<String>, all_tools: bool| {
+            handle_command(
+                crate::CronCommands::Update {
+                    id: job.id.clone(),
+                    expression: None,
+                    tz: None,
+                    command: None,
+                    name: None,
+                    model: Some("cheap/model".into()),
+                    memory: memory.map(Into::into),
+                    tools,
+                    all_tools,
+                },
+                &config,
+            )
+        };
+        update(Some("isolated"), vec!["shell".into()], false).unwrap();
+        let updated = get_job(&config, &job.id).unwrap();
+        assert_eq!(updated.model.as_deref(), Some("cheap/model"));
+        assert_eq!(
+            updated.context.memory,
+            Some(crate::config::CronMemoryScope::Isolated)
+        );
+        assert_eq!(updated.context.tools, vec!["shell".to_string()]);
+
+        update(None, vec![], true).unwrap();
+        let updated = get_job(&config, &job.id).unwrap();
+        assert!(updated.context.tools.is_empty());
+        assert_eq!(
+            updated.context.memory,
+            Some(crate::config::CronMemoryScope::Isolated)
+        );
+    }
+
     #[test]
     fn update_changes_command_via_handler() {
         let tmp = TempDir::new().unwrap();
@@ -1,11 +1,13 @@
 use crate::channels::{
     Channel, DiscordChannel, MattermostChannel, SendMessage, SlackChannel, TelegramChannel,
 };
-use crate::config::Config;
+use crate::config::{Config, CronMemoryScope};
 use crate::cron::{
     due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
     update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
 };
+use crate::memory::scoped::{with_task_memory, TaskMemory};
+use crate::observability::runtime_trace;
 use crate::security::SecurityPolicy;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -30,11 +32,29 @@ pub async fn run(config: Config) -> Result<()> {
 
     crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
+    // With shared coordination only the lease holder runs jobs; the lease
+    // outlives a few missed polls before another daemon takes over.
+    let lease_ttl = Duration::from_secs(poll_secs.saturating_mul(3));
+    let mut leading = true;
+
     loop {
         interval.tick().await;
         // Keep scheduler liveness fresh even when there are no due jobs.
         crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
+        let holds_lease = crate::coordination::hold_lease("cron", lease_ttl);
+        if holds_lease != leading {
+            leading = holds_lease;
+            if leading {
+                tracing::info!("Scheduler took the cron lease; running due jobs on this node");
+            } else {
+                tracing::info!("Another node holds the cron lease; scheduler is standing by");
+            }
+        }
+        if !leading {
+            continue;
+        }
+
         let jobs = match due_jobs(&config, Utc::now()) {
             Ok(jobs) => jobs,
             Err(e) => {
@@ -127,14 +147,78 @@ async fn execute_and_persist_job(
     crate::health::mark_component_ok(component);
     warn_if_high_frequency_agent_job(job);
 
+    let job_type: &'static str = job.job_type.clone().into();
+    runtime_trace::record_event(
+        "cron_job_start",
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        serde_json::json!({
+            "job_id": job.id,
+            "name": job.name,
+            "job_type": job_type,
+        }),
+    );
+
     let started_at = Utc::now();
     let (success, output) = execute_job_with_retry(config, security, job).await;
     let finished_at = Utc::now();
     let success = persist_job_result(config, job, success, &output, started_at, finished_at).await;
 
+    runtime_trace::record_event(
+        "cron_job_result",
+        None,
+        None,
+        None,
+        None,
+        Some(success),
+        None,
+        serde_json::json!({
+            "job_id": job.id,
+            "name": job.name,
+            "job_type": job_type,
+            "duration_ms": (finished_at - started_at).num_milliseconds(),
+        }),
+    );
+
     (job.id.clone(), success)
 }
 
+/// Model an agent job runs on: its own, then `[cron] default_model`.
+pub(crate) fn job_model(config: &Config, job: &CronJob) -> Option<String> {
+    job.model
+        .clone()
+        .or_else(|| config.cron.default_model.clone())
+}
+
+/// Memory scope for an agent job; `None` leaves the owner's memory visible.
+fn job_memory(config: &Config, job: &CronJob) -> Option<TaskMemory> {
+    match job.context.memory.unwrap_or(config.cron.default_memory) {
+        CronMemoryScope::Shared => None,
+        CronMemoryScope::Isolated => Some(TaskMemory::Session(format!("cron:{}", job.id))),
+        CronMemoryScope::None => Some(TaskMemory::Disabled),
+    }
+}
+
+/// Tools an agent job may call, given a task template's list and the job's
+/// own. Empty allows every tool; `None` means the two lists do not overlap.
+fn job_tools(template: Vec<String>, job: &[String]) -> Option<Vec<String>> {
+    if job.is_empty() {
+        return Some(template);
+    }
+    if template.is_empty() {
+        return Some(job.to_vec());
+    }
+    let allowed: Vec<String> = template
+        .into_iter()
+        .filter(|tool| job.contains(tool))
+        .collect();
+    (!allowed.is_empty()).then_some(allowed)
+}
+
 async fn run_agent_job(
     config: &Config,
     security: &SecurityPolicy,
@@ -161,21 +245,58 @@ async fn run_agent_job(
         );
     }
     let name = job.name.clone().unwrap_or_else(|| "cron-job".to_string());
-    let prompt = job.prompt.clone().unwrap_or_default();
+    let mut prompt = job.prompt.clone().unwrap_or_default();
+    if let Some(invocation) = crate::reports::parse_invocation(&prompt) {
+        let result = match invocation {
+            Ok(report) => crate::reports::run(config, &report, None).await,
+            Err(e) => Err(e),
+        };
+        return match result {
+            Ok(summary) => (true, summary),
+            Err(e) => (false, format!("report job failed: {e:#}")),
+        };
+    }
+    let mut allowed_tools = Vec::new();
+    if let Some(expanded) = crate::tasks::expand_invocation(&config.workspace_dir, &prompt) {
+        match expanded {
+            Ok(task) => {
+                prompt = task.prompt;
+                allowed_tools = task.tools;
+            }
+            Err(e) => return (false, format!("agent job failed: {e}")),
+        }
+    }
+    let Some(allowed_tools) = job_tools(allowed_tools, &job.context.tools) else {
+        return (
+            false,
+            "agent job failed: the task template and job allow no tools in common".to_string(),
+        );
+    };
     let prefixed_prompt = format!("[cron:{} {name}] {prompt}", job.id);
-    let model_override = job.model.clone();
+    let (provider_override, model_override) = crate::cost::downgrade::background_overrides(
+        config,
+        &format!("cron:{}", job.id),
+        job_model(config, job),
+    )
+    .await;
 
     let run_result = match job.session_target {
         SessionTarget::Main | SessionTarget::Isolated => {
-            crate::agent::run(
-                config.clone(),
-                Some(prefixed_prompt),
-                None,
-                model_override,
-                config.default_temperature,
-                vec![],
-                false,
-            )
+            Box::pin(with_task_memory(
+                job_memory(config, job),
+                crate::agent::run(
+                    config.clone(),
+                    Some(prefixed_prompt),
+                    provider_override,
+                    model_override,
+                    config.default_temperature,
+                    vec![],
+                    false,
+                    false,
+                    allowed_tools,
+                    crate::handoff::CliHandoff::default(),
+                ),
+            ))
             .await
         }
     };
@@ -296,19 +417,54 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str) ->
         .as_deref()
         .ok_or_else(|| anyhow::anyhow!("delivery.to is required for announce mode"))?;
 
-    match channel.to_ascii_lowercase().as_str() {
+    send_announcement(config, channel, target, output).await
+}
+
+/// Channels that [`send_announcement`] can deliver to with the current config.
+pub fn announce_channels(config: &Config) -> Vec<&'static str> {
+    let channels = &config.channels_config;
+    let mut available = Vec::new();
+    if channels.telegram.is_some() {
+        available.push("telegram");
+    }
+    if channels.discord.is_some() {
+        available.push("discord");
+    }
+    if channels.slack.is_some() {
+        available.push("slack");
+    }
+    if channels.mattermost.is_some() {
+        available.push("mattermost");
+    }
+    available
+}
+
+/// Send a one-off message to `target` on a configured channel.
+pub async fn send_announcement(
+    config: &Config,
+    channel: &str,
+    target: &str,
+    output: &str,
+) -> Result<()> {
+    announce_channel(config, channel)?
+        .send(&SendMessage::new(output, target))
+        .await
+}
+
+/// Build the named channel from config for outbound-only delivery.
+pub fn announce_channel(config: &Config, channel: &str) -> Result<Box<dyn Channel>> {
+    let channel: Box<dyn Channel> = match channel.to_ascii_lowercase().as_str() {
         "telegram" => {
             let tg = config
                 .channels_config
                 .telegram
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("telegram channel not configured"))?;
-            let channel = TelegramChannel::new(
+            Box::new(TelegramChannel::new(
                 tg.bot_token.clone(),
                 tg.allowed_users.clone(),
                 tg.mention_only,
-            );
-            channel.send(&SendMessage::new(output, target)).await?;
+            ))
         }
         "discord" => {
             let dc = config
@@ -316,14 +472,13 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str) ->
                 .discord
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("discord channel not configured"))?;
-            let channel = DiscordChannel::new(
+            Box::new(DiscordChannel::new(
                 dc.bot_token.clone(),
                 dc.guild_id.clone(),
                 dc.allowed_users.clone(),
                 dc.listen_to_bots,
                 dc.mention_only,
-            );
-            channel.send(&SendMessage::new(output, target)).await?;
+            ))
         }
         "slack" => {
             let sl = config
@@ -331,12 +486,11 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str) ->
                 .slack
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("slack channel not configured"))?;
-            let channel = SlackChannel::new(
+            Box::new(SlackChannel::new(
                 sl.bot_token.clone(),
                 sl.channel_id.clone(),
                 sl.allowed_users.clone(),
-            );
-            channel.send(&SendMessage::new(output, target)).await?;
+            ))
         }
         "mattermost" => {
             let mm = config
@@ -344,20 +498,19 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str) ->
                 .mattermost
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("mattermost channel not configured"))?;
-            let channel = MattermostChannel::new(
+            Box::new(MattermostChannel::new(
                 mm.url.clone(),
                 mm.bot_token.clone(),
                 mm.channel_id.clone(),
                 mm.allowed_users.clone(),
                 mm.thread_replies.unwrap_or(true),
                 mm.mention_only.unwrap_or(false),
-            );
-            channel.send(&SendMessage::new(output, target)).await?;
+            ))
         }
         other => anyhow::bail!("unsupported delivery channel: {other}"),
-    }
+    };
 
-    Ok(())
+    Ok(channel)
 }
 
 async fn run_job_command(
@@ -456,7 +609,7 @@ async fn run_job_command_with_timeout(
 mod tests {
     use super::*;
     use crate::config::Config;
-    use crate::cron::{self, DeliveryConfig};
+    use crate::cron::{self, DeliveryConfig, JobContext};
     use crate::security::SecurityPolicy;
     use chrono::{Duration as ChronoDuration, Utc};
     use tempfile::TempDir;
@@ -489,6 +642,7 @@ mod tests {
             model: None,
             enabled: true,
             delivery: DeliveryConfig::default(),
+            context: JobContext::default(),
             delete_after_run: false,
             created_at: Utc::now(),
             next_run: Utc::now(),
@@ -709,6 +863,34 @@ mod tests {
         assert!(output.contains("agent job failed:"));
     }
 
+    #[tokio::test]
+    async fn run_agent_job_reports_unknown_task_template() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let mut job = test_job("");
+        job.job_type = JobType::Agent;
+        job.prompt = Some("/task missing-report env=prod".into());
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let (success, output) = run_agent_job(&config, &security, &job).await;
+        assert!(!success);
+        assert!(output.contains("Unknown task 'missing-report'"));
+    }
+
+    #[tokio::test]
+    async fn run_agent_job_renders_report_invocations() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let mut job = test_job("");
+        job.job_type = JobType::Agent;
+        job.prompt = Some("/report missing-report".into());
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let (success, output) = run_agent_job(&config, &security, &job).await;
+        assert!(!success);
+        assert!(output.contains("Unknown report 'missing-report'"));
+    }
+
     #[tokio::test]
     async fn run_agent_job_blocks_readonly_mode() {
         let tmp = TempDir::new().unwrap();
@@ -741,6 +923,39 @@ mod tests {
         assert!(output.contains("rate limit exceeded"));
     }
 
+    #[tokio::test]
+    async fn agent_job_context_resolves_model_memory_and_tools() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.cron.default_model = Some("cheap/model".into());
+        let mut job = test_job("");
+        assert_eq!(job_model(&config, &job).as_deref(), Some("cheap/model"));
+        assert_eq!(job_memory(&config, &job), None);
+
+        job.model = Some("big/model".into());
+        job.context.memory = Some(CronMemoryScope::Isolated);
+        assert_eq!(job_model(&config, &job).as_deref(), Some("big/model"));
+        assert_eq!(
+            job_memory(&config, &job),
+            Some(TaskMemory::Session("cron:test-job".into()))
+        );
+        config.cron.default_memory = CronMemoryScope::None;
+        job.context.memory = None;
+        assert_eq!(job_memory(&config, &job), Some(TaskMemory::Disabled));
+
+        let tools = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert_eq!(job_tools(tools(&["shell"]), &[]), Some(tools(&["shell"])));
+        assert_eq!(
+            job_tools(vec![], &tools(&["file_read"])),
+            Some(tools(&["file_read"]))
+        );
+        assert_eq!(
+            job_tools(tools(&["shell", "file_read"]), &tools(&["file_read"])),
+            Some(tools(&["file_read"]))
+        );
+        assert_eq!(job_tools(tools(&["shell"]), &tools(&["file_read"])), None);
+    }
+
     #[tokio::test]
     async fn process_due_jobs_marks_component_ok_even_when_idle() {
         let tmp = TempDir::new().unwrap();
@@ -809,6 +1024,7 @@ mod tests {
             "Hello",
             SessionTarget::Isolated,
             None,
+            JobContext::default(),
             None,
             true,
         )
@@ -834,6 +1050,7 @@ mod tests {
             "Hello",
             SessionTarget::Isolated,
             None,
+            JobContext::default(),
             None,
             true,
         )
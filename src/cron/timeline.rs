@@ -0,0 +1,625 @@
+//! Forecast of upcoming daemon activity (`zeroclaw cron timeline`).
+//!
+//! Lists what the daemon will run within a window: cron jobs, heartbeat
+//! task ticks, heartbeat probes and research topics, each with an expected
+//! provider cost. Heartbeat and probe ticks follow the daemon's start time,
+//! so they are counted from now and marked approximate. Agent turns are
+//! priced at the average traced turn when runtime traces exist, otherwise
+//! at a typical turn under the model's `[cost.prices]` entry.
+
+use super::{list_jobs, next_run_for_schedule, scheduler, CronJob, JobType, Schedule};
+use crate::config::Config;
+use crate::datetime::UserTimezone;
+use crate::observability::runtime_trace;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::Serialize;
+use std::fmt::Write as _;
+
+/// Longest window accepted by `--next`.
+const MAX_WINDOW_DAYS: i64 = 31;
+/// Occurrences listed per source before the rest are dropped.
+const MAX_RUNS_PER_SOURCE: usize = 5_000;
+/// Rows printed by the text renderer; `--json` lists every run.
+const TEXT_ROW_LIMIT: usize = 100;
+/// Shortest heartbeat interval the daemon honours.
+const MIN_HEARTBEAT_MINUTES: u32 = 5;
+/// Tokens assumed for one agent turn when no traces are available.
+const TYPICAL_INPUT_TOKENS: u64 = 6_000;
+const TYPICAL_OUTPUT_TOKENS: u64 = 800;
+
+/// What schedules a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunSource {
+    Cron,
+    Heartbeat,
+    Probe,
+    Research,
+}
+
+impl RunSource {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Cron => "cron",
+            Self::Heartbeat => "heartbeat",
+            Self::Probe => "probe",
+            Self::Research => "research",
+        }
+    }
+}
+
+/// One expected run.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedRun {
+    pub at: DateTime<Utc>,
+    pub source: RunSource,
+    pub name: String,
+    pub detail: String,
+    /// `None` when the run uses a model without a known price.
+    pub expected_cost_usd: Option<f64>,
+    /// The time depends on when the daemon started or last checked.
+    pub approximate: bool,
+}
+
+/// Runs and cost per scheduled item.
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceSummary {
+    pub source: RunSource,
+    pub name: String,
+    pub runs: usize,
+    pub expected_cost_usd: Option<f64>,
+}
+
+/// Forecast for one window.
+#[derive(Debug, Clone, Serialize)]
+pub struct Timeline {
+    pub from: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub runs: Vec<PlannedRun>,
+    pub summary: Vec<SourceSummary>,
+    /// Sum of the known run costs.
+    pub expected_cost_usd: f64,
+    /// Runs whose cost could not be estimated.
+    pub unpriced_runs: usize,
+    /// How agent turns were priced.
+    pub cost_basis: String,
+    /// Schedules that will not run as configured.
+    pub warnings: Vec<String>,
+}
+
+/// Parse a `--next` window such as `90m`, `24h` or `7d`.
+pub fn parse_window(input: &str) -> Result<Duration> {
+    let window = super::parse_delay(input)?;
+    if window <= Duration::zero() {
+        bail!("--next must be a positive duration");
+    }
+    if window > Duration::days(MAX_WINDOW_DAYS) {
+        bail!("--next is limited to {MAX_WINDOW_DAYS}d");
+    }
+    Ok(window)
+}
+
+/// Prices agent turns.
+struct TurnPricing {
+    traced_average: Option<f64>,
+    basis: String,
+}
+
+impl TurnPricing {
+    fn from_config(config: &Config) -> Self {
+        let path = runtime_trace::resolve_trace_path(&config.observability, &config.workspace_dir);
+        let events = runtime_trace::load_events(&path, usize::MAX, None, None).unwrap_or_default();
+        let summary = runtime_trace::summarize_events(&events);
+        let turns = summary.sessions.len();
+        if turns > 0 && summary.total.cost_usd > 0.0 {
+            return Self {
+                traced_average: Some(summary.total.cost_usd / turns as f64),
+                basis: format!("average of {turns} traced turn(s)"),
+            };
+        }
+        Self {
+            traced_average: None,
+            basis: format!(
+                "[cost.prices] for a typical turn ({TYPICAL_INPUT_TOKENS} input / {TYPICAL_OUTPUT_TOKENS} output tokens)"
+            ),
+        }
+    }
+
+    /// Expected cost of one agent turn on `model` (default model when unset).
+    fn agent_turn(&self, config: &Config, model: Option<&str>) -> Option<f64> {
+        if let Some(average) = self.traced_average {
+            return Some(average);
+        }
+        let provider = config.default_provider.as_deref().unwrap_or("openrouter");
+        let model = model.or(config.default_model.as_deref())?;
+        let pricing = runtime_trace::lookup_pricing(&config.cost.prices, provider, model)?;
+        let usage = crate::cost::TokenUsage::new(
+            model,
+            TYPICAL_INPUT_TOKENS,
+            TYPICAL_OUTPUT_TOKENS,
+            pricing.input,
+            pricing.output,
+        );
+        Some(usage.cost_usd)
+    }
+}
+
+struct Builder {
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+    runs: Vec<PlannedRun>,
+    warnings: Vec<String>,
+}
+
+impl Builder {
+    /// Add runs at `first`, then every `step(previous)` until the window ends.
+    fn repeat(
+        &mut self,
+        template: &PlannedRun,
+        first: DateTime<Utc>,
+        mut step: impl FnMut(DateTime<Utc>) -> Result<DateTime<Utc>>,
+    ) {
+        let mut at = first;
+        let mut count = 0;
+        while at < self.until {
+            if count == MAX_RUNS_PER_SOURCE {
+                self.warnings.push(format!(
+                    "{} {}: more than {MAX_RUNS_PER_SOURCE} runs in the window; the rest are not listed",
+                    template.source.label(),
+                    template.name
+                ));
+                return;
+            }
+            self.runs.push(PlannedRun {
+                at,
+                ..template.clone()
+            });
+            count += 1;
+            match step(at) {
+                Ok(next) if next > at => at = next,
+                Ok(_) => return,
+                Err(e) => {
+                    self.warnings.push(format!(
+                        "{} {}: {e:#}",
+                        template.source.label(),
+                        template.name
+                    ));
+                    return;
+                }
+            }
+        }
+    }
+
+    fn cron_job(&mut self, config: &Config, pricing: &TurnPricing, job: &CronJob) {
+        let name = job.name.clone().unwrap_or_else(|| job.id.clone());
+        let (detail, expected_cost_usd) = match job.job_type {
+            JobType::Shell => (format!("shell: {}", job.command), Some(0.0)),
+            JobType::Agent => (
+                format!("agent: {}", job.prompt.as_deref().unwrap_or_default()),
+                pricing.agent_turn(config, scheduler::job_model(config, job).as_deref()),
+            ),
+        };
+        let template = PlannedRun {
+            at: self.from,
+            source: RunSource::Cron,
+            name,
+            detail,
+            expected_cost_usd,
+            approximate: false,
+        };
+        if let Err(e) = next_run_for_schedule(&job.schedule, self.from) {
+            self.warnings.push(format!("cron {}: {e:#}", template.name));
+            return;
+        }
+        let first = if job.next_run < self.from {
+            self.warnings.push(format!(
+                "cron {}: overdue since {}; runs at the next scheduler poll",
+                template.name,
+                job.next_run.to_rfc3339()
+            ));
+            self.from
+        } else {
+            job.next_run
+        };
+        match &job.schedule {
+            Schedule::At { .. } => self.repeat(&template, first, Ok),
+            schedule => self.repeat(&template, first, |at| next_run_for_schedule(schedule, at)),
+        }
+    }
+
+    fn heartbeat(&mut self, config: &Config, pricing: &TurnPricing) {
+        let heartbeat = &config.heartbeat;
+        if !heartbeat.enabled {
+            return;
+        }
+        if heartbeat.interval_minutes < MIN_HEARTBEAT_MINUTES {
+            self.warnings.push(format!(
+                "heartbeat: interval_minutes = {} is raised to {MIN_HEARTBEAT_MINUTES}",
+                heartbeat.interval_minutes
+            ));
+        }
+        let interval = minutes(heartbeat.interval_minutes.max(MIN_HEARTBEAT_MINUTES));
+        let tasks = std::fs::read_to_string(config.workspace_dir.join("HEARTBEAT.md"))
+            .map(|content| crate::heartbeat::engine::HeartbeatEngine::parse_tasks(&content))
+            .unwrap_or_default();
+        if tasks.is_empty() {
+            self.warnings
+                .push("heartbeat: HEARTBEAT.md has no tasks; ticks do nothing".into());
+        } else {
+            let template = PlannedRun {
+                at: self.from,
+                source: RunSource::Heartbeat,
+                name: "tasks".into(),
+                detail: format!("{} task(s) from HEARTBEAT.md", tasks.len()),
+                expected_cost_usd: pricing
+                    .agent_turn(config, None)
+                    .map(|turn| turn * tasks.len() as f64),
+                approximate: true,
+            };
+            self.repeat(&template, self.from + interval, |at| Ok(at + interval));
+        }
+
+        for probe in &heartbeat.probes {
+            let interval = minutes(
+                probe
+                    .interval_minutes
+                    .unwrap_or(heartbeat.interval_minutes)
+                    .max(1),
+            );
+            let template = PlannedRun {
+                at: self.from,
+                source: RunSource::Probe,
+                name: probe.name.clone(),
+                detail: "check only; the agent runs when the probe changes state".into(),
+                expected_cost_usd: Some(0.0),
+                approximate: true,
+            };
+            self.repeat(&template, self.from + interval, |at| Ok(at + interval));
+        }
+    }
+
+    fn research(&mut self, config: &Config, pricing: &TurnPricing) {
+        if !config.research.enabled {
+            return;
+        }
+        let state = crate::research::ResearchState::load(&config.workspace_dir);
+        for (name, topic) in &config.research.topics {
+            let every = Duration::hours(i64::from(topic.every_hours.max(1)));
+            let due = state
+                .last_run
+                .get(name)
+                .map_or(self.from, |last| *last + every);
+            let expected_cost_usd = if topic.max_cost_usd > 0.0 {
+                Some(topic.max_cost_usd)
+            } else {
+                pricing.agent_turn(config, None)
+            };
+            let template = PlannedRun {
+                at: self.from,
+                source: RunSource::Research,
+                name: name.clone(),
+                detail: format!("brief every {}h", topic.every_hours.max(1)),
+                expected_cost_usd,
+                approximate: due <= self.from,
+            };
+            self.repeat(&template, due.max(self.from), |at| Ok(at + every));
+        }
+    }
+}
+
+fn minutes(minutes: u32) -> Duration {
+    Duration::minutes(i64::from(minutes))
+}
+
+/// Forecast runs in `[from, from + window)`.
+pub fn build(config: &Config, from: DateTime<Utc>, window: Duration) -> Result<Timeline> {
+    let pricing = TurnPricing::from_config(config);
+    let mut builder = Builder {
+        from,
+        until: from + window,
+        runs: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    let jobs = list_jobs(config)?;
+    let enabled: Vec<&CronJob> = jobs.iter().filter(|job| job.enabled).collect();
+    if !config.cron.enabled && !enabled.is_empty() {
+        builder.warnings.push(format!(
+            "cron: [cron] enabled = false, so {} job(s) will not run",
+            enabled.len()
+        ));
+    } else {
+        for job in enabled {
+            builder.cron_job(config, &pricing, job);
+        }
+    }
+    builder.heartbeat(config, &pricing);
+    builder.research(config, &pricing);
+
+    let Builder {
+        mut runs, warnings, ..
+    } = builder;
+    runs.sort_by(|a, b| a.at.cmp(&b.at).then_with(|| a.source.cmp(&b.source)));
+
+    let mut summary: Vec<SourceSummary> = Vec::new();
+    for run in &runs {
+        match summary
+            .iter_mut()
+            .find(|item| item.source == run.source && item.name == run.name)
+        {
+            Some(item) => {
+                item.runs += 1;
+                item.expected_cost_usd = item
+                    .expected_cost_usd
+                    .zip(run.expected_cost_usd)
+                    .map(|(total, cost)| total + cost);
+            }
+            None => summary.push(SourceSummary {
+                source: run.source,
+                name: run.name.clone(),
+                runs: 1,
+                expected_cost_usd: run.expected_cost_usd,
+            }),
+        }
+    }
+    summary.sort_by(|a, b| a.source.cmp(&b.source).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(Timeline {
+        from,
+        until: from + window,
+        expected_cost_usd: runs.iter().filter_map(|run| run.expected_cost_usd).sum(),
+        unpriced_runs: runs
+            .iter()
+            .filter(|run| run.expected_cost_usd.is_none())
+            .count(),
+        runs,
+        summary,
+        cost_basis: pricing.basis,
+        warnings,
+    })
+}
+
+fn format_time(tz: UserTimezone, at: DateTime<Utc>) -> String {
+    const FORMAT: &str = "%a %m-%d %H:%M";
+    match tz {
+        UserTimezone::Named(tz) => at.with_timezone(&tz).format(FORMAT).to_string(),
+        UserTimezone::HostLocal => at.with_timezone(&Local).format(FORMAT).to_string(),
+    }
+}
+
+fn format_cost(cost: Option<f64>) -> String {
+    cost.map_or_else(|| "unpriced".into(), |cost| format!("${cost:.4}"))
+}
+
+/// Human-readable timeline in `tz`.
+pub fn render(timeline: &Timeline, tz: UserTimezone) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "🗓️  {} → {} ({})",
+        format_time(tz, timeline.from),
+        format_time(tz, timeline.until),
+        tz.label()
+    );
+    if timeline.runs.is_empty() {
+        out.push_str("\nNothing is scheduled in this window.\n");
+    }
+    for run in timeline.runs.iter().take(TEXT_ROW_LIMIT) {
+        let _ = writeln!(
+            out,
+            "  {}{}  {:<9}  {:<20}  {:>10}  {}",
+            if run.approximate { "~" } else { " " },
+            format_time(tz, run.at),
+            run.source.label(),
+            run.name,
+            format_cost(run.expected_cost_usd),
+            run.detail
+        );
+    }
+    if timeline.runs.len() > TEXT_ROW_LIMIT {
+        let _ = writeln!(
+            out,
+            "  … {} more run(s); use --json for the full list",
+            timeline.runs.len() - TEXT_ROW_LIMIT
+        );
+    }
+
+    if !timeline.summary.is_empty() {
+        out.push_str("\nPer schedule:\n");
+        for item in &timeline.summary {
+            let _ = writeln!(
+                out,
+                "  {:<9}  {:<20}  {:>5} run(s)  {:>10}",
+                item.source.label(),
+                item.name,
+                item.runs,
+                format_cost(item.expected_cost_usd)
+            );
+        }
+    }
+    let _ = writeln!(
+        out,
+        "\nExpected cost: ${:.4} ({})",
+        timeline.expected_cost_usd, timeline.cost_basis
+    );
+    if timeline.unpriced_runs > 0 {
+        let _ = writeln!(
+            out,
+            "  {} run(s) use a model without a [cost.prices] entry and are not counted",
+            timeline.unpriced_runs
+        );
+    }
+    if timeline.runs.iter().any(|run| run.approximate) {
+        out.push_str("  ~ times depend on when the daemon started\n");
+    }
+    for warning in &timeline.warnings {
+        let _ = writeln!(out, "⚠️  {warning}");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{HeartbeatProbeConfig, ModelPricing, ProbeCheck, ResearchTopicConfig};
+    use crate::cron::{add_agent_job, add_shell_job, JobContext, SessionTarget};
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir) -> Config {
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            default_provider: Some("openrouter".into()),
+            default_model: Some("test/model".into()),
+            ..Config::default()
+        };
+        config.cost.prices.insert(
+            "test/model".into(),
+            ModelPricing {
+                input: 1.0,
+                output: 10.0,
+            },
+        );
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        config
+    }
+
+    #[test]
+    fn cron_jobs_repeat_across_the_window_with_costs() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        add_shell_job(
+            &config,
+            Some("backup".into()),
+            Schedule::Every {
+                every_ms: 6 * 3_600_000,
+            },
+            "echo backup",
+        )
+        .unwrap();
+        add_agent_job(
+            &config,
+            Some("digest".into()),
+            Schedule::Cron {
+                expr: "0 9 * * *".into(),
+                tz: Some("UTC".into()),
+            },
+            "summarize my inbox",
+            SessionTarget::Isolated,
+            None,
+            JobContext::default(),
+            None,
+            false,
+        )
+        .unwrap();
+
+        let timeline = build(&config, Utc::now(), Duration::hours(24)).unwrap();
+        let backup = timeline
+            .summary
+            .iter()
+            .find(|item| item.name == "backup")
+            .unwrap();
+        assert_eq!(backup.runs, 4);
+        assert_eq!(backup.expected_cost_usd, Some(0.0));
+
+        // 6000 input at $1/M plus 800 output at $10/M.
+        let digest = timeline
+            .summary
+            .iter()
+            .find(|item| item.name == "digest")
+            .unwrap();
+        assert_eq!(digest.runs, 1);
+        let turn = digest.expected_cost_usd.unwrap();
+        assert!((turn - 0.014).abs() < 1e-9, "{turn}");
+        assert!((timeline.expected_cost_usd - turn).abs() < 1e-9);
+        assert!(timeline.runs.windows(2).all(|w| w[0].at <= w[1].at));
+        assert!(timeline.warnings.is_empty(), "{:?}", timeline.warnings);
+    }
+
+    #[test]
+    fn heartbeats_probes_and_research_are_forecast() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        config.heartbeat.enabled = true;
+        config.heartbeat.interval_minutes = 2;
+        config.heartbeat.probes.push(HeartbeatProbeConfig {
+            name: "disk".into(),
+            check: ProbeCheck::DiskSpace {
+                path: "/".into(),
+                min_free_percent: 10.0,
+            },
+            interval_minutes: Some(30),
+            failure_threshold: 1,
+            on_failure: None,
+            notify_recovery: true,
+        });
+        std::fs::write(
+            config.workspace_dir.join("HEARTBEAT.md"),
+            "# Tasks\n- check mail\n- water plants\n",
+        )
+        .unwrap();
+        config.research.enabled = true;
+        config.research.topics.insert(
+            "rust".into(),
+            ResearchTopicConfig {
+                queries: Vec::new(),
+                every_hours: 12,
+                max_sources: 5,
+                max_cost_usd: 0.05,
+                fetch_pages: false,
+                channel: None,
+                to: None,
+            },
+        );
+        config.cron.enabled = false;
+        add_shell_job(
+            &config,
+            None,
+            Schedule::Every { every_ms: 60_000 },
+            "echo hi",
+        )
+        .unwrap();
+
+        let timeline = build(&config, Utc::now(), Duration::hours(2)).unwrap();
+        let runs = |source| {
+            timeline
+                .runs
+                .iter()
+                .filter(|run| run.source == source)
+                .count()
+        };
+        assert_eq!(runs(RunSource::Cron), 0);
+        // Ticks every 5 minutes (raised from 2), two tasks each.
+        assert_eq!(runs(RunSource::Heartbeat), 23);
+        let tick = timeline
+            .runs
+            .iter()
+            .find(|run| run.source == RunSource::Heartbeat)
+            .unwrap();
+        assert!(tick.approximate);
+        assert!((tick.expected_cost_usd.unwrap() - 0.028).abs() < 1e-9);
+        assert_eq!(runs(RunSource::Probe), 3);
+        // Never run before, so due now and again 12h later (outside the window).
+        assert_eq!(runs(RunSource::Research), 1);
+
+        assert!(timeline.warnings.iter().any(|w| w.contains("raised to 5")));
+        assert!(timeline
+            .warnings
+            .iter()
+            .any(|w| w.contains("[cron] enabled = false")));
+
+        let text = render(&timeline, UserTimezone::from_name(Some("UTC")));
+        assert!(text.contains("heartbeat"));
+        assert!(text.contains("⚠️"));
+    }
+
+    #[test]
+    fn window_must_be_positive_and_bounded() {
+        assert_eq!(parse_window("24h").unwrap(), Duration::hours(24));
+        assert_eq!(parse_window("90").unwrap(), Duration::minutes(90));
+        assert!(parse_window("0h").is_err());
+        assert!(parse_window("60d").is_err());
+        assert!(parse_window("soon").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::config::CronMemoryScope;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -101,6 +102,17 @@ fn default_true() -> bool {
     true
 }
 
+/// What an agent job may touch while it runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JobContext {
+    /// Memory scope; `None` uses `[cron] default_memory`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<CronMemoryScope>,
+    /// Tools the job may call; empty allows every tool.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CronJob {
     pub id: String,
@@ -114,6 +126,8 @@ pub struct CronJob {
     pub model: Option<String>,
     pub enabled: bool,
     pub delivery: DeliveryConfig,
+    #[serde(default)]
+    pub context: JobContext,
     pub delete_after_run: bool,
     pub created_at: DateTime<Utc>,
     pub next_run: DateTime<Utc>,
@@ -143,6 +157,7 @@ pub struct CronJobPatch {
     pub delivery: Option<DeliveryConfig>,
     pub model: Option<String>,
     pub session_target: Option<SessionTarget>,
+    pub context: Option<JobContext>,
     pub delete_after_run: Option<bool>,
 }
 
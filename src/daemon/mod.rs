@@ -5,16 +5,26 @@ use std::future::Future;
 use std::path::PathBuf;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
+use tokio_util::task::AbortOnDropHandle;
 
 const STATUS_FLUSH_SECONDS: u64 = 5;
+const HEARTBEAT_PROBE_TICK_SECONDS: u64 = 60;
 
 pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
+    crate::config::ensure_runtime_globals(&config);
     let initial_backoff = config.reliability.channel_initial_backoff_secs.max(1);
     let max_backoff = config
         .reliability
         .channel_max_backoff_secs
         .max(initial_backoff);
 
+    if config.coordination.ha && !crate::coordination::is_shared() {
+        anyhow::bail!(
+            "coordination.ha is enabled but the shared coordination backend is unavailable; \
+             refusing to start so channels and the scheduler never run on two nodes"
+        );
+    }
+
     crate::health::mark_component_ok("daemon");
 
     if config.heartbeat.enabled {
@@ -35,61 +45,226 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
             move || {
                 let cfg = gateway_cfg.clone();
                 let host = gateway_host.clone();
-                async move { crate::gateway::run_gateway(&host, port, cfg).await }
+                async move { Box::pin(crate::gateway::run_gateway(&host, port, cfg)).await }
+            },
+        ));
+    }
+
+    if config.control.enabled && !cfg!(unix) {
+        tracing::warn!("[control] is enabled but the control socket needs a UNIX platform");
+    } else if config.control.enabled {
+        let control_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "control",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = control_cfg.clone();
+                async move { Box::pin(crate::control::serve(cfg)).await }
+            },
+        ));
+    }
+
+    if config.coordination.ha {
+        handles.push(spawn_leader_election(
+            config.clone(),
+            initial_backoff,
+            max_backoff,
+        ));
+    } else {
+        handles.extend(spawn_leader_components(
+            &config,
+            initial_backoff,
+            max_backoff,
+        ));
+    }
+
+    if config.questions.enabled {
+        let questions_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "questions",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = questions_cfg.clone();
+                async move { Box::pin(run_questions_worker(cfg)).await }
+            },
+        ));
+    }
+
+    if config.inbox.enabled {
+        let inbox_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "inbox_summary",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = inbox_cfg.clone();
+                async move { Box::pin(crate::inbox::run_summary_worker(cfg)).await }
+            },
+        ));
+    }
+
+    if config.recordings.enabled {
+        let recordings_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "recordings",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = recordings_cfg.clone();
+                async move { Box::pin(crate::recordings::run_worker(cfg)).await }
             },
         ));
     }
 
+    if config.research.enabled {
+        let research_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "research",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = research_cfg.clone();
+                async move { Box::pin(crate::research::run_worker(cfg)).await }
+            },
+        ));
+    }
+
+    if config.quotas.enabled {
+        let gc_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "workspace_gc",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = gc_cfg.clone();
+                async move { Box::pin(crate::quotas::run_worker(cfg)).await }
+            },
+        ));
+    }
+
+    if crate::retention::DataClass::ALL
+        .into_iter()
+        .any(|class| class.policy(&config.retention).max_days > 0)
     {
-        if has_supervised_channels(&config) {
-            let channels_cfg = config.clone();
-            handles.push(spawn_component_supervisor(
-                "channels",
-                initial_backoff,
-                max_backoff,
-                move || {
-                    let cfg = channels_cfg.clone();
-                    async move { crate::channels::start_channels(cfg).await }
-                },
-            ));
-        } else {
-            crate::health::mark_component_ok("channels");
-            tracing::info!("No real-time channels configured; channel supervisor disabled");
-        }
+        let retention_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "retention",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = retention_cfg.clone();
+                async move { Box::pin(crate::retention::run_worker(cfg)).await }
+            },
+        ));
     }
 
-    if config.heartbeat.enabled {
-        let heartbeat_cfg = config.clone();
+    if crate::telemetry::is_enabled(&config.telemetry) {
+        let telemetry_cfg = config.clone();
         handles.push(spawn_component_supervisor(
-            "heartbeat",
+            "telemetry",
             initial_backoff,
             max_backoff,
             move || {
-                let cfg = heartbeat_cfg.clone();
-                async move { Box::pin(run_heartbeat_worker(cfg)).await }
+                let cfg = telemetry_cfg.clone();
+                async move { Box::pin(crate::telemetry::run_worker(cfg)).await }
             },
         ));
     }
 
-    if config.cron.enabled {
-        let scheduler_cfg = config.clone();
+    if config.triage.enabled && !config.triage.watch.is_empty() {
+        let triage_cfg = config.clone();
         handles.push(spawn_component_supervisor(
-            "scheduler",
+            "test_triage",
             initial_backoff,
             max_backoff,
             move || {
-                let cfg = scheduler_cfg.clone();
-                async move { crate::cron::scheduler::run(cfg).await }
+                let cfg = triage_cfg.clone();
+                async move { Box::pin(crate::triage::run_worker(cfg)).await }
+            },
+        ));
+    }
+
+    if config.hardware.logs.enabled && !cfg!(all(feature = "hardware", feature = "probe")) {
+        tracing::warn!(
+            "[hardware.logs] is enabled but this build lacks the probe feature; device log capture disabled"
+        );
+    } else if config.hardware.logs.enabled {
+        let logs_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "hardware_logs",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = logs_cfg.clone();
+                async move { Box::pin(crate::hardware::rtt::run_capture(cfg)).await }
+            },
+        ));
+    }
+
+    if config.camera.enabled
+        && config
+            .camera
+            .cameras
+            .values()
+            .any(|camera| camera.motion.is_some())
+    {
+        let camera_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "camera",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = camera_cfg.clone();
+                async move { Box::pin(crate::camera::run_motion_watch(cfg)).await }
             },
         ));
-    } else {
-        crate::health::mark_component_ok("scheduler");
-        tracing::info!("Cron disabled; scheduler supervisor not started");
+    }
+
+    if config.federation.role == crate::config::FederationRole::Worker {
+        let federation_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "federation",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = federation_cfg.clone();
+                async move { Box::pin(crate::federation::worker::run(cfg)).await }
+            },
+        ));
+    }
+
+    // Smart home bridge: reconnects on its own, so it runs outside the supervisor
+    if config.smart_home.enabled {
+        #[cfg(feature = "mqtt")]
+        crate::smart_home::ensure_started(&config.smart_home);
+        #[cfg(not(feature = "mqtt"))]
+        tracing::warn!(
+            "[smart_home] is enabled but this build has no MQTT support. Build with: cargo build --features mqtt"
+        );
+    }
+
+    // BLE scanner: also restarts itself, and keeps presence history between agent runs
+    if config.peripherals.enabled && config.peripherals.ble.enabled {
+        crate::peripherals::ble::ensure_started(&config.peripherals.ble);
     }
 
     println!("🧠 ZeroClaw daemon started");
     println!("   Gateway:  http://{host}:{port}");
     println!("   Components: gateway, channels, heartbeat, scheduler");
+    if config.control.enabled && cfg!(unix) {
+        println!(
+            "   Control:  {}",
+            crate::control::socket_path(&config).display()
+        );
+    }
+    if config.coordination.ha {
+        println!(
+            "   HA:       node {}; channels, heartbeat and scheduler run only while leader",
+            crate::coordination::node_id(&config.coordination)
+        );
+    }
     println!("   Ctrl+C to stop");
 
     tokio::signal::ctrl_c().await?;
@@ -101,10 +276,126 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     for handle in handles {
         let _ = handle.await;
     }
+    if config.coordination.ha {
+        // Hand over to a standby now instead of when the lease expires.
+        let _ = tokio::task::spawn_blocking(|| {
+            crate::coordination::release_lease(crate::coordination::LEADER_LEASE);
+        })
+        .await;
+    }
 
     Ok(())
 }
 
+/// Components that must run on exactly one node in HA mode: channels,
+/// heartbeat and the cron scheduler.
+fn spawn_leader_components(
+    config: &Config,
+    initial_backoff: u64,
+    max_backoff: u64,
+) -> Vec<JoinHandle<()>> {
+    let mut handles = Vec::new();
+
+    if has_supervised_channels(config) {
+        let channels_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "channels",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = channels_cfg.clone();
+                async move { crate::channels::start_channels(cfg).await }
+            },
+        ));
+    } else {
+        crate::health::mark_component_ok("channels");
+        tracing::info!("No real-time channels configured; channel supervisor disabled");
+    }
+
+    if config.heartbeat.enabled {
+        let heartbeat_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "heartbeat",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = heartbeat_cfg.clone();
+                async move { Box::pin(run_heartbeat_worker(cfg)).await }
+            },
+        ));
+    }
+
+    if config.cron.enabled {
+        let scheduler_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "scheduler",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = scheduler_cfg.clone();
+                async move { Box::pin(crate::cron::scheduler::run(cfg)).await }
+            },
+        ));
+    } else {
+        crate::health::mark_component_ok("scheduler");
+        tracing::info!("Cron disabled; scheduler supervisor not started");
+    }
+
+    handles
+}
+
+/// Run the leader components only while this node holds the `leader` lease.
+///
+/// The lease is renewed every third of its duration; when renewal fails the
+/// node steps down at once, before the lease can expire and a standby take
+/// over, so the components never run on two nodes.
+fn spawn_leader_election(config: Config, initial_backoff: u64, max_backoff: u64) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let lease = Duration::from_secs(config.coordination.lease_secs);
+        let node = crate::coordination::node_id(&config.coordination);
+        let mut interval = tokio::time::interval(lease / 3);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        // Dropping the handles stops the components, including when this task is aborted.
+        let mut components: Option<Vec<AbortOnDropHandle<()>>> = None;
+        crate::health::mark_component_ok("leader_election");
+
+        loop {
+            interval.tick().await;
+            let held = tokio::task::spawn_blocking(move || {
+                crate::coordination::hold_lease(crate::coordination::LEADER_LEASE, lease)
+            })
+            .await
+            .unwrap_or(false);
+
+            match (held, components.is_some()) {
+                (true, false) => {
+                    tracing::info!(
+                        node,
+                        "Elected leader; starting channels, heartbeat and scheduler"
+                    );
+                    components = Some(
+                        spawn_leader_components(&config, initial_backoff, max_backoff)
+                            .into_iter()
+                            .map(AbortOnDropHandle::new)
+                            .collect(),
+                    );
+                }
+                (false, true) => {
+                    tracing::warn!(
+                        node,
+                        "Lost leadership; stopping channels, heartbeat and scheduler"
+                    );
+                    components = None;
+                }
+                _ => {}
+            }
+        }
+    })
+}
+
+/// Health snapshot rewritten every few seconds for `zeroclaw doctor` and
+/// external monitors. It is a liveness heartbeat rather than state to
+/// replay, so it stays out of the [`crate::state::StateStore`] log.
 pub fn state_file_path(config: &Config) -> PathBuf {
     config
         .config_path
@@ -131,7 +422,11 @@ fn spawn_state_writer(config: Config) -> JoinHandle<()> {
                 );
             }
             let data = serde_json::to_vec_pretty(&json).unwrap_or_else(|_| b"{}".to_vec());
-            let _ = tokio::fs::write(&path, data).await;
+            // Replace atomically so readers never see a half-written snapshot.
+            let tmp = path.with_extension("json.tmp");
+            if tokio::fs::write(&tmp, data).await.is_ok() {
+                let _ = tokio::fs::rename(&tmp, &path).await;
+            }
         }
     })
 }
@@ -181,36 +476,108 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
         config.workspace_dir.clone(),
         observer,
     );
+    let mut probes = crate::heartbeat::probes::ProbeRunner::new(&config);
 
     let interval_mins = config.heartbeat.interval_minutes.max(5);
-    let mut interval = tokio::time::interval(Duration::from_secs(u64::from(interval_mins) * 60));
+    let task_interval = Duration::from_secs(u64::from(interval_mins) * 60);
+    let tick = if probes.is_empty() {
+        task_interval
+    } else {
+        Duration::from_secs(HEARTBEAT_PROBE_TICK_SECONDS)
+    };
+    let mut interval = tokio::time::interval(tick);
+    let mut next_tasks = tokio::time::Instant::now();
 
     loop {
         interval.tick().await;
 
-        let tasks = engine.collect_tasks().await?;
-        if tasks.is_empty() {
+        // Probes only involve the agent when one changes state.
+        for transition in probes.run_due().await {
+            run_heartbeat_prompt(&config, transition.prompt()).await;
+        }
+
+        if tokio::time::Instant::now() < next_tasks {
             continue;
         }
+        next_tasks += task_interval;
 
-        for task in tasks {
-            let prompt = format!("[Heartbeat Task] {task}");
-            let temp = config.default_temperature;
-            if let Err(e) = crate::agent::run(
+        for task in engine.collect_tasks().await? {
+            run_heartbeat_prompt(&config, format!("[Heartbeat Task] {task}")).await;
+        }
+    }
+}
+
+async fn run_heartbeat_prompt(config: &Config, prompt: String) {
+    let (provider, model) =
+        crate::cost::downgrade::background_overrides(config, "heartbeat", None).await;
+    if let Err(e) = Box::pin(crate::agent::run(
+        config.clone(),
+        Some(prompt),
+        provider,
+        model,
+        config.default_temperature,
+        vec![],
+        false,
+        false,
+        Vec::new(),
+        crate::handoff::CliHandoff::default(),
+    ))
+    .await
+    {
+        crate::health::mark_component_error("heartbeat", e.to_string());
+        tracing::warn!("Heartbeat task failed: {e}");
+    } else {
+        crate::health::mark_component_ok("heartbeat");
+    }
+}
+
+/// Resume tasks whose `ask_user` question has been answered, replying on the
+/// channel the question was asked on.
+async fn run_questions_worker(config: Config) -> Result<()> {
+    let queue = crate::questions::QuestionQueue::from_config(&config);
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(config.questions.poll_secs.max(1)));
+
+    loop {
+        interval.tick().await;
+
+        for question in queue.take_answered()? {
+            tracing::info!(question = %question.id, "Resuming task after owner answer");
+            let result = Box::pin(crate::agent::run(
                 config.clone(),
-                Some(prompt),
+                Some(question.resume_prompt()),
                 None,
                 None,
-                temp,
+                config.default_temperature,
                 vec![],
                 false,
-            )
-            .await
-            {
-                crate::health::mark_component_error("heartbeat", e.to_string());
-                tracing::warn!("Heartbeat task failed: {e}");
-            } else {
-                crate::health::mark_component_ok("heartbeat");
+                false,
+                Vec::new(),
+                crate::handoff::CliHandoff::default(),
+            ))
+            .await;
+            match result {
+                Ok(output) => {
+                    crate::health::mark_component_ok("questions");
+                    if let (Some(channel), Some(to)) = (&question.channel, &question.to) {
+                        if !output.trim().is_empty() {
+                            if let Err(e) = crate::cron::scheduler::send_announcement(
+                                &config, channel, to, &output,
+                            )
+                            .await
+                            {
+                                tracing::warn!(
+                                    "Failed to deliver resumed task result for {}: {e}",
+                                    question.id
+                                );
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    crate::health::mark_component_error("questions", e.to_string());
+                    tracing::warn!("Resumed task for question {} failed: {e}", question.id);
+                }
             }
         }
     }
@@ -286,6 +653,18 @@ mod tests {
             .contains("component exited unexpectedly"));
     }
 
+    #[tokio::test]
+    async fn ha_mode_refuses_to_start_without_shared_coordination() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        config.coordination.ha = true;
+
+        let err = Box::pin(run(config, "127.0.0.1".into(), 0))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("coordination.ha"));
+    }
+
     #[test]
     fn detects_no_supervised_channels() {
         let config = Config::default();
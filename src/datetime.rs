@@ -0,0 +1,464 @@
+//! Timezone-aware date handling for agent context and scheduling.
+//!
+//! The user's timezone (`[ui] timezone`, with per-identity overrides in
+//! `[ui.identity_timezones]`) is injected into the agent's date/time context
+//! and used to resolve relative expressions such as "tomorrow at 9",
+//! "next friday 14:30" or "in 2 hours" into absolute instants for cron jobs.
+
+use crate::config::UiConfig;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+/// Hour used when an expression names a day but no time ("tomorrow").
+const DEFAULT_HOUR: u32 = 9;
+
+static USER_CLOCK: RwLock<Option<UserClock>> = RwLock::new(None);
+
+static RELATIVE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:in\s+)?(\d+)\s*(minutes?|mins?|m|hours?|hrs?|h|days?|d|weeks?|w)(?:\s+from\s+now)?$",
+    )
+    .expect("valid relative regex")
+});
+static ISO_DATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(\d{4})-(\d{1,2})-(\d{1,2})\b").expect("valid date regex"));
+static CLOCK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(\d{1,2})(?::(\d{2}))?\s*(am|pm)?\b").expect("valid time regex")
+});
+
+/// A user's timezone: a named IANA zone, or the host's local zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserTimezone {
+    Named(Tz),
+    HostLocal,
+}
+
+impl UserTimezone {
+    /// Parse an IANA timezone name; `None` or an invalid name yields the
+    /// host's local zone.
+    pub fn from_name(name: Option<&str>) -> Self {
+        name.and_then(|name| name.trim().parse::<Tz>().ok())
+            .map_or(Self::HostLocal, Self::Named)
+    }
+
+    /// Display label, e.g. `Europe/Berlin` or `host local`.
+    pub fn label(self) -> String {
+        match self {
+            Self::Named(tz) => tz.name().to_string(),
+            Self::HostLocal => "host local".to_string(),
+        }
+    }
+
+    /// IANA name for cron schedules; `None` for the host's local zone.
+    pub fn iana_name(self) -> Option<String> {
+        match self {
+            Self::Named(tz) => Some(tz.name().to_string()),
+            Self::HostLocal => None,
+        }
+    }
+
+    /// Current time formatted for prompts:
+    /// `Saturday 2026-10-17 09:00:00 (CEST)`.
+    pub fn format_now(self, now: DateTime<Utc>) -> String {
+        match self {
+            Self::Named(tz) => format_instant(&now.with_timezone(&tz)),
+            Self::HostLocal => format_instant(&now.with_timezone(&Local)),
+        }
+    }
+
+    /// Resolve a natural-language or RFC 3339 date/time relative to `now`.
+    pub fn resolve(self, input: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if let Ok(exact) = DateTime::parse_from_rfc3339(input.trim()) {
+            return Some(exact.with_timezone(&Utc));
+        }
+        match self {
+            Self::Named(tz) => resolve_natural(input, &now.with_timezone(&tz))
+                .map(|resolved| resolved.with_timezone(&Utc)),
+            Self::HostLocal => resolve_natural(input, &now.with_timezone(&Local))
+                .map(|resolved| resolved.with_timezone(&Utc)),
+        }
+    }
+}
+
+/// Timezone settings resolved from `[ui]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserClock {
+    pub default: UserTimezone,
+    pub locale: String,
+    overrides: HashMap<String, UserTimezone>,
+}
+
+impl Default for UserClock {
+    fn default() -> Self {
+        Self {
+            default: UserTimezone::HostLocal,
+            locale: crate::i18n::DEFAULT_LOCALE.to_string(),
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl UserClock {
+    pub fn from_config(config: &UiConfig) -> Self {
+        let overrides = config
+            .identity_timezones
+            .iter()
+            .filter_map(|(identity, name)| {
+                let tz = name.trim().parse::<Tz>().ok()?;
+                Some((identity.trim().to_string(), UserTimezone::Named(tz)))
+            })
+            .collect();
+        Self {
+            default: UserTimezone::from_name(config.timezone.as_deref()),
+            locale: config
+                .locale
+                .as_deref()
+                .and_then(crate::i18n::resolve_locale)
+                .unwrap_or(crate::i18n::DEFAULT_LOCALE)
+                .to_string(),
+            overrides,
+        }
+    }
+
+    /// Timezone for a sender: `"<channel>:<sender>"` override, then
+    /// `"<sender>"` override, then the configured default.
+    pub fn timezone_for(&self, channel: &str, sender: &str) -> UserTimezone {
+        self.overrides
+            .get(&format!("{channel}:{sender}"))
+            .or_else(|| self.overrides.get(sender))
+            .copied()
+            .unwrap_or(self.default)
+    }
+
+    /// `## Current Date & Time` prompt section for the default timezone.
+    pub fn prompt_section(&self, now: DateTime<Utc>) -> String {
+        format!(
+            "## Current Date & Time\n\nTimezone: {} | Locale: {}\n\
+             Interpret relative dates and times (e.g. \"tomorrow at 9\") in this timezone unless the user names another.\n\
+             Now: {}",
+            self.default.label(),
+            self.locale,
+            self.default.format_now(now)
+        )
+    }
+
+    /// Per-message time context for a channel sender.
+    pub fn sender_section(&self, channel: &str, sender: &str, now: DateTime<Utc>) -> String {
+        let tz = self.timezone_for(channel, sender);
+        format!(
+            "## Sender Local Time\n\nThe sender's timezone is {}; their local time is now {}. \
+             Resolve relative dates in this message against it.",
+            tz.label(),
+            tz.format_now(now)
+        )
+    }
+}
+
+/// Install the process-wide clock from `[ui]` config. Call once at startup.
+pub fn init_from_config(config: &UiConfig) {
+    let mut guard = USER_CLOCK.write().unwrap_or_else(|e| e.into_inner());
+    *guard = Some(UserClock::from_config(config));
+}
+
+/// The process-wide clock (host local timezone when not initialized).
+pub fn user_clock() -> UserClock {
+    USER_CLOCK
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+fn format_instant<T: TimeZone>(instant: &DateTime<T>) -> String
+where
+    T::Offset: std::fmt::Display,
+{
+    instant.format("%A %Y-%m-%d %H:%M:%S (%Z)").to_string()
+}
+
+/// Resolve expressions like "tomorrow at 9", "next monday 14:30",
+/// "friday evening", "2026-11-02 8am", "at 5pm" or "in 90 minutes" in the
+/// timezone of `now`. Unrecognized words make the whole expression fail
+/// rather than silently resolving to the wrong time.
+pub fn resolve_natural<T: TimeZone>(input: &str, now: &DateTime<T>) -> Option<DateTime<T>> {
+    let mut text = input
+        .trim()
+        .to_ascii_lowercase()
+        .replace(',', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    if text == "now" {
+        return Some(now.clone());
+    }
+    if let Some(caps) = RELATIVE_RE.captures(&text) {
+        let amount: i64 = caps[1].parse().ok()?;
+        // The `try_` constructors, since the model can ask for any amount and
+        // the panicking ones would take the daemon down.
+        let delta = match &caps[2] {
+            unit if unit.starts_with("mi") || unit == "m" => Duration::try_minutes(amount),
+            unit if unit.starts_with('h') => Duration::try_hours(amount),
+            unit if unit.starts_with('d') => Duration::try_days(amount),
+            _ => Duration::try_weeks(amount),
+        }?;
+        return now.clone().checked_add_signed(delta);
+    }
+
+    let today = now.date_naive();
+    let mut date: Option<NaiveDate> = None;
+    let mut time: Option<NaiveTime> = None;
+    let mut weekday_rolls = false;
+
+    if let Some(caps) = ISO_DATE_RE.captures(&text) {
+        date = Some(NaiveDate::from_ymd_opt(
+            caps[1].parse().ok()?,
+            caps[2].parse().ok()?,
+            caps[3].parse().ok()?,
+        )?);
+        text = text.replacen(&caps[0].to_string(), " ", 1);
+    }
+
+    for (phrase, offset) in [
+        ("day after tomorrow", 2),
+        ("tomorrow", 1),
+        ("today", 0),
+        ("tonight", 0),
+    ] {
+        if text.contains(phrase) {
+            if date.is_some() {
+                return None;
+            }
+            date = Some(today + Duration::days(offset));
+            if phrase == "tonight" {
+                time = NaiveTime::from_hms_opt(20, 0, 0);
+            }
+            text = text.replacen(phrase, " ", 1);
+            break;
+        }
+    }
+
+    if let Some((weekday, is_next, matched)) = find_weekday(&text) {
+        if date.is_some() {
+            return None;
+        }
+        let current = today.weekday().num_days_from_monday();
+        let target = weekday.num_days_from_monday();
+        let mut ahead = (7 + target - current) % 7;
+        if is_next && ahead == 0 {
+            ahead = 7;
+        }
+        weekday_rolls = !is_next && ahead == 0;
+        date = Some(today + Duration::days(i64::from(ahead)));
+        text = text.replacen(&matched, " ", 1);
+    }
+
+    // Whole words only, so "afternoon" is never read as "noon".
+    let time_of_day = text.split_whitespace().find_map(|word| match word {
+        "noon" => Some((word, 12)),
+        "midnight" => Some((word, 0)),
+        "morning" => Some((word, 9)),
+        "afternoon" => Some((word, 15)),
+        "evening" => Some((word, 18)),
+        _ => None,
+    });
+    if let Some((word, hour)) = time_of_day {
+        time = NaiveTime::from_hms_opt(hour, 0, 0);
+        text = text
+            .split_whitespace()
+            .map(|token| if token == word { " " } else { token })
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+
+    if let Some(caps) = CLOCK_RE.captures(&text) {
+        if time.is_some() && caps.get(3).is_none() && caps.get(2).is_none() {
+            return None;
+        }
+        let mut hour: u32 = caps[1].parse().ok()?;
+        let minute: u32 = caps.get(2).map_or(Some(0), |m| m.as_str().parse().ok())?;
+        match caps.get(3).map(|m| m.as_str()) {
+            Some(meridiem) => {
+                if !(1..=12).contains(&hour) {
+                    return None;
+                }
+                hour %= 12;
+                if meridiem == "pm" {
+                    hour += 12;
+                }
+            }
+            None if hour > 23 => return None,
+            None => {}
+        }
+        time = Some(NaiveTime::from_hms_opt(hour, minute, 0)?);
+        text = text.replacen(&caps[0].to_string(), " ", 1);
+    }
+
+    let leftover_is_filler = text
+        .split_whitespace()
+        .all(|word| matches!(word, "at" | "on" | "the" | "this" | "by" | "in"));
+    if !leftover_is_filler || (date.is_none() && time.is_none()) {
+        return None;
+    }
+
+    let time_only = date.is_none();
+    let date = date.unwrap_or(today);
+    let time = time.unwrap_or_else(|| {
+        NaiveTime::from_hms_opt(DEFAULT_HOUR, 0, 0).expect("valid default hour")
+    });
+    let mut resolved = now
+        .timezone()
+        .from_local_datetime(&date.and_time(time))
+        .earliest()?;
+    if (time_only || weekday_rolls) && resolved <= *now {
+        let days = if time_only { 1 } else { 7 };
+        resolved = now
+            .timezone()
+            .from_local_datetime(&(date + Duration::days(days)).and_time(time))
+            .earliest()?;
+    }
+    Some(resolved)
+}
+
+fn find_weekday(text: &str) -> Option<(Weekday, bool, String)> {
+    const NAMES: [(&str, &str, Weekday); 7] = [
+        ("monday", "mon", Weekday::Mon),
+        ("tuesday", "tue", Weekday::Tue),
+        ("wednesday", "wed", Weekday::Wed),
+        ("thursday", "thu", Weekday::Thu),
+        ("friday", "fri", Weekday::Fri),
+        ("saturday", "sat", Weekday::Sat),
+        ("sunday", "sun", Weekday::Sun),
+    ];
+    let words: Vec<&str> = text.split_whitespace().collect();
+    for (index, word) in words.iter().enumerate() {
+        let Some((_, _, weekday)) = NAMES
+            .iter()
+            .find(|(full, short, _)| word == full || word == short)
+        else {
+            continue;
+        };
+        let is_next = index > 0 && words[index - 1] == "next";
+        let matched = if is_next {
+            format!("next {word}")
+        } else {
+            (*word).to_string()
+        };
+        return Some((*weekday, is_next, matched));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn berlin(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Tz> {
+        chrono_tz::Europe::Berlin
+            .with_ymd_and_hms(y, m, d, h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn resolve_natural_handles_common_expressions() {
+        // Saturday 2026-10-17 10:00 Berlin.
+        let now = berlin(2026, 10, 17, 10, 0);
+        let cases = [
+            ("tomorrow at 9", berlin(2026, 10, 18, 9, 0)),
+            ("Tomorrow 9:30pm", berlin(2026, 10, 18, 21, 30)),
+            ("at 5pm", berlin(2026, 10, 17, 17, 0)),
+            ("8am", berlin(2026, 10, 18, 8, 0)),
+            ("next monday 14:30", berlin(2026, 10, 19, 14, 30)),
+            ("saturday at 9", berlin(2026, 10, 24, 9, 0)),
+            ("friday evening", berlin(2026, 10, 23, 18, 0)),
+            ("tomorrow afternoon", berlin(2026, 10, 18, 15, 0)),
+            ("tomorrow at noon", berlin(2026, 10, 18, 12, 0)),
+            ("tonight", berlin(2026, 10, 17, 20, 0)),
+            ("2026-11-02 8am", berlin(2026, 11, 2, 8, 0)),
+            ("in 90 minutes", berlin(2026, 10, 17, 11, 30)),
+            ("2 days from now", berlin(2026, 10, 19, 10, 0)),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(resolve_natural(input, &now), Some(expected), "{input}");
+        }
+        for input in [
+            "",
+            "someday",
+            "tomorrow at 25",
+            "13pm",
+            "tomorrow next week",
+        ] {
+            assert_eq!(resolve_natural(input, &now), None, "{input}");
+        }
+    }
+
+    #[test]
+    fn overflowing_relative_amounts_are_rejected() {
+        let now = berlin(2026, 10, 17, 10, 0);
+        for input in [
+            "in 99999999999 weeks",
+            "in 9999999999999999 minutes",
+            "in 99999999999999999999 days",
+        ] {
+            assert_eq!(resolve_natural(input, &now), None, "{input}");
+        }
+    }
+
+    #[test]
+    fn user_timezone_resolves_in_configured_zone() {
+        let tz = UserTimezone::from_name(Some("America/New_York"));
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        let resolved = tz.resolve("tomorrow at 9", now).unwrap();
+        assert_eq!(
+            resolved,
+            Utc.with_ymd_and_hms(2026, 10, 18, 13, 0, 0).unwrap()
+        );
+        assert_eq!(
+            tz.resolve("2026-10-20T08:00:00Z", now),
+            Some(Utc.with_ymd_and_hms(2026, 10, 20, 8, 0, 0).unwrap())
+        );
+        assert_eq!(
+            UserTimezone::from_name(Some("Mars/Base")),
+            UserTimezone::HostLocal
+        );
+    }
+
+    #[test]
+    fn identity_overrides_take_precedence() {
+        let config = UiConfig {
+            locale: Some("de".into()),
+            timezone: Some("Europe/Berlin".into()),
+            identity_timezones: HashMap::from([
+                ("telegram:alice".to_string(), "Asia/Tokyo".to_string()),
+                ("bob".to_string(), "America/Chicago".to_string()),
+            ]),
+        };
+        let clock = UserClock::from_config(&config);
+        assert_eq!(
+            clock.timezone_for("telegram", "alice").label(),
+            "Asia/Tokyo"
+        );
+        assert_eq!(
+            clock.timezone_for("slack", "alice").label(),
+            "Europe/Berlin"
+        );
+        assert_eq!(
+            clock.timezone_for("discord", "bob").label(),
+            "America/Chicago"
+        );
+
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        let section = clock.prompt_section(now);
+        assert!(section.starts_with("## Current Date & Time\n\n"));
+        assert!(section.contains("Timezone: Europe/Berlin | Locale: de"));
+        assert!(section.ends_with("Saturday 2026-10-17 14:00:00 (CEST)"));
+        assert!(clock
+            .sender_section("telegram", "alice", now)
+            .contains("Asia/Tokyo; their local time is now Saturday 2026-10-17 21:00:00 (JST)"));
+    }
+}
@@ -1,9 +1,12 @@
 use crate::config::Config;
+use crate::security::shell_dialect::{normalize_windows_path, ShellDialect};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use std::io::Write;
 use std::path::Path;
 
+pub mod security;
+
 const DAEMON_STALE_SECONDS: i64 = 30;
 const SCHEDULER_STALE_SECONDS: i64 = 120;
 const CHANNEL_STALE_SECONDS: i64 = 300;
@@ -80,9 +83,15 @@ pub fn diagnose(config: &Config) -> Vec<DiagResult> {
     let mut items: Vec<DiagItem> = Vec::new();
 
     check_config_semantics(config, &mut items);
+    check_secret_rotation(config, &mut items, Utc::now());
     check_workspace(config, &mut items);
+    check_quotas(config, &mut items);
+    check_retention(config, &mut items);
     check_daemon_state(config, &mut items);
     check_environment(&mut items);
+    if cfg!(windows) {
+        check_windows(config, ShellDialect::host(), &mut items);
+    }
     check_cli_tools(&mut items);
 
     items.into_iter().map(DiagItem::into_result).collect()
@@ -269,6 +278,7 @@ pub fn run_traces(
     event_filter: Option<&str>,
     contains: Option<&str>,
     limit: usize,
+    summarize: bool,
 ) -> Result<()> {
     let path = crate::observability::runtime_trace::resolve_trace_path(
         &config.observability,
@@ -316,6 +326,11 @@ pub fn run_traces(
         return Ok(());
     }
 
+    if summarize {
+        print_trace_summary(&events, &path, event_filter, contains, safe_limit);
+        return Ok(());
+    }
+
     println!("Runtime traces (newest first)");
     println!("Path: {}", path.display());
     println!(
@@ -345,6 +360,62 @@ pub fn run_traces(
     Ok(())
 }
 
+fn print_trace_summary(
+    events: &[crate::observability::runtime_trace::RuntimeTraceEvent],
+    path: &Path,
+    event_filter: Option<&str>,
+    contains: Option<&str>,
+    limit: usize,
+) {
+    use crate::observability::runtime_trace::TraceUsageTotals;
+
+    fn print_group(title: &str, groups: &std::collections::BTreeMap<String, TraceUsageTotals>) {
+        if groups.is_empty() {
+            return;
+        }
+        println!("{title}");
+        for (key, totals) in groups {
+            println!(
+                "  {} | {}",
+                truncate_for_display(key, 48),
+                format_totals(totals)
+            );
+        }
+        println!();
+    }
+
+    fn format_totals(totals: &TraceUsageTotals) -> String {
+        format!(
+            "llm={} tools={} fail={} in={} out={} cached={} cost=${:.4} time={}ms",
+            totals.llm_calls,
+            totals.tool_calls,
+            totals.failures,
+            totals.input_tokens,
+            totals.output_tokens,
+            totals.cached_input_tokens,
+            totals.cost_usd,
+            totals.duration_ms
+        )
+    }
+
+    let summary = crate::observability::runtime_trace::summarize_events(events);
+
+    println!("Runtime trace summary ({} events)", events.len());
+    println!("Path: {}", path.display());
+    println!(
+        "Filters: event={} contains={} limit={}",
+        event_filter.unwrap_or("*"),
+        contains.unwrap_or("*"),
+        limit
+    );
+    println!();
+    println!("Total  | {}", format_totals(&summary.total));
+    println!();
+    print_group("By session", &summary.sessions);
+    print_group("By tool", &summary.tools);
+    print_group("By model", &summary.models);
+}
+
 // ── Config semantic validation ───────────────────────────────────
 
 fn check_config_semantics(config: &Config, items: &mut Vec<DiagItem>) {
@@ -546,6 +617,45 @@ fn check_config_semantics(config: &Config, items: &mut Vec<DiagItem>) {
     }
 }
 
+fn check_secret_rotation(config: &Config, items: &mut Vec<DiagItem>, now: DateTime<Utc>) {
+    let cat = "secrets";
+    let max_days = i64::from(config.secrets.rotation_reminder_days);
+    if max_days == 0 {
+        return;
+    }
+    let secrets = crate::security::rotation::configured_secrets(config);
+    if secrets.is_empty() {
+        return;
+    }
+
+    let history = crate::security::rotation::load_history(config);
+    let mut untracked = Vec::new();
+    for id in secrets {
+        match crate::security::rotation::secret_age_days(config, &history, &id, now) {
+            Some(days) if days >= max_days => items.push(DiagItem::warn(
+                cat,
+                format!(
+                    "{id} last rotated {days} days ago (reminder after {max_days}) — run `zeroclaw security rotate {id}`"
+                ),
+            )),
+            Some(days) => items.push(DiagItem::ok(
+                cat,
+                format!("{id} rotated {days} days ago"),
+            )),
+            None => untracked.push(id),
+        }
+    }
+    if !untracked.is_empty() {
+        items.push(DiagItem::ok(
+            cat,
+            format!(
+                "no rotation history for {} — `zeroclaw security rotate` starts tracking age",
+                untracked.join(", ")
+            ),
+        ));
+    }
+}
+
 fn provider_validation_error(name: &str) -> Option<String> {
     match crate::providers::create_provider(name, None) {
         Ok(_) => None,
@@ -650,6 +760,62 @@ fn check_workspace(config: &Config, items: &mut Vec<DiagItem>) {
     check_file_exists(ws, "AGENTS.md", false, cat, items);
 }
 
+fn check_quotas(config: &Config, items: &mut Vec<DiagItem>) {
+    let cat = "quotas";
+    for usage in crate::quotas::usage(config) {
+        let message = format!("{}: {}", usage.category.name(), usage.summary());
+        if !usage.over_quota() {
+            items.push(DiagItem::ok(cat, message));
+        } else if config.quotas.enabled {
+            items.push(DiagItem::warn(
+                cat,
+                format!("{message} — over quota; the daemon's next gc pass will trim it"),
+            ));
+        } else {
+            items.push(DiagItem::warn(
+                cat,
+                format!("{message} — over quota and [quotas] gc is disabled"),
+            ));
+        }
+    }
+}
+
+fn check_retention(config: &Config, items: &mut Vec<DiagItem>) {
+    let cat = "retention";
+    let now = Utc::now();
+    for status in crate::retention::status(config) {
+        let message = format!("{}: {}", status.class.name(), status.summary());
+        if status.overdue(now) {
+            items.push(DiagItem::warn(
+                cat,
+                format!(
+                    "{message} — records older than {} days; the daemon's next retention pass will delete them",
+                    status.policy.max_days
+                ),
+            ));
+        } else {
+            items.push(DiagItem::ok(cat, message));
+        }
+    }
+
+    let hold = config.retention.conversations.min_days;
+    let memory = &config.memory;
+    for (key, days) in [
+        (
+            "conversation_retention_days",
+            memory.conversation_retention_days,
+        ),
+        ("purge_after_days", memory.purge_after_days),
+    ] {
+        if days > 0 && days < hold {
+            items.push(DiagItem::warn(
+                cat,
+                format!("memory.{key} = {days} is below the {hold}-day legal hold; the hold wins"),
+            ));
+        }
+    }
+}
+
 fn check_file_exists(
     base: &Path,
     name: &str,
@@ -839,10 +1005,12 @@ fn check_environment(items: &mut Vec<DiagItem>) {
     // git
     check_command_available("git", &["--version"], cat, items);
 
-    // Shell
+    // Shell (Windows has no $SHELL; see the `windows` checks)
     let shell = std::env::var("SHELL").unwrap_or_default();
     if shell.is_empty() {
-        items.push(DiagItem::warn(cat, "$SHELL not set"));
+        if !cfg!(windows) {
+            items.push(DiagItem::warn(cat, "$SHELL not set"));
+        }
     } else {
         items.push(DiagItem::ok(cat, format!("shell: {shell}")));
     }
@@ -861,6 +1029,98 @@ fn check_environment(items: &mut Vec<DiagItem>) {
     check_command_available("curl", &["--version"], cat, items);
 }
 
+fn check_windows(config: &Config, dialect: ShellDialect, items: &mut Vec<DiagItem>) {
+    let (program, _) = dialect.program();
+    let version_args: &[&str] = match dialect {
+        ShellDialect::Cmd => &["/D", "/C", "ver"],
+        _ => &[
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "$PSVersionTable.PSVersion.ToString()",
+        ],
+    };
+    check_command_available(program, version_args, "windows", items);
+    check_windows_policy(config, dialect, items);
+}
+
+/// Policy settings that leave gaps on Windows.
+fn check_windows_policy(config: &Config, dialect: ShellDialect, items: &mut Vec<DiagItem>) {
+    let cat = "windows";
+    let autonomy = &config.autonomy;
+
+    let workspace = config.workspace_dir.to_string_lossy();
+    let on_share = if workspace.starts_with("\\\\?\\") {
+        workspace.starts_with("\\\\?\\UNC\\")
+    } else {
+        dialect.is_network_or_device_path(&workspace)
+    };
+    if on_share {
+        items.push(DiagItem::warn(
+            cat,
+            format!("workspace is on a network share ({workspace}); keep it on a local drive"),
+        ));
+    }
+
+    if autonomy.workspace_only {
+        items.push(DiagItem::ok(
+            cat,
+            "workspace_only blocks drive and UNC paths",
+        ));
+    } else {
+        items.push(DiagItem::warn(
+            cat,
+            "workspace_only = false: commands may read and write any drive path (C:\\…) outside forbidden_paths",
+        ));
+    }
+
+    let forbidden: Vec<String> = autonomy
+        .forbidden_paths
+        .iter()
+        .map(|path| normalize_windows_path(path))
+        .collect();
+    let missing: Vec<String> = dialect
+        .system_paths()
+        .into_iter()
+        .filter(|path| !forbidden.contains(&normalize_windows_path(path)))
+        .collect();
+    if missing.is_empty() {
+        items.push(DiagItem::ok(
+            cat,
+            "Windows system directories are forbidden",
+        ));
+    } else {
+        items.push(DiagItem::warn(
+            cat,
+            format!(
+                "forbidden_paths does not cover: {} (add them to [autonomy].forbidden_paths)",
+                missing.join(", ")
+            ),
+        ));
+    }
+
+    let shells: Vec<&str> = autonomy
+        .allowed_commands
+        .iter()
+        .map(String::as_str)
+        .filter(|command| {
+            matches!(
+                dialect.command_name(command).as_str(),
+                "cmd" | "powershell" | "pwsh" | "bash" | "wsl"
+            )
+        })
+        .collect();
+    if !shells.is_empty() {
+        items.push(DiagItem::warn(
+            cat,
+            format!(
+                "allowed_commands includes a shell ({}); commands passed to it bypass the allowlist",
+                shells.join(", ")
+            ),
+        ));
+    }
+}
+
 fn check_cli_tools(items: &mut Vec<DiagItem>) {
     let cat = "cli-tools";
 
@@ -1242,4 +1502,70 @@ mod tests {
         assert!(agent_messages[0].contains("agent \"alpha\""));
         assert!(agent_messages[1].contains("agent \"zeta\""));
     }
+
+    #[test]
+    fn secret_rotation_warns_about_old_secrets() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.api_key = Some("sk-old".into());
+        config.web_search.brave_api_key = Some("brave".into());
+        let rotated_at = Utc::now() - chrono::Duration::days(120);
+        std::fs::write(
+            tmp.path().join("secret-rotations.json"),
+            serde_json::json!({
+                "api_key": {
+                    "rotated_at": rotated_at,
+                    "fingerprint": crate::security::rotation::fingerprint("sk-old"),
+                    "method": "manual",
+                }
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let mut items = Vec::new();
+        check_secret_rotation(&config, &mut items, Utc::now());
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].severity, Severity::Warn);
+        assert!(items[0]
+            .message
+            .contains("api_key last rotated 120 days ago"));
+        assert_eq!(items[1].severity, Severity::Ok);
+        assert!(items[1].message.contains("web_search.brave_api_key"));
+
+        config.secrets.rotation_reminder_days = 0;
+        let mut items = Vec::new();
+        check_secret_rotation(&config, &mut items, Utc::now());
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn windows_policy_gaps_are_reported() {
+        let mut config = Config::default();
+        config.workspace_dir = "\\\\fileserver\\share\\ws".into();
+        config.autonomy.workspace_only = false;
+        config.autonomy.allowed_commands = vec!["git".into(), "PowerShell.exe".into()];
+        let mut items = Vec::new();
+        check_windows_policy(&config, ShellDialect::PowerShell, &mut items);
+        let warnings: Vec<&str> = items
+            .iter()
+            .filter(|item| item.severity == Severity::Warn)
+            .map(|item| item.message.as_str())
+            .collect();
+        assert_eq!(warnings.len(), 4, "{warnings:?}");
+        assert!(warnings[0].contains("network share"));
+        assert!(warnings[2].contains("C:\\Windows"));
+        assert!(warnings[3].contains("PowerShell.exe"));
+
+        config.workspace_dir = "C:\\Users\\me\\.zeroclaw\\workspace".into();
+        config.autonomy.workspace_only = true;
+        config.autonomy.allowed_commands = vec!["git".into()];
+        config.autonomy.forbidden_paths = ShellDialect::Cmd.system_paths();
+        let mut items = Vec::new();
+        check_windows_policy(&config, ShellDialect::Cmd, &mut items);
+        assert!(items.iter().all(|item| item.severity == Severity::Ok));
+    }
 }
@@ -0,0 +1,414 @@
+//! `zeroclaw doctor security` — guardrail posture audit.
+//!
+//! Compares the effective configuration against a hardening baseline and
+//! reports what falls short, most urgent first, each with the command or
+//! config change that fixes it.
+
+use crate::config::Config;
+use crate::security::pairing::is_public_bind;
+use crate::security::AutonomyLevel;
+use anyhow::Result;
+
+/// Tools whose automatic approval lets the agent change the host unattended.
+const SIDE_EFFECT_TOOLS: &[&str] = &[
+    "shell",
+    "file_write",
+    "file_edit",
+    "browser",
+    "http_request",
+];
+
+/// Interpreters that make a command allowlist meaningless.
+const INTERPRETERS: &[&str] = &[
+    "sh", "bash", "zsh", "python", "python3", "node", "perl", "ruby",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl Priority {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Critical => "CRITICAL",
+            Self::High => "HIGH",
+            Self::Medium => "MEDIUM",
+            Self::Low => "LOW",
+        }
+    }
+}
+
+/// One way the configuration falls short of the baseline.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Finding {
+    pub priority: Priority,
+    /// Stable identifier, e.g. `gateway.public_unpaired`.
+    pub id: &'static str,
+    pub message: String,
+    /// Command to run or config change to make.
+    pub fix: String,
+}
+
+impl Finding {
+    fn new(
+        priority: Priority,
+        id: &'static str,
+        message: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            priority,
+            id,
+            message: message.into(),
+            fix: fix.into(),
+        }
+    }
+}
+
+/// Evaluate `config` against the hardening baseline, most urgent first.
+pub fn audit(config: &Config) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let exposed = gateway_exposed(config);
+
+    check_autonomy(config, exposed, &mut findings);
+    check_tool_allowlists(config, &mut findings);
+    check_gateway(config, exposed, &mut findings);
+    check_secrets(config, &mut findings);
+    check_estop_and_otp(config, &mut findings);
+
+    findings.sort_by_key(|finding| finding.priority);
+    findings
+}
+
+/// Print the audit, or its JSON form with `json`.
+pub fn run(config: &Config, json: bool) -> Result<()> {
+    let findings = audit(config);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&findings)?);
+        return Ok(());
+    }
+
+    println!("🛡️  ZeroClaw Doctor — security posture");
+    println!();
+    if findings.is_empty() {
+        println!("  ✅ Configuration meets the hardening baseline.");
+        return Ok(());
+    }
+    for finding in &findings {
+        println!("  [{}] {}", finding.priority.label(), finding.message);
+        println!("      fix: {}", finding.fix);
+    }
+
+    let count = |priority| findings.iter().filter(|f| f.priority == priority).count();
+    println!();
+    println!(
+        "  Summary: {} critical, {} high, {} medium, {} low",
+        count(Priority::Critical),
+        count(Priority::High),
+        count(Priority::Medium),
+        count(Priority::Low)
+    );
+    Ok(())
+}
+
+/// Whether the gateway listens beyond localhost without a tunnel in front.
+fn gateway_exposed(config: &Config) -> bool {
+    is_public_bind(&config.gateway.host) && config.tunnel.provider == "none"
+}
+
+/// Profile to suggest in fix commands: the current one when it is a named
+/// supervised profile, otherwise `balanced`.
+fn suggested_profile(config: &Config) -> &'static str {
+    match crate::onboard::security_profile_id_from_autonomy(&config.autonomy) {
+        profile @ ("strict" | "balanced" | "flexible") => profile,
+        _ => "balanced",
+    }
+}
+
+fn check_autonomy(config: &Config, exposed: bool, findings: &mut Vec<Finding>) {
+    let autonomy = &config.autonomy;
+    let profile = suggested_profile(config);
+    let set_profile = format!("zeroclaw security profile set {profile}");
+
+    if autonomy.level == AutonomyLevel::Full {
+        findings.push(Finding::new(
+            if exposed {
+                Priority::Critical
+            } else {
+                Priority::High
+            },
+            "autonomy.full",
+            "autonomy level is `full`: tools run without approval",
+            set_profile.clone(),
+        ));
+    }
+    if !autonomy.block_high_risk_commands {
+        findings.push(Finding::new(
+            Priority::High,
+            "autonomy.high_risk_allowed",
+            "high-risk shell commands are not blocked",
+            set_profile.clone(),
+        ));
+    }
+    if autonomy.allow_non_cli_auto_approval {
+        findings.push(Finding::new(
+            Priority::High,
+            "autonomy.non_cli_auto_approval",
+            "chat channels (Telegram, Discord, ...) may auto-approve guarded tools",
+            format!("zeroclaw security profile set {profile} --non-cli-approval manual"),
+        ));
+    }
+    if autonomy.level == AutonomyLevel::Supervised && !autonomy.require_approval_for_medium_risk {
+        findings.push(Finding::new(
+            Priority::Medium,
+            "autonomy.medium_risk_unapproved",
+            "medium-risk shell commands run without approval",
+            set_profile.clone(),
+        ));
+    }
+    if !autonomy.workspace_only {
+        findings.push(Finding::new(
+            Priority::Medium,
+            "autonomy.outside_workspace",
+            "file tools may reach absolute paths outside the workspace",
+            "set `[autonomy] workspace_only = true` and list extra directories in `allowed_roots`",
+        ));
+    }
+}
+
+fn check_tool_allowlists(config: &Config, findings: &mut Vec<Finding>) {
+    let autonomy = &config.autonomy;
+
+    if autonomy.allowed_commands.iter().any(|cmd| cmd == "*") {
+        findings.push(Finding::new(
+            Priority::High,
+            "tools.any_command",
+            "`allowed_commands` contains `*`: any executable may run",
+            "replace `*` in `[autonomy] allowed_commands` with the executables you need",
+        ));
+    }
+    let interpreters: Vec<&str> = autonomy
+        .allowed_commands
+        .iter()
+        .map(String::as_str)
+        .filter(|cmd| INTERPRETERS.contains(cmd))
+        .collect();
+    if !interpreters.is_empty() {
+        findings.push(Finding::new(
+            Priority::Medium,
+            "tools.interpreters_allowed",
+            format!(
+                "`allowed_commands` includes interpreters ({}) that can run arbitrary code",
+                interpreters.join(", ")
+            ),
+            "remove interpreters from `[autonomy] allowed_commands`",
+        ));
+    }
+
+    let auto_approved: Vec<&str> = autonomy
+        .auto_approve
+        .iter()
+        .map(String::as_str)
+        .filter(|tool| *tool == "*" || SIDE_EFFECT_TOOLS.contains(tool))
+        .collect();
+    if !auto_approved.is_empty() {
+        findings.push(Finding::new(
+            Priority::High,
+            "tools.side_effects_auto_approved",
+            format!(
+                "tools with side effects never ask for approval: {}",
+                auto_approved.join(", ")
+            ),
+            format!(
+                "remove {} from `[autonomy] auto_approve`",
+                auto_approved.join(", ")
+            ),
+        ));
+    }
+}
+
+fn check_gateway(config: &Config, exposed: bool, findings: &mut Vec<Finding>) {
+    let gateway = &config.gateway;
+
+    if exposed && gateway.allow_public_bind {
+        findings.push(if gateway.require_pairing {
+            Finding::new(
+                Priority::High,
+                "gateway.public_bind",
+                format!(
+                    "gateway binds {} without a tunnel (`allow_public_bind = true`)",
+                    gateway.host
+                ),
+                "set `[gateway] host = \"127.0.0.1\"` and expose it through `[tunnel]`",
+            )
+        } else {
+            Finding::new(
+                Priority::Critical,
+                "gateway.public_unpaired",
+                format!(
+                    "gateway binds {} without a tunnel and accepts unpaired requests",
+                    gateway.host
+                ),
+                "set `[gateway] require_pairing = true` and `host = \"127.0.0.1\"`",
+            )
+        });
+    } else if !gateway.require_pairing {
+        findings.push(Finding::new(
+            Priority::High,
+            "gateway.pairing_disabled",
+            "gateway accepts requests without pairing",
+            "set `[gateway] require_pairing = true`",
+        ));
+    }
+
+    if gateway.trust_forwarded_headers && gateway.trusted_proxies.is_empty() {
+        findings.push(Finding::new(
+            Priority::Medium,
+            "gateway.untrusted_forwarded_headers",
+            "gateway believes `X-Forwarded-For` from any peer, so rate limits can be spoofed",
+            "list your reverse proxies in `[gateway] trusted_proxies`",
+        ));
+    }
+}
+
+fn check_secrets(config: &Config, findings: &mut Vec<Finding>) {
+    if !config.secrets.encrypt {
+        findings.push(Finding::new(
+            Priority::High,
+            "secrets.plaintext",
+            "API keys and tokens are stored in config.toml unencrypted",
+            "set `[secrets] encrypt = true`, then `zeroclaw security rotate <secret>` to re-save each key",
+        ));
+    }
+}
+
+fn check_estop_and_otp(config: &Config, findings: &mut Vec<Finding>) {
+    let estop = &config.security.estop;
+    let otp = &config.security.otp;
+    let unattended = config.autonomy.level == AutonomyLevel::Full;
+
+    if !estop.enabled {
+        findings.push(Finding::new(
+            if unattended {
+                Priority::High
+            } else {
+                Priority::Medium
+            },
+            "estop.disabled",
+            "emergency stop is disabled: a runaway agent cannot be halted from the CLI",
+            "set `[security.estop] enabled = true`",
+        ));
+    } else if !estop.require_otp_to_resume || !otp.enabled {
+        findings.push(Finding::new(
+            Priority::Low,
+            "estop.resume_unprotected",
+            "resuming from an emergency stop does not require an OTP",
+            "set `[security.estop] require_otp_to_resume = true` and `[security.otp] enabled = true`",
+        ));
+    }
+
+    if !otp.enabled {
+        findings.push(Finding::new(
+            if unattended {
+                Priority::Medium
+            } else {
+                Priority::Low
+            },
+            "otp.disabled",
+            "no OTP gating for sensitive actions",
+            "set `[security.otp] enabled = true`",
+        ));
+    } else if otp.gated_actions.is_empty()
+        && otp.gated_domains.is_empty()
+        && otp.gated_domain_categories.is_empty()
+    {
+        findings.push(Finding::new(
+            Priority::Low,
+            "otp.nothing_gated",
+            "OTP is enabled but gates no actions or domains",
+            "list tools in `[security.otp] gated_actions`",
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(findings: &[Finding]) -> Vec<&'static str> {
+        findings.iter().map(|finding| finding.id).collect()
+    }
+
+    #[test]
+    fn hardened_config_passes_baseline() {
+        let mut config = Config::default();
+        config.security.estop.enabled = true;
+        config.security.otp.enabled = true;
+        assert!(audit(&config).is_empty(), "{:?}", audit(&config));
+    }
+
+    #[test]
+    fn defaults_only_flag_missing_estop_and_otp() {
+        let findings = audit(&Config::default());
+        assert_eq!(ids(&findings), vec!["estop.disabled", "otp.disabled"]);
+        assert_eq!(findings[0].priority, Priority::Medium);
+    }
+
+    #[test]
+    fn exposed_unpaired_gateway_is_critical_and_sorted_first() {
+        let mut config = Config::default();
+        config.gateway.host = "0.0.0.0".into();
+        config.gateway.allow_public_bind = true;
+        config.gateway.require_pairing = false;
+        config.autonomy.allow_non_cli_auto_approval = true;
+
+        let findings = audit(&config);
+        assert_eq!(findings[0].id, "gateway.public_unpaired");
+        assert_eq!(findings[0].priority, Priority::Critical);
+        let non_cli = findings
+            .iter()
+            .find(|f| f.id == "autonomy.non_cli_auto_approval")
+            .unwrap();
+        assert_eq!(non_cli.priority, Priority::High);
+        assert!(non_cli.fix.contains("--non-cli-approval manual"));
+    }
+
+    #[test]
+    fn tunnel_in_front_of_gateway_is_not_exposure() {
+        let mut config = Config::default();
+        config.gateway.host = "0.0.0.0".into();
+        config.gateway.allow_public_bind = true;
+        config.tunnel.provider = "cloudflare".into();
+        config.autonomy.level = AutonomyLevel::Full;
+
+        let findings = audit(&config);
+        assert!(!ids(&findings).contains(&"gateway.public_bind"));
+        let full = findings.iter().find(|f| f.id == "autonomy.full").unwrap();
+        assert_eq!(full.priority, Priority::High);
+    }
+
+    #[test]
+    fn flags_broad_allowlists_and_plaintext_secrets() {
+        let mut config = Config::default();
+        config.autonomy.allowed_commands = vec!["git".into(), "bash".into(), "*".into()];
+        config.autonomy.auto_approve = vec!["file_read".into(), "shell".into()];
+        config.secrets.encrypt = false;
+
+        let findings = audit(&config);
+        let found = ids(&findings);
+        assert!(found.contains(&"tools.any_command"));
+        assert!(found.contains(&"tools.interpreters_allowed"));
+        assert!(found.contains(&"secrets.plaintext"));
+        let approved = findings
+            .iter()
+            .find(|f| f.id == "tools.side_effects_auto_approved")
+            .unwrap();
+        assert!(approved.message.ends_with("shell"));
+    }
+}
@@ -0,0 +1,376 @@
+//! Error taxonomy shared across module boundaries.
+//!
+//! Most of the crate passes `anyhow::Error` around, which is fine inside a
+//! module but leaves callers guessing whether a failure is worth retrying.
+//! Boundary code either raises a [`ClassifiedError`] directly or lets
+//! [`classify`] infer the class from typed errors (`reqwest`, I/O,
+//! [`ProviderCapabilityError`]) and, as a last resort, from the message.
+//! Each [`ErrorClass`] carries a [`RetryPolicy`] and a stable
+//! machine-readable code used in JSON output and runtime traces.
+
+use crate::providers::traits::ProviderCapabilityError;
+use serde::Serialize;
+
+/// Broad failure categories with stable codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    /// Missing, invalid or rejected credentials.
+    Auth,
+    /// Throttled by a provider or by the local action budget.
+    RateLimit,
+    /// Connection failures, timeouts and server-side (5xx) errors.
+    Network,
+    /// The request itself is wrong: bad arguments, unknown model, oversized
+    /// context, unsupported capability.
+    Validation,
+    /// Blocked by security policy, approval or a hook.
+    PolicyDenied,
+    /// A tool ran and reported failure.
+    ToolFailure,
+    /// Anything not recognised above.
+    Internal,
+}
+
+/// How callers should react to a class of error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RetryPolicy {
+    /// Retrying the same request (with backoff) may succeed.
+    pub retriable: bool,
+    /// Falling back to another provider or model may succeed.
+    pub fallback: bool,
+}
+
+impl ErrorClass {
+    /// Stable code used in JSON output and traces.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Auth => "auth",
+            Self::RateLimit => "rate_limit",
+            Self::Network => "network",
+            Self::Validation => "validation",
+            Self::PolicyDenied => "policy_denied",
+            Self::ToolFailure => "tool_failure",
+            Self::Internal => "internal",
+        }
+    }
+
+    pub fn retry_policy(self) -> RetryPolicy {
+        let (retriable, fallback) = match self {
+            Self::RateLimit | Self::Network | Self::Internal => (true, true),
+            // Another provider has other credentials, and another model may
+            // accept what this one rejected.
+            Self::Auth | Self::Validation => (false, true),
+            // Policy denials are deliberate and tool failures may have side
+            // effects; neither is retried or routed elsewhere.
+            Self::PolicyDenied | Self::ToolFailure => (false, false),
+        };
+        RetryPolicy {
+            retriable,
+            fallback,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// An error whose class is known where it was raised.
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct ClassifiedError {
+    pub class: ErrorClass,
+    pub message: String,
+}
+
+impl ClassifiedError {
+    pub fn new(class: ErrorClass, message: impl Into<String>) -> Self {
+        Self {
+            class,
+            message: message.into(),
+        }
+    }
+}
+
+/// Machine-readable error body for JSON output.
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorReport {
+    pub code: ErrorClass,
+    pub retriable: bool,
+    pub message: String,
+}
+
+impl ErrorReport {
+    pub fn new(class: ErrorClass, message: impl Into<String>) -> Self {
+        Self {
+            code: class,
+            retriable: class.retry_policy().retriable,
+            message: message.into(),
+        }
+    }
+
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        Self::new(classify(err), format!("{err:#}"))
+    }
+}
+
+/// Classify an error, preferring typed information anywhere in the chain
+/// over message heuristics.
+pub fn classify(err: &anyhow::Error) -> ErrorClass {
+    for cause in err.chain() {
+        if let Some(classified) = cause.downcast_ref::<ClassifiedError>() {
+            return classified.class;
+        }
+        if cause.downcast_ref::<ProviderCapabilityError>().is_some() {
+            return ErrorClass::Validation;
+        }
+    }
+
+    let message = err.to_string();
+    if is_context_window_exceeded(&message) {
+        return ErrorClass::Validation;
+    }
+
+    for cause in err.chain() {
+        if let Some(reqwest_err) = cause.downcast_ref::<reqwest::Error>() {
+            if let Some(status) = reqwest_err.status() {
+                return classify_status(status.as_u16());
+            }
+            if reqwest_err.is_timeout() || reqwest_err.is_connect() {
+                return ErrorClass::Network;
+            }
+        }
+        if let Some(io_err) = cause.downcast_ref::<std::io::Error>() {
+            use std::io::ErrorKind;
+            match io_err.kind() {
+                ErrorKind::PermissionDenied => return ErrorClass::PolicyDenied,
+                ErrorKind::NotFound | ErrorKind::InvalidInput | ErrorKind::InvalidData => {
+                    return ErrorClass::Validation
+                }
+                ErrorKind::TimedOut
+                | ErrorKind::ConnectionRefused
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::BrokenPipe => return ErrorClass::Network,
+                _ => {}
+            }
+        }
+    }
+
+    classify_message(&message).unwrap_or(ErrorClass::Internal)
+}
+
+/// Class for an HTTP status code.
+pub fn classify_status(code: u16) -> ErrorClass {
+    match code {
+        401 | 403 => ErrorClass::Auth,
+        429 => ErrorClass::RateLimit,
+        408 => ErrorClass::Network,
+        400..=499 => ErrorClass::Validation,
+        _ => ErrorClass::Network,
+    }
+}
+
+/// Whether a message reports that the prompt did not fit the context window.
+pub fn is_context_window_exceeded(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    [
+        "exceeds the context window",
+        "context window of this model",
+        "maximum context length",
+        "context length exceeded",
+        "too many tokens",
+        "token limit exceeded",
+        "prompt is too long",
+        "input is too long",
+    ]
+    .iter()
+    .any(|hint| lower.contains(hint))
+}
+
+/// Heuristics for stringly errors: embedded status codes from providers
+/// that do not return typed HTTP errors, then well-known phrases.
+fn classify_message(message: &str) -> Option<ErrorClass> {
+    let lower = message.to_lowercase();
+
+    if message.contains("429")
+        && (message.contains("Too Many") || message.contains("rate") || message.contains("limit"))
+    {
+        return Some(ErrorClass::RateLimit);
+    }
+
+    for word in message.split(|c: char| !c.is_ascii_digit()) {
+        if let Ok(code) = word.parse::<u16>() {
+            if (400..500).contains(&code) {
+                return Some(classify_status(code));
+            }
+        }
+    }
+
+    let auth_hints = [
+        "invalid api key",
+        "incorrect api key",
+        "missing api key",
+        "api key not set",
+        "authentication failed",
+        "auth failed",
+        "unauthorized",
+        "forbidden",
+        "permission denied",
+        "access denied",
+        "invalid token",
+    ];
+    if auth_hints.iter().any(|hint| lower.contains(hint)) {
+        return Some(ErrorClass::Auth);
+    }
+
+    let policy_hints = [
+        "security policy",
+        "action blocked",
+        "denied by user",
+        "cancelled by hook",
+        "not allowed",
+        "path traversal",
+    ];
+    if policy_hints.iter().any(|hint| lower.contains(hint)) {
+        return Some(ErrorClass::PolicyDenied);
+    }
+
+    if lower.contains("rate limit") || lower.contains("too many requests") {
+        return Some(ErrorClass::RateLimit);
+    }
+
+    if lower.contains("model")
+        && (lower.contains("not found")
+            || lower.contains("unknown")
+            || lower.contains("unsupported")
+            || lower.contains("does not exist")
+            || lower.contains("invalid"))
+    {
+        return Some(ErrorClass::Validation);
+    }
+
+    let network_hints = [
+        "timed out",
+        "timeout",
+        "connection refused",
+        "connection reset",
+        "connection closed",
+        "dns error",
+        "network",
+        "unreachable",
+        "overloaded",
+        "service unavailable",
+        "bad gateway",
+    ];
+    if network_hints.iter().any(|hint| lower.contains(hint)) {
+        return Some(ErrorClass::Network);
+    }
+
+    None
+}
+
+/// Class for a failed tool call, given the tool's error text. Tools report
+/// failures as strings, so this is heuristic; anything unrecognised is a
+/// plain [`ErrorClass::ToolFailure`].
+pub fn classify_tool_failure(reason: &str) -> ErrorClass {
+    let lower = reason.to_lowercase();
+    if lower.starts_with("unknown tool")
+        || lower.starts_with("missing ")
+        || lower.starts_with("invalid ")
+        || lower.contains("skipped duplicate tool call")
+    {
+        return ErrorClass::Validation;
+    }
+    // The local action budget is a policy limit, not a provider throttle.
+    if lower.starts_with("rate limit exceeded") {
+        return ErrorClass::PolicyDenied;
+    }
+    match classify_message(reason) {
+        Some(class @ (ErrorClass::Auth | ErrorClass::PolicyDenied | ErrorClass::Network)) => class,
+        _ => ErrorClass::ToolFailure,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_typed_errors_before_messages() {
+        let err = anyhow::Error::new(ClassifiedError::new(
+            ErrorClass::PolicyDenied,
+            "500 but blocked by policy",
+        ))
+        .context("running tool");
+        assert_eq!(classify(&err), ErrorClass::PolicyDenied);
+
+        let io = anyhow::Error::new(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+        assert_eq!(classify(&io), ErrorClass::Network);
+
+        let capability = anyhow::Error::new(ProviderCapabilityError {
+            provider: "p".into(),
+            capability: "vision".into(),
+            message: "no images".into(),
+        });
+        assert_eq!(classify(&capability), ErrorClass::Validation);
+    }
+
+    #[test]
+    fn classifies_provider_messages() {
+        let cases = [
+            ("401 Unauthorized", ErrorClass::Auth),
+            ("invalid api key provided", ErrorClass::Auth),
+            ("429 Too Many Requests", ErrorClass::RateLimit),
+            ("408 Request Timeout", ErrorClass::Network),
+            ("404 Not Found", ErrorClass::Validation),
+            ("unsupported model: glm-4.7", ErrorClass::Validation),
+            (
+                "Your input exceeds the context window of this model.",
+                ErrorClass::Validation,
+            ),
+            ("connection reset", ErrorClass::Network),
+            ("model overloaded, try again later", ErrorClass::Network),
+            ("something odd happened", ErrorClass::Internal),
+        ];
+        for (message, expected) in cases {
+            assert_eq!(
+                classify(&anyhow::anyhow!("{message}")),
+                expected,
+                "{message}"
+            );
+        }
+        assert!(ErrorClass::RateLimit.retry_policy().retriable);
+        assert!(!ErrorClass::Auth.retry_policy().retriable);
+        assert!(!ErrorClass::ToolFailure.retry_policy().fallback);
+    }
+
+    #[test]
+    fn classifies_tool_failures_and_serializes_reports() {
+        assert_eq!(
+            classify_tool_failure("Security policy: read-only mode, cannot perform 'shell'"),
+            ErrorClass::PolicyDenied
+        );
+        assert_eq!(
+            classify_tool_failure("Rate limit exceeded: action budget exhausted"),
+            ErrorClass::PolicyDenied
+        );
+        assert_eq!(
+            classify_tool_failure("Unknown tool: nope"),
+            ErrorClass::Validation
+        );
+        assert_eq!(
+            classify_tool_failure("exit status 1: grep found nothing"),
+            ErrorClass::ToolFailure
+        );
+
+        let report = ErrorReport::from_error(&anyhow::anyhow!("403 Forbidden"));
+        assert_eq!(
+            serde_json::to_value(report).unwrap(),
+            serde_json::json!({"code": "auth", "retriable": false, "message": "403 Forbidden"})
+        );
+    }
+}
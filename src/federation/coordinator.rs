@@ -0,0 +1,301 @@
+//! Coordinator side of federation: authenticates worker nodes, keeps a
+//! registry of connected nodes and forwards tool calls to them.
+
+use super::{NodeFrame, HANDSHAKE_TIMEOUT_SECS};
+use crate::config::FederationConfig;
+use crate::security::pairing::constant_time_eq;
+use crate::tools::{ToolResult, ToolSpec};
+use anyhow::Result;
+use axum::extract::ws::{Message, WebSocket};
+use chrono::{DateTime, Utc};
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+/// Calls queued for one node before new calls are refused.
+const OUTBOUND_CAPACITY: usize = 64;
+
+/// A worker node currently connected to this coordinator.
+pub struct RemoteNode {
+    pub name: String,
+    pub tools: Vec<ToolSpec>,
+    pub connected_at: DateTime<Utc>,
+    outbound: mpsc::Sender<NodeFrame>,
+    pending: Mutex<HashMap<String, oneshot::Sender<ToolResult>>>,
+}
+
+impl RemoteNode {
+    fn resolve(&self, id: &str, result: ToolResult) {
+        if let Some(waiter) = self.pending.lock().remove(id) {
+            let _ = waiter.send(result);
+        }
+    }
+}
+
+static NODES: LazyLock<RwLock<HashMap<String, Arc<RemoteNode>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Check a node's credentials against `[federation.nodes]`.
+pub fn authenticate(config: &FederationConfig, node: &str, token: &str) -> bool {
+    config
+        .nodes
+        .get(node)
+        .is_some_and(|entry| constant_time_eq(entry.token.trim(), token.trim()))
+}
+
+/// Register a connected node, replacing an older connection with the same name.
+fn register(name: &str, tools: Vec<ToolSpec>) -> (Arc<RemoteNode>, mpsc::Receiver<NodeFrame>) {
+    let (outbound, rx) = mpsc::channel(OUTBOUND_CAPACITY);
+    let node = Arc::new(RemoteNode {
+        name: name.to_string(),
+        tools,
+        connected_at: Utc::now(),
+        outbound,
+        pending: Mutex::new(HashMap::new()),
+    });
+    NODES.write().insert(name.to_string(), Arc::clone(&node));
+    (node, rx)
+}
+
+/// Remove `node` unless a newer connection has already replaced it.
+fn unregister(node: &Arc<RemoteNode>) {
+    let mut nodes = NODES.write();
+    if nodes
+        .get(&node.name)
+        .is_some_and(|current| Arc::ptr_eq(current, node))
+    {
+        nodes.remove(&node.name);
+    }
+}
+
+/// Nodes currently connected, sorted by name.
+pub fn connected_nodes() -> Vec<Arc<RemoteNode>> {
+    let mut nodes: Vec<_> = NODES.read().values().cloned().collect();
+    nodes.sort_by(|a, b| a.name.cmp(&b.name));
+    nodes
+}
+
+/// Run `tool` on a connected node and wait for its result.
+pub async fn call(
+    node_name: &str,
+    tool: &str,
+    args: serde_json::Value,
+    timeout: Duration,
+) -> Result<ToolResult> {
+    let node = NODES
+        .read()
+        .get(node_name)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Node '{node_name}' is not connected"))?;
+    if !node.tools.iter().any(|spec| spec.name == tool) {
+        anyhow::bail!("Node '{node_name}' does not expose tool '{tool}'");
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let (tx, rx) = oneshot::channel();
+    node.pending.lock().insert(id.clone(), tx);
+    let frame = NodeFrame::Call {
+        id: id.clone(),
+        tool: tool.to_string(),
+        args,
+    };
+    if node.outbound.try_send(frame).is_err() {
+        node.pending.lock().remove(&id);
+        anyhow::bail!("Node '{node_name}' is busy or disconnecting");
+    }
+
+    match tokio::time::timeout(timeout, rx).await {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(_)) => anyhow::bail!("Node '{node_name}' disconnected before answering"),
+        Err(_) => {
+            node.pending.lock().remove(&id);
+            anyhow::bail!(
+                "Node '{node_name}' did not answer within {}s",
+                timeout.as_secs()
+            )
+        }
+    }
+}
+
+async fn send_frame(
+    sender: &mut futures_util::stream::SplitSink<WebSocket, Message>,
+    frame: &NodeFrame,
+) -> bool {
+    sender
+        .send(Message::Text(frame.to_text().into()))
+        .await
+        .is_ok()
+}
+
+/// Serve one upgraded `/ws/node` connection until it closes.
+pub async fn serve_node(
+    socket: WebSocket,
+    config: FederationConfig,
+    on_auth_failure: impl FnOnce() + Send,
+) {
+    let (mut sender, mut receiver) = socket.split();
+
+    let hello =
+        tokio::time::timeout(Duration::from_secs(HANDSHAKE_TIMEOUT_SECS), receiver.next()).await;
+    let Ok(Some(Ok(Message::Text(text)))) = hello else {
+        return;
+    };
+    let Some(NodeFrame::Hello { node, token, tools }) = NodeFrame::parse(&text) else {
+        let _ = send_frame(
+            &mut sender,
+            &NodeFrame::Error {
+                message: "expected hello".into(),
+            },
+        )
+        .await;
+        return;
+    };
+    if !authenticate(&config, &node, &token) {
+        tracing::warn!(node = %node, "Federation node failed authentication");
+        on_auth_failure();
+        let _ = send_frame(
+            &mut sender,
+            &NodeFrame::Error {
+                message: "authentication failed".into(),
+            },
+        )
+        .await;
+        return;
+    }
+
+    let tool_count = tools.len();
+    let (remote, mut outbound) = register(&node, tools);
+    if !send_frame(&mut sender, &NodeFrame::Welcome).await {
+        unregister(&remote);
+        return;
+    }
+    tracing::info!(node = %node, tools = tool_count, "Federation node connected");
+
+    loop {
+        tokio::select! {
+            frame = outbound.recv() => {
+                let Some(frame) = frame else { break };
+                if !send_frame(&mut sender, &frame).await {
+                    break;
+                }
+            }
+            message = receiver.next() => {
+                match message {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Some(NodeFrame::Result { id, success, output, error }) =
+                            NodeFrame::parse(&text)
+                        {
+                            remote.resolve(&id, ToolResult { success, output, error });
+                        }
+                    }
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+    }
+
+    unregister(&remote);
+    tracing::info!(node = %node, "Federation node disconnected");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FederationNodeConfig;
+    use serde_json::json;
+
+    fn spec(name: &str) -> ToolSpec {
+        ToolSpec {
+            name: name.into(),
+            description: String::new(),
+            parameters: json!({"type": "object"}),
+        }
+    }
+
+    #[test]
+    fn authenticate_requires_known_node_and_matching_token() {
+        let mut config = FederationConfig::default();
+        config.nodes.insert(
+            "kitchen-pi".into(),
+            FederationNodeConfig {
+                token: "0123456789abcdef".into(),
+            },
+        );
+        assert!(authenticate(&config, "kitchen-pi", "0123456789abcdef"));
+        assert!(!authenticate(&config, "kitchen-pi", "wrong-token-value"));
+        assert!(!authenticate(&config, "garage-pi", "0123456789abcdef"));
+    }
+
+    #[tokio::test]
+    async fn call_round_trips_through_registered_node() {
+        let (node, mut outbound) = register("test-roundtrip", vec![spec("gpio_read")]);
+        let worker = tokio::spawn(async move {
+            let Some(NodeFrame::Call { id, args, .. }) = outbound.recv().await else {
+                panic!("expected call");
+            };
+            node.resolve(
+                &id,
+                ToolResult {
+                    success: true,
+                    output: format!("pin {}", args["pin"]),
+                    error: None,
+                },
+            );
+            node
+        });
+
+        let result = call(
+            "test-roundtrip",
+            "gpio_read",
+            json!({"pin": 4}),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.output, "pin 4");
+
+        let missing = call("test-roundtrip", "shell", json!({}), Duration::from_secs(5))
+            .await
+            .unwrap_err();
+        assert!(missing.to_string().contains("does not expose"));
+
+        let node = worker.await.unwrap();
+        unregister(&node);
+        assert!(call(
+            "test-roundtrip",
+            "gpio_read",
+            json!({}),
+            Duration::from_secs(1)
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn reconnect_replaces_node_and_stale_unregister_is_ignored() {
+        let (old, _old_rx) = register("test-replace", vec![spec("a")]);
+        let (new, _new_rx) = register("test-replace", vec![spec("b")]);
+        unregister(&old);
+        let current = connected_nodes()
+            .into_iter()
+            .find(|node| node.name == "test-replace")
+            .unwrap();
+        assert!(Arc::ptr_eq(&current, &new));
+        unregister(&new);
+    }
+
+    #[tokio::test]
+    async fn unanswered_call_times_out() {
+        let (node, _outbound) = register("test-timeout", vec![spec("slow")]);
+        let err = call("test-timeout", "slow", json!({}), Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("did not answer"));
+        assert!(node.pending.lock().is_empty());
+        unregister(&node);
+    }
+}
@@ -0,0 +1,84 @@
+//! Federated multi-node mode (`[federation]`).
+//!
+//! A coordinator daemon accepts worker nodes on the gateway's `/ws/node`
+//! WebSocket endpoint. Each worker authenticates with its node name and a
+//! shared token, advertises the local tools it is willing to expose (typically
+//! peripherals), and then executes calls the coordinator forwards to it. The
+//! coordinator's agent reaches those tools through the `remote_node` tool.
+//!
+//! Protocol (one JSON object per text frame):
+//! ```text
+//! Worker -> Coordinator: {"type":"hello","node":"kitchen-pi","token":"...","tools":[{name,description,parameters}]}
+//! Coordinator -> Worker: {"type":"welcome"} | {"type":"error","message":"..."}
+//! Coordinator -> Worker: {"type":"call","id":"<uuid>","tool":"gpio_write","args":{...}}
+//! Worker -> Coordinator: {"type":"result","id":"<uuid>","success":true,"output":"...","error":null}
+//! ```
+
+pub mod coordinator;
+pub mod worker;
+
+use crate::tools::ToolSpec;
+use serde::{Deserialize, Serialize};
+
+/// Seconds a new connection has to complete the hello/welcome handshake.
+pub const HANDSHAKE_TIMEOUT_SECS: u64 = 10;
+/// Largest accepted frame, bounding tool specs and results.
+pub const MAX_FRAME_BYTES: usize = 1024 * 1024;
+
+/// Frame exchanged between coordinator and worker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NodeFrame {
+    Hello {
+        node: String,
+        token: String,
+        tools: Vec<ToolSpec>,
+    },
+    Welcome,
+    Call {
+        id: String,
+        tool: String,
+        args: serde_json::Value,
+    },
+    Result {
+        id: String,
+        success: bool,
+        output: String,
+        error: Option<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+impl NodeFrame {
+    pub fn to_text(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        serde_json::from_str(text).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn frames_roundtrip_with_type_tag() {
+        let call = NodeFrame::Call {
+            id: "1".into(),
+            tool: "gpio_write".into(),
+            args: json!({"pin": 17, "value": 1}),
+        };
+        let text = call.to_text();
+        assert!(text.contains(r#""type":"call""#));
+        assert!(matches!(
+            NodeFrame::parse(&text),
+            Some(NodeFrame::Call { tool, .. }) if tool == "gpio_write"
+        ));
+        assert!(NodeFrame::parse(r#"{"type":"bogus"}"#).is_none());
+    }
+}
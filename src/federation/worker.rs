@@ -0,0 +1,233 @@
+//! Worker side of federation: connects to the coordinator, advertises the
+//! exposed local tools and executes the calls it receives.
+
+use super::{NodeFrame, HANDSHAKE_TIMEOUT_SECS, MAX_FRAME_BYTES};
+use crate::config::Config;
+use crate::security::SecurityPolicy;
+use crate::tools::{self, Tool, ToolResult};
+use crate::{memory, runtime};
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Keep-alive ping interval, so idle NAT mappings are not dropped.
+const PING_INTERVAL_SECS: u64 = 30;
+/// Output characters returned per call, keeping results below the frame limit.
+const MAX_OUTPUT_CHARS: usize = MAX_FRAME_BYTES / 8;
+
+/// Build the local tool registry (including peripherals) and keep only the
+/// tools listed in `[federation].exposed_tools`.
+async fn exposed_tools(config: &Config) -> Result<Vec<Box<dyn Tool>>> {
+    let security = Arc::new(SecurityPolicy::from_config(
+        &config.autonomy,
+        &config.workspace_dir,
+    ));
+    let runtime: Arc<dyn runtime::RuntimeAdapter> =
+        Arc::from(runtime::create_runtime(&config.runtime)?);
+    let mem: Arc<dyn memory::Memory> = Arc::from(memory::create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?);
+
+    let mut registry = tools::all_tools_with_runtime(
+        Arc::new(config.clone()),
+        &security,
+        runtime,
+        mem,
+        None,
+        None,
+        &config.browser,
+        &config.http_request,
+        &config.workspace_dir,
+        &config.agents,
+        config.api_key.as_deref(),
+        config,
+    );
+    registry.extend(
+        crate::peripherals::create_peripheral_tools(&config.peripherals, &config.workspace_dir)
+            .await?,
+    );
+
+    let exposed = &config.federation.exposed_tools;
+    registry.retain(|tool| exposed.iter().any(|name| name == tool.name()));
+    for name in exposed {
+        if !registry.iter().any(|tool| tool.name() == name) {
+            tracing::warn!(tool = %name, "Exposed federation tool is not available on this node");
+        }
+    }
+    Ok(registry)
+}
+
+/// Execute one forwarded call. Approval flags are never accepted from the
+/// coordinator; tools apply this node's own security policy.
+async fn execute_call(
+    tools: &[Box<dyn Tool>],
+    tool: &str,
+    mut args: serde_json::Value,
+) -> ToolResult {
+    let Some(tool) = tools.iter().find(|candidate| candidate.name() == tool) else {
+        return ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(format!("Tool '{tool}' is not exposed on this node")),
+        };
+    };
+    if let Some(object) = args.as_object_mut() {
+        object.remove("approved");
+    }
+    let mut result = match tool.execute(args).await {
+        Ok(result) => result,
+        Err(e) => ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(e.to_string()),
+        },
+    };
+    if result.output.chars().count() > MAX_OUTPUT_CHARS {
+        result.output = result.output.chars().take(MAX_OUTPUT_CHARS).collect();
+        result.output.push_str("\n[output truncated]");
+    }
+    result
+}
+
+/// Connect to the coordinator and serve calls until the connection drops.
+///
+/// Returns an error on disconnect so the daemon supervisor reconnects with backoff.
+pub async fn run(config: Config) -> Result<()> {
+    let federation = &config.federation;
+    let url = federation
+        .coordinator_url
+        .clone()
+        .context("federation.coordinator_url is not set")?;
+    let node = federation
+        .node_name
+        .clone()
+        .context("federation.node_name is not set")?;
+    let token = federation
+        .token
+        .clone()
+        .context("federation.token is not set")?;
+
+    let tools = Arc::new(exposed_tools(&config).await?);
+    let specs = tools.iter().map(|tool| tool.spec()).collect();
+
+    let (ws, _) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .with_context(|| format!("Failed to connect to coordinator at {url}"))?;
+    let (mut write, mut read) = ws.split();
+
+    let hello = NodeFrame::Hello {
+        node: node.clone(),
+        token,
+        tools: specs,
+    };
+    write.send(Message::Text(hello.to_text().into())).await?;
+
+    let reply = tokio::time::timeout(Duration::from_secs(HANDSHAKE_TIMEOUT_SECS), read.next())
+        .await
+        .context("Coordinator did not answer the handshake")?;
+    match reply {
+        Some(Ok(Message::Text(text))) => match NodeFrame::parse(&text) {
+            Some(NodeFrame::Welcome) => {}
+            Some(NodeFrame::Error { message }) => {
+                anyhow::bail!("Coordinator rejected node '{node}': {message}")
+            }
+            _ => anyhow::bail!("Unexpected handshake reply from coordinator"),
+        },
+        _ => anyhow::bail!("Coordinator closed the connection during the handshake"),
+    }
+    tracing::info!(node = %node, tools = tools.len(), "Registered with federation coordinator");
+
+    let (results_tx, mut results_rx) = mpsc::channel::<NodeFrame>(64);
+    let mut ping = tokio::time::interval(Duration::from_secs(PING_INTERVAL_SECS));
+    ping.tick().await; // consume immediate tick
+
+    loop {
+        tokio::select! {
+            _ = ping.tick() => {
+                write.send(Message::Ping(Vec::new().into())).await?;
+            }
+            result = results_rx.recv() => {
+                if let Some(frame) = result {
+                    write.send(Message::Text(frame.to_text().into())).await?;
+                }
+            }
+            message = read.next() => {
+                match message {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Some(NodeFrame::Call { id, tool, args }) = NodeFrame::parse(&text) {
+                            let tools = Arc::clone(&tools);
+                            let results_tx = results_tx.clone();
+                            tokio::spawn(async move {
+                                tracing::info!(tool = %tool, "Executing federated tool call");
+                                let result = execute_call(&tools, &tool, args).await;
+                                let _ = results_tx
+                                    .send(NodeFrame::Result {
+                                        id,
+                                        success: result.success,
+                                        output: result.output,
+                                        error: result.error,
+                                    })
+                                    .await;
+                            });
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => {
+                        anyhow::bail!("Coordinator closed the connection")
+                    }
+                    Some(Err(e)) => return Err(e.into()),
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    struct EchoTool;
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Echo arguments"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            json!({"type": "object"})
+        }
+
+        async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: args.to_string(),
+                error: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_call_strips_approval_and_rejects_unexposed_tools() {
+        let tools: Vec<Box<dyn Tool>> = vec![Box::new(EchoTool)];
+        let result = execute_call(&tools, "echo", json!({"x": 1, "approved": true})).await;
+        assert!(result.success);
+        assert_eq!(result.output, r#"{"x":1}"#);
+
+        let missing = execute_call(&tools, "shell", json!({})).await;
+        assert!(!missing.success);
+        assert!(missing.error.unwrap().contains("not exposed"));
+    }
+}
@@ -0,0 +1,319 @@
+//! Per-route request size and concurrency limits (`[gateway.limits]`).
+//!
+//! Every request passes through [`enforce`], which looks up the route it
+//! matched. Bodies over the route's size limit are refused with `413`. A
+//! route already handling `max_concurrent` requests answers `429`, and a
+//! gateway at `max_concurrent_requests` answers `503`; both carry
+//! `Retry-After`. With `queue_timeout_ms` set, a request waits that long
+//! for a free slot before it is turned away.
+
+use crate::config::GatewayLimitsConfig;
+use axum::body::Body;
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Json, Response};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Routes that take larger uploads than the default unless overridden.
+const BUILTIN_BODY_LIMITS: &[(&str, usize)] =
+    &[("/api/config", 1_048_576), ("/api/triage", 4 * 1_048_576)];
+
+struct RouteLimit {
+    max_body_bytes: usize,
+    in_flight: Option<Arc<Semaphore>>,
+}
+
+/// Resolved limits shared by all requests.
+pub struct RouteLimits {
+    default_body_bytes: usize,
+    routes: HashMap<String, RouteLimit>,
+    global: Option<Arc<Semaphore>>,
+    queue_timeout: Duration,
+    retry_after_secs: u64,
+}
+
+fn semaphore(limit: usize) -> Option<Arc<Semaphore>> {
+    (limit > 0).then(|| Arc::new(Semaphore::new(limit)))
+}
+
+impl RouteLimits {
+    pub fn from_config(config: &GatewayLimitsConfig) -> Self {
+        let default_body_bytes = if config.max_body_bytes == 0 {
+            super::MAX_BODY_SIZE
+        } else {
+            config.max_body_bytes
+        };
+        let builtin = |path: &str| {
+            BUILTIN_BODY_LIMITS
+                .iter()
+                .find(|(builtin, _)| *builtin == path)
+                .map(|(_, bytes)| (*bytes).max(default_body_bytes))
+        };
+        let mut routes: HashMap<String, RouteLimit> = BUILTIN_BODY_LIMITS
+            .iter()
+            .map(|(path, bytes)| {
+                let limit = RouteLimit {
+                    max_body_bytes: (*bytes).max(default_body_bytes),
+                    in_flight: None,
+                };
+                ((*path).to_string(), limit)
+            })
+            .collect();
+        for (path, route) in &config.routes {
+            let max_body_bytes = route
+                .max_body_bytes
+                .or_else(|| builtin(path))
+                .unwrap_or(default_body_bytes);
+            routes.insert(
+                path.clone(),
+                RouteLimit {
+                    max_body_bytes,
+                    in_flight: semaphore(route.max_concurrent),
+                },
+            );
+        }
+        Self {
+            default_body_bytes,
+            routes,
+            global: semaphore(config.max_concurrent_requests),
+            queue_timeout: Duration::from_millis(config.queue_timeout_ms),
+            retry_after_secs: config.retry_after_secs,
+        }
+    }
+
+    /// Largest body accepted on `path` (a route pattern such as `/webhook`).
+    pub fn max_body_bytes(&self, path: &str) -> usize {
+        self.routes
+            .get(path)
+            .map_or(self.default_body_bytes, |route| route.max_body_bytes)
+    }
+
+    async fn acquire(&self, slots: &Arc<Semaphore>) -> Option<OwnedSemaphorePermit> {
+        if let Ok(permit) = Arc::clone(slots).try_acquire_owned() {
+            return Some(permit);
+        }
+        if self.queue_timeout.is_zero() {
+            return None;
+        }
+        tokio::time::timeout(self.queue_timeout, Arc::clone(slots).acquire_owned())
+            .await
+            .ok()?
+            .ok()
+    }
+
+    fn busy(&self, status: StatusCode, message: &str) -> Response {
+        let body = serde_json::json!({
+            "error": message,
+            "retry_after": self.retry_after_secs,
+        });
+        let mut response = (status, Json(body)).into_response();
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(self.retry_after_secs),
+        );
+        response
+    }
+}
+
+/// Middleware applying [`RouteLimits`] to the matched route.
+pub async fn enforce(
+    State(limits): State<Arc<RouteLimits>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.extensions().get::<MatchedPath>().map_or_else(
+        || request.uri().path().to_string(),
+        |p| p.as_str().to_string(),
+    );
+    let route = limits.routes.get(&path);
+    let max_body_bytes = limits.max_body_bytes(&path);
+
+    let declared = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    if declared.is_some_and(|len| len > max_body_bytes as u64) {
+        tracing::warn!("{path}: rejected body over {max_body_bytes} bytes");
+        let body = serde_json::json!({
+            "error": format!("Request body too large (limit {max_body_bytes} bytes)"),
+        });
+        return (StatusCode::PAYLOAD_TOO_LARGE, Json(body)).into_response();
+    }
+
+    let _route_permit = match route.and_then(|route| route.in_flight.as_ref()) {
+        Some(slots) => match limits.acquire(slots).await {
+            Some(permit) => Some(permit),
+            None => {
+                tracing::warn!("{path}: concurrent request limit reached");
+                return limits.busy(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    "Too many concurrent requests for this endpoint. Please retry later.",
+                );
+            }
+        },
+        None => None,
+    };
+    let _global_permit = match limits.global.as_ref() {
+        Some(slots) => match limits.acquire(slots).await {
+            Some(permit) => Some(permit),
+            None => {
+                tracing::warn!("{path}: gateway at max_concurrent_requests");
+                return limits.busy(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "Gateway is busy. Please retry later.",
+                );
+            }
+        },
+        None => None,
+    };
+
+    // Chunked bodies carry no length up front; cap them while streaming so
+    // body extractors answer 413 once the limit is crossed.
+    let request = request.map(|body| Body::new(http_body_util::Limited::new(body, max_body_bytes)));
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GatewayRouteLimitConfig;
+    use axum::body::Bytes;
+    use axum::routing::{get, post};
+    use axum::Router;
+    use tokio::sync::Notify;
+    use tower::Service;
+
+    fn limits(routes: &[(&str, Option<usize>, usize)], global: usize) -> Arc<RouteLimits> {
+        let config = GatewayLimitsConfig {
+            max_body_bytes: 64,
+            max_concurrent_requests: global,
+            routes: routes
+                .iter()
+                .map(|(path, max_body_bytes, max_concurrent)| {
+                    (
+                        (*path).to_string(),
+                        GatewayRouteLimitConfig {
+                            max_body_bytes: *max_body_bytes,
+                            max_concurrent: *max_concurrent,
+                        },
+                    )
+                })
+                .collect(),
+            ..GatewayLimitsConfig::default()
+        };
+        Arc::new(RouteLimits::from_config(&config))
+    }
+
+    fn app(limits: Arc<RouteLimits>, gate: Arc<Notify>) -> Router {
+        Router::new()
+            .route(
+                "/echo",
+                post(|body: Bytes| async move { body.len().to_string() }),
+            )
+            .route(
+                "/slow",
+                get(move || async move {
+                    gate.notified().await;
+                    "done"
+                }),
+            )
+            .route("/fast", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(limits, enforce))
+    }
+
+    async fn status(app: &mut Router, request: Request) -> StatusCode {
+        app.call(request).await.unwrap().status()
+    }
+
+    #[test]
+    fn route_overrides_and_builtin_limits_resolve() {
+        let limits = limits(&[("/webhook", Some(1_000), 2), ("/api/triage", None, 1)], 0);
+        assert_eq!(limits.max_body_bytes("/webhook"), 1_000);
+        assert_eq!(limits.max_body_bytes("/api/triage"), 4 * 1_048_576);
+        assert_eq!(limits.max_body_bytes("/api/config"), 1_048_576);
+        assert_eq!(limits.max_body_bytes("/pair"), 64);
+        assert!(limits.routes["/api/triage"].in_flight.is_some());
+        assert!(limits.global.is_none());
+
+        let unset = RouteLimits::from_config(&GatewayLimitsConfig {
+            max_body_bytes: 0,
+            ..GatewayLimitsConfig::default()
+        });
+        assert_eq!(unset.max_body_bytes("/pair"), super::super::MAX_BODY_SIZE);
+    }
+
+    #[tokio::test]
+    async fn oversized_bodies_are_rejected_with_413() {
+        let mut app = app(limits(&[], 0), Arc::new(Notify::new()));
+
+        let declared = Request::post("/echo")
+            .header(header::CONTENT_LENGTH, "65")
+            .body(Body::from(vec![b'x'; 65]))
+            .unwrap();
+        assert_eq!(
+            status(&mut app, declared).await,
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+
+        let undeclared = Request::post("/echo")
+            .body(Body::from(vec![b'x'; 65]))
+            .unwrap();
+        assert_eq!(
+            status(&mut app, undeclared).await,
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+
+        let small = Request::post("/echo").body(Body::from("hi")).unwrap();
+        assert_eq!(status(&mut app, small).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn saturated_routes_return_429_and_saturated_gateway_503() {
+        let limits = limits(&[("/slow", None, 1)], 2);
+        let gate = Arc::new(Notify::new());
+        let mut app = app(Arc::clone(&limits), Arc::clone(&gate));
+
+        let mut first = app.clone();
+        let held = tokio::spawn(async move {
+            first
+                .call(Request::get("/slow").body(Body::empty()).unwrap())
+                .await
+                .unwrap()
+                .status()
+        });
+        while limits.routes["/slow"]
+            .in_flight
+            .as_ref()
+            .unwrap()
+            .available_permits()
+            > 0
+        {
+            tokio::task::yield_now().await;
+        }
+
+        let second = app
+            .call(Request::get("/slow").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(second.headers()[header::RETRY_AFTER], "1");
+
+        // One of two gateway slots is held by /slow; take the other.
+        let _other = Arc::clone(limits.global.as_ref().unwrap())
+            .try_acquire_owned()
+            .unwrap();
+        let busy = Request::get("/fast").body(Body::empty()).unwrap();
+        assert_eq!(
+            status(&mut app, busy).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        gate.notify_one();
+        assert_eq!(held.await.unwrap(), StatusCode::OK);
+    }
+}
@@ -8,6 +8,10 @@
 //! - Header sanitization (handled by axum/hyper)
 
 pub mod api;
+pub mod limits;
+pub mod peers;
+pub mod proxy;
+pub mod share;
 pub mod sse;
 pub mod static_files;
 pub mod ws;
@@ -18,6 +22,7 @@ use crate::cost::CostTracker;
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
+use crate::security::audit::{AuditEvent, AuditEventType, AuditLogger};
 use crate::security::pairing::{constant_time_eq, is_public_bind, PairingGuard};
 use crate::security::SecurityPolicy;
 use crate::tools;
@@ -26,7 +31,7 @@ use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
 use axum::{
     body::Bytes,
-    extract::{ConnectInfo, Query, State},
+    extract::{ConnectInfo, DefaultBodyLimit, Query, State},
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json},
     routing::{delete, get, post, put},
@@ -34,14 +39,13 @@ use axum::{
 };
 use parking_lot::Mutex;
 use std::collections::HashMap;
-use std::net::{IpAddr, SocketAddr};
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::timeout::TimeoutLayer;
 use uuid::Uuid;
 
-/// Maximum request body size (64KB) — prevents memory exhaustion
+/// Default maximum request body size (64KB) — prevents memory exhaustion
 pub const MAX_BODY_SIZE: usize = 65_536;
 /// Request timeout (30s) — prevents slow-loris attacks
 pub const REQUEST_TIMEOUT_SECS: u64 = 30;
@@ -146,12 +150,34 @@ impl SlidingWindowRateLimiter {
         entry.push(now);
         true
     }
+
+    /// Whether `key` used up its window, without counting a request.
+    fn exhausted(&self, key: &str) -> bool {
+        if self.limit_per_window == 0 {
+            return false;
+        }
+        let cutoff = Instant::now()
+            .checked_sub(self.window)
+            .unwrap_or_else(Instant::now);
+        self.requests.lock().0.get(key).is_some_and(|timestamps| {
+            timestamps.iter().filter(|t| **t > cutoff).count() >= self.limit_per_window as usize
+        })
+    }
+
+    /// Count against the limit shared by all daemons when `[coordination]`
+    /// provides one, otherwise against this process's window.
+    fn allow_shared(&self, scope: &str, key: &str) -> bool {
+        crate::coordination::rate_limit(scope, key, self.limit_per_window, self.window)
+            .unwrap_or_else(|| self.allow(key))
+    }
 }
 
 #[derive(Debug)]
 pub struct GatewayRateLimiter {
     pair: SlidingWindowRateLimiter,
     webhook: SlidingWindowRateLimiter,
+    /// Failed peer authentications; shares the pairing limit.
+    peer_failures: SlidingWindowRateLimiter,
 }
 
 impl GatewayRateLimiter {
@@ -160,15 +186,25 @@ impl GatewayRateLimiter {
         Self {
             pair: SlidingWindowRateLimiter::new(pair_per_minute, window, max_keys),
             webhook: SlidingWindowRateLimiter::new(webhook_per_minute, window, max_keys),
+            peer_failures: SlidingWindowRateLimiter::new(pair_per_minute, window, max_keys),
         }
     }
 
+    /// Whether `key` failed peer authentication too often to try again yet.
+    fn peer_locked_out(&self, key: &str) -> bool {
+        self.peer_failures.exhausted(key)
+    }
+
+    fn record_peer_failure(&self, key: &str) {
+        let _ = self.peer_failures.allow(key);
+    }
+
     fn allow_pair(&self, key: &str) -> bool {
-        self.pair.allow(key)
+        self.pair.allow_shared("gateway_pair", key)
     }
 
     fn allow_webhook(&self, key: &str) -> bool {
-        self.webhook.allow(key)
+        self.webhook.allow_shared("gateway_webhook", key)
     }
 }
 
@@ -214,55 +250,47 @@ impl IdempotencyStore {
     }
 }
 
-fn parse_client_ip(value: &str) -> Option<IpAddr> {
-    let value = value.trim().trim_matches('"').trim();
-    if value.is_empty() {
-        return None;
-    }
-
-    if let Ok(ip) = value.parse::<IpAddr>() {
-        return Some(ip);
-    }
-
-    if let Ok(addr) = value.parse::<SocketAddr>() {
-        return Some(addr.ip());
-    }
-
-    let value = value.trim_matches(['[', ']']);
-    value.parse::<IpAddr>().ok()
-}
-
-fn forwarded_client_ip(headers: &HeaderMap) -> Option<IpAddr> {
-    if let Some(xff) = headers.get("X-Forwarded-For").and_then(|v| v.to_str().ok()) {
-        for candidate in xff.split(',') {
-            if let Some(ip) = parse_client_ip(candidate) {
-                return Some(ip);
-            }
-        }
-    }
-
-    headers
-        .get("X-Real-IP")
-        .and_then(|v| v.to_str().ok())
-        .and_then(parse_client_ip)
-}
-
 fn client_key_from_request(
     peer_addr: Option<SocketAddr>,
     headers: &HeaderMap,
-    trust_forwarded_headers: bool,
+    proxies: &proxy::TrustedProxies,
 ) -> String {
-    if trust_forwarded_headers {
-        if let Some(ip) = forwarded_client_ip(headers) {
-            return ip.to_string();
-        }
-    }
-
     peer_addr
-        .map(|addr| addr.ip().to_string())
+        .map(|addr| proxies.client_ip(addr.ip(), headers).to_string())
         .unwrap_or_else(|| "unknown".to_string())
 }
 
+/// Record a gateway authentication decision in the security audit log,
+/// keyed by the real client address.
+fn audit_gateway_auth(state: &AppState, client: &str, check: &str, reason: Option<&str>) {
+    let (audit, zeroclaw_dir) = {
+        let config = state.config.lock();
+        let dir = config
+            .config_path
+            .parent()
+            .unwrap_or(&config.workspace_dir)
+            .to_path_buf();
+        (config.security.audit.clone(), dir)
+    };
+    if !audit.enabled {
+        return;
+    }
+    let allowed = reason.is_none();
+    let event_type = if allowed {
+        AuditEventType::AuthSuccess
+    } else {
+        AuditEventType::AuthFailure
+    };
+    let event = AuditEvent::new(event_type)
+        .with_actor("gateway".into(), Some(client.to_string()), None)
+        .with_action(check.to_string(), "medium".into(), allowed, allowed)
+        .with_result(allowed, None, 0, reason.map(str::to_string));
+    let logged = AuditLogger::new(audit, zeroclaw_dir).and_then(|logger| logger.log(&event));
+    if let Err(e) = logged {
+        tracing::warn!("Failed to write gateway audit event: {e}");
+    }
+}
+
 fn normalize_max_keys(configured: usize, fallback: usize) -> usize {
     if configured == 0 {
         fallback.max(1)
@@ -283,7 +311,8 @@ pub struct AppState {
     /// SHA-256 hash of `X-Webhook-Secret` (hex-encoded), never plaintext.
     pub webhook_secret_hash: Option<Arc<str>>,
     pub pairing: Arc<PairingGuard>,
-    pub trust_forwarded_headers: bool,
+    /// Reverse proxies whose forwarded client address headers are believed.
+    pub proxies: Arc<proxy::TrustedProxies>,
     pub rate_limiter: Arc<GatewayRateLimiter>,
     pub idempotency_store: Arc<IdempotencyStore>,
     pub whatsapp: Option<Arc<WhatsAppChannel>>,
@@ -303,11 +332,15 @@ pub struct AppState {
     pub cost_tracker: Option<Arc<CostTracker>>,
     /// SSE broadcast channel for real-time events
     pub event_tx: tokio::sync::broadcast::Sender<serde_json::Value>,
+    /// Board connections shared by the gateway agent and the peripheral API
+    pub peripherals: Arc<crate::peripherals::PeripheralHandles>,
 }
 
 /// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
 #[allow(clippy::too_many_lines)]
 pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
+    crate::config::ensure_runtime_globals(&config);
+
     // ── Security: refuse public bind without tunnel or explicit opt-in ──
     if is_public_bind(host) && config.tunnel.provider == "none" && !config.gateway.allow_public_bind
     {
@@ -331,19 +364,28 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     let actual_port = listener.local_addr()?.port();
     let display_addr = format!("{host}:{actual_port}");
 
-    let provider: Arc<dyn Provider> = Arc::from(providers::create_resilient_provider_with_options(
-        config.default_provider.as_deref().unwrap_or("openrouter"),
-        config.api_key.as_deref(),
-        config.api_url.as_deref(),
-        &config.reliability,
-        &providers::ProviderRuntimeOptions {
-            auth_profile_override: None,
-            zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
-            secrets_encrypt: config.secrets.encrypt,
-            provider_api_url: config.api_url.clone(),
-            reasoning_enabled: config.runtime.reasoning_enabled,
-        },
-    )?);
+    let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
+    let provider_runtime_options = providers::ProviderRuntimeOptions {
+        auth_profile_override: None,
+        zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+        secrets_encrypt: config.secrets.encrypt,
+        provider_api_url: config.api_url.clone(),
+        reasoning_enabled: config.runtime.reasoning_enabled,
+        custom_providers: config.custom_providers.clone(),
+        provider_plugins: config.provider_plugins.clone(),
+    };
+    let provider: Arc<dyn Provider> = Arc::from(providers::shadow::wrap_with_shadow(
+        providers::create_resilient_provider_with_options(
+            provider_name,
+            config.api_key.as_deref(),
+            config.api_url.as_deref(),
+            &config.reliability,
+            &provider_runtime_options,
+        )?,
+        provider_name,
+        &config,
+        &provider_runtime_options,
+    ));
     let model = config
         .default_model
         .clone()
@@ -514,6 +556,13 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         config.gateway.require_pairing,
         &config.gateway.paired_tokens,
     ));
+    let proxies = Arc::new(proxy::TrustedProxies::from_config(&config.gateway)?);
+    if proxies.trusts_any() {
+        tracing::warn!(
+            "gateway.trust_forwarded_headers is on without gateway.trusted_proxies; \
+             any client can choose its own address. List your proxies in trusted_proxies."
+        );
+    }
     let rate_limit_max_keys = normalize_max_keys(
         config.gateway.rate_limit_max_keys,
         RATE_LIMIT_MAX_KEYS_DEFAULT,
@@ -568,6 +617,9 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         println!("  POST /nextcloud-talk — Nextcloud Talk bot webhook");
     }
     println!("  GET  /api/*     — REST API (bearer token required)");
+    if config.triage.enabled {
+        println!("  POST /api/triage — test report triage (bearer token required)");
+    }
     println!("  GET  /ws/chat   — WebSocket agent chat");
     println!("  GET  /health    — health check");
     println!("  GET  /metrics   — Prometheus metrics");
@@ -608,7 +660,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         auto_save: config.memory.auto_save,
         webhook_secret_hash,
         pairing,
-        trust_forwarded_headers: config.gateway.trust_forwarded_headers,
+        proxies,
         rate_limiter,
         idempotency_store,
         whatsapp: whatsapp_channel,
@@ -621,15 +673,28 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         tools_registry,
         cost_tracker,
         event_tx,
+        peripherals: Arc::default(),
     };
 
-    // Config PUT needs larger body limit (1MB)
-    let config_put_router = Router::new()
-        .route("/api/config", put(api::handle_api_config_put))
-        .layer(RequestBodyLimitLayer::new(1_048_576));
+    // Per-route body sizes and concurrency caps; config PUT (1MB) and test
+    // reports (4MB) get larger bodies unless overridden.
+    let limits = Arc::new(limits::RouteLimits::from_config(&config.gateway.limits));
+
+    let app = build_router(state, limits);
+
+    // Run the server
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
-    // Build router with middleware
-    let app = Router::new()
+    Ok(())
+}
+
+/// Build the gateway router with its middleware.
+fn build_router(state: AppState, limits: Arc<limits::RouteLimits>) -> Router {
+    Router::new()
         // ── Existing routes ──
         .route("/health", get(handle_health))
         .route("/metrics", get(handle_metrics))
@@ -643,6 +708,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/status", get(api::handle_api_status))
         .route("/api/config", get(api::handle_api_config_get))
         .route("/api/tools", get(api::handle_api_tools))
+        .route("/api/capabilities", get(api::handle_api_capabilities))
         .route("/api/cron", get(api::handle_api_cron_list))
         .route("/api/cron", post(api::handle_api_cron_add))
         .route("/api/cron/{id}", delete(api::handle_api_cron_delete))
@@ -652,33 +718,42 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/memory", post(api::handle_api_memory_store))
         .route("/api/memory/{key}", delete(api::handle_api_memory_delete))
         .route("/api/cost", get(api::handle_api_cost))
+        .route("/api/artifacts/{id}", get(api::handle_api_artifact_get))
         .route("/api/cli-tools", get(api::handle_api_cli_tools))
         .route("/api/health", get(api::handle_api_health))
+        .route("/api/peripherals", get(api::handle_api_peripherals))
+        .route(
+            "/api/peripherals/call",
+            post(api::handle_api_peripheral_call),
+        )
         // ── SSE event stream ──
         .route("/api/events", get(sse::handle_sse_events))
         // ── WebSocket agent chat ──
         .route("/ws/chat", get(ws::handle_ws_chat))
+        // ── Federation worker nodes ──
+        .route("/ws/node", get(ws::handle_ws_node))
+        // ── Tasks delegated by peer instances (shared token, no pairing) ──
+        .route("/api/peers/tasks", post(peers::handle_submit))
+        .route("/api/peers/tasks/{id}", get(peers::handle_status))
+        // ── Shared snapshots (signed links, no pairing) ──
+        .route("/share/{id}", get(share::handle_share))
         // ── Static assets (web dashboard) ──
         .route("/_app/{*path}", get(static_files::handle_static))
-        // ── Config PUT with larger body limit ──
-        .merge(config_put_router)
+        .route("/api/config", put(api::handle_api_config_put))
+        .route("/api/triage", post(api::handle_api_triage))
         .with_state(state)
-        .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
+        // `limits::enforce` owns body sizes; axum's 2MB default would cap them.
+        .layer(DefaultBodyLimit::disable())
+        .layer(axum::middleware::from_fn_with_state(
+            limits,
+            limits::enforce,
+        ))
         .layer(TimeoutLayer::with_status_code(
             StatusCode::REQUEST_TIMEOUT,
             Duration::from_secs(REQUEST_TIMEOUT_SECS),
         ))
         // ── SPA fallback: non-API GET requests serve index.html ──
-        .fallback(get(static_files::handle_spa_fallback));
-
-    // Run the server
-    axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .await?;
-
-    Ok(())
+        .fallback(get(static_files::handle_spa_fallback))
 }
 
 // ══════════════════════════════════════════════════════════════════════════════
@@ -725,10 +800,9 @@ async fn handle_pair(
     ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    let rate_key =
-        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    let rate_key = client_key_from_request(Some(peer_addr), &headers, &state.proxies);
     if !state.rate_limiter.allow_pair(&rate_key) {
-        tracing::warn!("/pair rate limit exceeded");
+        tracing::warn!("/pair rate limit exceeded for {rate_key}");
         let err = serde_json::json!({
             "error": "Too many pairing requests. Please retry later.",
             "retry_after": RATE_LIMIT_WINDOW_SECS,
@@ -743,8 +817,11 @@ async fn handle_pair(
 
     match state.pairing.try_pair(code, &rate_key).await {
         Ok(Some(token)) => {
-            tracing::info!("🔐 New client paired successfully");
-            if let Err(err) = persist_pairing_tokens(state.config.clone(), &state.pairing).await {
+            tracing::info!("🔐 New client paired successfully from {rate_key}");
+            audit_gateway_auth(&state, &rate_key, "pair", None);
+            if let Err(err) =
+                Box::pin(persist_pairing_tokens(state.config.clone(), &state.pairing)).await
+            {
                 tracing::error!("🔐 Pairing succeeded but token persistence failed: {err:#}");
                 let body = serde_json::json!({
                     "paired": true,
@@ -764,14 +841,16 @@ async fn handle_pair(
             (StatusCode::OK, Json(body))
         }
         Ok(None) => {
-            tracing::warn!("🔐 Pairing attempt with invalid code");
+            tracing::warn!("🔐 Pairing attempt with invalid code from {rate_key}");
+            audit_gateway_auth(&state, &rate_key, "pair", Some("invalid pairing code"));
             let err = serde_json::json!({"error": "Invalid pairing code"});
             (StatusCode::FORBIDDEN, Json(err))
         }
         Err(lockout_secs) => {
             tracing::warn!(
-                "🔐 Pairing locked out — too many failed attempts ({lockout_secs}s remaining)"
+                "🔐 Pairing locked out for {rate_key} — too many failed attempts ({lockout_secs}s remaining)"
             );
+            audit_gateway_auth(&state, &rate_key, "pair", Some("locked out"));
             let err = serde_json::json!({
                 "error": format!("Too many failed attempts. Try again in {lockout_secs}s."),
                 "retry_after": lockout_secs
@@ -832,7 +911,12 @@ async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Res
 /// Full-featured chat with tools for channel handlers (WhatsApp, Linq, Nextcloud Talk).
 async fn run_gateway_chat_with_tools(state: &AppState, message: &str) -> anyhow::Result<String> {
     let config = state.config.lock().clone();
-    crate::agent::process_message(config, message).await
+    Box::pin(crate::agent::process_message_with_peripherals(
+        config,
+        message,
+        &state.peripherals,
+    ))
+    .await
 }
 
 /// Webhook request body
@@ -848,10 +932,9 @@ async fn handle_webhook(
     headers: HeaderMap,
     body: Result<Json<WebhookBody>, axum::extract::rejection::JsonRejection>,
 ) -> impl IntoResponse {
-    let rate_key =
-        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    let rate_key = client_key_from_request(Some(peer_addr), &headers, &state.proxies);
     if !state.rate_limiter.allow_webhook(&rate_key) {
-        tracing::warn!("/webhook rate limit exceeded");
+        tracing::warn!("/webhook rate limit exceeded for {rate_key}");
         let err = serde_json::json!({
             "error": "Too many webhook requests. Please retry later.",
             "retry_after": RATE_LIMIT_WINDOW_SECS,
@@ -867,7 +950,8 @@ async fn handle_webhook(
             .unwrap_or("");
         let token = auth.strip_prefix("Bearer ").unwrap_or("");
         if !state.pairing.is_authenticated(token) {
-            tracing::warn!("Webhook: rejected — not paired / invalid bearer token");
+            tracing::warn!("Webhook: rejected {rate_key} — not paired / invalid bearer token");
+            audit_gateway_auth(&state, &rate_key, "webhook", Some("invalid bearer token"));
             let err = serde_json::json!({
                 "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
             });
@@ -886,7 +970,10 @@ async fn handle_webhook(
         match header_hash {
             Some(val) if constant_time_eq(&val, secret_hash.as_ref()) => {}
             _ => {
-                tracing::warn!("Webhook: rejected request — invalid or missing X-Webhook-Secret");
+                tracing::warn!(
+                    "Webhook: rejected request from {rate_key} — invalid or missing X-Webhook-Secret"
+                );
+                audit_gateway_auth(&state, &rate_key, "webhook", Some("invalid webhook secret"));
                 let err = serde_json::json!({"error": "Unauthorized — invalid or missing X-Webhook-Secret header"});
                 return (StatusCode::UNAUTHORIZED, Json(err));
             }
@@ -1021,7 +1108,13 @@ async fn handle_webhook(
                 });
 
             tracing::error!("Webhook provider error: {}", sanitized);
-            let err = serde_json::json!({"error": "LLM request failed"});
+            let report =
+                crate::errors::ErrorReport::new(crate::errors::classify(&e), "LLM request failed");
+            let err = serde_json::json!({
+                "error": report.message,
+                "code": report.code,
+                "retriable": report.retriable,
+            });
             (StatusCode::INTERNAL_SERVER_ERROR, Json(err))
         }
     }
@@ -1041,6 +1134,8 @@ pub struct WhatsAppVerifyQuery {
 /// GET /whatsapp — Meta webhook verification
 async fn handle_whatsapp_verify(
     State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Query(params): Query<WhatsAppVerifyQuery>,
 ) -> impl IntoResponse {
     let Some(ref wa) = state.whatsapp else {
@@ -1060,7 +1155,9 @@ async fn handle_whatsapp_verify(
         return (StatusCode::BAD_REQUEST, "Missing hub.challenge".to_string());
     }
 
-    tracing::warn!("WhatsApp webhook verification failed — token mismatch");
+    let client = client_key_from_request(Some(peer_addr), &headers, &state.proxies);
+    tracing::warn!("WhatsApp webhook verification from {client} failed — token mismatch");
+    audit_gateway_auth(&state, &client, "whatsapp verify", Some("token mismatch"));
     (StatusCode::FORBIDDEN, "Forbidden".to_string())
 }
 
@@ -1094,6 +1191,7 @@ pub fn verify_whatsapp_signature(app_secret: &str, body: &[u8], signature_header
 /// POST /whatsapp — incoming message webhook
 async fn handle_whatsapp_message(
     State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
     body: Bytes,
 ) -> impl IntoResponse {
@@ -1112,14 +1210,16 @@ async fn handle_whatsapp_message(
             .unwrap_or("");
 
         if !verify_whatsapp_signature(app_secret, &body, signature) {
+            let client = client_key_from_request(Some(peer_addr), &headers, &state.proxies);
+            let reason = if signature.is_empty() {
+                "missing signature"
+            } else {
+                "invalid signature"
+            };
             tracing::warn!(
-                "WhatsApp webhook signature verification failed (signature: {})",
-                if signature.is_empty() {
-                    "missing"
-                } else {
-                    "invalid"
-                }
+                "WhatsApp webhook signature verification failed for {client} ({reason})"
             );
+            audit_gateway_auth(&state, &client, "whatsapp webhook", Some(reason));
             return (
                 StatusCode::UNAUTHORIZED,
                 Json(serde_json::json!({"error": "Invalid signature"})),
@@ -1160,7 +1260,7 @@ async fn handle_whatsapp_message(
                 .await;
         }
 
-        match run_gateway_chat_with_tools(&state, &msg.content).await {
+        match Box::pin(run_gateway_chat_with_tools(&state, &msg.content)).await {
             Ok(response) => {
                 // Send reply via WhatsApp
                 if let Err(e) = wa
@@ -1189,6 +1289,7 @@ async fn handle_whatsapp_message(
 /// POST /linq — incoming message webhook (iMessage/RCS/SMS via Linq)
 async fn handle_linq_webhook(
     State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
     body: Bytes,
 ) -> impl IntoResponse {
@@ -1219,14 +1320,14 @@ async fn handle_linq_webhook(
             timestamp,
             signature,
         ) {
-            tracing::warn!(
-                "Linq webhook signature verification failed (signature: {})",
-                if signature.is_empty() {
-                    "missing"
-                } else {
-                    "invalid"
-                }
-            );
+            let client = client_key_from_request(Some(peer_addr), &headers, &state.proxies);
+            let reason = if signature.is_empty() {
+                "missing signature"
+            } else {
+                "invalid signature"
+            };
+            tracing::warn!("Linq webhook signature verification failed for {client} ({reason})");
+            audit_gateway_auth(&state, &client, "linq webhook", Some(reason));
             return (
                 StatusCode::UNAUTHORIZED,
                 Json(serde_json::json!({"error": "Invalid signature"})),
@@ -1268,7 +1369,7 @@ async fn handle_linq_webhook(
         }
 
         // Call the LLM
-        match run_gateway_chat_with_tools(&state, &msg.content).await {
+        match Box::pin(run_gateway_chat_with_tools(&state, &msg.content)).await {
             Ok(response) => {
                 // Send reply via Linq
                 if let Err(e) = linq
@@ -1297,6 +1398,7 @@ async fn handle_linq_webhook(
 /// POST /nextcloud-talk — incoming message webhook (Nextcloud Talk bot API)
 async fn handle_nextcloud_talk_webhook(
     State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
     body: Bytes,
 ) -> impl IntoResponse {
@@ -1327,14 +1429,16 @@ async fn handle_nextcloud_talk_webhook(
             &body_str,
             signature,
         ) {
+            let client = client_key_from_request(Some(peer_addr), &headers, &state.proxies);
+            let reason = if signature.is_empty() {
+                "missing signature"
+            } else {
+                "invalid signature"
+            };
             tracing::warn!(
-                "Nextcloud Talk webhook signature verification failed (signature: {})",
-                if signature.is_empty() {
-                    "missing"
-                } else {
-                    "invalid"
-                }
+                "Nextcloud Talk webhook signature verification failed for {client} ({reason})"
             );
+            audit_gateway_auth(&state, &client, "nextcloud-talk webhook", Some(reason));
             return (
                 StatusCode::UNAUTHORIZED,
                 Json(serde_json::json!({"error": "Invalid signature"})),
@@ -1372,7 +1476,7 @@ async fn handle_nextcloud_talk_webhook(
                 .await;
         }
 
-        match run_gateway_chat_with_tools(&state, &msg.content).await {
+        match Box::pin(run_gateway_chat_with_tools(&state, &msg.content)).await {
             Ok(response) => {
                 if let Err(e) = nextcloud_talk
                     .send(&SendMessage::new(response, &msg.reply_target))
@@ -1453,6 +1557,78 @@ mod tests {
         assert_clone::<AppState>();
     }
 
+    #[tokio::test]
+    async fn failed_federation_hellos_lock_out_the_client() {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::{Error as WsError, Message as WsMessage};
+
+        let mut config = Config::default();
+        config.federation.role = crate::config::FederationRole::Coordinator;
+        config.federation.nodes.insert(
+            "pi".into(),
+            crate::config::FederationNodeConfig {
+                token: "correct-node-token-0123456789".into(),
+            },
+        );
+        let state = AppState {
+            config: Arc::new(Mutex::new(config)),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            proxies: Arc::default(),
+            rate_limiter: Arc::new(GatewayRateLimiter::new(3, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            peripherals: Arc::default(),
+        };
+        let app = Router::new()
+            .route("/ws/node", get(ws::handle_ws_node))
+            .with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/ws/node", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        });
+
+        let hello = crate::federation::NodeFrame::Hello {
+            node: "pi".into(),
+            token: "wrong-token".into(),
+            tools: Vec::new(),
+        };
+        for _ in 0..3 {
+            let (mut socket, _) = tokio_tungstenite::connect_async(url.as_str())
+                .await
+                .unwrap();
+            socket.send(WsMessage::text(hello.to_text())).await.unwrap();
+            let reply = socket.next().await.unwrap().unwrap();
+            assert!(reply.to_text().unwrap().contains("authentication failed"));
+        }
+
+        match tokio_tungstenite::connect_async(url.as_str()).await {
+            Err(WsError::Http(response)) => {
+                assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+            }
+            other => panic!("expected lockout, got {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn metrics_endpoint_returns_hint_when_prometheus_is_disabled() {
         let state = AppState {
@@ -1464,7 +1640,7 @@ mod tests {
             auto_save: false,
             webhook_secret_hash: None,
             pairing: Arc::new(PairingGuard::new(false, &[])),
-            trust_forwarded_headers: false,
+            proxies: Arc::default(),
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -1477,6 +1653,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            peripherals: Arc::default(),
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -1512,7 +1689,7 @@ mod tests {
             auto_save: false,
             webhook_secret_hash: None,
             pairing: Arc::new(PairingGuard::new(false, &[])),
-            trust_forwarded_headers: false,
+            proxies: Arc::default(),
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -1525,6 +1702,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            peripherals: Arc::default(),
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -1535,6 +1713,123 @@ mod tests {
         assert!(text.contains("zeroclaw_heartbeat_ticks_total 1"));
     }
 
+    #[tokio::test]
+    async fn triage_accepts_reports_over_axum_default_body_limit() {
+        let mut config = Config::default();
+        config.triage.enabled = true;
+        let limits = Arc::new(limits::RouteLimits::from_config(&config.gateway.limits));
+        let state = AppState {
+            config: Arc::new(Mutex::new(config)),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            proxies: Arc::default(),
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            peripherals: Arc::default(),
+        };
+
+        // 3 MiB of passing cargo test output: over axum's 2MB default, under
+        // the 4MB built-in limit for /api/triage.
+        let line = "test module::passing_case ... ok\n";
+        let mut report = line.repeat(3 * 1_048_576 / line.len() + 1);
+        report.push_str("test result: ok. 1 passed; 0 failed; 0 ignored\n");
+        assert!(report.len() > 3 * 1_048_576);
+
+        let request = axum::http::Request::post("/api/triage")
+            .header(header::CONTENT_LENGTH, report.len())
+            .body(axum::body::Body::from(report))
+            .unwrap();
+        let response = tower::Service::call(&mut build_router(state, limits), request)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn peer_auth_failures_lock_out_the_client() {
+        let mut config = Config::default();
+        config.peers.accept = true;
+        config.peers.instances.insert(
+            "home".into(),
+            crate::config::PeerInstanceConfig {
+                url: None,
+                token: "shared-token".into(),
+            },
+        );
+        let state = AppState {
+            config: Arc::new(Mutex::new(config)),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            proxies: Arc::default(),
+            rate_limiter: Arc::new(GatewayRateLimiter::new(2, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            peripherals: Arc::default(),
+        };
+        let status = |ip: [u8; 4], token: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(crate::peers::PEER_HEADER, HeaderValue::from_static("home"));
+            headers.insert(
+                header::AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+            );
+            let state = state.clone();
+            async move {
+                peers::handle_status(
+                    State(state),
+                    ConnectInfo(SocketAddr::from((ip, 4000))),
+                    headers,
+                    axum::extract::Path("missing".into()),
+                )
+                .await
+                .status()
+            }
+        };
+
+        let attacker = [10, 0, 0, 1];
+        assert_eq!(status(attacker, "guess-1").await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(attacker, "guess-2").await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status(attacker, "shared-token").await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        // Other clients are unaffected; an authenticated lookup of an unknown
+        // task is a 404.
+        assert_eq!(
+            status([10, 0, 0, 2], "shared-token").await,
+            StatusCode::NOT_FOUND
+        );
+    }
+
     #[test]
     fn gateway_rate_limiter_blocks_after_limit() {
         let limiter = GatewayRateLimiter::new(2, 2, 100);
@@ -1631,10 +1926,19 @@ mod tests {
             HeaderValue::from_static("198.51.100.10, 203.0.113.11"),
         );
 
-        let key = client_key_from_request(Some(peer), &headers, false);
+        let key = client_key_from_request(Some(peer), &headers, &proxy::TrustedProxies::default());
         assert_eq!(key, "10.0.0.5");
     }
 
+    fn trusted_proxies(list: &[&str], trust_forwarded_headers: bool) -> proxy::TrustedProxies {
+        let gateway = crate::config::GatewayConfig {
+            trusted_proxies: list.iter().map(|entry| (*entry).to_string()).collect(),
+            trust_forwarded_headers,
+            ..crate::config::GatewayConfig::default()
+        };
+        proxy::TrustedProxies::from_config(&gateway).unwrap()
+    }
+
     #[test]
     fn client_key_uses_forwarded_ip_only_in_trusted_proxy_mode() {
         let peer = SocketAddr::from(([10, 0, 0, 5], 42617));
@@ -1644,8 +1948,14 @@ mod tests {
             HeaderValue::from_static("198.51.100.10, 203.0.113.11"),
         );
 
-        let key = client_key_from_request(Some(peer), &headers, true);
+        // Legacy mode believes the first forwarded address from any peer.
+        let key = client_key_from_request(Some(peer), &headers, &trusted_proxies(&[], true));
         assert_eq!(key, "198.51.100.10");
+
+        // With listed proxies, the nearest untrusted hop is the client.
+        let proxies = trusted_proxies(&["10.0.0.0/8"], false);
+        let key = client_key_from_request(Some(peer), &headers, &proxies);
+        assert_eq!(key, "203.0.113.11");
     }
 
     #[test]
@@ -1654,7 +1964,8 @@ mod tests {
         let mut headers = HeaderMap::new();
         headers.insert("X-Forwarded-For", HeaderValue::from_static("garbage-value"));
 
-        let key = client_key_from_request(Some(peer), &headers, true);
+        let proxies = trusted_proxies(&["10.0.0.0/8"], false);
+        let key = client_key_from_request(Some(peer), &headers, &proxies);
         assert_eq!(key, "10.0.0.5");
     }
 
@@ -1687,7 +1998,7 @@ mod tests {
         assert!(guard.is_authenticated(&token));
 
         let shared_config = Arc::new(Mutex::new(config));
-        persist_pairing_tokens(shared_config.clone(), &guard)
+        Box::pin(persist_pairing_tokens(shared_config.clone(), &guard))
             .await
             .unwrap();
 
@@ -1723,6 +2034,7 @@ mod tests {
             channel: "whatsapp".into(),
             timestamp: 1,
             thread_ts: None,
+            group: None,
         };
 
         let key = whatsapp_memory_key(&msg);
@@ -1877,7 +2189,7 @@ mod tests {
             auto_save: false,
             webhook_secret_hash: None,
             pairing: Arc::new(PairingGuard::new(false, &[])),
-            trust_forwarded_headers: false,
+            proxies: Arc::default(),
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -1890,6 +2202,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            peripherals: Arc::default(),
         };
 
         let mut headers = HeaderMap::new();
@@ -1940,7 +2253,7 @@ mod tests {
             auto_save: true,
             webhook_secret_hash: None,
             pairing: Arc::new(PairingGuard::new(false, &[])),
-            trust_forwarded_headers: false,
+            proxies: Arc::default(),
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -1953,6 +2266,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            peripherals: Arc::default(),
         };
 
         let headers = HeaderMap::new();
@@ -2015,7 +2329,7 @@ mod tests {
             auto_save: false,
             webhook_secret_hash: Some(Arc::from(hash_webhook_secret(&secret))),
             pairing: Arc::new(PairingGuard::new(false, &[])),
-            trust_forwarded_headers: false,
+            proxies: Arc::default(),
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -2028,6 +2342,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            peripherals: Arc::default(),
         };
 
         let response = handle_webhook(
@@ -2062,7 +2377,7 @@ mod tests {
             auto_save: false,
             webhook_secret_hash: Some(Arc::from(hash_webhook_secret(&valid_secret))),
             pairing: Arc::new(PairingGuard::new(false, &[])),
-            trust_forwarded_headers: false,
+            proxies: Arc::default(),
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -2075,6 +2390,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            peripherals: Arc::default(),
         };
 
         let mut headers = HeaderMap::new();
@@ -2114,7 +2430,7 @@ mod tests {
             auto_save: false,
             webhook_secret_hash: Some(Arc::from(hash_webhook_secret(&secret))),
             pairing: Arc::new(PairingGuard::new(false, &[])),
-            trust_forwarded_headers: false,
+            proxies: Arc::default(),
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -2127,6 +2443,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            peripherals: Arc::default(),
         };
 
         let mut headers = HeaderMap::new();
@@ -2171,7 +2488,7 @@ mod tests {
             auto_save: false,
             webhook_secret_hash: None,
             pairing: Arc::new(PairingGuard::new(false, &[])),
-            trust_forwarded_headers: false,
+            proxies: Arc::default(),
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -2184,13 +2501,15 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            peripherals: Arc::default(),
         };
 
-        let response = handle_nextcloud_talk_webhook(
+        let response = Box::pin(handle_nextcloud_talk_webhook(
             State(state),
+            test_connect_info(),
             HeaderMap::new(),
             Bytes::from_static(br#"{"type":"message"}"#),
-        )
+        ))
         .await
         .into_response();
 
@@ -2224,7 +2543,7 @@ mod tests {
             auto_save: false,
             webhook_secret_hash: None,
             pairing: Arc::new(PairingGuard::new(false, &[])),
-            trust_forwarded_headers: false,
+            proxies: Arc::default(),
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -2237,6 +2556,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            peripherals: Arc::default(),
         };
 
         let mut headers = HeaderMap::new();
@@ -2249,9 +2569,14 @@ mod tests {
             HeaderValue::from_str(invalid_signature).unwrap(),
         );
 
-        let response = handle_nextcloud_talk_webhook(State(state), headers, Bytes::from(body))
-            .await
-            .into_response();
+        let response = Box::pin(handle_nextcloud_talk_webhook(
+            State(state),
+            test_connect_info(),
+            headers,
+            Bytes::from(body),
+        ))
+        .await
+        .into_response();
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
         assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 0);
     }
@@ -0,0 +1,115 @@
+//! Delegated tasks from peer instances (`[peers]`, see [`crate::peers`]).
+//!
+//! These routes do not use pairing: each request names the sending instance
+//! in `X-ZeroClaw-Peer` and carries the token shared with it. They answer
+//! 404 unless `[peers] accept = true`. A client that fails authentication
+//! `pair_rate_limit_per_minute` times is answered `429` for the rest of the
+//! minute, whatever it sends.
+
+use super::{audit_gateway_auth, client_key_from_request, AppState, RATE_LIMIT_WINDOW_SECS};
+use crate::peers::{self, Rejection, TaskSpec, PEER_HEADER};
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use std::net::SocketAddr;
+
+type ApiError = (StatusCode, Json<serde_json::Value>);
+
+fn error(status: StatusCode, message: &str) -> ApiError {
+    (status, Json(serde_json::json!({ "error": message })))
+}
+
+/// Name of the authenticated peer, or the error to answer with.
+fn authenticate(
+    state: &AppState,
+    peer_addr: SocketAddr,
+    headers: &HeaderMap,
+) -> Result<String, ApiError> {
+    let config = state.config.lock().peers.clone();
+    if !config.accept {
+        return Err(error(StatusCode::NOT_FOUND, "Not found"));
+    }
+    let rate_key = client_key_from_request(Some(peer_addr), headers, &state.proxies);
+    if state.rate_limiter.peer_locked_out(&rate_key) {
+        tracing::warn!("Peer task: {rate_key} locked out after repeated authentication failures");
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(serde_json::json!({
+                "error": "Too many failed peer authentications. Please retry later.",
+                "retry_after": RATE_LIMIT_WINDOW_SECS,
+            })),
+        ));
+    }
+    let peer = headers
+        .get(PEER_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .unwrap_or_default();
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    if peers::authenticate(&config, peer, token) {
+        return Ok(peer.to_string());
+    }
+    state.rate_limiter.record_peer_failure(&rate_key);
+    tracing::warn!("Peer task: rejected {rate_key} — unknown peer or invalid token");
+    audit_gateway_auth(state, &rate_key, "peers", Some("invalid peer credentials"));
+    Err(error(
+        StatusCode::UNAUTHORIZED,
+        "Unauthorized — send X-ZeroClaw-Peer and the shared Bearer token",
+    ))
+}
+
+/// POST /api/peers/tasks — start a task delegated by a peer
+pub async fn handle_submit(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Result<Json<TaskSpec>, axum::extract::rejection::JsonRejection>,
+) -> Response {
+    let peer = match authenticate(&state, peer_addr, &headers) {
+        Ok(peer) => peer,
+        Err(e) => return e.into_response(),
+    };
+    let Ok(Json(spec)) = body else {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "Invalid JSON body. Expected: {\"task\": \"...\", \"context\": \"...\", \"budget\": {...}}",
+        )
+        .into_response();
+    };
+    let config = state.config.lock().clone();
+    match peers::submit(&config, &peer, spec) {
+        Ok(submitted) => (StatusCode::ACCEPTED, Json(submitted)).into_response(),
+        Err(Rejection::Unauthorized) => {
+            error(StatusCode::UNAUTHORIZED, "Unknown peer").into_response()
+        }
+        Err(Rejection::Invalid(reason)) => error(StatusCode::BAD_REQUEST, &reason).into_response(),
+        Err(Rejection::Busy) => error(
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too many delegated tasks running. Please retry later.",
+        )
+        .into_response(),
+    }
+}
+
+/// GET /api/peers/tasks/{id} — status or result of a delegated task
+pub async fn handle_status(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    let peer = match authenticate(&state, peer_addr, &headers) {
+        Ok(peer) => peer,
+        Err(e) => return e.into_response(),
+    };
+    match peers::status(&peer, &id) {
+        Some(status) => Json(status).into_response(),
+        None => error(StatusCode::NOT_FOUND, "Unknown task").into_response(),
+    }
+}
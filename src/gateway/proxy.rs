@@ -0,0 +1,169 @@
+//! Real client addresses behind reverse proxies (`[gateway].trusted_proxies`).
+//!
+//! `X-Forwarded-For` and `X-Real-IP` are only believed when the connecting
+//! peer is a listed proxy. The client is then the nearest address in the
+//! `X-Forwarded-For` chain that is not itself a trusted proxy, so a client
+//! cannot pick its own address by sending the header.
+
+use crate::config::GatewayConfig;
+use crate::security::network::IpRange;
+use anyhow::{Context, Result};
+use axum::http::HeaderMap;
+use std::net::{IpAddr, SocketAddr};
+
+fn parse_client_ip(value: &str) -> Option<IpAddr> {
+    let value = value.trim().trim_matches('"').trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    if let Ok(ip) = value.parse::<IpAddr>() {
+        return Some(ip);
+    }
+
+    if let Ok(addr) = value.parse::<SocketAddr>() {
+        return Some(addr.ip());
+    }
+
+    let value = value.trim_matches(['[', ']']);
+    value.parse::<IpAddr>().ok()
+}
+
+/// Addresses in `X-Forwarded-For`, client first; unparsable entries are skipped.
+fn forwarded_for(headers: &HeaderMap) -> Vec<IpAddr> {
+    headers
+        .get_all("X-Forwarded-For")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(parse_client_ip)
+        .collect()
+}
+
+fn real_ip_header(headers: &HeaderMap) -> Option<IpAddr> {
+    headers
+        .get("X-Real-IP")
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_client_ip)
+}
+
+/// Peers allowed to report the client address in forwarded headers.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    ranges: Vec<IpRange>,
+    /// `trust_forwarded_headers = true` without `trusted_proxies`: headers
+    /// from any peer are believed, and the first forwarded address wins.
+    trust_any: bool,
+}
+
+impl TrustedProxies {
+    pub fn from_config(config: &GatewayConfig) -> Result<Self> {
+        let ranges = config
+            .trusted_proxies
+            .iter()
+            .map(|raw| {
+                IpRange::parse(raw)
+                    .with_context(|| format!("Invalid gateway.trusted_proxies entry '{raw}'"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            trust_any: ranges.is_empty() && config.trust_forwarded_headers,
+            ranges,
+        })
+    }
+
+    /// Whether forwarded headers from every peer are believed.
+    pub fn trusts_any(&self) -> bool {
+        self.trust_any
+    }
+
+    fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.trust_any || self.ranges.iter().any(|range| range.contains(ip))
+    }
+
+    /// Address of the client behind a request from `peer`.
+    pub fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let peer = peer.to_canonical();
+        if !self.is_trusted(peer) {
+            return peer;
+        }
+        let chain = forwarded_for(headers);
+        if self.trust_any {
+            return chain
+                .first()
+                .copied()
+                .or_else(|| real_ip_header(headers))
+                .unwrap_or(peer);
+        }
+        if chain.is_empty() {
+            return real_ip_header(headers).unwrap_or(peer);
+        }
+        // Walk back from the hop nearest to us; proxies append, so the first
+        // address not added by one of ours is the client.
+        chain
+            .iter()
+            .rev()
+            .copied()
+            .find(|ip| !self.is_trusted(*ip))
+            .or_else(|| chain.first().copied())
+            .unwrap_or(peer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn proxies(list: &[&str], trust_forwarded_headers: bool) -> TrustedProxies {
+        let config = GatewayConfig {
+            trusted_proxies: list.iter().map(|entry| (*entry).to_string()).collect(),
+            trust_forwarded_headers,
+            ..GatewayConfig::default()
+        };
+        TrustedProxies::from_config(&config).unwrap()
+    }
+
+    fn xff(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Forwarded-For", HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn forwarded_headers_only_count_from_listed_proxies() {
+        let proxies = proxies(&["10.0.0.0/8"], false);
+        let headers = xff("198.51.100.10");
+
+        let via_proxy = proxies.client_ip("10.0.0.5".parse().unwrap(), &headers);
+        assert_eq!(via_proxy, "198.51.100.10".parse::<IpAddr>().unwrap());
+
+        let direct = proxies.client_ip("203.0.113.7".parse().unwrap(), &headers);
+        assert_eq!(direct, "203.0.113.7".parse::<IpAddr>().unwrap());
+    }
+
+    #[test]
+    fn spoofed_leading_entries_are_ignored() {
+        let proxies = proxies(&["10.0.0.0/8"], false);
+        // The client sent "1.2.3.4" itself; our proxies appended the rest.
+        let headers = xff("1.2.3.4, 198.51.100.10, 10.0.0.9");
+        let ip = proxies.client_ip("10.0.0.5".parse().unwrap(), &headers);
+        assert_eq!(ip, "198.51.100.10".parse::<IpAddr>().unwrap());
+
+        let mut real_ip = HeaderMap::new();
+        real_ip.insert("X-Real-IP", HeaderValue::from_static("198.51.100.20"));
+        let ip = proxies.client_ip("::ffff:10.0.0.5".parse().unwrap(), &real_ip);
+        assert_eq!(ip, "198.51.100.20".parse::<IpAddr>().unwrap());
+    }
+
+    #[test]
+    fn invalid_entries_are_rejected() {
+        let config = GatewayConfig {
+            trusted_proxies: vec!["proxy.internal".into()],
+            ..GatewayConfig::default()
+        };
+        assert!(TrustedProxies::from_config(&config).is_err());
+        assert!(proxies(&[], true).trusts_any());
+        assert!(!proxies(&["10.0.0.1"], true).trusts_any());
+    }
+}
@@ -0,0 +1,96 @@
+//! Public read-only page for shared snapshots (`zeroclaw share`).
+//!
+//! `/share/{id}` needs no pairing token: the signed `exp`/`sig` query created
+//! by the CLI is the only credential, and it stops working at expiry or when
+//! the share is revoked.
+
+use super::AppState;
+use crate::share::ShareStore;
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{Html, IntoResponse, Response},
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct ShareQuery {
+    pub exp: Option<i64>,
+    pub sig: Option<String>,
+}
+
+/// GET /share/{id}?exp=..&sig=.. — render a shared snapshot
+pub async fn handle_share(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ShareQuery>,
+) -> Response {
+    let store = ShareStore::from_config(&state.config.lock());
+
+    let (Some(exp), Some(sig)) = (query.exp, query.sig.as_deref()) else {
+        return not_found();
+    };
+    if !store.verify(&id, exp, sig) {
+        return not_found();
+    }
+    let share = match store.get(&id) {
+        Ok(Some(share)) if share.expires_at.timestamp() == exp => share,
+        Ok(_) => return not_found(),
+        Err(e) => {
+            tracing::warn!("Failed to load share {id}: {e}");
+            return not_found();
+        }
+    };
+
+    let page = format!(
+        "<!doctype html><html><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <title>{title}</title>\
+         <style>body{{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem}}\
+         pre{{white-space:pre-wrap;word-break:break-word;background:#f5f5f5;padding:1rem;border-radius:6px}}\
+         small{{color:#666}}</style></head><body>\
+         <h1>{title}</h1><small>Shared from ZeroClaw · expires {expires}</small>\
+         <pre>{content}</pre></body></html>",
+        title = escape_html(&share.title),
+        expires = share.expires_at.format("%Y-%m-%d %H:%M UTC"),
+        content = escape_html(&share.content),
+    );
+    (
+        StatusCode::OK,
+        [
+            (header::CACHE_CONTROL, "no-store"),
+            (header::HeaderName::from_static("x-robots-tag"), "noindex"),
+            (
+                header::CONTENT_SECURITY_POLICY,
+                "default-src 'none'; style-src 'unsafe-inline'",
+            ),
+        ],
+        Html(page),
+    )
+        .into_response()
+}
+
+/// Missing, revoked, expired and forged links all look the same.
+fn not_found() -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        [(header::CACHE_CONTROL, "no-store")],
+        "This share does not exist or has expired.",
+    )
+        .into_response()
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -9,22 +9,66 @@
 //! Server -> Client: {"type":"done","full_response":"..."}
 //! ```
 
-use super::AppState;
+use super::{audit_gateway_auth, client_key_from_request, AppState};
 use axum::{
     extract::{
         ws::{Message, WebSocket},
-        Query, State, WebSocketUpgrade,
+        ConnectInfo, Query, State, WebSocketUpgrade,
     },
+    http::{HeaderMap, StatusCode},
     response::IntoResponse,
 };
 use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
+use std::net::SocketAddr;
 
 #[derive(Deserialize)]
 pub struct WsQuery {
     pub token: Option<String>,
 }
 
+/// GET /ws/node — WebSocket upgrade for federated worker nodes.
+///
+/// Only served when `[federation].role = "coordinator"`; nodes authenticate
+/// with their hello frame (see [`crate::federation`]). Failed hellos count
+/// toward the same lockout as peer authentication.
+pub async fn handle_ws_node(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    let federation = state.config.lock().federation.clone();
+    if federation.role != crate::config::FederationRole::Coordinator {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let rate_key = client_key_from_request(Some(peer_addr), &headers, &state.proxies);
+    if state.rate_limiter.peer_locked_out(&rate_key) {
+        tracing::warn!(
+            "Federation node: {rate_key} locked out after repeated authentication failures"
+        );
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too many failed node authentications. Please retry later.",
+        )
+            .into_response();
+    }
+
+    ws.max_message_size(crate::federation::MAX_FRAME_BYTES)
+        .on_upgrade(move |socket| {
+            crate::federation::coordinator::serve_node(socket, federation, move || {
+                state.rate_limiter.record_peer_failure(&rate_key);
+                audit_gateway_auth(
+                    &state,
+                    &rate_key,
+                    "federation",
+                    Some("invalid node credentials"),
+                );
+            })
+        })
+        .into_response()
+}
+
 /// GET /ws/chat — WebSocket upgrade for agent chat
 pub async fn handle_ws_chat(
     State(state): State<AppState>,
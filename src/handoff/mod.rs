@@ -0,0 +1,371 @@
+//! Session handoff between the CLI and channels.
+//!
+//! A conversation is parked for a target surface (`cli` or a channel name)
+//! in `<workspace>/state/handoff/<target>.json`, together with the provider
+//! and model it was using. The target claims it: the CLI with
+//! `zeroclaw agent --continue-here`, a channel with `/continue-here <code>`
+//! (the code keeps other allowed users of a shared bot from taking it).
+//! Claiming into a channel also re-routes pending owner questions there so
+//! `/answer` works from the new surface. One handoff waits per target: the
+//! user who parked it may replace it, anyone else is refused until it is
+//! claimed or expires after an hour. Three wrong codes discard it.
+
+use crate::providers::ChatMessage;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const HANDOFF_DIR: &str = "state/handoff";
+/// Target name for the interactive CLI.
+pub const CLI_TARGET: &str = "cli";
+/// Channel command that parks the conversation for another surface.
+pub const HANDOFF_COMMAND: &str = "/handoff";
+/// Channel command that claims a conversation parked for this channel.
+pub const CONTINUE_COMMAND: &str = "/continue-here";
+/// Unclaimed handoffs expire after this long.
+pub const HANDOFF_TTL_MINUTES: i64 = 60;
+/// Most recent turns carried over.
+const MAX_TURNS: usize = 40;
+/// Wrong `/continue-here` codes tolerated before the handoff is discarded.
+pub const MAX_CODE_ATTEMPTS: u32 = 3;
+
+/// A parked conversation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Handoff {
+    pub source: String,
+    pub target: String,
+    /// Who parked it (`cli` or `<channel>_<sender>`); only they may replace it.
+    #[serde(default)]
+    pub parked_by: String,
+    /// Six-digit code a channel must echo back in `/continue-here`.
+    pub code: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Wrong codes tried so far.
+    #[serde(default)]
+    pub failed_attempts: u32,
+    /// User and assistant turns, oldest first.
+    pub turns: Vec<ChatMessage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// Handoff options for an interactive `zeroclaw agent` session.
+#[derive(Debug, Clone, Default)]
+pub struct CliHandoff {
+    /// Conversation claimed with `--continue-here`, seeded into the session.
+    pub resume: Option<Handoff>,
+    /// Surface to park the session for when it ends (`--handoff`).
+    pub park_for: Option<String>,
+}
+
+/// A channel handoff command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandoffCommand {
+    /// `/handoff <target>`
+    Park(String),
+    /// `/continue-here [code]`
+    Continue(Option<String>),
+}
+
+/// Parse `/handoff <target>` and `/continue-here [code]` (with an optional
+/// `@bot` suffix, as Telegram sends in groups).
+pub fn parse_command(text: &str) -> Option<HandoffCommand> {
+    let text = text.trim();
+    let (token, args) = text
+        .split_once(char::is_whitespace)
+        .map_or((text, ""), |(token, args)| (token, args.trim()));
+    let command = token
+        .split('@')
+        .next()
+        .unwrap_or(token)
+        .to_ascii_lowercase();
+    let arg = (!args.is_empty()).then(|| args.to_string());
+    match command.as_str() {
+        HANDOFF_COMMAND => Some(HandoffCommand::Park(
+            arg.unwrap_or_default().to_ascii_lowercase(),
+        )),
+        // Telegram and Discord menus cannot contain '-', so accept '_' too.
+        CONTINUE_COMMAND | "/continue_here" => Some(HandoffCommand::Continue(arg)),
+        _ => None,
+    }
+}
+
+/// Keep the user/assistant turns worth carrying to another surface.
+pub fn transferable_turns(history: &[ChatMessage]) -> Vec<ChatMessage> {
+    let turns: Vec<ChatMessage> = history
+        .iter()
+        .filter(|m| matches!(m.role.as_str(), "user" | "assistant") && !m.content.is_empty())
+        .cloned()
+        .collect();
+    let skip = turns.len().saturating_sub(MAX_TURNS);
+    turns.into_iter().skip(skip).collect()
+}
+
+/// Parked handoffs for one workspace.
+pub struct HandoffStore {
+    dir: PathBuf,
+}
+
+impl HandoffStore {
+    pub fn for_workspace(workspace_dir: &Path) -> Self {
+        Self {
+            dir: workspace_dir.join(HANDOFF_DIR),
+        }
+    }
+
+    /// Park `turns` for `target`. A handoff already waiting there is replaced
+    /// only when `parked_by` parked it too.
+    pub fn park(
+        &self,
+        source: &str,
+        parked_by: &str,
+        target: &str,
+        turns: Vec<ChatMessage>,
+        provider: Option<String>,
+        model: Option<String>,
+    ) -> Result<Handoff> {
+        let target = target.trim().to_ascii_lowercase();
+        if target.is_empty() {
+            bail!("Name the surface to hand off to, e.g. `{HANDOFF_COMMAND} telegram` or `{HANDOFF_COMMAND} cli`");
+        }
+        if target.eq_ignore_ascii_case(source) {
+            bail!("This conversation is already on {target}");
+        }
+        if turns.is_empty() {
+            bail!("There is no conversation to hand off yet");
+        }
+        let now = Utc::now();
+        if let Some(waiting) = self.load(&target)? {
+            if waiting.expires_at > now && waiting.parked_by != parked_by {
+                bail!("Another conversation is already waiting to continue on {target}; try again once it is claimed or expires");
+            }
+        }
+        let handoff = Handoff {
+            source: source.to_string(),
+            target,
+            parked_by: parked_by.to_string(),
+            code: format!("{:06}", rand::random::<u32>() % 1_000_000),
+            created_at: now,
+            expires_at: now + Duration::minutes(HANDOFF_TTL_MINUTES),
+            failed_attempts: 0,
+            turns,
+            provider,
+            model,
+        };
+        self.write(&handoff)?;
+        Ok(handoff)
+    }
+
+    /// Take the handoff waiting for `target`. Channels must pass the code;
+    /// the CLI (a local, trusted surface) passes `None`. After
+    /// [`MAX_CODE_ATTEMPTS`] wrong codes the handoff is discarded.
+    pub fn claim(&self, target: &str, code: Option<&str>) -> Result<Handoff> {
+        let path = self.path(target)?;
+        let Some(mut handoff) = self.load(target)? else {
+            bail!("No conversation is waiting to continue on {target}");
+        };
+        if handoff.expires_at <= Utc::now() {
+            let _ = fs::remove_file(&path);
+            bail!("The waiting conversation expired; hand it off again");
+        }
+        if target != CLI_TARGET {
+            match code {
+                None => bail!("Include the handoff code: `{CONTINUE_COMMAND} <code>`"),
+                Some(code) if code.trim() != handoff.code => {
+                    handoff.failed_attempts += 1;
+                    if handoff.failed_attempts >= MAX_CODE_ATTEMPTS {
+                        fs::remove_file(&path)
+                            .with_context(|| format!("Failed to remove {}", path.display()))?;
+                        bail!("Wrong handoff code; the waiting conversation was discarded after {MAX_CODE_ATTEMPTS} wrong codes");
+                    }
+                    self.write(&handoff)?;
+                    bail!("Wrong handoff code");
+                }
+                Some(_) => {}
+            }
+        }
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+        Ok(handoff)
+    }
+
+    fn load(&self, target: &str) -> Result<Option<Handoff>> {
+        let path = self.path(target)?;
+        let raw = match fs::read(&path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        serde_json::from_slice(&raw)
+            .map(Some)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn write(&self, handoff: &Handoff) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.path(&handoff.target)?;
+        fs::write(&path, serde_json::to_vec_pretty(handoff)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn path(&self, target: &str) -> Result<PathBuf> {
+        let target = target.trim().to_ascii_lowercase();
+        if target.is_empty()
+            || !target
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            bail!("Invalid handoff target '{target}'");
+        }
+        Ok(self.dir.join(format!("{target}.json")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn conversation() -> Vec<ChatMessage> {
+        vec![
+            ChatMessage::system("system prompt"),
+            ChatMessage::user("check the deploy"),
+            ChatMessage::tool("{\"ok\":true}"),
+            ChatMessage::assistant("deploy is green"),
+        ]
+    }
+
+    #[test]
+    fn parse_recognizes_handoff_commands() {
+        assert_eq!(
+            parse_command("/handoff Telegram"),
+            Some(HandoffCommand::Park("telegram".into()))
+        );
+        assert_eq!(
+            parse_command("/continue-here@zc_bot 123456"),
+            Some(HandoffCommand::Continue(Some("123456".into())))
+        );
+        assert_eq!(
+            parse_command("/continue_here"),
+            Some(HandoffCommand::Continue(None))
+        );
+        assert_eq!(parse_command("/handoffs"), None);
+        assert_eq!(parse_command("handoff cli"), None);
+    }
+
+    #[test]
+    fn channel_claims_require_matching_code() {
+        let tmp = TempDir::new().unwrap();
+        let store = HandoffStore::for_workspace(tmp.path());
+        let turns = transferable_turns(&conversation());
+        assert_eq!(turns.len(), 2);
+        let parked = store
+            .park("cli", CLI_TARGET, "telegram", turns, None, Some("m".into()))
+            .unwrap();
+
+        assert!(store.claim("telegram", None).is_err());
+        assert!(store.claim("telegram", Some("not-it")).is_err());
+        assert_eq!(store.load("telegram").unwrap().unwrap().failed_attempts, 1);
+        let claimed = store.claim("telegram", Some(&parked.code)).unwrap();
+        assert_eq!(claimed.source, "cli");
+        assert_eq!(claimed.turns[1].content, "deploy is green");
+        assert!(store.claim("telegram", Some(&parked.code)).is_err());
+    }
+
+    #[test]
+    fn cli_claims_without_code_and_rejects_bad_handoffs() {
+        let tmp = TempDir::new().unwrap();
+        let store = HandoffStore::for_workspace(tmp.path());
+        let turns = transferable_turns(&conversation());
+        assert!(store
+            .park(
+                "telegram",
+                "telegram_alice",
+                "telegram",
+                turns.clone(),
+                None,
+                None
+            )
+            .is_err());
+        assert!(store
+            .park("telegram", "telegram_alice", "cli", Vec::new(), None, None)
+            .is_err());
+        assert!(store
+            .park(
+                "telegram",
+                "telegram_alice",
+                "../x",
+                turns.clone(),
+                None,
+                None
+            )
+            .is_err());
+
+        store
+            .park("telegram", "telegram_alice", "cli", turns, None, None)
+            .unwrap();
+        assert_eq!(store.claim(CLI_TARGET, None).unwrap().source, "telegram");
+    }
+
+    #[test]
+    fn wrong_codes_discard_the_handoff() {
+        let tmp = TempDir::new().unwrap();
+        let store = HandoffStore::for_workspace(tmp.path());
+        let turns = transferable_turns(&conversation());
+        let parked = store
+            .park("cli", CLI_TARGET, "telegram", turns, None, None)
+            .unwrap();
+        let wrong = if parked.code == "000000" {
+            "000001"
+        } else {
+            "000000"
+        };
+
+        for _ in 0..MAX_CODE_ATTEMPTS {
+            assert!(store.claim("telegram", Some(wrong)).is_err());
+        }
+        let err = store.claim("telegram", Some(&parked.code)).unwrap_err();
+        assert!(err.to_string().contains("No conversation is waiting"));
+    }
+
+    #[test]
+    fn pending_handoff_is_not_replaced_by_another_user() {
+        let tmp = TempDir::new().unwrap();
+        let store = HandoffStore::for_workspace(tmp.path());
+        let turns = transferable_turns(&conversation());
+        store
+            .park(
+                "telegram",
+                "telegram_alice",
+                "cli",
+                turns.clone(),
+                None,
+                None,
+            )
+            .unwrap();
+
+        let err = store
+            .park(
+                "telegram",
+                "telegram_mallory",
+                "cli",
+                turns.clone(),
+                None,
+                None,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("already waiting"));
+        store
+            .park("telegram", "telegram_alice", "cli", turns, None, None)
+            .unwrap();
+        assert_eq!(
+            store.claim(CLI_TARGET, None).unwrap().parked_by,
+            "telegram_alice"
+        );
+    }
+}
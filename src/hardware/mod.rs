@@ -3,6 +3,7 @@
 //! See `docs/hardware-peripherals-design.md` for the full design.
 
 pub mod registry;
+pub mod rtt;
 
 #[cfg(all(
     feature = "hardware",
@@ -103,10 +104,10 @@ pub fn config_from_wizard_choice(choice: usize, devices: &[DiscoveredDevice]) ->
 
 /// Handle `zeroclaw hardware` subcommands.
 #[allow(clippy::module_name_repetitions)]
-pub fn handle_command(cmd: crate::HardwareCommands, _config: &Config) -> Result<()> {
+pub fn handle_command(cmd: crate::HardwareCommands, config: &Config) -> Result<()> {
     #[cfg(not(feature = "hardware"))]
     {
-        let _ = &cmd;
+        let _ = (&cmd, config);
         println!("Hardware discovery requires the 'hardware' feature.");
         println!("Build with: cargo build --features hardware");
         return Ok(());
@@ -117,7 +118,7 @@ pub fn handle_command(cmd: crate::HardwareCommands, _config: &Config) -> Result<
         not(any(target_os = "linux", target_os = "macos", target_os = "windows"))
     ))]
     {
-        let _ = &cmd;
+        let _ = (&cmd, config);
         println!("Hardware USB discovery is not supported on this platform.");
         println!("Supported platforms: Linux, macOS, Windows.");
         return Ok(());
@@ -131,6 +132,12 @@ pub fn handle_command(cmd: crate::HardwareCommands, _config: &Config) -> Result<
         crate::HardwareCommands::Discover => run_discover(),
         crate::HardwareCommands::Introspect { path } => run_introspect(&path),
         crate::HardwareCommands::Info { chip } => run_info(&chip),
+        crate::HardwareCommands::Logs { chip, follow, wait } => {
+            let chip = chip
+                .or_else(|| config.hardware.probe_target.clone())
+                .unwrap_or_else(|| "STM32F401RETx".to_string());
+            run_logs(&chip, follow, wait, config.hardware.logs.semihosting)
+        }
     }
 }
 
@@ -190,6 +197,32 @@ fn run_introspect(path: &str) -> Result<()> {
     Ok(())
 }
 
+#[cfg(all(
+    feature = "hardware",
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+))]
+fn run_logs(chip: &str, follow: bool, wait_secs: u64, semihosting: bool) -> Result<()> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    if !follow {
+        let stop = Arc::clone(&stop);
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_secs(wait_secs));
+            stop.store(true, Ordering::Relaxed);
+        });
+    }
+
+    println!("Attaching to {chip} for device logs (Ctrl+C to stop)...");
+    let mut buffer = rtt::LogBuffer::new(1);
+    rtt::stream(chip, semihosting, &stop, |source, bytes| {
+        for line in buffer.push_bytes(source, bytes, chrono::Utc::now()) {
+            println!("{}", rtt::format_line(&line));
+        }
+    })
+}
+
 #[cfg(all(
     feature = "hardware",
     any(target_os = "linux", target_os = "macos", target_os = "windows")
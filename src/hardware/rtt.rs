@@ -0,0 +1,285 @@
+//! Device log capture over probe-rs RTT and semihosting (`[hardware.logs]`).
+//!
+//! The daemon attaches to the configured `probe_target`, reads every RTT up
+//! channel and (optionally) semihosting console writes, and appends complete
+//! lines to a process-wide ring buffer. The `hardware_logs` tool queries that
+//! buffer ("what did the firmware print in the last minute?");
+//! `zeroclaw hardware logs --follow` streams the same data to a terminal.
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::LazyLock;
+
+/// Partial lines longer than this are emitted without waiting for a newline.
+const MAX_LINE_CHARS: usize = 1024;
+
+/// One line printed by the firmware.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    pub timestamp: DateTime<Utc>,
+    /// `rtt:<channel name or number>` or `semihosting`.
+    pub source: String,
+    pub text: String,
+}
+
+/// Bounded buffer of device log lines, assembling lines per source.
+pub struct LogBuffer {
+    lines: VecDeque<LogLine>,
+    capacity: usize,
+    partial: HashMap<String, String>,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            capacity: capacity.max(1),
+            partial: HashMap::new(),
+        }
+    }
+
+    /// Change the capacity, dropping the oldest lines if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.lines.len() > self.capacity {
+            self.lines.pop_front();
+        }
+    }
+
+    /// Append raw bytes from `source`; complete lines are returned and stored.
+    pub fn push_bytes(&mut self, source: &str, bytes: &[u8], now: DateTime<Utc>) -> Vec<LogLine> {
+        let mut completed = Vec::new();
+        let pending = self.partial.entry(source.to_string()).or_default();
+        for ch in String::from_utf8_lossy(bytes).chars() {
+            match ch {
+                '\n' => completed.push(std::mem::take(pending)),
+                '\r' => {}
+                _ => {
+                    pending.push(ch);
+                    if pending.chars().count() >= MAX_LINE_CHARS {
+                        completed.push(std::mem::take(pending));
+                    }
+                }
+            }
+        }
+
+        let completed: Vec<LogLine> = completed
+            .into_iter()
+            .map(|text| LogLine {
+                timestamp: now,
+                source: source.to_string(),
+                text,
+            })
+            .collect();
+        for line in &completed {
+            if self.lines.len() == self.capacity {
+                self.lines.pop_front();
+            }
+            self.lines.push_back(line.clone());
+        }
+        completed
+    }
+
+    /// The newest `limit` lines at or after `since`, optionally filtered by a
+    /// case-insensitive substring, oldest first.
+    pub fn query(
+        &self,
+        since: DateTime<Utc>,
+        contains: Option<&str>,
+        limit: usize,
+    ) -> Vec<LogLine> {
+        let needle = contains.map(str::to_lowercase);
+        let mut matched: Vec<LogLine> = self
+            .lines
+            .iter()
+            .rev()
+            .take_while(|line| line.timestamp >= since)
+            .filter(|line| {
+                needle
+                    .as_deref()
+                    .is_none_or(|needle| line.text.to_lowercase().contains(needle))
+            })
+            .take(limit)
+            .cloned()
+            .collect();
+        matched.reverse();
+        matched
+    }
+}
+
+static LOGS: LazyLock<Mutex<LogBuffer>> = LazyLock::new(|| Mutex::new(LogBuffer::new(2000)));
+static CAPTURING: AtomicBool = AtomicBool::new(false);
+
+/// Query the process-wide device log buffer (see [`LogBuffer::query`]).
+pub fn recent(since: DateTime<Utc>, contains: Option<&str>, limit: usize) -> Vec<LogLine> {
+    LOGS.lock().query(since, contains, limit)
+}
+
+/// Whether the daemon capture is running and has received device output.
+pub fn is_capturing() -> bool {
+    CAPTURING.load(Ordering::Relaxed)
+}
+
+/// Format a line for terminal or tool output.
+pub fn format_line(line: &LogLine) -> String {
+    format!(
+        "{} [{}] {}",
+        line.timestamp.format("%H:%M:%S%.3f"),
+        line.source,
+        line.text
+    )
+}
+
+/// Daemon component: attach to `[hardware].probe_target` and feed the buffer
+/// until the probe connection fails.
+pub async fn run_capture(config: crate::config::Config) -> anyhow::Result<()> {
+    let chip = config
+        .hardware
+        .probe_target
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("hardware.probe_target is not set"))?;
+    let settings = config.hardware.logs.clone();
+    LOGS.lock().set_capacity(settings.buffer_lines);
+
+    let result = tokio::task::spawn_blocking(move || {
+        let stop = AtomicBool::new(false);
+        stream(&chip, settings.semihosting, &stop, |source, bytes| {
+            CAPTURING.store(true, Ordering::Relaxed);
+            LOGS.lock().push_bytes(source, bytes, Utc::now());
+        })
+    })
+    .await;
+    CAPTURING.store(false, Ordering::Relaxed);
+    result?
+}
+
+/// Attach to `chip` and pass raw RTT/semihosting output to `sink` until
+/// `stop` is set or the probe fails. Blocks the calling thread.
+#[cfg(all(feature = "hardware", feature = "probe"))]
+pub fn stream(
+    chip: &str,
+    semihosting: bool,
+    stop: &AtomicBool,
+    mut sink: impl FnMut(&str, &[u8]),
+) -> anyhow::Result<()> {
+    use probe_rs::rtt::Rtt;
+    use probe_rs::semihosting::SemihostingCommand;
+    use probe_rs::{BreakpointCause, CoreStatus, HaltReason, Session, SessionConfig};
+    use std::num::NonZeroU32;
+    use std::time::Duration;
+
+    let mut session = Session::auto_attach(chip, SessionConfig::default())
+        .map_err(|e| anyhow::anyhow!("probe-rs attach to {chip} failed: {e}"))?;
+    let mut core = session.core(0)?;
+    let mut rtt = match Rtt::attach(&mut core) {
+        Ok(rtt) => Some(rtt),
+        Err(e) if semihosting => {
+            tracing::warn!(
+                "No RTT control block found on {chip} ({e}); capturing semihosting only"
+            );
+            None
+        }
+        Err(e) => anyhow::bail!("No RTT control block found on {chip}: {e}"),
+    };
+    tracing::info!(chip = %chip, "Attached to target for device log capture");
+
+    let mut buf = vec![0u8; 1024];
+    while !stop.load(Ordering::Relaxed) {
+        let mut idle = true;
+
+        if let Some(rtt) = rtt.as_mut() {
+            for channel in rtt.up_channels() {
+                let read = channel.read(&mut core, &mut buf)?;
+                if read > 0 {
+                    idle = false;
+                    let source = match channel.name() {
+                        Some(name) if !name.is_empty() => format!("rtt:{name}"),
+                        _ => format!("rtt:{}", channel.number()),
+                    };
+                    sink(&source, &buf[..read]);
+                }
+            }
+        }
+
+        if semihosting {
+            if let CoreStatus::Halted(HaltReason::Breakpoint(BreakpointCause::Semihosting(
+                command,
+            ))) = core.status()?
+            {
+                idle = false;
+                match command {
+                    SemihostingCommand::WriteConsole(request) => {
+                        let text = request.read(&mut core)?;
+                        sink("semihosting", text.as_bytes());
+                    }
+                    SemihostingCommand::Write(request) => {
+                        let bytes = request.read(&mut core)?;
+                        sink("semihosting", &bytes);
+                        request.write_status(&mut core, 0)?;
+                    }
+                    SemihostingCommand::Open(request) if request.path(&mut core)? == ":tt" => {
+                        request.respond_with_handle(&mut core, NonZeroU32::MIN)?;
+                    }
+                    _ => {}
+                }
+                core.run()?;
+            }
+        }
+
+        if idle {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(all(feature = "hardware", feature = "probe")))]
+pub fn stream(
+    _chip: &str,
+    _semihosting: bool,
+    _stop: &AtomicBool,
+    _sink: impl FnMut(&str, &[u8]),
+) -> anyhow::Result<()> {
+    anyhow::bail!("Device log capture requires the probe feature. Build with: cargo build --features hardware,probe")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn lines_are_assembled_across_chunks_per_source() {
+        let mut buffer = LogBuffer::new(10);
+        let now = Utc::now();
+        assert!(buffer.push_bytes("rtt:Terminal", b"temp=2", now).is_empty());
+        buffer.push_bytes("semihosting", b"boot ok\n", now);
+        let lines = buffer.push_bytes("rtt:Terminal", b"1.5C\r\nhumidity=40%\n", now);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].text, "temp=21.5C");
+        assert_eq!(lines[0].source, "rtt:Terminal");
+
+        let all = buffer.query(now - Duration::seconds(1), None, 10);
+        let texts: Vec<_> = all.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(texts, ["boot ok", "temp=21.5C", "humidity=40%"]);
+    }
+
+    #[test]
+    fn query_honours_window_filter_limit_and_capacity() {
+        let mut buffer = LogBuffer::new(3);
+        let now = Utc::now();
+        buffer.push_bytes("rtt:0", b"old ERROR\n", now - Duration::minutes(5));
+        buffer.push_bytes("rtt:0", b"a\nERROR b\nerror c\n", now);
+        assert_eq!(buffer.query(now - Duration::minutes(10), None, 10).len(), 3);
+
+        let errors = buffer.query(now - Duration::minutes(1), Some("error"), 10);
+        assert_eq!(errors.len(), 2);
+        let last = buffer.query(now - Duration::minutes(1), None, 1);
+        assert_eq!(last[0].text, "error c");
+
+        buffer.set_capacity(1);
+        assert_eq!(buffer.query(now - Duration::minutes(1), None, 10).len(), 1);
+    }
+}
@@ -75,7 +75,7 @@ impl HeartbeatEngine {
     }
 
     /// Parse tasks from HEARTBEAT.md (lines starting with `- `)
-    fn parse_tasks(content: &str) -> Vec<String> {
+    pub fn parse_tasks(content: &str) -> Vec<String> {
         content
             .lines()
             .filter_map(|line| {
@@ -248,6 +248,7 @@ mod tests {
             HeartbeatConfig {
                 enabled: true,
                 interval_minutes: 30,
+                probes: Vec::new(),
             },
             dir.clone(),
             observer,
@@ -273,6 +274,7 @@ mod tests {
             HeartbeatConfig {
                 enabled: true,
                 interval_minutes: 30,
+                probes: Vec::new(),
             },
             dir.clone(),
             observer,
@@ -290,6 +292,7 @@ mod tests {
             HeartbeatConfig {
                 enabled: false,
                 interval_minutes: 30,
+                probes: Vec::new(),
             },
             std::env::temp_dir(),
             observer,
@@ -0,0 +1,806 @@
+//! Heartbeat health probes (`[[heartbeat.probes]]`).
+//!
+//! Each probe runs on its own schedule. Results are folded into a persisted
+//! per-probe state, and only a state change (healthy → failing after
+//! `failure_threshold` misses, or failing → healthy) is handed to the agent.
+//! Steady state costs no LLM calls.
+
+use crate::config::{Config, HeartbeatProbeConfig, PeripheralBoardConfig, ProbeCheck};
+use crate::state::{retire_legacy_file, StateStore};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const PROBE_STATE_FILE: &str = "heartbeat_probes.json";
+const STATE_KEY_PREFIX: &str = "heartbeat.probe.";
+
+/// Result of a single probe check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeOutcome {
+    pub healthy: bool,
+    pub detail: String,
+}
+
+impl ProbeOutcome {
+    pub fn ok(detail: impl Into<String>) -> Self {
+        Self {
+            healthy: true,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn fail(detail: impl Into<String>) -> Self {
+        Self {
+            healthy: false,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// A health check the heartbeat can run.
+#[async_trait]
+pub trait Probe: Send + Sync {
+    async fn check(&self) -> ProbeOutcome;
+}
+
+/// Build the probe implementation for a configured check.
+pub fn build_probe(check: &ProbeCheck, config: &Config) -> Box<dyn Probe> {
+    match check {
+        ProbeCheck::DiskSpace {
+            path,
+            min_free_percent,
+        } => Box::new(DiskSpaceProbe {
+            path: PathBuf::from(path),
+            min_free_percent: *min_free_percent,
+        }),
+        ProbeCheck::CertExpiry {
+            host,
+            port,
+            warn_days,
+        } => Box::new(CertExpiryProbe {
+            host: host.clone(),
+            port: *port,
+            warn_days: *warn_days,
+        }),
+        ProbeCheck::Url {
+            url,
+            expect_status,
+            timeout_secs,
+        } => Box::new(UrlProbe {
+            url: url.clone(),
+            expect_status: *expect_status,
+            timeout: Duration::from_secs((*timeout_secs).max(1)),
+        }),
+        ProbeCheck::Process { process } => Box::new(ProcessProbe {
+            process: process.clone(),
+        }),
+        ProbeCheck::Peripheral { board } => Box::new(PeripheralProbe {
+            name: board.clone(),
+            board: config
+                .peripherals
+                .boards
+                .iter()
+                .find(|b| b.board == *board)
+                .cloned(),
+        }),
+        ProbeCheck::Script {
+            command,
+            timeout_secs,
+        } => Box::new(ScriptProbe {
+            command: command.clone(),
+            timeout: Duration::from_secs((*timeout_secs).max(1)),
+            workspace_dir: config.workspace_dir.clone(),
+        }),
+    }
+}
+
+// ── Probe implementations ───────────────────────────────────────
+
+struct DiskSpaceProbe {
+    path: PathBuf,
+    min_free_percent: f64,
+}
+
+#[async_trait]
+impl Probe for DiskSpaceProbe {
+    async fn check(&self) -> ProbeOutcome {
+        match free_space_percent(&self.path) {
+            Ok(free) if free >= self.min_free_percent => {
+                ProbeOutcome::ok(format!("{free:.1}% free on {}", self.path.display()))
+            }
+            Ok(free) => ProbeOutcome::fail(format!(
+                "only {free:.1}% free on {} (minimum {:.1}%)",
+                self.path.display(),
+                self.min_free_percent
+            )),
+            Err(e) => ProbeOutcome::fail(format!("cannot stat {}: {e}", self.path.display())),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn free_space_percent(path: &Path) -> Result<f64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())?;
+    // SAFETY: `statvfs` only writes into the zeroed struct we own, and
+    // `c_path` is a valid NUL-terminated string for the duration of the call.
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    let rc = unsafe { libc::statvfs(c_path.as_ptr(), std::ptr::addr_of_mut!(stat)) };
+    if rc != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    #[allow(clippy::cast_precision_loss, clippy::unnecessary_cast)]
+    let (total, available) = (stat.f_blocks as f64, stat.f_bavail as f64);
+    if total <= 0.0 {
+        anyhow::bail!("filesystem reports zero size");
+    }
+    Ok(available / total * 100.0)
+}
+
+#[cfg(not(unix))]
+fn free_space_percent(_path: &Path) -> Result<f64> {
+    anyhow::bail!("disk_space probes are only supported on Unix")
+}
+
+struct CertExpiryProbe {
+    host: String,
+    port: u16,
+    warn_days: u32,
+}
+
+#[async_trait]
+impl Probe for CertExpiryProbe {
+    async fn check(&self) -> ProbeOutcome {
+        let not_after =
+            match tokio::time::timeout(Duration::from_secs(15), self.fetch_not_after()).await {
+                Ok(Ok(not_after)) => not_after,
+                Ok(Err(e)) => return ProbeOutcome::fail(format!("TLS check failed: {e}")),
+                Err(_) => return ProbeOutcome::fail("TLS check timed out"),
+            };
+        let days_left = (not_after - Utc::now()).num_days();
+        let detail = format!(
+            "certificate for {} expires {} ({days_left} days)",
+            self.host,
+            not_after.format("%Y-%m-%d")
+        );
+        if days_left > i64::from(self.warn_days) {
+            ProbeOutcome::ok(detail)
+        } else {
+            ProbeOutcome::fail(detail)
+        }
+    }
+}
+
+impl CertExpiryProbe {
+    async fn fetch_not_after(&self) -> Result<DateTime<Utc>> {
+        use tokio_rustls::rustls;
+
+        let tcp = tokio::net::TcpStream::connect((self.host.as_str(), self.port)).await?;
+        let root_store: rustls::RootCertStore =
+            webpki_roots::TLS_SERVER_ROOTS.iter().cloned().collect();
+        let tls_config = rustls::ClientConfig::builder()
+            .with_root_certificates(root_store)
+            .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(tls_config));
+        let domain = rustls::pki_types::ServerName::try_from(self.host.clone())?;
+        let tls = connector.connect(domain, tcp).await?;
+        let (_, session) = tls.get_ref();
+        let leaf = session
+            .peer_certificates()
+            .and_then(|certs| certs.first())
+            .context("server sent no certificate")?;
+        cert_not_after(leaf.as_ref()).context("could not read certificate validity")
+    }
+}
+
+/// Walk the DER just far enough to reach `tbsCertificate.validity.notAfter`.
+fn cert_not_after(der: &[u8]) -> Option<DateTime<Utc>> {
+    let (_, certificate, _) = der_read(der)?;
+    let (_, tbs, _) = der_read(certificate)?;
+    let mut rest = tbs;
+    // Optional explicit `[0] version`.
+    if rest.first() == Some(&0xA0) {
+        rest = der_read(rest)?.2;
+    }
+    // serialNumber, signature, issuer
+    for _ in 0..3 {
+        rest = der_read(rest)?.2;
+    }
+    let (_, validity, _) = der_read(rest)?;
+    let after_not_before = der_read(validity)?.2;
+    let (tag, value, _) = der_read(after_not_before)?;
+    parse_asn1_time(tag, value)
+}
+
+/// Split one TLV off `input`: `(tag, value, rest)`.
+fn der_read(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let tag = *input.first()?;
+    let first = *input.get(1)?;
+    let (len, header) = if first & 0x80 == 0 {
+        (usize::from(first), 2)
+    } else {
+        let count = usize::from(first & 0x7f);
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let mut len = 0usize;
+        for i in 0..count {
+            len = (len << 8) | usize::from(*input.get(2 + i)?);
+        }
+        (len, 2 + count)
+    };
+    let end = header.checked_add(len)?;
+    Some((tag, input.get(header..end)?, input.get(end..)?))
+}
+
+fn parse_asn1_time(tag: u8, value: &[u8]) -> Option<DateTime<Utc>> {
+    let text = std::str::from_utf8(value).ok()?.strip_suffix('Z')?;
+    let full = match tag {
+        // UTCTime: YYMMDDHHMMSS, years 50-99 are 19xx.
+        0x17 => {
+            let year: u32 = text.get(..2)?.parse().ok()?;
+            let century = if year >= 50 { "19" } else { "20" };
+            format!("{century}{text}")
+        }
+        // GeneralizedTime: YYYYMMDDHHMMSS
+        0x18 => text.to_string(),
+        _ => return None,
+    };
+    NaiveDateTime::parse_from_str(&full, "%Y%m%d%H%M%S")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+struct UrlProbe {
+    url: String,
+    expect_status: Option<u16>,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl Probe for UrlProbe {
+    async fn check(&self) -> ProbeOutcome {
+        let client = match reqwest::Client::builder().timeout(self.timeout).build() {
+            Ok(client) => client,
+            Err(e) => return ProbeOutcome::fail(format!("HTTP client error: {e}")),
+        };
+        let started = Instant::now();
+        match client.get(&self.url).send().await {
+            Ok(response) => {
+                let status = response.status();
+                let healthy = match self.expect_status {
+                    Some(expected) => status.as_u16() == expected,
+                    None => status.is_success(),
+                };
+                let detail = format!(
+                    "{} returned {status} in {}ms",
+                    self.url,
+                    started.elapsed().as_millis()
+                );
+                ProbeOutcome { healthy, detail }
+            }
+            Err(e) => ProbeOutcome::fail(format!("{} unreachable: {e}", self.url)),
+        }
+    }
+}
+
+struct ProcessProbe {
+    process: String,
+}
+
+#[async_trait]
+impl Probe for ProcessProbe {
+    async fn check(&self) -> ProbeOutcome {
+        let output = tokio::process::Command::new("pgrep")
+            .arg("-x")
+            .arg(&self.process)
+            .output()
+            .await;
+        match output {
+            Ok(output) if output.status.success() => {
+                let count = String::from_utf8_lossy(&output.stdout).lines().count();
+                ProbeOutcome::ok(format!("{} running ({count} process(es))", self.process))
+            }
+            Ok(_) => ProbeOutcome::fail(format!("{} is not running", self.process)),
+            Err(e) => ProbeOutcome::fail(format!("pgrep failed: {e}")),
+        }
+    }
+}
+
+struct PeripheralProbe {
+    name: String,
+    board: Option<PeripheralBoardConfig>,
+}
+
+#[async_trait]
+impl Probe for PeripheralProbe {
+    async fn check(&self) -> ProbeOutcome {
+        let Some(board) = &self.board else {
+            return ProbeOutcome::fail(format!(
+                "board {} is not in [[peripherals.boards]]",
+                self.name
+            ));
+        };
+        let path = board.path.as_deref().unwrap_or_default();
+        match board.transport.as_str() {
+            "serial" if path.is_empty() => {
+                ProbeOutcome::fail(format!("board {} has no serial path", self.name))
+            }
+            "serial" if Path::new(path).exists() => {
+                ProbeOutcome::ok(format!("{} present at {path}", self.name))
+            }
+            "serial" => ProbeOutcome::fail(format!("{} missing: {path} not found", self.name)),
+            "websocket" => {
+                let Some(addr) = websocket_authority(path) else {
+                    return ProbeOutcome::fail(format!("board {} has no valid URL", self.name));
+                };
+                let connect = tokio::net::TcpStream::connect(addr.as_str());
+                match tokio::time::timeout(Duration::from_secs(5), connect).await {
+                    Ok(Ok(_)) => ProbeOutcome::ok(format!("{} reachable at {addr}", self.name)),
+                    Ok(Err(e)) => ProbeOutcome::fail(format!("{} unreachable: {e}", self.name)),
+                    Err(_) => ProbeOutcome::fail(format!("{} timed out at {addr}", self.name)),
+                }
+            }
+            _ => ProbeOutcome::ok(format!("{} uses {} transport", self.name, board.transport)),
+        }
+    }
+}
+
+/// `ws://host:port/path` → `host:port` (default ports 80/443).
+fn websocket_authority(url: &str) -> Option<String> {
+    let (default_port, rest) = if let Some(rest) = url.strip_prefix("wss://") {
+        (443, rest)
+    } else {
+        (80, url.strip_prefix("ws://")?)
+    };
+    let authority = rest.split('/').next().filter(|a| !a.is_empty())?;
+    if authority
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| port.parse::<u16>().is_ok())
+    {
+        Some(authority.to_string())
+    } else {
+        Some(format!("{authority}:{default_port}"))
+    }
+}
+
+struct ScriptProbe {
+    command: String,
+    timeout: Duration,
+    workspace_dir: PathBuf,
+}
+
+#[async_trait]
+impl Probe for ScriptProbe {
+    async fn check(&self) -> ProbeOutcome {
+        let output = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .current_dir(&self.workspace_dir)
+            .kill_on_drop(true)
+            .output();
+        match tokio::time::timeout(self.timeout, output).await {
+            Ok(Ok(output)) => {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let last_line = stdout
+                    .lines()
+                    .chain(stderr.lines())
+                    .rfind(|line| !line.trim().is_empty())
+                    .unwrap_or("")
+                    .trim();
+                let detail = crate::util::truncate_with_ellipsis(
+                    &format!("exit {}: {last_line}", output.status.code().unwrap_or(-1)),
+                    300,
+                );
+                ProbeOutcome {
+                    healthy: output.status.success(),
+                    detail,
+                }
+            }
+            Ok(Err(e)) => ProbeOutcome::fail(format!("failed to run script: {e}")),
+            Err(_) => ProbeOutcome::fail(format!(
+                "script timed out after {}s",
+                self.timeout.as_secs()
+            )),
+        }
+    }
+}
+
+// ── State tracking ──────────────────────────────────────────────
+
+/// Persisted state of one probe.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbeState {
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    pub detail: String,
+    pub last_checked: DateTime<Utc>,
+    pub since: DateTime<Utc>,
+}
+
+/// A probe changed state and the agent should look at it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeTransition {
+    pub probe: String,
+    pub healthy: bool,
+    pub detail: String,
+    pub instruction: Option<String>,
+}
+
+impl ProbeTransition {
+    pub fn prompt(&self) -> String {
+        if self.healthy {
+            format!(
+                "[Heartbeat Probe] {} recovered: {}. Follow up on anything done while it was failing.",
+                self.probe, self.detail
+            )
+        } else {
+            let instruction = self
+                .instruction
+                .as_deref()
+                .unwrap_or("Investigate the cause and notify the owner if action is needed.");
+            format!(
+                "[Heartbeat Probe] {} is failing: {}. {instruction}",
+                self.probe, self.detail
+            )
+        }
+    }
+}
+
+/// Fold one outcome into the previous state. Returns the new state and
+/// whether the probe flipped. Unknown probes start out healthy, so a first
+/// success is silent and a first failure counts toward the threshold.
+pub fn apply_outcome(
+    previous: Option<&ProbeState>,
+    outcome: &ProbeOutcome,
+    failure_threshold: u32,
+    now: DateTime<Utc>,
+) -> (ProbeState, bool) {
+    let was_healthy = previous.is_none_or(|state| state.healthy);
+    let failures = if outcome.healthy {
+        0
+    } else {
+        previous.map_or(0, |state| state.consecutive_failures) + 1
+    };
+    let healthy = outcome.healthy || (was_healthy && failures < failure_threshold.max(1));
+    let flipped = healthy != was_healthy;
+    let since = match previous {
+        Some(state) if !flipped => state.since,
+        _ => now,
+    };
+    (
+        ProbeState {
+            healthy,
+            consecutive_failures: failures,
+            detail: outcome.detail.clone(),
+            last_checked: now,
+            since,
+        },
+        flipped,
+    )
+}
+
+/// Runs configured probes when due and reports state transitions.
+pub struct ProbeRunner {
+    probes: Vec<(HeartbeatProbeConfig, Box<dyn Probe>)>,
+    default_interval: Duration,
+    next_due: HashMap<String, Instant>,
+    states: HashMap<String, ProbeState>,
+    store: StateStore,
+}
+
+impl ProbeRunner {
+    pub fn new(config: &Config) -> Self {
+        let probes = config
+            .heartbeat
+            .probes
+            .iter()
+            .map(|probe| (probe.clone(), build_probe(&probe.check, config)))
+            .collect();
+        let store = StateStore::for_config(config);
+        import_legacy_states(&store, &probe_state_path(&config.workspace_dir));
+        Self::with_probes(probes, config.heartbeat.interval_minutes, store)
+    }
+
+    pub fn with_probes(
+        probes: Vec<(HeartbeatProbeConfig, Box<dyn Probe>)>,
+        default_interval_minutes: u32,
+        store: StateStore,
+    ) -> Self {
+        Self {
+            probes,
+            default_interval: minutes(default_interval_minutes),
+            next_due: HashMap::new(),
+            states: load_states(&store),
+            store,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.probes.is_empty()
+    }
+
+    /// Check every probe whose schedule is due and return the transitions
+    /// that should wake the agent.
+    pub async fn run_due(&mut self) -> Vec<ProbeTransition> {
+        let now = Instant::now();
+        let mut transitions = Vec::new();
+
+        for (probe_config, probe) in &self.probes {
+            let name = &probe_config.name;
+            if self.next_due.get(name).is_some_and(|due| *due > now) {
+                continue;
+            }
+            let interval = probe_config
+                .interval_minutes
+                .map_or(self.default_interval, minutes);
+            self.next_due.insert(name.clone(), now + interval);
+
+            let outcome = probe.check().await;
+            let (state, flipped) = apply_outcome(
+                self.states.get(name),
+                &outcome,
+                probe_config.failure_threshold,
+                Utc::now(),
+            );
+            let changed = self.states.get(name).is_none_or(|previous| {
+                previous.healthy != state.healthy
+                    || previous.consecutive_failures != state.consecutive_failures
+            });
+            if changed {
+                let cause = if flipped {
+                    format!(
+                        "{name} {}",
+                        if state.healthy { "recovered" } else { "failed" }
+                    )
+                } else {
+                    format!("{name} checked")
+                };
+                if let Err(e) = self.store.set(&state_key(name), &state, &cause) {
+                    tracing::warn!("Failed to persist heartbeat probe state: {e:#}");
+                }
+            }
+            if flipped {
+                tracing::info!(
+                    probe = name.as_str(),
+                    healthy = state.healthy,
+                    "Heartbeat probe changed state: {}",
+                    state.detail
+                );
+                if !state.healthy || probe_config.notify_recovery {
+                    transitions.push(ProbeTransition {
+                        probe: name.clone(),
+                        healthy: state.healthy,
+                        detail: state.detail.clone(),
+                        instruction: probe_config.on_failure.clone(),
+                    });
+                }
+            }
+            self.states.insert(name.clone(), state);
+        }
+
+        transitions
+    }
+}
+
+fn minutes(value: u32) -> Duration {
+    Duration::from_secs(u64::from(value.max(1)) * 60)
+}
+
+fn probe_state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(PROBE_STATE_FILE)
+}
+
+fn state_key(probe: &str) -> String {
+    format!("{STATE_KEY_PREFIX}{probe}")
+}
+
+fn load_states(store: &StateStore) -> HashMap<String, ProbeState> {
+    let entries = store.entries(STATE_KEY_PREFIX).unwrap_or_else(|e| {
+        tracing::warn!("Failed to read heartbeat probe state: {e:#}");
+        std::collections::BTreeMap::default()
+    });
+    entries
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let name = key.strip_prefix(STATE_KEY_PREFIX)?.to_string();
+            Some((name, serde_json::from_value(value).ok()?))
+        })
+        .collect()
+}
+
+/// Move probe states from the pre-store `heartbeat_probes.json` into `store`.
+fn import_legacy_states(store: &StateStore, path: &Path) {
+    let Some(states) = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<HashMap<String, ProbeState>>(&raw).ok())
+    else {
+        return;
+    };
+    for (name, state) in &states {
+        if let Err(e) = store.set(&state_key(name), state, "import legacy probe state") {
+            tracing::warn!("Failed to import heartbeat probe state: {e:#}");
+            return;
+        }
+    }
+    retire_legacy_file(path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct ScriptedProbe {
+        outcomes: Mutex<Vec<bool>>,
+    }
+
+    #[async_trait]
+    impl Probe for ScriptedProbe {
+        async fn check(&self) -> ProbeOutcome {
+            let healthy = self.outcomes.lock().unwrap().remove(0);
+            ProbeOutcome {
+                healthy,
+                detail: if healthy { "up" } else { "down" }.into(),
+            }
+        }
+    }
+
+    fn probe_config(name: &str, failure_threshold: u32) -> HeartbeatProbeConfig {
+        HeartbeatProbeConfig {
+            name: name.into(),
+            check: ProbeCheck::Process {
+                process: "unused".into(),
+            },
+            interval_minutes: None,
+            failure_threshold,
+            on_failure: Some("Restart it.".into()),
+            notify_recovery: true,
+        }
+    }
+
+    fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag];
+        if content.len() < 0x80 {
+            out.push(u8::try_from(content.len()).unwrap());
+        } else {
+            out.push(0x82);
+            out.extend_from_slice(&u16::try_from(content.len()).unwrap().to_be_bytes());
+        }
+        out.extend_from_slice(content);
+        out
+    }
+
+    #[test]
+    fn failures_flip_state_only_after_threshold() {
+        let now = Utc::now();
+        let fail = ProbeOutcome::fail("down");
+        let (first, flipped) = apply_outcome(None, &fail, 2, now);
+        assert!(first.healthy);
+        assert!(!flipped);
+        let (second, flipped) = apply_outcome(Some(&first), &fail, 2, now);
+        assert!(!second.healthy);
+        assert!(flipped);
+        let (third, flipped) = apply_outcome(Some(&second), &fail, 2, now);
+        assert!(!third.healthy);
+        assert!(!flipped);
+        let (recovered, flipped) = apply_outcome(Some(&third), &ProbeOutcome::ok("up"), 2, now);
+        assert!(recovered.healthy);
+        assert!(flipped);
+        assert_eq!(recovered.consecutive_failures, 0);
+    }
+
+    #[test]
+    fn first_success_is_silent() {
+        let (state, flipped) = apply_outcome(None, &ProbeOutcome::ok("up"), 1, Utc::now());
+        assert!(state.healthy);
+        assert!(!flipped);
+    }
+
+    #[tokio::test]
+    async fn runner_reports_transitions_and_persists_state() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let probe = ScriptedProbe {
+            outcomes: Mutex::new(vec![false, true]),
+        };
+        let mut runner = ProbeRunner::with_probes(
+            vec![(probe_config("api", 1), Box::new(probe))],
+            30,
+            StateStore::open(tmp.path()),
+        );
+
+        let transitions = runner.run_due().await;
+        assert_eq!(transitions.len(), 1);
+        assert!(!transitions[0].healthy);
+        assert!(transitions[0]
+            .prompt()
+            .contains("api is failing: down. Restart it."));
+
+        // Not due again until the interval passes.
+        assert!(runner.run_due().await.is_empty());
+
+        let saved = load_states(&StateStore::open(tmp.path()));
+        assert!(!saved["api"].healthy);
+        assert_eq!(
+            StateStore::open(tmp.path())
+                .history("heartbeat.probe.api", 10)
+                .unwrap()[0]
+                .cause,
+            "api failed"
+        );
+    }
+
+    #[test]
+    fn reads_not_after_from_certificate_der() {
+        let validity = [tlv(0x17, b"240101000000Z"), tlv(0x18, b"20300615120000Z")].concat();
+        let tbs = [
+            tlv(0xA0, &tlv(0x02, &[2])),
+            tlv(0x02, &[1, 2, 3]),
+            tlv(0x30, &tlv(0x06, &[1, 2])),
+            tlv(0x30, &[0u8; 200]),
+            tlv(0x30, &validity),
+        ]
+        .concat();
+        let cert = tlv(0x30, &[tlv(0x30, &tbs), tlv(0x30, &[])].concat());
+
+        let not_after = cert_not_after(&cert).unwrap();
+        assert_eq!(
+            not_after.format("%Y-%m-%d %H:%M").to_string(),
+            "2030-06-15 12:00"
+        );
+    }
+
+    #[test]
+    fn websocket_authority_adds_default_ports() {
+        assert_eq!(
+            websocket_authority("ws://10.0.0.5/ws").as_deref(),
+            Some("10.0.0.5:80")
+        );
+        assert_eq!(
+            websocket_authority("wss://board.local:8443/x").as_deref(),
+            Some("board.local:8443")
+        );
+        assert_eq!(websocket_authority("/dev/ttyUSB0"), None);
+    }
+
+    #[test]
+    fn probe_config_parses_from_toml() {
+        let config: crate::config::HeartbeatConfig = toml::from_str(
+            r#"
+enabled = true
+interval_minutes = 30
+
+[[probes]]
+name = "root-disk"
+kind = "disk_space"
+min_free_percent = 5
+
+[[probes]]
+name = "site"
+kind = "url"
+url = "https://example.com/health"
+interval_minutes = 5
+failure_threshold = 3
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.probes.len(), 2);
+        assert_eq!(
+            config.probes[0].check,
+            ProbeCheck::DiskSpace {
+                path: "/".into(),
+                min_free_percent: 5.0,
+            }
+        );
+        assert_eq!(config.probes[1].interval_minutes, Some(5));
+        assert_eq!(config.probes[1].failure_threshold, 3);
+        assert!(config.probes[1].notify_recovery);
+    }
+}
@@ -0,0 +1,136 @@
+# Deutsch
+
+## Consent and warning reason codes
+
+consent-reason-risky_pack = Auswahl aktiviert risikobehaftete Pakete
+consent-reason-rebuild = Neubau ersetzt die installierte Binärdatei
+consent-reason-security_non_strict = Sicherheitsprofil ist nicht strikt
+security-risk_reason-non_strict_profile = Sicherheitsprofil ist nicht strikt
+security-risk_reason-non_cli_auto_approval = Nicht-CLI-Kanäle dürfen Werkzeugaufrufe automatisch freigeben
+onboard-warning-risky_pack_requires_consent = risikobehaftete Pakete erfordern `--yes-security-risk`
+onboard-warning-security_non_strict_requires_consent = ein nicht striktes Sicherheitsprofil erfordert `--yes-security-risk`
+
+## Approval prompts
+
+approval-wants_to_execute = 🔧 Der Agent möchte ausführen: { $tool }
+approval-no_content_changes = (keine inhaltlichen Änderungen)
+approval-choices = [Y] Ja / [N] Nein / [A] Immer für { $tool }:
+approval-four_eyes_needed = 🔐 Freigabe erforderlich: { $requester } möchte '{ $tool }' mit { $args } ausführen
+    Antworte innerhalb von { $seconds } s mit /approve { $code } oder /deny { $code }.
+
+## Channel system messages
+
+channel-current_route =
+    Aktueller Anbieter: `{ $provider }`
+    Aktuelles Modell: `{ $model }`
+channel-switch_model_hint = Modell wechseln mit `/model <model-id>`.
+channel-switch_provider_hint = Anbieter wechseln mit `/models <provider>`.
+channel-no_cached_models = Keine zwischengespeicherte Modellliste für `{ $provider }` gefunden. Bitte den Betreiber, `zeroclaw models refresh --provider { $provider }` auszuführen.
+channel-cached_models = Zwischengespeicherte Modell-IDs (Top { $count }):
+channel-available_providers = Verfügbare Anbieter:
+channel-provider_aliases = Aliase: { $aliases }
+channel-provider_switched =
+    Anbieter für diese Sitzung auf `{ $provider }` umgestellt. Aktuelles Modell ist `{ $model }`.
+    Mit `/model <model-id>` ein zum Anbieter passendes Modell festlegen.
+channel-provider_switch_failed =
+    Anbieter `{ $provider }` konnte nicht initialisiert werden. Route unverändert.
+    Details: { $details }
+channel-unknown_provider = Unbekannter Anbieter `{ $provider }`. Mit `/models` gültige Anbieter auflisten.
+channel-model_empty = Die Modell-ID darf nicht leer sein. Verwende `/model <model-id>`.
+channel-model_switched = Modell für Anbieter `{ $provider }` in dieser Sitzung auf `{ $model }` umgestellt.
+channel-provider_unavailable =
+    ⚠️ Anbieter `{ $provider }` konnte nicht initialisiert werden. Bitte mit `/models` einen anderen Anbieter wählen.
+    Details: { $details }
+channel-context_overflow_compacted = ⚠️ Kontextfenster für diese Unterhaltung überschritten. Ich habe den jüngsten Verlauf verdichtet und den neuesten Kontext behalten. Bitte sende deine letzte Nachricht erneut.
+channel-context_overflow = ⚠️ Kontextfenster für diese Unterhaltung überschritten. Bitte sende deine letzte Nachricht erneut.
+channel-request_timeout = ⚠️ Zeitüberschreitung beim Warten auf das Modell. Bitte versuche es erneut.
+channel-error = ⚠️ Fehler: { $error }
+channel-running_tool = ⏳ Werkzeug läuft: { $tool }…
+channel-preparing_tool = ⏳ Werkzeug wird vorbereitet: { $tool }…
+channel-tool_stream_issue = ⚠️ Problem beim Werkzeugaufruf: { $issue }
+channel-long_reply_attached = 📎 Die vollständige Antwort ({ $chars } Zeichen) ist als `{ $file }` angehängt.
+channel-task_failed = ⚠️ Aufgabe konnte nicht ausgeführt werden: { $details }
+channel-question_asked =
+    ❓ Frage { $id }: { $question }
+    Antworte mit `/answer { $id } <deine Antwort>`.
+channel-question_answered = ✅ Danke, die Aufgabe, die auf { $id } wartet, wird gleich fortgesetzt.
+channel-question_answer_failed = ⚠️ Antwort konnte nicht gespeichert werden: { $details }
+channel-handoff_parked = 📤 Unterhaltung für { $target } gespeichert. Sende dort innerhalb von { $minutes } Minuten `/continue-here { $code }`, um sie fortzusetzen.
+channel-handoff_parked_cli = 📤 Unterhaltung für die CLI gespeichert. Führe innerhalb von { $minutes } Minuten `zeroclaw agent --continue-here` aus, um sie fortzusetzen.
+channel-handoff_resumed = 📥 Setze die Unterhaltung von { $source } fort ({ $turns } Nachrichten).
+channel-handoff_failed = ⚠️ Die Unterhaltung konnte nicht übergeben werden: { $details }
+channel-preference_saved = ✅ Notiert, daran halte ich mich ab jetzt ({ $preference }).
+channel-preference_failed = ⚠️ Die Einstellung konnte nicht gespeichert werden: { $details }
+channel-instruction_saved = ✅ Dauerhafte Anweisung #{ $id } für diesen Kanal gespeichert: { $text }
+channel-instruction_removed = 🗑️ Dauerhafte Anweisung #{ $id } entfernt.
+channel-instruction_missing = ⚠️ Es gibt keine dauerhafte Anweisung #{ $id }.
+channel-instruction_failed = ⚠️ Die dauerhaften Anweisungen konnten nicht aktualisiert werden: { $details }
+channel-approval_granted = ✅ Anfrage { $code } genehmigt: { $requester } darf { $tool } ausführen.
+channel-approval_denied = 🚫 Anfrage { $code } abgelehnt: { $tool } für { $requester } wird nicht ausgeführt.
+channel-approval_failed = ⚠️ Deine Entscheidung konnte nicht gespeichert werden: { $details }
+channel-incognito_on = 🕶️ Inkognito ist an. Nichts aus dieser Unterhaltung wird im Gedächtnis gespeichert, und sie wird nach { $minutes } Minuten Inaktivität vergessen. Sende `/incognito off`, um es zu beenden.
+channel-incognito_off = Inkognito ist aus. Die Inkognito-Unterhaltung wurde verworfen.
+channel-incognito_inactive = Inkognito ist nicht aktiv. Sende `/incognito`, um eine Inkognito-Sitzung zu starten.
+channel-incognito_always = 🕶️ Dieser Kanal ist immer inkognito: Nichts wird gespeichert, und Unterhaltungen werden nach { $minutes } Minuten Inaktivität vergessen.
+channel-incognito_no_handoff = ⚠️ Inkognito-Unterhaltungen können nicht übergeben werden.
+channel-incognito_not_saved = 🕶️ Inkognito ist aktiv, daher wurde diese Änderung nicht gespeichert.
+channel-verify_code_challenge = 🔐 Ich antworte nur Personen, die mein Besitzer bestätigt hat. Frag ihn nach deinem Bestätigungscode und sende innerhalb von { $minutes } Minuten `/verify <code>`.
+channel-verify_approve_challenge = 🔐 Ich antworte nur Personen, die mein Besitzer bestätigt hat. Ich habe ihn gebeten, dich freizugeben; schreib wieder, sobald er es getan hat.
+channel-verify_success = ✅ Danke, du bist bestätigt. Wie kann ich helfen?
+channel-verify_wrong_code = ⚠️ Dieser Code stimmt nicht. Noch { $remaining } Versuche.
+channel-verify_locked = 🚫 Zu viele falsche Codes. Versuche es in { $minutes } Minuten erneut.
+channel-verify_owner_code = Neuer Absender { $identity } möchte mit dem Agenten sprechen. Wenn du ihn kennst, gib ihm diesen Code: { $code } ({ $minutes } Minuten gültig). Oder lass ihn direkt zu mit `zeroclaw channel approve-sender { $identity }`.
+channel-verify_owner_approve = Neuer Absender { $identity } möchte mit dem Agenten sprechen. Lass ihn zu mit `zeroclaw channel approve-sender { $identity }`.
+channel-admin_help =
+    Befehle für den Besitzer:
+    `!status`: Kanäle, Standardmodell, Not-Aus
+    `!budget`: Ausgaben im Vergleich zu den Kostenlimits
+    `!pause [Kanal]` / `!resume [Kanal]`: einen Kanal anhalten oder fortsetzen (standardmäßig diesen)
+    `!model <Name>`: Standardmodell wechseln
+    `!estop [network]`: Not-Aus auslösen
+    Wenn OTP verlangt wird, `!pause`, `!model` und `!estop` mit dem aktuellen Code abschließen.
+channel-simulate_unavailable = 🧪 `/simulate` ist nur in `zeroclaw agent` verfügbar; es wurde nichts ausgeführt.
+channel-admin_denied = 🚫 Admin-Befehle werden nur vom Besitzer angenommen.
+channel-admin_otp_required = 🔐 Dieser Befehl braucht deinen aktuellen OTP-Code als letztes Wort, z. B. `!estop 123456`.
+channel-admin_otp_invalid = 🚫 Dieser OTP-Code ist nicht gültig.
+channel-admin_otp_unavailable = 🚫 Dieser Befehl braucht einen OTP-Code, aber OTP ist auf diesem Host nicht eingerichtet (`[security.otp]`).
+channel-admin_failed = ⚠️ Der Admin-Befehl ist fehlgeschlagen: { $details }
+channel-admin_status =
+    📊 Kanäle: { $channels }
+    Angehalten: { $paused }
+    Standardmodell: `{ $model }` (Anbieter `{ $provider }`)
+    Not-Aus: { $estop }
+    Aktive Unterhaltungen: { $sessions }
+channel-admin_budget = 💰 Heute: { $daily } von { $daily_limit } USD. Dieser Monat: { $monthly } von { $monthly_limit } USD.
+channel-admin_budget_disabled = Kostenerfassung ist aus (`[cost] enabled = false`).
+channel-admin_paused = ⏸️ { $channel } angehalten. Nachrichten dort werden verworfen, bis du `!resume { $channel }` sendest.
+channel-admin_already_paused = { $channel } ist bereits angehalten.
+channel-admin_resumed = ▶️ { $channel } fortgesetzt.
+channel-admin_not_paused = { $channel } ist nicht angehalten.
+channel-admin_model = ✅ Das Standardmodell ist jetzt `{ $model }` (Anbieter `{ $provider }`), bis sich die Konfigurationsdatei ändert. Wer mit `/model` ein Modell gewählt hat, behält es.
+channel-admin_estop = 🛑 Not-Aus ausgelöst: { $state }. Aufheben auf dem Host mit `zeroclaw estop resume`.
+channel-auto_started = 🤖 Autonomer Modus an für { $minutes } Min. oder { $actions } Tool-Aufrufe: Ich handle ohne Freigabe und kündige jeden Schritt hier an. Mit `/auto off` beenden.
+channel-auto_status = 🤖 Autonomer Modus: noch { $minutes } Min. und { $actions } Tool-Aufrufe.
+channel-auto_inactive = Der autonome Modus ist aus. Starten z. B. mit `/auto 20m`.
+channel-auto_stopped = ✋ Autonomer Modus nach { $used } Tool-Aufrufen beendet. Freigaben gelten wieder wie gewohnt.
+channel-auto_disabled = 🚫 Der autonome Modus ist deaktiviert (`[autonomy.auto_mode]`).
+channel-auto_owner_only = 🚫 Nur der Besitzer kann den autonomen Modus starten.
+channel-auto_usage = Verwendung: `/auto 20m` oder `/auto 1h` zum Starten, `/auto` für den Status, `/auto off` zum Beenden.
+channel-auto_action = 🤖 auto: `{ $tool }` { $summary } (noch { $remaining })
+channel-auto_ended_timer = ⏱️ Der autonome Modus ist abgelaufen; Freigaben gelten wieder wie gewohnt.
+channel-auto_ended_budget = ⏱️ Der autonome Modus hat alle { $actions } Tool-Aufrufe verbraucht; Freigaben gelten wieder wie gewohnt.
+channel-cost_suffix = 💰 { $reply } für diese Antwort · { $total } bisher in diesem Gespräch
+
+## CLI output
+
+cli-preset-saved_selection = Preset-Auswahl des Arbeitsbereichs gespeichert: { $path }
+cli-preset-risky_packs = Risikobehaftete Pakete: { $packs }
+cli-preset-risky_packs_need_consent = Die Auswahl enthält risikobehaftete Pakete [{ $packs }]. Erneut mit `--yes-risky` ausführen oder `--dry-run` verwenden.
+cli-preset-missing_features = Nicht in diese Binärdatei kompilierte Pakete: { $packs }
+cli-preset-missing_features_need_rebuild = Die Pakete [{ $packs }] benötigen Cargo-Features, ohne die diese Binärdatei gebaut wurde. Erneut mit `--rebuild --yes-rebuild` ausführen oder `--dry-run` verwenden.
+cli-preset-apply_dry_run = Apply-Probelauf: keine Änderungen geschrieben.
+cli-preset-import_dry_run = Import-Probelauf: keine Änderungen geschrieben.
+cli-preset-intent_apply_dry_run = Intent-Apply-Probelauf: keine Änderungen geschrieben.
+cli-preset-plan_only = Nur Plan. Erneut mit `--apply` ausführen, um diese Auswahl zu speichern.
+cli-preset-knowledge_dry_run = Wissensdokumente (im Probelauf nicht abgerufen):
+cli-cost_footer = 💰 Diese Antwort: { $reply_cost } ({ $reply_tokens } Tokens) · Gespräch: { $total_cost } ({ $total_tokens } Tokens)
@@ -0,0 +1,137 @@
+# English (source of truth). Keys mirror the dotted i18n keys used in code,
+# with `.` replaced by `-`.
+
+## Consent and warning reason codes (machine-readable keys in JSON reports)
+
+consent-reason-risky_pack = selection enables risk-gated packs
+consent-reason-rebuild = rebuild replaces the installed binary
+consent-reason-security_non_strict = security profile is not strict
+security-risk_reason-non_strict_profile = security profile is not strict
+security-risk_reason-non_cli_auto_approval = non-CLI channels may auto-approve tool calls
+onboard-warning-risky_pack_requires_consent = risky packs require `--yes-security-risk`
+onboard-warning-security_non_strict_requires_consent = non-strict security profile requires `--yes-security-risk`
+
+## Approval prompts
+
+approval-wants_to_execute = 🔧 Agent wants to execute: { $tool }
+approval-no_content_changes = (no content changes)
+approval-choices = [Y]es / [N]o / [A]lways for { $tool }:
+approval-four_eyes_needed = 🔐 Approval needed: { $requester } wants to run '{ $tool }' with { $args }
+    Reply /approve { $code } or /deny { $code } within { $seconds }s.
+
+## Channel system messages
+
+channel-current_route =
+    Current provider: `{ $provider }`
+    Current model: `{ $model }`
+channel-switch_model_hint = Switch model with `/model <model-id>`.
+channel-switch_provider_hint = Switch provider with `/models <provider>`.
+channel-no_cached_models = No cached model list found for `{ $provider }`. Ask the operator to run `zeroclaw models refresh --provider { $provider }`.
+channel-cached_models = Cached model IDs (top { $count }):
+channel-available_providers = Available providers:
+channel-provider_aliases = aliases: { $aliases }
+channel-provider_switched =
+    Provider switched to `{ $provider }` for this sender session. Current model is `{ $model }`.
+    Use `/model <model-id>` to set a provider-compatible model.
+channel-provider_switch_failed =
+    Failed to initialize provider `{ $provider }`. Route unchanged.
+    Details: { $details }
+channel-unknown_provider = Unknown provider `{ $provider }`. Use `/models` to list valid providers.
+channel-model_empty = Model ID cannot be empty. Use `/model <model-id>`.
+channel-model_switched = Model switched to `{ $model }` for provider `{ $provider }` in this sender session.
+channel-provider_unavailable =
+    ⚠️ Failed to initialize provider `{ $provider }`. Please run `/models` to choose another provider.
+    Details: { $details }
+channel-context_overflow_compacted = ⚠️ Context window exceeded for this conversation. I compacted recent history and kept the latest context. Please resend your last message.
+channel-context_overflow = ⚠️ Context window exceeded for this conversation. Please resend your last message.
+channel-request_timeout = ⚠️ Request timed out while waiting for the model. Please try again.
+channel-error = ⚠️ Error: { $error }
+channel-running_tool = ⏳ Running tool: { $tool }…
+channel-preparing_tool = ⏳ Preparing tool: { $tool }…
+channel-tool_stream_issue = ⚠️ Tool call problem: { $issue }
+channel-long_reply_attached = 📎 The full reply ({ $chars } characters) is attached as `{ $file }`.
+channel-task_failed = ⚠️ Could not run task: { $details }
+channel-question_asked =
+    ❓ Question { $id }: { $question }
+    Reply with `/answer { $id } <your answer>`.
+channel-question_answered = ✅ Thanks, the task waiting on { $id } will resume shortly.
+channel-question_answer_failed = ⚠️ Could not record the answer: { $details }
+channel-handoff_parked = 📤 Conversation saved for { $target }. Send `/continue-here { $code }` there within { $minutes } minutes to pick it up.
+channel-handoff_parked_cli = 📤 Conversation saved for the CLI. Run `zeroclaw agent --continue-here` within { $minutes } minutes to pick it up.
+channel-handoff_resumed = 📥 Continuing the conversation from { $source } ({ $turns } messages).
+channel-handoff_failed = ⚠️ Could not hand off the conversation: { $details }
+channel-preference_saved = ✅ Noted, I will keep that in mind from now on ({ $preference }).
+channel-preference_failed = ⚠️ Could not save the preference: { $details }
+channel-instruction_saved = ✅ Saved standing instruction #{ $id } for this channel: { $text }
+channel-instruction_removed = 🗑️ Removed standing instruction #{ $id }.
+channel-instruction_missing = ⚠️ There is no standing instruction #{ $id }.
+channel-instruction_failed = ⚠️ Could not update standing instructions: { $details }
+channel-approval_granted = ✅ Approved request { $code }: { $requester } may run { $tool }.
+channel-approval_denied = 🚫 Denied request { $code }: { $tool } for { $requester } will not run.
+channel-approval_failed = ⚠️ Could not record your decision: { $details }
+channel-incognito_on = 🕶️ Incognito is on. Nothing from this conversation is saved to memory, and it is forgotten after { $minutes } idle minutes. Send `/incognito off` to end it.
+channel-incognito_off = Incognito is off. The incognito conversation has been discarded.
+channel-incognito_inactive = Incognito is not on. Send `/incognito` to start an incognito session.
+channel-incognito_always = 🕶️ This channel is always incognito: nothing is saved to memory, and conversations are forgotten after { $minutes } idle minutes.
+channel-incognito_no_handoff = ⚠️ Incognito conversations cannot be handed off.
+channel-incognito_not_saved = 🕶️ Incognito is on, so this change was not saved.
+channel-verify_code_challenge = 🔐 I only answer people my owner has verified. Ask them for your verification code, then send `/verify <code>` within { $minutes } minutes.
+channel-verify_approve_challenge = 🔐 I only answer people my owner has verified. I have asked them to approve you; write again once they have.
+channel-verify_success = ✅ Thanks, you are verified. How can I help?
+channel-verify_wrong_code = ⚠️ That code is not right. { $remaining } attempts left.
+channel-verify_locked = 🚫 Too many wrong codes. Try again in { $minutes } minutes.
+channel-verify_owner_code = New sender { $identity } wants to talk to the agent. If you know them, give them this code: { $code } (valid { $minutes } minutes). Or let them in with `zeroclaw channel approve-sender { $identity }`.
+channel-verify_owner_approve = New sender { $identity } wants to talk to the agent. Let them in with `zeroclaw channel approve-sender { $identity }`.
+channel-admin_help =
+    Owner commands:
+    `!status`: channels, default model, emergency stop
+    `!budget`: spend against the cost limits
+    `!pause [channel]` / `!resume [channel]`: stop or restart a channel (this one by default)
+    `!model <name>`: switch the default model
+    `!estop [network]`: engage the emergency stop
+    When OTP is required, end `!pause`, `!model` and `!estop` with your current code.
+channel-simulate_unavailable = 🧪 `/simulate` is only available in `zeroclaw agent`; nothing was run.
+channel-admin_denied = 🚫 Admin commands are only accepted from the owner.
+channel-admin_otp_required = 🔐 This command needs your current OTP code as its last word, e.g. `!estop 123456`.
+channel-admin_otp_invalid = 🚫 That OTP code is not valid.
+channel-admin_otp_unavailable = 🚫 This command needs an OTP code, but OTP is not set up on this host (`[security.otp]`).
+channel-admin_failed = ⚠️ The admin command failed: { $details }
+channel-admin_status =
+    📊 Channels: { $channels }
+    Paused: { $paused }
+    Default model: `{ $model }` (provider `{ $provider }`)
+    Emergency stop: { $estop }
+    Active conversations: { $sessions }
+channel-admin_budget = 💰 Today: { $daily } of { $daily_limit } USD. This month: { $monthly } of { $monthly_limit } USD.
+channel-admin_budget_disabled = Cost tracking is off (`[cost] enabled = false`).
+channel-admin_paused = ⏸️ Paused { $channel }. Messages there are dropped until you send `!resume { $channel }`.
+channel-admin_already_paused = { $channel } is already paused.
+channel-admin_resumed = ▶️ Resumed { $channel }.
+channel-admin_not_paused = { $channel } is not paused.
+channel-admin_model = ✅ The default model is now `{ $model }` (provider `{ $provider }`) until the config file changes. Senders who chose a model with `/model` keep theirs.
+channel-admin_estop = 🛑 Emergency stop engaged: { $state }. Resume it on the host with `zeroclaw estop resume`.
+channel-auto_started = 🤖 Autonomous mode on for { $minutes } min or { $actions } tool calls: I'll act without asking for approval and announce each step here. Send `/auto off` to stop.
+channel-auto_status = 🤖 Autonomous mode: { $minutes } min and { $actions } tool calls left.
+channel-auto_inactive = Autonomous mode is off. Start it with e.g. `/auto 20m`.
+channel-auto_stopped = ✋ Autonomous mode off after { $used } tool calls. Approvals are back to normal.
+channel-auto_disabled = 🚫 Autonomous mode is disabled (`[autonomy.auto_mode]`).
+channel-auto_owner_only = 🚫 Autonomous mode can only be started by the owner.
+channel-auto_usage = Usage: `/auto 20m` or `/auto 1h` to start, `/auto` for the status, `/auto off` to stop.
+channel-auto_action = 🤖 auto: `{ $tool }` { $summary } ({ $remaining } left)
+channel-auto_ended_timer = ⏱️ Autonomous mode expired; approvals are back to normal.
+channel-auto_ended_budget = ⏱️ Autonomous mode used all { $actions } tool calls; approvals are back to normal.
+channel-cost_suffix = 💰 { $reply } for this reply · { $total } so far in this conversation
+
+## CLI output
+
+cli-preset-saved_selection = Saved workspace preset selection: { $path }
+cli-preset-risky_packs = Risky packs: { $packs }
+cli-preset-risky_packs_need_consent = Selection includes risky packs [{ $packs }]. Re-run with `--yes-risky`, or use `--dry-run`.
+cli-preset-missing_features = Packs not compiled into this binary: { $packs }
+cli-preset-missing_features_need_rebuild = Packs [{ $packs }] need cargo features this binary was built without. Re-run with `--rebuild --yes-rebuild`, or use `--dry-run`.
+cli-preset-apply_dry_run = Apply dry-run: no changes written.
+cli-preset-import_dry_run = Import dry-run: no changes written.
+cli-preset-intent_apply_dry_run = Intent apply dry-run: no changes written.
+cli-preset-plan_only = Plan only. Re-run with `--apply` to persist this selection.
+cli-preset-knowledge_dry_run = Knowledge documents (not fetched in dry-run):
+cli-cost_footer = 💰 This reply: { $reply_cost } ({ $reply_tokens } tokens) · Conversation: { $total_cost } ({ $total_tokens } tokens)
@@ -0,0 +1,136 @@
+# Español
+
+## Consent and warning reason codes
+
+consent-reason-risky_pack = la selección habilita paquetes con riesgo controlado
+consent-reason-rebuild = la recompilación reemplaza el binario instalado
+consent-reason-security_non_strict = el perfil de seguridad no es estricto
+security-risk_reason-non_strict_profile = el perfil de seguridad no es estricto
+security-risk_reason-non_cli_auto_approval = los canales que no son CLI pueden aprobar herramientas automáticamente
+onboard-warning-risky_pack_requires_consent = los paquetes de riesgo requieren `--yes-security-risk`
+onboard-warning-security_non_strict_requires_consent = un perfil de seguridad no estricto requiere `--yes-security-risk`
+
+## Approval prompts
+
+approval-wants_to_execute = 🔧 El agente quiere ejecutar: { $tool }
+approval-no_content_changes = (sin cambios de contenido)
+approval-choices = [Y] sí / [N] no / [A] siempre para { $tool }:
+approval-four_eyes_needed = 🔐 Se necesita aprobación: { $requester } quiere ejecutar '{ $tool }' con { $args }
+    Responde /approve { $code } o /deny { $code } en { $seconds } s.
+
+## Channel system messages
+
+channel-current_route =
+    Proveedor actual: `{ $provider }`
+    Modelo actual: `{ $model }`
+channel-switch_model_hint = Cambia de modelo con `/model <model-id>`.
+channel-switch_provider_hint = Cambia de proveedor con `/models <provider>`.
+channel-no_cached_models = No hay una lista de modelos en caché para `{ $provider }`. Pide al operador que ejecute `zeroclaw models refresh --provider { $provider }`.
+channel-cached_models = IDs de modelos en caché (primeros { $count }):
+channel-available_providers = Proveedores disponibles:
+channel-provider_aliases = alias: { $aliases }
+channel-provider_switched =
+    Proveedor cambiado a `{ $provider }` para esta sesión. El modelo actual es `{ $model }`.
+    Usa `/model <model-id>` para elegir un modelo compatible con el proveedor.
+channel-provider_switch_failed =
+    No se pudo inicializar el proveedor `{ $provider }`. La ruta no cambió.
+    Detalles: { $details }
+channel-unknown_provider = Proveedor desconocido `{ $provider }`. Usa `/models` para ver los proveedores válidos.
+channel-model_empty = El ID del modelo no puede estar vacío. Usa `/model <model-id>`.
+channel-model_switched = Modelo cambiado a `{ $model }` para el proveedor `{ $provider }` en esta sesión.
+channel-provider_unavailable =
+    ⚠️ No se pudo inicializar el proveedor `{ $provider }`. Ejecuta `/models` para elegir otro proveedor.
+    Detalles: { $details }
+channel-context_overflow_compacted = ⚠️ Se superó la ventana de contexto de esta conversación. Compacté el historial reciente y conservé el contexto más reciente. Vuelve a enviar tu último mensaje.
+channel-context_overflow = ⚠️ Se superó la ventana de contexto de esta conversación. Vuelve a enviar tu último mensaje.
+channel-request_timeout = ⚠️ Se agotó el tiempo de espera del modelo. Inténtalo de nuevo.
+channel-error = ⚠️ Error: { $error }
+channel-running_tool = ⏳ Ejecutando herramienta: { $tool }…
+channel-preparing_tool = ⏳ Preparando herramienta: { $tool }…
+channel-tool_stream_issue = ⚠️ Problema en la llamada a herramienta: { $issue }
+channel-long_reply_attached = 📎 La respuesta completa ({ $chars } caracteres) se adjunta como `{ $file }`.
+channel-task_failed = ⚠️ No se pudo ejecutar la tarea: { $details }
+channel-question_asked =
+    ❓ Pregunta { $id }: { $question }
+    Responde con `/answer { $id } <tu respuesta>`.
+channel-question_answered = ✅ Gracias, la tarea que esperaba { $id } se reanudará en breve.
+channel-question_answer_failed = ⚠️ No se pudo registrar la respuesta: { $details }
+channel-handoff_parked = 📤 Conversación guardada para { $target }. Envía `/continue-here { $code }` allí en los próximos { $minutes } minutos para retomarla.
+channel-handoff_parked_cli = 📤 Conversación guardada para la CLI. Ejecuta `zeroclaw agent --continue-here` en los próximos { $minutes } minutos para retomarla.
+channel-handoff_resumed = 📥 Continuando la conversación desde { $source } ({ $turns } mensajes).
+channel-handoff_failed = ⚠️ No se pudo transferir la conversación: { $details }
+channel-preference_saved = ✅ Anotado, lo tendré en cuenta a partir de ahora ({ $preference }).
+channel-preference_failed = ⚠️ No se pudo guardar la preferencia: { $details }
+channel-instruction_saved = ✅ Instrucción permanente #{ $id } guardada para este canal: { $text }
+channel-instruction_removed = 🗑️ Instrucción permanente #{ $id } eliminada.
+channel-instruction_missing = ⚠️ No existe la instrucción permanente #{ $id }.
+channel-instruction_failed = ⚠️ No se pudieron actualizar las instrucciones permanentes: { $details }
+channel-approval_granted = ✅ Solicitud { $code } aprobada: { $requester } puede ejecutar { $tool }.
+channel-approval_denied = 🚫 Solicitud { $code } denegada: { $tool } para { $requester } no se ejecutará.
+channel-approval_failed = ⚠️ No se pudo registrar tu decisión: { $details }
+channel-incognito_on = 🕶️ Modo incógnito activado. Nada de esta conversación se guarda en memoria y se olvidará tras { $minutes } minutos de inactividad. Envía `/incognito off` para terminarlo.
+channel-incognito_off = Modo incógnito desactivado. La conversación incógnito se ha descartado.
+channel-incognito_inactive = El modo incógnito no está activo. Envía `/incognito` para iniciar una sesión incógnito.
+channel-incognito_always = 🕶️ Este canal siempre es incógnito: no se guarda nada en memoria y las conversaciones se olvidan tras { $minutes } minutos de inactividad.
+channel-incognito_no_handoff = ⚠️ Las conversaciones incógnito no se pueden transferir.
+channel-incognito_not_saved = 🕶️ El modo incógnito está activo, así que este cambio no se guardó.
+channel-verify_code_challenge = 🔐 Solo respondo a personas verificadas por mi propietario. Pídele tu código de verificación y envía `/verify <código>` en los próximos { $minutes } minutos.
+channel-verify_approve_challenge = 🔐 Solo respondo a personas verificadas por mi propietario. Le he pedido que te apruebe; vuelve a escribir cuando lo haya hecho.
+channel-verify_success = ✅ Gracias, ya estás verificado. ¿En qué puedo ayudarte?
+channel-verify_wrong_code = ⚠️ Ese código no es correcto. Quedan { $remaining } intentos.
+channel-verify_locked = 🚫 Demasiados códigos incorrectos. Inténtalo de nuevo en { $minutes } minutos.
+channel-verify_owner_code = Un remitente nuevo, { $identity }, quiere hablar con el agente. Si lo conoces, dale este código: { $code } (válido { $minutes } minutos). O dale acceso con `zeroclaw channel approve-sender { $identity }`.
+channel-verify_owner_approve = Un remitente nuevo, { $identity }, quiere hablar con el agente. Dale acceso con `zeroclaw channel approve-sender { $identity }`.
+channel-admin_help =
+    Comandos del propietario:
+    `!status`: canales, modelo predeterminado, parada de emergencia
+    `!budget`: gasto frente a los límites de coste
+    `!pause [canal]` / `!resume [canal]`: detener o reanudar un canal (este por defecto)
+    `!model <nombre>`: cambiar el modelo predeterminado
+    `!estop [network]`: activar la parada de emergencia
+    Si se exige OTP, termina `!pause`, `!model` y `!estop` con tu código actual.
+channel-simulate_unavailable = 🧪 `/simulate` solo está disponible en `zeroclaw agent`; no se ejecutó nada.
+channel-admin_denied = 🚫 Los comandos de administración solo se aceptan del propietario.
+channel-admin_otp_required = 🔐 Este comando necesita tu código OTP actual como última palabra, p. ej. `!estop 123456`.
+channel-admin_otp_invalid = 🚫 Ese código OTP no es válido.
+channel-admin_otp_unavailable = 🚫 Este comando necesita un código OTP, pero OTP no está configurado en este host (`[security.otp]`).
+channel-admin_failed = ⚠️ El comando de administración falló: { $details }
+channel-admin_status =
+    📊 Canales: { $channels }
+    En pausa: { $paused }
+    Modelo predeterminado: `{ $model }` (proveedor `{ $provider }`)
+    Parada de emergencia: { $estop }
+    Conversaciones activas: { $sessions }
+channel-admin_budget = 💰 Hoy: { $daily } de { $daily_limit } USD. Este mes: { $monthly } de { $monthly_limit } USD.
+channel-admin_budget_disabled = El seguimiento de costes está desactivado (`[cost] enabled = false`).
+channel-admin_paused = ⏸️ { $channel } en pausa. Los mensajes se descartan hasta que envíes `!resume { $channel }`.
+channel-admin_already_paused = { $channel } ya está en pausa.
+channel-admin_resumed = ▶️ { $channel } reanudado.
+channel-admin_not_paused = { $channel } no está en pausa.
+channel-admin_model = ✅ El modelo predeterminado es ahora `{ $model }` (proveedor `{ $provider }`) hasta que cambie el archivo de configuración. Quien eligió un modelo con `/model` lo conserva.
+channel-admin_estop = 🛑 Parada de emergencia activada: { $state }. Reanúdala en el host con `zeroclaw estop resume`.
+channel-auto_started = 🤖 Modo autónomo activado durante { $minutes } min o { $actions } llamadas a herramientas: actuaré sin pedir aprobación y anunciaré cada paso aquí. Envía `/auto off` para detenerlo.
+channel-auto_status = 🤖 Modo autónomo: quedan { $minutes } min y { $actions } llamadas a herramientas.
+channel-auto_inactive = El modo autónomo está desactivado. Inícialo con, por ejemplo, `/auto 20m`.
+channel-auto_stopped = ✋ Modo autónomo desactivado tras { $used } llamadas a herramientas. Las aprobaciones vuelven a ser las habituales.
+channel-auto_disabled = 🚫 El modo autónomo está deshabilitado (`[autonomy.auto_mode]`).
+channel-auto_owner_only = 🚫 Solo el propietario puede iniciar el modo autónomo.
+channel-auto_usage = Uso: `/auto 20m` o `/auto 1h` para iniciar, `/auto` para ver el estado, `/auto off` para detener.
+channel-auto_action = 🤖 auto: `{ $tool }` { $summary } (quedan { $remaining })
+channel-auto_ended_timer = ⏱️ El modo autónomo ha caducado; las aprobaciones vuelven a ser las habituales.
+channel-auto_ended_budget = ⏱️ El modo autónomo agotó sus { $actions } llamadas a herramientas; las aprobaciones vuelven a ser las habituales.
+channel-cost_suffix = 💰 { $reply } por esta respuesta · { $total } en lo que va de conversación
+
+## CLI output
+
+cli-preset-saved_selection = Selección de preset del espacio de trabajo guardada: { $path }
+cli-preset-risky_packs = Paquetes de riesgo: { $packs }
+cli-preset-risky_packs_need_consent = La selección incluye paquetes de riesgo [{ $packs }]. Vuelve a ejecutar con `--yes-risky` o usa `--dry-run`.
+cli-preset-missing_features = Paquetes no compilados en este binario: { $packs }
+cli-preset-missing_features_need_rebuild = Los paquetes [{ $packs }] necesitan features de cargo con las que este binario no se compiló. Vuelve a ejecutar con `--rebuild --yes-rebuild` o usa `--dry-run`.
+cli-preset-apply_dry_run = Simulación de apply: no se escribieron cambios.
+cli-preset-import_dry_run = Simulación de import: no se escribieron cambios.
+cli-preset-intent_apply_dry_run = Simulación de intent apply: no se escribieron cambios.
+cli-preset-plan_only = Solo plan. Vuelve a ejecutar con `--apply` para guardar esta selección.
+cli-preset-knowledge_dry_run = Documentos de conocimiento (no se descargan en la simulación):
+cli-cost_footer = 💰 Esta respuesta: { $reply_cost } ({ $reply_tokens } tokens) · Conversación: { $total_cost } ({ $total_tokens } tokens)
@@ -0,0 +1,136 @@
+# 日本語
+
+## Consent and warning reason codes
+
+consent-reason-risky_pack = 選択内容にリスク管理対象のパックが含まれています
+consent-reason-rebuild = 再ビルドによりインストール済みのバイナリが置き換えられます
+consent-reason-security_non_strict = セキュリティプロファイルが strict ではありません
+security-risk_reason-non_strict_profile = セキュリティプロファイルが strict ではありません
+security-risk_reason-non_cli_auto_approval = CLI 以外のチャネルでツール呼び出しが自動承認される可能性があります
+onboard-warning-risky_pack_requires_consent = リスクのあるパックには `--yes-security-risk` が必要です
+onboard-warning-security_non_strict_requires_consent = strict 以外のセキュリティプロファイルには `--yes-security-risk` が必要です
+
+## Approval prompts
+
+approval-wants_to_execute = 🔧 エージェントが実行しようとしています: { $tool }
+approval-no_content_changes = (内容の変更なし)
+approval-choices = [Y] はい / [N] いいえ / [A] { $tool } を常に許可:
+approval-four_eyes_needed = 🔐 承認が必要です: { $requester } が '{ $tool }' を { $args } で実行しようとしています
+    { $seconds } 秒以内に /approve { $code } または /deny { $code } で返信してください。
+
+## Channel system messages
+
+channel-current_route =
+    現在のプロバイダー: `{ $provider }`
+    現在のモデル: `{ $model }`
+channel-switch_model_hint = `/model <model-id>` でモデルを切り替えます。
+channel-switch_provider_hint = `/models <provider>` でプロバイダーを切り替えます。
+channel-no_cached_models = `{ $provider }` のモデル一覧キャッシュが見つかりません。運用者に `zeroclaw models refresh --provider { $provider }` の実行を依頼してください。
+channel-cached_models = キャッシュ済みモデル ID (上位 { $count } 件):
+channel-available_providers = 利用可能なプロバイダー:
+channel-provider_aliases = エイリアス: { $aliases }
+channel-provider_switched =
+    このセッションのプロバイダーを `{ $provider }` に切り替えました。現在のモデルは `{ $model }` です。
+    `/model <model-id>` でプロバイダーに対応したモデルを設定してください。
+channel-provider_switch_failed =
+    プロバイダー `{ $provider }` を初期化できませんでした。ルートは変更されていません。
+    詳細: { $details }
+channel-unknown_provider = 不明なプロバイダー `{ $provider }` です。`/models` で有効なプロバイダーを確認してください。
+channel-model_empty = モデル ID は空にできません。`/model <model-id>` を使用してください。
+channel-model_switched = このセッションでプロバイダー `{ $provider }` のモデルを `{ $model }` に切り替えました。
+channel-provider_unavailable =
+    ⚠️ プロバイダー `{ $provider }` を初期化できませんでした。`/models` で別のプロバイダーを選択してください。
+    詳細: { $details }
+channel-context_overflow_compacted = ⚠️ この会話のコンテキストウィンドウを超えました。最近の履歴を圧縮し、最新のコンテキストを残しました。最後のメッセージをもう一度送信してください。
+channel-context_overflow = ⚠️ この会話のコンテキストウィンドウを超えました。最後のメッセージをもう一度送信してください。
+channel-request_timeout = ⚠️ モデルの応答待ちがタイムアウトしました。もう一度お試しください。
+channel-error = ⚠️ エラー: { $error }
+channel-running_tool = ⏳ ツールを実行中: { $tool }…
+channel-preparing_tool = ⏳ ツールを準備中: { $tool }…
+channel-tool_stream_issue = ⚠️ ツール呼び出しの問題: { $issue }
+channel-long_reply_attached = 📎 完全な返信（{ $chars } 文字）を `{ $file }` として添付しました。
+channel-task_failed = ⚠️ タスクを実行できませんでした: { $details }
+channel-question_asked =
+    ❓ 質問 { $id }: { $question }
+    `/answer { $id } <回答>` で返信してください。
+channel-question_answered = ✅ ありがとうございます。{ $id } を待っていたタスクをまもなく再開します。
+channel-question_answer_failed = ⚠️ 回答を記録できませんでした: { $details }
+channel-handoff_parked = 📤 { $target } 用に会話を保存しました。{ $minutes } 分以内にそちらで `/continue-here { $code }` を送信すると続きから再開できます。
+channel-handoff_parked_cli = 📤 CLI 用に会話を保存しました。{ $minutes } 分以内に `zeroclaw agent --continue-here` を実行すると続きから再開できます。
+channel-handoff_resumed = 📥 { $source } からの会話を再開します（{ $turns } 件のメッセージ）。
+channel-handoff_failed = ⚠️ 会話を引き継げませんでした: { $details }
+channel-preference_saved = ✅ 承知しました。今後はそのようにします（{ $preference }）。
+channel-preference_failed = ⚠️ 設定を保存できませんでした: { $details }
+channel-instruction_saved = ✅ このチャンネルの常設指示 #{ $id } を保存しました: { $text }
+channel-instruction_removed = 🗑️ 常設指示 #{ $id } を削除しました。
+channel-instruction_missing = ⚠️ 常設指示 #{ $id } は存在しません。
+channel-instruction_failed = ⚠️ 常設指示を更新できませんでした: { $details }
+channel-approval_granted = ✅ リクエスト { $code } を承認しました: { $requester } は { $tool } を実行できます。
+channel-approval_denied = 🚫 リクエスト { $code } を却下しました: { $requester } の { $tool } は実行されません。
+channel-approval_failed = ⚠️ 判断を記録できませんでした: { $details }
+channel-incognito_on = 🕶️ シークレットモードをオンにしました。この会話はメモリに保存されず、{ $minutes } 分間操作がないと破棄されます。終了するには `/incognito off` を送信してください。
+channel-incognito_off = シークレットモードをオフにしました。シークレットの会話は破棄されました。
+channel-incognito_inactive = シークレットモードはオフです。開始するには `/incognito` を送信してください。
+channel-incognito_always = 🕶️ このチャンネルは常にシークレットモードです。メモリには何も保存されず、会話は { $minutes } 分間操作がないと破棄されます。
+channel-incognito_no_handoff = ⚠️ シークレットの会話は引き継げません。
+channel-incognito_not_saved = 🕶️ シークレットモード中のため、この変更は保存されませんでした。
+channel-verify_code_challenge = 🔐 オーナーが確認した相手にのみ返信します。オーナーに確認コードを聞き、{ $minutes } 分以内に `/verify <コード>` を送信してください。
+channel-verify_approve_challenge = 🔐 オーナーが確認した相手にのみ返信します。オーナーに承認を依頼しました。承認後にもう一度メッセージを送ってください。
+channel-verify_success = ✅ 確認できました。ご用件は何でしょう？
+channel-verify_wrong_code = ⚠️ コードが正しくありません。残り { $remaining } 回です。
+channel-verify_locked = 🚫 誤ったコードが多すぎます。{ $minutes } 分後にもう一度お試しください。
+channel-verify_owner_code = 新しい送信者 { $identity } がエージェントとの会話を希望しています。知っている相手なら、このコードを伝えてください: { $code }（{ $minutes } 分間有効）。直接許可する場合は `zeroclaw channel approve-sender { $identity }` を実行してください。
+channel-verify_owner_approve = 新しい送信者 { $identity } がエージェントとの会話を希望しています。許可するには `zeroclaw channel approve-sender { $identity }` を実行してください。
+channel-admin_help =
+    オーナー用コマンド:
+    `!status`: チャネル、デフォルトモデル、緊急停止
+    `!budget`: コスト上限に対する支出
+    `!pause [チャネル]` / `!resume [チャネル]`: チャネルを一時停止または再開 (既定はこのチャネル)
+    `!model <名前>`: デフォルトモデルを切り替え
+    `!estop [network]`: 緊急停止を発動
+    OTP が必要な場合は、`!pause`、`!model`、`!estop` の最後に現在のコードを付けてください。
+channel-simulate_unavailable = 🧪 `/simulate` は `zeroclaw agent` でのみ使えます。何も実行していません。
+channel-admin_denied = 🚫 管理コマンドはオーナーからのみ受け付けます。
+channel-admin_otp_required = 🔐 このコマンドには最後の語として現在の OTP コードが必要です (例: `!estop 123456`)。
+channel-admin_otp_invalid = 🚫 その OTP コードは無効です。
+channel-admin_otp_unavailable = 🚫 このコマンドには OTP コードが必要ですが、このホストでは OTP が設定されていません (`[security.otp]`)。
+channel-admin_failed = ⚠️ 管理コマンドが失敗しました: { $details }
+channel-admin_status =
+    📊 チャネル: { $channels }
+    一時停止中: { $paused }
+    デフォルトモデル: `{ $model }` (プロバイダー `{ $provider }`)
+    緊急停止: { $estop }
+    アクティブな会話: { $sessions }
+channel-admin_budget = 💰 今日: { $daily } / { $daily_limit } USD。今月: { $monthly } / { $monthly_limit } USD。
+channel-admin_budget_disabled = コスト追跡はオフです (`[cost] enabled = false`)。
+channel-admin_paused = ⏸️ { $channel } を一時停止しました。`!resume { $channel }` を送るまでメッセージは破棄されます。
+channel-admin_already_paused = { $channel } はすでに一時停止中です。
+channel-admin_resumed = ▶️ { $channel } を再開しました。
+channel-admin_not_paused = { $channel } は一時停止していません。
+channel-admin_model = ✅ 設定ファイルが変わるまで、デフォルトモデルは `{ $model }` (プロバイダー `{ $provider }`) です。`/model` でモデルを選んだ送信者はそのままです。
+channel-admin_estop = 🛑 緊急停止を発動しました: { $state }。ホストで `zeroclaw estop resume` を実行して解除してください。
+channel-auto_started = 🤖 自律モードを { $minutes } 分間または { $actions } 回のツール呼び出しまで有効にしました。承認なしで実行し、各ステップをここでお知らせします。停止するには `/auto off` を送信してください。
+channel-auto_status = 🤖 自律モード: 残り { $minutes } 分、ツール呼び出し { $actions } 回。
+channel-auto_inactive = 自律モードはオフです。`/auto 20m` などで開始できます。
+channel-auto_stopped = ✋ { $used } 回のツール呼び出しの後、自律モードをオフにしました。承認は通常どおりに戻りました。
+channel-auto_disabled = 🚫 自律モードは無効です (`[autonomy.auto_mode]`)。
+channel-auto_owner_only = 🚫 自律モードを開始できるのはオーナーだけです。
+channel-auto_usage = 使い方: 開始は `/auto 20m` または `/auto 1h`、状態確認は `/auto`、停止は `/auto off`。
+channel-auto_action = 🤖 auto: `{ $tool }` { $summary } (残り { $remaining })
+channel-auto_ended_timer = ⏱️ 自律モードの期限が切れました。承認は通常どおりに戻りました。
+channel-auto_ended_budget = ⏱️ 自律モードは { $actions } 回のツール呼び出しを使い切りました。承認は通常どおりに戻りました。
+channel-cost_suffix = 💰 この返信 { $reply } · この会話の累計 { $total }
+
+## CLI output
+
+cli-preset-saved_selection = ワークスペースのプリセット選択を保存しました: { $path }
+cli-preset-risky_packs = リスクのあるパック: { $packs }
+cli-preset-risky_packs_need_consent = 選択内容にリスクのあるパック [{ $packs }] が含まれています。`--yes-risky` を付けて再実行するか、`--dry-run` を使用してください。
+cli-preset-missing_features = このバイナリにコンパイルされていないパック: { $packs }
+cli-preset-missing_features_need_rebuild = パック [{ $packs }] には、このバイナリのビルドに含まれていない cargo feature が必要です。`--rebuild --yes-rebuild` を付けて再実行するか、`--dry-run` を使用してください。
+cli-preset-apply_dry_run = Apply のドライラン: 変更は書き込まれていません。
+cli-preset-import_dry_run = Import のドライラン: 変更は書き込まれていません。
+cli-preset-intent_apply_dry_run = Intent apply のドライラン: 変更は書き込まれていません。
+cli-preset-plan_only = 計画のみです。この選択を保存するには `--apply` を付けて再実行してください。
+cli-preset-knowledge_dry_run = ナレッジ文書 (ドライランでは取得しません):
+cli-cost_footer = 💰 この返信: { $reply_cost }({ $reply_tokens } トークン)· 会話の累計: { $total_cost }({ $total_tokens } トークン)
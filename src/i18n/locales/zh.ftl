@@ -0,0 +1,136 @@
+# 简体中文
+
+## Consent and warning reason codes
+
+consent-reason-risky_pack = 所选内容启用了受风险管控的功能包
+consent-reason-rebuild = 重新构建会替换已安装的二进制文件
+consent-reason-security_non_strict = 安全配置不是严格模式
+security-risk_reason-non_strict_profile = 安全配置不是严格模式
+security-risk_reason-non_cli_auto_approval = 非 CLI 渠道可以自动批准工具调用
+onboard-warning-risky_pack_requires_consent = 高风险功能包需要 `--yes-security-risk`
+onboard-warning-security_non_strict_requires_consent = 非严格安全配置需要 `--yes-security-risk`
+
+## Approval prompts
+
+approval-wants_to_execute = 🔧 智能体请求执行：{ $tool }
+approval-no_content_changes = （内容无变化）
+approval-choices = [Y] 是 / [N] 否 / [A] 始终允许 { $tool }：
+approval-four_eyes_needed = 🔐 需要审批：{ $requester } 想要使用 { $args } 运行 '{ $tool }'
+    请在 { $seconds } 秒内回复 /approve { $code } 或 /deny { $code }。
+
+## Channel system messages
+
+channel-current_route =
+    当前提供商：`{ $provider }`
+    当前模型：`{ $model }`
+channel-switch_model_hint = 使用 `/model <model-id>` 切换模型。
+channel-switch_provider_hint = 使用 `/models <provider>` 切换提供商。
+channel-no_cached_models = 未找到 `{ $provider }` 的模型缓存列表。请让管理员运行 `zeroclaw models refresh --provider { $provider }`。
+channel-cached_models = 已缓存的模型 ID（前 { $count } 个）：
+channel-available_providers = 可用的提供商：
+channel-provider_aliases = 别名：{ $aliases }
+channel-provider_switched =
+    当前会话的提供商已切换为 `{ $provider }`。当前模型为 `{ $model }`。
+    使用 `/model <model-id>` 设置与该提供商兼容的模型。
+channel-provider_switch_failed =
+    无法初始化提供商 `{ $provider }`，路由未变更。
+    详情：{ $details }
+channel-unknown_provider = 未知的提供商 `{ $provider }`。使用 `/models` 查看可用提供商。
+channel-model_empty = 模型 ID 不能为空。请使用 `/model <model-id>`。
+channel-model_switched = 当前会话中提供商 `{ $provider }` 的模型已切换为 `{ $model }`。
+channel-provider_unavailable =
+    ⚠️ 无法初始化提供商 `{ $provider }`。请运行 `/models` 选择其他提供商。
+    详情：{ $details }
+channel-context_overflow_compacted = ⚠️ 此对话超出了上下文窗口。我已压缩近期历史并保留了最新上下文，请重新发送你的上一条消息。
+channel-context_overflow = ⚠️ 此对话超出了上下文窗口，请重新发送你的上一条消息。
+channel-request_timeout = ⚠️ 等待模型响应超时，请重试。
+channel-error = ⚠️ 错误：{ $error }
+channel-running_tool = ⏳ 正在运行工具：{ $tool }…
+channel-preparing_tool = ⏳ 正在准备工具：{ $tool }…
+channel-tool_stream_issue = ⚠️ 工具调用问题：{ $issue }
+channel-long_reply_attached = 📎 完整回复（{ $chars } 个字符）已作为 `{ $file }` 附上。
+channel-task_failed = ⚠️ 无法运行任务：{ $details }
+channel-question_asked =
+    ❓ 问题 { $id }：{ $question }
+    请回复 `/answer { $id } <你的回答>`。
+channel-question_answered = ✅ 谢谢，等待 { $id } 的任务即将恢复。
+channel-question_answer_failed = ⚠️ 无法记录回答：{ $details }
+channel-handoff_parked = 📤 已为 { $target } 保存对话。请在 { $minutes } 分钟内在那里发送 `/continue-here { $code }` 以继续。
+channel-handoff_parked_cli = 📤 已为 CLI 保存对话。请在 { $minutes } 分钟内运行 `zeroclaw agent --continue-here` 以继续。
+channel-handoff_resumed = 📥 继续来自 { $source } 的对话（{ $turns } 条消息）。
+channel-handoff_failed = ⚠️ 无法转交对话：{ $details }
+channel-preference_saved = ✅ 好的，从现在起我会照此执行（{ $preference }）。
+channel-preference_failed = ⚠️ 无法保存偏好设置：{ $details }
+channel-instruction_saved = ✅ 已为此频道保存常驻指令 #{ $id }：{ $text }
+channel-instruction_removed = 🗑️ 已删除常驻指令 #{ $id }。
+channel-instruction_missing = ⚠️ 不存在常驻指令 #{ $id }。
+channel-instruction_failed = ⚠️ 无法更新常驻指令：{ $details }
+channel-approval_granted = ✅ 已批准请求 { $code }：{ $requester } 可以运行 { $tool }。
+channel-approval_denied = 🚫 已拒绝请求 { $code }：{ $requester } 的 { $tool } 不会运行。
+channel-approval_failed = ⚠️ 无法记录你的决定：{ $details }
+channel-incognito_on = 🕶️ 已开启隐身模式。此对话不会保存到记忆中，闲置 { $minutes } 分钟后将被遗忘。发送 `/incognito off` 结束。
+channel-incognito_off = 已关闭隐身模式，隐身对话已被丢弃。
+channel-incognito_inactive = 隐身模式未开启。发送 `/incognito` 开始隐身会话。
+channel-incognito_always = 🕶️ 此频道始终为隐身模式：不会保存任何记忆，对话闲置 { $minutes } 分钟后将被遗忘。
+channel-incognito_no_handoff = ⚠️ 隐身对话无法转交。
+channel-incognito_not_saved = 🕶️ 隐身模式已开启，此更改未保存。
+channel-verify_code_challenge = 🔐 我只回复经主人验证过的人。请向主人索取验证码，并在 { $minutes } 分钟内发送 `/verify <验证码>`。
+channel-verify_approve_challenge = 🔐 我只回复经主人验证过的人。我已请主人批准你，批准后请再发消息。
+channel-verify_success = ✅ 谢谢，你已通过验证。有什么可以帮你？
+channel-verify_wrong_code = ⚠️ 验证码不正确。还剩 { $remaining } 次机会。
+channel-verify_locked = 🚫 错误次数过多。请在 { $minutes } 分钟后重试。
+channel-verify_owner_code = 新发送者 { $identity } 想与智能体对话。如果你认识对方，请把这个验证码告诉他：{ $code }（{ $minutes } 分钟内有效）。或直接运行 `zeroclaw channel approve-sender { $identity }` 允许对方。
+channel-verify_owner_approve = 新发送者 { $identity } 想与智能体对话。运行 `zeroclaw channel approve-sender { $identity }` 允许对方。
+channel-admin_help =
+    所有者命令:
+    `!status`:频道、默认模型、紧急停止
+    `!budget`:相对成本上限的支出
+    `!pause [频道]` / `!resume [频道]`:暂停或恢复频道(默认为当前频道)
+    `!model <名称>`:切换默认模型
+    `!estop [network]`:触发紧急停止
+    需要 OTP 时,请在 `!pause`、`!model` 和 `!estop` 末尾附上当前验证码。
+channel-simulate_unavailable = 🧪 `/simulate` 仅在 `zeroclaw agent` 中可用;未执行任何操作。
+channel-admin_denied = 🚫 管理命令只接受所有者发送。
+channel-admin_otp_required = 🔐 此命令需要以当前 OTP 验证码作为最后一个词,例如 `!estop 123456`。
+channel-admin_otp_invalid = 🚫 该 OTP 验证码无效。
+channel-admin_otp_unavailable = 🚫 此命令需要 OTP 验证码,但此主机未设置 OTP(`[security.otp]`)。
+channel-admin_failed = ⚠️ 管理命令失败:{ $details }
+channel-admin_status =
+    📊 频道:{ $channels }
+    已暂停:{ $paused }
+    默认模型:`{ $model }`(提供商 `{ $provider }`)
+    紧急停止:{ $estop }
+    活跃会话:{ $sessions }
+channel-admin_budget = 💰 今日:{ $daily } / { $daily_limit } USD。本月:{ $monthly } / { $monthly_limit } USD。
+channel-admin_budget_disabled = 成本跟踪已关闭(`[cost] enabled = false`)。
+channel-admin_paused = ⏸️ 已暂停 { $channel }。在你发送 `!resume { $channel }` 之前,那里的消息会被丢弃。
+channel-admin_already_paused = { $channel } 已处于暂停状态。
+channel-admin_resumed = ▶️ 已恢复 { $channel }。
+channel-admin_not_paused = { $channel } 未暂停。
+channel-admin_model = ✅ 在配置文件变更前,默认模型为 `{ $model }`(提供商 `{ $provider }`)。已用 `/model` 选择模型的发送者保持不变。
+channel-admin_estop = 🛑 已触发紧急停止:{ $state }。请在主机上运行 `zeroclaw estop resume` 解除。
+channel-auto_started = 🤖 自主模式已开启 { $minutes } 分钟或 { $actions } 次工具调用:我将无需审批直接执行,并在此报告每一步。发送 `/auto off` 可停止。
+channel-auto_status = 🤖 自主模式:剩余 { $minutes } 分钟和 { $actions } 次工具调用。
+channel-auto_inactive = 自主模式未开启。可用 `/auto 20m` 等命令开启。
+channel-auto_stopped = ✋ 自主模式已在 { $used } 次工具调用后关闭。审批已恢复正常。
+channel-auto_disabled = 🚫 自主模式已禁用(`[autonomy.auto_mode]`)。
+channel-auto_owner_only = 🚫 只有所有者可以启动自主模式。
+channel-auto_usage = 用法:`/auto 20m` 或 `/auto 1h` 开启,`/auto` 查看状态,`/auto off` 停止。
+channel-auto_action = 🤖 auto:`{ $tool }` { $summary }(剩余 { $remaining })
+channel-auto_ended_timer = ⏱️ 自主模式已到期,审批已恢复正常。
+channel-auto_ended_budget = ⏱️ 自主模式已用完 { $actions } 次工具调用,审批已恢复正常。
+channel-cost_suffix = 💰 本次回复 { $reply } · 本次对话累计 { $total }
+
+## CLI output
+
+cli-preset-saved_selection = 已保存工作区预设选择：{ $path }
+cli-preset-risky_packs = 高风险功能包：{ $packs }
+cli-preset-risky_packs_need_consent = 所选内容包含高风险功能包 [{ $packs }]。请使用 `--yes-risky` 重新运行，或使用 `--dry-run`。
+cli-preset-missing_features = 未编译进当前二进制文件的功能包：{ $packs }
+cli-preset-missing_features_need_rebuild = 功能包 [{ $packs }] 需要当前二进制文件构建时未启用的 cargo feature。请使用 `--rebuild --yes-rebuild` 重新运行，或使用 `--dry-run`。
+cli-preset-apply_dry_run = Apply 试运行：未写入任何更改。
+cli-preset-import_dry_run = Import 试运行：未写入任何更改。
+cli-preset-intent_apply_dry_run = Intent apply 试运行：未写入任何更改。
+cli-preset-plan_only = 仅生成计划。使用 `--apply` 重新运行以保存此选择。
+cli-preset-knowledge_dry_run = 知识文档（试运行时不下载）：
+cli-cost_footer = 💰 本次回复:{ $reply_cost }({ $reply_tokens } 个 token)· 对话累计:{ $total_cost }({ $total_tokens } 个 token)
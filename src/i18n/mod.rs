@@ -0,0 +1,207 @@
+//! Localization of CLI output, channel system messages and approval prompts.
+//!
+//! Messages live in Fluent (`.ftl`) resources embedded at build time, one per
+//! supported locale. English is the source of truth: a message missing from
+//! another locale falls back to English, and an unknown key renders as the key
+//! itself so a typo is visible rather than silently empty.
+//!
+//! Keys follow the dotted form already used for machine-readable reason codes
+//! (for example `consent.reason.risky_pack`); dots map to `-` because Fluent
+//! identifiers cannot contain them.
+
+use crate::config::UiConfig;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use unic_langid::LanguageIdentifier;
+
+/// Locale used when none is configured or the configured one is unsupported.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Locales with bundled translations.
+pub const SUPPORTED_LOCALES: &[&str] = &["en", "es", "de", "zh", "ja"];
+
+const RESOURCES: &[(&str, &str)] = &[
+    ("en", include_str!("locales/en.ftl")),
+    ("es", include_str!("locales/es.ftl")),
+    ("de", include_str!("locales/de.ftl")),
+    ("zh", include_str!("locales/zh.ftl")),
+    ("ja", include_str!("locales/ja.ftl")),
+];
+
+static BUNDLES: OnceLock<HashMap<&'static str, FluentBundle<FluentResource>>> = OnceLock::new();
+static ACTIVE_LOCALE: RwLock<&'static str> = RwLock::new(DEFAULT_LOCALE);
+
+/// Select the active locale from `[ui]` config. Call once at startup.
+pub fn init_from_config(config: &UiConfig) {
+    let locale = config
+        .locale
+        .as_deref()
+        .and_then(resolve_locale)
+        .unwrap_or(DEFAULT_LOCALE);
+    set_locale(locale);
+}
+
+/// Set the active locale. Unsupported locales fall back to English.
+pub fn set_locale(locale: &str) {
+    let locale = resolve_locale(locale).unwrap_or(DEFAULT_LOCALE);
+    let mut guard = ACTIVE_LOCALE.write().unwrap_or_else(|e| e.into_inner());
+    *guard = locale;
+}
+
+/// The active locale code.
+pub fn current_locale() -> &'static str {
+    *ACTIVE_LOCALE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Map a user-supplied locale (`es`, `de-AT`, `zh_CN.UTF-8`, …) to a
+/// supported locale code.
+pub fn resolve_locale(raw: &str) -> Option<&'static str> {
+    let tag = raw
+        .trim()
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-");
+    let langid: LanguageIdentifier = tag.parse().ok()?;
+    let language = langid.language.as_str();
+    SUPPORTED_LOCALES
+        .iter()
+        .copied()
+        .find(|supported| *supported == language)
+}
+
+/// Translate a message without arguments.
+pub fn tr(key: &str) -> String {
+    tr_args(key, &[])
+}
+
+/// Translate a message, substituting `{ $name }` placeholders.
+pub fn tr_args(key: &str, args: &[(&str, &str)]) -> String {
+    translate(current_locale(), key, args)
+}
+
+/// Translate a message for one reader: in `locale` (for example their
+/// preferred language) when it is supported, else in the active locale.
+pub fn tr_args_in(locale: Option<&str>, key: &str, args: &[(&str, &str)]) -> String {
+    let locale = locale
+        .and_then(resolve_locale)
+        .unwrap_or_else(current_locale);
+    translate(locale, key, args)
+}
+
+fn translate(locale: &str, key: &str, args: &[(&str, &str)]) -> String {
+    let id = key.replace('.', "-");
+    let bundles = bundles();
+    let fluent_args = (!args.is_empty()).then(|| {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, FluentValue::from(*value));
+        }
+        fluent_args
+    });
+
+    for candidate in [locale, DEFAULT_LOCALE] {
+        let Some(bundle) = bundles.get(candidate) else {
+            continue;
+        };
+        let Some(pattern) = bundle.get_message(&id).and_then(|message| message.value()) else {
+            continue;
+        };
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, fluent_args.as_ref(), &mut errors);
+        if !errors.is_empty() {
+            tracing::debug!("i18n: errors formatting '{key}' for {candidate}: {errors:?}");
+        }
+        return text.into_owned();
+    }
+    key.to_string()
+}
+
+fn bundles() -> &'static HashMap<&'static str, FluentBundle<FluentResource>> {
+    BUNDLES.get_or_init(|| {
+        RESOURCES
+            .iter()
+            .map(|(locale, source)| {
+                let langid: LanguageIdentifier =
+                    locale.parse().expect("bundled locale ids are valid");
+                let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+                // Isolation marks would leak into terminals and chat messages.
+                bundle.set_use_isolating(false);
+                let resource = FluentResource::try_new((*source).to_string()).unwrap_or_else(
+                    |(resource, errors)| {
+                        tracing::warn!("i18n: {locale}.ftl has syntax errors: {errors:?}");
+                        resource
+                    },
+                );
+                if let Err(errors) = bundle.add_resource(resource) {
+                    tracing::warn!("i18n: {locale}.ftl has duplicate messages: {errors:?}");
+                }
+                (*locale, bundle)
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message_ids(source: &str) -> Vec<String> {
+        source
+            .lines()
+            .filter(|line| line.chars().next().is_some_and(|c| c.is_ascii_alphabetic()))
+            .filter_map(|line| line.split_once(" =").map(|(id, _)| id.trim().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn resolve_locale_normalizes_tags() {
+        assert_eq!(resolve_locale("es"), Some("es"));
+        assert_eq!(resolve_locale("de-AT"), Some("de"));
+        assert_eq!(resolve_locale("zh_CN.UTF-8"), Some("zh"));
+        assert_eq!(resolve_locale("JA"), Some("ja"));
+        assert_eq!(resolve_locale("fr"), None);
+        assert_eq!(resolve_locale(""), None);
+    }
+
+    #[test]
+    fn translate_substitutes_args_and_falls_back_to_english() {
+        let text = translate(
+            "es",
+            "channel.model_switched",
+            &[("model", "gpt-4o"), ("provider", "openai")],
+        );
+        assert!(text.contains("`gpt-4o`"));
+        assert!(text.contains("`openai`"));
+        assert!(!text.contains('\u{2068}'));
+
+        assert_eq!(
+            translate("en", "consent.reason.risky_pack", &[]),
+            "selection enables risk-gated packs"
+        );
+        assert_eq!(translate("de", "no.such.key", &[]), "no.such.key");
+    }
+
+    #[test]
+    fn every_locale_translates_every_english_message() {
+        let english = message_ids(RESOURCES[0].1);
+        assert!(!english.is_empty());
+        for (locale, source) in RESOURCES {
+            assert!(
+                FluentResource::try_new((*source).to_string()).is_ok(),
+                "{locale}.ftl has syntax errors"
+            );
+        }
+        for (locale, source) in &RESOURCES[1..] {
+            let ids = message_ids(source);
+            for id in &english {
+                assert!(ids.contains(id), "{locale}.ftl is missing '{id}'");
+            }
+        }
+        for locale in SUPPORTED_LOCALES {
+            assert!(bundles().contains_key(locale));
+        }
+    }
+}
@@ -0,0 +1,393 @@
+//! Standing instructions with scoped activation.
+//!
+//! Rules such as "always reply in metric units" or "never touch the prod
+//! cluster" live in `instructions.toml` next to `config.toml`, out of reach of
+//! the agent's file tools, and are injected into the
+//! system prompt of every session they apply to, instead of depending on
+//! memory recall. Each rule has a scope:
+//!
+//! - `global`: every session;
+//! - `channel:<name>`: sessions on that channel (`cli` for interactive
+//!   `zeroclaw agent`, `daemon` for unattended runs);
+//! - `skill:<name>`: sessions where that skill is loaded.
+//!
+//! Rules are listed from least to most specific so the model can apply the
+//! precedence rule "skill over channel over global, newer over older".
+//!
+//! Managed with `zeroclaw instructions` or in plain language
+//! ("from now on, always …", "forget standing instruction 3"). Channel senders
+//! only manage rules scoped to their own channel unless they are owners.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const INSTRUCTIONS_FILE: &str = "instructions.toml";
+const MAX_TEXT_CHARS: usize = 300;
+const MAX_INSTRUCTIONS: usize = 100;
+
+/// Where a standing instruction applies.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Scope {
+    Global,
+    Channel(String),
+    Skill(String),
+}
+
+impl Scope {
+    /// Parse `global`, `channel:<name>` or `skill:<name>`.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let raw = raw.trim();
+        if raw.eq_ignore_ascii_case("global") {
+            return Ok(Self::Global);
+        }
+        let (kind, name) = raw.split_once(':').unwrap_or((raw, ""));
+        let name = name.trim();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            bail!("Invalid scope '{raw}' (expected global, channel:<name> or skill:<name>)");
+        }
+        match kind.trim().to_ascii_lowercase().as_str() {
+            "channel" => Ok(Self::Channel(name.to_ascii_lowercase())),
+            "skill" => Ok(Self::Skill(name.to_string())),
+            _ => bail!("Invalid scope '{raw}' (expected global, channel:<name> or skill:<name>)"),
+        }
+    }
+
+    fn applies(&self, channel: &str, skills: &[String]) -> bool {
+        match self {
+            Self::Global => true,
+            Self::Channel(name) => name.eq_ignore_ascii_case(channel),
+            Self::Skill(name) => skills.iter().any(|skill| skill == name),
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Global => f.write_str("global"),
+            Self::Channel(name) => write!(f, "channel:{name}"),
+            Self::Skill(name) => write!(f, "skill:{name}"),
+        }
+    }
+}
+
+impl TryFrom<String> for Scope {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: String) -> Result<Self> {
+        Self::parse(&raw)
+    }
+}
+
+impl From<Scope> for String {
+    fn from(scope: Scope) -> Self {
+        scope.to_string()
+    }
+}
+
+/// One standing instruction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Instruction {
+    pub id: u64,
+    pub scope: Scope,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct InstructionsFile {
+    #[serde(default)]
+    next_id: u64,
+    #[serde(default)]
+    instructions: Vec<Instruction>,
+}
+
+/// A plain-language request to manage standing instructions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Add(String),
+    Remove(u64),
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    text.get(..prefix.len())
+        .filter(|head| head.eq_ignore_ascii_case(prefix))
+        .map(|_| &text[prefix.len()..])
+}
+
+/// Parse a whole-message instruction: "from now on, always …", "from now on,
+/// never …", "remember to always …", "standing instruction: …" or "forget
+/// standing instruction 3". Ordinary requests are left alone.
+pub fn parse_command(text: &str) -> Option<Command> {
+    let text = text.trim();
+    for prefix in [
+        "forget standing instruction",
+        "remove standing instruction",
+        "delete standing instruction",
+    ] {
+        if let Some(rest) = strip_prefix_ignore_case(text, prefix) {
+            let id = rest.trim().trim_start_matches('#').trim_end_matches('.');
+            return id.parse().ok().map(Command::Remove);
+        }
+    }
+    if let Some(rest) = strip_prefix_ignore_case(text, "standing instruction:") {
+        let rest = rest.trim();
+        return (!rest.is_empty()).then(|| Command::Add(rest.to_string()));
+    }
+    for prefix in ["from now on, ", "from now on ", "remember to "] {
+        let Some(rest) = strip_prefix_ignore_case(text, prefix) else {
+            continue;
+        };
+        let rest = rest.trim();
+        let rule = ["always ", "never "]
+            .iter()
+            .find_map(|word| strip_prefix_ignore_case(rest, word).map(|body| (word, body)));
+        if let Some((word, body)) = rule.filter(|(_, body)| !body.trim().is_empty()) {
+            return Some(Command::Add(format!("{word}{}", body.trim())));
+        }
+    }
+    None
+}
+
+/// Standing instructions stored next to `config.toml`.
+pub struct InstructionStore {
+    path: PathBuf,
+}
+
+impl InstructionStore {
+    pub fn new(zeroclaw_dir: &Path) -> Self {
+        Self {
+            path: zeroclaw_dir.join(INSTRUCTIONS_FILE),
+        }
+    }
+
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        Self::new(config.config_path.parent().unwrap_or(&config.workspace_dir))
+    }
+
+    fn load(&self) -> Result<InstructionsFile> {
+        if !self.path.exists() {
+            return Ok(InstructionsFile::default());
+        }
+        let raw = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        toml::from_str(&raw).with_context(|| format!("Failed to parse {}", self.path.display()))
+    }
+
+    fn write(&self, file: &InstructionsFile) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let raw = toml::to_string_pretty(file)?;
+        fs::write(&self.path, raw)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    pub fn list(&self) -> Result<Vec<Instruction>> {
+        Ok(self.load()?.instructions)
+    }
+
+    /// Add an instruction; an identical one in the same scope is returned
+    /// instead of duplicated.
+    pub fn add(&self, scope: Scope, text: &str) -> Result<Instruction> {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() || text.chars().count() > MAX_TEXT_CHARS {
+            bail!("Standing instructions must be 1-{MAX_TEXT_CHARS} characters");
+        }
+        let mut file = self.load()?;
+        if let Some(existing) = file
+            .instructions
+            .iter()
+            .find(|i| i.scope == scope && i.text.eq_ignore_ascii_case(&text))
+        {
+            return Ok(existing.clone());
+        }
+        if file.instructions.len() >= MAX_INSTRUCTIONS {
+            bail!("At most {MAX_INSTRUCTIONS} standing instructions can be kept; remove one first");
+        }
+        file.next_id = file
+            .next_id
+            .max(file.instructions.iter().map(|i| i.id).max().unwrap_or(0))
+            + 1;
+        let instruction = Instruction {
+            id: file.next_id,
+            scope,
+            text,
+            created_at: Utc::now(),
+        };
+        file.instructions.push(instruction.clone());
+        self.write(&file)?;
+        Ok(instruction)
+    }
+
+    /// Remove an instruction by id; returns it when it existed.
+    pub fn remove(&self, id: u64) -> Result<Option<Instruction>> {
+        self.remove_where(id, |_| Ok(()))
+    }
+
+    /// Remove an instruction by id only when it has `scope`. Instructions in
+    /// other scopes are refused rather than reported missing.
+    pub fn remove_in_scope(&self, id: u64, scope: &Scope) -> Result<Option<Instruction>> {
+        self.remove_where(id, |instruction| {
+            if instruction.scope == *scope {
+                Ok(())
+            } else {
+                bail!(
+                    "Standing instruction {id} applies to {}; only an owner can remove it",
+                    instruction.scope
+                )
+            }
+        })
+    }
+
+    fn remove_where(
+        &self,
+        id: u64,
+        allowed: impl Fn(&Instruction) -> Result<()>,
+    ) -> Result<Option<Instruction>> {
+        let mut file = self.load()?;
+        let Some(index) = file.instructions.iter().position(|i| i.id == id) else {
+            return Ok(None);
+        };
+        allowed(&file.instructions[index])?;
+        let removed = file.instructions.remove(index);
+        self.write(&file)?;
+        Ok(Some(removed))
+    }
+
+    /// Instructions for a session on `channel` with `skills` loaded, from
+    /// lowest to highest precedence.
+    pub fn active(&self, channel: &str, skills: &[String]) -> Result<Vec<Instruction>> {
+        let mut active: Vec<Instruction> = self
+            .list()?
+            .into_iter()
+            .filter(|i| i.scope.applies(channel, skills))
+            .collect();
+        active.sort_by_key(|i| {
+            let rank = match i.scope {
+                Scope::Global => 0,
+                Scope::Channel(_) => 1,
+                Scope::Skill(_) => 2,
+            };
+            (rank, i.id)
+        });
+        Ok(active)
+    }
+
+    /// System prompt section for a session; empty when no instruction
+    /// applies or the file cannot be read.
+    pub fn prompt_section(&self, channel: &str, skills: &[String]) -> String {
+        let active = match self.active(channel, skills) {
+            Ok(active) => active,
+            Err(err) => {
+                tracing::warn!("Failed to load standing instructions: {err:#}");
+                return String::new();
+            }
+        };
+        if active.is_empty() {
+            return String::new();
+        }
+        let mut section = String::from(
+            "\n\n## Standing Instructions\n\n\
+             The user set these rules ahead of time. Follow them in every reply and tool call \
+             without being reminded. If two conflict, the later one in this list wins.\n",
+        );
+        for instruction in &active {
+            let _ = match &instruction.scope {
+                Scope::Skill(name) => writeln!(
+                    section,
+                    "- When using the `{name}` skill: {}",
+                    instruction.text
+                ),
+                _ => writeln!(section, "- {}", instruction.text),
+            };
+        }
+        section
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn plain_language_commands_are_recognized() {
+        assert_eq!(
+            parse_command("From now on, always reply in metric units"),
+            Some(Command::Add("always reply in metric units".into()))
+        );
+        assert_eq!(
+            parse_command("remember to never touch the prod cluster"),
+            Some(Command::Add("never touch the prod cluster".into()))
+        );
+        assert_eq!(
+            parse_command("Standing instruction: cite sources"),
+            Some(Command::Add("cite sources".into()))
+        );
+        assert_eq!(
+            parse_command("forget standing instruction #3"),
+            Some(Command::Remove(3))
+        );
+        assert_eq!(parse_command("from now on, use metric units"), None);
+        assert_eq!(parse_command("never mind"), None);
+        assert_eq!(parse_command("always the same error"), None);
+    }
+
+    #[test]
+    fn scopes_filter_and_order_by_precedence() {
+        let tmp = TempDir::new().unwrap();
+        let store = InstructionStore::new(tmp.path());
+        let deploy = store
+            .add(
+                Scope::parse("skill:deploy").unwrap(),
+                "never touch the prod cluster",
+            )
+            .unwrap();
+        store
+            .add(
+                Scope::parse("Channel:Telegram").unwrap(),
+                "keep replies short",
+            )
+            .unwrap();
+        let global = store.add(Scope::Global, "always use metric units").unwrap();
+        assert_eq!(
+            store
+                .add(Scope::Global, "Always  use metric units")
+                .unwrap()
+                .id,
+            global.id
+        );
+        assert!(Scope::parse("team:ops").is_err());
+
+        let cli = store.active("cli", &[]).unwrap();
+        assert_eq!(cli.len(), 1);
+
+        let telegram = store
+            .active("telegram", &["deploy".to_string()])
+            .unwrap()
+            .into_iter()
+            .map(|i| i.scope.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(telegram, ["global", "channel:telegram", "skill:deploy"]);
+
+        let section = store.prompt_section("cli", &["deploy".to_string()]);
+        assert!(section.contains("- always use metric units\n- When using the `deploy` skill"));
+
+        let telegram_scope = Scope::Channel("telegram".into());
+        assert!(store.remove_in_scope(deploy.id, &telegram_scope).is_err());
+        assert_eq!(
+            store.active("cli", &["deploy".to_string()]).unwrap().len(),
+            2
+        );
+        assert_eq!(store.remove(deploy.id).unwrap().unwrap().id, deploy.id);
+        assert!(store.remove(deploy.id).unwrap().is_none());
+        let next = store.add(Scope::Global, "cite sources").unwrap();
+        assert!(next.id > global.id);
+    }
+}
@@ -40,36 +40,75 @@ use serde::{Deserialize, Serialize};
 
 pub mod agent;
 pub(crate) mod approval;
+pub(crate) mod artifacts;
 pub(crate) mod auth;
+pub(crate) mod batch;
+pub(crate) mod calendar;
+pub(crate) mod camera;
+pub(crate) mod capabilities;
 pub mod channels;
 pub mod config;
+pub(crate) mod contacts;
+pub(crate) mod control;
+pub(crate) mod coordination;
 pub(crate) mod cost;
 pub(crate) mod cron;
 pub(crate) mod daemon;
+pub(crate) mod datetime;
 pub(crate) mod doctor;
+pub(crate) mod errors;
+pub(crate) mod federation;
 pub mod gateway;
+pub(crate) mod handoff;
 pub(crate) mod hardware;
 pub(crate) mod health;
 pub(crate) mod heartbeat;
 pub mod hooks;
+pub(crate) mod i18n;
 pub(crate) mod identity;
+pub(crate) mod inbox;
+pub(crate) mod instructions;
 pub(crate) mod integrations;
 pub mod memory;
 pub(crate) mod migration;
 pub(crate) mod multimodal;
 pub mod observability;
 pub(crate) mod onboard;
+pub(crate) mod peers;
 pub mod peripherals;
+pub(crate) mod pins;
+pub(crate) mod preferences;
 pub mod presets;
+pub(crate) mod prompts;
+pub(crate) mod provenance;
 pub mod providers;
+pub(crate) mod questions;
+pub(crate) mod queue_store;
+pub(crate) mod quotas;
 pub mod rag;
+pub(crate) mod recordings;
+pub(crate) mod reports;
+pub(crate) mod research;
+pub(crate) mod retention;
 pub mod runtime;
 pub(crate) mod security;
 pub(crate) mod service;
+pub(crate) mod share;
 pub(crate) mod skills;
+#[cfg(feature = "mqtt")]
+pub(crate) mod smart_home;
+pub(crate) mod state;
+pub(crate) mod tasks;
+pub(crate) mod telemetry;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 pub mod tools;
+pub(crate) mod tour;
+pub(crate) mod triage;
 pub(crate) mod tunnel;
+pub(crate) mod undo;
 pub(crate) mod util;
+pub(crate) mod voice;
 
 pub use config::Config;
 
@@ -137,6 +176,27 @@ Examples:
         /// Telegram identity to allow (username without '@' or numeric user ID)
         identity: String,
     },
+    /// List pending sender verification challenges and verified senders
+    Verifications,
+    /// Verify a channel sender so the agent answers them
+    #[command(long_about = "\
+Verify a channel sender so the agent answers them.
+
+Used with [channels_config.verification]: approves a sender who is \
+waiting on a challenge, or verifies someone in advance.
+
+Examples:
+  zeroclaw channel approve-sender telegram:alice
+  zeroclaw channel approve-sender discord:123456789012345678")]
+    ApproveSender {
+        /// Sender as <channel>:<sender>
+        identity: String,
+    },
+    /// Remove a verified sender; they are challenged again on their next message
+    RevokeSender {
+        /// Sender as <channel>:<sender>
+        identity: String,
+    },
 }
 
 /// Skills management subcommands
@@ -159,6 +219,40 @@ pub enum SkillCommands {
         /// Skill name to remove
         name: String,
     },
+    /// Enable an installed skill and register its declared schedules with cron
+    Enable {
+        /// Skill name to enable
+        name: String,
+    },
+    /// Disable an installed skill and remove its scheduled jobs
+    Disable {
+        /// Skill name to disable
+        name: String,
+    },
+    /// Propose a reusable skill from a successful multi-step conversation
+    #[command(long_about = "\
+Propose a reusable skill from a successful multi-step conversation.
+
+The session is a runtime trace turn id (or the id of any event in that \
+turn). Its request becomes a prompt template, its successful tool calls \
+an ordered pipeline, and argument values that appear in the request \
+become {{parameters}}. The proposed SKILL.toml is printed for review; \
+nothing is installed without --install.
+
+Examples:
+  zeroclaw skills distill --session 3f2c9a1e-...
+  zeroclaw skills distill --session 3f2c9a1e-... --name weekly-report --install")]
+    Distill {
+        /// Runtime trace turn id or event id
+        #[arg(long)]
+        session: String,
+        /// Skill name (defaults to a slug of the request)
+        #[arg(long)]
+        name: Option<String>,
+        /// Install the proposal after printing it
+        #[arg(long)]
+        install: bool,
+    },
 }
 
 /// Migration subcommands
@@ -174,6 +268,48 @@ pub enum MigrateCommands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Copy memory, runtime traces and the cost ledger to another storage backend
+    #[command(long_about = "\
+Copy memory, runtime traces and the cost ledger to another storage backend.
+
+Memory entries from memory/brain.db, runtime trace events and cost ledger \
+rows are streamed into PostgreSQL in batches, then source and target are \
+compared by per-dataset checksums. Runs are idempotent, so the daemon can \
+keep running and the command can be repeated. With --cutover the sync is \
+repeated until the checksums match, and [storage.provider.config] is \
+switched to the new backend; restart the daemon afterwards.
+
+Requires a build with the memory-postgres feature.
+
+Examples:
+  zeroclaw migrate storage --to postgres --dsn postgres://zeroclaw@db/zeroclaw --dry-run
+  zeroclaw migrate storage --to postgres --dsn postgres://zeroclaw@db/zeroclaw
+  zeroclaw migrate storage --to postgres --dsn postgres://zeroclaw@db/zeroclaw --cutover")]
+    Storage {
+        /// Target backend (`postgres`)
+        #[arg(long)]
+        to: String,
+
+        /// Connection URL of the target database
+        #[arg(long)]
+        dsn: String,
+
+        /// Schema for the migrated tables
+        #[arg(long, default_value = "public")]
+        schema: String,
+
+        /// Table for memory entries
+        #[arg(long, default_value = "memories")]
+        table: String,
+
+        /// Count and checksum the local data without connecting
+        #[arg(long)]
+        dry_run: bool,
+
+        /// After a verified sync, switch the configured storage to the target
+        #[arg(long, conflicts_with = "dry_run")]
+        cutover: bool,
+    },
 }
 
 /// Cron subcommands
@@ -186,8 +322,8 @@ pub enum CronCommands {
 Add a new recurring scheduled task.
 
 Uses standard 5-field cron syntax: 'min hour day month weekday'. \
-Times are evaluated in UTC by default; use --tz with an IANA \
-timezone name to override.
+Times are evaluated in `[ui] timezone` when configured, otherwise UTC; \
+use --tz with an IANA timezone name to override.
 
 Examples:
   zeroclaw cron add '0 9 * * 1-5' 'Good morning' --tz America/New_York
@@ -201,17 +337,20 @@ Examples:
         /// Command to run
         command: String,
     },
-    /// Add a one-shot scheduled task at an RFC3339 timestamp
+    /// Add a one-shot scheduled task at a specific time
     #[command(long_about = "\
-Add a one-shot task that fires at a specific UTC timestamp.
+Add a one-shot task that fires at a specific time.
 
-The timestamp must be in RFC 3339 format (e.g. 2025-01-15T14:00:00Z).
+Accepts an RFC 3339 timestamp (e.g. 2025-01-15T14:00:00Z) or a relative \
+phrase such as 'tomorrow at 9', 'next friday 14:30' or 'in 2 hours', \
+resolved in `[ui] timezone` (host local time when unset).
 
 Examples:
   zeroclaw cron add-at 2025-01-15T14:00:00Z 'Send reminder'
+  zeroclaw cron add-at 'tomorrow at 9' 'Standup reminder'
   zeroclaw cron add-at 2025-12-31T23:59:00Z 'Happy New Year!'")]
     AddAt {
-        /// One-shot timestamp in RFC3339 format
+        /// RFC3339 timestamp or relative phrase (e.g. "tomorrow at 9")
         at: String,
         /// Command to run
         command: String,
@@ -259,10 +398,16 @@ Update one or more fields of an existing scheduled task.
 
 Only the fields you specify are changed; others remain unchanged.
 
+Agent jobs also carry an execution context: the model they run on \
+(`[cron] default_model` when unset), the memory they may see \
+(shared, isolated to the job, or none) and the tools they may call.
+
 Examples:
   zeroclaw cron update <task-id> --expression '0 8 * * *'
   zeroclaw cron update <task-id> --tz Europe/London --name 'Morning check'
-  zeroclaw cron update <task-id> --command 'Updated message'")]
+  zeroclaw cron update <task-id> --command 'Updated message'
+  zeroclaw cron update <task-id> --memory isolated --tool shell --tool file_read
+  zeroclaw cron update <task-id> --model anthropic/claude-haiku-4.5 --all-tools")]
     Update {
         /// Task ID
         id: String,
@@ -278,6 +423,18 @@ Examples:
         /// New job name
         #[arg(long)]
         name: Option<String>,
+        /// Model for an agent job
+        #[arg(long)]
+        model: Option<String>,
+        /// Memory an agent job may use
+        #[arg(long, value_parser = ["shared", "isolated", "none"])]
+        memory: Option<String>,
+        /// Restrict an agent job to these tools (repeatable; replaces the list)
+        #[arg(long = "tool", conflicts_with = "all_tools")]
+        tools: Vec<String>,
+        /// Lift an agent job's tool restriction
+        #[arg(long)]
+        all_tools: bool,
     },
     /// Pause a scheduled task
     Pause {
@@ -289,6 +446,28 @@ Examples:
         /// Task ID
         id: String,
     },
+    /// Show upcoming runs and their expected cost
+    #[command(long_about = "\
+Show what the daemon will run in the coming window.
+
+Lists cron jobs, heartbeat task ticks, heartbeat probes and research \
+topics in time order with the expected provider cost of each run, then \
+a per-schedule summary. Schedules that will not run as configured \
+(invalid expressions, overdue one-shots, a disabled scheduler) are \
+reported as warnings.
+
+Examples:
+  zeroclaw cron timeline
+  zeroclaw cron timeline --next 7d
+  zeroclaw cron timeline --next 6h --json")]
+    Timeline {
+        /// Window to forecast (e.g. 90m, 24h, 7d)
+        #[arg(long, default_value = "24h")]
+        next: String,
+        /// Print the full report as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 /// Memory management subcommands
@@ -382,6 +561,29 @@ Examples:
         #[arg(long, default_value = "STM32F401RETx")]
         chip: String,
     },
+    /// Print firmware logs from the target over probe-rs RTT/semihosting
+    #[command(long_about = "\
+Print firmware logs from the target over probe-rs RTT/semihosting.
+
+Attaches to the chip through the debug probe and prints lines from \
+every RTT up channel and semihosting console writes. Without --follow, \
+prints what arrives within --wait seconds and exits. Stop the daemon's \
+[hardware.logs] capture first; only one process can hold the probe.
+
+Examples:
+  zeroclaw hardware logs --follow
+  zeroclaw hardware logs --chip STM32F401RETx --wait 5")]
+    Logs {
+        /// Chip name. Default: [hardware].probe_target, else STM32F401RETx
+        #[arg(long)]
+        chip: Option<String>,
+        /// Keep streaming until interrupted
+        #[arg(long, short)]
+        follow: bool,
+        /// Seconds to collect output when not following
+        #[arg(long, default_value_t = 2)]
+        wait: u64,
+    },
 }
 
 /// Peripheral (hardware) management subcommands
@@ -409,6 +611,21 @@ Examples:
         /// Path for serial transport (/dev/ttyACM0) or "native" for local GPIO
         path: String,
     },
+    /// Validate a serial protocol definition and list the tools it defines
+    #[command(long_about = "\
+Validate a serial protocol definition for a custom device.
+
+Protocol files describe framing, commands and reply parsing in TOML; \
+each command becomes an agent tool when a board sets \
+protocol = \"<file>\" in [[peripherals.boards]]. Relative paths that \
+do not exist in the current directory resolve against the workspace.
+
+Examples:
+  zeroclaw peripheral check-protocol peripherals/psu.toml")]
+    CheckProtocol {
+        /// Path to the protocol definition (TOML)
+        file: String,
+    },
     /// Flash ZeroClaw firmware to Arduino (creates .ino, installs arduino-cli if needed, uploads)
     #[command(long_about = "\
 Flash ZeroClaw firmware to an Arduino board.
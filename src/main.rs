@@ -39,7 +39,7 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::io::{IsTerminal, Write};
 use tracing::{info, warn};
-use tracing_subscriber::{fmt, EnvFilter};
+use tracing_subscriber::{fmt, layer::SubscriberExt, EnvFilter};
 
 fn parse_temperature(s: &str) -> std::result::Result<f64, String> {
     let t: f64 = s.parse().map_err(|e| format!("{e}"))?;
@@ -51,40 +51,76 @@ fn parse_temperature(s: &str) -> std::result::Result<f64, String> {
 
 mod agent;
 mod approval;
+mod artifacts;
 mod auth;
+mod batch;
+mod calendar;
+mod camera;
+mod capabilities;
 mod channels;
-mod rag {
-    pub use zeroclaw::rag::*;
-}
+mod code;
 mod config;
+mod contacts;
+mod control;
+mod coordination;
 mod cost;
 mod cron;
 mod daemon;
+mod datetime;
 mod doctor;
+mod errors;
+mod federation;
 mod gateway;
+mod handoff;
 mod hardware;
 mod health;
 mod heartbeat;
 mod hooks;
+mod i18n;
 mod identity;
+mod inbox;
+mod instructions;
 mod integrations;
 mod memory;
 mod migration;
 mod multimodal;
 mod observability;
 mod onboard;
+mod peers;
 mod peripherals;
+mod pins;
+mod preferences;
 mod presets;
+mod prompts;
+mod provenance;
 mod providers;
+mod questions;
+mod queue_store;
+mod quotas;
+mod rag;
+mod recordings;
+mod reports;
+mod research;
+mod retention;
 mod runtime;
 mod security;
 mod service;
+mod share;
 mod skillforge;
 mod skills;
+#[cfg(feature = "mqtt")]
+mod smart_home;
+mod state;
+mod tasks;
+mod telemetry;
 mod tools;
+mod tour;
+mod triage;
 mod tunnel;
+mod undo;
 mod updater;
 mod util;
+mod voice;
 
 use config::Config;
 
@@ -120,6 +156,14 @@ enum EstopLevelArg {
     ToolFreeze,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+enum VoiceModeArg {
+    #[value(name = "push-to-talk")]
+    PushToTalk,
+    #[value(name = "wake-word")]
+    WakeWord,
+}
+
 /// `ZeroClaw` - Zero overhead. Zero compromise. 100% Rust.
 #[derive(Parser, Debug)]
 #[command(name = "zeroclaw")]
@@ -176,6 +220,11 @@ enum Commands {
         #[arg(long)]
         intent: Option<String>,
 
+        /// Role template to provision: personas, skills, paused cron jobs,
+        /// RAG collections and channel stubs (see `zeroclaw checklist`)
+        #[arg(long, value_enum)]
+        template: Option<onboard::templates::TemplateId>,
+
         /// Security profile for quick onboarding (default: strict)
         #[arg(long = "security-profile", value_enum)]
         security_profile: Option<SecurityProfileArg>,
@@ -212,7 +261,9 @@ Examples:
   zeroclaw agent                              # interactive session
   zeroclaw agent -m \"Summarize today's logs\"  # single message
   zeroclaw agent -p anthropic --model claude-sonnet-4-20250514
-  zeroclaw agent --peripheral nucleo-f401re:/dev/ttyACM0")]
+  zeroclaw agent --peripheral nucleo-f401re:/dev/ttyACM0
+  zeroclaw agent --handoff telegram           # continue on Telegram when done
+  zeroclaw agent --continue-here              # pick up a chat handed off to the CLI")]
     Agent {
         /// Single message mode (don't enter interactive mode)
         #[arg(short, long)]
@@ -233,6 +284,92 @@ Examples:
         /// Attach a peripheral (board:path, e.g. nucleo-f401re:/dev/ttyACM0)
         #[arg(long)]
         peripheral: Vec<String>,
+
+        /// Simulation mode: tools return mock results instead of executing
+        #[arg(long)]
+        simulate: bool,
+
+        /// When the session ends, save it so it can continue on this channel
+        /// (claim it there with `/continue-here <code>`)
+        #[arg(long, value_name = "CHANNEL", conflicts_with = "message")]
+        handoff: Option<String>,
+
+        /// Start from the conversation a channel handed off with `/handoff cli`
+        #[arg(long, conflicts_with = "message")]
+        continue_here: bool,
+
+        /// Answer only from this knowledge collection (an imported preset's
+        /// knowledge pack), citing its documents. Tools are disabled.
+        #[arg(long, value_name = "NAME")]
+        collection: Option<String>,
+    },
+
+    /// Work on a task in a git repository and open a pull request
+    #[command(long_about = "\
+Work on a task in a git repository and open a pull request.
+
+Creates a branch from the base branch, lets the agent edit, build and test \
+with file, git and shell tools confined to the repository, then pushes the \
+branch and opens a pull request on GitHub. Protected branches are never \
+pushed to. Iterations and estimated cost are capped by [code].
+
+Examples:
+  zeroclaw code --repo ~/src/app --task \"Fix the failing date parser test\"
+  zeroclaw code --repo . --task \"Add --json to the list command\" --no-pr")]
+    Code {
+        /// Repository to work in
+        #[arg(long)]
+        repo: std::path::PathBuf,
+
+        /// What to do
+        #[arg(long)]
+        task: String,
+
+        /// Branch to start from and open the PR against (default: current branch)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// Override [code].max_iterations
+        #[arg(long)]
+        max_iterations: Option<usize>,
+
+        /// Override [code].max_cost_usd
+        #[arg(long)]
+        max_cost_usd: Option<f64>,
+
+        /// Leave the work on the local branch instead of pushing and opening a PR
+        #[arg(long)]
+        no_pr: bool,
+
+        /// Provider to use
+        #[arg(short, long)]
+        provider: Option<String>,
+
+        /// Model to use
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Temperature (0.0 - 2.0)
+        #[arg(short, long, default_value = "0.2", value_parser = parse_temperature)]
+        temperature: f64,
+    },
+
+    /// Talk to the agent through the microphone
+    #[command(long_about = "\
+Talk to the agent through the microphone.
+
+Speech is transcribed via [transcription] and replies are spoken with \
+[voice] tts_command. Push-to-talk (default) records after Enter is pressed; \
+wake-word mode listens continuously and requires a local transcription \
+endpoint. Needs a build with --features voice.
+
+Examples:
+  zeroclaw voice                     # push-to-talk
+  zeroclaw voice --mode wake-word    # answer \"hey zeroclaw ...\"")]
+    Voice {
+        /// Override [voice] mode
+        #[arg(long, value_enum)]
+        mode: Option<VoiceModeArg>,
     },
 
     /// Check and apply `zeroclaw` binary updates from GitHub Releases
@@ -307,6 +444,19 @@ Examples:
         host: Option<String>,
     },
 
+    /// Control the running daemon over its local socket ([control]).
+    ///
+    /// Examples:
+    /// - `zeroclaw ctl status`
+    /// - `zeroclaw ctl pause-channel telegram`
+    /// - `zeroclaw ctl flush-queues`
+    /// - `zeroclaw ctl sessions list`
+    /// - `zeroclaw ctl approve a1b2c3`
+    Ctl {
+        #[command(subcommand)]
+        ctl_command: CtlCommands,
+    },
+
     /// Manage OS service lifecycle (launchd/systemd user service)
     Service {
         /// Init system to use: auto (detect), systemd, or openrc
@@ -326,6 +476,245 @@ Examples:
     /// Show system status (full details)
     Status,
 
+    /// Report what this instance can do with its current config.
+    ///
+    /// Lists compiled features and packs, the tools the agent is given with
+    /// their schemas and security gates, configured channels, active
+    /// integrations and the default model's capabilities.
+    ///
+    /// Examples:
+    /// - `zeroclaw capabilities`
+    /// - `zeroclaw capabilities --json`
+    Capabilities {
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Take a guided first run against your config.
+    ///
+    /// Asks the agent a question, keeps a memory, schedules a cron job and
+    /// snapshots a demo page, checking each subsystem and showing the
+    /// matching commands.
+    ///
+    /// Examples:
+    /// - `zeroclaw tour`
+    /// - `zeroclaw tour --step browser`
+    Tour {
+        /// Run only this step
+        #[arg(long, value_enum)]
+        step: Option<tour::Step>,
+
+        /// Run every step without asking first
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// Show what is left to set up for the applied workspace template.
+    ///
+    /// After `zeroclaw onboard --template <role>`, lists the template's
+    /// personas, skills, RAG collections, cron jobs and channels, marking
+    /// what is done and what to run next.
+    ///
+    /// Examples:
+    /// - `zeroclaw onboard --template devops`
+    /// - `zeroclaw checklist`
+    Checklist,
+
+    /// Generate monitoring assets for the Prometheus metrics backend
+    Observability {
+        #[command(subcommand)]
+        observability_command: ObservabilityCommands,
+    },
+
+    /// List and roll back agent file changes from the undo journal
+    Undo {
+        #[command(subcommand)]
+        undo_command: UndoCommands,
+    },
+
+    /// Inspect the daemon's runtime state store (estop, pending logins, probes)
+    State {
+        #[command(subcommand)]
+        state_command: StateCommands,
+    },
+
+    /// Save and run parameterized prompt templates.
+    ///
+    /// Examples:
+    /// - `zeroclaw tasks save deploy-report --prompt "Summarize the {{env}} deploy" --tool shell`
+    /// - `zeroclaw tasks run deploy-report --param env=prod`
+    /// - `zeroclaw tasks list`
+    Tasks {
+        #[command(subcommand)]
+        tasks_command: TasksCommands,
+    },
+
+    /// Render templated reports from memory, cost and telemetry data.
+    ///
+    /// Rendering needs a build with `--features reports`; HTML, DOCX and
+    /// PDF output also need `--features doc-convert`.
+    ///
+    /// Examples:
+    /// - `zeroclaw reports new infra-weekly`
+    /// - `zeroclaw reports run infra-weekly --channel slack --to C0123456789`
+    /// - `zeroclaw reports schedule infra-weekly "0 9 * * MON"`
+    Reports {
+        #[command(subcommand)]
+        reports_command: ReportsCommands,
+    },
+
+    /// Publish a redacted, expiring snapshot through the gateway.
+    ///
+    /// Examples:
+    /// - `zeroclaw share 3f2b9c1e-...` (a runtime trace id from `zeroclaw doctor traces`)
+    /// - `zeroclaw share reports/out/infra-weekly.md --ttl 7d --base-url https://zc.example.com`
+    /// - `zeroclaw share list`
+    /// - `zeroclaw share revoke <id>`
+    #[command(args_conflicts_with_subcommands = true)]
+    Share {
+        #[command(subcommand)]
+        share_command: Option<ShareCommands>,
+
+        /// Runtime trace id or file path to share
+        target: Option<String>,
+
+        /// How long the link stays valid (e.g. 30m, 24h, 7d; max 30d)
+        #[arg(long, default_value = "24h")]
+        ttl: String,
+
+        /// Page title (default: file name or trace id)
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Public gateway URL for the link (default: http://<gateway host>:<port>)
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+
+    /// Inspect tool outputs stored as artifacts (`[artifacts]`).
+    ///
+    /// Large tool outputs are kept out of runtime traces; the trace's
+    /// `tool_call_result` event links them by `artifact_id`.
+    ///
+    /// Examples:
+    /// - `zeroclaw artifacts list`
+    /// - `zeroclaw artifacts get 3f2b9c1e`
+    /// - `zeroclaw artifacts prune --older-than-days 7`
+    Artifacts {
+        #[command(subcommand)]
+        artifacts_command: ArtifactsCommands,
+    },
+
+    /// Inspect and customize the built-in prompt bundle.
+    ///
+    /// Files in `<workspace>/prompts/` replace the built-in prompt of the
+    /// same name; the daemon picks up edits without a restart.
+    ///
+    /// Examples:
+    /// - `zeroclaw prompts list`
+    /// - `zeroclaw prompts export`
+    /// - `zeroclaw prompts diff safety`
+    Prompts {
+        #[command(subcommand)]
+        prompts_command: PromptsCommands,
+    },
+
+    /// Run a prompt template across many inputs.
+    ///
+    /// Examples:
+    /// - `zeroclaw batch run --input files.txt --template "Summarize {{file}}" --concurrency 4 --out results/`
+    Batch {
+        #[command(subcommand)]
+        batch_command: BatchCommands,
+    },
+
+    /// Manage contacts the agent can message by name.
+    ///
+    /// Examples:
+    /// - `zeroclaw contacts add "Alice Smith" --address telegram=123456 --address slack=U024BE7LH --prefer telegram`
+    /// - `zeroclaw contacts approve alice`
+    /// - `zeroclaw contacts list`
+    Contacts {
+        #[command(subcommand)]
+        contacts_command: ContactsCommands,
+    },
+
+    /// Manage per-identity reply preferences (tone, verbosity, formatting, units, language).
+    ///
+    /// Examples:
+    /// - `zeroclaw identity prefs show`
+    /// - `zeroclaw identity prefs set verbosity concise --identity contact:alice`
+    /// - `zeroclaw identity prefs clear --identity telegram:123456`
+    Identity {
+        #[command(subcommand)]
+        identity_command: IdentityCommands,
+    },
+
+    /// Manage standing instructions injected into every matching session.
+    ///
+    /// Examples:
+    /// - `zeroclaw instructions add always reply in metric units`
+    /// - `zeroclaw instructions add never touch the prod cluster --scope skill:deploy`
+    /// - `zeroclaw instructions active --channel telegram --skill deploy`
+    Instructions {
+        #[command(subcommand)]
+        instructions_command: InstructionsCommands,
+    },
+
+    /// Answer or cancel questions queued by unattended agent tasks.
+    ///
+    /// Examples:
+    /// - `zeroclaw questions list`
+    /// - `zeroclaw questions answer q1a2b3 use the staging cluster`
+    Questions {
+        #[command(subcommand)]
+        questions_command: QuestionsCommands,
+    },
+
+    /// Review email triage: drafts awaiting approval, summaries and sender rules.
+    ///
+    /// Examples:
+    /// - `zeroclaw inbox drafts`
+    /// - `zeroclaw inbox approve m1a2b3`
+    /// - `zeroclaw inbox rules set @newsletters.example.com fyi`
+    Inbox {
+        #[command(subcommand)]
+        inbox_command: InboxCommands,
+    },
+
+    /// Manage RAG document collections configured under [rag.collections].
+    ///
+    /// Examples:
+    /// - `zeroclaw rag list`
+    /// - `zeroclaw rag ingest team-docs ~/docs/runbooks`
+    /// - `zeroclaw rag search team-docs "rotate the API key"`
+    Rag {
+        #[command(subcommand)]
+        rag_command: RagCommands,
+    },
+
+    /// Transcribe and summarize a meeting or podcast recording.
+    ///
+    /// Examples:
+    /// - `zeroclaw recordings process ~/Downloads/standup.m4a`
+    /// - `zeroclaw recordings process weekly-sync.mp3 --post`
+    Recordings {
+        #[command(subcommand)]
+        recordings_command: RecordingsCommands,
+    },
+
+    /// Preview or send research briefs for topics configured under [research.topics].
+    ///
+    /// Examples:
+    /// - `zeroclaw research list`
+    /// - `zeroclaw research run rust-async`
+    /// - `zeroclaw research run rust-async --deliver`
+    Research {
+        #[command(subcommand)]
+        research_command: ResearchCommands,
+    },
+
     /// Engage, inspect, and resume emergency-stop states.
     ///
     /// Examples:
@@ -504,16 +893,55 @@ Manage ZeroClaw configuration.
 
 Inspect and export configuration settings. Use 'schema' to dump \
 the full JSON Schema for the config file, which documents every \
-available key, type, and default value.
+available key, type, and default value. Use 'migrate' to upgrade \
+an older config.toml to the current schema version.
 
 Examples:
   zeroclaw config schema              # print JSON Schema to stdout
-  zeroclaw config schema > schema.json")]
+  zeroclaw config schema > schema.json
+  zeroclaw config migrate --dry-run   # preview schema upgrades
+  zeroclaw config migrate             # upgrade, keeping a backup")]
     Config {
         #[command(subcommand)]
         config_command: ConfigCommands,
     },
 
+    /// Inspect opt-in anonymous usage telemetry
+    #[command(long_about = "\
+Inspect opt-in anonymous usage telemetry.
+
+Telemetry is off unless [telemetry].enabled = true. ZEROCLAW_TELEMETRY=off \
+or DO_NOT_TRACK=1 turns it off regardless of config. Only counts of event \
+kinds, built-in tools, channel types and error classes are kept.
+
+Examples:
+  zeroclaw telemetry preview")]
+    Telemetry {
+        #[command(subcommand)]
+        telemetry_command: TelemetryCommands,
+    },
+
+    /// Print the version, optionally with build provenance and SBOM
+    #[command(long_about = "\
+Print the version of this binary.
+
+With --provenance, also print the embedded build metadata: git commit, \
+builder identity, toolchain, target, compiled Cargo features and feature \
+packs, and the crates the binary links (an SBOM derived from Cargo.lock).
+
+Examples:
+  zeroclaw version
+  zeroclaw version --provenance
+  zeroclaw version --provenance --json > zeroclaw-sbom.json")]
+    Version {
+        /// Include build metadata and the component list
+        #[arg(long)]
+        provenance: bool,
+        /// Print provenance as JSON
+        #[arg(long, requires = "provenance")]
+        json: bool,
+    },
+
     /// Generate shell completion script to stdout
     #[command(long_about = "\
 Generate shell completion scripts for `zeroclaw`.
@@ -529,12 +957,36 @@ Examples:
         #[arg(value_enum)]
         shell: CompletionShell,
     },
+
+    /// Run one tool request on behalf of a remote zeroclaw ([runtime.remote])
+    #[command(hide = true)]
+    RemoteWorker {
+        /// Refuse requests whose working directory is outside this directory
+        /// (a working-directory check, not a sandbox)
+        #[arg(long)]
+        root: Option<std::path::PathBuf>,
+
+        /// Encoded request; defaults to the last word of SSH_ORIGINAL_COMMAND
+        request: Option<String>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
 enum ConfigCommands {
     /// Dump the full configuration JSON Schema to stdout
     Schema,
+    /// Upgrade config.toml to the current schema version (backs up the original)
+    Migrate {
+        /// Show the changes and the migrated file without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum TelemetryCommands {
+    /// Print the exact report the next send would contain
+    Preview,
 }
 
 #[derive(Subcommand, Debug)]
@@ -653,6 +1105,12 @@ enum ModelCommands {
         #[arg(long)]
         force: bool,
     },
+    /// Compare the primary model against the `[shadow]` model on sampled traffic
+    Shadow {
+        /// Number of most recent samples to list
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -848,6 +1306,26 @@ enum SecurityCommands {
         #[command(subcommand)]
         profile_command: SecurityProfileCommands,
     },
+    /// List rotatable secrets and when each was last rotated
+    Secrets,
+    /// Rotate a secret: issue or enter a new value, verify it, save it and
+    /// retire the old one
+    ///
+    /// OpenRouter keys are issued and revoked through the provider API when
+    /// OPENROUTER_PROVISIONING_KEY is set; other secrets prompt for the new
+    /// value.
+    Rotate {
+        /// Secret id, e.g. `api_key` or `agents.<name>.api_key`
+        secret: String,
+
+        /// Read the new value from stdin instead of prompting
+        #[arg(long)]
+        stdin: bool,
+
+        /// Keep the old key active at the provider after rotating
+        #[arg(long)]
+        keep_old: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -907,7 +1385,11 @@ enum SecurityProfileCommands {
 
 fn command_requests_machine_json(command: &Commands) -> bool {
     match command {
-        Commands::Onboard { json, .. } => *json,
+        Commands::Onboard { json, .. }
+        | Commands::Capabilities { json }
+        | Commands::Doctor {
+            doctor_command: Some(DoctorCommands::Security { json }),
+        } => *json,
         Commands::Preset { preset_command } => match preset_command {
             PresetCommands::Apply { json, .. }
             | PresetCommands::Export { json, .. }
@@ -921,7 +1403,9 @@ fn command_requests_machine_json(command: &Commands) -> bool {
                 SecurityProfileCommands::Set { json, .. }
                 | SecurityProfileCommands::Recommend { json, .. } => *json,
             },
-            SecurityCommands::Show => false,
+            SecurityCommands::Show
+            | SecurityCommands::Secrets
+            | SecurityCommands::Rotate { .. } => false,
         },
         _ => false,
     }
@@ -953,48 +1437,600 @@ enum DoctorCommands {
         /// Maximum number of events to display
         #[arg(long, default_value = "20")]
         limit: usize,
+        /// Aggregate tokens, cost and latency per session, tool and model
+        /// across the matched events instead of listing them
+        #[arg(long)]
+        summarize: bool,
+    },
+    /// Audit guardrails (autonomy, tool allowlists, gateway exposure,
+    /// secrets, estop/OTP) against a hardening baseline
+    Security {
+        /// Print findings as JSON
+        #[arg(long)]
+        json: bool,
     },
 }
 
 #[derive(Subcommand, Debug)]
-enum MemoryCommands {
-    /// List memory entries with optional filters
+enum ObservabilityCommands {
+    /// Grafana dashboard and Prometheus alert rules
+    Grafana {
+        #[command(subcommand)]
+        grafana_command: GrafanaCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum UndoCommands {
+    /// List recent agent file changes (newest first)
     List {
+        /// Maximum number of changes to display
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Restore a file to its state before the given change
+    Revert {
+        /// Change ID (or a unique prefix) from `zeroclaw undo list`
+        change_id: String,
+        /// Also discard later changes to the same file
         #[arg(long)]
-        category: Option<String>,
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum StateCommands {
+    /// Show current values
+    Show {
+        /// Only keys starting with this prefix (e.g. `estop`, `heartbeat.probe.`)
+        key: Option<String>,
+    },
+    /// Show the recorded changes that led to the current state (oldest first)
+    History {
+        /// Only keys starting with this prefix
         #[arg(long)]
-        session: Option<String>,
+        key: Option<String>,
+        /// Maximum number of events to display
         #[arg(long, default_value = "50")]
         limit: usize,
-        #[arg(long, default_value = "0")]
-        offset: usize,
     },
-    /// Get a specific memory entry by key
-    Get { key: String },
-    /// Show memory backend statistics and health
-    Stats,
-    /// Clear memories by category, by key, or clear all
-    Clear {
-        /// Delete a single entry by key (supports prefix match)
+}
+
+#[derive(Subcommand, Debug)]
+enum TasksCommands {
+    /// List saved task templates
+    List,
+    /// Print a task template
+    Show {
+        /// Task name
+        name: String,
+    },
+    /// Save (or replace) a task template in the workspace
+    Save {
+        /// Task name (letters, digits, '-' or '_')
+        name: String,
+        /// Prompt text; `{{input}}` placeholders are filled from parameters
+        #[arg(
+            long,
+            conflicts_with = "prompt_file",
+            required_unless_present = "prompt_file"
+        )]
+        prompt: Option<String>,
+        /// Read the prompt from a file instead of --prompt
         #[arg(long)]
-        key: Option<String>,
+        prompt_file: Option<std::path::PathBuf>,
+        /// Short description shown by `zeroclaw tasks list`
         #[arg(long)]
-        category: Option<String>,
-        /// Skip confirmation prompt
+        description: Option<String>,
+        /// Declared input as `name` (required) or `name=default` (repeatable)
+        #[arg(long = "input")]
+        inputs: Vec<String>,
+        /// Tool available while the task runs (repeatable; default: all tools)
+        #[arg(long = "tool")]
+        tools: Vec<String>,
+    },
+    /// Run a task template once through the agent
+    Run {
+        /// Task name
+        name: String,
+        /// Input value as `key=value` (repeatable)
+        #[arg(long = "param")]
+        params: Vec<String>,
+        /// Provider override
+        #[arg(short, long)]
+        provider: Option<String>,
+        /// Model override
         #[arg(long)]
-        yes: bool,
+        model: Option<String>,
+        /// Temperature (0.0 - 2.0)
+        #[arg(short, long, default_value = "0.7", value_parser = parse_temperature)]
+        temperature: f64,
+    },
+    /// Delete a task template
+    Remove {
+        /// Task name
+        name: String,
     },
 }
 
-fn print_selection(selection: &presets::WorkspacePresetSelection) {
-    println!("Preset: {}", selection.preset_id);
-    println!("Packs:  {}", selection.packs.join(", "));
-    if !selection.added_packs.is_empty() {
-        println!("Added:  {}", selection.added_packs.join(", "));
-    }
-}
-
-fn print_selection_diff(diff: &presets::SelectionDiff) {
+#[derive(Subcommand, Debug)]
+enum ReportsCommands {
+    /// List report definitions
+    List,
+    /// Print a report definition
+    Show {
+        /// Report name
+        name: String,
+    },
+    /// Create a starter report definition and template in the workspace
+    New {
+        /// Report name (letters, digits, '-' or '_')
+        name: String,
+    },
+    /// Render a report now, delivering it if it declares a destination
+    Run {
+        /// Report name
+        name: String,
+        /// Deliver to this channel instead of the report's own destination
+        #[arg(long, requires = "to")]
+        channel: Option<String>,
+        /// Recipient on --channel (chat, channel or room id)
+        #[arg(long, requires = "channel")]
+        to: Option<String>,
+        /// Only write the report file
+        #[arg(long, conflicts_with = "channel")]
+        no_deliver: bool,
+    },
+    /// Add a cron job that renders and delivers the report
+    Schedule {
+        /// Report name
+        name: String,
+        /// Cron expression, e.g. "0 9 * * MON"
+        expression: String,
+        /// IANA timezone for the expression (default: [ui] timezone)
+        #[arg(long)]
+        tz: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ShareCommands {
+    /// List active shares
+    List,
+    /// Revoke a share so its link stops working
+    Revoke {
+        /// Share id
+        id: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ArtifactsCommands {
+    /// List stored artifacts, newest first
+    List,
+    /// Print an artifact's content
+    Get {
+        /// Artifact id or a unique prefix (at least 8 characters)
+        id: String,
+    },
+    /// Delete old artifacts
+    Prune {
+        /// Age in days (default: [artifacts] max_age_days)
+        #[arg(long)]
+        older_than_days: Option<u32>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum PromptsCommands {
+    /// List the bundled prompts and which ones the workspace overrides
+    List,
+    /// Print the prompt in effect
+    Show {
+        /// Prompt name
+        name: String,
+        /// Print the built-in text even when overridden
+        #[arg(long)]
+        builtin: bool,
+    },
+    /// Show how workspace overrides differ from the built-in prompts
+    Diff {
+        /// Prompt name (default: every override)
+        name: Option<String>,
+    },
+    /// Copy the built-in prompts into the workspace for editing
+    Export {
+        /// Overwrite existing override files
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ContactsCommands {
+    /// List saved contacts
+    List,
+    /// Add or replace a contact (contacts added here are approved)
+    Add {
+        /// Display name
+        name: String,
+        /// Channel address as `channel=recipient` (repeatable)
+        #[arg(long = "address", required = true)]
+        addresses: Vec<String>,
+        /// Alternative name the agent may use (repeatable)
+        #[arg(long = "alias")]
+        aliases: Vec<String>,
+        /// Channel to use when a message does not name one
+        #[arg(long)]
+        prefer: Option<String>,
+        /// Preferences the agent should respect when messaging this contact
+        #[arg(long)]
+        notes: Option<String>,
+    },
+    /// Approve outbound messages to a contact the agent added
+    Approve {
+        /// Contact name or alias
+        name: String,
+    },
+    /// Delete a contact
+    Remove {
+        /// Contact name or alias
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum IdentityCommands {
+    /// Long-term reply preferences injected into an identity's sessions
+    Prefs {
+        #[command(subcommand)]
+        prefs_command: PrefsCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum PrefsCommands {
+    /// List every identity with saved preferences
+    List,
+    /// Show one identity's preferences
+    Show {
+        /// `cli`, `<channel>:<sender>` or `contact:<name>`
+        #[arg(long, default_value = "cli")]
+        identity: String,
+    },
+    /// Set one preference
+    Set {
+        /// tone, verbosity, formatting, units or language
+        field: String,
+        /// New value (e.g. `concise`, `metric`, `de`)
+        #[arg(required = true, trailing_var_arg = true)]
+        value: Vec<String>,
+        /// `cli`, `<channel>:<sender>` or `contact:<name>`
+        #[arg(long, default_value = "cli")]
+        identity: String,
+    },
+    /// Clear one preference, or the whole profile when no field is given
+    Clear {
+        /// tone, verbosity, formatting, units or language
+        field: Option<String>,
+        /// `cli`, `<channel>:<sender>` or `contact:<name>`
+        #[arg(long, default_value = "cli")]
+        identity: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum InstructionsCommands {
+    /// List every standing instruction
+    List,
+    /// Add a standing instruction
+    Add {
+        /// Instruction text (e.g. `never touch the prod cluster`)
+        #[arg(required = true, trailing_var_arg = true)]
+        text: Vec<String>,
+        /// `global`, `channel:<name>` or `skill:<name>`
+        #[arg(long, default_value = "global")]
+        scope: String,
+    },
+    /// Remove a standing instruction by id
+    Remove {
+        /// Instruction id (see `instructions list`)
+        id: u64,
+    },
+    /// Show the instructions a session would receive, in precedence order
+    Active {
+        /// Channel name (`cli` for interactive `zeroclaw agent`)
+        #[arg(long, default_value = "cli")]
+        channel: String,
+        /// Loaded skill (repeatable)
+        #[arg(long = "skill")]
+        skills: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CtlCommands {
+    /// Show daemon health, estop state, paused channels and jobs
+    Status,
+    /// Re-read config.toml and apply the settings held process-wide
+    Reload,
+    /// Engage the emergency stop (everything unless narrowed)
+    Estop {
+        /// Block network access only
+        #[arg(long)]
+        network: bool,
+        /// Domain pattern(s) to block (repeatable)
+        #[arg(long = "domain")]
+        domains: Vec<String>,
+        /// Tool name(s) to freeze (repeatable)
+        #[arg(long = "tool")]
+        tools: Vec<String>,
+    },
+    /// List requests waiting for a second approval
+    Approvals,
+    /// Approve a pending request as `[control].operator`
+    Approve {
+        /// Approval code
+        code: String,
+    },
+    /// Deny a pending request as `[control].operator`
+    Deny {
+        /// Approval code
+        code: String,
+    },
+    /// Run an agent turn in the daemon and print its job id
+    Submit {
+        /// Message for the agent
+        #[arg(required = true, trailing_var_arg = true)]
+        message: Vec<String>,
+    },
+    /// Show the state and output of a submitted job
+    Job {
+        /// Job id printed by `submit`
+        id: String,
+    },
+    /// Drop inbound messages from a channel until it is resumed
+    PauseChannel {
+        /// Channel name (e.g. telegram)
+        channel: String,
+    },
+    /// Dispatch messages from a paused channel again
+    ResumeChannel {
+        /// Channel name (e.g. telegram)
+        channel: String,
+    },
+    /// Drop inbound channel messages still waiting to be dispatched
+    FlushQueues,
+    /// Inspect channel conversation sessions
+    Sessions {
+        #[command(subcommand)]
+        sessions_command: CtlSessionsCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CtlSessionsCommands {
+    /// List per-sender conversation histories held in memory
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+enum QuestionsCommands {
+    /// List pending questions
+    List {
+        /// Include answered, resumed and cancelled questions
+        #[arg(long)]
+        all: bool,
+    },
+    /// Answer a pending question; the daemon resumes the waiting task
+    Answer {
+        /// Question id (e.g. q1a2b3)
+        id: String,
+        /// Answer text (or the number of one of the offered options)
+        #[arg(required = true, trailing_var_arg = true)]
+        answer: Vec<String>,
+    },
+    /// Cancel a pending question; its task is not resumed
+    Cancel {
+        /// Question id
+        id: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum InboxCommands {
+    /// List reply drafts waiting for approval
+    Drafts,
+    /// Send a drafted reply through [email_send]
+    Approve {
+        /// Draft id (e.g. m1a2b3)
+        id: String,
+    },
+    /// Discard a drafted reply without sending it
+    Discard {
+        /// Draft id
+        id: String,
+    },
+    /// Print the summary of the last 24 hours
+    Summary,
+    /// Manage per-sender triage rules (stored in memory)
+    Rules {
+        #[command(subcommand)]
+        rules_command: InboxRuleCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum RagCommands {
+    /// List collections, their embedding settings, readers and documents
+    List,
+    /// Chunk and store a file or a directory of text/markdown files
+    Ingest {
+        /// Collection name
+        collection: String,
+        /// File or directory to ingest
+        path: std::path::PathBuf,
+    },
+    /// Search one collection (as the local CLI, ignoring readers)
+    Search {
+        /// Collection name
+        collection: String,
+        /// Search query
+        query: String,
+        /// Maximum results
+        #[arg(long, default_value_t = 5)]
+        limit: usize,
+    },
+    /// Remove an ingested document from a collection
+    Remove {
+        /// Collection name
+        collection: String,
+        /// Document name as shown by `zeroclaw rag list`
+        document: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum RecordingsCommands {
+    /// Transcribe, summarize and store one audio file
+    Process {
+        /// Audio file (flac, m4a, mp3, mp4, ogg, opus, wav, webm, ...)
+        path: std::path::PathBuf,
+        /// Also post the summary to the [recordings] channel
+        #[arg(long)]
+        post: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ResearchCommands {
+    /// List topics with their schedule, budget and last run
+    List,
+    /// Compose a brief now and print it
+    Run {
+        /// Topic name
+        topic: String,
+        /// Also send the brief and mark its sources as seen
+        #[arg(long)]
+        deliver: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum InboxRuleCommands {
+    /// List sender rules
+    List,
+    /// Always classify mail from a sender as action, fyi or spam
+    Set {
+        /// Address (alice@example.com) or domain (@example.com)
+        sender: String,
+        /// action, fyi or spam
+        category: String,
+        /// Optional note shown as the triage reason
+        #[arg(long, default_value = "")]
+        note: String,
+    },
+    /// Remove the rule for a sender
+    Remove {
+        /// Address or domain of the rule
+        sender: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum BatchCommands {
+    /// Render the template for every input and run each through the agent
+    Run {
+        /// File with one input per line (blank lines and `#` comments are skipped)
+        #[arg(long)]
+        input: std::path::PathBuf,
+        /// Prompt template; supports {{file}}, {{input}}, {{index}} and {{content}}
+        #[arg(
+            long,
+            conflicts_with = "template_file",
+            required_unless_present = "template_file"
+        )]
+        template: Option<String>,
+        /// Read the template from a file instead of --template
+        #[arg(long)]
+        template_file: Option<std::path::PathBuf>,
+        /// Number of inputs processed at once
+        #[arg(long, default_value = "4")]
+        concurrency: usize,
+        /// Directory for per-input outputs and `manifest.json`
+        #[arg(long, default_value = "batch-results")]
+        out: std::path::PathBuf,
+        /// Retries per input after a failed attempt
+        #[arg(long, default_value = "2")]
+        retries: u32,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum GrafanaCommands {
+    /// Write an importable dashboard JSON and Prometheus alert rules
+    Export {
+        /// Directory to write `zeroclaw-dashboard.json` and `zeroclaw-alerts.yml` into
+        #[arg(long, default_value = ".")]
+        output_dir: std::path::PathBuf,
+        /// Minutes without inbound messages before a channel is considered stale
+        #[arg(long, default_value = "60")]
+        channel_stale_minutes: u64,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum MemoryCommands {
+    /// List memory entries with optional filters
+    List {
+        #[arg(long)]
+        category: Option<String>,
+        #[arg(long)]
+        session: Option<String>,
+        #[arg(long, default_value = "50")]
+        limit: usize,
+        #[arg(long, default_value = "0")]
+        offset: usize,
+    },
+    /// Get a specific memory entry by key
+    Get { key: String },
+    /// Show memory backend statistics and health
+    Stats,
+    /// Clear memories by category, by key, or clear all
+    Clear {
+        /// Delete a single entry by key (supports prefix match)
+        #[arg(long)]
+        key: Option<String>,
+        #[arg(long)]
+        category: Option<String>,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+fn print_selection(selection: &presets::WorkspacePresetSelection) {
+    println!("Preset: {}", selection.preset_id);
+    println!("Packs:  {}", selection.packs.join(", "));
+    if !selection.added_packs.is_empty() {
+        println!("Added:  {}", selection.added_packs.join(", "));
+    }
+}
+
+fn print_knowledge_sources(sources: &[presets::knowledge::KnowledgeSource]) {
+    for source in sources {
+        let location = source
+            .path
+            .as_deref()
+            .or(source.url.as_deref())
+            .or(source.manifest_url.as_deref())
+            .unwrap_or_default();
+        let license = source.license.as_deref().unwrap_or("per manifest");
+        println!("  - {} [{license}] {location}", source.id);
+    }
+}
+
+fn print_selection_diff(diff: &presets::SelectionDiff) {
     if let Some(before) = &diff.before_preset_id {
         if before != &diff.after_preset_id {
             println!("Preset: {before} -> {}", diff.after_preset_id);
@@ -1018,6 +2054,75 @@ fn print_selection_diff(diff: &presets::SelectionDiff) {
     }
 }
 
+fn print_config_overrides(plan: &presets::overrides::OverridesPlan) {
+    if plan.changes.is_empty() && plan.conflicts.is_empty() {
+        println!("Config: no changes");
+    }
+    for change in &plan.changes {
+        println!("Config: {} {} -> {}", change.path, change.from, change.to);
+    }
+    for path in &plan.preserved {
+        println!("Config: {path} keeps local value (preset unchanged)");
+    }
+    for conflict in &plan.conflicts {
+        let kept = match conflict.resolution {
+            presets::overrides::ConflictResolution::KeptLocal => "keeping local value",
+            presets::overrides::ConflictResolution::TookPreset => "taking preset value",
+        };
+        println!(
+            "Conflict: {} changed locally ({}) and by the preset ({} -> {}); {kept}",
+            conflict.path, conflict.local, conflict.base, conflict.preset
+        );
+    }
+}
+
+fn format_missing_features(missing: &[presets::MissingPackFeatures]) -> String {
+    missing
+        .iter()
+        .map(|entry| format!("{} ({})", entry.pack, entry.features.join(", ")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn missing_features_warning(
+    missing: &[presets::MissingPackFeatures],
+    rebuild: bool,
+) -> Option<String> {
+    (!missing.is_empty() && !rebuild).then(|| {
+        format!(
+            "Packs [{}] are not compiled into this binary. Applying this plan requires `--rebuild`.",
+            format_missing_features(missing)
+        )
+    })
+}
+
+/// Refuse to save a selection whose packs this binary cannot run unless the
+/// binary is rebuilt for it in the same step.
+fn check_compiled_features(
+    missing: &[presets::MissingPackFeatures],
+    rebuild: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let packs = format_missing_features(missing);
+    if !rebuild && !dry_run {
+        bail!(
+            "{}",
+            i18n::tr_args(
+                "cli.preset.missing_features_need_rebuild",
+                &[("packs", &packs)]
+            )
+        );
+    }
+    println!(
+        "{}",
+        i18n::tr_args("cli.preset.missing_features", &[("packs", &packs)])
+    );
+    Ok(())
+}
+
 async fn maybe_rebuild_selection(
     selection: &presets::WorkspacePresetSelection,
     rebuild: bool,
@@ -1374,6 +2479,8 @@ struct PresetApplyDryRunReport {
     planned_selection: presets::WorkspacePresetSelection,
     selection_diff: presets::SelectionDiff,
     risky_packs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    missing_features: Vec<presets::MissingPackFeatures>,
     apply_requires_explicit_consent: bool,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     apply_consent_reasons: Vec<ConsentReasonCode>,
@@ -1397,6 +2504,8 @@ struct PresetImportDryRunReport {
     planned_selection: presets::WorkspacePresetSelection,
     selection_diff: presets::SelectionDiff,
     risky_packs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    missing_features: Vec<presets::MissingPackFeatures>,
     apply_requires_explicit_consent: bool,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     apply_consent_reasons: Vec<ConsentReasonCode>,
@@ -1407,6 +2516,10 @@ struct PresetImportDryRunReport {
     rebuild_requested: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     rebuild_preview: Option<OnboardRebuildPreview>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    knowledge: Vec<presets::knowledge::KnowledgeSource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    config_overrides: Option<presets::overrides::OverridesPlan>,
     workspace_written: bool,
 }
 
@@ -1438,6 +2551,8 @@ struct OnboardQuickDryRunReport {
     report_type: String,
     mode: String,
     intent_plan: Option<OnboardIntentPlanPreview>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    template: Option<onboard::templates::TemplateId>,
     planned_selection: presets::WorkspacePresetSelection,
     risky_packs: Vec<String>,
     security_profile: String,
@@ -1500,7 +2615,7 @@ fn shell_quote(raw: &str) -> String {
 fn format_consent_reasons(reasons: &[ConsentReasonCode]) -> String {
     reasons
         .iter()
-        .map(ToString::to_string)
+        .map(|reason| format!("{reason} ({})", i18n::tr(reason.i18n_key())))
         .collect::<Vec<_>>()
         .join(", ")
 }
@@ -1522,7 +2637,7 @@ fn onboard_warning_keys(codes: &[OnboardWarningCode]) -> Vec<String> {
 fn format_security_risk_consent_reasons(reasons: &[SecurityRiskConsentReasonCode]) -> String {
     reasons
         .iter()
-        .map(ToString::to_string)
+        .map(|reason| format!("{reason} ({})", i18n::tr(reason.i18n_key())))
         .collect::<Vec<_>>()
         .join(", ")
 }
@@ -1934,6 +3049,66 @@ async fn handle_security_command(command: SecurityCommands, config: &mut Config)
             print_security_profile_summary(config);
             Ok(())
         }
+        SecurityCommands::Secrets => {
+            let secrets = security::rotation::configured_secrets(config);
+            if secrets.is_empty() {
+                println!("No rotatable secrets configured.");
+                return Ok(());
+            }
+            let history = security::rotation::load_history(config);
+            let now = chrono::Utc::now();
+            for id in secrets {
+                let age = match security::rotation::secret_age_days(config, &history, &id, now) {
+                    Some(days) => format!("rotated {days}d ago"),
+                    None => "no rotation history".to_string(),
+                };
+                let method = if security::rotation::issuer_for(config, &id).is_some() {
+                    "provider api"
+                } else {
+                    "manual"
+                };
+                println!("{id:<44} {age:<22} {method}");
+            }
+            Ok(())
+        }
+        SecurityCommands::Rotate {
+            secret,
+            stdin,
+            keep_old,
+        } => {
+            let source = match security::rotation::issuer_for(config, &secret) {
+                Some(issuer) if !stdin => security::rotation::NewSecret::Issued(issuer),
+                _ if stdin => {
+                    let mut value = String::new();
+                    std::io::stdin().read_line(&mut value)?;
+                    security::rotation::NewSecret::Provided(value.trim().to_string())
+                }
+                _ => security::rotation::NewSecret::Provided(read_auth_input(&format!(
+                    "New value for {secret}"
+                ))?),
+            };
+            let report = security::rotation::rotate(config, &secret, source, keep_old).await?;
+            println!(
+                "✅ Rotated {} via {} ({} → {})",
+                report.secret,
+                report.method,
+                report.old_fingerprint.as_deref().unwrap_or("unset"),
+                report.new_fingerprint
+            );
+            if report.verified {
+                println!("  Verified against the provider.");
+            } else {
+                println!("  Not verified: no provider check exists for this secret.");
+            }
+            println!("  Saved config: {}", config.config_path.display());
+            match report.retired {
+                Some(Ok(())) => println!("  Old key revoked."),
+                Some(Err(error)) => println!("  ⚠️  {error}. Revoke the old key manually."),
+                None if keep_old || report.old_fingerprint.is_none() => {}
+                None => println!("  Revoke the old value at its provider now."),
+            }
+            Ok(())
+        }
         SecurityCommands::Profile { profile_command } => match profile_command {
             SecurityProfileCommands::Set {
                 level,
@@ -2137,15 +3312,1021 @@ async fn handle_security_command(command: SecurityCommands, config: &mut Config)
     }
 }
 
-async fn handle_preset_command(command: PresetCommands, config: &Config) -> Result<()> {
+async fn handle_batch_command(command: BatchCommands, config: Config) -> Result<()> {
     match command {
-        PresetCommands::List => {
-            println!("Official presets:");
-            for preset in onboard::PRESETS {
-                println!("- {}: {}", preset.id, preset.description);
-                println!("  packs: {}", preset.packs.join(", "));
-            }
-            println!();
+        BatchCommands::Run {
+            input,
+            template,
+            template_file,
+            concurrency,
+            out,
+            retries,
+        } => {
+            let template = match (template, template_file) {
+                (Some(template), _) => template,
+                (None, Some(path)) => std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?,
+                (None, None) => bail!("Provide --template or --template-file"),
+            };
+            let inputs = batch::read_inputs(&input)?;
+            println!(
+                "Running {} inputs (concurrency {}) into {}",
+                inputs.len(),
+                concurrency.max(1),
+                out.display()
+            );
+            let options = batch::BatchOptions {
+                template,
+                concurrency,
+                retries,
+                retry_backoff: std::time::Duration::from_secs(1),
+                out_dir: out,
+            };
+            let manifest = batch::run_batch(inputs, &options, |prompt| {
+                batch::run_agent_prompt(&config, prompt)
+            })
+            .await?;
+
+            let totals = &manifest.totals;
+            println!(
+                "\nDone: {}/{} succeeded · {} input + {} output tokens · est. ${:.4}",
+                totals.succeeded,
+                totals.items,
+                totals.usage.input_tokens,
+                totals.usage.output_tokens,
+                totals.usage.cost_usd
+            );
+            println!(
+                "Manifest: {}",
+                options.out_dir.join(batch::MANIFEST_FILE).display()
+            );
+            if totals.failed > 0 {
+                bail!("{} of {} inputs failed", totals.failed, totals.items);
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn handle_ctl_command(command: CtlCommands, config: &Config) -> Result<()> {
+    let request = match &command {
+        CtlCommands::Status => control::Command::Status,
+        CtlCommands::Reload => control::Command::Reload,
+        CtlCommands::Estop {
+            network,
+            domains,
+            tools,
+        } => control::Command::Estop {
+            network: *network,
+            domains: domains.clone(),
+            tools: tools.clone(),
+        },
+        CtlCommands::Approvals => control::Command::Approvals,
+        CtlCommands::Approve { code } | CtlCommands::Deny { code } => control::Command::Decide {
+            code: code.clone(),
+            approved: matches!(command, CtlCommands::Approve { .. }),
+        },
+        CtlCommands::Submit { message } => control::Command::Submit {
+            message: message.join(" "),
+        },
+        CtlCommands::Job { id } => control::Command::Job { id: id.clone() },
+        CtlCommands::PauseChannel { channel } => control::Command::PauseChannel {
+            channel: channel.clone(),
+        },
+        CtlCommands::ResumeChannel { channel } => control::Command::ResumeChannel {
+            channel: channel.clone(),
+        },
+        CtlCommands::FlushQueues => control::Command::FlushQueues,
+        CtlCommands::Sessions {
+            sessions_command: CtlSessionsCommands::List,
+        } => control::Command::Sessions,
+    };
+    let response = control::request(config, request).await?;
+    if !response.ok {
+        bail!(
+            "{}",
+            response
+                .error
+                .unwrap_or_else(|| "The daemon rejected the request".into())
+        );
+    }
+    let data = response.data;
+    match command {
+        CtlCommands::Reload => println!("Reloaded {}", data["reloaded"].as_str().unwrap_or("")),
+        CtlCommands::Approve { code, .. } => println!("Approved {code}"),
+        CtlCommands::Deny { code, .. } => println!("Denied {code}"),
+        CtlCommands::Submit { .. } => {
+            let id = data["id"].as_str().unwrap_or_default();
+            println!("Submitted job {id}; check it with `zeroclaw ctl job {id}`");
+        }
+        CtlCommands::PauseChannel { .. } | CtlCommands::ResumeChannel { .. } => {
+            let channel = data["channel"].as_str().unwrap_or_default();
+            let state = if data["paused"] == true {
+                "paused"
+            } else {
+                "resumed"
+            };
+            if data["changed"] == true {
+                println!("Channel {channel} {state}");
+            } else {
+                println!("Channel {channel} was already {state}");
+            }
+        }
+        CtlCommands::FlushQueues => println!(
+            "Dropped {} queued message(s)",
+            data["dropped"].as_u64().unwrap_or(0)
+        ),
+        CtlCommands::Sessions { .. } => {
+            let sessions = data.as_array().cloned().unwrap_or_default();
+            if sessions.is_empty() {
+                println!("No channel sessions in memory.");
+            }
+            for session in sessions {
+                println!(
+                    "{}  {} message(s)",
+                    session["key"].as_str().unwrap_or_default(),
+                    session["messages"]
+                );
+            }
+        }
+        _ => println!("{}", serde_json::to_string_pretty(&data)?),
+    }
+    Ok(())
+}
+
+fn handle_questions_command(command: QuestionsCommands, config: &Config) -> Result<()> {
+    let queue = questions::QuestionQueue::from_config(config);
+    match command {
+        QuestionsCommands::List { all } => {
+            let listed: Vec<_> = queue
+                .list()?
+                .into_iter()
+                .filter(|q| all || q.status == questions::QuestionStatus::Pending)
+                .collect();
+            if listed.is_empty() {
+                println!("No pending questions.");
+                return Ok(());
+            }
+            for question in listed {
+                println!(
+                    "{}  {:?}  {}  {}",
+                    question.id,
+                    question.status,
+                    question.asked_at.format("%Y-%m-%d %H:%M"),
+                    question.question
+                );
+                for (index, option) in question.options.iter().enumerate() {
+                    println!("      {}. {option}", index + 1);
+                }
+                if let Some(answer) = &question.answer {
+                    println!("      → {answer}");
+                }
+            }
+            Ok(())
+        }
+        QuestionsCommands::Answer { id, answer } => {
+            let question = queue.answer(Some(&id), &answer.join(" "), None)?;
+            println!(
+                "Answered {}: {}",
+                question.id,
+                question.answer.unwrap_or_default()
+            );
+            if config.questions.enabled {
+                println!("The daemon will resume the waiting task shortly.");
+            } else {
+                println!(
+                    "Note: [questions] is disabled, so no daemon worker will resume the task."
+                );
+            }
+            Ok(())
+        }
+        QuestionsCommands::Cancel { id } => {
+            queue.cancel(&id)?;
+            println!("Cancelled question {id}");
+            Ok(())
+        }
+    }
+}
+
+async fn handle_recordings_command(command: RecordingsCommands, config: &Config) -> Result<()> {
+    match command {
+        RecordingsCommands::Process { path, post } => {
+            let memory = memory::create_memory_with_storage(
+                &config.memory,
+                Some(&config.storage.provider.config),
+                &config.workspace_dir,
+                config.api_key.as_deref(),
+            )?;
+            let processed = recordings::process(config, memory.as_ref(), &path).await?;
+            println!("{}\n\n{}", processed.transcript, processed.summary);
+            if post {
+                let (channel, to) = recordings::route(config)?;
+                cron::scheduler::send_announcement(
+                    config,
+                    &channel,
+                    &to,
+                    &recordings::announcement(&processed),
+                )
+                .await?;
+                println!("Summary posted to {channel}.");
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn handle_research_command(command: ResearchCommands, config: &Config) -> Result<()> {
+    match command {
+        ResearchCommands::List => {
+            if config.research.topics.is_empty() {
+                println!("No research topics configured; add one under [research.topics.<name>]");
+                return Ok(());
+            }
+            let state = research::ResearchState::load(&config.workspace_dir);
+            for (name, topic) in &config.research.topics {
+                let last_run = state
+                    .last_run
+                    .get(name)
+                    .map_or_else(|| "never".to_string(), |at| at.to_rfc3339());
+                println!("{name}");
+                println!(
+                    "  every {}h  max {} sources  budget ${:.2}  last run: {last_run}",
+                    topic.every_hours, topic.max_sources, topic.max_cost_usd
+                );
+                if !topic.queries.is_empty() {
+                    println!("  queries: {}", topic.queries.join(" | "));
+                }
+            }
+        }
+        ResearchCommands::Run { topic, deliver } => {
+            let settings = research::topic(config, &topic)?;
+            let memory = memory::create_memory_with_storage(
+                &config.memory,
+                Some(&config.storage.provider.config),
+                &config.workspace_dir,
+                config.api_key.as_deref(),
+            )?;
+            let brief = if deliver {
+                research::deliver(config, memory.as_ref(), &topic, settings).await?
+            } else {
+                research::compose_brief(config, memory.as_ref(), &topic, settings).await?
+            };
+            match brief {
+                Some(brief) => println!("{}", brief.text),
+                None => println!("No new sources for '{topic}'."),
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn handle_rag_command(command: RagCommands, config: &Config) -> Result<()> {
+    let Some(rag) = rag::collections::RagCollections::from_config(config)? else {
+        bail!("No RAG collections configured; add one under [rag.collections.<name>]");
+    };
+    match command {
+        RagCommands::List => {
+            for collection in rag.all() {
+                let settings = &collection.settings;
+                let readers = if settings.readers.is_empty() {
+                    "cli".to_string()
+                } else {
+                    settings.readers.join(", ")
+                };
+                println!("{}  {}", collection.name, settings.description);
+                println!(
+                    "  embedding: {}  chunk: {} tokens  readers: {readers}",
+                    settings
+                        .embedding_model
+                        .as_deref()
+                        .unwrap_or(&config.memory.embedding_model),
+                    settings.chunk_max_tokens
+                );
+                for (document, chunks) in collection.documents().await? {
+                    println!("  - {document} ({chunks} chunks)");
+                }
+            }
+        }
+        RagCommands::Ingest { collection, path } => {
+            let collection = rag.get(&collection)?;
+            let files = rag::collections::ingest_files(&path)?;
+            if files.is_empty() {
+                bail!("No .md, .txt or .rst files found in {}", path.display());
+            }
+            for (document, file) in files {
+                let content = std::fs::read_to_string(&file)
+                    .with_context(|| format!("Failed to read {}", file.display()))?;
+                let chunks = collection.ingest(&document, &content).await?;
+                println!(
+                    "Ingested {document} ({chunks} chunks) into {}",
+                    collection.name
+                );
+            }
+        }
+        RagCommands::Search {
+            collection,
+            query,
+            limit,
+        } => {
+            let results = rag.get(&collection)?.search(&query, limit).await?;
+            if results.is_empty() {
+                println!("No matches.");
+            }
+            for entry in results {
+                let score = entry
+                    .score
+                    .map(|score| format!(" ({score:.2})"))
+                    .unwrap_or_default();
+                println!("{}{score}\n{}\n", entry.key, entry.content);
+            }
+        }
+        RagCommands::Remove {
+            collection,
+            document,
+        } => {
+            let removed = rag.get(&collection)?.remove(&document).await?;
+            if removed == 0 {
+                bail!("No document '{document}' in {collection}");
+            }
+            println!("Removed {document} ({removed} chunks) from {collection}");
+        }
+    }
+    Ok(())
+}
+
+async fn handle_inbox_command(command: InboxCommands, config: &Config) -> Result<()> {
+    let log = inbox::InboxLog::from_config(config);
+    match command {
+        InboxCommands::Drafts => {
+            println!("{}", inbox::render_drafts(&log.pending_drafts()?));
+            Ok(())
+        }
+        InboxCommands::Approve { id } => {
+            let entry = inbox::approve(config, &log, &id).await?;
+            println!("Sent reply to {} ({})", entry.sender, entry.id);
+            Ok(())
+        }
+        InboxCommands::Discard { id } => {
+            let entry = log.set_draft_status(&id, inbox::DraftStatus::Discarded)?;
+            println!("Discarded draft {} to {}", entry.id, entry.sender);
+            Ok(())
+        }
+        InboxCommands::Summary => {
+            println!(
+                "{}",
+                inbox::render_summary(&log.list()?, chrono::Utc::now())
+            );
+            Ok(())
+        }
+        InboxCommands::Rules { rules_command } => {
+            let mem = memory::create_memory_with_storage(
+                &config.memory,
+                Some(&config.storage.provider.config),
+                &config.workspace_dir,
+                config.api_key.as_deref(),
+            )?;
+            match rules_command {
+                InboxRuleCommands::List => {
+                    let rules = inbox::list_rules(mem.as_ref()).await?;
+                    if rules.is_empty() {
+                        println!("No sender rules. Add one with `zeroclaw inbox rules set <sender> <category>`.");
+                    }
+                    for rule in rules {
+                        println!("{}  {}", rule.sender, rule.memory_content());
+                    }
+                }
+                InboxRuleCommands::Set {
+                    sender,
+                    category,
+                    note,
+                } => {
+                    let category = inbox::Category::parse(&category).with_context(|| {
+                        format!("Unknown category '{category}' (expected action, fyi or spam)")
+                    })?;
+                    let rule = inbox::SenderRule {
+                        sender: sender.trim().to_ascii_lowercase(),
+                        category,
+                        note,
+                    };
+                    inbox::set_rule(mem.as_ref(), &rule).await?;
+                    println!("Mail from {} is now {}", rule.sender, category.label());
+                }
+                InboxRuleCommands::Remove { sender } => {
+                    if inbox::remove_rule(mem.as_ref(), &sender).await? {
+                        println!("Removed the rule for {sender}");
+                    } else {
+                        println!("No rule for {sender}");
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn handle_contacts_command(command: ContactsCommands, config: &Config) -> Result<()> {
+    let book = contacts::ContactBook::from_config(config);
+    match command {
+        ContactsCommands::List => {
+            let saved = book.list()?;
+            if saved.is_empty() {
+                println!("No contacts. Add one with `zeroclaw contacts add <name> --address channel=id`.");
+                return Ok(());
+            }
+            for contact in saved {
+                println!("{}", contact.summary());
+            }
+            Ok(())
+        }
+        ContactsCommands::Add {
+            name,
+            addresses,
+            aliases,
+            prefer,
+            notes,
+        } => {
+            let contact = contacts::Contact {
+                name,
+                aliases,
+                addresses: contacts::parse_addresses(&addresses)?,
+                preferred_channel: prefer,
+                notes,
+                approved: true,
+            };
+            let name = contact.name.clone();
+            book.upsert(contact, true)?;
+            println!("Saved contact '{name}' to {}", book.path().display());
+            Ok(())
+        }
+        ContactsCommands::Approve { name } => {
+            book.approve(&name)?;
+            println!("Approved outbound messages to '{name}'");
+            Ok(())
+        }
+        ContactsCommands::Remove { name } => {
+            if book.remove(&name)? {
+                println!("Removed contact '{name}'");
+                Ok(())
+            } else {
+                bail!("Unknown contact '{name}'")
+            }
+        }
+    }
+}
+
+fn handle_identity_command(command: IdentityCommands, config: &Config) -> Result<()> {
+    let IdentityCommands::Prefs { prefs_command } = command;
+    let store = preferences::PreferenceStore::for_workspace(&config.workspace_dir);
+    match prefs_command {
+        PrefsCommands::List => {
+            let profiles = store.list()?;
+            if profiles.is_empty() {
+                println!("No saved preferences. Set one with `zeroclaw identity prefs set verbosity concise`.");
+                return Ok(());
+            }
+            for (identity, profile) in profiles {
+                println!("{identity}: {}", profile.summary());
+            }
+            Ok(())
+        }
+        PrefsCommands::Show { identity } => {
+            let identity = store.resolve_identity(&identity)?;
+            let profile = store.get(&identity)?;
+            if profile.is_empty() {
+                println!("{identity} has no saved preferences.");
+                return Ok(());
+            }
+            for field in preferences::Field::ALL {
+                if let Some(value) = profile.get(field) {
+                    println!("{:<11} {value}", field.as_str());
+                }
+            }
+            Ok(())
+        }
+        PrefsCommands::Set {
+            field,
+            value,
+            identity,
+        } => {
+            let identity = store.resolve_identity(&identity)?;
+            let field = preferences::Field::parse(&field)?;
+            let profile = store.set(&identity, field, &value.join(" "))?;
+            println!("Saved preferences for {identity}: {}", profile.summary());
+            Ok(())
+        }
+        PrefsCommands::Clear { field, identity } => {
+            let identity = store.resolve_identity(&identity)?;
+            let field = field
+                .as_deref()
+                .map(preferences::Field::parse)
+                .transpose()?;
+            if store.clear(&identity, field)? {
+                println!("Cleared preferences for {identity}");
+            } else {
+                println!("Nothing to clear for {identity}");
+            }
+            Ok(())
+        }
+    }
+}
+
+fn handle_instructions_command(command: InstructionsCommands, config: &Config) -> Result<()> {
+    let store = instructions::InstructionStore::from_config(config);
+    match command {
+        InstructionsCommands::List => {
+            let all = store.list()?;
+            if all.is_empty() {
+                println!(
+                    "No standing instructions. Add one with `zeroclaw instructions add <text>`."
+                );
+                return Ok(());
+            }
+            for instruction in all {
+                println!(
+                    "#{:<3} [{}] {}",
+                    instruction.id, instruction.scope, instruction.text
+                );
+            }
+            Ok(())
+        }
+        InstructionsCommands::Add { text, scope } => {
+            let scope = instructions::Scope::parse(&scope)?;
+            let instruction = store.add(scope, &text.join(" "))?;
+            println!(
+                "Saved standing instruction #{} [{}]: {}",
+                instruction.id, instruction.scope, instruction.text
+            );
+            Ok(())
+        }
+        InstructionsCommands::Remove { id } => {
+            match store.remove(id)? {
+                Some(removed) => println!("Removed standing instruction #{id}: {}", removed.text),
+                None => println!("No standing instruction #{id}"),
+            }
+            Ok(())
+        }
+        InstructionsCommands::Active { channel, skills } => {
+            let active = store.active(&channel, &skills)?;
+            if active.is_empty() {
+                println!("No standing instructions apply to this session.");
+                return Ok(());
+            }
+            println!("Lowest to highest precedence:");
+            for instruction in active {
+                println!(
+                    "#{:<3} [{}] {}",
+                    instruction.id, instruction.scope, instruction.text
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+fn handle_models_shadow(config: &Config, limit: usize) -> Result<()> {
+    let records = providers::shadow::load_records(&config.workspace_dir)?;
+    if records.is_empty() {
+        if config.shadow.enabled {
+            println!("No shadow samples recorded yet.");
+        } else {
+            println!("Shadow mode is disabled. Set [shadow] enabled = true, provider and model.");
+        }
+        return Ok(());
+    }
+
+    let summary = providers::shadow::summarize(&records);
+    let last = &records[records.len() - 1];
+    println!(
+        "Shadow evaluation: {} samples ({} -> {}:{})",
+        summary.samples, last.primary.provider, last.shadow.provider, last.shadow.model
+    );
+    println!(
+        "  cost      primary ${:.4}   shadow ${:.4}",
+        summary.primary_cost_usd, summary.shadow_cost_usd
+    );
+    println!(
+        "  latency   primary {}ms avg   shadow {}ms avg",
+        summary.primary_avg_latency_ms, summary.shadow_avg_latency_ms
+    );
+    println!(
+        "  errors    primary {}   shadow {}",
+        summary.primary_errors, summary.shadow_errors
+    );
+    println!(
+        "  tool calls matched on {}/{} comparable samples",
+        summary.tool_agreement, summary.comparable
+    );
+
+    for record in records.iter().rev().take(limit) {
+        println!();
+        println!(
+            "{}  {}",
+            record.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+            util::truncate_with_ellipsis(&record.prompt, 80)
+        );
+        for (label, side) in [("primary", &record.primary), ("shadow", &record.shadow)] {
+            let outcome = match (&side.error, side.tool_calls.is_empty()) {
+                (Some(error), _) => format!("error: {error}"),
+                (None, false) => format!("tools: {}", side.tool_calls.join(", ")),
+                (None, true) => {
+                    util::truncate_with_ellipsis(side.text.as_deref().unwrap_or(""), 100)
+                }
+            };
+            println!(
+                "  {label:<8}{}ms  {}",
+                side.latency_ms,
+                outcome.replace('\n', " ")
+            );
+        }
+    }
+    Ok(())
+}
+
+fn handle_share_command(
+    config: &Config,
+    command: Option<ShareCommands>,
+    target: Option<String>,
+    ttl: &str,
+    title: Option<String>,
+    base_url: Option<String>,
+) -> Result<()> {
+    let store = share::ShareStore::from_config(config);
+    match command {
+        Some(ShareCommands::List) => {
+            let shares = store.list()?;
+            if shares.is_empty() {
+                println!("No active shares.");
+            }
+            for item in shares {
+                println!(
+                    "{}  {:<6} expires {}  {}",
+                    item.id,
+                    item.kind,
+                    item.expires_at.format("%Y-%m-%d %H:%M UTC"),
+                    item.title
+                );
+            }
+            Ok(())
+        }
+        Some(ShareCommands::Revoke { id }) => {
+            if store.revoke(&id)? {
+                println!("Revoked share {id}.");
+                Ok(())
+            } else {
+                bail!("No active share '{id}'")
+            }
+        }
+        None => {
+            let Some(target) = target else {
+                bail!(
+                    "Usage: zeroclaw share <trace-id|file> (or `share list`, `share revoke <id>`)"
+                );
+            };
+            let ttl = share::parse_ttl(ttl)?;
+            let (kind, default_title, text) = share::snapshot(config, &target)?;
+            let secrets = security::rotation::configured_secret_values(config);
+            let content = share::redact(&text, &secrets);
+            let created = store.create(
+                title.as_deref().unwrap_or(&default_title),
+                kind,
+                content,
+                ttl,
+            )?;
+            let base_url = base_url.unwrap_or_else(|| {
+                format!("http://{}:{}", config.gateway.host, config.gateway.port)
+            });
+            println!("{}", share::share_url(&store, &created, &base_url)?);
+            println!(
+                "Share {} expires {}. Revoke with `zeroclaw share revoke {}`.",
+                created.id,
+                created.expires_at.format("%Y-%m-%d %H:%M UTC"),
+                created.id
+            );
+            Ok(())
+        }
+    }
+}
+
+fn handle_artifacts_command(config: &Config, command: ArtifactsCommands) -> Result<()> {
+    let store = artifacts::ArtifactStore::for_workspace(&config.workspace_dir);
+    match command {
+        ArtifactsCommands::List => {
+            let items = store.list()?;
+            if items.is_empty() {
+                println!("No artifacts stored.");
+            }
+            for item in items {
+                println!(
+                    "{}  {:>9} bytes  {}  {}",
+                    &item.id[..12],
+                    item.bytes,
+                    item.created_at.format("%Y-%m-%d %H:%M UTC"),
+                    item.tool
+                );
+            }
+            Ok(())
+        }
+        ArtifactsCommands::Get { id } => {
+            let Some((_, content)) = store.get(&id)? else {
+                bail!("No artifact '{id}'");
+            };
+            print!("{content}");
+            Ok(())
+        }
+        ArtifactsCommands::Prune { older_than_days } => {
+            let days = older_than_days.unwrap_or(config.artifacts.max_age_days);
+            let removed = store.prune(chrono::Duration::days(i64::from(days)))?;
+            println!("Removed {removed} artifact(s) older than {days} day(s).");
+            Ok(())
+        }
+    }
+}
+
+fn handle_prompts_command(config: &Config, command: PromptsCommands) -> Result<()> {
+    let workspace = &config.workspace_dir;
+    match command {
+        PromptsCommands::List => {
+            match prompts::exported_version(workspace)? {
+                Some(version) if version != prompts::BUNDLE_VERSION => println!(
+                    "Prompt bundle v{} (overrides exported from v{version})",
+                    prompts::BUNDLE_VERSION
+                ),
+                _ => println!("Prompt bundle v{}", prompts::BUNDLE_VERSION),
+            }
+            for entry in prompts::status(workspace)? {
+                let source = match entry.source {
+                    prompts::PromptSource::Builtin => "built-in",
+                    prompts::PromptSource::Override => "override",
+                    prompts::PromptSource::StaleOverride => "override (built-in changed)",
+                };
+                println!(
+                    "  {:<22} {:<28} {}",
+                    entry.prompt.name, source, entry.prompt.summary
+                );
+            }
+            Ok(())
+        }
+        PromptsCommands::Show { name, builtin } => {
+            let Some(prompt) = prompts::builtin(&name) else {
+                bail!("Unknown prompt '{name}'");
+            };
+            if builtin {
+                print!("{}", prompt.text);
+            } else {
+                print!("{}", prompts::load(workspace, &name));
+            }
+            Ok(())
+        }
+        PromptsCommands::Diff { name } => {
+            let names: Vec<&str> = match name.as_deref() {
+                Some(name) => vec![name],
+                None => prompts::BUILTIN.iter().map(|prompt| prompt.name).collect(),
+            };
+            let mut any = false;
+            for name in names {
+                let diff = prompts::diff(workspace, name)?;
+                if !diff.is_empty() {
+                    print!("{diff}");
+                    any = true;
+                }
+            }
+            if !any {
+                println!("No differences from the built-in prompts.");
+            }
+            Ok(())
+        }
+        PromptsCommands::Export { force } => {
+            let written = prompts::export(workspace, force)?;
+            if written.is_empty() {
+                println!("All prompts already exported; use --force to overwrite.");
+            } else {
+                println!(
+                    "Exported {} to {}",
+                    written.join(", "),
+                    workspace.join("prompts").display()
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn handle_reports_command(command: ReportsCommands, config: Config) -> Result<()> {
+    let store = reports::ReportStore::for_workspace(&config.workspace_dir);
+    match command {
+        ReportsCommands::List => {
+            let definitions = store.list()?;
+            if definitions.is_empty() {
+                println!("No reports. Create one with `zeroclaw reports new <name>`.");
+                return Ok(());
+            }
+            for definition in definitions {
+                let delivery = definition
+                    .delivery
+                    .as_ref()
+                    .map(|d| format!(" → {}:{}", d.channel, d.to))
+                    .unwrap_or_default();
+                println!(
+                    "{:<24} {:<8} {:<4} {}{delivery}",
+                    definition.name,
+                    definition.format,
+                    definition.period,
+                    definition.description.as_deref().unwrap_or("")
+                );
+            }
+            Ok(())
+        }
+        ReportsCommands::Show { name } => {
+            let definition = store.load(&name)?;
+            print!("{}", toml::to_string_pretty(&definition)?);
+            Ok(())
+        }
+        ReportsCommands::New { name } => {
+            let (definition, template) = store.scaffold(&name)?;
+            println!("Created {}", definition.display());
+            println!("Created {}", template.display());
+            println!("Render it with `zeroclaw reports run {name}`.");
+            Ok(())
+        }
+        ReportsCommands::Run {
+            name,
+            channel,
+            to,
+            no_deliver,
+        } => {
+            let summary = if no_deliver {
+                let definition = store.load(&name)?;
+                let report = store
+                    .render(&config, &definition, chrono::Utc::now())
+                    .await?;
+                format!(
+                    "Report '{}' written to {} ({} bytes)",
+                    report.name,
+                    report.path.display(),
+                    report.bytes
+                )
+            } else {
+                let delivery = channel
+                    .zip(to)
+                    .map(|(channel, to)| reports::ReportDelivery { channel, to });
+                reports::run(&config, &name, delivery.as_ref()).await?
+            };
+            println!("{summary}");
+            Ok(())
+        }
+        ReportsCommands::Schedule {
+            name,
+            expression,
+            tz,
+        } => {
+            store.load(&name)?;
+            let tz = tz.or_else(|| {
+                datetime::UserClock::from_config(&config.ui)
+                    .default
+                    .iana_name()
+            });
+            let job = cron::add_agent_job(
+                &config,
+                Some(format!("report:{name}")),
+                cron::Schedule::Cron {
+                    expr: expression,
+                    tz,
+                },
+                &format!("{} {name}", reports::REPORT_COMMAND),
+                cron::SessionTarget::Isolated,
+                None,
+                cron::JobContext::default(),
+                None,
+                false,
+            )?;
+            println!("✅ Scheduled report '{name}' as cron job {}", job.id);
+            println!("  Expr: {}", job.expression);
+            println!("  Next: {}", job.next_run.to_rfc3339());
+            Ok(())
+        }
+    }
+}
+
+async fn handle_tasks_command(command: TasksCommands, config: Config) -> Result<()> {
+    let store = tasks::TaskStore::for_workspace(&config.workspace_dir);
+    match command {
+        TasksCommands::List => {
+            let templates = store.list()?;
+            if templates.is_empty() {
+                println!(
+                    "No saved tasks. Create one with `zeroclaw tasks save <name> --prompt ...`."
+                );
+                return Ok(());
+            }
+            for template in templates {
+                let inputs: Vec<String> = template
+                    .inputs
+                    .iter()
+                    .map(|input| match &input.default {
+                        Some(default) => format!("{}={default}", input.name),
+                        None => input.name.clone(),
+                    })
+                    .collect();
+                println!(
+                    "{:<24} {}{}",
+                    template.name,
+                    template.description.as_deref().unwrap_or(""),
+                    if inputs.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" [{}]", inputs.join(", "))
+                    }
+                );
+            }
+            Ok(())
+        }
+        TasksCommands::Show { name } => {
+            let template = store.load(&name)?;
+            print!("{}", toml::to_string_pretty(&template)?);
+            Ok(())
+        }
+        TasksCommands::Save {
+            name,
+            prompt,
+            prompt_file,
+            description,
+            inputs,
+            tools,
+        } => {
+            let prompt = match (prompt, prompt_file) {
+                (Some(prompt), _) => prompt,
+                (None, Some(path)) => std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?,
+                (None, None) => bail!("Provide --prompt or --prompt-file"),
+            };
+            let inputs = inputs
+                .iter()
+                .map(|raw| match raw.split_once('=') {
+                    Some((input, default)) => tasks::TaskInput {
+                        name: input.trim().to_string(),
+                        description: None,
+                        default: Some(default.to_string()),
+                    },
+                    None => tasks::TaskInput {
+                        name: raw.trim().to_string(),
+                        description: None,
+                        default: None,
+                    },
+                })
+                .collect();
+            let mut template = tasks::TaskTemplate {
+                name,
+                description,
+                prompt,
+                inputs,
+                tools,
+            };
+            template.declare_missing_inputs();
+            let path = store.save(&template)?;
+            println!("Saved task '{}' to {}", template.name, path.display());
+            Ok(())
+        }
+        TasksCommands::Run {
+            name,
+            params,
+            provider,
+            model,
+            temperature,
+        } => {
+            let params = tasks::parse_params(&params)?;
+            let task = store.render(&name, &params)?;
+            Box::pin(agent::run(
+                config,
+                Some(task.prompt),
+                provider,
+                model,
+                temperature,
+                Vec::new(),
+                false,
+                false,
+                task.tools,
+                handoff::CliHandoff::default(),
+            ))
+            .await
+            .map(|_| ())
+        }
+        TasksCommands::Remove { name } => {
+            store.remove(&name)?;
+            println!("Removed task '{name}'");
+            Ok(())
+        }
+    }
+}
+
+async fn handle_preset_command(command: PresetCommands, config: &Config) -> Result<()> {
+    match command {
+        PresetCommands::List => {
+            println!("Official presets:");
+            for preset in onboard::PRESETS {
+                println!("- {}: {}", preset.id, preset.description);
+                println!("  packs: {}", preset.packs.join(", "));
+            }
+            println!();
             println!("Available packs:");
             for pack in onboard::FEATURE_PACKS {
                 let risk = if pack.requires_confirmation {
@@ -2220,10 +4401,12 @@ async fn handle_preset_command(command: PresetCommands, config: &Config) -> Resu
             let diff = presets::selection_diff(before.as_ref(), &after);
 
             let risky = presets::risky_pack_ids(&after);
+            let missing = presets::missing_features(&after, &provenance::enabled_features());
             let execution_consent_reasons =
                 build_preset_execution_consent_reasons(&risky, yes_risky, rebuild, yes_rebuild);
-            let execution_warnings =
+            let mut execution_warnings =
                 build_preset_execution_warnings(&risky, rebuild, &execution_consent_reasons);
+            execution_warnings.extend(missing_features_warning(&missing, rebuild));
 
             if json {
                 let report = PresetApplyDryRunReport {
@@ -2233,6 +4416,7 @@ async fn handle_preset_command(command: PresetCommands, config: &Config) -> Resu
                     planned_selection: after.clone(),
                     selection_diff: diff,
                     risky_packs: risky.clone(),
+                    missing_features: missing,
                     apply_requires_explicit_consent: !execution_consent_reasons.is_empty(),
                     apply_consent_reason_keys: consent_reason_keys(&execution_consent_reasons),
                     apply_consent_reasons: execution_consent_reasons,
@@ -2250,22 +4434,36 @@ async fn handle_preset_command(command: PresetCommands, config: &Config) -> Resu
 
             if !risky.is_empty() && !yes_risky && !dry_run {
                 bail!(
-                    "Selection includes risky packs [{}]. Re-run with `--yes-risky`, or use `--dry-run`.",
-                    risky.join(", ")
+                    "{}",
+                    i18n::tr_args(
+                        "cli.preset.risky_packs_need_consent",
+                        &[("packs", &risky.join(", "))]
+                    )
                 );
             }
             if !risky.is_empty() {
-                println!("Risky packs: {}", risky.join(", "));
+                println!(
+                    "{}",
+                    i18n::tr_args("cli.preset.risky_packs", &[("packs", &risky.join(", "))])
+                );
             }
 
+            check_compiled_features(&missing, rebuild, dry_run)?;
+
             if dry_run {
-                println!("Apply dry-run: no changes written.");
+                println!("{}", i18n::tr("cli.preset.apply_dry_run"));
                 maybe_rebuild_selection(&after, rebuild, true, true).await?;
                 return Ok(());
             }
 
             let path = presets::save_workspace_selection(config, &after)?;
-            println!("Saved workspace preset selection: {}", path.display());
+            println!(
+                "{}",
+                i18n::tr_args(
+                    "cli.preset.saved_selection",
+                    &[("path", &path.display().to_string())]
+                )
+            );
             maybe_rebuild_selection(&after, rebuild, false, yes_rebuild).await?;
             Ok(())
         }
@@ -2490,28 +4688,46 @@ async fn handle_preset_command(command: PresetCommands, config: &Config) -> Resu
                     println!("  {}", entry.command);
                 }
                 println!();
-                println!("Plan only. Re-run with `--apply` to persist this selection.");
+                println!("{}", i18n::tr("cli.preset.plan_only"));
                 return Ok(());
             }
 
             if !risky.is_empty() && !yes_risky && !dry_run {
                 bail!(
-                    "Selection includes risky packs [{}]. Re-run with `--yes-risky`, or use `--dry-run`.",
-                    risky.join(", ")
+                    "{}",
+                    i18n::tr_args(
+                        "cli.preset.risky_packs_need_consent",
+                        &[("packs", &risky.join(", "))]
+                    )
                 );
             }
             if !risky.is_empty() {
-                println!("Risky packs: {}", risky.join(", "));
+                println!(
+                    "{}",
+                    i18n::tr_args("cli.preset.risky_packs", &[("packs", &risky.join(", "))])
+                );
             }
 
+            check_compiled_features(
+                &presets::missing_features(&after, &provenance::enabled_features()),
+                rebuild,
+                dry_run,
+            )?;
+
             if dry_run {
-                println!("Intent apply dry-run: no changes written.");
+                println!("{}", i18n::tr("cli.preset.intent_apply_dry_run"));
                 maybe_rebuild_selection(&after, rebuild, true, true).await?;
                 return Ok(());
             }
 
             let path = presets::save_workspace_selection(config, &after)?;
-            println!("Saved workspace preset selection: {}", path.display());
+            println!(
+                "{}",
+                i18n::tr_args(
+                    "cli.preset.saved_selection",
+                    &[("path", &path.display().to_string())]
+                )
+            );
             maybe_rebuild_selection(&after, rebuild, false, yes_rebuild).await?;
             println!("Recommended follow-up security command:");
             println!("  {security_apply_command}");
@@ -2578,10 +4794,12 @@ async fn handle_preset_command(command: PresetCommands, config: &Config) -> Resu
             let result = presets::import_selection_from_path(config, &path, mode)?;
             let diff = presets::selection_diff(result.before.as_ref(), &result.after);
             let risky = presets::risky_pack_ids(&result.after);
+            let missing = presets::missing_features(&result.after, &provenance::enabled_features());
             let execution_consent_reasons =
                 build_preset_execution_consent_reasons(&risky, yes_risky, rebuild, yes_rebuild);
-            let execution_warnings =
+            let mut execution_warnings =
                 build_preset_execution_warnings(&risky, rebuild, &execution_consent_reasons);
+            execution_warnings.extend(missing_features_warning(&missing, rebuild));
 
             if json {
                 let report = PresetImportDryRunReport {
@@ -2593,12 +4811,19 @@ async fn handle_preset_command(command: PresetCommands, config: &Config) -> Resu
                     planned_selection: result.after.clone(),
                     selection_diff: diff,
                     risky_packs: risky,
+                    missing_features: missing,
                     apply_requires_explicit_consent: !execution_consent_reasons.is_empty(),
                     apply_consent_reason_keys: consent_reason_keys(&execution_consent_reasons),
                     apply_consent_reasons: execution_consent_reasons,
                     warnings: execution_warnings,
                     rebuild_requested: rebuild,
                     rebuild_preview: build_rebuild_preview(&result.after, rebuild)?,
+                    knowledge: result
+                        .knowledge
+                        .as_ref()
+                        .map(|pack| pack.sources.clone())
+                        .unwrap_or_default(),
+                    config_overrides: result.config_overrides.clone(),
                     workspace_written: false,
                 };
                 println!("{}", serde_json::to_string_pretty(&report)?);
@@ -2607,25 +4832,94 @@ async fn handle_preset_command(command: PresetCommands, config: &Config) -> Resu
 
             println!("Import mode: {}", result.mode);
             print_selection_diff(&diff);
+            if let Some(plan) = &result.config_overrides {
+                print_config_overrides(plan);
+            }
 
             if !risky.is_empty() && !yes_risky && !dry_run {
                 bail!(
-                    "Selection includes risky packs [{}]. Re-run with `--yes-risky`, or use `--dry-run`.",
-                    risky.join(", ")
+                    "{}",
+                    i18n::tr_args(
+                        "cli.preset.risky_packs_need_consent",
+                        &[("packs", &risky.join(", "))]
+                    )
                 );
             }
             if !risky.is_empty() {
-                println!("Risky packs: {}", risky.join(", "));
+                println!(
+                    "{}",
+                    i18n::tr_args("cli.preset.risky_packs", &[("packs", &risky.join(", "))])
+                );
             }
 
+            check_compiled_features(&missing, rebuild, dry_run)?;
+
             if dry_run {
-                println!("Import dry-run: no changes written.");
+                if let Some(pack) = &result.knowledge {
+                    println!("{}", i18n::tr("cli.preset.knowledge_dry_run"));
+                    print_knowledge_sources(&pack.sources);
+                }
+                println!("{}", i18n::tr("cli.preset.import_dry_run"));
                 maybe_rebuild_selection(&result.after, rebuild, true, true).await?;
                 return Ok(());
             }
 
+            // Fetch and verify every document before writing anything.
+            let knowledge_documents = match &result.knowledge {
+                Some(pack) => Some((pack, presets::knowledge::fetch_documents(pack).await?)),
+                None => None,
+            };
+
             let saved = presets::save_workspace_selection(config, &result.after)?;
-            println!("Saved workspace preset selection: {}", saved.display());
+            println!(
+                "{}",
+                i18n::tr_args(
+                    "cli.preset.saved_selection",
+                    &[("path", &saved.display().to_string())]
+                )
+            );
+            if let Some(plan) = &result.config_overrides {
+                Box::pin(presets::overrides::apply(config, plan)).await?;
+                if !plan.changes.is_empty() {
+                    println!(
+                        "Applied {} config override(s) to {}",
+                        plan.changes.len(),
+                        config.config_path.display()
+                    );
+                }
+            }
+            if let Some((pack, documents)) = knowledge_documents {
+                let mem = memory::create_memory_with_storage(
+                    &config.memory,
+                    Some(&config.storage.provider.config),
+                    &config.workspace_dir,
+                    config.api_key.as_deref(),
+                )?;
+                let record = presets::knowledge::ingest_documents(
+                    mem.as_ref(),
+                    &config.workspace_dir,
+                    &pack.preset_id,
+                    &documents,
+                )
+                .await?;
+                let chunks: usize = record
+                    .documents
+                    .iter()
+                    .map(|document| document.memory_keys.len())
+                    .sum();
+                println!(
+                    "Ingested {} knowledge document(s) ({chunks} chunks) into memory:",
+                    record.documents.len()
+                );
+                for document in &record.documents {
+                    println!(
+                        "  - {} [{}] sha256:{}",
+                        document.id,
+                        document.license,
+                        &document.sha256[..12]
+                    );
+                }
+            }
             maybe_rebuild_selection(&result.after, rebuild, false, yes_rebuild).await?;
             Ok(())
         }
@@ -2684,7 +4978,6 @@ async fn handle_preset_command(command: PresetCommands, config: &Config) -> Resu
 }
 
 #[tokio::main]
-#[allow(clippy::too_many_lines)]
 async fn main() -> Result<()> {
     // Install default crypto provider for Rustls TLS.
     // This prevents the error: "could not automatically determine the process-level CryptoProvider"
@@ -2694,7 +4987,25 @@ async fn main() -> Result<()> {
     }
 
     let cli = Cli::parse();
+    let machine_json_mode = command_requests_machine_json(&cli.command);
 
+    let result = Box::pin(run(cli)).await;
+    observability::sinks::shutdown().await;
+    match result {
+        // JSON consumers get a classified error on stdout; stderr keeps the
+        // human-readable message.
+        Err(error) if machine_json_mode => {
+            let body = serde_json::json!({ "error": errors::ErrorReport::from_error(&error) });
+            println!("{}", serde_json::to_string_pretty(&body)?);
+            eprintln!("Error: {error:#}");
+            std::process::exit(1);
+        }
+        result => result,
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+async fn run(cli: Cli) -> Result<()> {
     if let Some(config_dir) = &cli.config_dir {
         if config_dir.trim().is_empty() {
             bail!("--config-dir cannot be empty");
@@ -2710,17 +5021,39 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Like completions, version output is stdout-only and needs no config.
+    if let Commands::Version { provenance, json } = &cli.command {
+        let mut stdout = std::io::stdout().lock();
+        if !*provenance {
+            writeln!(stdout, "zeroclaw {}", env!("CARGO_PKG_VERSION"))?;
+        } else if *json {
+            let report = provenance::Provenance::current()?;
+            writeln!(stdout, "{}", serde_json::to_string_pretty(&report)?)?;
+        } else {
+            write!(stdout, "{}", provenance::Provenance::current()?.render())?;
+        }
+        return Ok(());
+    }
+
+    // The remote worker's stdout/stderr belong to the tool it runs.
+    if let Commands::RemoteWorker { root, request } = &cli.command {
+        let code = runtime::remote::run_worker(request.as_deref(), root.as_deref())?;
+        std::process::exit(code);
+    }
+
     let machine_json_mode = command_requests_machine_json(&cli.command);
 
     if !machine_json_mode {
         // Initialize logging - respects RUST_LOG env var, defaults to INFO.
         // Logs stay on stderr to keep stdout user/machine payloads clean.
+        // The sink layer stays inert until [observability.sinks] is loaded below.
         let subscriber = fmt::Subscriber::builder()
             .with_env_filter(
                 EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
             )
             .with_writer(std::io::stderr)
-            .finish();
+            .finish()
+            .with(observability::sinks::SinkLayer);
 
         tracing::subscriber::set_global_default(subscriber)
             .expect("setting default subscriber failed");
@@ -2741,6 +5074,7 @@ async fn main() -> Result<()> {
         preset,
         pack,
         intent,
+        template,
         security_profile,
         yes_security_risk,
         dry_run,
@@ -2759,6 +5093,7 @@ async fn main() -> Result<()> {
         let preset = preset.clone();
         let pack = pack.clone();
         let intent = intent.clone();
+        let template = *template;
         let security_profile = *security_profile;
         let yes_security_risk = *yes_security_risk;
         let dry_run = *dry_run;
@@ -2784,13 +5119,21 @@ async fn main() -> Result<()> {
             rebuild,
             yes_rebuild,
         )?;
-        let config = if channels_only {
-            onboard::run_channels_repair_wizard().await
+        if template.is_some() && channels_only {
+            bail!("--template cannot be combined with --channels-only");
+        }
+        let mut config = if channels_only {
+            Box::pin(onboard::run_channels_repair_wizard()).await
         } else if interactive {
-            onboard::run_wizard(force).await
+            Box::pin(onboard::run_wizard(force)).await
         } else {
             let mut resolved_preset = preset.clone();
             let mut resolved_pack = pack.clone();
+            if let Some(template) = template.filter(|_| preset.is_none() && intent.is_none()) {
+                let template = template.template();
+                resolved_preset = Some(template.preset.to_string());
+                resolved_pack.extend(template.packs.iter().map(|pack| (*pack).to_string()));
+            }
             let mut resolved_remove_pack: Vec<String> = Vec::new();
             let mut intent_preview: Option<OnboardIntentPlanPreview> = None;
             let mut resolved_security_profile =
@@ -2899,6 +5242,7 @@ async fn main() -> Result<()> {
                         report_type: ONBOARD_QUICK_DRY_RUN_REPORT_TYPE.to_string(),
                         mode: "quick_dry_run".to_string(),
                         intent_plan: intent_preview,
+                        template,
                         planned_selection: planned_selection.clone(),
                         risky_packs: risky.clone(),
                         security_profile: effective_security_profile.clone(),
@@ -2916,6 +5260,9 @@ async fn main() -> Result<()> {
                     println!("{}", serde_json::to_string_pretty(&report)?);
                 } else {
                     println!("Onboard quick dry-run (no files written):");
+                    if let Some(template) = template {
+                        println!("  template: {}", template.as_str());
+                    }
                     println!("  preset: {}", planned_selection.preset_id);
                     println!("  packs: {}", planned_selection.packs.join(", "));
                     if !planned_selection.added_packs.is_empty() {
@@ -2938,7 +5285,7 @@ async fn main() -> Result<()> {
                 return Ok(());
             }
 
-            onboard::run_quick_setup(
+            Box::pin(onboard::run_quick_setup(
                 api_key.as_deref(),
                 provider.as_deref(),
                 model.as_deref(),
@@ -2949,7 +5296,7 @@ async fn main() -> Result<()> {
                 resolved_security_profile.as_deref(),
                 yes_security_risk,
                 force,
-            )
+            ))
             .await
         }?;
         let mut run_rebuild = rebuild;
@@ -2991,17 +5338,37 @@ async fn main() -> Result<()> {
             };
             maybe_rebuild_selection(&selection, true, false, rebuild_approved).await?;
         }
+        if let Some(template) = template {
+            let applied = onboard::templates::apply(&mut config, template).await?;
+            println!();
+            onboard::templates::print_applied(template, &applied);
+            println!();
+            onboard::templates::print_checklist(&config).await?;
+        }
         // Auto-start channels if user said yes during wizard
         if std::env::var("ZEROCLAW_AUTOSTART_CHANNELS").as_deref() == Ok("1") {
+            config::init_runtime_globals(&config);
             channels::start_channels(config).await?;
         }
         return Ok(());
     }
 
+    // Migrate before loading: loading would upgrade the file on its own.
+    if let Commands::Config {
+        config_command: ConfigCommands::Migrate { dry_run },
+    } = &cli.command
+    {
+        return handle_config_migrate(*dry_run).await;
+    }
+
     // All other commands need config loaded first
-    let mut config = Config::load_or_init().await?;
+    let mut config = Box::pin(Config::load_or_init()).await?;
     config.apply_env_overrides();
     observability::runtime_trace::init_from_config(&config.observability, &config.workspace_dir);
+    observability::runtime_trace::set_model_pricing(&config.cost);
+    providers::capabilities::init_from_workspace(&config.workspace_dir);
+    coordination::init_from_config(&config);
+    config::init_runtime_globals(&config);
     if config.security.otp.enabled {
         let config_dir = config
             .config_path
@@ -3018,7 +5385,9 @@ async fn main() -> Result<()> {
 
     match cli.command {
         Commands::Onboard { .. } => unreachable!(),
-        Commands::Completions { .. } => unreachable!(),
+        Commands::Completions { .. } | Commands::RemoteWorker { .. } | Commands::Version { .. } => {
+            unreachable!()
+        }
 
         Commands::Agent {
             message,
@@ -3026,17 +5395,77 @@ async fn main() -> Result<()> {
             model,
             temperature,
             peripheral,
-        } => agent::run(
-            config,
-            message,
+            simulate,
+            handoff: park_for,
+            continue_here,
+            collection,
+        } => {
+            let mut config = config;
+            if collection.is_some() {
+                config.agent.collection = collection;
+            }
+            let resume = if continue_here {
+                Some(
+                    handoff::HandoffStore::for_workspace(&config.workspace_dir)
+                        .claim(handoff::CLI_TARGET, None)?,
+                )
+            } else {
+                None
+            };
+            // A resumed conversation keeps its provider and model unless overridden.
+            let provider = provider.or_else(|| resume.as_ref()?.provider.clone());
+            let model = model.or_else(|| resume.as_ref()?.model.clone());
+            Box::pin(agent::run(
+                config,
+                message,
+                provider,
+                model,
+                temperature,
+                peripheral,
+                true,
+                simulate,
+                Vec::new(),
+                handoff::CliHandoff { resume, park_for },
+            ))
+            .await
+            .map(|_| ())
+        }
+
+        Commands::Code {
+            repo,
+            task,
+            base,
+            max_iterations,
+            max_cost_usd,
+            no_pr,
             provider,
             model,
             temperature,
-            peripheral,
-            true,
-        )
-        .await
-        .map(|_| ()),
+        } => {
+            Box::pin(code::run(
+                config,
+                code::CodeRun {
+                    repo,
+                    task,
+                    base,
+                    max_iterations,
+                    max_cost_usd,
+                    open_pr: !no_pr,
+                    provider,
+                    model,
+                    temperature,
+                },
+            ))
+            .await
+        }
+
+        Commands::Voice { mode } => {
+            let mode = mode.map(|mode| match mode {
+                VoiceModeArg::PushToTalk => config::VoiceMode::PushToTalk,
+                VoiceModeArg::WakeWord => config::VoiceMode::WakeWord,
+            });
+            Box::pin(voice::run(config, mode)).await
+        }
 
         Commands::Update {
             apply,
@@ -3107,7 +5536,7 @@ async fn main() -> Result<()> {
             } else {
                 info!("🚀 Starting ZeroClaw Gateway on {host}:{port}");
             }
-            gateway::run_gateway(&host, port, config).await
+            Box::pin(gateway::run_gateway(&host, port, config)).await
         }
 
         Commands::Daemon { port, host } => {
@@ -3118,7 +5547,7 @@ async fn main() -> Result<()> {
             } else {
                 info!("🧠 Starting ZeroClaw Daemon on {host}:{port}");
             }
-            daemon::run(config, host, port).await
+            Box::pin(daemon::run(config, host, port)).await
         }
 
         Commands::Status => {
@@ -3160,6 +5589,14 @@ async fn main() -> Result<()> {
                 effective_memory_backend,
                 if config.memory.auto_save { "on" } else { "off" }
             );
+            println!(
+                "📈 Telemetry:      {}",
+                telemetry::describe(&config.telemetry)
+            );
+            println!(
+                "🏛️  HA:            {}",
+                coordination::describe_ha(&config.coordination)
+            );
 
             println!();
             println!("Security:");
@@ -3211,6 +5648,27 @@ async fn main() -> Result<()> {
                 }
             );
             println!("  Boards:    {}", config.peripherals.boards.len());
+            println!();
+            println!(
+                "Workspace storage (gc: {}):",
+                if config.quotas.enabled {
+                    format!("every {}h", config.quotas.interval_hours.max(1))
+                } else {
+                    "disabled".into()
+                }
+            );
+            for usage in quotas::usage(&config) {
+                println!(
+                    "  {:12} {}{}",
+                    usage.category.name(),
+                    usage.summary(),
+                    if usage.over_quota() {
+                        "  ⚠️ over quota"
+                    } else {
+                        ""
+                    }
+                );
+            }
 
             Ok(())
         }
@@ -3228,6 +5686,7 @@ async fn main() -> Result<()> {
             ModelCommands::Refresh { provider, force } => {
                 onboard::run_models_refresh(&config, provider.as_deref(), force).await
             }
+            ModelCommands::Shadow { limit } => handle_models_shadow(&config, limit),
         },
 
         Commands::Preset { preset_command } => handle_preset_command(preset_command, &config).await,
@@ -3287,20 +5746,191 @@ async fn main() -> Result<()> {
                 event,
                 contains,
                 limit,
+                summarize,
             }) => doctor::run_traces(
                 &config,
                 id.as_deref(),
                 event.as_deref(),
                 contains.as_deref(),
                 limit,
+                summarize,
             ),
+            Some(DoctorCommands::Security { json }) => doctor::security::run(&config, json),
             None => doctor::run(&config),
         },
 
+        Commands::Observability {
+            observability_command:
+                ObservabilityCommands::Grafana {
+                    grafana_command:
+                        GrafanaCommands::Export {
+                            output_dir,
+                            channel_stale_minutes,
+                        },
+                },
+        } => {
+            let thresholds = observability::grafana::AlertThresholds::from_config(
+                &config,
+                channel_stale_minutes,
+            );
+            let (dashboard, rules) = observability::grafana::export(&output_dir, &thresholds)?;
+            println!("Grafana dashboard: {}", dashboard.display());
+            println!("Prometheus alert rules: {}", rules.display());
+            if config.observability.backend != "prometheus" {
+                println!(
+                    "Note: set [observability] backend = \"prometheus\" so the gateway exposes these metrics at /metrics."
+                );
+            }
+            Ok(())
+        }
+
+        Commands::Undo { undo_command } => {
+            let journal = undo::UndoJournal::for_workspace(&config.workspace_dir);
+            match undo_command {
+                UndoCommands::List { limit } => {
+                    let entries = journal.list()?;
+                    if entries.is_empty() {
+                        println!("No journaled file changes.");
+                        return Ok(());
+                    }
+                    for entry in entries.iter().rev().take(limit) {
+                        let status = if entry.reverted_at.is_some() {
+                            " [reverted]"
+                        } else if entry.existed {
+                            ""
+                        } else {
+                            " [created]"
+                        };
+                        println!(
+                            "{}  {}  {:<10} {}{status}",
+                            entry.id,
+                            entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                            entry.tool,
+                            entry.path.display()
+                        );
+                    }
+                    Ok(())
+                }
+                UndoCommands::Revert { change_id, force } => {
+                    let policy = security::SecurityPolicy::from_config(
+                        &config.autonomy,
+                        &config.workspace_dir,
+                    );
+                    let entry = journal.revert(&change_id, force, &policy)?;
+                    println!("Reverted change {}: {}", entry.id, entry.path.display());
+                    Ok(())
+                }
+            }
+        }
+
+        Commands::State { state_command } => {
+            let store = state::StateStore::for_config(&config);
+            match state_command {
+                StateCommands::Show { key } => {
+                    let entries = store.entries(key.as_deref().unwrap_or_default())?;
+                    if entries.is_empty() {
+                        println!("No recorded state.");
+                    }
+                    for (key, value) in entries {
+                        println!("{key} = {value}");
+                    }
+                    Ok(())
+                }
+                StateCommands::History { key, limit } => {
+                    let events = store.history(key.as_deref().unwrap_or_default(), limit)?;
+                    if events.is_empty() {
+                        println!("No recorded state changes.");
+                    }
+                    for event in events {
+                        let value = event
+                            .value
+                            .map_or_else(|| "(deleted)".to_string(), |value| value.to_string());
+                        println!(
+                            "#{:<5} {}  {:<28} {}\n       {value}",
+                            event.seq,
+                            event.at.format("%Y-%m-%d %H:%M:%S"),
+                            event.key,
+                            event.cause
+                        );
+                    }
+                    Ok(())
+                }
+            }
+        }
+
+        Commands::Batch { batch_command } => {
+            Box::pin(handle_batch_command(batch_command, config)).await
+        }
+
+        Commands::Tasks { tasks_command } => {
+            Box::pin(handle_tasks_command(tasks_command, config)).await
+        }
+
+        Commands::Reports { reports_command } => {
+            Box::pin(handle_reports_command(reports_command, config)).await
+        }
+
+        Commands::Share {
+            share_command,
+            target,
+            ttl,
+            title,
+            base_url,
+        } => handle_share_command(&config, share_command, target, &ttl, title, base_url),
+
+        Commands::Artifacts { artifacts_command } => {
+            handle_artifacts_command(&config, artifacts_command)
+        }
+
+        Commands::Prompts { prompts_command } => handle_prompts_command(&config, prompts_command),
+
+        Commands::Contacts { contacts_command } => {
+            handle_contacts_command(contacts_command, &config)
+        }
+
+        Commands::Identity { identity_command } => {
+            handle_identity_command(identity_command, &config)
+        }
+
+        Commands::Instructions {
+            instructions_command,
+        } => handle_instructions_command(instructions_command, &config),
+
+        Commands::Ctl { ctl_command } => handle_ctl_command(ctl_command, &config).await,
+
+        Commands::Questions { questions_command } => {
+            handle_questions_command(questions_command, &config)
+        }
+
+        Commands::Inbox { inbox_command } => handle_inbox_command(inbox_command, &config).await,
+
+        Commands::Rag { rag_command } => handle_rag_command(rag_command, &config).await,
+
+        Commands::Recordings { recordings_command } => {
+            handle_recordings_command(recordings_command, &config).await
+        }
+
+        Commands::Research { research_command } => {
+            handle_research_command(research_command, &config).await
+        }
+
+        Commands::Tour { step, yes } => tour::run(&config, step, yes).await,
+        Commands::Checklist => onboard::templates::print_checklist(&config).await,
+
+        Commands::Capabilities { json } => {
+            let report = capabilities::collect(&config)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print!("{}", report.render());
+            }
+            Ok(())
+        }
+
         Commands::Channel { channel_command } => match channel_command {
             ChannelCommands::Start => channels::start_channels(config).await,
-            ChannelCommands::Doctor => channels::doctor_channels(config).await,
-            other => channels::handle_command(other, &config).await,
+            ChannelCommands::Doctor => Box::pin(channels::doctor_channels(config)).await,
+            other => Box::pin(channels::handle_command(other, &config)).await,
         },
 
         Commands::Integrations {
@@ -3324,7 +5954,11 @@ async fn main() -> Result<()> {
         }
 
         Commands::Peripheral { peripheral_command } => {
-            peripherals::handle_command(peripheral_command.clone(), &config).await
+            Box::pin(peripherals::handle_command(
+                peripheral_command.clone(),
+                &config,
+            ))
+            .await
         }
 
         Commands::Config { config_command } => match config_command {
@@ -3336,10 +5970,56 @@ async fn main() -> Result<()> {
                 );
                 Ok(())
             }
+            ConfigCommands::Migrate { .. } => unreachable!("handled before config load"),
+        },
+
+        Commands::Telemetry { telemetry_command } => match telemetry_command {
+            TelemetryCommands::Preview => {
+                let report = telemetry::build_report(&config.workspace_dir, chrono::Utc::now())?;
+                eprintln!("Telemetry: {}", telemetry::describe(&config.telemetry));
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                Ok(())
+            }
         },
     }
 }
 
+async fn handle_config_migrate(dry_run: bool) -> Result<()> {
+    let path = config::resolve_config_path().await?;
+    if !path.exists() {
+        bail!(
+            "No config file at {}. Run `zeroclaw onboard` first.",
+            path.display()
+        );
+    }
+    let outcome = config::migrations::migrate_file(&path, dry_run).await?;
+    let report = &outcome.report;
+    if report.is_noop() {
+        println!(
+            "{} is already at config schema v{}.",
+            path.display(),
+            report.to
+        );
+        return Ok(());
+    }
+    println!(
+        "{}: config schema v{} -> v{}",
+        path.display(),
+        report.from,
+        report.to
+    );
+    for change in &report.changes {
+        println!("  {change}");
+    }
+    if dry_run {
+        println!("\nMigrated config (dry run, nothing written):\n");
+        print!("{}", outcome.migrated);
+    } else if let Some(backup) = &outcome.backup_path {
+        println!("\nOriginal saved to {}", backup.display());
+    }
+    Ok(())
+}
+
 fn handle_estop_command(
     config: &Config,
     estop_command: Option<EstopSubcommands>,
@@ -3552,11 +6232,16 @@ struct PendingOAuthLoginFile {
     created_at: String,
 }
 
+/// Pre-store location of a pending login, read as a fallback.
 fn pending_oauth_login_path(config: &Config, provider: &str) -> std::path::PathBuf {
     let filename = format!("auth-{}-pending.json", provider);
     auth::state_dir_from_config(config).join(filename)
 }
 
+fn pending_oauth_state_key(provider: &str) -> String {
+    format!("auth.pending.{provider}")
+}
+
 fn pending_oauth_secret_store(config: &Config) -> security::secrets::SecretStore {
     security::secrets::SecretStore::new(
         &auth::state_dir_from_config(config),
@@ -3564,23 +6249,7 @@ fn pending_oauth_secret_store(config: &Config) -> security::secrets::SecretStore
     )
 }
 
-#[cfg(unix)]
-fn set_owner_only_permissions(path: &std::path::Path) -> Result<()> {
-    use std::os::unix::fs::PermissionsExt;
-    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
-    Ok(())
-}
-
-#[cfg(not(unix))]
-fn set_owner_only_permissions(_path: &std::path::Path) -> Result<()> {
-    Ok(())
-}
-
 fn save_pending_oauth_login(config: &Config, pending: &PendingOAuthLogin) -> Result<()> {
-    let path = pending_oauth_login_path(config, &pending.provider);
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
     let secret_store = pending_oauth_secret_store(config);
     let encrypted_code_verifier = secret_store.encrypt(&pending.code_verifier)?;
     let persisted = PendingOAuthLoginFile {
@@ -3591,29 +6260,32 @@ fn save_pending_oauth_login(config: &Config, pending: &PendingOAuthLogin) -> Res
         state: pending.state.clone(),
         created_at: pending.created_at.clone(),
     };
-    let tmp = path.with_extension(format!(
-        "tmp.{}.{}",
-        std::process::id(),
-        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
-    ));
-    let json = serde_json::to_vec_pretty(&persisted)?;
-    std::fs::write(&tmp, json)?;
-    set_owner_only_permissions(&tmp)?;
-    std::fs::rename(tmp, &path)?;
-    set_owner_only_permissions(&path)?;
+    state::StateStore::for_config(config).set(
+        &pending_oauth_state_key(&pending.provider),
+        &persisted,
+        &format!("start {} login ({})", pending.provider, pending.profile),
+    )?;
+    remove_legacy_pending_oauth_login(config, &pending.provider);
     Ok(())
 }
 
 fn load_pending_oauth_login(config: &Config, provider: &str) -> Result<Option<PendingOAuthLogin>> {
-    let path = pending_oauth_login_path(config, provider);
-    if !path.exists() {
-        return Ok(None);
-    }
-    let bytes = std::fs::read(&path)?;
-    if bytes.is_empty() {
-        return Ok(None);
-    }
-    let persisted: PendingOAuthLoginFile = serde_json::from_slice(&bytes)?;
+    let stored = state::StateStore::for_config(config)
+        .get::<PendingOAuthLoginFile>(&pending_oauth_state_key(provider))?;
+    let persisted = match stored {
+        Some(persisted) => persisted,
+        None => {
+            let path = pending_oauth_login_path(config, provider);
+            if !path.exists() {
+                return Ok(None);
+            }
+            let bytes = std::fs::read(&path)?;
+            if bytes.is_empty() {
+                return Ok(None);
+            }
+            serde_json::from_slice(&bytes)?
+        }
+    };
     let secret_store = pending_oauth_secret_store(config);
     let code_verifier = if let Some(encrypted) = persisted.encrypted_code_verifier {
         secret_store.decrypt(&encrypted)?
@@ -3632,6 +6304,16 @@ fn load_pending_oauth_login(config: &Config, provider: &str) -> Result<Option<Pe
 }
 
 fn clear_pending_oauth_login(config: &Config, provider: &str) {
+    if let Err(e) = state::StateStore::for_config(config).delete(
+        &pending_oauth_state_key(provider),
+        &format!("finish {provider} login"),
+    ) {
+        tracing::warn!("Failed to clear pending {provider} login: {e:#}");
+    }
+    remove_legacy_pending_oauth_login(config, provider);
+}
+
+fn remove_legacy_pending_oauth_login(config: &Config, provider: &str) {
     let path = pending_oauth_login_path(config, provider);
     if let Ok(file) = std::fs::OpenOptions::new().write(true).open(&path) {
         let _ = file.set_len(0);
@@ -4787,6 +7469,315 @@ mod tests {
         }
     }
 
+    #[test]
+    fn code_cli_parses_repo_task_and_caps() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "code",
+            "--repo",
+            "/work/app",
+            "--task",
+            "Fix the parser",
+            "--max-iterations",
+            "5",
+            "--no-pr",
+        ])
+        .expect("code invocation should parse");
+        match cli.command {
+            Commands::Code {
+                repo,
+                task,
+                max_iterations,
+                no_pr,
+                ..
+            } => {
+                assert_eq!(repo, std::path::PathBuf::from("/work/app"));
+                assert_eq!(task, "Fix the parser");
+                assert_eq!(max_iterations, Some(5));
+                assert!(no_pr);
+            }
+            other => panic!("expected code command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn telemetry_preview_cli_parses() {
+        let cli = Cli::try_parse_from(["zeroclaw", "telemetry", "preview"])
+            .expect("telemetry preview invocation should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Telemetry {
+                telemetry_command: TelemetryCommands::Preview
+            }
+        ));
+    }
+
+    #[test]
+    fn remote_worker_cli_parses_root_and_request() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "remote-worker",
+            "--root",
+            "/srv/work",
+            "eyJ2IjoxfQ",
+        ])
+        .expect("remote-worker invocation should parse");
+        match cli.command {
+            Commands::RemoteWorker { root, request } => {
+                assert_eq!(root.as_deref(), Some(std::path::Path::new("/srv/work")));
+                assert_eq!(request.as_deref(), Some("eyJ2IjoxfQ"));
+            }
+            other => panic!("expected remote-worker command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn observability_grafana_export_cli_parses_output_dir() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "observability",
+            "grafana",
+            "export",
+            "--output-dir",
+            "/tmp/zc-grafana",
+            "--channel-stale-minutes",
+            "15",
+        ])
+        .expect("grafana export invocation should parse");
+        match cli.command {
+            Commands::Observability {
+                observability_command:
+                    ObservabilityCommands::Grafana {
+                        grafana_command:
+                            GrafanaCommands::Export {
+                                output_dir,
+                                channel_stale_minutes,
+                            },
+                    },
+            } => {
+                assert_eq!(output_dir, std::path::PathBuf::from("/tmp/zc-grafana"));
+                assert_eq!(channel_stale_minutes, 15);
+            }
+            other => panic!("expected observability command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn undo_revert_cli_parses_change_id_and_force() {
+        let cli = Cli::try_parse_from(["zeroclaw", "undo", "revert", "3f9a1c", "--force"])
+            .expect("undo revert invocation should parse");
+        match cli.command {
+            Commands::Undo {
+                undo_command: UndoCommands::Revert { change_id, force },
+            } => {
+                assert_eq!(change_id, "3f9a1c");
+                assert!(force);
+            }
+            other => panic!("expected undo command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn tasks_run_cli_collects_params() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "tasks",
+            "run",
+            "deploy-report",
+            "--param",
+            "env=prod",
+            "--param",
+            "days=3",
+        ])
+        .expect("tasks run invocation should parse");
+        match cli.command {
+            Commands::Tasks {
+                tasks_command: TasksCommands::Run { name, params, .. },
+            } => {
+                assert_eq!(name, "deploy-report");
+                assert_eq!(params, vec!["env=prod", "days=3"]);
+            }
+            other => panic!("expected tasks command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn contacts_add_cli_requires_an_address() {
+        assert!(Cli::try_parse_from(["zeroclaw", "contacts", "add", "Alice"]).is_err());
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "contacts",
+            "add",
+            "Alice",
+            "--address",
+            "telegram=42",
+            "--prefer",
+            "telegram",
+        ])
+        .expect("contacts add invocation should parse");
+        match cli.command {
+            Commands::Contacts {
+                contacts_command:
+                    ContactsCommands::Add {
+                        name,
+                        addresses,
+                        prefer,
+                        ..
+                    },
+            } => {
+                assert_eq!(name, "Alice");
+                assert_eq!(addresses, vec!["telegram=42"]);
+                assert_eq!(prefer.as_deref(), Some("telegram"));
+            }
+            other => panic!("expected contacts command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn questions_answer_cli_joins_trailing_words() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "questions",
+            "answer",
+            "q1a2b3",
+            "use",
+            "staging",
+        ])
+        .expect("questions answer invocation should parse");
+        match cli.command {
+            Commands::Questions {
+                questions_command: QuestionsCommands::Answer { id, answer },
+            } => {
+                assert_eq!(id, "q1a2b3");
+                assert_eq!(answer.join(" "), "use staging");
+            }
+            other => panic!("expected questions command, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["zeroclaw", "questions", "answer", "q1a2b3"]).is_err());
+    }
+
+    #[test]
+    fn ctl_cli_parses_channel_and_session_commands() {
+        let cli = Cli::try_parse_from(["zeroclaw", "ctl", "pause-channel", "telegram"])
+            .expect("ctl pause-channel invocation should parse");
+        match cli.command {
+            Commands::Ctl {
+                ctl_command: CtlCommands::PauseChannel { channel },
+            } => assert_eq!(channel, "telegram"),
+            other => panic!("expected ctl command, got {other:?}"),
+        }
+        let cli = Cli::try_parse_from(["zeroclaw", "ctl", "sessions", "list"])
+            .expect("ctl sessions list invocation should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Ctl {
+                ctl_command: CtlCommands::Sessions {
+                    sessions_command: CtlSessionsCommands::List
+                }
+            }
+        ));
+        let cli = Cli::try_parse_from(["zeroclaw", "ctl", "approve", "a1b2c3"])
+            .expect("ctl approve invocation should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Ctl {
+                ctl_command: CtlCommands::Approve { ref code }
+            } if code == "a1b2c3"
+        ));
+        // The decider comes from [control].operator, never from the client.
+        assert!(
+            Cli::try_parse_from(["zeroclaw", "ctl", "approve", "a1b2c3", "--as", "alice"]).is_err()
+        );
+    }
+
+    #[test]
+    fn inbox_rules_set_cli_parses_note() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "inbox",
+            "rules",
+            "set",
+            "@example.com",
+            "spam",
+            "--note",
+            "vendor blasts",
+        ])
+        .expect("inbox rules set invocation should parse");
+        match cli.command {
+            Commands::Inbox {
+                inbox_command:
+                    InboxCommands::Rules {
+                        rules_command:
+                            InboxRuleCommands::Set {
+                                sender,
+                                category,
+                                note,
+                            },
+                    },
+            } => {
+                assert_eq!(sender, "@example.com");
+                assert_eq!(category, "spam");
+                assert_eq!(note, "vendor blasts");
+            }
+            other => panic!("expected inbox rules command, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["zeroclaw", "inbox", "approve"]).is_err());
+    }
+
+    #[test]
+    fn agent_cli_parses_collection() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "agent",
+            "--collection",
+            "acme-handbook",
+            "-m",
+            "How many leave days?",
+        ])
+        .expect("agent --collection invocation should parse");
+        match cli.command {
+            Commands::Agent { collection, .. } => {
+                assert_eq!(collection.as_deref(), Some("acme-handbook"));
+            }
+            other => panic!("expected agent command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn batch_run_cli_requires_a_template() {
+        let missing = Cli::try_parse_from(["zeroclaw", "batch", "run", "--input", "files.txt"]);
+        assert!(missing.is_err());
+
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "batch",
+            "run",
+            "--input",
+            "files.txt",
+            "--template",
+            "Summarize {{file}}",
+            "--concurrency",
+            "8",
+        ])
+        .expect("batch run invocation should parse");
+        match cli.command {
+            Commands::Batch {
+                batch_command:
+                    BatchCommands::Run {
+                        concurrency,
+                        retries,
+                        out,
+                        ..
+                    },
+            } => {
+                assert_eq!(concurrency, 8);
+                assert_eq!(retries, 2);
+                assert_eq!(out, std::path::PathBuf::from("batch-results"));
+            }
+            other => panic!("expected batch command, got {other:?}"),
+        }
+    }
+
     #[test]
     fn completion_generation_mentions_binary_name() {
         let mut output = Vec::new();
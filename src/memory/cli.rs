@@ -180,6 +180,22 @@ async fn handle_stats(config: &Config) -> Result<()> {
     );
     println!("  Total:    {total}");
 
+    let index_path = super::pq_index::index_path(&config.workspace_dir.join("memory/brain.db"));
+    if mem.name() == "sqlite" && index_path.exists() {
+        match super::pq_index::PqIndex::open(&index_path) {
+            Ok(index) => println!(
+                "  Index:    pq, {} vectors × {} bytes ({} KB on disk)",
+                index.len(),
+                index.subvectors(),
+                index.file_size() / 1024
+            ),
+            Err(e) => println!(
+                "  Index:    {}",
+                style(format!("unreadable ({e})")).yellow()
+            ),
+        }
+    }
+
     let all = mem.list(None, None).await.unwrap_or_default();
     if !all.is_empty() {
         let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
@@ -1,4 +1,6 @@
 use crate::config::MemoryConfig;
+use crate::retention::{self, DataClass};
+use crate::state::{retire_legacy_file, StateStore};
 use anyhow::Result;
 use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
 use rusqlite::{params, Connection};
@@ -8,7 +10,10 @@ use std::path::{Path, PathBuf};
 use std::time::{Duration as StdDuration, SystemTime};
 
 const HYGIENE_INTERVAL_HOURS: i64 = 12;
-const STATE_FILE: &str = "memory_hygiene_state.json";
+/// Key of the hygiene cadence in the workspace's [`StateStore`].
+const STATE_KEY: &str = "memory_hygiene";
+/// State file used before the state store; imported once.
+const LEGACY_STATE_FILE: &str = "memory_hygiene_state.json";
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct HygieneReport {
@@ -54,10 +59,13 @@ pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
         )?,
         archived_session_files: archive_session_files(workspace_dir, config.archive_after_days)?,
         purged_memory_archives: purge_memory_archives(workspace_dir, config.purge_after_days)?,
-        purged_session_archives: purge_session_archives(workspace_dir, config.purge_after_days)?,
+        purged_session_archives: purge_session_archives(
+            workspace_dir,
+            retention::respect_hold(DataClass::Conversations, config.purge_after_days),
+        )?,
         pruned_conversation_rows: prune_conversation_rows(
             workspace_dir,
-            config.conversation_retention_days,
+            retention::respect_hold(DataClass::Conversations, config.conversation_retention_days),
         )?,
     };
 
@@ -78,15 +86,13 @@ pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
 }
 
 fn should_run_now(workspace_dir: &Path) -> Result<bool> {
-    let path = state_path(workspace_dir);
-    if !path.exists() {
-        return Ok(true);
-    }
-
-    let raw = fs::read_to_string(&path)?;
-    let state: HygieneState = match serde_json::from_str(&raw) {
-        Ok(s) => s,
-        Err(_) => return Ok(true),
+    let state = match load_state(workspace_dir) {
+        Ok(Some(state)) => state,
+        Ok(None) => return Ok(true),
+        Err(e) => {
+            tracing::warn!("memory hygiene state unreadable; running now: {e:#}");
+            return Ok(true);
+        }
     };
 
     let Some(last_run_at) = state.last_run_at else {
@@ -101,23 +107,42 @@ fn should_run_now(workspace_dir: &Path) -> Result<bool> {
     Ok(Utc::now().signed_duration_since(last) >= Duration::hours(HYGIENE_INTERVAL_HOURS))
 }
 
-fn write_state(workspace_dir: &Path, report: &HygieneReport) -> Result<()> {
-    let path = state_path(workspace_dir);
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
+/// Hygiene state from the store, importing a legacy state file once.
+fn load_state(workspace_dir: &Path) -> Result<Option<HygieneState>> {
+    let store = state_store(workspace_dir);
+    if let Some(state) = store.get::<HygieneState>(STATE_KEY)? {
+        return Ok(Some(state));
+    }
+    let legacy_path = state_dir(workspace_dir).join(LEGACY_STATE_FILE);
+    if !legacy_path.exists() {
+        return Ok(None);
     }
+    let state = fs::read_to_string(&legacy_path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<HygieneState>(&raw).ok());
+    if let Some(state) = &state {
+        store.set(STATE_KEY, state, "import legacy state file")?;
+    }
+    retire_legacy_file(&legacy_path);
+    Ok(state)
+}
 
+fn write_state(workspace_dir: &Path, report: &HygieneReport) -> Result<()> {
     let state = HygieneState {
         last_run_at: Some(Utc::now().to_rfc3339()),
         last_report: report.clone(),
     };
-    let json = serde_json::to_vec_pretty(&state)?;
-    fs::write(path, json)?;
-    Ok(())
+    state_store(workspace_dir).set(STATE_KEY, &state, "memory hygiene run")
+}
+
+fn state_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state")
 }
 
-fn state_path(workspace_dir: &Path) -> PathBuf {
-    workspace_dir.join("state").join(STATE_FILE)
+/// Hygiene state belongs to the workspace it cleans, so it is kept in a
+/// store under `<workspace>/state/` rather than next to `config.toml`.
+fn state_store(workspace_dir: &Path) -> StateStore {
+    StateStore::open(state_dir(workspace_dir))
 }
 
 fn archive_daily_memory_files(workspace_dir: &Path, archive_after_days: u32) -> Result<u64> {
@@ -471,6 +496,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn legacy_state_file_is_imported_once() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path();
+        let legacy = workspace.join("state").join(LEGACY_STATE_FILE);
+        fs::create_dir_all(legacy.parent().unwrap()).unwrap();
+        fs::write(
+            &legacy,
+            serde_json::to_vec(&HygieneState {
+                last_run_at: Some(Utc::now().to_rfc3339()),
+                last_report: HygieneReport::default(),
+            })
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert!(!should_run_now(workspace).unwrap());
+        assert!(!legacy.exists());
+        let stored = state_store(workspace)
+            .get::<HygieneState>(STATE_KEY)
+            .unwrap()
+            .unwrap();
+        assert!(stored.last_run_at.is_some());
+        assert!(!should_run_now(workspace).unwrap());
+    }
+
     #[test]
     fn purges_old_memory_archives() {
         let tmp = TempDir::new().unwrap();
@@ -8,7 +8,9 @@ pub mod markdown;
 pub mod none;
 #[cfg(feature = "memory-postgres")]
 pub mod postgres;
+pub mod pq_index;
 pub mod response_cache;
+pub mod scoped;
 pub mod snapshot;
 pub mod sqlite;
 pub mod traits;
@@ -86,6 +88,15 @@ pub fn is_assistant_autosave_key(key: &str) -> bool {
     normalized == "assistant_resp" || normalized.starts_with("assistant_resp_")
 }
 
+/// Key prefix for entries written by the `kv` tool (`kv:<namespace>:<key>`).
+pub const KV_KEY_PREFIX: &str = "kv:";
+
+/// Skill state written by the `kv` tool. It is program state, not knowledge,
+/// so it is kept out of recalled conversation context.
+pub fn is_kv_key(key: &str) -> bool {
+    key.trim_start().starts_with(KV_KEY_PREFIX)
+}
+
 #[derive(Clone, PartialEq, Eq)]
 struct ResolvedEmbeddingConfig {
     provider: String,
@@ -166,6 +177,45 @@ fn resolve_embedding_config(
     }
 }
 
+fn build_sqlite_memory(
+    config: &MemoryConfig,
+    workspace_dir: &Path,
+    resolved_embedding: &ResolvedEmbeddingConfig,
+) -> anyhow::Result<SqliteMemory> {
+    let embedder: Arc<dyn embeddings::EmbeddingProvider> =
+        Arc::from(embeddings::create_embedding_provider(
+            &resolved_embedding.provider,
+            resolved_embedding.api_key.as_deref(),
+            &resolved_embedding.model,
+            resolved_embedding.dimensions,
+        ));
+
+    #[allow(clippy::cast_possible_truncation)]
+    let mem = SqliteMemory::with_embedder(
+        workspace_dir,
+        embedder,
+        config.vector_weight as f32,
+        config.keyword_weight as f32,
+        config.embedding_cache_size,
+        config.sqlite_open_timeout_secs,
+    )?
+    .with_vector_index(&config.vector_index);
+    Ok(mem)
+}
+
+/// Factory: standalone SQLite store under `dir` with the embedding settings
+/// of `config`. RAG collections use one each, so collections with different
+/// embedding models never share a vector index.
+pub fn create_sqlite_memory_in(
+    config: &MemoryConfig,
+    embedding_routes: &[EmbeddingRouteConfig],
+    dir: &Path,
+    api_key: Option<&str>,
+) -> anyhow::Result<SqliteMemory> {
+    let resolved_embedding = resolve_embedding_config(config, embedding_routes, api_key);
+    build_sqlite_memory(config, dir, &resolved_embedding)
+}
+
 /// Factory: create the right memory backend from config
 pub fn create_memory(
     config: &MemoryConfig,
@@ -237,31 +287,6 @@ pub fn create_memory_with_storage_and_routes(
         }
     }
 
-    fn build_sqlite_memory(
-        config: &MemoryConfig,
-        workspace_dir: &Path,
-        resolved_embedding: &ResolvedEmbeddingConfig,
-    ) -> anyhow::Result<SqliteMemory> {
-        let embedder: Arc<dyn embeddings::EmbeddingProvider> =
-            Arc::from(embeddings::create_embedding_provider(
-                &resolved_embedding.provider,
-                resolved_embedding.api_key.as_deref(),
-                &resolved_embedding.model,
-                resolved_embedding.dimensions,
-            ));
-
-        #[allow(clippy::cast_possible_truncation)]
-        let mem = SqliteMemory::with_embedder(
-            workspace_dir,
-            embedder,
-            config.vector_weight as f32,
-            config.keyword_weight as f32,
-            config.embedding_cache_size,
-            config.sqlite_open_timeout_secs,
-        )?;
-        Ok(mem)
-    }
-
     #[cfg(feature = "memory-postgres")]
     fn build_postgres_memory(
         storage_provider: Option<&StorageProviderConfig>,
@@ -384,6 +409,13 @@ mod tests {
         assert!(!is_assistant_autosave_key("user_msg_1234"));
     }
 
+    #[test]
+    fn kv_keys_are_detected_by_prefix() {
+        assert!(is_kv_key("kv:rss-digest:cursor"));
+        assert!(!is_kv_key("kvstore_notes"));
+        assert!(!is_kv_key("user_msg_1234"));
+    }
+
     #[test]
     fn factory_markdown() {
         let tmp = TempDir::new().unwrap();
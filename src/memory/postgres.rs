@@ -81,7 +81,11 @@ impl PostgresMemory {
         init_result
     }
 
-    fn init_schema(client: &mut Client, schema_ident: &str, qualified_table: &str) -> Result<()> {
+    pub(crate) fn init_schema(
+        client: &mut Client,
+        schema_ident: &str,
+        qualified_table: &str,
+    ) -> Result<()> {
         client.batch_execute(&format!(
             "
             CREATE SCHEMA IF NOT EXISTS {schema_ident};
@@ -138,7 +142,7 @@ impl PostgresMemory {
     }
 }
 
-fn validate_identifier(value: &str, field_name: &str) -> Result<()> {
+pub(crate) fn validate_identifier(value: &str, field_name: &str) -> Result<()> {
     if value.is_empty() {
         anyhow::bail!("{field_name} must not be empty");
     }
@@ -161,7 +165,7 @@ fn validate_identifier(value: &str, field_name: &str) -> Result<()> {
     Ok(())
 }
 
-fn quote_identifier(value: &str) -> String {
+pub(crate) fn quote_identifier(value: &str) -> String {
     format!("\"{value}\"")
 }
 
@@ -0,0 +1,653 @@
+//! Product-quantized vector index for the sqlite backend
+//! (`[memory.vector_index] kind = "pq"`).
+//!
+//! Each embedding is normalized, split into `subvectors` slices, and every
+//! slice is replaced by the id of its nearest centroid (256 per slice, trained
+//! with k-means on a sample). A 1536-dim embedding then takes 32 bytes instead
+//! of 6 KB. The codes live in `brain.pq` next to `brain.db` and are
+//! memory-mapped, so a search reads them through the page cache instead of
+//! loading every vector from SQLite.
+//!
+//! A query scores all codes with per-slice lookup tables, then the best
+//! candidates are re-scored with their exact vectors. Rows written or changed
+//! after the index was built are scanned exactly until the next rebuild, which
+//! runs in the background once that tail grows.
+
+use crate::config::VectorIndexConfig;
+use anyhow::{bail, Context, Result};
+use parking_lot::RwLock;
+use rusqlite::{Connection, OpenFlags};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+const MAGIC: &[u8; 4] = b"ZCPQ";
+const FORMAT_VERSION: u32 = 1;
+const HEADER_LEN: usize = 40;
+const MAX_CENTROIDS: usize = 256;
+const KMEANS_ITERATIONS: usize = 8;
+
+/// Index file that belongs to the database at `db_path`.
+pub fn index_path(db_path: &Path) -> PathBuf {
+    db_path.with_extension("pq")
+}
+
+/// Read-only contents of an index file, memory-mapped on unix.
+enum FileBytes {
+    #[cfg(unix)]
+    Mapped { ptr: *mut libc::c_void, len: usize },
+    #[cfg_attr(unix, allow(dead_code))]
+    Owned(Vec<u8>),
+}
+
+// SAFETY: the mapping is private and read-only, and is only unmapped on drop.
+unsafe impl Send for FileBytes {}
+unsafe impl Sync for FileBytes {}
+
+impl FileBytes {
+    #[cfg(unix)]
+    fn open(path: &Path) -> Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let file = std::fs::File::open(path)?;
+        let len = usize::try_from(file.metadata()?.len())?;
+        if len == 0 {
+            bail!("index file is empty");
+        }
+        // SAFETY: we map `len` bytes of an open file read-only and private;
+        // the mapping stays valid after the descriptor is closed, and files
+        // are only ever replaced by rename, never truncated in place.
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        Ok(Self::Mapped { ptr, len })
+    }
+
+    #[cfg(not(unix))]
+    fn open(path: &Path) -> Result<Self> {
+        Ok(Self::Owned(std::fs::read(path)?))
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            #[cfg(unix)]
+            // SAFETY: `ptr` points at `len` readable bytes until drop.
+            Self::Mapped { ptr, len } => unsafe {
+                std::slice::from_raw_parts(ptr.cast::<u8>(), *len)
+            },
+            Self::Owned(bytes) => bytes,
+        }
+    }
+}
+
+impl Drop for FileBytes {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Self::Mapped { ptr, len } = self {
+            // SAFETY: unmaps exactly the region returned by `mmap`.
+            unsafe {
+                libc::munmap(*ptr, *len);
+            }
+        }
+    }
+}
+
+/// A loaded `brain.pq` file.
+///
+/// Layout (little-endian): 40-byte header, build watermark, codebooks
+/// (`f32`, `centroids × dims`), row ids (`i64` per vector), codes
+/// (`subvectors` bytes per vector).
+pub struct PqIndex {
+    bytes: FileBytes,
+    dims: usize,
+    subvectors: usize,
+    centroids: usize,
+    count: usize,
+    max_rowid: i64,
+    watermark: String,
+    codebooks: Vec<f32>,
+    rowids_at: usize,
+    codes_at: usize,
+}
+
+impl PqIndex {
+    pub fn open(path: &Path) -> Result<Self> {
+        let bytes = FileBytes::open(path)
+            .with_context(|| format!("Failed to open vector index {}", path.display()))?;
+        let data = bytes.as_slice();
+        if data.len() < HEADER_LEN || &data[..4] != MAGIC {
+            bail!("{} is not a vector index", path.display());
+        }
+        let version = read_u32(data, 4);
+        if version != FORMAT_VERSION {
+            bail!("unsupported vector index version {version}");
+        }
+        let dims = read_u32(data, 8) as usize;
+        let subvectors = read_u32(data, 12) as usize;
+        let centroids = read_u32(data, 16) as usize;
+        let watermark_len = read_u32(data, 20) as usize;
+        let count = usize::try_from(read_u64(data, 24))?;
+        let max_rowid = read_i64(data, 32);
+        if dims == 0 || subvectors == 0 || subvectors > dims || centroids > MAX_CENTROIDS {
+            bail!("corrupt vector index header");
+        }
+
+        let watermark_at = HEADER_LEN;
+        let codebooks_at = watermark_at + watermark_len;
+        let rowids_at = codebooks_at + centroids * dims * 4;
+        let sizes = count
+            .checked_mul(8 + subvectors)
+            .and_then(|rows| rows.checked_add(rowids_at));
+        if sizes != Some(data.len()) {
+            bail!("vector index {} is truncated", path.display());
+        }
+        let codes_at = rowids_at + count * 8;
+        let watermark = String::from_utf8(data[watermark_at..codebooks_at].to_vec())
+            .context("corrupt vector index watermark")?;
+        let codebooks = data[codebooks_at..rowids_at]
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+
+        Ok(Self {
+            bytes,
+            dims,
+            subvectors,
+            centroids,
+            count,
+            max_rowid,
+            watermark,
+            codebooks,
+            rowids_at,
+            codes_at,
+        })
+    }
+
+    /// Number of indexed vectors.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn dims(&self) -> usize {
+        self.dims
+    }
+
+    /// Bytes per vector code.
+    pub fn subvectors(&self) -> usize {
+        self.subvectors
+    }
+
+    /// Highest sqlite rowid covered by the index.
+    pub fn max_rowid(&self) -> i64 {
+        self.max_rowid
+    }
+
+    /// Latest `updated_at` covered by the index; later rows are not indexed.
+    pub fn watermark(&self) -> &str {
+        &self.watermark
+    }
+
+    /// Size of the index file in bytes.
+    pub fn file_size(&self) -> usize {
+        self.bytes.as_slice().len()
+    }
+
+    /// Rowids of the `k` codes closest to `query`, with their approximate
+    /// cosine similarity.
+    pub fn search(&self, query: &[f32], k: usize) -> Vec<(i64, f32)> {
+        if query.len() != self.dims || k == 0 || self.count == 0 {
+            return Vec::new();
+        }
+        let query = normalized(query);
+        let mut table = vec![0.0_f32; self.subvectors * self.centroids];
+        for sub in 0..self.subvectors {
+            let (start, end) = subspace(self.dims, self.subvectors, sub);
+            let query_part = &query[start..end];
+            for centroid in 0..self.centroids {
+                table[sub * self.centroids + centroid] =
+                    dot(query_part, self.centroid(sub, centroid));
+            }
+        }
+
+        let data = self.bytes.as_slice();
+        let codes = &data[self.codes_at..];
+        let mut best: Vec<(f32, usize)> = Vec::with_capacity(k * 2);
+        for (row, code) in codes.chunks_exact(self.subvectors).enumerate() {
+            let score: f32 = code
+                .iter()
+                .enumerate()
+                .map(|(sub, &c)| table[sub * self.centroids + usize::from(c)])
+                .sum();
+            best.push((score, row));
+            if best.len() >= k * 2 {
+                keep_best(&mut best, k);
+            }
+        }
+        keep_best(&mut best, k);
+        best.sort_by(|a, b| b.0.total_cmp(&a.0));
+        best.into_iter()
+            .map(|(score, row)| (read_i64(data, self.rowids_at + row * 8), score))
+            .collect()
+    }
+
+    fn centroid(&self, sub: usize, centroid: usize) -> &[f32] {
+        let (start, end) = subspace(self.dims, self.subvectors, sub);
+        let base = self.centroids * start + centroid * (end - start);
+        &self.codebooks[base..base + end - start]
+    }
+}
+
+fn keep_best(best: &mut Vec<(f32, usize)>, k: usize) {
+    if best.len() > k {
+        best.select_nth_unstable_by(k - 1, |a, b| b.0.total_cmp(&a.0));
+        best.truncate(k);
+    }
+}
+
+/// Outcome of an index build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildStats {
+    pub vectors: usize,
+    pub dims: usize,
+    pub bytes: usize,
+}
+
+/// Build `brain.pq` for the database at `db_path`.
+///
+/// Returns `None` (and removes any old index) when fewer than
+/// `min_vectors` embeddings are stored. Reads through its own read-only
+/// connection and streams rows, so only the training sample and the codes
+/// are held in memory.
+pub fn build(db_path: &Path, config: &VectorIndexConfig) -> Result<Option<BuildStats>> {
+    let path = index_path(db_path);
+    let conn = Connection::open_with_flags(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    let (total, max_rowid, watermark): (i64, Option<i64>, Option<String>) = conn.query_row(
+        "SELECT COUNT(*), MAX(rowid), MAX(updated_at) FROM memories WHERE embedding IS NOT NULL",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )?;
+    let (Some(max_rowid), Some(watermark)) = (max_rowid, watermark) else {
+        remove_index(&path)?;
+        return Ok(None);
+    };
+    if usize::try_from(total)? < config.min_vectors.max(1) {
+        remove_index(&path)?;
+        return Ok(None);
+    }
+
+    let dims: i64 = conn.query_row(
+        "SELECT length(embedding) / 4 FROM memories WHERE embedding IS NOT NULL
+         ORDER BY rowid DESC LIMIT 1",
+        [],
+        |row| row.get(0),
+    )?;
+    let dims = usize::try_from(dims)?;
+    if dims == 0 {
+        bail!("stored embeddings are empty");
+    }
+
+    let mut sample = Vec::new();
+    {
+        let mut stmt = conn.prepare(
+            "SELECT embedding FROM memories WHERE embedding IS NOT NULL
+             ORDER BY random() LIMIT ?1",
+        )?;
+        let limit = i64::try_from(config.train_sample.max(MAX_CENTROIDS))?;
+        let mut rows = stmt.query([limit])?;
+        while let Some(row) = rows.next()? {
+            let vector = super::vector::bytes_to_vec(&row.get::<_, Vec<u8>>(0)?);
+            if vector.len() == dims {
+                sample.push(normalized(&vector));
+            }
+        }
+    }
+    let subvectors = config.subvectors.clamp(1, dims);
+    let centroids = sample.len().min(MAX_CENTROIDS);
+    if centroids == 0 {
+        bail!("no embeddings to train on");
+    }
+    let mut codebooks = vec![0.0_f32; centroids * dims];
+    for sub in 0..subvectors {
+        let (start, end) = subspace(dims, subvectors, sub);
+        let trained = train_subspace(&sample, start, end, centroids);
+        let base = centroids * start;
+        codebooks[base..base + trained.len()].copy_from_slice(&trained);
+    }
+    drop(sample);
+
+    let mut rowids = Vec::new();
+    let mut codes = Vec::new();
+    {
+        let mut stmt = conn.prepare(
+            "SELECT rowid, embedding FROM memories
+             WHERE embedding IS NOT NULL AND rowid <= ?1 ORDER BY rowid",
+        )?;
+        let mut rows = stmt.query([max_rowid])?;
+        while let Some(row) = rows.next()? {
+            let vector = super::vector::bytes_to_vec(&row.get::<_, Vec<u8>>(1)?);
+            if vector.len() != dims {
+                continue;
+            }
+            let vector = normalized(&vector);
+            rowids.push(row.get::<_, i64>(0)?);
+            for sub in 0..subvectors {
+                let (start, end) = subspace(dims, subvectors, sub);
+                let width = end - start;
+                let book = &codebooks[centroids * start..centroids * end];
+                let nearest = nearest(&vector[start..end], book, width);
+                codes.push(u8::try_from(nearest)?);
+            }
+        }
+    }
+
+    let mut out = Vec::with_capacity(
+        HEADER_LEN + watermark.len() + codebooks.len() * 4 + rowids.len() * 8 + codes.len(),
+    );
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    for value in [dims, subvectors, centroids, watermark.len()] {
+        out.extend_from_slice(&u32::try_from(value)?.to_le_bytes());
+    }
+    out.extend_from_slice(&u64::try_from(rowids.len())?.to_le_bytes());
+    out.extend_from_slice(&max_rowid.to_le_bytes());
+    out.extend_from_slice(watermark.as_bytes());
+    for value in &codebooks {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    for rowid in &rowids {
+        out.extend_from_slice(&rowid.to_le_bytes());
+    }
+    out.extend_from_slice(&codes);
+
+    let tmp = path.with_extension("pq.tmp");
+    {
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(&out)?;
+        file.sync_all()?;
+    }
+    std::fs::rename(&tmp, &path)?;
+    Ok(Some(BuildStats {
+        vectors: rowids.len(),
+        dims,
+        bytes: out.len(),
+    }))
+}
+
+fn remove_index(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Lloyd's k-means on one slice of the (normalized) sample. Centroids start
+/// at evenly spaced sample points; the sample is already in random order.
+fn train_subspace(sample: &[Vec<f32>], start: usize, end: usize, centroids: usize) -> Vec<f32> {
+    let width = end - start;
+    let mut book = Vec::with_capacity(centroids * width);
+    for c in 0..centroids {
+        book.extend_from_slice(&sample[c * sample.len() / centroids][start..end]);
+    }
+    let mut sums = vec![0.0_f32; centroids * width];
+    let mut counts = vec![0_usize; centroids];
+    for _ in 0..KMEANS_ITERATIONS {
+        sums.fill(0.0);
+        counts.fill(0);
+        for vector in sample {
+            let part = &vector[start..end];
+            let c = nearest(part, &book, width);
+            counts[c] += 1;
+            for (sum, value) in sums[c * width..(c + 1) * width].iter_mut().zip(part) {
+                *sum += value;
+            }
+        }
+        for c in 0..centroids {
+            if counts[c] == 0 {
+                continue;
+            }
+            #[allow(clippy::cast_precision_loss)]
+            let n = counts[c] as f32;
+            for (value, sum) in book[c * width..(c + 1) * width]
+                .iter_mut()
+                .zip(&sums[c * width..(c + 1) * width])
+            {
+                *value = sum / n;
+            }
+        }
+    }
+    book
+}
+
+fn nearest(part: &[f32], book: &[f32], width: usize) -> usize {
+    book.chunks_exact(width)
+        .map(|centroid| {
+            part.iter()
+                .zip(centroid)
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum::<f32>()
+        })
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(0, |(index, _)| index)
+}
+
+/// Dimension range `[start, end)` of subvector `sub`.
+fn subspace(dims: usize, subvectors: usize, sub: usize) -> (usize, usize) {
+    (sub * dims / subvectors, (sub + 1) * dims / subvectors)
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn normalized(vector: &[f32]) -> Vec<f32> {
+    let norm = dot(vector, vector).sqrt();
+    if norm > f32::EPSILON {
+        vector.iter().map(|value| value / norm).collect()
+    } else {
+        vector.to_vec()
+    }
+}
+
+fn read_u32(data: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]])
+}
+
+fn read_u64(data: &[u8], at: usize) -> u64 {
+    let mut bytes = [0_u8; 8];
+    bytes.copy_from_slice(&data[at..at + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn read_i64(data: &[u8], at: usize) -> i64 {
+    let mut bytes = [0_u8; 8];
+    bytes.copy_from_slice(&data[at..at + 8]);
+    i64::from_le_bytes(bytes)
+}
+
+/// The index of one sqlite store: the loaded file plus rebuild bookkeeping.
+pub struct IndexHandle {
+    config: VectorIndexConfig,
+    db_path: PathBuf,
+    current: RwLock<Option<Arc<PqIndex>>>,
+    building: AtomicBool,
+}
+
+impl IndexHandle {
+    /// Load the index next to `db_path` if one was built before.
+    pub fn open(db_path: &Path, config: VectorIndexConfig) -> Self {
+        let path = index_path(db_path);
+        let current = if path.exists() {
+            PqIndex::open(&path)
+                .map_err(|e| tracing::warn!("Ignoring vector index: {e:#}"))
+                .ok()
+                .map(Arc::new)
+        } else {
+            None
+        };
+        Self {
+            config,
+            db_path: db_path.to_path_buf(),
+            current: RwLock::new(current),
+            building: AtomicBool::new(false),
+        }
+    }
+
+    pub fn config(&self) -> &VectorIndexConfig {
+        &self.config
+    }
+
+    pub fn current(&self) -> Option<Arc<PqIndex>> {
+        self.current.read().clone()
+    }
+
+    /// Rebuild the index now and swap it in.
+    pub fn rebuild(&self) -> Result<Option<BuildStats>> {
+        let stats = build(&self.db_path, &self.config)?;
+        let loaded = match stats {
+            Some(_) => Some(Arc::new(PqIndex::open(&index_path(&self.db_path))?)),
+            None => None,
+        };
+        *self.current.write() = loaded;
+        Ok(stats)
+    }
+
+    /// Start a background rebuild when `unindexed` embeddings (rows newer
+    /// than the index, or all rows when there is none) make it worthwhile.
+    pub fn rebuild_if_due(self: &Arc<Self>, unindexed: usize) {
+        let due = match self.current() {
+            Some(index) => unindexed * 10 >= index.len().max(self.config.min_vectors),
+            None => unindexed >= self.config.min_vectors.max(1),
+        };
+        if !due || self.building.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let handle = Arc::clone(self);
+        std::thread::spawn(move || {
+            match handle.rebuild() {
+                Ok(Some(stats)) => tracing::info!(
+                    vectors = stats.vectors,
+                    bytes = stats.bytes,
+                    "Rebuilt vector index"
+                ),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Vector index rebuild failed: {e:#}"),
+            }
+            handle.building.store(false, Ordering::SeqCst);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::VectorIndexKind;
+    use tempfile::TempDir;
+
+    fn seeded_db(dir: &TempDir, rows: usize, dims: usize) -> PathBuf {
+        let db_path = dir.path().join("brain.db");
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE memories (key TEXT, embedding BLOB, updated_at TEXT NOT NULL);",
+        )
+        .unwrap();
+        for row in 0..rows {
+            // Deterministic, well spread vectors: a few sine waves per row.
+            #[allow(clippy::cast_precision_loss)]
+            let vector: Vec<f32> = (0..dims)
+                .map(|d| ((row * 7 + d * 13) as f32 * 0.37).sin() + (row % 5) as f32 * 0.1)
+                .collect();
+            conn.execute(
+                "INSERT INTO memories (key, embedding, updated_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![
+                    format!("k{row}"),
+                    super::super::vector::vec_to_bytes(&vector),
+                    "2026-01-01T00:00:00+00:00"
+                ],
+            )
+            .unwrap();
+        }
+        db_path
+    }
+
+    fn config(min_vectors: usize) -> VectorIndexConfig {
+        VectorIndexConfig {
+            kind: VectorIndexKind::Pq,
+            subvectors: 8,
+            min_vectors,
+            ..VectorIndexConfig::default()
+        }
+    }
+
+    #[test]
+    fn build_writes_compact_index_that_finds_stored_vectors() {
+        let dir = TempDir::new().unwrap();
+        let db_path = seeded_db(&dir, 400, 32);
+        let stats = build(&db_path, &config(10)).unwrap().unwrap();
+        assert_eq!(stats.vectors, 400);
+        assert_eq!(stats.dims, 32);
+
+        let index = PqIndex::open(&index_path(&db_path)).unwrap();
+        assert_eq!(index.len(), 400);
+        assert_eq!(index.subvectors(), 8);
+        assert_eq!(index.max_rowid(), 400);
+        assert_eq!(index.watermark(), "2026-01-01T00:00:00+00:00");
+
+        // Querying with a stored vector ranks that row among the top hits.
+        let conn = Connection::open(&db_path).unwrap();
+        let blob: Vec<u8> = conn
+            .query_row(
+                "SELECT embedding FROM memories WHERE rowid = 123",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        let hits = index.search(&super::super::vector::bytes_to_vec(&blob), 10);
+        assert_eq!(hits.len(), 10);
+        assert!(hits.iter().any(|(rowid, _)| *rowid == 123));
+        assert!(hits.windows(2).all(|w| w[0].1 >= w[1].1));
+    }
+
+    #[test]
+    fn build_skips_small_stores_and_removes_stale_index() {
+        let dir = TempDir::new().unwrap();
+        let db_path = seeded_db(&dir, 50, 16);
+        assert!(build(&db_path, &config(10)).unwrap().is_some());
+        assert!(index_path(&db_path).exists());
+
+        assert!(build(&db_path, &config(100)).unwrap().is_none());
+        assert!(!index_path(&db_path).exists());
+    }
+
+    #[test]
+    fn open_rejects_foreign_and_truncated_files() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("other.pq");
+        std::fs::write(&path, b"not an index at all, just some bytes here").unwrap();
+        assert!(PqIndex::open(&path).is_err());
+
+        let db_path = seeded_db(&dir, 300, 16);
+        build(&db_path, &config(10)).unwrap();
+        let bytes = std::fs::read(index_path(&db_path)).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        assert!(PqIndex::open(&path).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! Memory confined to one session, for agent runs that must not see the
+//! owner's conversation memory (cron jobs with `memory = "isolated"`).
+//!
+//! Keys are stored under `<session>:` so scoped writes never overwrite
+//! shared entries, and reads return only entries tagged with the session.
+//! Backends that ignore sessions (markdown) therefore look empty.
+
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::NoneMemory;
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+
+/// Memory visible to the agent runs of the current task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskMemory {
+    /// Only entries written under this session.
+    Session(String),
+    /// No memory at all.
+    Disabled,
+}
+
+tokio::task_local! {
+    static TASK_MEMORY: TaskMemory;
+}
+
+/// Run `future` with the memory of agent runs it starts narrowed to `scope`.
+pub async fn with_task_memory<F: Future>(scope: Option<TaskMemory>, future: F) -> F::Output {
+    match scope {
+        Some(scope) => TASK_MEMORY.scope(scope, future).await,
+        None => future.await,
+    }
+}
+
+/// Narrow `memory` to the scope set by [`with_task_memory`], if any.
+pub fn for_current_task(memory: Arc<dyn Memory>) -> Arc<dyn Memory> {
+    match TASK_MEMORY.try_with(Clone::clone) {
+        Ok(TaskMemory::Session(session)) => Arc::new(ScopedMemory::new(memory, session)),
+        Ok(TaskMemory::Disabled) => Arc::new(NoneMemory::new()),
+        Err(_) => memory,
+    }
+}
+
+/// Whether the current task may see the owner's shared memory.
+pub fn shared_memory_visible() -> bool {
+    TASK_MEMORY.try_with(|_| ()).is_err()
+}
+
+/// A [`Memory`] that reads and writes only one session.
+pub struct ScopedMemory {
+    inner: Arc<dyn Memory>,
+    session: String,
+    prefix: String,
+}
+
+impl ScopedMemory {
+    pub fn new(inner: Arc<dyn Memory>, session: impl Into<String>) -> Self {
+        let session = session.into();
+        Self {
+            inner,
+            prefix: format!("{session}:"),
+            session,
+        }
+    }
+
+    fn owns(&self, entry: &MemoryEntry) -> bool {
+        entry.session_id.as_deref() == Some(self.session.as_str())
+            && entry.key.starts_with(&self.prefix)
+    }
+
+    fn unscoped(&self, mut entry: MemoryEntry) -> MemoryEntry {
+        entry.key = entry.key[self.prefix.len()..].to_string();
+        entry
+    }
+
+    fn only_owned(&self, entries: Vec<MemoryEntry>) -> Vec<MemoryEntry> {
+        entries
+            .into_iter()
+            .filter(|entry| self.owns(entry))
+            .map(|entry| self.unscoped(entry))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Memory for ScopedMemory {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.inner
+            .store(
+                &format!("{}{key}", self.prefix),
+                content,
+                category,
+                Some(&self.session),
+            )
+            .await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let entries = self.inner.recall(query, limit, Some(&self.session)).await?;
+        Ok(self.only_owned(entries))
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        let entry = self.inner.get(&format!("{}{key}", self.prefix)).await?;
+        Ok(entry
+            .filter(|entry| self.owns(entry))
+            .map(|entry| self.unscoped(entry)))
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let entries = self.inner.list(category, Some(&self.session)).await?;
+        Ok(self.only_owned(entries))
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        if self.get(key).await?.is_none() {
+            return Ok(false);
+        }
+        self.inner.forget(&format!("{}{key}", self.prefix)).await
+    }
+
+    async fn count(&self) -> anyhow::Result<usize> {
+        Ok(self.list(None, None).await?.len())
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn scoped_memory_hides_shared_entries_and_never_overwrites_them() {
+        let tmp = TempDir::new().unwrap();
+        let shared: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        shared
+            .store("diet", "owner is vegetarian", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let scoped = ScopedMemory::new(Arc::clone(&shared), "cron:nightly");
+        assert!(scoped.get("diet").await.unwrap().is_none());
+        assert!(scoped
+            .recall("vegetarian", 10, None)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(!scoped.forget("diet").await.unwrap());
+
+        scoped
+            .store("diet", "disk cleanup ran", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let own = scoped.get("diet").await.unwrap().unwrap();
+        assert_eq!(own.content, "disk cleanup ran");
+        assert_eq!(scoped.count().await.unwrap(), 1);
+        assert_eq!(
+            shared.get("diet").await.unwrap().unwrap().content,
+            "owner is vegetarian"
+        );
+    }
+
+    #[tokio::test]
+    async fn task_scope_selects_the_wrapper() {
+        let tmp = TempDir::new().unwrap();
+        let shared: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        assert_eq!(for_current_task(Arc::clone(&shared)).name(), "sqlite");
+        assert!(shared_memory_visible());
+
+        let (disabled, visible) = with_task_memory(Some(TaskMemory::Disabled), async {
+            (
+                for_current_task(Arc::clone(&shared)),
+                shared_memory_visible(),
+            )
+        })
+        .await;
+        assert_eq!(disabled.name(), "none");
+        assert!(!visible);
+
+        let scoped = with_task_memory(Some(TaskMemory::Session("cron:a".into())), async {
+            for_current_task(Arc::clone(&shared))
+        })
+        .await;
+        scoped
+            .store("k", "v", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+        assert!(shared.get("cron:a:k").await.unwrap().is_some());
+    }
+}
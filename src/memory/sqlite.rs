@@ -1,6 +1,8 @@
 use super::embeddings::EmbeddingProvider;
+use super::pq_index::IndexHandle;
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use super::vector;
+use crate::config::{VectorIndexConfig, VectorIndexKind};
 use anyhow::Context;
 use async_trait::async_trait;
 use chrono::Local;
@@ -21,6 +23,7 @@ const SQLITE_OPEN_TIMEOUT_CAP_SECS: u64 = 300;
 ///
 /// Full-stack search engine:
 /// - **Vector DB**: embeddings stored as BLOB, cosine similarity search
+///   (optionally through a product-quantized index, see [`super::pq_index`])
 /// - **Keyword Search**: FTS5 virtual table with BM25 scoring
 /// - **Hybrid Merge**: weighted fusion of vector + keyword results
 /// - **Embedding Cache**: LRU-evicted cache to avoid redundant API calls
@@ -32,6 +35,7 @@ pub struct SqliteMemory {
     vector_weight: f32,
     keyword_weight: f32,
     cache_max: usize,
+    vector_index: Option<Arc<IndexHandle>>,
 }
 
 impl SqliteMemory {
@@ -90,9 +94,18 @@ impl SqliteMemory {
             vector_weight,
             keyword_weight,
             cache_max,
+            vector_index: None,
         })
     }
 
+    /// Search embeddings through the index configured in `[memory.vector_index]`.
+    #[must_use]
+    pub fn with_vector_index(mut self, config: &VectorIndexConfig) -> Self {
+        self.vector_index = (config.kind == VectorIndexKind::Pq)
+            .then(|| Arc::new(IndexHandle::open(&self.db_path, config.clone())));
+        self
+    }
+
     /// Open SQLite connection, optionally with a timeout (for locked/slow storage).
     fn open_connection(
         db_path: &Path,
@@ -139,6 +152,7 @@ impl SqliteMemory {
             );
             CREATE INDEX IF NOT EXISTS idx_memories_category ON memories(category);
             CREATE INDEX IF NOT EXISTS idx_memories_key ON memories(key);
+            CREATE INDEX IF NOT EXISTS idx_memories_updated ON memories(updated_at);
 
             -- FTS5 full-text search (BM25 scoring)
             CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
@@ -333,11 +347,32 @@ impl SqliteMemory {
     /// when the caller already knows the scope of relevant memories.
     fn vector_search(
         conn: &Connection,
+        index: Option<&Arc<IndexHandle>>,
         query_embedding: &[f32],
         limit: usize,
         category: Option<&str>,
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<(String, f32)>> {
+        if let Some(handle) = index {
+            if let Some(pq) = handle.current() {
+                return Self::indexed_vector_search(
+                    conn,
+                    handle,
+                    &pq,
+                    query_embedding,
+                    limit,
+                    category,
+                    session_id,
+                );
+            }
+            let embedded: i64 = conn.query_row(
+                "SELECT COUNT(*) FROM memories WHERE embedding IS NOT NULL",
+                [],
+                |row| row.get(0),
+            )?;
+            handle.rebuild_if_due(usize::try_from(embedded).unwrap_or(0));
+        }
+
         let mut sql = "SELECT id, embedding FROM memories WHERE embedding IS NOT NULL".to_string();
         let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
         let mut idx = 1;
@@ -376,6 +411,125 @@ impl SqliteMemory {
         Ok(scored)
     }
 
+    /// Vector search through a product-quantized index.
+    ///
+    /// The codes pick `limit × rerank` candidates, which are re-scored with
+    /// their stored embeddings (or ranked by the codes alone when `rerank` is
+    /// 0). Rows added or changed since the index was built are scored exactly.
+    fn indexed_vector_search(
+        conn: &Connection,
+        handle: &Arc<IndexHandle>,
+        pq: &super::pq_index::PqIndex,
+        query_embedding: &[f32],
+        limit: usize,
+        category: Option<&str>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<(String, f32)>> {
+        /// Stay well below SQLite's bound-parameter limit.
+        const MAX_IN_PARAMS: usize = 500;
+
+        let rerank = handle.config().rerank;
+        let mut wanted = limit * rerank.max(1);
+        if category.is_some() || session_id.is_some() {
+            // Filters are applied after the code scan, so look further.
+            wanted *= 4;
+        }
+        let candidates = pq.search(query_embedding, wanted);
+
+        let mut filters = String::new();
+        let mut filter_values: Vec<String> = Vec::new();
+        if let Some(cat) = category {
+            filters.push_str(" AND category = ?");
+            filter_values.push(cat.to_string());
+        }
+        if let Some(sid) = session_id {
+            filters.push_str(" AND session_id = ?");
+            filter_values.push(sid.to_string());
+        }
+
+        let mut scored: Vec<(String, f32)> = Vec::new();
+        let max_rowid = pq.max_rowid();
+        let watermark = pq.watermark();
+
+        // Exact scan of the unindexed tail.
+        let tail_sql = format!(
+            "SELECT id, embedding FROM memories
+             WHERE embedding IS NOT NULL AND (rowid > ? OR updated_at > ?){filters}"
+        );
+        let mut stmt = conn.prepare(&tail_sql)?;
+        let mut tail_params: Vec<&dyn rusqlite::types::ToSql> = vec![&max_rowid, &watermark];
+        tail_params.extend(
+            filter_values
+                .iter()
+                .map(|v| v as &dyn rusqlite::types::ToSql),
+        );
+        let rows = stmt.query_map(tail_params.as_slice(), |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?;
+        for row in rows {
+            let (id, blob) = row?;
+            let sim = vector::cosine_similarity(query_embedding, &vector::bytes_to_vec(&blob));
+            if sim > 0.0 {
+                scored.push((id, sim));
+            }
+        }
+
+        // Candidates from the codes, skipping rows the tail scan covered.
+        let approx: std::collections::HashMap<i64, f32> = candidates.iter().copied().collect();
+        let column = if rerank == 0 { "NULL" } else { "embedding" };
+        for chunk in candidates.chunks(MAX_IN_PARAMS) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let sql = format!(
+                "SELECT rowid, id, {column} FROM memories
+                 WHERE rowid IN ({placeholders}) AND embedding IS NOT NULL
+                   AND rowid <= ? AND updated_at <= ?{filters}"
+            );
+            let mut stmt = conn.prepare(&sql)?;
+            let mut params: Vec<&dyn rusqlite::types::ToSql> = chunk
+                .iter()
+                .map(|(rowid, _)| rowid as &dyn rusqlite::types::ToSql)
+                .collect();
+            params.push(&max_rowid);
+            params.push(&watermark);
+            params.extend(
+                filter_values
+                    .iter()
+                    .map(|v| v as &dyn rusqlite::types::ToSql),
+            );
+            let rows = stmt.query_map(params.as_slice(), |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<Vec<u8>>>(2)?,
+                ))
+            })?;
+            for row in rows {
+                let (rowid, id, blob) = row?;
+                let sim = match blob {
+                    Some(blob) => {
+                        vector::cosine_similarity(query_embedding, &vector::bytes_to_vec(&blob))
+                    }
+                    None => approx.get(&rowid).copied().unwrap_or(0.0).clamp(0.0, 1.0),
+                };
+                if sim > 0.0 {
+                    scored.push((id, sim));
+                }
+            }
+        }
+
+        let unindexed: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM memories
+             WHERE embedding IS NOT NULL AND (rowid > ?1 OR updated_at > ?2)",
+            params![max_rowid, watermark],
+            |row| row.get(0),
+        )?;
+        handle.rebuild_if_due(usize::try_from(unindexed).unwrap_or(0));
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(limit);
+        Ok(scored)
+    }
+
     /// Safe reindex: rebuild FTS5 + embeddings with rollback on failure
     #[allow(dead_code)]
     pub async fn reindex(&self) -> anyhow::Result<usize> {
@@ -426,6 +580,11 @@ impl SqliteMemory {
             }
         }
 
+        // Step 3: Rebuild the vector index over the refreshed embeddings
+        if let Some(handle) = self.vector_index.clone() {
+            tokio::task::spawn_blocking(move || handle.rebuild()).await??;
+        }
+
         Ok(count)
     }
 }
@@ -494,6 +653,7 @@ impl Memory for SqliteMemory {
         let sid = session_id.map(String::from);
         let vector_weight = self.vector_weight;
         let keyword_weight = self.keyword_weight;
+        let index = self.vector_index.clone();
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<MemoryEntry>> {
             let conn = conn.lock();
@@ -504,7 +664,8 @@ impl Memory for SqliteMemory {
 
             // Vector similarity search (if embeddings available)
             let vector_results = if let Some(ref qe) = query_embedding {
-                Self::vector_search(&conn, qe, limit * 2, None, session_ref).unwrap_or_default()
+                Self::vector_search(&conn, index.as_ref(), qe, limit * 2, None, session_ref)
+                    .unwrap_or_default()
             } else {
                 Vec::new()
             };
@@ -1897,4 +2058,77 @@ mod tests {
 
         assert_eq!(mem.count().await.unwrap(), 1);
     }
+
+    /// Deterministic pseudo-random embeddings: equal texts map to equal vectors.
+    struct HashEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for HashEmbedding {
+        fn name(&self) -> &str {
+            "hash"
+        }
+
+        fn dimensions(&self) -> usize {
+            32
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            use std::hash::{Hash, Hasher};
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    (0..32)
+                        .map(|i| {
+                            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                            (text, i).hash(&mut hasher);
+                            #[allow(clippy::cast_precision_loss)]
+                            let unit = (hasher.finish() % 2001) as f32 / 1000.0 - 1.0;
+                            unit
+                        })
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn pq_index_serves_indexed_and_newer_rows() {
+        let tmp = TempDir::new().unwrap();
+        let index_config = VectorIndexConfig {
+            kind: VectorIndexKind::Pq,
+            subvectors: 8,
+            min_vectors: 50,
+            ..VectorIndexConfig::default()
+        };
+        let mem =
+            SqliteMemory::with_embedder(tmp.path(), Arc::new(HashEmbedding), 0.7, 0.3, 1000, None)
+                .unwrap()
+                .with_vector_index(&index_config);
+        for i in 0..300 {
+            mem.store(
+                &format!("k{i}"),
+                &format!("note {i}"),
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        mem.reindex().await.unwrap();
+        let handle = mem.vector_index.clone().unwrap();
+        assert_eq!(handle.current().unwrap().len(), 300);
+
+        mem.store("fresh", "added after the build", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        for (key, content) in [("k42", "note 42"), ("fresh", "added after the build")] {
+            let query = HashEmbedding.embed_one(content).await.unwrap();
+            let expected = mem.get(key).await.unwrap().unwrap().id;
+            let conn = mem.conn.lock();
+            let hits =
+                SqliteMemory::vector_search(&conn, Some(&handle), &query, 3, None, None).unwrap();
+            assert_eq!(hits[0].0, expected);
+            assert!((hits[0].1 - 1.0).abs() < 1e-4, "reranked score is exact");
+        }
+    }
 }
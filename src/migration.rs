@@ -7,6 +7,8 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+mod storage;
+
 #[derive(Debug, Clone)]
 struct SourceEntry {
     key: String,
@@ -28,6 +30,17 @@ pub async fn handle_command(command: crate::MigrateCommands, config: &Config) ->
         crate::MigrateCommands::Openclaw { source, dry_run } => {
             migrate_openclaw_memory(config, source, dry_run).await
         }
+        crate::MigrateCommands::Storage {
+            to,
+            dsn,
+            schema,
+            table,
+            dry_run,
+            cutover,
+        } => {
+            let target = storage::StorageTarget { dsn, schema, table };
+            Box::pin(storage::migrate(config, &to, target, dry_run, cutover)).await
+        }
     }
 }
 
@@ -0,0 +1,648 @@
+//! Online SQLite → PostgreSQL storage migration (`zeroclaw migrate storage`).
+//!
+//! Each run streams memory entries (`memory/brain.db`), runtime trace events
+//! and the cost ledger (`state/costs.jsonl`) into PostgreSQL in batches, then
+//! compares per-dataset checksums of source and target. Runs are idempotent
+//! syncs, so the daemon keeps running and the command can be repeated:
+//!
+//! - memory rows are upserted by key and rows no longer in the source are
+//!   removed, so the target table mirrors `brain.db`;
+//! - trace events and cost records are append-only and keyed by id, so
+//!   events trimmed from a rolling trace file stay in PostgreSQL.
+//!
+//! `--cutover` repeats the sync until the checksums match and then points
+//! `[storage.provider.config]` at PostgreSQL; memory is served from there once
+//! the daemon restarts. Traces and costs are still written locally and can be
+//! synced again later.
+
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, SecondsFormat, SubsecRound, Utc};
+use rusqlite::{Connection, OpenFlags};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+/// Rows written per transaction.
+const BATCH_SIZE: usize = 500;
+/// Syncs attempted by `--cutover` before giving up on a moving source.
+const CUTOVER_PASSES: usize = 3;
+#[cfg(feature = "memory-postgres")]
+const TRACE_TABLE: &str = "zeroclaw_trace_events";
+#[cfg(feature = "memory-postgres")]
+const COST_TABLE: &str = "zeroclaw_cost_records";
+
+/// Where `zeroclaw migrate storage` copies to.
+#[derive(Debug, Clone)]
+pub struct StorageTarget {
+    pub dsn: String,
+    pub schema: String,
+    pub table: String,
+}
+
+/// Row count and SHA-256 over the rows of one dataset, sorted by id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Checksum {
+    rows: usize,
+    sha256: String,
+}
+
+impl Checksum {
+    fn of(digests: &BTreeMap<String, [u8; 32]>) -> Self {
+        let mut hasher = Sha256::new();
+        for (id, digest) in digests {
+            hasher.update(id.as_bytes());
+            hasher.update([0]);
+            hasher.update(digest);
+        }
+        Self {
+            rows: digests.len(),
+            sha256: hex::encode(hasher.finalize()),
+        }
+    }
+
+    fn short(&self) -> &str {
+        &self.sha256[..12]
+    }
+}
+
+/// Outcome of syncing one dataset.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "memory-postgres"), allow(dead_code))]
+struct DatasetReport {
+    name: &'static str,
+    copied: usize,
+    source: Checksum,
+    target: Checksum,
+}
+
+impl DatasetReport {
+    fn verified(&self) -> bool {
+        self.target == self.source
+    }
+}
+
+fn row_digest(fields: &[&str]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for field in fields {
+        hasher.update(field.as_bytes());
+        hasher.update([0]);
+    }
+    hasher.finalize().into()
+}
+
+/// One `memories` row. Timestamps are kept at the microsecond precision
+/// PostgreSQL stores, so both sides hash alike.
+#[derive(Debug, Clone)]
+struct MemoryRow {
+    id: String,
+    key: String,
+    content: String,
+    category: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    session_id: Option<String>,
+}
+
+impl MemoryRow {
+    fn digest(&self) -> [u8; 32] {
+        row_digest(&[
+            &self.key,
+            &self.content,
+            &self.category,
+            &self.created_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+            &self.updated_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+            self.session_id.as_deref().unwrap_or(""),
+        ])
+    }
+}
+
+fn parse_timestamp(raw: &str, id: &str) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(raw)
+        .with_context(|| format!("Memory entry {id} has an invalid timestamp '{raw}'"))?
+        .with_timezone(&Utc)
+        .trunc_subsecs(6))
+}
+
+/// Stream `brain.db` in id order, handing each batch to `on_batch`.
+fn read_memory(
+    db_path: &Path,
+    mut on_batch: impl FnMut(&[MemoryRow]) -> Result<()>,
+) -> Result<BTreeMap<String, [u8; 32]>> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open {}", db_path.display()))?;
+    let session_expr = if super::table_columns(&conn, "memories")?
+        .iter()
+        .any(|column| column == "session_id")
+    {
+        "session_id"
+    } else {
+        "NULL"
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, key, content, category, created_at, updated_at, {session_expr}
+         FROM memories ORDER BY id"
+    ))?;
+    let mut rows = stmt.query([])?;
+    let mut digests = BTreeMap::new();
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+    while let Some(row) = rows.next()? {
+        let id: String = row.get(0)?;
+        let memory = MemoryRow {
+            key: row.get(1)?,
+            content: row.get(2)?,
+            category: row.get(3)?,
+            created_at: parse_timestamp(&row.get::<_, String>(4)?, &id)?,
+            updated_at: parse_timestamp(&row.get::<_, String>(5)?, &id)?,
+            session_id: row.get(6)?,
+            id,
+        };
+        digests.insert(memory.id.clone(), memory.digest());
+        batch.push(memory);
+        if batch.len() == BATCH_SIZE {
+            on_batch(&batch)?;
+            batch.clear();
+        }
+    }
+    if !batch.is_empty() {
+        on_batch(&batch)?;
+    }
+    Ok(digests)
+}
+
+/// One JSONL record with its `id`.
+#[derive(Debug, Clone)]
+struct JsonRow {
+    id: String,
+    value: Value,
+}
+
+/// Canonical form of a JSON record: keys sorted, no whitespace.
+fn json_digest(value: &Value) -> [u8; 32] {
+    row_digest(&[&value.to_string()])
+}
+
+/// Stream a JSONL file, handing each batch to `on_batch`. Lines that are not
+/// JSON objects with a string `id` are skipped, as the readers of these files do.
+fn read_jsonl(
+    path: &Path,
+    mut on_batch: impl FnMut(&[JsonRow]) -> Result<()>,
+) -> Result<BTreeMap<String, [u8; 32]>> {
+    let mut digests = BTreeMap::new();
+    if !path.exists() {
+        return Ok(digests);
+    }
+    let file = File::open(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+    for (line_number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let parsed = serde_json::from_str::<Value>(line).ok().and_then(|value| {
+            let id = value.get("id")?.as_str()?.to_string();
+            Some(JsonRow { id, value })
+        });
+        let Some(row) = parsed else {
+            tracing::warn!(
+                "Skipping malformed record at {}:{}",
+                path.display(),
+                line_number + 1
+            );
+            continue;
+        };
+        digests.insert(row.id.clone(), json_digest(&row.value));
+        batch.push(row);
+        if batch.len() == BATCH_SIZE {
+            on_batch(&batch)?;
+            batch.clear();
+        }
+    }
+    if !batch.is_empty() {
+        on_batch(&batch)?;
+    }
+    Ok(digests)
+}
+
+/// Local files of one workspace.
+struct Sources {
+    /// `None` when memory is not stored in SQLite.
+    memory: Option<PathBuf>,
+    traces: PathBuf,
+    costs: PathBuf,
+}
+
+impl Sources {
+    fn for_config(config: &Config) -> Self {
+        let db_path = config.workspace_dir.join("memory").join("brain.db");
+        Self {
+            memory: (memory_backend(config) == "sqlite" && db_path.exists()).then_some(db_path),
+            traces: crate::observability::runtime_trace::resolve_trace_path(
+                &config.observability,
+                &config.workspace_dir,
+            ),
+            costs: config.workspace_dir.join("state").join("costs.jsonl"),
+        }
+    }
+}
+
+fn memory_backend(config: &Config) -> String {
+    crate::memory::effective_memory_backend_name(
+        &config.memory.backend,
+        Some(&config.storage.provider.config),
+    )
+}
+
+/// `zeroclaw migrate storage`.
+pub async fn migrate(
+    config: &Config,
+    to: &str,
+    target: StorageTarget,
+    dry_run: bool,
+    cutover: bool,
+) -> Result<()> {
+    if !to.trim().eq_ignore_ascii_case("postgres") {
+        bail!("Unsupported storage target '{to}' (supported: postgres)");
+    }
+    let backend = memory_backend(config);
+    if cutover && backend != "sqlite" {
+        bail!("Cutover moves SQLite memory to PostgreSQL, but the memory backend is '{backend}'");
+    }
+    let sources = Sources::for_config(config);
+
+    if dry_run {
+        let reports = [
+            sources
+                .memory
+                .as_deref()
+                .map(|path| read_memory(path, |_| Ok(())).map(|d| ("memory", d)))
+                .transpose()?,
+            Some(("traces", read_jsonl(&sources.traces, |_| Ok(()))?)),
+            Some(("costs", read_jsonl(&sources.costs, |_| Ok(()))?)),
+        ];
+        println!("🔎 Dry run: storage migration preview (nothing copied)");
+        for (name, digests) in reports.into_iter().flatten() {
+            let checksum = Checksum::of(&digests);
+            println!(
+                "  {name:<7} {:>8} rows  sha256 {}",
+                checksum.rows,
+                checksum.short()
+            );
+        }
+        print_memory_note(&sources, &backend);
+        return Ok(());
+    }
+
+    let reports = sync_postgres(&sources, &target, cutover)?;
+    println!("✅ Storage synced to postgres (schema {})", target.schema);
+    for report in &reports {
+        let status = if report.verified() {
+            format!("checksum {} ✓", report.source.short())
+        } else {
+            format!(
+                "checksum mismatch (source {} / {} rows, target {} / {} rows)",
+                report.source.short(),
+                report.source.rows,
+                report.target.short(),
+                report.target.rows
+            )
+        };
+        println!(
+            "  {:<7} {:>8} rows copied  {status}",
+            report.name, report.copied
+        );
+    }
+    print_memory_note(&sources, &backend);
+
+    if !reports.iter().all(DatasetReport::verified) {
+        if cutover {
+            bail!("Source data kept changing during {CUTOVER_PASSES} syncs; cutover aborted, re-run it at a quieter moment");
+        }
+        println!("Rows changed while copying; run the command again before cutting over.");
+        return Ok(());
+    }
+    if !cutover {
+        println!("Re-run with --cutover to switch memory to PostgreSQL.");
+        return Ok(());
+    }
+
+    let mut updated = config.clone();
+    updated.memory.backend = "postgres".to_string();
+    let provider = &mut updated.storage.provider.config;
+    provider.provider = "postgres".to_string();
+    provider.db_url = Some(target.dsn);
+    provider.schema = target.schema;
+    provider.table = target.table;
+    updated.save().await?;
+    println!(
+        "🔀 Cutover complete: {} now uses PostgreSQL for memory.",
+        updated.config_path.display()
+    );
+    println!(
+        "   Restart the daemon to serve memory from it; sync traces and costs again any time."
+    );
+    Ok(())
+}
+
+fn print_memory_note(sources: &Sources, backend: &str) {
+    if sources.memory.is_none() {
+        println!(
+            "  memory   skipped (backend '{backend}' is not SQLite, or brain.db does not exist)"
+        );
+    }
+}
+
+#[cfg(not(feature = "memory-postgres"))]
+fn sync_postgres(
+    _sources: &Sources,
+    _target: &StorageTarget,
+    _cutover: bool,
+) -> Result<Vec<DatasetReport>> {
+    bail!(
+        "PostgreSQL storage migration requires a build with `memory-postgres`; rebuild with `--features memory-postgres`"
+    )
+}
+
+/// Sync every dataset, repeating up to `CUTOVER_PASSES` times for a cutover.
+///
+/// The `postgres` client drives its own runtime, so the work runs on its own
+/// OS thread rather than on the caller's async worker.
+#[cfg(feature = "memory-postgres")]
+fn sync_postgres(
+    sources: &Sources,
+    target: &StorageTarget,
+    cutover: bool,
+) -> Result<Vec<DatasetReport>> {
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let mut client = pg::connect(target)?;
+                let passes = if cutover { CUTOVER_PASSES } else { 1 };
+                let mut reports = Vec::new();
+                for _ in 0..passes {
+                    reports = pg::sync(&mut client, sources, target)?;
+                    if reports.iter().all(DatasetReport::verified) {
+                        break;
+                    }
+                }
+                Ok(reports)
+            })
+            .join()
+            .map_err(|_| anyhow::anyhow!("PostgreSQL migration thread panicked"))?
+    })
+}
+
+#[cfg(feature = "memory-postgres")]
+mod pg {
+    use super::*;
+    use crate::memory::postgres::{quote_identifier, validate_identifier, PostgresMemory};
+    use postgres::fallible_iterator::FallibleIterator;
+    use postgres::types::ToSql;
+    use postgres::{Client, NoTls};
+
+    struct Tables {
+        memory: String,
+        traces: String,
+        costs: String,
+    }
+
+    impl Tables {
+        fn new(target: &StorageTarget) -> Result<Self> {
+            validate_identifier(&target.schema, "storage schema")?;
+            validate_identifier(&target.table, "storage table")?;
+            let schema = quote_identifier(&target.schema);
+            Ok(Self {
+                memory: format!("{schema}.{}", quote_identifier(&target.table)),
+                traces: format!("{schema}.{}", quote_identifier(TRACE_TABLE)),
+                costs: format!("{schema}.{}", quote_identifier(COST_TABLE)),
+            })
+        }
+    }
+
+    pub(super) fn connect(target: &StorageTarget) -> Result<Client> {
+        let tables = Tables::new(target)?;
+        let mut client = target
+            .dsn
+            .parse::<postgres::Config>()
+            .context("Invalid PostgreSQL connection URL")?
+            .connect(NoTls)
+            .context("Failed to connect to PostgreSQL")?;
+        PostgresMemory::init_schema(
+            &mut client,
+            &quote_identifier(&target.schema),
+            &tables.memory,
+        )?;
+        for table in [&tables.traces, &tables.costs] {
+            client.batch_execute(&format!(
+                "CREATE TABLE IF NOT EXISTS {table} (id TEXT PRIMARY KEY, record JSONB NOT NULL)"
+            ))?;
+        }
+        Ok(client)
+    }
+
+    pub(super) fn sync(
+        client: &mut Client,
+        sources: &Sources,
+        target: &StorageTarget,
+    ) -> Result<Vec<DatasetReport>> {
+        let tables = Tables::new(target)?;
+        let mut reports = Vec::new();
+        if let Some(db_path) = sources.memory.as_deref() {
+            reports.push(sync_memory(client, db_path, &tables.memory)?);
+        }
+        reports.push(sync_jsonl(
+            client,
+            "traces",
+            &sources.traces,
+            &tables.traces,
+        )?);
+        reports.push(sync_jsonl(client, "costs", &sources.costs, &tables.costs)?);
+        Ok(reports)
+    }
+
+    fn sync_memory(client: &mut Client, db_path: &Path, table: &str) -> Result<DatasetReport> {
+        let upsert = format!(
+            "INSERT INTO {table} (id, key, content, category, created_at, updated_at, session_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             ON CONFLICT (key) DO UPDATE SET
+                 id = EXCLUDED.id,
+                 content = EXCLUDED.content,
+                 category = EXCLUDED.category,
+                 created_at = EXCLUDED.created_at,
+                 updated_at = EXCLUDED.updated_at,
+                 session_id = EXCLUDED.session_id"
+        );
+        let mut copied = 0;
+        let source = read_memory(db_path, |batch| {
+            let mut tx = client.transaction()?;
+            let stmt = tx.prepare(&upsert)?;
+            for row in batch {
+                tx.execute(
+                    &stmt,
+                    &[
+                        &row.id,
+                        &row.key,
+                        &row.content,
+                        &row.category,
+                        &row.created_at,
+                        &row.updated_at,
+                        &row.session_id,
+                    ],
+                )?;
+            }
+            tx.commit()?;
+            copied += batch.len();
+            Ok(())
+        })?;
+        // Mirror deletions so forgotten memories do not come back after cutover.
+        let ids: Vec<&str> = source.keys().map(String::as_str).collect();
+        client.execute(
+            &format!("DELETE FROM {table} WHERE NOT (id = ANY($1))"),
+            &[&ids],
+        )?;
+
+        let mut digests = BTreeMap::new();
+        let mut rows = client.query_raw(
+            &format!(
+                "SELECT id, key, content, category, created_at, updated_at, session_id FROM {table}"
+            ),
+            std::iter::empty::<&dyn ToSql>(),
+        )?;
+        while let Some(row) = rows.next()? {
+            let memory = MemoryRow {
+                id: row.get(0),
+                key: row.get(1),
+                content: row.get(2),
+                category: row.get(3),
+                created_at: row.get(4),
+                updated_at: row.get(5),
+                session_id: row.get(6),
+            };
+            digests.insert(memory.id.clone(), memory.digest());
+        }
+        Ok(DatasetReport {
+            name: "memory",
+            copied,
+            source: Checksum::of(&source),
+            target: Checksum::of(&digests),
+        })
+    }
+
+    fn sync_jsonl(
+        client: &mut Client,
+        name: &'static str,
+        path: &Path,
+        table: &str,
+    ) -> Result<DatasetReport> {
+        let upsert = format!(
+            "INSERT INTO {table} (id, record) VALUES ($1, $2::text::jsonb)
+             ON CONFLICT (id) DO UPDATE SET record = EXCLUDED.record"
+        );
+        let mut copied = 0;
+        let source = read_jsonl(path, |batch| {
+            let mut tx = client.transaction()?;
+            let stmt = tx.prepare(&upsert)?;
+            for row in batch {
+                tx.execute(&stmt, &[&row.id, &row.value.to_string()])?;
+            }
+            tx.commit()?;
+            copied += batch.len();
+            Ok(())
+        })?;
+
+        // Append-only: compare the rows the source still has.
+        let mut digests = BTreeMap::new();
+        let mut rows = client.query_raw(
+            &format!("SELECT id, record::text FROM {table}"),
+            std::iter::empty::<&dyn ToSql>(),
+        )?;
+        while let Some(row) = rows.next()? {
+            let id: String = row.get(0);
+            if !source.contains_key(&id) {
+                continue;
+            }
+            let value: Value = serde_json::from_str(row.get(1))
+                .with_context(|| format!("Invalid JSON for {id} in {table}"))?;
+            digests.insert(id, json_digest(&value));
+        }
+        Ok(DatasetReport {
+            name,
+            copied,
+            source: Checksum::of(&source),
+            target: Checksum::of(&digests),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{Memory, MemoryCategory, SqliteMemory};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn memory_checksum_is_stable_and_batches_cover_every_row() {
+        let tmp = TempDir::new().unwrap();
+        let memory = SqliteMemory::new(tmp.path()).unwrap();
+        for i in 0..3 {
+            memory
+                .store(&format!("key-{i}"), "content", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        }
+        let db_path = tmp.path().join("memory").join("brain.db");
+
+        let mut seen = 0;
+        let first = read_memory(&db_path, |batch| {
+            seen += batch.len();
+            assert!(batch
+                .iter()
+                .all(|row| row.created_at.timestamp_subsec_nanos() % 1000 == 0));
+            Ok(())
+        })
+        .unwrap();
+        let second = read_memory(&db_path, |_| Ok(())).unwrap();
+
+        assert_eq!(seen, 3);
+        assert_eq!(Checksum::of(&first), Checksum::of(&second));
+        memory.forget("key-0").await.unwrap();
+        let after = read_memory(&db_path, |_| Ok(())).unwrap();
+        assert_eq!(Checksum::of(&after).rows, 2);
+        assert_ne!(Checksum::of(&after), Checksum::of(&first));
+    }
+
+    #[test]
+    fn jsonl_checksum_ignores_key_order_and_skips_bad_lines() {
+        let tmp = TempDir::new().unwrap();
+        let a = tmp.path().join("a.jsonl");
+        let b = tmp.path().join("b.jsonl");
+        std::fs::write(
+            &a,
+            "{\"id\":\"1\",\"cost\":0.5,\"model\":\"m\"}\n\nnot json\n{\"no_id\":true}\n{\"id\":\"2\"}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &b,
+            "{\"id\":\"2\"}\n{\"model\":\"m\",\"id\":\"1\",\"cost\":0.5}\n",
+        )
+        .unwrap();
+
+        let mut batches = Vec::new();
+        let from_a = read_jsonl(&a, |batch| {
+            batches.push(batch.len());
+            Ok(())
+        })
+        .unwrap();
+        let from_b = read_jsonl(&b, |_| Ok(())).unwrap();
+
+        assert_eq!(batches, [2]);
+        assert_eq!(Checksum::of(&from_a), Checksum::of(&from_b));
+        assert!(read_jsonl(&tmp.path().join("missing.jsonl"), |_| Ok(()))
+            .unwrap()
+            .is_empty());
+    }
+}
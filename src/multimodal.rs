@@ -97,6 +97,33 @@ pub fn contains_image_markers(messages: &[ChatMessage]) -> bool {
     count_image_markers(messages) > 0
 }
 
+/// Replace image markers in user messages with a note, for models that
+/// cannot take image input.
+pub fn strip_image_markers(messages: &[ChatMessage], note: &str) -> Vec<ChatMessage> {
+    messages
+        .iter()
+        .map(|message| {
+            if message.role != "user" {
+                return message.clone();
+            }
+            let (cleaned, refs) = parse_image_markers(&message.content);
+            if refs.is_empty() {
+                return message.clone();
+            }
+            let omitted = format!("[{} image(s) omitted: {note}]", refs.len());
+            let content = if cleaned.is_empty() {
+                omitted
+            } else {
+                format!("{cleaned}\n\n{omitted}")
+            };
+            ChatMessage {
+                role: message.role.clone(),
+                content,
+            }
+        })
+        .collect()
+}
+
 pub fn extract_ollama_image_payload(image_ref: &str) -> Option<String> {
     if image_ref.starts_with("data:") {
         let comma_idx = image_ref.find(',')?;
@@ -467,6 +494,22 @@ mod tests {
         assert!(refs.is_empty());
     }
 
+    #[test]
+    fn strip_image_markers_leaves_a_note() {
+        let messages = vec![
+            ChatMessage::system("[IMAGE:/tmp/not-user.png]"),
+            ChatMessage::user("What is this? [IMAGE:/tmp/a.png]"),
+        ];
+        let stripped = strip_image_markers(&messages, "model is text-only");
+
+        assert_eq!(stripped[0].content, messages[0].content);
+        assert_eq!(
+            stripped[1].content,
+            "What is this?\n\n[1 image(s) omitted: model is text-only]"
+        );
+        assert!(!contains_image_markers(&stripped));
+    }
+
     #[tokio::test]
     async fn prepare_messages_normalizes_local_image_to_data_uri() {
         let temp = tempfile::tempdir().unwrap();
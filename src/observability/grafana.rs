@@ -0,0 +1,446 @@
+//! Grafana dashboard and Prometheus alert rule generation.
+//!
+//! Everything emitted here is derived from the metric names registered by
+//! [`super::PrometheusObserver`], so the generated artifacts stay in sync with
+//! what `/metrics` actually exposes.
+
+use crate::config::Config;
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// File name of the generated dashboard inside the export directory.
+pub const DASHBOARD_FILE_NAME: &str = "zeroclaw-dashboard.json";
+/// File name of the generated alert rules inside the export directory.
+pub const ALERT_RULES_FILE_NAME: &str = "zeroclaw-alerts.yml";
+
+const DASHBOARD_UID: &str = "zeroclaw-overview";
+
+/// Thresholds baked into the generated alert rules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertThresholds {
+    /// Alert when projected 24h spend exceeds this many USD.
+    pub daily_cost_usd: f64,
+    /// Alert when the failed share of LLM requests exceeds this ratio.
+    pub llm_error_ratio: f64,
+    /// Alert when the failed share of a tool's calls exceeds this ratio.
+    pub tool_error_ratio: f64,
+    /// Alert when a channel has not received an inbound message for this long.
+    pub channel_stale_minutes: u64,
+}
+
+impl AlertThresholds {
+    /// Derive thresholds from config, falling back to conservative defaults.
+    pub fn from_config(config: &Config, channel_stale_minutes: u64) -> Self {
+        Self {
+            daily_cost_usd: config.cost.daily_limit_usd,
+            llm_error_ratio: 0.2,
+            tool_error_ratio: 0.5,
+            channel_stale_minutes: channel_stale_minutes.max(1),
+        }
+    }
+}
+
+struct AlertRule {
+    name: &'static str,
+    expr: String,
+    for_duration: &'static str,
+    severity: &'static str,
+    summary: &'static str,
+    description: String,
+}
+
+fn alert_rules(thresholds: &AlertThresholds) -> Vec<AlertRule> {
+    let stale_secs = thresholds.channel_stale_minutes * 60;
+    vec![
+        AlertRule {
+            name: "ZeroClawCostVelocityHigh",
+            expr: format!(
+                "sum(rate(zeroclaw_cost_usd_total[1h])) * 86400 > {}",
+                thresholds.daily_cost_usd
+            ),
+            for_duration: "15m",
+            severity: "warning",
+            summary: "ZeroClaw spend is on pace to exceed the daily budget",
+            description: format!(
+                "Projected 24h provider spend is {{{{ $value | printf \"%.2f\" }}}} USD (limit {} USD).",
+                thresholds.daily_cost_usd
+            ),
+        },
+        AlertRule {
+            name: "ZeroClawLlmErrorRateHigh",
+            expr: format!(
+                "sum by (provider, model) (rate(zeroclaw_llm_requests_total{{success=\"false\"}}[10m])) \
+                 / sum by (provider, model) (rate(zeroclaw_llm_requests_total[10m])) > {}",
+                thresholds.llm_error_ratio
+            ),
+            for_duration: "10m",
+            severity: "warning",
+            summary: "LLM requests are failing",
+            description: "{{ $labels.provider }}/{{ $labels.model }} error ratio is {{ $value | humanizePercentage }}.".to_string(),
+        },
+        AlertRule {
+            name: "ZeroClawToolErrorRateHigh",
+            expr: format!(
+                "sum by (tool) (rate(zeroclaw_tool_calls_total{{success=\"false\"}}[15m])) \
+                 / sum by (tool) (rate(zeroclaw_tool_calls_total[15m])) > {}",
+                thresholds.tool_error_ratio
+            ),
+            for_duration: "15m",
+            severity: "warning",
+            summary: "A tool is failing most of its calls",
+            description: "Tool {{ $labels.tool }} error ratio is {{ $value | humanizePercentage }}.".to_string(),
+        },
+        AlertRule {
+            name: "ZeroClawComponentErrors",
+            expr: "sum by (component) (increase(zeroclaw_errors_total[10m])) > 5".to_string(),
+            for_duration: "5m",
+            severity: "warning",
+            summary: "ZeroClaw components are reporting errors",
+            description: "{{ $labels.component }} reported {{ $value }} errors in the last 10 minutes.".to_string(),
+        },
+        AlertRule {
+            name: "ZeroClawChannelStale",
+            expr: format!(
+                "time() - max by (channel) (zeroclaw_channel_last_message_timestamp_seconds{{direction=\"inbound\"}}) > {stale_secs}"
+            ),
+            for_duration: "5m",
+            severity: "info",
+            summary: "A channel has gone quiet",
+            description: format!(
+                "No inbound messages on {{{{ $labels.channel }}}} for more than {} minutes.",
+                thresholds.channel_stale_minutes
+            ),
+        },
+    ]
+}
+
+fn yaml_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Render Prometheus alerting rules as a rule-file YAML document.
+pub fn render_alert_rules(thresholds: &AlertThresholds) -> String {
+    let mut out = String::from("groups:\n  - name: zeroclaw\n    rules:\n");
+    for rule in alert_rules(thresholds) {
+        let _ = writeln!(out, "      - alert: {}", rule.name);
+        let _ = writeln!(out, "        expr: {}", yaml_quote(&rule.expr));
+        let _ = writeln!(out, "        for: {}", rule.for_duration);
+        let _ = writeln!(out, "        labels:");
+        let _ = writeln!(out, "          severity: {}", rule.severity);
+        let _ = writeln!(out, "        annotations:");
+        let _ = writeln!(out, "          summary: {}", yaml_quote(rule.summary));
+        let _ = writeln!(
+            out,
+            "          description: {}",
+            yaml_quote(&rule.description)
+        );
+    }
+    out
+}
+
+fn panel(
+    id: u32,
+    title: &str,
+    kind: &str,
+    unit: &str,
+    grid: (u32, u32, u32, u32),
+    targets: &[(&str, &str)],
+) -> Value {
+    let (x, y, w, h) = grid;
+    let targets: Vec<Value> = targets
+        .iter()
+        .zip('A'..)
+        .map(|((expr, legend), ref_id)| {
+            json!({
+                "datasource": { "type": "prometheus", "uid": "${DS_PROMETHEUS}" },
+                "expr": expr,
+                "legendFormat": legend,
+                "refId": ref_id.to_string(),
+            })
+        })
+        .collect();
+
+    json!({
+        "id": id,
+        "title": title,
+        "type": kind,
+        "datasource": { "type": "prometheus", "uid": "${DS_PROMETHEUS}" },
+        "gridPos": { "x": x, "y": y, "w": w, "h": h },
+        "fieldConfig": { "defaults": { "unit": unit }, "overrides": [] },
+        "targets": targets,
+    })
+}
+
+/// Build an importable Grafana dashboard covering cost, errors, latency and channels.
+pub fn dashboard_json(thresholds: &AlertThresholds) -> Value {
+    let stale_secs = thresholds.channel_stale_minutes * 60;
+    let mut staleness = panel(
+        9,
+        "Channel staleness (since last inbound message)",
+        "timeseries",
+        "s",
+        (12, 24, 12, 8),
+        &[(
+            "time() - max by (channel) (zeroclaw_channel_last_message_timestamp_seconds{direction=\"inbound\"})",
+            "{{channel}}",
+        )],
+    );
+    staleness["fieldConfig"]["defaults"]["thresholds"] = json!({
+        "mode": "absolute",
+        "steps": [
+            { "color": "green", "value": null },
+            { "color": "red", "value": stale_secs },
+        ],
+    });
+
+    let panels = vec![
+        panel(
+            1,
+            "Cost velocity (USD/hour)",
+            "timeseries",
+            "currencyUSD",
+            (0, 0, 12, 8),
+            &[(
+                "sum by (provider, model) (rate(zeroclaw_cost_usd_total[$__rate_interval])) * 3600",
+                "{{provider}}/{{model}}",
+            )],
+        ),
+        panel(
+            2,
+            "Spend (last 24h)",
+            "stat",
+            "currencyUSD",
+            (12, 0, 6, 8),
+            &[("sum(increase(zeroclaw_cost_usd_total[24h]))", "spend")],
+        ),
+        panel(
+            3,
+            "Tokens per second",
+            "timeseries",
+            "short",
+            (18, 0, 6, 8),
+            &[
+                ("sum(rate(zeroclaw_tokens_input_total[$__rate_interval]))", "input"),
+                ("sum(rate(zeroclaw_tokens_output_total[$__rate_interval]))", "output"),
+            ],
+        ),
+        panel(
+            4,
+            "LLM error rate",
+            "timeseries",
+            "percentunit",
+            (0, 8, 12, 8),
+            &[(
+                "sum by (provider, model) (rate(zeroclaw_llm_requests_total{success=\"false\"}[$__rate_interval])) \
+                 / sum by (provider, model) (rate(zeroclaw_llm_requests_total[$__rate_interval]))",
+                "{{provider}}/{{model}}",
+            )],
+        ),
+        panel(
+            5,
+            "Tool error rate",
+            "timeseries",
+            "percentunit",
+            (12, 8, 12, 8),
+            &[(
+                "sum by (tool) (rate(zeroclaw_tool_calls_total{success=\"false\"}[$__rate_interval])) \
+                 / sum by (tool) (rate(zeroclaw_tool_calls_total[$__rate_interval]))",
+                "{{tool}}",
+            )],
+        ),
+        panel(
+            6,
+            "Errors by component",
+            "timeseries",
+            "ops",
+            (0, 16, 12, 8),
+            &[(
+                "sum by (component) (rate(zeroclaw_errors_total[$__rate_interval]))",
+                "{{component}}",
+            )],
+        ),
+        panel(
+            7,
+            "Latency p95",
+            "timeseries",
+            "s",
+            (12, 16, 12, 8),
+            &[
+                (
+                    "histogram_quantile(0.95, sum by (le) (rate(zeroclaw_agent_duration_seconds_bucket[$__rate_interval])))",
+                    "agent",
+                ),
+                (
+                    "histogram_quantile(0.95, sum by (le, tool) (rate(zeroclaw_tool_duration_seconds_bucket[$__rate_interval])))",
+                    "tool {{tool}}",
+                ),
+            ],
+        ),
+        panel(
+            8,
+            "Channel messages",
+            "timeseries",
+            "ops",
+            (0, 24, 12, 8),
+            &[(
+                "sum by (channel, direction) (rate(zeroclaw_channel_messages_total[$__rate_interval]))",
+                "{{channel}} {{direction}}",
+            )],
+        ),
+        staleness,
+    ];
+
+    json!({
+        "__inputs": [{
+            "name": "DS_PROMETHEUS",
+            "label": "Prometheus",
+            "type": "datasource",
+            "pluginId": "prometheus",
+            "pluginName": "Prometheus",
+        }],
+        "uid": DASHBOARD_UID,
+        "title": "ZeroClaw",
+        "tags": ["zeroclaw"],
+        "timezone": "browser",
+        "schemaVersion": 39,
+        "version": 1,
+        "refresh": "30s",
+        "time": { "from": "now-24h", "to": "now" },
+        "panels": panels,
+    })
+}
+
+/// Write the dashboard JSON and alert rules into `output_dir`.
+pub fn export(output_dir: &Path, thresholds: &AlertThresholds) -> Result<(PathBuf, PathBuf)> {
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+
+    let dashboard_path = output_dir.join(DASHBOARD_FILE_NAME);
+    let dashboard = serde_json::to_string_pretty(&dashboard_json(thresholds))?;
+    std::fs::write(&dashboard_path, dashboard + "\n")
+        .with_context(|| format!("Failed to write {}", dashboard_path.display()))?;
+
+    let rules_path = output_dir.join(ALERT_RULES_FILE_NAME);
+    std::fs::write(&rules_path, render_alert_rules(thresholds))
+        .with_context(|| format!("Failed to write {}", rules_path.display()))?;
+
+    Ok((dashboard_path, rules_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::traits::{Observer, ObserverEvent};
+    use crate::observability::PrometheusObserver;
+    use std::collections::BTreeSet;
+    use std::time::Duration;
+
+    fn thresholds() -> AlertThresholds {
+        AlertThresholds {
+            daily_cost_usd: 10.0,
+            llm_error_ratio: 0.2,
+            tool_error_ratio: 0.5,
+            channel_stale_minutes: 60,
+        }
+    }
+
+    fn referenced_metrics(text: &str) -> BTreeSet<String> {
+        let mut names = BTreeSet::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("zeroclaw_") {
+            let tail = &rest[start..];
+            let end = tail
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(tail.len());
+            let name = &tail[..end];
+            names.insert(name.trim_end_matches("_bucket").to_string());
+            rest = &tail[end..];
+        }
+        names
+    }
+
+    #[test]
+    fn generated_artifacts_only_reference_exported_metrics() {
+        let obs = PrometheusObserver::new();
+        obs.record_event(&ObserverEvent::AgentEnd {
+            provider: "openai".into(),
+            model: "gpt-4o".into(),
+            duration: Duration::from_millis(200),
+            tokens_used: Some(10),
+            cost_usd: None,
+        });
+        obs.record_event(&ObserverEvent::LlmResponse {
+            provider: "openai".into(),
+            model: "gpt-4o".into(),
+            duration: Duration::from_millis(100),
+            success: true,
+            error_message: None,
+            input_tokens: Some(10),
+            output_tokens: Some(5),
+        });
+        obs.record_event(&ObserverEvent::ToolCall {
+            tool: "shell".into(),
+            duration: Duration::from_millis(5),
+            success: true,
+        });
+        obs.record_event(&ObserverEvent::ChannelMessage {
+            channel: "telegram".into(),
+            direction: "inbound".into(),
+        });
+        obs.record_event(&ObserverEvent::Error {
+            component: "provider".into(),
+            message: "boom".into(),
+        });
+        let exposition = obs.encode();
+
+        let thresholds = thresholds();
+        let mut text = dashboard_json(&thresholds).to_string();
+        text.push_str(&render_alert_rules(&thresholds));
+
+        for metric in referenced_metrics(&text) {
+            if metric == "zeroclaw_cost_usd_total" {
+                // Only emitted once a priced model has been called.
+                continue;
+            }
+            assert!(
+                exposition.contains(&format!("# TYPE {metric} ")),
+                "{metric} is not exported by PrometheusObserver"
+            );
+        }
+    }
+
+    #[test]
+    fn alert_rules_embed_thresholds() {
+        let rules = render_alert_rules(&AlertThresholds {
+            daily_cost_usd: 25.0,
+            channel_stale_minutes: 30,
+            ..thresholds()
+        });
+        assert!(rules.starts_with("groups:\n"));
+        assert!(rules.contains("alert: ZeroClawCostVelocityHigh"));
+        assert!(rules.contains("* 86400 > 25'"));
+        assert!(rules.contains("> 1800'"));
+        assert!(rules.contains("alert: ZeroClawChannelStale"));
+    }
+
+    #[test]
+    fn yaml_quote_escapes_single_quotes() {
+        assert_eq!(yaml_quote("it's"), "'it''s'");
+    }
+
+    #[test]
+    fn export_writes_both_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (dashboard, rules) = export(tmp.path(), &thresholds()).unwrap();
+
+        let parsed: Value =
+            serde_json::from_str(&std::fs::read_to_string(dashboard).unwrap()).unwrap();
+        assert_eq!(parsed["uid"], DASHBOARD_UID);
+        assert_eq!(parsed["panels"].as_array().unwrap().len(), 9);
+        assert!(std::fs::read_to_string(rules)
+            .unwrap()
+            .contains("ZeroClawLlmErrorRateHigh"));
+    }
+}
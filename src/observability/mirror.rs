@@ -0,0 +1,330 @@
+//! Read-only activity mirror (`[observability.mirror]`).
+//!
+//! Runtime trace events are condensed into one-line summaries and posted in
+//! batches to a single chat, giving the owner a lightweight oversight console.
+//! Summaries carry only event kinds, tool names, outcomes, token counts and
+//! costs; message contents, tool arguments and outputs are never mirrored.
+//! Messages arriving from the mirror chat are dropped by the channel runtime,
+//! so the feed cannot be used to command the agent.
+
+use super::runtime_trace::RuntimeTraceEvent;
+use crate::config::{Config, ObservabilityMirrorConfig};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Summaries buffered before new ones are dropped.
+const QUEUE_CAPACITY: usize = 1000;
+/// Characters kept from free-text trace messages (errors, timeouts).
+const MAX_DETAIL_CHARS: usize = 160;
+/// Event types skipped by default because their result events cover them.
+const DEFAULT_SKIPPED_EVENTS: [&str; 2] = ["llm_request", "tool_call_start"];
+
+struct Mirror {
+    tx: mpsc::Sender<String>,
+    channel: String,
+    to: String,
+    events: Vec<String>,
+    dropped: Arc<AtomicU64>,
+}
+
+static MIRROR: LazyLock<RwLock<Option<Arc<Mirror>>>> = LazyLock::new(|| RwLock::new(None));
+
+fn current_mirror() -> Option<Arc<Mirror>> {
+    MIRROR.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Initialize (or disable) the mirror from config. Must run inside a Tokio runtime.
+pub fn init_from_config(config: &Config) {
+    let settings = &config.observability.mirror;
+    let mirror = if settings.enabled {
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        let dropped = Arc::new(AtomicU64::new(0));
+        tokio::spawn(run_worker(
+            rx,
+            Arc::new(config.clone()),
+            settings.clone(),
+            Arc::clone(&dropped),
+        ));
+        Some(Arc::new(Mirror {
+            tx,
+            channel: settings.channel.trim().to_ascii_lowercase(),
+            to: settings.to.trim().to_string(),
+            events: settings.events.clone(),
+            dropped,
+        }))
+    } else {
+        None
+    };
+
+    let mut guard = MIRROR.write().unwrap_or_else(|e| e.into_inner());
+    *guard = mirror;
+}
+
+/// Whether runtime trace events should be built for the mirror.
+pub fn is_active() -> bool {
+    current_mirror().is_some()
+}
+
+/// Whether a message from `channel`/`reply_target` came from the mirror chat.
+pub fn is_mirror_chat(channel: &str, reply_target: &str) -> bool {
+    current_mirror().is_some_and(|mirror| {
+        mirror.channel.eq_ignore_ascii_case(channel) && mirror.to == reply_target
+    })
+}
+
+/// Queue a summary of `event` for the mirror, if it is enabled and wants it.
+pub fn mirror_trace(event: &RuntimeTraceEvent) {
+    let Some(mirror) = current_mirror() else {
+        return;
+    };
+    if !wants(&mirror.events, &event.event_type) {
+        return;
+    }
+    if mirror.tx.try_send(summarize(event)).is_err() {
+        mirror.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn wants(events: &[String], event_type: &str) -> bool {
+    if events.is_empty() {
+        return !DEFAULT_SKIPPED_EVENTS.contains(&event_type);
+    }
+    events.iter().any(|wanted| wanted == event_type)
+}
+
+fn outcome(success: Option<bool>) -> &'static str {
+    match success {
+        Some(true) => "✅",
+        Some(false) => "❌",
+        None => "•",
+    }
+}
+
+fn duration_text(payload: &Value) -> String {
+    payload
+        .get("duration_ms")
+        .and_then(Value::as_u64)
+        .map(|ms| {
+            if ms >= 1000 {
+                format!(" ({:.1}s)", Duration::from_millis(ms).as_secs_f64())
+            } else {
+                format!(" ({ms}ms)")
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Scrubbed, truncated free-text detail from a trace message.
+fn detail(message: Option<&str>) -> String {
+    message
+        .map(|text| {
+            let scrubbed = crate::agent::loop_::scrub_credentials(text);
+            let mut short: String = scrubbed.chars().take(MAX_DETAIL_CHARS).collect();
+            if scrubbed.chars().count() > MAX_DETAIL_CHARS {
+                short.push('…');
+            }
+            format!(": {short}")
+        })
+        .unwrap_or_default()
+}
+
+/// One-line, redacted summary of a trace event.
+fn summarize(event: &RuntimeTraceEvent) -> String {
+    let payload = &event.payload;
+    let channel = event.channel.as_deref().unwrap_or("cli");
+    let time = event.timestamp.get(11..19).unwrap_or_default();
+    let line = match event.event_type.as_str() {
+        "channel_message_inbound" => format!("📥 message received on {channel}"),
+        "channel_message_outbound" => format!("📤 reply sent on {channel}"),
+        "llm_request" => format!(
+            "🧠 request to {}/{}",
+            event.provider.as_deref().unwrap_or("?"),
+            event.model.as_deref().unwrap_or("?")
+        ),
+        "llm_response" => {
+            let mut line = format!(
+                "🧠 {} {}/{}{}",
+                outcome(event.success),
+                event.provider.as_deref().unwrap_or("?"),
+                event.model.as_deref().unwrap_or("?"),
+                duration_text(payload)
+            );
+            if let (Some(input), Some(output)) = (
+                payload.get("input_tokens").and_then(Value::as_u64),
+                payload.get("output_tokens").and_then(Value::as_u64),
+            ) {
+                let _ = write!(line, " · {input}→{output} tokens");
+            }
+            if let Some(cost) = payload.get("cost_usd").and_then(Value::as_f64) {
+                let _ = write!(line, " · ${cost:.4}");
+            }
+            line
+        }
+        "tool_call_start" => format!(
+            "🔧 {} started",
+            payload.get("tool").and_then(Value::as_str).unwrap_or("?")
+        ),
+        "tool_call_result" => format!(
+            "🔧 {} {}{}",
+            outcome(event.success),
+            payload.get("tool").and_then(Value::as_str).unwrap_or("?"),
+            duration_text(payload)
+        ),
+        "turn_final_response" => format!("💬 turn finished on {channel}"),
+        "cron_job_start" | "cron_job_result" => {
+            let name = payload
+                .get("name")
+                .and_then(Value::as_str)
+                .or_else(|| payload.get("job_id").and_then(Value::as_str))
+                .unwrap_or("?");
+            if event.event_type == "cron_job_start" {
+                format!("⏰ task '{name}' started")
+            } else {
+                format!(
+                    "⏰ {} task '{name}' finished{}",
+                    outcome(event.success),
+                    duration_text(payload)
+                )
+            }
+        }
+        "file_change_applied" => "📝 file change applied".to_string(),
+        "workspace_checkpoint" => "💾 workspace checkpoint".to_string(),
+        "channel_message_error"
+        | "channel_message_timeout"
+        | "channel_message_cancelled"
+        | "tool_loop_exhausted"
+        | "tool_call_parse_issue" => format!(
+            "⚠️ {} on {channel}{}",
+            event.event_type.replace('_', " "),
+            detail(event.message.as_deref())
+        ),
+        other => format!("• {}", other.replace('_', " ")),
+    };
+    format!("{time} {line}")
+}
+
+/// Join queued summaries into one message, capping the number of lines.
+fn render_batch(lines: &[String], max_lines: usize, dropped: u64) -> String {
+    let max_lines = max_lines.max(1);
+    let mut message = lines
+        .iter()
+        .take(max_lines)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n");
+    let hidden = lines.len().saturating_sub(max_lines) as u64 + dropped;
+    if hidden > 0 {
+        let _ = write!(message, "\n… and {hidden} more events");
+    }
+    message
+}
+
+async fn run_worker(
+    mut rx: mpsc::Receiver<String>,
+    config: Arc<Config>,
+    settings: ObservabilityMirrorConfig,
+    dropped: Arc<AtomicU64>,
+) {
+    let interval = Duration::from_secs(settings.batch_secs.max(1));
+    let mut batch = Vec::new();
+    loop {
+        let Some(first) = rx.recv().await else {
+            return;
+        };
+        batch.push(first);
+        // Collect whatever else arrives during the batching window.
+        let deadline = tokio::time::Instant::now() + interval;
+        while let Ok(Some(line)) = tokio::time::timeout_at(deadline, rx.recv()).await {
+            batch.push(line);
+        }
+
+        let message = render_batch(
+            &batch,
+            settings.max_events_per_message,
+            dropped.swap(0, Ordering::Relaxed),
+        );
+        batch.clear();
+        if let Err(e) = crate::cron::scheduler::send_announcement(
+            &config,
+            &settings.channel,
+            &settings.to,
+            &message,
+        )
+        .await
+        {
+            tracing::warn!("Failed to post activity mirror update: {e:#}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(event_type: &str, success: Option<bool>, payload: Value) -> RuntimeTraceEvent {
+        RuntimeTraceEvent {
+            id: "id".into(),
+            timestamp: "2026-03-01T12:34:56+00:00".into(),
+            event_type: event_type.into(),
+            channel: Some("telegram".into()),
+            provider: Some("openrouter".into()),
+            model: Some("gpt-4o".into()),
+            turn_id: None,
+            session: None,
+            success,
+            message: Some("failed with api_key=sk-abcdef1234567890".into()),
+            payload,
+        }
+    }
+
+    #[test]
+    fn summaries_omit_contents_and_arguments() {
+        let tool = summarize(&event(
+            "tool_call_result",
+            Some(true),
+            json!({"tool": "shell", "arguments": "{\"command\":\"cat secrets\"}", "output": "hunter2", "duration_ms": 1500}),
+        ));
+        assert_eq!(tool, "12:34:56 🔧 ✅ shell (1.5s)");
+
+        let inbound = summarize(&event(
+            "channel_message_inbound",
+            None,
+            json!({"content_preview": "my bank password is"}),
+        ));
+        assert!(!inbound.contains("password"));
+
+        let llm = summarize(&event(
+            "llm_response",
+            Some(true),
+            json!({"input_tokens": 1200, "output_tokens": 80, "cost_usd": 0.0123, "raw_response": "text"}),
+        ));
+        assert!(llm.contains("1200→80 tokens"));
+        assert!(llm.contains("$0.0123"));
+        assert!(!llm.contains("text"));
+
+        let error = summarize(&event("channel_message_error", Some(false), json!({})));
+        assert!(!error.contains("sk-abcdef1234567890"));
+    }
+
+    #[test]
+    fn event_filter_defaults_and_overrides() {
+        assert!(wants(&[], "tool_call_result"));
+        assert!(!wants(&[], "tool_call_start"));
+        let only_cron = vec!["cron_job_start".to_string()];
+        assert!(wants(&only_cron, "cron_job_start"));
+        assert!(!wants(&only_cron, "tool_call_result"));
+    }
+
+    #[test]
+    fn batch_is_capped_with_overflow_count() {
+        let lines: Vec<String> = (0..5).map(|i| format!("line {i}")).collect();
+        let message = render_batch(&lines, 3, 2);
+        assert_eq!(message, "line 0\nline 1\nline 2\n… and 4 more events");
+        assert_eq!(render_batch(&lines[..1], 3, 0), "line 0");
+    }
+}
@@ -1,10 +1,13 @@
+pub mod grafana;
 pub mod log;
+pub mod mirror;
 pub mod multi;
 pub mod noop;
 #[cfg(feature = "observability-otel")]
 pub mod otel;
 pub mod prometheus;
 pub mod runtime_trace;
+pub mod sinks;
 pub mod traits;
 pub mod verbose;
 
@@ -1,7 +1,9 @@
 use super::traits::{Observer, ObserverEvent, ObserverMetric};
 use prometheus::{
-    Encoder, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounterVec, Registry, TextEncoder,
+    CounterVec, Encoder, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounterVec, Registry,
+    TextEncoder,
 };
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Prometheus-backed observer — exposes metrics for scraping via `/metrics`.
 pub struct PrometheusObserver {
@@ -12,10 +14,12 @@ pub struct PrometheusObserver {
     llm_requests: IntCounterVec,
     tokens_input_total: IntCounterVec,
     tokens_output_total: IntCounterVec,
+    cost_usd_total: CounterVec,
     tool_calls: IntCounterVec,
     channel_messages: IntCounterVec,
     heartbeat_ticks: prometheus::IntCounter,
     errors: IntCounterVec,
+    queue_evicted: IntCounterVec,
 
     // Histograms
     agent_duration: HistogramVec,
@@ -26,6 +30,8 @@ pub struct PrometheusObserver {
     tokens_used: prometheus::IntGauge,
     active_sessions: GaugeVec,
     queue_depth: GaugeVec,
+    durable_queue_entries: GaugeVec,
+    channel_last_message: GaugeVec,
 }
 
 impl PrometheusObserver {
@@ -59,6 +65,15 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let cost_usd_total = CounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_cost_usd_total",
+                "Estimated provider spend in USD from [cost] pricing",
+            ),
+            &["provider", "model"],
+        )
+        .expect("valid metric");
+
         let tool_calls = IntCounterVec::new(
             prometheus::Opts::new("zeroclaw_tool_calls_total", "Total tool calls"),
             &["tool", "success"],
@@ -81,6 +96,15 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let queue_evicted = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_durable_queue_evicted_total",
+                "Entries evicted from durable queues by the [queues] caps",
+            ),
+            &["queue"],
+        )
+        .expect("valid metric");
+
         let agent_duration = HistogramVec::new(
             HistogramOpts::new(
                 "zeroclaw_agent_duration_seconds",
@@ -128,6 +152,24 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let durable_queue_entries = GaugeVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_durable_queue_entries",
+                "Entries in each durable on-disk queue after its last write",
+            ),
+            &["queue"],
+        )
+        .expect("valid metric");
+
+        let channel_last_message = GaugeVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_channel_last_message_timestamp_seconds",
+                "Unix time of the last message seen per channel and direction",
+            ),
+            &["channel", "direction"],
+        )
+        .expect("valid metric");
+
         // Register all metrics
         registry.register(Box::new(agent_starts.clone())).ok();
         registry.register(Box::new(llm_requests.clone())).ok();
@@ -135,16 +177,24 @@ impl PrometheusObserver {
         registry
             .register(Box::new(tokens_output_total.clone()))
             .ok();
+        registry.register(Box::new(cost_usd_total.clone())).ok();
         registry.register(Box::new(tool_calls.clone())).ok();
         registry.register(Box::new(channel_messages.clone())).ok();
         registry.register(Box::new(heartbeat_ticks.clone())).ok();
         registry.register(Box::new(errors.clone())).ok();
+        registry.register(Box::new(queue_evicted.clone())).ok();
         registry.register(Box::new(agent_duration.clone())).ok();
         registry.register(Box::new(tool_duration.clone())).ok();
         registry.register(Box::new(request_latency.clone())).ok();
         registry.register(Box::new(tokens_used.clone())).ok();
         registry.register(Box::new(active_sessions.clone())).ok();
         registry.register(Box::new(queue_depth.clone())).ok();
+        registry
+            .register(Box::new(durable_queue_entries.clone()))
+            .ok();
+        registry
+            .register(Box::new(channel_last_message.clone()))
+            .ok();
 
         Self {
             registry,
@@ -152,27 +202,44 @@ impl PrometheusObserver {
             llm_requests,
             tokens_input_total,
             tokens_output_total,
+            cost_usd_total,
             tool_calls,
             channel_messages,
             heartbeat_ticks,
             errors,
+            queue_evicted,
             agent_duration,
             tool_duration,
             request_latency,
             tokens_used,
             active_sessions,
             queue_depth,
+            durable_queue_entries,
+            channel_last_message,
         }
     }
 
     /// Encode all registered metrics into Prometheus text exposition format.
     pub fn encode(&self) -> String {
+        self.sync_queue_stats();
         let encoder = TextEncoder::new();
         let families = self.registry.gather();
         let mut buf = Vec::new();
         encoder.encode(&families, &mut buf).unwrap_or_default();
         String::from_utf8(buf).unwrap_or_default()
     }
+
+    /// Durable queues are written outside the observer; pick up their
+    /// counters at scrape time.
+    fn sync_queue_stats(&self) {
+        for (queue, stats) in crate::queue_store::queue_stats() {
+            self.durable_queue_entries
+                .with_label_values(&[queue.as_str()])
+                .set(stats.depth as f64);
+            let evicted = self.queue_evicted.with_label_values(&[queue.as_str()]);
+            evicted.inc_by(stats.evicted.saturating_sub(evicted.get()));
+        }
+    }
 }
 
 impl Observer for PrometheusObserver {
@@ -220,6 +287,16 @@ impl Observer for PrometheusObserver {
                         .with_label_values(&[provider.as_str(), model.as_str()])
                         .inc_by(*output);
                 }
+                if let Some(cost) = super::runtime_trace::estimate_cost_usd(
+                    provider,
+                    model,
+                    *input_tokens,
+                    *output_tokens,
+                ) {
+                    self.cost_usd_total
+                        .with_label_values(&[provider.as_str(), model.as_str()])
+                        .inc_by(cost);
+                }
             }
             ObserverEvent::ToolCallStart { tool: _ }
             | ObserverEvent::TurnComplete
@@ -241,6 +318,13 @@ impl Observer for PrometheusObserver {
                 self.channel_messages
                     .with_label_values(&[channel, direction])
                     .inc();
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs_f64())
+                    .unwrap_or_default();
+                self.channel_last_message
+                    .with_label_values(&[channel, direction])
+                    .set(now);
             }
             ObserverEvent::HeartbeatTick => {
                 self.heartbeat_ticks.inc();
@@ -493,4 +577,24 @@ mod tests {
         assert!(!output.contains("zeroclaw_tokens_input_total{"));
         assert!(!output.contains("zeroclaw_tokens_output_total{"));
     }
+
+    #[test]
+    fn channel_messages_record_last_seen_timestamp() {
+        let obs = PrometheusObserver::new();
+        obs.record_event(&ObserverEvent::ChannelMessage {
+            channel: "telegram".into(),
+            direction: "inbound".into(),
+        });
+
+        let output = obs.encode();
+        let line = output
+            .lines()
+            .find(|line| {
+                line.starts_with("zeroclaw_channel_last_message_timestamp_seconds{")
+                    && line.contains(r#"channel="telegram""#)
+            })
+            .expect("staleness gauge should be exported");
+        let value: f64 = line.rsplit(' ').next().unwrap().parse().unwrap();
+        assert!(value > 1_600_000_000.0);
+    }
 }
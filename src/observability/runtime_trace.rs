@@ -1,8 +1,9 @@
-use crate::config::ObservabilityConfig;
+use crate::config::{CostConfig, ModelPricing, ObservabilityConfig};
 use anyhow::Result;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -44,6 +45,10 @@ pub struct RuntimeTraceEvent {
     pub model: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub turn_id: Option<String>,
+    /// Conversation the turn belongs to (channel history key or CLI
+    /// conversation id); absent for one-shot runs and incognito sessions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub success: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -145,6 +150,9 @@ impl RuntimeTraceLogger {
 static TRACE_LOGGER: LazyLock<RwLock<Option<Arc<RuntimeTraceLogger>>>> =
     LazyLock::new(|| RwLock::new(None));
 
+static TRACE_PRICING: LazyLock<RwLock<HashMap<String, ModelPricing>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
 /// Resolve runtime trace storage mode from config.
 pub fn storage_mode_from_config(config: &ObservabilityConfig) -> RuntimeTraceStorageMode {
     let mode = RuntimeTraceStorageMode::from_raw(&config.runtime_trace_mode);
@@ -193,6 +201,102 @@ pub fn init_from_config(config: &ObservabilityConfig, workspace_dir: &Path) {
     *guard = logger;
 }
 
+/// Install the `[cost]` price table used to annotate provider-call events with cost.
+pub fn set_model_pricing(config: &CostConfig) {
+    let mut guard = TRACE_PRICING.write().unwrap_or_else(|e| e.into_inner());
+    guard.clone_from(&config.prices);
+}
+
+pub(crate) fn lookup_pricing<'a>(
+    prices: &'a HashMap<String, ModelPricing>,
+    provider: &str,
+    model: &str,
+) -> Option<&'a ModelPricing> {
+    prices
+        .get(model)
+        .or_else(|| prices.get(&format!("{provider}/{model}")))
+        .or_else(|| {
+            let suffix = format!("/{model}");
+            prices
+                .iter()
+                .filter(|(key, _)| key.ends_with(&suffix))
+                .min_by(|(a, _), (b, _)| a.cmp(b))
+                .map(|(_, pricing)| pricing)
+        })
+}
+
+/// Estimate the USD cost of a provider call from the installed price table.
+///
+/// Returns `None` when the model has no pricing entry or the provider did not
+/// report any token usage.
+pub fn estimate_cost_usd(
+    provider: &str,
+    model: &str,
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+) -> Option<f64> {
+    if input_tokens.is_none() && output_tokens.is_none() {
+        return None;
+    }
+
+    let prices = TRACE_PRICING.read().unwrap_or_else(|e| e.into_inner());
+    let pricing = lookup_pricing(&prices, provider, model)?;
+    let usage = crate::cost::TokenUsage::new(
+        model,
+        input_tokens.unwrap_or(0),
+        output_tokens.unwrap_or(0),
+        pricing.input,
+        pricing.output,
+    );
+    Some(usage.cost_usd)
+}
+
+tokio::task_local! {
+    static REDACTED: bool;
+}
+
+/// Run `future` with its trace events reduced to metadata (incognito sessions).
+pub async fn with_redaction<F: std::future::Future>(redact: bool, future: F) -> F::Output {
+    if redact {
+        REDACTED.scope(true, future).await
+    } else {
+        future.await
+    }
+}
+
+fn redaction_active() -> bool {
+    REDACTED.try_with(|redacted| *redacted).unwrap_or(false)
+}
+
+/// Keep numbers, booleans and object structure; drop strings and arrays,
+/// which carry message text, arguments and tool output.
+fn metadata_only(payload: Value) -> Value {
+    match payload {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(_, value)| !matches!(value, Value::String(_) | Value::Array(_)))
+                .map(|(key, value)| (key, metadata_only(value)))
+                .collect(),
+        ),
+        Value::String(_) | Value::Array(_) => Value::Null,
+        other => other,
+    }
+}
+
+/// Whether events recorded now keep their text payloads somewhere: a trace
+/// file, sink or mirror is active and the current task is not redacted.
+pub fn records_payloads() -> bool {
+    if redaction_active() {
+        return false;
+    }
+    TRACE_LOGGER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .is_some()
+        || super::sinks::ships_traces()
+        || super::mirror::is_active()
+}
+
 /// Record a runtime trace event.
 pub fn record_event(
     event_type: &str,
@@ -204,13 +308,26 @@ pub fn record_event(
     message: Option<&str>,
     payload: Value,
 ) {
+    let (message, payload, session) = if redaction_active() {
+        let mut payload = metadata_only(payload);
+        if let Value::Object(map) = &mut payload {
+            map.insert("redacted".into(), Value::Bool(true));
+        }
+        (None, payload, None)
+    } else {
+        (message, payload, crate::cost::conversation::current())
+    };
+    crate::telemetry::record_trace(event_type, channel, &payload);
+
     let logger = TRACE_LOGGER
         .read()
         .unwrap_or_else(|e| e.into_inner())
         .clone();
-    let Some(logger) = logger else {
+    let ship = super::sinks::ships_traces();
+    let mirror = super::mirror::is_active();
+    if logger.is_none() && !ship && !mirror {
         return;
-    };
+    }
 
     let event = RuntimeTraceEvent {
         id: Uuid::new_v4().to_string(),
@@ -220,13 +337,24 @@ pub fn record_event(
         provider: provider.map(str::to_string),
         model: model.map(str::to_string),
         turn_id: turn_id.map(str::to_string),
+        session,
         success,
         message: message.map(str::to_string),
         payload,
     };
 
-    if let Err(err) = logger.append(&event) {
-        tracing::warn!("Failed to write runtime trace event: {err}");
+    if ship {
+        super::sinks::ship_trace(&event);
+    }
+
+    if mirror {
+        super::mirror::mirror_trace(&event);
+    }
+
+    if let Some(logger) = logger {
+        if let Err(err) = logger.append(&event) {
+            tracing::warn!("Failed to write runtime trace event: {err}");
+        }
     }
 }
 
@@ -314,6 +442,120 @@ pub fn find_event_by_id(path: &Path, id: &str) -> Result<Option<RuntimeTraceEven
     Ok(None)
 }
 
+/// Aggregated usage for one group of runtime trace events.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TraceUsageTotals {
+    pub llm_calls: u64,
+    pub tool_calls: u64,
+    pub failures: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub cost_usd: f64,
+    pub duration_ms: u64,
+}
+
+impl TraceUsageTotals {
+    fn add_llm_response(&mut self, event: &RuntimeTraceEvent) {
+        self.llm_calls += 1;
+        if event.success == Some(false) {
+            self.failures += 1;
+        }
+        self.input_tokens += payload_u64(&event.payload, "input_tokens");
+        self.output_tokens += payload_u64(&event.payload, "output_tokens");
+        self.cached_input_tokens += payload_u64(&event.payload, "cached_input_tokens");
+        self.cost_usd += event
+            .payload
+            .get("cost_usd")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0);
+        self.duration_ms += payload_u64(&event.payload, "duration_ms");
+    }
+
+    fn add_tool_result(&mut self, event: &RuntimeTraceEvent) {
+        self.tool_calls += 1;
+        if event.success == Some(false) {
+            self.failures += 1;
+        }
+        self.duration_ms += payload_u64(&event.payload, "duration_ms");
+    }
+}
+
+/// Per-session, per-tool and per-model aggregation of runtime trace events.
+///
+/// Sessions are keyed by the conversation each event was recorded in, so all
+/// turns of one chat or CLI conversation add up. Events recorded outside a
+/// conversation (one-shot runs, incognito sessions, older traces) fall back to
+/// their `turn_id`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RuntimeTraceSummary {
+    pub total: TraceUsageTotals,
+    pub sessions: BTreeMap<String, TraceUsageTotals>,
+    pub tools: BTreeMap<String, TraceUsageTotals>,
+    pub models: BTreeMap<String, TraceUsageTotals>,
+}
+
+fn payload_u64(payload: &Value, key: &str) -> u64 {
+    payload.get(key).and_then(Value::as_u64).unwrap_or(0)
+}
+
+/// Aggregate token usage, cost and latency across loaded trace events.
+pub fn summarize_events(events: &[RuntimeTraceEvent]) -> RuntimeTraceSummary {
+    let mut summary = RuntimeTraceSummary::default();
+
+    for event in events {
+        let session = event
+            .session
+            .as_deref()
+            .or(event.turn_id.as_deref())
+            .unwrap_or("-")
+            .to_string();
+        match event.event_type.as_str() {
+            "llm_response" => {
+                let model = match (event.provider.as_deref(), event.model.as_deref()) {
+                    (Some(provider), Some(model)) => format!("{provider}/{model}"),
+                    (None, Some(model)) => model.to_string(),
+                    (Some(provider), None) => provider.to_string(),
+                    (None, None) => "-".to_string(),
+                };
+                summary.total.add_llm_response(event);
+                summary
+                    .sessions
+                    .entry(session)
+                    .or_default()
+                    .add_llm_response(event);
+                summary
+                    .models
+                    .entry(model)
+                    .or_default()
+                    .add_llm_response(event);
+            }
+            "tool_call_result" => {
+                let tool = event
+                    .payload
+                    .get("tool")
+                    .and_then(Value::as_str)
+                    .unwrap_or("-")
+                    .to_string();
+                summary.total.add_tool_result(event);
+                summary
+                    .sessions
+                    .entry(session)
+                    .or_default()
+                    .add_tool_result(event);
+                summary
+                    .tools
+                    .entry(tool)
+                    .or_default()
+                    .add_tool_result(event);
+            }
+            _ => {}
+        }
+    }
+
+    summary
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,6 +568,8 @@ mod tests {
             runtime_trace_mode: "rolling".to_string(),
             runtime_trace_path: "state/runtime-trace.jsonl".to_string(),
             runtime_trace_max_entries: 3,
+            sinks: crate::config::ObservabilitySinksConfig::default(),
+            mirror: crate::config::ObservabilityMirrorConfig::default(),
         }
     }
 
@@ -374,6 +618,7 @@ mod tests {
                 provider: None,
                 model: None,
                 turn_id: None,
+                session: None,
                 success: None,
                 message: Some(format!("event-{i}")),
                 payload: serde_json::json!({ "i": i }),
@@ -402,6 +647,7 @@ mod tests {
             provider: Some("openrouter".into()),
             model: Some("x".into()),
             turn_id: Some("turn-1".into()),
+            session: None,
             success: Some(false),
             message: Some("boom".into()),
             payload: serde_json::json!({ "error": "boom" }),
@@ -412,4 +658,135 @@ mod tests {
         assert!(found.is_some());
         assert_eq!(found.unwrap().id, target_id);
     }
+
+    fn trace_event(
+        event_type: &str,
+        turn_id: &str,
+        session: Option<&str>,
+        success: bool,
+        payload: Value,
+    ) -> RuntimeTraceEvent {
+        RuntimeTraceEvent {
+            id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            event_type: event_type.into(),
+            channel: Some("cli".into()),
+            provider: Some("openai".into()),
+            model: Some("gpt-4o".into()),
+            turn_id: Some(turn_id.into()),
+            session: session.map(str::to_string),
+            success: Some(success),
+            message: None,
+            payload,
+        }
+    }
+
+    #[test]
+    fn summarize_events_groups_by_session_tool_and_model() {
+        let events = vec![
+            trace_event(
+                "llm_response",
+                "turn-1",
+                Some("telegram_alice"),
+                true,
+                serde_json::json!({
+                    "duration_ms": 120,
+                    "input_tokens": 1000,
+                    "output_tokens": 200,
+                    "cached_input_tokens": 400,
+                    "cost_usd": 0.5,
+                }),
+            ),
+            trace_event(
+                "tool_call_result",
+                "turn-1",
+                Some("telegram_alice"),
+                false,
+                serde_json::json!({ "tool": "shell", "duration_ms": 30 }),
+            ),
+            trace_event(
+                "llm_response",
+                "turn-2",
+                Some("telegram_alice"),
+                true,
+                serde_json::json!({
+                    "duration_ms": 80,
+                    "input_tokens": 500,
+                    "output_tokens": 100,
+                    "cost_usd": 0.25,
+                }),
+            ),
+            trace_event(
+                "llm_request",
+                "turn-2",
+                Some("telegram_alice"),
+                true,
+                serde_json::json!({ "input_tokens": 9999 }),
+            ),
+        ];
+
+        let summary = summarize_events(&events);
+        assert_eq!(summary.total.llm_calls, 2);
+        assert_eq!(summary.total.tool_calls, 1);
+        assert_eq!(summary.total.failures, 1);
+        assert_eq!(summary.total.input_tokens, 1500);
+        assert_eq!(summary.total.cached_input_tokens, 400);
+        assert!((summary.total.cost_usd - 0.75).abs() < f64::EPSILON);
+
+        assert_eq!(summary.sessions.len(), 1);
+        let session = &summary.sessions["telegram_alice"];
+        assert_eq!(session.llm_calls, 2);
+        assert_eq!(session.tool_calls, 1);
+        assert_eq!(session.duration_ms, 230);
+
+        let one_shot = summarize_events(&[trace_event(
+            "llm_response",
+            "turn-3",
+            None,
+            true,
+            serde_json::json!({}),
+        )]);
+        assert_eq!(one_shot.sessions["turn-3"].llm_calls, 1);
+
+        assert_eq!(summary.tools["shell"].failures, 1);
+        assert_eq!(summary.models["openai/gpt-4o"].output_tokens, 300);
+    }
+
+    #[test]
+    fn lookup_pricing_matches_bare_and_prefixed_model_names() {
+        let mut prices = HashMap::new();
+        prices.insert(
+            "openai/gpt-4o".to_string(),
+            ModelPricing {
+                input: 5.0,
+                output: 15.0,
+            },
+        );
+
+        assert!(lookup_pricing(&prices, "openai", "gpt-4o").is_some());
+        assert!(lookup_pricing(&prices, "openrouter", "gpt-4o").is_some());
+        assert!(lookup_pricing(&prices, "openai", "openai/gpt-4o").is_some());
+        assert!(lookup_pricing(&prices, "openai", "gpt-4o-mini").is_none());
+    }
+
+    #[tokio::test]
+    async fn redaction_keeps_metadata_only_inside_scope() {
+        assert!(!redaction_active());
+        let redacted = with_redaction(true, async {
+            assert!(redaction_active());
+            metadata_only(serde_json::json!({
+                "iteration": 2,
+                "success": true,
+                "content_preview": "my bank PIN is 1234",
+                "tool_calls": [{"name": "shell"}],
+                "usage": {"input_tokens": 120, "model": "x"},
+            }))
+        })
+        .await;
+        assert_eq!(
+            redacted,
+            serde_json::json!({"iteration": 2, "success": true, "usage": {"input_tokens": 120}})
+        );
+        assert!(!with_redaction(false, async { redaction_active() }).await);
+    }
 }
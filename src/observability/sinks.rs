@@ -0,0 +1,777 @@
+//! Shipping of structured logs and runtime trace events to external sinks.
+//!
+//! Records are queued on a bounded channel and drained by a background task
+//! that batches them per `[observability.sinks]`. When the queue is full new
+//! records are dropped (and counted) so that a slow or unreachable collector
+//! never stalls the agent loop. [`shutdown`] drains what is still queued
+//! before the process exits.
+
+use crate::config::{HttpSinkConfig, LokiSinkConfig, ObservabilitySinksConfig, SyslogSinkConfig};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context as LayerContext;
+use tracing_subscriber::Layer;
+
+/// One record queued for shipping.
+#[derive(Debug, Clone, Serialize)]
+pub struct SinkRecord {
+    pub timestamp: DateTime<Utc>,
+    /// `"log"` or `"trace"`.
+    pub kind: &'static str,
+    /// Lowercase level name (`"error"`, `"warn"`, `"info"`, ...).
+    pub level: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub fields: Map<String, Value>,
+}
+
+impl SinkRecord {
+    fn to_json_line(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// External destination for shipped records.
+#[async_trait]
+pub trait LogSink: Send + Sync {
+    fn name(&self) -> &str;
+    async fn ship(&self, batch: &[SinkRecord]) -> Result<()>;
+}
+
+struct SinkShipper {
+    tx: mpsc::Sender<SinkRecord>,
+    ship_logs: bool,
+    ship_traces: bool,
+    min_level: Level,
+    dropped: Arc<AtomicU64>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+/// How long [`shutdown`] waits for queued records to be delivered.
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+static SHIPPER: LazyLock<RwLock<Option<Arc<SinkShipper>>>> = LazyLock::new(|| RwLock::new(None));
+
+fn current_shipper() -> Option<Arc<SinkShipper>> {
+    SHIPPER.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn parse_level(raw: &str) -> Level {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "error" => Level::ERROR,
+        "warn" => Level::WARN,
+        "debug" => Level::DEBUG,
+        "trace" => Level::TRACE,
+        _ => Level::INFO,
+    }
+}
+
+fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::ERROR => "error",
+        Level::WARN => "warn",
+        Level::INFO => "info",
+        Level::DEBUG => "debug",
+        Level::TRACE => "trace",
+    }
+}
+
+/// Build the configured sinks. Returns an empty list when none are set.
+pub fn build_sinks(config: &ObservabilitySinksConfig) -> Result<Vec<Box<dyn LogSink>>> {
+    let mut sinks: Vec<Box<dyn LogSink>> = Vec::new();
+    if let Some(loki) = &config.loki {
+        sinks.push(Box::new(LokiSink::new(loki)?));
+    }
+    if let Some(syslog) = &config.syslog {
+        sinks.push(Box::new(SyslogSink::new(syslog)));
+    }
+    if let Some(http) = &config.http {
+        sinks.push(Box::new(HttpNdjsonSink::new(http)?));
+    }
+    Ok(sinks)
+}
+
+/// Start (or stop) shipping according to config. Must run inside a Tokio runtime.
+pub fn init_from_config(config: &ObservabilitySinksConfig) {
+    let sinks = match build_sinks(config) {
+        Ok(sinks) => sinks,
+        Err(err) => {
+            tracing::warn!("Failed to initialize observability sinks: {err:#}");
+            Vec::new()
+        }
+    };
+
+    let shipper = if sinks.is_empty() || (!config.ship_logs && !config.ship_traces) {
+        None
+    } else {
+        let (tx, rx) = mpsc::channel(config.queue_capacity.max(1));
+        let dropped = Arc::new(AtomicU64::new(0));
+        let worker = tokio::spawn(run_worker(
+            rx,
+            sinks,
+            config.batch_size.max(1),
+            Duration::from_millis(config.flush_interval_ms.max(1)),
+            config.max_retries,
+            Arc::clone(&dropped),
+        ));
+        Some(Arc::new(SinkShipper {
+            tx,
+            ship_logs: config.ship_logs,
+            ship_traces: config.ship_traces,
+            min_level: parse_level(&config.min_level),
+            dropped,
+            worker: Mutex::new(Some(worker)),
+        }))
+    };
+
+    let mut guard = SHIPPER.write().unwrap_or_else(|e| e.into_inner());
+    *guard = shipper;
+}
+
+/// Stop shipping and deliver the records still queued or batched, waiting at
+/// most [`SHUTDOWN_DRAIN_TIMEOUT`]. Call before the process exits.
+pub async fn shutdown() {
+    let shipper = SHIPPER.write().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some(shipper) = shipper {
+        drain(shipper, SHUTDOWN_DRAIN_TIMEOUT).await;
+    }
+}
+
+async fn drain(shipper: Arc<SinkShipper>, timeout: Duration) {
+    let worker = shipper
+        .worker
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take();
+    // Dropping the sender closes the queue; the worker then flushes and exits.
+    drop(shipper);
+    if let Some(worker) = worker {
+        if tokio::time::timeout(timeout, worker).await.is_err() {
+            tracing::warn!("Observability sinks did not drain within {timeout:?}");
+        }
+    }
+}
+
+/// Whether runtime trace events should be built for shipping.
+pub fn ships_traces() -> bool {
+    current_shipper().is_some_and(|shipper| shipper.ship_traces)
+}
+
+fn enqueue(shipper: &SinkShipper, record: SinkRecord) {
+    if shipper.tx.try_send(record).is_err() {
+        shipper.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Queue a runtime trace event for shipping.
+pub fn ship_trace(event: &super::runtime_trace::RuntimeTraceEvent) {
+    let Some(shipper) = current_shipper().filter(|shipper| shipper.ship_traces) else {
+        return;
+    };
+
+    let level = if event.success == Some(false) {
+        "warn"
+    } else {
+        "info"
+    };
+    let fields = match serde_json::to_value(event) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    let timestamp = DateTime::parse_from_rfc3339(&event.timestamp)
+        .map(|ts| ts.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now());
+
+    enqueue(
+        &shipper,
+        SinkRecord {
+            timestamp,
+            kind: "trace",
+            level,
+            message: event.event_type.clone(),
+            fields,
+        },
+    );
+}
+
+async fn run_worker(
+    mut rx: mpsc::Receiver<SinkRecord>,
+    sinks: Vec<Box<dyn LogSink>>,
+    batch_size: usize,
+    flush_interval: Duration,
+    max_retries: u32,
+    dropped: Arc<AtomicU64>,
+) {
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut reported_dropped = 0;
+    loop {
+        let deadline = tokio::time::sleep(flush_interval);
+        tokio::pin!(deadline);
+
+        let closed = loop {
+            tokio::select! {
+                record = rx.recv() => match record {
+                    Some(record) => {
+                        batch.push(record);
+                        if batch.len() >= batch_size {
+                            break false;
+                        }
+                    }
+                    None => break true,
+                },
+                () = &mut deadline => break false,
+            }
+        };
+
+        if !batch.is_empty() {
+            for sink in &sinks {
+                deliver(sink.as_ref(), &batch, max_retries).await;
+            }
+            batch.clear();
+        }
+
+        let dropped_now = dropped.load(Ordering::Relaxed);
+        if dropped_now > reported_dropped {
+            tracing::warn!(
+                "Observability sink queue full; dropped {} records",
+                dropped_now - reported_dropped
+            );
+            reported_dropped = dropped_now;
+        }
+
+        if closed {
+            return;
+        }
+    }
+}
+
+async fn deliver(sink: &dyn LogSink, batch: &[SinkRecord], max_retries: u32) {
+    let mut backoff = Duration::from_millis(250);
+    for attempt in 0..=max_retries {
+        match sink.ship(batch).await {
+            Ok(()) => return,
+            Err(err) if attempt == max_retries => {
+                // SinkLayer ignores this module's events, so this cannot loop back into the sinks.
+                tracing::warn!(
+                    sink = sink.name(),
+                    records = batch.len(),
+                    "Dropping batch after {} attempts: {err:#}",
+                    attempt + 1
+                );
+            }
+            Err(_) => {
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(Duration::from_secs(10));
+            }
+        }
+    }
+}
+
+// ── tracing layer ───────────────────────────────────────────────
+
+/// `tracing` layer that forwards log events to the configured sinks.
+///
+/// The layer is installed unconditionally at startup and is a no-op until
+/// [`init_from_config`] enables shipping.
+pub struct SinkLayer;
+
+#[derive(Default)]
+struct FieldCollector {
+    message: String,
+    fields: Map<String, Value>,
+}
+
+impl Visit for FieldCollector {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields
+                .insert(field.name().to_string(), Value::from(value));
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.fields
+            .insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.fields
+            .insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.fields
+            .insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        } else {
+            self.fields
+                .insert(field.name().to_string(), Value::from(format!("{value:?}")));
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for SinkLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: LayerContext<'_, S>) {
+        let metadata = event.metadata();
+        // Never ship the shipper's own diagnostics or the HTTP stack it drives.
+        if metadata.target().starts_with(module_path!())
+            || metadata.target().starts_with("reqwest")
+            || metadata.target().starts_with("hyper")
+        {
+            return;
+        }
+
+        let Some(shipper) = current_shipper().filter(|shipper| shipper.ship_logs) else {
+            return;
+        };
+        if *metadata.level() > shipper.min_level {
+            return;
+        }
+
+        let mut collector = FieldCollector::default();
+        event.record(&mut collector);
+        collector
+            .fields
+            .insert("target".into(), Value::from(metadata.target()));
+
+        enqueue(
+            &shipper,
+            SinkRecord {
+                timestamp: Utc::now(),
+                kind: "log",
+                level: level_name(*metadata.level()),
+                message: collector.message,
+                fields: collector.fields,
+            },
+        );
+    }
+}
+
+// ── sinks ───────────────────────────────────────────────────────
+
+fn sink_http_client() -> reqwest::Client {
+    crate::config::build_runtime_proxy_client_with_timeouts("observability.sinks", 15, 5)
+}
+
+fn bearer(request: reqwest::RequestBuilder, token: Option<&str>) -> reqwest::RequestBuilder {
+    match token.map(str::trim).filter(|token| !token.is_empty()) {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+async fn check_status(response: reqwest::Response, sink: &str) -> Result<()> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let body = response.text().await.unwrap_or_default();
+    anyhow::bail!(
+        "{sink} sink returned {status}: {}",
+        crate::util::truncate_with_ellipsis(&body, 200)
+    )
+}
+
+/// Grafana Loki push API sink.
+pub struct LokiSink {
+    url: String,
+    labels: std::collections::BTreeMap<String, String>,
+    tenant_id: Option<String>,
+    bearer_token: Option<String>,
+    client: reqwest::Client,
+}
+
+impl LokiSink {
+    pub fn new(config: &LokiSinkConfig) -> Result<Self> {
+        reqwest::Url::parse(config.url.trim()).context("invalid Loki push URL")?;
+        Ok(Self {
+            url: config.url.trim().to_string(),
+            labels: config.labels.clone(),
+            tenant_id: config.tenant_id.clone(),
+            bearer_token: config.bearer_token.clone(),
+            client: sink_http_client(),
+        })
+    }
+
+    fn push_body(&self, batch: &[SinkRecord]) -> Value {
+        let mut streams: std::collections::BTreeMap<(&str, &str), Vec<Value>> =
+            std::collections::BTreeMap::new();
+        for record in batch {
+            let ts = record
+                .timestamp
+                .timestamp_nanos_opt()
+                .unwrap_or_default()
+                .to_string();
+            streams
+                .entry((record.kind, record.level))
+                .or_default()
+                .push(json!([ts, record.to_json_line()]));
+        }
+
+        let streams: Vec<Value> = streams
+            .into_iter()
+            .map(|((kind, level), values)| {
+                let mut labels: Map<String, Value> = self
+                    .labels
+                    .iter()
+                    .map(|(k, v)| (k.clone(), Value::from(v.as_str())))
+                    .collect();
+                labels.insert("kind".into(), Value::from(kind));
+                labels.insert("level".into(), Value::from(level));
+                json!({ "stream": labels, "values": values })
+            })
+            .collect();
+
+        json!({ "streams": streams })
+    }
+}
+
+#[async_trait]
+impl LogSink for LokiSink {
+    fn name(&self) -> &str {
+        "loki"
+    }
+
+    async fn ship(&self, batch: &[SinkRecord]) -> Result<()> {
+        let mut request = self.client.post(&self.url).json(&self.push_body(batch));
+        if let Some(tenant) = self.tenant_id.as_deref() {
+            request = request.header("X-Scope-OrgID", tenant);
+        }
+        let response = bearer(request, self.bearer_token.as_deref()).send().await?;
+        check_status(response, "loki").await
+    }
+}
+
+/// HTTP endpoint receiving newline-delimited JSON.
+pub struct HttpNdjsonSink {
+    url: String,
+    bearer_token: Option<String>,
+    headers: reqwest::header::HeaderMap,
+    client: reqwest::Client,
+}
+
+impl HttpNdjsonSink {
+    pub fn new(config: &HttpSinkConfig) -> Result<Self> {
+        reqwest::Url::parse(config.url.trim()).context("invalid HTTP sink URL")?;
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &config.headers {
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .with_context(|| format!("invalid HTTP sink header name: {name}"))?;
+            let value = reqwest::header::HeaderValue::from_str(value)
+                .with_context(|| format!("invalid HTTP sink header value for {name}"))?;
+            headers.insert(name, value);
+        }
+        Ok(Self {
+            url: config.url.trim().to_string(),
+            bearer_token: config.bearer_token.clone(),
+            headers,
+            client: sink_http_client(),
+        })
+    }
+}
+
+fn ndjson_body(batch: &[SinkRecord]) -> String {
+    batch.iter().fold(String::new(), |mut body, record| {
+        let _ = writeln!(body, "{}", record.to_json_line());
+        body
+    })
+}
+
+#[async_trait]
+impl LogSink for HttpNdjsonSink {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    async fn ship(&self, batch: &[SinkRecord]) -> Result<()> {
+        let request = self
+            .client
+            .post(&self.url)
+            .headers(self.headers.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+            .body(ndjson_body(batch));
+        let response = bearer(request, self.bearer_token.as_deref()).send().await?;
+        check_status(response, "http").await
+    }
+}
+
+/// RFC 5424 syslog sink over UDP or TCP (octet-counted framing, RFC 6587).
+pub struct SyslogSink {
+    address: String,
+    tcp: bool,
+    app_name: String,
+    facility: u8,
+    hostname: String,
+}
+
+impl SyslogSink {
+    pub fn new(config: &SyslogSinkConfig) -> Self {
+        let hostname = hostname::get()
+            .ok()
+            .and_then(|name| name.into_string().ok())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "-".to_string());
+        Self {
+            address: config.address.trim().to_string(),
+            tcp: config.protocol.trim().eq_ignore_ascii_case("tcp"),
+            app_name: config.app_name.clone(),
+            facility: config.facility.min(23),
+            hostname,
+        }
+    }
+
+    fn severity(level: &str) -> u8 {
+        match level {
+            "error" => 3,
+            "warn" => 4,
+            "info" => 6,
+            _ => 7,
+        }
+    }
+
+    fn format(&self, record: &SinkRecord) -> String {
+        let pri = u16::from(self.facility) * 8 + u16::from(Self::severity(record.level));
+        format!(
+            "<{pri}>1 {} {} {} {} {} - {}",
+            record
+                .timestamp
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            self.hostname,
+            self.app_name,
+            std::process::id(),
+            record.kind,
+            record.to_json_line()
+        )
+    }
+}
+
+#[async_trait]
+impl LogSink for SyslogSink {
+    fn name(&self) -> &str {
+        "syslog"
+    }
+
+    async fn ship(&self, batch: &[SinkRecord]) -> Result<()> {
+        if self.tcp {
+            let mut stream = tokio::net::TcpStream::connect(&self.address).await?;
+            let mut payload = String::new();
+            for record in batch {
+                let line = self.format(record);
+                let _ = write!(payload, "{} {line}", line.len());
+            }
+            stream.write_all(payload.as_bytes()).await?;
+            stream.flush().await?;
+        } else {
+            let socket = tokio::net::UdpSocket::bind("0.0.0.0:0").await?;
+            socket.connect(&self.address).await?;
+            for record in batch {
+                socket.send(self.format(record).as_bytes()).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(kind: &'static str, level: &'static str, message: &str) -> SinkRecord {
+        SinkRecord {
+            timestamp: Utc::now(),
+            kind,
+            level,
+            message: message.into(),
+            fields: Map::new(),
+        }
+    }
+
+    struct CollectingSink {
+        batches: Arc<Mutex<Vec<usize>>>,
+        failures_left: AtomicU64,
+    }
+
+    #[async_trait]
+    impl LogSink for CollectingSink {
+        fn name(&self) -> &str {
+            "collect"
+        }
+
+        async fn ship(&self, batch: &[SinkRecord]) -> Result<()> {
+            if self.failures_left.load(Ordering::Relaxed) > 0 {
+                self.failures_left.fetch_sub(1, Ordering::Relaxed);
+                anyhow::bail!("transient");
+            }
+            self.batches.lock().unwrap().push(batch.len());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn loki_push_body_groups_streams_by_kind_and_level() {
+        let sink = LokiSink::new(&LokiSinkConfig {
+            url: "http://loki:3100/loki/api/v1/push".into(),
+            labels: [("job".to_string(), "zeroclaw".to_string())].into(),
+            tenant_id: None,
+            bearer_token: None,
+        })
+        .unwrap();
+
+        let body = sink.push_body(&[
+            record("log", "info", "a"),
+            record("log", "info", "b"),
+            record("trace", "warn", "tool_call_result"),
+        ]);
+        let streams = body["streams"].as_array().unwrap();
+        assert_eq!(streams.len(), 2);
+        assert_eq!(streams[0]["stream"]["job"], "zeroclaw");
+        assert_eq!(streams[0]["stream"]["kind"], "log");
+        assert_eq!(streams[0]["values"].as_array().unwrap().len(), 2);
+        assert_eq!(streams[1]["stream"]["level"], "warn");
+    }
+
+    #[test]
+    fn ndjson_body_emits_one_line_per_record() {
+        let body = ndjson_body(&[record("log", "info", "a"), record("trace", "info", "b")]);
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let parsed: Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(parsed["kind"], "trace");
+        assert_eq!(parsed["message"], "b");
+    }
+
+    #[test]
+    fn syslog_format_uses_rfc5424_priority() {
+        let sink = SyslogSink::new(&SyslogSinkConfig {
+            address: "127.0.0.1:514".into(),
+            protocol: "udp".into(),
+            app_name: "zeroclaw".into(),
+            facility: 16,
+        });
+        let line = sink.format(&record("log", "error", "boom"));
+        // local0 (16) * 8 + error (3)
+        assert!(line.starts_with("<131>1 "));
+        assert!(line.contains(" zeroclaw "));
+        assert!(line.contains("\"message\":\"boom\""));
+    }
+
+    #[tokio::test]
+    async fn syslog_udp_sink_delivers_datagrams() {
+        let receiver = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let sink = SyslogSink::new(&SyslogSinkConfig {
+            address: receiver.local_addr().unwrap().to_string(),
+            protocol: "udp".into(),
+            app_name: "zeroclaw".into(),
+            facility: 1,
+        });
+
+        sink.ship(&[record("log", "info", "hello")]).await.unwrap();
+
+        let mut buf = [0u8; 2048];
+        let len = receiver.recv(&mut buf).await.unwrap();
+        let datagram = String::from_utf8_lossy(&buf[..len]);
+        assert!(datagram.starts_with("<14>1 "));
+        assert!(datagram.contains("hello"));
+    }
+
+    #[tokio::test]
+    async fn worker_batches_and_retries_transient_failures() {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let sink = CollectingSink {
+            batches: Arc::clone(&batches),
+            failures_left: AtomicU64::new(1),
+        };
+        let (tx, rx) = mpsc::channel(16);
+        let worker = tokio::spawn(run_worker(
+            rx,
+            vec![Box::new(sink)],
+            2,
+            Duration::from_millis(20),
+            2,
+            Arc::new(AtomicU64::new(0)),
+        ));
+
+        for i in 0..3 {
+            tx.send(record("log", "info", &format!("m{i}")))
+                .await
+                .unwrap();
+        }
+        drop(tx);
+        worker.await.unwrap();
+
+        assert_eq!(*batches.lock().unwrap(), vec![2, 1]);
+    }
+
+    #[test]
+    fn full_queue_drops_and_counts_records() {
+        let (tx, _rx) = mpsc::channel(1);
+        let shipper = SinkShipper {
+            tx,
+            ship_logs: true,
+            ship_traces: true,
+            min_level: Level::INFO,
+            dropped: Arc::new(AtomicU64::new(0)),
+            worker: Mutex::new(None),
+        };
+
+        enqueue(&shipper, record("log", "info", "kept"));
+        enqueue(&shipper, record("log", "info", "dropped"));
+        assert_eq!(shipper.dropped.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn drain_delivers_records_still_batched() {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let sink = CollectingSink {
+            batches: Arc::clone(&batches),
+            failures_left: AtomicU64::new(0),
+        };
+        let (tx, rx) = mpsc::channel(16);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let worker = tokio::spawn(run_worker(
+            rx,
+            vec![Box::new(sink)],
+            100,
+            Duration::from_secs(3600),
+            0,
+            Arc::clone(&dropped),
+        ));
+        let shipper = Arc::new(SinkShipper {
+            tx,
+            ship_logs: true,
+            ship_traces: true,
+            min_level: Level::INFO,
+            dropped,
+            worker: Mutex::new(Some(worker)),
+        });
+
+        enqueue(&shipper, record("log", "info", "last words"));
+        drain(shipper, Duration::from_secs(5)).await;
+
+        assert_eq!(*batches.lock().unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn build_sinks_is_empty_without_configuration() {
+        let sinks = build_sinks(&ObservabilitySinksConfig::default()).unwrap();
+        assert!(sinks.is_empty());
+    }
+}
@@ -58,6 +58,18 @@ pub const FEATURE_PACKS: &[FeaturePack] = &[
         cargo_features: &["rag-pdf"],
         requires_confirmation: false,
     },
+    FeaturePack {
+        id: "doc-convert",
+        description: "Document conversion and report output as HTML, DOCX and PDF",
+        cargo_features: &["doc-convert"],
+        requires_confirmation: false,
+    },
+    FeaturePack {
+        id: "reports",
+        description: "Templated reports from memory, cost and telemetry data",
+        cargo_features: &["reports"],
+        requires_confirmation: false,
+    },
     FeaturePack {
         id: "sandbox-landlock",
         description: "Linux Landlock sandbox policy",
@@ -70,6 +82,24 @@ pub const FEATURE_PACKS: &[FeaturePack] = &[
         cargo_features: &["peripheral-rpi"],
         requires_confirmation: false,
     },
+    FeaturePack {
+        id: "peripheral-ble",
+        description: "Bluetooth LE thermometers, beacons and presence tags",
+        cargo_features: &["peripheral-ble"],
+        requires_confirmation: false,
+    },
+    FeaturePack {
+        id: "smart-home",
+        description: "Zigbee/Z-Wave devices over MQTT (zigbee2mqtt, zwave-js-ui)",
+        cargo_features: &["mqtt"],
+        requires_confirmation: false,
+    },
+    FeaturePack {
+        id: "voice",
+        description: "Local microphone voice frontend (wake word / push-to-talk)",
+        cargo_features: &["voice"],
+        requires_confirmation: false,
+    },
 ];
 
 /// Built-in onboarding presets.
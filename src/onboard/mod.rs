@@ -1,4 +1,5 @@
 pub mod feature_packs;
+pub mod templates;
 pub mod wizard;
 
 // Re-exported for CLI and external use
@@ -0,0 +1,764 @@
+//! Workspace templates for common roles (`zeroclaw onboard --template`).
+//!
+//! A template goes beyond a preset: besides choosing the preset's feature
+//! packs it provisions delegate personas (`[agents]`), workspace skills,
+//! paused cron jobs, RAG collections with a folder to fill, and commented
+//! channel stubs. Anything the user already has is kept, so applying a
+//! template again only adds what is missing. `zeroclaw checklist` lists the
+//! steps that still need a human: credentials, documents, reviewing jobs.
+
+use crate::config::{Config, DelegateAgentConfig, RagCollectionConfig};
+use crate::cron::{self, CronJob, JobContext, Schedule, SessionTarget};
+use crate::rag::collections::RagCollections;
+use crate::state::StateStore;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// State store key holding the applied template.
+const STATE_KEY: &str = "onboard.template";
+
+const PERSONA_MAX_DEPTH: u32 = 3;
+const PERSONA_MAX_ITERATIONS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateId {
+    Devops,
+    Research,
+    Home,
+    Support,
+}
+
+impl TemplateId {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Devops => "devops",
+            Self::Research => "research",
+            Self::Home => "home",
+            Self::Support => "support",
+        }
+    }
+
+    pub fn template(self) -> &'static WorkspaceTemplate {
+        TEMPLATES
+            .iter()
+            .find(|template| template.id == self)
+            .expect("every template id has a catalog entry")
+    }
+}
+
+/// A delegate sub-agent (`[agents.<name>]`). Personas with tools run agentic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Persona {
+    pub name: &'static str,
+    pub system_prompt: &'static str,
+    pub tools: &'static [&'static str],
+}
+
+/// A `SKILL.md` written to `skills/<name>/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkillSpec {
+    pub name: &'static str,
+    pub content: &'static str,
+}
+
+/// An agent cron job, created paused until the user has reviewed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CronSpec {
+    pub name: &'static str,
+    pub expression: &'static str,
+    pub prompt: &'static str,
+    pub tools: &'static [&'static str],
+}
+
+/// A `[rag.collections.<name>]` entry, filled from `knowledge/<name>/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollectionSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub readers: &'static [&'static str],
+}
+
+/// A commented `[channels_config.<channel>]` section for the user to fill in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelStub {
+    pub channel: &'static str,
+    pub purpose: &'static str,
+    pub snippet: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkspaceTemplate {
+    pub id: TemplateId,
+    pub description: &'static str,
+    /// Onboarding preset used when `--preset`/`--intent` are not given
+    pub preset: &'static str,
+    /// Packs added on top of `preset`
+    pub packs: &'static [&'static str],
+    pub personas: &'static [Persona],
+    pub skills: &'static [SkillSpec],
+    pub cron_jobs: &'static [CronSpec],
+    pub collections: &'static [CollectionSpec],
+    pub channels: &'static [ChannelStub],
+}
+
+const TELEGRAM_STUB: &str = "\
+# [channels_config.telegram]
+# bot_token = \"123456:ABC...\"        # from @BotFather
+# allowed_users = [\"your_username\"]
+";
+
+const SLACK_STUB: &str = "\
+# [channels_config.slack]
+# bot_token = \"xoxb-...\"
+# app_token = \"xapp-...\"             # Socket Mode
+# channel_id = \"C0123456789\"
+# allowed_users = [\"U0123456789\"]
+";
+
+const EMAIL_STUB: &str = "\
+# [channels_config.email]
+# imap_host = \"imap.example.com\"
+# smtp_host = \"smtp.example.com\"
+# username = \"support@example.com\"
+# password = \"...\"
+# from_address = \"support@example.com\"
+# allowed_senders = [\"*\"]
+";
+
+/// Built-in workspace templates.
+pub const TEMPLATES: &[WorkspaceTemplate] = &[
+    WorkspaceTemplate {
+        id: TemplateId::Devops,
+        description: "On-call and infrastructure operations",
+        preset: "hardened-linux",
+        packs: &[],
+        personas: &[
+            Persona {
+                name: "incident-commander",
+                system_prompt: "You coordinate incident response. Establish impact and timeline first, check the runbooks collection before improvising, propose one change at a time with its rollback, and keep a running incident log.",
+                tools: &["shell", "file_read", "content_search", "git_operations", "http_request", "memory_recall"],
+            },
+            Persona {
+                name: "change-reviewer",
+                system_prompt: "You review infrastructure changes (diffs, plans, manifests) before they ship. Point out blast radius, missing rollback steps, secrets in plain text and changes that skip staging. Be brief and concrete.",
+                tools: &[],
+            },
+        ],
+        skills: &[
+            SkillSpec {
+                name: "incident-response",
+                content: "# Incident response\n\nFollow this when the user reports an outage or alert.\n\n1. Ask for (or find) the affected service, first alert time and user impact.\n2. Search the `runbooks` collection for the service before running anything.\n3. Prefer read-only checks (status, logs, metrics) until the cause is clear.\n4. Propose each fix with its rollback and wait for approval.\n5. Keep a timestamped log and finish with a short post-incident summary.\n",
+            },
+            SkillSpec {
+                name: "deploy-checklist",
+                content: "# Deploy checklist\n\nWalk through this before any production deploy.\n\n- Change reviewed and CI green on the exact commit.\n- Database migrations are backward compatible or scheduled separately.\n- Rollback command written down and tested in staging.\n- Dashboards and alerts for the service are open.\n- Announce start and end in the ops channel.\n",
+            },
+        ],
+        cron_jobs: &[
+            CronSpec {
+                name: "morning-health-report",
+                expression: "0 8 * * 1-5",
+                prompt: "Check the health endpoints and services listed in the runbooks collection and summarize anything degraded since yesterday, most urgent first.",
+                tools: &["http_request", "shell", "memory_recall"],
+            },
+            CronSpec {
+                name: "weekly-dependency-audit",
+                expression: "0 9 * * 1",
+                prompt: "List outdated or vulnerable dependencies in the workspace repositories and suggest which upgrades to schedule this week.",
+                tools: &["shell", "file_read", "content_search"],
+            },
+        ],
+        collections: &[CollectionSpec {
+            name: "runbooks",
+            description: "Runbooks, architecture notes and on-call docs",
+            readers: &["cli", "daemon", "slack:*"],
+        }],
+        channels: &[
+            ChannelStub {
+                channel: "slack",
+                purpose: "Ops channel for alerts and incident threads",
+                snippet: SLACK_STUB,
+            },
+            ChannelStub {
+                channel: "telegram",
+                purpose: "Paging the on-call engineer",
+                snippet: TELEGRAM_STUB,
+            },
+        ],
+    },
+    WorkspaceTemplate {
+        id: TemplateId::Research,
+        description: "Literature review and note-taking",
+        preset: "automation",
+        packs: &["rag-pdf"],
+        personas: &[
+            Persona {
+                name: "literature-reviewer",
+                system_prompt: "You find and summarize sources on a research question. Prefer primary sources, record full citations, separate what a source claims from what it shows, and store key findings in memory.",
+                tools: &["web_search_tool", "fetch_url", "pdf_read", "memory_store", "memory_recall"],
+            },
+            Persona {
+                name: "critic",
+                system_prompt: "You review drafts and arguments as a skeptical peer reviewer: unsupported claims, missing counter-evidence, weak methods and overstated conclusions. Suggest the smallest fix for each.",
+                tools: &[],
+            },
+        ],
+        skills: &[
+            SkillSpec {
+                name: "literature-review",
+                content: "# Literature review\n\nUse this when the user asks what is known about a topic.\n\n1. Restate the question and agree on scope (years, fields, source types).\n2. Search the `papers` collection first, then the web.\n3. For each source note: citation, method, main finding, limitations.\n4. Group findings by theme and flag where sources disagree.\n5. End with open questions and the next sources worth reading.\n",
+            },
+            SkillSpec {
+                name: "source-citation",
+                content: "# Citing sources\n\nEvery factual claim in a research answer cites its source.\n\n- Use author, year, title and a link or DOI.\n- Quote sparingly and mark quotes clearly.\n- Say so when a claim comes from your own reasoning rather than a source.\n",
+            },
+        ],
+        cron_jobs: &[CronSpec {
+            name: "weekly-reading-digest",
+            expression: "0 9 * * 1",
+            prompt: "Search for new papers and articles on the research topics stored in memory, and write a short digest of the five most relevant with one-line summaries and links.",
+            tools: &["web_search_tool", "fetch_url", "memory_recall"],
+        }],
+        collections: &[
+            CollectionSpec {
+                name: "papers",
+                description: "Papers and articles to draw on",
+                readers: &["cli"],
+            },
+            CollectionSpec {
+                name: "notes",
+                description: "Your own research notes and drafts",
+                readers: &["cli"],
+            },
+        ],
+        channels: &[ChannelStub {
+            channel: "telegram",
+            purpose: "Reading digests and quick questions on the go",
+            snippet: TELEGRAM_STUB,
+        }],
+    },
+    WorkspaceTemplate {
+        id: TemplateId::Home,
+        description: "Household planning and home automation",
+        preset: "default",
+        packs: &[],
+        personas: &[Persona {
+            name: "household-planner",
+            system_prompt: "You help run a household: meals, shopping, chores, appointments and home maintenance. Keep plans realistic, remember preferences and allergies, and confirm before changing devices or calendars.",
+            tools: &["memory_recall", "memory_store", "calendar", "smart_home_devices", "smart_home_sensor"],
+        }],
+        skills: &[
+            SkillSpec {
+                name: "meal-planning",
+                content: "# Meal planning\n\nUse this when the user asks for a meal plan or shopping list.\n\n1. Recall dietary preferences, allergies and household size from memory.\n2. Plan around what is already in stock when the user says so.\n3. Reuse ingredients across meals to cut waste.\n4. Finish with a shopping list grouped by store section.\n",
+            },
+            SkillSpec {
+                name: "home-maintenance",
+                content: "# Home maintenance\n\nTrack recurring tasks (filters, smoke alarms, gutters, servicing).\n\n- Check the `household` collection for manuals and warranty dates.\n- Store each task with its interval and last-done date in memory.\n- When asked what is due, list overdue items first.\n",
+            },
+        ],
+        cron_jobs: &[
+            CronSpec {
+                name: "morning-briefing",
+                expression: "0 7 * * *",
+                prompt: "Give a short morning briefing: today's appointments, chores due, and anything stored in memory the household should not forget.",
+                tools: &["memory_recall", "calendar"],
+            },
+            CronSpec {
+                name: "weekly-chores",
+                expression: "0 18 * * 0",
+                prompt: "Plan next week's chores and meals from the preferences in memory and list what needs buying.",
+                tools: &["memory_recall"],
+            },
+        ],
+        collections: &[CollectionSpec {
+            name: "household",
+            description: "Appliance manuals, warranties and house documents",
+            readers: &["cli", "telegram:*"],
+        }],
+        channels: &[ChannelStub {
+            channel: "telegram",
+            purpose: "Family chat for briefings and reminders",
+            snippet: TELEGRAM_STUB,
+        }],
+    },
+    WorkspaceTemplate {
+        id: TemplateId::Support,
+        description: "Customer support desk",
+        preset: "minimal",
+        packs: &[],
+        personas: &[
+            Persona {
+                name: "support-agent",
+                system_prompt: "You answer customer questions from the support knowledge base. Be friendly and precise, never invent policies or prices, and hand over to a human when the answer is not in the knowledge base or the customer is upset.",
+                tools: &["memory_recall", "contacts"],
+            },
+            Persona {
+                name: "escalation-triage",
+                system_prompt: "You triage support conversations for escalation: classify urgency and topic, summarize the customer's problem in three lines, and say which team should own it.",
+                tools: &[],
+            },
+        ],
+        skills: &[
+            SkillSpec {
+                name: "ticket-triage",
+                content: "# Ticket triage\n\nApply to every new support request.\n\n1. Classify: question, bug report, billing, account access, complaint.\n2. Set urgency: outage or data loss is urgent; blocked work is high.\n3. Answer from the `support-kb` collection when it covers the request.\n4. Otherwise summarize the case and escalate to a human.\n",
+            },
+            SkillSpec {
+                name: "reply-style",
+                content: "# Reply style\n\nHow support replies are written.\n\n- Greet by name when known, and get to the answer in the first sentence.\n- Use short paragraphs and numbered steps for instructions.\n- Link the knowledge-base article you used.\n- Never promise refunds, dates or features; offer to check with the team.\n",
+            },
+        ],
+        cron_jobs: &[CronSpec {
+            name: "daily-support-summary",
+            expression: "0 17 * * 1-5",
+            prompt: "Summarize today's support conversations stored in memory: volume by topic, unresolved cases, and questions the knowledge base could not answer.",
+            tools: &["memory_recall"],
+        }],
+        collections: &[CollectionSpec {
+            name: "support-kb",
+            description: "Help-center articles, policies and product FAQs",
+            readers: &["*"],
+        }],
+        channels: &[
+            ChannelStub {
+                channel: "email",
+                purpose: "Support inbox customers write to",
+                snippet: EMAIL_STUB,
+            },
+            ChannelStub {
+                channel: "slack",
+                purpose: "Internal channel for escalations",
+                snippet: SLACK_STUB,
+            },
+        ],
+    },
+];
+
+/// Folder whose files feed `collection`.
+pub fn knowledge_dir(config: &Config, collection: &str) -> PathBuf {
+    config.workspace_dir.join("knowledge").join(collection)
+}
+
+/// File holding the template's commented channel sections.
+pub fn channel_stub_path(config: &Config, id: TemplateId) -> PathBuf {
+    config
+        .workspace_dir
+        .join("templates")
+        .join(id.as_str())
+        .join("channels.toml")
+}
+
+/// What applying a template did.
+#[derive(Debug, Default)]
+pub struct Applied {
+    /// Things that were added
+    pub created: Vec<String>,
+    /// Things that already existed and were left alone
+    pub kept: Vec<String>,
+}
+
+/// Provision `id` into `config` and its workspace, then save the config.
+pub async fn apply(config: &mut Config, id: TemplateId) -> Result<Applied> {
+    let template = id.template();
+    let mut applied = Applied::default();
+
+    let provider = config
+        .default_provider
+        .clone()
+        .unwrap_or_else(|| "openrouter".to_string());
+    let model = config
+        .default_model
+        .clone()
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".to_string());
+    for persona in template.personas {
+        let label = format!("persona `{}`", persona.name);
+        if config.agents.contains_key(persona.name) {
+            applied.kept.push(label);
+            continue;
+        }
+        config.agents.insert(
+            persona.name.to_string(),
+            DelegateAgentConfig {
+                provider: provider.clone(),
+                model: model.clone(),
+                system_prompt: Some(persona.system_prompt.to_string()),
+                api_key: None,
+                temperature: None,
+                max_depth: PERSONA_MAX_DEPTH,
+                agentic: !persona.tools.is_empty(),
+                allowed_tools: persona.tools.iter().map(|tool| tool.to_string()).collect(),
+                max_iterations: PERSONA_MAX_ITERATIONS,
+            },
+        );
+        applied.created.push(label);
+    }
+
+    for collection in template.collections {
+        let label = format!("RAG collection `{}`", collection.name);
+        let dir = knowledge_dir(config, collection.name);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        if config.rag.collections.contains_key(collection.name) {
+            applied.kept.push(label);
+            continue;
+        }
+        config.rag.collections.insert(
+            collection.name.to_string(),
+            RagCollectionConfig {
+                description: collection.description.to_string(),
+                readers: collection
+                    .readers
+                    .iter()
+                    .map(|reader| reader.to_string())
+                    .collect(),
+                ..RagCollectionConfig::default()
+            },
+        );
+        applied.created.push(label);
+    }
+    config.save().await?;
+
+    crate::skills::init_skills_dir(&config.workspace_dir)?;
+    for skill in template.skills {
+        let label = format!("skill `{}`", skill.name);
+        let dir = crate::skills::skills_dir(&config.workspace_dir).join(skill.name);
+        let path = dir.join("SKILL.md");
+        if dir.exists() {
+            applied.kept.push(label);
+            continue;
+        }
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(&path, skill.content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        applied.created.push(label);
+    }
+
+    let jobs = cron::list_jobs(config)?;
+    for spec in template.cron_jobs {
+        let label = format!("cron job `{}` (paused)", spec.name);
+        if find_job(&jobs, spec.name).is_some() {
+            applied.kept.push(label);
+            continue;
+        }
+        let job = cron::add_agent_job(
+            config,
+            Some(spec.name.to_string()),
+            Schedule::Cron {
+                expr: spec.expression.to_string(),
+                tz: None,
+            },
+            spec.prompt,
+            SessionTarget::Isolated,
+            None,
+            JobContext {
+                memory: None,
+                tools: spec.tools.iter().map(|tool| tool.to_string()).collect(),
+            },
+            None,
+            false,
+        )?;
+        cron::pause_job(config, &job.id)?;
+        applied.created.push(label);
+    }
+
+    if !template.channels.is_empty() {
+        let path = channel_stub_path(config, id);
+        let label = format!("channel stubs in {}", path.display());
+        if path.exists() {
+            applied.kept.push(label);
+        } else {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, channel_stubs(template))
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            applied.created.push(label);
+        }
+    }
+
+    StateStore::for_config(config).set(
+        STATE_KEY,
+        &id,
+        &format!("apply template {}", id.as_str()),
+    )?;
+    Ok(applied)
+}
+
+fn channel_stubs(template: &WorkspaceTemplate) -> String {
+    let mut out = format!(
+        "# Channel wiring for the {} template.\n\
+         # Fill in a section, uncomment it and copy it into config.toml, then\n\
+         # run `zeroclaw checklist` to confirm.\n",
+        template.id.as_str()
+    );
+    for stub in template.channels {
+        let _ = write!(out, "\n# {}\n{}", stub.purpose, stub.snippet);
+    }
+    out
+}
+
+fn find_job<'a>(jobs: &'a [CronJob], name: &str) -> Option<&'a CronJob> {
+    jobs.iter().find(|job| job.name.as_deref() == Some(name))
+}
+
+/// One line of the post-setup checklist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecklistItem {
+    pub done: bool,
+    pub task: String,
+    /// What to do next when not done
+    pub hint: Option<String>,
+}
+
+impl ChecklistItem {
+    fn new(done: bool, task: String, hint: impl FnOnce() -> String) -> Self {
+        let hint = (!done).then(hint);
+        Self { done, task, hint }
+    }
+}
+
+/// The template recorded by the last `zeroclaw onboard --template`.
+pub fn applied_template(config: &Config) -> Result<Option<TemplateId>> {
+    StateStore::for_config(config).get(STATE_KEY)
+}
+
+/// What is done and what is left for `id` in this config and workspace.
+pub async fn checklist(config: &Config, id: TemplateId) -> Result<Vec<ChecklistItem>> {
+    let template = id.template();
+    let reapply = || format!("Run `zeroclaw onboard --template {}` again", id.as_str());
+    let mut items = Vec::new();
+
+    for persona in template.personas {
+        items.push(ChecklistItem::new(
+            config.agents.contains_key(persona.name),
+            format!("Persona `{}` in [agents]", persona.name),
+            reapply,
+        ));
+    }
+    for skill in template.skills {
+        let path = crate::skills::skills_dir(&config.workspace_dir)
+            .join(skill.name)
+            .join("SKILL.md");
+        items.push(ChecklistItem::new(
+            path.exists(),
+            format!("Skill `{}`", skill.name),
+            reapply,
+        ));
+    }
+
+    let collections = RagCollections::from_config(config)?;
+    for spec in template.collections {
+        let task = format!("Documents in RAG collection `{}`", spec.name);
+        let Some(collection) = collections
+            .as_ref()
+            .and_then(|collections| collections.get(spec.name).ok())
+        else {
+            items.push(ChecklistItem::new(false, task, reapply));
+            continue;
+        };
+        let documents = collection.documents().await?.len();
+        let dir = knowledge_dir(config, spec.name);
+        items.push(ChecklistItem::new(documents > 0, task, || {
+            format!(
+                "Add files to {dir}, then run `zeroclaw rag ingest {name} {dir}`",
+                dir = dir.display(),
+                name = spec.name
+            )
+        }));
+    }
+
+    let jobs = cron::list_jobs(config)?;
+    if !template.cron_jobs.is_empty() && !config.cron.enabled {
+        items.push(ChecklistItem::new(
+            false,
+            "Cron enabled".to_string(),
+            || "Set [cron].enabled = true".to_string(),
+        ));
+    }
+    for spec in template.cron_jobs {
+        let task = format!("Cron job `{}` reviewed and resumed", spec.name);
+        match find_job(&jobs, spec.name) {
+            Some(job) => items.push(ChecklistItem::new(job.enabled, task, || {
+                format!(
+                    "Check its prompt with `zeroclaw cron list`, then `zeroclaw cron resume {}`",
+                    job.id
+                )
+            })),
+            None => items.push(ChecklistItem::new(false, task, reapply)),
+        }
+    }
+
+    let configured: Vec<&'static str> = config
+        .channels_config
+        .channels_except_webhook()
+        .into_iter()
+        .filter(|(_, configured)| *configured)
+        .map(|(handle, _)| handle.name())
+        .collect();
+    let stubs = channel_stub_path(config, id);
+    for stub in template.channels {
+        let done = configured
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(stub.channel));
+        items.push(ChecklistItem::new(
+            done,
+            format!("Channel `{}`: {}", stub.channel, stub.purpose),
+            || {
+                format!(
+                    "Fill in [channels_config.{}] from {} and add it to {}",
+                    stub.channel,
+                    stubs.display(),
+                    config.config_path.display()
+                )
+            },
+        ));
+    }
+
+    Ok(items)
+}
+
+/// Print the checklist for the applied template (`zeroclaw checklist`).
+pub async fn print_checklist(config: &Config) -> Result<()> {
+    let Some(id) = applied_template(config)? else {
+        println!("No workspace template applied yet.");
+        println!(
+            "Apply one with `zeroclaw onboard --template <{}>`.",
+            TEMPLATES
+                .iter()
+                .map(|template| template.id.as_str())
+                .collect::<Vec<_>>()
+                .join("|")
+        );
+        return Ok(());
+    };
+    let items = checklist(config, id).await?;
+    let done = items.iter().filter(|item| item.done).count();
+    println!(
+        "{}",
+        style(format!(
+            "📋 {} workspace checklist ({done}/{} done)",
+            id.as_str(),
+            items.len()
+        ))
+        .bold()
+    );
+    for item in &items {
+        let mark = if item.done { "✅" } else { "⬜" };
+        println!("  {mark} {}", item.task);
+        if let Some(hint) = &item.hint {
+            println!("       {}", style(hint).dim());
+        }
+    }
+    if done == items.len() {
+        println!("\nAll set. Start everything with `zeroclaw daemon`.");
+    }
+    Ok(())
+}
+
+/// Print what `apply` did.
+pub fn print_applied(id: TemplateId, applied: &Applied) {
+    println!(
+        "{}",
+        style(format!(
+            "🧩 Applied the {} workspace template ({})",
+            id.as_str(),
+            id.template().description
+        ))
+        .bold()
+    );
+    for item in &applied.created {
+        println!("  + {item}");
+    }
+    for item in &applied.kept {
+        println!("  = {item} (already present, kept)");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn catalog_covers_every_id_and_references_known_presets() {
+        let mut ids = HashSet::new();
+        for template in TEMPLATES {
+            assert!(ids.insert(template.id), "duplicate {:?}", template.id);
+            assert!(
+                super::super::preset_by_id(template.preset).is_some(),
+                "{} uses unknown preset {}",
+                template.id.as_str(),
+                template.preset
+            );
+            for pack in template.packs {
+                assert!(super::super::feature_pack_by_id(pack).is_some(), "{pack}");
+            }
+            for job in template.cron_jobs {
+                cron::normalize_expression(job.expression).unwrap();
+            }
+            for collection in template.collections {
+                for reader in collection.readers {
+                    assert!(
+                        crate::rag::collections::validate_reader(reader).is_none(),
+                        "{reader}"
+                    );
+                }
+            }
+        }
+        assert_eq!(ids.len(), TemplateId::value_variants().len());
+    }
+
+    #[tokio::test]
+    async fn apply_provisions_once_and_checklist_tracks_what_is_left() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+
+        let applied = apply(&mut config, TemplateId::Devops).await.unwrap();
+        assert!(applied.kept.is_empty(), "{:?}", applied.kept);
+        assert!(config.agents["incident-commander"].agentic);
+        assert!(!config.agents["change-reviewer"].agentic);
+        assert!(config
+            .workspace_dir
+            .join("skills/incident-response/SKILL.md")
+            .exists());
+        assert!(knowledge_dir(&config, "runbooks").is_dir());
+        let stubs =
+            std::fs::read_to_string(channel_stub_path(&config, TemplateId::Devops)).unwrap();
+        assert!(stubs.contains("# [channels_config.slack]"));
+        let jobs = cron::list_jobs(&config).unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert!(jobs.iter().all(|job| !job.enabled));
+        let saved = std::fs::read_to_string(&config.config_path).unwrap();
+        assert!(saved.contains("incident-commander"));
+
+        let again = apply(&mut config, TemplateId::Devops).await.unwrap();
+        assert!(again.created.is_empty(), "{:?}", again.created);
+        assert_eq!(cron::list_jobs(&config).unwrap().len(), 2);
+        assert_eq!(applied_template(&config).unwrap(), Some(TemplateId::Devops));
+
+        let items = checklist(&config, TemplateId::Devops).await.unwrap();
+        let open: Vec<&str> = items
+            .iter()
+            .filter(|item| !item.done)
+            .map(|item| item.task.as_str())
+            .collect();
+        assert_eq!(open.len(), 5, "{open:?}");
+        assert!(open[0].contains("runbooks"));
+
+        let job = &jobs[0];
+        cron::resume_job(&config, &job.id).unwrap();
+        let items = checklist(&config, TemplateId::Devops).await.unwrap();
+        assert_eq!(items.iter().filter(|item| !item.done).count(), 4);
+    }
+}
@@ -5,7 +5,8 @@ use crate::config::schema::{
 use crate::config::{
     AutonomyConfig, BrowserConfig, ChannelsConfig, ComposioConfig, Config, DiscordConfig,
     HeartbeatConfig, IMessageConfig, LarkConfig, MatrixConfig, MemoryConfig, ObservabilityConfig,
-    RuntimeConfig, SecretsConfig, SlackConfig, StorageConfig, TelegramConfig, WebhookConfig,
+    RuntimeConfig, SecretsConfig, SlackConfig, StorageConfig, TelegramConfig, VectorIndexConfig,
+    WebhookConfig,
 };
 use crate::hardware::{self, HardwareConfig};
 use crate::memory::{
@@ -14,6 +15,7 @@ use crate::memory::{
 use crate::onboard::feature_packs::{
     feature_pack_by_id, preset_by_id, FeaturePack, FEATURE_PACKS, PRESETS,
 };
+use crate::providers::capabilities::{self, ModelCapabilities};
 use crate::providers::{
     canonical_china_provider_name, is_glm_alias, is_glm_cn_alias, is_minimax_alias,
     is_moonshot_alias, is_qianfan_alias, is_qwen_alias, is_qwen_oauth_alias, is_zai_alias,
@@ -26,7 +28,7 @@ use console::style;
 use dialoguer::{Confirm, Input, MultiSelect, Select};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -315,6 +317,7 @@ pub fn recommend_security_profile(
     if pack_set.contains("hardware-core")
         || pack_set.contains("probe-rs")
         || pack_set.contains("peripheral-rpi")
+        || pack_set.contains("peripheral-ble")
     {
         balanced_score += 2;
         push_unique_reason(
@@ -746,7 +749,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
     match resolve_interactive_onboarding_mode(&config_path, force)? {
         InteractiveOnboardingMode::FullOnboarding => {}
         InteractiveOnboardingMode::UpdateProviderOnly => {
-            return run_provider_update_wizard(&workspace_dir, &config_path).await;
+            return Box::pin(run_provider_update_wizard(&workspace_dir, &config_path)).await;
         }
     }
 
@@ -785,6 +788,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
     let config = Config {
         workspace_dir: workspace_dir.clone(),
         config_path: config_path.clone(),
+        schema_version: crate::config::migrations::CURRENT_SCHEMA_VERSION,
         api_key: if api_key.is_empty() {
             None
         } else {
@@ -799,33 +803,65 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         security: crate::config::SecurityConfig::default(),
         runtime: RuntimeConfig::default(),
         reliability: crate::config::ReliabilityConfig::default(),
+        shadow: crate::config::ShadowConfig::default(),
+        custom_providers: std::collections::HashMap::new(),
+        provider_plugins: std::collections::HashMap::new(),
         scheduler: crate::config::schema::SchedulerConfig::default(),
         agent: crate::config::schema::AgentConfig::default(),
         skills: crate::config::SkillsConfig::default(),
         model_routes: Vec::new(),
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
+        questions: crate::config::QuestionsConfig::default(),
+        queues: crate::config::QueuesConfig::default(),
+        quotas: crate::config::QuotasConfig::default(),
+        telemetry: crate::config::TelemetryConfig::default(),
+        triage: crate::config::TriageConfig::default(),
+        inbox: crate::config::InboxConfig::default(),
+        calendar: crate::config::CalendarConfig::default(),
+        rag: crate::config::RagConfig::default(),
+        research: crate::config::ResearchConfig::default(),
+        recordings: crate::config::RecordingsConfig::default(),
+        tool_schemas: crate::config::ToolSchemasConfig::default(),
+        tool_validation: crate::config::ToolValidationConfig::default(),
+        artifacts: crate::config::ArtifactsConfig::default(),
+        pins: crate::config::PinsConfig::default(),
+        coordination: crate::config::CoordinationConfig::default(),
+        retention: crate::config::DataRetentionConfig::default(),
+        code: crate::config::CodeConfig::default(),
         cron: crate::config::CronConfig::default(),
         channels_config,
         memory: memory_config, // User-selected memory backend
         storage: StorageConfig::default(),
         tunnel: tunnel_config,
         gateway: crate::config::GatewayConfig::default(),
+        control: crate::config::ControlConfig::default(),
         composio: composio_config,
         secrets: secrets_config,
         browser: BrowserConfig::default(),
         http_request: crate::config::HttpRequestConfig::default(),
         multimodal: crate::config::MultimodalConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
+        fetch_url: crate::config::FetchUrlConfig::default(),
+        graphql: crate::config::GraphqlConfig::default(),
+        email_send: crate::config::EmailSendConfig::default(),
+        firmware_build: crate::config::FirmwareBuildConfig::default(),
         proxy: crate::config::ProxyConfig::default(),
         identity: crate::config::IdentityConfig::default(),
         cost: crate::config::CostConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
+        federation: crate::config::FederationConfig::default(),
+        peers: crate::config::PeersConfig::default(),
+        smart_home: crate::config::SmartHomeConfig::default(),
+        camera: crate::config::CameraConfig::default(),
         agents: std::collections::HashMap::new(),
         hooks: crate::config::HooksConfig::default(),
         hardware: hardware_config,
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        translation: crate::config::TranslationConfig::default(),
+        voice: crate::config::VoiceConfig::default(),
+        ui: crate::config::UiConfig::default(),
     };
 
     let security_label = security_profile_label(&config.autonomy);
@@ -908,7 +944,7 @@ pub async fn run_channels_repair_wizard() -> Result<Config> {
     );
     println!();
 
-    let mut config = Config::load_or_init().await?;
+    let mut config = Box::pin(Config::load_or_init()).await?;
 
     print_step(1, 1, "Channels (How You Talk to ZeroClaw)");
     config.channels_config = setup_channels()?;
@@ -1069,6 +1105,7 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         snapshot_on_hygiene: false,
         auto_hydrate: true,
         sqlite_open_timeout_secs: None,
+        vector_index: VectorIndexConfig::default(),
     }
 }
 
@@ -1089,7 +1126,7 @@ pub async fn run_quick_setup(
         .map(|u| u.home_dir().to_path_buf())
         .context("Could not find home directory")?;
 
-    run_quick_setup_with_home_with_selection(
+    Box::pin(run_quick_setup_with_home_with_selection(
         credential_override,
         provider,
         model_override,
@@ -1101,7 +1138,7 @@ pub async fn run_quick_setup(
         yes_security_risk,
         force,
         &home,
-    )
+    ))
     .await
 }
 
@@ -1137,7 +1174,7 @@ async fn run_quick_setup_with_home(
     home: &Path,
 ) -> Result<Config> {
     let extra_pack_ids: Vec<String> = Vec::new();
-    run_quick_setup_with_home_with_selection(
+    Box::pin(run_quick_setup_with_home_with_selection(
         credential_override,
         provider,
         model_override,
@@ -1149,7 +1186,7 @@ async fn run_quick_setup_with_home(
         false,
         force,
         home,
-    )
+    ))
     .await
 }
 
@@ -1222,6 +1259,7 @@ async fn run_quick_setup_with_home_with_selection(
     let config = Config {
         workspace_dir: workspace_dir.clone(),
         config_path: config_path.clone(),
+        schema_version: crate::config::migrations::CURRENT_SCHEMA_VERSION,
         api_key: credential_override.map(|c| {
             let mut s = String::with_capacity(c.len());
             s.push_str(c);
@@ -1236,33 +1274,65 @@ async fn run_quick_setup_with_home_with_selection(
         security: crate::config::SecurityConfig::default(),
         runtime: RuntimeConfig::default(),
         reliability: crate::config::ReliabilityConfig::default(),
+        shadow: crate::config::ShadowConfig::default(),
+        custom_providers: std::collections::HashMap::new(),
+        provider_plugins: std::collections::HashMap::new(),
         scheduler: crate::config::schema::SchedulerConfig::default(),
         agent: crate::config::schema::AgentConfig::default(),
         skills: crate::config::SkillsConfig::default(),
         model_routes: Vec::new(),
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
+        questions: crate::config::QuestionsConfig::default(),
+        queues: crate::config::QueuesConfig::default(),
+        quotas: crate::config::QuotasConfig::default(),
+        telemetry: crate::config::TelemetryConfig::default(),
+        triage: crate::config::TriageConfig::default(),
+        inbox: crate::config::InboxConfig::default(),
+        calendar: crate::config::CalendarConfig::default(),
+        rag: crate::config::RagConfig::default(),
+        research: crate::config::ResearchConfig::default(),
+        recordings: crate::config::RecordingsConfig::default(),
+        tool_schemas: crate::config::ToolSchemasConfig::default(),
+        tool_validation: crate::config::ToolValidationConfig::default(),
+        artifacts: crate::config::ArtifactsConfig::default(),
+        pins: crate::config::PinsConfig::default(),
+        coordination: crate::config::CoordinationConfig::default(),
+        retention: crate::config::DataRetentionConfig::default(),
+        code: crate::config::CodeConfig::default(),
         cron: crate::config::CronConfig::default(),
         channels_config: ChannelsConfig::default(),
         memory: memory_config,
         storage: StorageConfig::default(),
         tunnel: crate::config::TunnelConfig::default(),
         gateway: crate::config::GatewayConfig::default(),
+        control: crate::config::ControlConfig::default(),
         composio: ComposioConfig::default(),
         secrets: SecretsConfig::default(),
         browser: BrowserConfig::default(),
         http_request: crate::config::HttpRequestConfig::default(),
         multimodal: crate::config::MultimodalConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
+        fetch_url: crate::config::FetchUrlConfig::default(),
+        graphql: crate::config::GraphqlConfig::default(),
+        email_send: crate::config::EmailSendConfig::default(),
+        firmware_build: crate::config::FirmwareBuildConfig::default(),
         proxy: crate::config::ProxyConfig::default(),
         identity: crate::config::IdentityConfig::default(),
         cost: crate::config::CostConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
+        federation: crate::config::FederationConfig::default(),
+        peers: crate::config::PeersConfig::default(),
+        smart_home: crate::config::SmartHomeConfig::default(),
+        camera: crate::config::CameraConfig::default(),
         agents: std::collections::HashMap::new(),
         hooks: crate::config::HooksConfig::default(),
         hardware: crate::config::HardwareConfig::default(),
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        translation: crate::config::TranslationConfig::default(),
+        voice: crate::config::VoiceConfig::default(),
+        ui: crate::config::UiConfig::default(),
     };
 
     config.save().await?;
@@ -2032,11 +2102,27 @@ fn parse_ollama_model_ids(payload: &Value) -> Vec<String> {
     normalize_model_ids(ids)
 }
 
+/// Model ids from a live catalog, plus any per-model capabilities it reports.
+#[derive(Debug, Default)]
+struct LiveModelCatalog {
+    models: Vec<String>,
+    capabilities: BTreeMap<String, ModelCapabilities>,
+}
+
+impl LiveModelCatalog {
+    fn from_ids(models: Vec<String>) -> Self {
+        Self {
+            models,
+            capabilities: BTreeMap::new(),
+        }
+    }
+}
+
 fn fetch_openai_compatible_models(
     endpoint: &str,
     api_key: Option<&str>,
     allow_unauthenticated: bool,
-) -> Result<Vec<String>> {
+) -> Result<LiveModelCatalog> {
     let client = build_model_fetch_client()?;
     let mut request = client.get(endpoint);
 
@@ -2053,10 +2139,13 @@ fn fetch_openai_compatible_models(
         .json()
         .context("failed to parse model list response")?;
 
-    Ok(parse_openai_compatible_model_ids(&payload))
+    Ok(LiveModelCatalog {
+        models: parse_openai_compatible_model_ids(&payload),
+        capabilities: capabilities::parse_catalog(&payload),
+    })
 }
 
-fn fetch_openrouter_models(api_key: Option<&str>) -> Result<Vec<String>> {
+fn fetch_openrouter_models(api_key: Option<&str>) -> Result<LiveModelCatalog> {
     let client = build_model_fetch_client()?;
     let mut request = client.get("https://openrouter.ai/api/v1/models");
     if let Some(api_key) = api_key {
@@ -2070,10 +2159,13 @@ fn fetch_openrouter_models(api_key: Option<&str>) -> Result<Vec<String>> {
         .json()
         .context("failed to parse OpenRouter model list response")?;
 
-    Ok(parse_openai_compatible_model_ids(&payload))
+    Ok(LiveModelCatalog {
+        models: parse_openai_compatible_model_ids(&payload),
+        capabilities: capabilities::parse_catalog(&payload),
+    })
 }
 
-fn fetch_anthropic_models(api_key: Option<&str>) -> Result<Vec<String>> {
+fn fetch_anthropic_models(api_key: Option<&str>) -> Result<LiveModelCatalog> {
     let Some(api_key) = api_key else {
         bail!("Anthropic model fetch requires API key or OAuth token");
     };
@@ -2105,10 +2197,12 @@ fn fetch_anthropic_models(api_key: Option<&str>) -> Result<Vec<String>> {
         .json()
         .context("failed to parse Anthropic model list response")?;
 
-    Ok(parse_openai_compatible_model_ids(&payload))
+    Ok(LiveModelCatalog::from_ids(
+        parse_openai_compatible_model_ids(&payload),
+    ))
 }
 
-fn fetch_gemini_models(api_key: Option<&str>) -> Result<Vec<String>> {
+fn fetch_gemini_models(api_key: Option<&str>) -> Result<LiveModelCatalog> {
     let Some(api_key) = api_key else {
         bail!("Gemini model fetch requires API key");
     };
@@ -2123,7 +2217,10 @@ fn fetch_gemini_models(api_key: Option<&str>) -> Result<Vec<String>> {
         .json()
         .context("failed to parse Gemini model list response")?;
 
-    Ok(parse_gemini_model_ids(&payload))
+    Ok(LiveModelCatalog {
+        models: parse_gemini_model_ids(&payload),
+        capabilities: capabilities::parse_catalog(&payload),
+    })
 }
 
 fn fetch_ollama_models() -> Result<Vec<String>> {
@@ -2198,7 +2295,7 @@ fn fetch_live_models_for_provider(
     provider_name: &str,
     api_key: &str,
     provider_api_url: Option<&str>,
-) -> Result<Vec<String>> {
+) -> Result<LiveModelCatalog> {
     let requested_provider_name = provider_name;
     let provider_name = canonical_provider_name(provider_name);
     let ollama_remote = provider_name == "ollama" && ollama_uses_remote_endpoint(provider_api_url);
@@ -2233,7 +2330,7 @@ fn fetch_live_models_for_provider(
             if ollama_remote {
                 // Remote Ollama endpoints can serve cloud-routed models.
                 // Keep this curated list aligned with current Ollama cloud catalog.
-                vec![
+                LiveModelCatalog::from_ids(vec![
                     "glm-5:cloud".to_string(),
                     "glm-4.7:cloud".to_string(),
                     "gpt-oss:20b:cloud".to_string(),
@@ -2244,13 +2341,15 @@ fn fetch_live_models_for_provider(
                     "kimi-k2.5:cloud".to_string(),
                     "minimax-m2.5:cloud".to_string(),
                     "deepseek-v3.1:671b:cloud".to_string(),
-                ]
+                ])
             } else {
                 // Local endpoints should not surface cloud-only suffixes.
-                fetch_ollama_models()?
-                    .into_iter()
-                    .filter(|model_id| !model_id.ends_with(":cloud"))
-                    .collect()
+                LiveModelCatalog::from_ids(
+                    fetch_ollama_models()?
+                        .into_iter()
+                        .filter(|model_id| !model_id.ends_with(":cloud"))
+                        .collect(),
+                )
             }
         }
         _ => {
@@ -2265,7 +2364,7 @@ fn fetch_live_models_for_provider(
                     allow_unauthenticated,
                 )?
             } else {
-                Vec::new()
+                LiveModelCatalog::default()
             }
         }
     };
@@ -2278,6 +2377,8 @@ struct ModelCacheEntry {
     provider: String,
     fetched_at_unix: u64,
     models: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    capabilities: BTreeMap<String, ModelCapabilities>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -2288,6 +2389,7 @@ struct ModelCacheState {
 #[derive(Debug, Clone)]
 struct CachedModels {
     models: Vec<String>,
+    capabilities: BTreeMap<String, ModelCapabilities>,
     age_secs: u64,
 }
 
@@ -2339,12 +2441,13 @@ async fn save_model_cache_state(workspace_dir: &Path, state: &ModelCacheState) -
 async fn cache_live_models_for_provider(
     workspace_dir: &Path,
     provider_name: &str,
-    models: &[String],
+    catalog: &LiveModelCatalog,
 ) -> Result<()> {
-    let normalized_models = normalize_model_ids(models.to_vec());
+    let normalized_models = normalize_model_ids(catalog.models.clone());
     if normalized_models.is_empty() {
         return Ok(());
     }
+    capabilities::register(provider_name, catalog.capabilities.clone());
 
     let mut state = load_model_cache_state(workspace_dir).await?;
     let now = now_unix_secs();
@@ -2356,11 +2459,13 @@ async fn cache_live_models_for_provider(
     {
         entry.fetched_at_unix = now;
         entry.models = normalized_models;
+        entry.capabilities.clone_from(&catalog.capabilities);
     } else {
         state.entries.push(ModelCacheEntry {
             provider: provider_name.to_string(),
             fetched_at_unix: now,
             models: normalized_models,
+            capabilities: catalog.capabilities.clone(),
         });
     }
 
@@ -2394,6 +2499,7 @@ async fn load_cached_models_for_provider_internal(
 
     Ok(Some(CachedModels {
         models: entry.models,
+        capabilities: entry.capabilities,
         age_secs,
     }))
 }
@@ -2433,9 +2539,18 @@ fn build_model_options(model_ids: Vec<String>, source: &str) -> Vec<(String, Str
         .collect()
 }
 
-fn print_model_preview(models: &[String]) {
+fn print_model_preview(models: &[String], capabilities: &BTreeMap<String, ModelCapabilities>) {
     for model in models.iter().take(MODEL_PREVIEW_LIMIT) {
-        println!("  {} {model}", style("-"));
+        match capabilities.get(model).map(ModelCapabilities::summary) {
+            Some(summary) if !summary.is_empty() => {
+                println!(
+                    "  {} {model} {}",
+                    style("-"),
+                    style(format!("[{summary}]")).dim()
+                );
+            }
+            _ => println!("  {} {model}", style("-")),
+        }
     }
 
     if models.len() > MODEL_PREVIEW_LIMIT {
@@ -2479,7 +2594,7 @@ pub async fn run_models_refresh(
                 provider_name,
                 humanize_age(cached.age_secs)
             );
-            print_model_preview(&cached.models);
+            print_model_preview(&cached.models, &cached.capabilities);
             println!();
             println!(
                 "Tip: run `zeroclaw models refresh --force --provider {}` to fetch latest now.",
@@ -2492,14 +2607,14 @@ pub async fn run_models_refresh(
     let api_key = config.api_key.clone().unwrap_or_default();
 
     match fetch_live_models_for_provider(&provider_name, &api_key, config.api_url.as_deref()) {
-        Ok(models) if !models.is_empty() => {
-            cache_live_models_for_provider(&config.workspace_dir, &provider_name, &models).await?;
+        Ok(catalog) if !catalog.models.is_empty() => {
+            cache_live_models_for_provider(&config.workspace_dir, &provider_name, &catalog).await?;
             println!(
                 "Refreshed '{}' model cache with {} models.",
                 provider_name,
-                models.len()
+                catalog.models.len()
             );
-            print_model_preview(&models);
+            print_model_preview(&catalog.models, &catalog.capabilities);
             Ok(())
         }
         Ok(_) => {
@@ -2510,7 +2625,7 @@ pub async fn run_models_refresh(
                     "Provider returned no models; using stale cache (updated {} ago):",
                     humanize_age(stale_cache.age_secs)
                 );
-                print_model_preview(&stale_cache.models);
+                print_model_preview(&stale_cache.models, &stale_cache.capabilities);
                 return Ok(());
             }
 
@@ -2525,7 +2640,7 @@ pub async fn run_models_refresh(
                     error,
                     humanize_age(stale_cache.age_secs)
                 );
-                print_model_preview(&stale_cache.models);
+                print_model_preview(&stale_cache.models, &stale_cache.capabilities);
                 return Ok(());
             }
 
@@ -3281,13 +3396,10 @@ async fn setup_provider(workspace_dir: &Path) -> Result<(String, String, String,
                     &api_key,
                     provider_api_url.as_deref(),
                 ) {
-                    Ok(live_model_ids) if !live_model_ids.is_empty() => {
-                        cache_live_models_for_provider(
-                            workspace_dir,
-                            provider_name,
-                            &live_model_ids,
-                        )
-                        .await?;
+                    Ok(catalog) if !catalog.models.is_empty() => {
+                        cache_live_models_for_provider(workspace_dir, provider_name, &catalog)
+                            .await?;
+                        let live_model_ids = catalog.models;
 
                         let fetched_count = live_model_ids.len();
                         let shown_count = fetched_count.min(LIVE_MODEL_MAX_OPTIONS);
@@ -3544,7 +3656,10 @@ fn setup_tool_mode() -> Result<(ComposioConfig, SecretsConfig, AutonomyConfig)>
         .default(true)
         .interact()?;
 
-    let secrets_config = SecretsConfig { encrypt };
+    let secrets_config = SecretsConfig {
+        encrypt,
+        ..SecretsConfig::default()
+    };
 
     if encrypt {
         println!(
@@ -4416,6 +4531,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     allowed_users,
                     listen_to_bots: false,
                     mention_only: false,
+                    stream_mode: StreamMode::default(),
+                    draft_update_interval_ms: 1000,
                 });
             }
             ChannelMenuChoice::Slack => {
@@ -4543,6 +4660,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         Some(channel)
                     },
                     allowed_users,
+                    stream_mode: StreamMode::default(),
+                    draft_update_interval_ms: 1000,
                 });
             }
             ChannelMenuChoice::IMessage => {
@@ -6538,14 +6657,14 @@ mod tests {
         let _config_env = EnvVarGuard::unset("ZEROCLAW_CONFIG_DIR");
         let tmp = TempDir::new().unwrap();
 
-        let config = run_quick_setup_with_home(
+        let config = Box::pin(run_quick_setup_with_home(
             Some("sk-issue946"),
             Some("openrouter"),
             Some("custom-model-946"),
             Some("sqlite"),
             false,
             tmp.path(),
-        )
+        ))
         .await
         .unwrap();
 
@@ -6565,14 +6684,14 @@ mod tests {
         let _config_env = EnvVarGuard::unset("ZEROCLAW_CONFIG_DIR");
         let tmp = TempDir::new().unwrap();
 
-        let config = run_quick_setup_with_home(
+        let config = Box::pin(run_quick_setup_with_home(
             Some("sk-issue946"),
             Some("anthropic"),
             None,
             Some("sqlite"),
             false,
             tmp.path(),
-        )
+        ))
         .await
         .unwrap();
 
@@ -6588,14 +6707,14 @@ mod tests {
         let _config_env = EnvVarGuard::unset("ZEROCLAW_CONFIG_DIR");
         let tmp = TempDir::new().unwrap();
 
-        let config = run_quick_setup_with_home(
+        let config = Box::pin(run_quick_setup_with_home(
             Some("sk-minimal"),
             Some("openrouter"),
             None,
             Some("sqlite"),
             false,
             tmp.path(),
-        )
+        ))
         .await
         .expect("quick setup should succeed with minimal defaults");
 
@@ -6616,7 +6735,7 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         let extra = vec!["rag-pdf".to_string()];
 
-        let config = run_quick_setup_with_home_with_selection(
+        let config = Box::pin(run_quick_setup_with_home_with_selection(
             Some("sk-flex"),
             Some("openrouter"),
             None,
@@ -6628,7 +6747,7 @@ mod tests {
             true,
             false,
             tmp.path(),
-        )
+        ))
         .await
         .expect("quick setup should accept risky/non-strict config with explicit consent");
 
@@ -6653,7 +6772,7 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         let extra = vec!["tools-update".to_string()];
 
-        let err = run_quick_setup_with_home_with_selection(
+        let err = Box::pin(run_quick_setup_with_home_with_selection(
             Some("sk-risk"),
             Some("openrouter"),
             None,
@@ -6665,7 +6784,7 @@ mod tests {
             false,
             false,
             tmp.path(),
-        )
+        ))
         .await
         .expect_err("quick setup should reject risky pack without explicit consent");
 
@@ -6682,7 +6801,7 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         let extra: Vec<String> = Vec::new();
 
-        let err = run_quick_setup_with_home_with_selection(
+        let err = Box::pin(run_quick_setup_with_home_with_selection(
             Some("sk-sec"),
             Some("openrouter"),
             None,
@@ -6694,7 +6813,7 @@ mod tests {
             false,
             false,
             tmp.path(),
-        )
+        ))
         .await
         .expect_err("quick setup should reject non-strict profile without explicit consent");
 
@@ -6717,14 +6836,14 @@ mod tests {
             .await
             .unwrap();
 
-        let err = run_quick_setup_with_home(
+        let err = Box::pin(run_quick_setup_with_home(
             Some("sk-existing"),
             Some("openrouter"),
             Some("custom-model"),
             Some("sqlite"),
             false,
             tmp.path(),
-        )
+        ))
         .await
         .expect_err("quick setup should refuse overwrite without --force");
 
@@ -6750,14 +6869,14 @@ mod tests {
         .await
         .unwrap();
 
-        let config = run_quick_setup_with_home(
+        let config = Box::pin(run_quick_setup_with_home(
             Some("sk-force"),
             Some("openrouter"),
             Some("custom-model-fresh"),
             Some("sqlite"),
             true,
             tmp.path(),
-        )
+        ))
         .await
         .expect("quick setup should overwrite existing config with --force");
 
@@ -6784,14 +6903,14 @@ mod tests {
         );
         let _config_env = EnvVarGuard::unset("ZEROCLAW_CONFIG_DIR");
 
-        let config = run_quick_setup_with_home(
+        let config = Box::pin(run_quick_setup_with_home(
             Some("sk-env"),
             Some("openrouter"),
             Some("model-env"),
             Some("sqlite"),
             false,
             tmp.path(),
-        )
+        ))
         .await
         .expect("quick setup should honor ZEROCLAW_WORKSPACE");
 
@@ -7735,7 +7854,7 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         let models = vec!["gpt-5.1".to_string(), "gpt-5-mini".to_string()];
 
-        cache_live_models_for_provider(tmp.path(), "openai", &models)
+        cache_live_models_for_provider(tmp.path(), "openai", &LiveModelCatalog::from_ids(models))
             .await
             .unwrap();
 
@@ -7749,6 +7868,37 @@ mod tests {
         assert!(cached.models.contains(&"gpt-5-mini".to_string()));
     }
 
+    #[tokio::test]
+    async fn model_cache_keeps_catalog_capabilities() {
+        let tmp = TempDir::new().unwrap();
+        let catalog = LiveModelCatalog {
+            models: vec!["vision-model".to_string(), "text-model".to_string()],
+            capabilities: BTreeMap::from([(
+                "text-model".to_string(),
+                ModelCapabilities {
+                    vision: Some(false),
+                    max_context_tokens: Some(32_000),
+                    ..ModelCapabilities::default()
+                },
+            )]),
+        };
+
+        cache_live_models_for_provider(tmp.path(), "wizard-cache-test", &catalog)
+            .await
+            .unwrap();
+
+        let cached = load_any_cached_models_for_provider(tmp.path(), "wizard-cache-test")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached.capabilities, catalog.capabilities);
+        assert_eq!(
+            capabilities::lookup("wizard-cache-test", "text-model")
+                .and_then(|caps| caps.max_context_tokens),
+            Some(32_000)
+        );
+    }
+
     #[tokio::test]
     async fn model_cache_ttl_filters_stale_entries() {
         let tmp = TempDir::new().unwrap();
@@ -7757,6 +7907,7 @@ mod tests {
                 provider: "openai".to_string(),
                 fetched_at_unix: now_unix_secs().saturating_sub(MODEL_CACHE_TTL_SECS + 120),
                 models: vec!["gpt-5.1".to_string()],
+                capabilities: BTreeMap::new(),
             }],
         };
 
@@ -7777,9 +7928,13 @@ mod tests {
     async fn run_models_refresh_uses_fresh_cache_without_network() {
         let tmp = TempDir::new().unwrap();
 
-        cache_live_models_for_provider(tmp.path(), "openai", &["gpt-5.1".to_string()])
-            .await
-            .unwrap();
+        cache_live_models_for_provider(
+            tmp.path(),
+            "openai",
+            &LiveModelCatalog::from_ids(vec!["gpt-5.1".to_string()]),
+        )
+        .await
+        .unwrap();
 
         let config = Config {
             workspace_dir: tmp.path().to_path_buf(),
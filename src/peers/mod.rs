@@ -0,0 +1,419 @@
+//! Task delegation between trusted instances (`[peers]`).
+//!
+//! One instance (e.g. the home assistant) asks another it knows (the office
+//! assistant) to do a task there, such as checking a machine on the office
+//! network. Both list each other under `[peers.instances.<name>]` with the
+//! same shared token. The receiving side runs the task as a non-interactive
+//! agent turn under its own `[autonomy]` policy and `[peers]` budget caps.
+//!
+//! Protocol (JSON over the receiver's gateway; every request carries
+//! `Authorization: Bearer <shared token>` and `X-ZeroClaw-Peer: <sender>`):
+//! ```text
+//! POST /api/peers/tasks       {"task":"...","context":"...","budget":{"max_iterations":5,"max_cost_usd":0.2,"timeout_secs":120}}
+//!                          -> 202 {"id":"<uuid>","budget":{...as applied}}
+//! GET  /api/peers/tasks/{id} -> {"status":"running"} | {"status":"completed","output":"..."}
+//!                             | {"status":"failed","error":"..."} | {"status":"timed_out"}
+//! ```
+//! Tasks run in the background and are polled, so no request outlives the
+//! gateway's request timeout. Finished results are kept for an hour.
+
+use crate::config::{Config, PeersConfig};
+use crate::security::pairing::constant_time_eq;
+use anyhow::{bail, Context, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+/// Header naming the sending instance.
+pub const PEER_HEADER: &str = "X-ZeroClaw-Peer";
+/// Delegated tasks running at once on this instance, across all peers.
+const MAX_RUNNING_TASKS: usize = 4;
+/// Longest accepted task text (task plus context).
+const MAX_TASK_CHARS: usize = 8_000;
+/// How long a finished result stays available to its peer.
+const FINISHED_TTL: Duration = Duration::from_secs(3600);
+/// Interval between result polls on the sending side.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Extra time the sender waits past the task timeout before giving up.
+const POLL_GRACE_SECS: u64 = 30;
+
+/// Limits the sender asks for. Unset fields and values above the
+/// receiver's `[peers]` caps are replaced by the caps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Budget {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cost_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+impl Budget {
+    /// The budget a task actually runs with on an instance with `caps`.
+    pub fn clamp(&self, caps: &PeersConfig) -> Self {
+        Self {
+            max_iterations: Some(
+                self.max_iterations
+                    .filter(|n| *n > 0)
+                    .map_or(caps.max_iterations, |n| n.min(caps.max_iterations)),
+            ),
+            max_cost_usd: Some(
+                self.max_cost_usd
+                    .filter(|usd| usd.is_finite() && *usd > 0.0)
+                    .map_or(caps.max_cost_usd, |usd| usd.min(caps.max_cost_usd)),
+            ),
+            timeout_secs: Some(
+                self.timeout_secs
+                    .filter(|secs| *secs > 0)
+                    .map_or(caps.timeout_secs, |secs| secs.min(caps.timeout_secs)),
+            ),
+        }
+    }
+}
+
+/// A task sent to a peer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSpec {
+    pub task: String,
+    /// Background the peer needs (what to look for, earlier findings, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub budget: Budget,
+}
+
+/// Reply to a submitted task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submitted {
+    pub id: String,
+    pub budget: Budget,
+}
+
+/// State of a delegated task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TaskStatus {
+    Running,
+    Completed { output: String },
+    Failed { error: String },
+    TimedOut,
+}
+
+/// Why a task was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// Delegation is off, or the peer/token is unknown.
+    Unauthorized,
+    /// Empty or oversized task.
+    Invalid(String),
+    /// Too many tasks running.
+    Busy,
+}
+
+struct InboundTask {
+    peer: String,
+    status: TaskStatus,
+    finished_at: Option<Instant>,
+}
+
+static TASKS: LazyLock<Mutex<HashMap<String, InboundTask>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Whether `peer` may delegate to this instance with `token`.
+pub fn authenticate(config: &PeersConfig, peer: &str, token: &str) -> bool {
+    config.accept
+        && config
+            .instances
+            .get(peer)
+            .is_some_and(|instance| constant_time_eq(instance.token.trim(), token.trim()))
+}
+
+/// Accept `spec` from `peer` (already checked with [`authenticate`]) and
+/// start it in the background.
+pub fn submit(config: &Config, peer: &str, spec: TaskSpec) -> Result<Submitted, Rejection> {
+    if !config.peers.accept || !config.peers.instances.contains_key(peer) {
+        return Err(Rejection::Unauthorized);
+    }
+    let task = spec.task.trim();
+    if task.is_empty() {
+        return Err(Rejection::Invalid("task must not be empty".into()));
+    }
+    let length = task.chars().count() + spec.context.as_deref().map_or(0, |c| c.chars().count());
+    if length > MAX_TASK_CHARS {
+        return Err(Rejection::Invalid(format!(
+            "task and context exceed {MAX_TASK_CHARS} characters"
+        )));
+    }
+
+    let budget = spec.budget.clamp(&config.peers);
+    let id = uuid::Uuid::new_v4().to_string();
+    {
+        let mut tasks = TASKS.lock();
+        tasks.retain(|_, task| {
+            task.finished_at
+                .is_none_or(|finished| finished.elapsed() < FINISHED_TTL)
+        });
+        let running = tasks
+            .values()
+            .filter(|task| task.status == TaskStatus::Running)
+            .count();
+        if running >= MAX_RUNNING_TASKS {
+            return Err(Rejection::Busy);
+        }
+        tasks.insert(
+            id.clone(),
+            InboundTask {
+                peer: peer.to_string(),
+                status: TaskStatus::Running,
+                finished_at: None,
+            },
+        );
+    }
+    tracing::info!(peer, task_id = %id, "Accepted delegated task");
+
+    let config = config.clone();
+    let peer = peer.to_string();
+    let task_id = id.clone();
+    tokio::spawn(async move {
+        let status = Box::pin(run_task(config, &peer, spec, budget)).await;
+        tracing::info!(peer, task_id = %task_id, ?status, "Delegated task finished");
+        if let Some(task) = TASKS.lock().get_mut(&task_id) {
+            task.status = status;
+            task.finished_at = Some(Instant::now());
+        }
+    });
+    Ok(Submitted { id, budget })
+}
+
+/// Status of task `id`, if it exists and belongs to `peer`.
+pub fn status(peer: &str, id: &str) -> Option<TaskStatus> {
+    TASKS
+        .lock()
+        .get(id)
+        .filter(|task| task.peer == peer)
+        .map(|task| task.status.clone())
+}
+
+/// Settings a delegated task runs with.
+fn scoped_config(config: &Config, budget: &Budget) -> Config {
+    let mut scoped = config.clone();
+    scoped.agent.max_tool_iterations = budget.max_iterations.unwrap_or(config.peers.max_iterations);
+    scoped.agent.max_run_cost_usd = budget.max_cost_usd.unwrap_or(config.peers.max_cost_usd);
+    // A peer's wording is not something the owner said.
+    scoped.memory.auto_save = false;
+    scoped
+}
+
+fn build_prompt(peer: &str, spec: &TaskSpec) -> String {
+    let mut prompt = format!(
+        "The assistant '{peer}' delegated a task to you.\n\nTask: {}\n",
+        spec.task.trim()
+    );
+    if let Some(context) = spec.context.as_deref().map(str::trim) {
+        if !context.is_empty() {
+            let _ = write!(prompt, "\nContext from '{peer}': {context}\n");
+        }
+    }
+    prompt.push_str(
+        "\nDo the task with your tools, then reply with the result for the requesting \
+         assistant: what you checked, what you found, and anything you could not do.",
+    );
+    prompt
+}
+
+async fn run_task(config: Config, peer: &str, spec: TaskSpec, budget: Budget) -> TaskStatus {
+    let timeout = Duration::from_secs(budget.timeout_secs.unwrap_or(config.peers.timeout_secs));
+    let allowed_tools = config.peers.allowed_tools.clone();
+    let temperature = config.default_temperature;
+    let run = crate::agent::run(
+        scoped_config(&config, &budget),
+        Some(build_prompt(peer, &spec)),
+        None,
+        None,
+        temperature,
+        Vec::new(),
+        false,
+        false,
+        allowed_tools,
+        crate::handoff::CliHandoff::default(),
+    );
+    match tokio::time::timeout(timeout, Box::pin(run)).await {
+        Ok(Ok(output)) => TaskStatus::Completed { output },
+        Ok(Err(e)) => TaskStatus::Failed {
+            error: format!("{e:#}"),
+        },
+        Err(_) => TaskStatus::TimedOut,
+    }
+}
+
+/// Send `spec` to `peer` and wait for its result.
+pub async fn delegate(config: &PeersConfig, peer: &str, spec: &TaskSpec) -> Result<TaskStatus> {
+    let sender = config
+        .name
+        .as_deref()
+        .context("[peers] name must be set to delegate")?;
+    let Some(instance) = config.instances.get(peer) else {
+        bail!("unknown peer '{peer}'");
+    };
+    let base = instance
+        .url
+        .as_deref()
+        .map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+        .with_context(|| format!("peer '{peer}' has no url"))?;
+    let token = instance.token.trim();
+    let client =
+        crate::config::build_runtime_proxy_client_with_timeouts("tool.peer_delegate", 30, 10);
+
+    let response = client
+        .post(format!("{base}/api/peers/tasks"))
+        .bearer_auth(token)
+        .header(PEER_HEADER, sender)
+        .json(spec)
+        .send()
+        .await
+        .with_context(|| format!("peer '{peer}' is unreachable"))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let reason = body
+            .get("error")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("no details");
+        bail!("peer '{peer}' refused the task ({status}): {reason}");
+    }
+    let submitted: Submitted = response.json().await?;
+
+    let wait = submitted.budget.timeout_secs.unwrap_or(config.timeout_secs) + POLL_GRACE_SECS;
+    let deadline = Instant::now() + Duration::from_secs(wait);
+    let url = format!("{base}/api/peers/tasks/{}", submitted.id);
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let status: TaskStatus = client
+            .get(&url)
+            .bearer_auth(token)
+            .header(PEER_HEADER, sender)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if status != TaskStatus::Running {
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            return Ok(TaskStatus::TimedOut);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PeerInstanceConfig;
+
+    fn peers(accept: bool) -> PeersConfig {
+        let mut config = PeersConfig {
+            accept,
+            ..PeersConfig::default()
+        };
+        config.instances.insert(
+            "home".into(),
+            PeerInstanceConfig {
+                url: None,
+                token: "home-office-shared-token".into(),
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn authenticate_requires_accept_known_peer_and_matching_token() {
+        assert!(authenticate(
+            &peers(true),
+            "home",
+            "home-office-shared-token"
+        ));
+        assert!(!authenticate(
+            &peers(false),
+            "home",
+            "home-office-shared-token"
+        ));
+        assert!(!authenticate(
+            &peers(true),
+            "home",
+            "wrong-token-of-same-size!"
+        ));
+        assert!(!authenticate(
+            &peers(true),
+            "garage",
+            "home-office-shared-token"
+        ));
+    }
+
+    #[test]
+    fn budget_is_capped_by_local_limits() {
+        let caps = peers(true);
+        let asked = Budget {
+            max_iterations: Some(50),
+            max_cost_usd: Some(0.1),
+            timeout_secs: None,
+        };
+        assert_eq!(
+            asked.clamp(&caps),
+            Budget {
+                max_iterations: Some(caps.max_iterations),
+                max_cost_usd: Some(0.1),
+                timeout_secs: Some(caps.timeout_secs),
+            }
+        );
+        let nonsense = Budget {
+            max_iterations: Some(0),
+            max_cost_usd: Some(f64::NAN),
+            timeout_secs: Some(0),
+        };
+        assert_eq!(nonsense.clamp(&caps), Budget::default().clamp(&caps));
+    }
+
+    #[tokio::test]
+    async fn submit_rejects_unknown_peers_and_bad_tasks() {
+        let mut config = Config::default();
+        config.peers = peers(true);
+        let spec = |task: &str| TaskSpec {
+            task: task.into(),
+            context: None,
+            budget: Budget::default(),
+        };
+        assert_eq!(
+            submit(&config, "garage", spec("ping the printer")).unwrap_err(),
+            Rejection::Unauthorized
+        );
+        assert!(matches!(
+            submit(&config, "home", spec("   ")).unwrap_err(),
+            Rejection::Invalid(_)
+        ));
+        assert!(matches!(
+            submit(&config, "home", spec(&"x".repeat(MAX_TASK_CHARS + 1))).unwrap_err(),
+            Rejection::Invalid(_)
+        ));
+        assert_eq!(status("home", "missing"), None);
+    }
+
+    #[test]
+    fn status_round_trips_as_tagged_json() {
+        let done = TaskStatus::Completed {
+            output: "printer is online".into(),
+        };
+        let json = serde_json::to_value(&done).unwrap();
+        assert_eq!(json["status"], "completed");
+        assert_eq!(serde_json::from_value::<TaskStatus>(json).unwrap(), done);
+        assert_eq!(
+            serde_json::to_value(TaskStatus::TimedOut).unwrap()["status"],
+            "timed_out"
+        );
+    }
+}
@@ -0,0 +1,438 @@
+//! Bluetooth LE advertisement sensors (`[peripherals.ble]`).
+//!
+//! Passively scans advertisements, decodes the configured formats
+//! (ATC1441/pvvx thermometers, Xiaomi MiBeacon, iBeacon) and tracks when
+//! each sensor was last heard, so the agent can ask for the bedroom
+//! temperature or whether a keyring tag is home. Decoding is plain Rust;
+//! only the scanner needs the `peripheral-ble` feature (btleplug).
+
+use crate::config::{BleConfig, BleFormat, BleSensorConfig};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::LazyLock;
+
+/// Environmental Sensing service data (ATC1441 / pvvx custom firmware).
+const SERVICE_ENVIRONMENTAL: u16 = 0x181A;
+/// Xiaomi MiBeacon service data.
+const SERVICE_MIBEACON: u16 = 0xFE95;
+/// Apple company id (iBeacon manufacturer data).
+const COMPANY_APPLE: u16 = 0x004C;
+
+/// Decoded sensor values; absent fields were not in the advertisement.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Reading {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature_c: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub humidity_pct: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub battery_pct: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub battery_mv: Option<u16>,
+}
+
+impl Reading {
+    /// Overlay fields from a newer (possibly partial) reading.
+    fn merge(&mut self, newer: &Reading) {
+        self.temperature_c = newer.temperature_c.or(self.temperature_c);
+        self.humidity_pct = newer.humidity_pct.or(self.humidity_pct);
+        self.battery_pct = newer.battery_pct.or(self.battery_pct);
+        self.battery_mv = newer.battery_mv.or(self.battery_mv);
+    }
+}
+
+/// One received advertisement, independent of the BLE stack.
+#[derive(Debug, Clone, Default)]
+pub struct Advertisement {
+    /// Uppercase MAC address.
+    pub address: String,
+    pub rssi: Option<i16>,
+    /// Keyed by 16-bit service UUID.
+    pub service_data: HashMap<u16, Vec<u8>>,
+    /// Keyed by company id.
+    pub manufacturer_data: HashMap<u16, Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IBeacon {
+    pub uuid: uuid::Uuid,
+    pub major: u16,
+    pub minor: u16,
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// ATC1441 (13 bytes, big-endian) or pvvx (15 bytes, little-endian) custom format.
+pub fn decode_atc(data: &[u8]) -> Option<Reading> {
+    match data.len() {
+        13 => Some(Reading {
+            temperature_c: Some(f64::from(i16::from_be_bytes([data[6], data[7]])) / 10.0),
+            humidity_pct: Some(f64::from(data[8])),
+            battery_pct: Some(data[9]),
+            battery_mv: Some(u16::from_be_bytes([data[10], data[11]])),
+        }),
+        15 => Some(Reading {
+            temperature_c: Some(round2(
+                f64::from(i16::from_le_bytes([data[6], data[7]])) / 100.0,
+            )),
+            humidity_pct: Some(round2(
+                f64::from(u16::from_le_bytes([data[8], data[9]])) / 100.0,
+            )),
+            battery_mv: Some(u16::from_le_bytes([data[10], data[11]])),
+            battery_pct: Some(data[12]),
+        }),
+        _ => None,
+    }
+}
+
+/// Unencrypted Xiaomi MiBeacon object frames (temperature, humidity, battery).
+pub fn decode_mibeacon(data: &[u8]) -> Option<Reading> {
+    let frame_control = u16::from_le_bytes([*data.first()?, *data.get(1)?]);
+    if frame_control & 0x0008 != 0 || frame_control & 0x0040 == 0 {
+        // Encrypted, or no object payload.
+        return None;
+    }
+    let mut index = 5;
+    if frame_control & 0x0010 != 0 {
+        index += 6;
+    }
+    if frame_control & 0x0020 != 0 {
+        let capability = *data.get(index)?;
+        index += if capability & 0x20 != 0 { 3 } else { 1 };
+    }
+
+    let mut reading = Reading::default();
+    let mut found = false;
+    while let Some(header) = data.get(index..index + 3) {
+        let object = u16::from_le_bytes([header[0], header[1]]);
+        let len = usize::from(header[2]);
+        let value = data.get(index + 3..index + 3 + len)?;
+        let i16_at = |at: usize| Some(i16::from_le_bytes([*value.get(at)?, *value.get(at + 1)?]));
+        let u16_at = |at: usize| Some(u16::from_le_bytes([*value.get(at)?, *value.get(at + 1)?]));
+        match object {
+            0x1004 => reading.temperature_c = Some(f64::from(i16_at(0)?) / 10.0),
+            0x1006 => reading.humidity_pct = Some(f64::from(u16_at(0)?) / 10.0),
+            0x100A => reading.battery_pct = Some(*value.first()?),
+            0x100D => {
+                reading.temperature_c = Some(f64::from(i16_at(0)?) / 10.0);
+                reading.humidity_pct = Some(f64::from(u16_at(2)?) / 10.0);
+            }
+            _ => {}
+        }
+        found = true;
+        index += 3 + len;
+    }
+    found.then_some(reading)
+}
+
+/// Apple iBeacon manufacturer data (after the company id).
+pub fn decode_ibeacon(data: &[u8]) -> Option<IBeacon> {
+    if data.len() < 22 || data[0] != 0x02 || data[1] != 0x15 {
+        return None;
+    }
+    Some(IBeacon {
+        uuid: uuid::Uuid::from_slice(&data[2..18]).ok()?,
+        major: u16::from_be_bytes([data[18], data[19]]),
+        minor: u16::from_be_bytes([data[20], data[21]]),
+    })
+}
+
+/// Whether `advertisement` comes from `sensor`.
+fn matches_sensor(sensor: &BleSensorConfig, advertisement: &Advertisement) -> bool {
+    if sensor.format == BleFormat::Ibeacon {
+        let Some(beacon) = advertisement
+            .manufacturer_data
+            .get(&COMPANY_APPLE)
+            .and_then(|data| decode_ibeacon(data))
+        else {
+            return false;
+        };
+        return sensor
+            .uuid
+            .as_deref()
+            .and_then(|uuid| uuid::Uuid::parse_str(uuid.trim()).ok())
+            .is_some_and(|uuid| uuid == beacon.uuid)
+            && sensor.major.is_none_or(|major| major == beacon.major)
+            && sensor.minor.is_none_or(|minor| minor == beacon.minor);
+    }
+    sensor
+        .address
+        .as_deref()
+        .is_some_and(|address| address.trim().eq_ignore_ascii_case(&advertisement.address))
+}
+
+/// Decode the reading carried by a matched sensor's advertisement, if any.
+fn decode_reading(format: BleFormat, advertisement: &Advertisement) -> Option<Reading> {
+    match format {
+        BleFormat::Atc => decode_atc(advertisement.service_data.get(&SERVICE_ENVIRONMENTAL)?),
+        BleFormat::Mibeacon => decode_mibeacon(advertisement.service_data.get(&SERVICE_MIBEACON)?),
+        BleFormat::Presence | BleFormat::Ibeacon => None,
+    }
+}
+
+/// Last known state of a configured sensor.
+#[derive(Debug, Clone, Default)]
+pub struct SensorState {
+    pub last_seen: Option<DateTime<Utc>>,
+    pub rssi: Option<i16>,
+    pub reading: Reading,
+    pub reading_at: Option<DateTime<Utc>>,
+}
+
+/// Tracked sensor states keyed by sensor name.
+#[derive(Debug, Default)]
+pub struct Tracker {
+    states: BTreeMap<String, SensorState>,
+}
+
+impl Tracker {
+    /// Record an advertisement against every configured sensor it matches.
+    pub fn apply(
+        &mut self,
+        sensors: &[BleSensorConfig],
+        advertisement: &Advertisement,
+        now: DateTime<Utc>,
+    ) {
+        for sensor in sensors {
+            if !matches_sensor(sensor, advertisement) {
+                continue;
+            }
+            let state = self.states.entry(sensor.name.clone()).or_default();
+            state.last_seen = Some(now);
+            state.rssi = advertisement.rssi.or(state.rssi);
+            if let Some(reading) = decode_reading(sensor.format, advertisement) {
+                tracing::debug!(sensor = %sensor.name, ?reading, "BLE reading");
+                state.reading.merge(&reading);
+                state.reading_at = Some(now);
+            }
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SensorState> {
+        self.states.get(name)
+    }
+}
+
+static TRACKER: LazyLock<Mutex<Tracker>> = LazyLock::new(|| Mutex::new(Tracker::default()));
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Current state of a sensor (default if never heard).
+pub fn sensor_state(name: &str) -> SensorState {
+    TRACKER.lock().get(name).cloned().unwrap_or_default()
+}
+
+/// Start the background scanner for this process (no-op if already running).
+pub fn ensure_started(config: &BleConfig) {
+    if STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    start_scanner(config.clone());
+}
+
+#[cfg(all(
+    feature = "peripheral-ble",
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+))]
+fn start_scanner(config: BleConfig) {
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = scan(&config).await {
+                tracing::warn!("BLE scanning failed: {e:#}");
+                crate::health::mark_component_error("ble", format!("{e:#}"));
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+        }
+    });
+}
+
+#[cfg(not(all(
+    feature = "peripheral-ble",
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+)))]
+fn start_scanner(_config: BleConfig) {
+    tracing::warn!(
+        "[peripherals.ble] is enabled but this build has no BLE support. Build with: cargo build --features peripheral-ble"
+    );
+}
+
+/// Whether this build can scan (see [`ensure_started`]).
+pub fn scanner_available() -> bool {
+    cfg!(all(
+        feature = "peripheral-ble",
+        any(
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "windows"
+        )
+    ))
+}
+
+/// Convert a 128-bit Bluetooth base UUID to its 16-bit short form.
+#[cfg(all(
+    feature = "peripheral-ble",
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+))]
+fn short_uuid(uuid: &uuid::Uuid) -> Option<u16> {
+    const BASE: u128 = 0x0000_0000_0000_1000_8000_0080_5F9B_34FB;
+    let value = uuid.as_u128();
+    if value & !(0xFFFF_FFFF_u128 << 96) != BASE {
+        return None;
+    }
+    u16::try_from(value >> 96).ok()
+}
+
+/// Scan on the first adapter until the event stream ends or fails.
+#[cfg(all(
+    feature = "peripheral-ble",
+    any(target_os = "linux", target_os = "macos", target_os = "windows")
+))]
+async fn scan(config: &BleConfig) -> anyhow::Result<()> {
+    use btleplug::api::{Central, CentralEvent, Manager as _, Peripheral as _, ScanFilter};
+    use btleplug::platform::Manager;
+    use futures_util::StreamExt;
+
+    let manager = Manager::new().await?;
+    let central = manager
+        .adapters()
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No Bluetooth adapter found"))?;
+    let mut events = central.events().await?;
+    central.start_scan(ScanFilter::default()).await?;
+    tracing::info!(sensors = config.sensors.len(), "BLE scanning started");
+    crate::health::mark_component_ok("ble");
+
+    while let Some(event) = events.next().await {
+        let id = match event {
+            CentralEvent::DeviceDiscovered(id)
+            | CentralEvent::DeviceUpdated(id)
+            | CentralEvent::ManufacturerDataAdvertisement { id, .. }
+            | CentralEvent::ServiceDataAdvertisement { id, .. } => id,
+            _ => continue,
+        };
+        let Ok(peripheral) = central.peripheral(&id).await else {
+            continue;
+        };
+        let Some(properties) = peripheral.properties().await? else {
+            continue;
+        };
+        let advertisement = Advertisement {
+            address: properties.address.to_string().to_ascii_uppercase(),
+            rssi: properties.rssi,
+            service_data: properties
+                .service_data
+                .iter()
+                .filter_map(|(uuid, data)| Some((short_uuid(uuid)?, data.clone())))
+                .collect(),
+            manufacturer_data: properties.manufacturer_data,
+        };
+        TRACKER
+            .lock()
+            .apply(&config.sensors, &advertisement, Utc::now());
+    }
+    anyhow::bail!("BLE event stream ended")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sensor(name: &str, format: BleFormat, address: Option<&str>) -> BleSensorConfig {
+        BleSensorConfig {
+            name: name.into(),
+            format,
+            address: address.map(String::from),
+            uuid: None,
+            major: None,
+            minor: None,
+        }
+    }
+
+    #[test]
+    fn decodes_thermometer_formats() {
+        // ATC1441: 21.5 °C, 45 %, 87 %, 2950 mV
+        let atc = [
+            0xA4, 0xC1, 0x38, 0x12, 0x34, 0x56, 0x00, 0xD7, 45, 87, 0x0B, 0x86, 1,
+        ];
+        let reading = decode_atc(&atc).unwrap();
+        assert_eq!(reading.temperature_c, Some(21.5));
+        assert_eq!(reading.humidity_pct, Some(45.0));
+        assert_eq!(reading.battery_mv, Some(2950));
+
+        // pvvx: -3.25 °C, 51.5 %, 3000 mV, 90 %
+        let mut pvvx = vec![0x56, 0x34, 0x12, 0x38, 0xC1, 0xA4];
+        pvvx.extend_from_slice(&(-325_i16).to_le_bytes());
+        pvvx.extend_from_slice(&5150_u16.to_le_bytes());
+        pvvx.extend_from_slice(&3000_u16.to_le_bytes());
+        pvvx.extend_from_slice(&[90, 7, 0]);
+        let reading = decode_atc(&pvvx).unwrap();
+        assert_eq!(reading.temperature_c, Some(-3.25));
+        assert_eq!(reading.humidity_pct, Some(51.5));
+        assert_eq!(reading.battery_pct, Some(90));
+
+        // MiBeacon with MAC and a temperature+humidity object (23.4 °C, 56.7 %)
+        let mut mi = vec![0x50, 0x00, 0x5B, 0x05, 0x01];
+        mi.extend_from_slice(&[0x56, 0x34, 0x12, 0x38, 0xC1, 0xA4]);
+        mi.extend_from_slice(&[0x0D, 0x10, 0x04]);
+        mi.extend_from_slice(&234_i16.to_le_bytes());
+        mi.extend_from_slice(&567_u16.to_le_bytes());
+        let reading = decode_mibeacon(&mi).unwrap();
+        assert_eq!(reading.temperature_c, Some(23.4));
+        assert_eq!(reading.humidity_pct, Some(56.7));
+        // Encrypted frames are skipped.
+        mi[0] |= 0x08;
+        assert!(decode_mibeacon(&mi).is_none());
+    }
+
+    #[test]
+    fn tracker_matches_sensors_by_address_and_beacon_uuid() {
+        let uuid = uuid::Uuid::parse_str("fda50693-a4e2-4fb1-afcf-c6eb07647825").unwrap();
+        let mut keys = sensor("keys", BleFormat::Ibeacon, None);
+        keys.uuid = Some(uuid.to_string());
+        keys.major = Some(10);
+        let sensors = vec![
+            sensor("bedroom", BleFormat::Atc, Some("a4:c1:38:12:34:56")),
+            keys,
+        ];
+        let mut tracker = Tracker::default();
+        let now = Utc::now();
+
+        let mut beacon = vec![0x02, 0x15];
+        beacon.extend_from_slice(uuid.as_bytes());
+        beacon.extend_from_slice(&[0, 10, 0, 1, 0xC5]);
+        tracker.apply(
+            &sensors,
+            &Advertisement {
+                address: "11:22:33:44:55:66".into(),
+                rssi: Some(-70),
+                manufacturer_data: HashMap::from([(COMPANY_APPLE, beacon)]),
+                ..Advertisement::default()
+            },
+            now,
+        );
+        let keys = tracker.get("keys").unwrap();
+        assert_eq!(keys.last_seen, Some(now));
+        assert_eq!(keys.rssi, Some(-70));
+        assert!(tracker.get("bedroom").is_none());
+
+        let atc = vec![0, 0, 0, 0, 0, 0, 0x00, 0xD7, 45, 87, 0x0B, 0x86, 1];
+        tracker.apply(
+            &sensors,
+            &Advertisement {
+                address: "A4:C1:38:12:34:56".into(),
+                service_data: HashMap::from([(SERVICE_ENVIRONMENTAL, atc)]),
+                ..Advertisement::default()
+            },
+            now,
+        );
+        let bedroom = tracker.get("bedroom").unwrap();
+        assert_eq!(bedroom.reading.temperature_c, Some(21.5));
+        assert_eq!(bedroom.reading_at, Some(now));
+    }
+}
@@ -3,6 +3,8 @@
 //! Peripherals extend the agent with physical capabilities. See
 //! `docs/hardware-peripherals-design.md` for the full design.
 
+pub mod ble;
+pub mod protocol;
 pub mod traits;
 
 #[cfg(feature = "hardware")]
@@ -31,6 +33,68 @@ use crate::config::{Config, PeripheralBoardConfig, PeripheralsConfig};
 use crate::tools::HardwareMemoryMapTool;
 use crate::tools::Tool;
 use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Peripheral tools that only observe board state (no GPIO writes, uploads
+/// or flashing). Only these are served by the gateway's peripheral API.
+pub const READ_ONLY_TOOLS: [&str; 5] = [
+    "gpio_read",
+    "hardware_board_info",
+    "hardware_capabilities",
+    "hardware_memory_map",
+    "hardware_memory_read",
+];
+
+/// Whether `tool` is a read-only peripheral tool (see [`READ_ONLY_TOOLS`]).
+pub fn is_read_only_tool(tool: &str) -> bool {
+    READ_ONLY_TOOLS.contains(&tool)
+}
+
+/// Board connections shared by the agent and the gateway peripheral API.
+///
+/// Boards are connected on first use and reconnected when `[peripherals]`
+/// changes, so a config reload takes effect without a second connection
+/// to the same port.
+#[derive(Default)]
+pub struct PeripheralHandles {
+    connected: tokio::sync::Mutex<Option<ConnectedPeripherals>>,
+}
+
+struct ConnectedPeripherals {
+    config: PeripheralsConfig,
+    workspace_dir: PathBuf,
+    tools: Vec<Arc<dyn Tool>>,
+}
+
+impl PeripheralHandles {
+    /// Tools for the boards in `config`, connecting them if needed.
+    pub async fn tools(
+        &self,
+        config: &PeripheralsConfig,
+        workspace_dir: &Path,
+    ) -> Result<Vec<Arc<dyn Tool>>> {
+        let mut connected = self.connected.lock().await;
+        if let Some(current) = connected.as_ref() {
+            if current.config == *config && current.workspace_dir == workspace_dir {
+                return Ok(current.tools.clone());
+            }
+        }
+        // Release the old connections before the ports are opened again.
+        *connected = None;
+        let tools: Vec<Arc<dyn Tool>> = create_peripheral_tools(config, workspace_dir)
+            .await?
+            .into_iter()
+            .map(Arc::from)
+            .collect();
+        *connected = Some(ConnectedPeripherals {
+            config: config.clone(),
+            workspace_dir: workspace_dir.to_path_buf(),
+            tools: tools.clone(),
+        });
+        Ok(tools)
+    }
+}
 
 /// List configured boards from config (no connection yet).
 pub fn list_configured_boards(config: &PeripheralsConfig) -> Vec<&PeripheralBoardConfig> {
@@ -76,7 +140,7 @@ pub async fn handle_command(cmd: crate::PeripheralCommands, config: &Config) ->
                 Some(path.clone())
             };
 
-            let mut cfg = crate::config::Config::load_or_init().await?;
+            let mut cfg = Box::pin(crate::config::Config::load_or_init()).await?;
             cfg.peripherals.enabled = true;
 
             if cfg
@@ -94,10 +158,28 @@ pub async fn handle_command(cmd: crate::PeripheralCommands, config: &Config) ->
                 transport: transport.to_string(),
                 path: path_opt,
                 baud: 115_200,
+                protocol: None,
             });
             cfg.save().await?;
             println!("Added {} at {}. Restart daemon to apply.", board, path);
         }
+        crate::PeripheralCommands::CheckProtocol { file } => {
+            let path = Path::new(&file);
+            let path = if path.exists() || path.is_absolute() {
+                path.to_path_buf()
+            } else {
+                config.workspace_dir.join(path)
+            };
+            let protocol = protocol::Protocol::load(&path)?;
+            println!(
+                "Protocol {} is valid ({} tools):",
+                protocol.name,
+                protocol.tool_names().len()
+            );
+            for name in protocol.tool_names() {
+                println!("  {name}");
+            }
+        }
         #[cfg(feature = "hardware")]
         crate::PeripheralCommands::Flash { port } => {
             let port_str = arduino_flash::resolve_port(config, port.as_deref())
@@ -134,15 +216,31 @@ pub async fn handle_command(cmd: crate::PeripheralCommands, config: &Config) ->
     Ok(())
 }
 
+/// Load a board's protocol definition and open its port.
+#[cfg(feature = "hardware")]
+fn connect_protocol_board(
+    board: &PeripheralBoardConfig,
+    protocol_path: &Path,
+) -> Result<Vec<Box<dyn Tool>>> {
+    let protocol = protocol::Protocol::load(protocol_path)?;
+    let port = protocol::SerialProtocolPort::open(board)?;
+    Ok(protocol.tools(std::sync::Arc::new(port)))
+}
+
 /// Create and connect peripherals from config, returning their tools.
 /// Returns empty vec if peripherals disabled or hardware feature off.
+/// Board `protocol` paths are resolved against `workspace_dir`.
 #[cfg(feature = "hardware")]
-pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<Box<dyn Tool>>> {
+pub async fn create_peripheral_tools(
+    config: &PeripheralsConfig,
+    workspace_dir: &Path,
+) -> Result<Vec<Box<dyn Tool>>> {
     if !config.enabled || config.boards.is_empty() {
         return Ok(Vec::new());
     }
 
     let mut tools: Vec<Box<dyn Tool>> = Vec::new();
+    let mut protocol_tools: Vec<Box<dyn Tool>> = Vec::new();
     let mut serial_transports: Vec<(String, std::sync::Arc<serial::SerialTransport>)> = Vec::new();
 
     for board in &config.boards {
@@ -181,6 +279,35 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
             continue;
         }
 
+        // Custom device driven by a declarative protocol definition
+        if let Some(ref protocol_path) = board.protocol {
+            match connect_protocol_board(board, &workspace_dir.join(protocol_path)) {
+                Ok(board_tools) => {
+                    if let Some(clash) = board_tools
+                        .iter()
+                        .find(|t| protocol_tools.iter().any(|p| p.name() == t.name()))
+                    {
+                        tracing::warn!(
+                            "Skipping board {}: tool {} is already defined by another board",
+                            board.board,
+                            clash.name()
+                        );
+                        continue;
+                    }
+                    tracing::info!(
+                        board = %board.board,
+                        tools = board_tools.len(),
+                        "Serial protocol device connected"
+                    );
+                    protocol_tools.extend(board_tools);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to connect {}: {:#}", board.board, e);
+                }
+            }
+            continue;
+        }
+
         match serial::SerialPeripheral::connect(board).await {
             Ok(peripheral) => {
                 let mut p = peripheral;
@@ -224,18 +351,23 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
         )));
     }
 
+    tools.extend(protocol_tools);
     Ok(tools)
 }
 
 #[cfg(not(feature = "hardware"))]
-pub async fn create_peripheral_tools(_config: &PeripheralsConfig) -> Result<Vec<Box<dyn Tool>>> {
+#[allow(clippy::unused_async)] // Mirrors the async signature of the hardware build.
+pub async fn create_peripheral_tools(
+    _config: &PeripheralsConfig,
+    _workspace_dir: &Path,
+) -> Result<Vec<Box<dyn Tool>>> {
     Ok(Vec::new())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{PeripheralBoardConfig, PeripheralsConfig};
+    use crate::config::{BleConfig, PeripheralBoardConfig, PeripheralsConfig};
 
     #[test]
     fn list_configured_boards_when_disabled_returns_empty() {
@@ -246,8 +378,10 @@ mod tests {
                 transport: "serial".into(),
                 path: Some("/dev/ttyACM0".into()),
                 baud: 115_200,
+                protocol: None,
             }],
             datasheet_dir: None,
+            ble: BleConfig::default(),
         };
         let result = list_configured_boards(&config);
         assert!(
@@ -266,15 +400,18 @@ mod tests {
                     transport: "serial".into(),
                     path: Some("/dev/ttyACM0".into()),
                     baud: 115_200,
+                    protocol: None,
                 },
                 PeripheralBoardConfig {
                     board: "rpi-gpio".into(),
                     transport: "native".into(),
                     path: None,
                     baud: 115_200,
+                    protocol: None,
                 },
             ],
             datasheet_dir: None,
+            ble: BleConfig::default(),
         };
         let result = list_configured_boards(&config);
         assert_eq!(result.len(), 2);
@@ -288,6 +425,7 @@ mod tests {
             enabled: true,
             boards: vec![],
             datasheet_dir: None,
+            ble: BleConfig::default(),
         };
         let result = list_configured_boards(&config);
         assert!(
@@ -296,17 +434,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn read_only_tools_exclude_writes_and_flashing() {
+        assert!(is_read_only_tool("gpio_read"));
+        assert!(is_read_only_tool("hardware_capabilities"));
+        assert!(!is_read_only_tool("gpio_write"));
+        assert!(!is_read_only_tool("arduino_upload"));
+        assert!(!is_read_only_tool("shell"));
+    }
+
     #[tokio::test]
     async fn create_peripheral_tools_returns_empty_when_disabled() {
         let config = PeripheralsConfig {
             enabled: false,
             boards: vec![],
             datasheet_dir: None,
+            ble: BleConfig::default(),
         };
-        let tools = create_peripheral_tools(&config).await.unwrap();
+        let tools = create_peripheral_tools(&config, Path::new("."))
+            .await
+            .unwrap();
         assert!(
             tools.is_empty(),
             "disabled peripherals should produce no tools"
         );
     }
+
+    #[cfg(feature = "hardware")]
+    #[tokio::test]
+    async fn peripheral_handles_reuse_connections_until_config_changes() {
+        let mut config = PeripheralsConfig {
+            enabled: true,
+            boards: vec![PeripheralBoardConfig {
+                board: "arduino-uno-q".into(),
+                transport: "bridge".into(),
+                ..PeripheralBoardConfig::default()
+            }],
+            datasheet_dir: None,
+            ble: BleConfig::default(),
+        };
+        let handles = PeripheralHandles::default();
+        let first = handles.tools(&config, Path::new(".")).await.unwrap();
+        let again = handles.tools(&config, Path::new(".")).await.unwrap();
+        assert!(!first.is_empty());
+        assert!(Arc::ptr_eq(&first[0], &again[0]));
+
+        config.datasheet_dir = Some("docs".into());
+        let reloaded = handles.tools(&config, Path::new(".")).await.unwrap();
+        assert!(!Arc::ptr_eq(&first[0], &reloaded[0]));
+    }
 }
@@ -0,0 +1,868 @@
+//! Declarative serial protocols for custom devices.
+//!
+//! A `[[peripherals.boards]]` entry with `protocol = "peripherals/psu.toml"`
+//! is driven by a TOML definition in the workspace instead of the ZeroClaw
+//! JSON firmware protocol. Each command becomes an agent tool named
+//! `<protocol>_<command>`:
+//!
+//! ```toml
+//! name = "psu"
+//! description = "Bench power supply"
+//!
+//! [framing]
+//! suffix = "\n"      # appended to every command
+//! terminator = "\n"  # reply ends here
+//!
+//! [[commands]]
+//! name = "set_voltage"
+//! description = "Set the output voltage"
+//! send = "VSET1:{volts}"
+//! no_reply = true
+//! params.volts = { type = "number", min = 0, max = 30, description = "Volts" }
+//!
+//! [[commands]]
+//! name = "read_voltage"
+//! description = "Read the output voltage"
+//! send = "VOUT1?"
+//! response.pattern = '^(?P<volts>[0-9.]+)$'
+//! response.fields.volts = "number"
+//! ```
+//!
+//! With `encoding = "hex"`, `send`, `suffix` and `terminator` are hex bytes,
+//! integer params are written as `bytes`-wide big-endian hex, and response
+//! patterns match the reply as contiguous uppercase hex.
+
+use crate::tools::traits::{Tool, ToolResult};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Replies longer than this are treated as a framing error.
+pub const MAX_REPLY_BYTES: usize = 4096;
+/// Maximum length of a generated tool name.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    #[default]
+    Text,
+    Hex,
+}
+
+/// One-byte checksum appended to the command payload (before the suffix).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Checksum {
+    #[default]
+    None,
+    Xor8,
+    Sum8,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FramingDef {
+    #[serde(default)]
+    pub encoding: Encoding,
+    /// Appended to every command. Default `"\n"` for text, nothing for hex.
+    #[serde(default)]
+    pub suffix: Option<String>,
+    /// Replies end with this. Default `"\n"` for text; hex protocols need a
+    /// terminator or `reply_length`.
+    #[serde(default)]
+    pub terminator: Option<String>,
+    /// Fixed reply length in bytes (takes precedence over `terminator`).
+    #[serde(default)]
+    pub reply_length: Option<usize>,
+    #[serde(default)]
+    pub checksum: Checksum,
+    /// Reply timeout in milliseconds (default 1000).
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamType {
+    Integer,
+    Number,
+    String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParamDef {
+    #[serde(rename = "type")]
+    pub kind: ParamType,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Allowed values for string params.
+    #[serde(default)]
+    pub values: Vec<String>,
+    /// Hex encoding: integer width in bytes (1-8, default 1).
+    #[serde(default)]
+    pub bytes: Option<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    Integer,
+    Number,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseDef {
+    /// Regex the reply must match; named groups become result fields.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Types for named groups (untyped groups are returned as strings).
+    #[serde(default)]
+    pub fields: BTreeMap<String, FieldType>,
+    /// Regex marking a device-reported error.
+    #[serde(default)]
+    pub error_pattern: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommandDef {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Command template; `{param}` inserts a parameter, `{{`/`}}` are literal braces.
+    pub send: String,
+    #[serde(default)]
+    pub params: BTreeMap<String, ParamDef>,
+    #[serde(default)]
+    pub response: ResponseDef,
+    /// The device does not answer this command.
+    #[serde(default)]
+    pub no_reply: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProtocolDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub framing: FramingDef,
+    pub commands: Vec<CommandDef>,
+}
+
+/// How the port should read a reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    None,
+    Terminator(Vec<u8>),
+    Length(usize),
+}
+
+/// Byte transport for protocol tools (a serial port in production).
+#[async_trait]
+pub trait ProtocolPort: Send + Sync {
+    /// Write `frame` and read one reply as described by `reply`.
+    async fn exchange(&self, frame: &[u8], reply: &Reply) -> Result<Vec<u8>>;
+}
+
+#[derive(Debug)]
+struct Framing {
+    encoding: Encoding,
+    suffix: Vec<u8>,
+    reply: Reply,
+    checksum: Checksum,
+    timeout: Duration,
+}
+
+#[derive(Debug)]
+enum Segment {
+    Literal(String),
+    Param(String),
+}
+
+#[derive(Debug)]
+struct Command {
+    def: CommandDef,
+    segments: Vec<Segment>,
+    pattern: Option<Regex>,
+    error_pattern: Option<Regex>,
+}
+
+/// A validated protocol definition, ready to produce tools.
+#[derive(Debug)]
+pub struct Protocol {
+    pub name: String,
+    pub description: String,
+    framing: Arc<Framing>,
+    commands: Vec<Arc<Command>>,
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn decode_hex(field: &str, text: &str) -> Result<Vec<u8>> {
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    hex::decode(&compact).with_context(|| format!("{field} is not valid hex: {text:?}"))
+}
+
+fn parse_template(template: &str) -> Result<Vec<Segment>> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => anyhow::bail!("unclosed '{{' in {template:?}"),
+                    }
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Param(name));
+            }
+            '}' => anyhow::bail!("unmatched '}}' in {template:?}"),
+            _ => literal.push(ch),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+impl Protocol {
+    /// Read and validate a protocol definition file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read protocol {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("Invalid protocol {}", path.display()))
+    }
+
+    /// Parse and validate a protocol definition.
+    pub fn parse(text: &str) -> Result<Self> {
+        let def: ProtocolDefinition = toml::from_str(text)?;
+        if !is_identifier(&def.name) {
+            anyhow::bail!(
+                "name {:?} must be lowercase letters, digits and '_' (starting with a letter)",
+                def.name
+            );
+        }
+        if def.commands.is_empty() {
+            anyhow::bail!("protocol defines no commands");
+        }
+
+        let framing_def = &def.framing;
+        let hex = framing_def.encoding == Encoding::Hex;
+        let bytes_of = |field: &str, value: &str| -> Result<Vec<u8>> {
+            if hex {
+                decode_hex(field, value)
+            } else {
+                Ok(value.as_bytes().to_vec())
+            }
+        };
+        let suffix = match &framing_def.suffix {
+            Some(suffix) => bytes_of("framing.suffix", suffix)?,
+            None if hex => Vec::new(),
+            None => b"\n".to_vec(),
+        };
+        let reply = match (framing_def.reply_length, &framing_def.terminator) {
+            (Some(0), _) => anyhow::bail!("framing.reply_length must be greater than 0"),
+            (Some(len), _) if len > MAX_REPLY_BYTES => {
+                anyhow::bail!("framing.reply_length must be at most {MAX_REPLY_BYTES}")
+            }
+            (Some(len), _) => Reply::Length(len),
+            (None, Some(terminator)) => {
+                let terminator = bytes_of("framing.terminator", terminator)?;
+                if terminator.is_empty() {
+                    anyhow::bail!("framing.terminator must not be empty");
+                }
+                Reply::Terminator(terminator)
+            }
+            (None, None) if hex => Reply::None,
+            (None, None) => Reply::Terminator(b"\n".to_vec()),
+        };
+        let framing = Framing {
+            encoding: framing_def.encoding,
+            suffix,
+            reply,
+            checksum: framing_def.checksum,
+            timeout: Duration::from_millis(framing_def.timeout_ms.unwrap_or(1000).max(1)),
+        };
+
+        let mut commands = Vec::new();
+        for command in &def.commands {
+            let compiled = Self::compile_command(&def.name, command, &framing)
+                .with_context(|| format!("command {:?}", command.name))?;
+            if commands
+                .iter()
+                .any(|c: &Arc<Command>| c.def.name == command.name)
+            {
+                anyhow::bail!("duplicate command {:?}", command.name);
+            }
+            commands.push(Arc::new(compiled));
+        }
+
+        Ok(Self {
+            name: def.name,
+            description: def.description,
+            framing: Arc::new(framing),
+            commands,
+        })
+    }
+
+    fn compile_command(protocol: &str, def: &CommandDef, framing: &Framing) -> Result<Command> {
+        if !is_identifier(&def.name) {
+            anyhow::bail!(
+                "name must be lowercase letters, digits and '_' (starting with a letter)"
+            );
+        }
+        if protocol.len() + 1 + def.name.len() > MAX_TOOL_NAME_LEN {
+            anyhow::bail!("tool name {protocol}_{} is too long", def.name);
+        }
+        if !def.no_reply && framing.reply == Reply::None {
+            anyhow::bail!(
+                "expects a reply but framing has no terminator or reply_length (set no_reply = true?)"
+            );
+        }
+
+        let hex = framing.encoding == Encoding::Hex;
+        let segments = parse_template(&def.send)?;
+        for segment in &segments {
+            match segment {
+                Segment::Param(name) if !def.params.contains_key(name) => {
+                    anyhow::bail!("send uses undeclared param {{{name}}}")
+                }
+                Segment::Literal(text) if hex => {
+                    decode_hex("send", text)?;
+                    if text.chars().filter(char::is_ascii_hexdigit).count() % 2 != 0 {
+                        anyhow::bail!("send has an odd number of hex digits in {text:?}");
+                    }
+                }
+                _ => {}
+            }
+        }
+        for (name, param) in &def.params {
+            if !segments
+                .iter()
+                .any(|s| matches!(s, Segment::Param(p) if p == name))
+            {
+                anyhow::bail!("param {name:?} is not used in send");
+            }
+            if hex && param.kind != ParamType::Integer {
+                anyhow::bail!("param {name:?}: hex encoding only supports integer params");
+            }
+            if param.bytes.is_some_and(|bytes| !(1..=8).contains(&bytes)) {
+                anyhow::bail!("param {name:?}: bytes must be between 1 and 8");
+            }
+            if !param.values.is_empty() && param.kind != ParamType::String {
+                anyhow::bail!("param {name:?}: values is only allowed for string params");
+            }
+        }
+
+        let compile = |field: &str, pattern: &Option<String>| -> Result<Option<Regex>> {
+            pattern
+                .as_deref()
+                .map(|p| Regex::new(p).with_context(|| format!("invalid {field}")))
+                .transpose()
+        };
+        let pattern = compile("response.pattern", &def.response.pattern)?;
+        let error_pattern = compile("response.error_pattern", &def.response.error_pattern)?;
+        for field in def.response.fields.keys() {
+            let captured = pattern
+                .as_ref()
+                .is_some_and(|re| re.capture_names().flatten().any(|n| n == field));
+            if !captured {
+                anyhow::bail!("response field {field:?} is not a named group in response.pattern");
+            }
+        }
+        if def.no_reply && (pattern.is_some() || error_pattern.is_some()) {
+            anyhow::bail!("no_reply commands cannot have response patterns");
+        }
+
+        Ok(Command {
+            def: def.clone(),
+            segments,
+            pattern,
+            error_pattern,
+        })
+    }
+
+    /// Tool names this protocol provides, in definition order.
+    pub fn tool_names(&self) -> Vec<String> {
+        self.commands
+            .iter()
+            .map(|command| format!("{}_{}", self.name, command.def.name))
+            .collect()
+    }
+
+    /// One tool per command, all sharing `port`.
+    pub fn tools(&self, port: Arc<dyn ProtocolPort>) -> Vec<Box<dyn Tool>> {
+        self.commands
+            .iter()
+            .map(|command| {
+                let mut description = command.def.description.clone();
+                if description.is_empty() {
+                    description = format!("Send the {} command", command.def.name);
+                }
+                if !self.description.is_empty() {
+                    description = format!("{} ({description})", self.description);
+                }
+                Box::new(ProtocolCommandTool {
+                    name: format!("{}_{}", self.name, command.def.name),
+                    description,
+                    framing: self.framing.clone(),
+                    command: command.clone(),
+                    port: port.clone(),
+                }) as Box<dyn Tool>
+            })
+            .collect()
+    }
+}
+
+impl Command {
+    fn schema(&self) -> Value {
+        let mut properties = Map::new();
+        for (name, param) in &self.def.params {
+            let mut prop = json!({
+                "type": match param.kind {
+                    ParamType::Integer => "integer",
+                    ParamType::Number => "number",
+                    ParamType::String => "string",
+                },
+                "description": param.description,
+            });
+            if let Some(min) = param.min {
+                prop["minimum"] = json!(min);
+            }
+            if let Some(max) = param.max {
+                prop["maximum"] = json!(max);
+            }
+            if !param.values.is_empty() {
+                prop["enum"] = json!(param.values);
+            }
+            properties.insert(name.clone(), prop);
+        }
+        let required: Vec<&String> = self.def.params.keys().collect();
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        })
+    }
+
+    fn render_param(&self, name: &str, args: &Value, encoding: Encoding) -> Result<String> {
+        let param = &self.def.params[name];
+        let value = args
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("Missing '{name}' parameter"))?;
+        let check_range = |number: f64| -> Result<()> {
+            if param.min.is_some_and(|min| number < min)
+                || param.max.is_some_and(|max| number > max)
+            {
+                anyhow::bail!("'{name}' is out of range");
+            }
+            Ok(())
+        };
+        match param.kind {
+            ParamType::Integer => {
+                let number = value
+                    .as_i64()
+                    .ok_or_else(|| anyhow::anyhow!("'{name}' must be an integer"))?;
+                #[allow(clippy::cast_precision_loss)]
+                check_range(number as f64)?;
+                if encoding == Encoding::Hex {
+                    let bytes = usize::from(param.bytes.unwrap_or(1));
+                    let unsigned = u64::try_from(number)
+                        .map_err(|_| anyhow::anyhow!("'{name}' must not be negative"))?;
+                    if bytes < 8 && unsigned >> (bytes * 8) != 0 {
+                        anyhow::bail!("'{name}' does not fit in {bytes} byte(s)");
+                    }
+                    Ok(hex::encode_upper(&unsigned.to_be_bytes()[8 - bytes..]))
+                } else {
+                    Ok(number.to_string())
+                }
+            }
+            ParamType::Number => {
+                let number = value
+                    .as_f64()
+                    .ok_or_else(|| anyhow::anyhow!("'{name}' must be a number"))?;
+                check_range(number)?;
+                Ok(number.to_string())
+            }
+            ParamType::String => {
+                let text = value
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("'{name}' must be a string"))?;
+                if text.chars().any(char::is_control) {
+                    anyhow::bail!("'{name}' must not contain control characters");
+                }
+                if !param.values.is_empty() && !param.values.iter().any(|v| v == text) {
+                    anyhow::bail!("'{name}' must be one of: {}", param.values.join(", "));
+                }
+                Ok(text.to_string())
+            }
+        }
+    }
+
+    /// Build the bytes to send for `args`.
+    fn encode(&self, framing: &Framing, args: &Value) -> Result<Vec<u8>> {
+        let mut rendered = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => rendered.push_str(text),
+                Segment::Param(name) => {
+                    rendered.push_str(&self.render_param(name, args, framing.encoding)?);
+                }
+            }
+        }
+        let mut frame = match framing.encoding {
+            Encoding::Text => rendered.into_bytes(),
+            Encoding::Hex => decode_hex("send", &rendered)?,
+        };
+        match framing.checksum {
+            Checksum::None => {}
+            Checksum::Xor8 => frame.push(frame.iter().fold(0, |acc, b| acc ^ b)),
+            Checksum::Sum8 => frame.push(frame.iter().fold(0u8, |acc, b| acc.wrapping_add(*b))),
+        }
+        frame.extend_from_slice(&framing.suffix);
+        Ok(frame)
+    }
+
+    /// Interpret a raw reply according to the response definition.
+    fn parse_reply(&self, framing: &Framing, raw: &[u8]) -> ToolResult {
+        let mut raw = raw;
+        if let Reply::Terminator(terminator) = &framing.reply {
+            raw = raw.strip_suffix(terminator.as_slice()).unwrap_or(raw);
+        }
+        let text = match framing.encoding {
+            Encoding::Text => String::from_utf8_lossy(raw).trim().to_string(),
+            Encoding::Hex => hex::encode_upper(raw),
+        };
+
+        if self
+            .error_pattern
+            .as_ref()
+            .is_some_and(|re| re.is_match(&text))
+        {
+            return ToolResult {
+                success: false,
+                output: text.clone(),
+                error: Some(format!("Device reported an error: {text}")),
+            };
+        }
+        let Some(pattern) = &self.pattern else {
+            return ToolResult {
+                success: true,
+                output: text,
+                error: None,
+            };
+        };
+        let Some(captures) = pattern.captures(&text) else {
+            return ToolResult {
+                success: false,
+                output: text.clone(),
+                error: Some(format!("Unexpected reply: {text}")),
+            };
+        };
+
+        let mut fields = Map::new();
+        for name in pattern.capture_names().flatten() {
+            let Some(matched) = captures.name(name) else {
+                continue;
+            };
+            let value = matched.as_str();
+            let radix = if framing.encoding == Encoding::Hex {
+                16
+            } else {
+                10
+            };
+            let typed = match self.def.response.fields.get(name) {
+                Some(FieldType::Integer) => i64::from_str_radix(value, radix).ok().map(Value::from),
+                Some(FieldType::Number) => value.parse::<f64>().ok().map(Value::from),
+                Some(FieldType::String) | None => Some(Value::from(value)),
+            };
+            match typed {
+                Some(typed) => {
+                    fields.insert(name.to_string(), typed);
+                }
+                None => {
+                    return ToolResult {
+                        success: false,
+                        output: text.clone(),
+                        error: Some(format!(
+                            "Reply field '{name}' is not a valid number: {value}"
+                        )),
+                    };
+                }
+            }
+        }
+        ToolResult {
+            success: true,
+            output: json!({ "fields": fields, "raw": text }).to_string(),
+            error: None,
+        }
+    }
+}
+
+/// Tool: one command from a protocol definition.
+struct ProtocolCommandTool {
+    name: String,
+    description: String,
+    framing: Arc<Framing>,
+    command: Arc<Command>,
+    port: Arc<dyn ProtocolPort>,
+}
+
+#[async_trait]
+impl Tool for ProtocolCommandTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        self.command.schema()
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolResult> {
+        let frame = match self.command.encode(&self.framing, &args) {
+            Ok(frame) => frame,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e.to_string()),
+                })
+            }
+        };
+        let reply = if self.command.def.no_reply {
+            Reply::None
+        } else {
+            self.framing.reply.clone()
+        };
+        let raw = tokio::time::timeout(self.framing.timeout, self.port.exchange(&frame, &reply))
+            .await
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "No reply from device within {}ms",
+                    self.framing.timeout.as_millis()
+                )
+            })??;
+        if self.command.def.no_reply {
+            return Ok(ToolResult {
+                success: true,
+                output: "sent".into(),
+                error: None,
+            });
+        }
+        Ok(self.command.parse_reply(&self.framing, &raw))
+    }
+}
+
+/// Serial port transport for protocol tools.
+#[cfg(feature = "hardware")]
+pub struct SerialProtocolPort {
+    port: tokio::sync::Mutex<tokio_serial::SerialStream>,
+}
+
+#[cfg(feature = "hardware")]
+impl SerialProtocolPort {
+    pub fn open(config: &crate::config::PeripheralBoardConfig) -> Result<Self> {
+        Ok(Self {
+            port: tokio::sync::Mutex::new(super::serial::open_port(config)?),
+        })
+    }
+}
+
+#[cfg(feature = "hardware")]
+#[async_trait]
+impl ProtocolPort for SerialProtocolPort {
+    async fn exchange(&self, frame: &[u8], reply: &Reply) -> Result<Vec<u8>> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut port = self.port.lock().await;
+        port.write_all(frame).await?;
+        port.flush().await?;
+        match reply {
+            Reply::None => Ok(Vec::new()),
+            Reply::Length(len) => {
+                let mut buf = vec![0u8; *len];
+                port.read_exact(&mut buf).await?;
+                Ok(buf)
+            }
+            Reply::Terminator(terminator) => {
+                let mut buf = Vec::new();
+                let mut byte = [0u8; 1];
+                while !buf.ends_with(terminator) {
+                    if buf.len() >= MAX_REPLY_BYTES {
+                        anyhow::bail!("Reply exceeded {MAX_REPLY_BYTES} bytes without terminator");
+                    }
+                    port.read_exact(&mut byte).await?;
+                    buf.push(byte[0]);
+                }
+                Ok(buf)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    /// Records frames and answers with canned replies.
+    struct FakePort {
+        sent: Mutex<Vec<Vec<u8>>>,
+        reply: Vec<u8>,
+    }
+
+    #[async_trait]
+    impl ProtocolPort for FakePort {
+        async fn exchange(&self, frame: &[u8], reply: &Reply) -> Result<Vec<u8>> {
+            self.sent.lock().push(frame.to_vec());
+            Ok(match reply {
+                Reply::None => Vec::new(),
+                _ => self.reply.clone(),
+            })
+        }
+    }
+
+    fn fake(reply: &[u8]) -> Arc<FakePort> {
+        Arc::new(FakePort {
+            sent: Mutex::new(Vec::new()),
+            reply: reply.to_vec(),
+        })
+    }
+
+    const PSU: &str = r#"
+name = "psu"
+description = "Bench PSU"
+
+[[commands]]
+name = "set_voltage"
+send = "VSET1:{volts}"
+no_reply = true
+params.volts = { type = "number", min = 0, max = 30 }
+
+[[commands]]
+name = "read_voltage"
+send = "VOUT1?"
+response.pattern = '^(?P<volts>[0-9.]+)V$'
+response.fields.volts = "number"
+response.error_pattern = '^ERR'
+"#;
+
+    #[tokio::test]
+    async fn text_protocol_encodes_params_and_parses_typed_fields() {
+        let protocol = Protocol::parse(PSU).unwrap();
+        assert_eq!(
+            protocol.tool_names(),
+            ["psu_set_voltage", "psu_read_voltage"]
+        );
+
+        let port = fake(b"12.5V\r\n");
+        let tools = protocol.tools(port.clone());
+        let set = tools[0].execute(json!({"volts": 12.5})).await.unwrap();
+        assert!(set.success);
+        let out_of_range = tools[0].execute(json!({"volts": 48})).await.unwrap();
+        assert!(!out_of_range.success);
+
+        let read = tools[1].execute(json!({})).await.unwrap();
+        assert!(read.success, "{:?}", read.error);
+        let output: Value = serde_json::from_str(&read.output).unwrap();
+        assert_eq!(output["fields"]["volts"], json!(12.5));
+        assert_eq!(
+            *port.sent.lock(),
+            [b"VSET1:12.5\n".to_vec(), b"VOUT1?\n".to_vec()]
+        );
+
+        let err = protocol.tools(fake(b"ERR 3\n"))[1]
+            .execute(json!({}))
+            .await
+            .unwrap();
+        assert!(!err.success);
+    }
+
+    #[tokio::test]
+    async fn hex_protocol_applies_width_and_checksum() {
+        let protocol = Protocol::parse(
+            r#"
+name = "relay"
+
+[framing]
+encoding = "hex"
+reply_length = 3
+checksum = "xor8"
+
+[[commands]]
+name = "set"
+send = "AA 01 {channel} {state}"
+params.channel = { type = "integer", min = 1, max = 8 }
+params.state = { type = "integer", bytes = 2 }
+response.pattern = '^AA(?P<status>[0-9A-F]{2})'
+response.fields.status = "integer"
+"#,
+        )
+        .unwrap();
+        let port = fake(&[0xAA, 0x1F, 0x00]);
+        let tool = &protocol.tools(port.clone())[0];
+        let result = tool
+            .execute(json!({"channel": 3, "state": 256}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("\"status\":31"));
+        assert_eq!(
+            port.sent.lock()[0],
+            [0xAA, 0x01, 0x03, 0x01, 0x00, 0xAA ^ 0x01 ^ 0x03 ^ 0x01]
+        );
+    }
+
+    #[test]
+    fn rejects_inconsistent_definitions() {
+        let undeclared = "name = \"x\"\n[[commands]]\nname = \"a\"\nsend = \"{v}\"\n";
+        assert!(Protocol::parse(undeclared).is_err());
+        let bad_name = "name = \"X-1\"\n[[commands]]\nname = \"a\"\nsend = \"A\"\n";
+        assert!(Protocol::parse(bad_name).is_err());
+        let hex_without_reply =
+            "name = \"x\"\n[framing]\nencoding = \"hex\"\n[[commands]]\nname = \"a\"\nsend = \"01\"\n";
+        assert!(Protocol::parse(hex_without_reply).is_err());
+        let stray_field = "name = \"x\"\n[[commands]]\nname = \"a\"\nsend = \"A\"\nresponse.fields.v = \"number\"\n";
+        assert!(Protocol::parse(stray_field).is_err());
+    }
+}
@@ -30,6 +30,25 @@ fn is_path_allowed(path: &str) -> bool {
     ALLOWED_PATH_PREFIXES.iter().any(|p| path.starts_with(p))
 }
 
+/// Open the board's serial port after checking the path against the allowlist.
+pub(crate) fn open_port(config: &PeripheralBoardConfig) -> anyhow::Result<SerialStream> {
+    let path = config
+        .path
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("Serial peripheral requires path"))?;
+
+    if !is_path_allowed(path) {
+        anyhow::bail!(
+            "Serial path not allowed: {}. Allowed: /dev/ttyACM*, /dev/ttyUSB*, /dev/tty.usbmodem*, /dev/cu.usbmodem*",
+            path
+        );
+    }
+
+    tokio_serial::new(path, config.baud)
+        .open_native_async()
+        .map_err(|e| anyhow::anyhow!("Failed to open {}: {}", path, e))
+}
+
 /// JSON request/response over serial.
 async fn send_request(port: &mut SerialStream, cmd: &str, args: Value) -> anyhow::Result<Value> {
     static ID: AtomicU64 = AtomicU64::new(0);
@@ -114,21 +133,8 @@ impl SerialPeripheral {
     /// Create and connect to a serial peripheral.
     #[allow(clippy::unused_async)]
     pub async fn connect(config: &PeripheralBoardConfig) -> anyhow::Result<Self> {
-        let path = config
-            .path
-            .as_deref()
-            .ok_or_else(|| anyhow::anyhow!("Serial peripheral requires path"))?;
-
-        if !is_path_allowed(path) {
-            anyhow::bail!(
-                "Serial path not allowed: {}. Allowed: /dev/ttyACM*, /dev/ttyUSB*, /dev/tty.usbmodem*, /dev/cu.usbmodem*",
-                path
-            );
-        }
-
-        let port = tokio_serial::new(path, config.baud)
-            .open_native_async()
-            .map_err(|e| anyhow::anyhow!("Failed to open {}: {}", path, e))?;
+        let port = open_port(config)?;
+        let path = config.path.as_deref().unwrap_or_default();
 
         let name = format!("{}-{}", config.board, path.replace('/', "_"));
         let transport = Arc::new(SerialTransport {
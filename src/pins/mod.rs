@@ -0,0 +1,400 @@
+//! Context pins (`[pins]`, `/pin`, the `pin` tool).
+//!
+//! A pin marks a memory entry, a workspace file or a short note as always
+//! included: pinned content is rendered into the system prompt, which history
+//! trimming and compaction never touch, so it survives however long the
+//! conversation gets. Pins come from `[pins]` in config and from
+//! `<workspace>/pins.toml` (managed with `/pin` and the `pin` tool).
+//!
+//! Pins share a token budget. Pins are included in order (config first, then
+//! by id); one that no longer fits is left out with a warning instead of
+//! silently crowding out the conversation.
+
+use crate::config::{Config, PinsConfig};
+use crate::memory::Memory;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+
+const PINS_FILE: &str = "pins.toml";
+const MAX_PINS: usize = 50;
+const MAX_NOTE_CHARS: usize = 2_000;
+
+static PINS: LazyLock<RwLock<PinsConfig>> = LazyLock::new(|| RwLock::new(PinsConfig::default()));
+
+/// Apply `[pins]` from config.
+pub fn init_from_config(config: &Config) {
+    let mut guard = PINS.write().unwrap_or_else(|e| e.into_inner());
+    *guard = config.pins.clone();
+}
+
+fn settings() -> PinsConfig {
+    PINS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// What a pin refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PinKind {
+    /// A memory entry, by key.
+    Memory,
+    /// A file, by workspace-relative path.
+    File,
+    /// Literal text, such as an instruction.
+    Note,
+}
+
+impl PinKind {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "memory" => Ok(Self::Memory),
+            "file" => Ok(Self::File),
+            "note" | "text" | "instruction" => Ok(Self::Note),
+            _ => bail!("Unknown pin kind '{raw}' (expected memory, file or note)"),
+        }
+    }
+}
+
+impl fmt::Display for PinKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Memory => "memory",
+            Self::File => "file",
+            Self::Note => "note",
+        })
+    }
+}
+
+/// One pin from `pins.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pin {
+    pub id: u64,
+    pub kind: PinKind,
+    /// Memory key, workspace-relative path or note text.
+    pub target: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PinsFile {
+    #[serde(default)]
+    next_id: u64,
+    #[serde(default)]
+    pins: Vec<Pin>,
+}
+
+/// An interactive pin command: `/pin [memory|file|note] <target>`, `/pins`
+/// or `/unpin <id>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Add(PinKind, String),
+    Remove(u64),
+    List,
+}
+
+/// Parse a slash command; `/pin <text>` without a kind pins a note.
+pub fn parse_command(text: &str) -> Option<Command> {
+    let text = text.trim();
+    let (head, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    let rest = rest.trim();
+    match head {
+        "/pins" => Some(Command::List),
+        "/unpin" => rest
+            .trim_start_matches('#')
+            .parse()
+            .ok()
+            .map(Command::Remove),
+        "/pin" if rest.is_empty() => Some(Command::List),
+        "/pin" => {
+            let (kind, target) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            match kind.to_ascii_lowercase().as_str() {
+                "memory" | "file" | "note" if !target.trim().is_empty() => Some(Command::Add(
+                    PinKind::parse(kind).ok()?,
+                    target.trim().to_string(),
+                )),
+                _ => Some(Command::Add(PinKind::Note, rest.to_string())),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Reject paths that could leave the workspace.
+fn check_relative_path(path: &str) -> Result<()> {
+    let candidate = Path::new(path);
+    if path.is_empty()
+        || candidate.is_absolute()
+        || candidate
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        bail!("Pinned files must be relative paths inside the workspace: '{path}'");
+    }
+    Ok(())
+}
+
+/// Workspace-backed pins.
+pub struct PinStore {
+    path: PathBuf,
+}
+
+impl PinStore {
+    pub fn for_workspace(workspace_dir: &Path) -> Self {
+        Self {
+            path: workspace_dir.join(PINS_FILE),
+        }
+    }
+
+    fn load(&self) -> Result<PinsFile> {
+        if !self.path.exists() {
+            return Ok(PinsFile::default());
+        }
+        let raw = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        toml::from_str(&raw).with_context(|| format!("Failed to parse {}", self.path.display()))
+    }
+
+    fn write(&self, file: &PinsFile) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let raw = toml::to_string_pretty(file)?;
+        fs::write(&self.path, raw)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    pub fn list(&self) -> Result<Vec<Pin>> {
+        Ok(self.load()?.pins)
+    }
+
+    /// Pin `target`; pinning the same thing twice returns the existing pin.
+    pub fn add(&self, kind: PinKind, target: &str) -> Result<Pin> {
+        let target = target.trim();
+        match kind {
+            PinKind::File => check_relative_path(target)?,
+            PinKind::Memory if target.is_empty() => bail!("Memory pins need a key"),
+            PinKind::Note if target.is_empty() || target.chars().count() > MAX_NOTE_CHARS => {
+                bail!("Pinned notes must be 1-{MAX_NOTE_CHARS} characters")
+            }
+            _ => {}
+        }
+        let mut file = self.load()?;
+        if let Some(existing) = file
+            .pins
+            .iter()
+            .find(|p| p.kind == kind && p.target == target)
+        {
+            return Ok(existing.clone());
+        }
+        if file.pins.len() >= MAX_PINS {
+            bail!("At most {MAX_PINS} pins can be kept; unpin one first");
+        }
+        file.next_id = file
+            .next_id
+            .max(file.pins.iter().map(|p| p.id).max().unwrap_or(0))
+            + 1;
+        let pin = Pin {
+            id: file.next_id,
+            kind,
+            target: target.to_string(),
+            created_at: Utc::now(),
+        };
+        file.pins.push(pin.clone());
+        self.write(&file)?;
+        Ok(pin)
+    }
+
+    /// Remove a pin by id; returns it when it existed.
+    pub fn remove(&self, id: u64) -> Result<Option<Pin>> {
+        let mut file = self.load()?;
+        let Some(index) = file.pins.iter().position(|p| p.id == id) else {
+            return Ok(None);
+        };
+        let removed = file.pins.remove(index);
+        self.write(&file)?;
+        Ok(Some(removed))
+    }
+}
+
+/// Rough token estimate (chars / 4), matching the tool schema estimate.
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Pinned content resolved for one prompt.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PinnedContext {
+    /// System prompt section; empty when nothing is pinned.
+    pub section: String,
+    /// Estimated tokens used by the included pins.
+    pub tokens: usize,
+    /// Pins left out or unresolvable, for the user.
+    pub warnings: Vec<String>,
+}
+
+/// Resolve config and workspace pins against `memory` and the workspace.
+pub async fn resolve(workspace_dir: &Path, memory: Option<&dyn Memory>) -> PinnedContext {
+    let settings = settings();
+    let mut pins: Vec<(String, PinKind, String)> = Vec::new();
+    for (kind, targets) in [
+        (PinKind::Note, &settings.notes),
+        (PinKind::Memory, &settings.memories),
+        (PinKind::File, &settings.files),
+    ] {
+        pins.extend(
+            targets
+                .iter()
+                .map(|target| ("config".to_string(), kind, target.clone())),
+        );
+    }
+    match PinStore::for_workspace(workspace_dir).list() {
+        Ok(stored) => pins.extend(
+            stored
+                .into_iter()
+                .map(|pin| (format!("#{}", pin.id), pin.kind, pin.target)),
+        ),
+        Err(err) => tracing::warn!("Failed to load pins: {err:#}"),
+    }
+
+    let mut context = PinnedContext::default();
+    let mut body = String::new();
+    for (label, kind, target) in pins {
+        let (heading, content) = match kind {
+            PinKind::Note => ("Note".to_string(), Ok(target.clone())),
+            PinKind::Memory => (
+                format!("Memory `{target}`"),
+                read_memory(memory, &target).await,
+            ),
+            PinKind::File => (
+                format!("File `{target}`"),
+                read_workspace_file(workspace_dir, &target),
+            ),
+        };
+        let content = match content {
+            Ok(content) => content,
+            Err(err) => {
+                context
+                    .warnings
+                    .push(format!("Pin {label} ({kind} {target}) skipped: {err}"));
+                continue;
+            }
+        };
+        let entry = format!("### {heading}\n{}\n\n", content.trim_end());
+        let tokens = estimate_tokens(&entry);
+        if context.tokens + tokens > settings.token_budget {
+            context.warnings.push(format!(
+                "Pin {label} ({kind} {target}, ~{tokens} tokens) left out: pins exceed the {}-token budget ([pins] token_budget)",
+                settings.token_budget
+            ));
+            continue;
+        }
+        context.tokens += tokens;
+        body.push_str(&entry);
+    }
+    if !body.is_empty() {
+        let _ = write!(
+            context.section,
+            "\n\n## Pinned Context\n\n\
+             The user pinned the following so it stays in view for the whole conversation. \
+             Treat it as current and authoritative.\n\n{}",
+            body.trim_end()
+        );
+    }
+    context
+}
+
+async fn read_memory(memory: Option<&dyn Memory>, key: &str) -> Result<String> {
+    let Some(memory) = memory else {
+        bail!("memory is not available");
+    };
+    match memory.get(key).await? {
+        Some(entry) => Ok(entry.content),
+        None => bail!("no memory with this key"),
+    }
+}
+
+fn read_workspace_file(workspace_dir: &Path, path: &str) -> Result<String> {
+    check_relative_path(path)?;
+    let resolved = workspace_dir
+        .join(path)
+        .canonicalize()
+        .context("file not found")?;
+    let workspace = workspace_dir.canonicalize()?;
+    if !resolved.starts_with(&workspace) {
+        bail!("file resolves outside the workspace");
+    }
+    fs::read_to_string(&resolved).context("file is not readable text")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn store_deduplicates_and_rejects_escaping_paths() {
+        let tmp = TempDir::new().unwrap();
+        let store = PinStore::for_workspace(tmp.path());
+
+        let first = store.add(PinKind::File, "docs/plan.md").unwrap();
+        assert_eq!(store.add(PinKind::File, " docs/plan.md ").unwrap(), first);
+        assert!(store.add(PinKind::File, "../secrets.txt").is_err());
+        assert!(store.add(PinKind::File, "/etc/passwd").is_err());
+        assert!(store.add(PinKind::Note, "  ").is_err());
+
+        let note = store.add(PinKind::Note, "never deploy on Fridays").unwrap();
+        assert!(note.id > first.id);
+        assert_eq!(
+            store.remove(first.id).unwrap().unwrap().target,
+            "docs/plan.md"
+        );
+        assert!(store.remove(first.id).unwrap().is_none());
+        assert_eq!(store.list().unwrap(), [note]);
+        assert_eq!(PinKind::parse("Instruction").unwrap(), PinKind::Note);
+
+        assert_eq!(
+            parse_command("/pin file docs/plan.md"),
+            Some(Command::Add(PinKind::File, "docs/plan.md".into()))
+        );
+        assert_eq!(
+            parse_command("/pin always answer in French"),
+            Some(Command::Add(
+                PinKind::Note,
+                "always answer in French".into()
+            ))
+        );
+        assert_eq!(parse_command("/unpin #2"), Some(Command::Remove(2)));
+        assert_eq!(parse_command("/pins"), Some(Command::List));
+        assert_eq!(parse_command("/pinned"), None);
+    }
+
+    #[tokio::test]
+    async fn resolve_renders_pins_within_budget_and_warns_on_overflow() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("plan.md"), "Ship v2 by Friday.\n").unwrap();
+        fs::write(tmp.path().join("big.md"), "x".repeat(10_000)).unwrap();
+        let store = PinStore::for_workspace(tmp.path());
+        store.add(PinKind::File, "plan.md").unwrap();
+        let big = store.add(PinKind::File, "big.md").unwrap();
+        store.add(PinKind::File, "missing.md").unwrap();
+        store.add(PinKind::Memory, "project_goal").unwrap();
+
+        // Default budget: the small file fits, the large one does not.
+        let context = resolve(tmp.path(), None).await;
+        assert!(context.section.contains("## Pinned Context"));
+        assert!(context
+            .section
+            .contains("### File `plan.md`\nShip v2 by Friday."));
+        assert!(!context.section.contains("xxxx"));
+        assert_eq!(context.warnings.len(), 3);
+        assert!(context.warnings[0].contains(&format!("Pin #{}", big.id)));
+        assert!(context.warnings[0].contains("token budget"));
+        assert!(context.warnings[1].contains("missing.md"));
+        assert!(context.warnings[2].contains("memory is not available"));
+        assert!(context.tokens > 0 && context.tokens < 100);
+    }
+}
@@ -0,0 +1,567 @@
+//! Long-term reply preferences per identity.
+//!
+//! Each identity keeps a small profile (tone, verbosity, formatting, units,
+//! language) in `<workspace>/preferences.toml`, injected into the system
+//! prompt of that identity's sessions. Identities use the same names as RAG
+//! collection readers: `cli`, `<channel>:<sender>`, and `contact:<name>`.
+//! A sender who is in the contact book shares one `contact:` profile across
+//! all of their linked addresses.
+//!
+//! Profiles are edited with `zeroclaw identity prefs` or by the user in plain
+//! language ("be more concise", "reply in German", "use metric units").
+
+use crate::channels::translation;
+use crate::contacts::{Contact, ContactBook};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PREFERENCES_FILE: &str = "preferences.toml";
+const MAX_TONE_CHARS: usize = 60;
+
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("arabic", "ar"),
+    ("chinese", "zh"),
+    ("dutch", "nl"),
+    ("english", "en"),
+    ("french", "fr"),
+    ("german", "de"),
+    ("hindi", "hi"),
+    ("italian", "it"),
+    ("japanese", "ja"),
+    ("korean", "ko"),
+    ("polish", "pl"),
+    ("portuguese", "pt"),
+    ("russian", "ru"),
+    ("spanish", "es"),
+    ("swedish", "sv"),
+    ("turkish", "tr"),
+    ("ukrainian", "uk"),
+];
+
+/// Whole-message phrasings that set a fixed preference.
+const PHRASES: &[(&[&str], Field, &str)] = &[
+    (
+        &[
+            "be more concise",
+            "be concise",
+            "be brief",
+            "be more brief",
+            "keep it short",
+            "keep it shorter",
+            "shorter answers",
+            "shorter replies",
+            "less verbose",
+            "be less verbose",
+        ],
+        Field::Verbosity,
+        "concise",
+    ),
+    (
+        &[
+            "be more detailed",
+            "be detailed",
+            "be more thorough",
+            "more detail",
+            "more details",
+            "longer answers",
+            "longer replies",
+            "go into more detail",
+        ],
+        Field::Verbosity,
+        "detailed",
+    ),
+    (
+        &["use metric", "use metric units", "use the metric system"],
+        Field::Units,
+        "metric",
+    ),
+    (
+        &[
+            "use imperial",
+            "use imperial units",
+            "use the imperial system",
+        ],
+        Field::Units,
+        "imperial",
+    ),
+    (
+        &[
+            "no markdown",
+            "use plain text",
+            "don't use markdown",
+            "do not use markdown",
+            "stop using markdown",
+        ],
+        Field::Formatting,
+        "plain",
+    ),
+    (&["use markdown"], Field::Formatting, "markdown"),
+];
+
+const TONE_WORDS: &[&str] = &[
+    "casual",
+    "direct",
+    "formal",
+    "friendly",
+    "playful",
+    "professional",
+    "relaxed",
+    "warm",
+];
+
+/// A preference a profile can hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Tone,
+    Verbosity,
+    Formatting,
+    Units,
+    Language,
+}
+
+impl Field {
+    pub const ALL: [Self; 5] = [
+        Self::Tone,
+        Self::Verbosity,
+        Self::Formatting,
+        Self::Units,
+        Self::Language,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Tone => "tone",
+            Self::Verbosity => "verbosity",
+            Self::Formatting => "formatting",
+            Self::Units => "units",
+            Self::Language => "language",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|field| field.as_str() == name)
+            .with_context(|| {
+                format!("Unknown preference '{name}' (expected tone, verbosity, formatting, units or language)")
+            })
+    }
+
+    /// Validate and normalize a value for this field.
+    pub fn normalize(self, value: &str) -> Result<String> {
+        let value = value.trim();
+        let lower = value.to_ascii_lowercase();
+        let one_of = |allowed: &[&str]| {
+            if allowed.contains(&lower.as_str()) {
+                Ok(lower.clone())
+            } else {
+                bail!(
+                    "Invalid {} '{value}' (expected {})",
+                    self.as_str(),
+                    allowed.join(", ")
+                )
+            }
+        };
+        match self {
+            Self::Tone => {
+                if value.is_empty() || value.chars().count() > MAX_TONE_CHARS {
+                    bail!("tone must be 1-{MAX_TONE_CHARS} characters");
+                }
+                Ok(value.to_string())
+            }
+            Self::Verbosity => one_of(&["concise", "balanced", "detailed"]),
+            Self::Formatting => one_of(&["plain", "markdown"]),
+            Self::Units => one_of(&["metric", "imperial"]),
+            Self::Language => language_code(value)
+                .with_context(|| format!("Invalid language '{value}' (use a code such as \"de\")")),
+        }
+    }
+}
+
+/// Code of a language named in English ("german") or by one of the known
+/// codes ("de"). Plain-language instructions only accept these, so "speak
+/// up" is not read as a language.
+fn known_language(value: &str) -> Option<String> {
+    let lower = value.trim().to_ascii_lowercase();
+    LANGUAGE_NAMES
+        .iter()
+        .find(|(name, code)| *name == lower || *code == lower)
+        .map(|(_, code)| (*code).to_string())
+}
+
+/// ISO 639-1 code for a code or an English language name.
+fn language_code(value: &str) -> Option<String> {
+    let lower = value.trim().to_ascii_lowercase();
+    if let Some((_, code)) = LANGUAGE_NAMES.iter().find(|(name, _)| *name == lower) {
+        return Some((*code).to_string());
+    }
+    translation::is_language_code(&lower).then(|| translation::normalize_language(&lower))
+}
+
+/// One identity's profile.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Preferences {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formatting: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub units: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+impl Preferences {
+    fn slot(&mut self, field: Field) -> &mut Option<String> {
+        match field {
+            Field::Tone => &mut self.tone,
+            Field::Verbosity => &mut self.verbosity,
+            Field::Formatting => &mut self.formatting,
+            Field::Units => &mut self.units,
+            Field::Language => &mut self.language,
+        }
+    }
+
+    pub fn get(&self, field: Field) -> Option<&str> {
+        match field {
+            Field::Tone => self.tone.as_deref(),
+            Field::Verbosity => self.verbosity.as_deref(),
+            Field::Formatting => self.formatting.as_deref(),
+            Field::Units => self.units.as_deref(),
+            Field::Language => self.language.as_deref(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        Field::ALL.iter().all(|field| self.get(*field).is_none())
+    }
+
+    /// `field=value` pairs, e.g. `verbosity=concise, units=metric`.
+    pub fn summary(&self) -> String {
+        Field::ALL
+            .iter()
+            .filter_map(|field| {
+                self.get(*field)
+                    .map(|value| format!("{}={value}", field.as_str()))
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// System prompt section; empty when nothing is set.
+    pub fn prompt_section(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let mut section = String::from(
+            "\n\n## User Preferences\n\nThis user asked for the following. Follow it unless a message asks otherwise.\n",
+        );
+        if let Some(tone) = &self.tone {
+            let _ = writeln!(section, "- Tone: {tone}.");
+        }
+        match self.verbosity.as_deref() {
+            Some("concise") => section.push_str("- Keep replies short and to the point.\n"),
+            Some("detailed") => {
+                section.push_str("- Give thorough, detailed replies with explanations.\n");
+            }
+            Some(other) => {
+                let _ = writeln!(section, "- Verbosity: {other}.");
+            }
+            None => {}
+        }
+        match self.formatting.as_deref() {
+            Some("plain") => section.push_str("- Use plain text without Markdown formatting.\n"),
+            Some(other) => {
+                let _ = writeln!(section, "- Formatting: {other}.");
+            }
+            None => {}
+        }
+        if let Some(units) = &self.units {
+            let _ = writeln!(section, "- Use {units} units.");
+        }
+        if let Some(language) = &self.language {
+            let _ = writeln!(section, "- Reply in the language with code `{language}`.");
+        }
+        section
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PreferencesFile {
+    #[serde(default)]
+    profiles: BTreeMap<String, Preferences>,
+}
+
+/// Identity name for a sender: `contact:<name>` when the address belongs to
+/// a contact, `<channel>:<sender>` otherwise, or the bare surface (`cli`)
+/// when there is no sender.
+pub fn identity_key(channel: &str, sender: &str, contacts: &[Contact]) -> String {
+    if sender.is_empty() {
+        return channel.to_ascii_lowercase();
+    }
+    contacts
+        .iter()
+        .find(|contact| {
+            contact
+                .addresses
+                .iter()
+                .any(|(ch, address)| ch.eq_ignore_ascii_case(channel) && address == sender)
+        })
+        .map_or_else(
+            || format!("{}:{sender}", channel.to_ascii_lowercase()),
+            |contact| format!("contact:{}", contact.name),
+        )
+}
+
+/// Parse a plain-language preference change ("be more concise", "reply in
+/// German"). Only whole-message instructions match, so ordinary requests
+/// that mention these words are left alone.
+pub fn parse_instruction(text: &str) -> Option<(Field, String)> {
+    let mut text = text
+        .trim()
+        .trim_end_matches(['.', '!'])
+        .trim()
+        .to_lowercase();
+    for prefix in ["please ", "from now on, ", "from now on "] {
+        if let Some(rest) = text.strip_prefix(prefix) {
+            text = rest.trim().to_string();
+        }
+    }
+    for suffix in [" please", ", please", " from now on"] {
+        if let Some(rest) = text.strip_suffix(suffix) {
+            text = rest.trim().to_string();
+        }
+    }
+
+    if let Some((_, field, value)) = PHRASES
+        .iter()
+        .find(|(phrases, _, _)| phrases.contains(&text.as_str()))
+    {
+        return Some((*field, (*value).to_string()));
+    }
+    for prefix in [
+        "reply in ",
+        "answer in ",
+        "respond in ",
+        "talk to me in ",
+        "speak ",
+    ] {
+        if let Some(language) = text.strip_prefix(prefix).and_then(known_language) {
+            return Some((Field::Language, language));
+        }
+    }
+    let tone = text
+        .strip_prefix("be more ")
+        .or_else(|| text.strip_prefix("be "))
+        .or_else(|| {
+            text.strip_prefix("use a ")
+                .and_then(|rest| rest.strip_suffix(" tone"))
+        })?;
+    TONE_WORDS
+        .contains(&tone)
+        .then(|| (Field::Tone, tone.to_string()))
+}
+
+/// Workspace-backed preference profiles.
+pub struct PreferenceStore {
+    path: PathBuf,
+    workspace_dir: PathBuf,
+}
+
+impl PreferenceStore {
+    pub fn for_workspace(workspace_dir: &Path) -> Self {
+        Self {
+            path: workspace_dir.join(PREFERENCES_FILE),
+            workspace_dir: workspace_dir.to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn list(&self) -> Result<BTreeMap<String, Preferences>> {
+        if !self.path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let raw = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        let file: PreferencesFile = toml::from_str(&raw)
+            .with_context(|| format!("Failed to parse {}", self.path.display()))?;
+        Ok(file.profiles)
+    }
+
+    fn write(&self, profiles: BTreeMap<String, Preferences>) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let raw = toml::to_string_pretty(&PreferencesFile { profiles })?;
+        fs::write(&self.path, raw)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    /// Canonical identity name: `contact:<name>` is resolved through the
+    /// contact book, channel names are lowercased.
+    pub fn resolve_identity(&self, identity: &str) -> Result<String> {
+        let identity = identity.trim();
+        if let Some(name) = identity.strip_prefix("contact:") {
+            let contact = ContactBook::for_workspace(&self.workspace_dir)
+                .find(name)?
+                .with_context(|| format!("Unknown contact '{}'", name.trim()))?;
+            return Ok(format!("contact:{}", contact.name));
+        }
+        Ok(match identity.split_once(':') {
+            Some((channel, sender)) if !sender.trim().is_empty() => {
+                format!("{}:{}", channel.trim().to_ascii_lowercase(), sender.trim())
+            }
+            _ => identity.to_ascii_lowercase(),
+        })
+    }
+
+    /// Identity name for a message from `sender` on `channel`.
+    pub fn identity_for(&self, channel: &str, sender: &str) -> String {
+        let contacts = ContactBook::for_workspace(&self.workspace_dir)
+            .list()
+            .unwrap_or_else(|err| {
+                tracing::warn!("Failed to read contacts for preferences: {err:#}");
+                Vec::new()
+            });
+        identity_key(channel, sender, &contacts)
+    }
+
+    pub fn get(&self, identity: &str) -> Result<Preferences> {
+        Ok(self.list()?.remove(identity).unwrap_or_default())
+    }
+
+    /// Set one preference; returns the updated profile.
+    pub fn set(&self, identity: &str, field: Field, value: &str) -> Result<Preferences> {
+        let value = field.normalize(value)?;
+        let mut profiles = self.list()?;
+        let profile = profiles.entry(identity.to_string()).or_default();
+        *profile.slot(field) = Some(value);
+        let updated = profile.clone();
+        self.write(profiles)?;
+        Ok(updated)
+    }
+
+    /// Clear one preference, or the whole profile when `field` is `None`.
+    /// Returns whether anything was removed.
+    pub fn clear(&self, identity: &str, field: Option<Field>) -> Result<bool> {
+        let mut profiles = self.list()?;
+        let removed = match (field, profiles.get_mut(identity)) {
+            (_, None) => false,
+            (None, Some(_)) => profiles.remove(identity).is_some(),
+            (Some(field), Some(profile)) => {
+                let removed = profile.slot(field).take().is_some();
+                if profile.is_empty() {
+                    profiles.remove(identity);
+                }
+                removed
+            }
+        };
+        if removed {
+            self.write(profiles)?;
+        }
+        Ok(removed)
+    }
+
+    /// System prompt section for `identity`; empty when it has no profile
+    /// or the file cannot be read.
+    pub fn prompt_section(&self, identity: &str) -> String {
+        match self.get(identity) {
+            Ok(preferences) => preferences.prompt_section(),
+            Err(err) => {
+                tracing::warn!("Failed to load preferences for {identity}: {err:#}");
+                String::new()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn plain_language_instructions_map_to_fields() {
+        assert_eq!(
+            parse_instruction("Please be more concise."),
+            Some((Field::Verbosity, "concise".into()))
+        );
+        assert_eq!(
+            parse_instruction("From now on, reply in German"),
+            Some((Field::Language, "de".into()))
+        );
+        assert_eq!(
+            parse_instruction("use metric units!"),
+            Some((Field::Units, "metric".into()))
+        );
+        assert_eq!(
+            parse_instruction("be more formal"),
+            Some((Field::Tone, "formal".into()))
+        );
+        assert_eq!(
+            parse_instruction("no markdown please"),
+            Some((Field::Formatting, "plain".into()))
+        );
+        assert_eq!(parse_instruction("be more concise about the budget"), None);
+        assert_eq!(parse_instruction("reply in a minute"), None);
+        assert_eq!(parse_instruction("speak up"), None);
+    }
+
+    #[test]
+    fn contacts_share_one_profile_across_channels() {
+        let tmp = TempDir::new().unwrap();
+        ContactBook::for_workspace(tmp.path())
+            .upsert(
+                Contact {
+                    name: "Alice Smith".into(),
+                    aliases: Vec::new(),
+                    addresses: BTreeMap::from([
+                        ("slack".to_string(), "U123".to_string()),
+                        ("telegram".to_string(), "42".to_string()),
+                    ]),
+                    preferred_channel: None,
+                    notes: None,
+                    approved: true,
+                },
+                true,
+            )
+            .unwrap();
+        let store = PreferenceStore::for_workspace(tmp.path());
+        let telegram = store.identity_for("telegram", "42");
+        assert_eq!(telegram, "contact:Alice Smith");
+        assert_eq!(store.identity_for("Slack", "U123"), telegram);
+        assert_eq!(store.identity_for("slack", "U999"), "slack:U999");
+        assert_eq!(store.identity_for("cli", ""), "cli");
+        assert_eq!(
+            store.resolve_identity("contact:alice smith").unwrap(),
+            telegram
+        );
+
+        store.set(&telegram, Field::Verbosity, "Concise").unwrap();
+        store.set(&telegram, Field::Language, "French").unwrap();
+        assert!(store.set(&telegram, Field::Units, "furlongs").is_err());
+
+        let section = store.prompt_section(&telegram);
+        assert!(section.contains("Keep replies short"));
+        assert!(section.contains("`fr`"));
+        assert_eq!(
+            store.get(&telegram).unwrap().summary(),
+            "verbosity=concise, language=fr"
+        );
+
+        assert!(store.clear(&telegram, Some(Field::Verbosity)).unwrap());
+        assert!(store.clear(&telegram, None).unwrap());
+        assert!(store.prompt_section(&telegram).is_empty());
+        assert!(!store.clear(&telegram, None).unwrap());
+    }
+}
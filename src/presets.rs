@@ -9,6 +9,9 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
+pub mod knowledge;
+pub mod overrides;
+
 pub const WORKSPACE_PRESET_FILE: &str = ".zeroclaw-preset.json";
 pub const WORKSPACE_INTENT_CAPABILITIES_FILE: &str = ".zeroclaw-intent-capabilities.json";
 pub const INTENT_CAPABILITIES_FILE_ENV: &str = "ZEROCLAW_INTENT_CAPABILITIES_FILE";
@@ -34,6 +37,9 @@ pub struct PresetDocument {
     pub config_overrides: Value,
     #[serde(default)]
     pub metadata: Value,
+    /// Documents ingested into memory when the preset is imported.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub knowledge: Vec<knowledge::KnowledgeSource>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -107,11 +113,21 @@ pub struct RebuildPlan {
     pub args: Vec<String>,
 }
 
+/// A selected pack whose cargo features the running binary was built without.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MissingPackFeatures {
+    pub pack: String,
+    pub features: Vec<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ImportResult {
     pub before: Option<WorkspacePresetSelection>,
     pub after: WorkspacePresetSelection,
     pub mode: PresetImportMode,
+    pub knowledge: Option<knowledge::KnowledgePack>,
+    /// Merge of the document's `config_overrides` into the current config.
+    pub config_overrides: Option<overrides::OverridesPlan>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -219,7 +235,12 @@ const INTENT_CAPABILITIES: &[IntentCapability] = &[
             "browser-native",
             "probe-rs",
             "peripheral-rpi",
+            "peripheral-ble",
+            "smart-home",
+            "voice",
             "rag-pdf",
+            "doc-convert",
+            "reports",
             "sandbox-landlock",
         ],
         preset_biases: &[("minimal", 1.0), ("default", 0.35)],
@@ -304,6 +325,55 @@ const INTENT_CAPABILITIES: &[IntentCapability] = &[
         preset_biases: &[("hardware-lab", 0.85), ("default", 0.30)],
         base_weight: 0.64,
     },
+    IntentCapability {
+        id: "bluetooth-sensors",
+        rationale: "Enable BLE thermometer/beacon scanning and presence detection",
+        keywords: &["bluetooth", "ibeacon", "thermometer", "蓝牙"],
+        add_packs: &["peripheral-ble"],
+        remove_packs: &[],
+        preset_biases: &[("hardware-lab", 0.60), ("default", 0.40)],
+        base_weight: 0.60,
+    },
+    IntentCapability {
+        id: "documents",
+        rationale: "Enable document conversion and templated HTML/DOCX/PDF reports",
+        keywords: &[
+            "docx",
+            "word document",
+            "convert document",
+            "report",
+            "报告",
+        ],
+        add_packs: &["doc-convert", "reports"],
+        remove_packs: &[],
+        preset_biases: &[("default", 0.40), ("automation", 0.30)],
+        base_weight: 0.50,
+    },
+    IntentCapability {
+        id: "smart-home",
+        rationale: "Enable Zigbee/Z-Wave devices over MQTT",
+        keywords: &[
+            "zigbee",
+            "z-wave",
+            "zwave",
+            "smart home",
+            "mqtt",
+            "智能家居",
+        ],
+        add_packs: &["smart-home"],
+        remove_packs: &[],
+        preset_biases: &[("default", 0.45)],
+        base_weight: 0.60,
+    },
+    IntentCapability {
+        id: "voice-frontend",
+        rationale: "Enable the local microphone voice frontend",
+        keywords: &["voice", "wake word", "push to talk", "microphone", "语音"],
+        add_packs: &["voice"],
+        remove_packs: &[],
+        preset_biases: &[("default", 0.45)],
+        base_weight: 0.60,
+    },
     IntentCapability {
         id: "update-management",
         rationale: "Enable self-update workflow/tooling",
@@ -353,7 +423,12 @@ const INTENT_CAPABILITIES: &[IntentCapability] = &[
         rationale: "Explicitly disable hardware probing/peripherals",
         keywords: &["without hardware", "no hardware", "不要硬件", "不需要硬件"],
         add_packs: &[],
-        remove_packs: &["hardware-core", "probe-rs", "peripheral-rpi"],
+        remove_packs: &[
+            "hardware-core",
+            "probe-rs",
+            "peripheral-rpi",
+            "peripheral-ble",
+        ],
         preset_biases: &[("minimal", 0.55), ("automation", 0.35)],
         base_weight: 0.64,
     },
@@ -552,6 +627,7 @@ pub fn selection_to_document(selection: &WorkspacePresetSelection) -> PresetDocu
             "source": "zeroclaw",
             "type": "preset-export"
         }),
+        knowledge: Vec::new(),
     }
 }
 
@@ -576,8 +652,48 @@ pub fn import_selection_from_path(
     let payload = parse_import_payload(&raw)
         .with_context(|| format!("Unsupported preset payload format in {}", path.display()))?;
 
+    let mut knowledge = None;
+    let mut config_overrides = None;
     let imported = match payload {
-        ImportPayload::Document(document) => selection_from_document(document)?,
+        ImportPayload::Document(mut document) => {
+            let has_overrides = document
+                .config_overrides
+                .as_object()
+                .is_some_and(|map| !map.is_empty());
+            // A previous import may have set overrides this version dropped.
+            let applied_before =
+                overrides::record_path(&config.workspace_dir, document.id.trim()).exists();
+            if has_overrides || applied_before {
+                config_overrides =
+                    Some(
+                        overrides::plan(
+                            config,
+                            document.id.trim(),
+                            &document.config_overrides,
+                            mode,
+                        )
+                        .with_context(|| {
+                            format!("Invalid config_overrides in {}", path.display())
+                        })?,
+                    );
+            }
+            if !document.knowledge.is_empty() {
+                let errors = knowledge::validate_sources(&document.knowledge);
+                if !errors.is_empty() {
+                    bail!(
+                        "Invalid knowledge entries in {}: {}",
+                        path.display(),
+                        errors.join("; ")
+                    );
+                }
+                knowledge = Some(knowledge::KnowledgePack {
+                    preset_id: document.id.trim().to_string(),
+                    base_dir: path.parent().unwrap_or(Path::new(".")).to_path_buf(),
+                    sources: std::mem::take(&mut document.knowledge),
+                });
+            }
+            selection_from_document(document)?
+        }
         ImportPayload::Selection(selection) => {
             let mut normalized = selection;
             normalize_selection(&mut normalized)?;
@@ -591,6 +707,8 @@ pub fn import_selection_from_path(
         before,
         after,
         mode,
+        knowledge,
+        config_overrides,
     })
 }
 
@@ -815,6 +933,33 @@ pub fn rebuild_plan_for_selection(
     })
 }
 
+/// Packs of `selection` that need features missing from `compiled` (for the
+/// running binary, the `build.rs` manifest behind `zeroclaw version
+/// --provenance`). Such a pack would be enabled in the selection while its
+/// tools are absent at runtime until the binary is rebuilt.
+pub fn missing_features(
+    selection: &WorkspacePresetSelection,
+    compiled: &[&str],
+) -> Vec<MissingPackFeatures> {
+    selection
+        .packs
+        .iter()
+        .filter_map(|pack_id| {
+            let pack = feature_pack_by_id(pack_id)?;
+            let features: Vec<String> = pack
+                .cargo_features
+                .iter()
+                .filter(|feature| !compiled.contains(*feature))
+                .map(|feature| (*feature).to_string())
+                .collect();
+            (!features.is_empty()).then(|| MissingPackFeatures {
+                pack: pack_id.clone(),
+                features,
+            })
+        })
+        .collect()
+}
+
 pub fn execute_rebuild_plan(plan: &RebuildPlan) -> Result<()> {
     let status = Command::new("cargo")
         .args(&plan.args)
@@ -1207,6 +1352,7 @@ fn validate_preset_file(path: &Path, allow_unknown_packs: bool) -> PresetValidat
             }
             validate_optional_non_empty_string(object, "title", &mut errors);
             validate_optional_non_empty_string(object, "description", &mut errors);
+            validate_knowledge_array(object, &mut errors);
         }
         "selection" => {
             let preset_id = object
@@ -1261,6 +1407,16 @@ fn validate_optional_object(
     }
 }
 
+fn validate_knowledge_array(object: &serde_json::Map<String, Value>, errors: &mut Vec<String>) {
+    let Some(value) = object.get("knowledge") else {
+        return;
+    };
+    match serde_json::from_value::<Vec<knowledge::KnowledgeSource>>(value.clone()) {
+        Ok(sources) => errors.extend(knowledge::validate_sources(&sources)),
+        Err(error) => errors.push(format!("knowledge must be an array of entries: {error}")),
+    }
+}
+
 fn validate_packs_array(
     object: &serde_json::Map<String, Value>,
     allow_unknown_packs: bool,
@@ -1480,6 +1636,26 @@ mod tests {
         assert!(!with_changes.packs.contains(&"tools-update".to_string()));
     }
 
+    #[test]
+    fn missing_features_lists_packs_not_compiled_in() {
+        let selection = compose_selection(
+            from_preset_id("minimal").unwrap(),
+            &["browser-native".to_string(), "probe-rs".to_string()],
+            &[],
+        )
+        .unwrap();
+
+        let missing = missing_features(&selection, &["browser-native"]);
+        assert_eq!(
+            missing,
+            [MissingPackFeatures {
+                pack: "probe-rs".to_string(),
+                features: vec!["probe".to_string()],
+            }]
+        );
+        assert!(missing_features(&selection, &["browser-native", "probe"]).is_empty());
+    }
+
     #[test]
     fn merge_mode_prefers_imported_scalar() {
         let before = WorkspacePresetSelection {
@@ -0,0 +1,634 @@
+//! Knowledge packs: document sets shipped inside preset payloads.
+//!
+//! A preset document may carry a `knowledge` array. Each entry is either a
+//! single document (a `path` relative to the preset file, or an http(s)
+//! `url`) or a `manifest_url` pointing at a JSON manifest that lists more
+//! documents. Every entry pins a `sha256` checksum and carries licensing
+//! metadata. On `zeroclaw preset import`, documents are fetched, verified,
+//! chunked and stored in memory under the `knowledge` category, where the
+//! agent's context retrieval picks them up.
+//!
+//! What was ingested (checksums, licenses, memory keys) is recorded at
+//! `<workspace>/state/knowledge/<preset-id>.json`, so re-importing a pack
+//! replaces its previous chunks instead of duplicating them.
+//!
+//! An imported pack is also a named collection: `zeroclaw agent --collection
+//! <preset-id>` (or `[channels_config.collections]`) restricts retrieval to
+//! its chunks and has the model answer only from them, with citations.
+
+use crate::memory::{chunker, Memory, MemoryCategory};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Memory category that knowledge chunks are stored under.
+pub const KNOWLEDGE_CATEGORY: &str = "knowledge";
+const KNOWLEDGE_STATE_DIR: &str = "state/knowledge";
+const KNOWLEDGE_RECORD_SCHEMA_VERSION: u32 = 1;
+const CHUNK_MAX_TOKENS: usize = 512;
+const MAX_DOCUMENT_BYTES: usize = 10 * 1024 * 1024;
+const FETCH_TIMEOUT_SECS: u64 = 60;
+const FETCH_CONNECT_TIMEOUT_SECS: u64 = 10;
+/// Memory entries recalled before narrowing to a single collection.
+const COLLECTION_RECALL_CANDIDATES: usize = 50;
+
+/// One `knowledge` entry of a preset document.
+///
+/// Exactly one of `path`, `url` or `manifest_url` must be set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnowledgeSource {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Local document, relative to the directory of the preset file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Remote document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Remote JSON manifest of the form `{"documents": [...]}` whose entries
+    /// are `url` documents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_url: Option<String>,
+    /// Hex-encoded SHA-256 of the document (or manifest) bytes.
+    pub sha256: String,
+    /// SPDX identifier or license name. Required for documents; on a manifest
+    /// it is the default for listed documents that do not declare their own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attribution: Option<String>,
+}
+
+/// Knowledge carried by an imported preset document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnowledgePack {
+    pub preset_id: String,
+    /// Directory that relative `path` entries resolve against.
+    pub base_dir: PathBuf,
+    pub sources: Vec<KnowledgeSource>,
+}
+
+/// A fetched document whose checksum has been verified.
+#[derive(Debug, Clone)]
+pub struct FetchedDocument {
+    pub source: KnowledgeSource,
+    /// Where the document was read from (path or URL).
+    pub origin: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestedDocument {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub origin: String,
+    pub sha256: String,
+    pub license: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attribution: Option<String>,
+    pub memory_keys: Vec<String>,
+}
+
+/// Record of a knowledge pack ingestion, persisted per preset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnowledgeRecord {
+    pub schema_version: u32,
+    pub preset_id: String,
+    pub ingested_at: DateTime<Utc>,
+    pub documents: Vec<IngestedDocument>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KnowledgeManifest {
+    documents: Vec<KnowledgeSource>,
+}
+
+/// Validate `knowledge` entries, returning human-readable errors.
+pub fn validate_sources(sources: &[KnowledgeSource]) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut seen = BTreeSet::new();
+    for source in sources {
+        validate_source(source, false, &mut errors);
+        if !seen.insert(source.id.trim()) {
+            errors.push(format!("knowledge contains duplicate id '{}'", source.id));
+        }
+    }
+    errors
+}
+
+fn validate_source(source: &KnowledgeSource, in_manifest: bool, errors: &mut Vec<String>) {
+    let id = source.id.trim();
+    let label = format!("knowledge entry '{id}'");
+    if !super::is_valid_identifier(id) {
+        errors.push(format!(
+            "knowledge id '{id}' must match lowercase identifier format"
+        ));
+    }
+
+    let locations = [&source.path, &source.url, &source.manifest_url]
+        .iter()
+        .filter(|value| value.is_some())
+        .count();
+    if locations != 1 {
+        errors.push(format!(
+            "{label} must set exactly one of path, url or manifest_url"
+        ));
+    }
+    if in_manifest && (source.path.is_some() || source.manifest_url.is_some()) {
+        errors.push(format!("{label} in a manifest must use url"));
+    }
+    if let Some(path) = &source.path {
+        if !is_safe_relative_path(path) {
+            errors.push(format!(
+                "{label} path must be relative and stay inside the preset directory"
+            ));
+        }
+    }
+    for url in [&source.url, &source.manifest_url].into_iter().flatten() {
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            errors.push(format!("{label} url must use http or https"));
+        }
+    }
+
+    let sha256 = source.sha256.trim();
+    if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+        errors.push(format!("{label} sha256 must be 64 hex characters"));
+    }
+    if source.manifest_url.is_none()
+        && source
+            .license
+            .as_deref()
+            .is_none_or(|license| license.trim().is_empty())
+    {
+        errors.push(format!("{label} must declare a license"));
+    }
+}
+
+fn is_safe_relative_path(path: &str) -> bool {
+    let path = Path::new(path);
+    !path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Path of the ingestion record for a preset.
+pub fn record_path(workspace_dir: &Path, preset_id: &str) -> PathBuf {
+    workspace_dir
+        .join(KNOWLEDGE_STATE_DIR)
+        .join(format!("{preset_id}.json"))
+}
+
+/// Fetch every document of a pack, expanding manifests and verifying
+/// checksums. Nothing is written, so a mismatch aborts the import cleanly.
+pub async fn fetch_documents(pack: &KnowledgePack) -> Result<Vec<FetchedDocument>> {
+    let client = crate::config::build_runtime_proxy_client_with_timeouts(
+        "memory.knowledge",
+        FETCH_TIMEOUT_SECS,
+        FETCH_CONNECT_TIMEOUT_SECS,
+    );
+
+    let mut documents = Vec::new();
+    for source in &pack.sources {
+        if let Some(manifest_url) = &source.manifest_url {
+            let bytes = fetch_url(&client, manifest_url, MAX_DOCUMENT_BYTES).await?;
+            verify_checksum(&source.id, &source.sha256, &bytes)?;
+            let manifest: KnowledgeManifest = serde_json::from_slice(&bytes)
+                .with_context(|| format!("Invalid knowledge manifest at {manifest_url}"))?;
+
+            let mut errors = Vec::new();
+            let mut entries = Vec::with_capacity(manifest.documents.len());
+            for mut entry in manifest.documents {
+                if entry.license.is_none() {
+                    entry.license.clone_from(&source.license);
+                }
+                validate_source(&entry, true, &mut errors);
+                entries.push(entry);
+            }
+            if !errors.is_empty() {
+                bail!(
+                    "Knowledge manifest {manifest_url} is invalid: {}",
+                    errors.join("; ")
+                );
+            }
+            for entry in entries {
+                let url = entry.url.clone().unwrap_or_default();
+                let bytes = fetch_url(&client, &url, MAX_DOCUMENT_BYTES).await?;
+                documents.push(verified_document(entry, url, &bytes)?);
+            }
+        } else if let Some(url) = &source.url {
+            let bytes = fetch_url(&client, url, MAX_DOCUMENT_BYTES).await?;
+            documents.push(verified_document(source.clone(), url.clone(), &bytes)?);
+        } else if let Some(path) = &source.path {
+            let full_path = pack.base_dir.join(path);
+            let bytes = fs::read(&full_path).with_context(|| {
+                format!("Failed to read knowledge document {}", full_path.display())
+            })?;
+            if bytes.len() > MAX_DOCUMENT_BYTES {
+                bail!(
+                    "Knowledge document {} exceeds {MAX_DOCUMENT_BYTES} bytes",
+                    full_path.display()
+                );
+            }
+            documents.push(verified_document(
+                source.clone(),
+                full_path.display().to_string(),
+                &bytes,
+            )?);
+        }
+    }
+
+    let mut seen = BTreeSet::new();
+    for document in &documents {
+        if !seen.insert(document.source.id.as_str()) {
+            bail!(
+                "Knowledge document id '{}' is not unique",
+                document.source.id
+            );
+        }
+    }
+    Ok(documents)
+}
+
+/// Download `url`, refusing bodies larger than `max_bytes` without buffering them.
+async fn fetch_url(client: &reqwest::Client, url: &str, max_bytes: usize) -> Result<Vec<u8>> {
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch {url}"))?
+        .error_for_status()
+        .with_context(|| format!("Failed to fetch {url}"))?;
+    if response
+        .content_length()
+        .is_some_and(|length| usize::try_from(length).unwrap_or(usize::MAX) > max_bytes)
+    {
+        bail!("{url} exceeds {max_bytes} bytes");
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .with_context(|| format!("Failed to read {url}"))?
+    {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > max_bytes {
+            bail!("{url} exceeds {max_bytes} bytes");
+        }
+    }
+    Ok(bytes)
+}
+
+fn verify_checksum(id: &str, expected: &str, bytes: &[u8]) -> Result<()> {
+    let actual = hex::encode(Sha256::digest(bytes));
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        bail!("Checksum mismatch for knowledge entry '{id}': expected {expected}, got {actual}");
+    }
+    Ok(())
+}
+
+fn verified_document(
+    source: KnowledgeSource,
+    origin: String,
+    bytes: &[u8],
+) -> Result<FetchedDocument> {
+    verify_checksum(&source.id, &source.sha256, bytes)?;
+    let content = String::from_utf8(bytes.to_vec()).with_context(|| {
+        format!(
+            "Knowledge document '{}' is not UTF-8 text; only markdown and plain text are supported",
+            source.id
+        )
+    })?;
+    Ok(FetchedDocument {
+        source,
+        origin,
+        content,
+    })
+}
+
+/// Store fetched documents in memory, replacing chunks from a previous
+/// ingestion of the same preset, and persist the ingestion record.
+pub async fn ingest_documents(
+    memory: &dyn Memory,
+    workspace_dir: &Path,
+    preset_id: &str,
+    documents: &[FetchedDocument],
+) -> Result<KnowledgeRecord> {
+    let path = record_path(workspace_dir, preset_id);
+    if let Some(previous) = load_record(&path)? {
+        for key in previous
+            .documents
+            .iter()
+            .flat_map(|document| &document.memory_keys)
+        {
+            memory.forget(key).await?;
+        }
+    }
+
+    let category = MemoryCategory::Custom(KNOWLEDGE_CATEGORY.to_string());
+    let mut ingested = Vec::with_capacity(documents.len());
+    for document in documents {
+        let source = &document.source;
+        let license = source.license.clone().unwrap_or_default();
+        let title = source.title.as_deref().unwrap_or(&source.id);
+        let chunks: Vec<String> = chunker::chunk_markdown(&document.content, CHUNK_MAX_TOKENS)
+            .into_iter()
+            .map(|chunk| chunk.content)
+            .collect();
+
+        let mut memory_keys = Vec::with_capacity(chunks.len());
+        for (index, chunk) in chunks.iter().enumerate() {
+            let key = format!("knowledge:{preset_id}:{}:{index}", source.id);
+            let content = format!("[{title} ({license})]\n{chunk}");
+            memory.store(&key, &content, category.clone(), None).await?;
+            memory_keys.push(key);
+        }
+
+        ingested.push(IngestedDocument {
+            id: source.id.clone(),
+            title: source.title.clone(),
+            origin: document.origin.clone(),
+            sha256: source.sha256.trim().to_ascii_lowercase(),
+            license,
+            attribution: source.attribution.clone(),
+            memory_keys,
+        });
+    }
+
+    let record = KnowledgeRecord {
+        schema_version: KNOWLEDGE_RECORD_SCHEMA_VERSION,
+        preset_id: preset_id.to_string(),
+        ingested_at: Utc::now(),
+        documents: ingested,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&record)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(record)
+}
+
+fn load_record(path: &Path) -> Result<Option<KnowledgeRecord>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let record = serde_json::from_str(&raw)
+        .with_context(|| format!("Invalid knowledge record {}", path.display()))?;
+    Ok(Some(record))
+}
+
+/// Names of imported knowledge packs, usable as collections.
+pub fn list_collections(workspace_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(workspace_dir.join(KNOWLEDGE_STATE_DIR)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "json")
+                .then(|| path.file_stem()?.to_str().map(str::to_string))
+                .flatten()
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// An imported knowledge pack used as the only retrieval source of a
+/// scoped Q&A session.
+#[derive(Debug, Clone)]
+pub struct Collection {
+    pub name: String,
+    keys: HashSet<String>,
+}
+
+impl Collection {
+    /// Load the collection named after an imported preset.
+    pub fn load(workspace_dir: &Path, name: &str) -> Result<Self> {
+        let name = name.trim();
+        let record = if super::is_valid_identifier(name) {
+            load_record(&record_path(workspace_dir, name))?
+        } else {
+            None
+        };
+        let Some(record) = record else {
+            let available = list_collections(workspace_dir);
+            bail!(
+                "Unknown collection '{name}'. Import a preset with a knowledge pack first{}",
+                if available.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (available: {})", available.join(", "))
+                }
+            );
+        };
+        Ok(Self {
+            name: name.to_string(),
+            keys: record
+                .documents
+                .into_iter()
+                .flat_map(|document| document.memory_keys)
+                .collect(),
+        })
+    }
+
+    /// System-prompt section binding the model to this collection.
+    pub fn instructions(&self) -> String {
+        format!(
+            "\n## Scoped Q&A: collection \"{name}\"\n\n\
+            Answer only from the excerpts in the [Collection \"{name}\" excerpts] block of each \
+            message. Do not use general knowledge, memory or guesses. Cite every excerpt you rely on \
+            by its number and document title, e.g. [1] Company Handbook. If the excerpts do not \
+            answer the question, say that the collection does not cover it.\n",
+            name = self.name
+        )
+    }
+
+    /// Numbered excerpts from this collection that match `query`.
+    pub async fn context(&self, memory: &dyn Memory, query: &str, limit: usize) -> String {
+        let entries = memory
+            .recall(query, COLLECTION_RECALL_CANDIDATES, None)
+            .await
+            .unwrap_or_default();
+        let mut context = format!("[Collection \"{}\" excerpts]\n", self.name);
+        let mut found = 0;
+        for entry in entries
+            .iter()
+            .filter(|entry| self.keys.contains(&entry.key))
+            .take(limit)
+        {
+            found += 1;
+            let _ = writeln!(context, "[{found}] {}", entry.content.trim());
+        }
+        if found == 0 {
+            context.push_str("(no matching excerpts)\n");
+        }
+        context.push('\n');
+        context
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MemoryConfig;
+    use tempfile::TempDir;
+
+    fn local_source(id: &str, path: &str, content: &str) -> KnowledgeSource {
+        KnowledgeSource {
+            id: id.to_string(),
+            title: Some("Company Handbook".to_string()),
+            path: Some(path.to_string()),
+            url: None,
+            manifest_url: None,
+            sha256: hex::encode(Sha256::digest(content.as_bytes())),
+            license: Some("CC-BY-4.0".to_string()),
+            attribution: None,
+        }
+    }
+
+    #[test]
+    fn validate_sources_reports_missing_license_and_bad_locations() {
+        let mut source = local_source("handbook", "../handbook.md", "x");
+        source.license = None;
+        source.url = Some("ftp://example.com/handbook.md".to_string());
+        let errors = validate_sources(&[source.clone(), source]).join("\n");
+
+        assert!(errors.contains("exactly one of path, url or manifest_url"));
+        assert!(errors.contains("stay inside the preset directory"));
+        assert!(errors.contains("must use http or https"));
+        assert!(errors.contains("must declare a license"));
+        assert!(errors.contains("duplicate id 'handbook'"));
+
+        let ok = local_source("handbook", "docs/handbook.md", "x");
+        assert!(validate_sources(&[ok]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn fetch_url_refuses_oversized_bodies() {
+        use axum::{body::Body, routing::get, Router};
+
+        let app = Router::new()
+            .route("/sized", get(|| async { "x".repeat(2048) }))
+            .route(
+                "/streamed",
+                get(|| async {
+                    let chunks = (0..4).map(|_| Ok::<_, std::io::Error>("x".repeat(512)));
+                    Body::from_stream(futures_util::stream::iter(chunks))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = reqwest::Client::new();
+        for path in ["sized", "streamed"] {
+            let url = format!("http://{addr}/{path}");
+            let err = fetch_url(&client, &url, 1024).await.unwrap_err();
+            assert!(err.to_string().contains("exceeds 1024 bytes"), "{path}");
+            assert_eq!(fetch_url(&client, &url, 4096).await.unwrap().len(), 2048);
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_verifies_checksums_and_reingest_replaces_chunks() {
+        let tmp = TempDir::new().unwrap();
+        let content = "# Leave\nEmployees get 30 days of paid leave.\n";
+        fs::write(tmp.path().join("handbook.md"), content).unwrap();
+
+        let mut pack = KnowledgePack {
+            preset_id: "acme".to_string(),
+            base_dir: tmp.path().to_path_buf(),
+            sources: vec![local_source("handbook", "handbook.md", "tampered")],
+        };
+        let err = fetch_documents(&pack).await.unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"));
+
+        pack.sources = vec![local_source("handbook", "handbook.md", content)];
+        let documents = fetch_documents(&pack).await.unwrap();
+        let memory = crate::memory::create_memory(
+            &MemoryConfig {
+                backend: "sqlite".into(),
+                ..MemoryConfig::default()
+            },
+            tmp.path(),
+            None,
+        )
+        .unwrap();
+
+        let first = ingest_documents(memory.as_ref(), tmp.path(), "acme", &documents)
+            .await
+            .unwrap();
+        assert_eq!(first.documents[0].license, "CC-BY-4.0");
+        let key = first.documents[0].memory_keys[0].clone();
+        let entry = memory.get(&key).await.unwrap().unwrap();
+        assert!(entry.content.contains("30 days of paid leave"));
+        assert_eq!(
+            entry.category,
+            MemoryCategory::Custom(KNOWLEDGE_CATEGORY.to_string())
+        );
+
+        ingest_documents(memory.as_ref(), tmp.path(), "acme", &documents)
+            .await
+            .unwrap();
+        assert_eq!(
+            memory.count().await.unwrap(),
+            first.documents[0].memory_keys.len()
+        );
+        assert!(record_path(tmp.path(), "acme").exists());
+    }
+
+    #[tokio::test]
+    async fn collection_context_only_includes_its_own_chunks() {
+        let tmp = TempDir::new().unwrap();
+        let content = "# Leave\nEmployees get 30 days of paid leave.\n";
+        let documents = vec![FetchedDocument {
+            source: local_source("handbook", "handbook.md", content),
+            origin: "handbook.md".to_string(),
+            content: content.to_string(),
+        }];
+        let memory = crate::memory::create_memory(
+            &MemoryConfig {
+                backend: "sqlite".into(),
+                ..MemoryConfig::default()
+            },
+            tmp.path(),
+            None,
+        )
+        .unwrap();
+        ingest_documents(memory.as_ref(), tmp.path(), "acme", &documents)
+            .await
+            .unwrap();
+        memory
+            .store(
+                "note",
+                "Paid leave is unlimited at my other job",
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let err = Collection::load(tmp.path(), "other").unwrap_err();
+        assert!(err.to_string().contains("available: acme"));
+        let collection = Collection::load(tmp.path(), "acme").unwrap();
+        let context = collection.context(memory.as_ref(), "paid leave", 5).await;
+        assert!(context.starts_with("[Collection \"acme\" excerpts]\n[1] [Company Handbook"));
+        assert!(context.contains("30 days of paid leave"));
+        assert!(!context.contains("unlimited"));
+        assert!(collection
+            .context(memory.as_ref(), "parking", 5)
+            .await
+            .contains("(no matching excerpts)"));
+    }
+}
@@ -0,0 +1,356 @@
+//! Three-way merge of preset `config_overrides` into the live config.
+//!
+//! A preset document may carry `config_overrides`, a partial config object
+//! such as `{"autonomy": {"level": "supervised"}}`. Writing it over the
+//! config would undo manual tuning every time an updated preset is imported,
+//! so each override key is merged from three sides:
+//!
+//! - **base**: what this preset set last time, recorded at
+//!   `<workspace>/state/preset-overrides/<preset-id>.json` (the built-in
+//!   default when the preset was never imported);
+//! - **local**: the current config value;
+//! - **preset**: the value in the document being imported.
+//!
+//! A key the user never touched (local == base) takes the preset value. A key
+//! the preset did not change (preset == base) keeps the local value. When
+//! both moved, the key is a conflict: `--mode overwrite` takes the preset
+//! value, `merge` and `fill` keep the local one. A key the preset no longer
+//! overrides goes back to the default unless it was edited locally.
+
+use super::{is_valid_identifier, PresetImportMode};
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const OVERRIDES_STATE_DIR: &str = "state/preset-overrides";
+const OVERRIDES_RECORD_SCHEMA_VERSION: u32 = 1;
+
+/// An override key whose value changes on apply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OverrideChange {
+    /// Dotted config path, e.g. `autonomy.level`.
+    pub path: String,
+    pub from: Value,
+    pub to: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    KeptLocal,
+    TookPreset,
+}
+
+/// A key changed both locally and by the preset since the last import.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OverrideConflict {
+    pub path: String,
+    pub base: Value,
+    pub local: Value,
+    /// `null` when the preset dropped the override.
+    pub preset: Value,
+    pub resolution: ConflictResolution,
+}
+
+/// Result of merging a document's `config_overrides`; nothing is written
+/// until [`apply`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OverridesPlan {
+    pub preset_id: String,
+    pub changes: Vec<OverrideChange>,
+    /// Local edits kept because the preset did not change those keys.
+    pub preserved: Vec<String>,
+    pub conflicts: Vec<OverrideConflict>,
+    /// Config after the merge, as JSON.
+    #[serde(skip)]
+    merged: Value,
+    /// Overrides to record as the base for the next import.
+    #[serde(skip)]
+    record: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OverridesRecord {
+    schema_version: u32,
+    preset_id: String,
+    applied_at: DateTime<Utc>,
+    overrides: BTreeMap<String, Value>,
+}
+
+/// Path of the applied-overrides record for a preset.
+pub fn record_path(workspace_dir: &Path, preset_id: &str) -> PathBuf {
+    workspace_dir
+        .join(OVERRIDES_STATE_DIR)
+        .join(format!("{preset_id}.json"))
+}
+
+/// Merge `overrides` from preset `preset_id` into `config`.
+pub fn plan(
+    config: &Config,
+    preset_id: &str,
+    overrides: &Value,
+    mode: PresetImportMode,
+) -> Result<OverridesPlan> {
+    if !is_valid_identifier(preset_id) {
+        bail!("Preset id '{preset_id}' cannot carry config_overrides (expected [a-z0-9_-])");
+    }
+    let mut incoming = BTreeMap::new();
+    match overrides {
+        Value::Null => {}
+        Value::Object(map) => flatten(map, "", &mut incoming),
+        _ => bail!("config_overrides must be an object"),
+    }
+
+    let previous = load_record(&record_path(&config.workspace_dir, preset_id))?
+        .map(|record| record.overrides)
+        .unwrap_or_default();
+    let defaults = serde_json::to_value(Config::default())?;
+    let mut merged = serde_json::to_value(config)?;
+
+    let mut plan = OverridesPlan {
+        preset_id: preset_id.to_string(),
+        changes: Vec::new(),
+        preserved: Vec::new(),
+        conflicts: Vec::new(),
+        merged: Value::Null,
+        record: incoming.clone(),
+    };
+    let mut paths: Vec<&String> = incoming.keys().chain(previous.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    for path in paths {
+        let default = lookup(&defaults, path).unwrap_or(Value::Null);
+        let base = previous
+            .get(path)
+            .cloned()
+            .unwrap_or_else(|| default.clone());
+        let local = lookup(&merged, path).unwrap_or(Value::Null);
+        // A dropped override falls back to the default.
+        let preset = incoming.get(path).cloned();
+        let target = preset.clone().unwrap_or(default);
+
+        if local == target {
+            continue;
+        }
+        let take_preset = if local == base {
+            true
+        } else if target == base {
+            plan.preserved.push(path.clone());
+            false
+        } else {
+            let resolution = if mode == PresetImportMode::Overwrite {
+                ConflictResolution::TookPreset
+            } else {
+                ConflictResolution::KeptLocal
+            };
+            plan.conflicts.push(OverrideConflict {
+                path: path.clone(),
+                base,
+                local: local.clone(),
+                preset: preset.unwrap_or(Value::Null),
+                resolution,
+            });
+            resolution == ConflictResolution::TookPreset
+        };
+        if take_preset {
+            assign(&mut merged, path, target.clone())?;
+            plan.changes.push(OverrideChange {
+                path: path.clone(),
+                from: local,
+                to: target,
+            });
+        }
+    }
+
+    // Round-trip through Config so unknown keys and wrong types fail here,
+    // before anything is written.
+    let parsed: Config =
+        serde_json::from_value(merged).context("config_overrides do not produce a valid config")?;
+    let merged = serde_json::to_value(&parsed)?;
+    for change in &plan.changes {
+        if lookup(&merged, &change.path).unwrap_or(Value::Null) != change.to {
+            bail!(
+                "config_overrides key '{}' is not a config setting",
+                change.path
+            );
+        }
+    }
+    plan.merged = merged;
+    Ok(plan)
+}
+
+/// Save the merged config (when anything changed) and record the applied
+/// overrides as the base for the next import.
+pub async fn apply(config: &Config, plan: &OverridesPlan) -> Result<()> {
+    if !plan.changes.is_empty() {
+        let mut updated: Config = serde_json::from_value(plan.merged.clone())?;
+        updated.workspace_dir = config.workspace_dir.clone();
+        updated.config_path = config.config_path.clone();
+        updated.save().await?;
+    }
+
+    let path = record_path(&config.workspace_dir, &plan.preset_id);
+    let record = OverridesRecord {
+        schema_version: OVERRIDES_RECORD_SCHEMA_VERSION,
+        preset_id: plan.preset_id.clone(),
+        applied_at: Utc::now(),
+        overrides: plan.record.clone(),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&record)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+fn load_record(path: &Path) -> Result<Option<OverridesRecord>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let record = serde_json::from_str(&raw)
+        .with_context(|| format!("Invalid JSON at {}", path.display()))?;
+    Ok(Some(record))
+}
+
+/// Leaf values of a nested object, keyed by dotted path. Arrays are leaves.
+fn flatten(map: &Map<String, Value>, prefix: &str, out: &mut BTreeMap<String, Value>) {
+    for (key, value) in map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            Value::Object(inner) => flatten(inner, &path, out),
+            leaf => {
+                out.insert(path, leaf.clone());
+            }
+        }
+    }
+}
+
+fn lookup(value: &Value, path: &str) -> Option<Value> {
+    path.split('.')
+        .try_fold(value, |current, key| current.get(key))
+        .cloned()
+}
+
+fn assign(root: &mut Value, path: &str, value: Value) -> Result<()> {
+    let mut current = root;
+    let mut keys = path.split('.').peekable();
+    while let Some(key) = keys.next() {
+        if current.is_null() {
+            *current = Value::Object(Map::new());
+        }
+        let Value::Object(map) = current else {
+            bail!("config_overrides key '{path}' descends into a non-table value");
+        };
+        if keys.peek().is_none() {
+            map.insert(key.to_string(), value);
+            return Ok(());
+        }
+        current = map.entry(key.to_string()).or_insert(Value::Null);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn workspace_config(tmp: &TempDir) -> Config {
+        Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn upgrade_keeps_local_edits_and_takes_untouched_keys() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = workspace_config(&tmp);
+        let v1 = json!({"autonomy": {"max_actions_per_hour": 40, "max_cost_per_day_cents": 300}});
+        let first = plan(&config, "team", &v1, PresetImportMode::Merge).unwrap();
+        assert_eq!(first.changes.len(), 2);
+        Box::pin(apply(&config, &first)).await.unwrap();
+
+        // The user tunes one key by hand, then the preset ships new values.
+        config.autonomy.max_actions_per_hour = 40;
+        config.autonomy.max_cost_per_day_cents = 1000;
+        let v2 = json!({"autonomy": {"max_actions_per_hour": 60, "max_cost_per_day_cents": 300}});
+        let second = plan(&config, "team", &v2, PresetImportMode::Merge).unwrap();
+
+        assert_eq!(
+            second.changes,
+            [OverrideChange {
+                path: "autonomy.max_actions_per_hour".into(),
+                from: json!(40),
+                to: json!(60),
+            }]
+        );
+        assert_eq!(second.preserved, ["autonomy.max_cost_per_day_cents"]);
+        assert!(second.conflicts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn keys_changed_on_both_sides_conflict() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = workspace_config(&tmp);
+        let v1 = json!({"autonomy": {"max_actions_per_hour": 40}});
+        let first = plan(&config, "team", &v1, PresetImportMode::Merge).unwrap();
+        Box::pin(apply(&config, &first)).await.unwrap();
+        config.autonomy.max_actions_per_hour = 10;
+
+        let v2 = json!({"autonomy": {"max_actions_per_hour": 60}});
+        let merged = plan(&config, "team", &v2, PresetImportMode::Merge).unwrap();
+        assert!(merged.changes.is_empty());
+        assert_eq!(merged.conflicts.len(), 1);
+        assert_eq!(merged.conflicts[0].base, json!(40));
+        assert_eq!(merged.conflicts[0].local, json!(10));
+        assert_eq!(
+            merged.conflicts[0].resolution,
+            ConflictResolution::KeptLocal
+        );
+
+        let overwritten = plan(&config, "team", &v2, PresetImportMode::Overwrite).unwrap();
+        assert_eq!(
+            overwritten.conflicts[0].resolution,
+            ConflictResolution::TookPreset
+        );
+        assert_eq!(overwritten.changes[0].to, json!(60));
+    }
+
+    #[test]
+    fn first_import_conflicts_with_non_default_local_values() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = workspace_config(&tmp);
+        config.autonomy.max_actions_per_hour = 5;
+        let overrides = json!({"autonomy": {"max_actions_per_hour": 60}});
+        let merged = plan(&config, "team", &overrides, PresetImportMode::Merge).unwrap();
+        assert!(merged.changes.is_empty());
+        assert_eq!(merged.conflicts.len(), 1);
+    }
+
+    #[test]
+    fn unknown_keys_and_wrong_types_are_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let config = workspace_config(&tmp);
+        let unknown = json!({"autonomy": {"no_such_key": 1}});
+        assert!(plan(&config, "team", &unknown, PresetImportMode::Merge).is_err());
+        let wrong_type = json!({"autonomy": {"max_actions_per_hour": "many"}});
+        assert!(plan(&config, "team", &wrong_type, PresetImportMode::Merge).is_err());
+        assert!(plan(&config, "../x", &json!({}), PresetImportMode::Merge).is_err());
+    }
+}
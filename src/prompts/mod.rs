@@ -0,0 +1,351 @@
+//! Built-in prompt bundle with workspace overrides.
+//!
+//! The fixed parts of the system prompt (task framing, safety rules, the
+//! XML tool protocol, ...) ship as Markdown files under `prompts/` in the
+//! source tree and are embedded into the binary at compile time. A file with
+//! the same name in `<workspace>/prompts/` replaces the built-in text; an
+//! empty file drops the section. The daemon watches the override directory
+//! and rebuilds its base prompt when a file changes, so edits apply to the
+//! next message without a restart.
+//!
+//! `zeroclaw prompts export` copies the bundle into the workspace and records
+//! the bundle version and hashes in `prompts/.bundle.json`, so after an
+//! upgrade `zeroclaw prompts list` can point out overrides whose built-in
+//! changed underneath them and `zeroclaw prompts diff` shows the difference.
+
+use anyhow::{bail, Context, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Bumped whenever a built-in prompt changes.
+pub const BUNDLE_VERSION: u32 = 1;
+
+const OVERRIDE_DIR: &str = "prompts";
+const MANIFEST_FILE: &str = ".bundle.json";
+
+/// One prompt of the built-in bundle.
+#[derive(Debug)]
+pub struct BuiltinPrompt {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub text: &'static str,
+}
+
+/// The built-in bundle, in the order the sections appear in the prompt.
+pub const BUILTIN: &[BuiltinPrompt] = &[
+    BuiltinPrompt {
+        name: "hardware",
+        summary: "Hardware access rules, when GPIO or board tools are present",
+        text: include_str!("../../prompts/hardware.md"),
+    },
+    BuiltinPrompt {
+        name: "task_native",
+        summary: "Task framing for providers with native tool calling",
+        text: include_str!("../../prompts/task_native.md"),
+    },
+    BuiltinPrompt {
+        name: "task_xml",
+        summary: "Task framing for providers using the XML tool protocol",
+        text: include_str!("../../prompts/task_xml.md"),
+    },
+    BuiltinPrompt {
+        name: "safety",
+        summary: "Safety rules",
+        text: include_str!("../../prompts/safety.md"),
+    },
+    BuiltinPrompt {
+        name: "channel_capabilities",
+        summary: "How to behave as a messaging bot",
+        text: include_str!("../../prompts/channel_capabilities.md"),
+    },
+    BuiltinPrompt {
+        name: "tool_protocol",
+        summary: "<tool_call> instructions, without native tool calling",
+        text: include_str!("../../prompts/tool_protocol.md"),
+    },
+];
+
+/// Look up a built-in prompt by name.
+pub fn builtin(name: &str) -> Option<&'static BuiltinPrompt> {
+    BUILTIN.iter().find(|prompt| prompt.name == name)
+}
+
+/// `<workspace>/prompts/<name>.md`.
+pub fn override_path(workspace_dir: &Path, name: &str) -> PathBuf {
+    workspace_dir.join(OVERRIDE_DIR).join(format!("{name}.md"))
+}
+
+/// The prompt text: the workspace override when there is one, otherwise the
+/// built-in. Unreadable overrides fall back to the built-in with a warning.
+pub fn load(workspace_dir: &Path, name: &str) -> Cow<'static, str> {
+    let builtin = builtin(name).map_or("", |prompt| prompt.text);
+    let path = override_path(workspace_dir, name);
+    match fs::read_to_string(&path) {
+        Ok(text) => Cow::Owned(text),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Cow::Borrowed(builtin),
+        Err(err) => {
+            tracing::warn!("Ignoring prompt override {}: {err}", path.display());
+            Cow::Borrowed(builtin)
+        }
+    }
+}
+
+/// Append a prompt as its own section, unless it is empty.
+pub fn push_section(prompt: &mut String, workspace_dir: &Path, name: &str) {
+    let text = load(workspace_dir, name);
+    if text.trim().is_empty() {
+        return;
+    }
+    prompt.push_str(text.trim_end());
+    prompt.push_str("\n\n");
+}
+
+fn hash(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+/// Written by `export`: which bundle the workspace copies came from.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    /// Prompt name to the SHA-256 of the built-in text at export time.
+    hashes: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    fn path(workspace_dir: &Path) -> PathBuf {
+        workspace_dir.join(OVERRIDE_DIR).join(MANIFEST_FILE)
+    }
+
+    fn load(workspace_dir: &Path) -> Result<Option<Self>> {
+        let path = Self::path(workspace_dir);
+        match fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw)
+                .map(Some)
+                .with_context(|| format!("Failed to parse {}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+}
+
+/// Bundle version the workspace copies were exported from, if any.
+pub fn exported_version(workspace_dir: &Path) -> Result<Option<u32>> {
+    Ok(Manifest::load(workspace_dir)?.map(|manifest| manifest.version))
+}
+
+/// Where a prompt currently comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptSource {
+    Builtin,
+    Override,
+    /// An exported override whose built-in changed since the export.
+    StaleOverride,
+}
+
+/// One line of `zeroclaw prompts list`.
+#[derive(Debug)]
+pub struct PromptStatus {
+    pub prompt: &'static BuiltinPrompt,
+    pub source: PromptSource,
+}
+
+/// Status of every built-in prompt in a workspace.
+pub fn status(workspace_dir: &Path) -> Result<Vec<PromptStatus>> {
+    let manifest = Manifest::load(workspace_dir)?.unwrap_or_default();
+    Ok(BUILTIN
+        .iter()
+        .map(|prompt| {
+            let source = if !override_path(workspace_dir, prompt.name).is_file() {
+                PromptSource::Builtin
+            } else if manifest
+                .hashes
+                .get(prompt.name)
+                .is_some_and(|exported| *exported != hash(prompt.text))
+            {
+                PromptSource::StaleOverride
+            } else {
+                PromptSource::Override
+            };
+            PromptStatus { prompt, source }
+        })
+        .collect())
+}
+
+/// Copy the built-in bundle into `<workspace>/prompts/`. Existing files are
+/// kept unless `force`. Returns the names written.
+pub fn export(workspace_dir: &Path, force: bool) -> Result<Vec<&'static str>> {
+    let dir = workspace_dir.join(OVERRIDE_DIR);
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let mut manifest = Manifest::load(workspace_dir)?.unwrap_or_default();
+    let mut written = Vec::new();
+    for prompt in BUILTIN {
+        let path = override_path(workspace_dir, prompt.name);
+        if path.exists() && !force {
+            continue;
+        }
+        fs::write(&path, prompt.text)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        manifest
+            .hashes
+            .insert(prompt.name.to_string(), hash(prompt.text));
+        written.push(prompt.name);
+    }
+    manifest.version = BUNDLE_VERSION;
+    let path = Manifest::path(workspace_dir);
+    fs::write(&path, serde_json::to_string_pretty(&manifest)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(written)
+}
+
+/// Unified diff from the built-in to the workspace override (empty when the
+/// prompt is not overridden or identical).
+pub fn diff(workspace_dir: &Path, name: &str) -> Result<String> {
+    let Some(prompt) = builtin(name) else {
+        bail!("Unknown prompt '{name}'");
+    };
+    let path = override_path(workspace_dir, name);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(String::new()),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    Ok(crate::approval::unified_diff(
+        &format!("{OVERRIDE_DIR}/{name}.md"),
+        prompt.text,
+        &text,
+    ))
+}
+
+type Fingerprint = Vec<Option<(u64, Option<SystemTime>)>>;
+
+/// Size and modification time of every override file, to notice edits.
+fn fingerprint(workspace_dir: &Path) -> Fingerprint {
+    BUILTIN
+        .iter()
+        .map(|prompt| {
+            fs::metadata(override_path(workspace_dir, prompt.name))
+                .ok()
+                .map(|meta| (meta.len(), meta.modified().ok()))
+        })
+        .collect()
+}
+
+/// A prompt built from the bundle that is rebuilt when overrides change.
+pub struct PromptReloader {
+    workspace_dir: PathBuf,
+    build: Box<dyn Fn() -> String + Send + Sync>,
+    current: Mutex<(Fingerprint, Arc<String>)>,
+}
+
+impl PromptReloader {
+    pub fn new(workspace_dir: &Path, build: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        let current = (fingerprint(workspace_dir), Arc::new(build()));
+        Self {
+            workspace_dir: workspace_dir.to_path_buf(),
+            build: Box::new(build),
+            current: Mutex::new(current),
+        }
+    }
+
+    /// The current prompt, rebuilt first if an override file changed.
+    pub fn prompt(&self) -> Arc<String> {
+        let latest = fingerprint(&self.workspace_dir);
+        let mut current = self.current.lock();
+        if current.0 != latest {
+            tracing::info!(
+                "Prompt overrides in {} changed; rebuilding the system prompt",
+                self.workspace_dir.join(OVERRIDE_DIR).display()
+            );
+            *current = (latest, Arc::new((self.build)()));
+        }
+        Arc::clone(&current.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn overrides_replace_or_drop_builtin_sections() {
+        let tmp = TempDir::new().unwrap();
+        let mut prompt = String::new();
+        push_section(&mut prompt, tmp.path(), "safety");
+        assert!(prompt.starts_with("## Safety\n\n- Do not exfiltrate private data."));
+        assert!(prompt.ends_with("acting externally.\n\n"));
+
+        fs::create_dir_all(tmp.path().join("prompts")).unwrap();
+        fs::write(
+            override_path(tmp.path(), "safety"),
+            "## Rules\n\nBe careful.\n",
+        )
+        .unwrap();
+        fs::write(override_path(tmp.path(), "hardware"), "\n").unwrap();
+        let mut prompt = String::new();
+        push_section(&mut prompt, tmp.path(), "safety");
+        push_section(&mut prompt, tmp.path(), "hardware");
+        assert_eq!(prompt, "## Rules\n\nBe careful.\n\n");
+    }
+
+    #[test]
+    fn export_marks_overrides_stale_when_builtin_changes() {
+        let tmp = TempDir::new().unwrap();
+        assert!(status(tmp.path())
+            .unwrap()
+            .iter()
+            .all(|entry| entry.source == PromptSource::Builtin));
+
+        assert_eq!(export(tmp.path(), false).unwrap().len(), BUILTIN.len());
+        assert!(export(tmp.path(), false).unwrap().is_empty());
+        assert!(diff(tmp.path(), "safety").unwrap().is_empty());
+
+        let manifest_path = Manifest::path(tmp.path());
+        let mut manifest = Manifest::load(tmp.path()).unwrap().unwrap();
+        manifest.hashes.insert("safety".into(), hash("older text"));
+        fs::write(&manifest_path, serde_json::to_string(&manifest).unwrap()).unwrap();
+        let entries = status(tmp.path()).unwrap();
+        let safety = entries.iter().find(|e| e.prompt.name == "safety").unwrap();
+        assert_eq!(safety.source, PromptSource::StaleOverride);
+        let task = entries
+            .iter()
+            .find(|e| e.prompt.name == "task_xml")
+            .unwrap();
+        assert_eq!(task.source, PromptSource::Override);
+
+        fs::write(
+            override_path(tmp.path(), "safety"),
+            "## Safety\n\n- Ask first.\n",
+        )
+        .unwrap();
+        assert!(diff(tmp.path(), "safety")
+            .unwrap()
+            .contains("+- Ask first."));
+        assert!(diff(tmp.path(), "missing").is_err());
+    }
+
+    #[test]
+    fn reloader_rebuilds_when_an_override_changes() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path().to_path_buf();
+        let reloader = PromptReloader::new(tmp.path(), move || {
+            let mut prompt = String::new();
+            push_section(&mut prompt, &workspace, "safety");
+            prompt
+        });
+        let first = reloader.prompt();
+        assert!(Arc::ptr_eq(&first, &reloader.prompt()));
+
+        fs::create_dir_all(tmp.path().join("prompts")).unwrap();
+        fs::write(override_path(tmp.path(), "safety"), "Custom rules.").unwrap();
+        assert_eq!(reloader.prompt().as_str(), "Custom rules.\n\n");
+    }
+}
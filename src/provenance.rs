@@ -0,0 +1,186 @@
+//! Build provenance and software bill of materials (`zeroclaw version --provenance`).
+//!
+//! `build.rs` embeds the git commit, builder identity, toolchain and the
+//! compiled Cargo features. It also walks `Cargo.lock` from this crate,
+//! skipping dev-dependencies and optional dependencies no enabled feature
+//! pulls in, and embeds the resulting `name@version` list, so the SBOM shows
+//! what this binary can link without carrying the lock file itself.
+
+use crate::onboard::feature_packs::FEATURE_PACKS;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fmt::Write;
+
+#[cfg(test)]
+mod sbom;
+
+const COMPONENTS: &str = include_str!(concat!(env!("OUT_DIR"), "/sbom.txt"));
+
+/// One crate in the SBOM.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Component {
+    pub name: String,
+    pub version: String,
+    /// `local` or `git` for crates that do not come from a registry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+}
+
+/// Everything `zeroclaw version --provenance` reports.
+#[derive(Debug, Clone, Serialize)]
+pub struct Provenance {
+    pub version: &'static str,
+    pub git_commit: &'static str,
+    pub builder: &'static str,
+    pub rustc: &'static str,
+    pub target: &'static str,
+    pub profile: &'static str,
+    pub features: Vec<&'static str>,
+    pub packs: Vec<&'static str>,
+    pub components: Vec<Component>,
+}
+
+impl Provenance {
+    /// Provenance of the running binary.
+    pub fn current() -> Result<Self> {
+        let features = enabled_features();
+        Ok(Self {
+            version: env!("CARGO_PKG_VERSION"),
+            git_commit: env!("ZEROCLAW_BUILD_GIT_COMMIT"),
+            builder: env!("ZEROCLAW_BUILD_BUILDER"),
+            rustc: env!("ZEROCLAW_BUILD_RUSTC"),
+            target: env!("ZEROCLAW_BUILD_TARGET"),
+            profile: env!("ZEROCLAW_BUILD_PROFILE"),
+            packs: enabled_packs(&features),
+            components: components(COMPONENTS)?,
+            features,
+        })
+    }
+
+    /// Human-readable report.
+    pub fn render(&self) -> String {
+        let list = |items: &[&str]| {
+            if items.is_empty() {
+                "(none)".to_string()
+            } else {
+                items.join(", ")
+            }
+        };
+        let mut out = format!(
+            "zeroclaw {}\n\
+             Commit:     {}\n\
+             Builder:    {}\n\
+             Toolchain:  {}\n\
+             Target:     {} ({})\n\
+             Features:   {}\n\
+             Packs:      {}\n\
+             \n\
+             Components ({}):\n",
+            self.version,
+            self.git_commit,
+            self.builder,
+            self.rustc,
+            self.target,
+            self.profile,
+            list(&self.features),
+            list(&self.packs),
+            self.components.len(),
+        );
+        for component in &self.components {
+            let origin = component
+                .origin
+                .as_deref()
+                .map(|origin| format!(" ({origin})"))
+                .unwrap_or_default();
+            let _ = writeln!(out, "  {} {}{origin}", component.name, component.version);
+        }
+        out
+    }
+}
+
+/// Cargo features compiled into this binary.
+pub fn enabled_features() -> Vec<&'static str> {
+    env!("ZEROCLAW_BUILD_FEATURES")
+        .split(',')
+        .filter(|feature| !feature.is_empty())
+        .collect()
+}
+
+/// Feature packs whose Cargo features are all compiled in.
+pub fn enabled_packs(features: &[&str]) -> Vec<&'static str> {
+    FEATURE_PACKS
+        .iter()
+        .filter(|pack| {
+            pack.cargo_features
+                .iter()
+                .all(|feature| features.contains(feature))
+        })
+        .map(|pack| pack.id)
+        .collect()
+}
+
+/// Components listed by `build.rs`, one `name@version [local|git]` per line.
+fn components(list: &str) -> Result<Vec<Component>> {
+    list.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (crate_ref, origin) = match line.split_once(' ') {
+                Some((crate_ref, origin)) => (crate_ref, Some(origin.to_string())),
+                None => (line, None),
+            };
+            let (name, version) = crate_ref
+                .split_once('@')
+                .with_context(|| format!("malformed SBOM entry '{line}'"))?;
+            Ok(Component {
+                name: name.to_string(),
+                version: version.to_string(),
+                origin,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_list_round_trips_origins() {
+        let list = sbom::render_list(&[
+            sbom::Component {
+                name: "app".into(),
+                version: "1.0.0".into(),
+                source: None,
+            },
+            sbom::Component {
+                name: "serde".into(),
+                version: "1.0.1".into(),
+                source: Some("registry+https://github.com/rust-lang/crates.io-index".into()),
+            },
+            sbom::Component {
+                name: "forked".into(),
+                version: "0.3.0".into(),
+                source: Some("git+https://example.com/forked#abc".into()),
+            },
+        ]);
+        assert_eq!(list, "app@1.0.0 local\nserde@1.0.1\nforked@0.3.0 git\n");
+        let parsed = components(&list).unwrap();
+        assert_eq!(parsed[0].origin.as_deref(), Some("local"));
+        assert_eq!(parsed[1].origin, None);
+        assert_eq!(parsed[2].origin.as_deref(), Some("git"));
+        assert!(components("no-version").is_err());
+    }
+
+    #[test]
+    fn current_binary_reports_itself_and_core_pack() {
+        let provenance = Provenance::current().unwrap();
+        assert!(provenance.packs.contains(&"core-agent"));
+        assert!(provenance
+            .components
+            .iter()
+            .any(|c| c.name == "zeroclaw" && c.version == env!("CARGO_PKG_VERSION")));
+        assert!(provenance.components.iter().any(|c| c.name == "tokio"));
+        assert!(!provenance.components.iter().any(|c| c.name == "wiremock"));
+        assert!(provenance.render().contains("Components ("));
+    }
+}
@@ -0,0 +1,253 @@
+//! SBOM resolution from `Cargo.toml` and `Cargo.lock`.
+//!
+//! Shared with `build.rs` (through `#[path]`), which runs it at build time
+//! and embeds only the resulting component list, so it depends on nothing
+//! but `toml` and `std`.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// One crate in the lock file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Component {
+    pub name: String,
+    pub version: String,
+    /// Lock-file `source`; `None` for path crates.
+    pub source: Option<String>,
+}
+
+/// Dependencies the binary does not link: optional ones no enabled feature
+/// turns on, and dev- or build-only ones.
+fn excluded_dependencies(manifest: &toml::Table, features: &[&str]) -> HashSet<String> {
+    let mut optional = HashSet::new();
+    let mut runtime = HashSet::new();
+    let mut tables: Vec<&toml::Table> = manifest
+        .get("dependencies")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .collect();
+    if let Some(targets) = manifest.get("target").and_then(toml::Value::as_table) {
+        tables.extend(
+            targets
+                .values()
+                .filter_map(|target| target.get("dependencies")?.as_table()),
+        );
+    }
+    for table in tables {
+        for (name, spec) in table {
+            runtime.insert(name.clone());
+            if spec.get("optional").and_then(toml::Value::as_bool) == Some(true) {
+                optional.insert(name.clone());
+            }
+        }
+    }
+
+    // Resolve features transitively; `dep:x` and bare optional names enable x.
+    let declared = manifest.get("features").and_then(toml::Value::as_table);
+    let mut enabled_deps = HashSet::new();
+    let mut pending: Vec<String> = features.iter().map(|f| (*f).to_string()).collect();
+    let mut seen = HashSet::new();
+    while let Some(feature) = pending.pop() {
+        if !seen.insert(feature.clone()) {
+            continue;
+        }
+        let Some(items) = declared
+            .and_then(|table| table.get(&feature))
+            .and_then(toml::Value::as_array)
+        else {
+            continue;
+        };
+        for item in items.iter().filter_map(toml::Value::as_str) {
+            if let Some(dep) = item.strip_prefix("dep:") {
+                enabled_deps.insert(dep.to_string());
+            } else if let Some((dep, _)) = item.split_once('/') {
+                if !dep.ends_with('?') {
+                    enabled_deps.insert(dep.to_string());
+                }
+            } else if optional.contains(item) {
+                enabled_deps.insert(item.to_string());
+            } else {
+                pending.push(item.to_string());
+            }
+        }
+    }
+
+    let dev_only = ["dev-dependencies", "build-dependencies"]
+        .into_iter()
+        .filter_map(|section| manifest.get(section)?.as_table())
+        .flat_map(|table| table.keys())
+        .filter(|name| !runtime.contains(*name))
+        .cloned();
+    optional
+        .into_iter()
+        .filter(|dep| !enabled_deps.contains(dep))
+        .chain(dev_only)
+        .collect()
+}
+
+/// Crates reachable from `root` in the lock file, minus dependencies the
+/// enabled features leave out.
+pub fn sbom(
+    manifest: &str,
+    lock: &str,
+    root: &str,
+    features: &[&str],
+) -> Result<Vec<Component>, String> {
+    let manifest: toml::Table =
+        toml::from_str(manifest).map_err(|e| format!("failed to parse Cargo.toml: {e}"))?;
+    let lock: toml::Table =
+        toml::from_str(lock).map_err(|e| format!("failed to parse Cargo.lock: {e}"))?;
+    let excluded = excluded_dependencies(&manifest, features);
+
+    struct Package<'a> {
+        component: Component,
+        dependencies: Vec<&'a str>,
+    }
+    let packages: Vec<Package> = lock
+        .get("package")
+        .and_then(toml::Value::as_array)
+        .ok_or("Cargo.lock has no packages")?
+        .iter()
+        .filter_map(|package| {
+            let field = |key: &str| package.get(key)?.as_str().map(str::to_string);
+            Some(Package {
+                component: Component {
+                    name: field("name")?,
+                    version: field("version")?,
+                    source: field("source"),
+                },
+                dependencies: package
+                    .get("dependencies")
+                    .and_then(toml::Value::as_array)
+                    .map(|deps| deps.iter().filter_map(toml::Value::as_str).collect())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    let mut by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, package) in packages.iter().enumerate() {
+        by_name
+            .entry(package.component.name.as_str())
+            .or_default()
+            .push(index);
+    }
+    // Lock entries reference `name` or `name version [(source)]`.
+    let resolve = |reference: &str| -> Option<usize> {
+        let mut parts = reference.split_whitespace();
+        let candidates = by_name.get(parts.next()?)?;
+        match parts.next() {
+            Some(version) => candidates
+                .iter()
+                .copied()
+                .find(|&index| packages[index].component.version == version),
+            None => candidates.first().copied(),
+        }
+    };
+
+    let root = resolve(root).ok_or_else(|| format!("{root} is not in Cargo.lock"))?;
+    let mut reachable = BTreeSet::new();
+    let mut visited = HashSet::from([root]);
+    let mut pending = vec![root];
+    while let Some(index) = pending.pop() {
+        reachable.insert(packages[index].component.clone());
+        for reference in &packages[index].dependencies {
+            let name = reference.split_whitespace().next().unwrap_or_default();
+            if index == root && excluded.contains(name) {
+                continue;
+            }
+            if let Some(dep) = resolve(reference) {
+                if visited.insert(dep) {
+                    pending.push(dep);
+                }
+            }
+        }
+    }
+    Ok(reachable.into_iter().collect())
+}
+
+/// Compact embedded form: `name@version`, followed by ` git` or ` local`
+/// for crates that do not come from a registry.
+pub fn render_list(components: &[Component]) -> String {
+    let mut out = String::new();
+    for component in components {
+        out.push_str(&component.name);
+        out.push('@');
+        out.push_str(&component.version);
+        match component.source.as_deref() {
+            None => out.push_str(" local"),
+            Some(source) if !source.starts_with("registry+") => out.push_str(" git"),
+            Some(_) => {}
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[package]
+name = "app"
+version = "1.0.0"
+
+[dependencies]
+serde = "1"
+pdf = { version = "0.1", optional = true }
+voice-lib = { version = "0.2", optional = true }
+
+[features]
+rag-pdf = ["dep:pdf"]
+voice = ["voice-lib"]
+
+[dev-dependencies]
+mockito = "1"
+"#;
+
+    const LOCK: &str = r#"
+version = 4
+
+[[package]]
+name = "app"
+version = "1.0.0"
+dependencies = ["mockito", "pdf", "serde", "voice-lib"]
+
+[[package]]
+name = "mockito"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "pdf"
+version = "0.1.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["serde 1.0.1"]
+
+[[package]]
+name = "serde"
+version = "1.0.1"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "voice-lib"
+version = "0.2.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    fn names(components: &[Component]) -> Vec<&str> {
+        components.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    #[test]
+    fn sbom_skips_disabled_optional_and_dev_dependencies() {
+        let minimal = sbom(MANIFEST, LOCK, "app", &[]).unwrap();
+        assert_eq!(names(&minimal), ["app", "serde"]);
+
+        let with_pdf = sbom(MANIFEST, LOCK, "app", &["rag-pdf"]).unwrap();
+        assert_eq!(names(&with_pdf), ["app", "pdf", "serde"]);
+
+        let with_voice = sbom(MANIFEST, LOCK, "app", &["voice"]).unwrap();
+        assert_eq!(names(&with_voice), ["app", "serde", "voice-lib"]);
+    }
+}
@@ -138,6 +138,8 @@ struct AnthropicUsage {
     input_tokens: Option<u64>,
     #[serde(default)]
     output_tokens: Option<u64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -375,6 +377,7 @@ impl AnthropicProvider {
         let usage = response.usage.map(|u| TokenUsage {
             input_tokens: u.input_tokens,
             output_tokens: u.output_tokens,
+            cached_input_tokens: u.cache_read_input_tokens,
         });
 
         for block in response.content {
@@ -513,6 +516,10 @@ impl Provider for AnthropicProvider {
         true
     }
 
+    fn caches_tool_schemas(&self) -> bool {
+        true
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -1345,6 +1352,18 @@ mod tests {
         assert_eq!(usage.output_tokens, Some(75));
     }
 
+    #[test]
+    fn native_response_parses_cache_read_tokens() {
+        let json = r#"{
+            "content": [{"type": "text", "text": "Hello"}],
+            "usage": {"input_tokens": 300, "output_tokens": 75, "cache_read_input_tokens": 256}
+        }"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let result = AnthropicProvider::parse_native_response(resp);
+        let usage = result.usage.unwrap();
+        assert_eq!(usage.cached_input_tokens, Some(256));
+    }
+
     #[test]
     fn native_response_parses_without_usage() {
         let json = r#"{"content": [{"type": "text", "text": "Hello"}]}"#;
@@ -0,0 +1,201 @@
+//! Per-run cost cap (`[agent].max_run_cost_usd`).
+//!
+//! [`BudgetProvider`] prices each response with the `[cost]` table and
+//! refuses further calls once the run has spent its budget. Calls whose model
+//! has no price entry are not counted.
+
+use super::traits::{ChatMessage, ChatRequest, ChatResponse};
+use super::Provider;
+use crate::observability::runtime_trace;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+pub struct BudgetProvider {
+    inner: Box<dyn Provider>,
+    provider_name: String,
+    limit_usd: f64,
+    spent_usd: Mutex<f64>,
+}
+
+impl BudgetProvider {
+    pub fn new(inner: Box<dyn Provider>, provider_name: &str, limit_usd: f64) -> Self {
+        Self {
+            inner,
+            provider_name: provider_name.to_string(),
+            limit_usd,
+            spent_usd: Mutex::new(0.0),
+        }
+    }
+
+    /// Estimated spend of this run so far.
+    pub fn spent_usd(&self) -> f64 {
+        *self.spent_usd.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn ensure_budget(&self) -> Result<()> {
+        let spent = self.spent_usd();
+        if spent >= self.limit_usd {
+            anyhow::bail!(
+                "Run cost cap reached: spent ${spent:.2} of ${:.2} ([agent].max_run_cost_usd)",
+                self.limit_usd
+            );
+        }
+        Ok(())
+    }
+
+    fn charge(&self, cost_usd: f64) {
+        *self.spent_usd.lock().unwrap_or_else(|e| e.into_inner()) += cost_usd;
+    }
+
+    fn record(&self, response: &ChatResponse, model: &str) {
+        let Some(usage) = response.usage.as_ref() else {
+            return;
+        };
+        if let Some(cost) = runtime_trace::estimate_cost_usd(
+            &self.provider_name,
+            model,
+            usage.input_tokens,
+            usage.output_tokens,
+        ) {
+            self.charge(cost);
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for BudgetProvider {
+    fn capabilities(&self) -> super::traits::ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> Result<String> {
+        self.ensure_budget()?;
+        self.inner
+            .chat_with_system(system_prompt, message, model, temperature)
+            .await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> Result<String> {
+        self.ensure_budget()?;
+        self.inner
+            .chat_with_history(messages, model, temperature)
+            .await
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> Result<ChatResponse> {
+        self.ensure_budget()?;
+        let response = self.inner.chat(request, model, temperature).await?;
+        self.record(&response, model);
+        Ok(response)
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> Result<ChatResponse> {
+        self.ensure_budget()?;
+        let response = self
+            .inner
+            .chat_with_tools(messages, tools, model, temperature)
+            .await?;
+        self.record(&response, model);
+        Ok(response)
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.inner.supports_vision()
+    }
+
+    fn caches_tool_schemas(&self) -> bool {
+        self.inner.caches_tool_schemas()
+    }
+
+    async fn warmup(&self) -> Result<()> {
+        self.inner.warmup().await
+    }
+}
+
+/// Wrap `provider` in a [`BudgetProvider`] when a positive cap is set.
+pub fn wrap_with_budget(
+    provider: Box<dyn Provider>,
+    provider_name: &str,
+    max_run_cost_usd: f64,
+) -> Box<dyn Provider> {
+    if max_run_cost_usd > 0.0 {
+        Box::new(BudgetProvider::new(
+            provider,
+            provider_name,
+            max_run_cost_usd,
+        ))
+    } else {
+        provider
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EchoProvider;
+
+    #[async_trait]
+    impl Provider for EchoProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<String> {
+            Ok(message.to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn calls_stop_once_budget_is_spent() {
+        let provider = BudgetProvider::new(Box::new(EchoProvider), "echo", 1.0);
+        assert_eq!(
+            provider
+                .chat_with_system(None, "hi", "m", 0.0)
+                .await
+                .unwrap(),
+            "hi"
+        );
+
+        provider.charge(0.6);
+        assert!(provider
+            .chat_with_system(None, "hi", "m", 0.0)
+            .await
+            .is_ok());
+        provider.charge(0.6);
+        let err = provider
+            .chat_with_system(None, "hi", "m", 0.0)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("cost cap reached"));
+    }
+}
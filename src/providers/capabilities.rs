@@ -0,0 +1,363 @@
+//! Per-model capability registry.
+//!
+//! [`ProviderCapabilities`](super::traits::ProviderCapabilities) describes
+//! what a provider's API can do; this registry records what each *model*
+//! behind it can do, as reported by the provider's model catalog (context
+//! window, image input, tool calling, JSON mode, streaming). It is filled
+//! from `state/models_cache.json`, which `zeroclaw models refresh` and
+//! onboarding write, and consulted by the agent loop through [`negotiate`]
+//! so a text-only or tool-less model gets a degraded request with a warning
+//! instead of an API error.
+//!
+//! Every field is optional: `None` means the catalog did not say, and the
+//! provider-level capability is trusted.
+
+use super::traits::Provider;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock, RwLock};
+
+const MODEL_CACHE_FILE: &str = "models_cache.json";
+
+/// Capabilities of one model, as reported by its provider's catalog.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelCapabilities {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vision: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_mode: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub streaming: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_context_tokens: Option<u64>,
+}
+
+impl ModelCapabilities {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Short human-readable summary, e.g. `vision, tools, 128k ctx`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        for (flag, name) in [
+            (self.vision, "vision"),
+            (self.tools, "tools"),
+            (self.json_mode, "json"),
+            (self.streaming, "streaming"),
+        ] {
+            match flag {
+                Some(true) => parts.push(name.to_string()),
+                Some(false) => parts.push(format!("no {name}")),
+                None => {}
+            }
+        }
+        if let Some(tokens) = self.max_context_tokens {
+            parts.push(if tokens >= 1000 {
+                format!("{}k ctx", tokens / 1000)
+            } else {
+                format!("{tokens} ctx")
+            });
+        }
+        parts.join(", ")
+    }
+}
+
+/// Capability metadata keyed by model id, as found in a catalog response.
+///
+/// Understands the OpenAI-style `data` list (with OpenRouter, Mistral, Groq,
+/// vLLM and LM Studio extensions) and Gemini's `models` list. Models the
+/// catalog says nothing about are omitted.
+pub fn parse_catalog(payload: &Value) -> BTreeMap<String, ModelCapabilities> {
+    let entries = payload
+        .get("data")
+        .or_else(|| payload.get("models"))
+        .unwrap_or(payload)
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    entries
+        .iter()
+        .filter_map(|model| {
+            let id = model
+                .get("id")
+                .or_else(|| model.get("name"))
+                .and_then(Value::as_str)?
+                .trim()
+                .trim_start_matches("models/");
+            let capabilities = parse_model(model);
+            (!id.is_empty() && !capabilities.is_empty()).then(|| (id.to_string(), capabilities))
+        })
+        .collect()
+}
+
+fn parse_model(model: &Value) -> ModelCapabilities {
+    let max_context_tokens = [
+        "context_length",
+        "max_context_length",
+        "context_window",
+        "max_model_len",
+        "inputTokenLimit",
+    ]
+    .iter()
+    .find_map(|key| model.get(*key).and_then(Value::as_u64))
+    .or_else(|| model.pointer("/top_provider/context_length")?.as_u64())
+    .filter(|tokens| *tokens > 0);
+
+    // OpenRouter: architecture.input_modalities + supported_parameters.
+    let vision = model
+        .pointer("/architecture/input_modalities")
+        .and_then(Value::as_array)
+        .map(|modalities| modalities.iter().any(|m| m.as_str() == Some("image")))
+        .or_else(|| {
+            model
+                .pointer("/architecture/modality")
+                .and_then(Value::as_str)
+                .map(|modality| modality.split("->").next().unwrap_or("").contains("image"))
+        })
+        // Mistral-style capability flags.
+        .or_else(|| model.pointer("/capabilities/vision")?.as_bool())
+        // LM Studio marks vision models as "vlm".
+        .or_else(|| match model.get("type").and_then(Value::as_str) {
+            Some("vlm") => Some(true),
+            Some("llm") => Some(false),
+            _ => None,
+        });
+
+    let parameters: Option<Vec<&str>> = model
+        .get("supported_parameters")
+        .and_then(Value::as_array)
+        .map(|params| params.iter().filter_map(Value::as_str).collect());
+    let tools = parameters
+        .as_ref()
+        .map(|params| params.contains(&"tools"))
+        .or_else(|| model.pointer("/capabilities/function_calling")?.as_bool());
+    let json_mode = parameters
+        .as_ref()
+        .map(|params| params.contains(&"response_format") || params.contains(&"structured_outputs"))
+        .or_else(|| model.pointer("/capabilities/json_mode")?.as_bool());
+
+    // Gemini lists the generation methods each model accepts.
+    let streaming = model
+        .get("supportedGenerationMethods")
+        .and_then(Value::as_array)
+        .map(|methods| {
+            methods
+                .iter()
+                .any(|m| m.as_str() == Some("streamGenerateContent"))
+        })
+        .or_else(|| model.pointer("/capabilities/streaming")?.as_bool());
+
+    ModelCapabilities {
+        vision,
+        tools,
+        json_mode,
+        streaming,
+        max_context_tokens,
+    }
+}
+
+type Registry = HashMap<String, BTreeMap<String, ModelCapabilities>>;
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Replace the registered capabilities for `provider`.
+pub fn register(provider: &str, models: BTreeMap<String, ModelCapabilities>) {
+    let mut guard = registry().write().unwrap_or_else(|e| e.into_inner());
+    guard.insert(provider.to_string(), models);
+}
+
+/// Catalog capabilities for `model` served by `provider`, if known.
+pub fn lookup(provider: &str, model: &str) -> Option<ModelCapabilities> {
+    let guard = registry().read().unwrap_or_else(|e| e.into_inner());
+    guard.get(provider)?.get(model).cloned()
+}
+
+#[derive(Deserialize)]
+struct ModelCacheState {
+    #[serde(default)]
+    entries: Vec<ModelCacheEntry>,
+}
+
+#[derive(Deserialize)]
+struct ModelCacheEntry {
+    provider: String,
+    #[serde(default)]
+    capabilities: BTreeMap<String, ModelCapabilities>,
+}
+
+/// Load the registry from the workspace model cache. Missing or unreadable
+/// caches leave it empty, which disables negotiation.
+pub fn init_from_workspace(workspace_dir: &Path) {
+    let path = workspace_dir.join("state").join(MODEL_CACHE_FILE);
+    let Ok(raw) = std::fs::read_to_string(path) else {
+        return;
+    };
+    let Ok(state) = serde_json::from_str::<ModelCacheState>(&raw) else {
+        return;
+    };
+    for entry in state.entries {
+        register(&entry.provider, entry.capabilities);
+    }
+}
+
+/// What the agent loop should actually use for one provider/model pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NegotiatedCapabilities {
+    /// Send tool specs natively; otherwise describe them in the prompt.
+    pub native_tools: bool,
+    /// Forward image attachments.
+    pub vision: bool,
+    /// Context window from the catalog, when reported.
+    pub max_context_tokens: Option<u64>,
+}
+
+/// Combine the provider's declared capabilities with the model catalog.
+/// The catalog can only take capabilities away; each downgrade is logged
+/// once per provider/model.
+pub fn negotiate(
+    provider: &dyn Provider,
+    provider_name: &str,
+    model: &str,
+) -> NegotiatedCapabilities {
+    let catalog = lookup(provider_name, model).unwrap_or_default();
+    let mut negotiated = NegotiatedCapabilities {
+        native_tools: provider.supports_native_tools(),
+        vision: provider.supports_vision(),
+        max_context_tokens: catalog.max_context_tokens,
+    };
+    if negotiated.native_tools && catalog.tools == Some(false) {
+        negotiated.native_tools = false;
+        warn_once(
+            provider_name,
+            model,
+            "tools",
+            "model does not support native tool calling; falling back to prompt-guided tool calls",
+        );
+    }
+    if negotiated.vision && catalog.vision == Some(false) {
+        negotiated.vision = false;
+        warn_once(
+            provider_name,
+            model,
+            "vision",
+            "model does not accept images; image attachments will be replaced with a note",
+        );
+    }
+    negotiated
+}
+
+fn warn_once(provider: &str, model: &str, capability: &str, message: &str) {
+    static WARNED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    let key = format!("{provider}/{model}/{capability}");
+    let mut warned = WARNED
+        .get_or_init(|| Mutex::new(HashSet::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if warned.insert(key) {
+        tracing::warn!(provider, model, capability, "{message}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::traits::ProviderCapabilities;
+    use serde_json::json;
+
+    #[test]
+    fn parses_openrouter_and_gemini_catalogs() {
+        let openrouter = json!({"data": [
+            {
+                "id": "openai/gpt-4o",
+                "context_length": 128_000,
+                "architecture": {"input_modalities": ["text", "image"]},
+                "supported_parameters": ["tools", "response_format", "temperature"]
+            },
+            {
+                "id": "meta/text-only",
+                "context_length": 8192,
+                "architecture": {"input_modalities": ["text"]},
+                "supported_parameters": ["temperature"]
+            },
+            {"id": "bare/model"}
+        ]});
+        let parsed = parse_catalog(&openrouter);
+        assert_eq!(parsed.len(), 2);
+        let gpt = &parsed["openai/gpt-4o"];
+        assert_eq!(gpt.vision, Some(true));
+        assert_eq!(gpt.tools, Some(true));
+        assert_eq!(gpt.json_mode, Some(true));
+        assert_eq!(gpt.summary(), "vision, tools, json, 128k ctx");
+        let text_only = &parsed["meta/text-only"];
+        assert_eq!(text_only.vision, Some(false));
+        assert_eq!(text_only.tools, Some(false));
+
+        let gemini = json!({"models": [{
+            "name": "models/gemini-2.5-flash",
+            "inputTokenLimit": 1_048_576,
+            "supportedGenerationMethods": ["generateContent", "streamGenerateContent"]
+        }]});
+        let parsed = parse_catalog(&gemini);
+        let flash = &parsed["gemini-2.5-flash"];
+        assert_eq!(flash.streaming, Some(true));
+        assert_eq!(flash.max_context_tokens, Some(1_048_576));
+        assert_eq!(flash.vision, None);
+    }
+
+    struct FullFeatureProvider;
+
+    #[async_trait::async_trait]
+    impl Provider for FullFeatureProvider {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: true,
+                vision: true,
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+    }
+
+    #[test]
+    fn catalog_downgrades_provider_capabilities_per_model() {
+        register(
+            "negotiate-test",
+            BTreeMap::from([(
+                "text-only".to_string(),
+                ModelCapabilities {
+                    vision: Some(false),
+                    tools: Some(false),
+                    max_context_tokens: Some(8192),
+                    ..ModelCapabilities::default()
+                },
+            )]),
+        );
+
+        let limited = negotiate(&FullFeatureProvider, "negotiate-test", "text-only");
+        assert!(!limited.native_tools);
+        assert!(!limited.vision);
+        assert_eq!(limited.max_context_tokens, Some(8192));
+
+        let unknown = negotiate(&FullFeatureProvider, "negotiate-test", "other-model");
+        assert!(unknown.native_tools);
+        assert!(unknown.vision);
+        assert_eq!(unknown.max_context_tokens, None);
+    }
+}
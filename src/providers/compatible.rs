@@ -3,10 +3,11 @@
 //! This module provides a single implementation that works for all of them.
 
 use crate::multimodal;
+use crate::providers::custom_transport::CustomProviderTransport;
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
     Provider, StreamChunk, StreamError, StreamOptions, StreamResult, TokenUsage,
-    ToolCall as ProviderToolCall,
+    ToolCall as ProviderToolCall, ToolCallDelta,
 };
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
@@ -33,6 +34,9 @@ pub struct OpenAiCompatibleProvider {
     /// to the first `user` message, then drop the system messages.
     /// Required for providers that reject `role: system` (e.g. MiniMax).
     merge_system_into_user: bool,
+    /// Hardened transport (mTLS, request signing, egress allowlist) for
+    /// `custom:<URL>` endpoints configured under `[custom_providers]`.
+    transport: Option<CustomProviderTransport>,
 }
 
 /// How the provider expects the API key to be sent.
@@ -165,9 +169,16 @@ impl OpenAiCompatibleProvider {
             supports_responses_fallback,
             user_agent: user_agent.map(ToString::to_string),
             merge_system_into_user,
+            transport: None,
         }
     }
 
+    /// Route all requests through a hardened custom-provider transport.
+    pub fn with_transport(mut self, transport: CustomProviderTransport) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
     /// Collect all `system` role messages, concatenate their content,
     /// and prepend to the first `user` message. Drop all system messages.
     /// Used for providers (e.g. MiniMax) that reject `role: system`.
@@ -200,6 +211,10 @@ impl OpenAiCompatibleProvider {
     }
 
     fn http_client(&self) -> Client {
+        if let Some(transport) = self.transport.as_ref() {
+            return transport.client();
+        }
+
         if let Some(ua) = self.user_agent.as_deref() {
             let mut headers = HeaderMap::new();
             if let Ok(value) = HeaderValue::from_str(ua) {
@@ -352,6 +367,14 @@ struct UsageInfo {
     prompt_tokens: Option<u64>,
     #[serde(default)]
     completion_tokens: Option<u64>,
+    #[serde(default)]
+    prompt_tokens_details: Option<PromptTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -504,6 +527,13 @@ struct NativeChatRequest {
     tools: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<NativeStreamOptions>,
+}
+
+#[derive(Debug, Serialize)]
+struct NativeStreamOptions {
+    include_usage: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -561,7 +591,11 @@ struct ResponsesContent {
 /// Server-Sent Event stream chunk for OpenAI-compatible streaming.
 #[derive(Debug, Deserialize)]
 struct StreamChunkResponse {
+    #[serde(default)]
     choices: Vec<StreamChoice>,
+    /// Sent on the last chunk when `stream_options.include_usage` is set.
+    #[serde(default)]
+    usage: Option<UsageInfo>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -577,6 +611,27 @@ struct StreamDelta {
     /// Reasoning/thinking models may stream output via `reasoning_content`.
     #[serde(default)]
     reasoning_content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<StreamToolCall>,
+}
+
+/// Native tool-call fragment in a streamed delta.
+#[derive(Debug, Deserialize)]
+struct StreamToolCall {
+    #[serde(default)]
+    index: usize,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<StreamFunction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamFunction {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
 }
 
 /// Parse SSE (Server-Sent Events) stream from OpenAI-compatible providers.
@@ -618,17 +673,77 @@ fn parse_sse_line(line: &str) -> StreamResult<Option<String>> {
     Ok(None)
 }
 
+/// Parse one SSE line of a tool-enabled stream into text, tool-call and
+/// usage chunks.
+fn parse_sse_tool_line(line: &str) -> StreamResult<Vec<StreamChunk>> {
+    let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else {
+        return Ok(Vec::new());
+    };
+    if data == "[DONE]" {
+        return Ok(Vec::new());
+    }
+    let response: StreamChunkResponse = serde_json::from_str(data).map_err(StreamError::Json)?;
+
+    let mut chunks = Vec::new();
+    if let Some(choice) = response.choices.into_iter().next() {
+        if let Some(content) = choice.delta.content.filter(|c| !c.is_empty()) {
+            chunks.push(StreamChunk::delta(content));
+        }
+        for call in choice.delta.tool_calls {
+            let (name, arguments) = call
+                .function
+                .map(|f| (f.name, f.arguments.unwrap_or_default()))
+                .unwrap_or_default();
+            chunks.push(StreamChunk::tool_call(ToolCallDelta {
+                index: call.index,
+                id: call.id,
+                name,
+                arguments,
+            }));
+        }
+    }
+    if let Some(usage) = response.usage {
+        chunks.push(StreamChunk::delta("").with_usage(TokenUsage {
+            input_tokens: usage.prompt_tokens,
+            output_tokens: usage.completion_tokens,
+            cached_input_tokens: usage.prompt_tokens_details.and_then(|d| d.cached_tokens),
+        }));
+    }
+    Ok(chunks)
+}
+
 /// Convert SSE byte stream to text chunks.
 fn sse_bytes_to_chunks(
     response: reqwest::Response,
     count_tokens: bool,
+) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+    sse_bytes_to_stream(response, move |line| {
+        Ok(parse_sse_line(line)?
+            .map(|content| {
+                let chunk = StreamChunk::delta(content);
+                if count_tokens {
+                    chunk.with_token_estimate()
+                } else {
+                    chunk
+                }
+            })
+            .into_iter()
+            .collect())
+    })
+}
+
+/// Split an SSE byte stream into lines and convert each with `parse`.
+fn sse_bytes_to_stream(
+    response: reqwest::Response,
+    parse: impl Fn(&str) -> StreamResult<Vec<StreamChunk>> + Send + 'static,
 ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
     // Create a channel to send chunks
     let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
 
     tokio::spawn(async move {
-        // Buffer for incomplete lines
-        let mut buffer = String::new();
+        // Buffer for incomplete lines (bytes, so multi-byte characters may
+        // span network reads)
+        let mut buffer: Vec<u8> = Vec::new();
 
         // Get response body as bytes stream
         match response.error_for_status_ref() {
@@ -644,38 +759,32 @@ fn sse_bytes_to_chunks(
         while let Some(item) = bytes_stream.next().await {
             match item {
                 Ok(bytes) => {
-                    // Convert bytes to string and process line by line
-                    let text = match String::from_utf8(bytes.to_vec()) {
-                        Ok(t) => t,
-                        Err(e) => {
-                            let _ = tx
-                                .send(Err(StreamError::InvalidSse(format!(
-                                    "Invalid UTF-8: {}",
-                                    e
-                                ))))
-                                .await;
-                            break;
-                        }
-                    };
-
-                    buffer.push_str(&text);
+                    buffer.extend_from_slice(&bytes);
 
                     // Process complete lines
-                    while let Some(pos) = buffer.find('\n') {
-                        let line = buffer.drain(..=pos).collect::<String>();
-                        buffer = buffer[pos + 1..].to_string();
-
-                        match parse_sse_line(&line) {
-                            Ok(Some(content)) => {
-                                let mut chunk = StreamChunk::delta(content);
-                                if count_tokens {
-                                    chunk = chunk.with_token_estimate();
-                                }
-                                if tx.send(Ok(chunk)).await.is_err() {
-                                    return; // Receiver dropped
+                    while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                        let raw: Vec<u8> = buffer.drain(..=pos).collect();
+                        let line = match String::from_utf8(raw) {
+                            Ok(line) => line,
+                            Err(e) => {
+                                let _ = tx
+                                    .send(Err(StreamError::InvalidSse(format!(
+                                        "Invalid UTF-8: {}",
+                                        e
+                                    ))))
+                                    .await;
+                                return;
+                            }
+                        };
+
+                        match parse(&line) {
+                            Ok(chunks) => {
+                                for chunk in chunks {
+                                    if tx.send(Ok(chunk)).await.is_err() {
+                                        return; // Receiver dropped
+                                    }
                                 }
                             }
-                            Ok(None) => {}
                             Err(e) => {
                                 let _ = tx.send(Err(e)).await;
                                 return;
@@ -808,10 +917,14 @@ impl OpenAiCompatibleProvider {
         req: reqwest::RequestBuilder,
         credential: &str,
     ) -> reqwest::RequestBuilder {
-        match &self.auth_header {
+        let req = match &self.auth_header {
             AuthStyle::Bearer => req.header("Authorization", format!("Bearer {credential}")),
             AuthStyle::XApiKey => req.header("x-api-key", credential),
             AuthStyle::Custom(header) => req.header(header, credential),
+        };
+        match self.transport.as_ref() {
+            Some(transport) => transport.sign(req),
+            None => req,
         }
     }
 
@@ -1379,6 +1492,7 @@ impl Provider for OpenAiCompatibleProvider {
         let usage = chat_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cached_input_tokens: u.prompt_tokens_details.and_then(|d| d.cached_tokens),
         });
         let choice = chat_response
             .choices
@@ -1437,6 +1551,7 @@ impl Provider for OpenAiCompatibleProvider {
             stream: Some(false),
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            stream_options: None,
         };
 
         let url = self.chat_completions_url();
@@ -1514,6 +1629,7 @@ impl Provider for OpenAiCompatibleProvider {
         let usage = native_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cached_input_tokens: u.prompt_tokens_details.and_then(|d| d.cached_tokens),
         });
         let message = native_response
             .choices
@@ -1581,6 +1697,7 @@ impl Provider for OpenAiCompatibleProvider {
         let url = self.chat_completions_url();
         let client = self.http_client();
         let auth_header = self.auth_header.clone();
+        let transport = self.transport.clone();
 
         // Use a channel to bridge the async HTTP response to the stream
         let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
@@ -1601,6 +1718,11 @@ impl Provider for OpenAiCompatibleProvider {
             // Set accept header for streaming
             req_builder = req_builder.header("Accept", "text/event-stream");
 
+            // Sign last so the signature covers the final header set
+            if let Some(transport) = transport.as_ref() {
+                req_builder = transport.sign(req_builder);
+            }
+
             // Send request
             let response = match req_builder.send().await {
                 Ok(r) => r,
@@ -1639,6 +1761,87 @@ impl Provider for OpenAiCompatibleProvider {
         .boxed()
     }
 
+    fn supports_tool_streaming(&self) -> bool {
+        true
+    }
+
+    fn stream_chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let Some(credential) = self.credential.as_ref() else {
+            let provider_name = self.name.clone();
+            return stream::once(async move {
+                Err(StreamError::Provider(format!(
+                    "{provider_name} API key not set"
+                )))
+            })
+            .boxed();
+        };
+
+        let tools = Self::convert_tool_specs(request.tools);
+        let effective_messages = if self.merge_system_into_user {
+            Self::flatten_system_messages(request.messages)
+        } else {
+            request.messages.to_vec()
+        };
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages_for_native(&effective_messages),
+            temperature,
+            stream: Some(true),
+            tool_choice: tools.as_ref().map(|_| "auto".to_string()),
+            tools,
+            stream_options: Some(NativeStreamOptions {
+                include_usage: true,
+            }),
+        };
+        let req_builder = self.apply_auth_header(
+            self.http_client()
+                .post(self.chat_completions_url())
+                .header("Accept", "text/event-stream")
+                .json(&native_request),
+            credential,
+        );
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
+        tokio::spawn(async move {
+            let response = match req_builder.send().await {
+                Ok(r) => r,
+                Err(e) => {
+                    let _ = tx.send(Err(StreamError::Http(e))).await;
+                    return;
+                }
+            };
+            if !response.status().is_success() {
+                let status = response.status();
+                let error = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| format!("HTTP error: {status}"));
+                let error = super::sanitize_api_error(&error);
+                let _ = tx
+                    .send(Err(StreamError::Provider(format!("{status}: {error}"))))
+                    .await;
+                return;
+            }
+
+            let mut chunk_stream = sse_bytes_to_stream(response, parse_sse_tool_line);
+            while let Some(chunk) = chunk_stream.next().await {
+                if tx.send(chunk).await.is_err() {
+                    break; // Receiver dropped
+                }
+            }
+        });
+
+        stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|chunk| (chunk, rx))
+        })
+        .boxed()
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         if let Some(credential) = self.credential.as_ref() {
             // Hit the chat completions URL with a GET to establish the connection pool.
@@ -2622,6 +2825,29 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn parse_sse_tool_line_emits_tool_call_fragments_and_usage() {
+        let line = r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"id":"call_1","function":{"name":"shell","arguments":"{\"com"}}]}}]}"#;
+        let chunks = parse_sse_tool_line(line).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(
+            chunks[0].tool_call,
+            Some(ToolCallDelta {
+                index: 0,
+                id: Some("call_1".into()),
+                name: Some("shell".into()),
+                arguments: "{\"com".into(),
+            })
+        );
+
+        let line = r#"data: {"choices":[],"usage":{"prompt_tokens":12,"completion_tokens":3}}"#;
+        let chunks = parse_sse_tool_line(line).unwrap();
+        let usage = chunks[0].usage.as_ref().unwrap();
+        assert_eq!(usage.input_tokens, Some(12));
+        assert_eq!(usage.output_tokens, Some(3));
+        assert!(parse_sse_tool_line("data: [DONE]").unwrap().is_empty());
+    }
+
     #[test]
     fn api_response_parses_usage() {
         let json = r#"{
@@ -2634,6 +2860,21 @@ mod tests {
         assert_eq!(usage.completion_tokens, Some(60));
     }
 
+    #[test]
+    fn api_response_parses_cached_prompt_tokens() {
+        let json = r#"{
+            "choices": [{"message": {"content": "Hello"}}],
+            "usage": {
+                "prompt_tokens": 150,
+                "completion_tokens": 60,
+                "prompt_tokens_details": {"cached_tokens": 128}
+            }
+        }"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        let details = resp.usage.unwrap().prompt_tokens_details.unwrap();
+        assert_eq!(details.cached_tokens, Some(128));
+    }
+
     #[test]
     fn api_response_parses_without_usage() {
         let json = r#"{"choices": [{"message": {"content": "Hello"}}]}"#;
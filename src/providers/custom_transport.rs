@@ -0,0 +1,406 @@
+//! Hardened transport for `custom:<URL>` providers.
+//!
+//! Internal inference gateways are frequently locked down behind mutual TLS,
+//! request signing, and strict egress rules. This module turns a
+//! `[custom_providers."<URL>"]` entry into a pre-built HTTP client (client
+//! identity, private CA, redirect allowlist) plus an optional request signer
+//! (HMAC headers or AWS SigV4). Signing reuses the SigV4 primitives from the
+//! Bedrock provider so there is still no AWS SDK dependency.
+
+use super::bedrock::{derive_signing_key, hmac_sha256, sha256_hex};
+use crate::config::{CustomProviderConfig, RequestSigningConfig};
+use anyhow::Context;
+use reqwest::{header::HeaderValue, Client, Request, RequestBuilder};
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+/// Maximum redirect hops followed within the egress allowlist.
+const MAX_REDIRECTS: usize = 5;
+
+/// Pre-built client and signer for one custom provider endpoint.
+#[derive(Clone)]
+pub struct CustomProviderTransport {
+    client: Client,
+    signer: Option<Arc<RequestSigningConfig>>,
+    allowed_hosts: Arc<Vec<String>>,
+}
+
+impl CustomProviderTransport {
+    /// Build the transport for `base_url` from its config entry.
+    ///
+    /// Fails fast when certificate files are missing or unparsable, or when the
+    /// endpoint host itself is outside the configured allowlist.
+    pub fn from_config(base_url: &str, entry: &CustomProviderConfig) -> anyhow::Result<Self> {
+        let allowed_hosts = Arc::new(effective_allowed_hosts(base_url, entry)?);
+
+        let mut builder = Client::builder()
+            .timeout(std::time::Duration::from_secs(120))
+            .connect_timeout(std::time::Duration::from_secs(10));
+
+        if let Some(path) = entry.client_identity_path.as_deref() {
+            let pem = std::fs::read(path).with_context(|| {
+                format!("Failed to read custom provider client identity {path}")
+            })?;
+            let identity = reqwest::Identity::from_pem(&pem).with_context(|| {
+                format!("Custom provider client identity {path} must contain a PEM certificate and private key")
+            })?;
+            builder = builder.identity(identity);
+        }
+
+        if let Some(path) = entry.ca_cert_path.as_deref() {
+            let pem = std::fs::read(path)
+                .with_context(|| format!("Failed to read custom provider CA bundle {path}"))?;
+            for cert in reqwest::Certificate::from_pem_bundle(&pem)
+                .with_context(|| format!("Custom provider CA bundle {path} is not valid PEM"))?
+            {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        let redirect_hosts = Arc::clone(&allowed_hosts);
+        builder = builder.redirect(reqwest::redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                return attempt.error("too many redirects");
+            }
+            let host = attempt
+                .url()
+                .host_str()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            if redirect_hosts.iter().any(|allowed| allowed == &host) {
+                attempt.follow()
+            } else {
+                attempt.error(format!("redirect to non-allowlisted host '{host}' blocked"))
+            }
+        }));
+
+        let builder = crate::config::apply_runtime_proxy_to_builder(builder, "provider.compatible");
+        let client = builder
+            .build()
+            .context("Failed to build custom provider HTTP client")?;
+
+        Ok(Self {
+            client,
+            signer: entry.signing.clone().map(Arc::new),
+            allowed_hosts,
+        })
+    }
+
+    /// HTTP client with mTLS identity, private CA, and redirect allowlist applied.
+    pub fn client(&self) -> Client {
+        self.client.clone()
+    }
+
+    /// Lowercased hosts this transport may contact.
+    pub fn allowed_hosts(&self) -> &[String] {
+        &self.allowed_hosts
+    }
+
+    /// Sign an outgoing request when a signing scheme is configured.
+    ///
+    /// Must be called after the body and all other headers are set. When the
+    /// request cannot be built, the original builder is returned untouched so
+    /// `send()` surfaces the underlying build error.
+    pub fn sign(&self, builder: RequestBuilder) -> RequestBuilder {
+        let Some(signer) = self.signer.as_deref() else {
+            return builder;
+        };
+        let Some(request) = builder.try_clone().and_then(|clone| clone.build().ok()) else {
+            return builder;
+        };
+        let mut request = request;
+        sign_request(signer, &mut request, chrono::Utc::now());
+        RequestBuilder::from_parts(self.client.clone(), request)
+    }
+}
+
+fn effective_allowed_hosts(
+    base_url: &str,
+    entry: &CustomProviderConfig,
+) -> anyhow::Result<Vec<String>> {
+    let endpoint_host = reqwest::Url::parse(base_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        .with_context(|| format!("Custom provider URL {base_url} has no host"))?;
+
+    if entry.allowed_hosts.is_empty() {
+        return Ok(vec![endpoint_host]);
+    }
+
+    let hosts: Vec<String> = entry
+        .allowed_hosts
+        .iter()
+        .map(|host| host.trim().to_ascii_lowercase())
+        .filter(|host| !host.is_empty())
+        .collect();
+    if !hosts.contains(&endpoint_host) {
+        anyhow::bail!(
+            "Custom provider host '{endpoint_host}' is not in custom_providers.\"{base_url}\".allowed_hosts"
+        );
+    }
+    Ok(hosts)
+}
+
+fn body_bytes(request: &Request) -> &[u8] {
+    request
+        .body()
+        .and_then(reqwest::Body::as_bytes)
+        .unwrap_or_default()
+}
+
+fn path_and_query(url: &reqwest::Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    }
+}
+
+fn insert_header(request: &mut Request, name: &str, value: &str) {
+    match (
+        reqwest::header::HeaderName::from_bytes(name.as_bytes()),
+        HeaderValue::from_str(value),
+    ) {
+        (Ok(name), Ok(value)) => {
+            request.headers_mut().insert(name, value);
+        }
+        _ => tracing::warn!(
+            header = name,
+            "Skipping invalid custom provider signing header"
+        ),
+    }
+}
+
+fn sign_request(
+    signer: &RequestSigningConfig,
+    request: &mut Request,
+    now: chrono::DateTime<chrono::Utc>,
+) {
+    match signer {
+        RequestSigningConfig::Hmac {
+            secret,
+            key_id,
+            signature_header,
+            timestamp_header,
+        } => {
+            let timestamp = now.timestamp().to_string();
+            let canonical = format!(
+                "{timestamp}\n{}\n{}\n{}",
+                request.method().as_str(),
+                path_and_query(request.url()),
+                sha256_hex(body_bytes(request))
+            );
+            let signature = hex::encode(hmac_sha256(secret.as_bytes(), canonical.as_bytes()));
+            insert_header(request, timestamp_header, &timestamp);
+            insert_header(request, signature_header, &signature);
+            if let Some(key_id) = key_id.as_deref() {
+                insert_header(request, "x-zeroclaw-key-id", key_id);
+            }
+        }
+        RequestSigningConfig::SigV4 {
+            access_key_id,
+            secret_access_key,
+            session_token,
+            region,
+            service,
+        } => {
+            let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+            let date_stamp = now.format("%Y%m%d").to_string();
+            let payload_hash = sha256_hex(body_bytes(request));
+            let host = match (request.url().host_str(), request.url().port()) {
+                (Some(host), Some(port)) => format!("{host}:{port}"),
+                (Some(host), None) => host.to_string(),
+                (None, _) => String::new(),
+            };
+
+            insert_header(request, "x-amz-date", &amz_date);
+            insert_header(request, "x-amz-content-sha256", &payload_hash);
+            if let Some(token) = session_token.as_deref() {
+                insert_header(request, "x-amz-security-token", token);
+            }
+
+            let mut signed: Vec<(String, String)> = vec![
+                ("host".into(), host),
+                ("x-amz-content-sha256".into(), payload_hash.clone()),
+                ("x-amz-date".into(), amz_date.clone()),
+            ];
+            if let Some(content_type) = request
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+            {
+                signed.push(("content-type".into(), content_type.trim().to_string()));
+            }
+            if let Some(token) = session_token.as_deref() {
+                signed.push(("x-amz-security-token".into(), token.to_string()));
+            }
+            signed.sort_by(|a, b| a.0.cmp(&b.0));
+
+            let canonical_headers: String = signed.iter().fold(String::new(), |mut acc, (k, v)| {
+                let _ = writeln!(acc, "{k}:{v}");
+                acc
+            });
+            let signed_headers = signed
+                .iter()
+                .map(|(k, _)| k.as_str())
+                .collect::<Vec<_>>()
+                .join(";");
+
+            let mut query_pairs: Vec<&str> = request
+                .url()
+                .query()
+                .map(|q| q.split('&').filter(|p| !p.is_empty()).collect())
+                .unwrap_or_default();
+            query_pairs.sort_unstable();
+            let canonical_query = query_pairs.join("&");
+
+            let path = request.url().path();
+            let canonical_uri = if path.is_empty() { "/" } else { path };
+            let canonical_request = format!(
+                "{}\n{canonical_uri}\n{canonical_query}\n{canonical_headers}\n{signed_headers}\n{payload_hash}",
+                request.method().as_str()
+            );
+            let scope = format!("{date_stamp}/{region}/{service}/aws4_request");
+            let string_to_sign = format!(
+                "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+                sha256_hex(canonical_request.as_bytes())
+            );
+            let key = derive_signing_key(secret_access_key, &date_stamp, region, service);
+            let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+            let authorization = format!(
+                "AWS4-HMAC-SHA256 Credential={access_key_id}/{scope}, SignedHeaders={signed_headers}, Signature={signature}"
+            );
+            insert_header(request, "authorization", &authorization);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn fixed_now() -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap()
+    }
+
+    fn build_request(body: &str) -> Request {
+        Client::new()
+            .post("https://gateway.example.com/v1/chat/completions")
+            .header("content-type", "application/json")
+            .body(body.to_string())
+            .build()
+            .unwrap()
+    }
+
+    fn hmac_config() -> RequestSigningConfig {
+        RequestSigningConfig::Hmac {
+            secret: "zeroclaw-shared-secret".into(),
+            key_id: Some("zeroclaw_node".into()),
+            signature_header: "x-zeroclaw-signature".into(),
+            timestamp_header: "x-zeroclaw-timestamp".into(),
+        }
+    }
+
+    #[test]
+    fn hmac_signature_covers_method_path_and_body() {
+        let mut request = build_request("{\"model\":\"m\"}");
+        sign_request(&hmac_config(), &mut request, fixed_now());
+
+        let expected_canonical = format!(
+            "{}\nPOST\n/v1/chat/completions\n{}",
+            fixed_now().timestamp(),
+            sha256_hex(b"{\"model\":\"m\"}")
+        );
+        let expected = hex::encode(hmac_sha256(
+            b"zeroclaw-shared-secret",
+            expected_canonical.as_bytes(),
+        ));
+        let headers = request.headers();
+        assert_eq!(headers["x-zeroclaw-signature"], expected.as_str());
+        assert_eq!(
+            headers["x-zeroclaw-timestamp"],
+            fixed_now().timestamp().to_string().as_str()
+        );
+        assert_eq!(headers["x-zeroclaw-key-id"], "zeroclaw_node");
+    }
+
+    #[test]
+    fn hmac_signature_changes_with_body() {
+        let mut first = build_request("{\"a\":1}");
+        let mut second = build_request("{\"a\":2}");
+        sign_request(&hmac_config(), &mut first, fixed_now());
+        sign_request(&hmac_config(), &mut second, fixed_now());
+        assert_ne!(
+            first.headers()["x-zeroclaw-signature"],
+            second.headers()["x-zeroclaw-signature"]
+        );
+    }
+
+    #[test]
+    fn sigv4_sets_authorization_with_scope_and_signed_headers() {
+        let signer = RequestSigningConfig::SigV4 {
+            access_key_id: "AKIDEXAMPLE".into(),
+            secret_access_key: "secret".into(),
+            session_token: Some("session".into()),
+            region: "eu-west-1".into(),
+            service: "execute-api".into(),
+        };
+        let mut request = build_request("{}");
+        sign_request(&signer, &mut request, fixed_now());
+
+        let auth = request.headers()["authorization"].to_str().unwrap();
+        assert!(auth.starts_with(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20260102/eu-west-1/execute-api/aws4_request"
+        ));
+        assert!(auth.contains(
+            "SignedHeaders=content-type;host;x-amz-content-sha256;x-amz-date;x-amz-security-token"
+        ));
+        assert_eq!(request.headers()["x-amz-date"], "20260102T030405Z");
+        assert_eq!(request.headers()["x-amz-security-token"], "session");
+    }
+
+    #[test]
+    fn allowed_hosts_default_to_endpoint_host() {
+        let hosts = effective_allowed_hosts(
+            "https://Gateway.Example.com/v1",
+            &CustomProviderConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(hosts, vec!["gateway.example.com".to_string()]);
+    }
+
+    #[test]
+    fn allowed_hosts_must_include_endpoint_host() {
+        let entry = CustomProviderConfig {
+            allowed_hosts: vec!["other.example.com".into()],
+            ..CustomProviderConfig::default()
+        };
+        let err = effective_allowed_hosts("https://gateway.example.com/v1", &entry).unwrap_err();
+        assert!(err.to_string().contains("not in custom_providers"));
+    }
+
+    #[test]
+    fn from_config_rejects_missing_client_identity() {
+        let entry = CustomProviderConfig {
+            client_identity_path: Some("/nonexistent/zeroclaw-client.pem".into()),
+            ..CustomProviderConfig::default()
+        };
+        let result = CustomProviderTransport::from_config("https://gateway.example.com", &entry);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn sign_is_noop_without_signing_config() {
+        let transport = CustomProviderTransport::from_config(
+            "https://gateway.example.com",
+            &CustomProviderConfig::default(),
+        )
+        .unwrap();
+        let request = transport
+            .sign(transport.client().post("https://gateway.example.com/v1"))
+            .build()
+            .unwrap();
+        assert!(request.headers().get("x-zeroclaw-signature").is_none());
+        assert_eq!(transport.allowed_hosts(), ["gateway.example.com"]);
+    }
+}
@@ -18,15 +18,20 @@
 
 pub mod anthropic;
 pub mod bedrock;
+pub mod budget;
+pub mod capabilities;
 pub mod compatible;
 pub mod copilot;
+pub mod custom_transport;
 pub mod gemini;
 pub mod ollama;
 pub mod openai;
 pub mod openai_codex;
 pub mod openrouter;
+pub mod plugin;
 pub mod reliable;
 pub mod router;
+pub mod shadow;
 pub mod telnyx;
 pub mod traits;
 
@@ -40,6 +45,7 @@ use crate::auth::AuthService;
 use compatible::{AuthStyle, OpenAiCompatibleProvider};
 use reliable::ReliableProvider;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 const MAX_API_ERROR_CHARS: usize = 200;
@@ -676,6 +682,10 @@ pub struct ProviderRuntimeOptions {
     pub secrets_encrypt: bool,
     pub provider_api_url: Option<String>,
     pub reasoning_enabled: Option<bool>,
+    /// `[custom_providers]` entries keyed by `custom:<URL>` base URL.
+    pub custom_providers: HashMap<String, crate::config::CustomProviderConfig>,
+    /// `[provider_plugins]` sidecars keyed by the name after `plugin:`.
+    pub provider_plugins: HashMap<String, crate::config::ProviderPluginConfig>,
 }
 
 impl Default for ProviderRuntimeOptions {
@@ -686,6 +696,8 @@ impl Default for ProviderRuntimeOptions {
             secrets_encrypt: true,
             provider_api_url: None,
             reasoning_enabled: None,
+            custom_providers: HashMap::new(),
+            provider_plugins: HashMap::new(),
         }
     }
 }
@@ -904,6 +916,18 @@ fn parse_custom_provider_url(
     }
 }
 
+/// Look up the `[custom_providers]` entry for a custom base URL, ignoring trailing slashes.
+fn custom_provider_entry<'a>(
+    entries: &'a HashMap<String, crate::config::CustomProviderConfig>,
+    base_url: &str,
+) -> Option<&'a crate::config::CustomProviderConfig> {
+    let wanted = base_url.trim().trim_end_matches('/');
+    entries
+        .iter()
+        .find(|(key, _)| key.trim().trim_end_matches('/') == wanted)
+        .map(|(_, entry)| entry)
+}
+
 /// Factory: create the right provider from config (without custom URL)
 pub fn create_provider(name: &str, api_key: Option<&str>) -> anyhow::Result<Box<dyn Provider>> {
     create_provider_with_options(name, api_key, &ProviderRuntimeOptions::default())
@@ -1206,12 +1230,16 @@ fn create_provider_with_url_and_options(
                 "Custom provider",
                 "custom:https://your-api.com",
             )?;
-            Ok(Box::new(OpenAiCompatibleProvider::new(
-                "Custom",
-                &base_url,
-                key,
-                AuthStyle::Bearer,
-            )))
+            let provider =
+                OpenAiCompatibleProvider::new("Custom", &base_url, key, AuthStyle::Bearer);
+            match custom_provider_entry(&options.custom_providers, &base_url) {
+                Some(entry) => {
+                    let transport =
+                        custom_transport::CustomProviderTransport::from_config(&base_url, entry)?;
+                    Ok(Box::new(provider.with_transport(transport)))
+                }
+                None => Ok(Box::new(provider)),
+            }
         }
 
         // ── Anthropic-compatible custom endpoints ───────────
@@ -1228,10 +1256,23 @@ fn create_provider_with_url_and_options(
             )))
         }
 
+        // ── External sidecar providers ──────────────────────
+        // Format: "plugin:<name>" with a `[provider_plugins.<name>]` entry
+        name if name.starts_with("plugin:") => {
+            let plugin = name.strip_prefix("plugin:").unwrap_or("").trim();
+            let entry = options.provider_plugins.get(plugin).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Provider plugin '{plugin}' is not configured; add [provider_plugins.{plugin}] with a command"
+                )
+            })?;
+            Ok(Box::new(plugin::PluginProvider::new(plugin, entry.clone(), key)))
+        }
+
         _ => anyhow::bail!(
             "Unknown provider: {name}. Check README for supported providers or run `zeroclaw onboard --interactive` to reconfigure.\n\
              Tip: Use \"custom:https://your-api.com\" for OpenAI-compatible endpoints.\n\
-             Tip: Use \"anthropic-custom:https://your-api.com\" for Anthropic-compatible endpoints."
+             Tip: Use \"anthropic-custom:https://your-api.com\" for Anthropic-compatible endpoints.\n\
+             Tip: Use \"plugin:<name>\" with [provider_plugins.<name>] for external sidecar providers."
         ),
     }
 }
@@ -1240,10 +1281,10 @@ fn create_provider_with_url_and_options(
 ///
 /// Returns `(provider_name, Some(profile))` when the entry contains a colon-
 /// delimited profile, or `(original_str, None)` otherwise.  Entries starting
-/// with `custom:` or `anthropic-custom:` are left untouched because the colon
-/// is part of the URL scheme.
+/// with `custom:`, `anthropic-custom:` or `plugin:` are left untouched because
+/// the colon is part of the provider name.
 fn parse_provider_profile(s: &str) -> (&str, Option<&str>) {
-    if s.starts_with("custom:") || s.starts_with("anthropic-custom:") {
+    if s.starts_with("custom:") || s.starts_with("anthropic-custom:") || s.starts_with("plugin:") {
         return (s, None);
     }
     match s.split_once(':') {
@@ -2338,6 +2379,45 @@ mod tests {
         assert!(p.is_ok());
     }
 
+    #[test]
+    fn factory_custom_applies_matching_transport_entry() {
+        let mut options = ProviderRuntimeOptions::default();
+        options.custom_providers.insert(
+            "https://gateway.example.com/v1/".into(),
+            crate::config::CustomProviderConfig {
+                allowed_hosts: vec!["other.example.com".into()],
+                ..crate::config::CustomProviderConfig::default()
+            },
+        );
+        // The entry matches despite the trailing slash, so the allowlist is enforced.
+        let err = create_provider_with_options(
+            "custom:https://gateway.example.com/v1",
+            Some("key"),
+            &options,
+        )
+        .err()
+        .expect("endpoint outside allowlist should fail");
+        assert!(err.to_string().contains("allowed_hosts"));
+    }
+
+    #[test]
+    fn factory_custom_ignores_transport_entries_for_other_urls() {
+        let mut options = ProviderRuntimeOptions::default();
+        options.custom_providers.insert(
+            "https://gateway.example.com/v1".into(),
+            crate::config::CustomProviderConfig {
+                client_identity_path: Some("/nonexistent/zeroclaw-client.pem".into()),
+                ..crate::config::CustomProviderConfig::default()
+            },
+        );
+        let p = create_provider_with_options(
+            "custom:https://my-llm.example.com",
+            Some("key"),
+            &options,
+        );
+        assert!(p.is_ok());
+    }
+
     // ── Anthropic-compatible custom endpoints ─────────────────
 
     #[test]
@@ -2358,6 +2438,32 @@ mod tests {
         assert!(p.is_ok());
     }
 
+    #[test]
+    fn factory_plugin_requires_configured_entry() {
+        match create_provider("plugin:local-llm", None) {
+            Err(e) => assert!(
+                e.to_string().contains("[provider_plugins.local-llm]"),
+                "Expected missing plugin error, got: {e}"
+            ),
+            Ok(_) => panic!("Expected error for unconfigured plugin"),
+        }
+
+        let mut options = ProviderRuntimeOptions::default();
+        options.provider_plugins.insert(
+            "local-llm".into(),
+            crate::config::ProviderPluginConfig {
+                command: "local-llm-sidecar".into(),
+                args: Vec::new(),
+                env: HashMap::new(),
+                timeout_secs: 30,
+                native_tools: true,
+                vision: false,
+            },
+        );
+        let provider = create_provider_with_options("plugin:local-llm", None, &options).unwrap();
+        assert!(provider.supports_native_tools());
+    }
+
     #[test]
     fn factory_anthropic_custom_empty_url_errors() {
         match create_provider("anthropic-custom:", None) {
@@ -2805,6 +2911,13 @@ mod tests {
         assert_eq!(profile, None);
     }
 
+    #[test]
+    fn parse_provider_profile_plugin_not_split() {
+        let (name, profile) = parse_provider_profile("plugin:local-llm");
+        assert_eq!(name, "plugin:local-llm");
+        assert_eq!(profile, None);
+    }
+
     #[test]
     fn parse_provider_profile_empty_profile_ignored() {
         let (name, profile) = parse_provider_profile("openai-codex:");
@@ -2,6 +2,7 @@ use super::traits::{
     ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamOptions, StreamResult,
 };
 use super::Provider;
+use crate::errors::{self, ClassifiedError, ErrorClass};
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
 use std::collections::HashMap;
@@ -9,94 +10,23 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
 // ── Error Classification ─────────────────────────────────────────────────
-// Errors are split into retryable (transient server/network failures) and
-// non-retryable (permanent client errors). This distinction drives whether
-// the retry loop continues, falls back to the next provider, or aborts
-// immediately — avoiding wasted latency on errors that cannot self-heal.
+// Errors are classified by the crate-wide taxonomy in `crate::errors`; the
+// class's retry policy decides whether the retry loop continues, falls back
+// to the next provider, or aborts immediately — avoiding wasted latency on
+// errors that cannot self-heal.
 
 /// Check if an error is non-retryable (client errors that won't resolve with retries).
 fn is_non_retryable(err: &anyhow::Error) -> bool {
-    if is_context_window_exceeded(err) {
-        return true;
-    }
-
-    // 4xx errors are generally non-retryable (bad request, auth failure, etc.),
-    // except 429 (rate-limit — transient) and 408 (timeout — worth retrying).
-    if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
-        if let Some(status) = reqwest_err.status() {
-            let code = status.as_u16();
-            return status.is_client_error() && code != 429 && code != 408;
-        }
-    }
-    // Fallback: parse status codes from stringified errors (some providers
-    // embed codes in error messages rather than returning typed HTTP errors).
-    let msg = err.to_string();
-    for word in msg.split(|c: char| !c.is_ascii_digit()) {
-        if let Ok(code) = word.parse::<u16>() {
-            if (400..500).contains(&code) {
-                return code != 429 && code != 408;
-            }
-        }
-    }
-
-    // Heuristic: detect auth/model failures by keyword when no HTTP status
-    // is available (e.g. gRPC or custom transport errors).
-    let msg_lower = msg.to_lowercase();
-    let auth_failure_hints = [
-        "invalid api key",
-        "incorrect api key",
-        "missing api key",
-        "api key not set",
-        "authentication failed",
-        "auth failed",
-        "unauthorized",
-        "forbidden",
-        "permission denied",
-        "access denied",
-        "invalid token",
-    ];
-
-    if auth_failure_hints
-        .iter()
-        .any(|hint| msg_lower.contains(hint))
-    {
-        return true;
-    }
-
-    msg_lower.contains("model")
-        && (msg_lower.contains("not found")
-            || msg_lower.contains("unknown")
-            || msg_lower.contains("unsupported")
-            || msg_lower.contains("does not exist")
-            || msg_lower.contains("invalid"))
+    !errors::classify(err).retry_policy().retriable
 }
 
 fn is_context_window_exceeded(err: &anyhow::Error) -> bool {
-    let lower = err.to_string().to_lowercase();
-    let hints = [
-        "exceeds the context window",
-        "context window of this model",
-        "maximum context length",
-        "context length exceeded",
-        "too many tokens",
-        "token limit exceeded",
-        "prompt is too long",
-        "input is too long",
-    ];
-
-    hints.iter().any(|hint| lower.contains(hint))
+    errors::is_context_window_exceeded(&err.to_string())
 }
 
 /// Check if an error is a rate-limit (429) error.
 fn is_rate_limited(err: &anyhow::Error) -> bool {
-    if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
-        if let Some(status) = reqwest_err.status() {
-            return status.as_u16() == 429;
-        }
-    }
-    let msg = err.to_string();
-    msg.contains("429")
-        && (msg.contains("Too Many") || msg.contains("rate") || msg.contains("limit"))
+    errors::classify(err) == ErrorClass::RateLimit
 }
 
 /// Check if a 429 is a business/quota-plan error that retries cannot fix.
@@ -197,6 +127,7 @@ fn compact_error_detail(err: &anyhow::Error) -> String {
         .join(" ")
 }
 
+#[allow(clippy::too_many_arguments)]
 fn push_failure(
     failures: &mut Vec<String>,
     provider_name: &str,
@@ -204,10 +135,11 @@ fn push_failure(
     attempt: u32,
     max_attempts: u32,
     reason: &str,
+    class: ErrorClass,
     error_detail: &str,
 ) {
     failures.push(format!(
-        "provider={provider_name} model={model} attempt {attempt}/{max_attempts}: {reason}; error={error_detail}"
+        "provider={provider_name} model={model} attempt {attempt}/{max_attempts}: {reason}; code={class}; error={error_detail}"
     ));
 }
 
@@ -311,6 +243,7 @@ impl Provider for ReliableProvider {
     ) -> anyhow::Result<String> {
         let models = self.model_chain(model);
         let mut failures = Vec::new();
+        let mut last_class = ErrorClass::Internal;
 
         // Outer: model fallback chain. Middle: provider priority. Inner: retries.
         // Each iteration: attempt one (provider, model) call. On success, return
@@ -343,6 +276,7 @@ impl Provider for ReliableProvider {
                             let rate_limited = is_rate_limited(&e);
                             let failure_reason = failure_reason(rate_limited, non_retryable);
                             let error_detail = compact_error_detail(&e);
+                            last_class = errors::classify(&e);
 
                             push_failure(
                                 &mut failures,
@@ -351,6 +285,7 @@ impl Provider for ReliableProvider {
                                 attempt + 1,
                                 self.max_retries + 1,
                                 failure_reason,
+                                last_class,
                                 &error_detail,
                             );
 
@@ -377,11 +312,26 @@ impl Provider for ReliableProvider {
                                     "Non-retryable error, moving on"
                                 );
 
+                                if !last_class.retry_policy().fallback {
+                                    return Err(ClassifiedError::new(
+                                        last_class,
+                                        format!(
+                                            "Provider call failed ({last_class}); fallbacks were skipped. Attempts:\n{}",
+                                            failures.join("\n")
+                                        ),
+                                    )
+                                    .into());
+                                }
+
                                 if is_context_window_exceeded(&e) {
-                                    anyhow::bail!(
-                                        "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
-                                        failures.join("\n")
-                                    );
+                                    return Err(ClassifiedError::new(
+                                        ErrorClass::Validation,
+                                        format!(
+                                            "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
+                                            failures.join("\n")
+                                        ),
+                                    )
+                                    .into());
                                 }
 
                                 break;
@@ -421,10 +371,14 @@ impl Provider for ReliableProvider {
             }
         }
 
-        anyhow::bail!(
-            "All providers/models failed. Attempts:\n{}",
-            failures.join("\n")
+        Err(ClassifiedError::new(
+            last_class,
+            format!(
+                "All providers/models failed. Attempts:\n{}",
+                failures.join("\n")
+            ),
         )
+        .into())
     }
 
     async fn chat_with_history(
@@ -435,6 +389,7 @@ impl Provider for ReliableProvider {
     ) -> anyhow::Result<String> {
         let models = self.model_chain(model);
         let mut failures = Vec::new();
+        let mut last_class = ErrorClass::Internal;
 
         for current_model in &models {
             for (provider_name, provider) in &self.providers {
@@ -463,6 +418,7 @@ impl Provider for ReliableProvider {
                             let rate_limited = is_rate_limited(&e);
                             let failure_reason = failure_reason(rate_limited, non_retryable);
                             let error_detail = compact_error_detail(&e);
+                            last_class = errors::classify(&e);
 
                             push_failure(
                                 &mut failures,
@@ -471,6 +427,7 @@ impl Provider for ReliableProvider {
                                 attempt + 1,
                                 self.max_retries + 1,
                                 failure_reason,
+                                last_class,
                                 &error_detail,
                             );
 
@@ -495,11 +452,26 @@ impl Provider for ReliableProvider {
                                     "Non-retryable error, moving on"
                                 );
 
+                                if !last_class.retry_policy().fallback {
+                                    return Err(ClassifiedError::new(
+                                        last_class,
+                                        format!(
+                                            "Provider call failed ({last_class}); fallbacks were skipped. Attempts:\n{}",
+                                            failures.join("\n")
+                                        ),
+                                    )
+                                    .into());
+                                }
+
                                 if is_context_window_exceeded(&e) {
-                                    anyhow::bail!(
-                                        "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
-                                        failures.join("\n")
-                                    );
+                                    return Err(ClassifiedError::new(
+                                        ErrorClass::Validation,
+                                        format!(
+                                            "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
+                                            failures.join("\n")
+                                        ),
+                                    )
+                                    .into());
                                 }
 
                                 break;
@@ -531,10 +503,14 @@ impl Provider for ReliableProvider {
             }
         }
 
-        anyhow::bail!(
-            "All providers/models failed. Attempts:\n{}",
-            failures.join("\n")
+        Err(ClassifiedError::new(
+            last_class,
+            format!(
+                "All providers/models failed. Attempts:\n{}",
+                failures.join("\n")
+            ),
         )
+        .into())
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -550,6 +526,12 @@ impl Provider for ReliableProvider {
             .any(|(_, provider)| provider.supports_vision())
     }
 
+    fn caches_tool_schemas(&self) -> bool {
+        self.providers
+            .first()
+            .is_some_and(|(_, p)| p.caches_tool_schemas())
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -559,6 +541,7 @@ impl Provider for ReliableProvider {
     ) -> anyhow::Result<ChatResponse> {
         let models = self.model_chain(model);
         let mut failures = Vec::new();
+        let mut last_class = ErrorClass::Internal;
 
         for current_model in &models {
             for (provider_name, provider) in &self.providers {
@@ -587,6 +570,7 @@ impl Provider for ReliableProvider {
                             let rate_limited = is_rate_limited(&e);
                             let failure_reason = failure_reason(rate_limited, non_retryable);
                             let error_detail = compact_error_detail(&e);
+                            last_class = errors::classify(&e);
 
                             push_failure(
                                 &mut failures,
@@ -595,6 +579,7 @@ impl Provider for ReliableProvider {
                                 attempt + 1,
                                 self.max_retries + 1,
                                 failure_reason,
+                                last_class,
                                 &error_detail,
                             );
 
@@ -619,11 +604,26 @@ impl Provider for ReliableProvider {
                                     "Non-retryable error, moving on"
                                 );
 
+                                if !last_class.retry_policy().fallback {
+                                    return Err(ClassifiedError::new(
+                                        last_class,
+                                        format!(
+                                            "Provider call failed ({last_class}); fallbacks were skipped. Attempts:\n{}",
+                                            failures.join("\n")
+                                        ),
+                                    )
+                                    .into());
+                                }
+
                                 if is_context_window_exceeded(&e) {
-                                    anyhow::bail!(
-                                        "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
-                                        failures.join("\n")
-                                    );
+                                    return Err(ClassifiedError::new(
+                                        ErrorClass::Validation,
+                                        format!(
+                                            "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
+                                            failures.join("\n")
+                                        ),
+                                    )
+                                    .into());
                                 }
 
                                 break;
@@ -655,10 +655,14 @@ impl Provider for ReliableProvider {
             }
         }
 
-        anyhow::bail!(
-            "All providers/models failed. Attempts:\n{}",
-            failures.join("\n")
+        Err(ClassifiedError::new(
+            last_class,
+            format!(
+                "All providers/models failed. Attempts:\n{}",
+                failures.join("\n")
+            ),
         )
+        .into())
     }
 
     async fn chat(
@@ -669,6 +673,7 @@ impl Provider for ReliableProvider {
     ) -> anyhow::Result<ChatResponse> {
         let models = self.model_chain(model);
         let mut failures = Vec::new();
+        let mut last_class = ErrorClass::Internal;
 
         for current_model in &models {
             for (provider_name, provider) in &self.providers {
@@ -698,6 +703,7 @@ impl Provider for ReliableProvider {
                             let rate_limited = is_rate_limited(&e);
                             let failure_reason = failure_reason(rate_limited, non_retryable);
                             let error_detail = compact_error_detail(&e);
+                            last_class = errors::classify(&e);
 
                             push_failure(
                                 &mut failures,
@@ -706,6 +712,7 @@ impl Provider for ReliableProvider {
                                 attempt + 1,
                                 self.max_retries + 1,
                                 failure_reason,
+                                last_class,
                                 &error_detail,
                             );
 
@@ -730,11 +737,26 @@ impl Provider for ReliableProvider {
                                     "Non-retryable error, moving on"
                                 );
 
+                                if !last_class.retry_policy().fallback {
+                                    return Err(ClassifiedError::new(
+                                        last_class,
+                                        format!(
+                                            "Provider call failed ({last_class}); fallbacks were skipped. Attempts:\n{}",
+                                            failures.join("\n")
+                                        ),
+                                    )
+                                    .into());
+                                }
+
                                 if is_context_window_exceeded(&e) {
-                                    anyhow::bail!(
-                                        "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
-                                        failures.join("\n")
-                                    );
+                                    return Err(ClassifiedError::new(
+                                        ErrorClass::Validation,
+                                        format!(
+                                            "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
+                                            failures.join("\n")
+                                        ),
+                                    )
+                                    .into());
                                 }
 
                                 break;
@@ -774,10 +796,14 @@ impl Provider for ReliableProvider {
             }
         }
 
-        anyhow::bail!(
-            "All providers/models failed. Attempts:\n{}",
-            failures.join("\n")
+        Err(ClassifiedError::new(
+            last_class,
+            format!(
+                "All providers/models failed. Attempts:\n{}",
+                failures.join("\n")
+            ),
         )
+        .into())
     }
 
     fn supports_streaming(&self) -> bool {
@@ -852,6 +878,36 @@ impl Provider for ReliableProvider {
         })
         .boxed()
     }
+
+    fn supports_tool_streaming(&self) -> bool {
+        self.providers
+            .first()
+            .is_some_and(|(_, p)| p.supports_tool_streaming())
+    }
+
+    fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        // Only the primary provider streams tool calls; callers fall back to
+        // `chat` (and with it the full retry/fallback chain) on errors.
+        let Some((_, provider)) = self
+            .providers
+            .first()
+            .filter(|(_, p)| p.supports_tool_streaming())
+        else {
+            return stream::once(async move {
+                Err(super::traits::StreamError::Provider(
+                    "Primary provider does not stream tool calls".to_string(),
+                ))
+            })
+            .boxed();
+        };
+        let current_model = self.model_chain(model).first().copied().unwrap_or(model);
+        provider.stream_chat(request, current_model, temperature)
+    }
 }
 
 #[cfg(test)]
@@ -968,6 +1024,53 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 2);
     }
 
+    #[tokio::test]
+    async fn exhausted_and_policy_errors_carry_their_class() {
+        let failing = |error: &'static str, calls: &Arc<AtomicUsize>| -> Box<dyn Provider> {
+            Box::new(MockProvider {
+                calls: Arc::clone(calls),
+                fail_until_attempt: usize::MAX,
+                response: "never",
+                error,
+            })
+        };
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![("primary".into(), failing("401 Unauthorized", &calls))],
+            2,
+            1,
+        );
+        let err = provider
+            .simple_chat("hello", "test", 0.0)
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&err), ErrorClass::Auth);
+        assert!(err.to_string().contains("code=auth"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "primary".into(),
+                    failing("blocked by security policy", &primary_calls),
+                ),
+                ("fallback".into(), failing("500 boom", &fallback_calls)),
+            ],
+            2,
+            1,
+        );
+        let err = provider
+            .simple_chat("hello", "test", 0.0)
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&err), ErrorClass::PolicyDenied);
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 0);
+    }
+
     #[tokio::test]
     async fn falls_back_after_retries_exhausted() {
         let primary_calls = Arc::new(AtomicUsize::new(0));
@@ -164,6 +164,12 @@ impl Provider for RouterProvider {
             .any(|(_, provider)| provider.supports_vision())
     }
 
+    fn caches_tool_schemas(&self) -> bool {
+        self.providers
+            .get(self.default_index)
+            .is_some_and(|(_, p)| p.caches_tool_schemas())
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         for (name, provider) in &self.providers {
             tracing::info!(provider = name, "Warming up routed provider");
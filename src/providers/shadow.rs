@@ -0,0 +1,707 @@
+//! Provider A/A shadow mode.
+//!
+//! [`ShadowProvider`] wraps the live provider and mirrors a sampled share of
+//! calls to a second provider/model in the background. The caller only ever
+//! sees the primary response; both outcomes (text, tool calls, tokens, cost,
+//! latency) are appended to `state/shadow.jsonl` so a model switch can be
+//! judged on real traffic first.
+
+use super::traits::{ChatMessage, ChatRequest, ChatResponse};
+use super::{Provider, ProviderRuntimeOptions};
+use crate::config::Config;
+use crate::observability::runtime_trace;
+use crate::tools::ToolSpec;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+/// Shadow log file name under `<workspace>/state/`.
+pub const SHADOW_LOG_FILE: &str = "shadow.jsonl";
+
+/// Prompt and reply text is truncated to this many characters in the log.
+const MAX_LOGGED_CHARS: usize = 4000;
+
+/// One side (primary or shadow) of a mirrored call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowSide {
+    pub provider: String,
+    pub model: String,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub tool_calls: Vec<String>,
+    #[serde(default)]
+    pub input_tokens: Option<u64>,
+    #[serde(default)]
+    pub output_tokens: Option<u64>,
+    #[serde(default)]
+    pub cost_usd: Option<f64>,
+    pub latency_ms: u64,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl ShadowSide {
+    fn new(
+        provider: &str,
+        model: &str,
+        outcome: std::result::Result<&ChatResponse, &anyhow::Error>,
+        elapsed: Duration,
+    ) -> Self {
+        let latency_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        match outcome {
+            Ok(response) => {
+                let usage = response.usage.clone().unwrap_or_default();
+                Self {
+                    provider: provider.to_string(),
+                    model: model.to_string(),
+                    text: response.text.as_deref().map(redact_for_log),
+                    tool_calls: response
+                        .tool_calls
+                        .iter()
+                        .map(|call| call.name.clone())
+                        .collect(),
+                    input_tokens: usage.input_tokens,
+                    output_tokens: usage.output_tokens,
+                    cost_usd: runtime_trace::estimate_cost_usd(
+                        provider,
+                        model,
+                        usage.input_tokens,
+                        usage.output_tokens,
+                    ),
+                    latency_ms,
+                    error: None,
+                }
+            }
+            Err(e) => Self {
+                provider: provider.to_string(),
+                model: model.to_string(),
+                text: None,
+                tool_calls: Vec::new(),
+                input_tokens: None,
+                output_tokens: None,
+                cost_usd: None,
+                latency_ms,
+                error: Some(super::sanitize_api_error(&e.to_string())),
+            },
+        }
+    }
+}
+
+/// A mirrored call as stored in `state/shadow.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowRecord {
+    pub timestamp: DateTime<Utc>,
+    /// Last user message of the request.
+    pub prompt: String,
+    pub primary: ShadowSide,
+    pub shadow: ShadowSide,
+}
+
+/// Owned copy of a request so it can be replayed on a background task.
+enum ShadowRequest {
+    System {
+        system_prompt: Option<String>,
+        message: String,
+    },
+    History(Vec<ChatMessage>),
+    Chat {
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<ToolSpec>>,
+    },
+    Tools {
+        messages: Vec<ChatMessage>,
+        tools: Vec<serde_json::Value>,
+    },
+}
+
+impl ShadowRequest {
+    fn prompt(&self) -> String {
+        let text = match self {
+            Self::System { message, .. } => message.as_str(),
+            Self::History(messages)
+            | Self::Chat { messages, .. }
+            | Self::Tools { messages, .. } => messages
+                .iter()
+                .rfind(|m| m.role == "user")
+                .map_or("", |m| m.content.as_str()),
+        };
+        redact_for_log(text)
+    }
+
+    async fn send(
+        &self,
+        provider: &dyn Provider,
+        model: &str,
+        temperature: f64,
+    ) -> Result<ChatResponse> {
+        match self {
+            Self::System {
+                system_prompt,
+                message,
+            } => provider
+                .chat_with_system(system_prompt.as_deref(), message, model, temperature)
+                .await
+                .map(text_response),
+            Self::History(messages) => provider
+                .chat_with_history(messages, model, temperature)
+                .await
+                .map(text_response),
+            Self::Chat { messages, tools } => {
+                let request = ChatRequest {
+                    messages,
+                    tools: tools.as_deref(),
+                };
+                provider.chat(request, model, temperature).await
+            }
+            Self::Tools { messages, tools } => {
+                provider
+                    .chat_with_tools(messages, tools, model, temperature)
+                    .await
+            }
+        }
+    }
+}
+
+fn text_response(text: String) -> ChatResponse {
+    ChatResponse {
+        text: Some(text),
+        tool_calls: Vec::new(),
+        usage: None,
+    }
+}
+
+fn redact_for_log(text: &str) -> String {
+    crate::util::truncate_with_ellipsis(&super::scrub_secret_patterns(text), MAX_LOGGED_CHARS)
+}
+
+/// Provider wrapper that mirrors sampled calls to a shadow provider/model.
+pub struct ShadowProvider {
+    primary: Box<dyn Provider>,
+    primary_name: String,
+    shadow: Arc<dyn Provider>,
+    shadow_name: String,
+    shadow_model: String,
+    sample_percent: f64,
+    log_path: PathBuf,
+}
+
+impl ShadowProvider {
+    pub fn new(
+        primary: Box<dyn Provider>,
+        primary_name: &str,
+        shadow: Arc<dyn Provider>,
+        shadow_name: &str,
+        shadow_model: &str,
+        sample_percent: f64,
+        workspace_dir: &Path,
+    ) -> Self {
+        Self {
+            primary,
+            primary_name: primary_name.to_string(),
+            shadow,
+            shadow_name: shadow_name.to_string(),
+            shadow_model: shadow_model.to_string(),
+            sample_percent: sample_percent.clamp(0.0, 100.0),
+            log_path: shadow_log_path(workspace_dir),
+        }
+    }
+
+    fn should_sample(&self) -> bool {
+        self.sample_percent >= 100.0
+            || (self.sample_percent > 0.0 && rand::random::<f64>() * 100.0 < self.sample_percent)
+    }
+
+    /// Start the shadow call right away so it runs alongside the primary.
+    fn mirror(&self, request: ShadowRequest, temperature: f64) -> JoinHandle<ShadowSide> {
+        let shadow = Arc::clone(&self.shadow);
+        let name = self.shadow_name.clone();
+        let model = self.shadow_model.clone();
+        tokio::spawn(async move {
+            let started = Instant::now();
+            let result = request.send(shadow.as_ref(), &model, temperature).await;
+            ShadowSide::new(&name, &model, result.as_ref(), started.elapsed())
+        })
+    }
+
+    /// Wait for the shadow side off the request path, then append the record.
+    fn record(&self, prompt: String, primary: ShadowSide, pending: JoinHandle<ShadowSide>) {
+        let path = self.log_path.clone();
+        tokio::spawn(async move {
+            let shadow = match pending.await {
+                Ok(side) => side,
+                Err(e) => {
+                    tracing::warn!("Shadow call task failed: {e}");
+                    return;
+                }
+            };
+            let record = ShadowRecord {
+                timestamp: Utc::now(),
+                prompt,
+                primary,
+                shadow,
+            };
+            if let Err(e) = append_record(&path, &record) {
+                tracing::warn!("Failed to write shadow record: {e}");
+            }
+        });
+    }
+
+    async fn run_sampled(
+        &self,
+        request: ShadowRequest,
+        model: &str,
+        temperature: f64,
+        primary: impl std::future::Future<Output = Result<ChatResponse>>,
+    ) -> Result<ChatResponse> {
+        let prompt = request.prompt();
+        let pending = self.mirror(request, temperature);
+        let started = Instant::now();
+        let result = primary.await;
+        let side = ShadowSide::new(
+            &self.primary_name,
+            model,
+            result.as_ref(),
+            started.elapsed(),
+        );
+        self.record(prompt, side, pending);
+        result
+    }
+}
+
+#[async_trait]
+impl Provider for ShadowProvider {
+    fn capabilities(&self) -> super::traits::ProviderCapabilities {
+        self.primary.capabilities()
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> Result<String> {
+        if !self.should_sample() {
+            return self
+                .primary
+                .chat_with_system(system_prompt, message, model, temperature)
+                .await;
+        }
+        let request = ShadowRequest::System {
+            system_prompt: system_prompt.map(str::to_string),
+            message: message.to_string(),
+        };
+        let primary = async {
+            self.primary
+                .chat_with_system(system_prompt, message, model, temperature)
+                .await
+                .map(text_response)
+        };
+        let response = self
+            .run_sampled(request, model, temperature, primary)
+            .await?;
+        Ok(response.text.unwrap_or_default())
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> Result<String> {
+        if !self.should_sample() {
+            return self
+                .primary
+                .chat_with_history(messages, model, temperature)
+                .await;
+        }
+        let request = ShadowRequest::History(messages.to_vec());
+        let primary = async {
+            self.primary
+                .chat_with_history(messages, model, temperature)
+                .await
+                .map(text_response)
+        };
+        let response = self
+            .run_sampled(request, model, temperature, primary)
+            .await?;
+        Ok(response.text.unwrap_or_default())
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> Result<ChatResponse> {
+        if !self.should_sample() {
+            return self.primary.chat(request, model, temperature).await;
+        }
+        let mirrored = ShadowRequest::Chat {
+            messages: request.messages.to_vec(),
+            tools: request.tools.map(<[ToolSpec]>::to_vec),
+        };
+        let primary = self.primary.chat(request, model, temperature);
+        self.run_sampled(mirrored, model, temperature, primary)
+            .await
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> Result<ChatResponse> {
+        if !self.should_sample() {
+            return self
+                .primary
+                .chat_with_tools(messages, tools, model, temperature)
+                .await;
+        }
+        let request = ShadowRequest::Tools {
+            messages: messages.to_vec(),
+            tools: tools.to_vec(),
+        };
+        let primary = self
+            .primary
+            .chat_with_tools(messages, tools, model, temperature);
+        self.run_sampled(request, model, temperature, primary).await
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.primary.supports_native_tools()
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.primary.supports_vision()
+    }
+
+    fn caches_tool_schemas(&self) -> bool {
+        self.primary.caches_tool_schemas()
+    }
+
+    async fn warmup(&self) -> Result<()> {
+        if let Err(e) = self.shadow.warmup().await {
+            tracing::warn!(
+                provider = self.shadow_name,
+                "Shadow warmup failed (non-fatal): {e}"
+            );
+        }
+        self.primary.warmup().await
+    }
+}
+
+/// Wrap `primary` in a [`ShadowProvider`] when `[shadow]` is enabled.
+///
+/// Shadow mode must never break the live path: a misconfigured or
+/// uncreatable shadow provider is logged and the primary is returned as-is.
+pub fn wrap_with_shadow(
+    primary: Box<dyn Provider>,
+    primary_name: &str,
+    config: &Config,
+    options: &ProviderRuntimeOptions,
+) -> Box<dyn Provider> {
+    let shadow = &config.shadow;
+    if !shadow.enabled {
+        return primary;
+    }
+    let shadow_name = shadow.provider.trim();
+    let shadow_model = shadow.model.trim();
+    if shadow_name.is_empty() || shadow_model.is_empty() {
+        tracing::warn!("[shadow] is enabled but provider or model is empty; shadow mode disabled");
+        return primary;
+    }
+
+    // Same provider (A/A across models) reuses the configured key and URL;
+    // anything else resolves its own credential like a fallback provider.
+    let created = if shadow_name == primary_name {
+        super::create_provider_with_url_and_options(
+            shadow_name,
+            config.api_key.as_deref(),
+            config.api_url.as_deref(),
+            options,
+        )
+    } else {
+        let mut shadow_options = options.clone();
+        shadow_options.provider_api_url = None;
+        super::create_provider_with_options(shadow_name, None, &shadow_options)
+    };
+    match created {
+        Ok(provider) => {
+            tracing::info!(
+                provider = shadow_name,
+                model = shadow_model,
+                sample_percent = shadow.sample_percent,
+                "Shadow mode enabled"
+            );
+            Box::new(ShadowProvider::new(
+                primary,
+                primary_name,
+                Arc::from(provider),
+                shadow_name,
+                shadow_model,
+                shadow.sample_percent,
+                &config.workspace_dir,
+            ))
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Failed to create shadow provider {shadow_name}: {e}; shadow mode disabled"
+            );
+            primary
+        }
+    }
+}
+
+pub fn shadow_log_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(SHADOW_LOG_FILE)
+}
+
+fn append_record(path: &Path, record: &ShadowRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+/// Load all shadow records, skipping lines that no longer parse.
+pub fn load_records(workspace_dir: &Path) -> Result<Vec<ShadowRecord>> {
+    let path = shadow_log_path(workspace_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = std::fs::read_to_string(&path)?;
+    Ok(raw
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Aggregate comparison over a set of shadow records.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ShadowSummary {
+    pub samples: usize,
+    pub primary_errors: usize,
+    pub shadow_errors: usize,
+    pub primary_cost_usd: f64,
+    pub shadow_cost_usd: f64,
+    pub primary_avg_latency_ms: u64,
+    pub shadow_avg_latency_ms: u64,
+    /// Samples where both sides succeeded and requested the same tools.
+    pub tool_agreement: usize,
+    /// Samples where both sides succeeded.
+    pub comparable: usize,
+}
+
+pub fn summarize(records: &[ShadowRecord]) -> ShadowSummary {
+    let mut summary = ShadowSummary {
+        samples: records.len(),
+        ..ShadowSummary::default()
+    };
+    if records.is_empty() {
+        return summary;
+    }
+    let (mut primary_latency, mut shadow_latency) = (0u64, 0u64);
+    for record in records {
+        summary.primary_cost_usd += record.primary.cost_usd.unwrap_or(0.0);
+        summary.shadow_cost_usd += record.shadow.cost_usd.unwrap_or(0.0);
+        primary_latency = primary_latency.saturating_add(record.primary.latency_ms);
+        shadow_latency = shadow_latency.saturating_add(record.shadow.latency_ms);
+        match (&record.primary.error, &record.shadow.error) {
+            (None, None) => {
+                summary.comparable += 1;
+                if record.primary.tool_calls == record.shadow.tool_calls {
+                    summary.tool_agreement += 1;
+                }
+            }
+            (primary, shadow) => {
+                summary.primary_errors += usize::from(primary.is_some());
+                summary.shadow_errors += usize::from(shadow.is_some());
+            }
+        }
+    }
+    let count = records.len() as u64;
+    summary.primary_avg_latency_ms = primary_latency / count;
+    summary.shadow_avg_latency_ms = shadow_latency / count;
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::traits::TokenUsage;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FixedProvider {
+        reply: &'static str,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Provider for FixedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(self.reply.to_string())
+        }
+    }
+
+    fn shadowed(
+        workspace: &Path,
+        sample_percent: f64,
+    ) -> (ShadowProvider, Arc<AtomicUsize>, Arc<AtomicUsize>) {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let shadow_calls = Arc::new(AtomicUsize::new(0));
+        let provider = ShadowProvider::new(
+            Box::new(FixedProvider {
+                reply: "primary answer",
+                calls: Arc::clone(&primary_calls),
+            }),
+            "primary",
+            Arc::new(FixedProvider {
+                reply: "shadow answer",
+                calls: Arc::clone(&shadow_calls),
+            }),
+            "candidate",
+            "candidate-model",
+            sample_percent,
+            workspace,
+        );
+        (provider, primary_calls, shadow_calls)
+    }
+
+    fn side(error: Option<&str>, tools: &[&str], cost: f64, latency_ms: u64) -> ShadowSide {
+        ShadowSide {
+            provider: "p".into(),
+            model: "m".into(),
+            text: error.is_none().then(|| "ok".to_string()),
+            tool_calls: tools.iter().map(|t| (*t).to_string()).collect(),
+            input_tokens: None,
+            output_tokens: None,
+            cost_usd: Some(cost),
+            latency_ms,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn sampled_call_returns_primary_and_records_both_sides() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (provider, primary_calls, shadow_calls) = shadowed(tmp.path(), 100.0);
+
+        let reply = provider
+            .chat_with_history(&[ChatMessage::user("hello there")], "primary-model", 0.2)
+            .await
+            .unwrap();
+        assert_eq!(reply, "primary answer");
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+
+        let mut records = Vec::new();
+        for _ in 0..50 {
+            records = load_records(tmp.path()).unwrap();
+            if !records.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(shadow_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.prompt, "hello there");
+        assert_eq!(record.primary.model, "primary-model");
+        assert_eq!(record.primary.text.as_deref(), Some("primary answer"));
+        assert_eq!(record.shadow.provider, "candidate");
+        assert_eq!(record.shadow.model, "candidate-model");
+        assert_eq!(record.shadow.text.as_deref(), Some("shadow answer"));
+    }
+
+    #[tokio::test]
+    async fn zero_sample_rate_never_calls_shadow() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (provider, primary_calls, shadow_calls) = shadowed(tmp.path(), 0.0);
+
+        for _ in 0..5 {
+            provider
+                .chat_with_system(None, "hi", "primary-model", 0.2)
+                .await
+                .unwrap();
+        }
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 5);
+        assert_eq!(shadow_calls.load(Ordering::SeqCst), 0);
+        assert!(!shadow_log_path(tmp.path()).exists());
+    }
+
+    #[test]
+    fn shadow_side_redacts_text_and_keeps_usage() {
+        let response = ChatResponse {
+            text: Some("token sk-abcdefghijklmnopqrstuvwxyz1234".into()),
+            tool_calls: Vec::new(),
+            usage: Some(TokenUsage {
+                input_tokens: Some(120),
+                output_tokens: Some(30),
+                cached_input_tokens: None,
+            }),
+        };
+        let side = ShadowSide::new("p", "m", Ok(&response), Duration::from_millis(42));
+        assert!(!side
+            .text
+            .unwrap()
+            .contains("abcdefghijklmnopqrstuvwxyz1234"));
+        assert_eq!(side.input_tokens, Some(120));
+        assert_eq!(side.output_tokens, Some(30));
+        assert_eq!(side.latency_ms, 42);
+    }
+
+    #[test]
+    fn summarize_totals_costs_errors_and_tool_agreement() {
+        let record = |primary, shadow| ShadowRecord {
+            timestamp: Utc::now(),
+            prompt: "q".into(),
+            primary,
+            shadow,
+        };
+        let records = vec![
+            record(
+                side(None, &["shell"], 0.02, 100),
+                side(None, &["shell"], 0.01, 300),
+            ),
+            record(
+                side(None, &[], 0.02, 200),
+                side(None, &["browser"], 0.01, 100),
+            ),
+            record(
+                side(None, &[], 0.02, 300),
+                side(Some("timeout"), &[], 0.0, 200),
+            ),
+        ];
+        let summary = summarize(&records);
+        assert_eq!(summary.samples, 3);
+        assert_eq!(summary.comparable, 2);
+        assert_eq!(summary.tool_agreement, 1);
+        assert_eq!(summary.primary_errors, 0);
+        assert_eq!(summary.shadow_errors, 1);
+        assert!((summary.primary_cost_usd - 0.06).abs() < 1e-9);
+        assert!((summary.shadow_cost_usd - 0.02).abs() < 1e-9);
+        assert_eq!(summary.primary_avg_latency_ms, 200);
+        assert_eq!(summary.shadow_avg_latency_ms, 200);
+    }
+}
@@ -54,6 +54,8 @@ pub struct ToolCall {
 pub struct TokenUsage {
     pub input_tokens: Option<u64>,
     pub output_tokens: Option<u64>,
+    /// Portion of `input_tokens` served from the provider's prompt cache.
+    pub cached_input_tokens: Option<u64>,
 }
 
 /// An LLM response that may contain text, tool calls, or both.
@@ -108,6 +110,19 @@ pub enum ConversationMessage {
     ToolResults(Vec<ToolResultMessage>),
 }
 
+/// A fragment of a native tool call in a streaming response.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolCallDelta {
+    /// Position of the call in the response; fragments with the same index belong together.
+    pub index: usize,
+    /// Call id, usually sent with the first fragment only.
+    pub id: Option<String>,
+    /// Tool name, usually sent with the first fragment only.
+    pub name: Option<String>,
+    /// Next piece of the JSON arguments.
+    pub arguments: String,
+}
+
 /// A chunk of content from a streaming response.
 #[derive(Debug, Clone)]
 pub struct StreamChunk {
@@ -117,6 +132,10 @@ pub struct StreamChunk {
     pub is_final: bool,
     /// Approximate token count for this chunk (estimated).
     pub token_count: usize,
+    /// Tool-call fragment carried by this chunk, if any.
+    pub tool_call: Option<ToolCallDelta>,
+    /// Usage reported for the whole response, usually on the last chunk.
+    pub usage: Option<TokenUsage>,
 }
 
 impl StreamChunk {
@@ -126,27 +145,41 @@ impl StreamChunk {
             delta: text.into(),
             is_final: false,
             token_count: 0,
+            tool_call: None,
+            usage: None,
+        }
+    }
+
+    /// Create a non-final chunk carrying a tool-call fragment.
+    pub fn tool_call(delta: ToolCallDelta) -> Self {
+        Self {
+            tool_call: Some(delta),
+            ..Self::delta("")
         }
     }
 
     /// Create a final chunk.
     pub fn final_chunk() -> Self {
         Self {
-            delta: String::new(),
             is_final: true,
-            token_count: 0,
+            ..Self::delta("")
         }
     }
 
     /// Create an error chunk.
     pub fn error(message: impl Into<String>) -> Self {
         Self {
-            delta: message.into(),
             is_final: true,
-            token_count: 0,
+            ..Self::delta(message)
         }
     }
 
+    /// Attach response usage.
+    pub fn with_usage(mut self, usage: TokenUsage) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
     /// Estimate tokens (rough approximation: ~4 chars per token).
     pub fn with_token_estimate(mut self) -> Self {
         self.token_count = self.delta.len().div_ceil(4);
@@ -378,6 +411,12 @@ pub trait Provider: Send + Sync {
         self.capabilities().vision
     }
 
+    /// Whether the provider caches tool definitions across requests, so a
+    /// stable tool list is cheaper than a per-turn selection.
+    fn caches_tool_schemas(&self) -> bool {
+        false
+    }
+
     /// Warm up the HTTP connection pool (TLS handshake, DNS, HTTP/2 setup).
     /// Default implementation is a no-op; providers with HTTP clients should override.
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -423,6 +462,29 @@ pub trait Provider: Send + Sync {
         stream::empty().boxed()
     }
 
+    /// Whether [`Provider::stream_chat`] streams native tool calls.
+    /// Default implementation returns false.
+    fn supports_tool_streaming(&self) -> bool {
+        false
+    }
+
+    /// Streaming chat with native tools: text deltas, tool-call fragments
+    /// and, when the provider reports it, usage on the last chunk.
+    /// Default implementation yields an error so callers fall back to `chat`.
+    fn stream_chat(
+        &self,
+        _request: ChatRequest<'_>,
+        _model: &str,
+        _temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        stream::once(async {
+            Err(StreamError::Provider(
+                "tool-call streaming is not supported".to_string(),
+            ))
+        })
+        .boxed()
+    }
+
     /// Streaming chat with history.
     /// Default implementation falls back to stream_chat_with_system with last user message.
     fn stream_chat_with_history(
@@ -555,6 +617,7 @@ mod tests {
             usage: Some(TokenUsage {
                 input_tokens: Some(100),
                 output_tokens: Some(50),
+                cached_input_tokens: None,
             }),
         };
         assert_eq!(resp.usage.as_ref().unwrap().input_tokens, Some(100));
@@ -0,0 +1,434 @@
+//! Agent-initiated question queue ("ask me later").
+//!
+//! Unattended runs (heartbeat, cron) that need owner input call the
+//! `ask_user` tool instead of guessing. The question, together with the task
+//! context needed to pick the work back up, is persisted in
+//! `<workspace>/state/questions.json` (encrypted and capped per `[queues]`)
+//! and sent to the owner's channel. The owner answers with `/answer` in a
+//! channel or `zeroclaw questions answer`, and the daemon's question worker
+//! resumes the task with the answer.
+
+use crate::config::Config;
+use crate::contacts::ContactBook;
+use crate::cron::scheduler::{announce_channels, send_announcement};
+use crate::i18n;
+use crate::queue_store::{QueueEntry, QueuePolicy, QueueStore};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::Path;
+
+const QUESTIONS_FILE: &str = "questions.json";
+/// Prefix of channel messages that answer a queued question.
+pub const ANSWER_COMMAND: &str = "/answer";
+
+/// Serializes read-modify-write cycles on the queue file within the process.
+static QUEUE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionStatus {
+    /// Waiting for the owner.
+    Pending,
+    /// Answered; the daemon has not resumed the task yet.
+    Answered,
+    /// The task was resumed with the answer.
+    Resumed,
+    Cancelled,
+}
+
+/// A question the agent is waiting on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Question {
+    pub id: String,
+    pub asked_at: DateTime<Utc>,
+    pub question: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    /// What the suspended task was doing and how to use the answer.
+    pub context: String,
+    /// Channel the question was sent to; answers must come from it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    pub status: QuestionStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answered_at: Option<DateTime<Utc>>,
+}
+
+impl Question {
+    /// Prompt that resumes the suspended task with the owner's answer.
+    pub fn resume_prompt(&self) -> String {
+        format!(
+            "[Resumed Task] You paused this task to ask the owner a question; the answer has arrived.\n\
+            Task context: {}\n\
+            Question ({}): {}\n\
+            Answer: {}\n\
+            Continue the task using this answer.",
+            self.context,
+            self.id,
+            self.question,
+            self.answer.as_deref().unwrap_or_default()
+        )
+    }
+
+    /// Message sent to the owner.
+    pub fn notification(&self) -> String {
+        let mut question = self.question.clone();
+        for (index, option) in self.options.iter().enumerate() {
+            let _ = write!(question, "\n{}. {option}", index + 1);
+        }
+        i18n::tr_args(
+            "channel.question_asked",
+            &[("id", &self.id), ("question", &question)],
+        )
+    }
+}
+
+impl QueueEntry for Question {
+    fn enqueued_at(&self) -> DateTime<Utc> {
+        self.asked_at
+    }
+}
+
+/// Workspace-backed question queue.
+pub struct QuestionQueue {
+    store: QueueStore,
+}
+
+impl QuestionQueue {
+    /// Queue with `[queues]` encryption and caps applied.
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(&config.workspace_dir, QueuePolicy::from_config(config))
+    }
+
+    pub fn new(workspace_dir: &Path, policy: QueuePolicy) -> Self {
+        Self {
+            store: QueueStore::new(
+                "questions",
+                workspace_dir.join("state").join(QUESTIONS_FILE),
+                policy,
+            ),
+        }
+    }
+
+    /// Plaintext queue with the default caps.
+    pub fn for_workspace(workspace_dir: &Path) -> Self {
+        Self::new(workspace_dir, QueuePolicy::default())
+    }
+
+    pub fn list(&self) -> Result<Vec<Question>> {
+        self.store.load()
+    }
+
+    fn update<T>(&self, f: impl FnOnce(&mut Vec<Question>) -> Result<T>) -> Result<T> {
+        let _guard = QUEUE_LOCK.lock();
+        let mut questions = self.list()?;
+        let result = f(&mut questions)?;
+        self.store.save(&mut questions)?;
+        Ok(result)
+    }
+
+    /// Queue a new question.
+    pub fn ask(
+        &self,
+        question: &str,
+        options: Vec<String>,
+        context: &str,
+        route: Option<(String, String)>,
+    ) -> Result<Question> {
+        let question = question.trim();
+        if question.is_empty() {
+            bail!("Question cannot be empty");
+        }
+        let (channel, to) = route.unzip();
+        self.update(|questions| {
+            let id = loop {
+                let candidate = format!("q{}", &uuid::Uuid::new_v4().simple().to_string()[..6]);
+                if !questions.iter().any(|q| q.id == candidate) {
+                    break candidate;
+                }
+            };
+            let entry = Question {
+                id,
+                asked_at: Utc::now(),
+                question: question.to_string(),
+                options,
+                context: context.trim().to_string(),
+                channel,
+                to,
+                status: QuestionStatus::Pending,
+                answer: None,
+                answered_at: None,
+            };
+            questions.push(entry.clone());
+            Ok(entry)
+        })
+    }
+
+    /// Record an answer for `id`, or for the only pending question when
+    /// `id` is `None`. `channel` is the channel the answer came from (`None`
+    /// for the CLI, which may answer anything).
+    pub fn answer(&self, id: Option<&str>, text: &str, channel: Option<&str>) -> Result<Question> {
+        let text = text.trim();
+        if text.is_empty() {
+            bail!("Answer cannot be empty");
+        }
+        self.update(|questions| {
+            let mut pending = questions
+                .iter_mut()
+                .filter(|q| q.status == QuestionStatus::Pending)
+                .filter(|q| id.is_none_or(|id| q.id.eq_ignore_ascii_case(id)));
+            let question = match (pending.next(), pending.next(), id) {
+                (Some(question), None, _) | (Some(question), Some(_), Some(_)) => question,
+                (None, _, Some(id)) => bail!("No pending question with id '{id}'"),
+                (None, _, None) => bail!("There are no pending questions"),
+                (Some(_), Some(_), None) => {
+                    bail!("Several questions are pending; include the question id")
+                }
+            };
+            if let (Some(from), Some(asked_on)) = (channel, question.channel.as_deref()) {
+                if !from.eq_ignore_ascii_case(asked_on) {
+                    bail!(
+                        "Question {} was asked on {asked_on}; answer it there",
+                        question.id
+                    );
+                }
+            }
+            question.answer = Some(resolve_option(&question.options, text));
+            question.answered_at = Some(Utc::now());
+            question.status = QuestionStatus::Answered;
+            Ok(question.clone())
+        })
+    }
+
+    /// Handle the text after `/answer`: an optional question id followed by
+    /// the answer.
+    pub fn answer_command(&self, args: &str, channel: Option<&str>) -> Result<Question> {
+        let args = args.trim();
+        let (first, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+        let names_question = self
+            .list()?
+            .iter()
+            .any(|q| q.status == QuestionStatus::Pending && q.id.eq_ignore_ascii_case(first));
+        if names_question {
+            self.answer(Some(first), rest, channel)
+        } else {
+            self.answer(None, args, channel)
+        }
+    }
+
+    /// Send pending questions to a new channel and recipient, e.g. after the
+    /// owner hands the conversation off to another surface. Returns the
+    /// questions that moved.
+    pub fn reroute_pending(&self, channel: &str, to: &str) -> Result<Vec<Question>> {
+        self.update(|questions| {
+            let mut moved = Vec::new();
+            for question in questions
+                .iter_mut()
+                .filter(|q| q.status == QuestionStatus::Pending)
+            {
+                if question.channel.as_deref() == Some(channel)
+                    && question.to.as_deref() == Some(to)
+                {
+                    continue;
+                }
+                question.channel = Some(channel.to_string());
+                question.to = Some(to.to_string());
+                moved.push(question.clone());
+            }
+            Ok(moved)
+        })
+    }
+
+    pub fn cancel(&self, id: &str) -> Result<()> {
+        self.update(|questions| {
+            let question = questions
+                .iter_mut()
+                .find(|q| q.id.eq_ignore_ascii_case(id) && q.status == QuestionStatus::Pending)
+                .ok_or_else(|| anyhow::anyhow!("No pending question with id '{id}'"))?;
+            question.status = QuestionStatus::Cancelled;
+            Ok(())
+        })
+    }
+
+    /// Claim answered questions for resumption, marking them resumed.
+    pub fn take_answered(&self) -> Result<Vec<Question>> {
+        self.update(|questions| {
+            let mut claimed = Vec::new();
+            for question in questions
+                .iter_mut()
+                .filter(|q| q.status == QuestionStatus::Answered)
+            {
+                question.status = QuestionStatus::Resumed;
+                claimed.push(question.clone());
+            }
+            Ok(claimed)
+        })
+    }
+}
+
+/// Map a numeric reply ("2") to the matching option text.
+fn resolve_option(options: &[String], text: &str) -> String {
+    text.parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|index| options.get(index))
+        .cloned()
+        .unwrap_or_else(|| text.to_string())
+}
+
+/// Return the arguments of an `/answer` (or `/answer@bot`) message.
+pub fn parse_answer_command(text: &str) -> Option<&str> {
+    let rest = text.trim_start().strip_prefix(ANSWER_COMMAND)?;
+    let rest = match rest.strip_prefix('@') {
+        Some(mention) => mention
+            .split_once(char::is_whitespace)
+            .map_or("", |(_, args)| args),
+        None if rest.is_empty() || rest.starts_with(char::is_whitespace) => rest,
+        None => return None,
+    };
+    Some(rest.trim())
+}
+
+/// Channel and recipient that receive questions, from `[questions]`.
+pub fn owner_route(config: &Config) -> Result<(String, String)> {
+    let questions = &config.questions;
+    if let (Some(channel), Some(to)) = (&questions.channel, &questions.to) {
+        return Ok((channel.to_ascii_lowercase(), to.clone()));
+    }
+    let Some(owner) = questions.owner_contact.as_deref() else {
+        bail!("Set [questions].channel and .to, or [questions].owner_contact");
+    };
+    let contact = ContactBook::for_workspace(&config.workspace_dir)
+        .find(owner)?
+        .ok_or_else(|| anyhow::anyhow!("Owner contact '{owner}' is not in the contact book"))?;
+    contact.route(None, &announce_channels(config))
+}
+
+/// Send a queued question to the channel it was routed to.
+pub async fn notify(config: &Config, question: &Question) -> Result<()> {
+    let (Some(channel), Some(to)) = (&question.channel, &question.to) else {
+        bail!("Question {} has no delivery channel", question.id);
+    };
+    send_announcement(config, channel, to, &question.notification()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn route() -> Option<(String, String)> {
+        Some(("telegram".to_string(), "42".to_string()))
+    }
+
+    #[test]
+    fn answered_questions_are_claimed_once() {
+        let tmp = TempDir::new().unwrap();
+        let queue = QuestionQueue::for_workspace(tmp.path());
+        let asked = queue
+            .ask(
+                "Which region?",
+                vec!["eu".into(), "us".into()],
+                "Deploying release 1.2",
+                route(),
+            )
+            .unwrap();
+        assert!(queue.take_answered().unwrap().is_empty());
+
+        let answered = queue
+            .answer_command(&format!("{} 2", asked.id.to_uppercase()), Some("telegram"))
+            .unwrap();
+        assert_eq!(answered.answer.as_deref(), Some("us"));
+
+        let claimed = queue.take_answered().unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert!(claimed[0].resume_prompt().contains("Deploying release 1.2"));
+        assert!(claimed[0].resume_prompt().contains("Answer: us"));
+        assert!(queue.take_answered().unwrap().is_empty());
+        assert_eq!(queue.list().unwrap()[0].status, QuestionStatus::Resumed);
+    }
+
+    #[test]
+    fn answers_without_id_need_a_single_pending_question() {
+        let tmp = TempDir::new().unwrap();
+        let queue = QuestionQueue::for_workspace(tmp.path());
+        assert!(queue.answer_command("yes", None).is_err());
+
+        let first = queue.ask("Proceed?", Vec::new(), "ctx", route()).unwrap();
+        queue
+            .ask("Delete logs?", Vec::new(), "ctx", route())
+            .unwrap();
+        let err = queue.answer_command("yes", None).unwrap_err().to_string();
+        assert!(err.contains("include the question id"), "{err}");
+
+        queue.cancel(&first.id).unwrap();
+        let answered = queue.answer_command("yes please", None).unwrap();
+        assert_eq!(answered.question, "Delete logs?");
+        assert_eq!(answered.answer.as_deref(), Some("yes please"));
+    }
+
+    #[test]
+    fn answers_must_come_from_the_asking_channel() {
+        let tmp = TempDir::new().unwrap();
+        let queue = QuestionQueue::for_workspace(tmp.path());
+        let asked = queue.ask("Proceed?", Vec::new(), "ctx", route()).unwrap();
+        assert!(queue
+            .answer(Some(&asked.id), "yes", Some("discord"))
+            .is_err());
+        assert!(queue.answer(Some(&asked.id), "yes", None).is_ok());
+    }
+
+    #[test]
+    fn rerouted_questions_are_answered_from_the_new_channel() {
+        let tmp = TempDir::new().unwrap();
+        let queue = QuestionQueue::for_workspace(tmp.path());
+        let asked = queue.ask("Proceed?", Vec::new(), "ctx", route()).unwrap();
+        let moved = queue.reroute_pending("discord", "chan-1").unwrap();
+        assert_eq!(moved.len(), 1);
+        assert!(queue
+            .reroute_pending("discord", "chan-1")
+            .unwrap()
+            .is_empty());
+        assert!(queue
+            .answer(Some(&asked.id), "yes", Some("telegram"))
+            .is_err());
+        assert!(queue
+            .answer(Some(&asked.id), "yes", Some("discord"))
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_answer_command_accepts_bot_mentions() {
+        assert_eq!(
+            parse_answer_command("/answer q12ab3 yes"),
+            Some("q12ab3 yes")
+        );
+        assert_eq!(parse_answer_command("/answer@zc_bot  no"), Some("no"));
+        assert_eq!(parse_answer_command("/answer"), Some(""));
+        assert_eq!(parse_answer_command("/answers"), None);
+        assert_eq!(parse_answer_command("answer yes"), None);
+    }
+
+    #[test]
+    fn owner_route_prefers_explicit_channel() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        assert!(owner_route(&config).is_err());
+        config.questions.channel = Some("Telegram".into());
+        config.questions.to = Some("42".into());
+        assert_eq!(
+            owner_route(&config).unwrap(),
+            ("telegram".to_string(), "42".to_string())
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Durable on-disk queues (`[queues]`).
+//!
+//! Queues that must survive a restart (the `ask_user` question queue and the
+//! `[inbox]` triage log) persist through [`QueueStore`]. The JSON payload is
+//! encrypted with the [`SecretStore`] key when `[queues] encrypt` is set, and
+//! every write evicts the oldest entries until the queue fits `max_age_days`,
+//! `max_entries` and `max_bytes`. Depth and eviction counts are kept per queue and exported on
+//! the Prometheus `/metrics` endpoint.
+
+use crate::config::{Config, QueuesConfig};
+use crate::security::SecretStore;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+static QUEUE_STATS: LazyLock<Mutex<BTreeMap<String, QueueStats>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// An entry that can be evicted by age.
+pub trait QueueEntry {
+    fn enqueued_at(&self) -> DateTime<Utc>;
+}
+
+/// Per-queue counters since process start.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    /// Entries after the last write.
+    pub depth: u64,
+    /// Entries evicted by the caps.
+    pub evicted: u64,
+}
+
+/// Snapshot of every queue written by this process, keyed by queue name.
+pub fn queue_stats() -> BTreeMap<String, QueueStats> {
+    QUEUE_STATS.lock().clone()
+}
+
+/// Encryption and cap settings shared by all durable queues.
+#[derive(Debug, Clone)]
+pub struct QueuePolicy {
+    secrets: SecretStore,
+    limits: QueuesConfig,
+}
+
+impl QueuePolicy {
+    pub fn from_config(config: &Config) -> Self {
+        let key_dir = config.config_path.parent().unwrap_or(&config.workspace_dir);
+        Self::new(key_dir, config.queues.clone())
+    }
+
+    /// Policy with the secret key stored in `key_dir`.
+    pub fn new(key_dir: &Path, limits: QueuesConfig) -> Self {
+        Self {
+            secrets: SecretStore::new(key_dir, limits.encrypt),
+            limits,
+        }
+    }
+}
+
+impl Default for QueuePolicy {
+    /// Plaintext queue with the default caps.
+    fn default() -> Self {
+        Self::new(Path::new("."), QueuesConfig::default())
+    }
+}
+
+/// A queue persisted as one JSON array file.
+#[derive(Debug, Clone)]
+pub struct QueueStore {
+    name: &'static str,
+    path: PathBuf,
+    policy: QueuePolicy,
+}
+
+impl QueueStore {
+    pub fn new(name: &'static str, path: PathBuf, policy: QueuePolicy) -> Self {
+        Self { name, path, policy }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read all entries. Encrypted files are decrypted regardless of the
+    /// current `encrypt` setting, so the setting can be toggled safely.
+    pub fn load<T: DeserializeOwned>(&self) -> Result<Vec<T>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let raw = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        let json = self
+            .policy
+            .secrets
+            .decrypt(raw.trim())
+            .with_context(|| format!("Failed to decrypt {}", self.path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse {}", self.path.display()))
+    }
+
+    /// Apply the caps to `entries` (oldest first) and write them atomically.
+    /// Returns the number of evicted entries.
+    pub fn save<T: Serialize + QueueEntry>(&self, entries: &mut Vec<T>) -> Result<usize> {
+        let evicted = enforce_limits(entries, &self.policy.limits, Utc::now())?;
+        if evicted > 0 {
+            tracing::warn!(
+                queue = self.name,
+                "Durable queue over its caps; evicted {evicted} oldest entries"
+            );
+        }
+
+        let json = serde_json::to_string_pretty(entries)?;
+        let body = self.policy.secrets.encrypt(&json)?;
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, body)?;
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+
+        let mut stats = QUEUE_STATS.lock();
+        let entry = stats.entry(self.name.to_string()).or_default();
+        entry.depth = entries.len() as u64;
+        entry.evicted += evicted as u64;
+        Ok(evicted)
+    }
+}
+
+/// Drop expired entries, then the oldest ones until the count and the
+/// serialized size fit. Returns how many entries were removed.
+fn enforce_limits<T: Serialize + QueueEntry>(
+    entries: &mut Vec<T>,
+    limits: &QueuesConfig,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let before = entries.len();
+    if limits.max_age_days > 0 {
+        let cutoff = now - chrono::Duration::days(i64::from(limits.max_age_days));
+        entries.retain(|entry| entry.enqueued_at() >= cutoff);
+    }
+    entries.sort_by_key(QueueEntry::enqueued_at);
+
+    let max_entries = limits.max_entries.max(1);
+    if entries.len() > max_entries {
+        entries.drain(..entries.len() - max_entries);
+    }
+
+    // Pretty-printed JSON is within a few bytes of the sum of its entries.
+    let sizes = entries
+        .iter()
+        .map(|entry| serde_json::to_vec_pretty(entry).map(|bytes| bytes.len() as u64 + 4))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut total: u64 = sizes.iter().sum();
+    let mut drop = 0;
+    while total > limits.max_bytes && drop < sizes.len() {
+        total -= sizes[drop];
+        drop += 1;
+    }
+    entries.drain(..drop);
+
+    Ok(before - entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        at: DateTime<Utc>,
+        body: String,
+    }
+
+    impl QueueEntry for Item {
+        fn enqueued_at(&self) -> DateTime<Utc> {
+            self.at
+        }
+    }
+
+    fn items(now: DateTime<Utc>, ages_hours: &[i64]) -> Vec<Item> {
+        ages_hours
+            .iter()
+            .map(|hours| Item {
+                at: now - chrono::Duration::hours(*hours),
+                body: format!("{hours}h"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn evicts_expired_then_oldest_over_count() {
+        let now = Utc::now();
+        let mut queue = items(now, &[1, 2000, 3, 2]);
+        let limits = QueuesConfig {
+            max_entries: 2,
+            max_age_days: 30,
+            ..QueuesConfig::default()
+        };
+        let evicted = enforce_limits(&mut queue, &limits, now).unwrap();
+        assert_eq!(evicted, 2);
+        let bodies: Vec<_> = queue.iter().map(|item| item.body.as_str()).collect();
+        assert_eq!(bodies, ["2h", "1h"]);
+    }
+
+    #[test]
+    fn evicts_oldest_until_under_byte_cap() {
+        let now = Utc::now();
+        let mut queue = items(now, &[3, 2, 1]);
+        let one = serde_json::to_vec_pretty(&queue[0]).unwrap().len() as u64 + 4;
+        let limits = QueuesConfig {
+            max_bytes: one * 2,
+            ..QueuesConfig::default()
+        };
+        assert_eq!(enforce_limits(&mut queue, &limits, now).unwrap(), 1);
+        assert_eq!(queue[0].body, "2h");
+    }
+
+    #[test]
+    fn encrypted_queue_roundtrips_and_hides_payload() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let policy = QueuePolicy::new(
+            tmp.path(),
+            QueuesConfig {
+                encrypt: true,
+                ..QueuesConfig::default()
+            },
+        );
+        let store = QueueStore::new("test", tmp.path().join("queue.json"), policy);
+        let mut queue = vec![Item {
+            at: Utc::now(),
+            body: "secret payload".into(),
+        }];
+        store.save(&mut queue).unwrap();
+
+        let raw = fs::read_to_string(store.path()).unwrap();
+        assert!(raw.starts_with("enc2:"));
+        assert!(!raw.contains("secret payload"));
+        assert_eq!(store.load::<Item>().unwrap(), queue);
+
+        // Turning encryption off still reads the encrypted file.
+        let plain = QueueStore::new(
+            "test",
+            tmp.path().join("queue.json"),
+            QueuePolicy::new(tmp.path(), QueuesConfig::default()),
+        );
+        assert_eq!(plain.load::<Item>().unwrap(), queue);
+        assert_eq!(queue_stats()["test"].depth, 1);
+    }
+}
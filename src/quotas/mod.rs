@@ -0,0 +1,394 @@
+//! Workspace storage quotas and garbage collection (`[quotas]`).
+//!
+//! Files the agent generates are grouped into categories, each with its own
+//! `[quotas.<category>]` retention. A collection pass deletes files older
+//! than `max_age_days`, then the oldest remaining files until the category
+//! fits `max_size_mb`. Append-only telemetry logs are never deleted; they are
+//! trimmed from their oldest lines instead. The daemon runs a pass every
+//! `interval_hours`; `zeroclaw status` and `zeroclaw doctor` report usage.
+
+use crate::config::{Config, RetentionConfig};
+use anyhow::Result;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+/// Screenshots saved by the `screenshot` tool land in the workspace root.
+const SCREENSHOT_PREFIX: &str = "screenshot_";
+
+/// A group of generated files sharing one retention setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Screenshots,
+    Downloads,
+    Exports,
+    Telemetry,
+}
+
+impl Category {
+    pub const ALL: [Self; 4] = [
+        Self::Screenshots,
+        Self::Downloads,
+        Self::Exports,
+        Self::Telemetry,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Screenshots => "screenshots",
+            Self::Downloads => "downloads",
+            Self::Exports => "exports",
+            Self::Telemetry => "telemetry",
+        }
+    }
+
+    pub fn retention(self, config: &Config) -> RetentionConfig {
+        let quotas = &config.quotas;
+        match self {
+            Self::Screenshots => quotas.screenshots,
+            Self::Downloads => quotas.downloads,
+            Self::Exports => quotas.exports,
+            Self::Telemetry => quotas.telemetry,
+        }
+    }
+
+    fn locations(self, config: &Config) -> Vec<Location> {
+        let ws = &config.workspace_dir;
+        match self {
+            Self::Screenshots => vec![
+                Location::Prefixed(ws.clone(), SCREENSHOT_PREFIX),
+                Location::Dir(crate::camera::snapshot_dir(&config.camera, ws)),
+            ],
+            Self::Downloads => vec![
+                Location::Dir(ws.join("telegram_files")),
+                Location::Dir(ws.join("state").join("fetch_cache")),
+            ],
+            Self::Exports => vec![Location::Dir(
+                crate::reports::ReportStore::for_workspace(ws).output_dir(),
+            )],
+            Self::Telemetry => vec![
+                Location::Log(crate::observability::runtime_trace::resolve_trace_path(
+                    &config.observability,
+                    ws,
+                )),
+                Location::Log(crate::providers::shadow::shadow_log_path(ws)),
+            ],
+        }
+    }
+}
+
+/// Where a category's files live.
+enum Location {
+    /// Every file below a directory.
+    Dir(PathBuf),
+    /// Files directly in a directory whose name starts with a prefix.
+    Prefixed(PathBuf, &'static str),
+    /// Append-only log, trimmed rather than deleted.
+    Log(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+struct TrackedFile {
+    path: PathBuf,
+    bytes: u64,
+    modified: SystemTime,
+    log: bool,
+}
+
+/// Disk usage of one category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryUsage {
+    pub category: Category,
+    pub files: u64,
+    pub bytes: u64,
+    pub retention: RetentionConfig,
+}
+
+impl CategoryUsage {
+    pub fn over_quota(&self) -> bool {
+        self.retention.max_size_mb > 0 && self.bytes > self.retention.max_size_mb * BYTES_PER_MB
+    }
+
+    /// `12.3 MB / 512 MB (40 files)`
+    pub fn summary(&self) -> String {
+        let limit = if self.retention.max_size_mb == 0 {
+            "unlimited".to_string()
+        } else {
+            format!("{} MB", self.retention.max_size_mb)
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let used = self.bytes as f64 / BYTES_PER_MB as f64;
+        format!("{used:.1} MB / {limit} ({} files)", self.files)
+    }
+}
+
+/// What one collection pass removed from a category.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GcReport {
+    pub removed_files: u64,
+    pub freed_bytes: u64,
+}
+
+/// Current usage of every category.
+pub fn usage(config: &Config) -> Vec<CategoryUsage> {
+    Category::ALL
+        .into_iter()
+        .map(|category| {
+            let files = scan(&category.locations(config));
+            CategoryUsage {
+                category,
+                files: files.len() as u64,
+                bytes: files.iter().map(|file| file.bytes).sum(),
+                retention: category.retention(config),
+            }
+        })
+        .collect()
+}
+
+/// Run one collection pass over every category.
+pub fn collect(config: &Config, now: SystemTime) -> Vec<(Category, GcReport)> {
+    // Logs under a `[retention]` legal hold are never trimmed.
+    let held = crate::retention::held_paths(config);
+    Category::ALL
+        .into_iter()
+        .map(|category| {
+            let mut files = scan(&category.locations(config));
+            files.retain(|file| !held.contains(&file.path));
+            let report = collect_files(files, category.retention(config), now);
+            (category, report)
+        })
+        .collect()
+}
+
+/// Daemon worker: collect every `interval_hours`.
+pub async fn run_worker(config: Config) -> Result<()> {
+    let hours = u64::from(config.quotas.interval_hours.max(1));
+    let mut interval = tokio::time::interval(Duration::from_secs(hours * 60 * 60));
+
+    loop {
+        interval.tick().await;
+        let worker_config = config.clone();
+        let reports =
+            tokio::task::spawn_blocking(move || collect(&worker_config, SystemTime::now())).await?;
+        for (category, report) in reports {
+            if report.removed_files > 0 {
+                tracing::info!(
+                    category = category.name(),
+                    "workspace gc removed {} files ({} bytes)",
+                    report.removed_files,
+                    report.freed_bytes
+                );
+            }
+        }
+        crate::health::mark_component_ok("workspace_gc");
+    }
+}
+
+fn scan(locations: &[Location]) -> Vec<TrackedFile> {
+    let mut files = Vec::new();
+    for location in locations {
+        match location {
+            Location::Dir(dir) => walk(dir, &mut files),
+            Location::Prefixed(dir, prefix) => {
+                let Ok(entries) = fs::read_dir(dir) else {
+                    continue;
+                };
+                for entry in entries.flatten() {
+                    let name = entry.file_name();
+                    if name.to_string_lossy().starts_with(prefix) {
+                        track(&entry.path(), false, &mut files);
+                    }
+                }
+            }
+            Location::Log(path) => track(path, true, &mut files),
+        }
+    }
+    files
+}
+
+fn walk(dir: &Path, files: &mut Vec<TrackedFile>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(kind) if kind.is_dir() => walk(&path, files),
+            Ok(kind) if kind.is_file() => track(&path, false, files),
+            _ => {}
+        }
+    }
+}
+
+fn track(path: &Path, log: bool, files: &mut Vec<TrackedFile>) {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return;
+    };
+    if !meta.is_file() {
+        return;
+    }
+    files.push(TrackedFile {
+        path: path.to_path_buf(),
+        bytes: meta.len(),
+        modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+        log,
+    });
+}
+
+fn collect_files(
+    mut files: Vec<TrackedFile>,
+    retention: RetentionConfig,
+    now: SystemTime,
+) -> GcReport {
+    let mut report = GcReport::default();
+
+    if retention.max_age_days > 0 {
+        let max_age = Duration::from_secs(u64::from(retention.max_age_days) * 24 * 60 * 60);
+        let cutoff = now.checked_sub(max_age).unwrap_or(SystemTime::UNIX_EPOCH);
+        files.retain(|file| file.log || file.modified >= cutoff || !remove(file, &mut report));
+    }
+
+    if retention.max_size_mb == 0 {
+        return report;
+    }
+    let limit = retention.max_size_mb * BYTES_PER_MB;
+    let mut total: u64 = files.iter().map(|file| file.bytes).sum();
+    files.sort_by_key(|file| file.modified);
+    for file in files.iter().filter(|file| !file.log) {
+        if total <= limit {
+            break;
+        }
+        if remove(file, &mut report) {
+            total -= file.bytes;
+        }
+    }
+
+    // Still over: give each log an equal share of what the files left over.
+    let logs: Vec<_> = files.iter().filter(|file| file.log).collect();
+    if total > limit && !logs.is_empty() {
+        let file_bytes = total - logs.iter().map(|log| log.bytes).sum::<u64>();
+        let share = limit.saturating_sub(file_bytes) / logs.len() as u64;
+        for log in logs.into_iter().filter(|log| log.bytes > share) {
+            match trim_log(&log.path, share) {
+                Ok(freed) => report.freed_bytes += freed,
+                Err(e) => tracing::warn!("workspace gc could not trim {}: {e}", log.path.display()),
+            }
+        }
+    }
+    report
+}
+
+fn remove(file: &TrackedFile, report: &mut GcReport) -> bool {
+    match fs::remove_file(&file.path) {
+        Ok(()) => {
+            report.removed_files += 1;
+            report.freed_bytes += file.bytes;
+            true
+        }
+        Err(e) => {
+            tracing::warn!("workspace gc could not remove {}: {e}", file.path.display());
+            false
+        }
+    }
+}
+
+/// Drop whole lines from the start of `path` until it is at most
+/// `max_bytes`. Returns the number of bytes removed.
+fn trim_log(path: &Path, max_bytes: u64) -> Result<u64> {
+    let before = fs::metadata(path)?.len();
+    let mut skip = before.saturating_sub(max_bytes);
+    let mut reader = BufReader::new(fs::File::open(path)?);
+    let mut line = Vec::new();
+    let mut dropped = 0;
+    while dropped < skip {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line)? as u64;
+        if read == 0 {
+            break;
+        }
+        dropped += read;
+    }
+    skip = dropped;
+
+    let tmp = path.with_extension("gc.tmp");
+    let mut out = fs::File::create(&tmp)?;
+    std::io::copy(&mut reader, &mut out)?;
+    out.flush()?;
+    fs::rename(&tmp, path)?;
+    Ok(skip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, bytes: usize, age_days: u64) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![b'x'; bytes]).unwrap();
+        let modified = SystemTime::now() - Duration::from_secs(age_days * 24 * 60 * 60);
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    fn test_config(tmp: &TempDir) -> Config {
+        Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn expired_then_oldest_files_are_removed() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        config.quotas.screenshots = RetentionConfig::new(30, 1);
+        let ws = tmp.path();
+        write(&ws.join("screenshot_old.png"), 10, 40);
+        write(&ws.join("screenshot_a.png"), 600 * 1024, 3);
+        write(&ws.join("screenshot_b.png"), 600 * 1024, 1);
+        write(&ws.join("notes.png"), 10, 400);
+
+        let reports = collect(&config, SystemTime::now());
+        let (_, screenshots) = &reports[0];
+        assert_eq!(screenshots.removed_files, 2);
+        assert!(!ws.join("screenshot_old.png").exists());
+        assert!(!ws.join("screenshot_a.png").exists());
+        assert!(ws.join("screenshot_b.png").exists());
+        assert!(ws.join("notes.png").exists());
+    }
+
+    #[test]
+    fn usage_reports_each_category() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        write(&tmp.path().join("telegram_files/doc.pdf"), 2048, 0);
+
+        let usage = usage(&config);
+        let downloads = usage
+            .iter()
+            .find(|u| u.category == Category::Downloads)
+            .unwrap();
+        assert_eq!((downloads.files, downloads.bytes), (1, 2048));
+        assert!(!downloads.over_quota());
+        assert_eq!(usage.len(), Category::ALL.len());
+    }
+
+    #[test]
+    fn logs_are_trimmed_from_the_oldest_line() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("trace.jsonl");
+        fs::write(&log, "first line\nsecond line\nthird\n").unwrap();
+
+        let freed = trim_log(&log, 12).unwrap();
+        assert_eq!(fs::read_to_string(&log).unwrap(), "third\n");
+        assert_eq!(freed, 23);
+    }
+}
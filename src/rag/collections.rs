@@ -0,0 +1,455 @@
+//! Named retrieval collections (`[rag.collections.<name>]`).
+//!
+//! Each collection keeps its own SQLite store under `<workspace>/rag/<name>/`,
+//! chunked and embedded with the collection's own settings, so a collection
+//! embedded with one model never shares a vector index with another.
+//!
+//! Reads are gated by identity. A message from `<channel>:<sender>` only
+//! receives excerpts from collections whose `readers` admit that identity:
+//!
+//! - `*`: anyone
+//! - `cli`, `daemon`, `gateway`: the local CLI, scheduled runs, gateway requests
+//! - `<channel>:*`: anyone on a channel, e.g. `slack:*`
+//! - `<channel>:<sender>`: one sender, e.g. `telegram:123456`
+//! - `contact:<name>`: any address of a contact-book entry
+//!
+//! An empty `readers` list means the collection is private to the local CLI.
+
+use crate::config::{Config, RagCollectionConfig};
+use crate::contacts::{Contact, ContactBook};
+use crate::memory::{chunker, Memory, MemoryCategory, MemoryEntry, SqliteMemory};
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Memory category collection chunks are stored under.
+const CHUNK_CATEGORY: &str = "rag";
+const RAG_DIR: &str = "rag";
+const MAX_DOCUMENT_BYTES: u64 = 10 * 1024 * 1024;
+const INGEST_EXTENSIONS: &[&str] = &["md", "markdown", "txt", "rst"];
+
+/// Who is asking: the channel a request arrived on and the sender there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Identity<'a> {
+    pub channel: &'a str,
+    pub sender: &'a str,
+}
+
+impl<'a> Identity<'a> {
+    /// A local surface without a per-user sender (`cli`, `daemon`, `gateway`).
+    pub fn local(surface: &'a str) -> Self {
+        Self {
+            channel: surface,
+            sender: "",
+        }
+    }
+}
+
+/// Whether one `readers` rule admits `identity`.
+fn rule_admits(rule: &str, identity: Identity<'_>, contacts: &[Contact]) -> bool {
+    let rule = rule.trim();
+    if rule == "*" {
+        return true;
+    }
+    if let Some(name) = rule.strip_prefix("contact:") {
+        return contacts
+            .iter()
+            .filter(|contact| contact.name.eq_ignore_ascii_case(name.trim()))
+            .flat_map(|contact| &contact.addresses)
+            .any(|(channel, address)| {
+                channel.eq_ignore_ascii_case(identity.channel) && address == identity.sender
+            });
+    }
+    match rule.split_once(':') {
+        Some((channel, sender)) => {
+            channel.eq_ignore_ascii_case(identity.channel)
+                && !identity.sender.is_empty()
+                && (sender == "*" || sender == identity.sender)
+        }
+        None => identity.sender.is_empty() && rule.eq_ignore_ascii_case(identity.channel),
+    }
+}
+
+/// Whether `readers` admits `identity`; an empty list admits only the CLI.
+pub fn readers_admit(readers: &[String], identity: Identity<'_>, contacts: &[Contact]) -> bool {
+    if readers.is_empty() {
+        return identity == Identity::local("cli");
+    }
+    readers
+        .iter()
+        .any(|rule| rule_admits(rule, identity, contacts))
+}
+
+/// Validate one `readers` rule, returning a description of the problem.
+pub fn validate_reader(rule: &str) -> Option<String> {
+    let rule = rule.trim();
+    if rule.is_empty() {
+        return Some("empty reader".into());
+    }
+    if let Some(name) = rule.strip_prefix("contact:") {
+        return name
+            .trim()
+            .is_empty()
+            .then(|| format!("reader '{rule}' needs a contact name"));
+    }
+    match rule.split_once(':') {
+        Some((channel, sender)) if channel.trim().is_empty() || sender.trim().is_empty() => {
+            Some(format!("reader '{rule}' must be <channel>:<sender>"))
+        }
+        _ => None,
+    }
+}
+
+/// One opened collection.
+pub struct RagCollection {
+    pub name: String,
+    pub settings: RagCollectionConfig,
+    store: SqliteMemory,
+}
+
+impl RagCollection {
+    /// Chunk `content` with this collection's settings and store it as
+    /// document `doc`, replacing chunks from an earlier ingest of `doc`.
+    /// Returns the number of chunks stored.
+    pub async fn ingest(&self, doc: &str, content: &str) -> Result<usize> {
+        self.remove(doc).await?;
+        let category = MemoryCategory::Custom(CHUNK_CATEGORY.to_string());
+        // Chunks hold `Rc` headings; keep only their text across awaits.
+        let chunks: Vec<String> = chunker::chunk_markdown(content, self.settings.chunk_max_tokens)
+            .into_iter()
+            .map(|chunk| chunk.content)
+            .collect();
+        for (index, chunk) in chunks.iter().enumerate() {
+            let content = format!("[{doc}]\n{chunk}");
+            self.store
+                .store(&format!("{doc}#{index}"), &content, category.clone(), None)
+                .await?;
+        }
+        Ok(chunks.len())
+    }
+
+    /// Drop every chunk of document `doc`; returns how many were removed.
+    pub async fn remove(&self, doc: &str) -> Result<usize> {
+        let prefix = format!("{doc}#");
+        let mut removed = 0;
+        for entry in self.chunks().await? {
+            if entry.key.starts_with(&prefix) && self.store.forget(&entry.key).await? {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    async fn chunks(&self) -> Result<Vec<MemoryEntry>> {
+        let category = MemoryCategory::Custom(CHUNK_CATEGORY.to_string());
+        self.store.list(Some(&category), None).await
+    }
+
+    /// Ingested documents with their chunk counts.
+    pub async fn documents(&self) -> Result<BTreeMap<String, usize>> {
+        let mut documents = BTreeMap::new();
+        for entry in self.chunks().await? {
+            if let Some((doc, _)) = entry.key.rsplit_once('#') {
+                *documents.entry(doc.to_string()).or_insert(0) += 1;
+            }
+        }
+        Ok(documents)
+    }
+
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<MemoryEntry>> {
+        self.store.recall(query, limit, None).await
+    }
+}
+
+/// Every configured collection, with identity-gated retrieval.
+pub struct RagCollections {
+    workspace_dir: PathBuf,
+    collections: Vec<RagCollection>,
+    max_context_chunks: usize,
+    min_relevance_score: f64,
+}
+
+impl RagCollections {
+    /// Open every `[rag.collections.*]` store. `None` when none are configured.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        if config.rag.collections.is_empty() {
+            return Ok(None);
+        }
+        let collections = config
+            .rag
+            .collections
+            .iter()
+            .map(|(name, settings)| open_collection(config, name, settings))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(Self {
+            workspace_dir: config.workspace_dir.clone(),
+            collections,
+            max_context_chunks: config.rag.max_context_chunks,
+            min_relevance_score: config.memory.min_relevance_score,
+        }))
+    }
+
+    pub fn all(&self) -> &[RagCollection] {
+        &self.collections
+    }
+
+    pub fn get(&self, name: &str) -> Result<&RagCollection> {
+        self.collections
+            .iter()
+            .find(|collection| collection.name == name)
+            .with_context(|| {
+                format!(
+                    "Unknown RAG collection '{name}' (configured: {})",
+                    self.collections
+                        .iter()
+                        .map(|collection| collection.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+
+    /// Collections `identity` may read.
+    pub fn readable(&self, identity: Identity<'_>) -> Vec<&RagCollection> {
+        let needs_contacts = self.collections.iter().any(|collection| {
+            collection
+                .settings
+                .readers
+                .iter()
+                .any(|rule| rule.trim().starts_with("contact:"))
+        });
+        let contacts = if needs_contacts {
+            ContactBook::for_workspace(&self.workspace_dir)
+                .list()
+                .unwrap_or_else(|e| {
+                    tracing::warn!("RAG readers: contact book unavailable: {e}");
+                    Vec::new()
+                })
+        } else {
+            Vec::new()
+        };
+        self.collections
+            .iter()
+            .filter(|collection| readers_admit(&collection.settings.readers, identity, &contacts))
+            .collect()
+    }
+
+    /// Context block with the best excerpts from every collection
+    /// `identity` may read. Empty when nothing relevant is found.
+    pub async fn context(&self, identity: Identity<'_>, query: &str) -> String {
+        let mut scored = Vec::new();
+        for collection in self.readable(identity) {
+            match collection.search(query, self.max_context_chunks).await {
+                Ok(entries) => scored.extend(
+                    entries
+                        .into_iter()
+                        .filter(|entry| {
+                            entry
+                                .score
+                                .is_none_or(|score| score >= self.min_relevance_score)
+                        })
+                        .map(|entry| (collection.name.as_str(), entry)),
+                ),
+                Err(e) => tracing::warn!(collection = %collection.name, "RAG search failed: {e}"),
+            }
+        }
+        scored.sort_by(|(_, a), (_, b)| {
+            b.score
+                .unwrap_or(0.0)
+                .partial_cmp(&a.score.unwrap_or(0.0))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        if scored.is_empty() {
+            return String::new();
+        }
+        let mut context = String::from("[Document context]\n");
+        for (name, entry) in scored.iter().take(self.max_context_chunks) {
+            let _ = writeln!(context, "- ({name}) {}", entry.content.trim());
+        }
+        context.push('\n');
+        context
+    }
+}
+
+fn open_collection(
+    config: &Config,
+    name: &str,
+    settings: &RagCollectionConfig,
+) -> Result<RagCollection> {
+    let mut memory = config.memory.clone();
+    if let Some(provider) = &settings.embedding_provider {
+        memory.embedding_provider.clone_from(provider);
+    }
+    if let Some(model) = &settings.embedding_model {
+        memory.embedding_model.clone_from(model);
+    }
+    if let Some(dimensions) = settings.embedding_dimensions {
+        memory.embedding_dimensions = dimensions;
+    }
+    let dir = config.workspace_dir.join(RAG_DIR).join(name);
+    let store = crate::memory::create_sqlite_memory_in(
+        &memory,
+        &config.embedding_routes,
+        &dir,
+        config.api_key.as_deref(),
+    )
+    .with_context(|| format!("Failed to open RAG collection '{name}'"))?;
+    Ok(RagCollection {
+        name: name.to_string(),
+        settings: settings.clone(),
+        store,
+    })
+}
+
+/// Text files under `path` (a file or a directory, recursively) that can be
+/// ingested, with the document name each is stored under.
+pub fn ingest_files(path: &Path) -> Result<Vec<(String, PathBuf)>> {
+    if path.is_file() {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .context("Invalid document path")?;
+        return Ok(vec![(name, path.to_path_buf())]);
+    }
+    if !path.is_dir() {
+        bail!("{} does not exist", path.display());
+    }
+    let mut files = Vec::new();
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry_path = entry?.path();
+            if entry_path.is_dir() {
+                pending.push(entry_path);
+                continue;
+            }
+            let supported = entry_path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| INGEST_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+            let small = entry_path
+                .metadata()
+                .is_ok_and(|meta| meta.len() <= MAX_DOCUMENT_BYTES);
+            if supported && small {
+                let name = entry_path
+                    .strip_prefix(path)
+                    .unwrap_or(&entry_path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                files.push((name, entry_path));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn identity<'a>(channel: &'a str, sender: &'a str) -> Identity<'a> {
+        Identity { channel, sender }
+    }
+
+    #[test]
+    fn readers_gate_by_channel_sender_and_contact() {
+        let alice = Contact {
+            name: "Alice".into(),
+            aliases: Vec::new(),
+            addresses: BTreeMap::from([("telegram".to_string(), "42".to_string())]),
+            preferred_channel: None,
+            notes: None,
+            approved: true,
+        };
+        let contacts = [alice];
+        let readers = vec!["slack:*".to_string(), "contact:alice".to_string()];
+
+        assert!(readers_admit(&readers, identity("slack", "U1"), &contacts));
+        assert!(readers_admit(
+            &readers,
+            identity("telegram", "42"),
+            &contacts
+        ));
+        assert!(!readers_admit(
+            &readers,
+            identity("telegram", "43"),
+            &contacts
+        ));
+        assert!(!readers_admit(&readers, Identity::local("cli"), &contacts));
+        assert!(readers_admit(&[], Identity::local("cli"), &contacts));
+        assert!(!readers_admit(&[], identity("telegram", "42"), &contacts));
+        assert!(!readers_admit(
+            &["cli".into()],
+            identity("cli", "x"),
+            &contacts
+        ));
+        assert!(readers_admit(
+            &["*".into()],
+            identity("discord", "7"),
+            &contacts
+        ));
+
+        assert!(validate_reader("telegram:").is_some());
+        assert!(validate_reader("contact:").is_some());
+        assert!(validate_reader("gateway").is_none());
+    }
+
+    #[tokio::test]
+    async fn collections_keep_separate_stores_and_only_serve_their_readers() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.memory.min_relevance_score = 0.0;
+        config.rag.collections.insert(
+            "personal".into(),
+            RagCollectionConfig {
+                chunk_max_tokens: 64,
+                ..RagCollectionConfig::default()
+            },
+        );
+        config.rag.collections.insert(
+            "team".into(),
+            RagCollectionConfig {
+                readers: vec!["slack:*".into()],
+                ..RagCollectionConfig::default()
+            },
+        );
+        let rag = RagCollections::from_config(&config).unwrap().unwrap();
+        rag.get("personal")
+            .unwrap()
+            .ingest("diary.md", "My bank PIN hint is the dog's birthday.")
+            .await
+            .unwrap();
+        let team = rag.get("team").unwrap();
+        team.ingest(
+            "oncall.md",
+            "The bank holiday on-call rota is in PagerDuty.",
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            team.ingest("oncall.md", "Rota moved to PagerDuty.")
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(team.documents().await.unwrap().get("oncall.md"), Some(&1));
+        assert!(tmp.path().join("rag/personal/memory/brain.db").exists());
+
+        let slack = rag.context(identity("slack", "U1"), "bank rota").await;
+        assert!(slack.contains("(team) [oncall.md]"));
+        assert!(!slack.contains("PIN"));
+        let cli = rag.context(Identity::local("cli"), "bank PIN").await;
+        assert!(cli.contains("(personal) [diary.md]"));
+        assert!(!cli.contains("PagerDuty"));
+        assert!(rag
+            .context(identity("telegram", "9"), "bank")
+            .await
+            .is_empty());
+    }
+}
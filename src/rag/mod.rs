@@ -5,6 +5,11 @@
 //! - PDF ingestion (with `rag-pdf` feature)
 //! - Pin/alias tables (e.g. `red_led: 13`) for explicit lookup
 //! - Keyword retrieval (default) or semantic search via embeddings (optional)
+//!
+//! Named document collections with their own embeddings and readers live in
+//! [`collections`].
+
+pub mod collections;
 
 use crate::memory::chunker;
 use std::collections::HashMap;
@@ -0,0 +1,473 @@
+//! Meeting and podcast recordings (`[recordings]`).
+//!
+//! Audio files dropped into the watched folder are converted with `ffmpeg`
+//! into 16 kHz mono WAV chunks of `chunk_secs`, each chunk is transcribed by
+//! the `[transcription]` Whisper endpoint (a local server unless
+//! `require_local = false`), and the timed segments are stitched back into
+//! one transcript. Speaker labels are kept when the server diarizes. Long
+//! transcripts are summarized part by part before a final summary with
+//! action items is written.
+//!
+//! Results land next to the original in `<watch_dir>/done/` (the audio,
+//! `<name>.transcript.md` and `<name>.summary.md`). The summary is stored in
+//! memory under `recording:<name>`, the transcript is ingested into
+//! `rag_collection` when set, and the summary is posted to the owner channel.
+//! Files that fail move to `<watch_dir>/failed/` with an `.error.txt` note.
+
+use crate::channels::transcription::{is_local_endpoint, transcribe_segments, TranscriptSegment};
+use crate::config::Config;
+use crate::memory::{Memory, MemoryCategory};
+use crate::providers::{self, ChatMessage, ChatRequest, Provider};
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Extensions picked up from the watched folder.
+const AUDIO_EXTENSIONS: &[&str] = &[
+    "flac", "m4a", "mp3", "mp4", "mpeg", "mpga", "oga", "ogg", "opus", "wav", "webm",
+];
+/// Transcript characters summarized in one provider call.
+const PART_CHARS: usize = 12_000;
+/// Unlabeled segments are grouped into paragraphs of about this many seconds.
+const PARAGRAPH_SECS: f64 = 60.0;
+
+const NOTES_PROMPT: &str = "You are given one part of a meeting or podcast transcript. Write \
+concise notes on the points discussed, decisions made and commitments (who will do what, by \
+when). Keep speaker labels and timestamps where they matter. Do not invent details.";
+
+const SUMMARY_PROMPT: &str = "Summarize the recording from the transcript or notes provided. \
+Reply in Markdown with exactly two sections: '## Summary' (a short paragraph followed by the key \
+points) and '## Action items' (a list of '- [ ] <owner>: <task> (<due date if stated>)', or \
+'None.' when there are none). Only list action items that were actually stated.";
+
+/// Output of one processed recording.
+#[derive(Debug, Clone)]
+pub struct Processed {
+    pub name: String,
+    pub duration_secs: f64,
+    pub transcript: String,
+    pub summary: String,
+}
+
+/// `hh:mm:ss` for a transcript timestamp.
+fn timestamp(secs: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let secs = secs.max(0.0) as u64;
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Transcript lines `[hh:mm:ss] SPEAKER: text`. Consecutive segments of the
+/// same speaker are merged; unlabeled segments form paragraphs of about a minute.
+pub fn format_transcript(segments: &[TranscriptSegment]) -> String {
+    let mut lines: Vec<(f64, Option<&str>, String)> = Vec::new();
+    for segment in segments {
+        let speaker = segment.speaker.as_deref();
+        match lines.last_mut() {
+            Some((start, last_speaker, text))
+                if *last_speaker == speaker
+                    && (speaker.is_some() || segment.start - *start < PARAGRAPH_SECS) =>
+            {
+                text.push(' ');
+                text.push_str(&segment.text);
+            }
+            _ => lines.push((segment.start, speaker, segment.text.clone())),
+        }
+    }
+    lines
+        .into_iter()
+        .map(|(start, speaker, text)| match speaker {
+            Some(speaker) => format!("[{}] {speaker}: {text}", timestamp(start)),
+            None => format!("[{}] {text}", timestamp(start)),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Split a transcript into parts of at most `max_chars`, on line boundaries.
+fn transcript_parts(transcript: &str, max_chars: usize) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    for line in transcript.lines() {
+        let current = parts.last_mut().expect("parts is never empty");
+        if !current.is_empty() && current.len() + line.len() + 1 > max_chars {
+            parts.push(String::new());
+        }
+        let current = parts.last_mut().expect("parts is never empty");
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+fn is_audio(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Absolute watched folder (`watch_dir` is relative to the workspace).
+pub fn watch_dir(config: &Config) -> PathBuf {
+    let dir = PathBuf::from(crate::util::expand_tilde(
+        config.recordings.watch_dir.trim(),
+    ));
+    if dir.is_absolute() {
+        dir
+    } else {
+        config.workspace_dir.join(dir)
+    }
+}
+
+/// Convert `path` into WAV chunks of `chunk_secs` in `out_dir`, in order.
+async fn split_audio(path: &Path, out_dir: &Path, chunk_secs: u64) -> Result<Vec<PathBuf>> {
+    let output = tokio::process::Command::new("ffmpeg")
+        .args(["-nostdin", "-loglevel", "error", "-i"])
+        .arg(path)
+        .args(["-vn", "-ac", "1", "-ar", "16000", "-c:a", "pcm_s16le"])
+        .args(["-f", "segment", "-segment_time", &chunk_secs.to_string()])
+        .arg(out_dir.join("chunk_%04d.wav"))
+        .output()
+        .await
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                anyhow::anyhow!("ffmpeg not found; install ffmpeg to process recordings")
+            } else {
+                anyhow::anyhow!("Failed to run ffmpeg: {e}")
+            }
+        })?;
+    if !output.status.success() {
+        bail!(
+            "ffmpeg failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let mut chunks: Vec<PathBuf> = std::fs::read_dir(out_dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wav"))
+        .collect();
+    chunks.sort();
+    Ok(chunks)
+}
+
+/// Transcribe `path` chunk by chunk into timed segments.
+async fn transcribe(config: &Config, path: &Path) -> Result<Vec<TranscriptSegment>> {
+    if config.recordings.require_local && !is_local_endpoint(&config.transcription.api_url) {
+        bail!(
+            "[recordings] only uses a local Whisper server; point [transcription] api_url at one \
+             or set [recordings] require_local = false"
+        );
+    }
+    let chunk_dir = std::env::temp_dir().join(format!(
+        "zeroclaw-recording-{}",
+        uuid::Uuid::new_v4().simple()
+    ));
+    // Private (0700) on unix: the chunks are the recording itself.
+    let mut builder = std::fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder
+        .create(&chunk_dir)
+        .with_context(|| format!("Failed to create {}", chunk_dir.display()))?;
+    let segments = transcribe_chunks(config, path, &chunk_dir).await;
+    let _ = tokio::fs::remove_dir_all(&chunk_dir).await;
+    segments
+}
+
+async fn transcribe_chunks(
+    config: &Config,
+    path: &Path,
+    chunk_dir: &Path,
+) -> Result<Vec<TranscriptSegment>> {
+    let chunk_secs = config.recordings.chunk_secs;
+    let chunks = split_audio(path, chunk_dir, chunk_secs).await?;
+    if chunks.is_empty() {
+        bail!("ffmpeg produced no audio for {}", path.display());
+    }
+    let mut segments = Vec::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let offset = (i as u64 * chunk_secs) as f64;
+        let audio = tokio::fs::read(chunk).await?;
+        let name = format!("chunk_{i:04}.wav");
+        let chunk_segments = transcribe_segments(audio, &name, &config.transcription)
+            .await
+            .with_context(|| format!("Failed to transcribe chunk {} of {}", i + 1, chunks.len()))?;
+        segments.extend(chunk_segments.into_iter().map(|mut segment| {
+            segment.start += offset;
+            segment.end += offset;
+            segment
+        }));
+    }
+    Ok(segments)
+}
+
+async fn complete(
+    provider: &dyn Provider,
+    model: &str,
+    system: &str,
+    user: String,
+) -> Result<String> {
+    let messages = [ChatMessage::system(system), ChatMessage::user(user)];
+    let response = provider
+        .chat(
+            ChatRequest {
+                messages: &messages,
+                tools: None,
+            },
+            model,
+            0.2,
+        )
+        .await?;
+    Ok(response.text_or_empty().trim().to_string())
+}
+
+/// Summary with action items; long transcripts are condensed into notes first.
+async fn summarize(config: &Config, name: &str, transcript: &str) -> Result<String> {
+    let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
+    let model = config
+        .default_model
+        .clone()
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4".into());
+    let provider_runtime_options = providers::ProviderRuntimeOptions {
+        auth_profile_override: None,
+        zeroclaw_dir: config.config_path.parent().map(PathBuf::from),
+        secrets_encrypt: config.secrets.encrypt,
+        provider_api_url: config.api_url.clone(),
+        reasoning_enabled: config.runtime.reasoning_enabled,
+        custom_providers: config.custom_providers.clone(),
+        provider_plugins: config.provider_plugins.clone(),
+    };
+    let provider = providers::create_resilient_provider_with_options(
+        provider_name,
+        config.api_key.as_deref(),
+        config.api_url.as_deref(),
+        &config.reliability,
+        &provider_runtime_options,
+    )?;
+
+    let parts = transcript_parts(transcript, PART_CHARS);
+    let material = if parts.len() <= 1 {
+        format!("Recording: {name}\n\nTranscript:\n{transcript}")
+    } else {
+        let mut notes = format!("Recording: {name}\n\nNotes per part:");
+        for (i, part) in parts.iter().enumerate() {
+            let part_notes = complete(
+                provider.as_ref(),
+                &model,
+                NOTES_PROMPT,
+                format!("Part {} of {}:\n{part}", i + 1, parts.len()),
+            )
+            .await?;
+            let _ = write!(notes, "\n\n### Part {}\n{part_notes}", i + 1);
+        }
+        notes
+    };
+    let summary = complete(provider.as_ref(), &model, SUMMARY_PROMPT, material).await?;
+    if summary.is_empty() {
+        bail!("The provider returned an empty summary for '{name}'");
+    }
+    Ok(summary)
+}
+
+/// Transcribe and summarize one recording, then store the results in memory
+/// and, when configured, the RAG collection. Does not move or post anything.
+pub async fn process(config: &Config, memory: &dyn Memory, path: &Path) -> Result<Processed> {
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .context("Recording file name is not valid UTF-8")?
+        .to_string();
+    let segments = transcribe(config, path).await?;
+    if segments.is_empty() {
+        bail!("No speech found in {}", path.display());
+    }
+    let transcript = format_transcript(&segments);
+    let summary = summarize(config, &name, &transcript).await?;
+    let duration_secs = segments.last().map_or(0.0, |segment| segment.end);
+
+    memory
+        .store(
+            &format!("recording:{name}"),
+            &format!(
+                "Recording {name} ({})\n\n{summary}",
+                timestamp(duration_secs)
+            ),
+            MemoryCategory::Custom("recordings".into()),
+            None,
+        )
+        .await?;
+    if let Some(collection) = &config.recordings.rag_collection {
+        let rag = crate::rag::collections::RagCollections::from_config(config)?
+            .context("[recordings] rag_collection is set but no RAG collections are configured")?;
+        rag.get(collection)?
+            .ingest(&format!("recordings/{name}"), &transcript)
+            .await?;
+    }
+    Ok(Processed {
+        name,
+        duration_secs,
+        transcript,
+        summary,
+    })
+}
+
+/// Channel message announcing a processed recording.
+pub fn announcement(processed: &Processed) -> String {
+    format!(
+        "Recording processed: {} ({})\n\n{}",
+        processed.name,
+        timestamp(processed.duration_secs),
+        processed.summary
+    )
+}
+
+/// Channel and recipient that receive summaries.
+pub fn route(config: &Config) -> Result<(String, String)> {
+    match (&config.recordings.channel, &config.recordings.to) {
+        (Some(channel), Some(to)) => Ok((channel.to_ascii_lowercase(), to.clone())),
+        _ => crate::questions::owner_route(config)
+            .context("Set [recordings].channel and .to (or configure the [questions] owner route)"),
+    }
+}
+
+/// Move `path` into `dir` (created on demand), returning the new location.
+fn move_into(path: &Path, dir: &Path) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let target = dir.join(path.file_name().context("Recording has no file name")?);
+    std::fs::rename(path, &target)
+        .with_context(|| format!("Failed to move {} to {}", path.display(), dir.display()))?;
+    Ok(target)
+}
+
+/// Process a watched file and file it under `done/` or `failed/`.
+async fn handle_file(config: &Config, memory: &dyn Memory, path: &Path) -> Result<()> {
+    let dir = watch_dir(config);
+    match process(config, memory, path).await {
+        Ok(processed) => {
+            let done = dir.join("done");
+            move_into(path, &done)?;
+            std::fs::write(
+                done.join(format!("{}.transcript.md", processed.name)),
+                &processed.transcript,
+            )?;
+            std::fs::write(
+                done.join(format!("{}.summary.md", processed.name)),
+                &processed.summary,
+            )?;
+            tracing::info!(recording = %processed.name, "Recording processed");
+            let (channel, to) = route(config)?;
+            crate::cron::scheduler::send_announcement(
+                config,
+                &channel,
+                &to,
+                &announcement(&processed),
+            )
+            .await
+        }
+        Err(err) => {
+            let failed = dir.join("failed");
+            let target = move_into(path, &failed)?;
+            let mut note = target.into_os_string();
+            note.push(".error.txt");
+            std::fs::write(&note, format!("{err:#}\n"))?;
+            Err(err)
+        }
+    }
+}
+
+/// Audio files directly inside the watched folder, with their size and mtime.
+fn scan(dir: &Path) -> HashMap<PathBuf, (u64, Option<SystemTime>)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return HashMap::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+        .filter(|entry| is_audio(&entry.path()))
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            Some((entry.path(), (meta.len(), meta.modified().ok())))
+        })
+        .collect()
+}
+
+/// Daemon worker: process recordings once they stop changing between polls.
+pub async fn run_worker(config: Config) -> Result<()> {
+    let dir = watch_dir(&config);
+    std::fs::create_dir_all(&dir)?;
+    let memory = crate::memory::create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    let mut previous = HashMap::new();
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(config.recordings.poll_secs.max(1)));
+    loop {
+        interval.tick().await;
+        let current = scan(&dir);
+        for (path, stamp) in &current {
+            // A file still being copied changes size or mtime between polls.
+            if previous.get(path) != Some(stamp) {
+                continue;
+            }
+            if let Err(err) = handle_file(&config, memory.as_ref(), path).await {
+                tracing::warn!("Recording {} failed: {err:#}", path.display());
+            }
+        }
+        previous = current;
+        crate::health::mark_component_ok("recordings");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: f64, speaker: Option<&str>, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            start,
+            end: start + 5.0,
+            speaker: speaker.map(str::to_string),
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn transcript_merges_speaker_turns_and_paragraphs() {
+        let labeled = [
+            segment(0.0, Some("SPEAKER_00"), "Welcome back."),
+            segment(5.0, Some("SPEAKER_00"), "Today we ship."),
+            segment(3_725.0, Some("SPEAKER_01"), "Great."),
+        ];
+        assert_eq!(
+            format_transcript(&labeled),
+            "[00:00:00] SPEAKER_00: Welcome back. Today we ship.\n[01:02:05] SPEAKER_01: Great."
+        );
+
+        let plain = [
+            segment(0.0, None, "One."),
+            segment(30.0, None, "Two."),
+            segment(61.0, None, "Three."),
+        ];
+        assert_eq!(
+            format_transcript(&plain),
+            "[00:00:00] One. Two.\n[00:01:01] Three."
+        );
+    }
+
+    #[test]
+    fn long_transcripts_split_on_line_boundaries() {
+        let transcript = "aaaa\nbbbb\ncccc";
+        assert_eq!(transcript_parts(transcript, 9), vec!["aaaa\nbbbb", "cccc"]);
+        assert_eq!(transcript_parts(transcript, 100).len(), 1);
+        assert!(is_audio(Path::new("standup.M4A")));
+        assert!(!is_audio(Path::new("notes.txt")));
+    }
+}
@@ -0,0 +1,177 @@
+//! `{{chart ...}}` template helper: horizontal bar charts as fenced text.
+//!
+//! The data is either an object of `label: number` pairs or an array of
+//! objects, with the label and value read from the fields named by the
+//! `label` and `value` hash arguments:
+//!
+//! ```text
+//! {{chart spend.by_model label="model" value="cost_usd" title="Spend by model" unit="$"}}
+//! ```
+
+use handlebars::{
+    Context, Handlebars, Helper, HelperDef, HelperResult, JsonValue, Output, RenderContext,
+    RenderErrorReason,
+};
+use std::fmt::Write as _;
+
+const DEFAULT_WIDTH: u64 = 30;
+const MAX_WIDTH: u64 = 80;
+
+pub(super) struct ChartHelper {
+    /// Draw bars with `#` for outputs whose fonts lack block glyphs (PDF).
+    pub ascii: bool,
+}
+
+impl HelperDef for ChartHelper {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> HelperResult {
+        let data = h
+            .param(0)
+            .ok_or(RenderErrorReason::ParamNotFoundForIndex("chart", 0))?
+            .value();
+        let hash_str = |name: &str| h.hash_get(name).and_then(|v| v.value().as_str());
+        let rows = chart_rows(
+            data,
+            hash_str("label").unwrap_or("label"),
+            hash_str("value").unwrap_or("value"),
+        )?;
+        let width = h
+            .hash_get("width")
+            .and_then(|v| v.value().as_u64())
+            .unwrap_or(DEFAULT_WIDTH)
+            .clamp(1, MAX_WIDTH);
+        let chart = render_chart(
+            hash_str("title"),
+            &rows,
+            usize::try_from(width).unwrap_or(30),
+            hash_str("unit").unwrap_or(""),
+            if self.ascii { '#' } else { '█' },
+        );
+        out.write(&chart)?;
+        Ok(())
+    }
+}
+
+fn chart_rows(
+    data: &JsonValue,
+    label_field: &str,
+    value_field: &str,
+) -> Result<Vec<(String, f64)>, RenderErrorReason> {
+    let label_of = |value: &JsonValue| match value {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Null => "-".to_string(),
+        other => other.to_string(),
+    };
+    match data {
+        JsonValue::Object(map) => Ok(map
+            .iter()
+            .filter_map(|(key, value)| value.as_f64().map(|v| (key.clone(), v)))
+            .collect()),
+        JsonValue::Array(items) => Ok(items
+            .iter()
+            .filter_map(|item| {
+                let value = item.get(value_field)?.as_f64()?;
+                Some((label_of(item.get(label_field)?), value))
+            })
+            .collect()),
+        // Missing data (e.g. an empty query result) renders an empty chart.
+        JsonValue::Null => Ok(Vec::new()),
+        _ => Err(RenderErrorReason::InvalidParamType("object or array")),
+    }
+}
+
+fn render_chart(
+    title: Option<&str>,
+    rows: &[(String, f64)],
+    width: usize,
+    unit: &str,
+    bar: char,
+) -> String {
+    let mut out = String::from("\n```text\n");
+    if let Some(title) = title {
+        out.push_str(title);
+        out.push('\n');
+    }
+    if rows.is_empty() {
+        out.push_str("(no data)\n```\n");
+        return out;
+    }
+    let label_width = rows
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let max = rows.iter().map(|(_, v)| v.abs()).fold(0.0_f64, f64::max);
+    for (label, value) in rows {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let len = if max > 0.0 {
+            ((value.abs() / max) * width as f64).round() as usize
+        } else {
+            0
+        };
+        // Keep small non-zero values visible.
+        let len = if len == 0 && *value != 0.0 { 1 } else { len };
+        let padding = label_width - label.chars().count();
+        let _ = writeln!(
+            out,
+            "{label}{}  {}{} {unit}{}",
+            " ".repeat(padding),
+            bar.to_string().repeat(len),
+            " ".repeat(width - len.min(width)),
+            format_value(*value)
+        );
+    }
+    out.push_str("```\n");
+    out
+}
+
+/// Integers without decimals, everything else with two.
+pub(super) fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn chart_scales_bars_to_the_largest_value() {
+        let rows = chart_rows(
+            &json!([
+                {"model": "sonnet", "cost_usd": 12.5},
+                {"model": "haiku", "cost_usd": 0.01},
+                {"model": "idle", "cost_usd": 0},
+            ]),
+            "model",
+            "cost_usd",
+        )
+        .unwrap();
+        let chart = render_chart(Some("Spend"), &rows, 10, "$", '#');
+        assert_eq!(
+            chart,
+            "\n```text\nSpend\n\
+             sonnet  ########## $12.50\n\
+             haiku   #          $0.01\n\
+             idle               $0\n```\n"
+        );
+
+        let empty = render_chart(None, &[], 10, "", '#');
+        assert!(empty.contains("(no data)"));
+        assert!(chart_rows(&json!("x"), "label", "value").is_err());
+    }
+}
@@ -0,0 +1,748 @@
+//! Scheduled reports rendered from workspace templates.
+//!
+//! A report is a TOML definition under `<workspace>/reports/` that names a
+//! Handlebars template (`reports` build feature) and the data queries filling it: memory recall,
+//! datasheet RAG, and cost and runtime-trace usage over the report period.
+//! The filled Markdown is converted to HTML, DOCX or PDF with the
+//! `convert_doc` writers (`doc-convert` build feature; Markdown otherwise),
+//! saved under `reports/out/` and optionally delivered
+//! to a channel (as a file attachment where the channel supports uploads).
+//! Reports run from the CLI (`zeroclaw reports run infra-weekly`) or from
+//! agent cron jobs whose prompt is `/report infra-weekly`.
+
+#[cfg(feature = "reports")]
+mod chart;
+mod sources;
+
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+#[cfg(feature = "doc-convert")]
+use crate::tools::convert_doc::{format_extension, render_markdown};
+
+const REPORTS_DIR: &str = "reports";
+const OUTPUT_DIR: &str = "out";
+/// Prefix that marks a cron prompt as a report invocation.
+pub const REPORT_COMMAND: &str = "/report";
+/// Template variables filled by the renderer rather than by queries.
+const RESERVED_NAMES: &[&str] = &["report", "period", "generated_at"];
+
+fn default_format() -> String {
+    "markdown".into()
+}
+
+fn default_period() -> String {
+    "7d".into()
+}
+
+fn default_limit() -> usize {
+    5
+}
+
+fn format_name(format: &str) -> String {
+    format.trim().trim_start_matches('.').to_ascii_lowercase()
+}
+
+/// Without the `doc-convert` feature reports are written as Markdown only.
+#[cfg(not(feature = "doc-convert"))]
+fn format_extension(format: &str) -> Option<&'static str> {
+    matches!(format_name(format).as_str(), "md" | "markdown").then_some("md")
+}
+
+#[cfg(not(feature = "doc-convert"))]
+fn render_markdown(markdown: &str, _format: &str, _title: Option<&str>) -> Result<Vec<u8>> {
+    Ok(markdown.as_bytes().to_vec())
+}
+
+/// A document format that this build cannot write.
+fn is_document_format(format: &str) -> bool {
+    !cfg!(feature = "doc-convert")
+        && matches!(
+            format_name(format).as_str(),
+            "html" | "htm" | "docx" | "pdf"
+        )
+}
+
+/// A data query whose JSON result is exposed to the template under its name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "lowercase")]
+pub enum ReportQuery {
+    /// Memory entries recalled for `query`, or the newest entries of
+    /// `category` when no query is given.
+    Memory {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        query: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        category: Option<String>,
+        #[serde(default = "default_limit")]
+        limit: usize,
+    },
+    /// Datasheet chunks from `[peripherals] datasheet_dir`.
+    Rag {
+        query: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        boards: Vec<String>,
+        #[serde(default = "default_limit")]
+        limit: usize,
+    },
+    /// Model spend recorded by the cost tracker during the period.
+    Cost,
+    /// LLM and tool usage from the runtime trace during the period.
+    Telemetry,
+}
+
+/// Where a rendered report is sent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportDelivery {
+    /// `telegram`, `discord`, `slack` or `mattermost`.
+    pub channel: String,
+    /// Channel-specific recipient (chat, channel or room id).
+    pub to: String,
+}
+
+/// A report definition stored as `<workspace>/reports/<name>.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportDefinition {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Document title; may use template variables. Defaults to the first
+    /// heading of the rendered report.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Handlebars template file, relative to the reports directory.
+    pub template: String,
+    /// `markdown`, `html`, `docx` or `pdf`.
+    #[serde(default = "default_format")]
+    pub format: String,
+    /// Window covered by cost and telemetry queries: `<n>h`, `<n>d` or `<n>w`.
+    #[serde(default = "default_period")]
+    pub period: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub queries: BTreeMap<String, ReportQuery>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivery: Option<ReportDelivery>,
+}
+
+impl ReportDefinition {
+    /// Check names, paths, format and period.
+    pub fn validate(&self) -> Result<()> {
+        validate_name(&self.name)?;
+        let template = Path::new(&self.template);
+        if self.template.trim().is_empty()
+            || !template
+                .components()
+                .all(|part| matches!(part, Component::Normal(_)))
+        {
+            bail!(
+                "Report '{}': template must be a relative path inside {REPORTS_DIR}/",
+                self.name
+            );
+        }
+        if format_extension(&self.format).is_none() {
+            if is_document_format(&self.format) {
+                bail!(
+                    "Report '{}': format '{}' requires the 'doc-convert' build feature. \
+                     Rebuild with: cargo build --features doc-convert",
+                    self.name,
+                    self.format
+                );
+            }
+            bail!(
+                "Report '{}': unsupported format '{}' (expected markdown, html, docx or pdf)",
+                self.name,
+                self.format
+            );
+        }
+        parse_period(&self.period).with_context(|| format!("Report '{}'", self.name))?;
+        for (name, query) in &self.queries {
+            let identifier = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !identifier || RESERVED_NAMES.contains(&name.as_str()) {
+                bail!(
+                    "Report '{}': invalid query name '{name}' (use letters, digits and '_'; \
+                     not one of {})",
+                    self.name,
+                    RESERVED_NAMES.join(", ")
+                );
+            }
+            let limit = match query {
+                ReportQuery::Memory { limit, .. } | ReportQuery::Rag { limit, .. } => *limit,
+                ReportQuery::Cost | ReportQuery::Telemetry => 1,
+            };
+            if limit == 0 {
+                bail!("Report '{}': query '{name}' has limit 0", self.name);
+            }
+        }
+        if let Some(delivery) = &self.delivery {
+            if delivery.channel.trim().is_empty() || delivery.to.trim().is_empty() {
+                bail!("Report '{}': delivery needs both channel and to", self.name);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A report rendered and written to disk.
+#[derive(Debug, Clone)]
+pub struct RenderedReport {
+    pub name: String,
+    pub title: String,
+    pub format: String,
+    /// Filled template, before format conversion.
+    pub markdown: String,
+    pub path: PathBuf,
+    pub bytes: usize,
+}
+
+/// Report definitions stored under a workspace.
+pub struct ReportStore {
+    root: PathBuf,
+}
+
+impl ReportStore {
+    pub fn for_workspace(workspace_dir: &Path) -> Self {
+        Self {
+            root: workspace_dir.join(REPORTS_DIR),
+        }
+    }
+
+    fn path_for(&self, name: &str) -> PathBuf {
+        self.root.join(format!("{name}.toml"))
+    }
+
+    /// Directory rendered reports are written to.
+    pub fn output_dir(&self) -> PathBuf {
+        self.root.join(OUTPUT_DIR)
+    }
+
+    pub fn load(&self, name: &str) -> Result<ReportDefinition> {
+        validate_name(name)?;
+        let path = self.path_for(name);
+        if !path.is_file() {
+            bail!("Unknown report '{name}'. Create it with `zeroclaw reports new {name}`");
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let definition: ReportDefinition = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        if definition.name != name {
+            bail!(
+                "{} declares name '{}'; expected '{name}'",
+                path.display(),
+                definition.name
+            );
+        }
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    /// All report definitions, sorted by name. Invalid files are skipped.
+    pub fn list(&self) -> Result<Vec<ReportDefinition>> {
+        if !self.root.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut definitions = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            let Some(name) = path
+                .extension()
+                .filter(|ext| *ext == "toml")
+                .and_then(|_| path.file_stem())
+                .and_then(|stem| stem.to_str())
+            else {
+                continue;
+            };
+            match self.load(name) {
+                Ok(definition) => definitions.push(definition),
+                Err(error) => tracing::warn!("Skipping report file {}: {error}", path.display()),
+            }
+        }
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(definitions)
+    }
+
+    /// Write a starter definition and template for `name`.
+    pub fn scaffold(&self, name: &str) -> Result<(PathBuf, PathBuf)> {
+        validate_name(name)?;
+        let definition_path = self.path_for(name);
+        let template_name = format!("{name}.md.hbs");
+        let template_path = self.root.join(&template_name);
+        for path in [&definition_path, &template_path] {
+            if path.exists() {
+                bail!("{} already exists", path.display());
+            }
+        }
+        let definition = ReportDefinition {
+            name: name.to_string(),
+            description: Some("Infrastructure and spend over the last week".into()),
+            title: Some("Infrastructure and spend — week to {{period.until}}".into()),
+            template: template_name,
+            format: if cfg!(feature = "doc-convert") {
+                "pdf"
+            } else {
+                "markdown"
+            }
+            .into(),
+            period: default_period(),
+            queries: BTreeMap::from([
+                ("spend".to_string(), ReportQuery::Cost),
+                ("usage".to_string(), ReportQuery::Telemetry),
+                (
+                    "incidents".to_string(),
+                    ReportQuery::Memory {
+                        query: Some("incident outage".into()),
+                        category: None,
+                        limit: default_limit(),
+                    },
+                ),
+            ]),
+            delivery: None,
+        };
+        fs::create_dir_all(&self.root)
+            .with_context(|| format!("Failed to create {}", self.root.display()))?;
+        fs::write(&definition_path, toml::to_string_pretty(&definition)?)
+            .with_context(|| format!("Failed to write {}", definition_path.display()))?;
+        fs::write(&template_path, STARTER_TEMPLATE)
+            .with_context(|| format!("Failed to write {}", template_path.display()))?;
+        Ok((definition_path, template_path))
+    }
+
+    /// Gather data, fill the template and write the converted document to
+    /// `reports/out/<name>-<date>.<ext>`.
+    pub async fn render(
+        &self,
+        config: &Config,
+        definition: &ReportDefinition,
+        now: DateTime<Utc>,
+    ) -> Result<RenderedReport> {
+        definition.validate()?;
+        let template_path = self.root.join(&definition.template);
+        let template = fs::read_to_string(&template_path)
+            .with_context(|| format!("Failed to read template {}", template_path.display()))?;
+
+        let period = sources::Period {
+            since: now - parse_period(&definition.period)?,
+            until: now,
+        };
+        let mut context = Map::new();
+        let mut sources = sources::Sources::new(config, period);
+        for (name, query) in &definition.queries {
+            let value = sources
+                .run(query)
+                .await
+                .with_context(|| format!("Report '{}': query '{name}' failed", definition.name))?;
+            context.insert(name.clone(), value);
+        }
+        context.insert(
+            "period".into(),
+            json!({
+                "label": definition.period,
+                "since": period.since.date_naive().to_string(),
+                "until": period.until.date_naive().to_string(),
+            }),
+        );
+        context.insert(
+            "generated_at".into(),
+            json!(now.format("%Y-%m-%d %H:%M UTC").to_string()),
+        );
+        context.insert(
+            "report".into(),
+            json!({
+                "name": definition.name,
+                "description": definition.description,
+            }),
+        );
+
+        let ascii_charts = definition.format.eq_ignore_ascii_case("pdf");
+        let title = match &definition.title {
+            Some(title) => Some(fill_template(title, &context, ascii_charts)?),
+            None => None,
+        };
+        if let (Some(title), Some(report)) = (&title, context.get_mut("report")) {
+            report["title"] = json!(title);
+        }
+        let markdown = fill_template(&template, &context, ascii_charts)
+            .with_context(|| format!("Failed to render {}", template_path.display()))?;
+
+        let format = definition.format.clone();
+        let (bytes, markdown) = tokio::task::spawn_blocking(move || {
+            render_markdown(&markdown, &format, title.as_deref()).map(|bytes| (bytes, markdown))
+        })
+        .await??;
+        let extension = format_extension(&definition.format).unwrap_or("md");
+        let out_dir = self.output_dir();
+        fs::create_dir_all(&out_dir)
+            .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+        let path = out_dir.join(format!(
+            "{}-{}.{extension}",
+            definition.name,
+            now.format("%Y-%m-%d")
+        ));
+        fs::write(&path, &bytes).with_context(|| format!("Failed to write {}", path.display()))?;
+
+        Ok(RenderedReport {
+            name: definition.name.clone(),
+            title: match &definition.title {
+                Some(_) => context["report"]["title"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                None => first_heading(&markdown).unwrap_or_else(|| definition.name.clone()),
+            },
+            format: definition.format.clone(),
+            markdown,
+            path,
+            bytes: bytes.len(),
+        })
+    }
+}
+
+const STARTER_TEMPLATE: &str = "\
+# {{report.title}}
+
+_{{period.since}} to {{period.until}} · generated {{generated_at}}_
+
+## Spend
+
+Total: **${{fixed spend.total_usd}}** across {{spend.requests}} requests.
+
+{{chart spend.by_day label=\"date\" value=\"cost_usd\" title=\"Daily spend\" unit=\"$\"}}
+
+| Model | Requests | Cost |
+| --- | --- | --- |
+{{#each spend.by_model}}
+| {{model}} | {{requests}} | ${{fixed cost_usd}} |
+{{/each}}
+
+## Agent activity
+
+{{usage.llm_calls}} model calls, {{usage.tool_calls}} tool calls, {{usage.failures}} failures.
+
+{{chart usage.tools label=\"name\" value=\"tool_calls\" title=\"Tool calls\"}}
+
+## Incidents
+
+{{#each incidents}}
+- **{{key}}** ({{timestamp}}): {{content}}
+{{else}}
+No incidents recorded.
+{{/each}}
+";
+
+#[cfg(feature = "reports")]
+handlebars::handlebars_helper!(fixed: |value: f64, {digits: u64 = 2}| {
+    format!("{value:.*}", usize::try_from(digits).unwrap_or(2))
+});
+
+/// Fill a Handlebars template. Output is Markdown, so nothing is
+/// HTML-escaped.
+#[cfg(feature = "reports")]
+fn fill_template(
+    template: &str,
+    context: &Map<String, Value>,
+    ascii_charts: bool,
+) -> Result<String> {
+    let mut registry = handlebars::Handlebars::new();
+    registry.register_escape_fn(handlebars::no_escape);
+    registry.register_helper(
+        "chart",
+        Box::new(chart::ChartHelper {
+            ascii: ascii_charts,
+        }),
+    );
+    registry.register_helper("fixed", Box::new(fixed));
+    Ok(registry.render_template(template, context)?)
+}
+
+#[cfg(not(feature = "reports"))]
+fn fill_template(
+    _template: &str,
+    _context: &Map<String, Value>,
+    _ascii_charts: bool,
+) -> Result<String> {
+    bail!(
+        "Report templates require the 'reports' build feature. \
+         Rebuild with: cargo build --features reports"
+    )
+}
+
+fn first_heading(markdown: &str) -> Option<String> {
+    markdown
+        .lines()
+        .find_map(|line| line.strip_prefix('#'))
+        .map(|heading| heading.trim_start_matches('#').trim().to_string())
+        .filter(|heading| !heading.is_empty())
+}
+
+/// Parse `<n>h`, `<n>d` or `<n>w`.
+fn parse_period(raw: &str) -> Result<Duration> {
+    let raw = raw.trim();
+    let (count, unit) = raw.split_at(raw.len().saturating_sub(1));
+    let count: i64 = count
+        .parse()
+        .ok()
+        .filter(|count| *count > 0)
+        .with_context(|| format!("Invalid period '{raw}': expected e.g. 24h, 7d or 4w"))?;
+    Ok(match unit {
+        "h" => Duration::hours(count),
+        "d" => Duration::days(count),
+        "w" => Duration::weeks(count),
+        _ => bail!("Invalid period '{raw}': expected e.g. 24h, 7d or 4w"),
+    })
+}
+
+/// Send a rendered report: as an attachment for document formats when the
+/// channel accepts files, otherwise as (paginated) Markdown text.
+pub async fn deliver(
+    config: &Config,
+    report: &RenderedReport,
+    delivery: &ReportDelivery,
+) -> Result<()> {
+    let channel = crate::cron::scheduler::announce_channel(config, &delivery.channel)?;
+    let long_messages = &config.channels_config.long_messages;
+    if format_extension(&report.format) != Some("md") {
+        let contents = fs::read(&report.path)
+            .with_context(|| format!("Failed to read {}", report.path.display()))?;
+        let file_name = report
+            .path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("report");
+        let message = crate::channels::SendMessage::new(&report.title, &delivery.to);
+        if channel.send_file(&message, file_name, &contents).await? {
+            return Ok(());
+        }
+        tracing::info!(
+            "{} cannot receive files; sending report '{}' as text",
+            delivery.channel,
+            report.name
+        );
+    }
+    crate::channels::outbound::deliver_reply(
+        channel.as_ref(),
+        &delivery.to,
+        None,
+        None,
+        &report.markdown,
+        long_messages,
+    )
+    .await
+}
+
+/// Render the named report and deliver it when it declares a destination.
+/// Returns a one-line summary for logs and cron run history.
+pub async fn run(
+    config: &Config,
+    name: &str,
+    deliver_to: Option<&ReportDelivery>,
+) -> Result<String> {
+    let store = ReportStore::for_workspace(&config.workspace_dir);
+    let definition = store.load(name)?;
+    let report = store.render(config, &definition, Utc::now()).await?;
+    let mut summary = format!(
+        "Report '{}' written to {} ({} bytes)",
+        report.name,
+        report.path.display(),
+        report.bytes
+    );
+    if let Some(delivery) = deliver_to.or(definition.delivery.as_ref()) {
+        deliver(config, &report, delivery)
+            .await
+            .with_context(|| format!("Report '{}' rendered but delivery failed", report.name))?;
+        let _ = write!(
+            summary,
+            "; delivered to {}:{}",
+            delivery.channel, delivery.to
+        );
+    }
+    Ok(summary)
+}
+
+/// Parse `/report <name>`; `None` when `text` is not a report invocation.
+pub fn parse_invocation(text: &str) -> Option<Result<String>> {
+    let rest = text.trim().strip_prefix(REPORT_COMMAND)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let mut words = rest.split_whitespace();
+    Some(match (words.next(), words.next()) {
+        (Some(name), None) => validate_name(name).map(|()| name.to_string()),
+        _ => Err(anyhow::anyhow!("Usage: {REPORT_COMMAND} <name>")),
+    })
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        bail!("Invalid report name '{name}': use letters, digits, '-' or '_' (max 64)");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir) -> Config {
+        Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn validate_rejects_bad_paths_formats_and_query_names() {
+        let base = ReportDefinition {
+            name: "weekly".into(),
+            description: None,
+            title: None,
+            template: "weekly.md.hbs".into(),
+            format: "markdown".into(),
+            period: "7d".into(),
+            queries: BTreeMap::from([("spend".to_string(), ReportQuery::Cost)]),
+            delivery: None,
+        };
+        assert!(base.validate().is_ok());
+        let mut document = base.clone();
+        document.format = "pdf".into();
+        assert_eq!(document.validate().is_ok(), cfg!(feature = "doc-convert"));
+
+        let mut bad = base.clone();
+        bad.template = "../secrets.hbs".into();
+        assert!(bad.validate().is_err());
+        let mut bad = base.clone();
+        bad.format = "odt".into();
+        assert!(bad.validate().is_err());
+        let mut bad = base.clone();
+        bad.period = "7y".into();
+        assert!(bad.validate().is_err());
+        let mut bad = base.clone();
+        bad.queries.insert("period".into(), ReportQuery::Telemetry);
+        assert!(bad.validate().is_err());
+        let mut bad = base;
+        bad.queries.insert("daily-spend".into(), ReportQuery::Cost);
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn definitions_parse_queries_by_source() {
+        let definition: ReportDefinition = toml::from_str(
+            r#"
+name = "infra"
+template = "infra.md.hbs"
+format = "html"
+
+[queries.spend]
+source = "cost"
+
+[queries.notes]
+source = "memory"
+query = "outage"
+limit = 3
+
+[delivery]
+channel = "slack"
+to = "C012345"
+"#,
+        )
+        .unwrap();
+        assert_eq!(definition.period, "7d");
+        assert_eq!(definition.queries["spend"], ReportQuery::Cost);
+        assert_eq!(
+            definition.queries["notes"],
+            ReportQuery::Memory {
+                query: Some("outage".into()),
+                category: None,
+                limit: 3
+            }
+        );
+        assert_eq!(definition.delivery.unwrap().channel, "slack");
+    }
+
+    #[cfg(feature = "reports")]
+    #[test]
+    fn fill_template_supports_charts_and_fixed() {
+        let context = json!({
+            "spend": {"total_usd": 2.456, "by_model": [{"model": "sonnet", "cost_usd": 3.0}]},
+        });
+        let markdown = fill_template(
+            "Total ${{fixed spend.total_usd}} <b>\n{{chart spend.by_model label=\"model\" value=\"cost_usd\" width=4}}",
+            context.as_object().unwrap(),
+            true,
+        )
+        .unwrap();
+        assert!(markdown.starts_with("Total $2.46 <b>\n"));
+        assert!(markdown.contains("sonnet  #### 3\n"));
+    }
+
+    #[test]
+    fn parse_invocation_accepts_only_report_commands() {
+        assert_eq!(
+            parse_invocation("/report weekly").unwrap().unwrap(),
+            "weekly"
+        );
+        assert!(parse_invocation("/reports").is_none());
+        assert!(parse_invocation("/task weekly").is_none());
+        assert!(parse_invocation("/report").unwrap().is_err());
+        assert!(parse_invocation("/report a b").unwrap().is_err());
+        assert!(parse_invocation("/report ../x").unwrap().is_err());
+    }
+
+    #[tokio::test]
+    async fn scaffolded_report_renders_to_every_format() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let store = ReportStore::for_workspace(tmp.path());
+        store.scaffold("weekly").unwrap();
+        assert!(store.scaffold("weekly").is_err());
+
+        let mut definition = store.load("weekly").unwrap();
+        // Keep the test off the memory backend.
+        definition.queries.remove("incidents");
+        let now = Utc::now();
+        if cfg!(not(feature = "reports")) {
+            let error = store.render(&config, &definition, now).await.unwrap_err();
+            assert!(error.to_string().contains("--features reports"));
+            return;
+        }
+        let formats: &[&str] = if cfg!(feature = "doc-convert") {
+            &["markdown", "html", "docx", "pdf"]
+        } else {
+            &["markdown"]
+        };
+        for &format in formats {
+            definition.format = format.into();
+            let report = store.render(&config, &definition, now).await.unwrap();
+            assert!(report.path.is_file(), "{format}");
+            assert_eq!(report.bytes, fs::read(&report.path).unwrap().len());
+            assert_eq!(
+                report.title,
+                format!("Infrastructure and spend — week to {}", now.date_naive())
+            );
+        }
+        let markdown = fs::read_to_string(
+            tmp.path()
+                .join(format!("reports/out/weekly-{}.md", now.format("%Y-%m-%d"))),
+        )
+        .unwrap();
+        assert!(markdown.contains("Total: **$0.00** across 0 requests."));
+        assert!(markdown.contains("Daily spend"));
+        assert!(markdown.contains("No incidents recorded."));
+    }
+}
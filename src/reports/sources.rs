@@ -0,0 +1,314 @@
+//! Data queries that fill report templates.
+//!
+//! Each query produces plain JSON so templates can use `{{#each}}`, field
+//! paths and the `chart` helper on it directly.
+
+use super::ReportQuery;
+use crate::config::Config;
+use crate::cost::{CostRecord, CostTracker};
+use crate::memory::{self, Memory, MemoryCategory, MemoryEntry};
+use crate::observability::runtime_trace::{self, TraceUsageTotals};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveTime, Utc};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Time window covered by cost and telemetry queries.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Period {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+}
+
+/// Runs the queries of one report, opening backends only when needed.
+pub(super) struct Sources<'a> {
+    config: &'a Config,
+    period: Period,
+    memory: Option<Box<dyn Memory>>,
+}
+
+impl<'a> Sources<'a> {
+    pub fn new(config: &'a Config, period: Period) -> Self {
+        Self {
+            config,
+            period,
+            memory: None,
+        }
+    }
+
+    pub async fn run(&mut self, query: &ReportQuery) -> Result<Value> {
+        match query {
+            ReportQuery::Memory {
+                query,
+                category,
+                limit,
+            } => {
+                self.memory_entries(query.as_deref(), category.as_deref(), *limit)
+                    .await
+            }
+            ReportQuery::Rag {
+                query,
+                boards,
+                limit,
+            } => self.datasheet_chunks(query, boards, *limit),
+            ReportQuery::Cost => {
+                let tracker =
+                    CostTracker::new(self.config.cost.clone(), &self.config.workspace_dir)?;
+                let records = tracker.get_records_between(self.period.since, self.period.until)?;
+                Ok(cost_summary(&records, self.period))
+            }
+            ReportQuery::Telemetry => self.telemetry(),
+        }
+    }
+
+    async fn memory_entries(
+        &mut self,
+        query: Option<&str>,
+        category: Option<&str>,
+        limit: usize,
+    ) -> Result<Value> {
+        if self.memory.is_none() {
+            self.memory = Some(memory::create_memory_with_storage(
+                &self.config.memory,
+                Some(&self.config.storage.provider.config),
+                &self.config.workspace_dir,
+                self.config.api_key.as_deref(),
+            )?);
+        }
+        let Some(memory) = self.memory.as_deref() else {
+            bail!("Memory backend unavailable");
+        };
+
+        let category = category.map(parse_category);
+        let mut entries: Vec<MemoryEntry> = match query {
+            Some(query) => memory
+                .recall(query, limit.saturating_mul(2), None)
+                .await?
+                .into_iter()
+                .filter(|entry| category.as_ref().is_none_or(|c| &entry.category == c))
+                .collect(),
+            None => {
+                let mut entries = memory.list(category.as_ref(), None).await?;
+                entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+                entries
+            }
+        };
+        // Assistant autosaves and kv state are not report material.
+        entries.retain(|entry| {
+            !memory::is_assistant_autosave_key(&entry.key) && !memory::is_kv_key(&entry.key)
+        });
+        entries.truncate(limit);
+
+        Ok(Value::Array(
+            entries
+                .into_iter()
+                .map(|entry| {
+                    json!({
+                        "key": entry.key,
+                        "content": entry.content,
+                        "category": entry.category.to_string(),
+                        "timestamp": entry.timestamp,
+                    })
+                })
+                .collect(),
+        ))
+    }
+
+    fn datasheet_chunks(&self, query: &str, boards: &[String], limit: usize) -> Result<Value> {
+        let Some(dir) = self
+            .config
+            .peripherals
+            .datasheet_dir
+            .as_deref()
+            .map(str::trim)
+            .filter(|dir| !dir.is_empty())
+        else {
+            bail!("rag queries need [peripherals] datasheet_dir to be set");
+        };
+        let rag = crate::rag::HardwareRag::load(&self.config.workspace_dir, dir)
+            .with_context(|| format!("Failed to load datasheets from {dir}"))?;
+        Ok(Value::Array(
+            rag.retrieve(query, boards, limit)
+                .into_iter()
+                .map(|chunk| {
+                    json!({
+                        "source": chunk.source,
+                        "board": chunk.board,
+                        "content": chunk.content,
+                    })
+                })
+                .collect(),
+        ))
+    }
+
+    fn telemetry(&self) -> Result<Value> {
+        let path = runtime_trace::resolve_trace_path(
+            &self.config.observability,
+            &self.config.workspace_dir,
+        );
+        let mut events = runtime_trace::load_events(&path, usize::MAX, None, None)?;
+        events.retain(|event| {
+            DateTime::parse_from_rfc3339(&event.timestamp).is_ok_and(|ts| {
+                let ts = ts.with_timezone(&Utc);
+                ts >= self.period.since && ts < self.period.until
+            })
+        });
+        Ok(telemetry_summary(&runtime_trace::summarize_events(&events)))
+    }
+}
+
+fn parse_category(name: &str) -> MemoryCategory {
+    match name.trim() {
+        "core" => MemoryCategory::Core,
+        "daily" => MemoryCategory::Daily,
+        "conversation" => MemoryCategory::Conversation,
+        other => MemoryCategory::Custom(other.to_string()),
+    }
+}
+
+/// Spend over the period: totals, per-model rows (most expensive first) and
+/// one row per day so daily charts have no gaps.
+pub(super) fn cost_summary(records: &[CostRecord], period: Period) -> Value {
+    #[derive(Default)]
+    struct Totals {
+        cost_usd: f64,
+        tokens: u64,
+        requests: u64,
+    }
+    impl Totals {
+        fn add(&mut self, record: &CostRecord) {
+            self.cost_usd += record.usage.cost_usd;
+            self.tokens += record.usage.total_tokens;
+            self.requests += 1;
+        }
+    }
+
+    let mut total = Totals::default();
+    let mut by_model: BTreeMap<&str, Totals> = BTreeMap::new();
+    let mut by_day: BTreeMap<String, Totals> = BTreeMap::new();
+    let mut day = period.since.date_naive();
+    while day.and_time(NaiveTime::MIN).and_utc() < period.until {
+        by_day.insert(day.to_string(), Totals::default());
+        let Some(next) = day.succ_opt() else { break };
+        day = next;
+    }
+    for record in records {
+        total.add(record);
+        by_model
+            .entry(record.usage.model.as_str())
+            .or_default()
+            .add(record);
+        by_day
+            .entry(record.usage.timestamp.date_naive().to_string())
+            .or_default()
+            .add(record);
+    }
+
+    let mut by_model: Vec<(&str, Totals)> = by_model.into_iter().collect();
+    by_model.sort_by(|a, b| b.1.cost_usd.total_cmp(&a.1.cost_usd));
+    let models: Vec<Value> = by_model
+        .into_iter()
+        .map(|(model, totals)| {
+            json!({
+                "model": model,
+                "cost_usd": totals.cost_usd,
+                "tokens": totals.tokens,
+                "requests": totals.requests,
+            })
+        })
+        .collect();
+
+    json!({
+        "total_usd": total.cost_usd,
+        "tokens": total.tokens,
+        "requests": total.requests,
+        "by_model": models,
+        "by_day": by_day
+            .into_iter()
+            .map(|(date, totals)| {
+                json!({
+                    "date": date,
+                    "cost_usd": totals.cost_usd,
+                    "requests": totals.requests,
+                })
+            })
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Trace totals with per-tool and per-model rows, busiest first.
+fn telemetry_summary(summary: &runtime_trace::RuntimeTraceSummary) -> Value {
+    let rows = |groups: &BTreeMap<String, TraceUsageTotals>,
+                calls: fn(&TraceUsageTotals) -> u64| {
+        let mut rows: Vec<(&String, &TraceUsageTotals)> = groups.iter().collect();
+        rows.sort_by_key(|(_, totals)| std::cmp::Reverse(calls(totals)));
+        rows.into_iter()
+            .map(|(name, totals)| {
+                let mut row = serde_json::to_value(totals).unwrap_or_default();
+                row["name"] = json!(name);
+                row
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut value = serde_json::to_value(&summary.total).unwrap_or_default();
+    value["sessions"] = json!(summary.sessions.len());
+    value["tools"] = json!(rows(&summary.tools, |t| t.tool_calls));
+    value["models"] = json!(rows(&summary.models, |t| t.llm_calls));
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cost::TokenUsage;
+    use chrono::TimeZone;
+
+    fn record(model: &str, cost_usd: f64, day: u32) -> CostRecord {
+        let mut usage = TokenUsage::new(model, 1000, 500, 0.0, 0.0);
+        usage.cost_usd = cost_usd;
+        usage.timestamp = Utc.with_ymd_and_hms(2026, 10, day, 12, 0, 0).unwrap();
+        CostRecord::new("session", usage)
+    }
+
+    #[test]
+    fn cost_summary_groups_by_model_and_fills_days() {
+        let period = Period {
+            since: Utc.with_ymd_and_hms(2026, 10, 10, 0, 0, 0).unwrap(),
+            until: Utc.with_ymd_and_hms(2026, 10, 13, 0, 0, 0).unwrap(),
+        };
+        let summary = cost_summary(
+            &[
+                record("haiku", 0.25, 10),
+                record("sonnet", 2.0, 10),
+                record("sonnet", 1.5, 12),
+            ],
+            period,
+        );
+
+        assert_eq!(summary["total_usd"], json!(3.75));
+        assert_eq!(summary["requests"], json!(3));
+        assert_eq!(summary["tokens"], json!(4500));
+        assert_eq!(summary["by_model"][0]["model"], "sonnet");
+        assert_eq!(summary["by_model"][0]["requests"], json!(2));
+        let days: Vec<(String, f64)> = summary["by_day"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| {
+                (
+                    d["date"].as_str().unwrap().to_string(),
+                    d["cost_usd"].as_f64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            days,
+            vec![
+                ("2026-10-10".to_string(), 2.25),
+                ("2026-10-11".to_string(), 0.0),
+                ("2026-10-12".to_string(), 1.5),
+            ]
+        );
+    }
+}
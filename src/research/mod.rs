@@ -0,0 +1,520 @@
+//! Scheduled web research briefs (`[research]`).
+//!
+//! Each standing topic is searched on its own schedule (`every_hours`).
+//! Result URLs are normalized and checked against the ones already reported
+//! for the topic, which are kept in memory under `research_seen:<topic>:<url>`,
+//! so a brief only covers new sources. Up to `max_sources` new pages are read
+//! (through `fetch_url` when it is enabled, otherwise from search snippets)
+//! and summarized into a brief with numbered citations. Sources are dropped
+//! from the end until the estimated provider cost fits `max_cost_usd`.
+//! Briefs go to the topic's channel, `[research]`'s channel or the owner route.
+
+use crate::config::{Config, ResearchTopicConfig};
+use crate::cron::scheduler::send_announcement;
+use crate::memory::{Memory, MemoryCategory};
+use crate::observability::runtime_trace;
+use crate::providers::{self, ChatMessage, ChatRequest};
+use crate::security::{NetworkGuard, SecurityPolicy};
+use crate::tools::{FetchUrlTool, Tool, WebSearchTool};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const SEEN_PREFIX: &str = "research_seen:";
+/// How often the daemon worker looks for due topics.
+const CHECK_INTERVAL_SECS: u64 = 300;
+/// Characters of each fetched page passed to the model.
+const MAX_SOURCE_CHARS: usize = 4_000;
+/// Candidates requested per query, so enough remain after deduplication.
+const RESULTS_PER_QUERY: usize = 10;
+/// Output tokens assumed when estimating the cost of a brief.
+const ESTIMATED_OUTPUT_TOKENS: u64 = 800;
+
+const BRIEF_PROMPT: &str = "You write short research briefs. Using only the numbered sources \
+provided, summarize what is new about the topic in a few bullet points. Cite every claim with \
+the source numbers in brackets, e.g. [2] or [1][3]. Do not invent facts or sources and skip \
+sources that are off-topic. Do not add a source list; it is appended automatically.";
+
+/// A search result considered for a brief.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+}
+
+/// A composed brief and the sources it cites.
+#[derive(Debug, Clone)]
+pub struct Brief {
+    pub text: String,
+    pub sources: Vec<Source>,
+}
+
+/// Parse `web_search_tool` output (`N. title`, then indented URL and snippet lines).
+pub fn parse_search_results(output: &str) -> Vec<Source> {
+    let mut sources: Vec<Source> = Vec::new();
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if !line.starts_with(' ') {
+            if let Some((number, title)) = trimmed.split_once(". ") {
+                if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+                    sources.push(Source {
+                        title: title.trim().to_string(),
+                        url: String::new(),
+                        snippet: String::new(),
+                    });
+                }
+            }
+            continue;
+        }
+        let Some(last) = sources.last_mut() else {
+            continue;
+        };
+        if last.url.is_empty() {
+            last.url = trimmed.to_string();
+        } else if last.snippet.is_empty() {
+            last.snippet = trimmed.to_string();
+        }
+    }
+    sources
+        .retain(|source| source.url.starts_with("http://") || source.url.starts_with("https://"));
+    sources
+}
+
+fn is_tracking_param(pair: &str) -> bool {
+    let name = pair.split('=').next().unwrap_or(pair).to_ascii_lowercase();
+    name.starts_with("utm_") || matches!(name.as_str(), "fbclid" | "gclid" | "ref" | "ref_src")
+}
+
+/// Canonical URL used for deduplication: lowercase scheme and host without
+/// `www.`, no fragment, no tracking parameters and no trailing slash.
+pub fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    let url = url.split('#').next().unwrap_or(url);
+    let (base, query) = url
+        .split_once('?')
+        .map_or((url, None), |(base, query)| (base, Some(query)));
+    let (scheme, rest) = base.split_once("://").unwrap_or(("https", base));
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let host = host.to_ascii_lowercase();
+    let mut normalized = format!(
+        "{}://{}",
+        scheme.to_ascii_lowercase(),
+        host.strip_prefix("www.").unwrap_or(&host)
+    );
+    let path = path.trim_end_matches('/');
+    if !path.is_empty() {
+        normalized.push('/');
+        normalized.push_str(path);
+    }
+    let kept: Vec<&str> = query
+        .unwrap_or("")
+        .split('&')
+        .filter(|pair| !pair.is_empty() && !is_tracking_param(pair))
+        .collect();
+    if !kept.is_empty() {
+        normalized.push('?');
+        normalized.push_str(&kept.join("&"));
+    }
+    normalized
+}
+
+/// Memory key marking `url` as reported for `topic` (`http` and `https` share a key).
+fn seen_key(topic: &str, url: &str) -> String {
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    format!("{SEEN_PREFIX}{topic}:{url}")
+}
+
+/// New sources for `topic`: normalized, deduplicated and not reported before.
+pub async fn unseen_sources(
+    memory: &dyn Memory,
+    topic: &str,
+    candidates: Vec<Source>,
+    limit: usize,
+) -> Result<Vec<Source>> {
+    let mut keys = HashSet::new();
+    let mut fresh = Vec::new();
+    for mut source in candidates {
+        if fresh.len() >= limit {
+            break;
+        }
+        source.url = normalize_url(&source.url);
+        let key = seen_key(topic, &source.url);
+        if !keys.insert(key.clone()) || memory.get(&key).await?.is_some() {
+            continue;
+        }
+        fresh.push(source);
+    }
+    Ok(fresh)
+}
+
+/// Record `sources` as reported for `topic`.
+pub async fn mark_seen(memory: &dyn Memory, topic: &str, sources: &[Source]) -> Result<()> {
+    let today = Utc::now().format("%Y-%m-%d");
+    for source in sources {
+        memory
+            .store(
+                &seen_key(topic, &source.url),
+                &format!("{} (reported {today})", source.title),
+                MemoryCategory::Custom("research".into()),
+                None,
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// Brief text with the numbered source list appended.
+pub fn render_brief(topic: &str, summary: &str, sources: &[Source]) -> String {
+    let mut text = format!("Research brief: {topic}\n\n{}\n\nSources:", summary.trim());
+    for (i, source) in sources.iter().enumerate() {
+        let _ = write!(text, "\n[{}] {} - {}", i + 1, source.title, source.url);
+    }
+    text
+}
+
+fn topic_queries(name: &str, topic: &ResearchTopicConfig) -> Vec<String> {
+    if topic.queries.is_empty() {
+        vec![name.to_string()]
+    } else {
+        topic.queries.clone()
+    }
+}
+
+/// Source excerpts numbered as cited in the brief.
+async fn read_sources(
+    config: &Config,
+    topic: &ResearchTopicConfig,
+    sources: &[Source],
+) -> Vec<String> {
+    let fetcher = (topic.fetch_pages && config.fetch_url.enabled).then(|| {
+        FetchUrlTool::new(
+            Arc::new(SecurityPolicy::from_config(
+                &config.autonomy,
+                &config.workspace_dir,
+            )),
+            config.fetch_url.clone(),
+            &config.workspace_dir,
+        )
+        .with_network_guard(NetworkGuard::for_tool(
+            &config.security.network,
+            "fetch_url",
+        ))
+    });
+    let mut excerpts = Vec::with_capacity(sources.len());
+    for (i, source) in sources.iter().enumerate() {
+        let mut body = source.snippet.clone();
+        if let Some(fetcher) = &fetcher {
+            match fetcher
+                .execute(json!({ "url": source.url, "max_chars": MAX_SOURCE_CHARS }))
+                .await
+            {
+                Ok(result) if result.success => body = result.output,
+                Ok(result) => tracing::debug!(
+                    url = %source.url,
+                    "Research fetch skipped: {}",
+                    result.error.unwrap_or_default()
+                ),
+                Err(e) => tracing::debug!(url = %source.url, "Research fetch failed: {e}"),
+            }
+        }
+        excerpts.push(format!(
+            "[{}] {}\nURL: {}\n{body}",
+            i + 1,
+            source.title,
+            source.url
+        ));
+    }
+    excerpts
+}
+
+/// Estimated cost of summarizing `prompt`, `None` when the model has no price entry.
+fn estimate_cost(provider: &str, model: &str, prompt: &str) -> Option<f64> {
+    let input_tokens = (BRIEF_PROMPT.len() + prompt.len()) as u64 / 4;
+    runtime_trace::estimate_cost_usd(
+        provider,
+        model,
+        Some(input_tokens),
+        Some(ESTIMATED_OUTPUT_TOKENS),
+    )
+}
+
+/// Search, deduplicate and summarize one topic. `None` when nothing new was found.
+pub async fn compose_brief(
+    config: &Config,
+    memory: &dyn Memory,
+    name: &str,
+    topic: &ResearchTopicConfig,
+) -> Result<Option<Brief>> {
+    let search = WebSearchTool::new(
+        config.web_search.provider.clone(),
+        config.web_search.brave_api_key.clone(),
+        RESULTS_PER_QUERY,
+        config.web_search.timeout_secs,
+    );
+    let mut candidates = Vec::new();
+    for query in topic_queries(name, topic) {
+        let result = search.execute(json!({ "query": query })).await?;
+        candidates.extend(parse_search_results(&result.output));
+    }
+    let mut sources = unseen_sources(memory, name, candidates, topic.max_sources).await?;
+    if sources.is_empty() {
+        return Ok(None);
+    }
+
+    let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
+    let model = config
+        .default_model
+        .clone()
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4".into());
+    let mut excerpts = read_sources(config, topic, &sources).await;
+    let mut prompt = format!("Topic: {name}\n\n{}", excerpts.join("\n\n"));
+    if topic.max_cost_usd > 0.0 {
+        while estimate_cost(provider_name, &model, &prompt)
+            .is_some_and(|cost| cost > topic.max_cost_usd)
+        {
+            if excerpts.len() <= 1 {
+                bail!(
+                    "research.topics.{name}: a brief exceeds max_cost_usd (${:.2}) even with one source",
+                    topic.max_cost_usd
+                );
+            }
+            excerpts.pop();
+            sources.pop();
+            prompt = format!("Topic: {name}\n\n{}", excerpts.join("\n\n"));
+        }
+    }
+
+    let provider_runtime_options = providers::ProviderRuntimeOptions {
+        auth_profile_override: None,
+        zeroclaw_dir: config.config_path.parent().map(PathBuf::from),
+        secrets_encrypt: config.secrets.encrypt,
+        provider_api_url: config.api_url.clone(),
+        reasoning_enabled: config.runtime.reasoning_enabled,
+        custom_providers: config.custom_providers.clone(),
+        provider_plugins: config.provider_plugins.clone(),
+    };
+    let provider = providers::create_resilient_provider_with_options(
+        provider_name,
+        config.api_key.as_deref(),
+        config.api_url.as_deref(),
+        &config.reliability,
+        &provider_runtime_options,
+    )?;
+    let messages = [ChatMessage::system(BRIEF_PROMPT), ChatMessage::user(prompt)];
+    let response = provider
+        .chat(
+            ChatRequest {
+                messages: &messages,
+                tools: None,
+            },
+            &model,
+            config.default_temperature,
+        )
+        .await?;
+    let summary = response.text_or_empty();
+    if summary.trim().is_empty() {
+        bail!("The provider returned an empty brief for '{name}'");
+    }
+    Ok(Some(Brief {
+        text: render_brief(name, summary, &sources),
+        sources,
+    }))
+}
+
+/// Channel and recipient that receive briefs for `topic`.
+pub fn route(config: &Config, topic: &ResearchTopicConfig) -> Result<(String, String)> {
+    let research = &config.research;
+    match (
+        topic.channel.as_ref().or(research.channel.as_ref()),
+        topic.to.as_ref().or(research.to.as_ref()),
+    ) {
+        (Some(channel), Some(to)) => Ok((channel.to_ascii_lowercase(), to.clone())),
+        _ => crate::questions::owner_route(config)
+            .context("Set [research].channel and .to (or configure the [questions] owner route)"),
+    }
+}
+
+/// Look up a configured topic by name.
+pub fn topic<'a>(config: &'a Config, name: &str) -> Result<&'a ResearchTopicConfig> {
+    config.research.topics.get(name).with_context(|| {
+        let known: Vec<&str> = config.research.topics.keys().map(String::as_str).collect();
+        format!(
+            "Unknown research topic '{name}' (configured: {})",
+            if known.is_empty() {
+                "none".to_string()
+            } else {
+                known.join(", ")
+            }
+        )
+    })
+}
+
+/// Compose and send a brief for `name`, then record its sources as seen.
+pub async fn deliver(
+    config: &Config,
+    memory: &dyn Memory,
+    name: &str,
+    topic: &ResearchTopicConfig,
+) -> Result<Option<Brief>> {
+    let Some(brief) = compose_brief(config, memory, name, topic).await? else {
+        return Ok(None);
+    };
+    let (channel, to) = route(config, topic)?;
+    send_announcement(config, &channel, &to, &brief.text).await?;
+    mark_seen(memory, name, &brief.sources).await?;
+    Ok(Some(brief))
+}
+
+/// When each topic last ran, kept in `state/research.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ResearchState {
+    #[serde(default)]
+    pub last_run: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl ResearchState {
+    fn path(workspace_dir: &Path) -> PathBuf {
+        workspace_dir.join("state").join("research.json")
+    }
+
+    pub fn load(workspace_dir: &Path) -> Self {
+        std::fs::read_to_string(Self::path(workspace_dir))
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, workspace_dir: &Path) -> Result<()> {
+        let path = Self::path(workspace_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Whether `topic` has not run within its `every_hours`.
+    pub fn is_due(&self, name: &str, topic: &ResearchTopicConfig, now: DateTime<Utc>) -> bool {
+        self.last_run.get(name).map_or(true, |last| {
+            now - *last >= Duration::hours(i64::from(topic.every_hours))
+        })
+    }
+}
+
+/// Daemon worker: send a brief for every topic that is due.
+pub async fn run_worker(config: Config) -> Result<()> {
+    let memory = crate::memory::create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        let mut state = ResearchState::load(&config.workspace_dir);
+        for (name, topic) in &config.research.topics {
+            if !state.is_due(name, topic, Utc::now()) {
+                continue;
+            }
+            match deliver(&config, memory.as_ref(), name, topic).await {
+                Ok(Some(brief)) => {
+                    tracing::info!(topic = %name, sources = brief.sources.len(), "Research brief sent");
+                }
+                Ok(None) => tracing::info!(topic = %name, "No new sources for research topic"),
+                Err(e) => tracing::warn!(topic = %name, "Research brief failed: {e}"),
+            }
+            // Failed runs also wait for the next period instead of retrying every check.
+            state.last_run.insert(name.clone(), Utc::now());
+            state.save(&config.workspace_dir)?;
+        }
+        crate::health::mark_component_ok("research");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    fn source(url: &str) -> Source {
+        Source {
+            title: "Release notes".into(),
+            url: url.into(),
+            snippet: String::new(),
+        }
+    }
+
+    #[test]
+    fn search_output_is_parsed_and_urls_normalized() {
+        let output = "Search results for: rust async (via DuckDuckGo)\n\
+                      1. Tokio 2.0 released\n   https://tokio.rs/blog/2.0\n   Big release\n\
+                      2. No link here\n\
+                      3. Async book\n   https://WWW.Rust-Lang.org/async/?utm_source=x&page=2#intro";
+        let sources = parse_search_results(output);
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].title, "Tokio 2.0 released");
+        assert_eq!(sources[0].snippet, "Big release");
+        assert_eq!(
+            normalize_url(&sources[1].url),
+            "https://rust-lang.org/async?page=2"
+        );
+        assert_eq!(normalize_url("https://example.com/"), "https://example.com");
+    }
+
+    #[tokio::test]
+    async fn reported_urls_are_skipped_on_later_runs() {
+        let tmp = TempDir::new().unwrap();
+        let memory = SqliteMemory::new(tmp.path()).unwrap();
+        let candidates = vec![
+            source("https://example.com/a"),
+            source("https://example.com/a/?utm_medium=feed"),
+            source("https://example.com/b"),
+        ];
+
+        let fresh = unseen_sources(&memory, "rust", candidates.clone(), 5)
+            .await
+            .unwrap();
+        assert_eq!(fresh.len(), 2);
+        mark_seen(&memory, "rust", &fresh[..1]).await.unwrap();
+
+        let fresh = unseen_sources(&memory, "rust", candidates.clone(), 5)
+            .await
+            .unwrap();
+        assert_eq!(fresh, vec![source("https://example.com/b")]);
+        // Other topics keep their own history.
+        assert_eq!(
+            unseen_sources(&memory, "tokio", candidates, 1)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let brief = render_brief("rust", "- Tokio shipped [1]", &fresh);
+        assert!(brief.ends_with("[1] Release notes - https://example.com/b"));
+    }
+
+    #[test]
+    fn topics_are_due_after_their_period() {
+        let topic = ResearchTopicConfig {
+            every_hours: 6,
+            ..ResearchTopicConfig::default()
+        };
+        let now = Utc::now();
+        let mut state = ResearchState::default();
+        assert!(state.is_due("rust", &topic, now));
+        state
+            .last_run
+            .insert("rust".into(), now - Duration::hours(5));
+        assert!(!state.is_due("rust", &topic, now));
+        assert!(state.is_due("rust", &topic, now + Duration::hours(1)));
+    }
+}
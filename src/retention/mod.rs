@@ -0,0 +1,489 @@
+//! Retention policies per data class (`[retention]`).
+//!
+//! Each class has a legal hold (`min_days`) and a privacy limit (`max_days`).
+//! The daemon deletes records older than the limit every `interval_hours`.
+//! The hold binds every other deleter too: memory hygiene, quota gc and audit
+//! log rotation consult [`respect_hold`] / [`held_paths`] before removing
+//! anything. `zeroclaw doctor` reports each policy and the oldest record.
+
+use crate::config::{Config, DataRetentionConfig, RetentionPolicyConfig};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local, Utc};
+use rusqlite::{params, Connection};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+use std::time::SystemTime;
+
+static RETENTION: LazyLock<RwLock<DataRetentionConfig>> =
+    LazyLock::new(|| RwLock::new(DataRetentionConfig::default()));
+
+/// Apply `[retention]` from config.
+pub fn init_from_config(config: &Config) {
+    let mut guard = RETENTION.write().unwrap_or_else(|e| e.into_inner());
+    *guard = config.retention.clone();
+}
+
+/// A kind of record with its own retention policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataClass {
+    Conversations,
+    Traces,
+    AuditLog,
+    Telemetry,
+}
+
+impl DataClass {
+    pub const ALL: [Self; 4] = [
+        Self::Conversations,
+        Self::Traces,
+        Self::AuditLog,
+        Self::Telemetry,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Conversations => "conversations",
+            Self::Traces => "traces",
+            Self::AuditLog => "audit_log",
+            Self::Telemetry => "telemetry",
+        }
+    }
+
+    pub fn policy(self, config: &DataRetentionConfig) -> RetentionPolicyConfig {
+        match self {
+            Self::Conversations => config.conversations,
+            Self::Traces => config.traces,
+            Self::AuditLog => config.audit_log,
+            Self::Telemetry => config.telemetry,
+        }
+    }
+}
+
+/// Raise another deleter's age limit to the class's legal hold. `0` (keep
+/// forever) stays `0`.
+pub fn respect_hold(class: DataClass, days: u32) -> u32 {
+    if days == 0 {
+        return 0;
+    }
+    let guard = RETENTION.read().unwrap_or_else(|e| e.into_inner());
+    days.max(class.policy(&guard).min_days)
+}
+
+/// Whether the class is under legal hold in this process.
+pub fn is_held(class: DataClass) -> bool {
+    let guard = RETENTION.read().unwrap_or_else(|e| e.into_inner());
+    class.policy(&guard).min_days > 0
+}
+
+/// Log files under legal hold, which size-based trimming must leave alone.
+pub fn held_paths(config: &Config) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if config.retention.traces.min_days > 0 {
+        paths.push(trace_path(config));
+    }
+    paths
+}
+
+/// Policy and oldest record of one class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassStatus {
+    pub class: DataClass,
+    pub policy: RetentionPolicyConfig,
+    pub oldest: Option<DateTime<Utc>>,
+}
+
+impl ClassStatus {
+    /// Whether a record has outlived `max_days`.
+    pub fn overdue(&self, now: DateTime<Utc>) -> bool {
+        match (self.policy.max_days, self.oldest) {
+            (0, _) | (_, None) => false,
+            (max_days, Some(oldest)) => oldest < cutoff(now, max_days),
+        }
+    }
+
+    /// `keep ≥ 30 days, delete after 365 days; oldest 2026-01-02`
+    pub fn summary(&self) -> String {
+        let hold = match self.policy.min_days {
+            0 => "no hold".to_string(),
+            days => format!("keep ≥ {days} days"),
+        };
+        let limit = match self.policy.max_days {
+            0 => "no limit".to_string(),
+            days => format!("delete after {days} days"),
+        };
+        let oldest = self.oldest.map_or_else(
+            || "no records".to_string(),
+            |oldest| format!("oldest {}", oldest.format("%Y-%m-%d")),
+        );
+        format!("{hold}, {limit}; {oldest}")
+    }
+}
+
+/// Current policy and oldest record of every class.
+pub fn status(config: &Config) -> Vec<ClassStatus> {
+    DataClass::ALL
+        .into_iter()
+        .map(|class| ClassStatus {
+            class,
+            policy: class.policy(&config.retention),
+            oldest: oldest(config, class),
+        })
+        .collect()
+}
+
+/// Delete records older than each class's `max_days`. Returns the number of
+/// records removed per class.
+pub fn sweep(config: &Config, now: DateTime<Utc>) -> Vec<(DataClass, Result<u64>)> {
+    DataClass::ALL
+        .into_iter()
+        .filter_map(|class| {
+            let policy = class.policy(&config.retention);
+            if policy.max_days == 0 {
+                return None;
+            }
+            // Validation keeps min_days ≤ max_days; stay safe if it is bypassed.
+            let cutoff = cutoff(now, policy.max_days.max(policy.min_days));
+            let removed = match class {
+                DataClass::Conversations => purge_conversations(&config.workspace_dir, cutoff),
+                DataClass::Traces => prune_jsonl(&trace_path(config), cutoff).map(|(n, _)| n),
+                DataClass::AuditLog => purge_audit_log(config, cutoff),
+                DataClass::Telemetry => purge_telemetry(&config.workspace_dir, cutoff, now),
+            };
+            Some((class, removed))
+        })
+        .collect()
+}
+
+/// Daemon worker: sweep every `interval_hours`.
+pub async fn run_worker(config: Config) -> Result<()> {
+    let hours = u64::from(config.retention.interval_hours.max(1));
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(hours * 60 * 60));
+
+    loop {
+        interval.tick().await;
+        let worker_config = config.clone();
+        let results =
+            tokio::task::spawn_blocking(move || sweep(&worker_config, Utc::now())).await?;
+        for (class, result) in results {
+            match result {
+                Ok(0) => {}
+                Ok(removed) => {
+                    tracing::info!(class = class.name(), "retention removed {removed} records");
+                }
+                Err(err) => {
+                    tracing::warn!(class = class.name(), "retention sweep failed: {err:#}");
+                }
+            }
+        }
+        crate::health::mark_component_ok("retention");
+    }
+}
+
+fn cutoff(now: DateTime<Utc>, days: u32) -> DateTime<Utc> {
+    now - Duration::days(i64::from(days))
+}
+
+fn trace_path(config: &Config) -> PathBuf {
+    crate::observability::runtime_trace::resolve_trace_path(
+        &config.observability,
+        &config.workspace_dir,
+    )
+}
+
+fn brain_db(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("memory").join("brain.db")
+}
+
+/// Session logs, including archived ones.
+fn session_files(workspace_dir: &Path) -> Vec<(PathBuf, SystemTime)> {
+    let sessions = workspace_dir.join("sessions");
+    let mut files = Vec::new();
+    for dir in [sessions.clone(), sessions.join("archive")] {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_file() {
+                let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                files.push((entry.path(), modified));
+            }
+        }
+    }
+    files
+}
+
+/// The audit log and its rotated `<log>.N.log` files.
+fn audit_files(config: &Config) -> Vec<PathBuf> {
+    let Some(dir) = config.config_path.parent() else {
+        return Vec::new();
+    };
+    let log_path = dir.join(&config.security.audit.log_path);
+    let (Some(parent), Some(name)) = (
+        log_path.parent(),
+        log_path.file_name().and_then(|name| name.to_str()),
+    ) else {
+        return Vec::new();
+    };
+    let rotated_prefix = format!("{name}.");
+    let mut files: Vec<PathBuf> = fs::read_dir(parent)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| {
+            let file_name = entry.file_name();
+            let file_name = file_name.to_string_lossy();
+            file_name.starts_with(&rotated_prefix) && file_name.ends_with(".log")
+        })
+        .map(|entry| entry.path())
+        .collect();
+    if log_path.is_file() {
+        files.push(log_path);
+    }
+    files
+}
+
+fn oldest(config: &Config, class: DataClass) -> Option<DateTime<Utc>> {
+    match class {
+        DataClass::Conversations => {
+            let rows = oldest_conversation_row(&config.workspace_dir);
+            let files = session_files(&config.workspace_dir)
+                .into_iter()
+                .map(|(_, modified)| DateTime::<Utc>::from(modified))
+                .min();
+            rows.into_iter().chain(files).min()
+        }
+        DataClass::Traces => oldest_jsonl(&trace_path(config)),
+        DataClass::AuditLog => audit_files(config)
+            .iter()
+            .filter_map(|path| oldest_jsonl(path))
+            .min(),
+        DataClass::Telemetry => {
+            if !crate::telemetry::state_path(&config.workspace_dir).exists() {
+                return None;
+            }
+            crate::telemetry::build_report(&config.workspace_dir, Utc::now())
+                .ok()
+                .filter(|report| !report.is_empty())
+                .and_then(|report| report.period_start)
+        }
+    }
+}
+
+fn oldest_conversation_row(workspace_dir: &Path) -> Option<DateTime<Utc>> {
+    let db_path = brain_db(workspace_dir);
+    if !db_path.exists() {
+        return None;
+    }
+    let conn = Connection::open(db_path).ok()?;
+    let oldest: Option<String> = conn
+        .query_row(
+            "SELECT MIN(updated_at) FROM memories WHERE category = 'conversation'",
+            [],
+            |row| row.get(0),
+        )
+        .ok()?;
+    parse_timestamp(&oldest?)
+}
+
+fn purge_conversations(workspace_dir: &Path, cutoff: DateTime<Utc>) -> Result<u64> {
+    let mut removed = 0_u64;
+
+    let db_path = brain_db(workspace_dir);
+    if db_path.exists() {
+        let conn = Connection::open(db_path)?;
+        conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
+        // Rows are stamped in local time, like memory hygiene compares them.
+        let affected = conn.execute(
+            "DELETE FROM memories WHERE category = 'conversation' AND updated_at < ?1",
+            params![cutoff.with_timezone(&Local).to_rfc3339()],
+        )?;
+        removed += u64::try_from(affected).unwrap_or(0);
+    }
+
+    let cutoff_time = SystemTime::from(cutoff);
+    for (path, modified) in session_files(workspace_dir) {
+        if modified < cutoff_time {
+            fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
+fn purge_audit_log(config: &Config, cutoff: DateTime<Utc>) -> Result<u64> {
+    let active = config
+        .config_path
+        .parent()
+        .map(|dir| dir.join(&config.security.audit.log_path));
+    let mut removed = 0_u64;
+    for path in audit_files(config) {
+        let (dropped, empty) = prune_jsonl(&path, cutoff)?;
+        removed += dropped;
+        if empty && Some(&path) != active.as_ref() {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(removed)
+}
+
+fn purge_telemetry(workspace_dir: &Path, cutoff: DateTime<Utc>, now: DateTime<Utc>) -> Result<u64> {
+    if !crate::telemetry::state_path(workspace_dir).exists() {
+        return Ok(0);
+    }
+    let report = crate::telemetry::build_report(workspace_dir, now)?;
+    match report.period_start {
+        Some(start) if start < cutoff && !report.is_empty() => {
+            crate::telemetry::discard_counters(workspace_dir, now)?;
+            Ok(1)
+        }
+        _ => Ok(0),
+    }
+}
+
+fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|ts| ts.with_timezone(&Utc))
+}
+
+fn line_timestamp(line: &str) -> Option<DateTime<Utc>> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    parse_timestamp(value.get("timestamp")?.as_str()?)
+}
+
+/// First timestamp in an append-only JSONL file.
+fn oldest_jsonl(path: &Path) -> Option<DateTime<Utc>> {
+    let file = fs::File::open(path).ok()?;
+    BufReader::new(file)
+        .lines()
+        .map_while(std::io::Result::ok)
+        .find_map(|line| line_timestamp(&line))
+}
+
+/// Drop JSONL lines stamped before `cutoff`; lines without a timestamp are
+/// kept. Returns the number of lines dropped and whether the file is empty.
+fn prune_jsonl(path: &Path, cutoff: DateTime<Utc>) -> Result<(u64, bool)> {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok((0, false)),
+        Err(err) => return Err(err.into()),
+    };
+    let permissions = file.metadata()?.permissions();
+
+    let mut kept = Vec::new();
+    let mut dropped = 0_u64;
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line_timestamp(&line).is_some_and(|ts| ts < cutoff) {
+            dropped += 1;
+        } else {
+            kept.push(line);
+        }
+    }
+    if dropped == 0 {
+        return Ok((0, kept.is_empty()));
+    }
+
+    let tmp = path.with_extension("retention.tmp");
+    {
+        let mut out = fs::File::create(&tmp)?;
+        for line in &kept {
+            writeln!(out, "{line}")?;
+        }
+        out.sync_all()?;
+    }
+    fs::set_permissions(&tmp, permissions)?;
+    fs::rename(&tmp, path)?;
+    Ok((dropped, kept.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config_in(tmp: &TempDir) -> Config {
+        Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn sweep_drops_only_records_past_max_days() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = config_in(&tmp);
+        config.observability.runtime_trace_mode = "full".into();
+        config.retention.traces = RetentionPolicyConfig {
+            min_days: 7,
+            max_days: 30,
+        };
+        config.retention.audit_log.max_days = 30;
+
+        let now = Utc::now();
+        let old = (now - Duration::days(40)).to_rfc3339();
+        let recent = (now - Duration::days(10)).to_rfc3339();
+        let trace = trace_path(&config);
+        fs::create_dir_all(trace.parent().unwrap()).unwrap();
+        fs::write(
+            &trace,
+            format!("{{\"timestamp\":\"{old}\"}}\n{{\"timestamp\":\"{recent}\"}}\nnot json\n"),
+        )
+        .unwrap();
+        let audit = tmp.path().join("audit.log");
+        let rotated = tmp.path().join("audit.log.1.log");
+        fs::write(&audit, format!("{{\"timestamp\":\"{recent}\"}}\n")).unwrap();
+        fs::write(&rotated, format!("{{\"timestamp\":\"{old}\"}}\n")).unwrap();
+
+        let results = sweep(&config, now);
+        let removed: Vec<(DataClass, u64)> = results
+            .into_iter()
+            .map(|(class, result)| (class, result.unwrap()))
+            .collect();
+        assert_eq!(removed, [(DataClass::Traces, 1), (DataClass::AuditLog, 1)]);
+
+        let remaining = fs::read_to_string(&trace).unwrap();
+        assert!(!remaining.contains(&old));
+        assert!(remaining.contains(&recent) && remaining.contains("not json"));
+        assert!(audit.exists());
+        assert!(!rotated.exists());
+
+        let traces = status(&config)
+            .into_iter()
+            .find(|status| status.class == DataClass::Traces)
+            .unwrap();
+        assert!(!traces.overdue(now));
+        assert_eq!(held_paths(&config), [trace]);
+    }
+
+    #[test]
+    fn status_flags_records_past_max_days() {
+        let now = Utc::now();
+        let status = ClassStatus {
+            class: DataClass::Conversations,
+            policy: RetentionPolicyConfig {
+                min_days: 0,
+                max_days: 30,
+            },
+            oldest: Some(now - Duration::days(31)),
+        };
+        assert!(status.overdue(now));
+        assert!(status
+            .summary()
+            .starts_with("no hold, delete after 30 days; oldest "));
+
+        let unlimited = ClassStatus {
+            policy: RetentionPolicyConfig::default(),
+            ..status
+        };
+        assert!(!unlimited.overdue(now));
+        assert_eq!(respect_hold(DataClass::Conversations, 0), 0);
+    }
+}
@@ -1,9 +1,11 @@
 pub mod docker;
 pub mod native;
+pub mod remote;
 pub mod traits;
 
 pub use docker::DockerRuntime;
 pub use native::NativeRuntime;
+pub use remote::RemoteRuntime;
 pub use traits::RuntimeAdapter;
 
 use crate::config::RuntimeConfig;
@@ -1,7 +1,8 @@
 use super::traits::RuntimeAdapter;
+use crate::security::ShellDialect;
 use std::path::{Path, PathBuf};
 
-/// Native runtime — full access, runs on Mac/Linux/Docker/Raspberry Pi
+/// Native runtime — full access, runs on Mac/Linux/Windows/Docker/Raspberry Pi
 pub struct NativeRuntime;
 
 impl NativeRuntime {
@@ -39,8 +40,21 @@ impl RuntimeAdapter for NativeRuntime {
         command: &str,
         workspace_dir: &Path,
     ) -> anyhow::Result<tokio::process::Command> {
-        let mut process = tokio::process::Command::new("sh");
-        process.arg("-c").arg(command).current_dir(workspace_dir);
+        let dialect = ShellDialect::host();
+        let (program, args) = dialect.program();
+        let mut process = tokio::process::Command::new(program);
+        process.args(args);
+        #[cfg(windows)]
+        if dialect == ShellDialect::Cmd {
+            // cmd.exe does not parse arguments with the quoting rules `arg`
+            // applies; `/S` strips exactly one pair of outer quotes instead.
+            process.raw_arg(format!("\"{command}\""));
+        } else {
+            process.arg(command);
+        }
+        #[cfg(not(windows))]
+        process.arg(command);
+        process.current_dir(workspace_dir);
         Ok(process)
     }
 }
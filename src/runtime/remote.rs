@@ -0,0 +1,314 @@
+//! Remote tool execution over SSH (`[runtime.remote]`).
+//!
+//! Tools routed to the remote host build their processes through
+//! [`RemoteRuntime`]. Each call is wrapped in a small versioned request
+//! envelope and run as `ssh <host> <worker_command> <envelope>`. On the
+//! remote side `zeroclaw remote-worker` decodes the envelope, checks that the
+//! working directory lies inside its `--root` (when given) and runs the
+//! program, passing stdout, stderr and the exit code straight through.
+//!
+//! `--root` only constrains the starting working directory. It is not a
+//! security boundary: a shell command can still `cd /` or name absolute
+//! paths, so confine the worker with a dedicated unprivileged account (or a
+//! container) on the remote host. Pinning it with a forced command in the
+//! remote `authorized_keys`
+//! (`command="zeroclaw remote-worker --root /srv/work" ssh-ed25519 ...`)
+//! keeps the key from running anything but the worker, which then reads the
+//! envelope from `SSH_ORIGINAL_COMMAND`.
+
+use super::traits::RuntimeAdapter;
+use crate::config::RemoteWorkerConfig;
+use anyhow::{bail, Context, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Version of the request envelope understood by this build.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// One unit of work for the remote worker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkerRequest {
+    pub v: u32,
+    /// Absolute working directory on the remote host.
+    pub cwd: String,
+    #[serde(flatten)]
+    pub program: WorkerProgram,
+}
+
+/// Program the worker runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum WorkerProgram {
+    /// `sh -c <command>`
+    Shell { command: String },
+    /// Direct exec without a shell.
+    Exec { argv: Vec<String> },
+}
+
+impl WorkerRequest {
+    pub fn new(cwd: &Path, program: WorkerProgram) -> Self {
+        Self {
+            v: PROTOCOL_VERSION,
+            cwd: cwd.to_string_lossy().into_owned(),
+            program,
+        }
+    }
+
+    /// Shell-safe single token (URL-safe base64 of the JSON request).
+    pub fn encode(&self) -> Result<String> {
+        Ok(URL_SAFE_NO_PAD.encode(serde_json::to_vec(self)?))
+    }
+
+    pub fn decode(raw: &str) -> Result<Self> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(raw.trim())
+            .context("remote worker request is not valid base64")?;
+        let request: Self =
+            serde_json::from_slice(&bytes).context("remote worker request is malformed")?;
+        if request.v != PROTOCOL_VERSION {
+            bail!(
+                "remote worker speaks protocol v{PROTOCOL_VERSION}, request is v{}; upgrade zeroclaw on both hosts",
+                request.v
+            );
+        }
+        Ok(request)
+    }
+}
+
+/// Runtime that executes commands on a remote host over SSH.
+#[derive(Debug, Clone)]
+pub struct RemoteRuntime {
+    config: RemoteWorkerConfig,
+}
+
+impl RemoteRuntime {
+    pub fn new(config: RemoteWorkerConfig) -> Self {
+        Self { config }
+    }
+
+    /// Workspace directory on the remote host.
+    pub fn workspace(&self) -> PathBuf {
+        PathBuf::from(self.config.workspace.trim())
+    }
+
+    /// Whether `tool` is routed to the remote host.
+    pub fn routes(&self, tool: &str) -> bool {
+        self.config.tools.iter().any(|name| name.trim() == tool)
+    }
+
+    /// Build the local `ssh` process that runs `program` in `cwd` remotely.
+    pub fn command(&self, program: WorkerProgram, cwd: &Path) -> Result<tokio::process::Command> {
+        let request = WorkerRequest::new(cwd, program).encode()?;
+        let mut process = tokio::process::Command::new("ssh");
+        process
+            .arg("-T")
+            .arg("-o")
+            .arg("BatchMode=yes")
+            .arg("-o")
+            .arg(format!(
+                "ConnectTimeout={}",
+                self.config.connect_timeout_secs.max(1)
+            ))
+            .arg("-p")
+            .arg(self.config.port.to_string());
+
+        if let Some(identity) = self.config.identity_file.as_deref() {
+            process
+                .arg("-i")
+                .arg(crate::util::expand_tilde(identity.trim()));
+        }
+        // Tools clear the child environment; keep agent auth working.
+        if let Ok(agent) = std::env::var("SSH_AUTH_SOCK") {
+            process.arg("-o").arg(format!("IdentityAgent={agent}"));
+        }
+
+        let host = self.config.host.trim();
+        let destination = match self.config.user.as_deref().map(str::trim) {
+            Some(user) if !user.is_empty() => format!("{user}@{host}"),
+            _ => host.to_string(),
+        };
+        process
+            .arg("--")
+            .arg(destination)
+            .arg(format!("{} {request}", self.config.worker_command.trim()))
+            .kill_on_drop(true);
+        Ok(process)
+    }
+}
+
+impl RuntimeAdapter for RemoteRuntime {
+    fn name(&self) -> &str {
+        "ssh"
+    }
+
+    fn has_shell_access(&self) -> bool {
+        true
+    }
+
+    fn has_filesystem_access(&self) -> bool {
+        false
+    }
+
+    fn storage_path(&self) -> PathBuf {
+        self.workspace().join(".zeroclaw")
+    }
+
+    fn supports_long_running(&self) -> bool {
+        false
+    }
+
+    fn build_shell_command(
+        &self,
+        command: &str,
+        workspace_dir: &Path,
+    ) -> Result<tokio::process::Command> {
+        self.command(
+            WorkerProgram::Shell {
+                command: command.to_string(),
+            },
+            workspace_dir,
+        )
+    }
+}
+
+/// Worker side of the protocol: run one request and return its exit code.
+///
+/// `request` falls back to the last word of `SSH_ORIGINAL_COMMAND`, which is
+/// how a forced `authorized_keys` command receives it.
+pub fn run_worker(request: Option<&str>, root: Option<&Path>) -> Result<i32> {
+    let raw = match request {
+        Some(raw) => raw.to_string(),
+        None => std::env::var("SSH_ORIGINAL_COMMAND")
+            .ok()
+            .and_then(|original| original.split_whitespace().last().map(str::to_string))
+            .context("no request given and SSH_ORIGINAL_COMMAND is not set")?,
+    };
+    let request = WorkerRequest::decode(&raw)?;
+
+    let cwd = Path::new(&request.cwd)
+        .canonicalize()
+        .with_context(|| format!("remote workspace {} does not exist", request.cwd))?;
+    if let Some(root) = root {
+        let root = root
+            .canonicalize()
+            .with_context(|| format!("worker root {} does not exist", root.display()))?;
+        if !cwd.starts_with(&root) {
+            bail!(
+                "{} is outside the worker root {}",
+                cwd.display(),
+                root.display()
+            );
+        }
+    }
+
+    let mut process = match &request.program {
+        WorkerProgram::Shell { command } => {
+            let mut process = std::process::Command::new("sh");
+            process.arg("-c").arg(command);
+            process
+        }
+        WorkerProgram::Exec { argv } => {
+            let (program, args) = argv.split_first().context("exec request has no program")?;
+            let mut process = std::process::Command::new(program);
+            process.args(args);
+            process
+        }
+    };
+    let status = process
+        .current_dir(&cwd)
+        .status()
+        .context("remote worker failed to start the program")?;
+    Ok(status.code().unwrap_or(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RemoteWorkerConfig {
+        RemoteWorkerConfig {
+            enabled: true,
+            host: "build.example.com".into(),
+            user: Some("ci".into()),
+            port: 2222,
+            workspace: "/srv/work".into(),
+            ..RemoteWorkerConfig::default()
+        }
+    }
+
+    #[test]
+    fn request_roundtrips_and_is_shell_safe() {
+        let request = WorkerRequest::new(
+            Path::new("/srv/work"),
+            WorkerProgram::Shell {
+                command: "echo 'hi' && ls $HOME; cat <x".into(),
+            },
+        );
+        let encoded = request.encode().unwrap();
+        assert!(encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(WorkerRequest::decode(&encoded).unwrap(), request);
+    }
+
+    #[test]
+    fn decode_rejects_other_protocol_versions() {
+        let mut request = WorkerRequest::new(
+            Path::new("/srv/work"),
+            WorkerProgram::Exec {
+                argv: vec!["git".into(), "status".into()],
+            },
+        );
+        request.v = PROTOCOL_VERSION + 1;
+        let err = WorkerRequest::decode(&request.encode().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("protocol"));
+    }
+
+    #[test]
+    fn ssh_command_targets_configured_host() {
+        let runtime = RemoteRuntime::new(config());
+        let process = runtime
+            .build_shell_command("make -j8", Path::new("/srv/work"))
+            .unwrap();
+        let command = process.as_std();
+        assert_eq!(command.get_program(), "ssh");
+        let args: Vec<_> = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        assert!(args.windows(2).any(|pair| pair == ["-p", "2222"]));
+        let separator = args.iter().position(|arg| arg == "--").unwrap();
+        assert_eq!(args[separator + 1], "ci@build.example.com");
+        let remote = args[separator + 2]
+            .strip_prefix("zeroclaw remote-worker ")
+            .unwrap();
+        assert_eq!(
+            WorkerRequest::decode(remote).unwrap().program,
+            WorkerProgram::Shell {
+                command: "make -j8".into()
+            }
+        );
+        assert!(runtime.routes("shell"));
+        assert!(!runtime.routes("file_read"));
+    }
+
+    #[test]
+    fn worker_runs_request_inside_root() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let request = WorkerRequest::new(
+            tmp.path(),
+            WorkerProgram::Shell {
+                command: "exit 3".into(),
+            },
+        )
+        .encode()
+        .unwrap();
+        assert_eq!(run_worker(Some(&request), Some(tmp.path())).unwrap(), 3);
+
+        let other = tempfile::TempDir::new().unwrap();
+        let err = run_worker(Some(&request), Some(other.path())).unwrap_err();
+        assert!(err.to_string().contains("outside the worker root"));
+    }
+}
@@ -21,6 +21,8 @@ pub enum AuditEventType {
     AuthFailure,
     PolicyViolation,
     SecurityEvent,
+    SecretRotation,
+    SecondApproval,
 }
 
 /// Actor information (who performed the action)
@@ -244,9 +246,18 @@ impl AuditLogger {
         Ok(())
     }
 
-    /// Rotate the log file
+    /// Rotate the log file. Past 10 generations the oldest is overwritten,
+    /// unless `[retention.audit_log]` holds it; then every generation is kept.
     fn rotate(&self) -> Result<()> {
-        for i in (1..10).rev() {
+        let mut oldest = 9;
+        if crate::retention::is_held(crate::retention::DataClass::AuditLog) {
+            while std::path::Path::new(&format!("{}.{}.log", self.log_path.display(), oldest + 1))
+                .exists()
+            {
+                oldest += 1;
+            }
+        }
+        for i in (1..=oldest).rev() {
             let old_name = format!("{}.{}.log", self.log_path.display(), i);
             let new_name = format!("{}.{}.log", self.log_path.display(), i + 1);
             let _ = std::fs::rename(&old_name, &new_name);
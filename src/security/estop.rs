@@ -1,6 +1,7 @@
 use crate::config::EstopConfig;
 use crate::security::domain_matcher::DomainMatcher;
 use crate::security::otp::OtpValidator;
+use crate::state::{retire_legacy_file, StateStore};
 use crate::util::expand_tilde;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -62,63 +63,68 @@ impl EstopState {
     }
 }
 
+/// Key of the estop state in the [`StateStore`].
+const STATE_KEY: &str = "estop";
+
 #[derive(Debug, Clone)]
 pub struct EstopManager {
     config: EstopConfig,
-    state_path: PathBuf,
+    store: StateStore,
     state: EstopState,
 }
 
 impl EstopManager {
+    /// Load the estop state from the state store in `config_dir`. A legacy
+    /// `state_file` is imported once. Unreadable state fails closed.
     pub fn load(config: &EstopConfig, config_dir: &Path) -> Result<Self> {
-        let state_path = resolve_state_file_path(config_dir, &config.state_file);
+        let store = StateStore::for_config_dir(config_dir);
+        let legacy_path = resolve_state_file_path(config_dir, &config.state_file);
         let mut should_fail_closed = false;
-        let mut state = if state_path.exists() {
-            match fs::read_to_string(&state_path) {
-                Ok(raw) => match serde_json::from_str::<EstopState>(&raw) {
-                    Ok(mut parsed) => {
-                        parsed.normalize();
-                        parsed
-                    }
-                    Err(error) => {
-                        tracing::warn!(
-                            path = %state_path.display(),
-                            "Failed to parse estop state file; entering fail-closed mode: {error}"
-                        );
-                        should_fail_closed = true;
-                        EstopState::fail_closed()
-                    }
-                },
-                Err(error) => {
-                    tracing::warn!(
-                        path = %state_path.display(),
-                        "Failed to read estop state file; entering fail-closed mode: {error}"
-                    );
-                    should_fail_closed = true;
-                    EstopState::fail_closed()
+        let mut state = match store.get::<EstopState>(STATE_KEY) {
+            Ok(Some(state)) => state,
+            Ok(None) if legacy_path.exists() => {
+                let state = read_legacy_state(&legacy_path);
+                should_fail_closed = state.is_none();
+                let state = state.unwrap_or_else(EstopState::fail_closed);
+                let cause = if should_fail_closed {
+                    "unreadable legacy state file; fail closed"
+                } else {
+                    "import legacy state file"
+                };
+                match store.set(STATE_KEY, &state, cause) {
+                    Ok(()) => retire_legacy_file(&legacy_path),
+                    Err(error) => tracing::warn!("Failed to import estop state: {error:#}"),
                 }
+                state
+            }
+            Ok(None) => EstopState::default(),
+            Err(error) => {
+                tracing::warn!(
+                    dir = %store.dir().display(),
+                    "Failed to read estop state; entering fail-closed mode: {error:#}"
+                );
+                should_fail_closed = true;
+                EstopState::fail_closed()
             }
-        } else {
-            EstopState::default()
         };
 
         state.normalize();
 
-        let mut manager = Self {
+        let manager = Self {
             config: config.clone(),
-            state_path,
+            store,
             state,
         };
 
         if should_fail_closed {
-            let _ = manager.persist_state();
+            let _ = manager.store.set(STATE_KEY, &manager.state, "fail closed");
         }
 
         Ok(manager)
     }
 
-    pub fn state_path(&self) -> &Path {
-        &self.state_path
+    pub fn store(&self) -> &StateStore {
+        &self.store
     }
 
     pub fn status(&self) -> EstopState {
@@ -126,31 +132,41 @@ impl EstopManager {
     }
 
     pub fn engage(&mut self, level: EstopLevel) -> Result<()> {
-        match level {
-            EstopLevel::KillAll => {
-                self.state.kill_all = true;
-            }
-            EstopLevel::NetworkKill => {
-                self.state.network_kill = true;
-            }
-            EstopLevel::DomainBlock(domains) => {
-                for domain in domains {
-                    let normalized = domain.trim().to_ascii_lowercase();
-                    DomainMatcher::validate_pattern(&normalized)?;
-                    self.state.blocked_domains.push(normalized);
+        let cause = match &level {
+            EstopLevel::KillAll => "engage kill-all".to_string(),
+            EstopLevel::NetworkKill => "engage network-kill".to_string(),
+            EstopLevel::DomainBlock(domains) => format!("block domains {}", domains.join(", ")),
+            EstopLevel::ToolFreeze(tools) => format!("freeze tools {}", tools.join(", ")),
+        };
+        self.state = self.store.update(STATE_KEY, &cause, |current| {
+            let mut state: EstopState = current.unwrap_or_default();
+            match level {
+                EstopLevel::KillAll => {
+                    state.kill_all = true;
                 }
-            }
-            EstopLevel::ToolFreeze(tools) => {
-                for tool in tools {
-                    let normalized = normalize_tool_name(&tool)?;
-                    self.state.frozen_tools.push(normalized);
+                EstopLevel::NetworkKill => {
+                    state.network_kill = true;
+                }
+                EstopLevel::DomainBlock(domains) => {
+                    for domain in domains {
+                        let normalized = domain.trim().to_ascii_lowercase();
+                        DomainMatcher::validate_pattern(&normalized)?;
+                        state.blocked_domains.push(normalized);
+                    }
+                }
+                EstopLevel::ToolFreeze(tools) => {
+                    for tool in tools {
+                        let normalized = normalize_tool_name(&tool)?;
+                        state.frozen_tools.push(normalized);
+                    }
                 }
             }
-        }
 
-        self.state.updated_at = Some(now_rfc3339());
-        self.state.normalize();
-        self.persist_state()
+            state.updated_at = Some(now_rfc3339());
+            state.normalize();
+            Ok(state)
+        })?;
+        Ok(())
     }
 
     pub fn resume(
@@ -161,36 +177,48 @@ impl EstopManager {
     ) -> Result<()> {
         self.ensure_resume_is_authorized(otp_code, otp_validator)?;
 
-        match selector {
-            ResumeSelector::KillAll => {
-                self.state.kill_all = false;
-            }
-            ResumeSelector::Network => {
-                self.state.network_kill = false;
-            }
+        let cause = match &selector {
+            ResumeSelector::KillAll => "resume kill-all".to_string(),
+            ResumeSelector::Network => "resume network".to_string(),
             ResumeSelector::Domains(domains) => {
-                let normalized = domains
-                    .iter()
-                    .map(|domain| domain.trim().to_ascii_lowercase())
-                    .collect::<Vec<_>>();
-                self.state
-                    .blocked_domains
-                    .retain(|existing| !normalized.iter().any(|target| target == existing));
+                format!("unblock domains {}", domains.join(", "))
             }
-            ResumeSelector::Tools(tools) => {
-                let normalized = tools
-                    .iter()
-                    .map(|tool| normalize_tool_name(tool))
-                    .collect::<Result<Vec<_>>>()?;
-                self.state
-                    .frozen_tools
-                    .retain(|existing| !normalized.iter().any(|target| target == existing));
+            ResumeSelector::Tools(tools) => format!("unfreeze tools {}", tools.join(", ")),
+        };
+        self.state = self.store.update(STATE_KEY, &cause, |current| {
+            let mut state: EstopState = current.unwrap_or_default();
+            match selector {
+                ResumeSelector::KillAll => {
+                    state.kill_all = false;
+                }
+                ResumeSelector::Network => {
+                    state.network_kill = false;
+                }
+                ResumeSelector::Domains(domains) => {
+                    let normalized = domains
+                        .iter()
+                        .map(|domain| domain.trim().to_ascii_lowercase())
+                        .collect::<Vec<_>>();
+                    state
+                        .blocked_domains
+                        .retain(|existing| !normalized.iter().any(|target| target == existing));
+                }
+                ResumeSelector::Tools(tools) => {
+                    let normalized = tools
+                        .iter()
+                        .map(|tool| normalize_tool_name(tool))
+                        .collect::<Result<Vec<_>>>()?;
+                    state
+                        .frozen_tools
+                        .retain(|existing| !normalized.iter().any(|target| target == existing));
+                }
             }
-        }
 
-        self.state.updated_at = Some(now_rfc3339());
-        self.state.normalize();
-        self.persist_state()
+            state.updated_at = Some(now_rfc3339());
+            state.normalize();
+            Ok(state)
+        })?;
+        Ok(())
     }
 
     fn ensure_resume_is_authorized(
@@ -214,41 +242,21 @@ impl EstopManager {
         }
         Ok(())
     }
+}
 
-    fn persist_state(&mut self) -> Result<()> {
-        if let Some(parent) = self.state_path.parent() {
-            fs::create_dir_all(parent).with_context(|| {
-                format!("Failed to create estop state dir {}", parent.display())
-            })?;
-        }
-
-        let body =
-            serde_json::to_string_pretty(&self.state).context("Failed to serialize estop state")?;
-
-        let temp_path = self
-            .state_path
-            .with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
-        fs::write(&temp_path, body).with_context(|| {
-            format!(
-                "Failed to write temporary estop state file {}",
-                temp_path.display()
-            )
-        })?;
-
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let _ = fs::set_permissions(&temp_path, fs::Permissions::from_mode(0o600));
+fn read_legacy_state(path: &Path) -> Option<EstopState> {
+    let parsed = fs::read_to_string(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|raw| serde_json::from_str::<EstopState>(&raw).map_err(Into::into));
+    match parsed {
+        Ok(state) => Some(state),
+        Err(error) => {
+            tracing::warn!(
+                path = %path.display(),
+                "Failed to read legacy estop state file; entering fail-closed mode: {error}"
+            );
+            None
         }
-
-        fs::rename(&temp_path, &self.state_path).with_context(|| {
-            format!(
-                "Failed to atomically replace estop state file {}",
-                self.state_path.display()
-            )
-        })?;
-
-        Ok(())
     }
 }
 
@@ -420,4 +428,32 @@ mod tests {
             .unwrap();
         assert!(!manager.status().kill_all);
     }
+
+    #[test]
+    fn legacy_state_file_is_imported_once() {
+        let dir = tempdir().unwrap();
+        let state_path = dir.path().join("estop-state.json");
+        fs::write(&state_path, r#"{"network_kill": true}"#).unwrap();
+        let cfg = estop_config(&state_path);
+
+        let manager = EstopManager::load(&cfg, dir.path()).unwrap();
+        assert!(manager.status().network_kill);
+        assert!(!state_path.exists());
+        assert_eq!(manager.store().history(STATE_KEY, 10).unwrap().len(), 1);
+
+        let reloaded = EstopManager::load(&cfg, dir.path()).unwrap();
+        assert!(reloaded.status().network_kill);
+    }
+
+    #[test]
+    fn corrupted_store_defaults_to_fail_closed_kill_all() {
+        let dir = tempdir().unwrap();
+        let cfg = estop_config(&dir.path().join("estop-state.json"));
+        let store_dir = StateStore::for_config_dir(dir.path()).dir().to_path_buf();
+        fs::create_dir_all(&store_dir).unwrap();
+        fs::write(store_dir.join("snapshot.json"), "{not-valid-json").unwrap();
+
+        let manager = EstopManager::load(&cfg, dir.path()).unwrap();
+        assert!(manager.status().kill_all);
+    }
 }
@@ -0,0 +1,528 @@
+//! Guardrails for financial tool calls.
+//!
+//! Any tool that reports [`Tool::financial`](crate::tools::Tool::financial) or
+//! is listed in `[security.financial].tools` is routed through a
+//! [`FinancialGuard`] before it runs. A call must declare its `amount`, stay
+//! within the per-transaction and rolling 24-hour limits, wait out the
+//! cooling-off delay after it is first proposed, and carry a fresh OTP code.
+//! Every decision is appended to a dedicated JSONL audit trail, separate from
+//! the general security audit log.
+//!
+//! The ledger of spent amounts and used OTP codes lives next to `config.toml`,
+//! out of reach of the agent's workspace tools, and a ledger that cannot be
+//! read refuses every transaction rather than starting over empty.
+
+use crate::config::FinancialGuardrailConfig;
+use crate::security::OtpValidator;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Argument carrying the OTP code; never forwarded to the wrapped tool.
+pub const OTP_CODE_ARG: &str = "otp_code";
+
+/// Upper bound for the cooling-off delay (one year), keeping date math in range.
+const MAX_COOLING_OFF_SECS: u64 = 365 * 24 * 60 * 60;
+
+/// Arguments ignored when matching a call against its cooling-off entry.
+const UNSIGNED_ARGS: [&str; 2] = [OTP_CODE_ARG, "approved"];
+
+/// A transaction that passed every check and is reserved against the daily limit.
+#[derive(Debug, Clone)]
+pub struct Authorization {
+    id: String,
+    tool: String,
+    amount: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LedgerEntry {
+    id: String,
+    tool: String,
+    amount: f64,
+    at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Ledger {
+    /// Authorized transactions, kept for 24 hours.
+    #[serde(default)]
+    executed: Vec<LedgerEntry>,
+    /// Call signature -> when it was first proposed.
+    #[serde(default)]
+    pending: HashMap<String, DateTime<Utc>>,
+    /// OTP codes already spent -> when they were used.
+    #[serde(default)]
+    used_codes: HashMap<String, DateTime<Utc>>,
+}
+
+impl Ledger {
+    fn prune(&mut self, now: DateTime<Utc>, cooling_off: Duration) {
+        let day = Duration::hours(24);
+        self.executed
+            .retain(|entry| now.signed_duration_since(entry.at) < day);
+        self.pending
+            .retain(|_, first_seen| now.signed_duration_since(*first_seen) < cooling_off + day);
+        self.used_codes
+            .retain(|_, used_at| now.signed_duration_since(*used_at) < day);
+    }
+
+    fn spent(&self) -> f64 {
+        self.executed.iter().map(|entry| entry.amount).sum()
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct AuditRecord<'a> {
+    timestamp: DateTime<Utc>,
+    tool: &'a str,
+    decision: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    amount: Option<f64>,
+    currency: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'a str>,
+    arguments: Value,
+}
+
+/// Enforces limits, cooling-off, OTP and auditing for financial tool calls.
+pub struct FinancialGuard {
+    config: FinancialGuardrailConfig,
+    otp: Option<OtpValidator>,
+    ledger_path: PathBuf,
+    audit_path: PathBuf,
+    ledger_lock: tokio::sync::Mutex<()>,
+}
+
+impl FinancialGuard {
+    /// `otp` is `None` when `[security.otp]` is disabled; every financial
+    /// call is then refused.
+    pub fn new(
+        config: FinancialGuardrailConfig,
+        otp: Option<OtpValidator>,
+        zeroclaw_dir: &Path,
+    ) -> Self {
+        Self {
+            ledger_path: zeroclaw_dir.join("financial_ledger.json"),
+            audit_path: zeroclaw_dir.join(&config.audit_log_path),
+            config,
+            otp,
+            ledger_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Whether a tool must go through this guard.
+    pub fn covers(&self, tool_name: &str, flagged: bool) -> bool {
+        flagged || self.config.tools.iter().any(|name| name == tool_name)
+    }
+
+    /// Currency the limits are expressed in.
+    pub fn currency(&self) -> &str {
+        &self.config.currency
+    }
+
+    /// Check a call and reserve its amount against the daily limit.
+    ///
+    /// The error is a user-facing explanation of why the call was refused.
+    pub async fn authorize(&self, tool: &str, args: &Value) -> Result<Authorization, String> {
+        self.authorize_at(tool, args, Utc::now()).await
+    }
+
+    async fn authorize_at(
+        &self,
+        tool: &str,
+        args: &Value,
+        now: DateTime<Utc>,
+    ) -> Result<Authorization, String> {
+        let amount = args.get("amount").and_then(Value::as_f64);
+        let decision = self.check(tool, args, amount, now).await;
+        match &decision {
+            Ok(authorization) => self.audit(
+                tool,
+                "authorized",
+                Some(authorization.amount),
+                Some(&authorization.id),
+                None,
+                args,
+            ),
+            Err(reason) => self.audit(tool, "denied", amount, None, Some(reason), args),
+        }
+        decision
+    }
+
+    async fn check(
+        &self,
+        tool: &str,
+        args: &Value,
+        amount: Option<f64>,
+        now: DateTime<Utc>,
+    ) -> Result<Authorization, String> {
+        let currency = &self.config.currency;
+        let Some(amount) = amount.filter(|value| value.is_finite() && *value > 0.0) else {
+            return Err(format!(
+                "Financial action '{tool}' requires a positive numeric 'amount' in {currency}"
+            ));
+        };
+        if let Some(requested) = args.get("currency").and_then(Value::as_str) {
+            if !requested.trim().eq_ignore_ascii_case(currency) {
+                return Err(format!(
+                    "Financial limits are configured in {currency}; '{requested}' transactions are not allowed"
+                ));
+            }
+        }
+        if amount > self.config.max_per_transaction {
+            return Err(format!(
+                "Amount {amount:.2} {currency} exceeds the per-transaction limit of {:.2} {currency}",
+                self.config.max_per_transaction
+            ));
+        }
+
+        let _guard = self.ledger_lock.lock().await;
+        let cooling_off = Duration::seconds(
+            i64::try_from(self.config.cooling_off_secs.min(MAX_COOLING_OFF_SECS)).unwrap_or(0),
+        );
+        let mut ledger = self.read_ledger().map_err(|e| {
+            format!("Financial ledger is unreadable, so no transaction can be checked: {e:#}")
+        })?;
+        ledger.prune(now, cooling_off);
+
+        let spent = ledger.spent();
+        if spent + amount > self.config.max_per_day {
+            return Err(format!(
+                "Amount {amount:.2} {currency} would exceed the daily limit of {:.2} {currency} \
+                ({spent:.2} {currency} already spent in the last 24 hours)",
+                self.config.max_per_day
+            ));
+        }
+
+        let signature = call_signature(tool, args);
+        if self.config.cooling_off_secs > 0 {
+            let Some(first_seen) = ledger.pending.get(&signature).copied() else {
+                ledger.pending.insert(signature, now);
+                self.write_ledger(&ledger).map_err(|e| e.to_string())?;
+                return Err(format!(
+                    "Transaction of {amount:.2} {currency} queued for a {}s cooling-off period. \
+                    Confirm with the owner and repeat the same call with an '{OTP_CODE_ARG}' after {}",
+                    self.config.cooling_off_secs,
+                    (now + cooling_off).to_rfc3339()
+                ));
+            };
+            let ready_at = first_seen + cooling_off;
+            if now < ready_at {
+                return Err(format!(
+                    "Transaction is still cooling off; retry after {} ({}s remaining)",
+                    ready_at.to_rfc3339(),
+                    ready_at.signed_duration_since(now).num_seconds().max(1)
+                ));
+            }
+        }
+
+        let Some(otp) = &self.otp else {
+            return Err(
+                "Financial actions require OTP; enable [security.otp] to allow them".to_string(),
+            );
+        };
+        let code = args
+            .get(OTP_CODE_ARG)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        if code.is_empty() {
+            return Err(format!(
+                "Financial action requires a one-time code: ask the owner for the current code \
+                from their authenticator app and pass it as '{OTP_CODE_ARG}'"
+            ));
+        }
+        if ledger.used_codes.contains_key(code) {
+            return Err("OTP code was already used for a transaction; ask for a new one".into());
+        }
+        if !otp.validate(code).map_err(|e| e.to_string())? {
+            return Err("Invalid or expired OTP code".into());
+        }
+
+        let authorization = Authorization {
+            id: uuid::Uuid::new_v4().to_string(),
+            tool: tool.to_string(),
+            amount,
+        };
+        ledger.pending.remove(&signature);
+        ledger.used_codes.insert(code.to_string(), now);
+        ledger.executed.push(LedgerEntry {
+            id: authorization.id.clone(),
+            tool: authorization.tool.clone(),
+            amount,
+            at: now,
+        });
+        self.write_ledger(&ledger).map_err(|e| e.to_string())?;
+        Ok(authorization)
+    }
+
+    /// Record the outcome of an authorized call. Failed calls release their
+    /// reservation against the daily limit.
+    pub async fn complete(&self, authorization: &Authorization, success: bool, args: &Value) {
+        if !success {
+            let _guard = self.ledger_lock.lock().await;
+            let released = self.read_ledger().and_then(|mut ledger| {
+                ledger.executed.retain(|entry| entry.id != authorization.id);
+                self.write_ledger(&ledger)
+            });
+            if let Err(e) = released {
+                tracing::warn!("Failed to release financial reservation: {e:#}");
+            }
+        }
+        self.audit(
+            &authorization.tool,
+            if success { "executed" } else { "failed" },
+            Some(authorization.amount),
+            Some(&authorization.id),
+            None,
+            args,
+        );
+    }
+
+    /// A missing ledger is empty; any other read or parse failure is an
+    /// error, since resetting it would forget spent amounts and used codes.
+    fn read_ledger(&self) -> Result<Ledger> {
+        let raw = match std::fs::read_to_string(&self.ledger_path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Ledger::default()),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read {}", self.ledger_path.display()))
+            }
+        };
+        serde_json::from_str(&raw)
+            .with_context(|| format!("Failed to parse {}", self.ledger_path.display()))
+    }
+
+    fn write_ledger(&self, ledger: &Ledger) -> Result<()> {
+        if let Some(parent) = self.ledger_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.ledger_path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string(ledger)?)?;
+        std::fs::rename(&tmp, &self.ledger_path)?;
+        Ok(())
+    }
+
+    fn audit(
+        &self,
+        tool: &str,
+        decision: &str,
+        amount: Option<f64>,
+        transaction_id: Option<&str>,
+        reason: Option<&str>,
+        args: &Value,
+    ) {
+        let record = AuditRecord {
+            timestamp: Utc::now(),
+            tool,
+            decision,
+            amount,
+            currency: &self.config.currency,
+            transaction_id,
+            reason,
+            arguments: strip_unsigned_args(args),
+        };
+        if let Err(e) = self.append_audit(&record) {
+            tracing::warn!("Failed to write financial audit record: {e}");
+        }
+    }
+
+    fn append_audit(&self, record: &AuditRecord<'_>) -> Result<()> {
+        if let Some(parent) = self.audit_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.audit_path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        file.sync_all()?;
+        Ok(())
+    }
+}
+
+/// Copy of `args` without the OTP code and approval flag.
+pub fn strip_unsigned_args(args: &Value) -> Value {
+    let mut stripped = args.clone();
+    if let Some(object) = stripped.as_object_mut() {
+        for key in UNSIGNED_ARGS {
+            object.remove(key);
+        }
+    }
+    stripped
+}
+
+/// Stable identity of a call, used to match retries to their cooling-off entry.
+fn call_signature(tool: &str, args: &Value) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(tool.as_bytes());
+    hasher.update([0]);
+    hasher.update(strip_unsigned_args(args).to_string().as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::OtpConfig;
+    use crate::security::SecretStore;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn guard(tmp: &TempDir, cooling_off_secs: u64, with_otp: bool) -> FinancialGuard {
+        let otp = with_otp.then(|| {
+            let store = SecretStore::new(tmp.path(), true);
+            let config = OtpConfig {
+                enabled: true,
+                token_ttl_secs: 30,
+                cache_valid_secs: 120,
+                ..OtpConfig::default()
+            };
+            OtpValidator::from_config(&config, tmp.path(), &store)
+                .unwrap()
+                .0
+        });
+        FinancialGuard::new(
+            FinancialGuardrailConfig {
+                cooling_off_secs,
+                ..FinancialGuardrailConfig::default()
+            },
+            otp,
+            tmp.path(),
+        )
+    }
+
+    fn current_code(guard: &FinancialGuard) -> String {
+        let now = u64::try_from(Utc::now().timestamp()).unwrap();
+        guard.otp.as_ref().unwrap().code_for_timestamp(now)
+    }
+
+    #[tokio::test]
+    async fn enforces_amount_and_limits() {
+        let tmp = TempDir::new().unwrap();
+        let guard = guard(&tmp, 0, true);
+
+        let missing = guard.authorize("pay", &json!({})).await.unwrap_err();
+        assert!(missing.contains("'amount'"));
+        let currency = guard
+            .authorize("pay", &json!({"amount": 5, "currency": "EUR"}))
+            .await
+            .unwrap_err();
+        assert!(currency.contains("EUR"));
+        let too_big = guard
+            .authorize("pay", &json!({"amount": 75}))
+            .await
+            .unwrap_err();
+        assert!(too_big.contains("per-transaction limit"));
+
+        let mut ledger = Ledger::default();
+        ledger.executed.push(LedgerEntry {
+            id: "earlier".into(),
+            tool: "pay".into(),
+            amount: 180.0,
+            at: Utc::now(),
+        });
+        guard.write_ledger(&ledger).unwrap();
+        let over_day = guard
+            .authorize(
+                "pay",
+                &json!({"amount": 30, "otp_code": current_code(&guard)}),
+            )
+            .await
+            .unwrap_err();
+        assert!(over_day.contains("daily limit"));
+    }
+
+    #[tokio::test]
+    async fn cooling_off_then_otp_authorizes_once() {
+        let tmp = TempDir::new().unwrap();
+        let guard = guard(&tmp, 300, true);
+        let now = Utc::now();
+        let args = json!({"amount": 20, "to": "acct-1"});
+
+        let queued = guard.authorize_at("pay", &args, now).await.unwrap_err();
+        assert!(queued.contains("cooling-off"));
+        let early = guard
+            .authorize_at("pay", &args, now + Duration::seconds(60))
+            .await
+            .unwrap_err();
+        assert!(early.contains("still cooling off"));
+
+        let later = now + Duration::seconds(301);
+        let no_code = guard.authorize_at("pay", &args, later).await.unwrap_err();
+        assert!(no_code.contains(OTP_CODE_ARG));
+
+        let code = current_code(&guard);
+        let mut signed = args.clone();
+        signed[OTP_CODE_ARG] = json!(code);
+        let authorization = guard.authorize_at("pay", &signed, later).await.unwrap();
+        assert!((authorization.amount - 20.0).abs() < f64::EPSILON);
+        guard.complete(&authorization, true, &signed).await;
+
+        // The cooling-off entry and the OTP code are both consumed.
+        let repeat = guard.authorize_at("pay", &signed, later).await.unwrap_err();
+        assert!(repeat.contains("cooling-off"));
+
+        let audit = std::fs::read_to_string(tmp.path().join("financial-audit.jsonl")).unwrap();
+        let decisions: Vec<String> = audit
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap()["decision"].to_string())
+            .collect();
+        assert!(decisions.iter().any(|d| d.contains("authorized")));
+        assert!(decisions.iter().any(|d| d.contains("executed")));
+        assert!(!audit.contains(&code));
+    }
+
+    #[tokio::test]
+    async fn failed_call_releases_reservation_and_disabled_otp_denies() {
+        let tmp = TempDir::new().unwrap();
+        let guard = guard(&tmp, 0, true);
+        let args = json!({"amount": 50, "otp_code": current_code(&guard)});
+        let authorization = guard.authorize("pay", &args).await.unwrap();
+        assert!((guard.read_ledger().unwrap().spent() - 50.0).abs() < f64::EPSILON);
+        guard.complete(&authorization, false, &args).await;
+        assert!(guard.read_ledger().unwrap().spent().abs() < f64::EPSILON);
+
+        let no_otp = FinancialGuard::new(
+            FinancialGuardrailConfig {
+                cooling_off_secs: 0,
+                ..FinancialGuardrailConfig::default()
+            },
+            None,
+            tmp.path(),
+        );
+        let denied = no_otp
+            .authorize("pay", &json!({"amount": 1}))
+            .await
+            .unwrap_err();
+        assert!(denied.contains("[security.otp]"));
+    }
+
+    #[tokio::test]
+    async fn corrupt_ledger_denies_instead_of_resetting() {
+        let tmp = TempDir::new().unwrap();
+        let guard = guard(&tmp, 0, true);
+        assert!(guard.ledger_path.starts_with(tmp.path()));
+        assert!(!guard.ledger_path.starts_with(tmp.path().join("workspace")));
+        std::fs::write(&guard.ledger_path, "{ not json").unwrap();
+
+        let denied = guard
+            .authorize(
+                "pay",
+                &json!({"amount": 5, "otp_code": current_code(&guard)}),
+            )
+            .await
+            .unwrap_err();
+        assert!(denied.contains("ledger is unreadable"), "{denied}");
+    }
+}
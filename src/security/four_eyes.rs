@@ -0,0 +1,601 @@
+//! Second-person sign-off for high-risk tool calls (`[security.four_eyes]`).
+//!
+//! Tools listed in `[security.four_eyes].tools` only run once a configured
+//! approver other than the requester has said yes. Each call files a pending
+//! request under `approvals/<code>.json` next to `config.toml` (outside the
+//! workspace, which the agent's own tools can write), notifies every
+//! eligible approver on their own channel, and waits until one of them
+//! replies `/approve <code>` or `/deny <code>`, or until the timeout expires.
+//! Requests, decisions and timeouts are recorded in the security audit log.
+
+use crate::config::{AuditConfig, Config, FourEyesConfig};
+use crate::preferences::{Field, PreferenceStore};
+use crate::security::{AuditEvent, AuditEventType, AuditLogger};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::{Duration, Instant};
+
+/// How often a waiting call re-reads its pending request.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Characters of the call arguments shown to approvers.
+const MAX_SUMMARY_CHARS: usize = 300;
+/// Identity used for calls that do not come from a channel.
+const LOCAL_REQUESTER: &str = "cli";
+
+tokio::task_local! {
+    static REQUESTER: String;
+}
+
+/// Run `future` with `identity` (`channel:sender`) as the requester of any
+/// second-approval request it files.
+pub async fn with_requester<F: std::future::Future>(identity: String, future: F) -> F::Output {
+    REQUESTER.scope(identity, future).await
+}
+
+/// Identity the current task acts for: `channel:sender`, or `cli` outside channels.
+pub fn current_requester() -> String {
+    REQUESTER
+        .try_with(Clone::clone)
+        .unwrap_or_else(|_| LOCAL_REQUESTER.to_string())
+}
+
+/// Normalize a `channel:sender` identity; the channel part is case-insensitive.
+fn normalize_identity(identity: &str) -> String {
+    match identity.trim().split_once(':') {
+        Some((channel, sender)) => {
+            format!("{}:{}", channel.trim().to_ascii_lowercase(), sender.trim())
+        }
+        None => identity.trim().to_ascii_lowercase(),
+    }
+}
+
+/// Parse an approver's chat reply: `/approve <code>` or `/deny <code>`.
+///
+/// Returns whether the request is approved, and its code.
+pub fn parse_command(message: &str) -> Option<(bool, String)> {
+    let mut words = message.split_whitespace();
+    let approve = match words.next()? {
+        "/approve" => true,
+        "/deny" | "/reject" => false,
+        _ => return None,
+    };
+    let code = words.next()?.trim_start_matches('#');
+    if words.next().is_some() || code.is_empty() || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((approve, code.to_string()))
+}
+
+/// Final word on a pending request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Decision {
+    pub approved: bool,
+    pub by: String,
+    pub at: DateTime<Utc>,
+}
+
+/// A high-risk call waiting for a second person.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingApproval {
+    pub code: String,
+    pub tool: String,
+    pub summary: String,
+    pub requester: String,
+    pub approvers: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default)]
+    pub decision: Option<Decision>,
+}
+
+fn approvals_dir(zeroclaw_dir: &Path) -> PathBuf {
+    zeroclaw_dir.join("approvals")
+}
+
+fn read_pending(path: &Path) -> Option<PendingApproval> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+}
+
+fn write_pending(path: &Path, pending: &PendingApproval) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(pending)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn summarize_args(args: &Value) -> String {
+    let raw = args.to_string();
+    if raw.chars().count() <= MAX_SUMMARY_CHARS {
+        raw
+    } else {
+        let truncated: String = raw.chars().take(MAX_SUMMARY_CHARS).collect();
+        format!("{truncated}…")
+    }
+}
+
+/// Enforces second-person sign-off for the tools named in `[security.four_eyes]`.
+pub struct FourEyesGuard {
+    config: FourEyesConfig,
+    audit: AuditConfig,
+    /// Full config, needed to deliver notifications through channels.
+    root: Arc<Config>,
+    zeroclaw_dir: PathBuf,
+}
+
+static GUARD: LazyLock<RwLock<Option<Arc<FourEyesGuard>>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Initialize (or disable) the process-wide guard used to record decisions.
+pub fn init_from_config(config: &Config) {
+    let guard = config
+        .security
+        .four_eyes
+        .enabled
+        .then(|| Arc::new(FourEyesGuard::from_config(config)));
+    *GUARD.write().unwrap_or_else(|e| e.into_inner()) = guard;
+}
+
+/// The process-wide guard, when `[security.four_eyes]` is enabled.
+pub fn current() -> Option<Arc<FourEyesGuard>> {
+    GUARD.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+impl FourEyesGuard {
+    pub fn from_config(config: &Config) -> Self {
+        let zeroclaw_dir = config
+            .config_path
+            .parent()
+            .unwrap_or(&config.workspace_dir)
+            .to_path_buf();
+        Self {
+            config: config.security.four_eyes.clone(),
+            audit: config.security.audit.clone(),
+            root: Arc::new(config.clone()),
+            zeroclaw_dir,
+        }
+    }
+
+    /// Whether a tool needs a second approval.
+    pub fn covers(&self, tool_name: &str) -> bool {
+        self.config.enabled && self.config.tools.iter().any(|name| name == tool_name)
+    }
+
+    /// Seconds a call waits for an approver.
+    pub fn timeout_secs(&self) -> u64 {
+        self.config.timeout_secs
+    }
+
+    fn pending_path(&self, code: &str) -> PathBuf {
+        approvals_dir(&self.zeroclaw_dir).join(format!("{code}.json"))
+    }
+
+    /// Requests still waiting for a decision, oldest first.
+    pub fn pending(&self) -> Vec<PendingApproval> {
+        let Ok(entries) = std::fs::read_dir(approvals_dir(&self.zeroclaw_dir)) else {
+            return Vec::new();
+        };
+        let now = Utc::now();
+        let mut pending: Vec<PendingApproval> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                read_pending(&path)
+            })
+            .filter(|pending| pending.decision.is_none() && pending.expires_at > now)
+            .collect();
+        pending.sort_by_key(|pending| pending.created_at);
+        pending
+    }
+
+    fn is_approver(&self, identity: &str) -> bool {
+        self.config
+            .approvers
+            .iter()
+            .any(|approver| normalize_identity(approver) == identity)
+    }
+
+    /// Ask a second person to sign off on a call and wait for the answer.
+    ///
+    /// Returns the approving identity; the error is a user-facing explanation
+    /// of why the call may not run.
+    pub async fn authorize(&self, tool: &str, args: &Value) -> Result<String, String> {
+        let requester = normalize_identity(&current_requester());
+        let approvers: Vec<String> = self
+            .config
+            .approvers
+            .iter()
+            .map(|approver| normalize_identity(approver))
+            .filter(|approver| *approver != requester)
+            .collect();
+        if approvers.is_empty() {
+            let reason = format!(
+                "'{tool}' needs sign-off from a second person, but no approver other than \
+                {requester} is configured in [security.four_eyes]"
+            );
+            self.audit(&requester, tool, "refused", None, false, Some(&reason));
+            return Err(reason);
+        }
+
+        let pending = self.file_request(tool, args, &requester, approvers)?;
+        self.audit(
+            &requester,
+            tool,
+            "requested",
+            Some(&pending.code),
+            false,
+            None,
+        );
+        if !self.notify(&pending).await {
+            let _ = std::fs::remove_file(self.pending_path(&pending.code));
+            let reason =
+                format!("'{tool}' needs a second approval, but no approver could be notified");
+            self.audit(
+                &requester,
+                tool,
+                "refused",
+                Some(&pending.code),
+                false,
+                Some(&reason),
+            );
+            return Err(reason);
+        }
+        self.wait(&pending).await
+    }
+
+    fn file_request(
+        &self,
+        tool: &str,
+        args: &Value,
+        requester: &str,
+        approvers: Vec<String>,
+    ) -> Result<PendingApproval, String> {
+        let now = Utc::now();
+        let timeout =
+            chrono::Duration::seconds(i64::try_from(self.config.timeout_secs).unwrap_or(0));
+        let code = loop {
+            let code = format!("{:06}", rand::random::<u32>() % 1_000_000);
+            if !self.pending_path(&code).exists() {
+                break code;
+            }
+        };
+        let pending = PendingApproval {
+            code,
+            tool: tool.to_string(),
+            summary: summarize_args(args),
+            requester: requester.to_string(),
+            approvers,
+            created_at: now,
+            expires_at: now + timeout,
+            decision: None,
+        };
+        write_pending(&self.pending_path(&pending.code), &pending)
+            .map_err(|e| format!("Failed to file approval request for '{tool}': {e}"))?;
+        Ok(pending)
+    }
+
+    /// Request text in the approver's preferred language, falling back to
+    /// the `[ui]` locale.
+    fn approval_message(&self, pending: &PendingApproval, channel: &str, target: &str) -> String {
+        let preferences = PreferenceStore::for_workspace(&self.root.workspace_dir);
+        let language = preferences
+            .get(&preferences.identity_for(channel, target))
+            .ok()
+            .and_then(|profile| profile.get(Field::Language).map(str::to_string));
+        let seconds = self.config.timeout_secs.to_string();
+        crate::i18n::tr_args_in(
+            language.as_deref(),
+            "approval.four_eyes_needed",
+            &[
+                ("requester", &pending.requester),
+                ("tool", &pending.tool),
+                ("args", &pending.summary),
+                ("code", &pending.code),
+                ("seconds", &seconds),
+            ],
+        )
+    }
+
+    /// Message each approver separately; true when at least one was reached.
+    async fn notify(&self, pending: &PendingApproval) -> bool {
+        let mut reached = false;
+        for approver in &pending.approvers {
+            let Some((channel, target)) = approver.split_once(':') else {
+                tracing::warn!("Four-eyes approver '{approver}' is not a channel:sender identity");
+                continue;
+            };
+            let message = self.approval_message(pending, channel, target);
+            match crate::cron::scheduler::send_announcement(&self.root, channel, target, &message)
+                .await
+            {
+                Ok(()) => reached = true,
+                Err(e) => tracing::warn!("Failed to notify approver {approver}: {e}"),
+            }
+        }
+        reached
+    }
+
+    /// The decision is only trusted when it names one of the approvers this
+    /// process filed the request for, so a hand-edited file cannot sign off.
+    async fn wait(&self, pending: &PendingApproval) -> Result<String, String> {
+        let path = self.pending_path(&pending.code);
+        let deadline = Instant::now() + Duration::from_secs(self.config.timeout_secs);
+        loop {
+            let decision = read_pending(&path).and_then(|current| current.decision);
+            if let Some(decision) = decision {
+                let _ = std::fs::remove_file(&path);
+                let by = normalize_identity(&decision.by);
+                if by == pending.requester || !pending.approvers.contains(&by) {
+                    let reason = format!(
+                        "'{}' was decided by {by}, who is not an approver of request {}; it did not run",
+                        pending.tool, pending.code
+                    );
+                    self.audit(
+                        &pending.requester,
+                        &pending.tool,
+                        "refused",
+                        Some(&pending.code),
+                        false,
+                        Some(&reason),
+                    );
+                    return Err(reason);
+                }
+                return if decision.approved {
+                    Ok(decision.by)
+                } else {
+                    Err(format!(
+                        "'{}' was denied by {} (request {})",
+                        pending.tool, decision.by, pending.code
+                    ))
+                };
+            }
+            if Instant::now() >= deadline {
+                let _ = std::fs::remove_file(&path);
+                let reason = format!(
+                    "'{}' was not approved within {}s (request {}); it did not run",
+                    pending.tool, self.config.timeout_secs, pending.code
+                );
+                self.audit(
+                    &pending.requester,
+                    &pending.tool,
+                    "timed_out",
+                    Some(&pending.code),
+                    false,
+                    Some(&reason),
+                );
+                return Err(reason);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Record an approver's answer to a pending request.
+    ///
+    /// `approver` is the replying `channel:sender`; it must be one of the
+    /// request's approvers, which never include the requester.
+    pub fn decide(&self, code: &str, approver: &str, approved: bool) -> Result<PendingApproval> {
+        let approver = normalize_identity(approver);
+        if !self.is_approver(&approver) {
+            bail!("{approver} is not a configured approver");
+        }
+        let path = self.pending_path(code);
+        let Some(mut pending) = read_pending(&path) else {
+            bail!("No pending approval request {code}");
+        };
+        if pending.requester == approver || !pending.approvers.contains(&approver) {
+            bail!("Request {code} must be signed off by someone other than its requester");
+        }
+        if let Some(decision) = &pending.decision {
+            bail!("Request {code} was already decided by {}", decision.by);
+        }
+        let now = Utc::now();
+        if now >= pending.expires_at {
+            bail!("Request {code} has expired");
+        }
+        pending.decision = Some(Decision {
+            approved,
+            by: approver.clone(),
+            at: now,
+        });
+        write_pending(&path, &pending)?;
+        self.audit(
+            &approver,
+            &pending.tool,
+            if approved { "approved" } else { "denied" },
+            Some(code),
+            approved,
+            None,
+        );
+        Ok(pending)
+    }
+
+    fn audit(
+        &self,
+        actor: &str,
+        tool: &str,
+        decision: &str,
+        code: Option<&str>,
+        approved: bool,
+        reason: Option<&str>,
+    ) {
+        let (channel, user) = actor.split_once(':').unwrap_or((actor, ""));
+        let command = match code {
+            Some(code) => format!("four_eyes {decision} {tool} (request {code})"),
+            None => format!("four_eyes {decision} {tool}"),
+        };
+        let event = AuditEvent::new(AuditEventType::SecondApproval)
+            .with_actor(
+                channel.to_string(),
+                (!user.is_empty()).then(|| user.to_string()),
+                None,
+            )
+            .with_action(command, "high".into(), approved, approved)
+            .with_result(reason.is_none(), None, 0, reason.map(str::to_string));
+        let logged = AuditLogger::new(self.audit.clone(), self.zeroclaw_dir.clone())
+            .and_then(|logger| logger.log(&event));
+        if let Err(e) = logged {
+            tracing::warn!("Failed to write four-eyes audit event: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn guard(tmp: &TempDir, timeout_secs: u64) -> FourEyesGuard {
+        let mut config = Config::default();
+        config.workspace_dir = tmp.path().join("workspace");
+        config.config_path = tmp.path().join("config.toml");
+        config.security.audit.enabled = true;
+        config.security.four_eyes = FourEyesConfig {
+            enabled: true,
+            tools: vec!["shell".into()],
+            approvers: vec!["Telegram:alice".into(), "slack:bob".into()],
+            timeout_secs,
+        };
+        FourEyesGuard::from_config(&config)
+    }
+
+    #[test]
+    fn parse_command_accepts_approve_and_deny_with_codes() {
+        assert_eq!(
+            parse_command("/approve 012345"),
+            Some((true, "012345".to_string()))
+        );
+        assert_eq!(parse_command("/deny #42"), Some((false, "42".to_string())));
+        assert_eq!(parse_command("/approve"), None);
+        assert_eq!(parse_command("/approve all of it"), None);
+        assert_eq!(parse_command("approve 12"), None);
+    }
+
+    #[test]
+    fn approval_message_uses_the_approvers_language() {
+        let tmp = TempDir::new().unwrap();
+        let guard = guard(&tmp, 60);
+        PreferenceStore::for_workspace(&guard.root.workspace_dir)
+            .set("slack:bob", Field::Language, "de")
+            .unwrap();
+        let now = Utc::now();
+        let pending = PendingApproval {
+            code: "123456".into(),
+            tool: "shell".into(),
+            summary: "{\"command\":\"ls\"}".into(),
+            requester: "cli".into(),
+            approvers: vec!["telegram:alice".into(), "slack:bob".into()],
+            created_at: now,
+            expires_at: now,
+            decision: None,
+        };
+
+        let german = guard.approval_message(&pending, "slack", "bob");
+        assert!(german.contains("Freigabe erforderlich"), "{german}");
+        assert!(german.contains("/approve 123456"));
+        assert!(german.contains("60 s"));
+        let default = guard.approval_message(&pending, "telegram", "alice");
+        assert!(!default.contains("Freigabe"), "{default}");
+        assert!(default.contains("/deny 123456"));
+    }
+
+    #[tokio::test]
+    async fn requester_cannot_sign_off_and_other_approver_can() {
+        let tmp = TempDir::new().unwrap();
+        let guard = guard(&tmp, 60);
+        let pending = guard
+            .file_request(
+                "shell",
+                &serde_json::json!({"command": "rm -rf build"}),
+                "telegram:alice",
+                vec!["slack:bob".into()],
+            )
+            .unwrap();
+
+        let err = guard
+            .decide(&pending.code, "telegram:alice", true)
+            .unwrap_err();
+        assert!(err.to_string().contains("someone other than its requester"));
+        assert!(guard.decide(&pending.code, "slack:mallory", true).is_err());
+        assert_eq!(guard.pending().len(), 1);
+
+        guard.decide(&pending.code, "Slack:bob", true).unwrap();
+        assert!(guard.pending().is_empty());
+        assert!(guard.decide(&pending.code, "slack:bob", false).is_err());
+        assert_eq!(guard.wait(&pending).await, Ok("slack:bob".to_string()));
+        assert!(!guard.pending_path(&pending.code).exists());
+
+        let audit = std::fs::read_to_string(tmp.path().join("audit.log")).unwrap();
+        assert!(audit.contains("second_approval"));
+        assert!(audit.contains("four_eyes approved shell"));
+    }
+
+    #[tokio::test]
+    async fn forged_decision_is_refused() {
+        let tmp = TempDir::new().unwrap();
+        let guard = guard(&tmp, 60);
+        let pending = guard
+            .file_request(
+                "shell",
+                &serde_json::json!({}),
+                "telegram:alice",
+                vec!["slack:bob".into()],
+            )
+            .unwrap();
+        let path = guard.pending_path(&pending.code);
+        assert!(path.starts_with(tmp.path().join("approvals")));
+
+        for by in ["slack:mallory", "telegram:alice"] {
+            let mut forged = pending.clone();
+            forged.approvers.push(by.into());
+            forged.decision = Some(Decision {
+                approved: true,
+                by: by.into(),
+                at: Utc::now(),
+            });
+            write_pending(&path, &forged).unwrap();
+            let err = guard.wait(&pending).await.unwrap_err();
+            assert!(err.contains("not an approver"), "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn unanswered_request_times_out_and_is_refused() {
+        let tmp = TempDir::new().unwrap();
+        let guard = guard(&tmp, 0);
+        let pending = guard
+            .file_request(
+                "shell",
+                &serde_json::json!({}),
+                "cli",
+                vec!["slack:bob".into()],
+            )
+            .unwrap();
+
+        let err = guard.wait(&pending).await.unwrap_err();
+        assert!(err.contains("not approved within 0s"));
+        assert!(guard.decide(&pending.code, "slack:bob", true).is_err());
+    }
+
+    #[tokio::test]
+    async fn lone_approver_cannot_approve_own_request() {
+        let tmp = TempDir::new().unwrap();
+        let mut guard = guard(&tmp, 60);
+        guard.config.approvers = vec!["telegram:alice".into()];
+        let err = with_requester(
+            "telegram:alice".into(),
+            guard.authorize("shell", &serde_json::json!({})),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("no approver other than telegram:alice"));
+    }
+}
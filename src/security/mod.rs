@@ -25,14 +25,20 @@ pub mod detect;
 pub mod docker;
 pub mod domain_matcher;
 pub mod estop;
+pub mod financial;
 #[cfg(target_os = "linux")]
 pub mod firejail;
+pub mod four_eyes;
 #[cfg(feature = "sandbox-landlock")]
 pub mod landlock;
+pub mod network;
 pub mod otp;
 pub mod pairing;
 pub mod policy;
+pub mod policy_engine;
+pub mod rotation;
 pub mod secrets;
+pub mod shell_dialect;
 pub mod traits;
 
 #[allow(unused_imports)]
@@ -43,12 +49,16 @@ pub use domain_matcher::DomainMatcher;
 #[allow(unused_imports)]
 pub use estop::{EstopLevel, EstopManager, EstopState, ResumeSelector};
 #[allow(unused_imports)]
+pub use financial::FinancialGuard;
+pub use network::NetworkGuard;
+#[allow(unused_imports)]
 pub use otp::OtpValidator;
 #[allow(unused_imports)]
 pub use pairing::PairingGuard;
 pub use policy::{AutonomyLevel, SecurityPolicy};
 #[allow(unused_imports)]
 pub use secrets::SecretStore;
+pub use shell_dialect::ShellDialect;
 #[allow(unused_imports)]
 pub use traits::{NoopSandbox, Sandbox};
 
@@ -0,0 +1,353 @@
+//! Per-tool outbound network policy with pinned DNS resolution.
+//!
+//! [`NetworkGuard`] checks a destination against a tool's
+//! `[security.network.<tool>]` policy (domains, ports, IP ranges), resolves
+//! the host once and rejects the destination if any answer is disallowed.
+//! HTTP tools then pin the checked answers into their client
+//! ([`NetworkGuard::pin`]), so the connection cannot be re-resolved to a
+//! different — e.g. internal — address after the check (DNS rebinding).
+
+use crate::config::ToolNetworkPolicyConfig;
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// A CIDR range such as `10.0.0.0/8` or `2001:db8::/32`. A bare address is
+/// treated as a single-host range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpRange {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl IpRange {
+    pub fn parse(raw: &str) -> Result<Self> {
+        let raw = raw.trim();
+        let (addr, prefix) = match raw.split_once('/') {
+            Some((addr, prefix)) => (addr.trim(), Some(prefix.trim())),
+            None => (raw, None),
+        };
+        let network: IpAddr = addr
+            .parse()
+            .with_context(|| format!("'{addr}' is not an IP address"))?;
+        let max = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= max)
+                .with_context(|| format!("prefix length must be 0-{max}, got '{prefix}'"))?,
+            None => max,
+        };
+        Ok(Self { network, prefix })
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+            IpAddr::V4(_) => ip,
+        };
+        match (self.network, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                prefix_matches(u32::from(net).into(), u32::from(ip).into(), self.prefix, 32)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                prefix_matches(u128::from(net), u128::from(ip), self.prefix, 128)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prefix_matches(network: u128, ip: u128, prefix: u8, bits: u8) -> bool {
+    if prefix == 0 {
+        return true;
+    }
+    let shift = u32::from(bits - prefix);
+    (network >> shift) == (ip >> shift)
+}
+
+/// Network policy enforced for one tool's outbound connections.
+///
+/// Without a configured policy the guard still refuses destinations that
+/// resolve to non-global addresses.
+#[derive(Debug, Clone)]
+pub struct NetworkGuard {
+    tool: String,
+    allowed_domains: Vec<String>,
+    /// `None` when no ranges are configured (any global address is allowed).
+    allowed_ranges: Option<Vec<IpRange>>,
+    allowed_ports: Vec<u16>,
+}
+
+impl NetworkGuard {
+    /// Guard with no policy beyond blocking non-global addresses.
+    pub fn new(tool: &str) -> Self {
+        Self {
+            tool: tool.to_string(),
+            allowed_domains: Vec::new(),
+            allowed_ranges: None,
+            allowed_ports: Vec::new(),
+        }
+    }
+
+    /// Guard for `tool` built from `[security.network]`.
+    pub fn for_tool(policies: &HashMap<String, ToolNetworkPolicyConfig>, tool: &str) -> Self {
+        let Some(policy) = policies.get(tool) else {
+            return Self::new(tool);
+        };
+        // An invalid range is dropped but still counts as configured, so a
+        // typo narrows access instead of lifting the range restriction.
+        let allowed_ranges = (!policy.allowed_ip_ranges.is_empty()).then(|| {
+            policy
+                .allowed_ip_ranges
+                .iter()
+                .filter_map(|raw| match IpRange::parse(raw) {
+                    Ok(range) => Some(range),
+                    Err(e) => {
+                        tracing::warn!(tool, "Ignoring invalid network range '{raw}': {e}");
+                        None
+                    }
+                })
+                .collect()
+        });
+        Self {
+            tool: tool.to_string(),
+            allowed_domains: policy
+                .allowed_domains
+                .iter()
+                .map(|domain| {
+                    domain
+                        .trim()
+                        .trim_start_matches("*.")
+                        .trim_end_matches('.')
+                        .to_ascii_lowercase()
+                })
+                .filter(|domain| !domain.is_empty())
+                .collect(),
+            allowed_ranges,
+            allowed_ports: policy.allowed_ports.clone(),
+        }
+    }
+
+    /// Check the URL's host and port against the policy and return them.
+    pub fn check_url(&self, url: &str) -> Result<(String, u16)> {
+        let parsed = reqwest::Url::parse(url).with_context(|| format!("Invalid URL: {url}"))?;
+        let host = parsed
+            .host_str()
+            .context("URL must include a host")?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .trim_end_matches('.')
+            .to_ascii_lowercase();
+        let port = parsed
+            .port_or_known_default()
+            .context("URL must use http or https")?;
+
+        if !self.allowed_domains.is_empty()
+            && !self.allowed_domains.iter().any(|domain| {
+                domain == "*"
+                    || host == *domain
+                    || host
+                        .strip_suffix(domain.as_str())
+                        .is_some_and(|prefix| prefix.ends_with('.'))
+            })
+        {
+            bail!(
+                "Host '{host}' is not in security.network.{}.allowed_domains",
+                self.tool
+            );
+        }
+        if !self.allowed_ports.is_empty() && !self.allowed_ports.contains(&port) {
+            bail!(
+                "Port {port} is not in security.network.{}.allowed_ports",
+                self.tool
+            );
+        }
+        Ok((host, port))
+    }
+
+    /// Check a resolved address against the policy.
+    pub fn check_ip(&self, ip: IpAddr) -> Result<()> {
+        match &self.allowed_ranges {
+            Some(ranges) if ranges.iter().any(|range| range.contains(ip)) => Ok(()),
+            Some(_) => bail!(
+                "Address {ip} is outside security.network.{}.allowed_ip_ranges",
+                self.tool
+            ),
+            None if is_non_global_ip(ip) => bail!("Blocked local/private address {ip}"),
+            None => Ok(()),
+        }
+    }
+
+    /// Resolve `host` once; fails unless every answer passes [`Self::check_ip`].
+    pub async fn resolve(&self, host: &str, port: u16) -> Result<Vec<SocketAddr>> {
+        let addrs: Vec<SocketAddr> = match host.parse::<IpAddr>() {
+            Ok(ip) => vec![SocketAddr::new(ip, port)],
+            Err(_) => tokio::net::lookup_host((host, port))
+                .await
+                .with_context(|| format!("DNS lookup failed for {host}"))?
+                .collect(),
+        };
+        if addrs.is_empty() {
+            bail!("DNS lookup returned no addresses for {host}");
+        }
+        for addr in &addrs {
+            self.check_ip(addr.ip())
+                .with_context(|| format!("{host} resolves to a disallowed address"))?;
+        }
+        Ok(addrs)
+    }
+
+    /// Check and resolve `url`, returning the host and its checked addresses.
+    pub async fn resolve_url(&self, url: &str) -> Result<(String, Vec<SocketAddr>)> {
+        let (host, port) = self.check_url(url)?;
+        let addrs = self.resolve(&host, port).await?;
+        Ok((host, addrs))
+    }
+
+    /// Check and resolve `url`, then pin the checked addresses into
+    /// `builder` so the connection uses exactly those answers.
+    ///
+    /// When a proxy applies, the proxy resolves the target itself; the check
+    /// still runs but the pin only governs direct connections.
+    pub async fn pin(
+        &self,
+        url: &str,
+        builder: reqwest::ClientBuilder,
+    ) -> Result<reqwest::ClientBuilder> {
+        let (host, addrs) = self.resolve_url(url).await?;
+        if host.parse::<IpAddr>().is_ok() {
+            return Ok(builder);
+        }
+        Ok(builder.resolve_to_addrs(&host, &addrs))
+    }
+}
+
+/// Returns true if the address is not globally routable.
+pub fn is_non_global_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => is_non_global_v4(v4),
+        IpAddr::V6(v6) => is_non_global_v6(v6),
+    }
+}
+
+/// Returns true if the IPv4 address is not globally routable.
+pub fn is_non_global_v4(v4: Ipv4Addr) -> bool {
+    let [a, b, c, _] = v4.octets();
+    v4.is_loopback()                       // 127.0.0.0/8
+        || v4.is_private()                 // 10/8, 172.16/12, 192.168/16
+        || v4.is_link_local()              // 169.254.0.0/16
+        || v4.is_unspecified()             // 0.0.0.0
+        || v4.is_broadcast()              // 255.255.255.255
+        || v4.is_multicast()              // 224.0.0.0/4
+        || (a == 100 && (64..=127).contains(&b)) // Shared address space (RFC 6598)
+        || a >= 240                        // Reserved (240.0.0.0/4, except broadcast)
+        || (a == 192 && b == 0 && (c == 0 || c == 2)) // IETF assignments + TEST-NET-1
+        || (a == 198 && b == 51)           // Documentation (198.51.100.0/24)
+        || (a == 203 && b == 0)            // Documentation (203.0.113.0/24)
+        || (a == 198 && (18..=19).contains(&b)) // Benchmarking (198.18.0.0/15)
+}
+
+/// Returns true if the IPv6 address is not globally routable.
+pub fn is_non_global_v6(v6: Ipv6Addr) -> bool {
+    let segs = v6.segments();
+    v6.is_loopback()                       // ::1
+        || v6.is_unspecified()             // ::
+        || v6.is_multicast()              // ff00::/8
+        || (segs[0] & 0xfe00) == 0xfc00   // Unique-local (fc00::/7)
+        || (segs[0] & 0xffc0) == 0xfe80   // Link-local (fe80::/10)
+        || (segs[0] == 0x2001 && segs[1] == 0x0db8) // Documentation (2001:db8::/32)
+        || v6.to_ipv4_mapped().is_some_and(is_non_global_v4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard(domains: &[&str], ranges: &[&str], ports: &[u16]) -> NetworkGuard {
+        let mut policies = HashMap::new();
+        policies.insert(
+            "http_request".to_string(),
+            ToolNetworkPolicyConfig {
+                allowed_domains: domains.iter().map(ToString::to_string).collect(),
+                allowed_ip_ranges: ranges.iter().map(ToString::to_string).collect(),
+                allowed_ports: ports.to_vec(),
+            },
+        );
+        NetworkGuard::for_tool(&policies, "http_request")
+    }
+
+    #[test]
+    fn ip_range_parses_and_matches_cidrs() {
+        let v4 = IpRange::parse("10.1.0.0/16").unwrap();
+        assert!(v4.contains("10.1.255.3".parse().unwrap()));
+        assert!(!v4.contains("10.2.0.1".parse().unwrap()));
+        assert!(v4.contains("::ffff:10.1.0.9".parse().unwrap()));
+
+        let v6 = IpRange::parse("2001:db8::/32").unwrap();
+        assert!(v6.contains("2001:db8:1::1".parse().unwrap()));
+        assert!(!v6.contains("10.1.0.1".parse().unwrap()));
+
+        assert!(IpRange::parse("0.0.0.0/0")
+            .unwrap()
+            .contains("8.8.8.8".parse().unwrap()));
+        assert!(IpRange::parse("192.0.2.7")
+            .unwrap()
+            .contains("192.0.2.7".parse().unwrap()));
+        assert!(IpRange::parse("10.0.0.0/33").is_err());
+        assert!(IpRange::parse("example.com/8").is_err());
+    }
+
+    #[test]
+    fn check_url_enforces_domains_and_ports() {
+        let guard = guard(&["*.example.com"], &[], &[443]);
+        assert_eq!(
+            guard.check_url("https://api.example.com/v1").unwrap(),
+            ("api.example.com".to_string(), 443)
+        );
+        assert!(guard
+            .check_url("https://other.org/")
+            .unwrap_err()
+            .to_string()
+            .contains("allowed_domains"));
+        assert!(guard
+            .check_url("http://api.example.com/")
+            .unwrap_err()
+            .to_string()
+            .contains("allowed_ports"));
+        assert!(guard.check_url("https://example.com:8443/").is_err());
+    }
+
+    #[test]
+    fn check_ip_blocks_private_unless_range_allows_it() {
+        let default = NetworkGuard::new("browser");
+        assert!(default.check_ip("10.0.0.5".parse().unwrap()).is_err());
+        assert!(default
+            .check_ip("::ffff:127.0.0.1".parse().unwrap())
+            .is_err());
+        assert!(default.check_ip("93.184.216.34".parse().unwrap()).is_ok());
+
+        let internal = guard(&[], &["10.0.0.0/8"], &[]);
+        assert!(internal.check_ip("10.0.0.5".parse().unwrap()).is_ok());
+        assert!(internal.check_ip("93.184.216.34".parse().unwrap()).is_err());
+
+        let typo = guard(&[], &["10.0.0.0/99"], &[]);
+        assert!(typo.check_ip("93.184.216.34".parse().unwrap()).is_err());
+    }
+
+    #[tokio::test]
+    async fn resolve_rejects_names_that_point_at_local_addresses() {
+        let default = NetworkGuard::new("fetch_url");
+        let err = default.resolve("localhost", 80).await.unwrap_err();
+        assert!(format!("{err:#}").contains("disallowed address"));
+
+        let loopback = guard(&[], &["127.0.0.0/8"], &[]);
+        let addrs = loopback
+            .resolve_url("http://127.0.0.1:8080/")
+            .await
+            .unwrap()
+            .1;
+        assert_eq!(addrs, vec!["127.0.0.1:8080".parse().unwrap()]);
+    }
+}
@@ -1,6 +1,8 @@
+use super::shell_dialect::{has_drive_prefix, normalize_windows_path, ShellDialect};
 use parking_lot::Mutex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
@@ -91,6 +93,8 @@ pub struct SecurityPolicy {
     pub require_approval_for_medium_risk: bool,
     pub block_high_risk_commands: bool,
     pub shell_env_passthrough: Vec<String>,
+    /// Shell syntax commands and paths are checked against.
+    pub shell_dialect: ShellDialect,
     pub tracker: ActionTracker,
 }
 
@@ -136,20 +140,26 @@ impl Default for SecurityPolicy {
                 "~/.gnupg".into(),
                 "~/.aws".into(),
                 "~/.config".into(),
-            ],
+            ]
+            .into_iter()
+            .chain(ShellDialect::host().system_paths())
+            .collect(),
             allowed_roots: Vec::new(),
             max_actions_per_hour: 20,
             max_cost_per_day_cents: 500,
             require_approval_for_medium_risk: true,
             block_high_risk_commands: true,
             shell_env_passthrough: vec![],
+            shell_dialect: ShellDialect::host(),
             tracker: ActionTracker::new(),
         }
     }
 }
 
 fn home_dir() -> Option<PathBuf> {
-    std::env::var_os("HOME").map(PathBuf::from)
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
 }
 
 fn expand_user_path(path: &str) -> PathBuf {
@@ -159,7 +169,7 @@ fn expand_user_path(path: &str) -> PathBuf {
         }
     }
 
-    if let Some(stripped) = path.strip_prefix("~/") {
+    if let Some(stripped) = path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\")) {
         if let Some(home) = home_dir() {
             return home.join(stripped);
         }
@@ -477,6 +487,27 @@ fn contains_unquoted_shell_variable_expansion(command: &str) -> bool {
     false
 }
 
+/// Detect `cmd.exe` variable expansions like `%USERPROFILE%` or `%~dp0`.
+/// `cmd` expands them inside double quotes too, so quoting is ignored.
+fn contains_cmd_variable_expansion(command: &str) -> bool {
+    let mut rest = command;
+    while let Some(start) = rest.find('%') {
+        let after = &rest[start + 1..];
+        if after.starts_with('~') {
+            return true;
+        }
+        let Some(end) = after.find('%') else {
+            return false;
+        };
+        let name = &after[..end];
+        if !name.is_empty() && !name.contains(char::is_whitespace) {
+            return true;
+        }
+        rest = &after[end..];
+    }
+    false
+}
+
 fn strip_wrapping_quotes(token: &str) -> &str {
     token.trim_matches(|c| c == '"' || c == '\'')
 }
@@ -522,6 +553,100 @@ fn redirection_target(token: &str) -> Option<&str> {
     }
 }
 
+/// Windows commands that delete data, change the system, run other code or
+/// reach the network (cmd built-ins, system tools and PowerShell cmdlets
+/// with their aliases).
+fn is_high_risk_windows_command(base: &str) -> bool {
+    matches!(
+        base,
+        "del"
+            | "erase"
+            | "rd"
+            | "rmdir"
+            | "format"
+            | "diskpart"
+            | "reg"
+            | "regedit"
+            | "bcdedit"
+            | "takeown"
+            | "icacls"
+            | "cacls"
+            | "net"
+            | "netsh"
+            | "sc"
+            | "schtasks"
+            | "runas"
+            | "taskkill"
+            | "wmic"
+            | "certutil"
+            | "bitsadmin"
+            | "mshta"
+            | "rundll32"
+            | "regsvr32"
+            | "cscript"
+            | "wscript"
+            | "cmd"
+            | "powershell"
+            | "pwsh"
+            | "remove-item"
+            | "ri"
+            | "clear-content"
+            | "format-volume"
+            | "clear-disk"
+            | "stop-computer"
+            | "restart-computer"
+            | "stop-process"
+            | "kill"
+            | "invoke-webrequest"
+            | "iwr"
+            | "invoke-restmethod"
+            | "irm"
+            | "start-bitstransfer"
+            | "invoke-expression"
+            | "iex"
+            | "invoke-command"
+            | "icm"
+            | "start-process"
+            | "saps"
+            | "set-executionpolicy"
+            | "set-acl"
+            | "new-service"
+            | "set-itemproperty"
+            | "new-itemproperty"
+            | "remove-itemproperty"
+    )
+}
+
+/// Windows commands that change files but are not inherently destructive.
+fn is_medium_risk_windows_command(base: &str) -> bool {
+    matches!(
+        base,
+        "copy"
+            | "xcopy"
+            | "robocopy"
+            | "move"
+            | "ren"
+            | "rename"
+            | "md"
+            | "mklink"
+            | "attrib"
+            | "copy-item"
+            | "cpi"
+            | "move-item"
+            | "mi"
+            | "rename-item"
+            | "rni"
+            | "new-item"
+            | "ni"
+            | "set-content"
+            | "add-content"
+            | "ac"
+            | "out-file"
+            | "expand-archive"
+            | "compress-archive"
+    )
+}
+
 impl SecurityPolicy {
     // ── Risk Classification ──────────────────────────────────────────────
     // Risk is assessed per-segment (split on shell operators), and the
@@ -531,18 +656,18 @@ impl SecurityPolicy {
     /// Classify command risk. Any high-risk segment marks the whole command high.
     pub fn command_risk_level(&self, command: &str) -> CommandRiskLevel {
         let mut saw_medium = false;
+        let command = self.lexable(command);
 
-        for segment in split_unquoted_segments(command) {
+        for segment in split_unquoted_segments(&command) {
             let cmd_part = skip_env_assignments(&segment);
             let mut words = cmd_part.split_whitespace();
             let Some(base_raw) = words.next() else {
                 continue;
             };
 
-            let base = base_raw
-                .rsplit('/')
-                .next()
-                .unwrap_or("")
+            let base = self
+                .shell_dialect
+                .command_name(base_raw)
                 .to_ascii_lowercase();
 
             let args: Vec<String> = words.map(|w| w.to_ascii_lowercase()).collect();
@@ -583,6 +708,10 @@ impl SecurityPolicy {
                 return CommandRiskLevel::High;
             }
 
+            if self.shell_dialect.is_windows() && is_high_risk_windows_command(&base) {
+                return CommandRiskLevel::High;
+            }
+
             if joined_segment.contains("rm -rf /")
                 || joined_segment.contains("rm -fr /")
                 || joined_segment.contains(":(){:|:&};:")
@@ -622,7 +751,7 @@ impl SecurityPolicy {
                     )
                 }),
                 "touch" | "mkdir" | "mv" | "cp" | "ln" => true,
-                _ => false,
+                _ => self.shell_dialect.is_windows() && is_medium_risk_windows_command(&base),
             };
 
             saw_medium |= medium;
@@ -699,6 +828,15 @@ impl SecurityPolicy {
         if self.autonomy == AutonomyLevel::ReadOnly {
             return false;
         }
+        let command = &*self.lexable(command);
+
+        // `cmd` expands `%VAR%` even inside quotes; PowerShell runs `@(...)`
+        // like `$(...)`.
+        if (self.shell_dialect == ShellDialect::Cmd && contains_cmd_variable_expansion(command))
+            || (self.shell_dialect == ShellDialect::PowerShell && command.contains("@("))
+        {
+            return false;
+        }
 
         // Block subshell/expansion operators — these allow hiding arbitrary
         // commands inside an allowed command (e.g. `echo $(rm -rf /)`) and
@@ -722,10 +860,15 @@ impl SecurityPolicy {
 
         // Block `tee` — it can write to arbitrary files, bypassing the
         // redirect check above (e.g. `echo secret | tee /etc/crontab`)
-        if command
-            .split_whitespace()
-            .any(|w| w == "tee" || w.ends_with("/tee"))
-        {
+        if command.split_whitespace().any(|w| {
+            w == "tee"
+                || w.ends_with("/tee")
+                || (self.shell_dialect.is_windows()
+                    && matches!(
+                        self.shell_dialect.command_name(w).as_str(),
+                        "tee" | "tee-object"
+                    ))
+        }) {
             return false;
         }
 
@@ -743,7 +886,7 @@ impl SecurityPolicy {
 
             let mut words = cmd_part.split_whitespace();
             let base_raw = words.next().unwrap_or("");
-            let base_cmd = base_raw.rsplit('/').next().unwrap_or("");
+            let base_cmd = self.shell_dialect.command_name(base_raw);
 
             if base_cmd.is_empty() {
                 continue;
@@ -752,14 +895,14 @@ impl SecurityPolicy {
             if !self
                 .allowed_commands
                 .iter()
-                .any(|allowed| allowed == base_cmd)
+                .any(|allowed| self.allowlist_matches(allowed, &base_cmd))
             {
                 return false;
             }
 
             // Validate arguments for the command
             let args: Vec<String> = words.map(|w| w.to_string()).collect();
-            if !self.is_args_safe(base_cmd, &args) {
+            if !self.is_args_safe(&base_cmd, &args) {
                 return false;
             }
         }
@@ -798,10 +941,44 @@ impl SecurityPolicy {
                         || arg == "-c"
                 })
             }
+            "forfiles" => {
+                // forfiles /c runs a command for every matched file
+                !args.iter().any(|arg| {
+                    let lower = arg.to_ascii_lowercase();
+                    lower == "/c" || lower.starts_with("/c:")
+                })
+            }
             _ => true,
         }
     }
 
+    /// Whether the allowlist entry `allowed` permits the command `name`
+    /// (already normalized by [`ShellDialect::command_name`]).
+    fn allowlist_matches(&self, allowed: &str, name: &str) -> bool {
+        if self.shell_dialect.is_windows() {
+            self.shell_dialect.command_name(allowed) == name
+        } else {
+            allowed == name
+        }
+    }
+
+    /// `command` rewritten so the POSIX lexer splits it the way the
+    /// dialect's shell does. Windows shells treat `\` as a path separator,
+    /// not an escape, and `cmd` treats `'` as an ordinary character.
+    fn lexable<'a>(&self, command: &'a str) -> Cow<'a, str> {
+        match self.shell_dialect {
+            ShellDialect::Posix => Cow::Borrowed(command),
+            ShellDialect::PowerShell => Cow::Owned(command.replace('\\', "/")),
+            ShellDialect::Cmd => Cow::Owned(command.replace('\\', "/").replace('\'', " ")),
+        }
+    }
+
+    fn looks_like_path(&self, candidate: &str) -> bool {
+        looks_like_path(candidate)
+            || (self.shell_dialect.is_windows()
+                && (has_drive_prefix(candidate) || candidate.contains('\\')))
+    }
+
     /// Return the first path-like argument blocked by path policy.
     ///
     /// This is best-effort token parsing for shell commands and is intended
@@ -812,14 +989,15 @@ impl SecurityPolicy {
             if candidate.is_empty() || candidate.contains("://") {
                 return None;
             }
-            if looks_like_path(candidate) && !self.is_path_allowed(candidate) {
+            if self.looks_like_path(candidate) && !self.is_path_allowed(candidate) {
                 Some(candidate.to_string())
             } else {
                 None
             }
         };
 
-        for segment in split_unquoted_segments(command) {
+        let command = self.lexable(command);
+        for segment in split_unquoted_segments(&command) {
             let cmd_part = skip_env_assignments(&segment);
             let mut words = cmd_part.split_whitespace();
             let Some(executable) = words.next() else {
@@ -845,9 +1023,23 @@ impl SecurityPolicy {
                     }
                 }
 
-                // Handle option assignment forms like `--file=/etc/passwd`.
+                // Windows switches like `/s` or `/g:C:/secrets.txt`.
+                if let Some(value) = self.shell_dialect.switch_value(candidate) {
+                    if let Some(blocked) = forbidden_candidate(value) {
+                        return Some(blocked);
+                    }
+                    continue;
+                }
+
+                // Handle option assignment forms like `--file=/etc/passwd`
+                // (and PowerShell's `-Path:C:/secrets`).
                 if candidate.starts_with('-') {
-                    if let Some((_, value)) = candidate.split_once('=') {
+                    let separator = if self.shell_dialect.is_windows() {
+                        ['=', ':'].as_slice()
+                    } else {
+                        ['='].as_slice()
+                    };
+                    if let Some((_, value)) = candidate.split_once(separator) {
                         if let Some(blocked) = forbidden_candidate(value) {
                             return Some(blocked);
                         }
@@ -883,22 +1075,52 @@ impl SecurityPolicy {
         }
 
         // Block path traversal: check for ".." as a path component
+        let dialect = self.shell_dialect;
         if Path::new(path)
             .components()
             .any(|c| matches!(c, std::path::Component::ParentDir))
+            || dialect.components(path).contains(&"..")
         {
             return false;
         }
 
-        // Block URL-encoded traversal attempts (e.g. ..%2f)
+        // Block URL-encoded traversal attempts (e.g. ..%2f, ..%5c)
         let lower = path.to_lowercase();
-        if lower.contains("..%2f") || lower.contains("%2f..") {
+        if lower.contains("..%2f")
+            || lower.contains("%2f..")
+            || (dialect.is_windows() && (lower.contains("..%5c") || lower.contains("%5c..")))
+        {
+            return false;
+        }
+
+        // Windows: block UNC and device paths (\\server\share, \\?\C:\),
+        // reserved device names (NUL, COM1) and alternate data streams
+        // (notes.txt:hidden).
+        if dialect.is_network_or_device_path(path) {
             return false;
         }
+        if dialect.is_windows() {
+            let rest = if has_drive_prefix(path) {
+                &path[2..]
+            } else {
+                path
+            };
+            if dialect
+                .components(rest)
+                .iter()
+                .any(|component| dialect.is_special_component(component))
+            {
+                return false;
+            }
+        }
 
         // Reject "~user" forms because the shell expands them at runtime and
         // they can escape workspace policy.
-        if path.starts_with('~') && path != "~" && !path.starts_with("~/") {
+        if path.starts_with('~')
+            && path != "~"
+            && !path.starts_with("~/")
+            && !(dialect.is_windows() && path.starts_with("~\\"))
+        {
             return false;
         }
 
@@ -906,10 +1128,26 @@ impl SecurityPolicy {
         let expanded_path = expand_user_path(path);
 
         // Block absolute paths when workspace_only is set
-        if self.workspace_only && expanded_path.is_absolute() {
+        if self.workspace_only
+            && (expanded_path.is_absolute()
+                || dialect.is_absolute(&expanded_path.to_string_lossy()))
+        {
             return false;
         }
 
+        // Windows paths compare case-insensitively and with either separator.
+        if dialect.is_windows() {
+            let candidate = normalize_windows_path(&expanded_path.to_string_lossy());
+            return !self.forbidden_paths.iter().any(|forbidden| {
+                let forbidden =
+                    normalize_windows_path(&expand_user_path(forbidden).to_string_lossy());
+                candidate == forbidden
+                    || candidate
+                        .strip_prefix(forbidden.as_str())
+                        .is_some_and(|rest| rest.starts_with('/') || forbidden.ends_with('/'))
+            });
+        }
+
         // Block forbidden paths using path-component-aware matching
         for forbidden in &self.forbidden_paths {
             let forbidden_path = expand_user_path(forbidden);
@@ -1037,6 +1275,7 @@ impl SecurityPolicy {
             require_approval_for_medium_risk: autonomy_config.require_approval_for_medium_risk,
             block_high_risk_commands: autonomy_config.block_high_risk_commands,
             shell_env_passthrough: autonomy_config.shell_env_passthrough.clone(),
+            shell_dialect: ShellDialect::host(),
             tracker: ActionTracker::new(),
         }
     }
@@ -2192,4 +2431,134 @@ mod tests {
             "URL-encoded parent dir traversal must be blocked"
         );
     }
+
+    // ── Windows dialects ─────────────────────────────────────
+
+    fn windows_policy(dialect: ShellDialect) -> SecurityPolicy {
+        SecurityPolicy {
+            shell_dialect: dialect,
+            allowed_commands: vec!["git".into(), "dir".into(), "type".into(), "findstr".into()],
+            forbidden_paths: ShellDialect::PowerShell.system_paths(),
+            ..SecurityPolicy::default()
+        }
+    }
+
+    #[test]
+    fn windows_command_names_match_allowlist_case_insensitively() {
+        let policy = windows_policy(ShellDialect::PowerShell);
+        assert!(policy.is_command_allowed("GIT.EXE status"));
+        assert!(policy.is_command_allowed("C:\\Git\\bin\\git.exe status"));
+        assert!(policy.is_command_allowed("dir src"));
+        assert!(!policy.is_command_allowed("del notes.txt"));
+
+        let posix = SecurityPolicy {
+            shell_dialect: ShellDialect::Posix,
+            ..policy
+        };
+        assert!(!posix.is_command_allowed("GIT.EXE status"));
+    }
+
+    #[test]
+    fn windows_backslash_does_not_escape_operators() {
+        let policy = windows_policy(ShellDialect::PowerShell);
+        assert!(!policy.is_command_allowed("dir \\; Remove-Item x"));
+        assert!(!policy.is_command_allowed("type a\\>out.txt"));
+        assert!(!policy.is_command_allowed("dir @(Remove-Item x)"));
+        assert!(!policy.is_command_allowed("type $env:USERPROFILE"));
+        assert!(!policy.is_command_allowed("dir | Tee-Object out.txt"));
+    }
+
+    #[test]
+    fn cmd_variables_and_single_quotes_are_not_trusted() {
+        let policy = windows_policy(ShellDialect::Cmd);
+        assert!(!policy.is_command_allowed("type \"%USERPROFILE%\\secret.txt\""));
+        assert!(!policy.is_command_allowed("type %~dp0x"));
+        assert!(policy.is_command_allowed("findstr 100% notes.txt"));
+        assert!(!policy.is_command_allowed("findstr '&' del notes.txt"));
+        assert!(!policy.is_command_allowed("forfiles /c \"cmd /c del @file\""));
+    }
+
+    #[test]
+    fn windows_commands_are_risk_classified() {
+        let policy = SecurityPolicy {
+            shell_dialect: ShellDialect::PowerShell,
+            ..SecurityPolicy::default()
+        };
+        for command in [
+            "del /s /q build",
+            "Remove-Item -Recurse build",
+            "iwr https://example.com",
+            "reg add HKCU\\Software\\x",
+            "powershell -EncodedCommand AAAA",
+            "cmd.exe /c dir",
+        ] {
+            assert_eq!(
+                policy.command_risk_level(command),
+                CommandRiskLevel::High,
+                "{command}"
+            );
+        }
+        assert_eq!(
+            policy.command_risk_level("Copy-Item a b"),
+            CommandRiskLevel::Medium
+        );
+        assert_eq!(policy.command_risk_level("dir"), CommandRiskLevel::Low);
+        let posix = SecurityPolicy {
+            shell_dialect: ShellDialect::Posix,
+            ..SecurityPolicy::default()
+        };
+        assert_eq!(posix.command_risk_level("del x"), CommandRiskLevel::Low);
+    }
+
+    #[test]
+    fn windows_paths_respect_workspace_only() {
+        let policy = windows_policy(ShellDialect::PowerShell);
+        for path in [
+            "C:\\Users\\me\\secret.txt",
+            "c:secret.txt",
+            "\\Windows\\System32",
+            "\\\\fileserver\\share\\x",
+            "\\\\?\\C:\\x",
+            "..\\outside",
+            "sub\\..\\..\\outside",
+            "..%5coutside",
+            "NUL",
+            "logs\\com1.txt",
+            "notes.txt:hidden",
+        ] {
+            assert!(!policy.is_path_allowed(path), "{path}");
+        }
+        assert!(policy.is_path_allowed("src\\main.rs"));
+        assert!(policy.is_path_allowed("docs/console.log"));
+    }
+
+    #[test]
+    fn windows_forbidden_paths_match_case_insensitively() {
+        let policy = SecurityPolicy {
+            workspace_only: false,
+            ..windows_policy(ShellDialect::Cmd)
+        };
+        assert!(!policy.is_path_allowed("c:/windows/system32/config"));
+        assert!(!policy.is_path_allowed("C:\\PROGRAM FILES\\app"));
+        assert!(policy.is_path_allowed("C:\\WindowsApps\\x"));
+        assert!(policy.is_path_allowed("D:\\projects\\x"));
+        assert!(!policy.is_path_allowed("\\\\server\\share"));
+    }
+
+    #[test]
+    fn windows_path_arguments_and_switches() {
+        let policy = windows_policy(ShellDialect::Cmd);
+        assert_eq!(
+            policy.forbidden_path_argument("type C:\\Windows\\win.ini"),
+            Some("C:/Windows/win.ini".into())
+        );
+        assert_eq!(policy.forbidden_path_argument("dir /s /b src"), None);
+        assert!(policy
+            .forbidden_path_argument("findstr /g:C:\\secrets.txt x")
+            .is_some());
+        assert!(policy
+            .forbidden_path_argument("type -Path:C:\\secret.txt")
+            .is_some());
+        assert!(policy.forbidden_path_argument("dir /Windows").is_some());
+    }
 }
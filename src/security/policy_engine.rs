@@ -0,0 +1,411 @@
+//! Policy-as-code hook for tool calls (`[security.policy_engine]`).
+//!
+//! Before the approval step, every proposed tool call is described as a JSON
+//! document (tool, arguments, identity, channel, time and cost state) and
+//! handed to a user-supplied policy: an OPA server evaluating Rego over HTTP,
+//! or a local command (for example `opa eval` or a CEL evaluator) that reads
+//! the document on stdin. The policy answers `allow`, `deny` or `approve`.
+//! Allowed calls still go through the built-in autonomy and approval rules;
+//! the hook can only narrow what they permit.
+
+use crate::config::{Config, CostConfig, PolicyEngineConfig, PolicyEngineKind, PolicyFallback};
+use crate::cost::CostTracker;
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, Local, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::process::Stdio;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// What the policy decided for one tool call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Allow,
+    /// Refuse the call, with the policy's reason.
+    Deny(String),
+    /// Run the call only after explicit approval, with the policy's reason.
+    Approve(String),
+}
+
+/// Budget usage visible to policies.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CostState {
+    pub tracking_enabled: bool,
+    pub daily_usd: f64,
+    pub monthly_usd: f64,
+    pub daily_limit_usd: f64,
+    pub monthly_limit_usd: f64,
+}
+
+/// When the call was proposed; `hour` and `weekday` use the host's local time.
+#[derive(Debug, Clone, Serialize)]
+pub struct CallTime {
+    pub timestamp: String,
+    pub hour: u32,
+    pub weekday: String,
+}
+
+/// Document every policy is evaluated against (`input` in Rego).
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyInput {
+    pub tool: String,
+    pub arguments: Value,
+    /// `channel:sender` of the person the agent acts for, or `cli`.
+    pub identity: String,
+    pub channel: String,
+    pub time: CallTime,
+    pub cost: CostState,
+}
+
+impl PolicyInput {
+    fn now(tool: &str, arguments: &Value, channel: &str, cost: CostState) -> Self {
+        let local = Local::now();
+        Self {
+            tool: tool.to_string(),
+            arguments: arguments.clone(),
+            identity: crate::security::four_eyes::current_requester(),
+            channel: channel.to_string(),
+            time: CallTime {
+                timestamp: Utc::now().to_rfc3339(),
+                hour: chrono::Timelike::hour(&local),
+                weekday: local.weekday().to_string().to_ascii_lowercase(),
+            },
+            cost,
+        }
+    }
+}
+
+/// Read a decision from a policy result.
+///
+/// Accepts a bare word (`"allow"`, `"deny"`, `"approve"`), a boolean (as
+/// produced by CEL expressions), an object `{"decision": ..., "reason": ...}`,
+/// or any of these wrapped in OPA's `{"result": ...}` envelope.
+pub fn parse_decision(value: &Value) -> Result<Verdict> {
+    match value {
+        Value::Bool(true) => Ok(Verdict::Allow),
+        Value::Bool(false) => Ok(Verdict::Deny("policy returned false".into())),
+        Value::String(word) => verdict_from_word(word, String::new()),
+        Value::Object(object) => {
+            if let Some(result) = object.get("result") {
+                // `opa eval --format json` nests the value in expressions.
+                if let Some(inner) = result
+                    .pointer("/0/expressions/0/value")
+                    .filter(|_| result.is_array())
+                {
+                    return parse_decision(inner);
+                }
+                return parse_decision(result);
+            }
+            let Some(word) = object.get("decision").and_then(Value::as_str) else {
+                bail!("policy result has no 'decision' field");
+            };
+            let reason = object
+                .get("reason")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            verdict_from_word(word, reason)
+        }
+        Value::Null => bail!("policy returned no decision"),
+        other => bail!("unsupported policy result: {other}"),
+    }
+}
+
+fn verdict_from_word(word: &str, reason: String) -> Result<Verdict> {
+    match word.trim().to_ascii_lowercase().as_str() {
+        "allow" => Ok(Verdict::Allow),
+        "deny" => Ok(Verdict::Deny(if reason.is_empty() {
+            "denied by policy".into()
+        } else {
+            reason
+        })),
+        "approve" => Ok(Verdict::Approve(reason)),
+        other => bail!("unknown policy decision '{other}' (expected allow, deny or approve)"),
+    }
+}
+
+/// Evaluates tool calls against the configured policy.
+pub struct PolicyEngine {
+    config: PolicyEngineConfig,
+    cost_config: CostConfig,
+    cost: Option<CostTracker>,
+    client: reqwest::Client,
+}
+
+/// State of the process-wide policy hook.
+#[derive(Clone)]
+enum Hook {
+    /// [`init_from_config`] has not run, so whether a policy applies is
+    /// unknown and calls are denied.
+    Uninitialized,
+    Disabled,
+    Enabled(Arc<PolicyEngine>),
+}
+
+static ENGINE: LazyLock<RwLock<Hook>> = LazyLock::new(|| RwLock::new(Hook::Uninitialized));
+
+/// Initialize (or disable) the process-wide policy hook from config.
+pub fn init_from_config(config: &Config) {
+    let hook = if config.security.policy_engine.enabled {
+        Hook::Enabled(Arc::new(PolicyEngine::from_config(config)))
+    } else {
+        Hook::Disabled
+    };
+    *ENGINE.write().unwrap_or_else(|e| e.into_inner()) = hook;
+}
+
+fn current() -> Hook {
+    ENGINE.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Evaluate a proposed tool call; `None` when no policy applies to it.
+pub async fn evaluate(tool: &str, arguments: &Value, channel: &str) -> Option<Verdict> {
+    evaluate_with(current(), tool, arguments, channel).await
+}
+
+async fn evaluate_with(
+    hook: Hook,
+    tool: &str,
+    arguments: &Value,
+    channel: &str,
+) -> Option<Verdict> {
+    let engine = match hook {
+        Hook::Enabled(engine) => engine,
+        Hook::Disabled => return None,
+        Hook::Uninitialized => {
+            tracing::error!(tool, "Policy engine used before initialization; denying");
+            return Some(Verdict::Deny("policy engine is not initialized".into()));
+        }
+    };
+    if !engine.covers(tool) {
+        return None;
+    }
+    Some(engine.decide(tool, arguments, channel).await)
+}
+
+/// Get the approval an `approve` verdict asks for when nobody can be
+/// prompted in this conversation: a second person's sign-off through
+/// `[security.four_eyes]`, when it is enabled.
+pub async fn request_second_approval(tool: &str, arguments: &Value) -> Result<(), String> {
+    let Some(guard) = crate::security::four_eyes::current() else {
+        return Err(format!(
+            "Policy requires approval for '{tool}', but no approver is available here \
+            (enable [security.four_eyes] to ask a second person)"
+        ));
+    };
+    if guard.covers(tool) {
+        // The tool is already wrapped and asks for sign-off when it runs.
+        return Ok(());
+    }
+    guard.authorize(tool, arguments).await.map(|_| ())
+}
+
+impl PolicyEngine {
+    pub fn from_config(config: &Config) -> Self {
+        let cost = if config.cost.enabled {
+            CostTracker::new(config.cost.clone(), &config.workspace_dir)
+                .map_err(|e| tracing::warn!("Policy engine cannot read cost state: {e}"))
+                .ok()
+        } else {
+            None
+        };
+        Self {
+            config: config.security.policy_engine.clone(),
+            cost_config: config.cost.clone(),
+            cost,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn covers(&self, tool: &str) -> bool {
+        self.config.tools.is_empty() || self.config.tools.iter().any(|name| name == tool)
+    }
+
+    fn cost_state(&self) -> CostState {
+        let mut state = CostState {
+            tracking_enabled: self.cost_config.enabled,
+            daily_limit_usd: self.cost_config.daily_limit_usd,
+            monthly_limit_usd: self.cost_config.monthly_limit_usd,
+            ..CostState::default()
+        };
+        if let Some(summary) = self.cost.as_ref().and_then(|cost| cost.get_summary().ok()) {
+            state.daily_usd = summary.daily_cost_usd;
+            state.monthly_usd = summary.monthly_cost_usd;
+        }
+        state
+    }
+
+    async fn decide(&self, tool: &str, arguments: &Value, channel: &str) -> Verdict {
+        let input = PolicyInput::now(tool, arguments, channel, self.cost_state());
+        match self
+            .query(&input)
+            .await
+            .and_then(|result| parse_decision(&result))
+        {
+            Ok(verdict) => verdict,
+            Err(e) => {
+                tracing::warn!(tool, "Policy evaluation failed: {e:#}");
+                let reason = format!("policy engine unavailable: {e}");
+                match self.config.on_error {
+                    PolicyFallback::Allow => Verdict::Allow,
+                    PolicyFallback::Deny => Verdict::Deny(reason),
+                    PolicyFallback::Approve => Verdict::Approve(reason),
+                }
+            }
+        }
+    }
+
+    async fn query(&self, input: &PolicyInput) -> Result<Value> {
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        match self.config.engine {
+            PolicyEngineKind::Opa => {
+                let response = self
+                    .client
+                    .post(&self.config.url)
+                    .timeout(timeout)
+                    .json(&serde_json::json!({ "input": input }))
+                    .send()
+                    .await
+                    .context("OPA request failed")?
+                    .error_for_status()
+                    .context("OPA rejected the query")?;
+                response.json().await.context("OPA returned invalid JSON")
+            }
+            PolicyEngineKind::Command => tokio::time::timeout(timeout, self.run_command(input))
+                .await
+                .with_context(|| {
+                    format!(
+                        "policy command timed out after {}ms",
+                        self.config.timeout_ms
+                    )
+                })?,
+        }
+    }
+
+    async fn run_command(&self, input: &PolicyInput) -> Result<Value> {
+        let Some((program, args)) = self.config.command.split_first() else {
+            bail!("security.policy_engine.command is empty");
+        };
+        let mut child = tokio::process::Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("failed to start policy command '{program}'"))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // A policy may decide without reading its input; the exit status
+            // below still reports real failures.
+            if let Err(e) = stdin.write_all(&serde_json::to_vec(input)?).await {
+                if e.kind() != std::io::ErrorKind::BrokenPipe {
+                    return Err(e.into());
+                }
+            }
+        }
+        let mut stdout = String::new();
+        if let Some(mut out) = child.stdout.take() {
+            out.read_to_string(&mut stdout).await?;
+        }
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            bail!(
+                "policy command exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        let stdout = stdout.trim();
+        Ok(serde_json::from_str(stdout).unwrap_or_else(|_| Value::String(stdout.to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn engine(command: &[&str], on_error: PolicyFallback) -> PolicyEngine {
+        let mut config = Config::default();
+        config.security.policy_engine = PolicyEngineConfig {
+            enabled: true,
+            engine: PolicyEngineKind::Command,
+            command: command.iter().map(ToString::to_string).collect(),
+            on_error,
+            ..PolicyEngineConfig::default()
+        };
+        PolicyEngine::from_config(&config)
+    }
+
+    #[tokio::test]
+    async fn uninitialized_hook_denies_and_disabled_hook_abstains() {
+        let args = json!({"command": "ls"});
+        assert!(matches!(
+            evaluate_with(Hook::Uninitialized, "shell", &args, "cli").await,
+            Some(Verdict::Deny(_))
+        ));
+        assert_eq!(
+            evaluate_with(Hook::Disabled, "shell", &args, "cli").await,
+            None
+        );
+    }
+
+    #[test]
+    fn parse_decision_accepts_words_booleans_objects_and_opa_envelopes() {
+        assert_eq!(parse_decision(&json!("allow")).unwrap(), Verdict::Allow);
+        assert_eq!(parse_decision(&json!(true)).unwrap(), Verdict::Allow);
+        assert!(matches!(
+            parse_decision(&json!(false)).unwrap(),
+            Verdict::Deny(_)
+        ));
+        assert_eq!(
+            parse_decision(&json!({"result": {"decision": "approve", "reason": "prod"}})).unwrap(),
+            Verdict::Approve("prod".into())
+        );
+        assert_eq!(
+            parse_decision(&json!({"result": [{"expressions": [{"value": "deny"}]}]})).unwrap(),
+            Verdict::Deny("denied by policy".into())
+        );
+        assert!(parse_decision(&json!({})).is_err());
+        assert!(parse_decision(&json!("maybe")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_policy_sees_the_call_on_stdin() {
+        let engine = engine(
+            &[
+                "sh",
+                "-c",
+                r#"grep -q '"tool":"shell"' && echo '{"decision":"deny","reason":"no shell"}' || echo allow"#,
+            ],
+            PolicyFallback::Deny,
+        );
+        assert_eq!(
+            engine
+                .decide("shell", &json!({"command": "ls"}), "cli")
+                .await,
+            Verdict::Deny("no shell".into())
+        );
+        assert_eq!(
+            engine.decide("file_read", &json!({}), "telegram").await,
+            Verdict::Allow
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn failing_policy_falls_back_to_on_error() {
+        let approve = engine(&["sh", "-c", "exit 3"], PolicyFallback::Approve)
+            .decide("shell", &json!({}), "cli")
+            .await;
+        assert!(matches!(approve, Verdict::Approve(reason) if reason.contains("exited")));
+        let allow = engine(&["/nonexistent/policy"], PolicyFallback::Allow);
+        assert_eq!(
+            allow.decide("shell", &json!({}), "cli").await,
+            Verdict::Allow
+        );
+    }
+}
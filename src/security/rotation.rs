@@ -0,0 +1,635 @@
+//! Secret rotation for credentials stored in `config.toml`.
+//!
+//! `zeroclaw security rotate <secret>` replaces one configured credential:
+//! the new value is issued through the provider's key-management API where
+//! one exists (a [`KeyIssuer`]) or supplied by the operator, verified against
+//! the provider, written through [`Config::save`] (re-encrypted, atomic
+//! replace), and only then is the old key retired. Every attempt is recorded
+//! in the security audit log. Rotation times are kept in
+//! `secret-rotations.json` beside the config so `zeroclaw doctor` can remind
+//! about secrets older than `[secrets] rotation_reminder_days`.
+
+use crate::config::Config;
+use crate::security::{AuditEvent, AuditEventType, AuditLogger};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Rotation history file, relative to the zeroclaw dir.
+const STATE_FILE: &str = "secret-rotations.json";
+
+/// Environment variable holding an OpenRouter provisioning key.
+pub const OPENROUTER_PROVISIONING_KEY_ENV: &str = "OPENROUTER_PROVISIONING_KEY";
+
+const OPENROUTER_KEYS_URL: &str = "https://openrouter.ai/api/v1/keys";
+
+/// Fixed-path secrets that can be rotated, in display order. Agent API keys
+/// (`agents.<name>.api_key`) and GraphQL bearer tokens
+/// (`graphql.endpoints.<name>.bearer_token`) are addressed by name.
+const FIXED_SECRETS: &[&str] = &[
+    "api_key",
+    "composio.api_key",
+    "browser.computer_use.api_key",
+    "web_search.brave_api_key",
+    "email_send.api_key",
+    "federation.token",
+    "smart_home.password",
+    "code.github_token",
+    "calendar.password",
+];
+
+/// Issues and revokes keys through a provider's key-management API.
+#[async_trait]
+pub trait KeyIssuer: Send + Sync {
+    /// Short name recorded in the audit log, e.g. `openrouter-api`.
+    fn name(&self) -> &str;
+
+    /// Create a new key labelled `label` and return its value.
+    async fn issue(&self, label: &str) -> Result<String>;
+
+    /// Disable `key` so it can no longer authenticate.
+    async fn revoke(&self, key: &str) -> Result<()>;
+}
+
+/// Where the replacement value comes from.
+pub enum NewSecret {
+    /// Created through the provider API; the old key is revoked the same way.
+    Issued(Box<dyn KeyIssuer>),
+    /// Supplied by the operator; the old key must be revoked by hand.
+    Provided(String),
+}
+
+/// Outcome of a successful rotation.
+#[derive(Debug, Clone)]
+pub struct RotationReport {
+    pub secret: String,
+    pub method: String,
+    /// Whether the new value was checked against the provider.
+    pub verified: bool,
+    pub old_fingerprint: Option<String>,
+    pub new_fingerprint: String,
+    /// `Some(Ok)` when the old key was revoked, `Some(Err)` when revocation
+    /// failed, `None` when it was kept or has to be revoked by hand.
+    pub retired: Option<Result<(), String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationRecord {
+    pub rotated_at: DateTime<Utc>,
+    /// Fingerprint of the value written by the rotation; a mismatch means the
+    /// secret was changed outside `security rotate`.
+    pub fingerprint: String,
+    pub method: String,
+}
+
+/// Every rotatable secret that currently has a value.
+pub fn configured_secrets(config: &Config) -> Vec<String> {
+    let mut config = config.clone();
+    let mut ids: Vec<String> = FIXED_SECRETS.iter().map(|id| (*id).to_string()).collect();
+    let mut agents: Vec<_> = config.agents.keys().cloned().collect();
+    agents.sort();
+    ids.extend(
+        agents
+            .into_iter()
+            .map(|name| format!("agents.{name}.api_key")),
+    );
+    let mut endpoints: Vec<_> = config.graphql.endpoints.keys().cloned().collect();
+    endpoints.sort();
+    ids.extend(
+        endpoints
+            .into_iter()
+            .map(|name| format!("graphql.endpoints.{name}.bearer_token")),
+    );
+    ids.retain(|id| {
+        secret_slot(&mut config, id)
+            .and_then(|slot| slot.as_deref())
+            .is_some_and(|value| !value.trim().is_empty())
+    });
+    ids
+}
+
+/// Plaintext values of every configured secret, for redacting exported text.
+pub fn configured_secret_values(config: &Config) -> Vec<String> {
+    let mut config = config.clone();
+    configured_secrets(&config)
+        .iter()
+        .filter_map(|id| secret_slot(&mut config, id)?.clone())
+        .collect()
+}
+
+fn secret_slot<'a>(config: &'a mut Config, id: &str) -> Option<&'a mut Option<String>> {
+    match id {
+        "api_key" => return Some(&mut config.api_key),
+        "composio.api_key" => return Some(&mut config.composio.api_key),
+        "browser.computer_use.api_key" => return Some(&mut config.browser.computer_use.api_key),
+        "web_search.brave_api_key" => return Some(&mut config.web_search.brave_api_key),
+        "email_send.api_key" => return Some(&mut config.email_send.api_key),
+        "federation.token" => return Some(&mut config.federation.token),
+        "smart_home.password" => return Some(&mut config.smart_home.password),
+        "code.github_token" => return Some(&mut config.code.github_token),
+        "calendar.password" => return Some(&mut config.calendar.password),
+        _ => {}
+    }
+    if let Some(name) = id
+        .strip_prefix("agents.")
+        .and_then(|rest| rest.strip_suffix(".api_key"))
+    {
+        return config.agents.get_mut(name).map(|agent| &mut agent.api_key);
+    }
+    let name = id
+        .strip_prefix("graphql.endpoints.")
+        .and_then(|rest| rest.strip_suffix(".bearer_token"))?;
+    config
+        .graphql
+        .endpoints
+        .get_mut(name)
+        .map(|endpoint| &mut endpoint.bearer_token)
+}
+
+/// Provider and model a secret authenticates against, for verification.
+fn provider_target(config: &Config, id: &str) -> Option<(String, String)> {
+    if id == "api_key" {
+        let provider = config.default_provider.clone()?;
+        let model = config.default_model.clone()?;
+        return Some((provider, model));
+    }
+    let name = id.strip_prefix("agents.")?.strip_suffix(".api_key")?;
+    let agent = config.agents.get(name)?;
+    Some((agent.provider.clone(), agent.model.clone()))
+}
+
+/// The provider API able to rotate `id`, if any is available.
+pub fn issuer_for(config: &Config, id: &str) -> Option<Box<dyn KeyIssuer>> {
+    let (provider, _) = provider_target(config, id)?;
+    if provider != "openrouter" {
+        return None;
+    }
+    let provisioning_key = std::env::var(OPENROUTER_PROVISIONING_KEY_ENV)
+        .ok()
+        .filter(|key| !key.trim().is_empty())?;
+    Some(Box::new(OpenRouterIssuer::new(provisioning_key)))
+}
+
+/// Short, non-reversible identifier for a secret value.
+pub fn fingerprint(value: &str) -> String {
+    let digest = Sha256::digest(value.as_bytes());
+    format!("sha256:{}", &hex::encode(digest)[..12])
+}
+
+fn zeroclaw_dir(config: &Config) -> Result<&Path> {
+    config
+        .config_path
+        .parent()
+        .context("Config path must have a parent directory")
+}
+
+fn state_path(config: &Config) -> Result<PathBuf> {
+    Ok(zeroclaw_dir(config)?.join(STATE_FILE))
+}
+
+/// Rotation history keyed by secret id. Missing or unreadable files yield an
+/// empty history.
+pub fn load_history(config: &Config) -> BTreeMap<String, RotationRecord> {
+    state_path(config)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn record_rotation(config: &Config, id: &str, record: RotationRecord) -> Result<()> {
+    let path = state_path(config)?;
+    let mut history = load_history(config);
+    history.insert(id.to_string(), record);
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(&history)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, &path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Days since `id` was last rotated, or `None` when its history is unknown
+/// or the value has since been changed by hand.
+pub fn secret_age_days(
+    config: &Config,
+    history: &BTreeMap<String, RotationRecord>,
+    id: &str,
+    now: DateTime<Utc>,
+) -> Option<i64> {
+    let record = history.get(id)?;
+    let mut config = config.clone();
+    let current = secret_slot(&mut config, id)?.as_deref()?;
+    (fingerprint(current) == record.fingerprint)
+        .then(|| now.signed_duration_since(record.rotated_at).num_days())
+}
+
+/// Replace secret `id` with a new value, verify it, save the config and
+/// retire the old value. Nothing is saved if verification fails.
+pub async fn rotate(
+    config: &mut Config,
+    id: &str,
+    source: NewSecret,
+    keep_old: bool,
+) -> Result<RotationReport> {
+    let started = Instant::now();
+    let method = match &source {
+        NewSecret::Issued(issuer) => issuer.name().to_string(),
+        NewSecret::Provided(_) => "manual".to_string(),
+    };
+    let result = rotate_inner(config, id, source, keep_old, &method).await;
+    let (success, detail, error) = match &result {
+        Ok(report) => (
+            true,
+            format!(
+                "{} -> {}",
+                report.old_fingerprint.as_deref().unwrap_or("none"),
+                report.new_fingerprint
+            ),
+            report.retired.clone().and_then(Result::err),
+        ),
+        Err(e) => (false, "aborted".to_string(), Some(format!("{e:#}"))),
+    };
+    audit(
+        config,
+        &format!("security rotate {id} via {method}: {detail}"),
+        success,
+        started,
+        error,
+    );
+    result
+}
+
+async fn rotate_inner(
+    config: &mut Config,
+    id: &str,
+    source: NewSecret,
+    keep_old: bool,
+    method: &str,
+) -> Result<RotationReport> {
+    let Some(old) = secret_slot(config, id).map(|slot| slot.clone()) else {
+        bail!(
+            "Unknown secret '{id}'. Configured secrets: {}",
+            configured_secrets(config).join(", ")
+        );
+    };
+    let old = old.filter(|value| !value.trim().is_empty());
+
+    let (new, issuer) = match source {
+        NewSecret::Issued(issuer) => {
+            let label = format!("zeroclaw {id} {}", Utc::now().format("%Y-%m-%d"));
+            let new = issuer
+                .issue(&label)
+                .await
+                .with_context(|| format!("{} could not issue a new key", issuer.name()))?;
+            (new, Some(issuer))
+        }
+        NewSecret::Provided(value) => (value.trim().to_string(), None),
+    };
+    if new.is_empty() {
+        bail!("New value for '{id}' is empty");
+    }
+    if old.as_deref() == Some(new.as_str()) {
+        bail!("New value for '{id}' is identical to the current one");
+    }
+
+    let verified = match verify(config, id, &new).await {
+        Ok(verified) => verified,
+        Err(e) => {
+            discard_issued(issuer.as_deref(), &new).await;
+            return Err(e.context(format!("New value for '{id}' failed verification")));
+        }
+    };
+
+    if let Some(slot) = secret_slot(config, id) {
+        *slot = Some(new.clone());
+    }
+    if let Err(e) = config.save().await {
+        if let Some(slot) = secret_slot(config, id) {
+            slot.clone_from(&old);
+        }
+        discard_issued(issuer.as_deref(), &new).await;
+        return Err(e.context(format!(
+            "Failed to save rotated '{id}'; config left unchanged"
+        )));
+    }
+
+    let new_fingerprint = fingerprint(&new);
+    if let Err(e) = record_rotation(
+        config,
+        id,
+        RotationRecord {
+            rotated_at: Utc::now(),
+            fingerprint: new_fingerprint.clone(),
+            method: method.to_string(),
+        },
+    ) {
+        tracing::warn!("Rotated '{id}' but could not record rotation history: {e:#}");
+    }
+
+    let retired = match (&issuer, &old) {
+        (Some(issuer), Some(old)) if !keep_old => Some(
+            issuer
+                .revoke(old)
+                .await
+                .map_err(|e| format!("{} could not revoke the old key: {e:#}", issuer.name())),
+        ),
+        _ => None,
+    };
+
+    Ok(RotationReport {
+        secret: id.to_string(),
+        method: method.to_string(),
+        verified,
+        old_fingerprint: old.as_deref().map(fingerprint),
+        new_fingerprint,
+        retired,
+    })
+}
+
+/// Check a provider key with a minimal chat request. Returns `false` for
+/// secrets that have no provider to check against.
+async fn verify(config: &Config, id: &str, value: &str) -> Result<bool> {
+    let Some((provider, model)) = provider_target(config, id) else {
+        return Ok(false);
+    };
+    let provider = crate::providers::create_provider(&provider, Some(value))?;
+    provider
+        .chat_with_system(None, "Reply with OK.", &model, 0.0)
+        .await?;
+    Ok(true)
+}
+
+/// Best-effort cleanup of a key issued for a rotation that did not complete.
+async fn discard_issued(issuer: Option<&dyn KeyIssuer>, key: &str) {
+    if let Some(issuer) = issuer {
+        if let Err(e) = issuer.revoke(key).await {
+            tracing::warn!(
+                "Could not revoke unused key {} via {}: {e:#}",
+                fingerprint(key),
+                issuer.name()
+            );
+        }
+    }
+}
+
+fn audit(config: &Config, command: &str, success: bool, started: Instant, error: Option<String>) {
+    let Ok(dir) = zeroclaw_dir(config) else {
+        return;
+    };
+    let event = AuditEvent::new(AuditEventType::SecretRotation)
+        .with_actor("cli".into(), None, None)
+        .with_action(command.to_string(), "high".into(), true, true)
+        .with_result(
+            success,
+            None,
+            u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            error,
+        );
+    let logged = AuditLogger::new(config.security.audit.clone(), dir.to_path_buf())
+        .and_then(|logger| logger.log(&event));
+    if let Err(e) = logged {
+        tracing::warn!("Failed to write secret rotation audit event: {e:#}");
+    }
+}
+
+/// OpenRouter key management through a provisioning key.
+pub struct OpenRouterIssuer {
+    client: reqwest::Client,
+    provisioning_key: String,
+    keys_url: String,
+}
+
+impl OpenRouterIssuer {
+    pub fn new(provisioning_key: String) -> Self {
+        Self {
+            client: crate::config::build_runtime_proxy_client("security.rotation"),
+            provisioning_key,
+            keys_url: OPENROUTER_KEYS_URL.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl KeyIssuer for OpenRouterIssuer {
+    fn name(&self) -> &str {
+        "openrouter-api"
+    }
+
+    async fn issue(&self, label: &str) -> Result<String> {
+        let response = self
+            .client
+            .post(&self.keys_url)
+            .bearer_auth(&self.provisioning_key)
+            .json(&serde_json::json!({ "name": label }))
+            .send()
+            .await?
+            .error_for_status()?;
+        let body: serde_json::Value = response.json().await?;
+        body.get("key")
+            .and_then(|key| key.as_str())
+            .map(str::to_string)
+            .context("OpenRouter response did not include the new key")
+    }
+
+    async fn revoke(&self, key: &str) -> Result<()> {
+        let response = self
+            .client
+            .get(&self.keys_url)
+            .bearer_auth(&self.provisioning_key)
+            .send()
+            .await?
+            .error_for_status()?;
+        let body: serde_json::Value = response.json().await?;
+        let hash = body
+            .get("data")
+            .and_then(|data| data.as_array())
+            .into_iter()
+            .flatten()
+            .find(|entry| {
+                entry
+                    .get("label")
+                    .and_then(|label| label.as_str())
+                    .is_some_and(|label| masked_label_matches(label, key))
+            })
+            .and_then(|entry| entry.get("hash"))
+            .and_then(|hash| hash.as_str())
+            .context("Key not found among keys managed by this provisioning key")?;
+        self.client
+            .delete(format!("{}/{hash}", self.keys_url))
+            .bearer_auth(&self.provisioning_key)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// OpenRouter lists keys by a masked label such as `sk-or-v1-0e6...1c96`.
+fn masked_label_matches(label: &str, key: &str) -> bool {
+    label.split_once("...").is_some_and(|(prefix, suffix)| {
+        !prefix.is_empty()
+            && !suffix.is_empty()
+            && key.len() >= prefix.len() + suffix.len()
+            && key.starts_with(prefix)
+            && key.ends_with(suffix)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir) -> Config {
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.secrets.encrypt = false;
+        config.web_search.brave_api_key = Some("brave-old".into());
+        config
+    }
+
+    #[derive(Default)]
+    struct FakeIssuer {
+        revoked: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl KeyIssuer for FakeIssuer {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        async fn issue(&self, _label: &str) -> Result<String> {
+            Ok("brave-issued".into())
+        }
+
+        async fn revoke(&self, key: &str) -> Result<()> {
+            self.revoked.lock().push(key.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn configured_secrets_lists_only_set_values() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        config.api_key = Some("sk-main".into());
+        config.composio.api_key = Some("  ".into());
+        assert_eq!(
+            configured_secrets(&config),
+            vec!["api_key", "web_search.brave_api_key"]
+        );
+        assert!(secret_slot(&mut config, "agents.missing.api_key").is_none());
+        assert!(secret_slot(&mut config, "gateway.port").is_none());
+    }
+
+    #[tokio::test]
+    async fn manual_rotation_saves_records_and_audits() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+
+        let report = rotate(
+            &mut config,
+            "web_search.brave_api_key",
+            NewSecret::Provided("brave-new".into()),
+            false,
+        )
+        .await
+        .unwrap();
+        assert!(!report.verified);
+        assert!(report.retired.is_none());
+        assert_eq!(report.old_fingerprint, Some(fingerprint("brave-old")));
+
+        let saved = std::fs::read_to_string(&config.config_path).unwrap();
+        assert!(saved.contains("brave-new"));
+        let now = Utc::now();
+        let history = load_history(&config);
+        assert_eq!(
+            secret_age_days(&config, &history, "web_search.brave_api_key", now),
+            Some(0)
+        );
+        config.web_search.brave_api_key = Some("edited".into());
+        assert_eq!(
+            secret_age_days(&config, &history, "web_search.brave_api_key", now),
+            None
+        );
+
+        let audit = std::fs::read_to_string(tmp.path().join("audit.log")).unwrap();
+        assert!(audit.contains("secret_rotation"));
+        assert!(audit.contains(&fingerprint("brave-new")));
+        assert!(!audit.contains("brave-new\""));
+    }
+
+    #[tokio::test]
+    async fn issued_rotation_revokes_old_key_unless_kept() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        let issuer = FakeIssuer::default();
+        let revoked = issuer.revoked.clone();
+
+        let report = rotate(
+            &mut config,
+            "web_search.brave_api_key",
+            NewSecret::Issued(Box::new(issuer)),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.retired, Some(Ok(())));
+        assert_eq!(*revoked.lock(), vec!["brave-old".to_string()]);
+        assert_eq!(
+            config.web_search.brave_api_key.as_deref(),
+            Some("brave-issued")
+        );
+
+        config.web_search.brave_api_key = Some("brave-old".into());
+        let issuer = FakeIssuer::default();
+        let revoked = issuer.revoked.clone();
+        let report = rotate(
+            &mut config,
+            "web_search.brave_api_key",
+            NewSecret::Issued(Box::new(issuer)),
+            true,
+        )
+        .await
+        .unwrap();
+        assert!(report.retired.is_none());
+        assert!(revoked.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_and_unchanged_secrets() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        let err = rotate(&mut config, "nope", NewSecret::Provided("x".into()), false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("web_search.brave_api_key"));
+        assert!(rotate(
+            &mut config,
+            "web_search.brave_api_key",
+            NewSecret::Provided("brave-old".into()),
+            false,
+        )
+        .await
+        .is_err());
+        assert!(!config.config_path.exists());
+    }
+
+    #[test]
+    fn masked_labels_match_prefix_and_suffix() {
+        let key = "sk-or-v1-0e6f44a9c1c96";
+        assert!(masked_label_matches("sk-or-v1-0e6...1c96", key));
+        assert!(!masked_label_matches("sk-or-v1-0e7...1c96", key));
+        assert!(!masked_label_matches("sk-or-v1-0e6", key));
+    }
+}
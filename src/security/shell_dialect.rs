@@ -0,0 +1,221 @@
+//! Shell syntax the command and path policy is checked against.
+//!
+//! The native runtime runs commands through the host shell: `sh -c` on Unix
+//! and, on Windows, PowerShell or `cmd.exe` (`[runtime].windows_shell`).
+//! [`SecurityPolicy`](super::SecurityPolicy) checks commands and paths with
+//! the matching dialect, so Windows syntax (drive letters, UNC paths, `\`
+//! separators, `%VAR%` expansion, `.exe` suffixes, `/switch` arguments)
+//! cannot slip past rules written for POSIX shells.
+
+use crate::config::{Config, WindowsShell};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShellDialect {
+    #[default]
+    Posix,
+    Cmd,
+    PowerShell,
+}
+
+/// `[runtime].windows_shell` is `cmd` (PowerShell otherwise).
+static WINDOWS_CMD: AtomicBool = AtomicBool::new(false);
+
+pub fn init_from_config(config: &Config) {
+    WINDOWS_CMD.store(
+        config.runtime.windows_shell == WindowsShell::Cmd,
+        Ordering::Relaxed,
+    );
+}
+
+/// Executable suffixes Windows resolves without being typed.
+const WINDOWS_EXECUTABLE_SUFFIXES: &[&str] = &[".exe", ".com", ".cmd", ".bat", ".ps1"];
+
+/// Device names Windows reserves in every directory.
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+impl ShellDialect {
+    /// Dialect of the shell the native runtime uses on this host.
+    pub fn host() -> Self {
+        if !cfg!(windows) {
+            Self::Posix
+        } else if WINDOWS_CMD.load(Ordering::Relaxed) {
+            Self::Cmd
+        } else {
+            Self::PowerShell
+        }
+    }
+
+    pub fn is_windows(self) -> bool {
+        matches!(self, Self::Cmd | Self::PowerShell)
+    }
+
+    /// Interpreter and the arguments that precede the command string.
+    pub fn program(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            Self::Posix => ("sh", &["-c"]),
+            Self::Cmd => ("cmd.exe", &["/D", "/S", "/C"]),
+            Self::PowerShell => (
+                "powershell.exe",
+                &["-NoProfile", "-NonInteractive", "-Command"],
+            ),
+        }
+    }
+
+    /// Name an allowlist entry is compared with: the last path component
+    /// and, on Windows, lowercased without an executable suffix
+    /// (`C:\Git\bin\GIT.EXE` → `git`).
+    pub fn command_name(self, raw: &str) -> String {
+        if !self.is_windows() {
+            return raw.rsplit('/').next().unwrap_or("").to_string();
+        }
+        let name = raw
+            .trim_matches(|c| c == '"' || c == '\'')
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        WINDOWS_EXECUTABLE_SUFFIXES
+            .iter()
+            .find_map(|suffix| name.strip_suffix(suffix))
+            .filter(|stem| !stem.is_empty())
+            .map_or_else(|| name.clone(), str::to_string)
+    }
+
+    /// Whether `path` is absolute for this dialect. On Windows that covers
+    /// drive-qualified (`C:\x`, and `C:x`, relative to that drive's current
+    /// directory), root-relative (`\x`) and UNC (`\\server\share`) forms.
+    pub fn is_absolute(self, path: &str) -> bool {
+        if !self.is_windows() {
+            return path.starts_with('/');
+        }
+        has_drive_prefix(path) || path.starts_with(['\\', '/'])
+    }
+
+    /// Windows paths that reach other machines or raw devices:
+    /// `\\server\share`, `\\?\C:\…`, `\\.\PhysicalDrive0`.
+    pub fn is_network_or_device_path(self, path: &str) -> bool {
+        self.is_windows() && (path.starts_with("\\\\") || path.starts_with("//"))
+    }
+
+    /// Path components, split on every separator the dialect accepts.
+    pub fn components(self, path: &str) -> Vec<&str> {
+        if self.is_windows() {
+            path.split(['/', '\\']).collect()
+        } else {
+            path.split('/').collect()
+        }
+    }
+
+    /// Whether a component names a reserved Windows device (`NUL`,
+    /// `COM1.txt`) or an alternate data stream (`notes.txt:hidden`).
+    pub fn is_special_component(self, component: &str) -> bool {
+        if !self.is_windows() {
+            return false;
+        }
+        let lower = component.trim_end_matches([' ', '.']).to_ascii_lowercase();
+        let stem = lower.split('.').next().unwrap_or_default();
+        WINDOWS_RESERVED_NAMES.contains(&stem) || lower.contains(':')
+    }
+
+    /// A `cmd`-style switch such as `/s`, `/ad` or `/A:H`, returning the
+    /// value after `:` (empty if it has none). Longer `/words` are treated
+    /// as paths.
+    pub fn switch_value(self, token: &str) -> Option<&str> {
+        if !self.is_windows() {
+            return None;
+        }
+        let body = token.strip_prefix('/')?;
+        let (name, value) = body.split_once(':').unwrap_or((body, ""));
+        let is_switch = (1..=2).contains(&name.len())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '?');
+        is_switch.then_some(value)
+    }
+
+    /// System directories to forbid by default on this dialect's host.
+    pub fn system_paths(self) -> Vec<String> {
+        if !self.is_windows() {
+            return Vec::new();
+        }
+        [
+            "C:\\Windows",
+            "C:\\Program Files",
+            "C:\\Program Files (x86)",
+            "C:\\ProgramData",
+            "C:\\Users",
+            "~/AppData",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    }
+}
+
+/// `C:` at the start of `path`.
+pub fn has_drive_prefix(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+/// Lowercased, `/`-separated form used to compare Windows paths.
+pub fn normalize_windows_path(path: &str) -> String {
+    let mut normalized = path.replace('\\', "/").to_ascii_lowercase();
+    while normalized.len() > 1 && normalized.ends_with('/') && !normalized.ends_with(":/") {
+        normalized.pop();
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_names_drop_windows_paths_and_suffixes() {
+        assert_eq!(ShellDialect::Posix.command_name("/usr/bin/git"), "git");
+        assert_eq!(ShellDialect::Posix.command_name("GIT.EXE"), "GIT.EXE");
+        assert_eq!(
+            ShellDialect::PowerShell.command_name("C:\\Program Files\\Git\\bin\\GIT.EXE"),
+            "git"
+        );
+        assert_eq!(ShellDialect::Cmd.command_name("build.cmd"), "build");
+        assert_eq!(ShellDialect::Cmd.command_name(".exe"), ".exe");
+    }
+
+    #[test]
+    fn windows_absolute_and_special_paths() {
+        let dialect = ShellDialect::Cmd;
+        for path in [
+            "C:\\Windows",
+            "c:relative",
+            "\\Users",
+            "/Users",
+            "\\\\srv\\share",
+        ] {
+            assert!(dialect.is_absolute(path), "{path}");
+        }
+        assert!(!dialect.is_absolute("src\\main.rs"));
+        assert!(!ShellDialect::Posix.is_absolute("C:\\Windows"));
+        assert!(dialect.is_network_or_device_path("\\\\?\\C:\\secret"));
+        assert!(dialect.is_special_component("NUL"));
+        assert!(dialect.is_special_component("com1.txt"));
+        assert!(dialect.is_special_component("notes.txt:hidden"));
+        assert!(!dialect.is_special_component("console.log"));
+    }
+
+    #[test]
+    fn short_slash_tokens_are_switches() {
+        let dialect = ShellDialect::Cmd;
+        assert_eq!(dialect.switch_value("/s"), Some(""));
+        assert_eq!(dialect.switch_value("/A:H"), Some("H"));
+        assert_eq!(
+            dialect.switch_value("/g:C:\\secret.txt"),
+            Some("C:\\secret.txt")
+        );
+        assert_eq!(dialect.switch_value("/Windows"), None);
+        assert_eq!(ShellDialect::Posix.switch_value("/s"), None);
+    }
+}
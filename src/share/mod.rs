@@ -0,0 +1,438 @@
+//! Expiring, redacted snapshots shared through the gateway.
+//!
+//! `zeroclaw share <trace-id|file>` snapshots a runtime-trace turn or a text
+//! file, redacts credentials, and stores it under `shares/` next to
+//! `config.toml`. The gateway serves it at `/share/<id>?exp=<unix>&sig=<hmac>`
+//! without pairing: the HMAC over id and expiry (keyed by `share.key`) is the
+//! capability, so people without ZeroClaw can open the link. Links stop
+//! working at expiry or when the share is revoked (its file deleted).
+//!
+//! Both live outside the workspace: an agent that could read the key could
+//! sign links, and one that could write `shares/` could publish pages.
+
+use crate::config::Config;
+use crate::observability::runtime_trace::{self, RuntimeTraceEvent};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SHARES_DIR: &str = "shares";
+const KEY_FILE: &str = "share.key";
+/// Files larger than this are refused rather than truncated.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+/// Longest accepted `--ttl`.
+const MAX_TTL_DAYS: i64 = 30;
+
+/// A stored snapshot. `content` is already redacted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Share {
+    pub id: String,
+    pub title: String,
+    /// `trace` or `file`.
+    pub kind: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub content: String,
+}
+
+impl Share {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// Shares and the signing key, kept in the config directory.
+pub struct ShareStore {
+    dir: PathBuf,
+    key_path: PathBuf,
+}
+
+impl ShareStore {
+    pub fn new(zeroclaw_dir: &Path) -> Self {
+        Self {
+            dir: zeroclaw_dir.join(SHARES_DIR),
+            key_path: zeroclaw_dir.join(KEY_FILE),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.config_path.parent().unwrap_or(&config.workspace_dir))
+    }
+
+    /// Store a new share; `content` must already be redacted.
+    pub fn create(&self, title: &str, kind: &str, content: String, ttl: Duration) -> Result<Share> {
+        let now = Utc::now();
+        let share = Share {
+            id: uuid::Uuid::new_v4().simple().to_string(),
+            title: title.to_string(),
+            kind: kind.to_string(),
+            created_at: now,
+            expires_at: now + ttl,
+            content,
+        };
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.path(&share.id)?;
+        fs::write(&path, serde_json::to_vec_pretty(&share)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(share)
+    }
+
+    /// Look up a share, deleting it if it has expired.
+    pub fn get(&self, id: &str) -> Result<Option<Share>> {
+        let path = self.path(id)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let share: Share = serde_json::from_slice(&fs::read(&path)?)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        if share.is_expired(Utc::now()) {
+            let _ = fs::remove_file(&path);
+            return Ok(None);
+        }
+        Ok(Some(share))
+    }
+
+    /// Active shares, newest first. Expired ones are deleted along the way.
+    pub fn list(&self) -> Result<Vec<Share>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut shares = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let Some(id) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .filter(|_| path.extension().is_some_and(|ext| ext == "json"))
+            else {
+                continue;
+            };
+            if let Some(share) = self.get(id)? {
+                shares.push(share);
+            }
+        }
+        shares.sort_by_key(|share| std::cmp::Reverse(share.created_at));
+        Ok(shares)
+    }
+
+    /// Delete a share so its links stop working. Returns whether it existed.
+    pub fn revoke(&self, id: &str) -> Result<bool> {
+        let path = self.path(id)?;
+        if !path.exists() {
+            return Ok(false);
+        }
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+        Ok(true)
+    }
+
+    /// Query string (`exp=..&sig=..`) that authorizes reading `share`.
+    pub fn signed_query(&self, share: &Share) -> Result<String> {
+        let expires = share.expires_at.timestamp();
+        let sig = hex::encode(self.mac(&share.id, expires)?.finalize().into_bytes());
+        Ok(format!("exp={expires}&sig={sig}"))
+    }
+
+    /// Check a link's signature and expiry (constant-time comparison).
+    pub fn verify(&self, id: &str, expires: i64, sig: &str) -> bool {
+        if expires <= Utc::now().timestamp() {
+            return false;
+        }
+        let Ok(expected) = hex::decode(sig) else {
+            return false;
+        };
+        self.mac(id, expires)
+            .is_ok_and(|mac| mac.verify_slice(&expected).is_ok())
+    }
+
+    fn mac(&self, id: &str, expires: i64) -> Result<Hmac<Sha256>> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.signing_key()?)
+            .context("Invalid share signing key")?;
+        mac.update(format!("{id}.{expires}").as_bytes());
+        Ok(mac)
+    }
+
+    /// Load the signing key, creating it on first use.
+    fn signing_key(&self) -> Result<Vec<u8>> {
+        match fs::read_to_string(&self.key_path) {
+            Ok(existing) => return decode_key(&existing),
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read {}", self.key_path.display()))
+            }
+            Err(_) => {}
+        }
+        if let Some(parent) = self.key_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let key: [u8; 32] = rand::random();
+        // Create the file private, so the key is never readable by others.
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        match options.open(&self.key_path) {
+            Ok(mut file) => std::io::Write::write_all(&mut file, hex::encode(key).as_bytes())
+                .with_context(|| format!("Failed to write {}", self.key_path.display()))?,
+            // Another process created it first; use theirs.
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                return decode_key(&fs::read_to_string(&self.key_path)?);
+            }
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to create {}", self.key_path.display()))
+            }
+        }
+        Ok(key.to_vec())
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            bail!("Invalid share id '{id}'");
+        }
+        Ok(self.dir.join(format!("{id}.json")))
+    }
+}
+
+fn decode_key(raw: &str) -> Result<Vec<u8>> {
+    hex::decode(raw.trim())
+        .ok()
+        .filter(|key| key.len() == 32)
+        .context("Corrupt share signing key")
+}
+
+/// Parse `--ttl` values like `30m`, `24h` or `7d` (at most 30 days).
+pub fn parse_ttl(raw: &str) -> Result<Duration> {
+    let raw = raw.trim();
+    let (count, unit) = raw.split_at(raw.len().saturating_sub(1));
+    let count: i64 = count
+        .parse()
+        .ok()
+        .filter(|count| *count > 0)
+        .with_context(|| format!("Invalid TTL '{raw}': expected e.g. 30m, 24h or 7d"))?;
+    let ttl = match unit {
+        "m" => Duration::minutes(count),
+        "h" => Duration::hours(count),
+        "d" => Duration::days(count),
+        _ => bail!("Invalid TTL '{raw}': expected e.g. 30m, 24h or 7d"),
+    };
+    if ttl > Duration::days(MAX_TTL_DAYS) {
+        bail!("TTL '{raw}' exceeds the {MAX_TTL_DAYS}-day maximum");
+    }
+    Ok(ttl)
+}
+
+/// Remove configured secret values and credential-shaped tokens.
+pub fn redact(text: &str, secrets: &[String]) -> String {
+    let mut redacted = text.to_string();
+    for secret in secrets.iter().filter(|s| s.trim().len() >= 8) {
+        redacted = redacted.replace(secret.as_str(), "[REDACTED]");
+    }
+    let redacted = crate::providers::scrub_secret_patterns(&redacted);
+    crate::agent::loop_::scrub_credentials(&redacted)
+}
+
+/// Snapshot content for `target`: a file path if one exists, otherwise a
+/// runtime-trace event id (expanded to every event of that turn).
+/// Returns `(kind, default title, unredacted text)`.
+pub fn snapshot(config: &Config, target: &str) -> Result<(&'static str, String, String)> {
+    let path = Path::new(target);
+    if path.is_file() {
+        let size = fs::metadata(path)?.len();
+        if size > MAX_FILE_BYTES {
+            bail!(
+                "{} is {size} bytes; only files up to {MAX_FILE_BYTES} bytes can be shared",
+                path.display()
+            );
+        }
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("{} is not a UTF-8 text file", path.display()))?;
+        let title = path
+            .file_name()
+            .map_or_else(|| target.to_string(), |name| name.to_string_lossy().into());
+        return Ok(("file", title, text));
+    }
+
+    let trace_path =
+        runtime_trace::resolve_trace_path(&config.observability, &config.workspace_dir);
+    let Some(event) = runtime_trace::find_event_by_id(&trace_path, target)? else {
+        bail!("'{target}' is neither a file nor a runtime trace id (see `zeroclaw doctor traces`)");
+    };
+    let events = match &event.turn_id {
+        Some(turn_id) => {
+            let mut turn = runtime_trace::load_events(&trace_path, usize::MAX, None, None)?;
+            turn.retain(|e| e.turn_id.as_ref() == Some(turn_id));
+            turn.reverse();
+            turn
+        }
+        None => vec![event.clone()],
+    };
+    let title = format!("Trace {}", event.turn_id.as_deref().unwrap_or(&event.id));
+    Ok(("trace", title, render_trace(&events)))
+}
+
+fn render_trace(events: &[RuntimeTraceEvent]) -> String {
+    let mut out = String::new();
+    for event in events {
+        let _ = write!(out, "[{}] {}", event.timestamp, event.event_type);
+        if let Some(channel) = &event.channel {
+            let _ = write!(out, " channel={channel}");
+        }
+        match (&event.provider, &event.model) {
+            (Some(provider), Some(model)) => {
+                let _ = write!(out, " model={provider}/{model}");
+            }
+            (None, Some(model)) => {
+                let _ = write!(out, " model={model}");
+            }
+            _ => {}
+        }
+        if let Some(success) = event.success {
+            let _ = write!(out, " success={success}");
+        }
+        out.push('\n');
+        if let Some(message) = event.message.as_deref().filter(|m| !m.is_empty()) {
+            let _ = writeln!(out, "{message}");
+        }
+        if !event.payload.is_null() {
+            let payload = serde_json::to_string_pretty(&event.payload).unwrap_or_default();
+            let _ = writeln!(out, "{payload}");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Public URL for a share, under `base_url` (the gateway or its tunnel).
+pub fn share_url(store: &ShareStore, share: &Share, base_url: &str) -> Result<String> {
+    Ok(format!(
+        "{}/share/{}?{}",
+        base_url.trim_end_matches('/'),
+        share.id,
+        store.signed_query(share)?
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn signed_links_verify_until_tampered_or_revoked() {
+        let tmp = TempDir::new().unwrap();
+        let store = ShareStore::new(tmp.path());
+        let share = store
+            .create("notes", "file", "hello".into(), Duration::hours(1))
+            .unwrap();
+        let url = share_url(&store, &share, "https://zc.example/").unwrap();
+        let query = url.split_once('?').unwrap().1;
+        let (exp, sig) = query.split_once('&').unwrap();
+        let exp: i64 = exp.strip_prefix("exp=").unwrap().parse().unwrap();
+        let sig = sig.strip_prefix("sig=").unwrap();
+
+        assert!(url.starts_with(&format!("https://zc.example/share/{}?", share.id)));
+        assert!(store.verify(&share.id, exp, sig));
+        assert!(!store.verify(&share.id, exp + 3600, sig));
+        assert!(!store.verify("otherid", exp, sig));
+        assert_eq!(store.get(&share.id).unwrap(), Some(share.clone()));
+
+        assert!(store.revoke(&share.id).unwrap());
+        assert!(store.get(&share.id).unwrap().is_none());
+        assert!(!store.revoke(&share.id).unwrap());
+    }
+
+    #[test]
+    fn store_lives_next_to_config_with_a_private_key() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        let store = ShareStore::from_config(&config);
+        let share = store
+            .create("notes", "file", "hello".into(), Duration::hours(1))
+            .unwrap();
+        store.signed_query(&share).unwrap();
+        assert!(tmp.path().join(KEY_FILE).exists());
+        assert!(tmp
+            .path()
+            .join(SHARES_DIR)
+            .join(format!("{}.json", share.id))
+            .exists());
+        assert!(!config.workspace_dir.exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(tmp.path().join(KEY_FILE))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fs::write(tmp.path().join(KEY_FILE), "").unwrap();
+        assert!(store.signed_query(&share).is_err());
+    }
+
+    #[test]
+    fn expired_shares_are_pruned() {
+        let tmp = TempDir::new().unwrap();
+        let store = ShareStore::new(tmp.path());
+        let expired = store
+            .create("old", "file", "x".into(), Duration::seconds(-1))
+            .unwrap();
+        let live = store
+            .create("new", "file", "y".into(), Duration::hours(1))
+            .unwrap();
+        let ids: Vec<_> = store.list().unwrap().into_iter().map(|s| s.id).collect();
+        assert_eq!(ids, [live.id]);
+        assert!(store.get(&expired.id).unwrap().is_none());
+        assert!(store.get("../config").is_err());
+    }
+
+    #[test]
+    fn redact_removes_configured_and_pattern_secrets() {
+        let text = "key my-provider-secret-value and sk-abc123def456 password=hunter2hunter2";
+        let redacted = redact(text, &["my-provider-secret-value".into()]);
+        assert!(!redacted.contains("my-provider-secret-value"));
+        assert!(!redacted.contains("sk-abc123def456"));
+        assert!(!redacted.contains("hunter2hunter2"));
+    }
+
+    #[test]
+    fn ttl_accepts_minutes_hours_days_within_limit() {
+        assert_eq!(parse_ttl("30m").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_ttl("24h").unwrap(), Duration::hours(24));
+        assert_eq!(parse_ttl("7d").unwrap(), Duration::days(7));
+        assert!(parse_ttl("31d").is_err());
+        assert!(parse_ttl("0h").is_err());
+        assert!(parse_ttl("1w").is_err());
+    }
+
+    #[test]
+    fn snapshot_reads_files_and_rejects_unknown_targets() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let file = tmp.path().join("out.txt");
+        fs::write(&file, "result").unwrap();
+        let (kind, title, text) = snapshot(&config, file.to_str().unwrap()).unwrap();
+        assert_eq!(
+            (kind, title.as_str(), text.as_str()),
+            ("file", "out.txt", "result")
+        );
+        assert!(snapshot(&config, "no-such-trace").is_err());
+    }
+}
@@ -222,6 +222,22 @@ fn audit_manifest_file(root: &Path, path: &Path, report: &mut SkillAuditReport)
         }
     }
 
+    if let Some(schedules) = parsed.get("schedules").and_then(toml::Value::as_array) {
+        for (idx, schedule) in schedules.iter().enumerate() {
+            if let Some(prompt) = schedule.get("prompt").and_then(toml::Value::as_str) {
+                if let Some(pattern) = detect_high_risk_snippet(prompt) {
+                    report.findings.push(format!(
+                        "{rel}: schedules[{idx}].prompt contains high-risk pattern ({pattern})."
+                    ));
+                }
+            } else {
+                report.findings.push(format!(
+                    "{rel}: schedules[{idx}] is missing a prompt field."
+                ));
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,386 @@
+//! Conversation-to-skill distillation (`zeroclaw skills distill`).
+//!
+//! One successful multi-step turn from the runtime trace becomes a reusable
+//! skill: the request turns into a prompt template, the successful tool calls
+//! into an ordered pipeline, and argument values that also appear in the
+//! request into `{{parameters}}` shared by both.
+
+use super::{SkillManifest, SkillMeta};
+use crate::observability::runtime_trace::RuntimeTraceEvent;
+use anyhow::{bail, Result};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+/// Shortest argument value worth turning into a parameter.
+const MIN_PARAMETER_LEN: usize = 3;
+
+/// One tool call of the distilled pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistilledStep {
+    pub tool: String,
+    pub arguments: Value,
+}
+
+/// A value lifted out of the conversation into a template placeholder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistilledParameter {
+    pub name: String,
+    pub example: String,
+}
+
+/// A skill proposal awaiting review.
+#[derive(Debug, Clone)]
+pub struct DistilledSkill {
+    pub name: String,
+    pub description: String,
+    pub template: String,
+    pub parameters: Vec<DistilledParameter>,
+    pub steps: Vec<DistilledStep>,
+}
+
+impl DistilledSkill {
+    /// Pipeline instructions injected into the agent prompt.
+    fn instructions(&self) -> String {
+        let mut out = format!(
+            "Repeatable pipeline distilled from a successful conversation.\n\
+             Use it for requests shaped like: {}\n",
+            self.template
+        );
+        if !self.parameters.is_empty() {
+            out.push_str("Parameters:\n");
+            for parameter in &self.parameters {
+                let _ = writeln!(out, "- {} (e.g. {:?})", parameter.name, parameter.example);
+            }
+        }
+        out.push_str("Steps:\n");
+        for (index, step) in self.steps.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{}. `{}` with {}",
+                index + 1,
+                step.tool,
+                step.arguments
+            );
+        }
+        out.push_str(
+            "Fill the parameters from the user's request, run the steps in order, \
+             and adapt if a step fails.",
+        );
+        out
+    }
+
+    /// `SKILL.toml` content for this proposal.
+    pub fn manifest(&self) -> Result<String> {
+        let manifest = SkillManifest {
+            skill: SkillMeta {
+                name: self.name.clone(),
+                description: self.description.clone(),
+                version: super::default_version(),
+                author: None,
+                tags: vec!["distilled".into()],
+            },
+            tools: Vec::new(),
+            prompts: vec![self.instructions()],
+            schedules: Vec::new(),
+        };
+        Ok(toml::to_string_pretty(&manifest)?)
+    }
+}
+
+/// Distill the turn `session` (a trace `turn_id`, or the id of any event in
+/// it) from chronologically ordered trace events.
+pub fn distill(events: &[RuntimeTraceEvent], session: &str) -> Result<DistilledSkill> {
+    let turn_id = if events.iter().any(|e| e.turn_id.as_deref() == Some(session)) {
+        session
+    } else {
+        match events.iter().find(|e| e.id == session) {
+            Some(RuntimeTraceEvent {
+                turn_id: Some(turn_id),
+                ..
+            }) => turn_id.as_str(),
+            _ => bail!(
+                "Session '{session}' not found in the runtime trace (see `zeroclaw doctor traces`)"
+            ),
+        }
+    };
+    let turn: Vec<&RuntimeTraceEvent> = events
+        .iter()
+        .filter(|e| e.turn_id.as_deref() == Some(turn_id))
+        .collect();
+
+    let Some(last) = turn
+        .iter()
+        .rev()
+        .find(|e| e.event_type == "turn_final_response" && e.success == Some(true))
+    else {
+        bail!("Session '{turn_id}' did not finish successfully; only successful conversations can be distilled");
+    };
+    let request = last
+        .payload
+        .get("request")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|r| !r.is_empty());
+    let Some(request) = request else {
+        bail!(
+            "Session '{turn_id}' has no recorded request (it predates distillation support or trace redaction was on)"
+        );
+    };
+
+    // Pair each start with the next result of the same tool; keep successes.
+    let mut outcomes: HashMap<&str, VecDeque<bool>> = HashMap::new();
+    for event in turn.iter().filter(|e| e.event_type == "tool_call_result") {
+        if let Some(tool) = event.payload.get("tool").and_then(Value::as_str) {
+            outcomes
+                .entry(tool)
+                .or_default()
+                .push_back(event.success == Some(true));
+        }
+    }
+    let mut steps = Vec::new();
+    for event in turn.iter().filter(|e| e.event_type == "tool_call_start") {
+        let Some(tool) = event.payload.get("tool").and_then(Value::as_str) else {
+            continue;
+        };
+        let succeeded = outcomes
+            .get_mut(tool)
+            .and_then(VecDeque::pop_front)
+            .unwrap_or(false);
+        if !succeeded {
+            continue;
+        }
+        let arguments = match event.payload.get("arguments") {
+            Some(Value::String(raw)) => {
+                serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.clone()))
+            }
+            Some(other) => other.clone(),
+            None => Value::Null,
+        };
+        steps.push(DistilledStep {
+            tool: tool.to_string(),
+            arguments,
+        });
+    }
+    if steps.len() < 2 {
+        bail!(
+            "Session '{turn_id}' made {} successful tool call(s); distillation needs a multi-step conversation",
+            steps.len()
+        );
+    }
+
+    let parameters = extract_parameters(request, &steps);
+    let mut template = request.to_string();
+    for parameter in &parameters {
+        template = template.replace(&parameter.example, &placeholder(&parameter.name));
+    }
+    for step in &mut steps {
+        templatize(&mut step.arguments, &parameters);
+    }
+
+    let summary: String = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take(80)
+        .collect();
+    Ok(DistilledSkill {
+        name: default_name(request, turn_id),
+        description: format!("Distilled from session {turn_id}: {summary}"),
+        template,
+        parameters,
+        steps,
+    })
+}
+
+fn placeholder(name: &str) -> String {
+    format!("{{{{{name}}}}}")
+}
+
+/// Top-level string arguments that also occur verbatim in the request,
+/// longest first so overlapping values substitute cleanly.
+fn extract_parameters(request: &str, steps: &[DistilledStep]) -> Vec<DistilledParameter> {
+    let mut parameters: Vec<DistilledParameter> = Vec::new();
+    for step in steps {
+        let Value::Object(arguments) = &step.arguments else {
+            continue;
+        };
+        for (key, value) in arguments {
+            let Some(value) = value.as_str().map(str::trim) else {
+                continue;
+            };
+            if value.chars().count() < MIN_PARAMETER_LEN
+                || !request.contains(value)
+                || parameters.iter().any(|p| p.example == value)
+            {
+                continue;
+            }
+            let base: String = key
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() {
+                        c.to_ascii_lowercase()
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while parameters.iter().any(|p| p.name == name) {
+                name = format!("{base}_{suffix}");
+                suffix += 1;
+            }
+            parameters.push(DistilledParameter {
+                name,
+                example: value.to_string(),
+            });
+        }
+    }
+    parameters.sort_by_key(|p| std::cmp::Reverse(p.example.len()));
+    parameters
+}
+
+fn templatize(value: &mut Value, parameters: &[DistilledParameter]) {
+    match value {
+        Value::String(text) => {
+            for parameter in parameters {
+                *text = text.replace(&parameter.example, &placeholder(&parameter.name));
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| templatize(v, parameters)),
+        Value::Object(map) => map.values_mut().for_each(|v| templatize(v, parameters)),
+        _ => {}
+    }
+}
+
+/// Slug of the request's first words, e.g. `summarize-open-github-issues`.
+fn default_name(request: &str, turn_id: &str) -> String {
+    let words: Vec<String> = request
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| w.len() > 1)
+        .take(4)
+        .map(str::to_ascii_lowercase)
+        .collect();
+    if words.is_empty() {
+        format!("distilled-{}", turn_id.chars().take(8).collect::<String>())
+    } else {
+        words.join("-")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(
+        id: &str,
+        event_type: &str,
+        success: Option<bool>,
+        payload: Value,
+    ) -> RuntimeTraceEvent {
+        RuntimeTraceEvent {
+            id: id.into(),
+            timestamp: "2026-01-01T00:00:00Z".into(),
+            event_type: event_type.into(),
+            channel: Some("cli".into()),
+            provider: None,
+            model: None,
+            turn_id: Some("turn-1".into()),
+            session: None,
+            success,
+            message: None,
+            payload,
+        }
+    }
+
+    fn start(id: &str, tool: &str, arguments: Value) -> RuntimeTraceEvent {
+        event(
+            id,
+            "tool_call_start",
+            None,
+            json!({"tool": tool, "arguments": arguments.to_string()}),
+        )
+    }
+
+    fn result(id: &str, tool: &str, success: bool) -> RuntimeTraceEvent {
+        event(id, "tool_call_result", Some(success), json!({"tool": tool}))
+    }
+
+    fn session() -> Vec<RuntimeTraceEvent> {
+        vec![
+            start("e1", "file_read", json!({"path": "notes/todo.md"})),
+            result("e2", "file_read", true),
+            start("e3", "shell", json!({"command": "rm -rf /"})),
+            result("e4", "shell", false),
+            start("e5", "shell", json!({"command": "wc -l notes/todo.md"})),
+            result("e6", "shell", true),
+            event(
+                "e7",
+                "turn_final_response",
+                Some(true),
+                json!({"request": "Count the lines in notes/todo.md", "text": "42"}),
+            ),
+        ]
+    }
+
+    #[test]
+    fn distills_successful_steps_into_parameterized_pipeline() {
+        let skill = distill(&session(), "e5").unwrap();
+
+        assert_eq!(skill.name, "count-the-lines-in");
+        assert_eq!(skill.template, "Count the lines in {{path}}");
+        assert_eq!(
+            skill.parameters,
+            [DistilledParameter {
+                name: "path".into(),
+                example: "notes/todo.md".into()
+            }]
+        );
+        assert_eq!(
+            skill.steps,
+            [
+                DistilledStep {
+                    tool: "file_read".into(),
+                    arguments: json!({"path": "{{path}}"})
+                },
+                DistilledStep {
+                    tool: "shell".into(),
+                    arguments: json!({"command": "wc -l {{path}}"})
+                },
+            ]
+        );
+
+        let manifest = skill.manifest().unwrap();
+        let parsed: SkillManifest = toml::from_str(&manifest).unwrap();
+        assert_eq!(parsed.skill.tags, ["distilled"]);
+        assert!(parsed.prompts[0].contains("2. `shell` with"));
+        assert!(!manifest.contains("rm -rf"));
+    }
+
+    #[test]
+    fn rejects_unsuccessful_single_step_or_unknown_sessions() {
+        let mut failed = session();
+        failed.pop();
+        assert!(distill(&failed, "turn-1")
+            .unwrap_err()
+            .to_string()
+            .contains("did not finish successfully"));
+
+        let single: Vec<_> = session()
+            .into_iter()
+            .filter(|e| e.id != "e5" && e.id != "e6")
+            .collect();
+        assert!(distill(&single, "turn-1")
+            .unwrap_err()
+            .to_string()
+            .contains("multi-step"));
+
+        assert!(distill(&session(), "missing")
+            .unwrap_err()
+            .to_string()
+            .contains("not found"));
+    }
+}
@@ -7,10 +7,13 @@ use std::process::Command;
 use std::time::{Duration, SystemTime};
 
 mod audit;
+mod distill;
 
 const OPEN_SKILLS_REPO_URL: &str = "https://github.com/besoeasy/open-skills";
 const OPEN_SKILLS_SYNC_MARKER: &str = ".zeroclaw-open-skills-sync";
 const OPEN_SKILLS_SYNC_INTERVAL_SECS: u64 = 60 * 60 * 24 * 7;
+/// Marker file that keeps a workspace skill installed but unloaded.
+const SKILL_DISABLED_MARKER: &str = ".disabled";
 
 /// A skill is a user-defined or community-built capability.
 /// Skills live in `~/.zeroclaw/workspace/skills/<name>/SKILL.md`
@@ -53,6 +56,31 @@ struct SkillManifest {
     tools: Vec<SkillTool>,
     #[serde(default)]
     prompts: Vec<String>,
+    #[serde(default)]
+    schedules: Vec<SkillSchedule>,
+}
+
+/// A scheduled trigger declared by a skill (`[[schedules]]` in SKILL.toml).
+///
+/// Registered as an agent cron job by `skills enable` and removed by
+/// `skills disable`/`skills remove`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillSchedule {
+    /// Unique name within the skill; the cron job is named `skill:<skill>:<name>`
+    pub name: String,
+    /// 5-field cron expression
+    pub cron: String,
+    /// Optional IANA timezone (defaults to UTC)
+    #[serde(default)]
+    pub tz: Option<String>,
+    /// Prompt the agent runs on each trigger
+    pub prompt: String,
+    /// Channel to announce the result on (requires `to`)
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Recipient on `channel`
+    #[serde(default)]
+    pub to: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,7 +130,8 @@ fn load_skills_with_open_skills_config(
     skills
 }
 
-fn load_workspace_skills(workspace_dir: &Path) -> Vec<Skill> {
+/// Load only the skills installed in the workspace (no open-skills sync).
+pub fn load_workspace_skills(workspace_dir: &Path) -> Vec<Skill> {
     let skills_dir = workspace_dir.join("skills");
     load_skills_from_directory(&skills_dir)
 }
@@ -120,7 +149,7 @@ fn load_skills_from_directory(skills_dir: &Path) -> Vec<Skill> {
 
     for entry in entries.flatten() {
         let path = entry.path();
-        if !path.is_dir() {
+        if !path.is_dir() || path.join(SKILL_DISABLED_MARKER).exists() {
             continue;
         }
 
@@ -593,6 +622,134 @@ pub fn skills_dir(workspace_dir: &Path) -> PathBuf {
     workspace_dir.join("skills")
 }
 
+fn skill_cron_prefix(skill_name: &str) -> String {
+    format!("skill:{skill_name}:")
+}
+
+/// Resolve an installed workspace skill directory, rejecting path traversal.
+fn installed_skill_dir(workspace_dir: &Path, name: &str) -> Result<PathBuf> {
+    if name.is_empty() || name.contains("..") || name.contains('/') || name.contains('\\') {
+        anyhow::bail!("Invalid skill name: {name}");
+    }
+
+    let skill_path = skills_dir(workspace_dir).join(name);
+
+    // Verify the resolved path is actually inside the skills directory
+    let canonical_skills = skills_dir(workspace_dir)
+        .canonicalize()
+        .unwrap_or_else(|_| skills_dir(workspace_dir));
+    if let Ok(canonical_skill) = skill_path.canonicalize() {
+        if !canonical_skill.starts_with(&canonical_skills) {
+            anyhow::bail!("Skill path escapes skills directory: {name}");
+        }
+    }
+
+    if !skill_path.exists() {
+        anyhow::bail!("Skill not found: {name}");
+    }
+    Ok(skill_path)
+}
+
+fn load_skill_schedules(skill_dir: &Path) -> Result<Vec<SkillSchedule>> {
+    let manifest_path = skill_dir.join("SKILL.toml");
+    if !manifest_path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&manifest_path)?;
+    let manifest: SkillManifest = toml::from_str(&content)
+        .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
+
+    let mut seen = HashSet::new();
+    for schedule in &manifest.schedules {
+        let name = schedule.name.trim();
+        if name.is_empty() || name.contains(':') {
+            anyhow::bail!("schedule names must be non-empty and must not contain ':'");
+        }
+        if !seen.insert(name.to_string()) {
+            anyhow::bail!("duplicate schedule name: {name}");
+        }
+        if schedule.prompt.trim().is_empty() {
+            anyhow::bail!("schedule '{name}' has an empty prompt");
+        }
+        if schedule.channel.is_some() != schedule.to.is_some() {
+            anyhow::bail!("schedule '{name}' must set both channel and to, or neither");
+        }
+    }
+    Ok(manifest.schedules)
+}
+
+/// Remove every cron job registered for a skill. Returns the number removed.
+pub fn unregister_skill_schedules(
+    config: &crate::config::Config,
+    skill_name: &str,
+) -> Result<usize> {
+    let prefix = skill_cron_prefix(skill_name);
+    let mut removed = 0;
+    for job in crate::cron::list_jobs(config)? {
+        if job
+            .name
+            .as_deref()
+            .is_some_and(|name| name.starts_with(&prefix))
+        {
+            crate::cron::remove_job(config, &job.id)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// (Re)register the cron jobs declared by an installed skill's manifest.
+///
+/// Existing jobs for the skill are replaced, so calling this repeatedly is
+/// idempotent. On failure no jobs are left registered for the skill.
+pub fn register_skill_schedules(
+    config: &crate::config::Config,
+    skill_name: &str,
+) -> Result<Vec<crate::cron::CronJob>> {
+    let skill_dir = installed_skill_dir(&config.workspace_dir, skill_name)?;
+    let schedules = load_skill_schedules(&skill_dir)?;
+    unregister_skill_schedules(config, skill_name)?;
+
+    let prefix = skill_cron_prefix(skill_name);
+    let mut jobs = Vec::with_capacity(schedules.len());
+    for schedule in schedules {
+        let delivery = match (schedule.channel, schedule.to) {
+            (Some(channel), Some(to)) => Some(crate::cron::DeliveryConfig {
+                mode: "announce".into(),
+                channel: Some(channel),
+                to: Some(to),
+                best_effort: true,
+            }),
+            _ => None,
+        };
+        let result = crate::cron::add_agent_job(
+            config,
+            Some(format!("{prefix}{}", schedule.name.trim())),
+            crate::cron::Schedule::Cron {
+                expr: schedule.cron.clone(),
+                tz: schedule.tz.clone(),
+            },
+            &schedule.prompt,
+            crate::cron::SessionTarget::Isolated,
+            None,
+            crate::cron::JobContext::default(),
+            delivery,
+            false,
+        );
+        match result {
+            Ok(job) => jobs.push(job),
+            Err(err) => {
+                let _ = unregister_skill_schedules(config, skill_name);
+                return Err(err.context(format!(
+                    "failed to register schedule '{}' ({})",
+                    schedule.name, schedule.cron
+                )));
+            }
+        }
+    }
+    Ok(jobs)
+}
+
 /// Initialize the skills directory with a README
 pub fn init_skills_dir(workspace_dir: &Path) -> Result<()> {
     let dir = skills_dir(workspace_dir);
@@ -805,6 +962,26 @@ fn install_local_skill_source(source: &str, skills_path: &Path) -> Result<(PathB
     }
 }
 
+fn install_distilled_skill(skills_path: &Path, name: &str, manifest: &str) -> Result<PathBuf> {
+    if name.is_empty() || name.contains("..") || name.contains('/') || name.contains('\\') {
+        anyhow::bail!("Invalid skill name: {name}");
+    }
+    let dest = skills_path.join(name);
+    if dest.exists() {
+        anyhow::bail!("Destination skill already exists: {}", dest.display());
+    }
+
+    std::fs::create_dir_all(&dest)?;
+    let written = std::fs::write(dest.join("SKILL.toml"), manifest)
+        .map_err(anyhow::Error::from)
+        .and_then(|()| enforce_skill_security_audit(&dest));
+    if let Err(err) = written {
+        let _ = std::fs::remove_dir_all(&dest);
+        return Err(err);
+    }
+    Ok(dest)
+}
+
 fn install_git_skill_source(source: &str, skills_path: &Path) -> Result<(PathBuf, usize)> {
     let before = snapshot_skill_children(skills_path)?;
     let output = std::process::Command::new("git")
@@ -934,33 +1111,105 @@ pub fn handle_command(command: crate::SkillCommands, config: &crate::config::Con
             Ok(())
         }
         crate::SkillCommands::Remove { name } => {
-            // Reject path traversal attempts
-            if name.contains("..") || name.contains('/') || name.contains('\\') {
-                anyhow::bail!("Invalid skill name: {name}");
-            }
+            let skill_path = installed_skill_dir(workspace_dir, &name)?;
 
-            let skill_path = skills_dir(workspace_dir).join(&name);
+            let unscheduled = unregister_skill_schedules(config, &name)?;
+            std::fs::remove_dir_all(&skill_path)?;
+            println!(
+                "  {} Skill '{}' removed.",
+                console::style("✓").green().bold(),
+                name
+            );
+            if unscheduled > 0 {
+                println!("    Removed {unscheduled} scheduled job(s).");
+            }
+            Ok(())
+        }
+        crate::SkillCommands::Enable { name } => {
+            let skill_path = installed_skill_dir(workspace_dir, &name)?;
 
-            // Verify the resolved path is actually inside the skills directory
-            let canonical_skills = skills_dir(workspace_dir)
-                .canonicalize()
-                .unwrap_or_else(|_| skills_dir(workspace_dir));
-            if let Ok(canonical_skill) = skill_path.canonicalize() {
-                if !canonical_skill.starts_with(&canonical_skills) {
-                    anyhow::bail!("Skill path escapes skills directory: {name}");
-                }
+            let report = audit::audit_skill_directory(&skill_path)?;
+            if !report.is_clean() {
+                anyhow::bail!("Skill '{name}' failed security audit: {}", report.summary());
             }
 
-            if !skill_path.exists() {
-                anyhow::bail!("Skill not found: {name}");
+            let marker = skill_path.join(SKILL_DISABLED_MARKER);
+            if marker.exists() {
+                std::fs::remove_file(&marker)?;
             }
 
-            std::fs::remove_dir_all(&skill_path)?;
+            let jobs = register_skill_schedules(config, &name)?;
             println!(
-                "  {} Skill '{}' removed.",
+                "  {} Skill '{}' enabled.",
                 console::style("✓").green().bold(),
                 name
             );
+            for job in &jobs {
+                println!(
+                    "    Scheduled {} ({}) next={}",
+                    job.name.as_deref().unwrap_or_default(),
+                    job.expression,
+                    job.next_run.to_rfc3339()
+                );
+            }
+            Ok(())
+        }
+        crate::SkillCommands::Disable { name } => {
+            let skill_path = installed_skill_dir(workspace_dir, &name)?;
+
+            std::fs::write(skill_path.join(SKILL_DISABLED_MARKER), b"disabled")?;
+            let removed = unregister_skill_schedules(config, &name)?;
+            println!(
+                "  {} Skill '{}' disabled ({} scheduled job(s) removed).",
+                console::style("✓").green().bold(),
+                name,
+                removed
+            );
+            Ok(())
+        }
+        crate::SkillCommands::Distill {
+            session,
+            name,
+            install,
+        } => {
+            let trace_path = crate::observability::runtime_trace::resolve_trace_path(
+                &config.observability,
+                workspace_dir,
+            );
+            let mut events = crate::observability::runtime_trace::load_events(
+                &trace_path,
+                usize::MAX,
+                None,
+                None,
+            )?;
+            events.reverse();
+            let mut skill = distill::distill(&events, &session)?;
+            if let Some(name) = name {
+                skill.name = name;
+            }
+            let manifest = skill.manifest()?;
+
+            println!(
+                "Proposed skill '{}' ({} steps, {} parameters):",
+                skill.name,
+                skill.steps.len(),
+                skill.parameters.len()
+            );
+            println!();
+            println!("{manifest}");
+            if !install {
+                println!("Review the proposal, then re-run with --install to add it.");
+                return Ok(());
+            }
+
+            let skills_path = skills_dir(workspace_dir);
+            std::fs::create_dir_all(&skills_path)?;
+            let dest = install_distilled_skill(&skills_path, &skill.name, &manifest)?;
+            println!(
+                "  {} Skill installed and audited: {}",
+                console::style("✓").green().bold(),
+                dest.display()
+            );
             Ok(())
         }
     }
@@ -1471,6 +1720,101 @@ description = "Bare minimum"
         assert_eq!(skills[0].name, "http_request");
         assert_ne!(skills[0].name, "CONTRIBUTING");
     }
+
+    fn schedule_test_config(dir: &tempfile::TempDir) -> crate::config::Config {
+        let config = crate::config::Config {
+            workspace_dir: dir.path().join("workspace"),
+            config_path: dir.path().join("config.toml"),
+            ..crate::config::Config::default()
+        };
+        fs::create_dir_all(config.workspace_dir.join("skills")).unwrap();
+        config
+    }
+
+    #[test]
+    fn skill_schedules_register_and_unregister_namespaced_jobs() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = schedule_test_config(&dir);
+        let skill_dir = config.workspace_dir.join("skills/briefing");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(
+            skill_dir.join("SKILL.toml"),
+            r#"
+[skill]
+name = "briefing"
+description = "Morning briefing"
+
+[[schedules]]
+name = "morning"
+cron = "0 7 * * 1-5"
+tz = "Europe/Berlin"
+prompt = "Summarize today's calendar and unread mail"
+channel = "telegram"
+to = "123456"
+"#,
+        )
+        .unwrap();
+
+        let jobs = register_skill_schedules(&config, "briefing").unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].name.as_deref(), Some("skill:briefing:morning"));
+        assert_eq!(jobs[0].delivery.mode, "announce");
+
+        // Re-registering replaces instead of duplicating.
+        register_skill_schedules(&config, "briefing").unwrap();
+        assert_eq!(crate::cron::list_jobs(&config).unwrap().len(), 1);
+
+        assert_eq!(unregister_skill_schedules(&config, "briefing").unwrap(), 1);
+        assert!(crate::cron::list_jobs(&config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn skill_schedules_reject_duplicate_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = schedule_test_config(&dir);
+        let skill_dir = config.workspace_dir.join("skills/dup");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(
+            skill_dir.join("SKILL.toml"),
+            r#"
+[skill]
+name = "dup"
+description = "Duplicate schedules"
+
+[[schedules]]
+name = "tick"
+cron = "*/5 * * * *"
+prompt = "a"
+
+[[schedules]]
+name = "tick"
+cron = "*/10 * * * *"
+prompt = "b"
+"#,
+        )
+        .unwrap();
+
+        let err = register_skill_schedules(&config, "dup").unwrap_err();
+        assert!(err.to_string().contains("duplicate schedule name"));
+        assert!(crate::cron::list_jobs(&config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn disabled_skills_are_not_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let skills_dir = dir.path().join("skills");
+        fs::create_dir_all(skills_dir.join("quiet")).unwrap();
+        fs::write(
+            skills_dir.join("quiet/SKILL.md"),
+            "# Quiet
+Does nothing.
+",
+        )
+        .unwrap();
+        fs::write(skills_dir.join("quiet").join(SKILL_DISABLED_MARKER), "").unwrap();
+
+        assert!(load_skills_from_directory(&skills_dir).is_empty());
+    }
 }
 
 #[cfg(test)]
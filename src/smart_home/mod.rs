@@ -0,0 +1,137 @@
+//! Zigbee/Z-Wave devices over MQTT (`[smart_home]`).
+//!
+//! Connects to the broker used by zigbee2mqtt and/or zwave-js-ui, discovers
+//! devices from the bridges' retained topics and keeps the latest state per
+//! device. Device-class profiles ([`profiles`]) turn bridge payloads into
+//! typed devices, so the `smart_home_*` tools can switch lights and read
+//! sensors instead of publishing raw topics.
+//!
+//! One connection runs per process. The daemon starts it at boot; tools start
+//! it on first use in one-shot agent runs.
+
+pub mod profiles;
+
+pub use profiles::{Device, SwitchAction};
+
+use crate::config::SmartHomeConfig;
+use anyhow::Result;
+use chrono::Utc;
+use parking_lot::Mutex;
+use profiles::Registry;
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS, Transport};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Seconds between reconnect attempts after a broker error.
+const RECONNECT_SECS: u64 = 5;
+/// zigbee2mqtt's `bridge/devices` payload lists every device with its exposes.
+const MAX_PACKET_BYTES: usize = 4 * 1024 * 1024;
+/// How long tools wait for retained discovery topics on a fresh connection.
+pub const DISCOVERY_WAIT: Duration = Duration::from_secs(3);
+
+static REGISTRY: LazyLock<Mutex<Registry>> = LazyLock::new(|| Mutex::new(Registry::default()));
+static CLIENT: Mutex<Option<AsyncClient>> = Mutex::new(None);
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Start the broker connection for this process (no-op if already running).
+pub fn ensure_started(config: &SmartHomeConfig) {
+    if STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let config = config.clone();
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = run_connection(&config).await {
+                tracing::warn!("Smart home MQTT connection failed: {e:#}");
+                crate::health::mark_component_error("smart_home", format!("{e:#}"));
+            }
+            *CLIENT.lock() = None;
+            tokio::time::sleep(Duration::from_secs(RECONNECT_SECS)).await;
+        }
+    });
+}
+
+/// Start the connection and give retained discovery topics a moment to
+/// arrive if nothing is known yet.
+pub async fn ready(config: &SmartHomeConfig) {
+    ensure_started(config);
+    let deadline = tokio::time::Instant::now() + DISCOVERY_WAIT;
+    while REGISTRY.lock().is_empty() && tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Snapshot of all known devices, sorted by name.
+pub fn devices() -> Vec<Device> {
+    REGISTRY.lock().list().cloned().collect()
+}
+
+/// Look up a device by name (case-insensitive).
+pub fn device(name: &str) -> Option<Device> {
+    REGISTRY.lock().get(name).cloned()
+}
+
+/// Publish a JSON command to the broker.
+pub async fn publish(topic: &str, payload: &serde_json::Value) -> Result<()> {
+    let client = CLIENT
+        .lock()
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("Not connected to the smart home MQTT broker"))?;
+    client
+        .publish(topic, QoS::AtLeastOnce, false, payload.to_string())
+        .await?;
+    Ok(())
+}
+
+fn mqtt_options(config: &SmartHomeConfig) -> Result<MqttOptions> {
+    let url = reqwest::Url::parse(config.broker_url.trim())?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow::anyhow!("smart_home.broker_url has no host"))?;
+    let tls = url.scheme() == "mqtts";
+    let port = url.port().unwrap_or(if tls { 8883 } else { 1883 });
+
+    let mut options = MqttOptions::new(config.client_id.trim(), host, port);
+    options.set_keep_alive(Duration::from_secs(30));
+    options.set_max_packet_size(MAX_PACKET_BYTES, MAX_PACKET_BYTES);
+    if let Some(username) = config.username.as_deref() {
+        options.set_credentials(username, config.password.as_deref().unwrap_or_default());
+    }
+    if tls {
+        options.set_transport(Transport::tls_with_default_config());
+    }
+    Ok(options)
+}
+
+/// Connect, subscribe to the bridge topics and feed the registry until the
+/// connection drops.
+async fn run_connection(config: &SmartHomeConfig) -> Result<()> {
+    let (client, mut eventloop) = AsyncClient::new(mqtt_options(config)?, 64);
+    *CLIENT.lock() = Some(client.clone());
+
+    let zigbee = config.zigbee2mqtt_topic.as_deref().map(str::trim);
+    let zwave = config.zwave_topic.as_deref().map(str::trim);
+    loop {
+        match eventloop.poll().await? {
+            Event::Incoming(Packet::ConnAck(_)) => {
+                for prefix in zigbee.iter().chain(zwave.iter()) {
+                    client.try_subscribe(format!("{prefix}/#"), QoS::AtMostOnce)?;
+                }
+                tracing::info!(broker = %config.broker_url, "Smart home MQTT connected");
+                crate::health::mark_component_ok("smart_home");
+            }
+            Event::Incoming(Packet::Publish(message)) => {
+                let mut registry = REGISTRY.lock();
+                let now = Utc::now();
+                if let Some(base) = zigbee.filter(|base| message.topic.starts_with(base)) {
+                    registry.apply_zigbee(base, &message.topic, &message.payload, now);
+                }
+                if let Some(prefix) = zwave.filter(|prefix| message.topic.starts_with(prefix)) {
+                    registry.apply_zwave(prefix, &message.topic, &message.payload, now);
+                }
+            }
+            _ => {}
+        }
+    }
+}
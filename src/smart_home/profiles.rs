@@ -0,0 +1,557 @@
+//! Device-class profiles for zigbee2mqtt and zwave-js-ui payloads.
+//!
+//! Both bridges are normalised onto zigbee2mqtt-style state keys (`state`,
+//! `brightness`, `contact`, `occupancy`, `temperature`, `humidity`,
+//! `battery`) so tools can read and switch devices without knowing which
+//! radio they are on.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// zwave-js `Door state` notification values.
+const ZWAVE_DOOR_OPEN: i64 = 22;
+const ZWAVE_DOOR_CLOSED: i64 = 23;
+/// zwave-js `Motion sensor status` value for detected motion.
+const ZWAVE_MOTION_DETECTED: i64 = 8;
+/// Maximum level of a Z-Wave multilevel switch.
+const ZWAVE_LEVEL_MAX: u64 = 99;
+/// Z-Wave multilevel "restore previous level".
+const ZWAVE_LEVEL_RESTORE: u64 = 255;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Bridge {
+    Zigbee2mqtt,
+    ZwaveJs,
+}
+
+/// What kind of device this is; ordered by precedence when a device exposes
+/// several features (a light with a temperature sensor is a light).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceClass {
+    Light,
+    Switch,
+    ContactSensor,
+    MotionSensor,
+    ClimateSensor,
+    Other,
+}
+
+impl DeviceClass {
+    /// Lights and switches can be turned on/off.
+    pub fn is_switchable(self) -> bool {
+        matches!(self, Self::Light | Self::Switch)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Switch => "switch",
+            Self::ContactSensor => "contact_sensor",
+            Self::MotionSensor => "motion_sensor",
+            Self::ClimateSensor => "climate_sensor",
+            Self::Other => "other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwitchAction {
+    On,
+    Off,
+    Toggle,
+}
+
+impl SwitchAction {
+    pub fn parse(action: &str) -> Option<Self> {
+        match action {
+            "on" => Some(Self::On),
+            "off" => Some(Self::Off),
+            "toggle" => Some(Self::Toggle),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Device {
+    /// zigbee2mqtt friendly name, or the zwave-js-ui node topic path.
+    pub name: String,
+    pub bridge: Bridge,
+    pub class: DeviceClass,
+    pub model: Option<String>,
+    /// Raw brightness scale for dimmable lights.
+    pub brightness_max: Option<u64>,
+    /// Latest values, normalised to zigbee2mqtt keys.
+    pub state: Map<String, Value>,
+    pub updated: Option<DateTime<Utc>>,
+    /// zigbee2mqtt: `<base>/<name>`; Z-Wave: `<prefix>/<node>/<class>/<endpoint>` of the switch.
+    command_topic: Option<String>,
+}
+
+impl Device {
+    fn new(name: &str, bridge: Bridge) -> Self {
+        Self {
+            name: name.to_string(),
+            bridge,
+            class: DeviceClass::Other,
+            model: None,
+            brightness_max: None,
+            state: Map::new(),
+            updated: None,
+            command_topic: None,
+        }
+    }
+
+    fn is_on(&self) -> Option<bool> {
+        match self.state.get("state")? {
+            Value::String(state) => Some(state.eq_ignore_ascii_case("on")),
+            Value::Bool(on) => Some(*on),
+            _ => None,
+        }
+    }
+
+    /// Typed view of the current state for tool output.
+    pub fn reading(&self) -> Value {
+        let mut reading = Map::new();
+        if let Some(on) = self.is_on() {
+            reading.insert("on".into(), json!(on));
+        }
+        if let (Some(max), Some(raw)) = (
+            self.brightness_max,
+            self.state.get("brightness").and_then(Value::as_f64),
+        ) {
+            #[allow(clippy::cast_precision_loss)]
+            let pct = (raw / max as f64 * 100.0).round();
+            reading.insert("brightness_pct".into(), json!(pct));
+        }
+        if let Some(closed) = self.state.get("contact").and_then(Value::as_bool) {
+            reading.insert(
+                "contact".into(),
+                json!(if closed { "closed" } else { "open" }),
+            );
+        }
+        if let Some(motion) = self.state.get("occupancy").and_then(Value::as_bool) {
+            reading.insert("motion".into(), json!(motion));
+        }
+        for key in ["temperature", "humidity", "battery", "availability"] {
+            if let Some(value) = self.state.get(key) {
+                reading.insert(key.into(), value.clone());
+            }
+        }
+        Value::Object(reading)
+    }
+
+    /// MQTT publishes that switch this device.
+    pub fn switch_command(
+        &self,
+        action: SwitchAction,
+        brightness_pct: Option<u8>,
+    ) -> anyhow::Result<(String, Value)> {
+        if !self.class.is_switchable() {
+            anyhow::bail!(
+                "{} is a {}, not a light or switch",
+                self.name,
+                self.class.as_str()
+            );
+        }
+        let topic = self
+            .command_topic
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("{} has not reported a command topic yet", self.name))?;
+        let brightness = match (brightness_pct, self.brightness_max) {
+            (Some(_), None) => anyhow::bail!("{} is not dimmable", self.name),
+            (Some(pct), Some(max)) => Some((u64::from(pct.min(100)) * max).div_ceil(100)),
+            (None, _) => None,
+        };
+
+        match self.bridge {
+            Bridge::Zigbee2mqtt => {
+                let mut payload = json!({
+                    "state": match action {
+                        SwitchAction::On => "ON",
+                        SwitchAction::Off => "OFF",
+                        SwitchAction::Toggle => "TOGGLE",
+                    }
+                });
+                if let (Some(brightness), SwitchAction::On) = (brightness, action) {
+                    payload["brightness"] = json!(brightness);
+                }
+                Ok((format!("{topic}/set"), payload))
+            }
+            Bridge::ZwaveJs => {
+                let on = match action {
+                    SwitchAction::On => true,
+                    SwitchAction::Off => false,
+                    SwitchAction::Toggle => !self.is_on().ok_or_else(|| {
+                        anyhow::anyhow!("Current state of {} is unknown; use on or off", self.name)
+                    })?,
+                };
+                let value = match (self.brightness_max, on) {
+                    (None, on) => json!(on),
+                    (Some(_), false) => json!(0),
+                    (Some(_), true) => json!(brightness.unwrap_or(ZWAVE_LEVEL_RESTORE)),
+                };
+                Ok((
+                    format!("{topic}/targetValue/set"),
+                    json!({ "value": value }),
+                ))
+            }
+        }
+    }
+}
+
+/// Classify a zigbee2mqtt device from its `definition.exposes`.
+fn classify_zigbee(exposes: &[Value]) -> (DeviceClass, Option<u64>) {
+    let mut class = DeviceClass::Other;
+    let mut brightness_max = None;
+    for expose in exposes {
+        let found = match expose["type"].as_str().unwrap_or_default() {
+            "light" => {
+                brightness_max = expose["features"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .find(|feature| feature["property"] == "brightness")
+                    .map(|feature| feature["value_max"].as_u64().unwrap_or(254));
+                DeviceClass::Light
+            }
+            "switch" => DeviceClass::Switch,
+            _ => match expose["property"].as_str().unwrap_or_default() {
+                "contact" => DeviceClass::ContactSensor,
+                "occupancy" => DeviceClass::MotionSensor,
+                "temperature" | "humidity" => DeviceClass::ClimateSensor,
+                _ => DeviceClass::Other,
+            },
+        };
+        class = class.min(found);
+    }
+    (class, brightness_max)
+}
+
+/// Map a zwave-js-ui value (command class, property path) onto a class and
+/// a normalised state entry.
+fn zwave_profile(
+    command_class: &str,
+    property: &str,
+    value: &Value,
+) -> Option<(DeviceClass, &'static str, Value)> {
+    let current = property == "currentValue";
+    match command_class {
+        "switch_binary" if current => {
+            Some((DeviceClass::Switch, "state", on_off(value.as_bool()?)))
+        }
+        "switch_multilevel" if current => {
+            let level = value.as_u64()?;
+            Some((DeviceClass::Light, "brightness", json!(level)))
+        }
+        "notification" if property.ends_with("Door_state") => {
+            let closed = match value.as_i64()? {
+                ZWAVE_DOOR_OPEN => false,
+                ZWAVE_DOOR_CLOSED => true,
+                _ => return None,
+            };
+            Some((DeviceClass::ContactSensor, "contact", json!(closed)))
+        }
+        "notification" if property.ends_with("Motion_sensor_status") => Some((
+            DeviceClass::MotionSensor,
+            "occupancy",
+            json!(value.as_i64()? == ZWAVE_MOTION_DETECTED),
+        )),
+        "binary_sensor" if property.contains("Door") => {
+            // Binary sensors report `true` when open.
+            Some((
+                DeviceClass::ContactSensor,
+                "contact",
+                json!(!value.as_bool()?),
+            ))
+        }
+        "binary_sensor" if property.contains("Motion") => Some((
+            DeviceClass::MotionSensor,
+            "occupancy",
+            json!(value.as_bool()?),
+        )),
+        "sensor_multilevel" if property.contains("temperature") => {
+            Some((DeviceClass::ClimateSensor, "temperature", value.clone()))
+        }
+        "sensor_multilevel" if property.contains("Humidity") => {
+            Some((DeviceClass::ClimateSensor, "humidity", value.clone()))
+        }
+        "battery" if property == "level" => Some((DeviceClass::Other, "battery", value.clone())),
+        _ => None,
+    }
+}
+
+fn on_off(on: bool) -> Value {
+    json!(if on { "ON" } else { "OFF" })
+}
+
+/// Known devices and their latest state, fed from broker messages.
+#[derive(Debug, Default)]
+pub struct Registry {
+    devices: BTreeMap<String, Device>,
+}
+
+impl Registry {
+    pub fn list(&self) -> impl Iterator<Item = &Device> {
+        self.devices.values()
+    }
+
+    /// Look up a device by name (case-insensitive).
+    pub fn get(&self, name: &str) -> Option<&Device> {
+        self.devices.get(name).or_else(|| {
+            self.devices
+                .values()
+                .find(|device| device.name.eq_ignore_ascii_case(name))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    /// Apply a message from zigbee2mqtt under `base`.
+    pub fn apply_zigbee(&mut self, base: &str, topic: &str, payload: &[u8], now: DateTime<Utc>) {
+        let Some(rest) = topic.strip_prefix(base).and_then(|t| t.strip_prefix('/')) else {
+            return;
+        };
+        if rest == "bridge/devices" {
+            self.apply_zigbee_devices(base, payload);
+            return;
+        }
+        if rest.starts_with("bridge/") || rest.ends_with("/set") || rest.ends_with("/get") {
+            return;
+        }
+
+        if let Some(name) = rest.strip_suffix("/availability") {
+            let availability = serde_json::from_slice::<Value>(payload)
+                .ok()
+                .and_then(|value| value["state"].as_str().map(String::from))
+                .unwrap_or_else(|| String::from_utf8_lossy(payload).trim().to_string());
+            if let Some(device) = self.devices.get_mut(name) {
+                device
+                    .state
+                    .insert("availability".into(), json!(availability));
+            }
+            return;
+        }
+
+        let Ok(Value::Object(values)) = serde_json::from_slice::<Value>(payload) else {
+            return;
+        };
+        let device = self.devices.entry(rest.to_string()).or_insert_with(|| {
+            let mut device = Device::new(rest, Bridge::Zigbee2mqtt);
+            device.command_topic = Some(topic.to_string());
+            device
+        });
+        device.state.extend(values);
+        device.updated = Some(now);
+    }
+
+    /// Replace the Zigbee device list from the retained `bridge/devices` topic,
+    /// keeping state already received for devices that still exist.
+    fn apply_zigbee_devices(&mut self, base: &str, payload: &[u8]) {
+        let Ok(Value::Array(entries)) = serde_json::from_slice::<Value>(payload) else {
+            tracing::warn!("Ignoring malformed {base}/bridge/devices payload");
+            return;
+        };
+        let (mut previous, others): (BTreeMap<_, _>, BTreeMap<_, _>) =
+            std::mem::take(&mut self.devices)
+                .into_iter()
+                .partition(|(_, device)| device.bridge == Bridge::Zigbee2mqtt);
+        self.devices = others;
+
+        for entry in entries {
+            let Some(name) = entry["friendly_name"].as_str() else {
+                continue;
+            };
+            if entry["type"] == "Coordinator" {
+                continue;
+            }
+            let definition = &entry["definition"];
+            let exposes = definition["exposes"]
+                .as_array()
+                .map_or(&[][..], Vec::as_slice);
+            let (class, brightness_max) = classify_zigbee(exposes);
+            let mut device = previous
+                .remove(name)
+                .unwrap_or_else(|| Device::new(name, Bridge::Zigbee2mqtt));
+            device.class = class;
+            device.brightness_max = brightness_max;
+            device.model = match (definition["vendor"].as_str(), definition["model"].as_str()) {
+                (Some(vendor), Some(model)) => Some(format!("{vendor} {model}")),
+                (None, Some(model)) => Some(model.to_string()),
+                _ => None,
+            };
+            device.command_topic = Some(format!("{base}/{name}"));
+            self.devices.insert(name.to_string(), device);
+        }
+    }
+
+    /// Apply a zwave-js-ui value published under `prefix` (named topics:
+    /// `<prefix>/<node...>/<command_class>/endpoint_<n>/<property...>`).
+    pub fn apply_zwave(&mut self, prefix: &str, topic: &str, payload: &[u8], now: DateTime<Utc>) {
+        let Some(rest) = topic.strip_prefix(prefix).and_then(|t| t.strip_prefix('/')) else {
+            return;
+        };
+        if rest.starts_with("_CLIENTS") || rest.ends_with("/set") {
+            return;
+        }
+        let segments: Vec<&str> = rest.split('/').collect();
+        // The node path is at least one segment, followed by the command class.
+        let Some(endpoint) = segments.iter().skip(2).position(|segment| {
+            segment
+                .strip_prefix("endpoint_")
+                .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        }) else {
+            return;
+        };
+        let endpoint = endpoint + 2;
+        let node = segments[..endpoint - 1].join("/");
+        let command_class = segments[endpoint - 1];
+        let property = segments[endpoint + 1..].join("/");
+
+        let parsed = serde_json::from_slice::<Value>(payload)
+            .unwrap_or_else(|_| json!(String::from_utf8_lossy(payload).trim()));
+        let value = match parsed {
+            Value::Object(ref object) if object.contains_key("value") => object["value"].clone(),
+            other => other,
+        };
+        let Some((class, key, normalised)) = zwave_profile(command_class, &property, &value) else {
+            return;
+        };
+
+        let device = self
+            .devices
+            .entry(node.clone())
+            .or_insert_with(|| Device::new(&node, Bridge::ZwaveJs));
+        device.class = device.class.min(class);
+        if class.is_switchable() && device.class == class {
+            device.command_topic = Some(format!(
+                "{prefix}/{node}/{command_class}/{}",
+                segments[endpoint]
+            ));
+        }
+        if command_class == "switch_multilevel" {
+            device.brightness_max = Some(ZWAVE_LEVEL_MAX);
+            let on = normalised.as_u64().is_some_and(|level| level > 0);
+            device.state.insert("state".into(), on_off(on));
+        }
+        device.state.insert(key.into(), normalised);
+        device.updated = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICES: &str = r#"[
+        {"friendly_name": "Coordinator", "type": "Coordinator"},
+        {"friendly_name": "kitchen/ceiling", "type": "Router",
+         "definition": {"vendor": "IKEA", "model": "LED1836G9", "exposes": [
+            {"type": "light", "features": [
+                {"name": "state", "property": "state", "type": "binary"},
+                {"name": "brightness", "property": "brightness", "type": "numeric", "value_max": 254}
+            ]}
+         ]}},
+        {"friendly_name": "front_door", "type": "EndDevice",
+         "definition": {"model": "MCCGQ11LM", "exposes": [
+            {"type": "binary", "name": "contact", "property": "contact"},
+            {"type": "numeric", "name": "battery", "property": "battery"},
+            {"type": "numeric", "name": "temperature", "property": "temperature"}
+         ]}}
+    ]"#;
+
+    #[test]
+    fn zigbee_discovery_classifies_devices_and_builds_commands() {
+        let mut registry = Registry::default();
+        let now = Utc::now();
+        registry.apply_zigbee(
+            "zigbee2mqtt",
+            "zigbee2mqtt/front_door",
+            br#"{"contact": false, "battery": 91}"#,
+            now,
+        );
+        registry.apply_zigbee(
+            "zigbee2mqtt",
+            "zigbee2mqtt/bridge/devices",
+            DEVICES.as_bytes(),
+            now,
+        );
+        registry.apply_zigbee(
+            "zigbee2mqtt",
+            "zigbee2mqtt/kitchen/ceiling",
+            br#"{"state": "ON", "brightness": 127}"#,
+            now,
+        );
+
+        assert_eq!(registry.list().count(), 2);
+        let door = registry.get("FRONT_DOOR").unwrap();
+        assert_eq!(door.class, DeviceClass::ContactSensor);
+        assert_eq!(door.reading()["contact"], "open");
+        assert_eq!(door.reading()["battery"], 91);
+        assert!(door.switch_command(SwitchAction::On, None).is_err());
+
+        let light = registry.get("kitchen/ceiling").unwrap();
+        assert_eq!(light.class, DeviceClass::Light);
+        assert_eq!(light.model.as_deref(), Some("IKEA LED1836G9"));
+        assert_eq!(light.reading()["brightness_pct"], json!(50.0));
+        let (topic, payload) = light.switch_command(SwitchAction::On, Some(100)).unwrap();
+        assert_eq!(topic, "zigbee2mqtt/kitchen/ceiling/set");
+        assert_eq!(payload, json!({"state": "ON", "brightness": 254}));
+        let (_, payload) = light.switch_command(SwitchAction::Toggle, None).unwrap();
+        assert_eq!(payload, json!({"state": "TOGGLE"}));
+    }
+
+    #[test]
+    fn zwave_values_map_onto_device_profiles() {
+        let mut registry = Registry::default();
+        let now = Utc::now();
+        registry.apply_zwave(
+            "zwave",
+            "zwave/Hall/Dimmer/switch_multilevel/endpoint_0/currentValue",
+            br#"{"time": 1, "value": 40}"#,
+            now,
+        );
+        registry.apply_zwave(
+            "zwave",
+            "zwave/Hall/Door/notification/endpoint_0/Access_Control/Door_state",
+            br#"{"time": 1, "value": 23}"#,
+            now,
+        );
+        registry.apply_zwave(
+            "zwave",
+            "zwave/Hall/Door/battery/endpoint_0/level",
+            b"80",
+            now,
+        );
+        registry.apply_zwave(
+            "zwave",
+            "zwave/_CLIENTS/ZWAVE_GATEWAY-x/status",
+            b"true",
+            now,
+        );
+
+        let door = registry.get("Hall/Door").unwrap();
+        assert_eq!(door.class, DeviceClass::ContactSensor);
+        assert_eq!(door.reading()["contact"], "closed");
+        assert_eq!(door.reading()["battery"], 80);
+
+        let dimmer = registry.get("Hall/Dimmer").unwrap();
+        assert_eq!(dimmer.class, DeviceClass::Light);
+        assert_eq!(dimmer.reading()["on"], true);
+        let (topic, payload) = dimmer.switch_command(SwitchAction::Toggle, None).unwrap();
+        assert_eq!(
+            topic,
+            "zwave/Hall/Dimmer/switch_multilevel/endpoint_0/targetValue/set"
+        );
+        assert_eq!(payload, json!({"value": 0}));
+        let (_, payload) = dimmer.switch_command(SwitchAction::On, Some(50)).unwrap();
+        assert_eq!(payload, json!({"value": 50}));
+        assert_eq!(registry.list().count(), 2);
+    }
+}
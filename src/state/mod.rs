@@ -0,0 +1,481 @@
+//! Event-sourced store for daemon runtime state.
+//!
+//! Small pieces of state that must survive restarts (the emergency stop,
+//! pending OAuth logins, heartbeat probe health) live here instead of in
+//! their own JSON files. Every change is appended to `events.jsonl` as one
+//! `{seq, at, key, value, cause}` line and synced before the call returns;
+//! the current state is the last snapshot plus the events after it. Writers
+//! hold a lock file, so a read-modify-write ([`StateStore::update`]) is
+//! atomic across processes, and a torn trailing line left by a crash is
+//! dropped on the next write. `zeroclaw state history` reads the log to show
+//! how the daemon got into its current state.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const LOG_FILE: &str = "events.jsonl";
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const LOCK_FILE: &str = "store.lock";
+
+/// Fold the log into a snapshot once it holds this many events...
+const COMPACT_AFTER: usize = 1000;
+/// ...keeping this many of the newest for `state history`.
+const KEEP_HISTORY: usize = 200;
+
+const LOCK_WAIT_MS: u64 = 20;
+const LOCK_TIMEOUT_MS: u64 = 5_000;
+/// A lock file older than this was left by a crashed writer.
+const LOCK_STALE: Duration = Duration::from_secs(30);
+
+/// One change to one key. `value` is `None` when the key was deleted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    pub seq: u64,
+    pub at: DateTime<Utc>,
+    pub key: String,
+    #[serde(default)]
+    pub value: Option<Value>,
+    pub cause: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Snapshot {
+    seq: u64,
+    state: BTreeMap<String, Value>,
+}
+
+/// State replayed from disk.
+struct Replay {
+    seq: u64,
+    state: BTreeMap<String, Value>,
+    events: Vec<Event>,
+    /// Length of the log up to its last complete event.
+    valid_len: u64,
+    log_len: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct StateStore {
+    dir: PathBuf,
+}
+
+impl StateStore {
+    /// Store in `dir` (created on first write).
+    pub fn open(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The store next to `config.toml`, in `<config_dir>/state/`.
+    pub fn for_config_dir(config_dir: &Path) -> Self {
+        Self::open(config_dir.join("state"))
+    }
+
+    pub fn for_config(config: &crate::config::Config) -> Self {
+        Self::for_config_dir(
+            config
+                .config_path
+                .parent()
+                .unwrap_or_else(|| Path::new(".")),
+        )
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Current value of `key`.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        self.replay()?
+            .state
+            .get(key)
+            .map(|value| {
+                serde_json::from_value(value.clone())
+                    .with_context(|| format!("State '{key}' has an unexpected shape"))
+            })
+            .transpose()
+    }
+
+    /// Current values of every key starting with `prefix`.
+    pub fn entries(&self, prefix: &str) -> Result<BTreeMap<String, Value>> {
+        Ok(self
+            .replay()?
+            .state
+            .into_iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .collect())
+    }
+
+    /// Set `key` to `value`. Writing the value it already has records nothing.
+    pub fn set<T: Serialize>(&self, key: &str, value: &T, cause: &str) -> Result<()> {
+        let value = serde_json::to_value(value)?;
+        self.write(key, cause, |_| Ok(Some(value)))
+    }
+
+    pub fn delete(&self, key: &str, cause: &str) -> Result<()> {
+        self.write(key, cause, |_| Ok(None))
+    }
+
+    /// Replace `key` with `f(current)` while holding the store lock, so
+    /// concurrent writers cannot interleave. Returns the new value.
+    pub fn update<T, F>(&self, key: &str, cause: &str, f: F) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce(Option<T>) -> Result<T>,
+    {
+        let mut updated = None;
+        self.write(key, cause, |current| {
+            let current = current
+                .map(|value| serde_json::from_value(value.clone()))
+                .transpose()
+                .with_context(|| format!("State '{key}' has an unexpected shape"))?;
+            let next = f(current)?;
+            let value = serde_json::to_value(&next)?;
+            updated = Some(next);
+            Ok(Some(value))
+        })?;
+        updated.context("State update produced no value")
+    }
+
+    /// Events for keys starting with `key_prefix`, oldest first, at most
+    /// `limit` of the newest.
+    pub fn history(&self, key_prefix: &str, limit: usize) -> Result<Vec<Event>> {
+        let mut events: Vec<Event> = self
+            .replay()?
+            .events
+            .into_iter()
+            .filter(|event| event.key.starts_with(key_prefix))
+            .collect();
+        let skip = events.len().saturating_sub(limit);
+        events.drain(..skip);
+        Ok(events)
+    }
+
+    fn write(
+        &self,
+        key: &str,
+        cause: &str,
+        f: impl FnOnce(Option<&Value>) -> Result<Option<Value>>,
+    ) -> Result<()> {
+        if key.trim().is_empty() {
+            bail!("State key must not be empty");
+        }
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create state dir {}", self.dir.display()))?;
+        let _lock = StoreLock::acquire(&self.dir.join(LOCK_FILE))?;
+
+        let replay = self.replay()?;
+        let current = replay.state.get(key);
+        let next = f(current)?;
+        if next.as_ref() == current {
+            return Ok(());
+        }
+
+        let log_path = self.dir.join(LOG_FILE);
+        let mut log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .with_context(|| format!("Failed to open state log {}", log_path.display()))?;
+        if replay.valid_len < replay.log_len {
+            tracing::warn!(
+                path = %log_path.display(),
+                "Dropping a torn event at the end of the state log"
+            );
+            log.set_len(replay.valid_len)?;
+        }
+        restrict_permissions(&log_path);
+
+        let event = Event {
+            seq: replay.seq + 1,
+            at: Utc::now(),
+            key: key.to_string(),
+            value: next,
+            cause: cause.to_string(),
+        };
+        let mut line = serde_json::to_vec(&event)?;
+        line.push(b'\n');
+        log.write_all(&line)?;
+        log.sync_all()
+            .with_context(|| format!("Failed to sync state log {}", log_path.display()))?;
+
+        if replay.events.len() + 1 >= COMPACT_AFTER {
+            let mut replay = replay;
+            apply(&mut replay.state, &event);
+            replay.seq = event.seq;
+            replay.events.push(event);
+            if let Err(e) = self.compact(replay) {
+                tracing::warn!("Failed to compact the state log: {e:#}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a snapshot of `replay`, then trim the log to its newest events.
+    /// A crash between the two leaves events the snapshot already covers,
+    /// which replay skips by sequence number.
+    fn compact(&self, replay: Replay) -> Result<()> {
+        let snapshot = Snapshot {
+            seq: replay.seq,
+            state: replay.state,
+        };
+        let kept = &replay.events[replay.events.len().saturating_sub(KEEP_HISTORY)..];
+        write_atomically(
+            &self.dir.join(SNAPSHOT_FILE),
+            &serde_json::to_vec_pretty(&snapshot)?,
+        )?;
+        let mut log = Vec::new();
+        for event in kept {
+            log.extend(serde_json::to_vec(event)?);
+            log.push(b'\n');
+        }
+        write_atomically(&self.dir.join(LOG_FILE), &log)
+    }
+
+    fn replay(&self) -> Result<Replay> {
+        let snapshot_path = self.dir.join(SNAPSHOT_FILE);
+        let snapshot = match fs::read(&snapshot_path) {
+            Ok(raw) => serde_json::from_slice::<Snapshot>(&raw).with_context(|| {
+                format!("State snapshot {} is corrupted", snapshot_path.display())
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Snapshot::default(),
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Failed to read state snapshot {}", snapshot_path.display())
+                })
+            }
+        };
+        let mut replay = Replay {
+            seq: snapshot.seq,
+            state: snapshot.state,
+            events: Vec::new(),
+            valid_len: 0,
+            log_len: 0,
+        };
+
+        let log_path = self.dir.join(LOG_FILE);
+        let raw = match fs::read(&log_path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(replay),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read state log {}", log_path.display()))
+            }
+        };
+        replay.log_len = raw.len() as u64;
+
+        let mut offset = 0;
+        while offset < raw.len() {
+            let Some(end) = raw[offset..].iter().position(|b| *b == b'\n') else {
+                // No newline: the last append was interrupted.
+                break;
+            };
+            let line = &raw[offset..offset + end];
+            offset += end + 1;
+            let is_last = offset == raw.len();
+            let event = match serde_json::from_slice::<Event>(line) {
+                Ok(event) => event,
+                Err(_) if is_last => break,
+                Err(e) => {
+                    bail!(
+                        "State log {} is corrupted at byte {}: {e}",
+                        log_path.display(),
+                        offset - end - 1
+                    )
+                }
+            };
+            replay.valid_len = offset as u64;
+            if event.seq > replay.seq {
+                apply(&mut replay.state, &event);
+                replay.seq = event.seq;
+            }
+            replay.events.push(event);
+        }
+        Ok(replay)
+    }
+}
+
+fn apply(state: &mut BTreeMap<String, Value>, event: &Event) {
+    match &event.value {
+        Some(value) => {
+            state.insert(event.key.clone(), value.clone());
+        }
+        None => {
+            state.remove(&event.key);
+        }
+    }
+}
+
+fn write_atomically(path: &Path, bytes: &[u8]) -> Result<()> {
+    let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+    let mut file =
+        fs::File::create(&tmp).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    restrict_permissions(&tmp);
+    fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+fn restrict_permissions(path: &Path) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
+
+/// Rename a state file that has been imported into the store, so it is not
+/// imported again.
+pub fn retire_legacy_file(path: &Path) {
+    let mut retired = path.as_os_str().to_owned();
+    retired.push(".migrated");
+    if let Err(e) = fs::rename(path, &retired) {
+        tracing::warn!(path = %path.display(), "Failed to retire imported state file: {e}");
+    }
+}
+
+struct StoreLock {
+    path: PathBuf,
+}
+
+impl StoreLock {
+    fn acquire(path: &Path) -> Result<Self> {
+        let mut waited = 0_u64;
+        loop {
+            match OpenOptions::new().create_new(true).write(true).open(path) {
+                Ok(mut file) => {
+                    let _ = writeln!(file, "pid={}", std::process::id());
+                    return Ok(Self {
+                        path: path.to_path_buf(),
+                    });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let stale = fs::metadata(path)
+                        .and_then(|meta| meta.modified())
+                        .ok()
+                        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                        .is_some_and(|age| age > LOCK_STALE);
+                    if stale {
+                        tracing::warn!(path = %path.display(), "Removing stale state store lock");
+                        let _ = fs::remove_file(path);
+                        continue;
+                    }
+                    if waited >= LOCK_TIMEOUT_MS {
+                        bail!("Timed out waiting for state store lock {}", path.display());
+                    }
+                    std::thread::sleep(Duration::from_millis(LOCK_WAIT_MS));
+                    waited += LOCK_WAIT_MS;
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| {
+                        format!("Failed to create state store lock {}", path.display())
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Drop for StoreLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn changes_replay_and_unchanged_writes_are_skipped() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = StateStore::open(tmp.path());
+        store
+            .set("estop", &json!({"kill_all": true}), "engage")
+            .unwrap();
+        store
+            .set("estop", &json!({"kill_all": true}), "engage")
+            .unwrap();
+        store.set("auth.pending.openai", &"x", "login").unwrap();
+        store.delete("auth.pending.openai", "login done").unwrap();
+
+        let reopened = StateStore::open(tmp.path());
+        assert_eq!(
+            reopened.get::<Value>("estop").unwrap(),
+            Some(json!({"kill_all": true}))
+        );
+        assert_eq!(reopened.get::<String>("auth.pending.openai").unwrap(), None);
+        let history = reopened.history("", 10).unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[2].cause, "login done");
+        assert_eq!(reopened.history("estop", 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn update_sees_the_current_value() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = StateStore::open(tmp.path());
+        for _ in 0..3 {
+            store
+                .update("count", "bump", |n: Option<u32>| Ok(n.unwrap_or(0) + 1))
+                .unwrap();
+        }
+        assert_eq!(store.get::<u32>("count").unwrap(), Some(3));
+        assert!(store
+            .update("count", "fail", |_: Option<u32>| bail!("refused"))
+            .is_err());
+        assert_eq!(store.history("count", 10).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn torn_trailing_event_is_dropped() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = StateStore::open(tmp.path());
+        store.set("a", &1, "first").unwrap();
+        let mut log = OpenOptions::new()
+            .append(true)
+            .open(tmp.path().join(LOG_FILE))
+            .unwrap();
+        log.write_all(br#"{"seq":2,"at":"#).unwrap();
+
+        assert_eq!(store.get::<u32>("a").unwrap(), Some(1));
+        store.set("a", &2, "second").unwrap();
+        let history = store.history("a", 10).unwrap();
+        assert_eq!(
+            history.iter().map(|e| e.seq).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn compaction_keeps_state_and_recent_history() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = StateStore::open(tmp.path());
+        store.set("kept", &"yes", "once").unwrap();
+        for n in 0..COMPACT_AFTER {
+            store.set("counter", &n, "tick").unwrap();
+        }
+        assert!(tmp.path().join(SNAPSHOT_FILE).exists());
+        assert_eq!(store.get::<String>("kept").unwrap().as_deref(), Some("yes"));
+        assert_eq!(
+            store.get::<usize>("counter").unwrap(),
+            Some(COMPACT_AFTER - 1)
+        );
+        let history = store.history("", usize::MAX).unwrap();
+        assert!(history.len() < COMPACT_AFTER);
+        assert_eq!(history.last().unwrap().seq, COMPACT_AFTER as u64 + 1);
+
+        store.set("counter", &0, "reset").unwrap();
+        assert_eq!(store.get::<usize>("counter").unwrap(), Some(0));
+    }
+}
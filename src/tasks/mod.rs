@@ -0,0 +1,430 @@
+//! Parameterized prompt templates ("tasks") saved in the workspace.
+//!
+//! A task is a named prompt with `{{input}}` placeholders, declared inputs
+//! (optionally with defaults) and an optional default toolset. Tasks live as
+//! TOML files under `<workspace>/tasks/` and can be run from the CLI
+//! (`zeroclaw tasks run deploy-report --param env=prod`), from channels
+//! (`/task deploy-report env=prod`) and from agent cron jobs whose prompt is
+//! a `/task` invocation.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TASKS_DIR: &str = "tasks";
+/// Prefix that marks a message or cron prompt as a task invocation.
+pub const TASK_COMMAND: &str = "/task";
+
+/// A declared template input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskInput {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Value used when the invocation omits this input; inputs without a
+    /// default are required.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+/// A saved, parameterized prompt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskTemplate {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub prompt: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<TaskInput>,
+    /// Tools available while the task runs; empty means every tool.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+}
+
+/// A template rendered with concrete parameters, ready to send to the agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedTask {
+    pub name: String,
+    pub prompt: String,
+    pub tools: Vec<String>,
+}
+
+impl TaskTemplate {
+    /// Declare a required input for every placeholder that has none yet.
+    pub fn declare_missing_inputs(&mut self) {
+        for placeholder in placeholders(&self.prompt) {
+            if !self.inputs.iter().any(|input| input.name == placeholder) {
+                self.inputs.push(TaskInput {
+                    name: placeholder.to_string(),
+                    description: None,
+                    default: None,
+                });
+            }
+        }
+    }
+
+    /// Check the name and that every placeholder refers to a declared input.
+    pub fn validate(&self) -> Result<()> {
+        validate_name(&self.name)?;
+        if self.prompt.trim().is_empty() {
+            bail!("Task '{}' has an empty prompt", self.name);
+        }
+        for (i, input) in self.inputs.iter().enumerate() {
+            validate_name(&input.name)
+                .with_context(|| format!("Invalid input in task '{}'", self.name))?;
+            if self.inputs[..i]
+                .iter()
+                .any(|other| other.name == input.name)
+            {
+                bail!("Task '{}' declares input '{}' twice", self.name, input.name);
+            }
+        }
+        for placeholder in placeholders(&self.prompt) {
+            if !self.inputs.iter().any(|input| input.name == placeholder) {
+                bail!(
+                    "Task '{}' uses {{{{{placeholder}}}}} but does not declare it as an input",
+                    self.name
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Substitute `params` (falling back to input defaults) into the prompt.
+    pub fn render(&self, params: &BTreeMap<String, String>) -> Result<RenderedTask> {
+        if let Some(unknown) = params
+            .keys()
+            .find(|key| !self.inputs.iter().any(|input| &input.name == *key))
+        {
+            bail!("Task '{}' has no input named '{unknown}'", self.name);
+        }
+
+        let mut values = BTreeMap::new();
+        for input in &self.inputs {
+            let value = params
+                .get(&input.name)
+                .or(input.default.as_ref())
+                .with_context(|| {
+                    format!(
+                        "Task '{}' requires input '{}' (pass {}=<value>)",
+                        self.name, input.name, input.name
+                    )
+                })?;
+            values.insert(input.name.as_str(), value.as_str());
+        }
+
+        let mut prompt = String::with_capacity(self.prompt.len());
+        let mut rest = self.prompt.as_str();
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            let key = rest[start + 2..start + 2 + len].trim();
+            prompt.push_str(&rest[..start]);
+            match values.get(key) {
+                Some(value) => prompt.push_str(value),
+                None => prompt.push_str(&rest[start..start + 4 + len]),
+            }
+            rest = &rest[start + 4 + len..];
+        }
+        prompt.push_str(rest);
+
+        Ok(RenderedTask {
+            name: self.name.clone(),
+            prompt,
+            tools: self.tools.clone(),
+        })
+    }
+}
+
+/// Task templates stored under a workspace.
+pub struct TaskStore {
+    root: PathBuf,
+}
+
+impl TaskStore {
+    pub fn for_workspace(workspace_dir: &Path) -> Self {
+        Self {
+            root: workspace_dir.join(TASKS_DIR),
+        }
+    }
+
+    fn path_for(&self, name: &str) -> PathBuf {
+        self.root.join(format!("{name}.toml"))
+    }
+
+    /// Validate and write `template`, replacing any task with the same name.
+    pub fn save(&self, template: &TaskTemplate) -> Result<PathBuf> {
+        template.validate()?;
+        fs::create_dir_all(&self.root)
+            .with_context(|| format!("Failed to create {}", self.root.display()))?;
+        let path = self.path_for(&template.name);
+        fs::write(&path, toml::to_string_pretty(template)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    pub fn load(&self, name: &str) -> Result<TaskTemplate> {
+        validate_name(name)?;
+        let path = self.path_for(name);
+        if !path.is_file() {
+            bail!("Unknown task '{name}'. Save it with `zeroclaw tasks save {name}`");
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let template: TaskTemplate = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        if template.name != name {
+            bail!(
+                "{} declares name '{}'; expected '{name}'",
+                path.display(),
+                template.name
+            );
+        }
+        Ok(template)
+    }
+
+    /// All saved tasks, sorted by name. Unreadable files are skipped.
+    pub fn list(&self) -> Result<Vec<TaskTemplate>> {
+        if !self.root.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut templates = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            let Some(name) = path
+                .extension()
+                .filter(|ext| *ext == "toml")
+                .and_then(|_| path.file_stem())
+                .and_then(|stem| stem.to_str())
+            else {
+                continue;
+            };
+            match self.load(name) {
+                Ok(template) => templates.push(template),
+                Err(error) => tracing::warn!("Skipping task file {}: {error}", path.display()),
+            }
+        }
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(templates)
+    }
+
+    pub fn remove(&self, name: &str) -> Result<()> {
+        validate_name(name)?;
+        let path = self.path_for(name);
+        if !path.is_file() {
+            bail!("Unknown task '{name}'");
+        }
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))
+    }
+
+    /// Load `name` and render it with `params`.
+    pub fn render(&self, name: &str, params: &BTreeMap<String, String>) -> Result<RenderedTask> {
+        self.load(name)?.render(params)
+    }
+}
+
+/// Parse `key=value` pairs as given to `--param` or a `/task` invocation.
+pub fn parse_params<S: AsRef<str>>(pairs: &[S]) -> Result<BTreeMap<String, String>> {
+    let mut params = BTreeMap::new();
+    for pair in pairs {
+        let pair = pair.as_ref();
+        let Some((key, value)) = pair.split_once('=') else {
+            bail!("Invalid parameter '{pair}': expected key=value");
+        };
+        let key = key.trim();
+        validate_name(key).with_context(|| format!("Invalid parameter '{pair}'"))?;
+        params.insert(key.to_string(), value.to_string());
+    }
+    Ok(params)
+}
+
+/// Parse `/task <name> key=value ...` into the task name and its parameters.
+///
+/// Returns `None` when `text` is not a task invocation. Values may be
+/// double-quoted to include spaces (`topic="release notes"`).
+pub fn parse_invocation(text: &str) -> Option<Result<(String, BTreeMap<String, String>)>> {
+    let rest = text.trim().strip_prefix(TASK_COMMAND)?;
+    // Telegram appends the bot name to commands in groups (`/task@my_bot`).
+    let rest = match rest.strip_prefix('@') {
+        Some(addressed) => addressed
+            .split_once(char::is_whitespace)
+            .map_or("", |(_, r)| r),
+        None if rest.is_empty() || rest.starts_with(char::is_whitespace) => rest,
+        None => return None,
+    };
+    let mut words = split_words(rest).into_iter();
+    let Some(name) = words.next() else {
+        return Some(Err(anyhow::anyhow!(
+            "Usage: {TASK_COMMAND} <name> [key=value ...]"
+        )));
+    };
+    let params: Vec<String> = words.collect();
+    Some(parse_params(&params).map(|params| (name, params)))
+}
+
+/// Expand a `/task` invocation against the workspace's saved tasks.
+pub fn expand_invocation(workspace_dir: &Path, text: &str) -> Option<Result<RenderedTask>> {
+    parse_invocation(text).map(|parsed| {
+        let (name, params) = parsed?;
+        TaskStore::for_workspace(workspace_dir).render(&name, &params)
+    })
+}
+
+fn split_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut in_word = false;
+    for ch in text.chars() {
+        match ch {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            ch if ch.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            ch => {
+                current.push(ch);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+fn placeholders(prompt: &str) -> Vec<&str> {
+    let mut found = Vec::new();
+    let mut rest = prompt;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        found.push(rest[start + 2..start + 2 + len].trim());
+        rest = &rest[start + 4 + len..];
+    }
+    found
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        bail!("Invalid name '{name}': use letters, digits, '-' or '_' (max 64)");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn deploy_report() -> TaskTemplate {
+        TaskTemplate {
+            name: "deploy-report".into(),
+            description: Some("Summarize a deployment".into()),
+            prompt: "Write a deploy report for {{env}} covering the last {{ days }} days.".into(),
+            inputs: vec![
+                TaskInput {
+                    name: "env".into(),
+                    description: None,
+                    default: None,
+                },
+                TaskInput {
+                    name: "days".into(),
+                    description: None,
+                    default: Some("7".into()),
+                },
+            ],
+            tools: vec!["shell".into(), "file_read".into()],
+        }
+    }
+
+    #[test]
+    fn render_substitutes_params_and_defaults() {
+        let params = parse_params(&["env=prod"]).unwrap();
+        let rendered = deploy_report().render(&params).unwrap();
+        assert_eq!(
+            rendered.prompt,
+            "Write a deploy report for prod covering the last 7 days."
+        );
+        assert_eq!(rendered.tools, vec!["shell", "file_read"]);
+    }
+
+    #[test]
+    fn render_rejects_missing_and_unknown_inputs() {
+        let missing = deploy_report().render(&BTreeMap::new()).unwrap_err();
+        assert!(missing.to_string().contains("requires input 'env'"));
+
+        let params = parse_params(&["env=prod", "region=eu"]).unwrap();
+        let unknown = deploy_report().render(&params).unwrap_err();
+        assert!(unknown.to_string().contains("no input named 'region'"));
+    }
+
+    #[test]
+    fn validate_rejects_undeclared_placeholders() {
+        let mut template = deploy_report();
+        template.prompt.push_str(" Tag {{owner}}.");
+        let err = template.validate().unwrap_err();
+        assert!(err.to_string().contains("{{owner}}"));
+    }
+
+    #[test]
+    fn store_round_trips_templates() {
+        let tmp = TempDir::new().unwrap();
+        let store = TaskStore::for_workspace(tmp.path());
+        store.save(&deploy_report()).unwrap();
+
+        assert_eq!(store.load("deploy-report").unwrap(), deploy_report());
+        assert_eq!(store.list().unwrap().len(), 1);
+        store.remove("deploy-report").unwrap();
+        assert!(store.list().unwrap().is_empty());
+        assert!(store.load("../escape").is_err());
+    }
+
+    #[test]
+    fn parse_invocation_handles_quotes_and_bot_suffix() {
+        let (name, params) = parse_invocation("/task deploy-report env=prod note=\"two words\"")
+            .unwrap()
+            .unwrap();
+        assert_eq!(name, "deploy-report");
+        assert_eq!(params["env"], "prod");
+        assert_eq!(params["note"], "two words");
+
+        let (name, _) = parse_invocation("/task@zeroclaw_bot digest")
+            .unwrap()
+            .unwrap();
+        assert_eq!(name, "digest");
+
+        assert!(parse_invocation("/tasks").is_none());
+        assert!(parse_invocation("run /task later").is_none());
+        assert!(parse_invocation("/task").unwrap().is_err());
+    }
+
+    #[test]
+    fn expand_invocation_renders_saved_task() {
+        let tmp = TempDir::new().unwrap();
+        TaskStore::for_workspace(tmp.path())
+            .save(&deploy_report())
+            .unwrap();
+        let rendered = expand_invocation(tmp.path(), "/task deploy-report env=staging days=1")
+            .unwrap()
+            .unwrap();
+        assert!(rendered.prompt.contains("staging"));
+        assert!(rendered.prompt.contains("last 1 days"));
+        assert!(expand_invocation(tmp.path(), "hello").is_none());
+    }
+}
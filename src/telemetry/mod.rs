@@ -0,0 +1,424 @@
+//! Opt-in anonymous usage telemetry (`[telemetry]`).
+//!
+//! Nothing is recorded or sent unless `[telemetry].enabled = true`, and
+//! `ZEROCLAW_TELEMETRY=off` or `DO_NOT_TRACK=1` overrides the config.
+//!
+//! When enabled, runtime trace events are reduced to counters kept in
+//! `state/telemetry.json`:
+//! - `event.<kind>` per trace event kind (`tool_call_result`, `llm_response`, ...)
+//! - `tool.<name>` per built-in tool call; other tools count as `tool.other`
+//! - `channel.<name>` per channel type
+//! - error class codes (`auth`, `network`, ...)
+//!
+//! Message contents, tool arguments and outputs, provider/model names, paths
+//! and hosts are never recorded. The daemon POSTs the counters with the
+//! version, OS and a random install id, then subtracts what it sent.
+//! `zeroclaw telemetry preview` prints the exact report.
+
+use crate::config::{Config, TelemetryConfig};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, RwLock};
+use std::time::Duration;
+
+const STATE_FILE: &str = "telemetry.json";
+/// Version of the report format.
+pub const REPORT_SCHEMA: u32 = 1;
+
+/// Tools counted by name. Anything else (skills, composio actions, test
+/// doubles) is counted as `tool.other` so user-defined names never leave.
+const BUILTIN_TOOLS: &[&str] = &[
+    "ask_user",
+    "ble_sensors",
+    "browser",
+    "browser_open",
+    "camera",
+    "composio",
+    "contacts",
+    "content_search",
+    "convert_doc",
+    "cron_add",
+    "cron_list",
+    "cron_remove",
+    "cron_run",
+    "cron_runs",
+    "cron_update",
+    "delegate",
+    "email_send",
+    "fetch_url",
+    "file_edit",
+    "file_read",
+    "file_write",
+    "firmware_build",
+    "git_operations",
+    "glob_search",
+    "graphql",
+    "hardware_board_info",
+    "hardware_logs",
+    "hardware_memory_map",
+    "hardware_memory_read",
+    "http_request",
+    "image_info",
+    "kv",
+    "memory_forget",
+    "memory_recall",
+    "memory_store",
+    "message_contact",
+    "model_routing_config",
+    "pdf_read",
+    "peer_delegate",
+    "pin",
+    "proxy_config",
+    "pushover",
+    "remote_node",
+    "schedule",
+    "screenshot",
+    "self_update",
+    "shell",
+    "smart_home_devices",
+    "smart_home_sensor",
+    "smart_home_switch",
+    "web_search_tool",
+];
+
+const ERROR_CODES: &[&str] = &[
+    "auth",
+    "rate_limit",
+    "network",
+    "validation",
+    "policy_denied",
+    "tool_failure",
+    "internal",
+];
+
+/// State file of the running process; `None` when telemetry is off.
+static RECORDER: LazyLock<RwLock<Option<PathBuf>>> = LazyLock::new(|| RwLock::new(None));
+/// Serializes read-modify-write cycles on the state file.
+static STATE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Counters {
+    install_id: String,
+    period_start: Option<DateTime<Utc>>,
+    #[serde(default)]
+    features: BTreeMap<String, u64>,
+    #[serde(default)]
+    errors: BTreeMap<String, u64>,
+}
+
+/// The exact payload sent to `[telemetry].endpoint`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub schema: u32,
+    pub install_id: String,
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    pub period_start: Option<DateTime<Utc>>,
+    pub period_end: DateTime<Utc>,
+    pub features: BTreeMap<String, u64>,
+    pub errors: BTreeMap<String, u64>,
+}
+
+impl Report {
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty() && self.errors.is_empty()
+    }
+}
+
+/// Environment variable that forces telemetry off, if one is set.
+pub fn off_switch() -> Option<&'static str> {
+    let value = |name: &str| {
+        std::env::var(name)
+            .map(|value| value.trim().to_ascii_lowercase())
+            .unwrap_or_default()
+    };
+    if matches!(
+        value("ZEROCLAW_TELEMETRY").as_str(),
+        "0" | "off" | "false" | "no"
+    ) {
+        return Some("ZEROCLAW_TELEMETRY");
+    }
+    if !matches!(value("DO_NOT_TRACK").as_str(), "" | "0") {
+        return Some("DO_NOT_TRACK");
+    }
+    None
+}
+
+pub fn is_enabled(config: &TelemetryConfig) -> bool {
+    config.enabled && off_switch().is_none()
+}
+
+/// One-line description of the current telemetry state.
+pub fn describe(config: &TelemetryConfig) -> String {
+    if let Some(var) = off_switch() {
+        format!("off ({var} is set; nothing is recorded or sent)")
+    } else if config.enabled {
+        format!(
+            "on (report every {}h to {})",
+            config.interval_hours.max(1),
+            config.endpoint.trim()
+        )
+    } else {
+        "off (opt in with [telemetry].enabled = true)".into()
+    }
+}
+
+pub fn state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(STATE_FILE)
+}
+
+/// Start (or stop) recording for this process.
+pub fn init_from_config(config: &Config) {
+    let path = is_enabled(&config.telemetry).then(|| state_path(&config.workspace_dir));
+    let mut guard = RECORDER.write().unwrap_or_else(|e| e.into_inner());
+    *guard = path;
+}
+
+/// Count a runtime trace event. No-op unless telemetry is enabled.
+pub fn record_trace(event_type: &str, channel: Option<&str>, payload: &Value) {
+    let Some(path) = RECORDER.read().unwrap_or_else(|e| e.into_inner()).clone() else {
+        return;
+    };
+    let (features, error) = classify(event_type, channel, payload);
+    let result = update(&path, |counters| {
+        for feature in &features {
+            *counters.features.entry(feature.clone()).or_default() += 1;
+        }
+        if let Some(error) = error {
+            *counters.errors.entry(error.to_string()).or_default() += 1;
+        }
+    });
+    if let Err(err) = result {
+        tracing::debug!("Failed to record telemetry: {err:#}");
+    }
+}
+
+fn classify(
+    event_type: &str,
+    channel: Option<&str>,
+    payload: &Value,
+) -> (Vec<String>, Option<&'static str>) {
+    let mut features = vec![format!("event.{}", token(event_type))];
+    if let Some(tool) = payload.get("tool").and_then(Value::as_str) {
+        let name = if BUILTIN_TOOLS.contains(&tool) {
+            tool
+        } else {
+            "other"
+        };
+        features.push(format!("tool.{name}"));
+    }
+    if let Some(channel) = channel {
+        features.push(format!("channel.{}", token(channel)));
+    }
+    let error = payload
+        .get("error_code")
+        .and_then(Value::as_str)
+        .and_then(|code| ERROR_CODES.iter().copied().find(|known| *known == code));
+    (features, error)
+}
+
+/// Fixed identifiers pass through; anything that could carry user data does not.
+fn token(raw: &str) -> &str {
+    let valid = !raw.is_empty()
+        && raw.len() <= 32
+        && raw
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_');
+    if valid {
+        raw
+    } else {
+        "other"
+    }
+}
+
+fn load(path: &Path) -> Result<Counters> {
+    match std::fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .with_context(|| format!("failed to parse {}", path.display())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Counters::default()),
+        Err(err) => Err(err).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+fn update(path: &Path, apply: impl FnOnce(&mut Counters)) -> Result<()> {
+    let _guard = STATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut counters = load(path)?;
+    if counters.install_id.is_empty() {
+        counters.install_id = uuid::Uuid::new_v4().to_string();
+    }
+    if counters.period_start.is_none() {
+        counters.period_start = Some(Utc::now());
+    }
+    apply(&mut counters);
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(&counters)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Build the report that would be sent now.
+pub fn build_report(workspace_dir: &Path, now: DateTime<Utc>) -> Result<Report> {
+    let _guard = STATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let counters = load(&state_path(workspace_dir))?;
+    Ok(Report {
+        schema: REPORT_SCHEMA,
+        install_id: counters.install_id,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        period_start: counters.period_start,
+        period_end: now,
+        features: counters.features,
+        errors: counters.errors,
+    })
+}
+
+/// Remove what `report` covered, keeping events recorded since it was built.
+fn mark_sent(workspace_dir: &Path, report: &Report) -> Result<()> {
+    fn subtract(counts: &mut BTreeMap<String, u64>, sent: &BTreeMap<String, u64>) {
+        for (key, value) in sent {
+            if let Some(count) = counts.get_mut(key) {
+                *count = count.saturating_sub(*value);
+            }
+        }
+        counts.retain(|_, count| *count > 0);
+    }
+
+    update(&state_path(workspace_dir), |counters| {
+        subtract(&mut counters.features, &report.features);
+        subtract(&mut counters.errors, &report.errors);
+        counters.period_start = Some(report.period_end);
+    })
+}
+
+/// Drop every unsent count and start a new period (`[retention.telemetry]`).
+pub fn discard_counters(workspace_dir: &Path, now: DateTime<Utc>) -> Result<()> {
+    let path = state_path(workspace_dir);
+    if !path.exists() {
+        return Ok(());
+    }
+    update(&path, |counters| {
+        counters.features.clear();
+        counters.errors.clear();
+        counters.period_start = Some(now);
+    })
+}
+
+async fn send_report(config: &Config) -> Result<()> {
+    let workspace_dir = config.workspace_dir.clone();
+    let report =
+        tokio::task::spawn_blocking(move || build_report(&workspace_dir, Utc::now())).await??;
+    if report.is_empty() {
+        return Ok(());
+    }
+
+    crate::config::build_runtime_proxy_client("telemetry")
+        .post(config.telemetry.endpoint.trim())
+        .timeout(Duration::from_secs(30))
+        .json(&report)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let workspace_dir = config.workspace_dir.clone();
+    tokio::task::spawn_blocking(move || mark_sent(&workspace_dir, &report)).await?
+}
+
+/// Daemon worker: send a report every `[telemetry].interval_hours`.
+pub async fn run_worker(config: Config) -> Result<()> {
+    let hours = u64::from(config.telemetry.interval_hours.max(1));
+    let mut interval = tokio::time::interval(Duration::from_secs(hours * 60 * 60));
+    // The first tick fires immediately; skip it so restarts don't send early.
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+        if off_switch().is_some() {
+            continue;
+        }
+        if let Err(err) = send_report(&config).await {
+            tracing::debug!("Telemetry report not sent: {err:#}");
+        }
+        crate::health::mark_component_ok("telemetry");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn classify_keeps_only_fixed_identifiers() {
+        let (features, error) = classify(
+            "tool_call_result",
+            Some("telegram"),
+            &json!({
+                "tool": "shell",
+                "arguments": "cat /home/alice/secret.txt",
+                "error_code": "policy_denied",
+            }),
+        );
+        assert_eq!(
+            features,
+            ["event.tool_call_result", "tool.shell", "channel.telegram"]
+        );
+        assert_eq!(error, Some("policy_denied"));
+
+        let (features, error) = classify(
+            "tool_call_result",
+            Some("Alice's Phone"),
+            &json!({ "tool": "acme_internal_deploy", "error_code": "boom: host db1" }),
+        );
+        assert_eq!(
+            features,
+            ["event.tool_call_result", "tool.other", "channel.other"]
+        );
+        assert_eq!(error, None);
+    }
+
+    #[test]
+    fn report_reflects_counters_and_sent_counts_are_subtracted() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = state_path(tmp.path());
+        update(&path, |counters| {
+            counters.features.insert("tool.shell".into(), 3);
+            counters.errors.insert("network".into(), 1);
+        })
+        .unwrap();
+
+        let report = build_report(tmp.path(), Utc::now()).unwrap();
+        assert_eq!(report.schema, REPORT_SCHEMA);
+        assert!(!report.install_id.is_empty());
+        assert_eq!(report.features["tool.shell"], 3);
+
+        update(&path, |counters| {
+            *counters.features.entry("tool.shell".into()).or_default() += 1;
+        })
+        .unwrap();
+        mark_sent(tmp.path(), &report).unwrap();
+
+        let next = build_report(tmp.path(), Utc::now()).unwrap();
+        assert_eq!(next.install_id, report.install_id);
+        assert_eq!(next.features["tool.shell"], 1);
+        assert!(next.errors.is_empty());
+        assert_eq!(next.period_start, Some(report.period_end));
+    }
+
+    #[test]
+    fn report_without_state_is_empty() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let report = build_report(tmp.path(), Utc::now()).unwrap();
+        assert!(report.is_empty());
+        assert!(!state_path(tmp.path()).exists());
+    }
+}
@@ -0,0 +1,94 @@
+//! In-memory channel.
+
+use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use async_trait::async_trait;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// A [`Channel`] backed by queues: [`inject`](Self::inject) delivers an
+/// inbound message to whoever is listening, and everything the runtime
+/// sends is recorded for [`next_reply`](Self::next_reply).
+pub struct FakeChannel {
+    name: String,
+    inbox_tx: mpsc::UnboundedSender<ChannelMessage>,
+    inbox_rx: tokio::sync::Mutex<mpsc::UnboundedReceiver<ChannelMessage>>,
+    outbox_tx: mpsc::UnboundedSender<SendMessage>,
+    outbox_rx: tokio::sync::Mutex<mpsc::UnboundedReceiver<SendMessage>>,
+    sent: Mutex<Vec<SendMessage>>,
+}
+
+impl FakeChannel {
+    pub fn new(name: impl Into<String>) -> Self {
+        let (inbox_tx, inbox_rx) = mpsc::unbounded_channel();
+        let (outbox_tx, outbox_rx) = mpsc::unbounded_channel();
+        Self {
+            name: name.into(),
+            inbox_tx,
+            inbox_rx: tokio::sync::Mutex::new(inbox_rx),
+            outbox_tx,
+            outbox_rx: tokio::sync::Mutex::new(outbox_rx),
+            sent: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Deliver a direct message from `sender`; replies go back to `sender`.
+    pub fn inject(&self, sender: &str, content: &str) -> ChannelMessage {
+        let message = ChannelMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            sender: sender.to_string(),
+            reply_target: sender.to_string(),
+            content: content.to_string(),
+            channel: self.name.clone(),
+            timestamp: chrono::Utc::now().timestamp().unsigned_abs(),
+            thread_ts: None,
+            group: None,
+        };
+        self.inject_message(message.clone());
+        message
+    }
+
+    /// Deliver a fully specified inbound message.
+    pub fn inject_message(&self, message: ChannelMessage) {
+        // The receiver lives as long as `self`, so this cannot fail.
+        let _ = self.inbox_tx.send(message);
+    }
+
+    /// Wait for the next message sent through this channel.
+    pub async fn next_reply(&self, timeout: Duration) -> Option<SendMessage> {
+        let mut outbox = self.outbox_rx.lock().await;
+        tokio::time::timeout(timeout, outbox.recv())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Every message sent through this channel so far.
+    pub fn sent(&self) -> Vec<SendMessage> {
+        self.sent.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[async_trait]
+impl Channel for FakeChannel {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        self.sent
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(message.clone());
+        let _ = self.outbox_tx.send(message.clone());
+        Ok(())
+    }
+
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        let mut inbox = self.inbox_rx.lock().await;
+        while let Some(message) = inbox.recv().await {
+            tx.send(message).await?;
+        }
+        Ok(())
+    }
+}
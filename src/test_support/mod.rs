@@ -0,0 +1,26 @@
+//! Test doubles for exercising ZeroClaw end to end without network access.
+//!
+//! - [`ScriptedProvider`] answers each model turn from a fixed script and
+//!   records what it was sent. Use it directly as a [`Provider`], or call
+//!   [`ScriptedProvider::serve`] to put it behind a local OpenAI-compatible
+//!   endpoint for code that builds its provider from config (channels,
+//!   gateway channel webhooks, `agent::process_message`).
+//! - [`FakeChannel`] is an in-memory [`Channel`]: inject inbound messages
+//!   and await the replies.
+//! - [`TestWorkspace`] is a temporary workspace plus a [`Config`] pointing
+//!   at it (and, optionally, at a scripted endpoint).
+//!
+//! `tests/daemon_flow.rs` drives a full message → agent → tool → reply
+//! round trip with these pieces.
+//!
+//! [`Provider`]: crate::providers::Provider
+//! [`Channel`]: crate::channels::traits::Channel
+//! [`Config`]: crate::config::Config
+
+mod channel;
+mod provider;
+mod workspace;
+
+pub use channel::FakeChannel;
+pub use provider::{RecordedRequest, ScriptedEndpoint, ScriptedProvider, ScriptedReply};
+pub use workspace::{TestWorkspace, TestWorkspaceBuilder, TEST_MODEL};
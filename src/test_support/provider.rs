@@ -0,0 +1,295 @@
+//! Scripted provider, in-process or behind a local OpenAI-compatible endpoint.
+
+use crate::providers::traits::{
+    ChatMessage, ChatRequest, ChatResponse, Provider, ProviderCapabilities, ToolCall,
+};
+use async_trait::async_trait;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// One scripted model turn.
+#[derive(Debug, Clone)]
+pub enum ScriptedReply {
+    /// A final text answer.
+    Text(String),
+    /// Native tool calls for the agent to execute.
+    ToolCalls(Vec<ToolCall>),
+    /// A provider error. Served as HTTP 400 so the resilient wrapper does
+    /// not retry it and consume the rest of the script.
+    Error(String),
+}
+
+impl ScriptedReply {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    /// A single tool call with JSON arguments.
+    pub fn tool_call(name: &str, arguments: Value) -> Self {
+        Self::ToolCalls(vec![ToolCall {
+            id: format!("call_{}", uuid::Uuid::new_v4().simple()),
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+        }])
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::Error(message.into())
+    }
+}
+
+/// What the agent sent for one turn.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub messages: Vec<ChatMessage>,
+    /// Names of the tools offered natively.
+    pub tools: Vec<String>,
+}
+
+impl RecordedRequest {
+    /// Content of the last message with `role`.
+    pub fn last(&self, role: &str) -> Option<&str> {
+        self.messages
+            .iter()
+            .rev()
+            .find(|m| m.role == role)
+            .map(|m| m.content.as_str())
+    }
+}
+
+#[derive(Default)]
+struct Script {
+    replies: Mutex<VecDeque<ScriptedReply>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+impl Script {
+    fn next(&self, request: RecordedRequest) -> ScriptedReply {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(request);
+        self.replies
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop_front()
+            .unwrap_or_else(|| ScriptedReply::error("scripted provider has no replies left"))
+    }
+}
+
+/// A provider that answers with a fixed script and records every request.
+/// Clones share the script.
+#[derive(Clone, Default)]
+pub struct ScriptedProvider {
+    script: Arc<Script>,
+}
+
+impl ScriptedProvider {
+    pub fn new(replies: impl IntoIterator<Item = ScriptedReply>) -> Self {
+        let provider = Self::default();
+        provider.push(replies);
+        provider
+    }
+
+    /// Append replies to the script.
+    pub fn push(&self, replies: impl IntoIterator<Item = ScriptedReply>) {
+        self.script
+            .replies
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend(replies);
+    }
+
+    /// Requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.script
+            .requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replies not yet consumed.
+    pub fn remaining(&self) -> usize {
+        self.script
+            .replies
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    /// Serve the script as an OpenAI-compatible chat completions endpoint on
+    /// 127.0.0.1, for code paths that build their provider from config.
+    pub async fn serve(&self) -> anyhow::Result<ScriptedEndpoint> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let app = Router::new()
+            .route("/v1/chat/completions", post(handle_chat_completions))
+            .with_state(Arc::clone(&self.script));
+        let task = tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+        Ok(ScriptedEndpoint {
+            base_url: format!("http://{addr}/v1"),
+            task,
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for ScriptedProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: true,
+            vision: false,
+        }
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::new();
+        if let Some(system) = system_prompt {
+            messages.push(ChatMessage::system(system));
+        }
+        messages.push(ChatMessage::user(message));
+        self.chat_with_history(&messages, model, temperature).await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let request = ChatRequest {
+            messages,
+            tools: None,
+        };
+        let response = self.chat(request, model, temperature).await?;
+        Ok(response.text.unwrap_or_default())
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        _model: &str,
+        _temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let recorded = RecordedRequest {
+            messages: request.messages.to_vec(),
+            tools: request
+                .tools
+                .unwrap_or_default()
+                .iter()
+                .map(|tool| tool.name.clone())
+                .collect(),
+        };
+        match self.script.next(recorded) {
+            ScriptedReply::Text(text) => Ok(ChatResponse {
+                text: Some(text),
+                tool_calls: Vec::new(),
+                usage: None,
+            }),
+            ScriptedReply::ToolCalls(tool_calls) => Ok(ChatResponse {
+                text: None,
+                tool_calls,
+                usage: None,
+            }),
+            ScriptedReply::Error(message) => anyhow::bail!("400 Bad Request: {message}"),
+        }
+    }
+}
+
+/// A running OpenAI-compatible endpoint; stops when dropped.
+pub struct ScriptedEndpoint {
+    base_url: String,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl ScriptedEndpoint {
+    /// Base URL, e.g. `http://127.0.0.1:40123/v1`.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Value for `default_provider`.
+    pub fn provider_name(&self) -> String {
+        format!("custom:{}", self.base_url)
+    }
+}
+
+impl Drop for ScriptedEndpoint {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn handle_chat_completions(
+    State(script): State<Arc<Script>>,
+    Json(body): Json<Value>,
+) -> (StatusCode, Json<Value>) {
+    let messages = body["messages"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|message| ChatMessage {
+            role: message["role"].as_str().unwrap_or_default().to_string(),
+            content: message_text(&message["content"]),
+        })
+        .collect();
+    let tools = body["tools"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|tool| tool.pointer("/function/name")?.as_str())
+        .map(str::to_string)
+        .collect();
+
+    let message = match script.next(RecordedRequest { messages, tools }) {
+        ScriptedReply::Text(text) => json!({"role": "assistant", "content": text}),
+        ScriptedReply::ToolCalls(calls) => json!({
+            "role": "assistant",
+            "content": null,
+            "tool_calls": calls.iter().map(|call| json!({
+                "id": call.id,
+                "type": "function",
+                "function": {"name": call.name, "arguments": call.arguments},
+            })).collect::<Vec<_>>(),
+        }),
+        ScriptedReply::Error(message) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": {"message": message}})),
+            );
+        }
+    };
+    (
+        StatusCode::OK,
+        Json(json!({"choices": [{"index": 0, "message": message}]})),
+    )
+}
+
+/// Text of an OpenAI message `content`, which is a string or a list of parts.
+fn message_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|part| part["text"].as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
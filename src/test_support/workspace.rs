@@ -0,0 +1,100 @@
+//! Temporary workspace with a matching config.
+
+use super::ScriptedEndpoint;
+use crate::config::Config;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Model name written to test configs.
+pub const TEST_MODEL: &str = "scripted-model";
+
+type ConfigEdit = Box<dyn FnOnce(&mut Config)>;
+
+/// Builder for [`TestWorkspace`].
+#[derive(Default)]
+pub struct TestWorkspaceBuilder {
+    files: Vec<(PathBuf, String)>,
+    provider: Option<String>,
+    edits: Vec<ConfigEdit>,
+}
+
+impl TestWorkspaceBuilder {
+    /// Write a file (relative to the workspace) before the config is built.
+    pub fn file(mut self, path: impl Into<PathBuf>, contents: impl Into<String>) -> Self {
+        self.files.push((path.into(), contents.into()));
+        self
+    }
+
+    /// Point `default_provider` at a scripted endpoint.
+    pub fn provider(mut self, endpoint: &ScriptedEndpoint) -> Self {
+        self.provider = Some(endpoint.provider_name());
+        self
+    }
+
+    /// Adjust the generated config.
+    pub fn configure(mut self, edit: impl FnOnce(&mut Config) + 'static) -> Self {
+        self.edits.push(Box::new(edit));
+        self
+    }
+
+    pub fn build(self) -> Result<TestWorkspace> {
+        let dir = TempDir::new().context("Failed to create temp workspace")?;
+        let workspace_dir = dir.path().join("workspace");
+        std::fs::create_dir_all(&workspace_dir)?;
+        for (path, contents) in &self.files {
+            let path = workspace_dir.join(path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, contents)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+
+        let mut config = Config {
+            workspace_dir,
+            config_path: dir.path().join("config.toml"),
+            api_key: Some("test-key".into()),
+            default_provider: self.provider,
+            default_model: Some(TEST_MODEL.into()),
+            ..Config::default()
+        };
+        // Scripted errors should surface immediately, not after backoff.
+        config.reliability.provider_retries = 0;
+        config.memory.backend = "none".into();
+        for edit in self.edits {
+            edit(&mut config);
+        }
+        Ok(TestWorkspace { dir, config })
+    }
+}
+
+/// A temporary directory holding a workspace and a config that points at
+/// it. The directory is deleted on drop. The config is not written to disk,
+/// so nothing hot-reloads it behind the test's back.
+pub struct TestWorkspace {
+    dir: TempDir,
+    pub config: Config,
+}
+
+impl TestWorkspace {
+    pub fn builder() -> TestWorkspaceBuilder {
+        TestWorkspaceBuilder::default()
+    }
+
+    /// Root of the temporary directory (the config lives here).
+    pub fn root(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// The agent's workspace directory.
+    pub fn path(&self) -> &Path {
+        &self.config.workspace_dir
+    }
+
+    /// Read a workspace file.
+    pub fn read(&self, path: impl AsRef<Path>) -> Result<String> {
+        let path = self.path().join(path);
+        std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))
+    }
+}
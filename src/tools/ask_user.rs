@@ -0,0 +1,160 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use crate::questions::{self, QuestionQueue};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Queue a question for the owner and suspend the current unattended task
+/// until it is answered (see [`crate::questions`]).
+pub struct AskUserTool {
+    config: Arc<Config>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl AskUserTool {
+    pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+}
+
+#[async_trait]
+impl Tool for AskUserTool {
+    fn name(&self) -> &str {
+        "ask_user"
+    }
+
+    fn description(&self) -> &str {
+        "Ask the owner a question when an unattended task (heartbeat, cron) cannot continue without their input. \
+        The question is sent to the owner's channel and the task is suspended: after calling this tool, stop and \
+        do not guess. The task resumes automatically with the answer, starting from the context you provide."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "question": {
+                    "type": "string",
+                    "description": "The question for the owner"
+                },
+                "options": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Optional choices; the owner may answer with the option number"
+                },
+                "context": {
+                    "type": "string",
+                    "description": "Everything needed to resume: the original task, progress so far, and how the answer will be used"
+                }
+            },
+            "required": ["question", "context"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let question = args
+            .get("question")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        ToolResult {
+            success: true,
+            output: format!("[simulated] ask_user: {question}\n(question not queued)"),
+            error: None,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let question = args
+            .get("question")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let context = args
+            .get("context")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        if question.trim().is_empty() || context.trim().is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Both 'question' and 'context' are required".into()),
+            });
+        }
+        let options: Vec<String> = args
+            .get("options")
+            .and_then(serde_json::Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let route = questions::owner_route(&self.config);
+        let queue = QuestionQueue::from_config(&self.config);
+        let queued = queue.ask(question, options, context, route.as_ref().ok().cloned())?;
+
+        let delivery = match route {
+            Ok((channel, _)) => match questions::notify(&self.config, &queued).await {
+                Ok(()) => format!("sent to the owner on {channel}"),
+                Err(e) => format!("could not be delivered on {channel} ({e})"),
+            },
+            Err(e) => format!("was not sent ({e})"),
+        };
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "Question {id} queued and {delivery}. The task is now suspended: end your turn without guessing. \
+                It resumes automatically once the owner answers (`/answer {id} <answer>` in a channel or \
+                `zeroclaw questions answer {id} <answer>`).",
+                id = queued.id
+            ),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::questions::QuestionStatus;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn queues_question_even_without_owner_channel() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let tool = AskUserTool::new(Arc::new(config), Arc::new(SecurityPolicy::default()));
+        let result = tool
+            .execute(json!({
+                "question": "Which environment?",
+                "options": ["staging", "prod"],
+                "context": "Nightly deploy job"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("was not sent"));
+        assert!(result.output.contains("suspended"));
+
+        let queued = QuestionQueue::for_workspace(tmp.path()).list().unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].status, QuestionStatus::Pending);
+        assert_eq!(queued[0].options, vec!["staging", "prod"]);
+        assert!(queued[0].channel.is_none());
+    }
+}
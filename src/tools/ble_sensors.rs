@@ -0,0 +1,124 @@
+//! BLE sensors tool — readings and presence from `[peripherals.ble]` sensors.
+//!
+//! Use when the user asks "how warm is the bedroom?", "is the battery on the
+//! fridge sensor low?" or "are my keys at home?" (iBeacon/presence tags).
+
+use super::traits::{Tool, ToolResult};
+use crate::config::BleConfig;
+use crate::peripherals::ble;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::json;
+
+/// Tool: latest decoded readings and presence for configured BLE sensors.
+pub struct BleSensorsTool {
+    config: BleConfig,
+}
+
+impl BleSensorsTool {
+    pub fn new(config: BleConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Tool for BleSensorsTool {
+    fn name(&self) -> &str {
+        "ble_sensors"
+    }
+
+    fn description(&self) -> &str {
+        "Read configured Bluetooth LE sensors: thermometers report temperature/humidity/battery, \
+        and every sensor reports present true/false (heard within the presence timeout) with \
+        seconds since it was last seen. Optional param: sensor (name) to read just one."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "sensor": {
+                    "type": "string",
+                    "description": "Sensor name from [peripherals.ble]; omit for all sensors"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !ble::scanner_available() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(
+                    "BLE scanning is not built in. Rebuild with: cargo build --features peripheral-ble"
+                        .into(),
+                ),
+            });
+        }
+        ble::ensure_started(&self.config);
+
+        let filter = args
+            .get("sensor")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|name| !name.is_empty());
+        let sensors: Vec<_> = self
+            .config
+            .sensors
+            .iter()
+            .filter(|sensor| filter.is_none_or(|name| sensor.name.eq_ignore_ascii_case(name)))
+            .collect();
+        if let (Some(name), true) = (filter, sensors.is_empty()) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Unknown BLE sensor '{name}'")),
+            });
+        }
+
+        let now = Utc::now();
+        let output: Vec<_> = sensors
+            .into_iter()
+            .map(|sensor| {
+                let state = ble::sensor_state(&sensor.name);
+                let seen_secs_ago = state
+                    .last_seen
+                    .map(|at| (now - at).num_seconds().max(0).unsigned_abs());
+                json!({
+                    "name": sensor.name,
+                    "format": sensor.format,
+                    "present": seen_secs_ago
+                        .is_some_and(|secs| secs <= self.config.presence_timeout_secs),
+                    "last_seen_secs_ago": seen_secs_ago,
+                    "rssi": state.rssi,
+                    "reading": state.reading_at.map(|_| &state.reading),
+                    "reading_at": state.reading_at.map(|at| at.to_rfc3339()),
+                })
+            })
+            .collect();
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&output)?,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_missing_ble_support_or_unknown_sensor() {
+        let tool = BleSensorsTool::new(BleConfig::default());
+        let result = tool.execute(json!({"sensor": "nope"})).await.unwrap();
+        assert!(!result.success);
+        let error = result.error.unwrap();
+        if ble::scanner_available() {
+            assert!(error.contains("Unknown BLE sensor"));
+        } else {
+            assert!(error.contains("peripheral-ble"));
+        }
+    }
+}
@@ -6,7 +6,7 @@
 //! Computer-use (OS-level) actions are supported via an optional sidecar endpoint.
 
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::{NetworkGuard, SecurityPolicy};
 use anyhow::Context;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -67,6 +67,7 @@ pub struct BrowserTool {
     native_webdriver_url: String,
     native_chrome_path: Option<String>,
     computer_use: ComputerUseConfig,
+    network: NetworkGuard,
     #[cfg(feature = "browser-native")]
     native_state: tokio::sync::Mutex<native_backend::NativeBrowserState>,
 }
@@ -234,11 +235,18 @@ impl BrowserTool {
             native_webdriver_url,
             native_chrome_path,
             computer_use,
+            network: NetworkGuard::new("browser"),
             #[cfg(feature = "browser-native")]
             native_state: tokio::sync::Mutex::new(native_backend::NativeBrowserState::default()),
         }
     }
 
+    /// Enforce `[security.network.browser]` on resolved destinations.
+    pub fn with_network_guard(mut self, network: NetworkGuard) -> Self {
+        self.network = network;
+        self
+    }
+
     /// Check if agent-browser CLI is available
     pub async fn is_agent_browser_available() -> bool {
         Command::new("agent-browser")
@@ -436,6 +444,18 @@ impl BrowserTool {
         Ok(())
     }
 
+    /// Validate an `open` target and check where its host resolves to. The
+    /// browser process does its own DNS lookup, so this rejects names that
+    /// point at disallowed addresses but cannot pin the browser's connection.
+    async fn validate_destination(&self, url: &str) -> anyhow::Result<()> {
+        self.validate_url(url)?;
+        self.network
+            .resolve_url(url.trim())
+            .await
+            .map_err(|e| anyhow::anyhow!("{e:#}"))?;
+        Ok(())
+    }
+
     /// Execute an agent-browser command
     async fn run_command(&self, args: &[&str]) -> anyhow::Result<AgentBrowserResponse> {
         let mut cmd = Command::new("agent-browser");
@@ -492,7 +512,6 @@ impl BrowserTool {
     ) -> anyhow::Result<ToolResult> {
         match action {
             BrowserAction::Open { url } => {
-                self.validate_url(&url)?;
                 let resp = self.run_command(&["open", &url]).await?;
                 self.to_result(resp)
             }
@@ -732,6 +751,11 @@ impl BrowserTool {
         params.remove("action");
 
         self.validate_computer_use_action(action, &params)?;
+        if action == "open" {
+            if let Some(url) = params.get("url").and_then(Value::as_str) {
+                self.validate_destination(url).await?;
+            }
+        }
 
         let payload = json!({
             "action": action,
@@ -836,6 +860,9 @@ impl BrowserTool {
         action: BrowserAction,
         backend: ResolvedBackend,
     ) -> anyhow::Result<ToolResult> {
+        if let BrowserAction::Open { url } = &action {
+            self.validate_destination(url).await?;
+        }
         match backend {
             ResolvedBackend::AgentBrowser => self.execute_agent_browser_action(action).await,
             ResolvedBackend::RustNative => self.execute_rust_native_action(action).await,
@@ -2072,7 +2099,7 @@ fn is_non_global_v6(v6: std::net::Ipv6Addr) -> bool {
         || v6.to_ipv4_mapped().is_some_and(is_non_global_v4)
 }
 
-fn host_matches_allowlist(host: &str, allowed: &[String]) -> bool {
+pub(crate) fn host_matches_allowlist(host: &str, allowed: &[String]) -> bool {
     allowed.iter().any(|pattern| {
         if pattern == "*" {
             return true;
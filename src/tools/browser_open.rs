@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::security::{NetworkGuard, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -8,6 +8,7 @@ use std::sync::Arc;
 pub struct BrowserOpenTool {
     security: Arc<SecurityPolicy>,
     allowed_domains: Vec<String>,
+    network: NetworkGuard,
 }
 
 impl BrowserOpenTool {
@@ -15,9 +16,16 @@ impl BrowserOpenTool {
         Self {
             security,
             allowed_domains: normalize_allowed_domains(allowed_domains),
+            network: NetworkGuard::new("browser_open"),
         }
     }
 
+    /// Enforce `[security.network.browser_open]` on resolved destinations.
+    pub fn with_network_guard(mut self, network: NetworkGuard) -> Self {
+        self.network = network;
+        self
+    }
+
     fn validate_url(&self, raw_url: &str) -> anyhow::Result<String> {
         let url = raw_url.trim();
 
@@ -109,6 +117,16 @@ impl Tool for BrowserOpenTool {
             }
         };
 
+        // Brave resolves the host itself, so this catches names that point at
+        // disallowed addresses but cannot pin the browser's own lookup.
+        if let Err(e) = self.network.resolve_url(&url).await {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("{e:#}")),
+            });
+        }
+
         match open_in_brave(&url).await {
             Ok(()) => Ok(ToolResult {
                 success: true,
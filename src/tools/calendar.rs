@@ -0,0 +1,583 @@
+use super::traits::{Tool, ToolResult};
+use crate::calendar::{self, Busy, NewEvent, Participant, SlotSearch, WorkingHours};
+use crate::config::Config;
+use crate::security::{AutonomyLevel, SecurityPolicy};
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+const MAX_ATTENDEES: usize = 20;
+const MAX_SEARCH_DAYS: u32 = 31;
+const DEFAULT_SEARCH_DAYS: u32 = 5;
+const MAX_DURATION_MINUTES: i64 = 12 * 60;
+
+/// Meeting scheduling against the owner's calendar. Free slots and timezone
+/// conversions are computed here, not by the model; creating an event below
+/// full autonomy needs approval of the previewed invitation.
+pub struct CalendarTool {
+    config: Arc<Config>,
+    security: Arc<SecurityPolicy>,
+}
+
+/// A validated event, ready to preview or write.
+#[derive(Debug)]
+struct EventDraft {
+    event: NewEvent,
+    attendee_zones: Vec<(String, Tz)>,
+}
+
+impl EventDraft {
+    fn preview(&self, owner_tz: Tz) -> String {
+        let duration = self.event.end - self.event.start;
+        let mut out = format!(
+            "Event: {}\nWhen: {}\n",
+            self.event.title,
+            calendar::format_slot(self.event.start, duration, owner_tz)
+        );
+        for (who, tz) in &self.attendee_zones {
+            let _ = writeln!(
+                out,
+                "      {} for {who}",
+                calendar::format_slot(self.event.start, duration, *tz)
+            );
+        }
+        if !self.event.attendees.is_empty() {
+            let _ = writeln!(out, "Attendees: {}", self.event.attendees.join(", "));
+        }
+        if !self.event.location.is_empty() {
+            let _ = writeln!(out, "Location: {}", self.event.location);
+        }
+        if !self.event.description.is_empty() {
+            let _ = write!(out, "\n{}", self.event.description);
+        }
+        out.trim_end().to_string()
+    }
+}
+
+fn parse_tz(value: &str) -> anyhow::Result<Tz> {
+    value.trim().parse::<Tz>().map_err(|_| {
+        anyhow::anyhow!("Unknown timezone '{value}' (use an IANA name such as Europe/Berlin)")
+    })
+}
+
+/// RFC 3339 instant, or a local `YYYY-MM-DDTHH:MM` wall-clock time in `tz`.
+fn parse_instant(value: &str, tz: Tz) -> anyhow::Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(instant) = DateTime::parse_from_rfc3339(value) {
+        return Ok(instant.with_timezone(&Utc));
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M"))
+        .with_context(|| {
+            format!("Invalid time '{value}' (expected RFC 3339 or YYYY-MM-DDTHH:MM)")
+        })?;
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .with_context(|| format!("'{value}' does not exist in {} (DST gap)", tz.name()))
+}
+
+fn duration_arg(args: &serde_json::Value) -> anyhow::Result<Duration> {
+    let minutes = args
+        .get("duration_minutes")
+        .and_then(serde_json::Value::as_i64)
+        .context("Missing 'duration_minutes'")?;
+    if !(1..=MAX_DURATION_MINUTES).contains(&minutes) {
+        bail!("'duration_minutes' must be between 1 and {MAX_DURATION_MINUTES}");
+    }
+    Ok(Duration::minutes(minutes))
+}
+
+fn str_arg<'a>(args: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+impl CalendarTool {
+    pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    fn owner_tz(&self) -> Tz {
+        calendar::owner_timezone(&self.config)
+    }
+
+    fn attendees(&self, args: &serde_json::Value) -> anyhow::Result<Vec<Participant>> {
+        let Some(items) = args.get("attendees") else {
+            return Ok(Vec::new());
+        };
+        let items = items.as_array().context("'attendees' must be an array")?;
+        if items.len() > MAX_ATTENDEES {
+            bail!("At most {MAX_ATTENDEES} attendees are supported");
+        }
+        let owner_hours = calendar::owner_hours(&self.config.calendar)?;
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let name = str_arg(item, "name")
+                    .or_else(|| str_arg(item, "email"))
+                    .map_or_else(|| format!("attendee {}", i + 1), str::to_string);
+                let tz = match str_arg(item, "timezone") {
+                    Some(tz) => parse_tz(tz)?,
+                    None => bail!("Attendee '{name}' needs a 'timezone'"),
+                };
+                let days: Vec<String> = match item.get("work_days") {
+                    Some(days) => serde_json::from_value(days.clone())
+                        .context("'work_days' must be an array of weekday names")?,
+                    None => self.config.calendar.work_days.clone(),
+                };
+                let hours = if item.get("work_start").is_some()
+                    || item.get("work_end").is_some()
+                    || item.get("work_days").is_some()
+                {
+                    WorkingHours::parse(
+                        str_arg(item, "work_start").unwrap_or("09:00"),
+                        str_arg(item, "work_end").unwrap_or("17:00"),
+                        &days,
+                    )
+                    .with_context(|| format!("Attendee '{name}'"))?
+                } else {
+                    owner_hours.clone()
+                };
+                let busy = match item.get("busy").and_then(serde_json::Value::as_array) {
+                    Some(ranges) => ranges
+                        .iter()
+                        .map(|range| {
+                            let start =
+                                str_arg(range, "start").context("busy range needs 'start'")?;
+                            let end = str_arg(range, "end").context("busy range needs 'end'")?;
+                            Ok(Busy {
+                                start: parse_instant(start, tz)?,
+                                end: parse_instant(end, tz)?,
+                            })
+                        })
+                        .collect::<anyhow::Result<Vec<_>>>()?,
+                    None => Vec::new(),
+                };
+                Ok(Participant {
+                    name,
+                    tz,
+                    hours,
+                    busy,
+                })
+            })
+            .collect()
+    }
+
+    async fn find_slots(&self, args: &serde_json::Value) -> anyhow::Result<String> {
+        let duration = duration_arg(args)?;
+        let owner_tz = self.owner_tz();
+        let now = Utc::now();
+        let first_day = match str_arg(args, "start_date") {
+            Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .with_context(|| format!("Invalid 'start_date' '{date}' (expected YYYY-MM-DD)"))?,
+            None => now.with_timezone(&owner_tz).date_naive(),
+        };
+        let days = args
+            .get("days")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_SEARCH_DAYS, |days| {
+                u32::try_from(days).unwrap_or(MAX_SEARCH_DAYS)
+            })
+            .clamp(1, MAX_SEARCH_DAYS);
+
+        let window_start = owner_tz
+            .from_local_datetime(&first_day.and_time(chrono::NaiveTime::MIN))
+            .earliest()
+            .map_or(now, |t| t.with_timezone(&Utc))
+            - Duration::days(1);
+        let window_end = window_start + Duration::days(i64::from(days) + 2);
+        let owner = Participant {
+            name: "you".into(),
+            tz: owner_tz,
+            hours: calendar::owner_hours(&self.config.calendar)?,
+            busy: calendar::owner_busy(&self.config, window_start, window_end).await?,
+        };
+        let mut participants = vec![owner];
+        participants.extend(self.attendees(args)?);
+
+        let search = SlotSearch {
+            first_day,
+            days,
+            duration,
+            step: Duration::minutes(i64::from(self.config.calendar.slot_step_minutes)),
+            max_slots: self.config.calendar.max_slots,
+            not_before: now,
+        };
+        let slots = calendar::find_slots(&participants, &search);
+        if slots.is_empty() {
+            return Ok(format!(
+                "No common free slot of {} minutes in the {days} day(s) from {first_day}. \
+                Try more days or a shorter meeting.",
+                duration.num_minutes()
+            ));
+        }
+        let mut out = format!(
+            "{} free slot(s) of {} minutes:\n",
+            slots.len(),
+            duration.num_minutes()
+        );
+        for (i, start) in slots.iter().enumerate() {
+            let _ = writeln!(out, "{}. start={}", i + 1, start.to_rfc3339());
+            for participant in &participants {
+                let _ = writeln!(
+                    out,
+                    "   {}: {}",
+                    participant.name,
+                    calendar::format_slot(*start, duration, participant.tz)
+                );
+            }
+        }
+        Ok(out.trim_end().to_string())
+    }
+
+    fn convert(&self, args: &serde_json::Value) -> anyhow::Result<String> {
+        let from = match str_arg(args, "from_timezone") {
+            Some(tz) => parse_tz(tz)?,
+            None => self.owner_tz(),
+        };
+        let time = str_arg(args, "time").context("Missing 'time'")?;
+        let instant = parse_instant(time, from)?;
+        let targets: Vec<String> = match args.get("to_timezones") {
+            Some(value) => serde_json::from_value(value.clone())
+                .context("'to_timezones' must be an array of timezone names")?,
+            None => Vec::new(),
+        };
+        let mut out = format!("UTC: {}\n", instant.format("%a %Y-%m-%d %H:%M"));
+        for name in std::iter::once(from.name().to_string()).chain(targets) {
+            let tz = parse_tz(&name)?;
+            let local = instant.with_timezone(&tz);
+            let _ = writeln!(
+                out,
+                "{}: {} ({})",
+                tz.name(),
+                local.format("%a %Y-%m-%d %H:%M"),
+                local.format("%Z %:z")
+            );
+        }
+        Ok(out.trim_end().to_string())
+    }
+
+    fn event_draft(&self, args: &serde_json::Value) -> anyhow::Result<EventDraft> {
+        let title = str_arg(args, "title").context("Missing 'title'")?;
+        let start = parse_instant(
+            str_arg(args, "start").context("Missing 'start'")?,
+            self.owner_tz(),
+        )?;
+        let duration = duration_arg(args)?;
+        let participants = self.attendees(args)?;
+        let mut emails = Vec::new();
+        if let Some(items) = args.get("attendees").and_then(serde_json::Value::as_array) {
+            for item in items {
+                if let Some(email) = str_arg(item, "email") {
+                    if !email.contains('@') {
+                        bail!("Invalid attendee email '{email}'");
+                    }
+                    emails.push(email.to_string());
+                }
+            }
+        }
+        let end = start + duration;
+        for participant in &participants {
+            if participant
+                .busy
+                .iter()
+                .any(|busy| busy.overlaps(start, end))
+            {
+                bail!("'{}' is busy at that time", participant.name);
+            }
+        }
+        Ok(EventDraft {
+            event: NewEvent {
+                uid: uuid::Uuid::new_v4().to_string(),
+                title: title.to_string(),
+                start,
+                end,
+                description: str_arg(args, "description").unwrap_or_default().to_string(),
+                location: str_arg(args, "location").unwrap_or_default().to_string(),
+                organizer: self.config.calendar.organizer.clone(),
+                attendees: emails,
+            },
+            attendee_zones: participants
+                .into_iter()
+                .map(|participant| (participant.name, participant.tz))
+                .collect(),
+        })
+    }
+
+    async fn create_event(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let draft = match self.event_draft(args) {
+            Ok(draft) => draft,
+            Err(e) => return Ok(Self::failure(e.to_string())),
+        };
+        let preview = draft.preview(self.owner_tz());
+        if !self.security.can_act() {
+            return Ok(Self::failure("Action blocked: autonomy is read-only"));
+        }
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if self.security.autonomy != AutonomyLevel::Full && !approved {
+            return Ok(ToolResult {
+                success: false,
+                output: format!("Event (not created):\n{preview}"),
+                error: Some(
+                    "Creating a calendar event requires explicit approval of this event below full autonomy"
+                        .into(),
+                ),
+            });
+        }
+
+        // The slot may have filled up since it was proposed.
+        let owner_busy =
+            match calendar::owner_busy(&self.config, draft.event.start, draft.event.end).await {
+                Ok(busy) => busy,
+                Err(e) => return Ok(Self::failure(format!("{e:#}"))),
+            };
+        if owner_busy
+            .iter()
+            .any(|busy| busy.overlaps(draft.event.start, draft.event.end))
+        {
+            return Ok(Self::failure(
+                "That time is no longer free on your calendar; search for slots again",
+            ));
+        }
+        if !self.security.record_action() {
+            return Ok(Self::failure(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+        match calendar::create_event(&self.config, &draft.event).await {
+            Ok(url) => Ok(ToolResult {
+                success: true,
+                output: format!("Created event at {url}\n{preview}"),
+                error: None,
+            }),
+            Err(e) => Ok(Self::failure(format!("Failed to create event: {e:#}"))),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for CalendarTool {
+    fn name(&self) -> &str {
+        "calendar"
+    }
+
+    fn description(&self) -> &str {
+        "Schedule meetings against the user's calendar. 'find_slots' returns times free for the \
+        user and every attendee within each person's working hours and timezone; 'convert' \
+        shows a time in other timezones; 'create_event' books a slot (needs approval below full \
+        autonomy). Never do timezone arithmetic yourself: use the times this tool returns."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["find_slots", "convert", "create_event"]
+                },
+                "duration_minutes": {
+                    "type": "integer",
+                    "description": "Meeting length (find_slots, create_event)"
+                },
+                "attendees": {
+                    "type": "array",
+                    "description": "Other participants. Busy ranges and working hours are in the attendee's timezone unless given as RFC 3339 with an offset",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "email": { "type": "string" },
+                            "timezone": { "type": "string", "description": "IANA name, e.g. America/New_York" },
+                            "work_start": { "type": "string", "description": "HH:MM" },
+                            "work_end": { "type": "string", "description": "HH:MM" },
+                            "work_days": { "type": "array", "items": { "type": "string" } },
+                            "busy": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "start": { "type": "string" },
+                                        "end": { "type": "string" }
+                                    }
+                                }
+                            }
+                        },
+                        "required": ["timezone"]
+                    }
+                },
+                "start_date": {
+                    "type": "string",
+                    "description": "First day to search, YYYY-MM-DD in the user's timezone (default: today)"
+                },
+                "days": {
+                    "type": "integer",
+                    "description": "Days to search (default 5, max 31)"
+                },
+                "time": {
+                    "type": "string",
+                    "description": "Time to convert: RFC 3339 or YYYY-MM-DDTHH:MM in from_timezone"
+                },
+                "from_timezone": { "type": "string" },
+                "to_timezones": { "type": "array", "items": { "type": "string" } },
+                "title": { "type": "string" },
+                "start": {
+                    "type": "string",
+                    "description": "Event start: a 'start=' value from find_slots (RFC 3339)"
+                },
+                "description": { "type": "string" },
+                "location": { "type": "string" }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        if str_arg(args, "action") != Some("create_event") {
+            return Self::failure("[simulated] Only create_event is simulated");
+        }
+        match self.event_draft(args) {
+            Ok(draft) => ToolResult {
+                success: true,
+                output: format!(
+                    "[simulated] Would create event:\n{}",
+                    draft.preview(self.owner_tz())
+                ),
+                error: None,
+            },
+            Err(e) => Self::failure(e.to_string()),
+        }
+    }
+
+    fn change_preview(&self, args: &serde_json::Value) -> Option<String> {
+        if str_arg(args, "action") != Some("create_event") {
+            return None;
+        }
+        self.event_draft(args)
+            .ok()
+            .map(|draft| draft.preview(self.owner_tz()))
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let result = match str_arg(&args, "action") {
+            Some("find_slots") => self.find_slots(&args).await,
+            Some("convert") => self.convert(&args),
+            Some("create_event") => return self.create_event(&args).await,
+            Some(other) => Err(anyhow::anyhow!("Unknown action '{other}'")),
+            None => Err(anyhow::anyhow!("Missing 'action'")),
+        };
+        Ok(match result {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(e) => Self::failure(format!("{e:#}")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CalendarConfig;
+
+    fn test_tool(autonomy: AutonomyLevel) -> CalendarTool {
+        let config = Config {
+            calendar: CalendarConfig {
+                enabled: true,
+                timezone: Some("Europe/Berlin".into()),
+                ..CalendarConfig::default()
+            },
+            ..Config::default()
+        };
+        let security = SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        };
+        CalendarTool::new(Arc::new(config), Arc::new(security))
+    }
+
+    #[tokio::test]
+    async fn convert_handles_local_times_across_zones() {
+        let tool = test_tool(AutonomyLevel::Full);
+        let result = tool
+            .execute(json!({
+                "action": "convert",
+                "time": "2026-10-26T09:30",
+                "from_timezone": "America/New_York",
+                "to_timezones": ["Europe/Berlin", "Asia/Kolkata"]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("UTC: Mon 2026-10-26 13:30"));
+        assert!(result
+            .output
+            .contains("Europe/Berlin: Mon 2026-10-26 14:30"));
+        assert!(result.output.contains("Asia/Kolkata: Mon 2026-10-26 19:00"));
+    }
+
+    #[tokio::test]
+    async fn create_event_returns_preview_until_approved() {
+        let tool = test_tool(AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({
+                "action": "create_event",
+                "title": "Roadmap sync",
+                "start": "2030-03-04T15:00:00Z",
+                "duration_minutes": 30,
+                "attendees": [
+                    { "name": "Bob", "email": "bob@example.com", "timezone": "America/New_York" }
+                ]
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.output.contains("Event (not created)"));
+        assert!(result
+            .output
+            .contains("Mon 2030-03-04 16:00–16:30 (Europe/Berlin)"));
+        assert!(result
+            .output
+            .contains("Mon 2030-03-04 10:00–10:30 (America/New_York) for Bob"));
+        assert!(result.output.contains("Attendees: bob@example.com"));
+    }
+
+    #[test]
+    fn attendee_busy_ranges_are_read_in_their_timezone() {
+        let tool = test_tool(AutonomyLevel::Full);
+        let error = tool
+            .event_draft(&json!({
+                "title": "Sync",
+                "start": "2030-03-04T15:00:00Z",
+                "duration_minutes": 60,
+                "attendees": [{
+                    "name": "Bob",
+                    "timezone": "America/New_York",
+                    "busy": [{ "start": "2030-03-04T10:30", "end": "2030-03-04T11:00" }]
+                }]
+            }))
+            .unwrap_err();
+        assert!(error.to_string().contains("'Bob' is busy"));
+    }
+}
@@ -0,0 +1,296 @@
+//! Camera tool — snapshots from RTSP/USB cameras, optionally described by
+//! the model.
+//!
+//! Use when the user asks "what's on the porch camera?", "take a picture of
+//! the 3D printer" or wants a motion snapshot looked at. Snapshots stay in
+//! the workspace; `describe` only works when `[camera] allow_vision` is set.
+
+use super::traits::{Tool, ToolResult};
+use crate::camera;
+use crate::config::{CameraConfig, MultimodalConfig};
+use crate::providers::{self, ChatMessage, ProviderRuntimeOptions};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Default question for `describe`.
+const DEFAULT_VISION_PROMPT: &str = "Describe what this camera snapshot shows. Be concise.";
+/// How many recent snapshots `list` reports.
+const LIST_LIMIT: usize = 10;
+
+/// Provider the `describe` action sends snapshots to.
+pub struct CameraVision {
+    pub provider: String,
+    pub model: String,
+    pub api_key: Option<String>,
+    pub options: ProviderRuntimeOptions,
+    pub multimodal: MultimodalConfig,
+    pub temperature: f64,
+}
+
+/// Tool: list cameras, take snapshots, describe snapshots with vision.
+pub struct CameraTool {
+    security: Arc<SecurityPolicy>,
+    config: CameraConfig,
+    vision: Option<CameraVision>,
+}
+
+impl CameraTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        config: CameraConfig,
+        vision: Option<CameraVision>,
+    ) -> Self {
+        Self {
+            security,
+            config,
+            vision,
+        }
+    }
+
+    fn workspace_dir(&self) -> &Path {
+        &self.security.workspace_dir
+    }
+
+    fn list(&self) -> anyhow::Result<ToolResult> {
+        let mut cameras: Vec<_> = self
+            .config
+            .cameras
+            .iter()
+            .map(|(name, source)| {
+                json!({
+                    "name": name,
+                    "motion_task": source.motion.is_some(),
+                    "privacy_zones": source.privacy_zones.len(),
+                })
+            })
+            .collect();
+        cameras.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+        let snapshots: Vec<_> = camera::list_snapshots(&self.config, self.workspace_dir())
+            .into_iter()
+            .take(LIST_LIMIT)
+            .map(|(path, modified)| {
+                json!({
+                    "path": path.display().to_string(),
+                    "taken": chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339(),
+                })
+            })
+            .collect();
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&json!({
+                "cameras": cameras,
+                "recent_snapshots": snapshots,
+                "vision": self.vision.is_some(),
+            }))?,
+            error: None,
+        })
+    }
+
+    /// Ask the vision model about a stored snapshot.
+    async fn describe(&self, path: &Path, prompt: &str) -> anyhow::Result<String> {
+        let Some(vision) = &self.vision else {
+            anyhow::bail!(
+                "Snapshot description is disabled; set [camera] allow_vision = true to let the model provider see snapshots"
+            );
+        };
+        let provider = providers::create_provider_with_options(
+            &vision.provider,
+            vision.api_key.as_deref(),
+            &vision.options,
+        )?;
+        if !provider.supports_vision() {
+            anyhow::bail!(
+                "Provider '{}' does not support images; snapshot saved without description",
+                vision.provider
+            );
+        }
+        let messages = [ChatMessage::user(format!(
+            "[IMAGE:{}]\n\n{prompt}",
+            path.display()
+        ))];
+        let prepared =
+            crate::multimodal::prepare_messages_for_provider(&messages, &vision.multimodal).await?;
+        provider
+            .chat_with_history(&prepared.messages, &vision.model, vision.temperature)
+            .await
+    }
+}
+
+#[async_trait]
+impl Tool for CameraTool {
+    fn name(&self) -> &str {
+        "camera"
+    }
+
+    fn description(&self) -> &str {
+        "Camera snapshots. Actions: 'list' (cameras and recent snapshots), 'snapshot' (grab a \
+        frame from a camera and save it in the workspace; set describe=true to have the vision \
+        model describe it), 'describe' (describe an existing snapshot by path, e.g. one from a \
+        motion alert). Privacy zones are always blacked out."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "snapshot", "describe"]
+                },
+                "camera": {
+                    "type": "string",
+                    "description": "Camera name (snapshot)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Snapshot path or file name (describe)"
+                },
+                "describe": {
+                    "type": "boolean",
+                    "description": "Describe the new snapshot with the vision model (snapshot)"
+                },
+                "prompt": {
+                    "type": "string",
+                    "description": "Question for the vision model, e.g. \"Is there a parcel by the door?\""
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let camera = args
+            .get("camera")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        ToolResult {
+            success: true,
+            output: format!("[simulated] camera would run '{action}' (camera: '{camera}')"),
+            error: None,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let str_arg = |key: &str| {
+            args.get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let prompt = str_arg("prompt").unwrap_or(DEFAULT_VISION_PROMPT);
+        let fail = |error: String| {
+            Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            })
+        };
+
+        match str_arg("action").unwrap_or_default() {
+            "list" => self.list(),
+            "snapshot" => {
+                let Some(name) = str_arg("camera") else {
+                    return fail("Missing 'camera' parameter".into());
+                };
+                if let Err(error) = self
+                    .security
+                    .enforce_tool_operation(ToolOperation::Act, "camera")
+                {
+                    return fail(error);
+                }
+                let snapshot = match camera::capture(&self.config, self.workspace_dir(), name).await
+                {
+                    Ok(snapshot) => snapshot,
+                    Err(e) => return fail(format!("{e:#}")),
+                };
+                let mut output = json!({
+                    "camera": name,
+                    "path": snapshot.path.display().to_string(),
+                    "taken": snapshot.captured_at.to_rfc3339(),
+                    "width": snapshot.width,
+                    "height": snapshot.height,
+                });
+                if args
+                    .get("describe")
+                    .and_then(serde_json::Value::as_bool)
+                    .unwrap_or(false)
+                {
+                    output["description"] = match self.describe(&snapshot.path, prompt).await {
+                        Ok(text) => json!(text),
+                        Err(e) => json!(format!("(not described: {e:#})")),
+                    };
+                }
+                Ok(ToolResult {
+                    success: true,
+                    output: serde_json::to_string_pretty(&output)?,
+                    error: None,
+                })
+            }
+            "describe" => {
+                let Some(path) = str_arg("path") else {
+                    return fail("Missing 'path' parameter".into());
+                };
+                let path = match camera::resolve_snapshot(&self.config, self.workspace_dir(), path)
+                {
+                    Ok(path) => path,
+                    Err(e) => return fail(e.to_string()),
+                };
+                match self.describe(&path, prompt).await {
+                    Ok(text) => Ok(ToolResult {
+                        success: true,
+                        output: text,
+                        error: None,
+                    }),
+                    Err(e) => fail(format!("{e:#}")),
+                }
+            }
+            other => fail(format!(
+                "Unknown action '{other}'; use list, snapshot or describe"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    #[tokio::test]
+    async fn snapshot_is_blocked_in_read_only_mode_and_describe_needs_opt_in() {
+        let tmp = tempfile::tempdir().unwrap();
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let tool = CameraTool::new(security, CameraConfig::default(), None);
+
+        let result = tool
+            .execute(json!({"action": "snapshot", "camera": "porch"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+
+        std::fs::create_dir_all(tmp.path().join("camera")).unwrap();
+        std::fs::write(tmp.path().join("camera/porch_1.jpg"), b"jpeg").unwrap();
+        let result = tool
+            .execute(json!({"action": "describe", "path": "porch_1.jpg"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("allow_vision"));
+
+        let listed = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert!(listed.output.contains("porch_1.jpg"));
+    }
+}
@@ -0,0 +1,298 @@
+use super::traits::{Tool, ToolResult};
+use crate::contacts::{Contact, ContactBook};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Look up and maintain the contact book used by `message_contact`.
+pub struct ContactsTool {
+    security: Arc<SecurityPolicy>,
+    workspace_dir: PathBuf,
+    /// Directory holding `config.toml`, where contact approvals are kept.
+    zeroclaw_dir: PathBuf,
+}
+
+impl ContactsTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        workspace_dir: PathBuf,
+        zeroclaw_dir: PathBuf,
+    ) -> Self {
+        Self {
+            security,
+            workspace_dir,
+            zeroclaw_dir,
+        }
+    }
+
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    fn enforce_mutation_allowed(&self, action: &str) -> Option<ToolResult> {
+        if !self.security.can_act() {
+            return Some(Self::failure(format!(
+                "Security policy: read-only mode, cannot perform '{action}'"
+            )));
+        }
+        if !self.security.record_action() {
+            return Some(Self::failure(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+        None
+    }
+
+    fn string_list(args: &serde_json::Value, key: &str) -> Vec<String> {
+        args.get(key)
+            .and_then(serde_json::Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl Tool for ContactsTool {
+    fn name(&self) -> &str {
+        "contacts"
+    }
+
+    fn description(&self) -> &str {
+        "Manage the contact book (names → channel addresses → preferences). \
+        Actions: list, lookup (resolve a name or alias), add (create or update a contact), remove. \
+        Contacts added or re-addressed here stay unapproved until the user approves them."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "lookup", "add", "remove"]
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Contact name or alias (lookup/add/remove)"
+                },
+                "addresses": {
+                    "type": "object",
+                    "description": "Channel → recipient ID, e.g. {\"telegram\": \"123456\"} (add)",
+                    "additionalProperties": { "type": "string" }
+                },
+                "aliases": {
+                    "type": "array",
+                    "items": { "type": "string" }
+                },
+                "preferred_channel": { "type": "string" },
+                "notes": {
+                    "type": "string",
+                    "description": "Preferences to respect when messaging this contact"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        if matches!(action, "add" | "remove") {
+            let name = args
+                .get("name")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default();
+            return ToolResult {
+                success: true,
+                output: format!("[simulated] contacts {action} '{name}'\n(contact book unchanged)"),
+                error: None,
+            };
+        }
+        ToolResult {
+            success: true,
+            output: format!("[simulated] contacts {action}"),
+            error: None,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let book = ContactBook::new(&self.workspace_dir, &self.zeroclaw_dir);
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let name = args
+            .get("name")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+
+        match action {
+            "list" => {
+                let contacts = book.list()?;
+                let output = if contacts.is_empty() {
+                    "No contacts saved.".to_string()
+                } else {
+                    contacts
+                        .iter()
+                        .map(Contact::summary)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+                Ok(ToolResult {
+                    success: true,
+                    output,
+                    error: None,
+                })
+            }
+            "lookup" => {
+                if name.is_empty() {
+                    return Ok(Self::failure("Missing 'name' for lookup"));
+                }
+                match book.find(name) {
+                    Ok(Some(contact)) => Ok(ToolResult {
+                        success: true,
+                        output: contact.summary(),
+                        error: None,
+                    }),
+                    Ok(None) => Ok(Self::failure(format!("No contact named '{name}'"))),
+                    Err(e) => Ok(Self::failure(e.to_string())),
+                }
+            }
+            "add" => {
+                if let Some(blocked) = self.enforce_mutation_allowed("contacts add") {
+                    return Ok(blocked);
+                }
+                let addresses: BTreeMap<String, String> = args
+                    .get("addresses")
+                    .and_then(serde_json::Value::as_object)
+                    .map(|map| {
+                        map.iter()
+                            .filter_map(|(channel, to)| {
+                                to.as_str().map(|to| (channel.clone(), to.to_string()))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let contact = Contact {
+                    name: name.to_string(),
+                    aliases: Self::string_list(&args, "aliases"),
+                    addresses,
+                    preferred_channel: args
+                        .get("preferred_channel")
+                        .and_then(serde_json::Value::as_str)
+                        .map(str::to_string),
+                    notes: args
+                        .get("notes")
+                        .and_then(serde_json::Value::as_str)
+                        .map(str::to_string),
+                    approved: false,
+                };
+                match book.upsert(contact, false) {
+                    Ok(()) => {
+                        let saved = book.find(name)?.map(|c| c.summary()).unwrap_or_default();
+                        Ok(ToolResult {
+                            success: true,
+                            output: format!("Saved contact: {saved}"),
+                            error: None,
+                        })
+                    }
+                    Err(e) => Ok(Self::failure(e.to_string())),
+                }
+            }
+            "remove" => {
+                if name.is_empty() {
+                    return Ok(Self::failure("Missing 'name' for remove"));
+                }
+                if let Some(blocked) = self.enforce_mutation_allowed("contacts remove") {
+                    return Ok(blocked);
+                }
+                if book.remove(name)? {
+                    Ok(ToolResult {
+                        success: true,
+                        output: format!("Removed contact '{name}'"),
+                        error: None,
+                    })
+                } else {
+                    Ok(Self::failure(format!("No contact named '{name}'")))
+                }
+            }
+            other => Ok(Self::failure(format!(
+                "Unknown action '{other}' (expected list, lookup, add or remove)"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn add_then_lookup_marks_agent_contacts_unapproved() {
+        let tmp = TempDir::new().unwrap();
+        let tool = ContactsTool::new(
+            Arc::new(SecurityPolicy::default()),
+            tmp.path().join("workspace"),
+            tmp.path().into(),
+        );
+
+        let added = tool
+            .execute(json!({
+                "action": "add",
+                "name": "Alice",
+                "addresses": {"Telegram": "42"},
+                "notes": "Prefers mornings"
+            }))
+            .await
+            .unwrap();
+        assert!(added.success, "{:?}", added.error);
+
+        let found = tool
+            .execute(json!({"action": "lookup", "name": "alice"}))
+            .await
+            .unwrap();
+        assert!(found.success);
+        assert!(found.output.contains("telegram:42"));
+        assert!(found.output.contains("(unapproved)"));
+        assert!(found.output.contains("Prefers mornings"));
+    }
+
+    #[tokio::test]
+    async fn read_only_mode_blocks_changes() {
+        let tmp = TempDir::new().unwrap();
+        let security = SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        };
+        let tool = ContactsTool::new(
+            Arc::new(security),
+            tmp.path().join("workspace"),
+            tmp.path().into(),
+        );
+        let result = tool
+            .execute(json!({"action": "add", "name": "Bob", "addresses": {"slack": "U1"}}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
@@ -0,0 +1,392 @@
+//! Block-level document model shared by the DOCX and PDF writers and the
+//! DOCX reader. Markdown is parsed into it with `pulldown-cmark` and
+//! rendered back from it.
+
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+/// A run of text with uniform styling.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct Span {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub code: bool,
+    pub link: Option<String>,
+}
+
+impl Span {
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    fn same_style(&self, other: &Self) -> bool {
+        self.bold == other.bold
+            && self.italic == other.italic
+            && self.code == other.code
+            && self.link == other.link
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Block {
+    Heading(u8, Vec<Span>),
+    Paragraph(Vec<Span>),
+    /// `marker` is `•` for bullets, `3.` for ordered items, and empty for
+    /// continuation paragraphs of the same item.
+    ListItem {
+        depth: usize,
+        marker: String,
+        spans: Vec<Span>,
+    },
+    Code(String),
+    Quote(Vec<Span>),
+    Rule,
+    /// Rows of cells; the first row is the header.
+    Table(Vec<Vec<Vec<Span>>>),
+}
+
+pub(super) const BULLET: &str = "•";
+
+/// Plain text of a span list.
+pub(super) fn plain_text(spans: &[Span]) -> String {
+    spans.iter().map(|span| span.text.as_str()).collect()
+}
+
+/// Text of the first heading, used as the document title.
+pub(super) fn first_heading(blocks: &[Block]) -> Option<String> {
+    blocks.iter().find_map(|block| match block {
+        Block::Heading(_, spans) => {
+            Some(plain_text(spans).trim().to_string()).filter(|t| !t.is_empty())
+        }
+        _ => None,
+    })
+}
+
+pub(super) fn markdown_options() -> Options {
+    Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS
+}
+
+#[derive(Default)]
+struct Builder {
+    blocks: Vec<Block>,
+    spans: Vec<Span>,
+    bold: usize,
+    italic: usize,
+    links: Vec<String>,
+    /// Next number for each open list (`None` for bullet lists).
+    lists: Vec<Option<u64>>,
+    /// Marker of the current item, until its first block is emitted.
+    item_marker: Option<String>,
+    in_item: bool,
+    quote: usize,
+    code: Option<String>,
+    table: Option<Vec<Vec<Vec<Span>>>>,
+    row: Vec<Vec<Span>>,
+}
+
+impl Builder {
+    fn push_text(&mut self, text: &str, code: bool) {
+        if text.is_empty() {
+            return;
+        }
+        let span = Span {
+            text: text.to_string(),
+            bold: self.bold > 0,
+            italic: self.italic > 0,
+            code,
+            link: self.links.last().cloned(),
+        };
+        match self.spans.last_mut() {
+            Some(last) if last.same_style(&span) => last.text.push_str(&span.text),
+            _ => self.spans.push(span),
+        }
+    }
+
+    /// Emit the pending inline content as a block fitting the context.
+    fn flush(&mut self) {
+        let spans = trim_spans(std::mem::take(&mut self.spans));
+        if spans.is_empty() {
+            return;
+        }
+        let block = if self.in_item {
+            Block::ListItem {
+                depth: self.lists.len().saturating_sub(1),
+                marker: self.item_marker.take().unwrap_or_default(),
+                spans,
+            }
+        } else if self.quote > 0 {
+            Block::Quote(spans)
+        } else {
+            Block::Paragraph(spans)
+        };
+        self.blocks.push(block);
+    }
+
+    fn event(&mut self, event: Event<'_>) {
+        if let Some(code) = self.code.as_mut() {
+            match event {
+                Event::Text(text) => code.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => {
+                    let code = self.code.take().unwrap_or_default();
+                    self.blocks
+                        .push(Block::Code(code.trim_end_matches('\n').to_string()));
+                }
+                _ => {}
+            }
+            return;
+        }
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.push_text(&text, false),
+            Event::Code(text) => self.push_text(&text, true),
+            Event::InlineHtml(html) => self.push_text(&html, false),
+            Event::SoftBreak => self.push_text(" ", false),
+            Event::HardBreak => self.push_text("\n", false),
+            Event::TaskListMarker(done) => {
+                self.push_text(if done { "[x] " } else { "[ ] " }, false);
+            }
+            Event::Rule => {
+                self.flush();
+                self.blocks.push(Block::Rule);
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Heading { .. } | Tag::BlockQuote(_) | Tag::Table(_) => {
+                self.flush();
+                if matches!(tag, Tag::BlockQuote(_)) {
+                    self.quote += 1;
+                }
+                if matches!(tag, Tag::Table(_)) {
+                    self.table = Some(Vec::new());
+                }
+            }
+            Tag::List(start) => {
+                self.flush();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                self.in_item = true;
+                let marker = match self.lists.last_mut() {
+                    Some(Some(next)) => {
+                        let marker = format!("{next}.");
+                        *next += 1;
+                        marker
+                    }
+                    _ => BULLET.to_string(),
+                };
+                self.item_marker = Some(marker);
+            }
+            Tag::CodeBlock(_) => {
+                self.flush();
+                self.code = Some(String::new());
+            }
+            Tag::Emphasis => self.italic += 1,
+            Tag::Strong => self.bold += 1,
+            Tag::Link { dest_url, .. } => self.links.push(dest_url.to_string()),
+            Tag::TableHead | Tag::TableRow => self.row.clear(),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Heading(level) => {
+                let spans = trim_spans(std::mem::take(&mut self.spans));
+                self.blocks.push(Block::Heading(level as u8, spans));
+            }
+            TagEnd::Paragraph => self.flush(),
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.quote = self.quote.saturating_sub(1);
+            }
+            TagEnd::Item => {
+                self.flush();
+                self.item_marker = None;
+                self.in_item = false;
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+                // Back in the enclosing item, if any.
+                self.in_item = !self.lists.is_empty();
+            }
+            TagEnd::Emphasis => self.italic = self.italic.saturating_sub(1),
+            TagEnd::Strong => self.bold = self.bold.saturating_sub(1),
+            TagEnd::Link => {
+                self.links.pop();
+            }
+            TagEnd::TableCell => {
+                let cell = trim_spans(std::mem::take(&mut self.spans));
+                self.row.push(cell);
+            }
+            TagEnd::TableHead | TagEnd::TableRow => {
+                let row = std::mem::take(&mut self.row);
+                if let Some(table) = self.table.as_mut() {
+                    table.push(row);
+                }
+            }
+            TagEnd::Table => {
+                if let Some(rows) = self.table.take() {
+                    self.blocks.push(Block::Table(rows));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Drop empty spans and surrounding whitespace.
+fn trim_spans(mut spans: Vec<Span>) -> Vec<Span> {
+    if let Some(first) = spans.first_mut() {
+        first.text = first.text.trim_start().to_string();
+    }
+    if let Some(last) = spans.last_mut() {
+        last.text = last.text.trim_end().to_string();
+    }
+    spans.retain(|span| !span.text.is_empty());
+    spans
+}
+
+/// Parse Markdown into blocks.
+pub(super) fn parse_markdown(markdown: &str) -> Vec<Block> {
+    let mut builder = Builder::default();
+    for event in Parser::new_ext(markdown, markdown_options()) {
+        builder.event(event);
+    }
+    builder.flush();
+    builder.blocks
+}
+
+/// Render blocks back to Markdown.
+pub(super) fn to_markdown(blocks: &[Block]) -> String {
+    let mut out = String::new();
+    let mut previous_was_item = false;
+    for block in blocks {
+        let is_item = matches!(block, Block::ListItem { .. });
+        if !out.is_empty() {
+            out.push_str(if is_item && previous_was_item {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+        previous_was_item = is_item;
+        match block {
+            Block::Heading(level, spans) => {
+                out.push_str(&"#".repeat(usize::from((*level).clamp(1, 6))));
+                out.push(' ');
+                out.push_str(&inline_markdown(spans));
+            }
+            Block::Paragraph(spans) => out.push_str(&inline_markdown(spans)),
+            Block::ListItem {
+                depth,
+                marker,
+                spans,
+            } => {
+                out.push_str(&"  ".repeat(*depth));
+                match marker.as_str() {
+                    "" => out.push_str("  "),
+                    BULLET => out.push_str("- "),
+                    marker => {
+                        out.push_str(marker);
+                        out.push(' ');
+                    }
+                }
+                out.push_str(&inline_markdown(spans));
+            }
+            Block::Code(code) => {
+                let fence = if code.contains("```") { "~~~" } else { "```" };
+                out.push_str(fence);
+                out.push('\n');
+                out.push_str(code);
+                out.push('\n');
+                out.push_str(fence);
+            }
+            Block::Quote(spans) => {
+                out.push_str("> ");
+                out.push_str(&inline_markdown(spans).replace('\n', "\n> "));
+            }
+            Block::Rule => out.push_str("---"),
+            Block::Table(rows) => {
+                let columns = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+                for (i, row) in rows.iter().enumerate() {
+                    if i > 0 {
+                        out.push('\n');
+                    }
+                    out.push('|');
+                    for column in 0..columns {
+                        let cell = row
+                            .get(column)
+                            .map(|cell| inline_markdown(cell).replace('|', "\\|"))
+                            .unwrap_or_default();
+                        out.push(' ');
+                        out.push_str(&cell.replace('\n', " "));
+                        out.push_str(" |");
+                    }
+                    if i == 0 {
+                        out.push_str("\n|");
+                        out.push_str(&" --- |".repeat(columns));
+                    }
+                }
+            }
+        }
+    }
+    out.push('\n');
+    out
+}
+
+/// Inline Markdown for a span list, keeping whitespace outside emphasis
+/// markers so `**bold **` never comes out.
+fn inline_markdown(spans: &[Span]) -> String {
+    let mut out = String::new();
+    for span in spans {
+        let text = span.text.as_str();
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            out.push_str(text);
+            continue;
+        }
+        let leading = &text[..text.len() - text.trim_start().len()];
+        let trailing = &text[text.trim_end().len()..];
+        let mut body = if span.code {
+            let ticks = if trimmed.contains('`') { "``" } else { "`" };
+            format!("{ticks}{trimmed}{ticks}")
+        } else {
+            escape_markdown(trimmed)
+        };
+        if span.italic {
+            body = format!("*{body}*");
+        }
+        if span.bold {
+            body = format!("**{body}**");
+        }
+        if let Some(link) = &span.link {
+            body = format!("[{body}]({link})");
+        }
+        out.push_str(leading);
+        out.push_str(&body);
+        out.push_str(trailing);
+    }
+    out.replace('\n', "  \n")
+}
+
+/// Escape characters that would otherwise start Markdown syntax.
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
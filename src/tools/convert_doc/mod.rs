@@ -0,0 +1,527 @@
+mod blocks;
+mod docx;
+mod pdf;
+
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Largest input document accepted (20 MB).
+const MAX_INPUT_BYTES: u64 = 20 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocFormat {
+    Markdown,
+    Html,
+    Docx,
+    Pdf,
+}
+
+impl DocFormat {
+    fn parse(name: &str) -> Option<Self> {
+        match name
+            .trim()
+            .trim_start_matches('.')
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "md" | "markdown" => Some(Self::Markdown),
+            "html" | "htm" => Some(Self::Html),
+            "docx" => Some(Self::Docx),
+            "pdf" => Some(Self::Pdf),
+            _ => None,
+        }
+    }
+
+    fn from_path(path: &str) -> Option<Self> {
+        Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::parse)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::Html => "html",
+            Self::Docx => "docx",
+            Self::Pdf => "pdf",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            other => other.name(),
+        }
+    }
+}
+
+/// Convert documents between Markdown, HTML, DOCX and PDF inside the
+/// workspace, so reports can be handed to people in the format they expect.
+///
+/// Conversion pivots through Markdown and is pure Rust: DOCX and PDF are
+/// written directly (standard PDF fonts, nothing embedded) and HTML is
+/// rendered with `pulldown-cmark`. Reading PDFs needs the `rag-pdf` build
+/// feature. The result tells the agent how to attach the file on channels
+/// that support `[DOCUMENT:...]` markers.
+pub struct ConvertDocTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl ConvertDocTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+
+    /// Resolve a workspace path for reading.
+    async fn resolve_input(&self, path: &str) -> Result<PathBuf, String> {
+        if !self.security.is_path_allowed(path) {
+            return Err(format!("Path not allowed by security policy: {path}"));
+        }
+        let resolved = tokio::fs::canonicalize(self.security.workspace_dir.join(path))
+            .await
+            .map_err(|e| format!("Failed to resolve input path: {e}"))?;
+        if !self.security.is_resolved_path_allowed(&resolved) {
+            return Err(self.security.resolved_path_violation_message(&resolved));
+        }
+        Ok(resolved)
+    }
+
+    /// Resolve a workspace path for writing, creating its parent directory.
+    async fn resolve_output(&self, path: &str) -> Result<PathBuf, String> {
+        if !self.security.is_path_allowed(path) {
+            return Err(format!("Path not allowed by security policy: {path}"));
+        }
+        let full_path = self.security.workspace_dir.join(path);
+        let (Some(parent), Some(file_name)) = (full_path.parent(), full_path.file_name()) else {
+            return Err("Invalid output path".into());
+        };
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create output directory: {e}"))?;
+        // Resolve the parent after creation to block symlink escapes.
+        let resolved_parent = tokio::fs::canonicalize(parent)
+            .await
+            .map_err(|e| format!("Failed to resolve output path: {e}"))?;
+        if !self.security.is_resolved_path_allowed(&resolved_parent) {
+            return Err(self
+                .security
+                .resolved_path_violation_message(&resolved_parent));
+        }
+        let resolved = resolved_parent.join(file_name);
+        if let Ok(meta) = tokio::fs::symlink_metadata(&resolved).await {
+            if meta.file_type().is_symlink() {
+                return Err(format!(
+                    "Refusing to write through symlink: {}",
+                    resolved.display()
+                ));
+            }
+        }
+        Ok(resolved)
+    }
+}
+
+/// Read a document into Markdown.
+fn to_markdown(format: DocFormat, bytes: &[u8]) -> anyhow::Result<String> {
+    match format {
+        DocFormat::Markdown => Ok(String::from_utf8_lossy(bytes).into_owned()),
+        DocFormat::Html => {
+            let html = String::from_utf8_lossy(bytes);
+            let (title, markdown) = super::fetch_url::html_to_markdown(&html, "");
+            Ok(match title {
+                Some(title) if !markdown.trim_start().starts_with('#') => {
+                    format!("# {title}\n\n{markdown}")
+                }
+                _ => markdown,
+            })
+        }
+        DocFormat::Docx => Ok(blocks::to_markdown(&docx::read_docx(bytes)?)),
+        DocFormat::Pdf => pdf_text(bytes),
+    }
+}
+
+#[cfg(feature = "rag-pdf")]
+fn pdf_text(bytes: &[u8]) -> anyhow::Result<String> {
+    let text = pdf_extract::extract_text_from_mem(bytes)?;
+    if text.trim().is_empty() {
+        anyhow::bail!("PDF contains no extractable text (may be image-only or encrypted)");
+    }
+    Ok(text)
+}
+
+#[cfg(not(feature = "rag-pdf"))]
+fn pdf_text(_bytes: &[u8]) -> anyhow::Result<String> {
+    anyhow::bail!(
+        "Reading PDF requires the 'rag-pdf' build feature. \
+         Rebuild with: cargo build --features rag-pdf"
+    )
+}
+
+/// Render Markdown as a standalone HTML page.
+fn render_html(markdown: &str, title: &str) -> String {
+    let mut body = String::new();
+    pulldown_cmark::html::push_html(
+        &mut body,
+        pulldown_cmark::Parser::new_ext(markdown, blocks::markdown_options()),
+    );
+    let title = title
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<style>\n\
+         body {{ font-family: -apple-system, \"Segoe UI\", Helvetica, Arial, sans-serif; line-height: 1.5; \
+         max-width: 46em; margin: 2em auto; padding: 0 1em; color: #222; }}\n\
+         pre, code {{ font-family: Consolas, Menlo, monospace; background: #f2f2f2; }}\n\
+         pre {{ padding: .75em; overflow-x: auto; }}\n\
+         blockquote {{ margin-left: 0; padding-left: 1em; border-left: 3px solid #bbb; color: #555; }}\n\
+         table {{ border-collapse: collapse; }}\n\
+         th, td {{ border: 1px solid #aaa; padding: .3em .6em; }}\n\
+         th {{ background: #e7e6e6; }}\n\
+         </style>\n</head>\n<body>\n{body}</body>\n</html>\n"
+    )
+}
+
+/// Convert Markdown into the target format.
+fn from_markdown(
+    format: DocFormat,
+    markdown: &str,
+    title: Option<&str>,
+) -> anyhow::Result<Vec<u8>> {
+    let blocks = blocks::parse_markdown(markdown);
+    let title = title
+        .map(str::to_string)
+        .or_else(|| blocks::first_heading(&blocks))
+        .unwrap_or_else(|| "Document".into());
+    match format {
+        DocFormat::Markdown => Ok(markdown.as_bytes().to_vec()),
+        DocFormat::Html => Ok(render_html(markdown, &title).into_bytes()),
+        DocFormat::Docx => docx::write_docx(&blocks, &title),
+        DocFormat::Pdf => pdf::write_pdf(&blocks, &title),
+    }
+}
+
+/// File extension for an output format name (`markdown`, `html`, `docx`,
+/// `pdf` or an extension such as `md`), or `None` if unsupported.
+pub(crate) fn format_extension(format: &str) -> Option<&'static str> {
+    DocFormat::parse(format).map(DocFormat::extension)
+}
+
+/// Render Markdown as `format` for callers outside the tool, such as
+/// scheduled reports.
+pub(crate) fn render_markdown(
+    markdown: &str,
+    format: &str,
+    title: Option<&str>,
+) -> anyhow::Result<Vec<u8>> {
+    let format = DocFormat::parse(format)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported document format '{format}'"))?;
+    from_markdown(format, markdown, title)
+}
+
+fn format_size(bytes: usize) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let kib = bytes as f64 / 1024.0;
+    if kib < 1.0 {
+        format!("{bytes} bytes")
+    } else if kib < 1024.0 {
+        format!("{kib:.1} KB")
+    } else {
+        format!("{:.1} MB", kib / 1024.0)
+    }
+}
+
+fn failure(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for ConvertDocTool {
+    fn name(&self) -> &str {
+        "convert_doc"
+    }
+
+    fn description(&self) -> &str {
+        "Convert a document between Markdown, HTML, DOCX and PDF in the workspace \
+        (e.g. turn a Markdown report into a .docx or .pdf for someone who does not read Markdown). \
+        Formats follow the file extensions. Pass 'content' instead of 'input' to convert Markdown text directly. \
+        The result includes a [DOCUMENT:...] marker for attaching the file in a channel reply."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "input": {
+                    "type": "string",
+                    "description": "Source file (.md, .html, .docx or .pdf), relative to the workspace"
+                },
+                "content": {
+                    "type": "string",
+                    "description": "Markdown text to convert instead of reading 'input'"
+                },
+                "output": {
+                    "type": "string",
+                    "description": "Destination file; its extension picks the format. Defaults to 'input' with the 'to' extension"
+                },
+                "to": {
+                    "type": "string",
+                    "enum": ["markdown", "html", "docx", "pdf"],
+                    "description": "Target format when 'output' has no recognised extension or is omitted"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Document title (defaults to the first heading)"
+                }
+            }
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let input = args
+            .get("input")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("(inline markdown)");
+        let output = args
+            .get("output")
+            .and_then(serde_json::Value::as_str)
+            .or_else(|| args.get("to").and_then(serde_json::Value::as_str))
+            .unwrap_or_default();
+        ToolResult {
+            success: true,
+            output: format!("[simulated] Would convert {input} to {output}"),
+            error: None,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let input = args
+            .get("input")
+            .and_then(serde_json::Value::as_str)
+            .filter(|s| !s.trim().is_empty());
+        let content = args.get("content").and_then(serde_json::Value::as_str);
+        let output = args
+            .get("output")
+            .and_then(serde_json::Value::as_str)
+            .filter(|s| !s.trim().is_empty());
+        let to = args.get("to").and_then(serde_json::Value::as_str);
+        let title = args
+            .get("title")
+            .and_then(serde_json::Value::as_str)
+            .filter(|s| !s.trim().is_empty());
+
+        let source_format = match (input, content) {
+            (Some(_), Some(_)) => return Ok(failure("Pass either 'input' or 'content', not both")),
+            (None, None) => return Ok(failure("Missing 'input' (or 'content')")),
+            (Some(input), None) => match DocFormat::from_path(input) {
+                Some(format) => format,
+                None => {
+                    return Ok(failure(format!(
+                        "Unsupported input type: {input} (expected .md, .html, .docx or .pdf)"
+                    )))
+                }
+            },
+            (None, Some(_)) => DocFormat::Markdown,
+        };
+        let target_format = match output
+            .and_then(DocFormat::from_path)
+            .or_else(|| to.and_then(DocFormat::parse))
+        {
+            Some(format) => format,
+            None => {
+                return Ok(failure(
+                    "Missing target format: give 'output' a .md, .html, .docx or .pdf extension, or pass 'to'",
+                ))
+            }
+        };
+        if source_format == target_format {
+            return Ok(failure(format!(
+                "Input is already {}; nothing to convert",
+                source_format.name()
+            )));
+        }
+        let output = match (output, input) {
+            (Some(output), _) if DocFormat::from_path(output).is_some() => output.to_string(),
+            (Some(output), _) => format!("{output}.{}", target_format.extension()),
+            (None, Some(input)) => Path::new(input)
+                .with_extension(target_format.extension())
+                .to_string_lossy()
+                .into_owned(),
+            (None, None) => return Ok(failure("Missing 'output' path for inline content")),
+        };
+
+        if !self.security.can_act() {
+            return Ok(failure("Action blocked: autonomy is read-only"));
+        }
+        if self.security.is_rate_limited() {
+            return Ok(failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        let source = match (input, content) {
+            (Some(input), _) => {
+                let path = match self.resolve_input(input).await {
+                    Ok(path) => path,
+                    Err(e) => return Ok(failure(e)),
+                };
+                match tokio::fs::metadata(&path).await {
+                    Ok(meta) if meta.len() > MAX_INPUT_BYTES => {
+                        return Ok(failure(format!(
+                            "Input too large: {} bytes (limit: {MAX_INPUT_BYTES} bytes)",
+                            meta.len()
+                        )))
+                    }
+                    Ok(_) => {}
+                    Err(e) => return Ok(failure(format!("Failed to read input: {e}"))),
+                }
+                match tokio::fs::read(&path).await {
+                    Ok(bytes) => bytes,
+                    Err(e) => return Ok(failure(format!("Failed to read input: {e}"))),
+                }
+            }
+            (None, content) => content.unwrap_or_default().as_bytes().to_vec(),
+        };
+        let output_path = match self.resolve_output(&output).await {
+            Ok(path) => path,
+            Err(e) => return Ok(failure(e)),
+        };
+        if !self.security.record_action() {
+            return Ok(failure("Rate limit exceeded: action budget exhausted"));
+        }
+
+        // Parsing and layout are CPU-bound; keep them off the async executor.
+        let title = title.map(str::to_string);
+        let converted = tokio::task::spawn_blocking(move || {
+            let markdown = to_markdown(source_format, &source)?;
+            from_markdown(target_format, &markdown, title.as_deref())
+        })
+        .await;
+        let bytes = match converted {
+            Ok(Ok(bytes)) => bytes,
+            Ok(Err(e)) => return Ok(failure(format!("Conversion failed: {e}"))),
+            Err(e) => return Ok(failure(format!("Conversion task failed: {e}"))),
+        };
+        if let Err(e) = tokio::fs::write(&output_path, &bytes).await {
+            return Ok(failure(format!("Failed to write output: {e}")));
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "Converted {} ({}) to {output} ({}, {}).\n\
+                 To send it in a channel reply, include [DOCUMENT:{}]",
+                input.unwrap_or("inline content"),
+                source_format.name(),
+                target_format.name(),
+                format_size(bytes.len()),
+                output_path.display()
+            ),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn tool(tmp: &TempDir, autonomy: AutonomyLevel) -> ConvertDocTool {
+        ConvertDocTool::new(Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        }))
+    }
+
+    #[tokio::test]
+    async fn converts_markdown_file_to_docx_and_back() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("report.md"),
+            "# Weekly report\n\n- shipped **v2**\n- fixed `login`\n",
+        )
+        .unwrap();
+        let tool = tool(&tmp, AutonomyLevel::Supervised);
+
+        let result = tool
+            .execute(json!({"input": "report.md", "to": "docx"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("[DOCUMENT:"));
+        assert!(result.output.contains("report.docx"));
+
+        let result = tool
+            .execute(json!({"input": "report.docx", "output": "out/report.md"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let markdown = std::fs::read_to_string(tmp.path().join("out/report.md")).unwrap();
+        assert_eq!(
+            markdown,
+            "# Weekly report\n\n- shipped **v2**\n- fixed `login`\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn converts_inline_content_to_html_and_pdf() {
+        let tmp = TempDir::new().unwrap();
+        let tool = tool(&tmp, AutonomyLevel::Supervised);
+
+        let result = tool
+            .execute(json!({"content": "# Notes <1>\n\nHello *world*", "output": "notes.html"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let html = std::fs::read_to_string(tmp.path().join("notes.html")).unwrap();
+        assert!(html.contains("<title>Notes &lt;1&gt;</title>"));
+        assert!(html.contains("<em>world</em>"));
+
+        let result = tool
+            .execute(json!({"input": "notes.html", "output": "notes", "to": "pdf"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let pdf = std::fs::read(tmp.path().join("notes.pdf")).unwrap();
+        assert!(pdf.starts_with(b"%PDF-"));
+    }
+
+    #[tokio::test]
+    async fn rejects_bad_requests_and_read_only_mode() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("a.md"), "# A").unwrap();
+        let tool = tool(&tmp, AutonomyLevel::Supervised);
+
+        for args in [
+            json!({"input": "a.md"}),
+            json!({"input": "a.md", "output": "b.md"}),
+            json!({"input": "a.txt", "to": "pdf"}),
+            json!({"content": "# A", "to": "pdf"}),
+            json!({"input": "../outside.md", "to": "pdf"}),
+        ] {
+            let result = tool.execute(args.clone()).await.unwrap();
+            assert!(!result.success, "{args} should fail");
+        }
+
+        let read_only = super::tests::tool(&tmp, AutonomyLevel::ReadOnly);
+        let result = read_only
+            .execute(json!({"input": "a.md", "to": "pdf"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("read-only"));
+        assert!(!tmp.path().join("a.pdf").exists());
+    }
+}
@@ -0,0 +1,729 @@
+//! Minimal PDF writer.
+//!
+//! Uses the standard Helvetica and Courier fonts, which every PDF reader
+//! provides, so nothing is embedded and no font files are needed. Text is
+//! WinAnsi-encoded; characters outside that set print as `?`.
+
+use super::blocks::{Block, Span};
+use anyhow::Result;
+use flate2::write::ZlibEncoder;
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+const PAGE_WIDTH: f32 = 595.28;
+const PAGE_HEIGHT: f32 = 841.89;
+/// 2 cm on every side.
+const MARGIN: f32 = 56.7;
+const TEXT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+const BODY_SIZE: f32 = 10.5;
+const CODE_SIZE: f32 = 9.0;
+const LIST_INDENT: f32 = 18.0;
+
+/// Advance widths (1/1000 em) of Helvetica for ASCII 32..=126.
+const HELVETICA: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Advance widths (1/1000 em) of Helvetica-Bold for ASCII 32..=126.
+const HELVETICA_BOLD: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722, 722, 667,
+    611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556,
+    278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Font {
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+    Mono,
+}
+
+impl Font {
+    const ALL: [Font; 5] = [
+        Font::Regular,
+        Font::Bold,
+        Font::Italic,
+        Font::BoldItalic,
+        Font::Mono,
+    ];
+
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Italic => "F3",
+            Font::BoldItalic => "F4",
+            Font::Mono => "F5",
+        }
+    }
+
+    fn base_font(self) -> &'static str {
+        match self {
+            Font::Regular => "Helvetica",
+            Font::Bold => "Helvetica-Bold",
+            Font::Italic => "Helvetica-Oblique",
+            Font::BoldItalic => "Helvetica-BoldOblique",
+            Font::Mono => "Courier",
+        }
+    }
+
+    fn styled(bold: bool, italic: bool, code: bool) -> Self {
+        match (code, bold, italic) {
+            (true, _, _) => Font::Mono,
+            (false, true, true) => Font::BoldItalic,
+            (false, true, false) => Font::Bold,
+            (false, false, true) => Font::Italic,
+            (false, false, false) => Font::Regular,
+        }
+    }
+
+    /// Advance width of a WinAnsi byte in 1/1000 em.
+    fn width(self, byte: u8) -> u16 {
+        let table = match self {
+            Font::Mono => return 600,
+            Font::Bold | Font::BoldItalic => &HELVETICA_BOLD,
+            Font::Regular | Font::Italic => &HELVETICA,
+        };
+        match byte {
+            32..=126 => table[usize::from(byte - 32)],
+            0x95 => 350,
+            _ => 556,
+        }
+    }
+
+    fn measure(self, bytes: &[u8], size: f32) -> f32 {
+        let units: u32 = bytes.iter().map(|&b| u32::from(self.width(b))).sum();
+        #[allow(clippy::cast_precision_loss)]
+        let units = units as f32;
+        units * size / 1000.0
+    }
+}
+
+/// Map a character to WinAnsiEncoding.
+fn encode(c: char) -> u8 {
+    match c {
+        ' '..='~' | '\u{a0}'..='\u{ff}' => u8::try_from(u32::from(c)).unwrap_or(b'?'),
+        '\t' => b' ',
+        '€' => 0x80,
+        '‚' => 0x82,
+        '„' => 0x84,
+        '…' => 0x85,
+        '†' => 0x86,
+        '‡' => 0x87,
+        '‰' => 0x89,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '™' => 0x99,
+        _ => b'?',
+    }
+}
+
+fn encode_str(text: &str) -> Vec<u8> {
+    text.chars().map(encode).collect()
+}
+
+/// PDF literal string, parentheses included.
+fn literal(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len() + 2);
+    out.push(b'(');
+    for &b in bytes {
+        match b {
+            b'(' | b')' | b'\\' => {
+                out.push(b'\\');
+                out.push(b);
+            }
+            b'\r' => out.extend_from_slice(b"\\r"),
+            b'\n' => out.extend_from_slice(b"\\n"),
+            _ => out.push(b),
+        }
+    }
+    out.push(b')');
+    out
+}
+
+#[derive(Debug, Clone)]
+struct Segment {
+    font: Font,
+    link: bool,
+    text: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+struct Line {
+    segments: Vec<Segment>,
+    width: f32,
+}
+
+impl Line {
+    fn push(&mut self, font: Font, link: bool, bytes: &[u8], size: f32) {
+        self.width += font.measure(bytes, size);
+        match self.segments.last_mut() {
+            Some(last) if last.font == font && last.link == link => {
+                last.text.extend_from_slice(bytes);
+            }
+            _ => self.segments.push(Segment {
+                font,
+                link,
+                text: bytes.to_vec(),
+            }),
+        }
+    }
+
+    fn trim_end(&mut self, size: f32) {
+        while let Some(last) = self.segments.last_mut() {
+            while last.text.last() == Some(&b' ') {
+                last.text.pop();
+                self.width -= last.font.measure(b" ", size);
+            }
+            if last.text.is_empty() {
+                self.segments.pop();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+/// Word-wrap styled spans to `max_width`. Links are followed by their URL
+/// in parentheses, since the page cannot be clicked on paper.
+fn wrap(
+    spans: &[Span],
+    force_bold: bool,
+    force_italic: bool,
+    size: f32,
+    max_width: f32,
+) -> Vec<Line> {
+    let mut pieces: Vec<(Font, bool, String)> = Vec::new();
+    for span in spans {
+        let font = Font::styled(
+            span.bold || force_bold,
+            span.italic || force_italic,
+            span.code,
+        );
+        pieces.push((font, span.link.is_some(), span.text.clone()));
+        if let Some(url) = &span.link {
+            if url.trim() != span.text.trim() && !url.starts_with('#') {
+                pieces.push((
+                    Font::styled(force_bold, force_italic, false),
+                    false,
+                    format!(" ({url})"),
+                ));
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut line = Line::default();
+    for (font, link, text) in pieces {
+        for (i, part) in text.split('\n').enumerate() {
+            if i > 0 {
+                line.trim_end(size);
+                lines.push(std::mem::take(&mut line));
+            }
+            for word in split_words(part) {
+                let bytes = encode_str(word);
+                let trimmed_len = bytes.iter().rposition(|&b| b != b' ').map_or(0, |p| p + 1);
+                let word_width = font.measure(&bytes[..trimmed_len], size);
+                if !line.is_empty() && line.width + word_width > max_width {
+                    line.trim_end(size);
+                    lines.push(std::mem::take(&mut line));
+                    if trimmed_len == 0 {
+                        continue;
+                    }
+                }
+                if word_width > max_width {
+                    // A single word wider than the line: break it anywhere.
+                    for &b in &bytes {
+                        let w = font.measure(&[b], size);
+                        if !line.is_empty() && line.width + w > max_width {
+                            lines.push(std::mem::take(&mut line));
+                        }
+                        line.push(font, link, &[b], size);
+                    }
+                } else {
+                    line.push(font, link, &bytes, size);
+                }
+            }
+        }
+    }
+    line.trim_end(size);
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Split text into words that keep their trailing spaces.
+fn split_words(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut in_space = false;
+    for (i, c) in text.char_indices() {
+        let space = c == ' ' || c == '\t';
+        if in_space && !space {
+            words.push(&text[start..i]);
+            start = i;
+        }
+        in_space = space;
+    }
+    if start < text.len() {
+        words.push(&text[start..]);
+    }
+    words
+}
+
+struct PdfBuilder {
+    pages: Vec<Vec<u8>>,
+    content: Vec<u8>,
+    /// Top of the free area on the current page.
+    y: f32,
+}
+
+impl PdfBuilder {
+    fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            content: Vec::new(),
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn at_page_top(&self) -> bool {
+        (self.y - (PAGE_HEIGHT - MARGIN)).abs() < f32::EPSILON
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(std::mem::take(&mut self.content));
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    /// Start a new page unless `height` still fits on this one.
+    fn ensure(&mut self, height: f32) {
+        if self.y - height < MARGIN && !self.at_page_top() {
+            self.new_page();
+        }
+    }
+
+    fn space(&mut self, height: f32) {
+        if !self.at_page_top() {
+            self.y -= height;
+        }
+    }
+
+    fn op(&mut self, op: &str) {
+        self.content.extend_from_slice(op.as_bytes());
+        self.content.push(b'\n');
+    }
+
+    fn draw_text(&mut self, x: f32, baseline: f32, line: &Line, size: f32) {
+        if line.is_empty() {
+            return;
+        }
+        self.op(&format!("BT {x:.2} {baseline:.2} Td"));
+        for segment in &line.segments {
+            if segment.link {
+                self.op("0.02 0.39 0.76 rg");
+            }
+            let mut op = format!("/{} {size:.2} Tf ", segment.font.resource()).into_bytes();
+            op.extend_from_slice(&literal(&segment.text));
+            op.extend_from_slice(b" Tj\n");
+            self.content.extend_from_slice(&op);
+            if segment.link {
+                self.op("0 g");
+            }
+        }
+        self.op("ET");
+    }
+
+    /// Lay out wrapped lines at `x`, breaking pages as needed. `decorate`
+    /// draws behind each line (given its bottom y and height).
+    fn lines(
+        &mut self,
+        x: f32,
+        lines: &[Line],
+        size: f32,
+        leading: f32,
+        mut decorate: impl FnMut(&mut Self, f32, f32),
+    ) {
+        for line in lines {
+            self.ensure(leading);
+            self.y -= leading;
+            decorate(self, self.y, leading);
+            self.draw_text(x, self.y + leading * 0.28, line, size);
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        match block {
+            Block::Heading(level, spans) => {
+                let size = match level {
+                    1 => 20.0,
+                    2 => 16.0,
+                    3 => 13.5,
+                    4 => 12.0,
+                    _ => BODY_SIZE + 0.5,
+                };
+                let leading = size * 1.25;
+                let lines = wrap(spans, true, *level >= 6, size, TEXT_WIDTH);
+                self.space(size * 0.7);
+                // Keep the heading with at least two lines of what follows.
+                #[allow(clippy::cast_precision_loss)]
+                let height = lines.len() as f32 * leading + 2.0 * BODY_SIZE * 1.4;
+                self.ensure(height);
+                self.lines(MARGIN, &lines, size, leading, |_, _, _| {});
+                self.y -= 4.0;
+            }
+            Block::Paragraph(spans) => {
+                let lines = wrap(spans, false, false, BODY_SIZE, TEXT_WIDTH);
+                self.lines(MARGIN, &lines, BODY_SIZE, BODY_SIZE * 1.4, |_, _, _| {});
+                self.y -= 6.0;
+            }
+            Block::ListItem {
+                depth,
+                marker,
+                spans,
+            } => {
+                #[allow(clippy::cast_precision_loss)]
+                let indent = MARGIN + LIST_INDENT * (*depth as f32);
+                let marker_bytes = encode_str(marker);
+                let gap = Font::Regular.measure(&marker_bytes, BODY_SIZE).max(10.0) + 6.0;
+                let leading = BODY_SIZE * 1.4;
+                let lines = wrap(
+                    spans,
+                    false,
+                    false,
+                    BODY_SIZE,
+                    TEXT_WIDTH - (indent - MARGIN) - gap,
+                );
+                let mut first = !marker.is_empty();
+                self.lines(
+                    indent + gap,
+                    &lines,
+                    BODY_SIZE,
+                    leading,
+                    |pdf, bottom, height| {
+                        if std::mem::take(&mut first) {
+                            let mut marker_line = Line::default();
+                            marker_line.push(Font::Regular, false, &marker_bytes, BODY_SIZE);
+                            pdf.draw_text(indent, bottom + height * 0.28, &marker_line, BODY_SIZE);
+                        }
+                    },
+                );
+                self.y -= 2.0;
+            }
+            Block::Code(code) => {
+                let leading = CODE_SIZE * 1.35;
+                let per_char = CODE_SIZE * 0.6;
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let max_chars = ((TEXT_WIDTH - 12.0) / per_char).floor().max(1.0) as usize;
+                let mut lines = Vec::new();
+                for source in code.replace('\t', "    ").split('\n') {
+                    let chars: Vec<char> = source.chars().collect();
+                    if chars.is_empty() {
+                        lines.push(Line::default());
+                    }
+                    for chunk in chars.chunks(max_chars) {
+                        let mut line = Line::default();
+                        let text: String = chunk.iter().collect();
+                        line.push(Font::Mono, false, &encode_str(&text), CODE_SIZE);
+                        lines.push(line);
+                    }
+                }
+                self.space(2.0);
+                self.lines(
+                    MARGIN + 6.0,
+                    &lines,
+                    CODE_SIZE,
+                    leading,
+                    |pdf, bottom, height| {
+                        pdf.op(&format!(
+                            "0.95 g {MARGIN:.2} {bottom:.2} {TEXT_WIDTH:.2} {height:.2} re f 0 g"
+                        ));
+                    },
+                );
+                self.y -= 8.0;
+            }
+            Block::Quote(spans) => {
+                let leading = BODY_SIZE * 1.4;
+                let lines = wrap(spans, false, true, BODY_SIZE, TEXT_WIDTH - 16.0);
+                self.op("0.35 g");
+                self.lines(
+                    MARGIN + 16.0,
+                    &lines,
+                    BODY_SIZE,
+                    leading,
+                    |pdf, bottom, height| {
+                        let top = bottom + height;
+                        pdf.op(&format!(
+                            "0.75 G 2 w {x:.2} {bottom:.2} m {x:.2} {top:.2} l S",
+                            x = MARGIN + 4.0
+                        ));
+                    },
+                );
+                self.op("0 g");
+                self.y -= 6.0;
+            }
+            Block::Rule => {
+                self.ensure(14.0);
+                self.y -= 7.0;
+                let right = PAGE_WIDTH - MARGIN;
+                let y = self.y;
+                self.op(&format!(
+                    "0.65 G 0.5 w {MARGIN:.2} {y:.2} m {right:.2} {y:.2} l S"
+                ));
+                self.y -= 7.0;
+            }
+            Block::Table(rows) => self.table(rows),
+        }
+    }
+
+    fn table(&mut self, rows: &[Vec<Vec<Span>>]) {
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        #[allow(clippy::cast_precision_loss)]
+        let column_width = TEXT_WIDTH / columns as f32;
+        let size = BODY_SIZE - 1.0;
+        let leading = size * 1.35;
+        let padding = 4.0;
+        self.space(2.0);
+        for (i, row) in rows.iter().enumerate() {
+            let cells: Vec<Vec<Line>> = (0..columns)
+                .map(|column| {
+                    let spans = row.get(column).map(Vec::as_slice).unwrap_or_default();
+                    wrap(spans, i == 0, false, size, column_width - 2.0 * padding)
+                })
+                .collect();
+            let line_count = cells.iter().map(Vec::len).max().unwrap_or(1);
+            #[allow(clippy::cast_precision_loss)]
+            let height = line_count as f32 * leading + 2.0 * padding;
+            self.ensure(height);
+            let top = self.y;
+            let bottom = top - height;
+            if i == 0 {
+                self.op(&format!(
+                    "0.91 g {MARGIN:.2} {bottom:.2} {TEXT_WIDTH:.2} {height:.2} re f 0 g"
+                ));
+            }
+            for (column, lines) in cells.iter().enumerate() {
+                #[allow(clippy::cast_precision_loss)]
+                let x = MARGIN + column_width * column as f32;
+                for (n, line) in lines.iter().enumerate() {
+                    #[allow(clippy::cast_precision_loss)]
+                    let baseline = top - padding - leading * (n as f32 + 1.0) + leading * 0.28;
+                    self.draw_text(x + padding, baseline, line, size);
+                }
+                self.op(&format!(
+                    "0.6 G 0.5 w {x:.2} {bottom:.2} {column_width:.2} {height:.2} re S"
+                ));
+            }
+            self.y = bottom;
+        }
+        self.y -= 8.0;
+    }
+
+    fn finish(mut self, title: &str) -> Result<Vec<u8>> {
+        if !self.content.is_empty() || self.pages.is_empty() {
+            self.pages.push(std::mem::take(&mut self.content));
+        }
+        let total = self.pages.len();
+        for (i, page) in self.pages.iter_mut().enumerate() {
+            let label = format!("{} / {total}", i + 1);
+            let width = Font::Regular.measure(label.as_bytes(), 8.0);
+            let x = (PAGE_WIDTH - width) / 2.0;
+            let _ = writeln!(
+                PageOps(page),
+                "0 g BT /F1 8 Tf {x:.2} {:.2} Td ({label}) Tj ET",
+                MARGIN / 2.0
+            );
+        }
+
+        // Objects: 1 catalog, 2 page tree, 3 info, 4.. fonts, then a page
+        // and a content stream per page.
+        let font_base = 4;
+        let page_base = font_base + Font::ALL.len();
+        let mut objects: Vec<Vec<u8>> = Vec::new();
+        let kids: Vec<String> = (0..total)
+            .map(|i| format!("{} 0 R", page_base + 2 * i))
+            .collect();
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        objects.push(
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {total} >>",
+                kids.join(" ")
+            )
+            .into_bytes(),
+        );
+        let mut info = b"<< /Title ".to_vec();
+        info.extend_from_slice(&literal(&encode_str(title)));
+        info.extend_from_slice(
+            format!(
+                " /Producer (ZeroClaw) /CreationDate (D:{}Z) >>",
+                chrono::Utc::now().format("%Y%m%d%H%M%S")
+            )
+            .as_bytes(),
+        );
+        objects.push(info);
+        for font in Font::ALL {
+            objects.push(
+                format!(
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                    font.base_font()
+                )
+                .into_bytes(),
+            );
+        }
+        let mut fonts = String::new();
+        for (i, font) in Font::ALL.iter().enumerate() {
+            let _ = write!(fonts, "/{} {} 0 R ", font.resource(), font_base + i);
+        }
+        for (i, page) in self.pages.iter().enumerate() {
+            let content_id = page_base + 2 * i + 1;
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                     /Resources << /Font << {fonts}>> >> /Contents {content_id} 0 R >>"
+                )
+                .into_bytes(),
+            );
+            let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(page)?;
+            let compressed = encoder.finish()?;
+            let mut stream = format!(
+                "<< /Length {} /Filter /FlateDecode >>\nstream\n",
+                compressed.len()
+            )
+            .into_bytes();
+            stream.extend_from_slice(&compressed);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut out = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+            out.extend_from_slice(object);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+        let xref = out.len();
+        let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(table, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            table,
+            "trailer\n<< /Size {} /Root 1 0 R /Info 3 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        );
+        out.extend_from_slice(table.as_bytes());
+        Ok(out)
+    }
+}
+
+/// `fmt::Write` over a page's content bytes.
+struct PageOps<'a>(&'a mut Vec<u8>);
+
+impl std::fmt::Write for PageOps<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Render blocks to an A4 PDF.
+pub(super) fn write_pdf(blocks: &[Block], title: &str) -> Result<Vec<u8>> {
+    let mut pdf = PdfBuilder::new();
+    for block in blocks {
+        pdf.block(block);
+    }
+    pdf.finish(title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::convert_doc::blocks::parse_markdown;
+
+    #[test]
+    fn wrap_breaks_lines_and_keeps_styles() {
+        let spans = parse_markdown("Plain words then **bold words** and `code` go on and on")
+            .into_iter()
+            .find_map(|block| match block {
+                Block::Paragraph(spans) => Some(spans),
+                _ => None,
+            })
+            .unwrap();
+        let lines = wrap(&spans, false, false, 10.0, 120.0);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| line.width <= 120.0 + 0.01));
+        let fonts: Vec<Font> = lines
+            .iter()
+            .flat_map(|line| line.segments.iter().map(|s| s.font))
+            .collect();
+        assert!(fonts.contains(&Font::Bold) && fonts.contains(&Font::Mono));
+        let text: Vec<u8> = lines
+            .iter()
+            .flat_map(|line| line.segments.iter().flat_map(|s| s.text.clone()))
+            .collect();
+        assert!(!text.starts_with(b" ") && text.contains(&b'P'));
+    }
+
+    #[test]
+    fn write_pdf_produces_a_well_formed_file() {
+        let mut markdown = String::from("# Quarterly (draft) report\n\nIntro — “quoted” text.\n\n");
+        for i in 0..120 {
+            let _ = writeln!(
+                markdown,
+                "- item {i} with a [link](https://example.com/{i})"
+            );
+        }
+        markdown.push_str("\n| A | B |\n| --- | --- |\n| 1 | 2 |\n\n```\ncode\n```\n");
+        let pdf = write_pdf(&parse_markdown(&markdown), "Quarterly (draft)").unwrap();
+
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        let find = |needle: &[u8]| pdf.windows(needle.len()).rposition(|w| w == needle);
+        assert!(find(b"/Title (Quarterly \\(draft\\))").is_some());
+        let text = String::from_utf8_lossy(&pdf);
+        let pages = text.matches("/Type /Page ").count();
+        assert!(pages >= 2, "{pages} pages");
+        assert!(text.contains(&format!("/Count {pages}")));
+
+        // Every xref offset points at the object it names.
+        let tail = find(b"startxref\n").unwrap() + "startxref\n".len();
+        let xref_at: usize = std::str::from_utf8(&pdf[tail..])
+            .unwrap()
+            .lines()
+            .next()
+            .and_then(|n| n.parse().ok())
+            .unwrap();
+        let table = std::str::from_utf8(&pdf[xref_at..]).unwrap();
+        let entries: Vec<usize> = table
+            .lines()
+            .skip(3)
+            .take_while(|line| line.ends_with(" n "))
+            .map(|line| line[..10].parse().unwrap())
+            .collect();
+        assert_eq!(entries.len(), 3 + 5 + 2 * pages);
+        for (i, offset) in entries.iter().enumerate() {
+            assert!(pdf[*offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+    }
+}
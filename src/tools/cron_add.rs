@@ -1,6 +1,7 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::Config;
-use crate::cron::{self, DeliveryConfig, JobType, Schedule, SessionTarget};
+use crate::cron::{self, DeliveryConfig, JobContext, JobType, Schedule, SessionTarget};
+use crate::datetime::{UserClock, UserTimezone};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -45,6 +46,51 @@ impl CronAddTool {
 
         None
     }
+
+    /// Build the schedule from `schedule` or natural-language `when`,
+    /// resolving relative `at` values and defaulting cron `tz` to the
+    /// user's configured timezone.
+    fn resolve_schedule(&self, args: &serde_json::Value) -> Result<Schedule, String> {
+        let timezone = match args.get("tz").and_then(serde_json::Value::as_str) {
+            Some(name) => match name.trim().parse::<chrono_tz::Tz>() {
+                Ok(tz) => UserTimezone::Named(tz),
+                Err(_) => return Err(format!("Invalid IANA timezone: {name}")),
+            },
+            None => UserClock::from_config(&self.config.ui).default,
+        };
+        let resolve = |expression: &str| {
+            timezone
+                .resolve(expression, chrono::Utc::now())
+                .ok_or_else(|| format!("Could not resolve date/time '{expression}'"))
+        };
+
+        let mut raw = match (args.get("schedule"), args.get("when")) {
+            (Some(schedule), _) => schedule.clone(),
+            (None, Some(when)) => {
+                let when = when.as_str().unwrap_or_default();
+                return Ok(Schedule::At { at: resolve(when)? });
+            }
+            (None, None) => return Err("Missing 'schedule' parameter".to_string()),
+        };
+
+        if let Some(object) = raw.as_object_mut() {
+            match object.get("kind").and_then(serde_json::Value::as_str) {
+                Some("at") => {
+                    if let Some(at) = object.get("at").and_then(serde_json::Value::as_str) {
+                        let resolved = resolve(at)?;
+                        object.insert("at".into(), json!(resolved));
+                    }
+                }
+                Some("cron") if object.get("tz").is_none_or(serde_json::Value::is_null) => {
+                    if let Some(name) = timezone.iana_name() {
+                        object.insert("tz".into(), json!(name));
+                    }
+                }
+                _ => {}
+            }
+        }
+        serde_json::from_value::<Schedule>(raw).map_err(|e| format!("Invalid schedule: {e}"))
+    }
 }
 
 #[async_trait]
@@ -64,13 +110,25 @@ impl Tool for CronAddTool {
                 "name": { "type": "string" },
                 "schedule": {
                     "type": "object",
-                    "description": "Schedule object: {kind:'cron',expr,tz?} | {kind:'at',at} | {kind:'every',every_ms}"
+                    "description": "Schedule object: {kind:'cron',expr,tz?} | {kind:'at',at} | {kind:'every',every_ms}. `at` accepts RFC3339 or relative phrases like 'tomorrow at 9'; cron tz defaults to the user's timezone"
+                },
+                "when": {
+                    "type": "string",
+                    "description": "Shorthand for a one-shot schedule in natural language, e.g. 'tomorrow at 9', 'next friday 14:30', 'in 2 hours'"
+                },
+                "tz": {
+                    "type": "string",
+                    "description": "IANA timezone for resolving 'when'/'at' and cron expressions (defaults to the user's configured timezone)"
                 },
                 "job_type": { "type": "string", "enum": ["shell", "agent"] },
                 "command": { "type": "string" },
                 "prompt": { "type": "string" },
                 "session_target": { "type": "string", "enum": ["isolated", "main"] },
                 "model": { "type": "string" },
+                "context": {
+                    "type": "object",
+                    "description": "Agent job isolation: {memory?: 'shared'|'isolated'|'none', tools?: [tool names]}. Omitted fields use the [cron] defaults; empty tools allows all"
+                },
                 "delivery": { "type": "object" },
                 "delete_after_run": { "type": "boolean" },
                 "approved": {
@@ -79,10 +137,30 @@ impl Tool for CronAddTool {
                     "default": false
                 }
             },
-            "required": ["schedule"]
+            "required": []
         })
     }
 
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let schedule = args.get("schedule").cloned().unwrap_or_default();
+        let job_type = args
+            .get("job_type")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("shell");
+        ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&json!({
+                "simulated": true,
+                "id": "sim-00000000",
+                "job_type": job_type,
+                "schedule": schedule,
+                "enabled": true,
+            }))
+            .unwrap_or_default(),
+            error: None,
+        }
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.config.cron.enabled {
             return Ok(ToolResult {
@@ -92,22 +170,13 @@ impl Tool for CronAddTool {
             });
         }
 
-        let schedule = match args.get("schedule") {
-            Some(v) => match serde_json::from_value::<Schedule>(v.clone()) {
-                Ok(schedule) => schedule,
-                Err(e) => {
-                    return Ok(ToolResult {
-                        success: false,
-                        output: String::new(),
-                        error: Some(format!("Invalid schedule: {e}")),
-                    });
-                }
-            },
-            None => {
+        let schedule = match self.resolve_schedule(&args) {
+            Ok(schedule) => schedule,
+            Err(error) => {
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
-                    error: Some("Missing 'schedule' parameter".to_string()),
+                    error: Some(error),
                 });
             }
         };
@@ -204,6 +273,20 @@ impl Tool for CronAddTool {
                     .and_then(serde_json::Value::as_str)
                     .map(str::to_string);
 
+                let context = match args.get("context") {
+                    Some(v) => match serde_json::from_value::<JobContext>(v.clone()) {
+                        Ok(context) => context,
+                        Err(e) => {
+                            return Ok(ToolResult {
+                                success: false,
+                                output: String::new(),
+                                error: Some(format!("Invalid context: {e}")),
+                            });
+                        }
+                    },
+                    None => JobContext::default(),
+                };
+
                 let delivery = match args.get("delivery") {
                     Some(v) => match serde_json::from_value::<DeliveryConfig>(v.clone()) {
                         Ok(cfg) => Some(cfg),
@@ -229,6 +312,7 @@ impl Tool for CronAddTool {
                     prompt,
                     session_target,
                     model,
+                    context,
                     delivery,
                     delete_after_run,
                 )
@@ -469,4 +553,79 @@ mod tests {
             .unwrap_or_default()
             .contains("Missing 'prompt'"));
     }
+
+    #[tokio::test]
+    async fn agent_job_stores_execution_context() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "0 3 * * *" },
+                "prompt": "Clean up old build artifacts",
+                "context": { "memory": "isolated", "tools": ["shell"] }
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let job = &cron::list_jobs(&cfg).unwrap()[0];
+        assert_eq!(
+            job.context.memory,
+            Some(crate::config::CronMemoryScope::Isolated)
+        );
+        assert_eq!(job.context.tools, vec!["shell".to_string()]);
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "0 3 * * *" },
+                "prompt": "Clean up",
+                "context": { "memory": "private" }
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap_or_default().contains("Invalid context"));
+    }
+
+    #[tokio::test]
+    async fn resolves_natural_language_when_in_configured_timezone() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.ui.timezone = Some("Asia/Tokyo".into());
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        let cfg = Arc::new(config);
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+
+        let schedule = tool
+            .resolve_schedule(&json!({ "when": "tomorrow at 9" }))
+            .unwrap();
+        let Schedule::At { at } = schedule else {
+            panic!("expected one-shot schedule, got {schedule:?}");
+        };
+        let local = at.with_timezone(&chrono_tz::Asia::Tokyo);
+        assert_eq!(local.format("%H:%M").to_string(), "09:00");
+        assert!(at > chrono::Utc::now());
+
+        let schedule = tool
+            .resolve_schedule(&json!({ "schedule": { "kind": "cron", "expr": "0 9 * * *" } }))
+            .unwrap();
+        assert_eq!(
+            schedule,
+            Schedule::Cron {
+                expr: "0 9 * * *".into(),
+                tz: Some("Asia/Tokyo".into())
+            }
+        );
+
+        let err = tool
+            .resolve_schedule(&json!({ "when": "someday", "tz": "UTC" }))
+            .unwrap_err();
+        assert!(err.contains("Could not resolve"));
+    }
 }
@@ -0,0 +1,578 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{Config, EmailSendConfig};
+use crate::security::{AutonomyLevel, SecurityPolicy};
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use lettre::message::{Mailbox, SinglePart};
+use lettre::{Message, Transport};
+use serde_json::json;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const MAX_RECIPIENTS: usize = 20;
+const MAX_SUBJECT_CHARS: usize = 200;
+const MAX_BODY_BYTES: usize = 100 * 1024;
+const SENDGRID_ENDPOINT: &str = "https://api.sendgrid.com/v3/mail/send";
+
+/// Send plain-text email through SMTP or SendGrid. Recipients must match
+/// `[email_send].allowed_recipients`, sends are capped per rolling day, and
+/// below `full` autonomy every message needs explicit approval of its draft.
+pub struct EmailSendTool {
+    config: Arc<Config>,
+    security: Arc<SecurityPolicy>,
+    /// Serializes read-modify-write of the send log.
+    log_lock: tokio::sync::Mutex<()>,
+}
+
+/// A validated message, ready to format or send.
+#[derive(Debug)]
+struct Draft {
+    from: Mailbox,
+    to: Vec<Mailbox>,
+    cc: Vec<Mailbox>,
+    subject: String,
+    body: String,
+}
+
+impl Draft {
+    fn preview(&self) -> String {
+        let join = |boxes: &[Mailbox]| {
+            boxes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut out = format!("From: {}\nTo: {}\n", self.from, join(&self.to));
+        if !self.cc.is_empty() {
+            let _ = writeln!(out, "Cc: {}", join(&self.cc));
+        }
+        let _ = write!(out, "Subject: {}\n\n{}", self.subject, self.body);
+        out
+    }
+
+    /// RFC 5322 message with a `Message-ID` in the sender's domain so it
+    /// aligns with the domain a relay signs for (DKIM).
+    fn to_message(&self) -> anyhow::Result<Message> {
+        let mut builder = Message::builder()
+            .from(self.from.clone())
+            .subject(self.subject.clone())
+            .message_id(Some(format!(
+                "<{}@{}>",
+                uuid::Uuid::new_v4(),
+                self.from.email.domain()
+            )))
+            .date_now();
+        for mailbox in &self.to {
+            builder = builder.to(mailbox.clone());
+        }
+        for mailbox in &self.cc {
+            builder = builder.cc(mailbox.clone());
+        }
+        Ok(builder.singlepart(SinglePart::plain(self.body.clone()))?)
+    }
+}
+
+impl EmailSendTool {
+    pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            config,
+            security,
+            log_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    fn settings(&self) -> &EmailSendConfig {
+        &self.config.email_send
+    }
+
+    /// Kept next to `config.toml`, out of reach of the agent's workspace
+    /// tools, so the agent cannot reset its own cap.
+    fn log_path(&self) -> PathBuf {
+        self.config
+            .config_path
+            .parent()
+            .unwrap_or(&self.config.workspace_dir)
+            .join("email_send_log.json")
+    }
+
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    fn sender(&self) -> anyhow::Result<Mailbox> {
+        let raw = self
+            .settings()
+            .from_address
+            .clone()
+            .filter(|from| !from.trim().is_empty())
+            .or_else(|| {
+                self.config
+                    .channels_config
+                    .email
+                    .as_ref()
+                    .map(|email| email.from_address.clone())
+            })
+            .context("No sender configured: set [email_send].from_address")?;
+        raw.trim()
+            .parse()
+            .with_context(|| format!("Invalid sender address '{raw}'"))
+    }
+
+    fn parse_recipients(&self, value: Option<&serde_json::Value>) -> anyhow::Result<Vec<Mailbox>> {
+        let raw: Vec<&str> = match value {
+            None | Some(serde_json::Value::Null) => Vec::new(),
+            Some(serde_json::Value::String(s)) => s.split(',').collect(),
+            Some(serde_json::Value::Array(items)) => {
+                items.iter().filter_map(serde_json::Value::as_str).collect()
+            }
+            Some(_) => anyhow::bail!("Recipients must be a string or an array of strings"),
+        };
+        raw.into_iter()
+            .map(str::trim)
+            .filter(|addr| !addr.is_empty())
+            .map(|addr| {
+                let mailbox: Mailbox = addr
+                    .parse()
+                    .with_context(|| format!("Invalid email address '{addr}'"))?;
+                if !recipient_allowed(mailbox.email.as_ref(), &self.settings().allowed_recipients) {
+                    anyhow::bail!(
+                        "Recipient '{}' is not in [email_send].allowed_recipients",
+                        mailbox.email
+                    );
+                }
+                Ok(mailbox)
+            })
+            .collect()
+    }
+
+    fn draft(&self, args: &serde_json::Value) -> anyhow::Result<Draft> {
+        let to = self.parse_recipients(args.get("to"))?;
+        let cc = self.parse_recipients(args.get("cc"))?;
+        if to.is_empty() {
+            anyhow::bail!("At least one 'to' recipient is required");
+        }
+        if to.len() + cc.len() > MAX_RECIPIENTS {
+            anyhow::bail!("Too many recipients (max {MAX_RECIPIENTS})");
+        }
+        let subject = args
+            .get("subject")
+            .and_then(serde_json::Value::as_str)
+            .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+            .unwrap_or_default();
+        if subject.is_empty() || subject.chars().count() > MAX_SUBJECT_CHARS {
+            anyhow::bail!("'subject' must be 1-{MAX_SUBJECT_CHARS} characters");
+        }
+        let body = normalize_body(
+            args.get("body")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default(),
+        );
+        if body.trim().is_empty() {
+            anyhow::bail!("'body' must not be empty");
+        }
+        if body.len() > MAX_BODY_BYTES {
+            anyhow::bail!("'body' exceeds {MAX_BODY_BYTES} bytes");
+        }
+        Ok(Draft {
+            from: self.sender()?,
+            to,
+            cc,
+            subject,
+            body,
+        })
+    }
+
+    /// A missing log is empty; any other read or parse failure is an error,
+    /// since starting over would forget today's sends.
+    fn read_log(&self) -> anyhow::Result<Vec<DateTime<Utc>>> {
+        let path = self.log_path();
+        let raw = match std::fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        serde_json::from_str(&raw).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Sends in the last 24 hours.
+    fn sent_in_window(&self, now: DateTime<Utc>) -> anyhow::Result<usize> {
+        Ok(self
+            .read_log()?
+            .into_iter()
+            .filter(|at| now.signed_duration_since(*at) < Duration::hours(24))
+            .count())
+    }
+
+    fn record_send(&self, now: DateTime<Utc>) -> anyhow::Result<()> {
+        let mut log: Vec<_> = self
+            .read_log()?
+            .into_iter()
+            .filter(|at| now.signed_duration_since(*at) < Duration::hours(24))
+            .collect();
+        log.push(now);
+        let path = self.log_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string(&log)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    async fn deliver(&self, draft: &Draft) -> anyhow::Result<()> {
+        match self
+            .settings()
+            .provider
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "smtp" => {
+                let email = self
+                    .config
+                    .channels_config
+                    .email
+                    .clone()
+                    .context("The smtp provider needs [channels_config.email] SMTP settings")?;
+                let message = draft.to_message()?;
+                tokio::task::spawn_blocking(move || {
+                    crate::channels::email_channel::smtp_transport(&email)?
+                        .send(&message)
+                        .map(|_| ())
+                        .map_err(anyhow::Error::from)
+                })
+                .await?
+            }
+            "sendgrid" => {
+                let api_key = self
+                    .settings()
+                    .api_key
+                    .as_deref()
+                    .filter(|key| !key.trim().is_empty())
+                    .context("The sendgrid provider needs [email_send].api_key")?;
+                let address = |mailbox: &Mailbox| match &mailbox.name {
+                    Some(name) => json!({"email": mailbox.email.to_string(), "name": name}),
+                    None => json!({"email": mailbox.email.to_string()}),
+                };
+                let addresses = |boxes: &[Mailbox]| boxes.iter().map(address).collect::<Vec<_>>();
+                let mut personalization = json!({ "to": addresses(&draft.to) });
+                if !draft.cc.is_empty() {
+                    personalization["cc"] = json!(addresses(&draft.cc));
+                }
+                let payload = json!({
+                    "personalizations": [personalization],
+                    "from": address(&draft.from),
+                    "subject": draft.subject,
+                    "content": [{ "type": "text/plain", "value": draft.body }],
+                });
+                let response = crate::config::build_runtime_proxy_client_with_timeouts(
+                    "tool.email_send",
+                    30,
+                    10,
+                )
+                .post(SENDGRID_ENDPOINT)
+                .bearer_auth(api_key)
+                .json(&payload)
+                .send()
+                .await?;
+                let status = response.status();
+                if !status.is_success() {
+                    let detail = response.text().await.unwrap_or_default();
+                    anyhow::bail!(
+                        "SendGrid returned {status}: {}",
+                        crate::util::truncate_with_ellipsis(&detail, 300)
+                    );
+                }
+                Ok(())
+            }
+            other => {
+                anyhow::bail!("Unknown [email_send].provider '{other}' (expected smtp or sendgrid)")
+            }
+        }
+    }
+}
+
+/// Whether `address` matches an allowlist entry: an exact address, an
+/// `@domain` suffix, or `"*"`.
+fn recipient_allowed(address: &str, allowed: &[String]) -> bool {
+    let address = address.trim().to_ascii_lowercase();
+    allowed.iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        entry == "*" || (entry.starts_with('@') && address.ends_with(&entry)) || entry == address
+    })
+}
+
+/// Normalize line endings and strip trailing whitespace, which relays may
+/// rewrite and so break body signatures.
+fn normalize_body(body: &str) -> String {
+    body.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+#[async_trait]
+impl Tool for EmailSendTool {
+    fn name(&self) -> &str {
+        "email_send"
+    }
+
+    fn description(&self) -> &str {
+        "Send a plain-text email to allowlisted recipients (e.g. 'email me the report'). \
+        Below full autonomy the draft is shown to the user for approval before sending. \
+        Sends are capped per day."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "to": {
+                    "description": "Recipient address, comma-separated addresses, or an array of addresses",
+                    "oneOf": [
+                        { "type": "string" },
+                        { "type": "array", "items": { "type": "string" } }
+                    ]
+                },
+                "cc": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Optional Cc recipients"
+                },
+                "subject": { "type": "string" },
+                "body": {
+                    "type": "string",
+                    "description": "Plain-text body"
+                }
+            },
+            "required": ["to", "subject", "body"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        match self.draft(args) {
+            Ok(draft) => ToolResult {
+                success: true,
+                output: format!("[simulated] Would send email:\n{}", draft.preview()),
+                error: None,
+            },
+            Err(e) => Self::failure(e.to_string()),
+        }
+    }
+
+    fn change_preview(&self, args: &serde_json::Value) -> Option<String> {
+        self.draft(args).ok().map(|draft| draft.preview())
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let draft = match self.draft(&args) {
+            Ok(draft) => draft,
+            Err(e) => return Ok(Self::failure(e.to_string())),
+        };
+
+        if !self.security.can_act() {
+            return Ok(Self::failure("Action blocked: autonomy is read-only"));
+        }
+
+        // Injected by the agent loop after a human approved the call; the
+        // model cannot pass it, since the schema does not declare it.
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if self.security.autonomy != AutonomyLevel::Full && !approved {
+            return Ok(ToolResult {
+                success: false,
+                output: format!("Draft (not sent):\n{}", draft.preview()),
+                error: Some(
+                    "Sending email requires explicit approval of this draft below full autonomy"
+                        .into(),
+                ),
+            });
+        }
+
+        let _guard = self.log_lock.lock().await;
+        let now = Utc::now();
+        let cap = self.settings().max_per_day as usize;
+        let sent = match self.sent_in_window(now) {
+            Ok(sent) => sent,
+            Err(e) => {
+                return Ok(Self::failure(format!(
+                    "Email send log is unreadable, so the daily cap cannot be checked: {e:#}"
+                )))
+            }
+        };
+        if sent >= cap {
+            return Ok(Self::failure(format!(
+                "Daily email cap reached ({cap} per 24h); raise [email_send].max_per_day or try later"
+            )));
+        }
+        if !self.security.record_action() {
+            return Ok(Self::failure(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+
+        if let Err(e) = self.deliver(&draft).await {
+            return Ok(Self::failure(format!("Failed to send email: {e}")));
+        }
+        self.record_send(now)?;
+        let recipients = draft
+            .to
+            .iter()
+            .chain(&draft.cc)
+            .map(|mailbox| mailbox.email.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        Ok(ToolResult {
+            success: true,
+            output: format!("Email '{}' sent to {recipients}", draft.subject),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_tool(tmp: &TempDir, autonomy: AutonomyLevel) -> EmailSendTool {
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            email_send: EmailSendConfig {
+                enabled: true,
+                from_address: Some("Assistant <bot@example.com>".into()),
+                allowed_recipients: vec!["owner@example.org".into(), "@example.com".into()],
+                max_per_day: 1,
+                ..EmailSendConfig::default()
+            },
+            ..Config::default()
+        };
+        let security = SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        };
+        EmailSendTool::new(Arc::new(config), Arc::new(security))
+    }
+
+    #[test]
+    fn recipient_allowlist_matches_addresses_and_domains() {
+        let allowed = vec!["Owner@Example.org".to_string(), "@corp.com".to_string()];
+        assert!(recipient_allowed("owner@example.org", &allowed));
+        assert!(recipient_allowed("alice@corp.com", &allowed));
+        assert!(!recipient_allowed("alice@evilcorp.com", &allowed));
+        assert!(!recipient_allowed("other@example.org", &allowed));
+        assert!(!recipient_allowed("owner@example.org", &[]));
+    }
+
+    #[test]
+    fn message_uses_sender_domain_message_id_and_clean_body() {
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(&tmp, AutonomyLevel::Full);
+        let draft = tool
+            .draft(&json!({
+                "to": "Owner <owner@example.org>, team@example.com",
+                "subject": "Weekly\n report",
+                "body": "Hello  \r\nLine two\t\r\n\r\n"
+            }))
+            .unwrap();
+        assert_eq!(draft.subject, "Weekly report");
+        assert_eq!(draft.body, "Hello\nLine two");
+        assert_eq!(draft.to.len(), 2);
+
+        let formatted = String::from_utf8(draft.to_message().unwrap().formatted()).unwrap();
+        assert!(formatted.contains("@example.com>\r\n"));
+        assert!(formatted.contains("Message-ID: <"));
+        assert!(formatted.contains("Date: "));
+
+        let err = tool
+            .draft(&json!({"to": "stranger@else.net", "subject": "x", "body": "y"}))
+            .unwrap_err();
+        assert!(err.to_string().contains("allowed_recipients"));
+    }
+
+    #[tokio::test]
+    async fn supervised_mode_returns_draft_without_sending() {
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(&tmp, AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({
+                "to": ["owner@example.org"],
+                "subject": "Report",
+                "body": "Numbers attached."
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("requires explicit approval"));
+        assert!(result.output.contains("Subject: Report"));
+        assert!(!tool.log_path().exists());
+    }
+
+    #[tokio::test]
+    async fn daily_cap_blocks_further_sends() {
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(&tmp, AutonomyLevel::Full);
+        tool.record_send(Utc::now() - Duration::hours(1)).unwrap();
+        let result = tool
+            .execute(json!({
+                "to": "owner@example.org",
+                "subject": "Report",
+                "body": "Numbers attached."
+            }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Daily email cap"));
+
+        // Entries older than 24h no longer count.
+        std::fs::write(
+            tool.log_path(),
+            serde_json::to_string(&vec![Utc::now() - Duration::hours(25)]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(tool.sent_in_window(Utc::now()).unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn corrupt_send_log_refuses_instead_of_resetting() {
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(&tmp, AutonomyLevel::Full);
+        assert!(tool.log_path().starts_with(tmp.path()));
+        assert!(!tool.log_path().starts_with(tmp.path().join("workspace")));
+        std::fs::write(tool.log_path(), "{ not json").unwrap();
+
+        let result = tool
+            .execute(json!({
+                "to": "owner@example.org",
+                "subject": "Report",
+                "body": "Numbers attached."
+            }))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("log is unreadable"));
+    }
+
+    #[test]
+    fn schema_leaves_approval_to_the_approval_prompt() {
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(&tmp, AutonomyLevel::Supervised);
+        assert!(tool
+            .parameters_schema()
+            .pointer("/properties/approved")
+            .is_none());
+    }
+}
@@ -0,0 +1,809 @@
+use super::http_request::{
+    extract_host, host_matches_allowlist, is_private_or_local_host, normalize_allowed_domains,
+};
+use super::traits::{Tool, ToolResult};
+use crate::config::FetchUrlConfig;
+use crate::security::{NetworkGuard, SecurityPolicy};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+const USER_AGENT: &str = "zeroclaw-fetch/1.0 (+https://github.com/zeroclaw-labs/zeroclaw)";
+/// Product token matched against `User-agent` lines in robots.txt.
+const ROBOTS_AGENT: &str = "zeroclaw";
+const MAX_REDIRECTS: usize = 5;
+const ROBOTS_TTL: Duration = Duration::from_secs(3600);
+/// Elements removed with their content before conversion.
+const BOILERPLATE_TAGS: &[&str] = &[
+    "script", "style", "noscript", "svg", "iframe", "template", "nav", "header", "footer", "aside",
+    "form", "button",
+];
+
+/// Fetch a page server-side (no JavaScript) and return its main content as
+/// Markdown. Honors the `[fetch_url]` domain allowlist and robots.txt, and
+/// caches results under `<workspace>/state/fetch_cache/`.
+pub struct FetchUrlTool {
+    security: Arc<SecurityPolicy>,
+    config: FetchUrlConfig,
+    cache_dir: PathBuf,
+    robots: Mutex<HashMap<String, (Instant, RobotsRules)>>,
+    network: NetworkGuard,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedPage {
+    url: String,
+    fetched_at: DateTime<Utc>,
+    content: String,
+}
+
+impl FetchUrlTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        mut config: FetchUrlConfig,
+        workspace_dir: &std::path::Path,
+    ) -> Self {
+        config.allowed_domains = normalize_allowed_domains(config.allowed_domains);
+        Self {
+            security,
+            config,
+            cache_dir: workspace_dir.join("state").join("fetch_cache"),
+            robots: Mutex::new(HashMap::new()),
+            network: NetworkGuard::new("fetch_url"),
+        }
+    }
+
+    /// Enforce `[security.network.fetch_url]` and pin resolved addresses.
+    pub fn with_network_guard(mut self, network: NetworkGuard) -> Self {
+        self.network = network;
+        self
+    }
+
+    fn validate_url(&self, raw_url: &str) -> anyhow::Result<String> {
+        let url = raw_url.trim();
+        if url.is_empty() || url.chars().any(char::is_whitespace) {
+            anyhow::bail!("URL cannot be empty or contain whitespace");
+        }
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            anyhow::bail!("Only http:// and https:// URLs are allowed");
+        }
+        if self.config.allowed_domains.is_empty() {
+            anyhow::bail!(
+                "fetch_url is enabled but no allowed_domains are configured. Add [fetch_url].allowed_domains in config.toml"
+            );
+        }
+        let host = extract_host(url)?;
+        if is_private_or_local_host(&host) {
+            anyhow::bail!("Blocked local/private host: {host}");
+        }
+        if !host_matches_allowlist(&host, &self.config.allowed_domains) {
+            anyhow::bail!("Host '{host}' is not in fetch_url.allowed_domains");
+        }
+        Ok(url.to_string())
+    }
+
+    /// Client for one hop, pinned to the checked addresses of `url`'s host.
+    async fn client(&self, url: &str) -> anyhow::Result<reqwest::Client> {
+        let builder = reqwest::Client::builder()
+            .timeout(Duration::from_secs(self.config.timeout_secs.max(1)))
+            .connect_timeout(Duration::from_secs(10))
+            .user_agent(USER_AGENT)
+            .redirect(reqwest::redirect::Policy::none());
+        let builder = self.network.pin(url, builder).await?;
+        let builder = crate::config::apply_runtime_proxy_to_builder(builder, "tool.fetch_url");
+        Ok(builder.build()?)
+    }
+
+    fn cache_path(&self, url: &str) -> PathBuf {
+        let digest = hex::encode(Sha256::digest(url.as_bytes()));
+        self.cache_dir.join(format!("{digest}.json"))
+    }
+
+    fn read_cache(&self, url: &str) -> Option<CachedPage> {
+        if self.config.cache_ttl_secs == 0 {
+            return None;
+        }
+        let raw = std::fs::read_to_string(self.cache_path(url)).ok()?;
+        let page: CachedPage = serde_json::from_str(&raw).ok()?;
+        let age = Utc::now().signed_duration_since(page.fetched_at);
+        let fresh =
+            age.num_seconds() >= 0 && age.num_seconds().unsigned_abs() < self.config.cache_ttl_secs;
+        (fresh && page.url == url).then_some(page)
+    }
+
+    fn write_cache(&self, url: &str, content: &str) {
+        if self.config.cache_ttl_secs == 0 {
+            return;
+        }
+        let page = CachedPage {
+            url: url.to_string(),
+            fetched_at: Utc::now(),
+            content: content.to_string(),
+        };
+        let result = std::fs::create_dir_all(&self.cache_dir).and_then(|()| {
+            std::fs::write(
+                self.cache_path(url),
+                serde_json::to_string(&page).unwrap_or_default(),
+            )
+        });
+        if let Err(e) = result {
+            tracing::warn!("fetch_url: failed to write cache entry: {e}");
+        }
+    }
+
+    /// Whether robots.txt on the URL's origin allows fetching its path.
+    async fn robots_allows(&self, client: &reqwest::Client, url: &str) -> bool {
+        if !self.config.respect_robots {
+            return true;
+        }
+        let (origin, path) = split_origin(url);
+        let cached = self
+            .robots
+            .lock()
+            .get(origin)
+            .filter(|(at, _)| at.elapsed() < ROBOTS_TTL)
+            .map(|(_, rules)| rules.clone());
+        let rules = match cached {
+            Some(rules) => rules,
+            None => {
+                let rules = match client.get(format!("{origin}/robots.txt")).send().await {
+                    Ok(resp) if resp.status().is_success() => {
+                        RobotsRules::parse(&resp.text().await.unwrap_or_default(), ROBOTS_AGENT)
+                    }
+                    Ok(_) => RobotsRules::default(),
+                    Err(e) => {
+                        tracing::debug!("fetch_url: robots.txt unavailable for {origin}: {e}");
+                        RobotsRules::default()
+                    }
+                };
+                self.robots
+                    .lock()
+                    .insert(origin.to_string(), (Instant::now(), rules.clone()));
+                rules
+            }
+        };
+        rules.allows(path)
+    }
+
+    /// Fetch `url`, following redirects that stay within the allowlist.
+    async fn fetch(&self, url: &str) -> anyhow::Result<(String, String)> {
+        let mut current = url.to_string();
+        for _ in 0..=MAX_REDIRECTS {
+            let client = self.client(&current).await?;
+            if !self.robots_allows(&client, &current).await {
+                anyhow::bail!("robots.txt disallows fetching {current}");
+            }
+            let response = client.get(&current).send().await?;
+            let status = response.status();
+            if status.is_redirection() {
+                let location = response
+                    .headers()
+                    .get(reqwest::header::LOCATION)
+                    .and_then(|value| value.to_str().ok())
+                    .ok_or_else(|| anyhow::anyhow!("Redirect ({status}) without Location"))?;
+                let next = resolve_link(&current, location)
+                    .ok_or_else(|| anyhow::anyhow!("Unsupported redirect target: {location}"))?;
+                current = self.validate_url(&next)?;
+                continue;
+            }
+            if !status.is_success() {
+                anyhow::bail!("HTTP {status} for {current}");
+            }
+
+            let content_type = response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("text/html")
+                .to_ascii_lowercase();
+            let body = self.read_body(response).await?;
+            let content = if content_type.contains("html") {
+                let (title, markdown) = html_to_markdown(&body, &current);
+                match title {
+                    Some(title) => format!("# {title}\n\n{markdown}"),
+                    None => markdown,
+                }
+            } else if content_type.starts_with("text/")
+                || content_type.contains("json")
+                || content_type.contains("xml")
+            {
+                body
+            } else {
+                anyhow::bail!("Unsupported content type '{content_type}' (fetch_url handles text and HTML pages)");
+            };
+            return Ok((current, content));
+        }
+        anyhow::bail!("Too many redirects (max {MAX_REDIRECTS})")
+    }
+
+    async fn read_body(&self, mut response: reqwest::Response) -> anyhow::Result<String> {
+        let limit = self.config.max_response_size.max(1);
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            if bytes.len() > limit {
+                anyhow::bail!("Response exceeds fetch_url.max_response_size ({limit} bytes)");
+            }
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    fn format_output(&self, url: &str, content: &str, max_chars: usize, cached: bool) -> String {
+        let source = if cached {
+            format!("Source: {url} (cached)")
+        } else {
+            format!("Source: {url}")
+        };
+        let total = content.chars().count();
+        if total <= max_chars {
+            return format!("{source}\n\n{content}");
+        }
+        let truncated: String = content.chars().take(max_chars).collect();
+        format!("{source}\n\n{truncated}\n\n... [truncated: {max_chars} of {total} characters shown] ...")
+    }
+}
+
+#[async_trait]
+impl Tool for FetchUrlTool {
+    fn name(&self) -> &str {
+        "fetch_url"
+    }
+
+    fn description(&self) -> &str {
+        "Fetch a web page server-side (no JavaScript) and return its main content as Markdown, \
+        with navigation and other boilerplate removed. Faster and cheaper than the browser for static pages. \
+        Allowlisted domains only; honors robots.txt; results are cached."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "HTTP or HTTPS URL of the page"
+                },
+                "max_chars": {
+                    "type": "integer",
+                    "description": "Maximum characters of Markdown to return (capped by config)"
+                },
+                "refresh": {
+                    "type": "boolean",
+                    "description": "Bypass the cache and fetch again",
+                    "default": false
+                }
+            },
+            "required": ["url"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let url = args
+            .get("url")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        ToolResult {
+            success: true,
+            output: format!("[simulated] fetch_url {url}\n(page not fetched)"),
+            error: None,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let url = args
+            .get("url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'url' parameter"))?;
+        let refresh = args
+            .get("refresh")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let max_chars = args
+            .get("max_chars")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|n| usize::try_from(n).ok())
+            .map_or(self.config.max_chars, |n| n.clamp(1, self.config.max_chars));
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: rate limit exceeded".into()),
+            });
+        }
+
+        let url = match self.validate_url(url) {
+            Ok(url) => url,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e.to_string()),
+                })
+            }
+        };
+
+        if !refresh {
+            if let Some(page) = self.read_cache(&url) {
+                return Ok(ToolResult {
+                    success: true,
+                    output: self.format_output(&url, &page.content, max_chars, true),
+                    error: None,
+                });
+            }
+        }
+
+        match self.fetch(&url).await {
+            Ok((final_url, content)) => {
+                self.write_cache(&url, &content);
+                Ok(ToolResult {
+                    success: true,
+                    output: self.format_output(&final_url, &content, max_chars, false),
+                    error: None,
+                })
+            }
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("fetch_url failed: {e}")),
+            }),
+        }
+    }
+}
+
+// ── robots.txt ──────────────────────────────────────────────────
+
+/// Allow/Disallow rules from the robots.txt group that applies to us.
+#[derive(Debug, Clone, Default)]
+struct RobotsRules {
+    /// `(allow, pattern)` pairs.
+    rules: Vec<(bool, String)>,
+}
+
+impl RobotsRules {
+    /// Parse robots.txt, keeping the groups for `agent` when present and the
+    /// `*` groups otherwise.
+    fn parse(text: &str, agent: &str) -> Self {
+        let mut specific = Vec::new();
+        let mut wildcard = Vec::new();
+        let mut group_agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((field, value)) = line.split_once(':') else {
+                continue;
+            };
+            let field = field.trim().to_ascii_lowercase();
+            let value = value.trim();
+            match field.as_str() {
+                "user-agent" => {
+                    if in_rules {
+                        group_agents.clear();
+                        in_rules = false;
+                    }
+                    group_agents.push(value.to_ascii_lowercase());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let rule = (field == "allow", value.to_string());
+                    if group_agents
+                        .iter()
+                        .any(|ua| agent.contains(ua.as_str()) && ua != "*")
+                    {
+                        specific.push(rule);
+                    } else if group_agents.iter().any(|ua| ua == "*") {
+                        wildcard.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            rules: if specific.is_empty() {
+                wildcard
+            } else {
+                specific
+            },
+        }
+    }
+
+    /// Longest matching rule wins; `Allow` wins ties.
+    fn allows(&self, path: &str) -> bool {
+        let mut best: Option<(usize, bool)> = None;
+        for (allow, pattern) in &self.rules {
+            if !robots_pattern_matches(pattern, path) {
+                continue;
+            }
+            let len = pattern.len();
+            match best {
+                Some((best_len, best_allow))
+                    if best_len > len || (best_len == len && best_allow) => {}
+                _ => best = Some((len, *allow)),
+            }
+        }
+        best.is_none_or(|(_, allow)| allow)
+    }
+}
+
+fn robots_pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(stripped) => (stripped, true),
+        None => (pattern, false),
+    };
+    if !pattern.contains('*') {
+        return if anchored {
+            path == pattern
+        } else {
+            path.starts_with(pattern)
+        };
+    }
+    let mut regex = String::from("^");
+    regex.push_str(
+        &pattern
+            .split('*')
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join(".*"),
+    );
+    if anchored {
+        regex.push('$');
+    }
+    Regex::new(&regex).is_ok_and(|re| re.is_match(path))
+}
+
+/// Split a URL into `scheme://authority` and the path (with query).
+fn split_origin(url: &str) -> (&str, &str) {
+    let after_scheme = url.find("://").map_or(0, |i| i + 3);
+    match url[after_scheme..].find(['/', '?', '#']) {
+        Some(i) => {
+            let (origin, rest) = url.split_at(after_scheme + i);
+            let path = rest.split('#').next().unwrap_or(rest);
+            if path.starts_with('/') {
+                (origin, path)
+            } else {
+                (origin, "/")
+            }
+        }
+        None => (url, "/"),
+    }
+}
+
+/// Resolve `href` against `base`; `None` for fragments and non-HTTP schemes.
+fn resolve_link(base: &str, href: &str) -> Option<String> {
+    let href = href.trim();
+    if href.is_empty() || href.starts_with('#') {
+        return None;
+    }
+    if href.starts_with("http://") || href.starts_with("https://") {
+        return Some(href.to_string());
+    }
+    if let Some(rest) = href.strip_prefix("//") {
+        let scheme = base.split("://").next().unwrap_or("https");
+        return Some(format!("{scheme}://{rest}"));
+    }
+    if href.contains(':') && !href.contains('/') || href.split('/').next()?.contains(':') {
+        // mailto:, javascript:, data:, ...
+        return None;
+    }
+    let (origin, path) = split_origin(base);
+    if href.starts_with('/') {
+        return Some(format!("{origin}{href}"));
+    }
+    let path = path.split('?').next().unwrap_or(path);
+    let dir = &path[..=path.rfind('/').unwrap_or(0)];
+    Some(format!("{origin}{dir}{href}"))
+}
+
+// ── HTML → Markdown ─────────────────────────────────────────────
+
+fn cached_regex(cell: &'static OnceLock<Regex>, pattern: &str) -> &'static Regex {
+    cell.get_or_init(|| Regex::new(pattern).expect("static regex"))
+}
+
+/// Convert an HTML page to Markdown, keeping only the main content.
+///
+/// Returns the page title (if any) and the Markdown body.
+pub(crate) fn html_to_markdown(html: &str, base_url: &str) -> (Option<String>, String) {
+    static TITLE: OnceLock<Regex> = OnceLock::new();
+    static COMMENT: OnceLock<Regex> = OnceLock::new();
+    static BOILERPLATE: OnceLock<Vec<Regex>> = OnceLock::new();
+    static MAIN: OnceLock<Vec<Regex>> = OnceLock::new();
+    static PRE: OnceLock<Regex> = OnceLock::new();
+    static HEADING: OnceLock<Regex> = OnceLock::new();
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    static STRONG: OnceLock<Regex> = OnceLock::new();
+    static EM: OnceLock<Regex> = OnceLock::new();
+    static CODE: OnceLock<Regex> = OnceLock::new();
+    static LIST_ITEM: OnceLock<Regex> = OnceLock::new();
+    static BREAK: OnceLock<Regex> = OnceLock::new();
+    static BLOCK: OnceLock<Regex> = OnceLock::new();
+    static CELL: OnceLock<Regex> = OnceLock::new();
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    static SPACE: OnceLock<Regex> = OnceLock::new();
+
+    let title = cached_regex(&TITLE, r"(?is)<title[^>]*>(.*?)</title>")
+        .captures(html)
+        .map(|c| collapse_spaces(&decode_entities(&strip_tags(&c[1]))))
+        .filter(|t| !t.is_empty());
+
+    let mut doc = cached_regex(&COMMENT, r"(?s)<!--.*?-->")
+        .replace_all(html, "")
+        .into_owned();
+    let boilerplate = BOILERPLATE.get_or_init(|| {
+        BOILERPLATE_TAGS
+            .iter()
+            .map(|tag| {
+                Regex::new(&format!(r"(?is)<{tag}\b[^>]*>.*?</{tag}\s*>")).expect("static regex")
+            })
+            .collect()
+    });
+    for re in boilerplate {
+        doc = re.replace_all(&doc, "").into_owned();
+    }
+
+    let main = MAIN.get_or_init(|| {
+        ["article", "main", "body"]
+            .iter()
+            .map(|tag| {
+                Regex::new(&format!(r"(?is)<{tag}\b[^>]*>(.*)</{tag}\s*>")).expect("static regex")
+            })
+            .collect()
+    });
+    if let Some(inner) = main
+        .iter()
+        .find_map(|re| re.captures(&doc).map(|c| c[1].to_string()))
+    {
+        doc = inner;
+    }
+
+    // Preformatted blocks are swapped for placeholders so whitespace
+    // normalization leaves them intact.
+    let mut blocks = Vec::new();
+    doc = cached_regex(&PRE, r"(?is)<pre\b[^>]*>(.*?)</pre\s*>")
+        .replace_all(&doc, |c: &regex::Captures| {
+            let code = decode_entities(&strip_tags(&c[1]));
+            blocks.push(format!("```\n{}\n```", code.trim_matches('\n')));
+            format!("<p>\u{0}{}\u{0}</p>", blocks.len() - 1)
+        })
+        .into_owned();
+
+    // Source line breaks carry no meaning outside <pre>.
+    doc = cached_regex(&SPACE, r"\s+")
+        .replace_all(&doc, " ")
+        .into_owned();
+    doc = cached_regex(&HEADING, r"(?is)<h([1-6])\b[^>]*>(.*?)</h[1-6]\s*>")
+        .replace_all(&doc, |c: &regex::Captures| {
+            let level: usize = c[1].parse().unwrap_or(2);
+            format!(
+                "\n\n{} {}\n\n",
+                "#".repeat(level),
+                collapse_spaces(&strip_tags(&c[2]))
+            )
+        })
+        .into_owned();
+    doc = cached_regex(
+        &LINK,
+        r#"(?is)<a\b[^>]*?href\s*=\s*["']([^"']*)["'][^>]*>(.*?)</a\s*>"#,
+    )
+    .replace_all(&doc, |c: &regex::Captures| {
+        let text = collapse_spaces(&strip_tags(&c[2]));
+        match resolve_link(base_url, &decode_entities(&c[1])) {
+            Some(href) if !text.is_empty() => format!("[{text}]({href})"),
+            _ => text,
+        }
+    })
+    .into_owned();
+    doc = cached_regex(
+        &STRONG,
+        r"(?is)<(?:strong|b)\b[^>]*>(.*?)</(?:strong|b)\s*>",
+    )
+    .replace_all(&doc, "**$1**")
+    .into_owned();
+    doc = cached_regex(&EM, r"(?is)<(?:em|i)\b[^>]*>(.*?)</(?:em|i)\s*>")
+        .replace_all(&doc, "*$1*")
+        .into_owned();
+    doc = cached_regex(&CODE, r"(?is)<code\b[^>]*>(.*?)</code\s*>")
+        .replace_all(&doc, "`$1`")
+        .into_owned();
+    doc = cached_regex(&LIST_ITEM, r"(?i)<li\b[^>]*>")
+        .replace_all(&doc, "\n- ")
+        .into_owned();
+    doc = cached_regex(&BREAK, r"(?i)<br\s*/?>")
+        .replace_all(&doc, "\n")
+        .into_owned();
+    doc = cached_regex(&CELL, r"(?i)</t[dh]\s*>")
+        .replace_all(&doc, " | ")
+        .into_owned();
+    doc = cached_regex(
+        &BLOCK,
+        r"(?i)</?(?:p|div|section|ul|ol|table|tr|blockquote|dl|dt|dd|figure|figcaption|hr)\b[^>]*>",
+    )
+    .replace_all(&doc, "\n\n")
+    .into_owned();
+    doc = cached_regex(&TAG, r"(?s)<[^>]*>")
+        .replace_all(&doc, "")
+        .into_owned();
+    let doc = decode_entities(&doc);
+
+    let mut markdown = String::new();
+    let mut blank = true;
+    for line in doc.lines() {
+        let line = collapse_spaces(line);
+        if line.is_empty() {
+            if !blank {
+                markdown.push('\n');
+                blank = true;
+            }
+            continue;
+        }
+        markdown.push_str(&line);
+        markdown.push('\n');
+        blank = false;
+    }
+    let mut markdown = markdown.trim().to_string();
+    for (i, block) in blocks.iter().enumerate() {
+        markdown = markdown.replace(&format!("\u{0}{i}\u{0}"), block);
+    }
+    (title, markdown)
+}
+
+fn strip_tags(html: &str) -> String {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    cached_regex(&TAG, r"(?s)<[^>]*>")
+        .replace_all(html, "")
+        .into_owned()
+}
+
+fn collapse_spaces(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn decode_entities(text: &str) -> String {
+    static ENTITY: OnceLock<Regex> = OnceLock::new();
+    cached_regex(
+        &ENTITY,
+        r"&(#[0-9]{1,7}|#[xX][0-9a-fA-F]{1,6}|[a-zA-Z]{2,8});",
+    )
+    .replace_all(text, |c: &regex::Captures| {
+        let entity = &c[1];
+        let decoded = if let Some(hex) = entity
+            .strip_prefix("#x")
+            .or_else(|| entity.strip_prefix("#X"))
+        {
+            u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+        } else if let Some(dec) = entity.strip_prefix('#') {
+            dec.parse().ok().and_then(char::from_u32)
+        } else {
+            match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "mdash" => Some('—'),
+                "ndash" => Some('–'),
+                "hellip" => Some('…'),
+                "rsquo" => Some('’'),
+                "lsquo" => Some('‘'),
+                "rdquo" => Some('”'),
+                "ldquo" => Some('“'),
+                "copy" => Some('©'),
+                _ => None,
+            }
+        };
+        decoded.map_or_else(|| c[0].to_string(), |ch| ch.to_string())
+    })
+    .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::SecurityPolicy;
+    use tempfile::TempDir;
+
+    fn tool(tmp: &TempDir, allowed: &[&str]) -> FetchUrlTool {
+        FetchUrlTool::new(
+            Arc::new(SecurityPolicy::default()),
+            FetchUrlConfig {
+                enabled: true,
+                allowed_domains: allowed.iter().map(|d| (*d).to_string()).collect(),
+                ..FetchUrlConfig::default()
+            },
+            tmp.path(),
+        )
+    }
+
+    #[test]
+    fn html_to_markdown_keeps_main_content() {
+        let html = r#"<html><head><title>Release &amp; notes</title><style>p{}</style></head>
+            <body><nav><a href="/">Home</a></nav>
+            <article><h1>Version 2</h1><p>Adds <strong>fast</strong> mode and
+            <a href="/docs/fast">docs</a>.</p><ul><li>One</li><li>Two</li></ul>
+            <pre><code>let x = 1;
+let y = 2;</code></pre></article>
+            <footer>Copyright</footer><script>track()</script></body></html>"#;
+        let (title, markdown) = html_to_markdown(html, "https://example.com/blog/post");
+        assert_eq!(title.as_deref(), Some("Release & notes"));
+        assert!(markdown.starts_with("# Version 2"));
+        assert!(markdown.contains("Adds **fast** mode and [docs](https://example.com/docs/fast)."));
+        assert!(markdown.contains("- One\n- Two"));
+        assert!(markdown.contains("```\nlet x = 1;\nlet y = 2;\n```"));
+        assert!(!markdown.contains("Home"));
+        assert!(!markdown.contains("Copyright"));
+        assert!(!markdown.contains("track()"));
+    }
+
+    #[test]
+    fn resolve_link_handles_relative_forms() {
+        let base = "https://example.com/a/b.html?x=1";
+        assert_eq!(
+            resolve_link(base, "c.html").as_deref(),
+            Some("https://example.com/a/c.html")
+        );
+        assert_eq!(
+            resolve_link(base, "/root").as_deref(),
+            Some("https://example.com/root")
+        );
+        assert_eq!(
+            resolve_link(base, "//cdn.example.com/x").as_deref(),
+            Some("https://cdn.example.com/x")
+        );
+        assert_eq!(resolve_link(base, "#top"), None);
+        assert_eq!(resolve_link(base, "javascript:void(0)"), None);
+        assert_eq!(resolve_link(base, "mailto:a@example.com"), None);
+    }
+
+    #[test]
+    fn robots_rules_prefer_specific_agent_and_longest_match() {
+        let robots = "User-agent: *\nDisallow: /\n\nUser-agent: ZeroClaw\nDisallow: /private\nAllow: /private/public\nDisallow: /*.pdf$\n";
+        let rules = RobotsRules::parse(robots, ROBOTS_AGENT);
+        assert!(rules.allows("/blog"));
+        assert!(!rules.allows("/private/notes"));
+        assert!(rules.allows("/private/public/page"));
+        assert!(!rules.allows("/files/report.pdf"));
+        assert!(rules.allows("/files/report.pdf?download=1"));
+
+        let wildcard_only = RobotsRules::parse("User-agent: *\nDisallow: /admin\n", ROBOTS_AGENT);
+        assert!(!wildcard_only.allows("/admin/users"));
+        assert!(wildcard_only.allows("/"));
+    }
+
+    #[test]
+    fn validate_url_enforces_allowlist_and_blocks_private_hosts() {
+        let tmp = TempDir::new().unwrap();
+        let tool = tool(&tmp, &["example.com"]);
+        assert!(tool.validate_url("https://docs.example.com/page").is_ok());
+        assert!(tool.validate_url("https://evil.com/").is_err());
+        assert!(tool.validate_url("http://127.0.0.1/").is_err());
+        assert!(tool.validate_url("ftp://example.com/").is_err());
+    }
+
+    #[tokio::test]
+    async fn cached_pages_are_served_without_fetching() {
+        let tmp = TempDir::new().unwrap();
+        let tool = tool(&tmp, &["example.com"]);
+        tool.write_cache("https://example.com/page", "# Cached\n\nbody");
+        let result = tool
+            .execute(json!({"url": "https://example.com/page"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("(cached)"));
+        assert!(result.output.contains("# Cached"));
+    }
+}
@@ -51,6 +51,48 @@ impl Tool for FileEditTool {
         })
     }
 
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let path = args
+            .get("path")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let old_len = args
+            .get("old_string")
+            .and_then(serde_json::Value::as_str)
+            .map_or(0, str::len);
+        let new_len = args
+            .get("new_string")
+            .and_then(serde_json::Value::as_str)
+            .map_or(0, str::len);
+        ToolResult {
+            success: true,
+            output: format!(
+                "[simulated] Would replace {old_len} bytes with {new_len} bytes in {path}"
+            ),
+            error: None,
+        }
+    }
+
+    fn change_preview(&self, args: &serde_json::Value) -> Option<String> {
+        let path = args.get("path").and_then(serde_json::Value::as_str)?;
+        let old_string = args.get("old_string").and_then(serde_json::Value::as_str)?;
+        let new_string = args.get("new_string").and_then(serde_json::Value::as_str)?;
+        if old_string.is_empty() || !self.security.is_path_allowed(path) {
+            return None;
+        }
+        let full_path = self.security.workspace_dir.join(path);
+        let resolved_parent = full_path.parent()?.canonicalize().ok()?;
+        if !self.security.is_resolved_path_allowed(&resolved_parent) {
+            return None;
+        }
+        let content = std::fs::read_to_string(&full_path).ok()?;
+        if content.matches(old_string).count() != 1 {
+            return None;
+        }
+        let updated = content.replacen(old_string, new_string, 1);
+        Some(crate::approval::unified_diff(path, &content, &updated))
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         // ── 1. Extract parameters ──────────────────────────────────
         let path = args
@@ -204,12 +246,26 @@ impl Tool for FileEditTool {
 
         let new_content = content.replacen(old_string, new_string, 1);
 
+        let change = match crate::undo::UndoJournal::for_workspace(&self.security.workspace_dir)
+            .record_preimage(self.name(), &resolved_target)
+        {
+            Ok(change) => change,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to journal change for undo: {e}")),
+                });
+            }
+        };
+
         match tokio::fs::write(&resolved_target, &new_content).await {
             Ok(()) => Ok(ToolResult {
                 success: true,
                 output: format!(
-                    "Edited {path}: replaced 1 occurrence ({} bytes)",
-                    new_content.len()
+                    "Edited {path}: replaced 1 occurrence ({} bytes, change {})",
+                    new_content.len(),
+                    change.id
                 ),
                 error: None,
             }),
@@ -42,6 +42,38 @@ impl Tool for FileWriteTool {
         })
     }
 
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let path = args
+            .get("path")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let bytes = args
+            .get("content")
+            .and_then(serde_json::Value::as_str)
+            .map_or(0, str::len);
+        ToolResult {
+            success: true,
+            output: format!("[simulated] Would write {bytes} bytes to {path}"),
+            error: None,
+        }
+    }
+
+    fn change_preview(&self, args: &serde_json::Value) -> Option<String> {
+        let path = args.get("path").and_then(serde_json::Value::as_str)?;
+        let content = args.get("content").and_then(serde_json::Value::as_str)?;
+        if !self.security.is_path_allowed(path) {
+            return None;
+        }
+        let full_path = self.security.workspace_dir.join(path);
+        if let Ok(resolved_parent) = full_path.parent()?.canonicalize() {
+            if !self.security.is_resolved_path_allowed(&resolved_parent) {
+                return None;
+            }
+        }
+        let existing = std::fs::read_to_string(&full_path).unwrap_or_default();
+        Some(crate::approval::unified_diff(path, &existing, content))
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let path = args
             .get("path")
@@ -146,10 +178,27 @@ impl Tool for FileWriteTool {
             });
         }
 
+        let change = match crate::undo::UndoJournal::for_workspace(&self.security.workspace_dir)
+            .record_preimage(self.name(), &resolved_target)
+        {
+            Ok(change) => change,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to journal change for undo: {e}")),
+                });
+            }
+        };
+
         match tokio::fs::write(&resolved_target, content).await {
             Ok(()) => Ok(ToolResult {
                 success: true,
-                output: format!("Written {} bytes to {path}", content.len()),
+                output: format!(
+                    "Written {} bytes to {path} (change {})",
+                    content.len(),
+                    change.id
+                ),
                 error: None,
             }),
             Err(e) => Ok(ToolResult {
@@ -226,6 +275,30 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(&dir).await;
     }
 
+    #[tokio::test]
+    async fn file_write_change_preview_diffs_existing_content() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_file_write_preview");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        tokio::fs::write(dir.join("out.txt"), "keep\nold\n")
+            .await
+            .unwrap();
+
+        let tool = FileWriteTool::new(test_security(dir.clone()));
+        let preview = tool
+            .change_preview(&json!({"path": "out.txt", "content": "keep\nnew\n"}))
+            .unwrap();
+        assert!(preview.starts_with("--- a/out.txt\n+++ b/out.txt\n"));
+        assert!(preview.contains(" keep\n-old\n+new\n"));
+
+        let created = tool
+            .change_preview(&json!({"path": "fresh.txt", "content": "hi\n"}))
+            .unwrap();
+        assert!(created.starts_with("--- /dev/null\n"));
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
     #[tokio::test]
     async fn file_write_creates_parent_dirs() {
         let dir = std::env::temp_dir().join("zeroclaw_test_file_write_nested");
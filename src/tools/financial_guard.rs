@@ -0,0 +1,227 @@
+//! Wrapper that routes financial tool calls through [`FinancialGuard`].
+
+use super::traits::{Tool, ToolResult, ToolSpec};
+use crate::security::financial::{strip_unsigned_args, FinancialGuard, OTP_CODE_ARG};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Financial tool wrapped with the `[security.financial]` guardrails.
+///
+/// Adds `amount`, `currency` and `otp_code` to the tool's schema and only
+/// calls the wrapped tool once the guard authorizes the transaction. The OTP
+/// code is never forwarded.
+pub struct FinancialGuardedTool {
+    inner: Arc<dyn Tool>,
+    guard: Arc<FinancialGuard>,
+    description: String,
+}
+
+impl FinancialGuardedTool {
+    pub fn new(inner: Arc<dyn Tool>, guard: Arc<FinancialGuard>) -> Self {
+        let description = format!(
+            "{} Financial action: pass the 'amount' in {}; new transactions wait out a \
+            cooling-off period and then need a one-time code from the owner as 'otp_code'.",
+            inner.description(),
+            guard.currency()
+        );
+        Self {
+            inner,
+            guard,
+            description,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for FinancialGuardedTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        let mut schema = self.inner.parameters_schema();
+        if let Some(properties) = schema
+            .as_object_mut()
+            .map(|object| object.entry("properties").or_insert_with(|| json!({})))
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            properties
+                .entry("amount")
+                .or_insert_with(|| json!({"type": "number", "description": "Transaction amount"}));
+            properties.entry("currency").or_insert_with(|| {
+                json!({"type": "string", "description": format!("Currency (must be {})", self.guard.currency())})
+            });
+            properties.insert(
+                OTP_CODE_ARG.into(),
+                json!({"type": "string", "description": "One-time code provided by the owner"}),
+            );
+        }
+        if let Some(object) = schema.as_object_mut() {
+            let required = object.entry("required").or_insert_with(|| json!([]));
+            if let Some(required) = required.as_array_mut() {
+                if !required.iter().any(|name| name == "amount") {
+                    required.push(json!("amount"));
+                }
+            }
+        }
+        schema
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let authorization = match self.guard.authorize(self.inner.name(), &args).await {
+            Ok(authorization) => authorization,
+            Err(reason) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(reason),
+                })
+            }
+        };
+
+        let mut forwarded = args.clone();
+        if let Some(object) = forwarded.as_object_mut() {
+            object.remove(OTP_CODE_ARG);
+        }
+        let result = self.inner.execute(forwarded).await;
+        let success = result.as_ref().is_ok_and(|r| r.success);
+        self.guard
+            .complete(&authorization, success, &strip_unsigned_args(&args))
+            .await;
+        result
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        self.inner.simulate(args)
+    }
+
+    fn change_preview(&self, args: &serde_json::Value) -> Option<String> {
+        let amount = args.get("amount").and_then(serde_json::Value::as_f64)?;
+        let transaction = format!(
+            "Financial transaction: {amount:.2} {}",
+            self.guard.currency()
+        );
+        Some(match self.inner.change_preview(args) {
+            Some(preview) => format!("{transaction}\n{preview}"),
+            None => transaction,
+        })
+    }
+
+    fn financial(&self) -> bool {
+        true
+    }
+
+    fn spec(&self) -> ToolSpec {
+        ToolSpec {
+            name: self.name().to_string(),
+            description: self.description().to_string(),
+            parameters: self.parameters_schema(),
+        }
+    }
+}
+
+/// Wrap every tool the guard covers; other tools pass through unchanged.
+pub fn wrap_financial_tools(
+    tools: Vec<Arc<dyn Tool>>,
+    guard: &Arc<FinancialGuard>,
+) -> Vec<Arc<dyn Tool>> {
+    tools
+        .into_iter()
+        .map(|tool| {
+            if guard.covers(tool.name(), tool.financial()) {
+                Arc::new(FinancialGuardedTool::new(tool, Arc::clone(guard))) as Arc<dyn Tool>
+            } else {
+                tool
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FinancialGuardrailConfig;
+    use tempfile::TempDir;
+
+    struct PayTool;
+
+    #[async_trait]
+    impl Tool for PayTool {
+        fn name(&self) -> &str {
+            "pay"
+        }
+
+        fn description(&self) -> &str {
+            "Send a payment"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            json!({"type": "object", "properties": {"to": {"type": "string"}}, "required": ["to"]})
+        }
+
+        async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: args.to_string(),
+                error: None,
+            })
+        }
+
+        fn financial(&self) -> bool {
+            true
+        }
+    }
+
+    struct EchoTool;
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Echo"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            json!({"type": "object"})
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            unreachable!()
+        }
+    }
+
+    #[tokio::test]
+    async fn wraps_flagged_and_listed_tools_and_blocks_unauthorized_calls() {
+        let tmp = TempDir::new().unwrap();
+        let guard = Arc::new(FinancialGuard::new(
+            FinancialGuardrailConfig {
+                tools: vec!["echo".into()],
+                ..FinancialGuardrailConfig::default()
+            },
+            None,
+            tmp.path(),
+        ));
+        let tools = wrap_financial_tools(vec![Arc::new(PayTool), Arc::new(EchoTool)], &guard);
+        assert!(tools.iter().all(|tool| tool.financial()));
+
+        let schema = tools[0].parameters_schema();
+        assert_eq!(schema["properties"]["amount"]["type"], "number");
+        assert!(schema["properties"][OTP_CODE_ARG].is_object());
+        assert_eq!(schema["required"], json!(["to", "amount"]));
+
+        let result = tools[1]
+            .execute(json!({"amount": 5, "otp_code": "123456"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(tmp.path().join("financial-audit.jsonl").exists());
+    }
+}
@@ -0,0 +1,643 @@
+//! Firmware build tool — build embedded projects with pinned toolchains and
+//! flash them through the existing probe-rs / PlatformIO upload flow.
+//!
+//! Compiler output is parsed into structured diagnostics so the agent can
+//! run "fix this firmware bug and flash it" loops. Flashing needs explicit
+//! approval below full autonomy.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::{FirmwareBuildConfig, FirmwareProjectConfig, FirmwareToolchain};
+use crate::security::policy::ToolOperation;
+use crate::security::{AutonomyLevel, SecurityPolicy};
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+/// Environment passed to build toolchains. Secrets are never forwarded.
+const BUILD_ENV_VARS: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LANG",
+    "TMPDIR",
+    "CARGO_HOME",
+    "RUSTUP_HOME",
+    "PLATFORMIO_CORE_DIR",
+];
+/// Diagnostics of each level returned to the agent.
+const MAX_DIAGNOSTICS: usize = 30;
+/// Log lines returned when a step fails without parsable diagnostics.
+const LOG_TAIL_LINES: usize = 40;
+
+/// `file:line:col: error: message` as printed by gcc/clang (PlatformIO).
+static GCC_DIAGNOSTIC: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<file>[^:\s][^:]*):(?P<line>\d+):(?P<col>\d+): (?P<level>fatal error|error|warning): (?P<msg>.+)$")
+        .expect("valid regex")
+});
+
+/// A compiler error or warning.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct Diagnostic {
+    level: String,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<u64>,
+}
+
+/// Diagnostics and build artifact extracted from a build.
+#[derive(Debug, Default)]
+struct BuildReport {
+    errors: Vec<Diagnostic>,
+    warnings: Vec<Diagnostic>,
+    artifact: Option<PathBuf>,
+}
+
+impl BuildReport {
+    fn add(&mut self, diagnostic: Diagnostic) {
+        let bucket = if diagnostic.level == "warning" {
+            &mut self.warnings
+        } else {
+            &mut self.errors
+        };
+        if !bucket.contains(&diagnostic) {
+            bucket.push(diagnostic);
+        }
+    }
+}
+
+/// Parse `cargo build --message-format=json` output.
+fn parse_cargo_messages(stdout: &str) -> BuildReport {
+    let mut report = BuildReport::default();
+    for line in stdout.lines() {
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        match message["reason"].as_str() {
+            Some("compiler-artifact") => {
+                if let Some(executable) = message["executable"].as_str() {
+                    report.artifact = Some(PathBuf::from(executable));
+                }
+            }
+            Some("compiler-message") => {
+                let inner = &message["message"];
+                let level = inner["level"].as_str().unwrap_or_default();
+                let text = inner["message"].as_str().unwrap_or_default();
+                if !matches!(level, "error" | "warning") || text.starts_with("aborting due to") {
+                    continue;
+                }
+                let primary = inner["spans"]
+                    .as_array()
+                    .and_then(|spans| spans.iter().find(|span| span["is_primary"] == true));
+                report.add(Diagnostic {
+                    level: level.to_string(),
+                    message: text.to_string(),
+                    file: primary.and_then(|span| span["file_name"].as_str().map(String::from)),
+                    line: primary.and_then(|span| span["line_start"].as_u64()),
+                    column: primary.and_then(|span| span["column_start"].as_u64()),
+                });
+            }
+            _ => {}
+        }
+    }
+    report
+}
+
+/// Parse gcc-style diagnostics from PlatformIO output.
+fn parse_gcc_diagnostics(output: &str) -> BuildReport {
+    let mut report = BuildReport::default();
+    for line in output.lines() {
+        if let Some(caps) = GCC_DIAGNOSTIC.captures(line.trim_end()) {
+            let level = if &caps["level"] == "warning" {
+                "warning"
+            } else {
+                "error"
+            };
+            report.add(Diagnostic {
+                level: level.to_string(),
+                message: caps["msg"].to_string(),
+                file: Some(caps["file"].to_string()),
+                line: caps["line"].parse().ok(),
+                column: caps["col"].parse().ok(),
+            });
+        }
+    }
+    report
+}
+
+fn log_tail(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(LOG_TAIL_LINES)..].join("\n")
+}
+
+fn combined_output(output: &Output) -> String {
+    format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+/// Tool: build and flash configured firmware projects.
+pub struct FirmwareBuildTool {
+    security: Arc<SecurityPolicy>,
+    settings: FirmwareBuildConfig,
+    workspace_dir: PathBuf,
+}
+
+impl FirmwareBuildTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        settings: FirmwareBuildConfig,
+        workspace_dir: &Path,
+    ) -> Self {
+        Self {
+            security,
+            settings,
+            workspace_dir: workspace_dir.to_path_buf(),
+        }
+    }
+
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    fn project(&self, args: &serde_json::Value) -> Result<(&str, &FirmwareProjectConfig), String> {
+        let name = args
+            .get("project")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        self.settings
+            .projects
+            .get_key_value(name)
+            .map(|(name, project)| (name.as_str(), project))
+            .ok_or_else(|| {
+                let mut known: Vec<_> = self.settings.projects.keys().cloned().collect();
+                known.sort();
+                format!(
+                    "Unknown firmware project '{name}'. Configured: {}",
+                    if known.is_empty() {
+                        "(none)".to_string()
+                    } else {
+                        known.join(", ")
+                    }
+                )
+            })
+    }
+
+    /// Resolve the project directory, refusing paths that escape the workspace.
+    fn project_dir(&self, project: &FirmwareProjectConfig) -> Result<PathBuf, String> {
+        let workspace = self
+            .workspace_dir
+            .canonicalize()
+            .map_err(|e| format!("Workspace is not accessible: {e}"))?;
+        let dir = workspace
+            .join(project.path.trim())
+            .canonicalize()
+            .map_err(|e| format!("Project directory {} not found: {e}", project.path))?;
+        if !dir.starts_with(&workspace) {
+            return Err(format!(
+                "Project directory {} resolves outside the workspace",
+                project.path
+            ));
+        }
+        Ok(dir)
+    }
+
+    async fn run(&self, program: &str, args: &[String], dir: &Path) -> Result<Output, String> {
+        let mut cmd = tokio::process::Command::new(program);
+        cmd.args(args)
+            .current_dir(dir)
+            .env_clear()
+            .kill_on_drop(true);
+        for var in BUILD_ENV_VARS {
+            if let Ok(value) = std::env::var(var) {
+                cmd.env(var, value);
+            }
+        }
+        let timeout = Duration::from_secs(self.settings.timeout_secs.max(1));
+        match tokio::time::timeout(timeout, cmd.output()).await {
+            Ok(Ok(output)) => Ok(output),
+            Ok(Err(e)) => Err(format!("Failed to run {program}: {e}")),
+            Err(_) => Err(format!(
+                "{program} timed out after {}s",
+                self.settings.timeout_secs
+            )),
+        }
+    }
+
+    /// Build the project; returns the report and, on failure, a log tail.
+    async fn build(
+        &self,
+        project: &FirmwareProjectConfig,
+        dir: &Path,
+    ) -> Result<(bool, BuildReport, Option<String>), String> {
+        match project.toolchain {
+            FirmwareToolchain::Cargo => {
+                let mut args = Vec::new();
+                if let Some(pin) = project.rust_toolchain.as_deref() {
+                    args.push(format!("+{}", pin.trim()));
+                } else if !dir.join("rust-toolchain.toml").exists()
+                    && !dir.join("rust-toolchain").exists()
+                {
+                    return Err("Toolchain is not pinned: set rust_toolchain for this project or add rust-toolchain.toml".into());
+                }
+                let target = project
+                    .target
+                    .as_deref()
+                    .ok_or("Cargo projects need a target triple")?;
+                args.extend(
+                    [
+                        "build",
+                        "--release",
+                        "--target",
+                        target,
+                        "--message-format=json",
+                    ]
+                    .map(String::from),
+                );
+                let output = self.run("cargo", &args, dir).await?;
+                let report = parse_cargo_messages(&String::from_utf8_lossy(&output.stdout));
+                let success = output.status.success();
+                let tail = (!success && report.errors.is_empty())
+                    .then(|| log_tail(&String::from_utf8_lossy(&output.stderr)));
+                Ok((success, report, tail))
+            }
+            FirmwareToolchain::Platformio => {
+                let pin = project
+                    .pio_version
+                    .as_deref()
+                    .ok_or("Toolchain is not pinned: set pio_version for this project")?;
+                let version = self.run("pio", &["--version".to_string()], dir).await?;
+                let version = String::from_utf8_lossy(&version.stdout);
+                if !version
+                    .split(|c: char| c.is_whitespace() || c == ',')
+                    .any(|word| word == pin.trim())
+                {
+                    return Err(format!(
+                        "PlatformIO version mismatch: project pins {pin}, found '{}'",
+                        version.trim()
+                    ));
+                }
+                let output = self
+                    .run("pio", &Self::pio_args(project, dir, false), dir)
+                    .await?;
+                let text = combined_output(&output);
+                let report = parse_gcc_diagnostics(&text);
+                let success = output.status.success();
+                let tail = (!success && report.errors.is_empty()).then(|| log_tail(&text));
+                Ok((success, report, tail))
+            }
+        }
+    }
+
+    fn pio_args(project: &FirmwareProjectConfig, dir: &Path, upload: bool) -> Vec<String> {
+        let mut args = vec![
+            "run".to_string(),
+            "-d".to_string(),
+            dir.to_string_lossy().into_owned(),
+        ];
+        if let Some(env) = project.pio_env.as_deref() {
+            args.extend(["-e".to_string(), env.to_string()]);
+        }
+        if upload {
+            args.extend(["-t".to_string(), "upload".to_string()]);
+            if let Some(port) = project.upload_port.as_deref() {
+                args.extend(["--upload-port".to_string(), port.to_string()]);
+            }
+        }
+        args
+    }
+
+    /// Flash a successful build. Returns the flash log on failure.
+    async fn flash(
+        &self,
+        project: &FirmwareProjectConfig,
+        dir: &Path,
+        artifact: Option<&Path>,
+    ) -> Result<(), String> {
+        let steps: Vec<(&str, Vec<String>)> = match project.toolchain {
+            FirmwareToolchain::Cargo => {
+                let chip = project
+                    .chip
+                    .as_deref()
+                    .ok_or("Set chip for this project to flash it with probe-rs")?;
+                let elf = artifact.ok_or("Build produced no executable to flash")?;
+                vec![
+                    (
+                        "probe-rs",
+                        vec![
+                            "download".into(),
+                            "--chip".into(),
+                            chip.into(),
+                            elf.to_string_lossy().into_owned(),
+                        ],
+                    ),
+                    (
+                        "probe-rs",
+                        vec!["reset".into(), "--chip".into(), chip.into()],
+                    ),
+                ]
+            }
+            FirmwareToolchain::Platformio => vec![("pio", Self::pio_args(project, dir, true))],
+        };
+        for (program, args) in steps {
+            let output = self.run(program, &args, dir).await?;
+            if !output.status.success() {
+                return Err(format!(
+                    "{program} {} failed:\n{}",
+                    args.first().map(String::as_str).unwrap_or_default(),
+                    log_tail(&combined_output(&output))
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn flash_target(project: &FirmwareProjectConfig) -> String {
+        match project.toolchain {
+            FirmwareToolchain::Cargo => format!(
+                "chip {} via probe-rs",
+                project.chip.as_deref().unwrap_or("(unset)")
+            ),
+            FirmwareToolchain::Platformio => format!(
+                "PlatformIO env {} (port {})",
+                project.pio_env.as_deref().unwrap_or("default"),
+                project.upload_port.as_deref().unwrap_or("auto")
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for FirmwareBuildTool {
+    fn name(&self) -> &str {
+        "firmware_build"
+    }
+
+    fn description(&self) -> &str {
+        "Build a configured firmware project (cargo or PlatformIO, pinned toolchains) and optionally flash it to the board. \
+        Action 'build' compiles and returns structured errors/warnings (file, line, column, message); \
+        action 'flash' builds, then flashes via probe-rs or PlatformIO upload and needs approval. \
+        Fix errors with file_edit, then build again."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["build", "flash"]
+                },
+                "project": {
+                    "type": "string",
+                    "description": "Project name from [firmware_build.projects]"
+                }
+            },
+            "required": ["action", "project"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("build");
+        match self.project(args) {
+            Ok((name, project)) => ToolResult {
+                success: true,
+                output: format!(
+                    "[simulated] would {action} firmware project '{name}' ({:?}, {})",
+                    project.toolchain, project.path
+                ),
+                error: None,
+            },
+            Err(e) => Self::failure(e),
+        }
+    }
+
+    fn change_preview(&self, args: &serde_json::Value) -> Option<String> {
+        if args.get("action").and_then(serde_json::Value::as_str) != Some("flash") {
+            return None;
+        }
+        let (name, project) = self.project(args).ok()?;
+        Some(format!(
+            "Build and flash firmware project '{name}' ({}) to {}",
+            project.path,
+            Self::flash_target(project)
+        ))
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        if !matches!(action, "build" | "flash") {
+            return Ok(Self::failure(format!(
+                "Unknown action '{action}' (expected build or flash)"
+            )));
+        }
+        let (name, project) = match self.project(&args) {
+            Ok(found) => found,
+            Err(e) => return Ok(Self::failure(e)),
+        };
+
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if action == "flash" && self.security.autonomy != AutonomyLevel::Full && !approved {
+            return Ok(Self::failure(format!(
+                "Flashing '{name}' to {} requires explicit approval below full autonomy",
+                Self::flash_target(project)
+            )));
+        }
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "firmware_build")
+        {
+            return Ok(Self::failure(e));
+        }
+        let dir = match self.project_dir(project) {
+            Ok(dir) => dir,
+            Err(e) => return Ok(Self::failure(e)),
+        };
+
+        let (built, report, log) = match self.build(project, &dir).await {
+            Ok(result) => result,
+            Err(e) => return Ok(Self::failure(e)),
+        };
+        let mut summary = json!({
+            "project": name,
+            "action": action,
+            "build_success": built,
+            "error_count": report.errors.len(),
+            "warning_count": report.warnings.len(),
+            "errors": report.errors.iter().take(MAX_DIAGNOSTICS).collect::<Vec<_>>(),
+            "warnings": report.warnings.iter().take(MAX_DIAGNOSTICS).collect::<Vec<_>>(),
+        });
+        if let Some(artifact) = &report.artifact {
+            summary["artifact"] = json!(artifact);
+        }
+        if let Some(log) = log {
+            summary["log_tail"] = json!(log);
+        }
+
+        let mut error = (!built).then(|| format!("Build of '{name}' failed"));
+        if built && action == "flash" {
+            match self.flash(project, &dir, report.artifact.as_deref()).await {
+                Ok(()) => summary["flashed"] = json!(true),
+                Err(e) => {
+                    summary["flashed"] = json!(false);
+                    error = Some(e);
+                }
+            }
+        }
+
+        Ok(ToolResult {
+            success: error.is_none(),
+            output: serde_json::to_string_pretty(&summary)?,
+            error,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn project(path: &str, toolchain: FirmwareToolchain) -> FirmwareProjectConfig {
+        FirmwareProjectConfig {
+            path: path.into(),
+            toolchain,
+            rust_toolchain: None,
+            target: Some("thumbv7em-none-eabihf".into()),
+            chip: Some("STM32F401RETx".into()),
+            pio_env: Some("uno".into()),
+            pio_version: Some("6.1.16".into()),
+            upload_port: None,
+        }
+    }
+
+    fn tool(tmp: &TempDir, autonomy: AutonomyLevel) -> FirmwareBuildTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let settings = FirmwareBuildConfig {
+            enabled: true,
+            timeout_secs: 30,
+            projects: HashMap::from([
+                (
+                    "blinky".to_string(),
+                    project("blinky", FirmwareToolchain::Cargo),
+                ),
+                (
+                    "escape".to_string(),
+                    project("../outside", FirmwareToolchain::Cargo),
+                ),
+            ]),
+        };
+        FirmwareBuildTool::new(security, settings, tmp.path())
+    }
+
+    #[test]
+    fn cargo_json_messages_become_structured_diagnostics() {
+        let stdout = [
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"cannot find value `led` in this scope","spans":[{"file_name":"src/main.rs","line_start":42,"column_start":9,"is_primary":true}]}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"warning","message":"unused import: `Delay`","spans":[{"file_name":"src/main.rs","line_start":3,"column_start":5,"is_primary":true}]}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"aborting due to 1 previous error","spans":[]}}"#,
+            r#"{"reason":"compiler-artifact","executable":"/ws/blinky/target/thumbv7em-none-eabihf/release/blinky"}"#,
+            "not json",
+        ]
+        .join("\n");
+        let report = parse_cargo_messages(&stdout);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].file.as_deref(), Some("src/main.rs"));
+        assert_eq!(report.errors[0].line, Some(42));
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(
+            report.artifact,
+            Some(PathBuf::from(
+                "/ws/blinky/target/thumbv7em-none-eabihf/release/blinky"
+            ))
+        );
+    }
+
+    #[test]
+    fn schema_leaves_flash_approval_to_the_approval_prompt() {
+        let tmp = TempDir::new().unwrap();
+        let tool = tool(&tmp, AutonomyLevel::Supervised);
+        assert!(tool
+            .parameters_schema()
+            .pointer("/properties/approved")
+            .is_none());
+    }
+
+    #[test]
+    fn gcc_output_is_parsed_and_deduplicated() {
+        let output = "Compiling .pio/build/uno/src/main.cpp.o\n\
+            src/main.cpp:12:5: error: 'digitalWrit' was not declared in this scope\n\
+            src/main.cpp:12:5: error: 'digitalWrit' was not declared in this scope\n\
+            src/sensor.h:3:1: warning: unused variable 'x' [-Wunused-variable]\n\
+            *** [.pio/build/uno/src/main.cpp.o] Error 1";
+        let report = parse_gcc_diagnostics(output);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].column, Some(5));
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn flash_requires_approval_and_paths_stay_in_workspace() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("blinky")).unwrap();
+
+        let supervised = tool(&tmp, AutonomyLevel::Supervised);
+        let blocked = supervised
+            .execute(json!({"action": "flash", "project": "blinky"}))
+            .await
+            .unwrap();
+        assert!(blocked.error.unwrap().contains("approval"));
+        assert!(supervised
+            .change_preview(&json!({"action": "flash", "project": "blinky"}))
+            .unwrap()
+            .contains("STM32F401RETx"));
+
+        let full = tool(&tmp, AutonomyLevel::Full);
+        let unpinned = full
+            .execute(json!({"action": "build", "project": "blinky"}))
+            .await
+            .unwrap();
+        assert!(unpinned.error.unwrap().contains("not pinned"));
+
+        let escape = full
+            .execute(json!({"action": "build", "project": "escape"}))
+            .await
+            .unwrap();
+        assert!(!escape.success);
+
+        let unknown = full
+            .execute(json!({"action": "build", "project": "nope"}))
+            .await
+            .unwrap();
+        assert!(unknown.error.unwrap().contains("blinky"));
+    }
+}
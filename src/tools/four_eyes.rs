@@ -0,0 +1,154 @@
+//! Wrapper that holds high-risk tool calls for a second person's sign-off.
+
+use super::traits::{Tool, ToolResult, ToolSpec};
+use crate::security::four_eyes::FourEyesGuard;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Tool wrapped with the `[security.four_eyes]` second-approval requirement.
+///
+/// Every call is announced to the configured approvers and only runs once
+/// one of them, other than the requester, approves it.
+pub struct FourEyesTool {
+    inner: Arc<dyn Tool>,
+    guard: Arc<FourEyesGuard>,
+    description: String,
+}
+
+impl FourEyesTool {
+    pub fn new(inner: Arc<dyn Tool>, guard: Arc<FourEyesGuard>) -> Self {
+        let description = format!(
+            "{} High-risk action: each call waits up to {}s for a second person to approve it.",
+            inner.description(),
+            guard.timeout_secs()
+        );
+        Self {
+            inner,
+            guard,
+            description,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for FourEyesTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.inner.parameters_schema()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if let Err(reason) = self.guard.authorize(self.inner.name(), &args).await {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(reason),
+            });
+        }
+        self.inner.execute(args).await
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        self.inner.simulate(args)
+    }
+
+    fn change_preview(&self, args: &serde_json::Value) -> Option<String> {
+        self.inner.change_preview(args)
+    }
+
+    fn financial(&self) -> bool {
+        self.inner.financial()
+    }
+
+    fn spec(&self) -> ToolSpec {
+        ToolSpec {
+            name: self.name().to_string(),
+            description: self.description().to_string(),
+            parameters: self.parameters_schema(),
+        }
+    }
+}
+
+/// Wrap every tool the guard covers; other tools pass through unchanged.
+pub fn wrap_four_eyes_tools(
+    tools: Vec<Arc<dyn Tool>>,
+    guard: &Arc<FourEyesGuard>,
+) -> Vec<Arc<dyn Tool>> {
+    tools
+        .into_iter()
+        .map(|tool| {
+            if guard.covers(tool.name()) {
+                Arc::new(FourEyesTool::new(tool, Arc::clone(guard))) as Arc<dyn Tool>
+            } else {
+                tool
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, FourEyesConfig};
+    use serde_json::json;
+
+    struct EchoTool(&'static str);
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn description(&self) -> &str {
+            "Echo"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            json!({"type": "object"})
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: "ran".into(),
+                error: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn only_listed_tools_are_held_for_approval() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.workspace_dir = tmp.path().to_path_buf();
+        config.config_path = tmp.path().join("config.toml");
+        config.security.four_eyes = FourEyesConfig {
+            enabled: true,
+            tools: vec!["deploy".into()],
+            approvers: vec!["cli".into()],
+            timeout_secs: 60,
+        };
+        let guard = Arc::new(FourEyesGuard::from_config(&config));
+        let tools = wrap_four_eyes_tools(
+            vec![Arc::new(EchoTool("deploy")), Arc::new(EchoTool("echo"))],
+            &guard,
+        );
+
+        assert!(tools[0].description().contains("second person"));
+        assert_eq!(tools[1].description(), "Echo");
+
+        // The only approver is the requester itself, so the call is refused.
+        let held = tools[0].execute(json!({})).await.unwrap();
+        assert!(!held.success);
+        assert!(held.error.unwrap().contains("second person"));
+        assert!(tools[1].execute(json!({})).await.unwrap().success);
+    }
+}
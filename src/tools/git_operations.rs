@@ -1,4 +1,5 @@
 use super::traits::{Tool, ToolResult};
+use crate::runtime::remote::{RemoteRuntime, WorkerProgram};
 use crate::security::{AutonomyLevel, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
@@ -9,6 +10,7 @@ use std::sync::Arc;
 pub struct GitOperationsTool {
     security: Arc<SecurityPolicy>,
     workspace_dir: std::path::PathBuf,
+    remote: Option<Arc<RemoteRuntime>>,
 }
 
 impl GitOperationsTool {
@@ -16,9 +18,16 @@ impl GitOperationsTool {
         Self {
             security,
             workspace_dir,
+            remote: None,
         }
     }
 
+    /// Run git on the remote worker; `workspace_dir` is then a remote path.
+    pub fn with_remote(mut self, remote: Arc<RemoteRuntime>) -> Self {
+        self.remote = Some(remote);
+        self
+    }
+
     /// Sanitize git arguments to prevent injection attacks
     fn sanitize_git_args(&self, args: &str) -> anyhow::Result<Vec<String>> {
         let mut result = Vec::new();
@@ -66,11 +75,25 @@ impl GitOperationsTool {
     }
 
     async fn run_git_command(&self, args: &[&str]) -> anyhow::Result<String> {
-        let output = tokio::process::Command::new("git")
-            .args(args)
-            .current_dir(&self.workspace_dir)
-            .output()
-            .await?;
+        let output = match &self.remote {
+            Some(remote) => {
+                let argv = std::iter::once("git")
+                    .chain(args.iter().copied())
+                    .map(str::to_string)
+                    .collect();
+                remote
+                    .command(WorkerProgram::Exec { argv }, &self.workspace_dir)?
+                    .output()
+                    .await?
+            }
+            None => {
+                tokio::process::Command::new("git")
+                    .args(args)
+                    .current_dir(&self.workspace_dir)
+                    .output()
+                    .await?
+            }
+        };
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -480,6 +503,27 @@ impl Tool for GitOperationsTool {
         })
     }
 
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let operation = args
+            .get("operation")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let detail = match operation {
+            "commit" => format!(
+                " with message {:?}",
+                args.get("message")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default()
+            ),
+            _ => String::new(),
+        };
+        ToolResult {
+            success: true,
+            output: format!("[simulated] Would run git {operation}{detail}"),
+            error: None,
+        }
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let operation = match args.get("operation").and_then(|v| v.as_str()) {
             Some(op) => op,
@@ -492,8 +536,9 @@ impl Tool for GitOperationsTool {
             }
         };
 
-        // Check if we're in a git repository
-        if !self.workspace_dir.join(".git").exists() {
+        // Check if we're in a git repository (a remote repository is
+        // reported by git itself)
+        if self.remote.is_none() && !self.workspace_dir.join(".git").exists() {
             // Try to find .git in parent directories
             let mut current_dir = self.workspace_dir.as_path();
             let mut found_git = false;
@@ -0,0 +1,1199 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{GraphqlConfig, GraphqlEndpointConfig};
+use crate::security::policy::ToolOperation;
+use crate::security::{NetworkGuard, SecurityPolicy};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+const USER_AGENT: &str = "zeroclaw-graphql/1.0";
+
+/// Introspection query used for the `schema` action. Type references are
+/// unwrapped four levels deep, which covers `[Type!]!` and friends.
+const INTROSPECTION_QUERY: &str = "query ZeroclawIntrospection { __schema { \
+    queryType { name } mutationType { name } \
+    types { kind name \
+      fields { name args { name type { ...TypeRef } } type { ...TypeRef } } \
+      inputFields { name type { ...TypeRef } } \
+      enumValues { name } } } } \
+    fragment TypeRef on __Type { kind name ofType { kind name ofType { kind name ofType { kind name } } } }";
+
+/// Run queries against the endpoints configured under `[graphql]`.
+///
+/// Persisted queries run by name with JSON variables; ad-hoc queries are
+/// only accepted where the endpoint allows them. Every document is checked
+/// for size, selection depth (fragments included) and operation type before
+/// it is sent, and introspected schemas are cached under
+/// `<workspace>/state/graphql_schema/`.
+pub struct GraphqlTool {
+    security: Arc<SecurityPolicy>,
+    config: GraphqlConfig,
+    cache_dir: PathBuf,
+    network: NetworkGuard,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedSchema {
+    url: String,
+    fetched_at: DateTime<Utc>,
+    schema: Value,
+}
+
+impl GraphqlTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        config: GraphqlConfig,
+        workspace_dir: &std::path::Path,
+    ) -> Self {
+        Self {
+            security,
+            config,
+            cache_dir: workspace_dir.join("state").join("graphql_schema"),
+            network: NetworkGuard::new("graphql"),
+        }
+    }
+
+    /// Enforce `[security.network.graphql]` and pin resolved addresses.
+    pub fn with_network_guard(mut self, network: NetworkGuard) -> Self {
+        self.network = network;
+        self
+    }
+
+    fn endpoint(&self, name: &str) -> anyhow::Result<&GraphqlEndpointConfig> {
+        self.config.endpoints.get(name).ok_or_else(|| {
+            let mut known: Vec<&str> = self.config.endpoints.keys().map(String::as_str).collect();
+            known.sort_unstable();
+            anyhow::anyhow!(
+                "Unknown GraphQL endpoint '{name}'. Configured: {}",
+                if known.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    known.join(", ")
+                }
+            )
+        })
+    }
+
+    fn list(&self) -> String {
+        if self.config.endpoints.is_empty() {
+            return "No GraphQL endpoints configured. Add [graphql.endpoints.<name>] in config.toml"
+                .into();
+        }
+        let mut names: Vec<&String> = self.config.endpoints.keys().collect();
+        names.sort();
+        let mut out = String::new();
+        for name in names {
+            let endpoint = &self.config.endpoints[name];
+            let mut queries: Vec<&str> = endpoint.queries.keys().map(String::as_str).collect();
+            queries.sort_unstable();
+            let _ = writeln!(
+                out,
+                "{name}: {} (ad-hoc: {}, mutations: {})",
+                endpoint.url,
+                if endpoint.allow_ad_hoc { "yes" } else { "no" },
+                if endpoint.allow_mutations {
+                    "yes"
+                } else {
+                    "no"
+                },
+            );
+            for query in queries {
+                let vars = analyze_document(&endpoint.queries[query])
+                    .map(|doc| {
+                        doc.operations
+                            .iter()
+                            .flat_map(|op| op.variables.iter())
+                            .map(|var| {
+                                if var.required {
+                                    format!("${}!", var.name)
+                                } else {
+                                    format!("${}", var.name)
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default();
+                let _ = writeln!(out, "  - {query}({vars})");
+            }
+        }
+        out.trim_end().to_string()
+    }
+
+    /// Pick the query document: a persisted query by name, or an ad-hoc one
+    /// when the endpoint allows it.
+    fn resolve_query<'a>(
+        &self,
+        endpoint_name: &str,
+        endpoint: &'a GraphqlEndpointConfig,
+        query_name: Option<&str>,
+        query: Option<&'a str>,
+    ) -> anyhow::Result<&'a str> {
+        match (query_name, query) {
+            (Some(_), Some(_)) => anyhow::bail!("Pass either 'query_name' or 'query', not both"),
+            (Some(name), None) => endpoint
+                .queries
+                .get(name)
+                .map(String::as_str)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No persisted query '{name}' on endpoint '{endpoint_name}' (use action 'list')"
+                    )
+                }),
+            (None, Some(query)) if endpoint.allow_ad_hoc => Ok(query),
+            (None, Some(_)) => anyhow::bail!(
+                "Endpoint '{endpoint_name}' only runs persisted queries; pass 'query_name' \
+                 (set allow_ad_hoc = true in [graphql.endpoints.{endpoint_name}] to allow others)"
+            ),
+            (None, None) => anyhow::bail!("Missing 'query_name' (or 'query')"),
+        }
+    }
+
+    /// Check a document against the limits and the endpoint's policy and
+    /// return the operation that will run.
+    fn check_document(
+        &self,
+        endpoint_name: &str,
+        endpoint: &GraphqlEndpointConfig,
+        document: &str,
+        operation_name: Option<&str>,
+        variables: &serde_json::Map<String, Value>,
+    ) -> anyhow::Result<Operation> {
+        if document.len() > self.config.max_query_bytes {
+            anyhow::bail!(
+                "Query is {} bytes; graphql.max_query_bytes is {}",
+                document.len(),
+                self.config.max_query_bytes
+            );
+        }
+        let analysis = analyze_document(document)?;
+        let operation = analysis.select(operation_name)?;
+        if operation.depth > self.config.max_depth {
+            anyhow::bail!(
+                "Query depth {} exceeds graphql.max_depth ({})",
+                operation.depth,
+                self.config.max_depth
+            );
+        }
+        match operation.kind {
+            OperationKind::Query => {}
+            OperationKind::Mutation if endpoint.allow_mutations => {}
+            OperationKind::Mutation => anyhow::bail!(
+                "Mutations are disabled for endpoint '{endpoint_name}' (allow_mutations = false)"
+            ),
+            OperationKind::Subscription => {
+                anyhow::bail!("Subscriptions are not supported by the graphql tool")
+            }
+        }
+        check_variables(&operation, variables)?;
+        Ok(operation)
+    }
+
+    async fn client(&self, url: &str) -> anyhow::Result<reqwest::Client> {
+        let builder = reqwest::Client::builder()
+            .timeout(Duration::from_secs(self.config.timeout_secs.max(1)))
+            .connect_timeout(Duration::from_secs(10))
+            .user_agent(USER_AGENT)
+            .redirect(reqwest::redirect::Policy::none());
+        let builder = self.network.pin(url, builder).await?;
+        let builder = crate::config::apply_runtime_proxy_to_builder(builder, "tool.graphql");
+        Ok(builder.build()?)
+    }
+
+    /// POST one GraphQL request and return the decoded response body.
+    async fn post(&self, endpoint: &GraphqlEndpointConfig, body: &Value) -> anyhow::Result<Value> {
+        let url = endpoint.url.trim();
+        let client = self.client(url).await?;
+        let mut request = client.post(url).json(body);
+        if let Some(token) = endpoint.bearer_token.as_deref().filter(|t| !t.is_empty()) {
+            request = request.bearer_auth(token);
+        }
+        for (name, value) in &endpoint.headers {
+            request = request.header(name, value);
+        }
+        let mut response = request.send().await?;
+        let status = response.status();
+        if status.is_redirection() {
+            anyhow::bail!("HTTP {status}: redirects are not followed for GraphQL requests");
+        }
+
+        let limit = self.config.max_response_size.max(1);
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            if bytes.len() > limit {
+                anyhow::bail!("Response exceeds graphql.max_response_size ({limit} bytes)");
+            }
+        }
+        match serde_json::from_slice::<Value>(&bytes) {
+            // Many servers answer invalid documents with 400 and a regular
+            // `errors` body; surface those instead of the bare status.
+            Ok(value) if value.get("data").is_some() || value.get("errors").is_some() => Ok(value),
+            _ if !status.is_success() => anyhow::bail!("HTTP {status}"),
+            _ => anyhow::bail!("Response is not a GraphQL JSON result"),
+        }
+    }
+
+    fn cache_path(&self, endpoint_name: &str) -> PathBuf {
+        let safe: String = endpoint_name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.cache_dir.join(format!("{safe}.json"))
+    }
+
+    fn read_cached_schema(&self, endpoint_name: &str, url: &str) -> Option<Value> {
+        if self.config.schema_cache_secs == 0 {
+            return None;
+        }
+        let raw = std::fs::read_to_string(self.cache_path(endpoint_name)).ok()?;
+        let cached: CachedSchema = serde_json::from_str(&raw).ok()?;
+        let age = Utc::now().signed_duration_since(cached.fetched_at);
+        let fresh = age.num_seconds() >= 0
+            && age.num_seconds().unsigned_abs() < self.config.schema_cache_secs;
+        (fresh && cached.url == url).then_some(cached.schema)
+    }
+
+    fn write_cached_schema(&self, endpoint_name: &str, url: &str, schema: &Value) {
+        if self.config.schema_cache_secs == 0 {
+            return;
+        }
+        let cached = CachedSchema {
+            url: url.to_string(),
+            fetched_at: Utc::now(),
+            schema: schema.clone(),
+        };
+        let result = std::fs::create_dir_all(&self.cache_dir).and_then(|()| {
+            std::fs::write(
+                self.cache_path(endpoint_name),
+                serde_json::to_string(&cached).unwrap_or_default(),
+            )
+        });
+        if let Err(e) = result {
+            tracing::warn!("graphql: failed to write schema cache: {e}");
+        }
+    }
+
+    async fn schema(
+        &self,
+        endpoint_name: &str,
+        type_name: Option<&str>,
+        refresh: bool,
+    ) -> anyhow::Result<String> {
+        let endpoint = self.endpoint(endpoint_name)?;
+        let url = endpoint.url.trim();
+        let cached = if refresh {
+            None
+        } else {
+            self.read_cached_schema(endpoint_name, url)
+        };
+        let (schema, from_cache) = match cached {
+            Some(schema) => (schema, true),
+            None => {
+                if !self.security.record_action() {
+                    anyhow::bail!("Action blocked: rate limit exceeded");
+                }
+                let response = self
+                    .post(endpoint, &json!({ "query": INTROSPECTION_QUERY }))
+                    .await?;
+                let schema = response
+                    .pointer("/data/__schema")
+                    .filter(|schema| !schema.is_null())
+                    .cloned()
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Introspection failed: {}",
+                            graphql_errors(&response)
+                                .unwrap_or_else(|| "no schema returned".into())
+                        )
+                    })?;
+                self.write_cached_schema(endpoint_name, url, &schema);
+                (schema, false)
+            }
+        };
+        let summary = summarize_schema(&schema, type_name)?;
+        let source = if from_cache {
+            format!("Schema: {endpoint_name} (cached)")
+        } else {
+            format!("Schema: {endpoint_name}")
+        };
+        Ok(truncate(
+            &format!("{source}\n\n{summary}"),
+            self.config.max_response_size,
+        ))
+    }
+
+    async fn query(&self, endpoint_name: &str, args: &Value) -> anyhow::Result<ToolResult> {
+        let endpoint = self.endpoint(endpoint_name)?;
+        let query_name = args.get("query_name").and_then(Value::as_str);
+        let query = args.get("query").and_then(Value::as_str);
+        let operation_name = args.get("operation_name").and_then(Value::as_str);
+        let variables = match args.get("variables") {
+            None | Some(Value::Null) => serde_json::Map::new(),
+            Some(Value::Object(map)) => map.clone(),
+            Some(Value::String(raw)) => match serde_json::from_str(raw)? {
+                Value::Object(map) => map,
+                _ => anyhow::bail!("'variables' must be a JSON object"),
+            },
+            Some(_) => anyhow::bail!("'variables' must be a JSON object"),
+        };
+
+        let document = self.resolve_query(endpoint_name, endpoint, query_name, query)?;
+        let operation = self.check_document(
+            endpoint_name,
+            endpoint,
+            document,
+            operation_name,
+            &variables,
+        )?;
+
+        let allowed = match operation.kind {
+            OperationKind::Mutation => self
+                .security
+                .enforce_tool_operation(ToolOperation::Act, "graphql mutation"),
+            _ => self
+                .security
+                .record_action()
+                .then_some(())
+                .ok_or_else(|| "Action blocked: rate limit exceeded".to_string()),
+        };
+        if let Err(error) = allowed {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        let mut body = json!({ "query": document, "variables": variables });
+        if let Some(name) = operation.name.as_deref() {
+            body["operationName"] = json!(name);
+        }
+        let response = self.post(endpoint, &body).await?;
+        let errors = graphql_errors(&response);
+        let has_data = response.get("data").is_some_and(|data| !data.is_null());
+        let output = truncate(
+            &serde_json::to_string_pretty(&response).unwrap_or_default(),
+            self.config.max_response_size,
+        );
+        Ok(ToolResult {
+            success: has_data,
+            output,
+            error: errors.map(|errors| format!("GraphQL errors: {errors}")),
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for GraphqlTool {
+    fn name(&self) -> &str {
+        "graphql"
+    }
+
+    fn description(&self) -> &str {
+        "Query configured GraphQL APIs. Actions: 'list' shows endpoints and their persisted queries, \
+        'schema' summarizes the (cached) schema or one type, 'query' runs a persisted query by name \
+        with JSON variables (or an ad-hoc query where the endpoint allows it). \
+        Depth and size limits apply; mutations only where enabled."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "schema", "query"],
+                    "description": "What to do"
+                },
+                "endpoint": {
+                    "type": "string",
+                    "description": "Configured endpoint name (schema, query)"
+                },
+                "query_name": {
+                    "type": "string",
+                    "description": "Persisted query to run (query)"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Ad-hoc GraphQL document, only where the endpoint allows it (query)"
+                },
+                "variables": {
+                    "type": "object",
+                    "description": "Variables for the operation; sent as JSON, never spliced into the document"
+                },
+                "operation_name": {
+                    "type": "string",
+                    "description": "Operation to run when the document defines several"
+                },
+                "type": {
+                    "type": "string",
+                    "description": "Show only this type (schema)"
+                },
+                "refresh": {
+                    "type": "boolean",
+                    "description": "Re-run introspection instead of using the cache (schema)",
+                    "default": false
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn simulate(&self, args: &Value) -> ToolResult {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let endpoint = args
+            .get("endpoint")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let target = args
+            .get("query_name")
+            .and_then(Value::as_str)
+            .unwrap_or("(ad-hoc)");
+        ToolResult {
+            success: true,
+            output: format!("[simulated] graphql {action} {endpoint} {target}\n(nothing sent)"),
+            error: None,
+        }
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        if action == "list" {
+            return Ok(ToolResult {
+                success: true,
+                output: self.list(),
+                error: None,
+            });
+        }
+        let endpoint = args
+            .get("endpoint")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'endpoint' parameter"))?;
+
+        let result = match action {
+            "schema" => {
+                let type_name = args.get("type").and_then(Value::as_str);
+                let refresh = args
+                    .get("refresh")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                self.schema(endpoint, type_name, refresh)
+                    .await
+                    .map(|output| ToolResult {
+                        success: true,
+                        output,
+                        error: None,
+                    })
+            }
+            "query" => self.query(endpoint, &args).await,
+            other => Err(anyhow::anyhow!(
+                "Unknown action '{other}' (expected list, schema or query)"
+            )),
+        };
+        Ok(result.unwrap_or_else(|e| ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(format!("graphql failed: {e}")),
+        }))
+    }
+}
+
+// ── Document analysis ───────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OperationKind {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+#[derive(Debug, Clone)]
+struct VariableDef {
+    name: String,
+    /// Non-null type without a default value.
+    required: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Operation {
+    kind: OperationKind,
+    name: Option<String>,
+    variables: Vec<VariableDef>,
+    /// Selection-set depth with fragment spreads expanded.
+    depth: usize,
+}
+
+#[derive(Debug)]
+struct DocumentAnalysis {
+    operations: Vec<Operation>,
+}
+
+impl DocumentAnalysis {
+    fn select(&self, operation_name: Option<&str>) -> anyhow::Result<Operation> {
+        match operation_name {
+            Some(name) => self
+                .operations
+                .iter()
+                .find(|op| op.name.as_deref() == Some(name))
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Document has no operation named '{name}'")),
+            None if self.operations.len() == 1 => Ok(self.operations[0].clone()),
+            None => {
+                anyhow::bail!("Document defines several operations; pass 'operation_name'")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Name(String),
+    Punct(char),
+    Spread,
+    /// String or number literal; only its position matters.
+    Value,
+}
+
+/// Split a GraphQL document into the tokens the checks need, dropping
+/// comments, commas and string contents.
+fn tokenize(document: &str) -> anyhow::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = document.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '#' => while chars.next_if(|&(_, c)| c != '\n').is_some() {},
+            c if c.is_whitespace() || c == ',' || c == '\u{feff}' => {}
+            '"' => {
+                if document[i..].starts_with("\"\"\"") {
+                    let rest = &document[i + 3..];
+                    let end = find_block_string_end(rest)
+                        .ok_or_else(|| anyhow::anyhow!("Unterminated block string"))?;
+                    let stop = i + 3 + end + 3;
+                    while chars.next_if(|&(j, _)| j < stop).is_some() {}
+                } else {
+                    let mut closed = false;
+                    while let Some((_, c)) = chars.next() {
+                        match c {
+                            '\\' => {
+                                chars.next();
+                            }
+                            '"' => {
+                                closed = true;
+                                break;
+                            }
+                            '\n' => break,
+                            _ => {}
+                        }
+                    }
+                    if !closed {
+                        anyhow::bail!("Unterminated string");
+                    }
+                }
+                tokens.push(Token::Value);
+            }
+            '.' => {
+                if document[i..].starts_with("...") {
+                    chars.next();
+                    chars.next();
+                    tokens.push(Token::Spread);
+                } else {
+                    anyhow::bail!("Unexpected '.'");
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end = i + c.len_utf8();
+                while let Some(&(j, c)) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        end = j + c.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Name(document[i..end].to_string()));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                while chars
+                    .next_if(|&(_, c)| c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-'))
+                    .is_some()
+                {}
+                tokens.push(Token::Value);
+            }
+            '{' | '}' | '(' | ')' | '[' | ']' | ':' | '=' | '!' | '$' | '@' | '|' | '&' => {
+                tokens.push(Token::Punct(c));
+            }
+            other => anyhow::bail!("Unexpected character '{other}'"),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Offset of the closing `"""` in `rest`, honoring `\"""` escapes.
+fn find_block_string_end(rest: &str) -> Option<usize> {
+    let mut from = 0;
+    loop {
+        let at = from + rest[from..].find("\"\"\"")?;
+        if at > 0 && rest.as_bytes()[at - 1] == b'\\' {
+            from = at + 3;
+            continue;
+        }
+        return Some(at);
+    }
+}
+
+/// One top-level definition while scanning.
+#[derive(Default)]
+struct Definition {
+    kind: Option<OperationKind>,
+    name: Option<String>,
+    fragment: Option<String>,
+    variables: Vec<VariableDef>,
+    depth: usize,
+    /// `(depth at the spread, fragment name)`
+    spreads: Vec<(usize, String)>,
+}
+
+/// Find the operations in a document together with their declared
+/// variables and selection depth.
+fn analyze_document(document: &str) -> anyhow::Result<DocumentAnalysis> {
+    let tokens = tokenize(document)?;
+    let mut definitions: Vec<Definition> = Vec::new();
+    let mut current: Option<Definition> = None;
+    let mut brace_depth = 0usize;
+    let mut paren_depth = 0usize;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let token = &tokens[i];
+        if brace_depth == 0 && paren_depth == 0 {
+            match token {
+                Token::Name(word) if current.is_none() => {
+                    let mut definition = Definition::default();
+                    match word.as_str() {
+                        "query" => definition.kind = Some(OperationKind::Query),
+                        "mutation" => definition.kind = Some(OperationKind::Mutation),
+                        "subscription" => definition.kind = Some(OperationKind::Subscription),
+                        "fragment" => {
+                            let Some(Token::Name(name)) = tokens.get(i + 1) else {
+                                anyhow::bail!("Fragment definition without a name");
+                            };
+                            definition.fragment = Some(name.clone());
+                            i += 1;
+                        }
+                        other => anyhow::bail!(
+                            "Unsupported definition '{other}' (only operations and fragments)"
+                        ),
+                    }
+                    if definition.kind.is_some() {
+                        if let Some(Token::Name(name)) = tokens.get(i + 1) {
+                            definition.name = Some(name.clone());
+                            i += 1;
+                        }
+                    }
+                    current = Some(definition);
+                    i += 1;
+                    continue;
+                }
+                Token::Punct('{') if current.is_none() => {
+                    // Shorthand query: `{ field }`
+                    current = Some(Definition {
+                        kind: Some(OperationKind::Query),
+                        ..Definition::default()
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        let Some(definition) = current.as_mut() else {
+            anyhow::bail!("Unexpected token outside a definition");
+        };
+        match token {
+            Token::Punct('(') => paren_depth += 1,
+            Token::Punct(')') => {
+                paren_depth = paren_depth
+                    .checked_sub(1)
+                    .ok_or_else(|| anyhow::anyhow!("Unbalanced ')'"))?;
+            }
+            // Braces inside arguments are input objects, not selections.
+            Token::Punct('{') if paren_depth == 0 => {
+                brace_depth += 1;
+                definition.depth = definition.depth.max(brace_depth);
+            }
+            Token::Punct('}') if paren_depth == 0 => {
+                brace_depth = brace_depth
+                    .checked_sub(1)
+                    .ok_or_else(|| anyhow::anyhow!("Unbalanced '}}'"))?;
+                if brace_depth == 0 {
+                    definitions.extend(current.take());
+                }
+            }
+            Token::Punct('$') if brace_depth == 0 && paren_depth == 1 => {
+                let Some(Token::Name(name)) = tokens.get(i + 1) else {
+                    anyhow::bail!("Variable definition without a name");
+                };
+                // `$name: Type! = default` — scan to the next definition.
+                let mut j = i + 2;
+                let mut non_null = false;
+                let mut has_default = false;
+                let mut nesting = 0usize;
+                while let Some(token) = tokens.get(j) {
+                    match token {
+                        Token::Punct('$' | ')') if nesting == 0 => break,
+                        Token::Punct('[' | '{' | '(') => nesting += 1,
+                        Token::Punct(']' | '}' | ')') => {
+                            nesting = nesting.saturating_sub(1);
+                            non_null = false;
+                        }
+                        Token::Punct('!') if !has_default => non_null = true,
+                        Token::Punct('=') if nesting == 0 => has_default = true,
+                        Token::Name(_) if !has_default => non_null = false,
+                        _ => {}
+                    }
+                    j += 1;
+                }
+                definition.variables.push(VariableDef {
+                    name: name.clone(),
+                    required: non_null && !has_default,
+                });
+                i = j;
+                continue;
+            }
+            Token::Spread if brace_depth > 0 => match tokens.get(i + 1) {
+                Some(Token::Name(name)) if name != "on" => {
+                    definition.spreads.push((brace_depth, name.clone()));
+                    i += 1;
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        i += 1;
+    }
+    if current.is_some() || brace_depth != 0 {
+        anyhow::bail!("Document ends inside a definition");
+    }
+
+    let fragments: HashMap<&str, &Definition> = definitions
+        .iter()
+        .filter_map(|d| d.fragment.as_deref().map(|name| (name, d)))
+        .collect();
+    let mut operations = Vec::new();
+    for definition in definitions.iter().filter(|d| d.kind.is_some()) {
+        let mut visiting = HashSet::new();
+        operations.push(Operation {
+            kind: definition.kind.unwrap_or(OperationKind::Query),
+            name: definition.name.clone(),
+            variables: definition.variables.clone(),
+            depth: expanded_depth(definition, &fragments, &mut visiting)?,
+        });
+    }
+    if operations.is_empty() {
+        anyhow::bail!("Document contains no operation");
+    }
+    Ok(DocumentAnalysis { operations })
+}
+
+/// Depth of `definition` with every fragment spread replaced by its body.
+fn expanded_depth<'a>(
+    definition: &'a Definition,
+    fragments: &HashMap<&str, &'a Definition>,
+    visiting: &mut HashSet<&'a str>,
+) -> anyhow::Result<usize> {
+    let mut depth = definition.depth;
+    for (at, name) in &definition.spreads {
+        let fragment = fragments
+            .get(name.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Unknown fragment '{name}'"))?;
+        if !visiting.insert(name.as_str()) {
+            anyhow::bail!("Fragment '{name}' spreads itself");
+        }
+        // The fragment's own selection set replaces the spread, so its
+        // outer braces line up with the enclosing selection.
+        let inner = expanded_depth(fragment, fragments, visiting)?;
+        visiting.remove(name.as_str());
+        depth = depth.max(at + inner.saturating_sub(1));
+    }
+    Ok(depth)
+}
+
+/// Variables must be declared by the operation, and required ones present.
+fn check_variables(
+    operation: &Operation,
+    variables: &serde_json::Map<String, Value>,
+) -> anyhow::Result<()> {
+    let declared: HashSet<&str> = operation
+        .variables
+        .iter()
+        .map(|var| var.name.as_str())
+        .collect();
+    if let Some(unknown) = variables
+        .keys()
+        .find(|key| !declared.contains(key.as_str()))
+    {
+        anyhow::bail!("Variable '{unknown}' is not declared by the operation");
+    }
+    let missing: Vec<String> = operation
+        .variables
+        .iter()
+        .filter(|var| var.required && variables.get(&var.name).is_none_or(Value::is_null))
+        .map(|var| format!("${}", var.name))
+        .collect();
+    if !missing.is_empty() {
+        anyhow::bail!("Missing required variables: {}", missing.join(", "));
+    }
+    Ok(())
+}
+
+// ── Output helpers ──────────────────────────────────────────────
+
+/// `errors[].message`, joined, when the response has any.
+fn graphql_errors(response: &Value) -> Option<String> {
+    let errors = response.get("errors")?.as_array()?;
+    if errors.is_empty() {
+        return None;
+    }
+    Some(
+        errors
+            .iter()
+            .map(|error| {
+                error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .map_or_else(|| error.to_string(), str::to_string)
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+    )
+}
+
+/// Render an introspection type reference as SDL (`[Post!]!`).
+fn type_ref(value: &Value) -> String {
+    match value.get("kind").and_then(Value::as_str) {
+        Some("NON_NULL") => format!("{}!", type_ref(&value["ofType"])),
+        Some("LIST") => format!("[{}]", type_ref(&value["ofType"])),
+        _ => value
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or("?")
+            .to_string(),
+    }
+}
+
+/// Compact SDL-like summary of an introspected schema, or of one type.
+fn summarize_schema(schema: &Value, only: Option<&str>) -> anyhow::Result<String> {
+    let types = schema
+        .get("types")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow::anyhow!("Introspection result has no types"))?;
+    let mut out = String::new();
+    if only.is_none() {
+        for (label, key) in [("query", "queryType"), ("mutation", "mutationType")] {
+            if let Some(name) = schema
+                .pointer(&format!("/{key}/name"))
+                .and_then(Value::as_str)
+            {
+                let _ = writeln!(out, "# {label} root: {name}");
+            }
+        }
+    }
+
+    let mut shown = 0;
+    for ty in types {
+        let name = ty.get("name").and_then(Value::as_str).unwrap_or_default();
+        if name.starts_with("__") || only.is_some_and(|only| only != name) {
+            continue;
+        }
+        shown += 1;
+        let kind = ty.get("kind").and_then(Value::as_str).unwrap_or_default();
+        match kind {
+            "SCALAR" => {
+                let _ = writeln!(out, "scalar {name}");
+            }
+            "ENUM" => {
+                let values: Vec<&str> = ty
+                    .get("enumValues")
+                    .and_then(Value::as_array)
+                    .map(|values| {
+                        values
+                            .iter()
+                            .filter_map(|v| v.get("name").and_then(Value::as_str))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let _ = writeln!(out, "enum {name} {{ {} }}", values.join(" | "));
+            }
+            "UNION" => {
+                let _ = writeln!(out, "union {name}");
+            }
+            _ => {
+                let keyword = match kind {
+                    "INPUT_OBJECT" => "input",
+                    "INTERFACE" => "interface",
+                    _ => "type",
+                };
+                let fields = ty
+                    .get("fields")
+                    .or_else(|| ty.get("inputFields"))
+                    .filter(|fields| !fields.is_null())
+                    .and_then(Value::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let _ = writeln!(out, "{keyword} {name} {{");
+                for field in fields {
+                    let field_name = field.get("name").and_then(Value::as_str).unwrap_or("?");
+                    let args: Vec<String> = field
+                        .get("args")
+                        .and_then(Value::as_array)
+                        .map(|args| {
+                            args.iter()
+                                .map(|arg| {
+                                    format!(
+                                        "{}: {}",
+                                        arg.get("name").and_then(Value::as_str).unwrap_or("?"),
+                                        type_ref(&arg["type"])
+                                    )
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    let args = if args.is_empty() {
+                        String::new()
+                    } else {
+                        format!("({})", args.join(", "))
+                    };
+                    let _ = writeln!(out, "  {field_name}{args}: {}", type_ref(&field["type"]));
+                }
+                let _ = writeln!(out, "}}");
+            }
+        }
+    }
+    if let Some(only) = only {
+        if shown == 0 {
+            anyhow::bail!("Type '{only}' is not in the schema");
+        }
+    }
+    Ok(out.trim_end().to_string())
+}
+
+fn truncate(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}\n\n... [truncated: {end} of {} bytes shown] ...",
+        &text[..end],
+        text.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tool(tmp: &TempDir, endpoint: GraphqlEndpointConfig) -> GraphqlTool {
+        GraphqlTool::new(
+            Arc::new(SecurityPolicy::default()),
+            GraphqlConfig {
+                enabled: true,
+                endpoints: HashMap::from([("api".to_string(), endpoint)]),
+                max_depth: 4,
+                ..GraphqlConfig::default()
+            },
+            tmp.path(),
+        )
+    }
+
+    fn endpoint(queries: &[(&str, &str)]) -> GraphqlEndpointConfig {
+        GraphqlEndpointConfig {
+            url: "https://api.example.com/graphql".into(),
+            bearer_token: None,
+            headers: HashMap::new(),
+            queries: queries
+                .iter()
+                .map(|(name, query)| ((*name).to_string(), (*query).to_string()))
+                .collect(),
+            allow_ad_hoc: false,
+            allow_mutations: false,
+        }
+    }
+
+    #[test]
+    fn analyze_document_reads_operations_variables_and_depth() {
+        let doc = r#"
+            # comment with { braces
+            query Repo($owner: String!, $name: String = "x", $first: Int, $ids: [ID!]!) {
+              repository(owner: $owner, filter: { name: "}{" }) {
+                issues(first: $first) { nodes { ...IssueFields } }
+              }
+            }
+            fragment IssueFields on Issue { title author { login } }
+        "#;
+        let analysis = analyze_document(doc).unwrap();
+        let op = analysis.select(None).unwrap();
+        assert_eq!(op.kind, OperationKind::Query);
+        assert_eq!(op.name.as_deref(), Some("Repo"));
+        let vars: Vec<(&str, bool)> = op
+            .variables
+            .iter()
+            .map(|v| (v.name.as_str(), v.required))
+            .collect();
+        assert_eq!(
+            vars,
+            [
+                ("owner", true),
+                ("name", false),
+                ("first", false),
+                ("ids", true)
+            ]
+        );
+        // repository { issues { nodes { <fragment: author { login }> } } }
+        assert_eq!(op.depth, 5);
+
+        let shorthand = analyze_document("{ viewer { login } }").unwrap();
+        assert_eq!(shorthand.operations[0].depth, 2);
+
+        assert!(analyze_document("query A { ...F } fragment F on Q { ...F }").is_err());
+        assert!(analyze_document("query { a { b }").is_err());
+        assert!(analyze_document("type Query { a: Int }").is_err());
+    }
+
+    #[test]
+    fn check_document_enforces_policy_and_limits() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = endpoint(&[]);
+        let tool = tool(&tmp, config.clone());
+        let none = serde_json::Map::new();
+
+        let deep = "{ a { b { c { d { e } } } } }";
+        let err = tool
+            .check_document("api", &config, deep, None, &none)
+            .unwrap_err();
+        assert!(err.to_string().contains("max_depth"), "{err}");
+
+        let mutation = "mutation { close(id: 1) { ok } }";
+        let err = tool
+            .check_document("api", &config, mutation, None, &none)
+            .unwrap_err();
+        assert!(err.to_string().contains("Mutations are disabled"), "{err}");
+        config.allow_mutations = true;
+        assert!(tool
+            .check_document("api", &config, mutation, None, &none)
+            .is_ok());
+
+        let sub = "subscription { events { id } }";
+        assert!(tool
+            .check_document("api", &config, sub, None, &none)
+            .is_err());
+
+        let two = "query A { a } query B { b }";
+        assert!(tool
+            .check_document("api", &config, two, None, &none)
+            .is_err());
+        assert_eq!(
+            tool.check_document("api", &config, two, Some("B"), &none)
+                .unwrap()
+                .name
+                .as_deref(),
+            Some("B")
+        );
+    }
+
+    #[test]
+    fn variables_must_be_declared_and_required_ones_present() {
+        let op = analyze_document("query Q($id: ID!, $limit: Int) { item(id: $id) { name } }")
+            .unwrap()
+            .select(None)
+            .unwrap();
+        let vars = |value: Value| value.as_object().unwrap().clone();
+        assert!(check_variables(&op, &vars(json!({"id": "1"}))).is_ok());
+        assert!(check_variables(&op, &vars(json!({"limit": 5})))
+            .unwrap_err()
+            .to_string()
+            .contains("$id"));
+        assert!(check_variables(&op, &vars(json!({"id": "1", "evil": "x"})))
+            .unwrap_err()
+            .to_string()
+            .contains("not declared"));
+    }
+
+    #[test]
+    fn persisted_queries_are_required_unless_ad_hoc_allowed() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = endpoint(&[("viewer", "{ viewer { login } }")]);
+        let tool = tool(&tmp, config.clone());
+        assert_eq!(
+            tool.resolve_query("api", &config, Some("viewer"), None)
+                .unwrap(),
+            "{ viewer { login } }"
+        );
+        assert!(tool
+            .resolve_query("api", &config, Some("missing"), None)
+            .is_err());
+        assert!(tool
+            .resolve_query("api", &config, None, Some("{ secrets }"))
+            .unwrap_err()
+            .to_string()
+            .contains("only runs persisted queries"));
+        config.allow_ad_hoc = true;
+        assert!(tool
+            .resolve_query("api", &config, None, Some("{ secrets }"))
+            .is_ok());
+        assert!(tool.list().contains("viewer()"));
+    }
+
+    #[test]
+    fn summarize_schema_renders_sdl_and_filters_types() {
+        let schema = json!({
+            "queryType": {"name": "Query"},
+            "mutationType": null,
+            "types": [
+                {"kind": "OBJECT", "name": "Query", "fields": [{
+                    "name": "user",
+                    "args": [{"name": "id", "type": {"kind": "NON_NULL", "name": null, "ofType": {"kind": "SCALAR", "name": "ID"}}}],
+                    "type": {"kind": "OBJECT", "name": "User"}
+                }]},
+                {"kind": "OBJECT", "name": "User", "fields": [{
+                    "name": "tags", "args": [],
+                    "type": {"kind": "LIST", "name": null, "ofType": {"kind": "NON_NULL", "name": null, "ofType": {"kind": "SCALAR", "name": "String"}}}
+                }]},
+                {"kind": "ENUM", "name": "Role", "enumValues": [{"name": "ADMIN"}, {"name": "USER"}]},
+                {"kind": "OBJECT", "name": "__Schema", "fields": []}
+            ]
+        });
+        let summary = summarize_schema(&schema, None).unwrap();
+        assert!(summary.contains("# query root: Query"));
+        assert!(summary.contains("  user(id: ID!): User"));
+        assert!(summary.contains("  tags: [String!]"));
+        assert!(summary.contains("enum Role { ADMIN | USER }"));
+        assert!(!summary.contains("__Schema"));
+
+        let user = summarize_schema(&schema, Some("User")).unwrap();
+        assert!(user.starts_with("type User {"));
+        assert!(summarize_schema(&schema, Some("Nope")).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! Hardware logs tool — query firmware output captured over probe-rs RTT/semihosting.
+//!
+//! Use when the user asks "what did the firmware print in the last minute?",
+//! "any errors from the board?", etc. Reads the daemon's `[hardware.logs]` buffer.
+
+use super::traits::{Tool, ToolResult};
+use crate::hardware::rtt;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+
+/// Default look-back window in seconds.
+const DEFAULT_SINCE_SECS: u64 = 60;
+/// Longest look-back window (the buffer rarely holds more anyway).
+const MAX_SINCE_SECS: u64 = 7 * 24 * 3600;
+/// Maximum lines returned per call.
+const MAX_LINES: u64 = 500;
+
+/// Tool: return recent device log lines from the capture buffer.
+pub struct HardwareLogsTool;
+
+#[async_trait]
+impl Tool for HardwareLogsTool {
+    fn name(&self) -> &str {
+        "hardware_logs"
+    }
+
+    fn description(&self) -> &str {
+        "Read recent firmware log lines captured from the target board over RTT/semihosting. \
+        Use when asked what the device printed, whether it logged errors, or to check sensor output. \
+        Params: since_secs (look-back window, default 60), contains (case-insensitive filter), limit (default 100)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "since_secs": {
+                    "type": "integer",
+                    "description": "Only lines from the last N seconds (default 60)."
+                },
+                "contains": {
+                    "type": "string",
+                    "description": "Only lines containing this text (case-insensitive)."
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum lines, newest kept (default 100, max 500)."
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let since_secs = args
+            .get("since_secs")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(DEFAULT_SINCE_SECS)
+            .min(MAX_SINCE_SECS);
+        let contains = args
+            .get("contains")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|text| !text.is_empty());
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(100)
+            .clamp(1, MAX_LINES);
+
+        let since = chrono::Utc::now()
+            - chrono::Duration::seconds(i64::try_from(since_secs).unwrap_or_default());
+        let lines = rtt::recent(since, contains, usize::try_from(limit).unwrap_or(100));
+
+        if lines.is_empty() {
+            let mut output = format!("No device log lines in the last {since_secs}s.");
+            if !rtt::is_capturing() {
+                output.push_str(
+                    " Capture is not attached to the target; check the probe connection and the daemon's [hardware.logs] status.",
+                );
+            }
+            return Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            });
+        }
+
+        let mut output = format!("{} lines from the last {since_secs}s:\n", lines.len());
+        for line in &lines {
+            let _ = writeln!(output, "{}", rtt::format_line(line));
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_empty_window_when_nothing_captured() {
+        let result = HardwareLogsTool
+            .execute(json!({"since_secs": 1, "contains": "no-such-line-7f3a"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.starts_with("No device log lines"));
+    }
+}
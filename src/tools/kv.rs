@@ -0,0 +1,349 @@
+use super::traits::{Tool, ToolResult};
+use crate::memory::{self, Memory, MemoryCategory};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Memory category holding `kv` entries.
+const KV_CATEGORY: &str = "kv";
+const MAX_NAME_LEN: usize = 128;
+/// Values are meant for small state (cursors, last-seen IDs), not documents.
+const MAX_VALUE_BYTES: usize = 16 * 1024;
+/// Characters of each value shown by `list`.
+const LIST_PREVIEW_CHARS: usize = 200;
+
+/// Namespaced key-value state for skills, stored in the memory backend under
+/// `kv:<namespace>:<key>` and kept out of recall context.
+pub struct KvTool {
+    memory: Arc<dyn Memory>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl KvTool {
+    pub fn new(memory: Arc<dyn Memory>, security: Arc<SecurityPolicy>) -> Self {
+        Self { memory, security }
+    }
+
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    fn validate_name(kind: &str, value: &str) -> Result<(), String> {
+        if value.is_empty() || value.len() > MAX_NAME_LEN {
+            return Err(format!("'{kind}' must be 1-{MAX_NAME_LEN} characters"));
+        }
+        if !value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/'))
+        {
+            return Err(format!(
+                "'{kind}' may only contain letters, digits, '_', '-', '.' or '/'"
+            ));
+        }
+        Ok(())
+    }
+
+    fn storage_key(namespace: &str, key: &str) -> String {
+        format!("{}{namespace}:{key}", memory::KV_KEY_PREFIX)
+    }
+
+    /// Stored form of `value`: strings as-is, other JSON as compact JSON.
+    fn value_text(value: &serde_json::Value) -> String {
+        match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for KvTool {
+    fn name(&self) -> &str {
+        "kv"
+    }
+
+    fn description(&self) -> &str {
+        "Persistent key-value store for small state such as cursors or last-seen IDs. \
+        Keys are grouped by namespace (use the skill name, or 'session/<id>' for per-session state). \
+        Actions: get, set, delete, list. Entries are not included in conversational memory."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["get", "set", "delete", "list"]
+                },
+                "namespace": {
+                    "type": "string",
+                    "description": "Owner of the keys, e.g. the skill name"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Key within the namespace (get/set/delete)"
+                },
+                "value": {
+                    "description": "Value to store (set); strings are stored as-is, other JSON as JSON text"
+                }
+            },
+            "required": ["action", "namespace"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let namespace = args
+            .get("namespace")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let key = args
+            .get("key")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        ToolResult {
+            success: true,
+            output: format!("[simulated] kv {action} {namespace}:{key}"),
+            error: None,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let namespace = args
+            .get("namespace")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        if let Err(e) = Self::validate_name("namespace", namespace) {
+            return Ok(Self::failure(e));
+        }
+
+        if action == "list" {
+            let prefix = Self::storage_key(namespace, "");
+            let category = MemoryCategory::Custom(KV_CATEGORY.into());
+            let mut entries: Vec<_> = self
+                .memory
+                .list(Some(&category), None)
+                .await?
+                .into_iter()
+                .filter(|entry| entry.key.starts_with(&prefix))
+                .collect();
+            entries.sort_by(|a, b| a.key.cmp(&b.key));
+            if entries.is_empty() {
+                return Ok(ToolResult {
+                    success: true,
+                    output: format!("No keys in namespace '{namespace}'."),
+                    error: None,
+                });
+            }
+            let mut output = format!("{} keys in '{namespace}':\n", entries.len());
+            for entry in &entries {
+                let mut value: String = entry.content.chars().take(LIST_PREVIEW_CHARS).collect();
+                if entry.content.chars().count() > LIST_PREVIEW_CHARS {
+                    value.push('…');
+                }
+                let _ = writeln!(output, "- {} = {value}", &entry.key[prefix.len()..]);
+            }
+            return Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            });
+        }
+
+        let key = args
+            .get("key")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        if let Err(e) = Self::validate_name("key", key) {
+            return Ok(Self::failure(e));
+        }
+        let storage_key = Self::storage_key(namespace, key);
+
+        match action {
+            "get" => match self.memory.get(&storage_key).await? {
+                Some(entry) => Ok(ToolResult {
+                    success: true,
+                    output: entry.content,
+                    error: None,
+                }),
+                None => Ok(Self::failure(format!("Key '{namespace}:{key}' not found"))),
+            },
+            "set" => {
+                let Some(value) = args.get("value") else {
+                    return Ok(Self::failure("Missing 'value' for set"));
+                };
+                let value = Self::value_text(value);
+                if value.len() > MAX_VALUE_BYTES {
+                    return Ok(Self::failure(format!(
+                        "Value is {} bytes; kv values are limited to {MAX_VALUE_BYTES} bytes",
+                        value.len()
+                    )));
+                }
+                if let Err(error) = self
+                    .security
+                    .enforce_tool_operation(ToolOperation::Act, "kv")
+                {
+                    return Ok(Self::failure(error));
+                }
+                self.memory
+                    .store(
+                        &storage_key,
+                        &value,
+                        MemoryCategory::Custom(KV_CATEGORY.into()),
+                        None,
+                    )
+                    .await?;
+                Ok(ToolResult {
+                    success: true,
+                    output: format!("Set {namespace}:{key}"),
+                    error: None,
+                })
+            }
+            "delete" => {
+                if let Err(error) = self
+                    .security
+                    .enforce_tool_operation(ToolOperation::Act, "kv")
+                {
+                    return Ok(Self::failure(error));
+                }
+                let removed = self.memory.forget(&storage_key).await?;
+                Ok(ToolResult {
+                    success: true,
+                    output: if removed {
+                        format!("Deleted {namespace}:{key}")
+                    } else {
+                        format!("Key '{namespace}:{key}' did not exist")
+                    },
+                    error: None,
+                })
+            }
+            other => Ok(Self::failure(format!(
+                "Unknown action '{other}' (expected get, set, delete or list)"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_tool(security: SecurityPolicy) -> (TempDir, Arc<dyn Memory>, KvTool) {
+        let tmp = TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        let tool = KvTool::new(mem.clone(), Arc::new(security));
+        (tmp, mem, tool)
+    }
+
+    #[tokio::test]
+    async fn set_get_list_delete_roundtrip() {
+        let (_tmp, _mem, tool) = test_tool(SecurityPolicy::default());
+
+        let set = tool
+            .execute(json!({"action": "set", "namespace": "rss-digest", "key": "cursor", "value": "item-42"}))
+            .await
+            .unwrap();
+        assert!(set.success, "{:?}", set.error);
+        tool.execute(
+            json!({"action": "set", "namespace": "rss-digest", "key": "seen", "value": [1, 2]}),
+        )
+        .await
+        .unwrap();
+        tool.execute(json!({"action": "set", "namespace": "other", "key": "cursor", "value": "x"}))
+            .await
+            .unwrap();
+
+        let got = tool
+            .execute(json!({"action": "get", "namespace": "rss-digest", "key": "cursor"}))
+            .await
+            .unwrap();
+        assert_eq!(got.output, "item-42");
+
+        let listed = tool
+            .execute(json!({"action": "list", "namespace": "rss-digest"}))
+            .await
+            .unwrap();
+        assert!(listed.output.contains("- cursor = item-42"));
+        assert!(listed.output.contains("- seen = [1,2]"));
+        assert!(!listed.output.contains("= x"));
+
+        let deleted = tool
+            .execute(json!({"action": "delete", "namespace": "rss-digest", "key": "cursor"}))
+            .await
+            .unwrap();
+        assert!(deleted.output.contains("Deleted"));
+        let missing = tool
+            .execute(json!({"action": "get", "namespace": "rss-digest", "key": "cursor"}))
+            .await
+            .unwrap();
+        assert!(!missing.success);
+    }
+
+    #[tokio::test]
+    async fn rejects_bad_names_and_oversized_values() {
+        let (_tmp, _mem, tool) = test_tool(SecurityPolicy::default());
+        let bad = tool
+            .execute(json!({"action": "get", "namespace": "a:b", "key": "k"}))
+            .await
+            .unwrap();
+        assert!(bad.error.unwrap().contains("namespace"));
+
+        let big = tool
+            .execute(json!({
+                "action": "set",
+                "namespace": "skill",
+                "key": "blob",
+                "value": "x".repeat(MAX_VALUE_BYTES + 1)
+            }))
+            .await
+            .unwrap();
+        assert!(big.error.unwrap().contains("limited"));
+    }
+
+    #[tokio::test]
+    async fn read_only_mode_allows_reads_only() {
+        let (_tmp, mem, tool) = test_tool(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        mem.store(
+            "kv:skill:cursor",
+            "7",
+            MemoryCategory::Custom(KV_CATEGORY.into()),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let got = tool
+            .execute(json!({"action": "get", "namespace": "skill", "key": "cursor"}))
+            .await
+            .unwrap();
+        assert_eq!(got.output, "7");
+        let set = tool
+            .execute(json!({"action": "set", "namespace": "skill", "key": "cursor", "value": "8"}))
+            .await
+            .unwrap();
+        assert!(!set.success);
+    }
+}
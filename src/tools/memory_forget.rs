@@ -41,6 +41,18 @@ impl Tool for MemoryForgetTool {
         })
     }
 
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let key = args
+            .get("key")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        ToolResult {
+            success: true,
+            output: format!("[simulated] Would forget memory: {key}"),
+            error: None,
+        }
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let key = args
             .get("key")
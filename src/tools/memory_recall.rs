@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::memory::Memory;
+use crate::memory::{self, Memory};
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write;
@@ -55,7 +55,14 @@ impl Tool for MemoryRecallTool {
             .and_then(serde_json::Value::as_u64)
             .map_or(5, |v| v as usize);
 
-        match self.memory.recall(query, limit, None).await {
+        // Skill state from the `kv` tool is read through that tool, not recall.
+        let recalled = self.memory.recall(query, limit, None).await.map(|entries| {
+            entries
+                .into_iter()
+                .filter(|entry| !memory::is_kv_key(&entry.key))
+                .collect::<Vec<_>>()
+        });
+        match recalled {
             Ok(entries) if entries.is_empty() => Ok(ToolResult {
                 success: true,
                 output: "No memories found matching that query.".into(),
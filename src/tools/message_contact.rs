@@ -0,0 +1,240 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use crate::contacts::ContactBook;
+use crate::cron::scheduler::{announce_channels, send_announcement};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Maximum characters of the message echoed back in approval requests.
+const PREVIEW_CHARS: usize = 200;
+
+/// Send a message to a contact, resolving the channel and recipient from the
+/// contact book. Sends to unapproved contacts go out only after the user
+/// confirms them through the approval prompt.
+pub struct MessageContactTool {
+    config: Arc<Config>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl MessageContactTool {
+    pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for MessageContactTool {
+    fn name(&self) -> &str {
+        "message_contact"
+    }
+
+    fn description(&self) -> &str {
+        "Send a message to a person from the contact book (e.g. \"message Alice the summary\"). \
+        Resolves the name or alias to the contact's preferred channel and address. \
+        Messages to contacts the user has not approved require the user's confirmation."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "contact": {
+                    "type": "string",
+                    "description": "Contact name or alias"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Message text to send"
+                },
+                "channel": {
+                    "type": "string",
+                    "description": "Override the channel (must be one of the contact's addresses)"
+                }
+            },
+            "required": ["contact", "message"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let contact = args
+            .get("contact")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        ToolResult {
+            success: true,
+            output: format!("[simulated] message_contact '{contact}'\n(no message sent)"),
+            error: None,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let query = args
+            .get("contact")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        let message = args
+            .get("message")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        if query.is_empty() || message.trim().is_empty() {
+            return Ok(Self::failure("Both 'contact' and 'message' are required"));
+        }
+        let channel = args
+            .get("channel")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|c| !c.is_empty());
+        // Set by the agent loop only after the user approved this call.
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        if !self.security.can_act() {
+            return Ok(Self::failure(
+                "Security policy: read-only mode, cannot perform 'message_contact'",
+            ));
+        }
+
+        let book = ContactBook::from_config(&self.config);
+        let contact = match book.find(query) {
+            Ok(Some(contact)) => contact,
+            Ok(None) => {
+                return Ok(Self::failure(format!(
+                    "No contact named '{query}'. Add one with the contacts tool first."
+                )))
+            }
+            Err(e) => return Ok(Self::failure(e.to_string())),
+        };
+        let (channel, to) = match contact.route(channel, &announce_channels(&self.config)) {
+            Ok(route) => route,
+            Err(e) => return Ok(Self::failure(e.to_string())),
+        };
+
+        if !contact.approved && !approved {
+            let preview: String = message.chars().take(PREVIEW_CHARS).collect();
+            return Ok(Self::failure(format!(
+                "'{name}' is a new contact: sending to {channel}:{to} requires explicit approval. \
+                Ask the user to run `zeroclaw contacts approve {name}` or confirm the send when prompted.\nMessage: {preview}",
+                name = contact.name
+            )));
+        }
+
+        if !self.security.record_action() {
+            return Ok(Self::failure(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+
+        if let Err(e) = send_announcement(&self.config, &channel, &to, message).await {
+            return Ok(Self::failure(format!(
+                "Failed to message {} via {channel}: {e}",
+                contact.name
+            )));
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: format!("Sent message to {} via {channel} ({to})", contact.name),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contacts::Contact;
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    fn setup(tmp: &TempDir, approved: bool) -> MessageContactTool {
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        ContactBook::from_config(&config)
+            .upsert(
+                Contact {
+                    name: "Alice".into(),
+                    aliases: Vec::new(),
+                    addresses: BTreeMap::from([("telegram".to_string(), "42".to_string())]),
+                    preferred_channel: None,
+                    notes: None,
+                    approved,
+                },
+                true,
+            )
+            .unwrap();
+        MessageContactTool::new(Arc::new(config), Arc::new(SecurityPolicy::default()))
+    }
+
+    #[tokio::test]
+    async fn new_contacts_require_explicit_approval() {
+        let tmp = TempDir::new().unwrap();
+        let tool = setup(&tmp, false);
+        let result = tool
+            .execute(json!({"contact": "alice", "message": "Summary", "channel": "telegram"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        let error = result.error.unwrap();
+        assert!(error.contains("requires explicit approval"), "{error}");
+        assert!(error.contains("telegram:42"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn approved_sends_report_unconfigured_channels() {
+        let tmp = TempDir::new().unwrap();
+        let tool = setup(&tmp, true);
+
+        let unrouted = tool
+            .execute(json!({"contact": "Alice", "message": "Summary"}))
+            .await
+            .unwrap();
+        assert!(unrouted
+            .error
+            .unwrap()
+            .contains("no address on a configured channel"));
+
+        let unconfigured = tool
+            .execute(json!({"contact": "Alice", "message": "Summary", "channel": "telegram"}))
+            .await
+            .unwrap();
+        assert!(!unconfigured.success);
+        assert!(unconfigured.error.unwrap().contains("not configured"));
+    }
+
+    #[test]
+    fn schema_leaves_approval_to_the_approval_prompt() {
+        let tmp = TempDir::new().unwrap();
+        let tool = setup(&tmp, false);
+        assert!(tool
+            .parameters_schema()
+            .pointer("/properties/approved")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn unknown_contacts_are_reported() {
+        let tmp = TempDir::new().unwrap();
+        let tool = setup(&tmp, true);
+        let result = tool
+            .execute(json!({"contact": "Bob", "message": "hi"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("No contact named 'Bob'"));
+    }
+}
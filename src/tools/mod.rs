@@ -15,11 +15,18 @@
 //! To add a new tool, implement [`Tool`] in a new submodule and register it in
 //! [`all_tools_with_runtime`]. See `AGENTS.md` §7.3 for the full change playbook.
 
+pub mod ask_user;
+pub mod ble_sensors;
 pub mod browser;
 pub mod browser_open;
+pub mod calendar;
+pub mod camera;
 pub mod cli_discovery;
 pub mod composio;
+pub mod contacts;
 pub mod content_search;
+#[cfg(feature = "doc-convert")]
+pub mod convert_doc;
 pub mod cron_add;
 pub mod cron_list;
 pub mod cron_remove;
@@ -27,36 +34,62 @@ pub mod cron_run;
 pub mod cron_runs;
 pub mod cron_update;
 pub mod delegate;
+pub mod email_send;
+pub mod fetch_url;
 pub mod file_edit;
 pub mod file_read;
 pub mod file_write;
+pub mod financial_guard;
+pub mod firmware_build;
+pub mod four_eyes;
 pub mod git_operations;
 pub mod glob_search;
+pub mod graphql;
 pub mod hardware_board_info;
+pub mod hardware_logs;
 pub mod hardware_memory_map;
 pub mod hardware_memory_read;
 pub mod http_request;
 pub mod image_info;
+pub mod kv;
 pub mod memory_forget;
 pub mod memory_recall;
 pub mod memory_store;
+pub mod message_contact;
 pub mod model_routing_config;
 pub mod pdf_read;
+pub mod peer_delegate;
+pub mod pin;
 pub mod proxy_config;
 pub mod pushover;
+pub mod remote_node;
 pub mod schedule;
 pub mod schema;
 pub mod screenshot;
 #[cfg(feature = "tool-update")]
 pub mod self_update;
 pub mod shell;
+pub mod simulation;
+#[cfg(feature = "mqtt")]
+pub mod smart_home_devices;
+#[cfg(feature = "mqtt")]
+pub mod smart_home_sensor;
+#[cfg(feature = "mqtt")]
+pub mod smart_home_switch;
 pub mod traits;
 pub mod web_search_tool;
 
+pub use ask_user::AskUserTool;
+pub use ble_sensors::BleSensorsTool;
 pub use browser::{BrowserTool, ComputerUseConfig};
 pub use browser_open::BrowserOpenTool;
+pub use calendar::CalendarTool;
+pub use camera::{CameraTool, CameraVision};
 pub use composio::ComposioTool;
+pub use contacts::ContactsTool;
 pub use content_search::ContentSearchTool;
+#[cfg(feature = "doc-convert")]
+pub use convert_doc::ConvertDocTool;
 pub use cron_add::CronAddTool;
 pub use cron_list::CronListTool;
 pub use cron_remove::CronRemoveTool;
@@ -64,23 +97,33 @@ pub use cron_run::CronRunTool;
 pub use cron_runs::CronRunsTool;
 pub use cron_update::CronUpdateTool;
 pub use delegate::DelegateTool;
+pub use email_send::EmailSendTool;
+pub use fetch_url::FetchUrlTool;
 pub use file_edit::FileEditTool;
 pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
+pub use firmware_build::FirmwareBuildTool;
 pub use git_operations::GitOperationsTool;
 pub use glob_search::GlobSearchTool;
+pub use graphql::GraphqlTool;
 pub use hardware_board_info::HardwareBoardInfoTool;
+pub use hardware_logs::HardwareLogsTool;
 pub use hardware_memory_map::HardwareMemoryMapTool;
 pub use hardware_memory_read::HardwareMemoryReadTool;
 pub use http_request::HttpRequestTool;
 pub use image_info::ImageInfoTool;
+pub use kv::KvTool;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
+pub use message_contact::MessageContactTool;
 pub use model_routing_config::ModelRoutingConfigTool;
 pub use pdf_read::PdfReadTool;
+pub use peer_delegate::PeerDelegateTool;
+pub use pin::PinTool;
 pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
+pub use remote_node::RemoteNodeTool;
 pub use schedule::ScheduleTool;
 #[allow(unused_imports)]
 pub use schema::{CleaningStrategy, SchemaCleanr};
@@ -88,15 +131,21 @@ pub use screenshot::ScreenshotTool;
 #[cfg(feature = "tool-update")]
 pub use self_update::SelfUpdateTool;
 pub use shell::ShellTool;
+#[cfg(feature = "mqtt")]
+pub use smart_home_devices::SmartHomeDevicesTool;
+#[cfg(feature = "mqtt")]
+pub use smart_home_sensor::SmartHomeSensorTool;
+#[cfg(feature = "mqtt")]
+pub use smart_home_switch::SmartHomeSwitchTool;
 pub use traits::Tool;
 #[allow(unused_imports)]
 pub use traits::{ToolResult, ToolSpec};
 pub use web_search_tool::WebSearchTool;
 
-use crate::config::{Config, DelegateAgentConfig};
+use crate::config::{Config, DelegateAgentConfig, FederationRole};
 use crate::memory::Memory;
 use crate::runtime::{NativeRuntime, RuntimeAdapter};
-use crate::security::SecurityPolicy;
+use crate::security::{FinancialGuard, NetworkGuard, OtpValidator, SecretStore, SecurityPolicy};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -129,12 +178,57 @@ impl Tool for ArcDelegatingTool {
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         self.inner.execute(args).await
     }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        self.inner.simulate(args)
+    }
+
+    fn change_preview(&self, args: &serde_json::Value) -> Option<String> {
+        self.inner.change_preview(args)
+    }
+
+    fn financial(&self) -> bool {
+        self.inner.financial()
+    }
+
+    fn spec(&self) -> ToolSpec {
+        self.inner.spec()
+    }
+}
+
+/// Guard for `[security.financial]`; financial calls are refused outright
+/// when OTP is disabled or its secret cannot be loaded.
+fn financial_guard(root_config: &Config, workspace_dir: &std::path::Path) -> FinancialGuard {
+    let zeroclaw_dir = root_config
+        .config_path
+        .parent()
+        .unwrap_or(workspace_dir)
+        .to_path_buf();
+    let otp = if root_config.security.otp.enabled {
+        let store = SecretStore::new(&zeroclaw_dir, root_config.secrets.encrypt);
+        match OtpValidator::from_config(&root_config.security.otp, &zeroclaw_dir, &store) {
+            Ok((validator, _)) => Some(validator),
+            Err(e) => {
+                tracing::warn!("OTP unavailable; financial tools will be refused: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    FinancialGuard::new(root_config.security.financial.clone(), otp, &zeroclaw_dir)
 }
 
 fn boxed_registry_from_arcs(tools: Vec<Arc<dyn Tool>>) -> Vec<Box<dyn Tool>> {
     tools.into_iter().map(ArcDelegatingTool::boxed).collect()
 }
 
+/// Box a tool that is shared with other registries (e.g. peripheral
+/// handles held by the gateway).
+pub fn boxed_shared_tool(tool: Arc<dyn Tool>) -> Box<dyn Tool> {
+    ArcDelegatingTool::boxed(tool)
+}
+
 /// Create the default tool registry
 pub fn default_tools(security: Arc<SecurityPolicy>) -> Vec<Box<dyn Tool>> {
     default_tools_with_runtime(security, Arc::new(NativeRuntime::new()))
@@ -186,6 +280,24 @@ pub fn all_tools(
     )
 }
 
+/// Provider options for tools that call a model themselves.
+fn provider_runtime_options(
+    root_config: &crate::config::Config,
+) -> crate::providers::ProviderRuntimeOptions {
+    crate::providers::ProviderRuntimeOptions {
+        auth_profile_override: None,
+        zeroclaw_dir: root_config
+            .config_path
+            .parent()
+            .map(std::path::PathBuf::from),
+        secrets_encrypt: root_config.secrets.encrypt,
+        provider_api_url: root_config.api_url.clone(),
+        reasoning_enabled: root_config.runtime.reasoning_enabled,
+        custom_providers: root_config.custom_providers.clone(),
+        provider_plugins: root_config.provider_plugins.clone(),
+    }
+}
+
 /// Create full tool registry including memory tools and optional Composio.
 #[allow(clippy::implicit_hasher, clippy::too_many_arguments)]
 pub fn all_tools_with_runtime(
@@ -202,8 +314,35 @@ pub fn all_tools_with_runtime(
     fallback_api_key: Option<&str>,
     root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
+    // Tools listed in [runtime.remote] run on the SSH worker, scoped to the
+    // remote workspace instead of the local one.
+    let remote = root_config.runtime.remote.enabled.then(|| {
+        Arc::new(crate::runtime::RemoteRuntime::new(
+            root_config.runtime.remote.clone(),
+        ))
+    });
+    let remote_security = remote.as_ref().map(|remote| {
+        Arc::new(SecurityPolicy {
+            workspace_dir: remote.workspace(),
+            ..(**security).clone()
+        })
+    });
+    let shell_tool = match (&remote, &remote_security) {
+        (Some(remote), Some(remote_security)) if remote.routes("shell") => {
+            ShellTool::new(remote_security.clone(), remote.clone())
+        }
+        _ => ShellTool::new(security.clone(), runtime),
+    };
+    let git_tool = match (&remote, &remote_security) {
+        (Some(remote), Some(remote_security)) if remote.routes("git_operations") => {
+            GitOperationsTool::new(remote_security.clone(), remote.workspace())
+                .with_remote(remote.clone())
+        }
+        _ => GitOperationsTool::new(security.clone(), workspace_dir.to_path_buf()),
+    };
+
     let mut tool_arcs: Vec<Arc<dyn Tool>> = vec![
-        Arc::new(ShellTool::new(security.clone(), runtime)),
+        Arc::new(shell_tool),
         Arc::new(FileReadTool::new(security.clone())),
         Arc::new(FileWriteTool::new(security.clone())),
         Arc::new(FileEditTool::new(security.clone())),
@@ -217,57 +356,74 @@ pub fn all_tools_with_runtime(
         Arc::new(CronRunsTool::new(config.clone())),
         Arc::new(MemoryStoreTool::new(memory.clone(), security.clone())),
         Arc::new(MemoryRecallTool::new(memory.clone())),
+        Arc::new(KvTool::new(memory.clone(), security.clone())),
         Arc::new(MemoryForgetTool::new(memory, security.clone())),
+        Arc::new(PinTool::new(security.clone())),
         Arc::new(ScheduleTool::new(security.clone(), root_config.clone())),
         Arc::new(ModelRoutingConfigTool::new(
             config.clone(),
             security.clone(),
         )),
         Arc::new(ProxyConfigTool::new(config.clone(), security.clone())),
-        Arc::new(GitOperationsTool::new(
+        Arc::new(git_tool),
+        Arc::new(PushoverTool::new(
             security.clone(),
             workspace_dir.to_path_buf(),
         )),
-        Arc::new(PushoverTool::new(
+        Arc::new(ContactsTool::new(
             security.clone(),
             workspace_dir.to_path_buf(),
+            root_config
+                .config_path
+                .parent()
+                .unwrap_or(workspace_dir)
+                .to_path_buf(),
         )),
+        Arc::new(MessageContactTool::new(config.clone(), security.clone())),
     ];
 
+    let network_policies = &root_config.security.network;
+
     if browser_config.enabled {
         // Add legacy browser_open tool for simple URL opening
-        tool_arcs.push(Arc::new(BrowserOpenTool::new(
-            security.clone(),
-            browser_config.allowed_domains.clone(),
-        )));
+        tool_arcs.push(Arc::new(
+            BrowserOpenTool::new(security.clone(), browser_config.allowed_domains.clone())
+                .with_network_guard(NetworkGuard::for_tool(network_policies, "browser_open")),
+        ));
         // Add full browser automation tool (pluggable backend)
-        tool_arcs.push(Arc::new(BrowserTool::new_with_backend(
-            security.clone(),
-            browser_config.allowed_domains.clone(),
-            browser_config.session_name.clone(),
-            browser_config.backend.clone(),
-            browser_config.native_headless,
-            browser_config.native_webdriver_url.clone(),
-            browser_config.native_chrome_path.clone(),
-            ComputerUseConfig {
-                endpoint: browser_config.computer_use.endpoint.clone(),
-                api_key: browser_config.computer_use.api_key.clone(),
-                timeout_ms: browser_config.computer_use.timeout_ms,
-                allow_remote_endpoint: browser_config.computer_use.allow_remote_endpoint,
-                window_allowlist: browser_config.computer_use.window_allowlist.clone(),
-                max_coordinate_x: browser_config.computer_use.max_coordinate_x,
-                max_coordinate_y: browser_config.computer_use.max_coordinate_y,
-            },
-        )));
+        tool_arcs.push(Arc::new(
+            BrowserTool::new_with_backend(
+                security.clone(),
+                browser_config.allowed_domains.clone(),
+                browser_config.session_name.clone(),
+                browser_config.backend.clone(),
+                browser_config.native_headless,
+                browser_config.native_webdriver_url.clone(),
+                browser_config.native_chrome_path.clone(),
+                ComputerUseConfig {
+                    endpoint: browser_config.computer_use.endpoint.clone(),
+                    api_key: browser_config.computer_use.api_key.clone(),
+                    timeout_ms: browser_config.computer_use.timeout_ms,
+                    allow_remote_endpoint: browser_config.computer_use.allow_remote_endpoint,
+                    window_allowlist: browser_config.computer_use.window_allowlist.clone(),
+                    max_coordinate_x: browser_config.computer_use.max_coordinate_x,
+                    max_coordinate_y: browser_config.computer_use.max_coordinate_y,
+                },
+            )
+            .with_network_guard(NetworkGuard::for_tool(network_policies, "browser")),
+        ));
     }
 
     if http_config.enabled {
-        tool_arcs.push(Arc::new(HttpRequestTool::new(
-            security.clone(),
-            http_config.allowed_domains.clone(),
-            http_config.max_response_size,
-            http_config.timeout_secs,
-        )));
+        tool_arcs.push(Arc::new(
+            HttpRequestTool::new(
+                security.clone(),
+                http_config.allowed_domains.clone(),
+                http_config.max_response_size,
+                http_config.timeout_secs,
+            )
+            .with_network_guard(NetworkGuard::for_tool(network_policies, "http_request")),
+        ));
     }
 
     // Web search tool (enabled by default for GLM and other models)
@@ -280,9 +436,135 @@ pub fn all_tools_with_runtime(
         )));
     }
 
+    // Owner question queue for unattended runs
+    if root_config.questions.enabled {
+        tool_arcs.push(Arc::new(AskUserTool::new(config.clone(), security.clone())));
+    }
+
+    // Outbound email (allowlisted recipients, daily cap, draft approval)
+    if root_config.email_send.enabled {
+        tool_arcs.push(Arc::new(EmailSendTool::new(
+            config.clone(),
+            security.clone(),
+        )));
+    }
+
+    // Calendar availability and event creation (events need approval)
+    if root_config.calendar.enabled {
+        tool_arcs.push(Arc::new(CalendarTool::new(
+            config.clone(),
+            security.clone(),
+        )));
+    }
+
+    // Embedded firmware build + flash (pinned toolchains, flash needs approval)
+    if root_config.firmware_build.enabled {
+        tool_arcs.push(Arc::new(FirmwareBuildTool::new(
+            security.clone(),
+            root_config.firmware_build.clone(),
+            workspace_dir,
+        )));
+    }
+
+    // Firmware logs captured over probe-rs RTT (daemon [hardware.logs])
+    if root_config.hardware.logs.enabled {
+        tool_arcs.push(Arc::new(HardwareLogsTool));
+    }
+
+    // BLE thermometers and presence tags ([peripherals.ble])
+    if root_config.peripherals.enabled && root_config.peripherals.ble.enabled {
+        tool_arcs.push(Arc::new(BleSensorsTool::new(
+            root_config.peripherals.ble.clone(),
+        )));
+    }
+
+    // Camera snapshots; vision only when the owner opted in
+    if root_config.camera.enabled {
+        let vision = root_config.camera.allow_vision.then(|| CameraVision {
+            provider: root_config
+                .default_provider
+                .clone()
+                .unwrap_or_else(|| "openrouter".into()),
+            model: root_config
+                .default_model
+                .clone()
+                .unwrap_or_else(|| "anthropic/claude-sonnet-4".into()),
+            api_key: root_config.api_key.clone(),
+            options: provider_runtime_options(root_config),
+            multimodal: root_config.multimodal.clone(),
+            temperature: root_config.default_temperature,
+        });
+        tool_arcs.push(Arc::new(CameraTool::new(
+            security.clone(),
+            root_config.camera.clone(),
+            vision,
+        )));
+    }
+
+    // Zigbee/Z-Wave devices via zigbee2mqtt / zwave-js-ui over MQTT
+    #[cfg(feature = "mqtt")]
+    if root_config.smart_home.enabled {
+        tool_arcs.push(Arc::new(SmartHomeDevicesTool::new(
+            root_config.smart_home.clone(),
+        )));
+        tool_arcs.push(Arc::new(SmartHomeSensorTool::new(
+            root_config.smart_home.clone(),
+        )));
+        tool_arcs.push(Arc::new(SmartHomeSwitchTool::new(
+            security.clone(),
+            root_config.smart_home.clone(),
+        )));
+    }
+
+    // Tools on federated worker nodes (coordinator daemon only)
+    if root_config.federation.role == FederationRole::Coordinator {
+        tool_arcs.push(Arc::new(RemoteNodeTool::new(
+            security.clone(),
+            root_config.federation.call_timeout_secs,
+        )));
+    }
+
+    // Tasks delegated to other instances over their gateways
+    if root_config.peers.name.is_some()
+        && root_config
+            .peers
+            .instances
+            .values()
+            .any(|instance| instance.url.is_some())
+    {
+        tool_arcs.push(Arc::new(PeerDelegateTool::new(
+            security.clone(),
+            root_config.peers.clone(),
+        )));
+    }
+
+    // Static page fetch + Markdown cleanup (lighter alternative to the browser)
+    if root_config.fetch_url.enabled {
+        tool_arcs.push(Arc::new(
+            FetchUrlTool::new(
+                security.clone(),
+                root_config.fetch_url.clone(),
+                workspace_dir,
+            )
+            .with_network_guard(NetworkGuard::for_tool(network_policies, "fetch_url")),
+        ));
+    }
+
+    // GraphQL endpoints with persisted-query allowlists and depth limits
+    if root_config.graphql.enabled {
+        tool_arcs.push(Arc::new(
+            GraphqlTool::new(security.clone(), root_config.graphql.clone(), workspace_dir)
+                .with_network_guard(NetworkGuard::for_tool(network_policies, "graphql")),
+        ));
+    }
+
     // PDF extraction (feature-gated at compile time via rag-pdf)
     tool_arcs.push(Arc::new(PdfReadTool::new(security.clone())));
 
+    // Document conversion (Markdown / HTML / DOCX / PDF; doc-convert feature)
+    #[cfg(feature = "doc-convert")]
+    tool_arcs.push(Arc::new(ConvertDocTool::new(security.clone())));
+
     // Vision tools are always available
     tool_arcs.push(Arc::new(ScreenshotTool::new(security.clone())));
     tool_arcs.push(Arc::new(ImageInfoTool::new(security.clone())));
@@ -300,6 +582,22 @@ pub fn all_tools_with_runtime(
         }
     }
 
+    // Second-person sign-off for high-risk tools; financial checks run first
+    if root_config.security.four_eyes.enabled {
+        tool_arcs = four_eyes::wrap_four_eyes_tools(
+            tool_arcs,
+            &Arc::new(crate::security::four_eyes::FourEyesGuard::from_config(
+                root_config,
+            )),
+        );
+    }
+
+    // Financial guardrails (limits, cooling-off, OTP, audit) for flagged tools
+    tool_arcs = financial_guard::wrap_financial_tools(
+        tool_arcs,
+        &Arc::new(financial_guard(root_config, workspace_dir)),
+    );
+
     // Add delegation tool when agents are configured
     if !agents.is_empty() {
         let delegate_agents: HashMap<String, DelegateAgentConfig> = agents
@@ -315,16 +613,7 @@ pub fn all_tools_with_runtime(
             delegate_agents,
             delegate_fallback_credential,
             security.clone(),
-            crate::providers::ProviderRuntimeOptions {
-                auth_profile_override: None,
-                zeroclaw_dir: root_config
-                    .config_path
-                    .parent()
-                    .map(std::path::PathBuf::from),
-                secrets_encrypt: root_config.secrets.encrypt,
-                provider_api_url: root_config.api_url.clone(),
-                reasoning_enabled: root_config.runtime.reasoning_enabled,
-            },
+            provider_runtime_options(root_config),
         )
         .with_parent_tools(parent_tools)
         .with_multimodal_config(root_config.multimodal.clone());
@@ -0,0 +1,240 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::PeersConfig;
+use crate::peers::{self, Budget, TaskSpec, TaskStatus};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Hands tasks to other ZeroClaw instances listed in `[peers.instances]`
+/// (see [`crate::peers`]).
+pub struct PeerDelegateTool {
+    security: Arc<SecurityPolicy>,
+    config: PeersConfig,
+}
+
+impl PeerDelegateTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: PeersConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    fn list(&self) -> ToolResult {
+        let mut names: Vec<&String> = self
+            .config
+            .instances
+            .iter()
+            .filter(|(_, instance)| instance.url.is_some())
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        if names.is_empty() {
+            return ToolResult {
+                success: true,
+                output: "No peers with a url are configured.".into(),
+                error: None,
+            };
+        }
+        let mut output = format!("{} peers:\n", names.len());
+        for name in names {
+            let _ = writeln!(output, "- {name}");
+        }
+        ToolResult {
+            success: true,
+            output,
+            error: None,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for PeerDelegateTool {
+    fn name(&self) -> &str {
+        "peer_delegate"
+    }
+
+    fn description(&self) -> &str {
+        "Ask another ZeroClaw assistant you know (e.g. the office assistant) to do a task on its side, \
+        such as checking a machine on its network. Action 'list' shows known peers; action 'delegate' \
+        sends a task with optional context and budget and waits for the peer's result."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "delegate"]
+                },
+                "peer": {
+                    "type": "string",
+                    "description": "Peer name (delegate)"
+                },
+                "task": {
+                    "type": "string",
+                    "description": "What the peer should do, self-contained (delegate)"
+                },
+                "context": {
+                    "type": "string",
+                    "description": "Background the peer needs (delegate)"
+                },
+                "max_iterations": {
+                    "type": "integer",
+                    "description": "Most tool iterations the peer may use; capped by the peer"
+                },
+                "max_cost_usd": {
+                    "type": "number",
+                    "description": "Most the peer may spend in USD; capped by the peer"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Longest the peer may work, in seconds; capped by the peer"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let peer = args
+            .get("peer")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        ToolResult {
+            success: true,
+            output: format!("[simulated] would delegate a task to peer '{peer}'"),
+            error: None,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        match action {
+            "list" => Ok(self.list()),
+            "delegate" => {
+                let text = |key: &str| {
+                    args.get(key)
+                        .and_then(serde_json::Value::as_str)
+                        .map(str::trim)
+                        .filter(|value| !value.is_empty())
+                };
+                let (Some(peer), Some(task)) = (text("peer"), text("task")) else {
+                    return Ok(Self::failure("'peer' and 'task' are required for delegate"));
+                };
+                if let Err(error) = self
+                    .security
+                    .enforce_tool_operation(ToolOperation::Act, "peer_delegate")
+                {
+                    return Ok(Self::failure(error));
+                }
+                let spec = TaskSpec {
+                    task: task.to_string(),
+                    context: text("context").map(str::to_string),
+                    budget: Budget {
+                        max_iterations: args
+                            .get("max_iterations")
+                            .and_then(serde_json::Value::as_u64)
+                            .and_then(|n| usize::try_from(n).ok()),
+                        max_cost_usd: args.get("max_cost_usd").and_then(serde_json::Value::as_f64),
+                        timeout_secs: args.get("timeout_secs").and_then(serde_json::Value::as_u64),
+                    },
+                };
+                match peers::delegate(&self.config, peer, &spec).await {
+                    Ok(TaskStatus::Completed { output }) => Ok(ToolResult {
+                        success: true,
+                        output: format!("Result from '{peer}':\n{output}"),
+                        error: None,
+                    }),
+                    Ok(TaskStatus::Failed { error }) => {
+                        Ok(Self::failure(format!("'{peer}' could not finish: {error}")))
+                    }
+                    Ok(TaskStatus::TimedOut | TaskStatus::Running) => Ok(Self::failure(format!(
+                        "'{peer}' did not finish within the budget"
+                    ))),
+                    Err(e) => Ok(Self::failure(format!("{e:#}"))),
+                }
+            }
+            other => Ok(Self::failure(format!(
+                "Unknown action '{other}' (expected list or delegate)"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PeerInstanceConfig;
+    use crate::security::AutonomyLevel;
+
+    fn config() -> PeersConfig {
+        let mut config = PeersConfig {
+            name: Some("home".into()),
+            ..PeersConfig::default()
+        };
+        for (name, url) in [
+            ("office", Some("https://office.example.com")),
+            ("cabin", None),
+        ] {
+            config.instances.insert(
+                name.into(),
+                PeerInstanceConfig {
+                    url: url.map(str::to_string),
+                    token: "home-office-shared-token".into(),
+                },
+            );
+        }
+        config
+    }
+
+    #[tokio::test]
+    async fn list_shows_reachable_peers_only() {
+        let tool = PeerDelegateTool::new(Arc::new(SecurityPolicy::default()), config());
+        let result = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert!(result.output.contains("- office"));
+        assert!(!result.output.contains("cabin"));
+    }
+
+    #[tokio::test]
+    async fn delegate_validates_input_and_respects_read_only_mode() {
+        let tool = PeerDelegateTool::new(Arc::new(SecurityPolicy::default()), config());
+        let missing = tool
+            .execute(json!({"action": "delegate", "peer": "office"}))
+            .await
+            .unwrap();
+        assert!(missing.error.unwrap().contains("required"));
+
+        let no_url = tool
+            .execute(json!({"action": "delegate", "peer": "cabin", "task": "check the boiler"}))
+            .await
+            .unwrap();
+        assert!(no_url.error.unwrap().contains("no url"));
+
+        let read_only = PeerDelegateTool::new(
+            Arc::new(SecurityPolicy {
+                autonomy: AutonomyLevel::ReadOnly,
+                ..SecurityPolicy::default()
+            }),
+            config(),
+        );
+        let blocked = read_only
+            .execute(json!({"action": "delegate", "peer": "cabin", "task": "check the boiler"}))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+        assert!(!blocked.error.unwrap().contains("no url"));
+    }
+}
@@ -0,0 +1,224 @@
+use super::traits::{Tool, ToolResult};
+use crate::pins::{PinKind, PinStore};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Let the agent pin memories, files or notes so they stay in context
+pub struct PinTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl PinTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+
+    fn store(&self) -> PinStore {
+        PinStore::for_workspace(&self.security.workspace_dir)
+    }
+
+    fn failure(error: String) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for PinTool {
+    fn name(&self) -> &str {
+        "pin"
+    }
+
+    fn description(&self) -> &str {
+        "Pin a memory (by key), a workspace file (by relative path) or a short note so it stays in the prompt for the rest of the conversation, even after history is compacted. Pins share a token budget. Actions: add, remove, list."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["add", "remove", "list"],
+                    "description": "What to do"
+                },
+                "kind": {
+                    "type": "string",
+                    "enum": ["memory", "file", "note"],
+                    "description": "What to pin (for add)"
+                },
+                "target": {
+                    "type": "string",
+                    "description": "Memory key, workspace-relative file path or note text (for add)"
+                },
+                "id": {
+                    "type": "integer",
+                    "description": "Pin id (for remove)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        ToolResult {
+            success: true,
+            output: format!("[simulated] Would {action} a pin"),
+            error: None,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+
+        if action == "list" {
+            let pins = self.store().list()?;
+            if pins.is_empty() {
+                return Ok(ToolResult {
+                    success: true,
+                    output: "No pins.".into(),
+                    error: None,
+                });
+            }
+            let mut output = String::new();
+            for pin in pins {
+                let _ = writeln!(output, "#{} {} {}", pin.id, pin.kind, pin.target);
+            }
+            return Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            });
+        }
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "pin")
+        {
+            return Ok(Self::failure(error));
+        }
+
+        match action {
+            "add" => {
+                let kind = args
+                    .get("kind")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'kind' parameter"))?;
+                let target = args
+                    .get("target")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'target' parameter"))?;
+                let kind = PinKind::parse(kind)?;
+                if kind == PinKind::File && !self.security.is_path_allowed(target) {
+                    return Ok(Self::failure(format!(
+                        "Path not allowed by security policy: {target}"
+                    )));
+                }
+                match self.store().add(kind, target) {
+                    Ok(pin) => Ok(ToolResult {
+                        success: true,
+                        output: format!("Pinned #{}: {} {}", pin.id, pin.kind, pin.target),
+                        error: None,
+                    }),
+                    Err(e) => Ok(Self::failure(format!("Failed to pin: {e}"))),
+                }
+            }
+            "remove" => {
+                let id = args
+                    .get("id")
+                    .and_then(serde_json::Value::as_u64)
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'id' parameter"))?;
+                match self.store().remove(id) {
+                    Ok(Some(pin)) => Ok(ToolResult {
+                        success: true,
+                        output: format!("Unpinned #{id}: {} {}", pin.kind, pin.target),
+                        error: None,
+                    }),
+                    Ok(None) => Ok(ToolResult {
+                        success: true,
+                        output: format!("No pin #{id}"),
+                        error: None,
+                    }),
+                    Err(e) => Ok(Self::failure(format!("Failed to unpin: {e}"))),
+                }
+            }
+            other => Ok(Self::failure(format!(
+                "Unknown action '{other}' (expected add, remove or list)"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn tool(tmp: &TempDir, autonomy: AutonomyLevel) -> PinTool {
+        PinTool::new(Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        }))
+    }
+
+    #[tokio::test]
+    async fn add_list_and_remove_pins() {
+        let tmp = TempDir::new().unwrap();
+        let tool = tool(&tmp, AutonomyLevel::Supervised);
+
+        let added = tool
+            .execute(json!({"action": "add", "kind": "file", "target": "notes/plan.md"}))
+            .await
+            .unwrap();
+        assert!(added.success, "{:?}", added.error);
+        assert!(added.output.starts_with("Pinned #1"));
+
+        let listed = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert_eq!(listed.output, "#1 file notes/plan.md\n");
+
+        let escaped = tool
+            .execute(json!({"action": "add", "kind": "file", "target": "../outside.md"}))
+            .await
+            .unwrap();
+        assert!(!escaped.success);
+
+        let removed = tool
+            .execute(json!({"action": "remove", "id": 1}))
+            .await
+            .unwrap();
+        assert!(removed.output.starts_with("Unpinned #1"));
+    }
+
+    #[tokio::test]
+    async fn read_only_autonomy_blocks_changes() {
+        let tmp = TempDir::new().unwrap();
+        let tool = tool(&tmp, AutonomyLevel::ReadOnly);
+        let result = tool
+            .execute(json!({"action": "add", "kind": "note", "target": "be brief"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(
+            tool.execute(json!({"action": "list"}))
+                .await
+                .unwrap()
+                .success
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use super::traits::{Tool, ToolResult};
+use crate::federation::coordinator;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Lists federated worker nodes and runs their exposed tools
+/// (see [`crate::federation`]).
+pub struct RemoteNodeTool {
+    security: Arc<SecurityPolicy>,
+    call_timeout: Duration,
+}
+
+impl RemoteNodeTool {
+    pub fn new(security: Arc<SecurityPolicy>, call_timeout_secs: u64) -> Self {
+        Self {
+            security,
+            call_timeout: Duration::from_secs(call_timeout_secs.max(1)),
+        }
+    }
+
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    fn list() -> ToolResult {
+        let nodes = coordinator::connected_nodes();
+        if nodes.is_empty() {
+            return ToolResult {
+                success: true,
+                output: "No worker nodes are connected.".into(),
+                error: None,
+            };
+        }
+        let mut output = format!("{} connected nodes:\n", nodes.len());
+        for node in &nodes {
+            let _ = writeln!(
+                output,
+                "\n## {} (connected since {})",
+                node.name,
+                node.connected_at.to_rfc3339()
+            );
+            for spec in &node.tools {
+                let _ = writeln!(
+                    output,
+                    "- {}: {}\n  parameters: {}",
+                    spec.name, spec.description, spec.parameters
+                );
+            }
+        }
+        ToolResult {
+            success: true,
+            output,
+            error: None,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for RemoteNodeTool {
+    fn name(&self) -> &str {
+        "remote_node"
+    }
+
+    fn description(&self) -> &str {
+        "Use tools on federated worker nodes (e.g. Raspberry Pis with sensors or GPIO at other locations). \
+        Action 'list' shows connected nodes and the tools each exposes with their parameters; \
+        action 'call' runs one of those tools on a node."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "call"]
+                },
+                "node": {
+                    "type": "string",
+                    "description": "Node name (call)"
+                },
+                "tool": {
+                    "type": "string",
+                    "description": "Tool exposed by the node (call)"
+                },
+                "arguments": {
+                    "type": "object",
+                    "description": "Arguments for the remote tool, matching its parameters (call)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let node = args
+            .get("node")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let tool = args
+            .get("tool")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        ToolResult {
+            success: true,
+            output: format!("[simulated] would run '{tool}' on node '{node}'"),
+            error: None,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        match action {
+            "list" => Ok(Self::list()),
+            "call" => {
+                let node = args
+                    .get("node")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::trim)
+                    .unwrap_or_default();
+                let tool = args
+                    .get("tool")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::trim)
+                    .unwrap_or_default();
+                if node.is_empty() || tool.is_empty() {
+                    return Ok(Self::failure("'node' and 'tool' are required for call"));
+                }
+                if let Err(error) = self
+                    .security
+                    .enforce_tool_operation(ToolOperation::Act, "remote_node")
+                {
+                    return Ok(Self::failure(error));
+                }
+                let arguments = args.get("arguments").cloned().unwrap_or_else(|| json!({}));
+                match coordinator::call(node, tool, arguments, self.call_timeout).await {
+                    Ok(result) => Ok(result),
+                    Err(e) => Ok(Self::failure(e.to_string())),
+                }
+            }
+            other => Ok(Self::failure(format!(
+                "Unknown action '{other}' (expected list or call)"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    #[tokio::test]
+    async fn call_requires_node_and_is_blocked_in_read_only_mode() {
+        let tool = RemoteNodeTool::new(Arc::new(SecurityPolicy::default()), 5);
+        let missing = tool.execute(json!({"action": "call"})).await.unwrap();
+        assert!(missing.error.unwrap().contains("required"));
+
+        let offline = tool
+            .execute(json!({"action": "call", "node": "nowhere", "tool": "gpio_read"}))
+            .await
+            .unwrap();
+        assert!(offline.error.unwrap().contains("not connected"));
+
+        let read_only = RemoteNodeTool::new(
+            Arc::new(SecurityPolicy {
+                autonomy: AutonomyLevel::ReadOnly,
+                ..SecurityPolicy::default()
+            }),
+            5,
+        );
+        let blocked = read_only
+            .execute(json!({"action": "call", "node": "nowhere", "tool": "gpio_read"}))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+        assert!(!blocked.error.unwrap().contains("not connected"));
+    }
+}
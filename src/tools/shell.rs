@@ -16,6 +16,19 @@ const MAX_OUTPUT_BYTES: usize = 1_048_576;
 const SAFE_ENV_VARS: &[&str] = &[
     "PATH", "HOME", "TERM", "LANG", "LC_ALL", "LC_CTYPE", "USER", "SHELL", "TMPDIR",
 ];
+/// Variables `cmd.exe` and PowerShell need to start and resolve programs.
+const WINDOWS_SAFE_ENV_VARS: &[&str] = &[
+    "SystemRoot",
+    "SystemDrive",
+    "windir",
+    "ComSpec",
+    "PATHEXT",
+    "USERPROFILE",
+    "USERNAME",
+    "TEMP",
+    "TMP",
+    "PSModulePath",
+];
 
 /// Shell command execution tool with sandboxing
 pub struct ShellTool {
@@ -41,8 +54,14 @@ fn is_valid_env_var_name(name: &str) -> bool {
 fn collect_allowed_shell_env_vars(security: &SecurityPolicy) -> Vec<String> {
     let mut out = Vec::new();
     let mut seen = HashSet::new();
+    let windows_vars: &[&str] = if security.shell_dialect.is_windows() {
+        WINDOWS_SAFE_ENV_VARS
+    } else {
+        &[]
+    };
     for key in SAFE_ENV_VARS
         .iter()
+        .chain(windows_vars)
         .copied()
         .chain(security.shell_env_passthrough.iter().map(|s| s.as_str()))
     {
@@ -85,6 +104,18 @@ impl Tool for ShellTool {
         })
     }
 
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let command = args
+            .get("command")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        ToolResult {
+            success: true,
+            output: format!("[simulated] $ {command}\n(command not executed; exit code 0 assumed)"),
+            error: None,
+        }
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let command = args
             .get("command")
@@ -252,6 +283,15 @@ mod tests {
         assert!(result.error.is_none());
     }
 
+    #[test]
+    fn shell_simulate_previews_command_without_running() {
+        let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
+        let result = tool.simulate(&json!({"command": "touch /tmp/zeroclaw-simulated"}));
+        assert!(result.success);
+        assert!(result.output.contains("$ touch /tmp/zeroclaw-simulated"));
+        assert!(result.output.contains("not executed"));
+    }
+
     #[tokio::test]
     async fn shell_blocks_disallowed_command() {
         let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
@@ -513,6 +553,23 @@ mod tests {
         assert!(!vars.contains(&"1NOPE".to_string()));
     }
 
+    #[test]
+    fn windows_dialect_passes_shell_startup_vars() {
+        let security = SecurityPolicy {
+            shell_dialect: crate::security::ShellDialect::PowerShell,
+            ..SecurityPolicy::default()
+        };
+        let vars = collect_allowed_shell_env_vars(&security);
+        assert!(vars.contains(&"SystemRoot".to_string()));
+        assert!(vars.contains(&"PATHEXT".to_string()));
+
+        let posix = collect_allowed_shell_env_vars(&SecurityPolicy {
+            shell_dialect: crate::security::ShellDialect::Posix,
+            ..SecurityPolicy::default()
+        });
+        assert!(!posix.contains(&"SystemRoot".to_string()));
+    }
+
     #[tokio::test]
     async fn shell_requires_approval_for_medium_risk_command() {
         let security = Arc::new(SecurityPolicy {
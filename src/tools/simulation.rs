@@ -0,0 +1,195 @@
+//! Simulation (dry-run) mode for tool execution.
+//!
+//! When simulation is active, every tool call is answered by the tool's own
+//! [`Tool::simulate`] mock instead of [`Tool::execute`], so users can preview
+//! what an autonomous task would do before granting approval. Simulation is
+//! enabled for a whole session with `zeroclaw agent --simulate`, or for a
+//! single message by prefixing it with the `/simulate` directive. Channels
+//! share one tool registry across conversations, so they refuse the directive
+//! rather than run the turn for real.
+
+use super::traits::{Tool, ToolResult, ToolSpec};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Per-message directive that runs one turn in simulation mode.
+pub const SIMULATE_DIRECTIVE: &str = "/simulate";
+
+/// Tool wrapper that routes calls to [`Tool::simulate`] while the shared
+/// switch is on and to the wrapped tool otherwise.
+pub struct SimulatableTool {
+    inner: Box<dyn Tool>,
+    active: Arc<AtomicBool>,
+}
+
+impl SimulatableTool {
+    pub fn new(inner: Box<dyn Tool>, active: Arc<AtomicBool>) -> Self {
+        Self { inner, active }
+    }
+}
+
+#[async_trait]
+impl Tool for SimulatableTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.inner.parameters_schema()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if self.active.load(Ordering::Relaxed) {
+            tracing::info!(tool = self.inner.name(), "Simulating tool call");
+            return Ok(self.inner.simulate(&args));
+        }
+        self.inner.execute(args).await
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        self.inner.simulate(args)
+    }
+
+    fn change_preview(&self, args: &serde_json::Value) -> Option<String> {
+        // Simulated calls change nothing, so there is nothing to preview or record.
+        if self.active.load(Ordering::Relaxed) {
+            return None;
+        }
+        self.inner.change_preview(args)
+    }
+
+    fn financial(&self) -> bool {
+        self.inner.financial()
+    }
+
+    fn spec(&self) -> ToolSpec {
+        self.inner.spec()
+    }
+}
+
+/// Wrap a tool registry so it can be switched into simulation mode at runtime.
+pub fn wrap_tools(tools: Vec<Box<dyn Tool>>, active: &Arc<AtomicBool>) -> Vec<Box<dyn Tool>> {
+    tools
+        .into_iter()
+        .map(|tool| Box::new(SimulatableTool::new(tool, Arc::clone(active))) as Box<dyn Tool>)
+        .collect()
+}
+
+/// Strip a leading `/simulate` directive, returning the remaining message.
+///
+/// Returns `None` when the message does not start with the directive.
+pub fn strip_simulate_directive(message: &str) -> Option<&str> {
+    let rest = message.trim_start().strip_prefix(SIMULATE_DIRECTIVE)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(rest.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    struct CountingTool {
+        executions: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Tool for CountingTool {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn description(&self) -> &str {
+            "Counts real executions"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            self.executions.fetch_add(1, Ordering::SeqCst);
+            Ok(ToolResult {
+                success: true,
+                output: "executed".into(),
+                error: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn simulation_switch_routes_between_execute_and_simulate() {
+        let executions = Arc::new(AtomicUsize::new(0));
+        let active = Arc::new(AtomicBool::new(true));
+        let tools = wrap_tools(
+            vec![Box::new(CountingTool {
+                executions: Arc::clone(&executions),
+            })],
+            &active,
+        );
+        let args = serde_json::json!({ "value": 1 });
+
+        let simulated = tools[0].execute(args.clone()).await.unwrap();
+        assert!(simulated.success);
+        assert!(simulated.output.starts_with("[simulated] counting"));
+        assert_eq!(executions.load(Ordering::SeqCst), 0);
+
+        active.store(false, Ordering::Relaxed);
+        let real = tools[0].execute(args).await.unwrap();
+        assert_eq!(real.output, "executed");
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+        assert_eq!(tools[0].spec().name, "counting");
+    }
+
+    struct PaymentTool;
+
+    #[async_trait]
+    impl Tool for PaymentTool {
+        fn name(&self) -> &str {
+            "payment"
+        }
+
+        fn description(&self) -> &str {
+            "Moves money"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            unreachable!("not executed in this test")
+        }
+
+        fn financial(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn wrapper_keeps_financial_marker() {
+        let tools = wrap_tools(
+            vec![Box::new(PaymentTool)],
+            &Arc::new(AtomicBool::new(false)),
+        );
+        assert!(tools[0].financial());
+    }
+
+    #[test]
+    fn strip_simulate_directive_requires_word_boundary() {
+        assert_eq!(
+            strip_simulate_directive("/simulate  clean up /tmp"),
+            Some("clean up /tmp")
+        );
+        assert_eq!(strip_simulate_directive("/simulate"), Some(""));
+        assert_eq!(strip_simulate_directive("/simulated run"), None);
+        assert_eq!(strip_simulate_directive("clean up /tmp"), None);
+    }
+}
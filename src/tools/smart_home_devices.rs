@@ -0,0 +1,81 @@
+//! Smart home devices tool — list Zigbee/Z-Wave devices discovered over MQTT.
+//!
+//! Use when the user asks "which lights do I have?", "what sensors are in the
+//! hall?", etc. Reads the `[smart_home]` device registry.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::SmartHomeConfig;
+use crate::smart_home;
+use async_trait::async_trait;
+use serde_json::json;
+
+/// Tool: list discovered devices with their class and current reading.
+pub struct SmartHomeDevicesTool {
+    config: SmartHomeConfig,
+}
+
+impl SmartHomeDevicesTool {
+    pub fn new(config: SmartHomeConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Tool for SmartHomeDevicesTool {
+    fn name(&self) -> &str {
+        "smart_home_devices"
+    }
+
+    fn description(&self) -> &str {
+        "List Zigbee/Z-Wave smart home devices (lights, switches, contact, motion and climate sensors) \
+        with their current state. Use the returned names with smart_home_switch and smart_home_sensor. \
+        Optional param: class (light, switch, contact_sensor, motion_sensor, climate_sensor, other)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "class": {
+                    "type": "string",
+                    "enum": ["light", "switch", "contact_sensor", "motion_sensor", "climate_sensor", "other"],
+                    "description": "Only devices of this class."
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let class = args.get("class").and_then(serde_json::Value::as_str);
+        smart_home::ready(&self.config).await;
+
+        let devices: Vec<serde_json::Value> = smart_home::devices()
+            .iter()
+            .filter(|device| class.is_none_or(|class| device.class.as_str() == class))
+            .map(|device| {
+                json!({
+                    "name": device.name,
+                    "class": device.class,
+                    "bridge": device.bridge,
+                    "model": device.model,
+                    "state": device.reading(),
+                    "updated": device.updated.map(|at| at.to_rfc3339()),
+                })
+            })
+            .collect();
+        if devices.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: "No matching devices discovered. Check that the MQTT broker is reachable \
+                    and the bridge topics in [smart_home] match zigbee2mqtt / zwave-js-ui."
+                    .into(),
+                error: None,
+            });
+        }
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&devices)?,
+            error: None,
+        })
+    }
+}
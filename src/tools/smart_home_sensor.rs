@@ -0,0 +1,97 @@
+//! Smart home sensor tool — read a Zigbee/Z-Wave device's current state.
+//!
+//! Use when the user asks "is the front door closed?", "how warm is the
+//! bedroom?", "is the kitchen light on?", etc.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::SmartHomeConfig;
+use crate::smart_home;
+use async_trait::async_trait;
+use serde_json::json;
+
+/// Tool: typed reading of one device (contact, motion, temperature, on/off...).
+pub struct SmartHomeSensorTool {
+    config: SmartHomeConfig,
+}
+
+impl SmartHomeSensorTool {
+    pub fn new(config: SmartHomeConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Tool for SmartHomeSensorTool {
+    fn name(&self) -> &str {
+        "smart_home_sensor"
+    }
+
+    fn description(&self) -> &str {
+        "Read the current state of a smart home device: contact sensors report open/closed, \
+        motion sensors motion true/false, climate sensors temperature/humidity, lights and switches on/off \
+        (plus battery when reported). Param: device (name from smart_home_devices)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "device": {
+                    "type": "string",
+                    "description": "Device name, e.g. \"front_door\""
+                }
+            },
+            "required": ["device"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let name = args
+            .get("device")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        if name.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Missing 'device' parameter".into()),
+            });
+        }
+
+        smart_home::ready(&self.config).await;
+        let Some(device) = smart_home::device(name) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Unknown device '{name}'; use smart_home_devices to list devices"
+                )),
+            });
+        };
+        let output = json!({
+            "name": device.name,
+            "class": device.class,
+            "state": device.reading(),
+            "updated": device.updated.map(|at| at.to_rfc3339()),
+        });
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&output)?,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn requires_device_name() {
+        let tool = SmartHomeSensorTool::new(SmartHomeConfig::default());
+        let result = tool.execute(json!({"device": "  "})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("device"));
+    }
+}
@@ -0,0 +1,170 @@
+//! Smart home switch tool — turn Zigbee/Z-Wave lights and switches on/off.
+//!
+//! Use when the user asks "turn off the kitchen light", "dim the hall to 30%",
+//! "toggle the heater plug", etc. Publishes via the `[smart_home]` bridge.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::SmartHomeConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use crate::smart_home::{self, SwitchAction};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Tool: switch a light or switch, optionally setting brightness.
+pub struct SmartHomeSwitchTool {
+    security: Arc<SecurityPolicy>,
+    config: SmartHomeConfig,
+}
+
+impl SmartHomeSwitchTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: SmartHomeConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for SmartHomeSwitchTool {
+    fn name(&self) -> &str {
+        "smart_home_switch"
+    }
+
+    fn description(&self) -> &str {
+        "Turn a smart home light or switch on, off or toggle it; dimmable lights accept brightness (0-100%). \
+        Params: device (name from smart_home_devices), action (on/off/toggle), brightness (optional)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "device": {
+                    "type": "string",
+                    "description": "Light or switch name, e.g. \"kitchen/ceiling\""
+                },
+                "action": {
+                    "type": "string",
+                    "enum": ["on", "off", "toggle"]
+                },
+                "brightness": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": 100,
+                    "description": "Brightness in percent (dimmable lights, with action on)"
+                }
+            },
+            "required": ["device", "action"]
+        })
+    }
+
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        let device = args
+            .get("device")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        ToolResult {
+            success: true,
+            output: format!("[simulated] would switch '{device}' {action}"),
+            error: None,
+        }
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let name = args
+            .get("device")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        let Some(action) = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .and_then(SwitchAction::parse)
+        else {
+            return Ok(Self::failure("'action' must be on, off or toggle"));
+        };
+        if name.is_empty() {
+            return Ok(Self::failure("Missing 'device' parameter"));
+        }
+        let brightness = match args.get("brightness").map(serde_json::Value::as_u64) {
+            None => None,
+            Some(Some(pct)) if pct <= 100 => u8::try_from(pct).ok(),
+            Some(_) => return Ok(Self::failure("'brightness' must be between 0 and 100")),
+        };
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "smart_home_switch")
+        {
+            return Ok(Self::failure(error));
+        }
+
+        smart_home::ready(&self.config).await;
+        let Some(device) = smart_home::device(name) else {
+            return Ok(Self::failure(format!(
+                "Unknown device '{name}'; use smart_home_devices to list devices"
+            )));
+        };
+        let (topic, payload) = match device.switch_command(action, brightness) {
+            Ok(command) => command,
+            Err(e) => return Ok(Self::failure(e.to_string())),
+        };
+        if let Err(e) = smart_home::publish(&topic, &payload).await {
+            return Ok(Self::failure(e.to_string()));
+        }
+        Ok(ToolResult {
+            success: true,
+            output: format!("Sent {payload} to {} ({topic})", device.name),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    #[tokio::test]
+    async fn validates_arguments_and_is_blocked_in_read_only_mode() {
+        let tool = SmartHomeSwitchTool::new(
+            Arc::new(SecurityPolicy::default()),
+            SmartHomeConfig::default(),
+        );
+        let bad_action = tool
+            .execute(json!({"device": "lamp", "action": "dim"}))
+            .await
+            .unwrap();
+        assert!(bad_action.error.unwrap().contains("action"));
+        let bad_brightness = tool
+            .execute(json!({"device": "lamp", "action": "on", "brightness": 150}))
+            .await
+            .unwrap();
+        assert!(bad_brightness.error.unwrap().contains("brightness"));
+
+        let read_only = SmartHomeSwitchTool::new(
+            Arc::new(SecurityPolicy {
+                autonomy: AutonomyLevel::ReadOnly,
+                ..SecurityPolicy::default()
+            }),
+            SmartHomeConfig::default(),
+        );
+        let blocked = read_only
+            .execute(json!({"device": "lamp", "action": "on"}))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+        assert!(!blocked.error.unwrap().contains("Unknown device"));
+    }
+}
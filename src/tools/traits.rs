@@ -32,6 +32,33 @@ pub trait Tool: Send + Sync {
     /// Execute the tool with given arguments
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult>;
 
+    /// Mock result returned in simulation mode instead of executing.
+    ///
+    /// Tools with side effects should override this with a realistic preview
+    /// of what `execute` would do; it must never touch the outside world.
+    fn simulate(&self, args: &serde_json::Value) -> ToolResult {
+        ToolResult {
+            success: true,
+            output: format!(
+                "[simulated] {} would run with arguments: {args}",
+                self.name()
+            ),
+            error: None,
+        }
+    }
+
+    /// Preview of the change this call would make (a unified diff for file
+    /// writes), shown in approval prompts and recorded once the call succeeds.
+    fn change_preview(&self, _args: &serde_json::Value) -> Option<String> {
+        None
+    }
+
+    /// Whether calls move money. Financial tools are wrapped with the
+    /// `[security.financial]` guardrails (limits, cooling-off, OTP, audit).
+    fn financial(&self) -> bool {
+        false
+    }
+
     /// Get the full spec for LLM registration
     fn spec(&self) -> ToolSpec {
         ToolSpec {
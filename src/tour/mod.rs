@@ -0,0 +1,364 @@
+//! `zeroclaw tour`: a guided first run.
+//!
+//! The tour works through a short script against the user's real config: ask
+//! the agent a question, keep a memory, schedule a cron job and snapshot a
+//! demo page in the browser. Each step checks that its subsystem answered and
+//! shows the commands that do the same by hand. Steps the config cannot run
+//! yet are skipped with the setting that enables them.
+
+use crate::config::Config;
+use crate::cron::{self, Schedule};
+use crate::datetime::UserClock;
+use crate::memory::{self, MemoryBackendKind, MemoryCategory};
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use console::style;
+use dialoguer::{Confirm, Input};
+use std::io::IsTerminal;
+
+/// Page the browser step opens (a domain reserved for documentation).
+const DEMO_URL: &str = "https://example.com";
+const DEMO_HOST: &str = "example.com";
+const DEMO_HEADING: &str = "Example Domain";
+
+const MEMORY_KEY: &str = "tour_note";
+const DEFAULT_NOTE: &str = "I took the ZeroClaw tour.";
+
+const CRON_NAME: &str = "tour";
+const CRON_EXPRESSION: &str = "0 9 * * MON";
+const CRON_COMMAND: &str = "echo 'Hello from the ZeroClaw tour'";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Step {
+    Ask,
+    Memory,
+    Cron,
+    Browser,
+}
+
+impl Step {
+    const ALL: [Self; 4] = [Self::Ask, Self::Memory, Self::Cron, Self::Browser];
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Ask => "Ask a question",
+            Self::Memory => "Keep a memory",
+            Self::Cron => "Schedule a job",
+            Self::Browser => "Snapshot a web page",
+        }
+    }
+
+    fn explanation(self) -> &'static str {
+        match self {
+            Self::Ask => {
+                "The agent answers with your configured provider and model, using tools when it needs them."
+            }
+            Self::Memory => {
+                "Memories persist across sessions; the agent recalls relevant ones before it answers."
+            }
+            Self::Cron => {
+                "Cron jobs run shell commands or agent prompts on a schedule while the daemon is up."
+            }
+            Self::Browser => {
+                "The browser tool opens allowlisted pages and reads them as structured snapshots."
+            }
+        }
+    }
+
+    fn commands(self) -> &'static [&'static str] {
+        match self {
+            Self::Ask => &[
+                "zeroclaw agent -m \"What can you do for me?\"",
+                "zeroclaw agent",
+            ],
+            Self::Memory => &[
+                "zeroclaw memory get tour_note",
+                "zeroclaw memory list",
+                "zeroclaw memory clear --key tour_note",
+            ],
+            Self::Cron => &[
+                "zeroclaw cron add \"0 9 * * MON\" \"echo hello\"",
+                "zeroclaw cron list",
+                "zeroclaw cron remove <id>",
+            ],
+            Self::Browser => &[
+                "zeroclaw agent -m \"Open https://example.com in the browser and summarize it\"",
+            ],
+        }
+    }
+}
+
+enum Outcome {
+    Passed(String),
+    Failed(String),
+    Skipped(String),
+}
+
+/// Why `step` cannot run with `config`, if it cannot.
+fn unavailable(config: &Config, step: Step) -> Option<String> {
+    match step {
+        Step::Ask => None,
+        Step::Memory => {
+            let backend = memory::effective_memory_backend_name(
+                &config.memory.backend,
+                Some(&config.storage.provider.config),
+            );
+            (memory::classify_memory_backend(&backend) == MemoryBackendKind::None).then(|| {
+                "Memory is disabled. Set [memory].backend = \"sqlite\" to keep notes.".to_string()
+            })
+        }
+        Step::Cron => (!config.cron.enabled)
+            .then(|| "Cron is disabled. Set [cron].enabled = true to schedule jobs.".to_string()),
+        Step::Browser => {
+            if !config.browser.enabled {
+                Some(format!(
+                    "The browser is disabled. Set [browser].enabled = true and add \"{DEMO_HOST}\" to [browser].allowed_domains."
+                ))
+            } else if !crate::tools::browser::host_matches_allowlist(
+                DEMO_HOST,
+                &config.browser.allowed_domains,
+            ) {
+                Some(format!(
+                    "Add \"{DEMO_HOST}\" to [browser].allowed_domains to try the demo page."
+                ))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Run the tour, or only `only`. `yes` runs every step without asking.
+pub async fn run(config: &Config, only: Option<Step>, yes: bool) -> Result<()> {
+    let interactive = !yes && std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    let steps = only.map_or_else(|| Step::ALL.to_vec(), |step| vec![step]);
+
+    println!("{}", style("🦀 Welcome to the ZeroClaw tour").bold());
+    println!(
+        "Each step uses your real config ({}) and shows the commands behind it.",
+        config.config_path.display()
+    );
+
+    let mut results = Vec::with_capacity(steps.len());
+    for (index, step) in steps.iter().copied().enumerate() {
+        println!();
+        println!(
+            "{}",
+            style(format!(
+                "Step {}/{} — {}",
+                index + 1,
+                steps.len(),
+                step.title()
+            ))
+            .cyan()
+            .bold()
+        );
+        println!("  {}", step.explanation());
+
+        let outcome = if let Some(reason) = unavailable(config, step) {
+            Outcome::Skipped(reason)
+        } else if interactive
+            && !Confirm::new()
+                .with_prompt("  Run this step?")
+                .default(true)
+                .interact()?
+        {
+            Outcome::Skipped("Skipped.".to_string())
+        } else {
+            let result = match step {
+                Step::Ask => ask(config).await,
+                Step::Memory => remember(config, interactive).await,
+                Step::Cron => schedule(config, interactive),
+                Step::Browser => snapshot(config).await,
+            };
+            match result {
+                Ok(detail) => Outcome::Passed(detail),
+                Err(e) => Outcome::Failed(format!("{e:#}")),
+            }
+        };
+
+        match &outcome {
+            Outcome::Passed(detail) => println!("  {} {detail}", style("✓").green().bold()),
+            Outcome::Failed(detail) => println!("  {} {detail}", style("✗").red().bold()),
+            Outcome::Skipped(detail) => println!("  {} {detail}", style("–").yellow().bold()),
+        }
+        println!("  Try it yourself:");
+        for command in step.commands() {
+            println!("    {}", style(command).dim());
+        }
+        results.push((step, outcome));
+    }
+
+    println!();
+    println!("{}", style("Tour summary").bold());
+    for (step, outcome) in &results {
+        let mark = match outcome {
+            Outcome::Passed(_) => style("✓").green(),
+            Outcome::Failed(_) => style("✗").red(),
+            Outcome::Skipped(_) => style("–").yellow(),
+        };
+        println!("  {mark} {}", step.title());
+    }
+    println!();
+    println!("Next: `zeroclaw agent` to chat, `zeroclaw daemon` to run channels and jobs, `zeroclaw doctor` to check your setup.");
+
+    let failed = results
+        .iter()
+        .filter(|(_, outcome)| matches!(outcome, Outcome::Failed(_)))
+        .count();
+    if failed > 0 {
+        bail!("{failed} tour step(s) failed");
+    }
+    Ok(())
+}
+
+async fn ask(config: &Config) -> Result<String> {
+    let reply = Box::pin(crate::agent::run(
+        config.clone(),
+        Some("In two sentences, introduce yourself and name one thing you can do for me.".into()),
+        None,
+        None,
+        config.default_temperature,
+        vec![],
+        false,
+        false,
+        Vec::new(),
+        crate::handoff::CliHandoff::default(),
+    ))
+    .await?;
+    if reply.trim().is_empty() {
+        bail!("The model returned an empty reply");
+    }
+    Ok(format!(
+        "{} answered.",
+        config.default_provider.as_deref().unwrap_or("openrouter")
+    ))
+}
+
+async fn remember(config: &Config, interactive: bool) -> Result<String> {
+    let note = if interactive {
+        Input::<String>::new()
+            .with_prompt("  Something for me to remember")
+            .default(DEFAULT_NOTE.to_string())
+            .interact_text()?
+    } else {
+        DEFAULT_NOTE.to_string()
+    };
+    let mem = memory::create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    mem.store(MEMORY_KEY, &note, MemoryCategory::Core, None)
+        .await?;
+    let entry = mem
+        .get(MEMORY_KEY)
+        .await?
+        .context("The note was not found after storing it")?;
+    if entry.content != note {
+        bail!("The stored note reads back differently: {}", entry.content);
+    }
+    Ok(format!(
+        "Stored '{MEMORY_KEY}' in the {} backend and read it back.",
+        mem.name()
+    ))
+}
+
+fn schedule(config: &Config, interactive: bool) -> Result<String> {
+    let schedule = Schedule::Cron {
+        expr: CRON_EXPRESSION.to_string(),
+        tz: UserClock::from_config(&config.ui).default.iana_name(),
+    };
+    let job = cron::add_shell_job(config, Some(CRON_NAME.into()), schedule, CRON_COMMAND)?;
+    if !cron::list_jobs(config)?
+        .iter()
+        .any(|listed| listed.id == job.id)
+    {
+        bail!("Job {} was added but is not listed", job.id);
+    }
+    let keep = interactive
+        && Confirm::new()
+            .with_prompt("  Keep the tour job?")
+            .default(false)
+            .interact()?;
+    let scheduled = format!(
+        "Scheduled job {} for `{CRON_EXPRESSION}` (next run {}).",
+        job.id,
+        job.next_run.to_rfc3339()
+    );
+    if keep {
+        return Ok(scheduled);
+    }
+    cron::remove_job(config, &job.id)?;
+    Ok(format!("{scheduled} Removed it again."))
+}
+
+async fn snapshot(config: &Config) -> Result<String> {
+    let reply = Box::pin(crate::agent::run(
+        config.clone(),
+        Some(format!(
+            "Use the browser tool to open {DEMO_URL}, take a snapshot, and reply with the page's main heading only."
+        )),
+        None,
+        None,
+        config.default_temperature,
+        vec![],
+        false,
+        false,
+        vec!["browser".to_string()],
+        crate::handoff::CliHandoff::default(),
+    ))
+    .await?;
+    if !reply
+        .to_ascii_lowercase()
+        .contains(&DEMO_HEADING.to_ascii_lowercase())
+    {
+        bail!("The reply did not include the demo page heading '{DEMO_HEADING}'");
+    }
+    Ok(format!("Opened {DEMO_URL} and read its heading."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unavailable_steps_name_the_setting_to_change() {
+        let mut config = Config::default();
+        assert!(unavailable(&config, Step::Ask).is_none());
+        assert!(unavailable(&config, Step::Browser)
+            .unwrap()
+            .contains("[browser].enabled"));
+
+        config.browser.enabled = true;
+        config.browser.allowed_domains = vec!["docs.rs".into()];
+        assert!(unavailable(&config, Step::Browser)
+            .unwrap()
+            .contains("allowed_domains"));
+        config.browser.allowed_domains.push(DEMO_HOST.into());
+        assert!(unavailable(&config, Step::Browser).is_none());
+
+        config.memory.backend = "none".into();
+        assert!(unavailable(&config, Step::Memory)
+            .unwrap()
+            .contains("[memory].backend"));
+        config.cron.enabled = false;
+        assert!(unavailable(&config, Step::Cron).is_some());
+    }
+
+    #[test]
+    fn cron_step_adds_and_removes_its_job() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+
+        let detail = schedule(&config, false).unwrap();
+        assert!(detail.contains("Removed it again"));
+        assert!(cron::list_jobs(&config).unwrap().is_empty());
+    }
+}
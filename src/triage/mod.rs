@@ -0,0 +1,673 @@
+//! Test-failure triage (`[triage]`).
+//!
+//! Test reports (JUnit XML, `cargo test` output or `ctest` output) arrive via
+//! `POST /api/triage` on the gateway or from files the daemon watches. Each
+//! failure is matched against recent commits of the configured repository:
+//! commits touching files named in the failure output rank highest, then
+//! commits touching files whose name matches part of the test name. The
+//! resulting summary is posted to `[triage].channel`.
+
+use crate::config::{Config, TriageConfig};
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::{Duration, SystemTime};
+
+/// Suspect commits listed per failure.
+const MAX_SUSPECTS: usize = 3;
+/// Characters kept from a failure message.
+const MAX_MESSAGE_CHARS: usize = 160;
+
+static TESTCASE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)<testcase\b([^>]*?)(?:/>|>(.*?)</testcase>)").expect("valid regex")
+});
+static ATTR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"([\w:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("valid regex")
+});
+static FAILURE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)<(failure|error)\b([^>]*?)(?:/>|>(.*?)</(?:failure|error)>)")
+        .expect("valid regex")
+});
+static SOURCE_PATH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"[\w./-]+\.(?:rs|py|js|jsx|ts|tsx|go|java|kt|c|cc|cpp|cxx|h|hpp|rb|swift|cs|php|scala)\b",
+    )
+    .expect("valid regex")
+});
+static CARGO_RESULT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"test result: \w+\. (\d+) passed; (\d+) failed").expect("valid regex")
+});
+static CTEST_SUMMARY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"tests passed, (\d+) tests? failed out of (\d+)").expect("valid regex")
+});
+static CTEST_FAILED_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*\d+\s+-\s+(.+?)\s+\(([^)]*)\)\s*$").expect("valid regex"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    Junit,
+    CargoTest,
+    Ctest,
+}
+
+impl ReportFormat {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Junit => "JUnit",
+            Self::CargoTest => "cargo test",
+            Self::Ctest => "ctest",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestFailure {
+    pub name: String,
+    /// First meaningful line of the failure.
+    pub message: String,
+    /// Captured output; only used to find referenced source files.
+    pub output: String,
+    pub file: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestReport {
+    pub format: ReportFormat,
+    pub total: usize,
+    pub failures: Vec<TestFailure>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commit {
+    pub hash: String,
+    pub author: String,
+    pub when: String,
+    pub subject: String,
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suspect<'a> {
+    pub commit: &'a Commit,
+    pub reason: String,
+    score: u32,
+}
+
+/// Result of one triage, as returned by the gateway endpoint.
+#[derive(Debug, Clone, Serialize)]
+pub struct TriageOutcome {
+    pub format: ReportFormat,
+    pub total: usize,
+    pub failed: usize,
+    pub summary: String,
+    pub posted: bool,
+}
+
+/// Detect the report format and extract failures.
+pub fn parse_report(text: &str) -> Result<TestReport> {
+    let trimmed = text.trim_start();
+    if trimmed.starts_with('<') {
+        return Ok(parse_junit(text));
+    }
+    if text.contains("test result: ") || text.contains("running ") && text.contains(" ... ") {
+        return Ok(parse_cargo_test(text));
+    }
+    if CTEST_SUMMARY_RE.is_match(text) || text.contains("The following tests FAILED:") {
+        return Ok(parse_ctest(text));
+    }
+    bail!("unrecognized test report (expected JUnit XML, cargo test or ctest output)")
+}
+
+fn attributes(raw: &str) -> HashMap<String, String> {
+    ATTR_RE
+        .captures_iter(raw)
+        .map(|caps| {
+            let value = caps
+                .get(2)
+                .or_else(|| caps.get(3))
+                .map_or("", |m| m.as_str());
+            (caps[1].to_string(), unescape_xml(value))
+        })
+        .collect()
+}
+
+fn unescape_xml(text: &str) -> String {
+    let text = text
+        .replace("<![CDATA[", "")
+        .replace("]]>", "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&#xA;", "\n");
+    text.replace("&amp;", "&")
+}
+
+fn parse_junit(text: &str) -> TestReport {
+    let mut total = 0;
+    let mut failures = Vec::new();
+    for case in TESTCASE_RE.captures_iter(text) {
+        total += 1;
+        let Some(body) = case.get(2) else {
+            continue;
+        };
+        let Some(failure) = FAILURE_RE.captures(body.as_str()) else {
+            continue;
+        };
+        let attrs = attributes(&case[1]);
+        let name = attrs.get("name").cloned().unwrap_or_default();
+        let name = match attrs.get("classname").filter(|c| !c.is_empty()) {
+            Some(class) => format!("{class}.{name}"),
+            None => name,
+        };
+        let output = failure
+            .get(3)
+            .map(|m| unescape_xml(m.as_str()))
+            .unwrap_or_default();
+        let message = attributes(&failure[2])
+            .remove("message")
+            .filter(|m| !m.trim().is_empty())
+            .unwrap_or_else(|| first_line(&output));
+        failures.push(TestFailure {
+            name,
+            message: first_line(&message),
+            output,
+            file: attrs.get("file").cloned(),
+        });
+    }
+    TestReport {
+        format: ReportFormat::Junit,
+        total,
+        failures,
+    }
+}
+
+fn parse_cargo_test(text: &str) -> TestReport {
+    let mut total = 0;
+    let mut failed_names = Vec::new();
+    let mut outputs: HashMap<String, String> = HashMap::new();
+    let mut current: Option<String> = None;
+
+    for line in text.lines() {
+        if let Some(caps) = CARGO_RESULT_RE.captures(line) {
+            total += caps[1].parse::<usize>().unwrap_or(0) + caps[2].parse::<usize>().unwrap_or(0);
+        }
+        if let Some(name) = line
+            .strip_prefix("test ")
+            .and_then(|rest| rest.strip_suffix(" ... FAILED"))
+        {
+            failed_names.push(name.trim().to_string());
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"))
+        {
+            current = Some(name.trim().to_string());
+            continue;
+        }
+        if line.trim() == "failures:" || line.starts_with("test result: ") {
+            current = None;
+            continue;
+        }
+        if let Some(name) = &current {
+            let output = outputs.entry(name.clone()).or_default();
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    if total == 0 {
+        total = failed_names.len();
+    }
+
+    let failures = failed_names
+        .into_iter()
+        .map(|name| {
+            let output = outputs.remove(&name).unwrap_or_default();
+            TestFailure {
+                message: panic_message(&output),
+                name,
+                output,
+                file: None,
+            }
+        })
+        .collect();
+    TestReport {
+        format: ReportFormat::CargoTest,
+        total,
+        failures,
+    }
+}
+
+/// The assertion text of a Rust panic, with its location when known.
+fn panic_message(output: &str) -> String {
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        let Some((_, rest)) = line.split_once("panicked at ") else {
+            continue;
+        };
+        // Rust >= 1.73: "panicked at src/x.rs:1:2:" followed by the message.
+        if let Some(location) = rest.strip_suffix(':') {
+            let message = lines.next().unwrap_or_default().trim();
+            return format!("{message} ({location})");
+        }
+        return rest.trim().to_string();
+    }
+    first_line(output)
+}
+
+fn parse_ctest(text: &str) -> TestReport {
+    let total = CTEST_SUMMARY_RE
+        .captures(text)
+        .and_then(|caps| caps[2].parse().ok())
+        .unwrap_or(0);
+    let failures: Vec<TestFailure> = text
+        .lines()
+        .skip_while(|line| !line.contains("The following tests FAILED:"))
+        .skip(1)
+        .map_while(|line| CTEST_FAILED_RE.captures(line))
+        .map(|caps| TestFailure {
+            name: caps[1].to_string(),
+            message: caps[2].to_string(),
+            output: String::new(),
+            file: None,
+        })
+        .collect();
+    TestReport {
+        format: ReportFormat::Ctest,
+        total: total.max(failures.len()),
+        failures,
+    }
+}
+
+fn first_line(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    crate::util::truncate_with_ellipsis(line, MAX_MESSAGE_CHARS)
+}
+
+/// The last `limit` commits with the files they touched.
+pub async fn recent_commits(repo: &Path, limit: usize) -> Result<Vec<Commit>> {
+    let output = tokio::process::Command::new("git")
+        .args([
+            "log",
+            &format!("-n{}", limit.max(1)),
+            "--name-only",
+            "--format=%x1e%h%x1f%an%x1f%ar%x1f%s",
+        ])
+        .current_dir(repo)
+        .output()
+        .await
+        .context("failed to run git log")?;
+    if !output.status.success() {
+        bail!(
+            "git log failed in {}: {}",
+            repo.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(parse_git_log(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_git_log(log: &str) -> Vec<Commit> {
+    log.split('\x1e')
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let header = lines.next()?;
+            let mut fields = header.split('\x1f');
+            Some(Commit {
+                hash: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                when: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+                files: lines
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+fn same_file(a: &str, b: &str) -> bool {
+    let a = a.trim_start_matches("./");
+    let b = b.trim_start_matches("./");
+    a == b || a.ends_with(&format!("/{b}")) || b.ends_with(&format!("/{a}"))
+}
+
+/// Parts of a test name that may name a source file (`parser` in `parser::tests::dates`).
+fn name_segments(name: &str) -> Vec<String> {
+    const IGNORED: [&str; 6] = ["test", "tests", "mod", "src", "lib", "main"];
+    name.split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .map(str::to_ascii_lowercase)
+        .filter(|segment| segment.len() >= 3 && !IGNORED.contains(&segment.as_str()))
+        .collect()
+}
+
+/// Commits most likely to have caused `failure`, best first.
+pub fn suspects<'a>(failure: &TestFailure, commits: &'a [Commit]) -> Vec<Suspect<'a>> {
+    let mut referenced: Vec<String> = SOURCE_PATH_RE
+        .find_iter(&format!("{}\n{}", failure.message, failure.output))
+        .map(|m| m.as_str().to_string())
+        .collect();
+    referenced.extend(failure.file.clone());
+    let segments = name_segments(&failure.name);
+
+    let mut ranked: Vec<Suspect<'a>> = commits
+        .iter()
+        .filter_map(|commit| {
+            let mut score = 0;
+            let mut reasons = Vec::new();
+            for file in &commit.files {
+                if referenced.iter().any(|path| same_file(path, file)) {
+                    score += 3;
+                    reasons.push(format!("touched {file}"));
+                    continue;
+                }
+                let stem = Path::new(file)
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+                if segments.contains(&stem) {
+                    score += 1;
+                    reasons.push(format!("touched {file} (matches test name)"));
+                }
+            }
+            (score > 0).then(|| Suspect {
+                commit,
+                reason: reasons.join(", "),
+                score,
+            })
+        })
+        .collect();
+    // Stable sort keeps newer commits first among equal scores.
+    ranked.sort_by_key(|suspect| std::cmp::Reverse(suspect.score));
+    ranked.truncate(MAX_SUSPECTS);
+    ranked
+}
+
+/// Chat-ready triage summary.
+pub fn render(report: &TestReport, commits: &[Commit], max_failures: usize) -> String {
+    let failed = report.failures.len();
+    let mut out = format!(
+        "🧪 Test triage ({}): {failed} failed of {} tests",
+        report.format.label(),
+        report.total
+    );
+    if failed == 0 {
+        out.push_str(" ✅");
+        return out;
+    }
+    for failure in report.failures.iter().take(max_failures.max(1)) {
+        let _ = write!(out, "\n\n❌ {}", failure.name);
+        if !failure.message.is_empty() {
+            let _ = write!(out, "\n   {}", failure.message);
+        }
+        let suspects = suspects(failure, commits);
+        if suspects.is_empty() {
+            out.push_str("\n   Suspects: none among recent commits");
+        }
+        for suspect in suspects {
+            let commit = suspect.commit;
+            let _ = write!(
+                out,
+                "\n   Suspect: {} \"{}\" by {}, {} — {}",
+                commit.hash, commit.subject, commit.author, commit.when, suspect.reason
+            );
+        }
+    }
+    if failed > max_failures.max(1) {
+        let _ = write!(out, "\n\n(+{} more failures)", failed - max_failures.max(1));
+    }
+    out
+}
+
+fn repo_dir(config: &Config) -> PathBuf {
+    let repo = config.triage.repo.trim();
+    if repo.is_empty() {
+        config.workspace_dir.clone()
+    } else {
+        PathBuf::from(crate::util::expand_tilde(repo))
+    }
+}
+
+/// Parse `text`, correlate failures with recent commits and post the summary.
+pub async fn triage(config: &Config, text: &str) -> Result<TriageOutcome> {
+    let settings = &config.triage;
+    let report = parse_report(text)?;
+    let commits = if report.failures.is_empty() {
+        Vec::new()
+    } else {
+        recent_commits(&repo_dir(config), settings.max_commits)
+            .await
+            .unwrap_or_else(|err| {
+                tracing::warn!("Test triage could not read commits: {err:#}");
+                Vec::new()
+            })
+    };
+    let summary = render(&report, &commits, settings.max_failures);
+
+    let should_post = !settings.channel.trim().is_empty()
+        && (!report.failures.is_empty() || settings.notify_on_success);
+    if should_post {
+        crate::cron::scheduler::send_announcement(
+            config,
+            settings.channel.trim(),
+            settings.to.trim(),
+            &summary,
+        )
+        .await
+        .context("failed to post test triage")?;
+    }
+    Ok(TriageOutcome {
+        format: report.format,
+        total: report.total,
+        failed: report.failures.len(),
+        summary,
+        posted: should_post,
+    })
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn watch_paths(config: &Config, settings: &TriageConfig) -> Vec<PathBuf> {
+    let repo = repo_dir(config);
+    settings
+        .watch
+        .iter()
+        .map(|path| {
+            let path = PathBuf::from(crate::util::expand_tilde(path.trim()));
+            if path.is_absolute() {
+                path
+            } else {
+                repo.join(path)
+            }
+        })
+        .collect()
+}
+
+/// Daemon worker: triage watched report files whenever they change.
+pub async fn run_worker(config: Config) -> Result<()> {
+    let paths = watch_paths(&config, &config.triage);
+    // Reports already on disk at startup were seen before.
+    let mut seen: HashMap<PathBuf, Option<SystemTime>> = paths
+        .iter()
+        .map(|path| (path.clone(), modified(path)))
+        .collect();
+    let mut interval = tokio::time::interval(Duration::from_secs(config.triage.poll_secs.max(1)));
+
+    loop {
+        interval.tick().await;
+        for path in &paths {
+            let current = modified(path);
+            if current.is_none() || seen.get(path) == Some(&current) {
+                continue;
+            }
+            seen.insert(path.clone(), current);
+            let text = match tokio::fs::read_to_string(path).await {
+                Ok(text) => text,
+                Err(err) => {
+                    tracing::warn!("Failed to read test report {}: {err}", path.display());
+                    continue;
+                }
+            };
+            match triage(&config, &text).await {
+                Ok(outcome) => tracing::info!(
+                    report = %path.display(),
+                    failed = outcome.failed,
+                    total = outcome.total,
+                    "Test report triaged"
+                ),
+                Err(err) => {
+                    tracing::warn!("Test triage failed for {}: {err:#}", path.display());
+                }
+            }
+        }
+        crate::health::mark_component_ok("test_triage");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(hash: &str, subject: &str, files: &[&str]) -> Commit {
+        Commit {
+            hash: hash.into(),
+            author: "Alice".into(),
+            when: "2 hours ago".into(),
+            subject: subject.into(),
+            files: files.iter().map(|f| (*f).to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn parses_junit_failures_and_errors() {
+        let xml = r#"<?xml version="1.0"?>
+<testsuites>
+  <testsuite name="app" tests="3">
+    <testcase classname="app.DateParser" name="parses_iso" file="src/date_parser.py"/>
+    <testcase classname="app.DateParser" name="parses_rfc">
+      <failure message="expected &quot;2026&quot;, got &lt;none&gt;">Traceback ...
+  File "src/date_parser.py", line 12</failure>
+    </testcase>
+    <testcase name="boots"><error>Timeout after 30s</error></testcase>
+  </testsuite>
+</testsuites>"#;
+        let report = parse_report(xml).unwrap();
+        assert_eq!(report.format, ReportFormat::Junit);
+        assert_eq!(report.total, 3);
+        assert_eq!(report.failures.len(), 2);
+        assert_eq!(report.failures[0].name, "app.DateParser.parses_rfc");
+        assert_eq!(report.failures[0].message, "expected \"2026\", got <none>");
+        assert!(report.failures[0].output.contains("src/date_parser.py"));
+        assert_eq!(report.failures[1].name, "boots");
+        assert_eq!(report.failures[1].message, "Timeout after 30s");
+    }
+
+    #[test]
+    fn parses_cargo_test_output_with_panic_locations() {
+        let output = "\
+running 3 tests
+test parser::tests::dates ... FAILED
+test parser::tests::numbers ... ok
+test util::tests::slug ... ok
+
+failures:
+
+---- parser::tests::dates stdout ----
+
+thread 'parser::tests::dates' panicked at src/parser.rs:42:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+
+failures:
+    parser::tests::dates
+
+test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out
+";
+        let report = parse_report(output).unwrap();
+        assert_eq!(report.format, ReportFormat::CargoTest);
+        assert_eq!(report.total, 3);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].name, "parser::tests::dates");
+        assert_eq!(
+            report.failures[0].message,
+            "assertion `left == right` failed (src/parser.rs:42:9)"
+        );
+    }
+
+    #[test]
+    fn parses_ctest_summary() {
+        let output = "\
+67% tests passed, 1 tests failed out of 3
+
+Total Test time (real) =   0.02 sec
+
+The following tests FAILED:
+\t  2 - codec_roundtrip (Failed)
+Errors while running CTest
+";
+        let report = parse_report(output).unwrap();
+        assert_eq!(report.format, ReportFormat::Ctest);
+        assert_eq!(report.total, 3);
+        assert_eq!(report.failures[0].name, "codec_roundtrip");
+        assert!(parse_report("hello world").is_err());
+    }
+
+    #[test]
+    fn suspects_rank_referenced_files_above_name_matches() {
+        let commits = vec![
+            commit("aaa1111", "Tweak docs", &["README.md"]),
+            commit(
+                "bbb2222",
+                "Rename parser helpers",
+                &["src/parser/mod.rs", "src/parser.rs"],
+            ),
+            commit("ccc3333", "Speed up parser", &["benches/parser.rs"]),
+        ];
+        let failure = TestFailure {
+            name: "parser::tests::dates".into(),
+            message: "assertion failed (src/parser.rs:42:9)".into(),
+            output: String::new(),
+            file: None,
+        };
+        let ranked = suspects(&failure, &commits);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].commit.hash, "bbb2222");
+        assert!(ranked[0].reason.contains("touched src/parser.rs"));
+        assert_eq!(ranked[1].commit.hash, "ccc3333");
+
+        let report = TestReport {
+            format: ReportFormat::CargoTest,
+            total: 10,
+            failures: vec![failure],
+        };
+        let summary = render(&report, &commits, 5);
+        assert!(summary.starts_with("🧪 Test triage (cargo test): 1 failed of 10 tests"));
+        assert!(summary.contains("Suspect: bbb2222 \"Rename parser helpers\" by Alice"));
+    }
+
+    #[test]
+    fn git_log_records_are_split_into_commits() {
+        let log = "\x1eabc1234\x1fAlice\x1f2 hours ago\x1fFix parser\n\nsrc/parser.rs\nsrc/lib.rs\n\x1edef5678\x1fBob\x1f1 day ago\x1fInitial\n\nREADME.md\n";
+        let commits = parse_git_log(log);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].files, ["src/parser.rs", "src/lib.rs"]);
+        assert_eq!(commits[1].author, "Bob");
+    }
+}
@@ -0,0 +1,355 @@
+//! Write-ahead undo journal for agent file modifications.
+//!
+//! Before `file_write` / `file_edit` touch a file, its current contents (the
+//! pre-image) are copied under `<workspace>/state/undo/` and a change entry is
+//! appended to `journal.jsonl`. `zeroclaw undo list` shows recent changes and
+//! `zeroclaw undo revert <change-id>` restores the pre-image, so mistakes made
+//! by autonomous tasks can be rolled back precisely.
+//!
+//! The journal is itself a workspace file the agent could edit, so a revert
+//! only touches paths the [`SecurityPolicy`] lets file tools write.
+
+use crate::security::SecurityPolicy;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+const JOURNAL_FILE: &str = "journal.jsonl";
+const PREIMAGES_DIR: &str = "preimages";
+/// Oldest entries (and their pre-images) are pruned beyond this many changes.
+const MAX_JOURNAL_ENTRIES: usize = 500;
+
+/// Serializes journal mutations across concurrently executing tools.
+static JOURNAL_LOCK: Mutex<()> = Mutex::new(());
+
+/// A single journaled file change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeEntry {
+    pub id: String,
+    pub timestamp: DateTime<Utc>,
+    /// Tool that made the change (`undo` for reverts themselves).
+    pub tool: String,
+    /// Resolved absolute path of the changed file.
+    pub path: PathBuf,
+    /// Whether the file existed before the change; reverting a creation
+    /// deletes the file.
+    pub existed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reverted_at: Option<DateTime<Utc>>,
+}
+
+/// Journal of file pre-images rooted in a workspace's state directory.
+pub struct UndoJournal {
+    root: PathBuf,
+}
+
+impl UndoJournal {
+    pub fn for_workspace(workspace_dir: &Path) -> Self {
+        Self {
+            root: workspace_dir.join("state").join("undo"),
+        }
+    }
+
+    /// Save the current contents of `target` before it is modified.
+    ///
+    /// Must be called before the write; callers should abort the write when
+    /// this fails so every change stays revertible.
+    pub fn record_preimage(&self, tool: &str, target: &Path) -> Result<ChangeEntry> {
+        let _guard = JOURNAL_LOCK.lock();
+        let entry = self.append_preimage(tool, target)?;
+        self.prune()?;
+        Ok(entry)
+    }
+
+    /// All journaled changes, oldest first.
+    pub fn list(&self) -> Result<Vec<ChangeEntry>> {
+        let path = self.root.join(JOURNAL_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read undo journal {}", path.display()))?;
+        let mut entries = Vec::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str::<ChangeEntry>(line) {
+                Ok(entry) => entries.push(entry),
+                Err(error) => tracing::warn!("Skipping malformed undo journal line: {error}"),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Restore the file changed by `change_id` (or a unique prefix of it) to
+    /// its pre-image.
+    ///
+    /// Refuses when later, unreverted changes touched the same file unless
+    /// `force` is set, in which case those are marked reverted as well. The
+    /// revert itself is journaled, so it can be undone too.
+    pub fn revert(
+        &self,
+        change_id: &str,
+        force: bool,
+        policy: &SecurityPolicy,
+    ) -> Result<ChangeEntry> {
+        let _guard = JOURNAL_LOCK.lock();
+        let mut entries = self.list()?;
+
+        let matches: Vec<usize> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.id.starts_with(change_id))
+            .map(|(idx, _)| idx)
+            .collect();
+        let idx = match matches.as_slice() {
+            [] => bail!("Unknown change id: {change_id}"),
+            [idx] => *idx,
+            _ => bail!("Change id prefix '{change_id}' is ambiguous"),
+        };
+
+        let entry = entries[idx].clone();
+        if entry.reverted_at.is_some() {
+            bail!("Change {} was already reverted", entry.id);
+        }
+        if !entry.id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            bail!("Malformed change id in undo journal: {}", entry.id);
+        }
+
+        let later: Vec<usize> = (idx + 1..entries.len())
+            .filter(|&later| {
+                entries[later].path == entry.path && entries[later].reverted_at.is_none()
+            })
+            .collect();
+        if !later.is_empty() && !force {
+            let ids: Vec<&str> = later.iter().map(|&i| entries[i].id.as_str()).collect();
+            bail!(
+                "{} was changed again later ({}); revert those first or pass --force",
+                entry.path.display(),
+                ids.join(", ")
+            );
+        }
+
+        if let Ok(meta) = fs::symlink_metadata(&entry.path) {
+            if meta.file_type().is_symlink() {
+                bail!(
+                    "Refusing to restore through symlink: {}",
+                    entry.path.display()
+                );
+            }
+        }
+        let target = confined_path(&entry.path, policy)?;
+
+        let undo_entry = self.append_preimage("undo", &target)?;
+        if entry.existed {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(self.preimage_path(&entry.id), &target)
+                .with_context(|| format!("Failed to restore pre-image of {}", target.display()))?;
+        } else if target.exists() {
+            fs::remove_file(&target)
+                .with_context(|| format!("Failed to remove {}", target.display()))?;
+        }
+
+        let now = Utc::now();
+        entries.push(undo_entry);
+        for i in std::iter::once(idx).chain(later) {
+            entries[i].reverted_at = Some(now);
+        }
+        self.write_all(&entries)?;
+
+        let mut reverted = entry;
+        reverted.reverted_at = Some(now);
+        Ok(reverted)
+    }
+
+    fn preimage_path(&self, id: &str) -> PathBuf {
+        self.root.join(PREIMAGES_DIR).join(id)
+    }
+
+    fn append_preimage(&self, tool: &str, target: &Path) -> Result<ChangeEntry> {
+        fs::create_dir_all(self.root.join(PREIMAGES_DIR))
+            .with_context(|| format!("Failed to create undo journal at {}", self.root.display()))?;
+
+        let id = uuid::Uuid::new_v4().simple().to_string()[..12].to_string();
+        let existed = target.is_file();
+        if existed {
+            fs::copy(target, self.preimage_path(&id))
+                .with_context(|| format!("Failed to save pre-image of {}", target.display()))?;
+        }
+
+        let entry = ChangeEntry {
+            id,
+            timestamp: Utc::now(),
+            tool: tool.to_string(),
+            path: target.to_path_buf(),
+            existed,
+            reverted_at: None,
+        };
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.root.join(JOURNAL_FILE))?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        Ok(entry)
+    }
+
+    fn prune(&self) -> Result<()> {
+        let entries = self.list()?;
+        if entries.len() <= MAX_JOURNAL_ENTRIES {
+            return Ok(());
+        }
+        let (dropped, kept) = entries.split_at(entries.len() - MAX_JOURNAL_ENTRIES);
+        for entry in dropped {
+            let _ = fs::remove_file(self.preimage_path(&entry.id));
+        }
+        self.write_all(kept)
+    }
+
+    fn write_all(&self, entries: &[ChangeEntry]) -> Result<()> {
+        let mut content = String::new();
+        for entry in entries {
+            content.push_str(&serde_json::to_string(entry)?);
+            content.push('\n');
+        }
+        let path = self.root.join(JOURNAL_FILE);
+        let tmp = path.with_extension("jsonl.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, &path)
+            .with_context(|| format!("Failed to update undo journal {}", path.display()))
+    }
+}
+
+/// Canonical form of a journaled path, refused unless `policy` allows file
+/// tools to write it. The file, and even its parent, may be gone, so the
+/// closest existing ancestor is canonicalized and the rest re-appended.
+fn confined_path(path: &Path, policy: &SecurityPolicy) -> Result<PathBuf> {
+    if !path.is_absolute() || path.components().any(|c| matches!(c, Component::ParentDir)) {
+        bail!("Refusing to revert {}: not a resolved path", path.display());
+    }
+    let mut existing = path;
+    let mut missing = Vec::new();
+    while fs::symlink_metadata(existing).is_err() {
+        let (Some(name), Some(parent)) = (existing.file_name(), existing.parent()) else {
+            bail!("Refusing to revert {}: no existing parent", path.display());
+        };
+        missing.push(name);
+        existing = parent;
+    }
+    let mut resolved = existing
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", existing.display()))?;
+    resolved.extend(missing.iter().rev());
+    if !policy.is_resolved_path_allowed(&resolved) {
+        bail!(
+            "Refusing to revert: {}",
+            policy.resolved_path_violation_message(&resolved)
+        );
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AutonomyConfig;
+    use tempfile::TempDir;
+
+    fn policy(tmp: &TempDir) -> SecurityPolicy {
+        SecurityPolicy::from_config(&AutonomyConfig::default(), tmp.path())
+    }
+
+    #[test]
+    fn revert_restores_preimage_and_is_itself_undoable() {
+        let tmp = TempDir::new().unwrap();
+        let journal = UndoJournal::for_workspace(tmp.path());
+        let file = tmp.path().join("notes.md");
+        fs::write(&file, "before\n").unwrap();
+
+        let change = journal.record_preimage("file_write", &file).unwrap();
+        fs::write(&file, "after\n").unwrap();
+
+        let reverted = journal
+            .revert(&change.id[..6], false, &policy(&tmp))
+            .unwrap();
+        assert_eq!(reverted.id, change.id);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "before\n");
+
+        let entries = journal.list().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].reverted_at.is_some());
+        assert_eq!(entries[1].tool, "undo");
+
+        journal
+            .revert(&entries[1].id, false, &policy(&tmp))
+            .unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "after\n");
+    }
+
+    #[test]
+    fn reverting_a_creation_deletes_the_file() {
+        let tmp = TempDir::new().unwrap();
+        let journal = UndoJournal::for_workspace(tmp.path());
+        let file = tmp.path().join("new.txt");
+
+        let change = journal.record_preimage("file_write", &file).unwrap();
+        assert!(!change.existed);
+        fs::write(&file, "created").unwrap();
+
+        journal.revert(&change.id, false, &policy(&tmp)).unwrap();
+        assert!(!file.exists());
+        assert!(journal.revert(&change.id, false, &policy(&tmp)).is_err());
+    }
+
+    #[test]
+    fn revert_refuses_when_later_changes_exist_unless_forced() {
+        let tmp = TempDir::new().unwrap();
+        let journal = UndoJournal::for_workspace(tmp.path());
+        let file = tmp.path().join("a.txt");
+        fs::write(&file, "v1").unwrap();
+
+        let first = journal.record_preimage("file_write", &file).unwrap();
+        fs::write(&file, "v2").unwrap();
+        let second = journal.record_preimage("file_edit", &file).unwrap();
+        fs::write(&file, "v3").unwrap();
+
+        let err = journal.revert(&first.id, false, &policy(&tmp)).unwrap_err();
+        assert!(err.to_string().contains(&second.id));
+
+        journal.revert(&first.id, true, &policy(&tmp)).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "v1");
+        let entries = journal.list().unwrap();
+        assert!(entries[1].reverted_at.is_some());
+    }
+
+    #[test]
+    fn revert_refuses_entries_pointing_outside_the_workspace() {
+        let tmp = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let victim = outside.path().join("victim.txt");
+        fs::write(&victim, "keep me").unwrap();
+
+        let journal = UndoJournal::for_workspace(tmp.path());
+        let inside = tmp.path().join("a.txt");
+        let change = journal.record_preimage("file_write", &inside).unwrap();
+        let mut forged = journal.list().unwrap();
+        forged[0].path = victim.clone();
+        forged.push(ChangeEntry {
+            id: "..%2f..".into(),
+            path: inside,
+            ..forged[0].clone()
+        });
+        journal.write_all(&forged).unwrap();
+
+        let err = journal
+            .revert(&change.id, false, &policy(&tmp))
+            .unwrap_err();
+        assert!(err.to_string().contains("escapes workspace"), "{err}");
+        assert_eq!(fs::read_to_string(&victim).unwrap(), "keep me");
+        let err = journal.revert("..", false, &policy(&tmp)).unwrap_err();
+        assert!(err.to_string().contains("Malformed change id"), "{err}");
+    }
+}
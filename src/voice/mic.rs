@@ -0,0 +1,130 @@
+//! Microphone capture.
+//!
+//! The cpal stream lives on its own thread (it is not `Send` on every
+//! platform) and forwards mono `f32` chunks over a channel until the
+//! [`Microphone`] is dropped.
+
+use anyhow::Result;
+use tokio::sync::mpsc;
+
+pub struct Microphone {
+    pub sample_rate: u32,
+    pub samples: mpsc::UnboundedReceiver<Vec<f32>>,
+    _stop: std::sync::mpsc::Sender<()>,
+}
+
+impl Microphone {
+    /// Discard audio captured while nobody was listening.
+    pub fn drain(&mut self) {
+        while self.samples.try_recv().is_ok() {}
+    }
+}
+
+#[cfg(not(feature = "voice"))]
+impl Microphone {
+    pub fn open(_device: Option<&str>) -> Result<Self> {
+        anyhow::bail!(
+            "Microphone input is not built in. Rebuild with: cargo build --features voice"
+        )
+    }
+}
+
+#[cfg(feature = "voice")]
+impl Microphone {
+    pub fn open(device: Option<&str>) -> Result<Self> {
+        let (sample_tx, samples) = mpsc::unbounded_channel();
+        let (stop, stop_rx) = std::sync::mpsc::channel::<()>();
+        let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+        let device = device.map(str::to_string);
+
+        std::thread::Builder::new().name("voice-mic".into()).spawn(
+            move || match capture::start(device.as_deref(), sample_tx) {
+                Ok((stream, rate)) => {
+                    let _ = ready_tx.send(Ok(rate));
+                    // Blocks until the Microphone (and its sender) is dropped.
+                    let _ = stop_rx.recv();
+                    drop(stream);
+                }
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                }
+            },
+        )?;
+
+        let sample_rate = ready_rx
+            .recv()
+            .map_err(|_| anyhow::anyhow!("Microphone thread exited unexpectedly"))??;
+        Ok(Self {
+            sample_rate,
+            samples,
+            _stop: stop,
+        })
+    }
+}
+
+#[cfg(feature = "voice")]
+mod capture {
+    use anyhow::{Context, Result};
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use tokio::sync::mpsc::UnboundedSender;
+
+    pub fn start(
+        device: Option<&str>,
+        tx: UnboundedSender<Vec<f32>>,
+    ) -> Result<(cpal::Stream, u32)> {
+        let host = cpal::default_host();
+        let device = match device {
+            Some(name) => host
+                .input_devices()?
+                .find(|d| d.name().is_ok_and(|n| n == name))
+                .with_context(|| format!("Input device '{name}' not found"))?,
+            None => host
+                .default_input_device()
+                .context("No default input device (microphone) available")?,
+        };
+        let supported = device.default_input_config()?;
+        let sample_rate = supported.sample_rate().0;
+        let channels = usize::from(supported.channels()).max(1);
+        let config: cpal::StreamConfig = supported.clone().into();
+        let on_error = |e| tracing::warn!("Microphone stream error: {e}");
+
+        let stream = match supported.sample_format() {
+            cpal::SampleFormat::F32 => device.build_input_stream(
+                &config,
+                move |data: &[f32], _: &_| {
+                    let _ = tx.send(downmix(data, channels, |s| s));
+                },
+                on_error,
+                None,
+            )?,
+            cpal::SampleFormat::I16 => device.build_input_stream(
+                &config,
+                move |data: &[i16], _: &_| {
+                    let _ = tx.send(downmix(data, channels, |s| f32::from(s) / 32_768.0));
+                },
+                on_error,
+                None,
+            )?,
+            cpal::SampleFormat::U16 => device.build_input_stream(
+                &config,
+                move |data: &[u16], _: &_| {
+                    let _ = tx.send(downmix(data, channels, |s| {
+                        (f32::from(s) - 32_768.0) / 32_768.0
+                    }));
+                },
+                on_error,
+                None,
+            )?,
+            other => anyhow::bail!("Unsupported microphone sample format {other:?}"),
+        };
+        stream.play()?;
+        Ok((stream, sample_rate))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn downmix<T: Copy>(data: &[T], channels: usize, to_f32: impl Fn(T) -> f32) -> Vec<f32> {
+        data.chunks(channels)
+            .map(|frame| frame.iter().map(|&s| to_f32(s)).sum::<f32>() / frame.len() as f32)
+            .collect()
+    }
+}
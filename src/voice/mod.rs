@@ -0,0 +1,309 @@
+//! Local voice frontend (`zeroclaw voice`).
+//!
+//! Microphone audio is cut into utterances by a level-based detector,
+//! transcribed through `[transcription]`, answered by the agent and spoken
+//! back with `[voice] tts_command`. In push-to-talk mode nothing is
+//! recorded until Enter is pressed. In wake-word mode every utterance is
+//! transcribed and only those starting with a wake word reach the agent;
+//! because that means transcribing ambient speech, wake-word mode refuses
+//! to run against a transcription endpoint outside this machine or LAN.
+//!
+//! Microphone capture needs the `voice` build feature (cpal).
+
+mod mic;
+mod vad;
+
+use crate::agent::Agent;
+use crate::channels::transcription::{is_local_endpoint, transcribe_audio};
+use crate::config::{Config, VoiceConfig, VoiceMode};
+use anyhow::{Context, Result};
+use mic::Microphone;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use vad::UtteranceDetector;
+
+/// What an utterance means in wake-word mode.
+#[derive(Debug, PartialEq, Eq)]
+pub enum WakeMatch {
+    /// No wake word; the utterance is ignored.
+    None,
+    /// Only the wake word; the request follows in the next utterance.
+    Bare,
+    /// Wake word followed by a request.
+    Request(String),
+}
+
+pub async fn run(config: Config, mode: Option<VoiceMode>) -> Result<()> {
+    let mut voice = config.voice.clone();
+    if let Some(mode) = mode {
+        voice.mode = mode;
+    }
+    if voice.mode == VoiceMode::WakeWord && !is_local_endpoint(&config.transcription.api_url) {
+        anyhow::bail!(
+            "Wake-word mode transcribes everything the microphone hears, so it only runs with a \
+             local Whisper server. Point [transcription] api_url at one (e.g. \
+             http://127.0.0.1:8000/v1/audio/transcriptions) or use push-to-talk."
+        );
+    }
+
+    let mut agent = Agent::from_config(&config)?;
+    let mut mic = Microphone::open(voice.input_device.as_deref())?;
+    let mut detector = UtteranceDetector::new(&voice, mic.sample_rate);
+
+    println!("🦀 ZeroClaw Voice Mode");
+    match voice.mode {
+        VoiceMode::PushToTalk => {
+            println!("Press Enter, then speak. Ctrl-D to exit.\n");
+            let mut lines = BufReader::new(tokio::io::stdin()).lines();
+            while lines.next_line().await?.is_some() {
+                mic.drain();
+                detector.reset();
+                println!("🎙️  Listening...");
+                let wait = Duration::from_secs(voice.follow_up_secs.max(1));
+                let Ok(utterance) =
+                    tokio::time::timeout(wait, next_utterance(&mut mic, &mut detector)).await
+                else {
+                    println!("(didn't hear anything)");
+                    continue;
+                };
+                let text = transcribe(&config, &utterance?, mic.sample_rate).await?;
+                if !text.is_empty() {
+                    respond(&mut agent, &voice, &text).await;
+                }
+                mic.drain();
+            }
+        }
+        VoiceMode::WakeWord => {
+            println!(
+                "Listening for \"{}\". Ctrl-C to exit.\n",
+                voice.wake_words.join("\" / \"")
+            );
+            let mut awake_until: Option<Instant> = None;
+            loop {
+                let utterance = next_utterance(&mut mic, &mut detector).await?;
+                let text = transcribe(&config, &utterance, mic.sample_rate).await?;
+                let request = if awake_until.take().is_some_and(|t| Instant::now() < t) {
+                    text
+                } else {
+                    match match_wake_word(&text, &voice.wake_words) {
+                        WakeMatch::None => continue,
+                        WakeMatch::Bare => {
+                            println!("👂");
+                            awake_until =
+                                Some(Instant::now() + Duration::from_secs(voice.follow_up_secs));
+                            continue;
+                        }
+                        WakeMatch::Request(request) => request,
+                    }
+                };
+                if !request.is_empty() {
+                    respond(&mut agent, &voice, &request).await;
+                }
+                // Don't answer our own voice.
+                mic.drain();
+                detector.reset();
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn next_utterance(
+    mic: &mut Microphone,
+    detector: &mut UtteranceDetector,
+) -> Result<Vec<f32>> {
+    loop {
+        let chunk = mic
+            .samples
+            .recv()
+            .await
+            .context("Microphone stream ended")?;
+        if let Some(utterance) = detector.push(&chunk) {
+            return Ok(utterance);
+        }
+    }
+}
+
+async fn transcribe(config: &Config, samples: &[f32], sample_rate: u32) -> Result<String> {
+    let wav = encode_wav(samples, sample_rate);
+    let text = transcribe_audio(wav, "utterance.wav", &config.transcription).await?;
+    Ok(text.trim().to_string())
+}
+
+async fn respond(agent: &mut Agent, voice: &VoiceConfig, text: &str) {
+    println!("🗣️  {text}");
+    let reply = match agent.turn(text).await {
+        Ok(reply) => reply,
+        Err(e) => {
+            eprintln!("Agent error: {e:#}");
+            return;
+        }
+    };
+    println!("🦀 {reply}\n");
+    if let Err(e) = speak(voice, &reply).await {
+        eprintln!("Speech output failed: {e:#}");
+    }
+}
+
+/// Classify a transcript against the configured wake words. Matching ignores
+/// case and punctuation ("Hey, ZeroClaw!"); longer phrases win.
+pub fn match_wake_word(transcript: &str, wake_words: &[String]) -> WakeMatch {
+    let normalize = |word: &str| -> String {
+        word.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let words: Vec<&str> = transcript
+        .split_whitespace()
+        .filter(|word| !normalize(word).is_empty())
+        .collect();
+    let mut phrases: Vec<Vec<String>> = wake_words
+        .iter()
+        .map(|phrase| {
+            phrase
+                .split_whitespace()
+                .map(normalize)
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|phrase| !phrase.is_empty())
+        .collect();
+    phrases.sort_by_key(|phrase| std::cmp::Reverse(phrase.len()));
+
+    for phrase in phrases {
+        if words.len() >= phrase.len()
+            && words
+                .iter()
+                .zip(&phrase)
+                .all(|(word, wake)| normalize(word) == *wake)
+        {
+            let rest = words[phrase.len()..].join(" ");
+            let rest = rest.trim_start_matches(|c: char| c.is_ascii_punctuation() || c == ' ');
+            return if rest.is_empty() {
+                WakeMatch::Bare
+            } else {
+                WakeMatch::Request(rest.to_string())
+            };
+        }
+    }
+    WakeMatch::None
+}
+
+/// 16-bit PCM mono WAV, the format every Whisper server accepts.
+pub fn encode_wav(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    #[allow(clippy::cast_possible_truncation)]
+    let data_len = (samples.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + samples.len() * 2);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        #[allow(clippy::cast_possible_truncation)]
+        let pcm = (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16;
+        wav.extend_from_slice(&pcm.to_le_bytes());
+    }
+    wav
+}
+
+/// Strip markdown markers that speech engines would read out loud.
+fn speakable(text: &str) -> String {
+    text.chars()
+        .filter(|c| !matches!(c, '*' | '_' | '#' | '`'))
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn tts_command(voice: &VoiceConfig) -> Vec<String> {
+    if !voice.tts_command.is_empty() {
+        return voice.tts_command.clone();
+    }
+    if cfg!(target_os = "macos") {
+        vec!["say".into()]
+    } else {
+        vec!["espeak-ng".into(), "--stdin".into()]
+    }
+}
+
+/// Speak `text` by piping it to the TTS command.
+pub async fn speak(voice: &VoiceConfig, text: &str) -> Result<()> {
+    let command = tts_command(voice);
+    let (program, args) = command.split_first().context("Empty voice.tts_command")?;
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                anyhow::anyhow!(
+                    "TTS command '{program}' not found; install it or set [voice] tts_command"
+                )
+            } else {
+                e.into()
+            }
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(speakable(text).as_bytes()).await?;
+    }
+    let status = child.wait().await?;
+    if !status.success() {
+        anyhow::bail!("TTS command '{program}' exited with {status}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wake_words_ignore_case_and_punctuation() {
+        let words = vec!["zeroclaw".to_string(), "hey zeroclaw".to_string()];
+        assert_eq!(
+            match_wake_word("Hey, ZeroClaw! What's the weather?", &words),
+            WakeMatch::Request("What's the weather?".into())
+        );
+        assert_eq!(
+            match_wake_word("zeroclaw, lights off", &words),
+            WakeMatch::Request("lights off".into())
+        );
+        assert_eq!(match_wake_word("Zeroclaw.", &words), WakeMatch::Bare);
+        assert_eq!(
+            match_wake_word("I told zeroclaw about it", &words),
+            WakeMatch::None
+        );
+        assert_eq!(match_wake_word("", &words), WakeMatch::None);
+    }
+
+    #[test]
+    fn wav_header_describes_mono_pcm() {
+        let wav = encode_wav(&[0.0, 1.0, -1.0], 16_000);
+        assert_eq!(wav.len(), 44 + 6);
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16_000);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 6);
+        assert_eq!(i16::from_le_bytes([wav[46], wav[47]]), i16::MAX);
+        assert_eq!(i16::from_le_bytes([wav[48], wav[49]]), -i16::MAX);
+    }
+
+    #[test]
+    fn replies_are_spoken_without_markdown() {
+        assert_eq!(
+            speakable("**Done.**  The `lamp` is\n# off"),
+            "Done. The lamp is off"
+        );
+    }
+}
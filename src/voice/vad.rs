@@ -0,0 +1,147 @@
+//! Level-based voice activity detection.
+//!
+//! Audio is judged in 20 ms frames: a frame louder than `vad_threshold`
+//! (RMS) is speech. An utterance starts at the first speech frame (plus a
+//! short pre-roll so the first syllable is not clipped) and ends after
+//! `silence_ms` of quiet or at `max_utterance_secs`.
+
+use crate::config::VoiceConfig;
+use std::collections::VecDeque;
+
+const FRAME_MS: usize = 20;
+/// Audio kept from before speech starts.
+const PRE_ROLL_FRAMES: usize = 10;
+/// Utterances with less speech than this are clicks or bumps.
+const MIN_SPEECH_FRAMES: usize = 10;
+
+pub struct UtteranceDetector {
+    threshold: f32,
+    frame_len: usize,
+    silence_frames: usize,
+    max_frames: usize,
+    partial: Vec<f32>,
+    pre_roll: VecDeque<Vec<f32>>,
+    current: Vec<f32>,
+    frames: usize,
+    speech_frames: usize,
+    quiet_frames: usize,
+    ready: VecDeque<Vec<f32>>,
+}
+
+impl UtteranceDetector {
+    pub fn new(config: &VoiceConfig, sample_rate: u32) -> Self {
+        let frame_len = (sample_rate as usize * FRAME_MS / 1000).max(1);
+        #[allow(clippy::cast_possible_truncation)]
+        let silence_frames = (config.silence_ms as usize / FRAME_MS).max(1);
+        #[allow(clippy::cast_possible_truncation)]
+        let max_frames = config.max_utterance_secs as usize * 1000 / FRAME_MS;
+        Self {
+            threshold: config.vad_threshold,
+            frame_len,
+            silence_frames,
+            max_frames,
+            partial: Vec::new(),
+            pre_roll: VecDeque::new(),
+            current: Vec::new(),
+            frames: 0,
+            speech_frames: 0,
+            quiet_frames: 0,
+            ready: VecDeque::new(),
+        }
+    }
+
+    /// Drop any audio heard so far (e.g. while the reply was being spoken).
+    pub fn reset(&mut self) {
+        self.partial.clear();
+        self.pre_roll.clear();
+        self.current.clear();
+        self.frames = 0;
+        self.speech_frames = 0;
+        self.quiet_frames = 0;
+        self.ready.clear();
+    }
+
+    /// Feed mono samples; returns a finished utterance when one completes.
+    pub fn push(&mut self, samples: &[f32]) -> Option<Vec<f32>> {
+        self.partial.extend_from_slice(samples);
+        while self.partial.len() >= self.frame_len {
+            let frame: Vec<f32> = self.partial.drain(..self.frame_len).collect();
+            self.process_frame(frame);
+        }
+        self.ready.pop_front()
+    }
+
+    fn process_frame(&mut self, frame: Vec<f32>) {
+        let speech = rms(&frame) >= self.threshold;
+        if self.frames == 0 {
+            if !speech {
+                self.pre_roll.push_back(frame);
+                if self.pre_roll.len() > PRE_ROLL_FRAMES {
+                    self.pre_roll.pop_front();
+                }
+                return;
+            }
+            for earlier in self.pre_roll.drain(..) {
+                self.current.extend(earlier);
+            }
+        }
+
+        self.current.extend(frame);
+        self.frames += 1;
+        if speech {
+            self.speech_frames += 1;
+            self.quiet_frames = 0;
+        } else {
+            self.quiet_frames += 1;
+        }
+
+        if self.quiet_frames >= self.silence_frames || self.frames >= self.max_frames {
+            let utterance = std::mem::take(&mut self.current);
+            if self.speech_frames >= MIN_SPEECH_FRAMES {
+                self.ready.push_back(utterance);
+            }
+            self.frames = 0;
+            self.speech_frames = 0;
+            self.quiet_frames = 0;
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn rms(frame: &[f32]) -> f32 {
+    (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16_000;
+
+    fn tone(ms: usize, amplitude: f32) -> Vec<f32> {
+        (0..RATE as usize * ms / 1000)
+            .map(|i| if i % 2 == 0 { amplitude } else { -amplitude })
+            .collect()
+    }
+
+    #[test]
+    fn splits_speech_on_silence_and_ignores_clicks() {
+        let config = VoiceConfig::default();
+        let mut detector = UtteranceDetector::new(&config, RATE);
+
+        assert!(detector.push(&tone(500, 0.0)).is_none());
+        // A 40 ms click is too short to be speech.
+        assert!(detector.push(&tone(40, 0.5)).is_none());
+        assert!(detector.push(&tone(1000, 0.001)).is_none());
+
+        assert!(detector.push(&tone(600, 0.2)).is_none());
+        let utterance = detector.push(&tone(1000, 0.001)).expect("utterance ends");
+        // Pre-roll + speech + trailing silence.
+        let ms = utterance.len() * 1000 / RATE as usize;
+        assert!((600 + 800..=200 + 600 + 800).contains(&ms), "{ms} ms");
+
+        // Continuous speech is cut at max_utterance_secs.
+        let long = detector.push(&tone(16_000, 0.2)).expect("max length");
+        assert_eq!(long.len(), RATE as usize * 15 + RATE as usize * 200 / 1000);
+    }
+}
@@ -0,0 +1,77 @@
+//! Daemon flow tests built on `zeroclaw::test_support`.
+//!
+//! The provider is a scripted OpenAI-compatible endpoint on localhost and
+//! the channel is in memory, so these run the real channel runtime, agent
+//! loop and tools without network access or credentials.
+
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use zeroclaw::channels::traits::Channel;
+use zeroclaw::test_support::{FakeChannel, ScriptedProvider, ScriptedReply, TestWorkspace};
+
+const REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[tokio::test]
+async fn channel_message_runs_tool_and_replies() {
+    let provider = ScriptedProvider::new([
+        ScriptedReply::tool_call(
+            "file_write",
+            json!({"path": "notes/todo.txt", "content": "buy milk"}),
+        ),
+        ScriptedReply::text("Saved your note."),
+    ]);
+    let endpoint = provider.serve().await.unwrap();
+    let workspace = TestWorkspace::builder()
+        .provider(&endpoint)
+        .file("notes/.keep", "")
+        .build()
+        .unwrap();
+
+    let channel = Arc::new(FakeChannel::new("fake"));
+    let daemon = tokio::spawn(zeroclaw::channels::start_channels_with(
+        workspace.config.clone(),
+        vec![Arc::clone(&channel) as Arc<dyn Channel>],
+    ));
+
+    channel.inject("alice", "remember to buy milk");
+    let reply = channel
+        .next_reply(REPLY_TIMEOUT)
+        .await
+        .expect("agent replies");
+    daemon.abort();
+
+    assert_eq!(reply.recipient, "alice");
+    assert!(reply.content.contains("Saved your note."), "{reply:?}");
+    assert_eq!(workspace.read("notes/todo.txt").unwrap(), "buy milk");
+
+    let requests = provider.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].tools.iter().any(|tool| tool == "file_write"));
+    assert!(requests[0]
+        .last("user")
+        .is_some_and(|text| text.contains("remember to buy milk")));
+    assert!(requests[1].last("tool").is_some(), "tool result fed back");
+    assert_eq!(provider.remaining(), 0);
+}
+
+#[tokio::test]
+async fn process_message_uses_configured_scripted_endpoint() {
+    let provider = ScriptedProvider::new([ScriptedReply::text("pong")]);
+    let endpoint = provider.serve().await.unwrap();
+    let workspace = TestWorkspace::builder()
+        .provider(&endpoint)
+        .build()
+        .unwrap();
+
+    let reply = zeroclaw::agent::process_message(workspace.config.clone(), "ping")
+        .await
+        .unwrap();
+    assert_eq!(reply, "pong");
+
+    provider.push([ScriptedReply::error("model exploded")]);
+    let err = zeroclaw::agent::process_message(workspace.config.clone(), "ping")
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("model exploded"), "{err:#}");
+}